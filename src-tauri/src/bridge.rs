@@ -0,0 +1,398 @@
+//! `devjournal-bridge`: a headless stdio mode for editor plugins (VS Code, Neovim).
+//!
+//! Start the app binary with `--bridge` and exchange newline-delimited JSON-RPC 2.0
+//! messages over stdin/stdout. Calls are authorized with an API token passed via
+//! `--token <secret>` or `DEV_JOURNAL_TOKEN`, so they are scoped, rate limited and
+//! logged like every other external interface.
+//!
+//! Methods: `get_today_entry`, `append_today`, `list_open_tasks` and
+//! `attach_command_output` (used by the CLI to save terminal output on an entry).
+
+use crate::commands::api_access::authorize_external_call_in_conn;
+use crate::commands::{attachments, encryption, entries, tasks};
+use chrono::{Local, NaiveDate};
+use rusqlite::Connection;
+use serde_json::{json, Value};
+use std::io::{BufRead, Write};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// Must match `identifier` in tauri.conf.json so the bridge opens the app's database.
+const APP_IDENTIFIER: &str = "com.devjournal.desktop";
+const DEFAULT_TASK_LIMIT: i64 = 50;
+
+const PARSE_ERROR: i64 = -32700;
+const INVALID_REQUEST: i64 = -32600;
+const METHOD_NOT_FOUND: i64 = -32601;
+const INVALID_PARAMS: i64 = -32602;
+const SERVER_ERROR: i64 = -32000;
+const UNAUTHORIZED: i64 = -32001;
+
+struct RpcError {
+    code: i64,
+    message: String,
+}
+
+impl RpcError {
+    fn new(code: i64, message: impl Into<String>) -> Self {
+        Self {
+            code,
+            message: message.into(),
+        }
+    }
+}
+
+fn server_error(message: String) -> RpcError {
+    RpcError::new(SERVER_ERROR, message)
+}
+
+fn authorize(conn: &Connection, secret: &str, method: &str, scope: &str) -> Result<(), RpcError> {
+    authorize_external_call_in_conn(conn, secret, &format!("bridge/{}", method), Some(scope))
+        .map(|_| ())
+        .map_err(|error| RpcError::new(UNAUTHORIZED, error))
+}
+
+/// Entry text is encrypted at rest when journal encryption is on and the bridge has no
+/// way to unlock it, so entry methods are refused.
+fn ensure_plaintext_journal(conn: &Connection) -> Result<(), RpcError> {
+    match encryption::journal_encryption_enabled_in_conn(conn) {
+        Ok(false) => Ok(()),
+        Ok(true) => Err(server_error(
+            "The journal is encrypted; entries are only available in the app".to_string(),
+        )),
+        Err(error) => Err(server_error(error)),
+    }
+}
+
+fn call_method(
+    conn: &Connection,
+    attachments_root: &Path,
+    secret: &str,
+    method: &str,
+    params: &Value,
+    today: NaiveDate,
+) -> Result<Value, RpcError> {
+    let date = today.format("%Y-%m-%d").to_string();
+    match method {
+        "get_today_entry" => {
+            authorize(conn, secret, method, "entries:read")?;
+            ensure_plaintext_journal(conn)?;
+            let entry = entries::get_entry_in_conn(conn, &date).map_err(server_error)?;
+            serde_json::to_value(entry).map_err(|e| server_error(e.to_string()))
+        }
+        "append_today" => {
+            let text = params
+                .get("text")
+                .and_then(Value::as_str)
+                .ok_or_else(|| RpcError::new(INVALID_PARAMS, "`text` is required"))?;
+            authorize(conn, secret, method, "entries:write")?;
+            ensure_plaintext_journal(conn)?;
+            let entry =
+                entries::append_to_entry_in_conn(conn, &date, text).map_err(server_error)?;
+            serde_json::to_value(entry).map_err(|e| server_error(e.to_string()))
+        }
+        "list_open_tasks" => {
+            let limit = params
+                .get("limit")
+                .and_then(Value::as_i64)
+                .unwrap_or(DEFAULT_TASK_LIMIT)
+                .clamp(1, 500);
+            authorize(conn, secret, method, "tasks:read")?;
+            let tasks = tasks::get_open_tasks_in_conn(conn, limit).map_err(server_error)?;
+            serde_json::to_value(tasks).map_err(|e| server_error(e.to_string()))
+        }
+        "attach_command_output" => {
+            let command = params
+                .get("command")
+                .and_then(Value::as_str)
+                .ok_or_else(|| RpcError::new(INVALID_PARAMS, "`command` is required"))?;
+            let output = params
+                .get("output")
+                .and_then(Value::as_str)
+                .ok_or_else(|| RpcError::new(INVALID_PARAMS, "`output` is required"))?;
+            let entry_date = params.get("date").and_then(Value::as_str).unwrap_or(&date);
+            authorize(conn, secret, method, "entries:write")?;
+            ensure_plaintext_journal(conn)?;
+            let attachment = attachments::attach_command_output_in_conn(
+                conn,
+                attachments_root,
+                entry_date,
+                command,
+                output,
+            )
+            .map_err(server_error)?;
+            serde_json::to_value(attachment).map_err(|e| server_error(e.to_string()))
+        }
+        _ => Err(RpcError::new(
+            METHOD_NOT_FOUND,
+            format!("Unknown method: {}", method),
+        )),
+    }
+}
+
+/// Handles one JSON-RPC message. Returns `None` for notifications (no `id`),
+/// which by the spec get no response.
+pub(crate) fn handle_message(
+    conn: &Connection,
+    attachments_root: &Path,
+    secret: &str,
+    line: &str,
+    today: NaiveDate,
+) -> Option<String> {
+    let request: Value = match serde_json::from_str(line) {
+        Ok(request) => request,
+        Err(error) => {
+            return Some(error_response(
+                Value::Null,
+                RpcError::new(PARSE_ERROR, error.to_string()),
+            ))
+        }
+    };
+    let id = request.get("id").cloned();
+    let Some(method) = request.get("method").and_then(Value::as_str) else {
+        return Some(error_response(
+            id.unwrap_or(Value::Null),
+            RpcError::new(INVALID_REQUEST, "`method` is required"),
+        ));
+    };
+    let params = request.get("params").cloned().unwrap_or(Value::Null);
+
+    let result = call_method(conn, attachments_root, secret, method, &params, today);
+    let id = id?;
+    Some(match result {
+        Ok(result) => json!({ "jsonrpc": "2.0", "id": id, "result": result }).to_string(),
+        Err(error) => error_response(id, error),
+    })
+}
+
+fn error_response(id: Value, error: RpcError) -> String {
+    json!({
+        "jsonrpc": "2.0",
+        "id": id,
+        "error": { "code": error.code, "message": error.message },
+    })
+    .to_string()
+}
+
+fn arg_value(args: &[String], flag: &str) -> Option<String> {
+    args.iter()
+        .position(|arg| arg == flag)
+        .and_then(|index| args.get(index + 1))
+        .cloned()
+}
+
+/// Runs the bridge until stdin closes and returns the process exit code.
+pub fn run(args: &[String]) -> i32 {
+    let Some(secret) =
+        arg_value(args, "--token").or_else(|| std::env::var("DEV_JOURNAL_TOKEN").ok())
+    else {
+        eprintln!("devjournal-bridge: pass an API token with --token or DEV_JOURNAL_TOKEN");
+        return 2;
+    };
+    let Some(data_dir) = arg_value(args, "--data-dir")
+        .map(PathBuf::from)
+        .or_else(|| dirs::data_dir().map(|dir| dir.join(APP_IDENTIFIER)))
+    else {
+        eprintln!("devjournal-bridge: cannot resolve the app data directory, pass --data-dir");
+        return 2;
+    };
+
+    let attachments_root = data_dir.join("attachments");
+    let conn = match crate::db::init(data_dir) {
+        Ok(conn) => conn,
+        Err(error) => {
+            eprintln!("devjournal-bridge: failed to open database: {error}");
+            return 1;
+        }
+    };
+    // The desktop app may hold the write lock briefly while both are open.
+    if let Err(error) = conn.busy_timeout(Duration::from_secs(5)) {
+        eprintln!("devjournal-bridge: {error}");
+        return 1;
+    }
+
+    let stdin = std::io::stdin();
+    let mut stdout = std::io::stdout();
+    for line in stdin.lock().lines() {
+        let line = match line {
+            Ok(line) => line,
+            Err(error) => {
+                eprintln!("devjournal-bridge: {error}");
+                return 1;
+            }
+        };
+        if line.trim().is_empty() {
+            continue;
+        }
+        let today = Local::now().date_naive();
+        if let Some(response) = handle_message(&conn, &attachments_root, &secret, &line, today) {
+            if writeln!(stdout, "{}", response)
+                .and_then(|_| stdout.flush())
+                .is_err()
+            {
+                return 1;
+            }
+        }
+    }
+
+    0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::api_access::create_api_token_in_conn;
+    use chrono::Utc;
+
+    fn bridge_test_connection() -> Connection {
+        let temp_dir = std::env::temp_dir().join(format!(
+            "dev-journal-bridge-test-{}",
+            Utc::now().timestamp_nanos_opt().unwrap_or_default()
+        ));
+        let conn = crate::db::init(temp_dir.clone()).expect("db init");
+        std::fs::remove_dir_all(temp_dir).ok();
+        conn
+    }
+
+    fn bridge_attachments_root() -> PathBuf {
+        std::env::temp_dir().join(format!(
+            "dev-journal-bridge-attachments-{}",
+            Utc::now().timestamp_nanos_opt().unwrap_or_default()
+        ))
+    }
+
+    fn call(conn: &Connection, secret: &str, request: Value) -> Value {
+        let today = NaiveDate::from_ymd_opt(2026, 5, 20).expect("date");
+        let root = bridge_attachments_root();
+        let response =
+            handle_message(conn, &root, secret, &request.to_string(), today).expect("response");
+        std::fs::remove_dir_all(root).ok();
+        serde_json::from_str(&response).expect("json")
+    }
+
+    #[test]
+    fn bridge_appends_to_today_and_lists_open_tasks() {
+        let conn = bridge_test_connection();
+        let scopes: Vec<String> = ["entries:read", "entries:write", "tasks:read"]
+            .iter()
+            .map(|scope| scope.to_string())
+            .collect();
+        let token = create_api_token_in_conn(&conn, "Neovim", &scopes).expect("token");
+        conn.execute(
+            "INSERT INTO tasks (title, description, status, priority, created_at, updated_at)
+             VALUES ('Open', '', 'todo', 'high', '2026-05-01', '2026-05-01'),
+                    ('Shipped', '', 'done', 'low', '2026-05-01', '2026-05-01')",
+            [],
+        )
+        .expect("tasks");
+
+        let empty = call(
+            &conn,
+            &token.secret,
+            json!({"jsonrpc": "2.0", "id": 1, "method": "get_today_entry"}),
+        );
+        assert_eq!(empty["result"], Value::Null);
+
+        for text in ["Reviewed PR #12", "  Paired on the parser  "] {
+            call(
+                &conn,
+                &token.secret,
+                json!({"jsonrpc": "2.0", "id": 2, "method": "append_today", "params": {"text": text}}),
+            );
+        }
+        let entry = call(
+            &conn,
+            &token.secret,
+            json!({"jsonrpc": "2.0", "id": 3, "method": "get_today_entry"}),
+        );
+        assert_eq!(entry["result"]["date"], "2026-05-20");
+        assert_eq!(
+            entry["result"]["today"],
+            "Reviewed PR #12\nPaired on the parser"
+        );
+
+        let tasks = call(
+            &conn,
+            &token.secret,
+            json!({"jsonrpc": "2.0", "id": "t", "method": "list_open_tasks"}),
+        );
+        assert_eq!(tasks["id"], "t");
+        let titles: Vec<&str> = tasks["result"]
+            .as_array()
+            .expect("tasks")
+            .iter()
+            .filter_map(|task| task["title"].as_str())
+            .collect();
+        assert_eq!(titles, vec!["Open"]);
+    }
+
+    #[test]
+    fn bridge_reports_json_rpc_errors() {
+        let conn = bridge_test_connection();
+        let token =
+            create_api_token_in_conn(&conn, "Reader", &["tasks:read".to_string()]).expect("token");
+        let today = NaiveDate::from_ymd_opt(2026, 5, 20).expect("date");
+
+        let unauthorized = call(
+            &conn,
+            &token.secret,
+            json!({"jsonrpc": "2.0", "id": 1, "method": "append_today", "params": {"text": "x"}}),
+        );
+        assert_eq!(unauthorized["error"]["code"], UNAUTHORIZED);
+        let missing = call(
+            &conn,
+            &token.secret,
+            json!({"jsonrpc": "2.0", "id": 2, "method": "append_today"}),
+        );
+        assert_eq!(missing["error"]["code"], INVALID_PARAMS);
+        let unknown = call(
+            &conn,
+            &token.secret,
+            json!({"jsonrpc": "2.0", "id": 3, "method": "delete_everything"}),
+        );
+        assert_eq!(unknown["error"]["code"], METHOD_NOT_FOUND);
+        let root = bridge_attachments_root();
+        let parse =
+            handle_message(&conn, &root, &token.secret, "{not json", today).expect("response");
+        assert!(parse.contains(&PARSE_ERROR.to_string()));
+        assert!(handle_message(
+            &conn,
+            &root,
+            &token.secret,
+            r#"{"jsonrpc":"2.0","method":"list_open_tasks"}"#,
+            today
+        )
+        .is_none());
+    }
+
+    #[test]
+    fn bridge_attaches_command_output_to_todays_entry() {
+        let conn = bridge_test_connection();
+        let scopes = vec!["entries:write".to_string()];
+        let token = create_api_token_in_conn(&conn, "CLI", &scopes).expect("token");
+
+        let response = call(
+            &conn,
+            &token.secret,
+            json!({
+                "jsonrpc": "2.0",
+                "id": 1,
+                "method": "attach_command_output",
+                "params": {"command": "cargo test", "output": "\u{1b}[32mtest result: ok\u{1b}[0m\n"}
+            }),
+        );
+        assert_eq!(response["result"]["kind"], "command_output");
+        assert_eq!(response["result"]["command"], "cargo test");
+        let entry = entries::get_entry_in_conn(&conn, "2026-05-20")
+            .expect("entry")
+            .expect("created");
+        assert_eq!(response["result"]["entry_id"], entry.id);
+
+        let missing = call(
+            &conn,
+            &token.secret,
+            json!({"jsonrpc": "2.0", "id": 2, "method": "attach_command_output", "params": {"command": "ls"}}),
+        );
+        assert_eq!(missing["error"]["code"], INVALID_PARAMS);
+    }
+}
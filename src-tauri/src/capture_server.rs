@@ -0,0 +1,416 @@
+use crate::commands::events::emit_event;
+use crate::commands::{self, AppState};
+use std::collections::HashMap;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::{Ipv4Addr, TcpListener, TcpStream};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+use tauri::{AppHandle, Manager};
+
+/// Clips are small; anything bigger is rejected before it is read.
+const MAX_BODY_BYTES: usize = 64 * 1024;
+const MAX_HEADER_LINES: usize = 100;
+/// Longest request or header line; longer lines are rejected without buffering them.
+const MAX_LINE_BYTES: usize = 8 * 1024;
+const READ_TIMEOUT: Duration = Duration::from_secs(5);
+/// Connections served at once; further ones get a 503 instead of a thread.
+const MAX_CONCURRENT_CONNECTIONS: usize = 16;
+const DEFAULT_TASK_LIMIT: i64 = 50;
+
+#[derive(Debug)]
+pub(crate) struct HttpRequest {
+    pub method: String,
+    pub path: String,
+    /// Header names are lowercased.
+    pub headers: HashMap<String, String>,
+    pub body: Vec<u8>,
+}
+
+impl HttpRequest {
+    /// Accepts `Authorization: Bearer <token>` or the `X-Dev-Journal-Token` header.
+    pub(crate) fn token(&self) -> Option<&str> {
+        self.headers
+            .get("authorization")
+            .and_then(|value| value.strip_prefix("Bearer "))
+            .or_else(|| self.headers.get("x-dev-journal-token").map(String::as_str))
+            .map(str::trim)
+            .filter(|token| !token.is_empty())
+    }
+}
+
+fn read_limited_line<R: BufRead>(reader: &mut R) -> Result<String, String> {
+    let mut line = String::new();
+    reader
+        .by_ref()
+        .take(MAX_LINE_BYTES as u64 + 1)
+        .read_line(&mut line)
+        .map_err(|e| e.to_string())?;
+    if line.len() > MAX_LINE_BYTES {
+        return Err("Request line is too long".to_string());
+    }
+    Ok(line)
+}
+
+/// Reads one HTTP/1.1 request; only what the capture endpoint needs is supported.
+pub(crate) fn read_http_request<R: BufRead>(reader: &mut R) -> Result<HttpRequest, String> {
+    let request_line = read_limited_line(reader)?;
+    let mut parts = request_line.split_whitespace();
+    let (method, path) = match (parts.next(), parts.next(), parts.next()) {
+        (Some(method), Some(path), Some(version)) if version.starts_with("HTTP/1.") => {
+            (method.to_ascii_uppercase(), path.to_string())
+        }
+        _ => return Err("Malformed request line".to_string()),
+    };
+
+    let mut headers = HashMap::new();
+    loop {
+        let line = read_limited_line(reader)?;
+        let line = line.trim_end_matches(['\r', '\n']);
+        if line.is_empty() {
+            break;
+        }
+        if headers.len() >= MAX_HEADER_LINES {
+            return Err("Too many headers".to_string());
+        }
+        let (name, value) = line
+            .split_once(':')
+            .ok_or_else(|| format!("Malformed header: {}", line))?;
+        headers.insert(name.trim().to_ascii_lowercase(), value.trim().to_string());
+    }
+
+    let length = match headers.get("content-length") {
+        Some(value) => value
+            .parse::<usize>()
+            .map_err(|_| "Invalid Content-Length".to_string())?,
+        None => 0,
+    };
+    if length > MAX_BODY_BYTES {
+        return Err("Request body is too large".to_string());
+    }
+    let mut body = vec![0; length];
+    reader.read_exact(&mut body).map_err(|e| e.to_string())?;
+
+    Ok(HttpRequest {
+        method,
+        path,
+        headers,
+        body,
+    })
+}
+
+/// Maps command errors to status codes so the extension can tell a bad token
+/// from a full rate limit.
+pub(crate) fn status_for_error(error: &str) -> u16 {
+    if error.starts_with("Invalid API token") {
+        401
+    } else if error.contains("is missing the") {
+        403
+    } else if error.starts_with("Rate limit") {
+        429
+    } else {
+        400
+    }
+}
+
+fn reason_phrase(status: u16) -> &'static str {
+    match status {
+        200 => "OK",
+        201 => "Created",
+        204 => "No Content",
+        401 => "Unauthorized",
+        403 => "Forbidden",
+        404 => "Not Found",
+        413 => "Payload Too Large",
+        429 => "Too Many Requests",
+        500 => "Internal Server Error",
+        503 => "Service Unavailable",
+        _ => "Bad Request",
+    }
+}
+
+fn write_response(stream: &mut TcpStream, status: u16, body: &str) -> std::io::Result<()> {
+    // Extensions call from their own origin; the token, not CORS, guards the endpoint.
+    let response = format!(
+        "HTTP/1.1 {} {}\r\n\
+         Content-Type: application/json\r\n\
+         Content-Length: {}\r\n\
+         Access-Control-Allow-Origin: *\r\n\
+         Access-Control-Allow-Methods: GET, POST, OPTIONS\r\n\
+         Access-Control-Allow-Headers: Authorization, Content-Type, X-Dev-Journal-Token\r\n\
+         Connection: close\r\n\r\n{}",
+        status,
+        reason_phrase(status),
+        body.len(),
+        body
+    );
+    stream.write_all(response.as_bytes())
+}
+
+fn error_body(error: &str) -> String {
+    serde_json::json!({ "error": error }).to_string()
+}
+
+fn handle_capture(app: &AppHandle, request: &HttpRequest) -> (u16, String) {
+    let Some(secret) = request.token() else {
+        return (401, error_body("Missing API token"));
+    };
+    let payload = match serde_json::from_slice::<commands::capture::CapturePayload>(&request.body) {
+        Ok(payload) => payload,
+        Err(error) => return (400, error_body(&format!("Invalid capture body: {}", error))),
+    };
+
+    let result = {
+        let state = app.state::<AppState>();
+        let conn = match state.db.lock() {
+            Ok(conn) => conn,
+            Err(error) => return (500, error_body(&error.to_string())),
+        };
+        commands::capture::capture_in_conn(&conn, secret, payload)
+    };
+
+    match result {
+        Ok(created) => {
+            if let Err(error) = emit_event(app, "capture-received", &created) {
+                eprintln!("Failed to emit capture event: {error}");
+            }
+            (
+                201,
+                serde_json::to_string(&created).unwrap_or_else(|_| "{}".to_string()),
+            )
+        }
+        Err(error) => (status_for_error(&error), error_body(&error)),
+    }
+}
+
+/// `GET /api/v1/tasks?limit=N`: open tasks, behind the experimental `rest_api` flag.
+fn handle_list_tasks(app: &AppHandle, request: &HttpRequest) -> (u16, String) {
+    let Some(secret) = request.token() else {
+        return (401, error_body("Missing API token"));
+    };
+    let limit = request
+        .path
+        .split_once('?')
+        .and_then(|(_, query)| {
+            query
+                .split('&')
+                .find_map(|pair| pair.strip_prefix("limit="))
+                .and_then(|limit| limit.parse::<i64>().ok())
+        })
+        .unwrap_or(DEFAULT_TASK_LIMIT)
+        .clamp(1, 500);
+
+    let state = app.state::<AppState>();
+    let conn = match state.db.lock() {
+        Ok(conn) => conn,
+        Err(error) => return (500, error_body(&error.to_string())),
+    };
+    if let Err(error) = commands::feature_flags::require_feature_in_conn(&conn, "rest_api") {
+        return (404, error_body(&error));
+    }
+    let result = commands::api_access::authorize_external_call_in_conn(
+        &conn,
+        secret,
+        "GET /api/v1/tasks",
+        Some("tasks:read"),
+    )
+    .and_then(|_| commands::tasks::get_open_tasks_in_conn(&conn, limit));
+
+    match result {
+        Ok(tasks) => (
+            200,
+            serde_json::to_string(&tasks).unwrap_or_else(|_| "[]".to_string()),
+        ),
+        Err(error) => (status_for_error(&error), error_body(&error)),
+    }
+}
+
+fn handle_connection(app: &AppHandle, mut stream: TcpStream) -> std::io::Result<()> {
+    stream.set_read_timeout(Some(READ_TIMEOUT))?;
+    let request = {
+        let mut reader = BufReader::new(&mut stream);
+        read_http_request(&mut reader)
+    };
+    let request = match request {
+        Ok(request) => request,
+        Err(error) => {
+            let status = if error.contains("too large") {
+                413
+            } else {
+                400
+            };
+            return write_response(&mut stream, status, &error_body(&error));
+        }
+    };
+
+    let path = request.path.split('?').next().unwrap_or("");
+    let (status, body) = match (request.method.as_str(), path) {
+        ("OPTIONS", _) => (204, String::new()),
+        ("GET", "/health") => (200, r#"{"status":"ok"}"#.to_string()),
+        ("POST", "/capture") => handle_capture(app, &request),
+        ("GET", "/api/v1/tasks") => handle_list_tasks(app, &request),
+        _ => (404, error_body("Not found")),
+    };
+    write_response(&mut stream, status, &body)
+}
+
+/// One in-flight connection, counted in the shared total until dropped.
+struct ConnectionSlot(Arc<AtomicUsize>);
+
+impl ConnectionSlot {
+    /// Takes a slot unless `MAX_CONCURRENT_CONNECTIONS` are already in use.
+    fn acquire(active: &Arc<AtomicUsize>) -> Option<Self> {
+        active
+            .fetch_update(Ordering::AcqRel, Ordering::Acquire, |count| {
+                (count < MAX_CONCURRENT_CONNECTIONS).then_some(count + 1)
+            })
+            .ok()
+            .map(|_| Self(Arc::clone(active)))
+    }
+}
+
+impl Drop for ConnectionSlot {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::AcqRel);
+    }
+}
+
+fn read_server_settings(app: &AppHandle) -> Result<(bool, i64), String> {
+    let state = app.state::<AppState>();
+    let conn = state.db.lock().map_err(|e| e.to_string())?;
+    Ok((
+        commands::settings::read_bool_setting(&conn, "capture_server_enabled")?,
+        commands::settings::read_i64_setting(&conn, "capture_server_port")?,
+    ))
+}
+
+/// Starts the localhost capture endpoint for the browser extension companion when
+/// it is enabled in settings. Only binds to 127.0.0.1; changes apply after a restart.
+pub fn spawn(app: AppHandle) {
+    let (enabled, port) = match read_server_settings(&app) {
+        Ok(settings) => settings,
+        Err(error) => {
+            eprintln!("Capture server failed to read settings: {error}");
+            return;
+        }
+    };
+    if !enabled {
+        return;
+    }
+    let Ok(port) = u16::try_from(port) else {
+        eprintln!("Capture server port {port} is out of range");
+        return;
+    };
+
+    let listener = match TcpListener::bind((Ipv4Addr::LOCALHOST, port)) {
+        Ok(listener) => listener,
+        Err(error) => {
+            eprintln!("Capture server failed to bind port {port}: {error}");
+            return;
+        }
+    };
+
+    // Each connection gets its own thread so a slow client cannot hold up the others,
+    // up to `MAX_CONCURRENT_CONNECTIONS` at a time.
+    thread::spawn(move || {
+        let active = Arc::new(AtomicUsize::new(0));
+        for stream in listener.incoming() {
+            match stream {
+                Ok(mut stream) => {
+                    let Some(slot) = ConnectionSlot::acquire(&active) else {
+                        let _ = stream.set_write_timeout(Some(READ_TIMEOUT));
+                        let _ = write_response(&mut stream, 503, &error_body("Server is busy"));
+                        continue;
+                    };
+                    let app = app.clone();
+                    thread::spawn(move || {
+                        let _slot = slot;
+                        if let Err(error) = handle_connection(&app, stream) {
+                            eprintln!("Capture request failed: {error}");
+                        }
+                    });
+                }
+                Err(error) => eprintln!("Capture connection failed: {error}"),
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    #[test]
+    fn read_http_request_parses_headers_and_body() {
+        let raw = "POST /capture HTTP/1.1\r\nHost: 127.0.0.1\r\nAuthorization: Bearer dj_abc\r\nContent-Length: 13\r\n\r\n{\"url\":\"x\"}  ";
+        let request = read_http_request(&mut Cursor::new(raw.as_bytes())).expect("capture request");
+
+        assert_eq!(request.method, "POST");
+        assert_eq!(request.path, "/capture");
+        assert_eq!(
+            request.headers.get("host").map(String::as_str),
+            Some("127.0.0.1")
+        );
+        assert_eq!(request.token(), Some("dj_abc"));
+        assert_eq!(request.body, b"{\"url\":\"x\"}  ");
+
+        let raw = "GET /health HTTP/1.1\r\nX-Dev-Journal-Token: dj_def\r\n\r\n";
+        let request = read_http_request(&mut Cursor::new(raw.as_bytes())).expect("health request");
+        assert_eq!(request.token(), Some("dj_def"));
+        assert!(request.body.is_empty());
+
+        let raw = format!(
+            "POST /capture HTTP/1.1\r\nContent-Length: {}\r\n\r\n",
+            MAX_BODY_BYTES + 1
+        );
+        assert!(read_http_request(&mut Cursor::new(raw.as_bytes())).is_err());
+        assert!(read_http_request(&mut Cursor::new(&b"garbage\r\n\r\n"[..])).is_err());
+    }
+
+    #[test]
+    fn read_http_request_rejects_overlong_lines() {
+        let long_path = format!("GET /{} HTTP/1.1\r\n\r\n", "a".repeat(MAX_LINE_BYTES));
+        assert!(read_http_request(&mut Cursor::new(long_path.as_bytes())).is_err());
+
+        let long_header = format!(
+            "GET /health HTTP/1.1\r\nX-Padding: {}\r\n\r\n",
+            "a".repeat(MAX_LINE_BYTES)
+        );
+        assert!(read_http_request(&mut Cursor::new(long_header.as_bytes())).is_err());
+
+        let fits = format!(
+            "GET /health HTTP/1.1\r\nX-Padding: {}\r\n\r\n",
+            "a".repeat(100)
+        );
+        assert!(read_http_request(&mut Cursor::new(fits.as_bytes())).is_ok());
+    }
+
+    #[test]
+    fn status_for_error_distinguishes_auth_failures() {
+        assert_eq!(status_for_error("Invalid API token"), 401);
+        assert_eq!(
+            status_for_error("Token \"x\" is missing the tasks:write scope"),
+            403
+        );
+        assert_eq!(
+            status_for_error("Rate limit of 60 calls per minute exceeded"),
+            429
+        );
+        assert_eq!(status_for_error("A http(s) URL is required"), 400);
+    }
+
+    #[test]
+    fn connection_slots_are_capped_and_released_on_drop() {
+        let active = Arc::new(AtomicUsize::new(0));
+        let slots: Vec<ConnectionSlot> = (0..MAX_CONCURRENT_CONNECTIONS)
+            .map(|_| ConnectionSlot::acquire(&active).expect("slot"))
+            .collect();
+        assert!(ConnectionSlot::acquire(&active).is_none());
+
+        drop(slots);
+        assert_eq!(active.load(Ordering::Acquire), 0);
+        assert!(ConnectionSlot::acquire(&active).is_some());
+    }
+}
@@ -1,28 +1,282 @@
+pub mod accessibility;
+pub mod achievements;
+pub mod api_access;
+pub mod appearance;
+pub mod attachments;
+pub mod automation;
 pub mod backup;
+pub mod capture;
+pub mod countdowns;
+pub mod daily_sheet;
+pub mod date_shift;
+pub mod due_reminders;
+pub mod encryption;
+pub mod entries;
+pub mod events;
+pub mod export;
+pub mod feature_flags;
+pub mod focus;
+pub mod goals;
+pub mod habits;
+pub mod incidents;
+pub mod insights;
+pub mod integrations;
+pub mod integrity;
+pub mod jobs;
+pub mod markdown;
 pub mod meetings;
+pub mod mirror;
+pub mod mirror_git;
+pub mod notifications;
+pub mod obsidian;
+pub mod oncall;
+pub mod org;
+pub mod page_assets;
+pub mod page_export;
+pub mod page_locks;
+pub mod page_properties;
+pub mod page_stats;
+pub mod pages;
+pub mod performance;
+pub mod presence;
+pub mod prompts;
+pub mod query_plans;
+pub mod review;
+pub mod review_queue;
+pub mod settings;
+pub mod shutdown;
+pub mod slack_import;
+pub mod slack_status;
+pub mod snapshots;
 pub mod tasks;
+pub mod todo_txt;
+pub mod updates;
 mod validation;
+pub mod weeks;
+pub mod whats_new;
+pub mod windows;
 
 use crate::models::{
-    Entry, Goal, GoalMilestone, Habit, HabitWithLogs, MeetingActionItem, Page, Project,
-    ProjectBranch,
+    AppSetting, DayOneImportSummary, Entry, EntrySearchResult, Goal, GoalMilestone, Habit,
+    HabitWithLogs, MeetingActionItem, Project, ProjectBranch,
 };
-use chrono::{Datelike, Duration, NaiveDate, Utc};
+use chrono::{Duration, NaiveDate, Utc};
 use rusqlite::Connection;
 use rusqlite::{params, OptionalExtension};
 use serde::Deserialize;
-use std::collections::HashSet;
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::sync::Mutex;
-use tauri::State;
+use tauri::{AppHandle, Manager, State};
 
+use encryption::JournalContent;
+use events::emit_event;
+
+#[cfg(test)]
+pub(crate) use accessibility::get_accessible_summary_in_conn;
+#[cfg(test)]
+pub(crate) use achievements::{evaluate_achievements_in_conn, get_achievements_in_conn};
+#[cfg(test)]
+pub(crate) use api_access::{
+    authorize_api_token_in_conn, authorize_external_call_in_conn, create_api_token_in_conn,
+    get_external_access_log_in_conn, list_api_tokens_in_conn, normalize_scopes, preset_scopes,
+};
+#[cfg(test)]
+pub(crate) use appearance::{get_appearance_settings_in_conn, update_appearance_settings_in_conn};
+#[cfg(test)]
+pub(crate) use attachments::{
+    attach_command_output_in_conn, attach_file_to_entry_in_conn, delete_attachment_in_conn,
+    entry_attachment_paths, get_attachment_text_in_conn, list_entry_attachments_in_conn,
+    remove_attachment_files, sanitize_file_name, strip_ansi_codes,
+};
+#[cfg(test)]
+pub(crate) use automation::{
+    create_automation_rule_in_conn, evaluate_automation_rules_in_conn, get_automation_log_in_conn,
+    get_task_tags_in_conn, update_automation_rule_in_conn, AutomationRuleInput,
+};
+#[cfg(test)]
+pub(crate) use backup::{import_backup_into_conn, upgrade_backup_payload, BACKUP_FORMAT_VERSION};
+#[cfg(test)]
+pub(crate) use capture::{capture_in_conn, get_bookmarks_in_conn, CapturePayload};
+#[cfg(test)]
+pub(crate) use countdowns::{
+    complete_due_countdowns_in_conn, get_active_countdowns_in_conn, start_countdown_in_conn,
+};
+#[cfg(test)]
+pub(crate) use daily_sheet::{daily_sheet_html_in_conn, daily_sheet_path};
+#[cfg(test)]
+pub(crate) use date_shift::{shift_dates_in_conn, DateShiftEntity, DateShiftFilter};
+#[cfg(test)]
+pub(crate) use due_reminders::{
+    format_time_left, parse_due_reminder_leads, snooze_task_due_reminder_in_conn,
+    take_due_task_reminders_in_conn,
+};
+#[cfg(test)]
+pub(crate) use encryption::{
+    disable_journal_encryption_in_conn, enable_journal_encryption_in_conn, encrypt_journal_in_conn,
+    unlock_journal_in_conn, JournalCipher,
+};
+#[cfg(test)]
+pub(crate) use entries::{
+    add_entry_tag_in_conn, append_entry_note_in_conn, compute_entry_streaks,
+    discard_entry_draft_in_conn, fts_query_from_text, get_entries_between_in_conn,
+    get_entries_by_tag_in_conn, get_entries_in_conn, get_entries_on_this_day_in_conn,
+    get_entry_commits_in_conn, get_entry_draft_in_conn, get_entry_in_conn, get_entry_notes_in_conn,
+    get_entry_streaks_in_conn, get_pinned_entries_in_conn, get_random_entry_in_conn,
+    get_trashed_entries_in_conn, journal_reminder_due_in_conn, parse_oneline_commit,
+    previous_workday_in_conn, purge_trash_in_conn, restore_entry_in_conn,
+    save_entry_commits_in_conn, save_entry_draft_in_conn, save_entry_section_templates_in_conn,
+    save_entry_sections_in_conn, search_entries_in_conn, set_entry_pinned_in_conn,
+    trash_entry_in_conn, EntrySectionInput, EntrySectionTemplateInput,
+};
+#[cfg(test)]
+pub(crate) use export::{export_filtered_in_conn, ExportFilter, ExportFormat};
+#[cfg(test)]
+pub(crate) use feature_flags::{
+    get_flags_in_conn, is_feature_enabled_in_conn, require_feature_in_conn, set_flag_in_conn,
+};
+#[cfg(test)]
+pub(crate) use focus::{pomodoro_heatmap_in_conn, pomodoro_status_in_conn};
+#[cfg(test)]
+pub(crate) use goals::{
+    check_in_goal_in_conn, get_similar_goal_reflections_in_conn, goal_checkin_due_in_conn,
+    goal_title_similarity, goals_needing_checkin_in_conn, save_goal_reflection_in_conn,
+};
+#[cfg(test)]
+pub(crate) use habits::{streak_risk_reminders_due_in_conn, streaks_at_risk_in_conn};
+#[cfg(test)]
+pub(crate) use incidents::{
+    create_incident_in_conn, get_incident_timeline_in_conn, get_incidents_in_conn,
+    update_incident_in_conn, IncidentInput,
+};
+#[cfg(test)]
+pub(crate) use insights::{
+    context_switches_in_conn, meeting_load_in_conn, mood_correlations_in_conn, pearson_correlation,
+    writing_stats_in_conn,
+};
+#[cfg(test)]
+pub(crate) use integrations::{
+    apply_linked_item_refresh_in_conn, create_linked_task_in_conn, get_task_link_in_conn,
+    parse_issue_url, priority_from_labels, sends_credentials_to, IssueMetadata,
+};
+#[cfg(test)]
+pub(crate) use integrity::{get_integrity_report_in_conn, verify_integrity_in_conn};
+#[cfg(test)]
+pub(crate) use jobs::{
+    due_scheduled_jobs_in_conn, list_scheduled_jobs_in_conn, record_job_run_in_conn,
+    rollover_overdue_tasks_in_conn, set_job_schedule_in_conn, weekly_digest_in_conn,
+    write_database_snapshot_in_conn, CronSchedule,
+};
+#[cfg(test)]
+pub(crate) use markdown::{
+    export_entries_markdown_in_conn, import_markdown_folder_in_conn, parse_markdown_note,
+    EntryDateRange,
+};
+#[cfg(test)]
+pub(crate) use mirror::{mirror_entry_in_conn, mirror_page_in_conn, sync_vault_mirror_in_conn};
+#[cfg(test)]
+pub(crate) use mirror_git::{commit_mirror_changes, mirror_history};
+#[cfg(test)]
+pub(crate) use notifications::{
+    get_notification_templates_in_conn, in_quiet_hours_in_conn, render_notification_template,
+};
+#[cfg(test)]
+pub(crate) use obsidian::import_obsidian_vault_in_conn;
+#[cfg(test)]
+pub(crate) use oncall::{
+    create_oncall_shift_in_conn, import_oncall_ics_in_conn, oncall_days_in_conn,
+};
+#[cfg(test)]
+pub(crate) use org::{import_org_in_conn, org_export_in_conn, parse_org};
+#[cfg(test)]
+pub(crate) use page_assets::{collect_orphan_page_assets_in_conn, save_page_asset_in_conn};
+#[cfg(test)]
+pub(crate) use page_export::{
+    export_all_pages_in_conn, export_page_in_conn, markdown_to_html, PageExportFormat,
+};
+#[cfg(test)]
+pub(crate) use page_locks::{
+    disable_page_lock_in_conn, enable_page_lock_in_conn, reveal_page, unlock_page_in_conn, PageKey,
+};
+#[cfg(test)]
+pub(crate) use page_properties::{
+    extract_page_properties, get_page_properties_in_conn, get_page_property_values_in_conn,
+    get_pages_by_property_in_conn,
+};
+#[cfg(test)]
+pub(crate) use page_stats::{get_page_stats_in_conn, get_pages_stats_in_conn, markdown_word_count};
+#[cfg(test)]
+pub(crate) use pages::{
+    add_page_tag_in_conn, create_page_in_conn, delete_page_in_conn, extract_wiki_links,
+    get_backlinks_in_conn, get_page_children_in_conn, get_page_revision_in_conn,
+    get_page_revisions_in_conn, get_page_tags_in_conn, get_pages_by_tag_in_conn, get_pages_in_conn,
+    move_page_in_conn, remove_page_tag_in_conn, restore_page_revision_in_conn,
+    set_page_links_in_conn, snapshot_page_in_conn,
+};
+#[cfg(test)]
+pub(crate) use pages::{
+    empty_page_trash_in_conn, get_listed_pages_in_conn, get_page_in_conn, get_recent_pages_in_conn,
+    get_trashed_pages_in_conn, record_page_view_in_conn, reorder_pages_in_conn,
+    restore_page_in_conn, search_pages_in_conn, set_page_appearance_in_conn,
+    set_page_archived_in_conn, set_page_favorite_in_conn, set_page_pinned_in_conn,
+    trash_page_in_conn,
+};
+#[cfg(test)]
+pub(crate) use performance::{
+    get_slow_ops_in_conn, record_slow_op_in_conn, summarize_params, PerformanceState,
+};
+#[cfg(test)]
+pub(crate) use presence::presence_in_conn;
+#[cfg(test)]
+pub(crate) use prompts::{add_journal_prompt_in_conn, daily_prompt_in_conn};
 #[cfg(test)]
-pub(crate) use backup::import_backup_into_conn;
+pub(crate) use query_plans::check_query_plans_in_conn;
 #[cfg(test)]
-pub(crate) use tasks::{compute_next_due_date, materialize_recurring_successor};
+pub(crate) use review::{generate_week_plan_in_conn, generate_weekly_review_in_conn};
+#[cfg(test)]
+pub(crate) use review_queue::{
+    accept_review_item_in_conn, dismiss_review_item_in_conn, get_review_queue_in_conn,
+};
+#[cfg(test)]
+pub(crate) use shutdown::shutdown_checklist_in_conn;
+#[cfg(test)]
+pub(crate) use slack_import::{
+    import_slack_standups_in_conn, parse_standup, ParsedStandup, SlackMessage, StandupHeaders,
+};
+#[cfg(test)]
+pub(crate) use slack_status::{
+    is_slack_status_opted_out_in_conn, plan_slack_status_update, set_slack_status_opt_out_in_conn,
+    SlackStatusUpdate,
+};
+#[cfg(test)]
+pub(crate) use snapshots::{
+    create_workspace_snapshot_in_conn, delete_workspace_snapshot_in_dir,
+    list_workspace_snapshots_in_dir, restore_workspace_snapshot_in_conn,
+};
+#[cfg(test)]
+pub(crate) use tasks::{
+    compute_next_due_date, create_task_subtask_in_conn, find_duplicate_tasks_in_conn,
+    get_recent_timer_tasks_in_conn, get_tasks_in_conn, long_running_timer_tasks_in_conn,
+    materialize_recurring_successor, pause_all_timers_in_conn, reorder_task_subtasks_in_conn,
+    reorder_tasks_in_conn, set_task_status_in_conn, set_task_tags_in_conn,
+    toggle_active_timer_in_conn, toggle_task_timer_in_conn, TimerToggleOutcome,
+};
+#[cfg(test)]
+pub(crate) use todo_txt::{import_todo_txt_in_conn, parse_todo_txt_line, todo_txt_export_in_conn};
+#[cfg(test)]
+pub(crate) use updates::{newest_release, update_check_from_releases, PublishedRelease};
 pub(crate) use validation::*;
+#[cfg(test)]
+pub(crate) use weeks::{get_week_info_in_conn, WeekNumbering};
+#[cfg(test)]
+pub(crate) use whats_new::{get_whats_new_in_conn, parse_changelog};
+#[cfg(test)]
+pub(crate) use windows::DetachedView;
 
 pub struct AppState {
     pub db: Mutex<Connection>,
+    pub journal_cipher: Mutex<encryption::JournalCipher>,
+    pub page_keys: Mutex<page_locks::PageKeys>,
+    pub presence: Mutex<crate::models::Presence>,
 }
 
 /// JSON payload accepted by the import command.
@@ -51,6 +305,8 @@ pub struct BackupPayload {
     pub habit_logs: Vec<BackupHabitLogInput>,
     #[serde(default)]
     pub meetings: Vec<BackupMeetingInput>,
+    #[serde(default)]
+    pub app_settings: Vec<AppSetting>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -58,8 +314,14 @@ pub struct BackupEntryInput {
     pub date: String,
     pub yesterday: String,
     pub today: String,
+    #[serde(default)]
+    pub blockers: String,
+    #[serde(default)]
+    pub notes: String,
     pub project_id: Option<i64>,
     pub created_at: Option<String>,
+    #[serde(default)]
+    pub pinned: bool,
 }
 
 #[derive(Debug, Deserialize)]
@@ -67,8 +329,22 @@ pub struct BackupPageInput {
     pub id: Option<i64>,
     pub title: String,
     pub content: String,
+    #[serde(default)]
+    pub parent_id: Option<i64>,
     pub created_at: Option<String>,
     pub updated_at: Option<String>,
+    #[serde(default)]
+    pub pinned: bool,
+    #[serde(default)]
+    pub favorite: bool,
+    #[serde(default)]
+    pub archived: bool,
+    #[serde(default)]
+    pub sort_index: Option<i64>,
+    #[serde(default)]
+    pub icon: Option<String>,
+    #[serde(default)]
+    pub cover: Option<String>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -231,39 +507,192 @@ fn sync_goal_progress_from_milestones(conn: &Connection, goal_id: i64) -> Result
     Ok(())
 }
 
-fn compute_current_streak(completed_dates: &[String]) -> i64 {
-    let parsed_dates: HashSet<NaiveDate> = completed_dates
+/// Logged and frozen days of one habit as stored, newest first.
+pub(crate) struct HabitHistory {
+    pub habit_id: i64,
+    pub completed_dates: Vec<String>,
+    pub frozen_dates: Vec<String>,
+}
+
+/// One habit's streak after replaying every habit's history.
+#[derive(Debug, Default, PartialEq)]
+pub(crate) struct HabitStreak {
+    pub current_streak: i64,
+    /// Missed days covered by a token that were not recorded yet.
+    pub new_freezes: Vec<NaiveDate>,
+}
+
+#[derive(Debug, Default, PartialEq)]
+pub(crate) struct HabitStreaks {
+    pub by_habit: HashMap<i64, HabitStreak>,
+    /// Unspent tokens in the pool shared by all habits.
+    pub freeze_tokens: i64,
+}
+
+/// Walks all habit histories day by day up to `today` with one shared pool of freeze
+/// tokens. Every `freeze_every` consecutive completions of any habit earn a token (0
+/// disables earning); a missed day inside any running streak spends one instead of
+/// breaking that streak. Each day's earnings count before its misses. Frozen days keep
+/// a streak alive but do not add to it, and already recorded freezes are always honoured.
+pub(crate) fn compute_habit_streaks(
+    habits: &[HabitHistory],
+    freeze_every: i64,
+    today: NaiveDate,
+) -> HabitStreaks {
+    let parse_dates = |dates: &[String]| -> HashSet<NaiveDate> {
+        dates
+            .iter()
+            .filter_map(|date| NaiveDate::parse_from_str(date, "%Y-%m-%d").ok())
+            .collect()
+    };
+    let parsed: Vec<(i64, HashSet<NaiveDate>, HashSet<NaiveDate>)> = habits
         .iter()
-        .filter_map(|date| NaiveDate::parse_from_str(date, "%Y-%m-%d").ok())
+        .map(|habit| {
+            (
+                habit.habit_id,
+                parse_dates(&habit.completed_dates),
+                parse_dates(&habit.frozen_dates),
+            )
+        })
         .collect();
 
-    if parsed_dates.is_empty() {
-        return 0;
+    let mut streaks = HabitStreaks::default();
+    // Current run and completions toward the next token, per habit.
+    let mut progress = vec![(0_i64, 0_i64); parsed.len()];
+    let mut new_freezes = vec![Vec::new(); parsed.len()];
+    let first_completed = parsed
+        .iter()
+        .filter_map(|(_, completed, _)| completed.iter().min().copied())
+        .min();
+
+    if let Some(mut day) = first_completed {
+        while day < today {
+            for ((_, completed, _), (run, toward_token)) in parsed.iter().zip(progress.iter_mut()) {
+                if completed.contains(&day) {
+                    *run += 1;
+                    *toward_token += 1;
+                    if freeze_every > 0 && *toward_token >= freeze_every {
+                        streaks.freeze_tokens += 1;
+                        *toward_token = 0;
+                    }
+                }
+            }
+            for (index, (_, completed, frozen)) in parsed.iter().enumerate() {
+                let (run, toward_token) = &mut progress[index];
+                if completed.contains(&day) {
+                    continue;
+                }
+                if frozen.contains(&day) {
+                    streaks.freeze_tokens = (streaks.freeze_tokens - 1).max(0);
+                } else if *run > 0 && streaks.freeze_tokens > 0 {
+                    streaks.freeze_tokens -= 1;
+                    new_freezes[index].push(day);
+                } else {
+                    *run = 0;
+                    *toward_token = 0;
+                }
+            }
+            day += Duration::days(1);
+        }
     }
 
-    let today = Utc::now().date_naive();
-    let yesterday = today - Duration::days(1);
-    let mut cursor = if parsed_dates.contains(&today) {
-        today
-    } else if parsed_dates.contains(&yesterday) {
-        yesterday
-    } else {
-        return 0;
-    };
+    for (((habit_id, completed, _), (run, _)), new_freezes) in
+        parsed.iter().zip(progress).zip(new_freezes)
+    {
+        let current_streak = run + i64::from(completed.contains(&today));
+        streaks.by_habit.insert(
+            *habit_id,
+            HabitStreak {
+                current_streak,
+                new_freezes,
+            },
+        );
+    }
+    streaks
+}
 
-    let mut streak = 0;
-    while parsed_dates.contains(&cursor) {
-        streak += 1;
-        cursor -= Duration::days(1);
+pub(crate) fn load_habit_histories_in_conn(conn: &Connection) -> Result<Vec<HabitHistory>, String> {
+    let mut habits = conn
+        .prepare("SELECT id FROM habits ORDER BY id ASC")
+        .map_err(|e| e.to_string())?
+        .query_map([], |row| row.get::<_, i64>(0))
+        .map_err(|e| e.to_string())?
+        .map(|habit_id| {
+            habit_id.map(|habit_id| HabitHistory {
+                habit_id,
+                completed_dates: Vec::new(),
+                frozen_dates: Vec::new(),
+            })
+        })
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| e.to_string())?;
+
+    let mut stmt = conn
+        .prepare(
+            "SELECT habit_id, date, 1 FROM habit_logs
+             UNION ALL
+             SELECT habit_id, date, 0 FROM streak_freezes
+             ORDER BY 2 DESC",
+        )
+        .map_err(|e| e.to_string())?;
+    let rows = stmt
+        .query_map([], |row| {
+            Ok((
+                row.get::<_, i64>(0)?,
+                row.get::<_, String>(1)?,
+                row.get::<_, bool>(2)?,
+            ))
+        })
+        .map_err(|e| e.to_string())?;
+    for row in rows {
+        let (habit_id, date, logged) = row.map_err(|e| e.to_string())?;
+        let Some(habit) = habits.iter_mut().find(|habit| habit.habit_id == habit_id) else {
+            continue;
+        };
+        if logged {
+            habit.completed_dates.push(date);
+        } else {
+            habit.frozen_dates.push(date);
+        }
     }
 
-    streak
+    Ok(habits)
 }
 
-fn compute_this_week_count(completed_dates: &[String]) -> i64 {
-    let today = Utc::now().date_naive();
-    let days_from_monday = i64::from(today.weekday().num_days_from_monday());
-    let week_start = today - Duration::days(days_from_monday);
+/// Records the freezes the shared pool spends up to `today`, so later logging cannot move
+/// them. Runs after habits are logged and in the scheduler's daily pass.
+pub(crate) fn record_streak_freezes_in_conn(
+    conn: &Connection,
+    today: NaiveDate,
+) -> Result<(), String> {
+    let freeze_every = settings::read_i64_setting(conn, "streak_freeze_every")?.max(0);
+    let streaks = compute_habit_streaks(&load_habit_histories_in_conn(conn)?, freeze_every, today);
+    let now = Utc::now().to_rfc3339();
+    for (habit_id, streak) in &streaks.by_habit {
+        for day in &streak.new_freezes {
+            conn.execute(
+                "INSERT OR IGNORE INTO streak_freezes (habit_id, date, created_at)
+                 VALUES (?1, ?2, ?3)",
+                params![habit_id, day.format("%Y-%m-%d").to_string(), now],
+            )
+            .map_err(|e| e.to_string())?;
+        }
+    }
+    Ok(())
+}
+
+pub(crate) fn record_streak_freezes_for_app(app: &AppHandle) -> Result<(), String> {
+    let state = app.state::<AppState>();
+    let conn = state.db.lock().map_err(|e| e.to_string())?;
+    record_streak_freezes_in_conn(&conn, Utc::now().date_naive())
+}
+
+fn compute_this_week_count(
+    completed_dates: &[String],
+    today: NaiveDate,
+    week_numbering: weeks::WeekNumbering,
+) -> i64 {
+    let week_start = week_numbering.week_start(today);
     let week_end = week_start + Duration::days(6);
 
     completed_dates
@@ -273,60 +702,32 @@ fn compute_this_week_count(completed_dates: &[String]) -> i64 {
         .count() as i64
 }
 
+/// Entries newest first. Without `limit` every entry is returned, as before pagination.
 #[tauri::command]
-pub fn get_entries(state: State<'_, AppState>) -> Result<Vec<Entry>, String> {
+pub fn get_entries(
+    limit: Option<i64>,
+    offset: Option<i64>,
+    state: State<'_, AppState>,
+) -> Result<Vec<Entry>, String> {
     let conn = state.db.lock().map_err(|e| e.to_string())?;
-    let mut stmt = conn
-        .prepare("SELECT id, date, yesterday, today, project_id, created_at FROM entries ORDER BY date DESC")
-        .map_err(|e| e.to_string())?;
-
-    let entries_iter = stmt
-        .query_map([], |row| {
-            Ok(Entry {
-                id: row.get(0)?,
-                date: row.get(1)?,
-                yesterday: row.get(2)?,
-                today: row.get(3)?,
-                project_id: row.get(4)?,
-                created_at: row.get(5)?,
-            })
-        })
-        .map_err(|e| e.to_string())?;
-
-    let mut entries = Vec::new();
-    for entry in entries_iter {
-        let entry = entry.map_err(|e| e.to_string())?;
-        entries.push(entry);
-    }
+    entries::get_entries_in_conn(&conn, limit, offset)?.decrypt_with(&state.journal_cipher()?)
+}
 
-    Ok(entries)
+#[tauri::command]
+pub fn count_entries(state: State<'_, AppState>) -> Result<i64, String> {
+    let conn = state.db.lock().map_err(|e| e.to_string())?;
+    conn.query_row(
+        "SELECT COUNT(*) FROM entries WHERE deleted_at IS NULL",
+        [],
+        |row| row.get(0),
+    )
+    .map_err(|e| e.to_string())
 }
 
 #[tauri::command]
 pub fn get_entry(date: String, state: State<'_, AppState>) -> Result<Option<Entry>, String> {
     let conn = state.db.lock().map_err(|e| e.to_string())?;
-    let mut stmt = conn
-        .prepare("SELECT id, date, yesterday, today, project_id, created_at FROM entries WHERE date = ?1")
-        .map_err(|e| e.to_string())?;
-
-    let mut entries_iter = stmt
-        .query_map(params![date], |row| {
-            Ok(Entry {
-                id: row.get(0)?,
-                date: row.get(1)?,
-                yesterday: row.get(2)?,
-                today: row.get(3)?,
-                project_id: row.get(4)?,
-                created_at: row.get(5)?,
-            })
-        })
-        .map_err(|e| e.to_string())?;
-
-    if let Some(entry) = entries_iter.next() {
-        Ok(Some(entry.map_err(|e| e.to_string())?))
-    } else {
-        Ok(None)
-    }
+    entries::get_entry_in_conn(&conn, &date)?.decrypt_with(&state.journal_cipher()?)
 }
 
 #[tauri::command]
@@ -334,68 +735,266 @@ pub fn save_entry(
     date: String,
     yesterday: String,
     today: String,
+    blockers: Option<String>,
+    notes: Option<String>,
     project_id: Option<i64>,
+    app: AppHandle,
     state: State<'_, AppState>,
 ) -> Result<(), String> {
+    let cipher = state.journal_cipher()?;
+    let (yesterday, today) = (cipher.encrypt(&yesterday)?, cipher.encrypt(&today)?);
+    let blockers = cipher.encrypt(&blockers.unwrap_or_default())?;
+    let notes = cipher.encrypt(&notes.unwrap_or_default())?;
     let conn = state.db.lock().map_err(|e| e.to_string())?;
     let created_at = chrono::Utc::now().to_rfc3339();
     let project_id = normalize_project_id(&conn, project_id)?;
+    let discarded_attachments = entries::discard_trashed_entry_in_conn(&conn, &date)?;
 
     conn.execute(
-        "INSERT INTO entries (date, yesterday, today, project_id, created_at)
-         VALUES (?1, ?2, ?3, ?4, ?5)
+        "INSERT INTO entries (date, yesterday, today, blockers, notes, project_id, created_at)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)
          ON CONFLICT(date) DO UPDATE SET
             yesterday = excluded.yesterday,
             today = excluded.today,
+            blockers = excluded.blockers,
+            notes = excluded.notes,
             project_id = excluded.project_id",
-        params![date, yesterday, today, project_id, created_at],
+        params![date, yesterday, today, blockers, notes, project_id, created_at],
     )
     .map_err(|e| e.to_string())?;
-
+    entries::discard_entry_draft_in_conn(&conn, &date)?;
+    mirror::mirror_entry(&conn, &date);
+
+    drop(conn);
+    if !discarded_attachments.is_empty() {
+        attachments::remove_attachment_files(
+            &attachments::attachments_root(&app)?,
+            &discarded_attachments,
+        );
+    }
+    achievements::evaluate_achievements_for_app(&app);
     Ok(())
 }
 
 #[tauri::command]
 pub fn delete_entry(date: String, state: State<'_, AppState>) -> Result<(), String> {
     let conn = state.db.lock().map_err(|e| e.to_string())?;
+    // Soft delete: the entry and its attachments stay in the trash until purged.
+    entries::trash_entry_in_conn(&conn, &date)?;
+    entries::discard_entry_draft_in_conn(&conn, &date)?;
+    mirror::mirror_entry(&conn, &date);
+
+    Ok(())
+}
+
+/// Day One JSON export (`Journal.json` inside the exported zip).
+#[derive(Debug, Deserialize)]
+pub(crate) struct DayOneExport {
+    entries: Vec<DayOneEntry>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct DayOneEntry {
+    creation_date: String,
+    #[serde(default)]
+    text: String,
+    #[serde(default)]
+    tags: Vec<String>,
+}
+
+/// Day One escapes Markdown punctuation (`\.`, `\-`) and embeds photos as
+/// `dayone-moment://` links that point nowhere outside the app; both are cleaned up.
+fn day_one_plain_text(text: &str) -> String {
+    let mut cleaned = String::new();
+    for line in text
+        .lines()
+        .filter(|line| !line.contains("dayone-moment:/"))
+    {
+        let mut chars = line.chars().peekable();
+        while let Some(ch) = chars.next() {
+            match chars.peek() {
+                Some(next) if ch == '\\' && next.is_ascii_punctuation() => {
+                    cleaned.push(*next);
+                    chars.next();
+                }
+                _ => cleaned.push(ch),
+            }
+        }
+        cleaned.push('\n');
+    }
+
+    cleaned.trim().to_string()
+}
+
+/// Imports Day One entries grouped by local day into the "today" section. Days that
+/// already have an entry get the new text appended; text already present is skipped,
+/// so importing the same export twice changes nothing. Returns the attachment files
+/// of trashed entries that were replaced, for the caller to delete.
+pub(crate) fn import_day_one_in_conn(
+    conn: &mut Connection,
+    export: DayOneExport,
+    cipher: &encryption::JournalCipher,
+) -> Result<(DayOneImportSummary, Vec<String>), String> {
+    let mut days: BTreeMap<String, Vec<DayOneEntry>> = BTreeMap::new();
+    let entries_read = export.entries.len() as i64;
+    for entry in export.entries {
+        let created_at = chrono::DateTime::parse_from_rfc3339(&entry.creation_date)
+            .map_err(|_| format!("Invalid Day One creationDate: {}", entry.creation_date))?;
+        let date = created_at
+            .with_timezone(&chrono::Local)
+            .format("%Y-%m-%d")
+            .to_string();
+        days.entry(date).or_default().push(entry);
+    }
+
+    let tx = conn.transaction().map_err(|e| e.to_string())?;
+    let mut days_written = 0;
+    let mut discarded_attachments = Vec::new();
+    for (date, mut day_entries) in days {
+        day_entries.sort_by(|a, b| a.creation_date.cmp(&b.creation_date));
+        let existing = entries::get_entry_in_conn(&tx, &date)?;
+        let mut today = match &existing {
+            Some(entry) => cipher.decrypt(&entry.today)?,
+            None => String::new(),
+        };
+        let mut changed = false;
+        for text in day_entries
+            .iter()
+            .map(|entry| day_one_plain_text(&entry.text))
+        {
+            if text.is_empty() || today.contains(&text) {
+                continue;
+            }
+            if !today.trim().is_empty() {
+                today.push_str("\n\n");
+            }
+            today.push_str(&text);
+            changed = true;
+        }
+        if !changed && existing.is_some() {
+            continue;
+        }
 
-    conn.execute("DELETE FROM entries WHERE date = ?1", params![date])
+        discarded_attachments.extend(entries::discard_trashed_entry_in_conn(&tx, &date)?);
+        tx.execute(
+            "INSERT INTO entries (date, yesterday, today, project_id, created_at)
+             VALUES (?1, '', ?2, NULL, ?3)
+             ON CONFLICT(date) DO UPDATE SET today = excluded.today",
+            params![date, cipher.encrypt(&today)?, day_entries[0].creation_date],
+        )
         .map_err(|e| e.to_string())?;
+        let entry_id = tx
+            .query_row(
+                "SELECT id FROM entries WHERE date = ?1",
+                params![date],
+                |row| row.get::<_, i64>(0),
+            )
+            .map_err(|e| e.to_string())?;
+        // Day One allows tags this journal rejects (e.g. over 48 characters); those are dropped.
+        for tag in day_entries.iter().flat_map(|entry| &entry.tags) {
+            if validation::normalize_entry_tag(tag).is_ok() {
+                entries::add_entry_tag_in_conn(&tx, entry_id, tag)?;
+            }
+        }
+        days_written += 1;
+    }
+    tx.commit().map_err(|e| e.to_string())?;
 
-    Ok(())
+    Ok((
+        DayOneImportSummary {
+            entries_read,
+            days_written,
+        },
+        discarded_attachments,
+    ))
 }
 
+/// `path` is the Day One JSON file, or the unzipped export folder holding one JSON
+/// file per journal.
 #[tauri::command]
-pub fn search_entries(query: String, state: State<'_, AppState>) -> Result<Vec<Entry>, String> {
-    let conn = state.db.lock().map_err(|e| e.to_string())?;
-    let search_term = format!("%{}%", query);
-    let mut stmt = conn.prepare("SELECT id, date, yesterday, today, project_id, created_at FROM entries WHERE yesterday LIKE ?1 OR today LIKE ?1 ORDER BY date DESC").map_err(|e| e.to_string())?;
+pub fn import_day_one(
+    path: String,
+    app: AppHandle,
+    state: State<'_, AppState>,
+) -> Result<DayOneImportSummary, String> {
+    let path = std::path::PathBuf::from(path.trim());
+    let files = if path.is_dir() {
+        let mut files = Vec::new();
+        for item in std::fs::read_dir(&path).map_err(|e| e.to_string())? {
+            let file = item.map_err(|e| e.to_string())?.path();
+            if file
+                .extension()
+                .is_some_and(|ext| ext.eq_ignore_ascii_case("json"))
+            {
+                files.push(file);
+            }
+        }
+        files.sort();
+        files
+    } else {
+        vec![path.clone()]
+    };
+    if files.is_empty() {
+        return Err(format!(
+            "No Day One JSON export found in {}",
+            path.display()
+        ));
+    }
 
-    let entries_iter = stmt
-        .query_map(params![search_term], |row| {
-            Ok(Entry {
-                id: row.get(0)?,
-                date: row.get(1)?,
-                yesterday: row.get(2)?,
-                today: row.get(3)?,
-                project_id: row.get(4)?,
-                created_at: row.get(5)?,
-            })
-        })
-        .map_err(|e| e.to_string())?;
+    let mut export = DayOneExport {
+        entries: Vec::new(),
+    };
+    for file in &files {
+        let content = std::fs::read_to_string(file).map_err(|e| e.to_string())?;
+        let journal: DayOneExport = serde_json::from_str(&content)
+            .map_err(|e| format!("{} is not a Day One export: {}", file.display(), e))?;
+        export.entries.extend(journal.entries);
+    }
 
-    let mut entries = Vec::new();
-    for entry in entries_iter {
-        entries.push(entry.map_err(|e| e.to_string())?);
+    let cipher = state.journal_cipher()?;
+    let mut conn = state.db.lock().map_err(|e| e.to_string())?;
+    let (summary, discarded_attachments) = import_day_one_in_conn(&mut conn, export, &cipher)?;
+
+    drop(conn);
+    if !discarded_attachments.is_empty() {
+        attachments::remove_attachment_files(
+            &attachments::attachments_root(&app)?,
+            &discarded_attachments,
+        );
     }
+    achievements::evaluate_achievements_for_app(&app);
+    Ok(summary)
+}
 
-    Ok(entries)
+#[tauri::command]
+pub fn search_entries(
+    query: String,
+    state: State<'_, AppState>,
+) -> Result<Vec<EntrySearchResult>, String> {
+    let cipher = state.journal_cipher()?;
+    let conn = state.db.lock().map_err(|e| e.to_string())?;
+    // The full-text index only holds ciphertext once the journal is encrypted.
+    if cipher.is_enabled() {
+        return encryption::search_encrypted_entries_in_conn(&conn, &cipher, &query);
+    }
+    entries::search_entries_in_conn(&conn, &query)
 }
 
+/// Commits in the working directory since the start of the previous working day,
+/// so the standup window spans weekends and days off.
 #[tauri::command]
-pub fn get_git_commits() -> Result<Vec<String>, String> {
+pub fn get_git_commits(state: State<'_, AppState>) -> Result<Vec<String>, String> {
+    let since = {
+        let conn = state.db.lock().map_err(|e| e.to_string())?;
+        entries::previous_workday_in_conn(&conn, chrono::Local::now().date_naive())?
+    };
     let output = match std::process::Command::new("git")
-        .args(["log", "--since=midnight", "--oneline"])
+        .args([
+            "log",
+            &format!("--since={} 00:00", since.format("%Y-%m-%d")),
+            "--oneline",
+        ])
         .current_dir(std::env::current_dir().unwrap_or_else(|_| std::path::PathBuf::from(".")))
         .output()
     {
@@ -413,122 +1012,12 @@ pub fn get_git_commits() -> Result<Vec<String>, String> {
 }
 
 #[tauri::command]
-pub fn get_pages(state: State<'_, AppState>) -> Result<Vec<Page>, String> {
-    let conn = state.db.lock().map_err(|e| e.to_string())?;
-    let mut stmt = conn
-        .prepare(
-            "SELECT id, title, content, created_at, updated_at FROM pages ORDER BY updated_at DESC",
-        )
-        .map_err(|e| e.to_string())?;
-
-    let pages_iter = stmt
-        .query_map([], |row| {
-            Ok(Page {
-                id: row.get(0)?,
-                title: row.get(1)?,
-                content: row.get(2)?,
-                created_at: row.get(3)?,
-                updated_at: row.get(4)?,
-            })
-        })
-        .map_err(|e| e.to_string())?;
-
-    let mut pages = Vec::new();
-    for page in pages_iter {
-        pages.push(page.map_err(|e| e.to_string())?);
-    }
-
-    Ok(pages)
-}
-
-#[tauri::command]
-pub fn get_page(id: i64, state: State<'_, AppState>) -> Result<Option<Page>, String> {
+pub fn get_goal_milestones(
+    goal_id: Option<i64>,
+    state: State<'_, AppState>,
+) -> Result<Vec<GoalMilestone>, String> {
     let conn = state.db.lock().map_err(|e| e.to_string())?;
-    let mut stmt = conn
-        .prepare("SELECT id, title, content, created_at, updated_at FROM pages WHERE id = ?1")
-        .map_err(|e| e.to_string())?;
-
-    let mut pages_iter = stmt
-        .query_map(params![id], |row| {
-            Ok(Page {
-                id: row.get(0)?,
-                title: row.get(1)?,
-                content: row.get(2)?,
-                created_at: row.get(3)?,
-                updated_at: row.get(4)?,
-            })
-        })
-        .map_err(|e| e.to_string())?;
-
-    if let Some(page) = pages_iter.next() {
-        Ok(Some(page.map_err(|e| e.to_string())?))
-    } else {
-        Ok(None)
-    }
-}
-
-#[tauri::command]
-pub fn create_page(
-    title: String,
-    content: String,
-    state: State<'_, AppState>,
-) -> Result<Page, String> {
-    let conn = state.db.lock().map_err(|e| e.to_string())?;
-    let now = chrono::Utc::now().to_rfc3339();
-
-    conn.execute(
-        "INSERT INTO pages (title, content, created_at, updated_at) VALUES (?1, ?2, ?3, ?4)",
-        params![title, content, now, now],
-    )
-    .map_err(|e| e.to_string())?;
-
-    let id = conn.last_insert_rowid();
-
-    Ok(Page {
-        id,
-        title,
-        content,
-        created_at: now.clone(),
-        updated_at: now,
-    })
-}
-
-#[tauri::command]
-pub fn update_page(
-    id: i64,
-    title: String,
-    content: String,
-    state: State<'_, AppState>,
-) -> Result<(), String> {
-    let conn = state.db.lock().map_err(|e| e.to_string())?;
-    let now = chrono::Utc::now().to_rfc3339();
-
-    conn.execute(
-        "UPDATE pages SET title = ?1, content = ?2, updated_at = ?3 WHERE id = ?4",
-        params![title, content, now, id],
-    )
-    .map_err(|e| e.to_string())?;
-
-    Ok(())
-}
-
-#[tauri::command]
-pub fn delete_page(id: i64, state: State<'_, AppState>) -> Result<(), String> {
-    let conn = state.db.lock().map_err(|e| e.to_string())?;
-
-    conn.execute("DELETE FROM pages WHERE id = ?1", params![id])
-        .map_err(|e| e.to_string())?;
-
-    Ok(())
-}
-
-#[tauri::command]
-pub fn get_goal_milestones(
-    goal_id: Option<i64>,
-    state: State<'_, AppState>,
-) -> Result<Vec<GoalMilestone>, String> {
-    let conn = state.db.lock().map_err(|e| e.to_string())?;
-    let mut milestones = Vec::new();
+    let mut milestones = Vec::new();
 
     if let Some(goal_id) = goal_id {
         let mut stmt = conn
@@ -1174,6 +1663,8 @@ pub fn delete_goal(id: i64, state: State<'_, AppState>) -> Result<(), String> {
     Ok(())
 }
 
+/// Habits with their streaks. Freezes the shared pool would spend but that are not
+/// recorded yet are included in `frozen_dates`; nothing is written here.
 #[tauri::command]
 pub fn get_habits(state: State<'_, AppState>) -> Result<Vec<HabitWithLogs>, String> {
     let conn = state.db.lock().map_err(|e| e.to_string())?;
@@ -1184,9 +1675,11 @@ pub fn get_habits(state: State<'_, AppState>) -> Result<Vec<HabitWithLogs>, Stri
              ORDER BY updated_at DESC",
         )
         .map_err(|e| e.to_string())?;
-    let mut logs_stmt = conn
-        .prepare("SELECT date FROM habit_logs WHERE habit_id = ?1 ORDER BY date DESC")
-        .map_err(|e| e.to_string())?;
+    let freeze_every = settings::read_i64_setting(&conn, "streak_freeze_every")?.max(0);
+    let week_numbering = weeks::read_week_numbering_in_conn(&conn)?;
+    let today = Utc::now().date_naive();
+    let mut histories = load_habit_histories_in_conn(&conn)?;
+    let mut streaks = compute_habit_streaks(&histories, freeze_every, today);
 
     let habits_iter = habits_stmt
         .query_map([], |row| {
@@ -1205,17 +1698,25 @@ pub fn get_habits(state: State<'_, AppState>) -> Result<Vec<HabitWithLogs>, Stri
     let mut habits = Vec::new();
     for habit in habits_iter {
         let habit = habit.map_err(|e| e.to_string())?;
-        let dates_iter = logs_stmt
-            .query_map(params![habit.id], |row| row.get::<_, String>(0))
-            .map_err(|e| e.to_string())?;
-
-        let mut completed_dates = Vec::new();
-        for date in dates_iter {
-            completed_dates.push(date.map_err(|e| e.to_string())?);
-        }
-
-        let current_streak = compute_current_streak(&completed_dates);
-        let this_week_count = compute_this_week_count(&completed_dates);
+        let (completed_dates, mut frozen_dates) = match histories
+            .iter()
+            .position(|history| history.habit_id == habit.id)
+        {
+            Some(index) => {
+                let history = histories.swap_remove(index);
+                (history.completed_dates, history.frozen_dates)
+            }
+            None => (Vec::new(), Vec::new()),
+        };
+        let streak = streaks.by_habit.remove(&habit.id).unwrap_or_default();
+        frozen_dates.extend(
+            streak
+                .new_freezes
+                .iter()
+                .map(|day| day.format("%Y-%m-%d").to_string()),
+        );
+        frozen_dates.sort_by(|a, b| b.cmp(a));
+        let this_week_count = compute_this_week_count(&completed_dates, today, week_numbering);
 
         habits.push(HabitWithLogs {
             id: habit.id,
@@ -1224,7 +1725,9 @@ pub fn get_habits(state: State<'_, AppState>) -> Result<Vec<HabitWithLogs>, Stri
             target_per_week: habit.target_per_week,
             color: habit.color,
             completed_dates,
-            current_streak,
+            frozen_dates,
+            current_streak: streak.current_streak,
+            freeze_tokens: streaks.freeze_tokens,
             this_week_count,
             created_at: habit.created_at,
             updated_at: habit.updated_at,
@@ -1311,6 +1814,7 @@ pub fn toggle_habit_completion(
     habit_id: i64,
     date: String,
     completed: bool,
+    app: AppHandle,
     state: State<'_, AppState>,
 ) -> Result<(), String> {
     let mut conn = state.db.lock().map_err(|e| e.to_string())?;
@@ -1341,13 +1845,87 @@ pub fn toggle_habit_completion(
     .map_err(|e| e.to_string())?;
 
     tx.commit().map_err(|e| e.to_string())?;
+    record_streak_freezes_in_conn(&conn, Utc::now().date_naive())?;
+    drop(conn);
+    if completed {
+        achievements::evaluate_achievements_for_app(&app);
+    }
     Ok(())
 }
 
+/// Marks several habits done on one date inside a single transaction.
+/// Duplicate ids are collapsed; an unknown habit rolls the whole batch back.
+pub(crate) fn log_habits_bulk_in_conn(
+    conn: &mut Connection,
+    date: String,
+    habit_ids: &[i64],
+) -> Result<Vec<i64>, String> {
+    let normalized_date = normalize_habit_date(date)?;
+    let now = Utc::now().to_rfc3339();
+    let mut seen = HashSet::new();
+    let habit_ids: Vec<i64> = habit_ids
+        .iter()
+        .copied()
+        .filter(|habit_id| seen.insert(*habit_id))
+        .collect();
+
+    let tx = conn.transaction().map_err(|e| e.to_string())?;
+    for habit_id in &habit_ids {
+        if !habit_exists(&tx, *habit_id)? {
+            return Err(format!("Habit {} not found", habit_id));
+        }
+
+        tx.execute(
+            "INSERT INTO habit_logs (habit_id, date, created_at)
+             VALUES (?1, ?2, ?3)
+             ON CONFLICT(habit_id, date) DO UPDATE SET created_at = excluded.created_at",
+            params![habit_id, normalized_date, now],
+        )
+        .map_err(|e| e.to_string())?;
+        tx.execute(
+            "UPDATE habits SET updated_at = ?1 WHERE id = ?2",
+            params![now, habit_id],
+        )
+        .map_err(|e| e.to_string())?;
+    }
+    tx.commit().map_err(|e| e.to_string())?;
+    record_streak_freezes_in_conn(conn, Utc::now().date_naive())?;
+
+    Ok(habit_ids)
+}
+
+/// Logs several habits at once (quick-log shortcut, CLI) and emits one `habits-changed` event.
+#[tauri::command]
+pub fn log_habits_bulk(
+    date: String,
+    habit_ids: Vec<i64>,
+    app: AppHandle,
+    state: State<'_, AppState>,
+) -> Result<Vec<i64>, String> {
+    let logged = {
+        let mut conn = state.db.lock().map_err(|e| e.to_string())?;
+        log_habits_bulk_in_conn(&mut conn, date, &habit_ids)?
+    };
+
+    if !logged.is_empty() {
+        if let Err(error) = emit_event(&app, "habits-changed", &logged) {
+            eprintln!("Failed to emit habit change: {error}");
+        }
+        achievements::evaluate_achievements_for_app(&app);
+    }
+
+    Ok(logged)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::models::{
+        AppearanceSettings, DailyWordCount, EntryStreaks, Page, PageProperty, PagePropertyValue,
+    };
+    use chrono::Datelike;
     use rusqlite::Connection;
+    use std::collections::HashMap;
     use std::fs;
 
     fn test_link_connection() -> Connection {
@@ -1389,6 +1967,14 @@ mod tests {
             compute_next_due_date("2026-04-06", "weekly"),
             Some("2026-04-13".to_string())
         );
+        assert_eq!(
+            compute_next_due_date("2026-12-15", "monthly"),
+            Some("2027-01-15".to_string())
+        );
+        assert_eq!(
+            compute_next_due_date("2028-01-31", "monthly"),
+            Some("2028-02-29".to_string())
+        );
         assert_eq!(compute_next_due_date("2026-04-06", "none"), None);
     }
 
@@ -1476,8 +2062,25 @@ mod tests {
         assert_eq!(normalize_optional_date(Some("   ".to_string())), None);
     }
 
+    /// Streak of a single habit and the pool left over.
+    fn single_habit_streak(
+        completed: &[String],
+        frozen: &[String],
+        freeze_every: i64,
+        today: NaiveDate,
+    ) -> (HabitStreak, i64) {
+        let history = HabitHistory {
+            habit_id: 1,
+            completed_dates: completed.to_vec(),
+            frozen_dates: frozen.to_vec(),
+        };
+        let mut streaks = compute_habit_streaks(&[history], freeze_every, today);
+        let streak = streaks.by_habit.remove(&1).expect("habit streak");
+        (streak, streaks.freeze_tokens)
+    }
+
     #[test]
-    fn compute_current_streak_counts_today_or_yesterday_runs() {
+    fn compute_habit_streaks_counts_today_or_yesterday_runs() {
         let today = Utc::now().date_naive();
         let yesterday = today - Duration::days(1);
         let two_days_ago = today - Duration::days(2);
@@ -1490,8 +2093,91 @@ mod tests {
         ];
         let stale = vec![last_week.format("%Y-%m-%d").to_string()];
 
-        assert_eq!(compute_current_streak(&current), 3);
-        assert_eq!(compute_current_streak(&stale), 0);
+        assert_eq!(
+            single_habit_streak(&current, &[], 0, today)
+                .0
+                .current_streak,
+            3
+        );
+        assert_eq!(
+            single_habit_streak(&stale, &[], 0, today).0.current_streak,
+            0
+        );
+    }
+
+    #[test]
+    fn compute_habit_streaks_spends_earned_freezes_on_missed_days() {
+        let today = NaiveDate::from_ymd_opt(2026, 5, 20).expect("date");
+        let dates = |days: &[u32]| -> Vec<String> {
+            days.iter()
+                .map(|day| format!("2026-05-{:02}", day))
+                .collect()
+        };
+        // Three completions earn a token that covers the 14th.
+        let completed = dates(&[11, 12, 13, 15, 16, 17, 18, 19, 20]);
+
+        let (without_freezes, _) = single_habit_streak(&completed, &[], 0, today);
+        assert_eq!(without_freezes.current_streak, 6);
+        assert!(without_freezes.new_freezes.is_empty());
+
+        let (with_freezes, tokens) = single_habit_streak(&completed, &[], 3, today);
+        assert_eq!(with_freezes.current_streak, 9);
+        assert_eq!(
+            with_freezes.new_freezes,
+            vec![NaiveDate::from_ymd_opt(2026, 5, 14).expect("date")]
+        );
+        assert_eq!(tokens, 1);
+
+        let (recorded, _) = single_habit_streak(&completed, &dates(&[14]), 3, today);
+        assert_eq!(recorded.current_streak, 9);
+        assert!(recorded.new_freezes.is_empty());
+
+        // A two-day gap with a single token still breaks the streak.
+        let (gap, _) = single_habit_streak(&dates(&[1, 2, 3, 6, 7]), &[], 3, today);
+        assert_eq!(gap.current_streak, 0);
+    }
+
+    #[test]
+    fn streak_freezes_come_from_one_pool_shared_by_all_habits() {
+        let conn = command_test_connection();
+        conn.execute_batch(
+            "INSERT INTO habits (id, title, description, target_per_week, color, created_at, updated_at)
+             VALUES (1, 'Run', '', 7, '#000000', '2026-05-01T00:00:00Z', '2026-05-01T00:00:00Z'),
+                    (2, 'Read', '', 7, '#000000', '2026-05-01T00:00:00Z', '2026-05-01T00:00:00Z');
+             INSERT INTO habit_logs (habit_id, date, created_at)
+             VALUES (1, '2026-05-11', ''), (1, '2026-05-12', ''), (1, '2026-05-13', ''),
+                    (1, '2026-05-14', ''), (1, '2026-05-15', ''),
+                    (2, '2026-05-13', ''), (2, '2026-05-15', '');",
+        )
+        .expect("seed habits");
+        settings::write_setting(&conn, "streak_freeze_every", "3").expect("setting");
+        let today = NaiveDate::from_ymd_opt(2026, 5, 15).expect("date");
+
+        // Run earns the token on the 13th and Read spends it on the 14th.
+        let streaks = compute_habit_streaks(
+            &load_habit_histories_in_conn(&conn).expect("histories"),
+            3,
+            today,
+        );
+        assert_eq!(streaks.by_habit[&1].current_streak, 5);
+        assert_eq!(streaks.by_habit[&2].current_streak, 2);
+        assert_eq!(
+            streaks.by_habit[&2].new_freezes,
+            vec![NaiveDate::from_ymd_opt(2026, 5, 14).expect("date")]
+        );
+        assert_eq!(streaks.freeze_tokens, 0);
+
+        let recorded = |conn: &Connection| -> Vec<(i64, String)> {
+            conn.prepare("SELECT habit_id, date FROM streak_freezes ORDER BY habit_id, date")
+                .expect("prepare")
+                .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))
+                .expect("query")
+                .collect::<Result<_, _>>()
+                .expect("rows")
+        };
+        assert!(recorded(&conn).is_empty());
+        record_streak_freezes_in_conn(&conn, today).expect("record freezes");
+        assert_eq!(recorded(&conn), vec![(2, "2026-05-14".to_string())]);
     }
 
     #[test]
@@ -1510,7 +2196,10 @@ mod tests {
             previous_week_day.format("%Y-%m-%d").to_string(),
         ];
 
-        assert_eq!(compute_this_week_count(&completed_dates), 3);
+        assert_eq!(
+            compute_this_week_count(&completed_dates, today, WeekNumbering::Iso),
+            3
+        );
     }
 
     #[test]
@@ -1596,6 +2285,40 @@ mod tests {
         assert_eq!(child_due_date, "2026-04-14");
     }
 
+    #[test]
+    fn monthly_recurrence_keeps_the_series_day_after_short_months() {
+        let conn = command_test_connection();
+        conn.execute(
+            "INSERT INTO tasks (id, title, description, status, priority, due_date, recurrence,
+                recurrence_until, time_estimate_minutes, timer_accumulated_seconds, created_at, updated_at)
+             VALUES (1, 'Pay rent', '', 'done', 'medium', '2026-01-31', 'monthly', '2026-04-30',
+                0, 0, '2026-01-01T09:00:00Z', '2026-01-31T09:00:00Z')",
+            [],
+        )
+        .expect("seed monthly task");
+
+        let mut due_dates = Vec::new();
+        let mut current = 1_i64;
+        loop {
+            materialize_recurring_successor(&conn, current).expect("materialize successor");
+            let Some((id, due_date)) = conn
+                .query_row(
+                    "SELECT id, due_date FROM tasks WHERE parent_task_id = ?1",
+                    [current],
+                    |row| Ok((row.get::<_, i64>(0)?, row.get::<_, String>(1)?)),
+                )
+                .optional()
+                .expect("successor")
+            else {
+                break;
+            };
+            due_dates.push(due_date);
+            current = id;
+        }
+
+        assert_eq!(due_dates, vec!["2026-02-28", "2026-03-31", "2026-04-30"]);
+    }
+
     #[test]
     fn materialize_recurring_successor_skips_invalid_recurrence_limit() {
         let conn = command_test_connection();
@@ -1655,6 +2378,41 @@ mod tests {
         assert_eq!(status, "completed");
     }
 
+    #[test]
+    fn backup_payloads_are_upgraded_from_older_formats() {
+        let legacy = serde_json::json!({
+            "exported_at": "2025-01-01T00:00:00Z",
+            "habits": [{ "id": 3, "title": "Read", "completed_dates": ["2025-01-01", "2025-01-02"] }],
+        });
+        let upgraded = upgrade_backup_payload(legacy).expect("upgrade v1");
+        assert_eq!(
+            upgraded
+                .habit_logs
+                .iter()
+                .map(|log| (log.habit_id, log.date.as_str()))
+                .collect::<Vec<_>>(),
+            vec![(3, "2025-01-01"), (3, "2025-01-02")]
+        );
+
+        // Current payloads keep their explicit logs untouched.
+        let current = serde_json::json!({
+            "format_version": BACKUP_FORMAT_VERSION,
+            "habits": [{ "id": 3, "title": "Read", "completed_dates": ["2025-01-01"] }],
+            "habit_logs": [],
+        });
+        assert!(upgrade_backup_payload(current)
+            .expect("current")
+            .habit_logs
+            .is_empty());
+
+        let newer = serde_json::json!({ "format_version": BACKUP_FORMAT_VERSION + 1 });
+        assert!(upgrade_backup_payload(newer)
+            .expect_err("newer format")
+            .contains("Update the app"));
+        assert!(upgrade_backup_payload(serde_json::json!({ "format_version": "two" })).is_err());
+        assert!(upgrade_backup_payload(serde_json::json!([])).is_err());
+    }
+
     #[test]
     fn import_backup_replaces_existing_data_and_sanitizes_links() {
         let mut conn = command_test_connection();
@@ -1767,8 +2525,11 @@ mod tests {
                     date: "2026-04-04".to_string(),
                     yesterday: "Code review".to_string(),
                     today: "Ship tests".to_string(),
+                    blockers: "Waiting on staging access".to_string(),
+                    notes: String::new(),
                     project_id: Some(1),
                     created_at: Some("2026-04-04T09:00:00Z".to_string()),
+                    pinned: true,
                 }],
                 ..BackupPayload::default()
             },
@@ -1808,6 +2569,15 @@ mod tests {
             .expect("goal progress");
         assert_eq!(goal_progress, 50);
 
+        let imported_entry_pinned: bool = conn
+            .query_row(
+                "SELECT pinned FROM entries WHERE date = '2026-04-04'",
+                [],
+                |row| row.get(0),
+            )
+            .expect("entry pinned");
+        assert!(imported_entry_pinned);
+
         let imported_entry_project_id: Option<i64> = conn
             .query_row(
                 "SELECT project_id FROM entries WHERE date = '2026-04-04'",
@@ -1816,6 +2586,18 @@ mod tests {
             )
             .expect("entry project");
         assert_eq!(imported_entry_project_id, Some(1));
+
+        let imported_entry = get_entry_in_conn(&conn, "2026-04-04")
+            .expect("entry")
+            .expect("imported");
+        assert_eq!(imported_entry.blockers, "Waiting on staging access");
+        assert_eq!(imported_entry.notes, "");
+        assert_eq!(
+            search_entries_in_conn(&conn, "staging")
+                .expect("search")
+                .len(),
+            1
+        );
     }
 
     #[test]
@@ -1896,4 +2678,5397 @@ mod tests {
         assert_eq!(meeting_urls_and_limit.1, None);
         assert_eq!(meeting_urls_and_limit.2, None);
     }
+
+    #[test]
+    fn parse_issue_url_recognizes_supported_providers() {
+        let github = parse_issue_url("https://github.com/acme/widgets/pull/42?tab=files")
+            .expect("github pull request");
+        assert_eq!(github.provider, "github");
+        assert_eq!(github.kind, "pull_request");
+        assert_eq!(github.external_ref(), "acme/widgets#42");
+
+        let gitlab = parse_issue_url("https://gitlab.example.com/group/sub/app/-/issues/7")
+            .expect("gitlab issue");
+        assert_eq!(gitlab.provider, "gitlab");
+        assert_eq!(gitlab.origin, "https://gitlab.example.com");
+        assert_eq!(gitlab.external_ref(), "group/sub/app#7");
+
+        let jira =
+            parse_issue_url("https://corp.example.com/jira/browse/OPS-118").expect("jira issue");
+        assert_eq!(jira.provider, "jira");
+        assert_eq!(jira.origin, "https://corp.example.com/jira");
+        assert_eq!(jira.external_ref(), "OPS-118");
+
+        assert!(parse_issue_url("https://github.com/acme/widgets").is_none());
+        assert!(parse_issue_url("ftp://github.com/acme/widgets/issues/1").is_none());
+        assert!(parse_issue_url("https://example.com/browse/not-a-key").is_none());
+    }
+
+    #[test]
+    fn integration_credentials_only_go_to_the_configured_host() {
+        let reference = |url: &str| parse_issue_url(url).expect("reference");
+
+        let github = reference("https://github.com/acme/widgets/issues/1");
+        assert!(sends_credentials_to(&github, None));
+        assert!(sends_credentials_to(
+            &github,
+            Some("https://api.github.com")
+        ));
+        assert!(!sends_credentials_to(
+            &reference("https://evil.example/acme/widgets/issues/1"),
+            None
+        ));
+
+        let enterprise = Some("https://ghe.corp.example/api/v3");
+        assert!(sends_credentials_to(
+            &reference("https://GHE.corp.example/acme/widgets/pull/2"),
+            enterprise
+        ));
+        assert!(!sends_credentials_to(&github, enterprise));
+
+        let jira = reference("https://corp.example.com/jira/browse/OPS-118");
+        assert!(!sends_credentials_to(&jira, None));
+        assert!(sends_credentials_to(
+            &jira,
+            Some("https://corp.example.com/jira")
+        ));
+        assert!(!sends_credentials_to(
+            &reference("https://evil.example/browse/OPS-118"),
+            Some("https://corp.example.com/jira")
+        ));
+        assert!(!sends_credentials_to(
+            &reference("https://evil.example/group/app/-/issues/3"),
+            Some("https://gitlab.corp.example/api/v4")
+        ));
+    }
+
+    #[test]
+    fn create_linked_task_in_conn_prefills_task_from_metadata() {
+        let mut conn = command_test_connection();
+        let reference =
+            parse_issue_url("https://github.com/acme/widgets/issues/9").expect("reference");
+
+        assert_eq!(
+            priority_from_labels(&["bug".to_string(), "priority: high".to_string()]),
+            "high"
+        );
+
+        let created = create_linked_task_in_conn(
+            &mut conn,
+            "https://github.com/acme/widgets/issues/9",
+            &reference,
+            IssueMetadata {
+                title: "Crash on save".to_string(),
+                labels: vec!["bug".to_string(), "P0".to_string()],
+                state: Some("open".to_string()),
+            },
+        )
+        .expect("linked task");
+
+        assert_eq!(created.task.title, "Crash on save");
+        assert_eq!(created.task.priority, "urgent");
+        assert_eq!(created.link.task_id, created.task.id);
+
+        let stored: (String, String) = conn
+            .query_row(
+                "SELECT external_ref, labels_json FROM task_links WHERE task_id = ?1",
+                params![created.task.id],
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            )
+            .expect("stored link");
+        assert_eq!(stored.0, "acme/widgets#9");
+        assert_eq!(stored.1, r#"["bug","P0"]"#);
+
+        let fallback = create_linked_task_in_conn(
+            &mut conn,
+            "https://github.com/acme/widgets/issues/10",
+            &parse_issue_url("https://github.com/acme/widgets/issues/10").expect("reference"),
+            IssueMetadata::default(),
+        )
+        .expect("fallback task");
+        assert_eq!(fallback.task.title, "acme/widgets#10");
+        assert_eq!(fallback.task.priority, "medium");
+
+        let duplicate = create_linked_task_in_conn(
+            &mut conn,
+            "https://github.com/acme/widgets/issues/10",
+            &parse_issue_url("https://github.com/acme/widgets/issues/10").expect("reference"),
+            IssueMetadata::default(),
+        );
+        assert_eq!(
+            duplicate.err().as_deref(),
+            Some(format!("This URL is already linked to task #{}", fallback.task.id).as_str())
+        );
+    }
+
+    #[test]
+    fn url_captured_tasks_wait_in_review_queue_until_accepted_or_dismissed() {
+        let mut conn = command_test_connection();
+        let mut task_ids = Vec::new();
+        for number in [21, 22] {
+            let url = format!("https://github.com/acme/widgets/issues/{}", number);
+            let reference = parse_issue_url(&url).expect("reference");
+            let created =
+                create_linked_task_in_conn(&mut conn, &url, &reference, IssueMetadata::default())
+                    .expect("linked task");
+            task_ids.push(created.task.id);
+        }
+
+        let queue = get_review_queue_in_conn(&conn).expect("queue");
+        assert_eq!(queue.len(), 2);
+        assert_eq!(queue[0].task.id, task_ids[0]);
+        assert_eq!(queue[0].source, "url_capture");
+        assert_eq!(
+            queue[0].source_url.as_deref(),
+            Some("https://github.com/acme/widgets/issues/21")
+        );
+
+        accept_review_item_in_conn(&conn, task_ids[0]).expect("accept");
+        assert!(accept_review_item_in_conn(&conn, task_ids[0]).is_err());
+        dismiss_review_item_in_conn(&mut conn, task_ids[1]).expect("dismiss");
+        assert!(get_review_queue_in_conn(&conn).expect("queue").is_empty());
+
+        let remaining: Vec<i64> = conn
+            .prepare("SELECT id FROM tasks ORDER BY id")
+            .expect("prepare")
+            .query_map([], |row| row.get(0))
+            .expect("query")
+            .collect::<Result<_, _>>()
+            .expect("ids");
+        assert_eq!(remaining, vec![task_ids[0]]);
+        let links: i64 = conn
+            .query_row("SELECT COUNT(*) FROM task_links", [], |row| row.get(0))
+            .expect("links");
+        assert_eq!(links, 1);
+    }
+
+    #[test]
+    fn apply_linked_item_refresh_in_conn_completes_task_when_pull_request_merges() {
+        let mut conn = command_test_connection();
+        let url = "https://github.com/acme/widgets/pull/5";
+        let reference = parse_issue_url(url).expect("reference");
+        let created = create_linked_task_in_conn(
+            &mut conn,
+            url,
+            &reference,
+            IssueMetadata {
+                title: "Add export".to_string(),
+                labels: Vec::new(),
+                state: Some("open".to_string()),
+            },
+        )
+        .expect("linked task");
+
+        let unchanged = apply_linked_item_refresh_in_conn(
+            &conn,
+            created.link,
+            IssueMetadata {
+                title: "Add CSV export".to_string(),
+                labels: Vec::new(),
+                state: Some("open".to_string()),
+            },
+            true,
+        )
+        .expect("refresh");
+        assert!(unchanged.is_none());
+
+        let link = get_task_link_in_conn(&conn, created.task.id).expect("stored link");
+        assert_eq!(link.title, "Add CSV export");
+
+        let change = apply_linked_item_refresh_in_conn(
+            &conn,
+            link,
+            IssueMetadata {
+                title: String::new(),
+                labels: Vec::new(),
+                state: Some("merged".to_string()),
+            },
+            true,
+        )
+        .expect("refresh")
+        .expect("merge change");
+        assert_eq!(change.previous_state.as_deref(), Some("open"));
+        assert!(change.task_completed);
+
+        let (status, completed_at): (String, Option<String>) = conn
+            .query_row(
+                "SELECT status, completed_at FROM tasks WHERE id = ?1",
+                params![created.task.id],
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            )
+            .expect("task row");
+        assert_eq!(status, "done");
+        assert!(completed_at.is_some());
+    }
+
+    #[test]
+    fn toggle_task_timer_in_conn_switches_between_recent_tasks() {
+        let conn = command_test_connection();
+        let now = Utc::now().to_rfc3339();
+        for (title, status) in [
+            ("Write docs", "todo"),
+            ("Fix build", "in_progress"),
+            ("Ship", "done"),
+        ] {
+            conn.execute(
+                "INSERT INTO tasks (title, description, status, priority, created_at, updated_at)
+                 VALUES (?1, '', ?2, 'medium', ?3, ?3)",
+                params![title, status, now],
+            )
+            .expect("insert task");
+        }
+
+        let recent = get_recent_timer_tasks_in_conn(&conn, 5).expect("recent tasks");
+        assert_eq!(
+            recent
+                .iter()
+                .map(|task| task.title.as_str())
+                .collect::<Vec<_>>(),
+            vec!["Fix build"]
+        );
+
+        toggle_task_timer_in_conn(&conn, 1).expect("start first timer");
+        toggle_task_timer_in_conn(&conn, 2).expect("switch timer");
+
+        let recent = get_recent_timer_tasks_in_conn(&conn, 5).expect("recent tasks");
+        assert_eq!(recent[0].id, 2);
+        assert!(recent[0].timer_started_at.is_some());
+        let first_running: Option<String> = conn
+            .query_row(
+                "SELECT timer_started_at FROM tasks WHERE id = 1",
+                [],
+                |row| row.get(0),
+            )
+            .expect("first task");
+        assert!(first_running.is_none());
+
+        toggle_task_timer_in_conn(&conn, 2).expect("pause timer");
+        let running: i64 = conn
+            .query_row(
+                "SELECT COUNT(*) FROM tasks WHERE timer_started_at IS NOT NULL",
+                [],
+                |row| row.get(0),
+            )
+            .expect("running count");
+        assert_eq!(running, 0);
+    }
+
+    #[test]
+    fn toggle_active_timer_in_conn_pauses_running_then_resumes_last_task() {
+        let conn = command_test_connection();
+        let now = Utc::now().to_rfc3339();
+
+        assert!(matches!(
+            toggle_active_timer_in_conn(&conn).expect("empty toggle"),
+            TimerToggleOutcome::NoTask
+        ));
+
+        conn.execute(
+            "INSERT INTO tasks (title, description, status, priority, timer_started_at, timer_accumulated_seconds, created_at, updated_at)
+             VALUES ('Review PR', '', 'in_progress', 'medium', ?1, 120, ?1, ?1)",
+            params![now],
+        )
+        .expect("insert task");
+
+        assert!(matches!(
+            toggle_active_timer_in_conn(&conn).expect("pause"),
+            TimerToggleOutcome::Paused(title) if title == "Review PR"
+        ));
+        assert!(matches!(
+            toggle_active_timer_in_conn(&conn).expect("resume"),
+            TimerToggleOutcome::Started(title) if title == "Review PR"
+        ));
+
+        let accumulated: i64 = conn
+            .query_row(
+                "SELECT timer_accumulated_seconds FROM tasks WHERE timer_started_at IS NOT NULL",
+                [],
+                |row| row.get(0),
+            )
+            .expect("running task");
+        assert!(accumulated >= 120);
+    }
+
+    #[test]
+    fn long_running_timer_tasks_in_conn_flags_runs_past_break_threshold() {
+        let conn = command_test_connection();
+        let now = Utc::now();
+        let insert_running = |title: &str, started_minutes_ago: i64| {
+            let started_at = (now - Duration::minutes(started_minutes_ago)).to_rfc3339();
+            conn.execute(
+                "INSERT INTO tasks (title, description, status, priority, timer_started_at, created_at, updated_at)
+                 VALUES (?1, '', 'in_progress', 'medium', ?2, ?2, ?2)",
+                params![title, started_at],
+            )
+            .expect("insert task");
+        };
+
+        insert_running("Deep work", 120);
+        insert_running("Quick fix", 10);
+
+        let due = long_running_timer_tasks_in_conn(&conn, 90 * 60).expect("long running");
+        assert_eq!(due.len(), 1);
+        assert_eq!(due[0].title, "Deep work");
+
+        let paused = pause_all_timers_in_conn(&conn).expect("pause all");
+        assert_eq!(paused.len(), 2);
+        assert!(long_running_timer_tasks_in_conn(&conn, 0)
+            .expect("after pause")
+            .is_empty());
+
+        let accumulated: i64 = conn
+            .query_row(
+                "SELECT timer_accumulated_seconds FROM tasks WHERE title = 'Deep work'",
+                [],
+                |row| row.get(0),
+            )
+            .expect("deep work");
+        assert!(accumulated >= 120 * 60);
+    }
+
+    #[test]
+    fn entry_tags_are_case_insensitive_and_filter_entries() {
+        let conn = command_test_connection();
+        for date in ["2026-05-04", "2026-05-05"] {
+            conn.execute(
+                "INSERT INTO entries (date, yesterday, today, created_at) VALUES (?1, '', '', ?1)",
+                params![date],
+            )
+            .expect("insert entry");
+        }
+        let first_id: i64 = conn
+            .query_row(
+                "SELECT id FROM entries WHERE date = '2026-05-04'",
+                [],
+                |row| row.get(0),
+            )
+            .expect("first entry");
+
+        add_entry_tag_in_conn(&conn, first_id, " #Payments ").expect("add tag");
+        add_entry_tag_in_conn(&conn, first_id, "payments").expect("re-add tag");
+        assert!(add_entry_tag_in_conn(&conn, first_id, "  ").is_err());
+        assert!(add_entry_tag_in_conn(&conn, 9999, "payments").is_err());
+
+        let tag_count: i64 = conn
+            .query_row("SELECT COUNT(*) FROM entry_tags", [], |row| row.get(0))
+            .expect("tag count");
+        assert_eq!(tag_count, 1);
+
+        let tagged = get_entries_by_tag_in_conn(&conn, "PAYMENTS").expect("by tag");
+        assert_eq!(tagged.len(), 1);
+        assert_eq!(tagged[0].date, "2026-05-04");
+
+        conn.execute("DELETE FROM entries WHERE id = ?1", params![first_id])
+            .expect("delete entry");
+        assert!(get_entries_by_tag_in_conn(&conn, "payments")
+            .expect("after delete")
+            .is_empty());
+    }
+
+    #[test]
+    fn complete_due_countdowns_in_conn_finishes_only_elapsed_countdowns() {
+        let conn = command_test_connection();
+        let started = Utc::now();
+
+        let triage =
+            start_countdown_in_conn(&conn, " Inbox triage ", 15, started).expect("start triage");
+        assert_eq!(triage.label, "Inbox triage");
+        start_countdown_in_conn(&conn, "", 45, started).expect("start timebox");
+
+        let completed = complete_due_countdowns_in_conn(&conn, started + Duration::minutes(10))
+            .expect("nothing due");
+        assert!(completed.is_empty());
+
+        let completed = complete_due_countdowns_in_conn(&conn, started + Duration::minutes(16))
+            .expect("triage due");
+        assert_eq!(completed.len(), 1);
+        assert_eq!(completed[0].id, triage.id);
+        assert!(completed[0].completed_at.is_some());
+
+        let active = get_active_countdowns_in_conn(&conn).expect("active");
+        assert_eq!(active.len(), 1);
+        assert_eq!(active[0].label, "Timebox");
+        assert_eq!(active[0].duration_minutes, 45);
+    }
+
+    #[test]
+    fn entry_sections_follow_template_order_and_drop_blank_content() {
+        let conn = command_test_connection();
+        conn.execute(
+            "INSERT INTO entries (date, yesterday, today, created_at) VALUES ('2026-05-04', '', '', '2026-05-04')",
+            [],
+        )
+        .expect("insert entry");
+
+        let template = |label: &str| EntrySectionTemplateInput {
+            section_key: None,
+            label: label.to_string(),
+        };
+        assert!(save_entry_section_templates_in_conn(&conn, vec![template("Today")]).is_err());
+        assert!(save_entry_section_templates_in_conn(
+            &conn,
+            vec![template("Blockers"), template("blockers")]
+        )
+        .is_err());
+
+        let templates = save_entry_section_templates_in_conn(
+            &conn,
+            vec![template("Wins & Learnings"), template("Blockers")],
+        )
+        .expect("save templates");
+        assert_eq!(templates[0].section_key, "wins_learnings");
+        assert_eq!(templates[1].section_key, "blockers");
+
+        let section = |key: &str, content: &str| EntrySectionInput {
+            section_key: key.to_string(),
+            content: content.to_string(),
+        };
+        let sections = save_entry_sections_in_conn(
+            &conn,
+            "2026-05-04",
+            vec![
+                section("blockers", "Waiting on API keys"),
+                section("wins_learnings", "Shipped the importer"),
+            ],
+        )
+        .expect("save sections");
+        assert_eq!(sections.len(), 2);
+        assert_eq!(sections[0].section_key, "wins_learnings");
+
+        let sections =
+            save_entry_sections_in_conn(&conn, "2026-05-04", vec![section("blockers", "  ")])
+                .expect("clear blockers");
+        assert_eq!(sections.len(), 1);
+        assert_eq!(sections[0].content, "Shipped the importer");
+
+        assert!(save_entry_sections_in_conn(&conn, "2026-05-05", vec![]).is_err());
+    }
+
+    #[test]
+    fn daily_prompt_in_conn_rotates_deterministically_through_enabled_prompts() {
+        let conn = command_test_connection();
+        conn.execute("UPDATE journal_prompts SET enabled = 0", [])
+            .expect("disable built-ins");
+        assert!(daily_prompt_in_conn(&conn, "2026-05-04")
+            .expect("no prompts")
+            .is_none());
+
+        add_journal_prompt_in_conn(&conn, " What blocked you? ").expect("add first");
+        add_journal_prompt_in_conn(&conn, "What went well?").expect("add second");
+
+        let monday = daily_prompt_in_conn(&conn, "2026-05-04")
+            .expect("monday")
+            .expect("monday prompt");
+        let tuesday = daily_prompt_in_conn(&conn, "2026-05-05")
+            .expect("tuesday")
+            .expect("tuesday prompt");
+        let wednesday = daily_prompt_in_conn(&conn, "2026-05-06")
+            .expect("wednesday")
+            .expect("wednesday prompt");
+        assert_ne!(monday.text, tuesday.text);
+        assert_eq!(monday.text, wednesday.text);
+        assert_eq!(
+            daily_prompt_in_conn(&conn, "2026-05-04")
+                .expect("repeat")
+                .expect("repeat prompt")
+                .id,
+            monday.id
+        );
+        assert!(daily_prompt_in_conn(&conn, "not-a-date").is_err());
+    }
+
+    #[test]
+    fn writing_stats_in_conn_counts_words_per_day_and_weekday() {
+        let conn = command_test_connection();
+        let empty = writing_stats_in_conn(&conn, None, &JournalCipher::default()).expect("stats");
+        assert_eq!(empty.entry_count, 0);
+        assert_eq!(empty.average_words_per_entry, 0.0);
+        assert_eq!(empty.most_productive_weekday, None);
+
+        conn.execute_batch(
+            "INSERT INTO entries (date, yesterday, today, created_at, deleted_at) VALUES
+                ('2026-05-04', 'Fixed the  flaky\ntest', 'Ship it', '2026-05-04', NULL),
+                ('2026-05-05', 'One', '', '2026-05-05', NULL),
+                ('2026-05-11', 'Two words', 'and three more', '2026-05-11', NULL),
+                ('2026-05-12', 'Trashed entry with many words', '', '2026-05-12', '2026-05-12');",
+        )
+        .expect("seed");
+
+        let stats = writing_stats_in_conn(&conn, None, &JournalCipher::default()).expect("stats");
+        assert_eq!(
+            stats.days,
+            vec![
+                DailyWordCount {
+                    date: "2026-05-04".to_string(),
+                    words: 6
+                },
+                DailyWordCount {
+                    date: "2026-05-05".to_string(),
+                    words: 1
+                },
+                DailyWordCount {
+                    date: "2026-05-11".to_string(),
+                    words: 5
+                },
+            ]
+        );
+        assert_eq!(stats.total_words, 12);
+        assert_eq!(stats.average_words_per_entry, 4.0);
+        assert_eq!(stats.most_productive_weekday.as_deref(), Some("Monday"));
+
+        let range = EntryDateRange {
+            start: "2026-05-05".to_string(),
+            end: "2026-05-10".to_string(),
+        };
+        let stats =
+            writing_stats_in_conn(&conn, Some(&range), &JournalCipher::default()).expect("stats");
+        assert_eq!(stats.entry_count, 1);
+        assert_eq!(stats.most_productive_weekday.as_deref(), Some("Tuesday"));
+    }
+
+    #[test]
+    fn mood_correlations_in_conn_ranks_factors_by_strength() {
+        let conn = command_test_connection();
+        let now = Utc::now().to_rfc3339();
+        let days = ["2026-05-04", "2026-05-05", "2026-05-06", "2026-05-07"];
+        for (index, day) in days.iter().enumerate() {
+            conn.execute(
+                "INSERT INTO daily_moods (date, mood, updated_at) VALUES (?1, ?2, ?3)",
+                params![day, index as i64 + 2, now],
+            )
+            .expect("insert mood");
+            // Meetings fall as mood rises; completed tasks rise with it.
+            for _ in 0..(3 - index) {
+                conn.execute(
+                    "INSERT INTO meetings (title, start_at, end_at, status, created_at, updated_at)
+                     VALUES ('Sync', ?1, ?1, 'done', ?2, ?2)",
+                    params![format!("{}T10:00", day), now],
+                )
+                .expect("insert meeting");
+            }
+            for _ in 0..index {
+                conn.execute(
+                    "INSERT INTO tasks (title, description, status, priority, completed_at, created_at, updated_at)
+                     VALUES ('Ship', '', 'done', 'medium', ?1, ?2, ?2)",
+                    params![format!("{}T12:00:00+00:00", day), now],
+                )
+                .expect("insert task");
+            }
+        }
+
+        let commits = HashMap::from([("2026-05-05".to_string(), 2.0)]);
+        let correlations = mood_correlations_in_conn(
+            &conn,
+            NaiveDate::from_ymd_opt(2026, 5, 4).expect("start"),
+            NaiveDate::from_ymd_opt(2026, 5, 7).expect("end"),
+            &commits,
+        )
+        .expect("correlations");
+
+        assert_eq!(correlations.len(), 5);
+        assert_eq!(correlations[0].sample_days, 4);
+        let by_factor = |factor: &str| {
+            correlations
+                .iter()
+                .find(|correlation| correlation.factor == factor)
+                .expect("factor")
+                .correlation
+        };
+        assert_eq!(by_factor("tasks_completed"), 1.0);
+        assert_eq!(by_factor("meetings"), -1.0);
+        assert_eq!(by_factor("deep_work_minutes"), 0.0);
+        assert_eq!(by_factor("on_call"), 0.0);
+        assert_eq!(correlations[3].factor, "deep_work_minutes");
+        assert!(pearson_correlation(&[1.0, 1.0], &[2.0, 3.0]).is_none());
+    }
+
+    #[test]
+    fn meeting_load_merges_overlaps_expands_recurrences_and_flags_heavy_days() {
+        let conn = command_test_connection();
+        let now = Utc::now().to_rfc3339();
+        let at = |day: &str, time: &str| {
+            chrono::NaiveDateTime::parse_from_str(&format!("{} {}", day, time), "%Y-%m-%d %H:%M")
+                .expect("datetime")
+                .and_local_timezone(chrono::Local)
+                .earliest()
+                .expect("local time")
+                .to_rfc3339()
+        };
+        let insert_meeting = |start: String, end: String, recurrence: &str, status: &str| {
+            conn.execute(
+                "INSERT INTO meetings (title, start_at, end_at, recurrence, recurrence_until, status, created_at, updated_at)
+                 VALUES ('Sync', ?1, ?2, ?3, '2026-05-06', ?4, ?5, ?5)",
+                params![start, end, recurrence, status, now],
+            )
+            .expect("insert meeting");
+        };
+        // Monday: 9-11 and an overlapping 10-12:30 count as 3.5h; daily standup adds 0.5h.
+        insert_meeting(
+            at("2026-05-04", "09:00"),
+            at("2026-05-04", "11:00"),
+            "none",
+            "planned",
+        );
+        insert_meeting(
+            at("2026-05-04", "10:00"),
+            at("2026-05-04", "12:30"),
+            "none",
+            "planned",
+        );
+        insert_meeting(
+            at("2026-05-04", "14:00"),
+            at("2026-05-04", "15:00"),
+            "none",
+            "cancelled",
+        );
+        insert_meeting(
+            at("2026-05-04", "16:00"),
+            at("2026-05-04", "16:30"),
+            "daily",
+            "planned",
+        );
+        conn.execute(
+            "INSERT INTO pomodoro_sessions (date, kind, duration_minutes, completed_at)
+             VALUES ('2026-05-05', 'focus', 50, ?1), ('2026-05-05', 'focus', 40, ?1)",
+            params![now],
+        )
+        .expect("insert focus");
+
+        let load = meeting_load_in_conn(
+            &conn,
+            NaiveDate::from_ymd_opt(2026, 5, 4).expect("start"),
+            NaiveDate::from_ymd_opt(2026, 5, 7).expect("end"),
+        )
+        .expect("load");
+        let hours: Vec<(f64, f64, bool)> = load
+            .iter()
+            .map(|day| (day.meeting_hours, day.deep_work_hours, day.meeting_heavy))
+            .collect();
+        assert_eq!(
+            hours,
+            vec![
+                (4.0, 0.0, true),
+                (0.5, 1.5, false),
+                (0.5, 0.0, false),
+                (0.0, 0.0, false)
+            ]
+        );
+
+        let review = generate_weekly_review_in_conn(&conn, "2026-05-04", &JournalCipher::default())
+            .expect("review");
+        assert!(review.contains(
+            "## Time\n\n- Meetings: 5h\n- Deep work: 1.5h\n- Meeting-heavy days: Monday\n"
+        ));
+    }
+
+    #[test]
+    fn context_switches_count_task_changes_per_day_and_feed_the_review() {
+        let conn = command_test_connection();
+        let now = Utc::now().to_rfc3339();
+        let at = |day: &str, time: &str| {
+            chrono::NaiveDateTime::parse_from_str(&format!("{} {}", day, time), "%Y-%m-%d %H:%M")
+                .expect("datetime")
+                .and_local_timezone(chrono::Local)
+                .earliest()
+                .expect("local time")
+                .with_timezone(&Utc)
+                .to_rfc3339()
+        };
+        for title in ["Review PR", "Fix build"] {
+            conn.execute(
+                "INSERT INTO tasks (title, description, status, priority, created_at, updated_at)
+                 VALUES (?1, '', 'todo', 'medium', ?2, ?2)",
+                params![title, now],
+            )
+            .expect("insert task");
+        }
+        // Monday: A, B, A, A is two switches over 3.5 tracked hours.
+        for (task_id, day, start, end) in [
+            (1, "2026-05-04", "09:00", "10:00"),
+            (2, "2026-05-04", "10:00", "10:30"),
+            (1, "2026-05-04", "10:30", "11:30"),
+            (1, "2026-05-04", "13:00", "14:00"),
+            (2, "2026-05-05", "09:00", "10:00"),
+        ] {
+            conn.execute(
+                "INSERT INTO task_time_entries (task_id, started_at, ended_at) VALUES (?1, ?2, ?3)",
+                params![task_id, at(day, start), at(day, end)],
+            )
+            .expect("insert time entry");
+        }
+
+        let days = context_switches_in_conn(
+            &conn,
+            NaiveDate::from_ymd_opt(2026, 5, 4).expect("start"),
+            NaiveDate::from_ymd_opt(2026, 5, 6).expect("end"),
+        )
+        .expect("context switches");
+        let summary: Vec<(i64, i64, i64, f64)> = days
+            .iter()
+            .map(|day| {
+                (
+                    day.switches,
+                    day.tasks_touched,
+                    day.tracked_minutes,
+                    day.switches_per_hour,
+                )
+            })
+            .collect();
+        assert_eq!(
+            summary,
+            vec![(2, 2, 210, 0.6), (0, 1, 60, 0.0), (0, 0, 0, 0.0)]
+        );
+
+        let review = generate_weekly_review_in_conn(&conn, "2026-05-04", &JournalCipher::default())
+            .expect("review");
+        assert!(review.contains("- Context switches: 2 (most fragmented: Monday, 2)\n"));
+
+        toggle_task_timer_in_conn(&conn, 1).expect("start timer");
+        toggle_task_timer_in_conn(&conn, 2).expect("switch timer");
+        let recorded: i64 = conn
+            .query_row(
+                "SELECT COUNT(*) FROM task_time_entries WHERE task_id = 1 AND started_at > ?1",
+                params![at("2026-05-05", "23:59")],
+                |row| row.get(0),
+            )
+            .expect("recorded runs");
+        assert_eq!(recorded, 1);
+    }
+
+    #[test]
+    fn search_entries_in_conn_ranks_fts_matches_and_tracks_updates() {
+        let conn = command_test_connection();
+        let insert_entry = |date: &str, yesterday: &str, today: &str| {
+            conn.execute(
+                "INSERT INTO entries (date, yesterday, today, created_at) VALUES (?1, ?2, ?3, ?1)",
+                params![date, yesterday, today],
+            )
+            .expect("insert entry");
+        };
+        insert_entry(
+            "2026-05-04",
+            "Fixed the payments webhook",
+            "Deploy payments service",
+        );
+        insert_entry("2026-05-05", "Reviewed PRs", "Payments retro");
+        insert_entry("2026-05-06", "Onboarding docs", "Pairing session");
+
+        assert_eq!(
+            fts_query_from_text("pay OR \"x"),
+            Some("\"pay\" \"OR\" \"x\"*".to_string())
+        );
+        assert!(fts_query_from_text("  !! ").is_none());
+
+        let results = search_entries_in_conn(&conn, "payment").expect("prefix search");
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].entry.date, "2026-05-04");
+        assert!(results[0].snippet.contains("<mark>"));
+
+        conn.execute(
+            "UPDATE entries SET today = 'Payments follow-up' WHERE date = '2026-05-06'",
+            [],
+        )
+        .expect("update entry");
+        conn.execute("DELETE FROM entries WHERE date = '2026-05-05'", [])
+            .expect("delete entry");
+        let dates: Vec<String> = search_entries_in_conn(&conn, "payments")
+            .expect("after changes")
+            .into_iter()
+            .map(|result| result.entry.date)
+            .collect();
+        assert_eq!(dates.len(), 2);
+        assert!(dates.contains(&"2026-05-06".to_string()));
+        assert!(!dates.contains(&"2026-05-05".to_string()));
+    }
+
+    #[test]
+    fn log_habits_bulk_in_conn_logs_all_or_nothing() {
+        let mut conn = command_test_connection();
+        for title in ["Stretch", "Read"] {
+            conn.execute(
+                "INSERT INTO habits (title, description, target_per_week, color, created_at, updated_at)
+                 VALUES (?1, '', 7, '#fff', '2026-05-01', '2026-05-01')",
+                params![title],
+            )
+            .expect("insert habit");
+        }
+        let log_count = |conn: &Connection| -> i64 {
+            conn.query_row("SELECT COUNT(*) FROM habit_logs", [], |row| row.get(0))
+                .expect("count logs")
+        };
+
+        let error = log_habits_bulk_in_conn(&mut conn, "2026-05-04".to_string(), &[1, 99])
+            .expect_err("unknown habit");
+        assert!(error.contains("99"));
+        assert_eq!(log_count(&conn), 0);
+
+        let logged = log_habits_bulk_in_conn(&mut conn, "2026-05-04".to_string(), &[2, 1, 2])
+            .expect("bulk log");
+        assert_eq!(logged, vec![2, 1]);
+        assert_eq!(log_count(&conn), 2);
+
+        log_habits_bulk_in_conn(&mut conn, "2026-05-04".to_string(), &[1]).expect("relog");
+        assert_eq!(log_count(&conn), 2);
+    }
+
+    #[test]
+    fn get_entries_between_in_conn_is_inclusive_and_validates_range() {
+        let conn = command_test_connection();
+        for date in [
+            "2026-04-30",
+            "2026-05-01",
+            "2026-05-15",
+            "2026-05-31",
+            "2026-06-01",
+        ] {
+            conn.execute(
+                "INSERT INTO entries (date, yesterday, today, created_at) VALUES (?1, '', '', ?1)",
+                params![date],
+            )
+            .expect("insert entry");
+        }
+
+        let dates: Vec<String> = get_entries_between_in_conn(&conn, "2026-05-01", "2026-05-31")
+            .expect("month range")
+            .into_iter()
+            .map(|entry| entry.date)
+            .collect();
+        assert_eq!(dates, vec!["2026-05-01", "2026-05-15", "2026-05-31"]);
+
+        assert!(get_entries_between_in_conn(&conn, "2026-05-31", "2026-05-01").is_err());
+        assert!(get_entries_between_in_conn(&conn, "May 1", "2026-05-31").is_err());
+    }
+
+    #[test]
+    fn streaks_at_risk_in_conn_flags_unlogged_habits_with_long_streaks() {
+        let conn = command_test_connection();
+        for title in ["Stretch", "Read", "Run"] {
+            conn.execute(
+                "INSERT INTO habits (title, description, target_per_week, color, created_at, updated_at)
+                 VALUES (?1, '', 7, '#fff', '2026-05-01', '2026-05-01')",
+                params![title],
+            )
+            .expect("insert habit");
+        }
+        let log = |habit_id: i64, date: &str| {
+            conn.execute(
+                "INSERT INTO habit_logs (habit_id, date, created_at) VALUES (?1, ?2, ?2)",
+                params![habit_id, date],
+            )
+            .expect("insert log");
+        };
+        // Stretch: 4-day streak, not logged today. Read: logged today. Run: 2-day streak.
+        for date in ["2026-05-06", "2026-05-07", "2026-05-08", "2026-05-09"] {
+            log(1, date);
+            log(2, date);
+        }
+        log(2, "2026-05-10");
+        log(3, "2026-05-08");
+        log(3, "2026-05-09");
+
+        let today = NaiveDate::from_ymd_opt(2026, 5, 10).expect("date");
+        let at_risk = streaks_at_risk_in_conn(&conn, today, 3).expect("at risk");
+        assert_eq!(at_risk.len(), 1);
+        assert_eq!(at_risk[0].title, "Stretch");
+        assert_eq!(at_risk[0].streak_days, 4);
+        assert_eq!(
+            streaks_at_risk_in_conn(&conn, today, 2)
+                .expect("lower")
+                .len(),
+            2
+        );
+
+        let afternoon = today.and_hms_opt(15, 0, 0).expect("time");
+        let evening = today.and_hms_opt(20, 30, 0).expect("time");
+        assert!(streak_risk_reminders_due_in_conn(&conn, afternoon)
+            .expect("afternoon")
+            .is_empty());
+        assert_eq!(
+            streak_risk_reminders_due_in_conn(&conn, evening)
+                .expect("evening")
+                .len(),
+            1
+        );
+    }
+
+    #[test]
+    fn journal_reminder_fires_after_the_reminder_time_until_the_day_has_text() {
+        let conn = command_test_connection();
+        let day = NaiveDate::from_ymd_opt(2026, 5, 8).expect("date");
+        let due = |hour: u32| {
+            journal_reminder_due_in_conn(&conn, day.and_hms_opt(hour, 15, 0).expect("time"))
+                .expect("due")
+        };
+
+        // Default reminder time is 18:00.
+        assert!(!due(17));
+        assert!(due(18));
+
+        conn.execute(
+            "INSERT INTO entries (date, yesterday, today, created_at)
+             VALUES ('2026-05-08', '', '  ', '2026-05-08T09:00:00Z')",
+            [],
+        )
+        .expect("empty entry");
+        assert!(due(19));
+
+        conn.execute(
+            "UPDATE entries SET blockers = 'Waiting on review' WHERE date = '2026-05-08'",
+            [],
+        )
+        .expect("fill entry");
+        assert!(!due(19));
+
+        conn.execute("DELETE FROM entries", []).expect("clear");
+        settings::write_setting(&conn, "journal_reminder_time", "").expect("disable");
+        assert!(!due(23));
+    }
+
+    #[test]
+    fn goals_needing_checkin_skip_recent_updates_and_wait_for_the_checkin_day() {
+        let conn = command_test_connection();
+        let days_ago = |days: i64| (Utc::now() - chrono::Duration::days(days)).to_rfc3339();
+        for (title, status, updated_at) in [
+            ("Ship v2", "active", days_ago(10)),
+            ("Learn Rust", "active", days_ago(2)),
+            ("Run a marathon", "active", days_ago(20)),
+            ("Write a book", "paused", days_ago(30)),
+        ] {
+            conn.execute(
+                "INSERT INTO goals (title, status, progress, created_at, updated_at)
+                 VALUES (?1, ?2, 30, ?3, ?3)",
+                params![title, status, updated_at],
+            )
+            .expect("insert goal");
+        }
+        // A milestone ticked off yesterday counts as an update for the marathon goal.
+        conn.execute(
+            "INSERT INTO goal_milestones (goal_id, title, completed, created_at, updated_at)
+             VALUES (3, '10k', 1, ?1, ?1)",
+            params![days_ago(1)],
+        )
+        .expect("insert milestone");
+
+        let stale = goals_needing_checkin_in_conn(&conn, Utc::now()).expect("stale goals");
+        assert_eq!(
+            stale
+                .iter()
+                .map(|goal| (goal.title.as_str(), goal.days_since_update))
+                .collect::<Vec<_>>(),
+            vec![("Ship v2", 10)]
+        );
+
+        // Defaults: Friday at 16:00. 2026-05-08 is a Friday.
+        let friday = NaiveDate::from_ymd_opt(2026, 5, 8).expect("date");
+        let due = |date: NaiveDate, hour: u32| {
+            goal_checkin_due_in_conn(&conn, date.and_hms_opt(hour, 30, 0).expect("time"))
+                .expect("due")
+                .len()
+        };
+        assert_eq!(due(friday, 15), 0);
+        assert_eq!(due(friday, 16), 1);
+        assert_eq!(due(friday - chrono::Duration::days(1), 17), 0);
+
+        check_in_goal_in_conn(&conn, 1, 140).expect("check in");
+        let progress: i64 = conn
+            .query_row("SELECT progress FROM goals WHERE id = 1", [], |row| {
+                row.get(0)
+            })
+            .expect("progress");
+        assert_eq!(progress, 100);
+        assert!(goals_needing_checkin_in_conn(&conn, Utc::now())
+            .expect("after check-in")
+            .is_empty());
+        assert!(check_in_goal_in_conn(&conn, 99, 10).is_err());
+    }
+
+    #[test]
+    fn get_entries_in_conn_pages_newest_first() {
+        let conn = command_test_connection();
+        for day in 1..=5 {
+            conn.execute(
+                "INSERT INTO entries (date, yesterday, today, created_at) VALUES (?1, '', '', ?1)",
+                params![format!("2026-05-0{}", day)],
+            )
+            .expect("insert entry");
+        }
+        let dates = |limit: Option<i64>, offset: Option<i64>| -> Vec<String> {
+            get_entries_in_conn(&conn, limit, offset)
+                .expect("entries page")
+                .into_iter()
+                .map(|entry| entry.date)
+                .collect()
+        };
+
+        assert_eq!(dates(None, None).len(), 5);
+        assert_eq!(dates(Some(2), None), vec!["2026-05-05", "2026-05-04"]);
+        assert_eq!(dates(Some(2), Some(4)), vec!["2026-05-01"]);
+        assert!(dates(Some(2), Some(10)).is_empty());
+        assert_eq!(dates(None, Some(3)), vec!["2026-05-02", "2026-05-01"]);
+    }
+
+    #[test]
+    fn evaluate_achievements_in_conn_unlocks_each_milestone_once() {
+        let conn = command_test_connection();
+        assert!(evaluate_achievements_in_conn(&conn)
+            .expect("empty evaluation")
+            .is_empty());
+
+        conn.execute(
+            "INSERT INTO entries (date, yesterday, today, created_at) VALUES ('2026-05-01', '', '', '2026-05-01')",
+            [],
+        )
+        .expect("insert entry");
+        conn.execute(
+            "INSERT INTO habits (title, description, target_per_week, color, created_at, updated_at)
+             VALUES ('Read', '', 7, '#fff', '2026-05-01', '2026-05-01')",
+            [],
+        )
+        .expect("insert habit");
+        // A 7-day run split by a gap from an older 3-day run.
+        for day in [1, 2, 3, 10, 11, 12, 13, 14, 15, 16] {
+            conn.execute(
+                "INSERT INTO habit_logs (habit_id, date, created_at) VALUES (1, ?1, ?1)",
+                params![format!("2026-05-{:02}", day)],
+            )
+            .expect("insert log");
+        }
+
+        let unlocked: Vec<String> = evaluate_achievements_in_conn(&conn)
+            .expect("evaluate")
+            .into_iter()
+            .map(|achievement| achievement.key)
+            .collect();
+        assert_eq!(unlocked, vec!["entries_1", "habit_streak_7"]);
+        assert!(evaluate_achievements_in_conn(&conn)
+            .expect("re-evaluate")
+            .is_empty());
+
+        let achievements = get_achievements_in_conn(&conn).expect("achievements");
+        let streak_30 = achievements
+            .iter()
+            .find(|achievement| achievement.key == "habit_streak_30")
+            .expect("streak achievement");
+        assert_eq!(streak_30.progress, 7);
+        assert!(streak_30.unlocked_at.is_none());
+        assert_eq!(
+            achievements
+                .iter()
+                .filter(|achievement| achievement.unlocked_at.is_some())
+                .count(),
+            2
+        );
+    }
+
+    #[test]
+    fn export_entries_markdown_in_conn_writes_frontmatter_files() {
+        let conn = command_test_connection();
+        conn.execute(
+            "INSERT INTO projects (name, description, color, status, created_at, updated_at)
+             VALUES ('Core \"API\"', '', '#fff', 'active', '2026-05-01', '2026-05-01')",
+            [],
+        )
+        .expect("insert project");
+        for (date, project_id) in [("2026-05-01", Some(1)), ("2026-05-02", None)] {
+            conn.execute(
+                "INSERT INTO entries (date, yesterday, today, project_id, created_at)
+                 VALUES (?1, 'Fixed bug', 'Ship it', ?2, ?1)",
+                params![date, project_id],
+            )
+            .expect("insert entry");
+        }
+        add_entry_tag_in_conn(&conn, 1, "release").expect("tag");
+        conn.execute(
+            "INSERT INTO daily_moods (date, mood, updated_at) VALUES ('2026-05-01', 4, '2026-05-01')",
+            [],
+        )
+        .expect("mood");
+
+        let dir = std::env::temp_dir().join(format!(
+            "dev-journal-md-export-{}",
+            Utc::now().timestamp_nanos_opt().unwrap_or_default()
+        ));
+        let range = EntryDateRange {
+            start: "2026-05-01".to_string(),
+            end: "2026-05-01".to_string(),
+        };
+        let summary =
+            export_entries_markdown_in_conn(&conn, &dir, Some(&range), &JournalCipher::default())
+                .expect("export");
+        assert_eq!(summary.files_written, 1);
+        assert!(!dir.join("2026-05-02.md").exists());
+
+        let markdown = fs::read_to_string(dir.join("2026-05-01.md")).expect("read export");
+        assert!(markdown.starts_with("---\ndate: 2026-05-01\n"));
+        assert!(markdown.contains("project: \"Core \\\"API\\\"\"\n"));
+        assert!(markdown.contains("tags: [\"release\"]\n"));
+        assert!(markdown.contains("mood: 4\n"));
+        assert!(markdown.contains("## Yesterday\n\nFixed bug\n"));
+
+        let summary = export_entries_markdown_in_conn(&conn, &dir, None, &JournalCipher::default())
+            .expect("export all");
+        assert_eq!(summary.files_written, 2);
+        fs::remove_dir_all(&dir).expect("cleanup");
+    }
+
+    #[test]
+    fn import_markdown_folder_round_trips_exports_and_reads_obsidian_notes() {
+        let mut conn = command_test_connection();
+        conn.execute(
+            "INSERT INTO projects (name, description, color, status, created_at, updated_at)
+             VALUES ('Core', '', '#fff', 'active', '2026-05-01', '2026-05-01')",
+            [],
+        )
+        .expect("insert project");
+        conn.execute(
+            "INSERT INTO entries (date, yesterday, today, created_at)
+             VALUES ('2026-05-03', 'Kept', 'Kept', '2026-05-03')",
+            [],
+        )
+        .expect("insert entry");
+
+        let dir = std::env::temp_dir().join(format!(
+            "dev-journal-md-import-{}",
+            Utc::now().timestamp_nanos_opt().unwrap_or_default()
+        ));
+        fs::create_dir_all(dir.join("2026/05")).expect("create dirs");
+        fs::write(
+            dir.join("2026-05-01.md"),
+            "---\ndate: 2026-05-01\ncreated_at: \"2026-05-01T09:00:00Z\"\nproject: \"core\"\n\
+             tags: [\"release\", \"on \\\"call\\\"\"]\nmood: 4\n---\n\n# 2026-05-01\n\n\
+             ## Yesterday\n\nFixed bug\n\n## Today\n\nShip it\n\n## Blockers\n\nCI\n",
+        )
+        .expect("write export");
+        fs::write(
+            dir.join("2026/05/2026-05-02.md"),
+            "---\naliases: []\ntags:\n  - daily\n  - '1:1'\n---\nMet with Sam.\n\n- [ ] follow up\n",
+        )
+        .expect("write obsidian note");
+        fs::write(dir.join("2026-05-03.md"), "Overwritten?").expect("write existing");
+        fs::write(dir.join("notes.md"), "Not a daily note").expect("write other");
+
+        let (summary, discarded) =
+            import_markdown_folder_in_conn(&mut conn, &dir, &JournalCipher::default())
+                .expect("import");
+        fs::remove_dir_all(&dir).expect("cleanup");
+        assert_eq!(summary.files_read, 3);
+        assert_eq!(summary.entries_imported, 2);
+        assert_eq!(summary.skipped_existing, 1);
+        assert!(discarded.is_empty());
+
+        let first = entries::get_entry_in_conn(&conn, "2026-05-01")
+            .expect("load")
+            .expect("entry");
+        assert_eq!(first.yesterday, "Fixed bug");
+        assert_eq!(first.today, "Ship it");
+        assert_eq!(first.blockers, "CI");
+        assert_eq!(first.project_id, Some(1));
+        assert_eq!(first.created_at, "2026-05-01T09:00:00Z");
+        let tags: Vec<String> = get_entries_by_tag_in_conn(&conn, "on \"call\"")
+            .expect("by tag")
+            .into_iter()
+            .map(|entry| entry.date)
+            .collect();
+        assert_eq!(tags, vec!["2026-05-01"]);
+        let mood: i64 = conn
+            .query_row(
+                "SELECT mood FROM daily_moods WHERE date = '2026-05-01'",
+                [],
+                |row| row.get(0),
+            )
+            .expect("mood");
+        assert_eq!(mood, 4);
+
+        let second = entries::get_entry_in_conn(&conn, "2026-05-02")
+            .expect("load")
+            .expect("entry");
+        assert_eq!(second.yesterday, "");
+        assert_eq!(second.today, "Met with Sam.\n\n- [ ] follow up");
+        assert_eq!(
+            get_entries_by_tag_in_conn(&conn, "1:1")
+                .expect("by tag")
+                .len(),
+            1
+        );
+        assert_eq!(
+            entries::get_entry_in_conn(&conn, "2026-05-03")
+                .expect("load")
+                .expect("entry")
+                .today,
+            "Kept"
+        );
+        assert_eq!(
+            parse_markdown_note("# 2026-05-04\n\n## Today\n\nPlan", "2026-05-04").today,
+            "Plan"
+        );
+    }
+
+    #[test]
+    fn goal_reflections_outlive_goals_and_match_similar_titles() {
+        let conn = command_test_connection();
+        for title in [
+            "Ship the billing API v2",
+            "Learn Rust async",
+            "Run a half marathon",
+        ] {
+            conn.execute(
+                "INSERT INTO goals (title, description, status, progress, created_at, updated_at)
+                 VALUES (?1, '', 'completed', 100, '2026-05-01', '2026-05-01')",
+                params![title],
+            )
+            .expect("insert goal");
+        }
+        assert!(save_goal_reflection_in_conn(&conn, 1, "  ", "").is_err());
+        assert!(save_goal_reflection_in_conn(&conn, 99, "Scope", "").is_err());
+        save_goal_reflection_in_conn(&conn, 1, "Small PRs", "Late API review")
+            .expect("billing reflection");
+        save_goal_reflection_in_conn(&conn, 2, "Daily katas", "").expect("rust reflection");
+        save_goal_reflection_in_conn(&conn, 3, "", "Started too fast").expect("run reflection");
+
+        assert!(goal_title_similarity("Ship the API", "ship api") > 0.99);
+        assert_eq!(goal_title_similarity("the and", "for"), 0.0);
+
+        conn.execute("DELETE FROM goals WHERE id = 1", [])
+            .expect("delete goal");
+        let similar = get_similar_goal_reflections_in_conn(&conn, "Ship the payments API", 3)
+            .expect("similar");
+        assert_eq!(similar.len(), 1);
+        assert_eq!(similar[0].goal_title, "Ship the billing API v2");
+        assert_eq!(similar[0].goal_id, None);
+        assert_eq!(similar[0].what_didnt, "Late API review");
+    }
+
+    #[test]
+    fn generate_weekly_review_in_conn_summarizes_only_the_requested_week() {
+        let conn = command_test_connection();
+        conn.execute_batch(
+            "INSERT INTO projects (id, name, description, color, status, created_at, updated_at)
+             VALUES (1, 'Billing', '', '#fff', 'active', '2026-05-01', '2026-05-01');
+             INSERT INTO entries (date, yesterday, today, created_at) VALUES
+                ('2026-05-04', 'Fixed invoices' || char(10) || 'Reviewed PRs', 'Ship refunds', '2026-05-04T09:00:00Z'),
+                ('2026-05-11', 'Next week', 'Out of range', '2026-05-11T09:00:00Z');
+             INSERT INTO tasks (title, description, status, priority, project_id, completed_at, created_at, updated_at) VALUES
+                ('Refund flow', '', 'done', 'medium', 1, '2026-05-06T15:00:00Z', '2026-05-01', '2026-05-06'),
+                ('Old task', '', 'done', 'medium', NULL, '2026-04-30T15:00:00Z', '2026-04-01', '2026-04-30'),
+                ('Open task', '', 'todo', 'medium', NULL, NULL, '2026-05-01', '2026-05-01');
+             INSERT INTO habits (id, title, description, target_per_week, color, created_at, updated_at)
+                VALUES (1, 'Run', '', 2, '#fff', '2026-05-01', '2026-05-01');
+             INSERT INTO habit_logs (habit_id, date, created_at) VALUES
+                (1, '2026-05-05', '2026-05-05'), (1, '2026-05-07', '2026-05-07'), (1, '2026-05-12', '2026-05-12');
+             INSERT INTO goals (id, title, description, status, progress, created_at, updated_at) VALUES
+                (1, 'Launch billing v2', '', 'active', 60, '2026-04-01', '2026-05-06'),
+                (2, 'Old archived goal', '', 'archived', 10, '2026-01-01', '2026-01-02');
+             INSERT INTO goal_milestones (goal_id, title, completed, position, created_at, updated_at)
+                VALUES (1, 'Beta', 1, 0, '2026-04-01', '2026-05-06T10:00:00Z');",
+        )
+        .expect("seed week");
+
+        assert!(generate_weekly_review_in_conn(&conn, "May 4", &JournalCipher::default()).is_err());
+        let review = generate_weekly_review_in_conn(&conn, "2026-05-04", &JournalCipher::default())
+            .expect("review");
+        assert!(review.starts_with("# Weekly review: May 4 – May 10, 2026\n"));
+        assert!(review.contains("### Monday, May 4\n\n- Done: Fixed invoices\n  Reviewed PRs\n- Planned: Ship refunds\n"));
+        assert!(!review.contains("Out of range"));
+        assert!(review.contains("- Refund flow (Billing)\n"));
+        assert!(!review.contains("Old task") && !review.contains("Open task"));
+        assert!(review.contains("- Run: 2/2 ✅\n"));
+        assert!(review.contains("- Launch billing v2: 60% (active), 1 milestone done this week\n"));
+        assert!(!review.contains("Old archived goal"));
+        assert!(!review.contains("## Incidents"));
+    }
+
+    #[test]
+    fn import_day_one_groups_by_day_appends_and_skips_repeats() {
+        let mut conn = command_test_connection();
+        conn.execute(
+            "INSERT INTO entries (date, yesterday, today, created_at)
+             VALUES ('2026-03-02', 'Planned', 'Existing note', '2026-03-02T08:00:00Z')",
+            [],
+        )
+        .expect("insert entry");
+        let export = || -> DayOneExport {
+            serde_json::from_value(serde_json::json!({
+                "metadata": { "version": "1.0" },
+                "entries": [
+                    {
+                        "creationDate": "2026-03-01T12:30:00Z",
+                        "text": "Second thought",
+                        "tags": ["Travel"]
+                    },
+                    {
+                        "creationDate": "2026-03-01T11:00:00Z",
+                        "text": "Flew to Lisbon\\.\n![](dayone-moment://ABC123)",
+                        "tags": ["travel", "#work"]
+                    },
+                    { "creationDate": "2026-03-02T12:00:00Z", "text": "Day One note" }
+                ]
+            }))
+            .expect("export")
+        };
+
+        let (summary, discarded) =
+            import_day_one_in_conn(&mut conn, export(), &JournalCipher::default()).expect("import");
+        assert_eq!(summary.entries_read, 3);
+        assert_eq!(summary.days_written, 2);
+        assert!(discarded.is_empty());
+
+        let first = entries::get_entry_in_conn(&conn, "2026-03-01")
+            .expect("load")
+            .expect("entry");
+        assert_eq!(first.today, "Flew to Lisbon.\n\nSecond thought");
+        assert_eq!(first.created_at, "2026-03-01T11:00:00Z");
+        let tagged: Vec<String> = get_entries_by_tag_in_conn(&conn, "travel")
+            .expect("by tag")
+            .into_iter()
+            .map(|entry| entry.date)
+            .collect();
+        assert_eq!(tagged, vec!["2026-03-01"]);
+        let second = entries::get_entry_in_conn(&conn, "2026-03-02")
+            .expect("load")
+            .expect("entry");
+        assert_eq!(second.yesterday, "Planned");
+        assert_eq!(second.today, "Existing note\n\nDay One note");
+
+        let (summary, _) = import_day_one_in_conn(&mut conn, export(), &JournalCipher::default())
+            .expect("reimport");
+        assert_eq!(summary.days_written, 0);
+    }
+
+    #[test]
+    fn oncall_shifts_import_from_ics_and_feed_mood_correlations() {
+        let conn = command_test_connection();
+        create_oncall_shift_in_conn(&conn, "2026-05-04", "2026-05-05", "Primary")
+            .expect("manual shift");
+        assert!(create_oncall_shift_in_conn(&conn, "2026-05-05", "2026-05-04", "").is_err());
+
+        let ics = "BEGIN:VCALENDAR\r\n\
+                   BEGIN:VEVENT\r\n\
+                   DTSTART;VALUE=DATE:20260510\r\n\
+                   DTEND;VALUE=DATE:20260512\r\n\
+                   SUMMARY:On-call\\, secondary\r\n\
+                   END:VEVENT\r\n\
+                   BEGIN:VEVENT\r\n\
+                   DTSTART:20260504T090000Z\r\n\
+                   DTEND:20260506T000000Z\r\n\
+                   SUMMARY:Already entered\r\n\
+                   END:VEVENT\r\n\
+                   END:VCALENDAR\r\n";
+        assert_eq!(import_oncall_ics_in_conn(&conn, ics).expect("import"), 1);
+        assert_eq!(import_oncall_ics_in_conn(&conn, ics).expect("reimport"), 0);
+        assert!(import_oncall_ics_in_conn(&conn, "not a calendar").is_err());
+
+        let start = NaiveDate::from_ymd_opt(2026, 5, 5).expect("start");
+        let end = NaiveDate::from_ymd_opt(2026, 5, 11).expect("end");
+        let days: Vec<String> = oncall_days_in_conn(&conn, start, end)
+            .expect("days")
+            .into_iter()
+            .collect();
+        assert_eq!(days, vec!["2026-05-05", "2026-05-10", "2026-05-11"]);
+
+        let now = Utc::now().to_rfc3339();
+        for (day, mood) in [("2026-05-05", 2), ("2026-05-06", 4), ("2026-05-07", 5)] {
+            conn.execute(
+                "INSERT INTO daily_moods (date, mood, updated_at) VALUES (?1, ?2, ?3)",
+                params![day, mood, now],
+            )
+            .expect("insert mood");
+        }
+        let correlations =
+            mood_correlations_in_conn(&conn, start, end, &HashMap::new()).expect("correlations");
+        let on_call = correlations
+            .iter()
+            .find(|correlation| correlation.factor == "on_call")
+            .expect("on_call factor");
+        assert!(on_call.correlation < -0.9);
+    }
+
+    #[test]
+    fn incidents_link_work_and_show_up_in_the_timeline_and_weekly_review() {
+        let conn = command_test_connection();
+        conn.execute_batch(
+            "INSERT INTO tasks (id, title, description, status, priority, created_at, updated_at, completed_at)
+             VALUES (1, 'Roll back deploy', '', 'done', 'high', '2026-05-05', '2026-05-05', '2026-05-05T10:20:00+00:00');
+             INSERT INTO pages (id, title, content, created_at, updated_at)
+             VALUES (1, 'Postmortem', '', '2026-05-05', '2026-05-05');",
+        )
+        .expect("seed");
+        let input = |ended_at: Option<&str>, severity: &str| IncidentInput {
+            started_at: "2026-05-05T09:30:00Z".to_string(),
+            ended_at: ended_at.map(str::to_string),
+            severity: Some(severity.to_string()),
+            summary: " Checkout 500s ".to_string(),
+            resolution: String::new(),
+            task_ids: vec![1, 99],
+            page_ids: vec![1, 99],
+        };
+
+        assert!(create_incident_in_conn(&conn, &input(None, "critical")).is_err());
+        assert!(
+            create_incident_in_conn(&conn, &input(Some("2026-05-05T09:00:00Z"), "sev1")).is_err()
+        );
+        let incident = create_incident_in_conn(&conn, &input(None, "SEV2")).expect("create");
+        assert_eq!(incident.summary, "Checkout 500s");
+        assert_eq!(incident.severity, "sev2");
+        assert_eq!(incident.task_ids, vec![1]);
+        assert_eq!(incident.page_ids, vec![1]);
+
+        let review = generate_weekly_review_in_conn(&conn, "2026-05-04", &JournalCipher::default())
+            .expect("review");
+        assert!(review.contains("## Incidents\n\n- SEV2 Checkout 500s (ongoing)\n"));
+
+        let resolved = update_incident_in_conn(
+            &conn,
+            incident.id,
+            &IncidentInput {
+                resolution: "Rolled back v2.3".to_string(),
+                ..input(Some("2026-05-05T11:45:00Z"), "sev2")
+            },
+        )
+        .expect("update");
+        assert_eq!(
+            resolved.ended_at.as_deref(),
+            Some("2026-05-05T11:45:00+00:00")
+        );
+        assert!(update_incident_in_conn(&conn, 999, &input(None, "sev2")).is_err());
+        let review = generate_weekly_review_in_conn(&conn, "2026-05-04", &JournalCipher::default())
+            .expect("review");
+        assert!(review.contains(
+            "- SEV2 Checkout 500s (resolved in 2h 15m)\n  - Resolution: Rolled back v2.3\n"
+        ));
+        assert!(
+            !generate_weekly_review_in_conn(&conn, "2026-05-11", &JournalCipher::default())
+                .expect("review")
+                .contains("## Incidents")
+        );
+
+        let timeline =
+            get_incident_timeline_in_conn(&conn, "2026-05-04", "2026-05-10").expect("timeline");
+        assert_eq!(
+            timeline
+                .iter()
+                .map(|event| event.kind.as_str())
+                .collect::<Vec<_>>(),
+            vec!["started", "task_completed", "resolved"]
+        );
+        assert_eq!(timeline[1].detail, "Roll back deploy");
+        assert_eq!(timeline[2].detail, "Rolled back v2.3");
+        assert!(get_incident_timeline_in_conn(&conn, "May 4", "2026-05-10").is_err());
+
+        conn.execute("DELETE FROM tasks WHERE id = 1", [])
+            .expect("delete task");
+        assert!(get_incidents_in_conn(&conn).expect("incidents")[0]
+            .task_ids
+            .is_empty());
+    }
+
+    #[test]
+    fn attachments_are_copied_listed_and_removed_with_their_files() {
+        let conn = command_test_connection();
+        let temp_dir = std::env::temp_dir().join(format!(
+            "dev-journal-attachments-test-{}",
+            Utc::now().timestamp_nanos_opt().unwrap_or_default()
+        ));
+        let root = temp_dir.join("attachments");
+        fs::create_dir_all(&temp_dir).expect("temp dir");
+        let source = temp_dir.join("release notes (v2).png");
+        fs::write(&source, b"png bytes").expect("source file");
+        conn.execute(
+            "INSERT INTO entries (id, date, yesterday, today, created_at)
+             VALUES (1, '2026-05-04', '', '', '2026-05-04T09:00:00Z')",
+            [],
+        )
+        .expect("insert entry");
+
+        assert_eq!(sanitize_file_name("../etc/passwd"), "_etc_passwd");
+        assert!(attach_file_to_entry_in_conn(&conn, &root, 2, &source).is_err());
+        assert!(attach_file_to_entry_in_conn(&conn, &root, 1, &temp_dir).is_err());
+
+        let first = attach_file_to_entry_in_conn(&conn, &root, 1, &source).expect("attach");
+        let second = attach_file_to_entry_in_conn(&conn, &root, 1, &source).expect("attach again");
+        assert_eq!(first.file_name, "release notes (v2).png");
+        assert_eq!(first.mime_type, "image/png");
+        assert_eq!(first.size_bytes, 9);
+        assert_ne!(first.path, second.path);
+        assert!(first.path.ends_with("release_notes__v2_.png"));
+        assert_eq!(fs::read(&first.path).expect("copied file"), b"png bytes");
+
+        let listed = list_entry_attachments_in_conn(&conn, &root, 1).expect("list");
+        assert_eq!(
+            listed
+                .iter()
+                .map(|attachment| attachment.id)
+                .collect::<Vec<_>>(),
+            vec![first.id, second.id]
+        );
+
+        delete_attachment_in_conn(&conn, &root, first.id).expect("delete");
+        assert!(!std::path::Path::new(&first.path).exists());
+        assert!(delete_attachment_in_conn(&conn, &root, first.id).is_err());
+
+        let remaining = entry_attachment_paths(&conn, "2026-05-04").expect("paths");
+        conn.execute("DELETE FROM entries WHERE id = 1", [])
+            .expect("delete entry");
+        remove_attachment_files(&root, &remaining);
+        assert!(!std::path::Path::new(&second.path).exists());
+        assert!(list_entry_attachments_in_conn(&conn, &root, 1)
+            .expect("list")
+            .is_empty());
+        fs::remove_dir_all(temp_dir).ok();
+    }
+
+    #[test]
+    fn command_output_is_attached_indexed_and_searchable() {
+        let mut conn = command_test_connection();
+        let root = std::env::temp_dir().join(format!(
+            "dev-journal-command-output-test-{}",
+            Utc::now().timestamp_nanos_opt().unwrap_or_default()
+        ));
+
+        assert_eq!(
+            strip_ansi_codes("\u{1b}]0;title\u{7}\u{1b}[1;31merror\u{1b}[0m: failed\r\n"),
+            "error: failed\n"
+        );
+        assert!(attach_command_output_in_conn(&conn, &root, "05/04", "ls", "out").is_err());
+        assert!(attach_command_output_in_conn(&conn, &root, "2026-05-04", " ", "out").is_err());
+        assert!(
+            attach_command_output_in_conn(&conn, &root, "2026-05-04", "ls", "\u{1b}[0m").is_err()
+        );
+
+        let attachment = attach_command_output_in_conn(
+            &conn,
+            &root,
+            "2026-05-04",
+            " cargo test ",
+            "\u{1b}[31mthread 'parser' panicked\u{1b}[0m\r\n",
+        )
+        .expect("attach");
+        assert_eq!(attachment.kind, "command_output");
+        assert_eq!(attachment.command.as_deref(), Some("cargo test"));
+        assert_eq!(
+            fs::read_to_string(&attachment.path).expect("log file"),
+            "thread 'parser' panicked\n"
+        );
+        assert_eq!(
+            get_attachment_text_in_conn(&conn, attachment.id).expect("text"),
+            "thread 'parser' panicked\n"
+        );
+        let entry = entries::get_entry_in_conn(&conn, "2026-05-04")
+            .expect("entry")
+            .expect("created");
+        assert_eq!(entry.id, attachment.entry_id);
+        let listed = list_entry_attachments_in_conn(&conn, &root, entry.id).expect("list");
+        assert_eq!(listed[0].command.as_deref(), Some("cargo test"));
+
+        let results = search_entries_in_conn(&conn, "panick").expect("search");
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].entry.id, entry.id);
+        assert!(results[0].snippet.contains("<mark>panicked</mark>"));
+        assert_eq!(
+            search_entries_in_conn(&conn, "cargo")
+                .expect("search")
+                .len(),
+            1
+        );
+
+        trash_entry_in_conn(&conn, "2026-05-04").expect("trash");
+        assert!(search_entries_in_conn(&conn, "panicked")
+            .expect("search")
+            .is_empty());
+
+        enable_journal_encryption_in_conn(&mut conn, "correct horse battery").expect("enable");
+        assert!(attach_command_output_in_conn(&conn, &root, "2026-05-05", "ls", "secret").is_err());
+        assert!(search_entries_in_conn(&conn, "secret")
+            .expect("search")
+            .is_empty());
+        fs::remove_dir_all(root).ok();
+    }
+
+    #[test]
+    fn api_tokens_are_hashed_and_enforce_their_scopes() {
+        let conn = command_test_connection();
+        assert!(normalize_scopes(&[]).is_err());
+        assert!(normalize_scopes(&["tasks:delete".to_string()]).is_err());
+        assert_eq!(
+            normalize_scopes(&[
+                "tasks:write".to_string(),
+                " TASKS:READ ".to_string(),
+                "tasks:write".to_string()
+            ])
+            .expect("scopes"),
+            vec!["tasks:read".to_string(), "tasks:write".to_string()]
+        );
+        assert!(normalize_scopes(&["pages:read".to_string()]).is_err());
+        assert_eq!(
+            preset_scopes("read_only").expect("preset"),
+            vec!["entries:read".to_string(), "tasks:read".to_string()]
+        );
+
+        let tasks_only = preset_scopes("tasks_only").expect("preset");
+        let created = create_api_token_in_conn(&conn, "Assistant", &tasks_only).expect("token");
+        assert!(created.secret.starts_with("dj_"));
+        assert!(created.secret.starts_with(&created.token.token_prefix));
+        let stored_hash: String = conn
+            .query_row("SELECT token_hash FROM api_tokens", [], |row| row.get(0))
+            .expect("hash");
+        assert!(!stored_hash.contains(&created.secret));
+
+        let access = authorize_api_token_in_conn(&conn, &created.secret).expect("authorize");
+        assert!(access.require("tasks:write").is_ok());
+        assert!(access.require("entries:read").is_err());
+        assert!(!access.has_scope("bookmarks:write"));
+        assert_eq!(access.scopes(), tasks_only);
+        assert!(authorize_api_token_in_conn(&conn, "dj_wrong").is_err());
+        assert!(list_api_tokens_in_conn(&conn).expect("tokens")[0]
+            .last_used_at
+            .is_some());
+    }
+
+    #[test]
+    fn previous_workday_skips_weekends_and_days_off() {
+        let conn = command_test_connection();
+        let date = |text: &str| NaiveDate::parse_from_str(text, "%Y-%m-%d").expect("date");
+
+        // 2026-05-04 is a Monday.
+        assert_eq!(
+            previous_workday_in_conn(&conn, date("2026-05-04")).expect("monday"),
+            date("2026-05-01")
+        );
+        assert_eq!(
+            previous_workday_in_conn(&conn, date("2026-05-05")).expect("tuesday"),
+            date("2026-05-04")
+        );
+
+        settings::write_setting(&conn, "days_off", "2026-05-01, not-a-date\n2026-04-30")
+            .expect("days off");
+        assert_eq!(
+            previous_workday_in_conn(&conn, date("2026-05-04")).expect("after holidays"),
+            date("2026-04-29")
+        );
+
+        settings::write_setting(&conn, "work_days", "sun,mon,tue,wed,thu").expect("work days");
+        assert_eq!(
+            previous_workday_in_conn(&conn, date("2026-05-04")).expect("sunday shift"),
+            date("2026-05-03")
+        );
+
+        settings::write_setting(&conn, "work_days", "").expect("no work days");
+        assert_eq!(
+            previous_workday_in_conn(&conn, date("2026-05-04")).expect("fallback"),
+            date("2026-05-03")
+        );
+    }
+
+    #[test]
+    fn save_entry_commits_in_conn_merges_snapshots_in_commit_order() {
+        let conn = command_test_connection();
+        assert!(
+            save_entry_commits_in_conn(&conn, "2026-05-04", &["abc1234 Fix".to_string()]).is_err()
+        );
+        conn.execute(
+            "INSERT INTO entries (date, yesterday, today, created_at)
+             VALUES ('2026-05-04', '', '', '2026-05-04T09:00:00Z')",
+            [],
+        )
+        .expect("insert entry");
+
+        assert_eq!(
+            parse_oneline_commit("  ABC1234  Fix login redirect "),
+            Some(("abc1234".to_string(), "Fix login redirect".to_string()))
+        );
+        assert_eq!(parse_oneline_commit("not a commit"), None);
+
+        // git log order: newest first.
+        let first_machine = vec![
+            "bbb2222 Add tests".to_string(),
+            "aaa1111 Fix login redirect".to_string(),
+            "garbage".to_string(),
+        ];
+        save_entry_commits_in_conn(&conn, "2026-05-04", &first_machine).expect("save");
+        let second_machine = vec![
+            "ccc3333 Deploy".to_string(),
+            "bbb2222 Add tests".to_string(),
+        ];
+        let saved =
+            save_entry_commits_in_conn(&conn, "2026-05-04", &second_machine).expect("merge");
+
+        assert_eq!(
+            saved
+                .iter()
+                .map(|commit| commit.hash.as_str())
+                .collect::<Vec<_>>(),
+            vec!["aaa1111", "bbb2222", "ccc3333"]
+        );
+        assert_eq!(saved[0].message, "Fix login redirect");
+        assert_eq!(
+            get_entry_commits_in_conn(&conn, "2026-05-04")
+                .expect("get")
+                .len(),
+            3
+        );
+    }
+
+    #[test]
+    fn external_calls_are_logged_and_rate_limited_per_token() {
+        let conn = command_test_connection();
+        settings::write_setting(&conn, "api_rate_limit_per_minute", "2").expect("limit");
+        let token =
+            create_api_token_in_conn(&conn, "Editor", &["tasks:read".to_string()]).expect("token");
+
+        assert!(authorize_external_call_in_conn(&conn, "dj_nope", "tasks/list", None).is_err());
+        assert!(authorize_external_call_in_conn(
+            &conn,
+            &token.secret,
+            "entries/list",
+            Some("entries:read")
+        )
+        .is_err());
+        for _ in 0..2 {
+            authorize_external_call_in_conn(&conn, &token.secret, "tasks/list", Some("tasks:read"))
+                .expect("allowed");
+        }
+        let limited =
+            authorize_external_call_in_conn(&conn, &token.secret, "tasks/list", Some("tasks:read"))
+                .expect_err("rate limited");
+        assert!(limited.contains("Rate limit"));
+
+        let log = get_external_access_log_in_conn(&conn, 10).expect("log");
+        let outcomes: Vec<(&str, &str, &str)> = log
+            .iter()
+            .rev()
+            .map(|entry| {
+                (
+                    entry.caller.as_str(),
+                    entry.endpoint.as_str(),
+                    entry.outcome.as_str(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            outcomes,
+            vec![
+                ("unknown", "tasks/list", "denied"),
+                ("Editor", "entries/list", "denied"),
+                ("Editor", "tasks/list", "allowed"),
+                ("Editor", "tasks/list", "allowed"),
+                ("Editor", "tasks/list", "rate_limited"),
+            ]
+        );
+        assert_eq!(log[0].token_id, Some(token.token.id));
+    }
+
+    #[test]
+    fn find_duplicate_tasks_matches_similar_open_titles() {
+        let conn = command_test_connection();
+        conn.execute(
+            "INSERT INTO tasks (title, description, status, priority, created_at, updated_at)
+             VALUES ('Fix login redirect bug', '', 'todo', 'medium', '2026-05-01', '2026-05-01'),
+                    ('Fix: login-redirect bug!', '', 'done', 'medium', '2026-05-01', '2026-05-01'),
+                    ('Write release notes', '', 'in_progress', 'low', '2026-05-01', '2026-05-01')",
+            [],
+        )
+        .expect("tasks");
+
+        let titles = |title: &str| {
+            find_duplicate_tasks_in_conn(&conn, title)
+                .expect("duplicates")
+                .into_iter()
+                .map(|duplicate| (duplicate.task_id, duplicate.similarity))
+                .collect::<Vec<_>>()
+        };
+        // Punctuation and case are ignored; done tasks are never offered.
+        assert_eq!(titles("fix LOGIN redirect bug."), vec![(1, 1.0)]);
+        assert_eq!(titles("Fix login redirct bug").len(), 1);
+        assert_eq!(titles("Write the release notes").len(), 1);
+        assert!(titles("Plan the team offsite").is_empty());
+        assert!(titles("   ").is_empty());
+    }
+
+    #[test]
+    fn capture_in_conn_creates_bookmarks_and_queued_tasks() {
+        let conn = command_test_connection();
+        let token = create_api_token_in_conn(&conn, "Browser", &preset_scopes("capture").unwrap())
+            .expect("token");
+        let payload = |kind: Option<&str>, url: &str| CapturePayload {
+            url: url.to_string(),
+            title: Some("  Rust release notes ".to_string()),
+            selection: Some("Line one\nLine two".to_string()),
+            kind: kind.map(str::to_string),
+        };
+
+        let bookmark = capture_in_conn(
+            &conn,
+            &token.secret,
+            payload(None, "https://blog.rust-lang.org/"),
+        )
+        .expect("bookmark");
+        assert_eq!(bookmark.kind, "bookmark");
+        let bookmarks = get_bookmarks_in_conn(&conn).expect("bookmarks");
+        assert_eq!(bookmarks.len(), 1);
+        assert_eq!(bookmarks[0].title, "Rust release notes");
+        assert_eq!(bookmarks[0].selection, "Line one\nLine two");
+
+        let task = capture_in_conn(
+            &conn,
+            &token.secret,
+            payload(Some("task"), "https://example.com/bug"),
+        )
+        .expect("task");
+        let (description, source): (String, String) = conn
+            .query_row(
+                "SELECT t.description, q.source FROM tasks t JOIN review_queue q ON q.task_id = t.id WHERE t.id = ?1",
+                params![task.id],
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            )
+            .expect("queued task");
+        assert_eq!(
+            description,
+            "Source: https://example.com/bug\n\n> Line one\n> Line two"
+        );
+        assert_eq!(source, "browser_capture");
+        assert!(task.duplicates.is_empty());
+
+        let again = capture_in_conn(
+            &conn,
+            &token.secret,
+            payload(Some("task"), "https://example.com/bug-again"),
+        )
+        .expect("duplicate task");
+        assert_eq!(
+            again
+                .duplicates
+                .iter()
+                .map(|duplicate| duplicate.task_id)
+                .collect::<Vec<_>>(),
+            vec![task.id]
+        );
+
+        let invalid = capture_in_conn(&conn, &token.secret, payload(None, "javascript:alert(1)"))
+            .expect_err("invalid url");
+        assert!(invalid.contains("URL"));
+        let read_only =
+            create_api_token_in_conn(&conn, "Reader", &preset_scopes("read_only").unwrap())
+                .expect("read-only token");
+        assert!(capture_in_conn(
+            &conn,
+            &read_only.secret,
+            payload(None, "https://example.com")
+        )
+        .expect_err("missing scope")
+        .contains("bookmarks:write"));
+
+        let outcomes: Vec<String> = get_external_access_log_in_conn(&conn, 10)
+            .expect("log")
+            .into_iter()
+            .rev()
+            .map(|entry| entry.outcome)
+            .collect();
+        assert_eq!(
+            outcomes,
+            vec!["allowed", "allowed", "allowed", "allowed", "error", "denied"]
+        );
+    }
+
+    #[test]
+    fn compute_entry_streaks_tracks_current_and_longest_runs() {
+        let today = NaiveDate::from_ymd_opt(2026, 5, 20).expect("date");
+        let dates = |days: &[u32]| -> Vec<String> {
+            days.iter()
+                .map(|day| format!("2026-05-{:02}", day))
+                .collect()
+        };
+
+        let streaks = compute_entry_streaks(&dates(&[1, 2, 3, 4, 10, 18, 19]), today);
+        assert_eq!(
+            streaks,
+            EntryStreaks {
+                current_streak: 2,
+                longest_streak: 4,
+                total_entries: 7,
+            }
+        );
+        assert_eq!(
+            compute_entry_streaks(&dates(&[17, 18]), today).current_streak,
+            0
+        );
+        assert_eq!(
+            compute_entry_streaks(&dates(&[18, 19, 20]), today).current_streak,
+            3
+        );
+        assert_eq!(compute_entry_streaks(&[], today), EntryStreaks::default());
+    }
+
+    #[test]
+    fn get_entry_streaks_in_conn_reads_entry_dates() {
+        let conn = command_test_connection();
+        for date in ["2026-05-18", "2026-05-19", "2026-05-20"] {
+            conn.execute(
+                "INSERT INTO entries (date, yesterday, today, created_at) VALUES (?1, '', 'Work', ?1)",
+                params![date],
+            )
+            .expect("entry");
+        }
+        let today = NaiveDate::from_ymd_opt(2026, 5, 21).expect("date");
+
+        let streaks = get_entry_streaks_in_conn(&conn, today).expect("streaks");
+        assert_eq!(streaks.current_streak, 3);
+        assert_eq!(streaks.longest_streak, 3);
+        assert_eq!(streaks.total_entries, 3);
+    }
+
+    #[test]
+    fn pinned_entries_are_listed_newest_first_and_survive_edits() {
+        let conn = command_test_connection();
+        for date in ["2026-05-01", "2026-05-02", "2026-05-03"] {
+            conn.execute(
+                "INSERT INTO entries (date, yesterday, today, created_at) VALUES (?1, 'Retro', '', ?1)",
+                params![date],
+            )
+            .expect("entry");
+        }
+
+        set_entry_pinned_in_conn(&conn, "2026-05-01", true).expect("pin");
+        set_entry_pinned_in_conn(&conn, "2026-05-03", true).expect("pin");
+        set_entry_pinned_in_conn(&conn, "2026-05-03", false).expect("unpin");
+        set_entry_pinned_in_conn(&conn, "2026-05-02", true).expect("pin");
+        assert!(set_entry_pinned_in_conn(&conn, "2026-06-01", true).is_err());
+
+        conn.execute(
+            "INSERT INTO entries (date, yesterday, today, created_at) VALUES ('2026-05-02', 'Edited', '', '')
+             ON CONFLICT(date) DO UPDATE SET yesterday = excluded.yesterday",
+            [],
+        )
+        .expect("edit");
+
+        let pinned = get_pinned_entries_in_conn(&conn).expect("pinned");
+        assert_eq!(
+            pinned
+                .iter()
+                .map(|entry| entry.date.as_str())
+                .collect::<Vec<_>>(),
+            vec!["2026-05-02", "2026-05-01"]
+        );
+        assert!(pinned.iter().all(|entry| entry.pinned));
+        assert_eq!(pinned[0].yesterday, "Edited");
+    }
+
+    #[test]
+    fn trashed_entries_are_hidden_restorable_and_purged() {
+        let conn = command_test_connection();
+        for date in ["2026-05-01", "2026-05-02", "2026-05-03"] {
+            conn.execute(
+                "INSERT INTO entries (date, yesterday, today, created_at) VALUES (?1, 'Retro', '', ?1)",
+                params![date],
+            )
+            .expect("entry");
+        }
+
+        trash_entry_in_conn(&conn, "2026-05-01").expect("trash");
+        trash_entry_in_conn(&conn, "2026-05-02").expect("trash");
+        assert!(trash_entry_in_conn(&conn, "2026-05-02").is_err());
+        assert!(entries::get_entry_in_conn(&conn, "2026-05-01")
+            .expect("get")
+            .is_none());
+        assert_eq!(
+            get_entries_in_conn(&conn, Some(10), None)
+                .expect("entries")
+                .iter()
+                .map(|entry| entry.date.as_str())
+                .collect::<Vec<_>>(),
+            vec!["2026-05-03"]
+        );
+        assert_eq!(get_trashed_entries_in_conn(&conn).expect("trash").len(), 2);
+
+        restore_entry_in_conn(&conn, "2026-05-02").expect("restore");
+        assert!(restore_entry_in_conn(&conn, "2026-05-02").is_err());
+        assert!(entries::get_entry_in_conn(&conn, "2026-05-02")
+            .expect("get")
+            .is_some());
+
+        trash_entry_in_conn(&conn, "2026-05-03").expect("trash");
+        conn.execute(
+            "UPDATE entries SET deleted_at = '2026-01-01T00:00:00+00:00' WHERE date = '2026-05-01'",
+            [],
+        )
+        .expect("age trash");
+        let (purged, _) =
+            purge_trash_in_conn(&conn, Some("2026-02-01T00:00:00+00:00")).expect("purge expired");
+        assert_eq!(purged, 1);
+        let trashed = get_trashed_entries_in_conn(&conn).expect("trash");
+        assert_eq!(
+            trashed
+                .iter()
+                .map(|trashed| trashed.entry.date.as_str())
+                .collect::<Vec<_>>(),
+            vec!["2026-05-03"]
+        );
+
+        let (purged, _) = purge_trash_in_conn(&conn, None).expect("purge all");
+        assert_eq!(purged, 1);
+        assert!(get_trashed_entries_in_conn(&conn)
+            .expect("trash")
+            .is_empty());
+        assert_eq!(
+            get_entries_in_conn(&conn, Some(10), None)
+                .expect("entries")
+                .len(),
+            1
+        );
+    }
+
+    #[test]
+    fn on_this_day_lists_the_same_date_in_earlier_years() {
+        let conn = command_test_connection();
+        for date in [
+            "2023-10-16",
+            "2024-10-16",
+            "2024-10-17",
+            "2026-10-16",
+            "2025-10-16",
+        ] {
+            conn.execute(
+                "INSERT INTO entries (date, yesterday, today, created_at) VALUES (?1, 'Work', '', ?1)",
+                params![date],
+            )
+            .expect("entry");
+        }
+        trash_entry_in_conn(&conn, "2025-10-16").expect("trash");
+
+        let entries = get_entries_on_this_day_in_conn(&conn, " 10-16 ", 2026).expect("on this day");
+        assert_eq!(
+            entries
+                .iter()
+                .map(|entry| entry.date.as_str())
+                .collect::<Vec<_>>(),
+            vec!["2024-10-16", "2023-10-16"]
+        );
+        assert!(get_entries_on_this_day_in_conn(&conn, "02-29", 2026)
+            .expect("leap day")
+            .is_empty());
+        assert!(get_entries_on_this_day_in_conn(&conn, "13-01", 2026).is_err());
+        assert!(get_entries_on_this_day_in_conn(&conn, "2024-10-16", 2026).is_err());
+    }
+
+    #[test]
+    fn random_entry_only_picks_old_enough_live_entries() {
+        let conn = command_test_connection();
+        for date in ["2026-01-05", "2026-03-01", "2026-05-01", "2026-05-04"] {
+            conn.execute(
+                "INSERT INTO entries (date, yesterday, today, created_at) VALUES (?1, 'Work', '', ?1)",
+                params![date],
+            )
+            .expect("entry");
+        }
+        trash_entry_in_conn(&conn, "2026-03-01").expect("trash");
+        let today = NaiveDate::from_ymd_opt(2026, 5, 4).expect("date");
+
+        let mut seen = HashSet::new();
+        for _ in 0..60 {
+            let entry = get_random_entry_in_conn(&conn, today, Some(30))
+                .expect("random")
+                .expect("old entry");
+            seen.insert(entry.date);
+        }
+        assert_eq!(seen, HashSet::from(["2026-01-05".to_string()]));
+
+        for _ in 0..60 {
+            let entry = get_random_entry_in_conn(&conn, today, None)
+                .expect("random")
+                .expect("entry");
+            seen.insert(entry.date);
+        }
+        assert_eq!(seen.len(), 2, "today's entry is never picked: {:?}", seen);
+        assert!(seen.contains("2026-05-01"));
+
+        assert!(get_random_entry_in_conn(&conn, today, Some(365))
+            .expect("random")
+            .is_none());
+        assert!(get_random_entry_in_conn(&conn, today, Some(-1)).is_err());
+    }
+
+    #[test]
+    fn cron_schedules_parse_wrap_and_find_next_run() {
+        let at = |text: &str| {
+            chrono::NaiveDateTime::parse_from_str(text, "%Y-%m-%d %H:%M").expect("datetime")
+        };
+
+        // 2026-05-01 is a Friday.
+        let night = CronSchedule::parse("*/30 22-2 * * fri-mon").expect("night shift");
+        assert_eq!(
+            night.next_after(at("2026-05-01 12:00")),
+            Some(at("2026-05-01 22:00"))
+        );
+        assert_eq!(
+            night.next_after(at("2026-05-01 23:45")),
+            Some(at("2026-05-02 00:00"))
+        );
+        assert_eq!(
+            night.next_after(at("2026-05-05 02:30")),
+            Some(at("2026-05-08 00:00")),
+            "Tuesday to Thursday are skipped"
+        );
+
+        let weekly = CronSchedule::parse("@weekly").expect("macro");
+        assert_eq!(
+            weekly,
+            CronSchedule::parse("0 0 * * 7").expect("sunday as 7")
+        );
+        // Both day fields restricted: the 1st or any Monday.
+        let either = CronSchedule::parse("0 9 1 * 1").expect("either day");
+        assert_eq!(
+            either.next_after(at("2026-05-01 10:00")),
+            Some(at("2026-05-04 09:00"))
+        );
+        assert!(either.due_between(at("2026-05-04 08:59"), at("2026-05-04 09:00")));
+        assert!(!either.due_between(at("2026-05-04 09:00"), at("2026-05-04 09:30")));
+
+        assert!(CronSchedule::parse("0 9 * *").is_err());
+        assert!(CronSchedule::parse("60 * * * *").is_err());
+        assert!(CronSchedule::parse("*/0 * * * *").is_err());
+        assert!(CronSchedule::parse("0 0 31 feb *")
+            .expect("parses")
+            .next_after(at("2026-05-01 00:00"))
+            .is_none());
+    }
+
+    #[test]
+    fn scheduled_jobs_store_schedules_and_run_data_jobs() {
+        let conn = command_test_connection();
+        let now = chrono::NaiveDateTime::parse_from_str("2026-05-04 08:30", "%Y-%m-%d %H:%M")
+            .expect("datetime");
+
+        let jobs = list_scheduled_jobs_in_conn(&conn, now).expect("jobs");
+        let names: Vec<&str> = jobs.iter().map(|job| job.job.as_str()).collect();
+        assert_eq!(
+            names,
+            [
+                "reminders",
+                "backup",
+                "digest",
+                "rollover",
+                "update_check",
+                "integrity"
+            ]
+        );
+        assert!(jobs[3].next_run_at.is_none(), "rollover is off by default");
+
+        assert!(set_job_schedule_in_conn(&conn, "backup", "0 25 * * *", true, now).is_err());
+        assert!(set_job_schedule_in_conn(&conn, "unknown", "* * * * *", true, now).is_err());
+        assert!(set_job_schedule_in_conn(&conn, "backup", "0 0 30 2 *", true, now).is_err());
+        let backup = set_job_schedule_in_conn(&conn, "backup", "45 8 * * *", true, now)
+            .expect("backup schedule");
+        assert_eq!(backup.next_run_at.as_deref(), Some("2026-05-04T08:45:00"));
+        let rollover =
+            set_job_schedule_in_conn(&conn, "rollover", "", true, now).expect("enable rollover");
+        assert_eq!(rollover.schedule, "5 0 * * *");
+
+        let until = now + chrono::Duration::minutes(20);
+        assert_eq!(
+            due_scheduled_jobs_in_conn(&conn, now, until).expect("due"),
+            ["reminders", "backup"]
+        );
+
+        record_job_run_in_conn(
+            &conn,
+            "backup",
+            &Err("disk full".to_string()),
+            "2026-05-04T08:45:00Z",
+        )
+        .expect("record");
+        let backup = list_scheduled_jobs_in_conn(&conn, now)
+            .expect("jobs")
+            .into_iter()
+            .find(|job| job.job == "backup")
+            .expect("backup");
+        assert_eq!(
+            backup.schedule, "45 8 * * *",
+            "recording keeps the schedule"
+        );
+        assert_eq!(backup.last_status.as_deref(), Some("error"));
+        assert_eq!(backup.last_error.as_deref(), Some("disk full"));
+
+        conn.execute(
+            "INSERT INTO tasks (title, description, status, due_date, recurrence, created_at, updated_at)
+             VALUES ('Overdue', '', 'todo', '2026-05-01', 'none', '', 'unchanged'),
+                    ('Weekly sync', '', 'todo', '2026-05-01', 'weekly', '', ''),
+                    ('Shipped', '', 'done', '2026-05-01', 'none', '', '')",
+            [],
+        )
+        .expect("tasks");
+        let today = now.date();
+        assert_eq!(
+            rollover_overdue_tasks_in_conn(&conn, today).expect("rollover"),
+            1
+        );
+        let (due_date, updated_at): (String, String) = conn
+            .query_row(
+                "SELECT due_date, updated_at FROM tasks WHERE title = 'Overdue'",
+                [],
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            )
+            .expect("rolled task");
+        assert_eq!(
+            (due_date.as_str(), updated_at.as_str()),
+            ("2026-05-04", "unchanged")
+        );
+
+        let digest = weekly_digest_in_conn(&conn, chrono::Local::now()).expect("digest");
+        assert_eq!(digest.tasks_done, 0);
+
+        let dir = std::env::temp_dir().join(format!(
+            "dev-journal-job-backups-{}",
+            Utc::now().timestamp_nanos_opt().unwrap_or_default()
+        ));
+        for minute in 0..9 {
+            write_database_snapshot_in_conn(&conn, &dir, now + chrono::Duration::minutes(minute))
+                .expect("snapshot");
+        }
+        let mut snapshots: Vec<String> = fs::read_dir(&dir)
+            .expect("backup dir")
+            .map(|entry| {
+                entry
+                    .expect("entry")
+                    .file_name()
+                    .to_string_lossy()
+                    .to_string()
+            })
+            .collect();
+        snapshots.sort();
+        assert_eq!(snapshots.len(), 7);
+        assert_eq!(snapshots[0], "dev_journal-20260504-083200.db");
+        let copy = Connection::open(dir.join(&snapshots[6])).expect("open snapshot");
+        let tasks: i64 = copy
+            .query_row("SELECT COUNT(*) FROM tasks", [], |row| row.get(0))
+            .expect("count");
+        assert_eq!(tasks, 3);
+        fs::remove_dir_all(dir).ok();
+    }
+
+    #[test]
+    fn journal_encryption_round_trips_entries_and_pages() {
+        let mut conn = command_test_connection();
+        conn.execute(
+            "INSERT INTO entries (date, yesterday, today, created_at)
+             VALUES ('2026-05-04', 'Fixed the payroll export', 'Review salaries', '2026-05-04')",
+            [],
+        )
+        .expect("entry");
+        conn.execute(
+            "INSERT INTO pages (title, content, created_at, updated_at)
+             VALUES ('1:1 notes', 'Performance concerns', '', '')",
+            [],
+        )
+        .expect("page");
+        conn.execute(
+            "INSERT INTO entry_sections (entry_id, section_key, content, sort_order, updated_at)
+             SELECT id, 'wins', 'Closed the hiring loop', 0, '' FROM entries",
+            [],
+        )
+        .expect("section");
+
+        assert!(enable_journal_encryption_in_conn(&mut conn, "short").is_err());
+        let cipher =
+            enable_journal_encryption_in_conn(&mut conn, "correct horse battery").expect("enable");
+        assert!(enable_journal_encryption_in_conn(&mut conn, "correct horse battery").is_err());
+
+        let stored: (String, String) = conn
+            .query_row(
+                "SELECT e.yesterday, p.content FROM entries e, pages p",
+                [],
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            )
+            .expect("stored");
+        assert!(stored.0.starts_with("enc:v1:") && stored.1.starts_with("enc:v1:"));
+        assert!(search_entries_in_conn(&conn, "payroll")
+            .expect("fts")
+            .is_empty());
+
+        let locked = JournalCipher::from_conn(&conn).expect("locked");
+        assert!(locked.is_locked());
+        assert!(locked.encrypt("note").is_err());
+        assert!(locked.decrypt(&stored.0).is_err());
+        assert_eq!(
+            locked.decrypt("legacy plaintext").expect("plain"),
+            "legacy plaintext"
+        );
+
+        assert!(unlock_journal_in_conn(&conn, "wrong passphrase").is_err());
+        let unlocked = unlock_journal_in_conn(&conn, "correct horse battery").expect("unlock");
+        assert_eq!(
+            unlocked.decrypt(&stored.1).expect("page"),
+            "Performance concerns"
+        );
+        let entry = entries::get_entry_in_conn(&conn, "2026-05-04")
+            .expect("get")
+            .decrypt_with(&unlocked)
+            .expect("decrypt")
+            .expect("entry");
+        assert_eq!(entry.today, "Review salaries");
+        let stored_section: String = conn
+            .query_row("SELECT content FROM entry_sections", [], |row| row.get(0))
+            .expect("stored section");
+        assert!(stored_section.starts_with("enc:v1:"));
+        let sections = entries::get_entry_sections_in_conn(&conn, "2026-05-04")
+            .expect("sections")
+            .decrypt_with(&unlocked)
+            .expect("decrypt sections");
+        assert_eq!(sections[0].content, "Closed the hiring loop");
+
+        let results =
+            encryption::search_encrypted_entries_in_conn(&conn, &cipher, "PAYROLL export")
+                .expect("search");
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].snippet, "Fixed the payroll export");
+        assert!(
+            encryption::search_encrypted_entries_in_conn(&conn, &cipher, "payroll budget")
+                .expect("search")
+                .is_empty()
+        );
+
+        conn.execute(
+            "INSERT INTO pages (title, content, created_at, updated_at) VALUES ('Imported', 'Plain', '', '')",
+            [],
+        )
+        .expect("plaintext page");
+        assert_eq!(
+            encrypt_journal_in_conn(&conn, &cipher).expect("re-encrypt"),
+            1
+        );
+
+        assert!(disable_journal_encryption_in_conn(&mut conn, "wrong passphrase").is_err());
+        disable_journal_encryption_in_conn(&mut conn, "correct horse battery").expect("disable");
+        assert!(!JournalCipher::from_conn(&conn)
+            .expect("status")
+            .is_enabled());
+        assert_eq!(
+            search_entries_in_conn(&conn, "payroll").expect("fts").len(),
+            1
+        );
+        let contents: Vec<String> = conn
+            .prepare("SELECT content FROM pages ORDER BY id")
+            .expect("prepare")
+            .query_map([], |row| row.get(0))
+            .expect("query")
+            .collect::<Result<_, _>>()
+            .expect("pages");
+        assert_eq!(contents, vec!["Performance concerns", "Plain"]);
+        let section: String = conn
+            .query_row("SELECT content FROM entry_sections", [], |row| row.get(0))
+            .expect("section");
+        assert_eq!(section, "Closed the hiring loop");
+    }
+
+    #[test]
+    fn entry_notes_log_through_the_day_and_follow_encryption() {
+        let mut conn = command_test_connection();
+        assert!(append_entry_note_in_conn(&conn, "today", "Standup done").is_err());
+        let first =
+            append_entry_note_in_conn(&conn, "2026-05-04", "Standup done").expect("first note");
+        append_entry_note_in_conn(&conn, "2026-05-04", "Switched to the flaky test")
+            .expect("second note");
+        append_entry_note_in_conn(&conn, "2026-05-05", "Next day").expect("other day");
+
+        let texts = |conn: &Connection| {
+            get_entry_notes_in_conn(conn, "2026-05-04")
+                .expect("notes")
+                .into_iter()
+                .map(|note| note.text)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(first.date, "2026-05-04");
+        assert_eq!(
+            texts(&conn),
+            vec!["Standup done", "Switched to the flaky test"]
+        );
+        // Notes do not create an entry for the day.
+        assert!(get_entry_in_conn(&conn, "2026-05-04")
+            .expect("entry")
+            .is_none());
+
+        let cipher =
+            enable_journal_encryption_in_conn(&mut conn, "correct horse battery").expect("enable");
+        assert!(texts(&conn).iter().all(|text| text.starts_with("enc:v1:")));
+        let decrypted = get_entry_notes_in_conn(&conn, "2026-05-04")
+            .expect("notes")
+            .decrypt_with(&cipher)
+            .expect("decrypt");
+        assert_eq!(decrypted[1].text, "Switched to the flaky test");
+
+        disable_journal_encryption_in_conn(&mut conn, "correct horse battery").expect("disable");
+        assert_eq!(
+            texts(&conn),
+            vec!["Standup done", "Switched to the flaky test"]
+        );
+    }
+
+    #[test]
+    fn entry_drafts_stay_out_of_the_entry_list_and_follow_encryption() {
+        let mut conn = command_test_connection();
+        assert!(save_entry_draft_in_conn(&conn, "May 4", "", "", "", "").is_err());
+        assert!(get_entry_draft_in_conn(&conn, "2026-05-04")
+            .expect("draft")
+            .is_none());
+
+        save_entry_draft_in_conn(&conn, "2026-05-04", "Half", "", "", "").expect("save");
+        let draft =
+            save_entry_draft_in_conn(&conn, "2026-05-04", "Half written", "Plan", "Flaky CI", "")
+                .expect("save again");
+        assert_eq!(draft.yesterday, "Half written");
+        assert!(get_entries_in_conn(&conn, None, None)
+            .expect("entries")
+            .is_empty());
+        assert!(search_entries_in_conn(&conn, "written")
+            .expect("search")
+            .is_empty());
+
+        let cipher =
+            enable_journal_encryption_in_conn(&mut conn, "correct horse battery").expect("enable");
+        let stored = get_entry_draft_in_conn(&conn, "2026-05-04")
+            .expect("draft")
+            .expect("stored");
+        assert!(stored.yesterday.starts_with("enc:v1:") && stored.today.starts_with("enc:v1:"));
+        assert!(stored.blockers.starts_with("enc:v1:") && stored.notes.starts_with("enc:v1:"));
+        let decrypted = stored.decrypt_with(&cipher).expect("decrypt");
+        assert_eq!(decrypted.yesterday, "Half written");
+        assert_eq!(decrypted.blockers, "Flaky CI");
+        disable_journal_encryption_in_conn(&mut conn, "correct horse battery").expect("disable");
+        assert_eq!(
+            get_entry_draft_in_conn(&conn, "2026-05-04")
+                .expect("draft")
+                .expect("stored")
+                .today,
+            "Plan"
+        );
+
+        discard_entry_draft_in_conn(&conn, "2026-05-04").expect("discard");
+        assert!(get_entry_draft_in_conn(&conn, "2026-05-04")
+            .expect("draft")
+            .is_none());
+    }
+
+    #[test]
+    fn pomodoro_status_in_conn_schedules_long_breaks_and_tracks_goal() {
+        let conn = command_test_connection();
+        let insert_session = |kind: &str, date: &str| {
+            conn.execute(
+                "INSERT INTO pomodoro_sessions (kind, task_id, duration_minutes, date, completed_at)
+                 VALUES (?1, NULL, 25, ?2, ?3)",
+                params![kind, date, Utc::now().to_rfc3339()],
+            )
+            .expect("insert session");
+        };
+
+        for _ in 0..3 {
+            insert_session("focus", "2026-05-04");
+            insert_session("short_break", "2026-05-04");
+        }
+        let status = pomodoro_status_in_conn(&conn, "2026-05-04").expect("status");
+        assert_eq!(status.completed_today, 3);
+        assert_eq!(status.next_break_kind, "short_break");
+
+        insert_session("focus", "2026-05-04");
+        let status = pomodoro_status_in_conn(&conn, "2026-05-04").expect("status");
+        assert_eq!(status.next_break_kind, "long_break");
+        assert_eq!(status.goal_progress, 50);
+        assert_eq!(status.focus_minutes_today, 100);
+
+        insert_session("long_break", "2026-05-04");
+        insert_session("focus", "2026-05-04");
+        let status = pomodoro_status_in_conn(&conn, "2026-05-04").expect("status");
+        assert_eq!(status.cycle_position, 1);
+        assert_eq!(status.next_break_kind, "short_break");
+
+        insert_session("focus", "2026-05-02");
+        let heatmap =
+            pomodoro_heatmap_in_conn(&conn, NaiveDate::from_ymd_opt(2026, 5, 4).expect("date"), 3)
+                .expect("heatmap");
+        assert_eq!(
+            heatmap
+                .iter()
+                .map(|day| (day.date.as_str(), day.count))
+                .collect::<Vec<_>>(),
+            vec![("2026-05-02", 1), ("2026-05-03", 0), ("2026-05-04", 5)]
+        );
+    }
+
+    #[test]
+    fn automation_rules_preview_then_apply_and_log() {
+        let conn = command_test_connection();
+        let now = Utc::now();
+        let today = now.with_timezone(&chrono::Local).date_naive();
+        let stale = (now - Duration::days(10)).to_rfc3339();
+        let fresh = now.to_rfc3339();
+        conn.execute(
+            "INSERT INTO tasks (title, description, status, priority, due_date, created_at, updated_at)
+             VALUES ('Ship hotfix', '', 'todo', 'low', ?1, ?3, ?3),
+                    ('Plan roadmap', '', 'todo', 'low', ?2, ?3, ?3),
+                    ('Urgent already', '', 'todo', 'high', ?1, ?3, ?3),
+                    ('Migrate CI', '', 'in_progress', 'medium', NULL, ?4, ?4),
+                    ('Fresh work', '', 'in_progress', 'medium', NULL, ?3, ?3)",
+            params![
+                today.format("%Y-%m-%d").to_string(),
+                (today + Duration::days(5)).format("%Y-%m-%d").to_string(),
+                fresh,
+                stale
+            ],
+        )
+        .expect("tasks");
+
+        let rule = |name: &str,
+                    condition: &str,
+                    value: i64,
+                    filter: Option<&str>,
+                    action: &str,
+                    action_value: &str| AutomationRuleInput {
+            name: name.to_string(),
+            condition: condition.to_string(),
+            condition_value: value,
+            priority_filter: filter.map(str::to_string),
+            action: action.to_string(),
+            action_value: action_value.to_string(),
+            enabled: true,
+        };
+        create_automation_rule_in_conn(
+            &conn,
+            &rule(
+                "Escalate due soon",
+                "due_within_hours",
+                24,
+                Some("low"),
+                "set_priority",
+                "Medium",
+            ),
+        )
+        .expect("priority rule");
+        let stale_rule = create_automation_rule_in_conn(
+            &conn,
+            &rule("Stale", "in_progress_days", 7, None, "add_tag", "#stale"),
+        )
+        .expect("tag rule");
+        assert_eq!(stale_rule.action_value, "stale");
+        assert!(create_automation_rule_in_conn(
+            &conn,
+            &rule(
+                "Bad",
+                "due_within_hours",
+                24,
+                None,
+                "set_priority",
+                "critical"
+            ),
+        )
+        .is_err());
+        assert!(create_automation_rule_in_conn(
+            &conn,
+            &rule("Bad", "overdue_forever", 1, None, "add_tag", "x"),
+        )
+        .is_err());
+
+        let summary = |changes: &[crate::models::AutomationRuleChange]| {
+            changes
+                .iter()
+                .map(|change| (change.task_title.clone(), change.change.clone()))
+                .collect::<Vec<_>>()
+        };
+        let expected = vec![
+            (
+                "Ship hotfix".to_string(),
+                "priority low → medium".to_string(),
+            ),
+            ("Migrate CI".to_string(), "tagged #stale".to_string()),
+        ];
+
+        // A dry run reports the changes without making them.
+        let preview = evaluate_automation_rules_in_conn(&conn, now, false).expect("preview");
+        assert_eq!(summary(&preview), expected);
+        let priority: String = conn
+            .query_row(
+                "SELECT priority FROM tasks WHERE title = 'Ship hotfix'",
+                [],
+                |row| row.get(0),
+            )
+            .expect("priority");
+        assert_eq!(priority, "low");
+        assert!(get_task_tags_in_conn(&conn).expect("tags").is_empty());
+        assert!(get_automation_log_in_conn(&conn, 10)
+            .expect("log")
+            .is_empty());
+
+        let applied = evaluate_automation_rules_in_conn(&conn, now, true).expect("apply");
+        assert_eq!(summary(&applied), expected);
+        let priority: String = conn
+            .query_row(
+                "SELECT priority FROM tasks WHERE title = 'Ship hotfix'",
+                [],
+                |row| row.get(0),
+            )
+            .expect("priority");
+        assert_eq!(priority, "medium");
+        let tags = get_task_tags_in_conn(&conn).expect("tags");
+        assert_eq!(tags.len(), 1);
+        assert_eq!(tags[0].tag, "stale");
+        // Tagging must not refresh the task, or it would stop looking stale.
+        let updated_at: String = conn
+            .query_row(
+                "SELECT updated_at FROM tasks WHERE title = 'Migrate CI'",
+                [],
+                |row| row.get(0),
+            )
+            .expect("updated_at");
+        assert_eq!(updated_at, stale);
+        let log = get_automation_log_in_conn(&conn, 10).expect("log");
+        assert_eq!(log.len(), 2);
+        assert!(log
+            .iter()
+            .any(|run| run.rule_name == "Stale" && run.change == "tagged #stale"));
+
+        // Applied rules have nothing left to do; disabled rules are skipped.
+        assert!(evaluate_automation_rules_in_conn(&conn, now, true)
+            .expect("rerun")
+            .is_empty());
+        conn.execute(
+            "UPDATE tasks SET priority = 'low' WHERE title = 'Ship hotfix'",
+            [],
+        )
+        .expect("reset priority");
+        let mut disabled = rule(
+            "Escalate due soon",
+            "due_within_hours",
+            24,
+            Some("low"),
+            "set_priority",
+            "medium",
+        );
+        disabled.enabled = false;
+        update_automation_rule_in_conn(&conn, 1, &disabled).expect("disable");
+        assert!(evaluate_automation_rules_in_conn(&conn, now, false)
+            .expect("preview")
+            .is_empty());
+    }
+
+    #[test]
+    fn notification_templates_render_placeholders_and_travel_with_backups() {
+        let mut conn = command_test_connection();
+        assert_eq!(
+            render_notification_template(
+                "{habit}: {days} days, {missing} {unclosed",
+                &[("habit", "Run"), ("days", "3")]
+            ),
+            "Run: 3 days, {missing} {unclosed"
+        );
+
+        let templates = get_notification_templates_in_conn(&conn).expect("templates");
+        let streak = templates
+            .iter()
+            .find(|template| template.kind == "streak_risk")
+            .expect("streak template");
+        assert_eq!(streak.placeholders, vec!["habit", "days"]);
+        assert_eq!(
+            streak.preview_body,
+            "Your 12-day \"Read 20 pages\" streak ends at midnight unless you log it today."
+        );
+
+        let payload = upgrade_backup_payload(serde_json::json!({
+            "format_version": BACKUP_FORMAT_VERSION,
+            "app_settings": [
+                { "key": "notification_streak_risk_body", "value": "Keep {habit} alive ({days}d)" },
+                { "key": "setting_from_the_future", "value": "1" }
+            ]
+        }))
+        .expect("payload");
+        import_backup_into_conn(&mut conn, payload, false).expect("import");
+        let (title, body) =
+            notifications::notification_templates_in_conn(&conn, "streak_risk").expect("custom");
+        assert_eq!(title, "Streak at risk");
+        assert_eq!(body, "Keep {habit} alive ({days}d)");
+
+        // Clearing a template restores the built-in text.
+        settings::write_setting(&conn, "notification_streak_risk_body", " ").expect("reset");
+        let (_, body) =
+            notifications::notification_templates_in_conn(&conn, "streak_risk").expect("default");
+        assert!(body.starts_with("Your {days}-day"));
+    }
+
+    #[test]
+    fn pages_nest_move_without_cycles_and_reparent_on_delete() {
+        let mut conn = command_test_connection();
+        let docs = create_page_in_conn(&conn, "Docs", "", None).expect("docs");
+        let api = create_page_in_conn(&conn, "API", "", Some(docs.id)).expect("api");
+        let auth = create_page_in_conn(&conn, "Auth", "", Some(api.id)).expect("auth");
+        let notes = create_page_in_conn(&conn, "notes", "", None).expect("notes");
+        assert!(create_page_in_conn(&conn, "Orphan", "", Some(999)).is_err());
+
+        let titles = |pages: Vec<crate::models::Page>| {
+            pages.into_iter().map(|page| page.title).collect::<Vec<_>>()
+        };
+        assert_eq!(
+            titles(get_page_children_in_conn(&conn, None).expect("roots")),
+            vec!["Docs", "notes"]
+        );
+        assert_eq!(
+            titles(get_page_children_in_conn(&conn, Some(docs.id)).expect("children")),
+            vec!["API"]
+        );
+
+        // A page cannot become its own ancestor.
+        assert!(move_page_in_conn(&conn, docs.id, Some(auth.id)).is_err());
+        assert!(move_page_in_conn(&conn, docs.id, Some(docs.id)).is_err());
+        move_page_in_conn(&conn, auth.id, Some(notes.id)).expect("move");
+        move_page_in_conn(&conn, api.id, None).expect("move to top");
+        let parents = get_pages_in_conn(&conn)
+            .expect("pages")
+            .into_iter()
+            .map(|page| (page.title, page.parent_id))
+            .collect::<HashMap<_, _>>();
+        assert_eq!(parents["Auth"], Some(notes.id));
+        assert_eq!(parents["API"], None);
+
+        // Subpages of a deleted page move up a level.
+        move_page_in_conn(&conn, notes.id, Some(docs.id)).expect("nest notes");
+        delete_page_in_conn(&conn, notes.id).expect("delete");
+        assert_eq!(
+            titles(get_page_children_in_conn(&conn, Some(docs.id)).expect("children")),
+            vec!["Auth"]
+        );
+
+        // Backups restore the hierarchy and drop links that would form a cycle.
+        let payload = upgrade_backup_payload(serde_json::json!({
+            "format_version": BACKUP_FORMAT_VERSION,
+            "pages": [
+                { "id": 1, "title": "Root", "content": "", "parent_id": 2 },
+                { "id": 2, "title": "Child", "content": "", "parent_id": 1 },
+                { "id": 3, "title": "Leaf", "content": "", "parent_id": 2 }
+            ]
+        }))
+        .expect("payload");
+        import_backup_into_conn(&mut conn, payload, true).expect("import");
+        let parents = get_pages_in_conn(&conn)
+            .expect("pages")
+            .into_iter()
+            .map(|page| (page.id, page.parent_id))
+            .collect::<HashMap<_, _>>();
+        assert_eq!(
+            parents,
+            HashMap::from([(1, Some(2)), (2, None), (3, Some(2))])
+        );
+    }
+
+    #[test]
+    fn page_tags_are_case_insensitive_and_filter_pages() {
+        let conn = command_test_connection();
+        let rust = create_page_in_conn(&conn, "Rust notes", "", None).expect("rust");
+        let sql = create_page_in_conn(&conn, "SQLite tips", "", None).expect("sql");
+
+        add_page_tag_in_conn(&conn, rust.id, "#Learning").expect("tag");
+        add_page_tag_in_conn(&conn, rust.id, "learning").expect("duplicate tag");
+        add_page_tag_in_conn(&conn, sql.id, "learning").expect("tag");
+        add_page_tag_in_conn(&conn, sql.id, "databases").expect("tag");
+        assert!(add_page_tag_in_conn(&conn, rust.id, "  ").is_err());
+        assert!(add_page_tag_in_conn(&conn, 999, "learning").is_err());
+
+        let titles = |pages: Vec<crate::models::Page>| {
+            pages.into_iter().map(|page| page.title).collect::<Vec<_>>()
+        };
+        let mut learning = titles(get_pages_by_tag_in_conn(&conn, "LEARNING").expect("by tag"));
+        learning.sort();
+        assert_eq!(learning, vec!["Rust notes", "SQLite tips"]);
+
+        remove_page_tag_in_conn(&conn, sql.id, "Learning").expect("untag");
+        assert_eq!(
+            titles(get_pages_by_tag_in_conn(&conn, "learning").expect("by tag")),
+            vec!["Rust notes"]
+        );
+
+        // Deleting a page drops its tags with it.
+        delete_page_in_conn(&conn, sql.id).expect("delete");
+        let tags = get_page_tags_in_conn(&conn)
+            .expect("tags")
+            .into_iter()
+            .map(|tag| (tag.page_id, tag.tag))
+            .collect::<Vec<_>>();
+        assert_eq!(tags, vec![(rust.id, "Learning".to_string())]);
+    }
+
+    #[test]
+    fn detached_window_views_parse_into_stable_labels() {
+        assert_eq!(DetachedView::parse("journal"), Ok(DetachedView::Journal));
+        assert_eq!(DetachedView::parse(" tasks "), Ok(DetachedView::Tasks));
+        assert_eq!(DetachedView::parse("page:42"), Ok(DetachedView::Page(42)));
+        assert!(DetachedView::parse("page:").is_err());
+        assert!(DetachedView::parse("page:-3").is_err());
+        assert!(DetachedView::parse("settings").is_err());
+
+        assert_eq!(DetachedView::Journal.label(), "view-journal");
+        assert_eq!(DetachedView::Tasks.label(), "view-tasks");
+        assert_eq!(DetachedView::Page(42).label(), "view-page-42");
+    }
+
+    #[test]
+    fn wiki_links_are_parsed_and_resolve_backlinks_by_title() {
+        assert_eq!(
+            extract_wiki_links("See [[Rust notes]], [[rust NOTES|again]] and [[ SQLite | db ]]."),
+            vec!["Rust notes", "SQLite"]
+        );
+        assert_eq!(extract_wiki_links("[[a [[b]] [[]] [[open"), vec!["b"]);
+        assert!(extract_wiki_links("[[split\nline]]").is_empty());
+
+        let mut conn = command_test_connection();
+        let rust = create_page_in_conn(&conn, "Rust notes", "", None).expect("rust");
+        let index = create_page_in_conn(&conn, "Index", "", None).expect("index");
+        set_page_links_in_conn(&conn, index.id, "[[Rust notes]] and [[Later]]").expect("links");
+        let titles = |pages: Vec<crate::models::Page>| {
+            pages.into_iter().map(|page| page.title).collect::<Vec<_>>()
+        };
+        assert_eq!(
+            titles(get_backlinks_in_conn(&conn, rust.id).expect("backlinks")),
+            vec!["Index"]
+        );
+
+        // A link written before its target existed resolves once the page is created.
+        let later = create_page_in_conn(&conn, "later", "", None).expect("later");
+        assert_eq!(
+            titles(get_backlinks_in_conn(&conn, later.id).expect("backlinks")),
+            vec!["Index"]
+        );
+
+        // Saving replaces the old links; self-links are not backlinks.
+        set_page_links_in_conn(&conn, index.id, "[[Index]]").expect("relink");
+        assert!(get_backlinks_in_conn(&conn, rust.id)
+            .expect("backlinks")
+            .is_empty());
+        assert!(get_backlinks_in_conn(&conn, index.id)
+            .expect("backlinks")
+            .is_empty());
+        assert!(get_backlinks_in_conn(&conn, 999).is_err());
+
+        // Backup imports index the plaintext content.
+        let payload = upgrade_backup_payload(serde_json::json!({
+            "format_version": BACKUP_FORMAT_VERSION,
+            "pages": [{ "id": 50, "title": "Imported", "content": "Links to [[Rust notes]]" }]
+        }))
+        .expect("payload");
+        import_backup_into_conn(&mut conn, payload, false).expect("import");
+        assert_eq!(
+            titles(get_backlinks_in_conn(&conn, rust.id).expect("backlinks")),
+            vec!["Imported"]
+        );
+    }
+
+    #[test]
+    fn page_revisions_snapshot_restore_and_respect_the_limit() {
+        let mut conn = command_test_connection();
+        let page = create_page_in_conn(&conn, "Plan", "v1", None).expect("page");
+        let edit = |conn: &Connection, content: &str| {
+            snapshot_page_in_conn(conn, page.id).expect("snapshot");
+            conn.execute(
+                "UPDATE pages SET content = ?1 WHERE id = ?2",
+                params![content, page.id],
+            )
+            .expect("edit");
+        };
+        edit(&conn, "v2");
+        edit(&conn, "v3");
+        assert!(snapshot_page_in_conn(&conn, 999).is_err());
+
+        let contents = |conn: &Connection| {
+            get_page_revisions_in_conn(conn, page.id)
+                .expect("revisions")
+                .into_iter()
+                .map(|revision| revision.content)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(contents(&conn), vec!["v2", "v1"]);
+
+        // Restoring snapshots the current version first, so it can be undone.
+        let oldest = get_page_revisions_in_conn(&conn, page.id).expect("revisions")[1].id;
+        let restored = restore_page_revision_in_conn(&conn, oldest).expect("restore");
+        assert_eq!(restored.content, "v1");
+        assert_eq!(contents(&conn), vec!["v3", "v2", "v1"]);
+        assert!(restore_page_revision_in_conn(&conn, 999).is_err());
+
+        settings::write_setting(&conn, "page_revision_limit", "2").expect("limit");
+        edit(&conn, "v4");
+        assert_eq!(contents(&conn), vec!["v1", "v3"]);
+
+        // Revisions are encrypted with the rest of the journal and go with their page.
+        enable_journal_encryption_in_conn(&mut conn, "correct horse battery").expect("enable");
+        let revision = get_page_revision_in_conn(&conn, oldest + 2)
+            .expect("revision")
+            .expect("exists");
+        assert!(revision.content.starts_with("enc:v1:"));
+        delete_page_in_conn(&conn, page.id).expect("delete");
+        assert!(get_page_revisions_in_conn(&conn, page.id)
+            .expect("revisions")
+            .is_empty());
+    }
+
+    #[test]
+    fn daily_sheet_lists_plan_tasks_meetings_and_habits() {
+        let conn = command_test_connection();
+        let now = Utc::now().to_rfc3339();
+        let at = |time: &str| {
+            chrono::NaiveDateTime::parse_from_str(&format!("2026-05-06 {}", time), "%Y-%m-%d %H:%M")
+                .expect("datetime")
+                .and_local_timezone(chrono::Local)
+                .earliest()
+                .expect("local time")
+                .to_rfc3339()
+        };
+        conn.execute(
+            "INSERT INTO entries (date, yesterday, today, created_at)
+             VALUES ('2026-05-06', '', '- Ship the <release>\n- Review PRs', ?1)",
+            params![now],
+        )
+        .expect("insert entry");
+        for (title, status, priority, due_date) in [
+            ("Someday idea", "todo", "low", None),
+            (
+                "Fix login & signup",
+                "in_progress",
+                "medium",
+                Some("2026-05-05"),
+            ),
+            ("Urgent hotfix", "todo", "urgent", Some("2026-05-20")),
+            ("Already done", "done", "urgent", Some("2026-05-01")),
+        ] {
+            conn.execute(
+                "INSERT INTO tasks (title, description, status, priority, due_date, created_at, updated_at)
+                 VALUES (?1, '', ?2, ?3, ?4, ?5, ?5)",
+                params![title, status, priority, due_date, now],
+            )
+            .expect("insert task");
+        }
+        conn.execute(
+            "INSERT INTO habits (id, title, description, target_per_week, color, created_at, updated_at)
+             VALUES (1, 'Read', '', 7, '#000000', ?1, ?1)",
+            params![now],
+        )
+        .expect("insert habit");
+        conn.execute(
+            "INSERT INTO habit_logs (habit_id, date, created_at) VALUES (1, '2026-05-04', ?1)",
+            params![now],
+        )
+        .expect("insert habit log");
+        conn.execute(
+            "INSERT INTO meetings (title, start_at, end_at, recurrence, status, created_at, updated_at)
+             VALUES ('Design review', ?1, ?2, 'none', 'planned', ?3, ?3)",
+            params![at("14:00"), at("15:00"), now],
+        )
+        .expect("insert meeting");
+
+        let date = NaiveDate::from_ymd_opt(2026, 5, 6).expect("date");
+        let html =
+            daily_sheet_html_in_conn(&conn, date, &JournalCipher::default()).expect("daily sheet");
+
+        assert!(html.contains("Wednesday, May 6, 2026"));
+        assert!(html.contains("<li>Ship the &lt;release&gt;</li>"));
+        assert!(html.contains("<li>Review PRs</li>"));
+        assert!(html.contains("14:00–15:00</time>Design review"));
+        assert!(html.contains("<td>Read</td><td>✓</td>"));
+        assert!(!html.contains("Already done"));
+        let overdue = html.find("Fix login &amp; signup").expect("overdue task");
+        let urgent = html.find("Urgent hotfix").expect("urgent task");
+        let someday = html.find("Someday idea").expect("low priority task");
+        assert!(overdue < urgent && urgent < someday);
+        assert!(html.contains("overdue 2026-05-05"));
+
+        let folder = std::env::temp_dir().join(format!("daily-sheet-test-{}", std::process::id()));
+        std::fs::create_dir_all(&folder).expect("temp folder");
+        assert_eq!(
+            daily_sheet_path(folder.to_str().expect("utf-8 path"), date).expect("folder path"),
+            folder.join("daily-sheet-2026-05-06.html")
+        );
+        assert_eq!(
+            daily_sheet_path("/tmp/sheets/monday", date).expect("bare path"),
+            std::path::PathBuf::from("/tmp/sheets/monday.html")
+        );
+        assert!(daily_sheet_path("/tmp/sheet.pdf", date).is_err());
+        assert!(daily_sheet_path("  ", date).is_err());
+        std::fs::remove_dir_all(&folder).expect("remove temp folder");
+    }
+
+    #[test]
+    fn vault_mirror_writes_renames_and_prunes_markdown_files() {
+        let conn = command_test_connection();
+        let root = std::env::temp_dir().join(format!("vault-mirror-test-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&root);
+
+        conn.execute(
+            "INSERT INTO entries (date, yesterday, today, created_at)
+             VALUES ('2026-05-06', 'Fixed the build', 'Ship it', '2026-05-06T09:00:00Z')",
+            [],
+        )
+        .expect("insert entry");
+        let page = create_page_in_conn(&conn, "Release plan", "Steps", None).expect("page");
+
+        // Off until a folder is chosen.
+        mirror_entry_in_conn(&conn, "2026-05-06").expect("mirror off");
+        assert!(!root.exists());
+
+        settings::write_setting(
+            &conn,
+            "vault_mirror_dir",
+            root.to_str().expect("utf-8 path"),
+        )
+        .expect("enable mirror");
+        mirror_entry_in_conn(&conn, "2026-05-06").expect("mirror entry");
+        mirror_page_in_conn(&conn, page.id).expect("mirror page");
+        let entry_file = root.join("journal").join("2026-05-06.md");
+        let entry_markdown = std::fs::read_to_string(&entry_file).expect("entry file");
+        assert!(entry_markdown.contains("## Yesterday\n\nFixed the build"));
+        let page_file = root
+            .join("pages")
+            .join(format!("{}-Release_plan.md", page.id));
+        let page_markdown = std::fs::read_to_string(&page_file).expect("page file");
+        assert!(page_markdown.contains("title: \"Release plan\""));
+        assert!(page_markdown.ends_with("# Release plan\n\nSteps\n"));
+
+        conn.execute(
+            "UPDATE pages SET title = 'Launch plan' WHERE id = ?1",
+            params![page.id],
+        )
+        .expect("rename page");
+        mirror_page_in_conn(&conn, page.id).expect("mirror renamed page");
+        assert!(!page_file.exists());
+        assert!(root
+            .join("pages")
+            .join(format!("{}-Launch_plan.md", page.id))
+            .exists());
+
+        conn.execute(
+            "UPDATE entries SET deleted_at = '2026-05-07T00:00:00Z' WHERE date = '2026-05-06'",
+            [],
+        )
+        .expect("trash entry");
+        mirror_entry_in_conn(&conn, "2026-05-06").expect("mirror trashed entry");
+        assert!(!entry_file.exists());
+
+        // A full sync removes stale mirror files but keeps anything else in the folder.
+        std::fs::write(root.join("journal").join("2026-01-01.md"), "old").expect("stale file");
+        std::fs::write(root.join("pages").join("notes.txt"), "mine").expect("own file");
+        let summary = sync_vault_mirror_in_conn(&conn, &root).expect("sync");
+        assert_eq!(summary.entries_written, 0);
+        assert_eq!(summary.pages_written, 0);
+        assert_eq!(summary.files_removed, 1);
+        assert!(root.join("pages").join("notes.txt").exists());
+
+        std::fs::remove_dir_all(&root).expect("remove mirror folder");
+    }
+
+    #[test]
+    fn trashed_pages_are_hidden_restorable_and_purged() {
+        let conn = command_test_connection();
+        let docs = create_page_in_conn(&conn, "Docs", "", None).expect("docs");
+        let guide =
+            create_page_in_conn(&conn, "Guide", "See [[Docs]]", Some(docs.id)).expect("guide");
+        let setup = create_page_in_conn(&conn, "Setup", "", Some(guide.id)).expect("setup");
+        set_page_links_in_conn(&conn, guide.id, "See [[Docs]]").expect("links");
+        add_page_tag_in_conn(&conn, guide.id, "howto").expect("tag");
+
+        trash_page_in_conn(&conn, guide.id).expect("trash");
+        assert!(trash_page_in_conn(&conn, guide.id).is_err());
+        assert!(get_page_in_conn(&conn, guide.id).expect("page").is_none());
+        assert_eq!(get_pages_in_conn(&conn).expect("pages").len(), 2);
+        assert!(get_backlinks_in_conn(&conn, docs.id)
+            .expect("backlinks")
+            .is_empty());
+        assert!(get_page_tags_in_conn(&conn).expect("tags").is_empty());
+        assert!(get_pages_by_tag_in_conn(&conn, "howto")
+            .expect("by tag")
+            .is_empty());
+        // Subpages move up to the trashed page's parent.
+        let setup_parent = get_page_in_conn(&conn, setup.id)
+            .expect("setup")
+            .expect("setup page")
+            .parent_id;
+        assert_eq!(setup_parent, Some(docs.id));
+        assert!(move_page_in_conn(&conn, setup.id, Some(guide.id)).is_err());
+
+        let trashed = get_trashed_pages_in_conn(&conn).expect("trash");
+        assert_eq!(trashed.len(), 1);
+        assert_eq!(trashed[0].page.title, "Guide");
+
+        let restored = restore_page_in_conn(&conn, guide.id).expect("restore");
+        assert_eq!(restored.parent_id, Some(docs.id));
+        assert!(restore_page_in_conn(&conn, guide.id).is_err());
+        assert_eq!(
+            get_backlinks_in_conn(&conn, docs.id)
+                .expect("backlinks")
+                .len(),
+            1
+        );
+        assert_eq!(get_page_tags_in_conn(&conn).expect("tags").len(), 1);
+
+        // A page whose parent went to the trash comes back at the top level.
+        trash_page_in_conn(&conn, guide.id).expect("trash guide");
+        trash_page_in_conn(&conn, docs.id).expect("trash docs");
+        assert_eq!(
+            restore_page_in_conn(&conn, guide.id)
+                .expect("restore")
+                .parent_id,
+            None
+        );
+
+        conn.execute(
+            "UPDATE pages SET deleted_at = '2026-01-01T00:00:00Z' WHERE id = ?1",
+            params![docs.id],
+        )
+        .expect("age trashed page");
+        trash_page_in_conn(&conn, setup.id).expect("trash setup");
+        assert_eq!(
+            empty_page_trash_in_conn(&conn, Some("2026-02-01T00:00:00Z")).expect("purge old"),
+            vec![docs.id]
+        );
+        assert_eq!(
+            empty_page_trash_in_conn(&conn, None).expect("empty trash"),
+            vec![setup.id]
+        );
+        assert!(get_trashed_pages_in_conn(&conn).expect("trash").is_empty());
+        assert_eq!(get_pages_in_conn(&conn).expect("pages").len(), 1);
+    }
+
+    #[test]
+    fn search_pages_ranks_title_matches_and_skips_trashed_pages() {
+        let mut conn = command_test_connection();
+        let runbook = create_page_in_conn(
+            &conn,
+            "Deploy runbook",
+            "Steps for the release train and rollback.",
+            None,
+        )
+        .expect("runbook");
+        let notes = create_page_in_conn(
+            &conn,
+            "Meeting notes",
+            "We talked about how to deploy the new service.",
+            None,
+        )
+        .expect("notes");
+        let scratch =
+            create_page_in_conn(&conn, "Scratch", "deploy checklist draft", None).expect("scratch");
+
+        let results = search_pages_in_conn(&conn, "deploy").expect("search");
+        assert_eq!(
+            results
+                .iter()
+                .map(|result| result.page.id)
+                .collect::<Vec<_>>()[0],
+            runbook.id
+        );
+        assert_eq!(results.len(), 3);
+        let notes_hit = results
+            .iter()
+            .find(|result| result.page.id == notes.id)
+            .expect("notes hit");
+        assert!(notes_hit.snippet.contains("<mark>deploy</mark>"));
+
+        // Prefix match on the last word; FTS operators are plain text.
+        assert_eq!(
+            search_pages_in_conn(&conn, "rollb").expect("prefix").len(),
+            1
+        );
+        assert!(search_pages_in_conn(&conn, "release NOT \"train")
+            .expect("operators")
+            .is_empty());
+        assert!(search_pages_in_conn(&conn, "  ** ")
+            .expect("blank")
+            .is_empty());
+
+        // The index follows edits and the trash.
+        conn.execute(
+            "UPDATE pages SET content = 'Renamed to shipping' WHERE id = ?1",
+            params![scratch.id],
+        )
+        .expect("edit page");
+        assert!(search_pages_in_conn(&conn, "shipping")
+            .expect("edited")
+            .iter()
+            .any(|result| result.page.id == scratch.id));
+        trash_page_in_conn(&conn, notes.id).expect("trash");
+        assert_eq!(
+            search_pages_in_conn(&conn, "deploy").expect("search").len(),
+            1
+        );
+
+        let cipher =
+            enable_journal_encryption_in_conn(&mut conn, "correct horse battery").expect("enable");
+        let encrypted =
+            encryption::search_encrypted_pages_in_conn(&conn, &cipher, "ROLLBACK").expect("search");
+        assert_eq!(encrypted.len(), 1);
+        assert_eq!(
+            encrypted[0].page.content,
+            "Steps for the release train and rollback."
+        );
+    }
+
+    #[test]
+    fn mirror_git_commits_once_per_day_and_lists_file_history() {
+        let root = std::env::temp_dir().join(format!("mirror-git-test-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&root);
+        std::fs::create_dir_all(root.join("journal")).expect("journal folder");
+        std::fs::create_dir_all(root.join("pages")).expect("pages folder");
+        let day = |d: u32| NaiveDate::from_ymd_opt(2026, 5, d).expect("date");
+        let write = |path: &str, text: &str| {
+            std::fs::write(root.join(path), text).expect("write mirror file");
+        };
+
+        write("journal/2026-05-04.md", "monday");
+        write("README.md", "not part of the mirror");
+        assert!(commit_mirror_changes(&root, day(4))
+            .expect("first commit")
+            .is_some());
+        assert!(commit_mirror_changes(&root, day(4))
+            .expect("nothing changed")
+            .is_none());
+
+        // Later saves the same day fold into that day's commit.
+        write("pages/1-Plan.md", "plan");
+        commit_mirror_changes(&root, day(4)).expect("amend");
+        let history = mirror_history(&root, "").expect("history");
+        assert_eq!(history.len(), 1);
+        assert_eq!(history[0].summary, "Journal mirror 2026-05-04");
+
+        write("journal/2026-05-05.md", "tuesday");
+        std::fs::remove_file(root.join("pages/1-Plan.md")).expect("remove page");
+        commit_mirror_changes(&root, day(5)).expect("next day");
+        assert_eq!(mirror_history(&root, "").expect("history").len(), 2);
+
+        // A pushed commit is never rewritten.
+        let repo = git2::Repository::open(&root).expect("repo");
+        let head = repo.head().expect("head").target().expect("head oid");
+        repo.reference("refs/remotes/origin/main", head, true, "test")
+            .expect("remote ref");
+        write("journal/2026-05-05.md", "tuesday, edited");
+        commit_mirror_changes(&root, day(5)).expect("after push");
+
+        let summaries = |path: &str| {
+            mirror_history(&root, path)
+                .expect("history")
+                .into_iter()
+                .map(|commit| commit.summary)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            summaries(""),
+            vec![
+                "Journal mirror 2026-05-05",
+                "Journal mirror 2026-05-05",
+                "Journal mirror 2026-05-04"
+            ]
+        );
+        assert_eq!(summaries("journal/2026-05-05.md").len(), 2);
+        assert_eq!(summaries("pages/1-Plan.md").len(), 2);
+        assert_eq!(
+            summaries(root.join("journal/2026-05-04.md").to_str().expect("utf-8")),
+            vec!["Journal mirror 2026-05-04"]
+        );
+        assert!(summaries("README.md").is_empty());
+        assert!(mirror_history(&root, "../outside.md").is_err());
+
+        std::fs::remove_dir_all(&root).expect("remove mirror folder");
+    }
+
+    #[test]
+    fn import_slack_standups_splits_sections_and_filters_by_author() {
+        let mut conn = command_test_connection();
+        conn.execute(
+            "INSERT INTO entries (date, yesterday, today, created_at)
+             VALUES ('2026-03-03', 'Paired on the release', '', '2026-03-03T08:00:00Z')",
+            [],
+        )
+        .expect("insert entry");
+        let headers = StandupHeaders::new("yesterday", "today", "blockers");
+        let lines = |text: &str| -> Vec<String> { text.lines().map(str::to_string).collect() };
+        assert_eq!(
+            parse_standup(
+                &lines("Morning!\n*Yesterday:* Fixed CI\nYesterday I also reviewed\n:rocket: Today\n• Ship it\n*Blockers*: none"),
+                &headers
+            ),
+            Some(ParsedStandup {
+                yesterday: "Fixed CI\nYesterday I also reviewed".to_string(),
+                today: "• Ship it".to_string(),
+                blockers: "none".to_string(),
+                notes: "Morning!".to_string(),
+            })
+        );
+        assert_eq!(parse_standup(&lines("lunch anyone?"), &headers), None);
+
+        // 2026-03-02 and 2026-03-03 around noon UTC.
+        let export = || -> Vec<SlackMessage> {
+            serde_json::from_value(serde_json::json!([
+                {
+                    "type": "message",
+                    "user": "U1",
+                    "user_profile": { "real_name": "Jane Doe", "display_name": "jane" },
+                    "text": "*Yesterday:* Wrote the &lt;importer&gt; for <https://example.com/pr/1|PR 1>\n*Today:* Tests\n*Blockers:* None",
+                    "ts": "1772452800.000100"
+                },
+                { "type": "message", "user": "U2", "text": "*Yesterday:* Not mine", "ts": "1772452900.000100" },
+                { "type": "message", "user": "U1", "text": "lunch?", "ts": "1772453000.000100" },
+                { "type": "message", "subtype": "channel_join", "user": "U1", "text": "Today: joined", "ts": "1772453100.000100" },
+                {
+                    "type": "message",
+                    "subtype": "bot_message",
+                    "username": "Geekbot",
+                    "text": "*Jane Doe* posted an update for *Daily Standup*",
+                    "ts": "1772539200.000200",
+                    "attachments": [
+                        { "title": "What did you do yesterday?", "text": "Paired on the release" },
+                        { "title": "What will you do today?", "text": "Write docs" }
+                    ]
+                }
+            ]))
+            .expect("export")
+        };
+
+        let (summary, discarded) = import_slack_standups_in_conn(
+            &mut conn,
+            export(),
+            "jane doe",
+            &JournalCipher::default(),
+        )
+        .expect("import");
+        assert_eq!(summary.messages_read, 5);
+        assert_eq!(summary.standups_found, 2);
+        assert_eq!(summary.days_written, 2);
+        assert!(discarded.is_empty());
+
+        let first = entries::get_entry_in_conn(&conn, "2026-03-02")
+            .expect("load")
+            .expect("entry");
+        assert_eq!(first.yesterday, "Wrote the <importer> for PR 1");
+        assert_eq!(first.today, "Tests");
+        assert_eq!(first.blockers, "None");
+        let second = entries::get_entry_in_conn(&conn, "2026-03-03")
+            .expect("load")
+            .expect("entry");
+        assert_eq!(second.yesterday, "Paired on the release");
+        assert_eq!(second.today, "Write docs");
+        assert_eq!(second.notes, "");
+
+        let (summary, _) =
+            import_slack_standups_in_conn(&mut conn, export(), "U1", &JournalCipher::default())
+                .expect("reimport");
+        assert_eq!(summary.standups_found, 1);
+        assert_eq!(summary.days_written, 0);
+    }
+
+    #[test]
+    fn org_import_maps_todos_to_tasks_and_headings_to_pages_and_round_trips() {
+        let mut conn = command_test_connection();
+        let org = "#+TITLE: Notes\n\
+                   #+TODO: TODO NEXT | DONE KILLED\n\
+                   * NEXT [#A] Write the importer :work:\n\
+                   DEADLINE: <2026-05-08 Fri +1w> SCHEDULED: <2026-05-06 Wed>\n\
+                   :PROPERTIES:\n\
+                   :ID: abc\n\
+                   :END:\n\
+                   See [[https://orgmode.org][the manual]].\n\
+                   ** Details\n\
+                   Parse drawers too.\n\
+                   * KILLED Old idea\n\
+                   CLOSED: [2026-05-01 Fri 10:00]\n\
+                   * Projects :ref:\n\
+                   Overview of [[*Dev Journal]].\n\
+                   ** Dev Journal\n\
+                   #+begin_src rust\n\
+                   ,* not a heading\n\
+                   #+end_src\n\
+                   *** TODO Ship Org export\n";
+        let headings = parse_org(org);
+        assert_eq!(headings.len(), 6);
+        assert_eq!(headings[0].keyword.as_deref(), Some("NEXT"));
+        assert_eq!(headings[0].priority, Some('A'));
+        assert_eq!(headings[0].tags, vec!["work"]);
+        assert!(headings[1].keyword.is_none());
+        assert!(headings[2].done);
+
+        let summary = import_org_in_conn(
+            &mut conn,
+            &[org.to_string()],
+            None,
+            &JournalCipher::default(),
+        )
+        .expect("import");
+        assert_eq!(summary.headings_read, 6);
+        assert_eq!(summary.tasks_created, 3);
+        assert_eq!(summary.pages_created, 2);
+
+        let task = |title: &str| -> crate::models::Task {
+            conn.query_row(
+                &format!("SELECT {} FROM tasks WHERE title = ?1", tasks::TASK_COLUMNS),
+                params![title],
+                tasks::task_from_row,
+            )
+            .expect("task")
+        };
+        let importer = task("Write the importer");
+        assert_eq!(importer.status, "in_progress");
+        assert_eq!(importer.priority, "high");
+        assert_eq!(importer.due_date.as_deref(), Some("2026-05-08"));
+        assert_eq!(importer.recurrence, "weekly");
+        assert_eq!(
+            importer.description,
+            "See [the manual](https://orgmode.org).\n\n**Details**\n\nParse drawers too."
+        );
+        let old = task("Old idea");
+        assert_eq!(old.status, "done");
+        assert!(old
+            .completed_at
+            .is_some_and(|at| at.starts_with("2026-05-01T10:00")));
+
+        let projects = get_page_children_in_conn(&conn, None).expect("pages");
+        assert_eq!(projects.len(), 1);
+        assert_eq!(projects[0].content, "Overview of [[Dev Journal]].");
+        let children = get_page_children_in_conn(&conn, Some(projects[0].id)).expect("children");
+        assert_eq!(children[0].title, "Dev Journal");
+        assert_eq!(children[0].content, "```rust\n* not a heading\n```");
+        assert_eq!(
+            get_pages_by_tag_in_conn(&conn, "ref")
+                .expect("tagged")
+                .len(),
+            1
+        );
+
+        let export = org_export_in_conn(&conn, &JournalCipher::default()).expect("export");
+        assert!(export.contains(
+            "* STARTED [#A] Write the importer :work:\nDEADLINE: <2026-05-08 Fri +1w>\nSee [[https://orgmode.org][the manual]].\n"
+        ));
+        assert!(export.contains("* DONE Old idea\nCLOSED: [2026-05-01 Fri 10:00]\n"));
+        assert!(export.contains(
+            "* Projects :ref:\nOverview of [[Dev Journal]].\n** Dev Journal\n#+begin_src rust\n,* not a heading\n#+end_src\n"
+        ));
+
+        let summary = import_org_in_conn(&mut conn, &[export], None, &JournalCipher::default())
+            .expect("reimport");
+        assert_eq!(summary.tasks_created, 0);
+        assert_eq!(summary.pages_created, 0);
+        assert_eq!(summary.pages_updated, 0);
+    }
+
+    #[test]
+    fn pinned_pages_are_listed_first_and_flags_do_not_touch_updated_at() {
+        let conn = command_test_connection();
+        let older = create_page_in_conn(&conn, "Runbook", "", None).expect("older");
+        let newer = create_page_in_conn(&conn, "Scratch", "", None).expect("newer");
+        conn.execute(
+            "UPDATE pages SET updated_at = '2026-01-01T00:00:00Z' WHERE id = ?1",
+            params![older.id],
+        )
+        .expect("age page");
+
+        let titles = |conn: &Connection| -> Vec<String> {
+            get_pages_in_conn(conn)
+                .expect("pages")
+                .into_iter()
+                .map(|page| page.title)
+                .collect()
+        };
+        assert_eq!(titles(&conn), vec!["Scratch", "Runbook"]);
+
+        set_page_pinned_in_conn(&conn, older.id, true).expect("pin");
+        set_page_favorite_in_conn(&conn, newer.id, true).expect("favorite");
+        assert_eq!(titles(&conn), vec!["Runbook", "Scratch"]);
+        let runbook = get_page_in_conn(&conn, older.id)
+            .expect("load")
+            .expect("page");
+        assert!(runbook.pinned && !runbook.favorite);
+        assert_eq!(runbook.updated_at, "2026-01-01T00:00:00Z");
+        assert!(
+            get_page_in_conn(&conn, newer.id)
+                .expect("load")
+                .expect("page")
+                .favorite
+        );
+
+        trash_page_in_conn(&conn, newer.id).expect("trash");
+        assert!(set_page_pinned_in_conn(&conn, newer.id, true).is_err());
+    }
+
+    #[test]
+    fn todo_txt_import_maps_priorities_projects_and_contexts_and_round_trips() {
+        let mut conn = command_test_connection();
+        conn.execute(
+            "INSERT INTO projects (name, description, color, status, created_at, updated_at)
+             VALUES ('Dev Journal', '', '#fff', 'active', '2026-05-01', '2026-05-01')",
+            [],
+        )
+        .expect("insert project");
+        let text =
+            "(A) 2026-05-01 Write the importer +dev-journal +side @work due:2026-05-08 rec:1w\n\
+                    \n\
+                    x 2026-05-03 2026-05-01 Fix login see:JIRA-12 @review pri:B\n\
+                    (E) Tidy notes\n";
+        let first =
+            parse_todo_txt_line("x 2026-05-03 2026-05-01 Fix login see:JIRA-12 @review pri:B")
+                .expect("line");
+        assert!(first.done);
+        assert_eq!(first.priority, Some('B'));
+        assert_eq!(first.title, "Fix login see:JIRA-12");
+        assert!(parse_todo_txt_line("   ").is_none());
+
+        let summary = import_todo_txt_in_conn(&mut conn, text).expect("import");
+        assert_eq!(summary.tasks_read, 3);
+        assert_eq!(summary.tasks_created, 3);
+
+        let task = |title: &str| -> crate::models::Task {
+            conn.query_row(
+                &format!("SELECT {} FROM tasks WHERE title = ?1", tasks::TASK_COLUMNS),
+                params![title],
+                tasks::task_from_row,
+            )
+            .expect("task")
+        };
+        let importer = task("Write the importer");
+        assert_eq!(importer.priority, "urgent");
+        assert_eq!(importer.status, "todo");
+        assert_eq!(importer.project_id, Some(1));
+        assert_eq!(importer.due_date.as_deref(), Some("2026-05-08"));
+        assert_eq!(importer.recurrence, "weekly");
+        let fixed = task("Fix login see:JIRA-12");
+        assert_eq!(fixed.status, "done");
+        assert_eq!(fixed.priority, "high");
+        assert_eq!(task("Tidy notes").priority, "low");
+
+        let export = todo_txt_export_in_conn(&conn).expect("export");
+        let lines: Vec<&str> = export.lines().collect();
+        assert_eq!(
+            lines[0],
+            "(A) 2026-05-01 Write the importer +Dev-Journal @side @work due:2026-05-08 rec:1w"
+        );
+        assert!(lines[1].ends_with("Tidy notes"));
+        assert_eq!(
+            lines[2],
+            "x 2026-05-03 2026-05-01 Fix login see:JIRA-12 @review pri:B"
+        );
+
+        let summary = import_todo_txt_in_conn(&mut conn, &export).expect("reimport");
+        assert_eq!(summary.tasks_created, 0);
+        assert_eq!(summary.tasks_updated, 0);
+    }
+
+    #[test]
+    fn page_assets_are_stored_and_collected_once_unreferenced() {
+        let conn = command_test_connection();
+        let root = std::env::temp_dir().join(format!(
+            "dev-journal-page-assets-test-{}",
+            Utc::now().timestamp_nanos_opt().unwrap_or_default()
+        ));
+        let page = create_page_in_conn(&conn, "Diagrams", "", None).expect("page");
+
+        assert!(save_page_asset_in_conn(&conn, &root, page.id, b"%PDF", "spec.pdf").is_err());
+        let kept = save_page_asset_in_conn(&conn, &root, page.id, b"png", "Схема 1.png")
+            .expect("save kept");
+        let dropped = save_page_asset_in_conn(&conn, &root, page.id, b"gif", "old.gif")
+            .expect("save dropped");
+        assert_eq!(kept.mime_type, "image/png");
+        let stored_path = |url: &str| url.split("localhost/").nth(1).expect("path").to_string();
+        let kept_path = stored_path(&kept.url);
+        assert!(kept_path.starts_with(&format!("{}/", page.id)));
+        assert!(kept_path.is_ascii());
+        assert!(root.join(&kept_path).is_file());
+
+        conn.execute(
+            "UPDATE pages SET content = ?1 WHERE id = ?2",
+            params![format!("![Схема]({})", kept.url), page.id],
+        )
+        .expect("embed");
+        assert!(collect_orphan_page_assets_in_conn(&conn, Utc::now())
+            .expect("within grace")
+            .is_empty());
+        let later = Utc::now() + Duration::hours(2);
+        assert_eq!(
+            collect_orphan_page_assets_in_conn(&conn, later).expect("collect"),
+            vec![stored_path(&dropped.url)]
+        );
+
+        trash_page_in_conn(&conn, page.id).expect("trash");
+        assert!(collect_orphan_page_assets_in_conn(&conn, later)
+            .expect("trashed")
+            .is_empty());
+        empty_page_trash_in_conn(&conn, None).expect("empty trash");
+        assert_eq!(
+            collect_orphan_page_assets_in_conn(&conn, Utc::now()).expect("deleted page"),
+            vec![kept_path]
+        );
+        let _ = std::fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn week_plan_lists_carried_over_tasks_goals_and_capacity() {
+        let conn = command_test_connection();
+        super::settings::write_setting(&conn, "days_off", "2026-05-08").expect("day off");
+        for (title, status, due, estimate) in [
+            ("Finish migration", "in_progress", None, 0),
+            ("Reply to review", "todo", Some("2026-04-30"), 0),
+            ("Ship release", "todo", Some("2026-05-05"), 600),
+            ("Later thing", "todo", Some("2026-05-20"), 0),
+            ("Closed work", "done", Some("2026-05-05"), 0),
+        ] {
+            conn.execute(
+                "INSERT INTO tasks (title, description, status, priority, due_date, time_estimate_minutes, completed_at, created_at, updated_at)
+                 VALUES (?1, '', ?2, 'medium', ?3, ?4, CASE WHEN ?2 = 'done' THEN '2026-04-29T10:00:00Z' END, '2026-04-01', '2026-04-01')",
+                params![title, status, due, estimate],
+            )
+            .expect("insert task");
+        }
+        conn.execute(
+            "INSERT INTO goals (title, description, status, progress, target_date, created_at, updated_at)
+             VALUES ('Learn Rust', '', 'active', 40, '2026-05-07', '2026-04-01T00:00:00Z', '2026-04-01T00:00:00Z')",
+            [],
+        )
+        .expect("insert goal");
+
+        let page = generate_week_plan_in_conn(&conn, "2026-05-04", &JournalCipher::default())
+            .expect("plan");
+        assert_eq!(page.title, "Week plan: May 4 – May 10, 2026");
+        let content = &page.content;
+        assert!(content.contains("- Tasks completed: 1\n"));
+        assert!(content.contains(
+            "## Carried over\n\n- [ ] Reply to review (due Thu Apr 30)\n- [ ] Finish migration (in progress)\n"
+        ));
+        assert!(content.contains("## Due this week\n\n- [ ] Ship release (due Tue May 5)\n"));
+        assert!(!content.contains("Later thing") && !content.contains("Closed work"));
+        assert!(content.contains("- Learn Rust: 40% (due Thu May 7, no update for 33 days)"));
+        assert!(content.contains("| Tue May 5 | 0h | 8h | 1 (10h) ⚠️ |"));
+        assert!(content.contains("| Fri May 8 | 0h | day off | – |"));
+        assert!(content.contains("Available this week: 32h"));
+
+        let again = generate_week_plan_in_conn(&conn, "2026-05-04", &JournalCipher::default())
+            .expect("regenerate");
+        assert_eq!(again.id, page.id);
+        assert_eq!(
+            get_page_revisions_in_conn(&conn, page.id)
+                .expect("revisions")
+                .len(),
+            1
+        );
+    }
+
+    #[test]
+    fn filtered_export_keeps_only_tagged_or_project_content() {
+        let conn = command_test_connection();
+        conn.execute_batch(
+            "INSERT INTO projects (id, name, description, color, status, created_at, updated_at)
+             VALUES (1, 'Project X', '', '#fff', 'active', '2026-05-01', '2026-05-01');
+             INSERT INTO entries (id, date, yesterday, today, project_id, created_at)
+             VALUES (1, '2026-05-01', 'Spec, \"draft\"', 'Build', 1, '2026-05-01'),
+                    (2, '2026-05-02', 'Dentist', 'Gym', NULL, '2026-05-02');
+             INSERT INTO pages (id, title, content, created_at, updated_at)
+             VALUES (1, 'Runbook', 'Restart the worker', '2026-05-01', '2026-05-01'),
+                    (2, 'Diary', 'Personal', '2026-05-01', '2026-05-01');
+             INSERT INTO page_tags (page_id, tag, created_at) VALUES (1, 'project x', '2026-05-01');
+             INSERT INTO tasks (id, title, description, status, priority, project_id, created_at, updated_at)
+             VALUES (1, 'Hand over', '', 'todo', 'high', 1, '2026-05-01', '2026-05-01'),
+                    (2, 'Buy milk', '', 'todo', 'low', NULL, '2026-05-01', '2026-05-01');
+             INSERT INTO task_tags (task_id, tag, created_at) VALUES (2, 'home', '2026-05-01');",
+        )
+        .expect("seed");
+
+        let dir = std::env::temp_dir().join(format!(
+            "dev-journal-filtered-export-{}",
+            Utc::now().timestamp_nanos_opt().unwrap_or_default()
+        ));
+        let filter = ExportFilter {
+            tag: None,
+            project_id: Some(1),
+        };
+        let summary = export_filtered_in_conn(
+            &conn,
+            &dir.join("csv"),
+            ExportFormat::Csv,
+            &filter,
+            &JournalCipher::default(),
+        )
+        .expect("csv export");
+        assert_eq!(
+            (
+                summary.entries_exported,
+                summary.pages_exported,
+                summary.tasks_exported
+            ),
+            (1, 1, 1)
+        );
+        let entries = fs::read_to_string(dir.join("csv/entries.csv")).expect("entries.csv");
+        assert!(entries.contains("2026-05-01,Project X,,\"Spec, \"\"draft\"\"\",Build"));
+        assert!(!entries.contains("Dentist"));
+        let pages = fs::read_to_string(dir.join("csv/pages.csv")).expect("pages.csv");
+        assert!(pages.contains("Runbook") && !pages.contains("Diary"));
+
+        let summary = export_filtered_in_conn(
+            &conn,
+            &dir.join("md"),
+            ExportFormat::Markdown,
+            &filter,
+            &JournalCipher::default(),
+        )
+        .expect("markdown export");
+        assert_eq!(summary.files_written, 3);
+        assert!(dir.join("md/journal/2026-05-01.md").exists());
+        assert!(dir.join("md/pages/1-Runbook.md").exists());
+        let tasks = fs::read_to_string(dir.join("md/tasks.md")).expect("tasks.md");
+        assert!(tasks.contains("- [ ] Hand over (Project X, high)"));
+
+        let filter = ExportFilter {
+            tag: Some("#HOME".to_string()),
+            project_id: None,
+        };
+        let summary = export_filtered_in_conn(
+            &conn,
+            &dir.join("json"),
+            ExportFormat::Json,
+            &filter,
+            &JournalCipher::default(),
+        )
+        .expect("json export");
+        assert_eq!(
+            (
+                summary.entries_exported,
+                summary.pages_exported,
+                summary.tasks_exported
+            ),
+            (0, 0, 1)
+        );
+        let document: serde_json::Value = serde_json::from_str(
+            &fs::read_to_string(dir.join("json/dev-journal-export.json")).expect("json"),
+        )
+        .expect("parse");
+        assert_eq!(document["tasks"][0]["title"], "Buy milk");
+        assert_eq!(document["tasks"][0]["tags"][0], "home");
+        assert_eq!(document["filter"]["tag"], "HOME");
+
+        assert!(export_filtered_in_conn(
+            &conn,
+            &dir,
+            ExportFormat::Json,
+            &ExportFilter {
+                tag: None,
+                project_id: Some(99),
+            },
+            &JournalCipher::default(),
+        )
+        .is_err());
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn pages_export_to_markdown_with_copied_assets_and_standalone_html() {
+        let html = markdown_to_html(
+            "## Plan <v2>\n\n- [x] **Ship** it\n- [ ] Read [docs](https://example.com)\n\n| Day | Hours |\n| --- | --- |\n| Mon | 8 |\n\n```rust\nfn main() {}\n```\nsnake_case and *done*",
+            &|url| url.to_string(),
+        );
+        assert!(html.contains("<h2>Plan &lt;v2&gt;</h2>"));
+        assert!(html.contains(
+            "<li class=\"task\"><input type=\"checkbox\" disabled checked> <strong>Ship</strong> it</li>"
+        ));
+        assert!(html.contains("<a href=\"https://example.com\">docs</a>"));
+        assert!(html.contains("<thead><tr><th>Day</th><th>Hours</th></tr></thead>"));
+        assert!(html.contains("<td>Mon</td><td>8</td>"));
+        assert!(html.contains("<pre><code class=\"language-rust\">fn main() {}</code></pre>"));
+        assert!(html.contains("<p>snake_case and <em>done</em></p>"));
+
+        let conn = command_test_connection();
+        let dir = std::env::temp_dir().join(format!(
+            "dev-journal-page-export-{}",
+            Utc::now().timestamp_nanos_opt().unwrap_or_default()
+        ));
+        let root = dir.join("app-assets");
+        let page = create_page_in_conn(&conn, "Architecture", "", None).expect("page");
+        let asset = save_page_asset_in_conn(&conn, &root, page.id, b"png-bytes", "diagram.png")
+            .expect("asset");
+        conn.execute(
+            "UPDATE pages SET content = ?1 WHERE id = ?2",
+            params![format!("Overview\n\n![Diagram]({})", asset.url), page.id],
+        )
+        .expect("embed");
+        create_page_in_conn(&conn, "Notes", "Plain", None).expect("second page");
+
+        let html_path = export_page_in_conn(
+            &conn,
+            page.id,
+            PageExportFormat::Html,
+            &dir.join("single.htm").display().to_string(),
+            &root,
+            &JournalCipher::default(),
+            None,
+        )
+        .expect("html export");
+        assert!(html_path.ends_with("single.htm"));
+        let document = fs::read_to_string(&html_path).expect("read html");
+        assert!(document.starts_with("<!DOCTYPE html>"));
+        assert!(document.contains("<title>Architecture</title>"));
+        assert!(
+            document.contains("<img src=\"data:image/png;base64,cG5nLWJ5dGVz\" alt=\"Diagram\">")
+        );
+
+        let out = dir.join("pages");
+        let summary = export_all_pages_in_conn(
+            &conn,
+            &out,
+            PageExportFormat::Markdown,
+            &root,
+            &JournalCipher::default(),
+        )
+        .expect("export all");
+        assert_eq!(summary.pages_written, 2);
+        assert_eq!(summary.assets_copied, 1);
+        let markdown = fs::read_to_string(out.join(format!("{}-Architecture.md", page.id)))
+            .expect("read markdown");
+        let stored_path = asset.url.split("localhost/").nth(1).expect("path");
+        assert!(markdown.contains(&format!("![Diagram](assets/{})", stored_path)));
+        assert_eq!(
+            fs::read(out.join("assets").join(stored_path)).expect("copied asset"),
+            b"png-bytes"
+        );
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn obsidian_vault_imports_folders_notes_links_images_and_daily_notes() {
+        let mut conn = command_test_connection();
+        let dir = std::env::temp_dir().join(format!(
+            "dev-journal-obsidian-{}",
+            Utc::now().timestamp_nanos_opt().unwrap_or_default()
+        ));
+        let vault = dir.join("vault");
+        let assets_root = dir.join("assets");
+        for folder in [".obsidian", "Daily", "Projects", "attachments"] {
+            fs::create_dir_all(vault.join(folder)).expect("folder");
+        }
+        let files = [
+            (".obsidian/workspace.md", "ignored"),
+            ("Daily/2026-05-01.md", "## Today\n\nReviewed [[Projects/Alpha]]"),
+            ("Projects.md", "Everything in flight"),
+            (
+                "Projects/Alpha.md",
+                "---\ntags: [work, alpha]\n---\nSee [[Projects/Beta#Plan|the plan]] and [[Inbox]].\n\n![[diagram.png|300]]\n![[Beta]]",
+            ),
+            ("Projects/Beta.md", "## Plan\n\nShip it"),
+            ("Inbox.md", "![photo](attachments/diagram.png) ![web](https://example.com/a.png)"),
+        ];
+        for (path, content) in files {
+            fs::write(vault.join(path), content).expect("write note");
+        }
+        fs::write(vault.join("attachments/diagram.png"), b"png").expect("write image");
+
+        let import = import_obsidian_vault_in_conn(
+            &mut conn,
+            &vault,
+            &assets_root,
+            &JournalCipher::default(),
+        )
+        .expect("import");
+        let summary = &import.summary;
+        assert_eq!(summary.notes_read, 5);
+        assert_eq!(summary.pages_created, 4);
+        assert_eq!(summary.entries_imported, 1);
+        assert_eq!(summary.assets_imported, 2);
+        assert_eq!(import.written_pages.len(), 4);
+        assert_eq!(import.written_entries, vec!["2026-05-01".to_string()]);
+
+        let top_level = get_page_children_in_conn(&conn, None).expect("top level");
+        let mut titles: Vec<&str> = top_level.iter().map(|page| page.title.as_str()).collect();
+        titles.sort();
+        assert_eq!(titles, vec!["Inbox", "Projects"]);
+        let projects = top_level
+            .iter()
+            .find(|page| page.title == "Projects")
+            .expect("projects");
+        assert_eq!(projects.content, "Everything in flight");
+        let children = get_page_children_in_conn(&conn, Some(projects.id)).expect("children");
+        let alpha = children
+            .iter()
+            .find(|page| page.title == "Alpha")
+            .expect("alpha");
+        assert!(alpha.content.starts_with(
+            "See [[Beta|the plan]] and [[Inbox]].\n\n![diagram.png](journal-asset://localhost/"
+        ));
+        assert!(alpha.content.ends_with("\n[[Beta]]"));
+        assert_eq!(
+            get_page_tags_in_conn(&conn)
+                .expect("tags")
+                .iter()
+                .filter(|tag| tag.page_id == alpha.id)
+                .count(),
+            2
+        );
+        let beta = children
+            .iter()
+            .find(|page| page.title == "Beta")
+            .expect("beta");
+        assert_eq!(
+            get_backlinks_in_conn(&conn, beta.id).expect("backlinks")[0].id,
+            alpha.id
+        );
+        let inbox = top_level
+            .iter()
+            .find(|page| page.title == "Inbox")
+            .expect("inbox");
+        assert!(inbox
+            .content
+            .starts_with("![photo](journal-asset://localhost/"));
+        assert!(inbox.content.ends_with("![web](https://example.com/a.png)"));
+        let entry = get_entry_in_conn(&conn, "2026-05-01")
+            .expect("entry")
+            .expect("daily note");
+        assert_eq!(entry.today, "Reviewed [[Alpha]]");
+
+        let again = import_obsidian_vault_in_conn(
+            &mut conn,
+            &vault,
+            &assets_root,
+            &JournalCipher::default(),
+        )
+        .expect("reimport");
+        assert_eq!(
+            (
+                again.summary.pages_created,
+                again.summary.pages_updated,
+                again.summary.assets_imported,
+                again.summary.skipped_existing
+            ),
+            (0, 0, 0, 1)
+        );
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn appearance_settings_default_validate_and_persist() {
+        let conn = command_test_connection();
+        let defaults = get_appearance_settings_in_conn(&conn).expect("defaults");
+        assert_eq!(defaults.theme_preset, "monochrome");
+        assert_eq!(defaults.mode, "system");
+        assert_eq!(defaults.accent_color, None);
+        assert_eq!((defaults.font_size, defaults.compact), (14, false));
+        assert!(defaults.updated_at.is_none());
+
+        let mut settings = defaults.clone();
+        settings.theme_preset = "Ocean".to_string();
+        settings.mode = "dark".to_string();
+        settings.accent_color = Some("#0AF".to_string());
+        settings.font_size = 16;
+        settings.compact = true;
+        let saved = update_appearance_settings_in_conn(&conn, &settings).expect("save");
+        assert_eq!(saved.theme_preset, "ocean");
+        assert_eq!(saved.accent_color.as_deref(), Some("#00aaff"));
+        assert!(saved.compact);
+        assert!(saved.updated_at.is_some());
+
+        for invalid in [
+            AppearanceSettings {
+                theme_preset: "neon".to_string(),
+                ..settings.clone()
+            },
+            AppearanceSettings {
+                accent_color: Some("#12345".to_string()),
+                ..settings.clone()
+            },
+            AppearanceSettings {
+                font_size: 30,
+                ..settings.clone()
+            },
+        ] {
+            assert!(update_appearance_settings_in_conn(&conn, &invalid).is_err());
+        }
+        let stored = get_appearance_settings_in_conn(&conn).expect("stored");
+        assert_eq!(stored.theme_preset, "ocean");
+        assert_eq!(stored.font_size, 16);
+    }
+
+    #[test]
+    fn whats_new_lists_releases_and_migrations_since_a_version() {
+        let conn = command_test_connection();
+        let changelog = "# Changelog\n\n## [Unreleased]\n- Not shipped\n\n## [1.2.0] - 2026-05-01\n\n### Added\n- Weekly plan\n  page\n\n## [1.1.0] - 2026-04-01\n\n### Fixed\n- Export crash\n\n## [1.0.0] - 2026-02-24\n- First release\n";
+        let releases = parse_changelog(changelog);
+        assert_eq!(
+            releases
+                .iter()
+                .map(|r| r.version.as_str())
+                .collect::<Vec<_>>(),
+            vec!["1.2.0", "1.1.0", "1.0.0"]
+        );
+        assert_eq!(releases[0].sections[0].items, vec!["Weekly plan page"]);
+        assert_eq!(releases[2].sections[0].title, "Changes");
+
+        // A fresh journal was created by the running version: nothing new to show.
+        let app_version = env!("CARGO_PKG_VERSION");
+        let fresh = get_whats_new_in_conn(&conn, changelog, app_version, None).expect("fresh");
+        assert_eq!(fresh.since_version.as_deref(), Some(app_version));
+        assert!(fresh.migrations.is_empty());
+
+        conn.execute(
+            "UPDATE schema_migrations SET app_version = CASE
+                 WHEN version <= 50 THEN NULL ELSE '1.2.0' END",
+            [],
+        )
+        .expect("simulate upgrade");
+        let upgrade =
+            get_whats_new_in_conn(&conn, changelog, "1.2.0", Some("1.0.0")).expect("upgrade");
+        assert_eq!(
+            upgrade
+                .releases
+                .iter()
+                .map(|r| r.version.as_str())
+                .collect::<Vec<_>>(),
+            vec!["1.2.0", "1.1.0"]
+        );
+        assert_eq!(
+            upgrade
+                .migrations
+                .iter()
+                .map(|m| m.version)
+                .collect::<Vec<_>>(),
+            (51..=crate::db::SCHEMA_VERSION).collect::<Vec<_>>()
+        );
+        assert_eq!(upgrade.migrations[0].note, crate::db::migration_note(51));
+
+        settings::write_setting(&conn, "last_seen_app_version", "1.2.0").expect("seen");
+        let seen = get_whats_new_in_conn(&conn, changelog, "1.2.0", None).expect("seen");
+        assert!(seen.releases.is_empty() && seen.migrations.is_empty());
+        assert!(get_whats_new_in_conn(&conn, changelog, "1.2.0", Some("next")).is_err());
+    }
+
+    #[test]
+    fn page_stats_count_prose_words_links_and_staleness() {
+        let conn = command_test_connection();
+        let cipher = JournalCipher::default();
+        assert_eq!(
+            markdown_word_count(
+                "# Setup\n\n- [ ] install the CLI\n\n```sh\ncargo build --release\n```\n| a | b |"
+            ),
+            6
+        );
+
+        let guide_text = format!("# Guide\n\n{}\n\nSee [[Runbook]].", "word ".repeat(399));
+        let guide = create_page_in_conn(&conn, "Guide", &guide_text, None).expect("guide");
+        set_page_links_in_conn(&conn, guide.id, &guide_text).expect("links");
+        let runbook = create_page_in_conn(&conn, "Runbook", "", None).expect("runbook");
+        let child =
+            create_page_in_conn(&conn, "Child", "Short note", Some(guide.id)).expect("child");
+        let now = Utc::now();
+        conn.execute(
+            "UPDATE pages SET updated_at = ?1 WHERE id = ?2",
+            params![(now - Duration::days(120)).to_rfc3339(), runbook.id],
+        )
+        .expect("age runbook");
+
+        let stats = get_page_stats_in_conn(&conn, guide.id, &cipher, now).expect("page stats");
+        assert_eq!(stats.word_count, 402);
+        assert_eq!((stats.reading_minutes, stats.heading_count), (3, 1));
+        assert_eq!((stats.outgoing_links, stats.backlinks), (1, 0));
+        let runbook_stats =
+            get_page_stats_in_conn(&conn, runbook.id, &cipher, now).expect("runbook");
+        assert_eq!(
+            (runbook_stats.word_count, runbook_stats.reading_minutes),
+            (0, 0)
+        );
+        assert_eq!(runbook_stats.backlinks, 1);
+        assert!(get_page_stats_in_conn(&conn, 999, &cipher, now).is_err());
+
+        let health = get_pages_stats_in_conn(&conn, &cipher, now).expect("health");
+        assert_eq!(health.page_count, 3);
+        assert_eq!(health.total_words, 404);
+        assert_eq!(health.empty_pages, 1);
+        assert_eq!(health.stale_pages, 1);
+        assert_eq!(
+            health.orphan_pages, 1,
+            "only Guide: Runbook is linked, Child has a parent"
+        );
+        assert_eq!(health.largest[0].page_id, guide.id);
+        assert_eq!(health.stalest[0].page_id, runbook.id);
+        assert_ne!(health.recently_edited[0].page_id, runbook.id);
+        assert!(health
+            .recently_edited
+            .iter()
+            .any(|page| page.page_id == child.id));
+    }
+
+    #[test]
+    fn archived_pages_and_their_subpages_leave_the_default_listing() {
+        let conn = command_test_connection();
+        let old = create_page_in_conn(&conn, "Old project", "notes", None).expect("old");
+        let child = create_page_in_conn(&conn, "Old child", "", Some(old.id)).expect("child");
+        let kept = create_page_in_conn(&conn, "Current", "", None).expect("kept");
+        let titles = |pages: Vec<Page>| {
+            let mut titles = pages.into_iter().map(|page| page.title).collect::<Vec<_>>();
+            titles.sort();
+            titles
+        };
+
+        set_page_archived_in_conn(&conn, old.id, true).expect("archive");
+        assert_eq!(
+            titles(get_listed_pages_in_conn(&conn, false).expect("listed")),
+            vec!["Current"]
+        );
+        let everything = get_listed_pages_in_conn(&conn, true).expect("all");
+        assert_eq!(everything.len(), 3);
+        assert!(everything
+            .iter()
+            .any(|page| page.id == old.id && page.archived));
+        assert!(everything
+            .iter()
+            .any(|page| page.id == child.id && !page.archived));
+        assert!(get_page_in_conn(&conn, old.id).expect("get").is_some());
+
+        set_page_archived_in_conn(&conn, old.id, false).expect("unarchive");
+        assert_eq!(
+            titles(get_listed_pages_in_conn(&conn, false).expect("listed")),
+            vec!["Current", "Old child", "Old project"]
+        );
+        trash_page_in_conn(&conn, kept.id).expect("trash");
+        assert!(set_page_archived_in_conn(&conn, kept.id, true).is_err());
+    }
+
+    #[test]
+    fn feature_flags_default_off_and_toggle_per_user() {
+        let conn = command_test_connection();
+
+        let flags = get_flags_in_conn(&conn).unwrap();
+        let keys: Vec<&str> = flags.iter().map(|flag| flag.key.as_str()).collect();
+        assert_eq!(keys, ["sync", "llm", "rest_api"]);
+        assert!(flags
+            .iter()
+            .all(|flag| !flag.enabled && !flag.default_enabled && flag.updated_at.is_none()));
+        assert_eq!(
+            require_feature_in_conn(&conn, "llm").unwrap_err(),
+            "The llm feature is disabled"
+        );
+
+        let flags = set_flag_in_conn(&conn, " llm ", true).unwrap();
+        let llm = flags.iter().find(|flag| flag.key == "llm").unwrap();
+        assert!(llm.enabled && llm.updated_at.is_some());
+        assert!(is_feature_enabled_in_conn(&conn, "llm").unwrap());
+        assert!(require_feature_in_conn(&conn, "llm").is_ok());
+        assert!(!is_feature_enabled_in_conn(&conn, "sync").unwrap());
+
+        set_flag_in_conn(&conn, "llm", false).unwrap();
+        assert!(require_feature_in_conn(&conn, "llm").is_err());
+        assert_eq!(
+            set_flag_in_conn(&conn, "telepathy", true).unwrap_err(),
+            "Unknown feature flag: telepathy"
+        );
+        assert!(is_feature_enabled_in_conn(&conn, "telepathy").is_err());
+    }
+
+    #[test]
+    fn update_check_picks_the_newest_release_on_the_channel() {
+        let release = |tag: &str, prerelease: bool, draft: bool| PublishedRelease {
+            tag_name: tag.to_string(),
+            prerelease,
+            draft,
+            html_url: Some(format!("https://example.com/{}", tag)),
+            body: None,
+            published_at: None,
+        };
+        let releases = [
+            release("v1.0.4", false, false),
+            release("v1.1.0", false, false),
+            release("v1.2.0-beta.1", true, false),
+            release("v1.3.0", false, true),
+        ];
+
+        let stable = update_check_from_releases(&releases, "stable", "1.0.4");
+        assert!(stable.update_available);
+        assert_eq!(stable.latest_version.as_deref(), Some("1.1.0"));
+        assert_eq!(
+            stable.release_url.as_deref(),
+            Some("https://example.com/v1.1.0")
+        );
+        let beta = newest_release(&releases, "beta", "1.0.4").unwrap();
+        assert_eq!(beta.tag_name, "v1.2.0-beta.1");
+        assert_eq!(
+            newest_release(&releases, "beta", "1.2.0-beta.1").map(|r| r.tag_name.as_str()),
+            None
+        );
+        assert_eq!(
+            newest_release(&[release("1.2.0", false, false)], "stable", "1.2.0-beta.1")
+                .map(|r| r.tag_name.as_str()),
+            Some("1.2.0")
+        );
+        let current = update_check_from_releases(&releases, "stable", "1.1.0");
+        assert!(!current.update_available && current.latest_version.is_none());
+
+        let conn = command_test_connection();
+        let at = |time: &str| chrono::NaiveTime::parse_from_str(time, "%H:%M").unwrap();
+        assert!(!in_quiet_hours_in_conn(&conn, at("23:30")).unwrap());
+        settings::write_setting(&conn, "quiet_hours_start", "22:00").unwrap();
+        settings::write_setting(&conn, "quiet_hours_end", "07:00").unwrap();
+        assert!(in_quiet_hours_in_conn(&conn, at("23:30")).unwrap());
+        assert!(in_quiet_hours_in_conn(&conn, at("06:59")).unwrap());
+        assert!(!in_quiet_hours_in_conn(&conn, at("07:00")).unwrap());
+        assert!(!in_quiet_hours_in_conn(&conn, at("12:00")).unwrap());
+    }
+
+    #[test]
+    fn reordered_pages_keep_their_position_within_a_level() {
+        let mut conn = command_test_connection();
+        let parent = create_page_in_conn(&conn, "Parent", "", None).expect("parent");
+        let first = create_page_in_conn(&conn, "First", "", Some(parent.id)).expect("first");
+        let second = create_page_in_conn(&conn, "Second", "", Some(parent.id)).expect("second");
+        let third = create_page_in_conn(&conn, "Third", "", Some(parent.id)).expect("third");
+        let order = |conn: &rusqlite::Connection| -> Vec<i64> {
+            get_pages_in_conn(conn)
+                .expect("pages")
+                .into_iter()
+                .filter(|page| page.parent_id == Some(parent.id))
+                .map(|page| page.id)
+                .collect()
+        };
+
+        reorder_pages_in_conn(&mut conn, &[third.id, first.id]).expect("reorder");
+        assert_eq!(order(&conn), [third.id, first.id, second.id]);
+        let reordered = get_page_in_conn(&conn, third.id).unwrap().unwrap();
+        assert_eq!(reordered.sort_index, Some(0));
+        assert_eq!(reordered.updated_at, third.updated_at);
+
+        reorder_pages_in_conn(&mut conn, &[second.id, third.id, first.id]).expect("reorder");
+        assert_eq!(order(&conn), [second.id, third.id, first.id]);
+
+        assert_eq!(
+            reorder_pages_in_conn(&mut conn, &[first.id, parent.id]).unwrap_err(),
+            "Pages to reorder must share a parent"
+        );
+        assert!(reorder_pages_in_conn(&mut conn, &[first.id, first.id]).is_err());
+        assert!(reorder_pages_in_conn(&mut conn, &[first.id, 9999]).is_err());
+        assert_eq!(order(&conn), [second.id, third.id, first.id]);
+    }
+
+    #[test]
+    fn accessible_summary_counts_tasks_habits_and_todays_entry() {
+        let conn = command_test_connection();
+        let today = NaiveDate::from_ymd_opt(2026, 5, 6).unwrap();
+        conn.execute_batch(
+            "INSERT INTO tasks (title, description, status, priority, due_date, created_at, updated_at)
+             VALUES ('Ship it', '', 'todo', 'high', '2026-05-06', '2026-05-01', '2026-05-01'),
+                    ('Review PR', '', 'in_progress', 'medium', '2026-05-06', '2026-05-01', '2026-05-01'),
+                    ('Old bug', '', 'todo', 'low', '2026-05-01', '2026-05-01', '2026-05-01'),
+                    ('Done', '', 'done', 'low', '2026-05-06', '2026-05-01', '2026-05-01');
+             INSERT INTO habits (id, title, description, target_per_week, color, created_at, updated_at)
+             VALUES (1, 'Read', '', 7, '#000', '2026-05-01', '2026-05-01');
+             INSERT INTO habit_logs (habit_id, date, created_at)
+             VALUES (1, '2026-05-04', '2026-05-04'), (1, '2026-05-05', '2026-05-05');",
+        )
+        .unwrap();
+
+        let summary = get_accessible_summary_in_conn(&conn, "today", today).unwrap();
+        assert_eq!(
+            summary.text,
+            "2 tasks due today, 1 overdue, habit streak 2 days, entry not yet written."
+        );
+        let tasks = get_accessible_summary_in_conn(&conn, "Tasks", today).unwrap();
+        assert_eq!(tasks.view, "tasks");
+        assert_eq!(tasks.parts[0], "3 tasks open");
+        let habits = get_accessible_summary_in_conn(&conn, "habits", today).unwrap();
+        assert_eq!(
+            habits.text,
+            "0 of 1 habit done today, longest streak 2 days on \"Read\"."
+        );
+
+        conn.execute(
+            "INSERT INTO entries (date, yesterday, today, created_at)
+             VALUES ('2026-05-06', '', 'Writing', '2026-05-06')",
+            [],
+        )
+        .unwrap();
+        let journal = get_accessible_summary_in_conn(&conn, "journal", today).unwrap();
+        assert_eq!(
+            journal.text,
+            "Today's entry written, journal streak 1 day, 1 entry."
+        );
+        assert!(get_accessible_summary_in_conn(&conn, "calendar", today).is_err());
+    }
+
+    #[test]
+    fn recent_pages_follow_views_not_edits() {
+        let conn = command_test_connection();
+        let notes = create_page_in_conn(&conn, "Notes", "", None).expect("notes");
+        let runbook = create_page_in_conn(&conn, "Runbook", "", None).expect("runbook");
+        let draft = create_page_in_conn(&conn, "Draft", "", None).expect("draft");
+
+        record_page_view_in_conn(&conn, runbook.id, "2026-05-01T09:00:00+00:00").unwrap();
+        record_page_view_in_conn(&conn, notes.id, "2026-05-02T09:00:00+00:00").unwrap();
+        record_page_view_in_conn(&conn, draft.id, "2026-05-03T09:00:00+00:00").unwrap();
+        record_page_view_in_conn(&conn, runbook.id, "2026-05-04T09:00:00+00:00").unwrap();
+        trash_page_in_conn(&conn, draft.id).expect("trash");
+
+        let recent = get_recent_pages_in_conn(&conn, 10).unwrap();
+        let ids: Vec<i64> = recent.iter().map(|page| page.page_id).collect();
+        assert_eq!(ids, [runbook.id, notes.id]);
+        assert_eq!(recent[0].view_count, 2);
+        assert_eq!(recent[0].viewed_at, "2026-05-04T09:00:00+00:00");
+        assert_eq!(get_recent_pages_in_conn(&conn, 1).unwrap().len(), 1);
+        assert!(record_page_view_in_conn(&conn, 9999, "2026-05-04T10:00:00+00:00").is_err());
+    }
+
+    #[test]
+    fn week_numbering_setting_moves_week_boundaries() {
+        let conn = command_test_connection();
+        let date = |value: &str| NaiveDate::parse_from_str(value, "%Y-%m-%d").expect("date");
+
+        assert_eq!(
+            WeekNumbering::Iso.week_number(date("2026-05-03")),
+            (2026, 18)
+        );
+        assert_eq!(
+            WeekNumbering::Us.week_number(date("2026-05-03")),
+            (2026, 19)
+        );
+        // Jan 1, 2027 is a Friday: the US week holding it starts in 2026.
+        assert_eq!(WeekNumbering::Us.week_number(date("2026-12-27")), (2027, 1));
+        assert_eq!(
+            WeekNumbering::Iso.week_number(date("2026-12-27")),
+            (2026, 52)
+        );
+
+        let completed = vec![
+            "2026-05-03".to_string(),
+            "2026-05-06".to_string(),
+            "2026-05-10".to_string(),
+        ];
+        let wednesday = date("2026-05-06");
+        assert_eq!(
+            compute_this_week_count(&completed, wednesday, WeekNumbering::Iso),
+            2
+        );
+        assert_eq!(
+            compute_this_week_count(&completed, wednesday, WeekNumbering::Us),
+            2
+        );
+        assert_eq!(
+            compute_this_week_count(&completed[..2], wednesday, WeekNumbering::Iso),
+            1
+        );
+
+        let iso = get_week_info_in_conn(&conn, wednesday).unwrap();
+        assert_eq!(
+            (
+                iso.numbering.as_str(),
+                iso.week_start.as_str(),
+                iso.week_end.as_str()
+            ),
+            ("iso", "2026-05-04", "2026-05-10")
+        );
+
+        settings::write_setting(&conn, "week_numbering", "us").unwrap();
+        let us = get_week_info_in_conn(&conn, wednesday).unwrap();
+        assert_eq!(
+            (us.week_start.as_str(), us.week_end.as_str(), us.week),
+            ("2026-05-03", "2026-05-09", 19)
+        );
+        let review =
+            generate_weekly_review_in_conn(&conn, "2026-05-06", &JournalCipher::default()).unwrap();
+        assert!(review.starts_with("# Weekly review: May 3 – May 9, 2026\n"));
+    }
+
+    #[test]
+    fn locked_pages_keep_content_and_history_out_of_reach_until_unlocked() {
+        let mut conn = command_test_connection();
+        let journal = JournalCipher::default();
+        let page = create_page_in_conn(&conn, "Credentials", "first draft", None).expect("page");
+        snapshot_page_in_conn(&conn, page.id).expect("snapshot");
+        conn.execute(
+            "UPDATE pages SET content = 'vpn token 4242' WHERE id = ?1",
+            params![page.id],
+        )
+        .expect("edit");
+        let updated_at = get_page_in_conn(&conn, page.id)
+            .unwrap()
+            .unwrap()
+            .updated_at;
+
+        assert!(enable_page_lock_in_conn(&mut conn, page.id, "short", &journal).is_err());
+        let key = enable_page_lock_in_conn(&mut conn, page.id, "correct horse", &journal).unwrap();
+        let stored = get_page_in_conn(&conn, page.id).unwrap().unwrap();
+        assert!(stored.locked);
+        assert!(stored.content.starts_with("lock:v1:"));
+        assert_eq!(stored.updated_at, updated_at);
+        assert!(get_page_revisions_in_conn(&conn, page.id)
+            .unwrap()
+            .iter()
+            .all(|revision| revision.content.starts_with("lock:v1:")));
+        assert!(search_pages_in_conn(&conn, "4242").unwrap().is_empty());
+        assert_eq!(
+            search_pages_in_conn(&conn, "credentials").unwrap()[0].snippet,
+            ""
+        );
+        assert!(reveal_page(stored, None)
+            .unwrap()
+            .content
+            .starts_with("lock:v1:"));
+        let stored = get_page_in_conn(&conn, page.id).unwrap().unwrap();
+        assert_eq!(
+            reveal_page(stored, Some(&key)).unwrap().content,
+            "vpn token 4242"
+        );
+
+        assert_eq!(
+            enable_page_lock_in_conn(&mut conn, page.id, "correct horse", &journal)
+                .err()
+                .as_deref(),
+            Some("Page is already locked")
+        );
+        assert_eq!(
+            unlock_page_in_conn(&conn, page.id, "wrong horse", &journal)
+                .err()
+                .as_deref(),
+            Some("Wrong passphrase")
+        );
+        let (unlocked, _) = unlock_page_in_conn(&conn, page.id, "correct horse", &journal).unwrap();
+        assert_eq!(unlocked.content, "vpn token 4242");
+
+        let unlocked =
+            disable_page_lock_in_conn(&mut conn, page.id, "correct horse", &journal).unwrap();
+        assert!(!unlocked.locked);
+        assert_eq!(unlocked.content, "vpn token 4242");
+        assert_eq!(
+            get_page_revisions_in_conn(&conn, page.id).unwrap()[0].content,
+            "first draft"
+        );
+        assert_eq!(search_pages_in_conn(&conn, "4242").unwrap().len(), 1);
+    }
+
+    #[test]
+    fn locked_pages_stay_out_of_links_stats_exports_and_the_mirror() {
+        let mut conn = command_test_connection();
+        let journal = JournalCipher::default();
+        let root = std::env::temp_dir().join(format!(
+            "dev-journal-locked-pages-test-{}",
+            Utc::now().timestamp_nanos_opt().unwrap_or_default()
+        ));
+        let text = "# Vault\n\nSee [[Runbook]] for the root password";
+        let page = create_page_in_conn(&conn, "Vault", text, None).expect("page");
+        set_page_links_in_conn(&conn, page.id, text).expect("links");
+        create_page_in_conn(&conn, "Runbook", "Steps", None).expect("runbook");
+        let links = |conn: &Connection| -> i64 {
+            conn.query_row(
+                "SELECT COUNT(*) FROM page_links WHERE source_page_id = ?1",
+                params![page.id],
+                |row| row.get(0),
+            )
+            .expect("links")
+        };
+        assert_eq!(links(&conn), 1);
+
+        let key =
+            enable_page_lock_in_conn(&mut conn, page.id, "correct horse", &journal).expect("lock");
+        assert_eq!(links(&conn), 0);
+        set_page_links_in_conn(&conn, page.id, text).expect("reindex");
+        assert_eq!(links(&conn), 0);
+
+        let stats = get_page_stats_in_conn(&conn, page.id, &journal, Utc::now()).expect("stats");
+        assert_eq!((stats.word_count, stats.heading_count), (0, 0));
+        let health = get_pages_stats_in_conn(&conn, &journal, Utc::now()).expect("health");
+        assert_eq!(health.page_count, 1);
+
+        let summary = export_all_pages_in_conn(
+            &conn,
+            &root.join("export"),
+            PageExportFormat::Markdown,
+            &root.join("assets"),
+            &journal,
+        )
+        .expect("export");
+        assert_eq!(summary.pages_written, 1);
+        let single = |key: Option<&PageKey>| {
+            export_page_in_conn(
+                &conn,
+                page.id,
+                PageExportFormat::Markdown,
+                &root.join("vault.md").display().to_string(),
+                &root.join("assets"),
+                &journal,
+                key,
+            )
+        };
+        assert!(single(None).is_err());
+        let exported = single(Some(&key)).expect("unlocked export");
+        assert!(fs::read_to_string(exported)
+            .expect("read")
+            .contains("root password"));
+
+        let mirror = root.join("mirror");
+        sync_vault_mirror_in_conn(&conn, &mirror).expect("sync");
+        let mirrored: Vec<String> = fs::read_dir(mirror.join("pages"))
+            .expect("pages folder")
+            .map(|item| {
+                item.expect("item")
+                    .file_name()
+                    .to_string_lossy()
+                    .to_string()
+            })
+            .collect();
+        assert_eq!(mirrored.len(), 1);
+        assert!(mirrored[0].contains("Runbook"));
+
+        disable_page_lock_in_conn(&mut conn, page.id, "correct horse", &journal).expect("unlock");
+        assert_eq!(links(&conn), 1);
+        fs::remove_dir_all(root).ok();
+    }
+
+    #[test]
+    fn shift_dates_previews_moves_and_refuses_collisions() {
+        let mut conn = command_test_connection();
+        conn.execute_batch(
+            "INSERT INTO habits (id, title, description, target_per_week, color, created_at, updated_at)
+             VALUES (1, 'Run', '', 3, '#60a5fa', 'now', 'now'), (2, 'Read', '', 3, '#60a5fa', 'now', 'now');
+             INSERT INTO habit_logs (habit_id, date, created_at) VALUES
+                (1, '2026-03-01', 'now'), (1, '2026-03-02', 'now'), (1, '2026-03-03', 'now'),
+                (2, '2026-03-04', 'now');",
+        )
+        .unwrap();
+        let habit_logs = |habit_id: Option<i64>| DateShiftFilter {
+            entity: DateShiftEntity::HabitLogs,
+            from_date: None,
+            to_date: None,
+            habit_id,
+        };
+        let dates = |conn: &Connection, habit_id: i64| {
+            let mut stmt = conn
+                .prepare("SELECT date FROM habit_logs WHERE habit_id = ?1 ORDER BY date")
+                .unwrap();
+            stmt.query_map(params![habit_id], |row| row.get::<_, String>(0))
+                .unwrap()
+                .collect::<Result<Vec<_>, _>>()
+                .unwrap()
+        };
+
+        // Consecutive days move together without tripping over each other.
+        let preview = shift_dates_in_conn(&mut conn, &habit_logs(Some(1)), 1, false).unwrap();
+        assert!(!preview.applied);
+        assert_eq!(preview.rows.len(), 3);
+        assert_eq!(
+            (
+                preview.rows[0].from_date.as_str(),
+                preview.rows[0].to_date.as_str()
+            ),
+            ("2026-03-03", "2026-03-04")
+        );
+        assert_eq!(dates(&conn, 1)[0], "2026-03-01");
+        let shift = shift_dates_in_conn(&mut conn, &habit_logs(Some(1)), 1, true).unwrap();
+        assert!(shift.applied);
+        assert_eq!(
+            dates(&conn, 1),
+            vec!["2026-03-02", "2026-03-03", "2026-03-04"]
+        );
+
+        // Moving only part of a habit's logs onto days it already has is refused whole.
+        let partial = DateShiftFilter {
+            from_date: Some("2026-03-02".to_string()),
+            to_date: Some("2026-03-02".to_string()),
+            ..habit_logs(None)
+        };
+        let preview = shift_dates_in_conn(&mut conn, &partial, 1, false).unwrap();
+        assert_eq!(preview.conflicts.len(), 1);
+        assert_eq!(preview.conflicts[0].label, "Run");
+        assert!(shift_dates_in_conn(&mut conn, &partial, 1, true).is_err());
+        assert_eq!(
+            dates(&conn, 1),
+            vec!["2026-03-02", "2026-03-03", "2026-03-04"]
+        );
+        assert_eq!(dates(&conn, 2), vec!["2026-03-04"]);
+
+        assert!(shift_dates_in_conn(&mut conn, &habit_logs(None), 0, false).is_err());
+        let tasks = DateShiftFilter {
+            entity: DateShiftEntity::TaskDueDates,
+            ..habit_logs(Some(1))
+        };
+        assert!(shift_dates_in_conn(&mut conn, &tasks, -1, false).is_err());
+    }
+
+    #[test]
+    fn integrity_check_reports_violations_and_fixes_safe_ones() {
+        let mut conn = command_test_connection();
+        let report = get_integrity_report_in_conn(&conn).unwrap();
+        assert!(report.checked_at.is_none());
+        assert_eq!(report.violation_count, 0);
+
+        conn.execute_batch(
+            "PRAGMA foreign_keys = OFF;
+             INSERT INTO entries (date, yesterday, today, created_at)
+             VALUES ('2026-02-30', '', '', 'now'), ('2026-03-01', '', '', 'now'),
+                    ('2026-03-01T00:00', '', '', 'now');
+             INSERT INTO goals (title, progress, created_at, updated_at)
+             VALUES ('Ship it', 140, 'now', 'now');
+             INSERT INTO tasks (title, description, status, created_at, updated_at)
+             VALUES ('Finished', '', 'done', 'now', '2026-03-02T10:00:00Z');
+             INSERT INTO habit_logs (habit_id, date, created_at) VALUES (99, '2026-03-01', 'now');
+             PRAGMA foreign_keys = ON;",
+        )
+        .unwrap();
+        let count = |report: &crate::models::IntegrityReport, rule: &str| {
+            report
+                .rules
+                .iter()
+                .find(|status| status.rule == rule)
+                .map(|status| (status.violation_count, status.fixed_count))
+                .unwrap()
+        };
+
+        let report = verify_integrity_in_conn(&mut conn, false).unwrap();
+        assert!(report.checked_at.is_some());
+        assert_eq!(report.violation_count, 6);
+        assert_eq!(count(&report, "entry_date_valid"), (2, 0));
+        assert_eq!(count(&report, "entry_date_unique"), (1, 0));
+        assert_eq!(count(&report, "goal_progress_range"), (1, 0));
+        assert_eq!(
+            get_integrity_report_in_conn(&conn).unwrap().violation_count,
+            6
+        );
+
+        let report = verify_integrity_in_conn(&mut conn, true).unwrap();
+        assert_eq!(count(&report, "goal_progress_range"), (0, 1));
+        assert_eq!(count(&report, "done_task_completed_at"), (0, 1));
+        assert_eq!(count(&report, "habit_log_habit_exists"), (0, 1));
+        // Entry dates need a person to decide which day was meant.
+        assert_eq!(report.violation_count, 3);
+        let completed_at: String = conn
+            .query_row("SELECT completed_at FROM tasks", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(completed_at, "2026-03-02T10:00:00Z");
+        let progress: i64 = conn
+            .query_row("SELECT progress FROM goals", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(progress, 100);
+    }
+
+    #[test]
+    fn page_icon_and_cover_are_normalized_and_survive_backups() {
+        let mut conn = command_test_connection();
+        let page = create_page_in_conn(&conn, "Roadmap", "", None).expect("page");
+        assert_eq!((page.icon.as_deref(), page.cover.as_deref()), (None, None));
+
+        set_page_appearance_in_conn(
+            &conn,
+            page.id,
+            Some(" 🚀 ".to_string()),
+            Some("#A1B2C3".to_string()),
+        )
+        .expect("set appearance");
+        let stored = get_page_in_conn(&conn, page.id)
+            .expect("get")
+            .expect("page");
+        assert_eq!(stored.icon.as_deref(), Some("🚀"));
+        assert_eq!(stored.cover.as_deref(), Some("#a1b2c3"));
+        assert_eq!(stored.updated_at, page.updated_at);
+
+        // Unsent fields stay; blank or invalid ones clear.
+        set_page_appearance_in_conn(&conn, page.id, None, Some("javascript:x".to_string()))
+            .expect("clear cover");
+        let stored = get_page_in_conn(&conn, page.id)
+            .expect("get")
+            .expect("page");
+        assert_eq!(stored.icon.as_deref(), Some("🚀"));
+        assert_eq!(stored.cover, None);
+        set_page_appearance_in_conn(&conn, page.id, Some("docs".to_string()), None)
+            .expect("clear icon");
+        let stored = get_page_in_conn(&conn, page.id)
+            .expect("get")
+            .expect("page");
+        assert_eq!(stored.icon, None);
+        assert!(set_page_appearance_in_conn(&conn, 999, None, None).is_err());
+
+        let payload = upgrade_backup_payload(serde_json::json!({
+            "format_version": BACKUP_FORMAT_VERSION,
+            "pages": [
+                {
+                    "id": page.id,
+                    "title": "Roadmap",
+                    "content": "",
+                    "icon": "🗺️",
+                    "cover": "https://example.com/map.png"
+                },
+                { "id": 60, "title": "Old export", "content": "" }
+            ]
+        }))
+        .expect("payload");
+        import_backup_into_conn(&mut conn, payload, false).expect("import");
+        let imported = get_page_in_conn(&conn, page.id)
+            .expect("get")
+            .expect("page");
+        assert_eq!(imported.icon.as_deref(), Some("🗺️"));
+        assert_eq!(
+            imported.cover.as_deref(),
+            Some("https://example.com/map.png")
+        );
+        let old = get_page_in_conn(&conn, 60).expect("get").expect("page");
+        assert_eq!((old.icon, old.cover), (None, None));
+    }
+
+    #[test]
+    fn page_properties_come_from_frontmatter_and_filter_pages() {
+        let mut conn = command_test_connection();
+        assert_eq!(
+            extract_page_properties(
+                "---\nStatus: Doing\narea: \"Work\"\ntags: [rust, sql]\nowners:\n  - Ann\n  - Bo\nmeta:\n  nested: skipped\nempty:\n---\n# Body\nkey: not frontmatter"
+            ),
+            vec![
+                ("status".to_string(), "Doing".to_string()),
+                ("area".to_string(), "Work".to_string()),
+                ("tags".to_string(), "rust".to_string()),
+                ("tags".to_string(), "sql".to_string()),
+                ("owners".to_string(), "Ann".to_string()),
+                ("owners".to_string(), "Bo".to_string()),
+            ]
+        );
+        assert!(extract_page_properties("---\nstatus: open\nno closing line").is_empty());
+        assert!(extract_page_properties("status: doing").is_empty());
+
+        let payload = upgrade_backup_payload(serde_json::json!({
+            "format_version": BACKUP_FORMAT_VERSION,
+            "pages": [
+                { "id": 1, "title": "Migration", "content": "---\nstatus: doing\nproject: Core\n---\nNotes" },
+                { "id": 2, "title": "Docs", "content": "---\nstatus: Done\n---\n" },
+                { "id": 3, "title": "Release", "content": "---\nstatus: doing\n---\n" },
+                { "id": 4, "title": "Plain", "content": "No frontmatter" }
+            ]
+        }))
+        .expect("payload");
+        import_backup_into_conn(&mut conn, payload, false).expect("import");
+
+        assert_eq!(
+            get_page_properties_in_conn(&conn, 1).expect("properties"),
+            vec![
+                PageProperty {
+                    key: "status".to_string(),
+                    value: "doing".to_string()
+                },
+                PageProperty {
+                    key: "project".to_string(),
+                    value: "Core".to_string()
+                },
+            ]
+        );
+        let titles =
+            |pages: Vec<Page>| pages.into_iter().map(|page| page.title).collect::<Vec<_>>();
+        let mut doing =
+            titles(get_pages_by_property_in_conn(&conn, "Status", Some("DOING")).expect("doing"));
+        doing.sort();
+        assert_eq!(doing, vec!["Migration", "Release"]);
+        assert_eq!(
+            get_pages_by_property_in_conn(&conn, "status", None)
+                .expect("any status")
+                .len(),
+            3
+        );
+        assert!(get_pages_by_property_in_conn(&conn, " ", None).is_err());
+
+        trash_page_in_conn(&conn, 3).expect("trash");
+        assert_eq!(
+            get_page_property_values_in_conn(&conn, "status").expect("values"),
+            vec![
+                PagePropertyValue {
+                    value: "doing".to_string(),
+                    page_count: 1
+                },
+                PagePropertyValue {
+                    value: "Done".to_string(),
+                    page_count: 1
+                },
+            ]
+        );
+
+        // Saving replaces the old properties.
+        super::page_properties::set_page_properties_in_conn(&conn, 1, "Frontmatter removed")
+            .expect("reindex");
+        assert!(get_page_properties_in_conn(&conn, 1)
+            .expect("properties")
+            .is_empty());
+    }
+
+    #[test]
+    fn page_properties_skip_locked_pages_and_encrypted_journals() {
+        let mut conn = command_test_connection();
+        let journal = JournalCipher::default();
+        let text = "---\nstatus: doing\nsalary: 120k\n---\nNotes";
+        let page = create_page_in_conn(&conn, "Review", text, None).expect("page");
+        let set_properties = |conn: &Connection| {
+            super::page_properties::set_page_properties_in_conn(conn, page.id, text).expect("index")
+        };
+        set_properties(&conn);
+        assert_eq!(
+            get_page_properties_in_conn(&conn, page.id)
+                .expect("properties")
+                .len(),
+            2
+        );
+
+        enable_page_lock_in_conn(&mut conn, page.id, "correct horse", &journal).expect("lock");
+        assert!(get_page_properties_in_conn(&conn, page.id)
+            .expect("properties")
+            .is_empty());
+        set_properties(&conn);
+        assert!(get_page_properties_in_conn(&conn, page.id)
+            .expect("properties")
+            .is_empty());
+        disable_page_lock_in_conn(&mut conn, page.id, "correct horse", &journal).expect("unlock");
+        assert_eq!(
+            get_page_properties_in_conn(&conn, page.id)
+                .expect("properties")
+                .len(),
+            2
+        );
+
+        enable_journal_encryption_in_conn(&mut conn, "correct horse battery").expect("enable");
+        let rows: i64 = conn
+            .query_row("SELECT COUNT(*) FROM page_properties", [], |row| row.get(0))
+            .expect("rows");
+        assert_eq!(rows, 0);
+        set_properties(&conn);
+        assert!(get_page_properties_in_conn(&conn, page.id)
+            .expect("properties")
+            .is_empty());
+
+        disable_journal_encryption_in_conn(&mut conn, "correct horse battery").expect("disable");
+        assert_eq!(
+            get_pages_by_property_in_conn(&conn, "salary", Some("120k"))
+                .expect("pages")
+                .len(),
+            1
+        );
+    }
+
+    #[test]
+    fn performance_tracing_summarizes_params_and_keeps_slow_ops() {
+        let conn = command_test_connection();
+        assert_eq!(
+            summarize_params(&serde_json::json!({
+                "title": "Secret plan",
+                "id": 7,
+                "ids": [1, 2, 3],
+                "pinned": true,
+                "filter": { "entity": "entries" },
+                "value": null
+            })),
+            "filter={1 fields}, id=7, ids=[3 items], pinned=true, title=<11 chars>, value=null"
+        );
+        let long_keys = serde_json::Map::from_iter(
+            (0..40).map(|index| (format!("argument_{index:02}"), serde_json::json!(index))),
+        );
+        assert!(summarize_params(&serde_json::Value::Object(long_keys)).ends_with('…'));
+
+        let performance = PerformanceState::from_conn(&conn).expect("state");
+        assert!(!performance.record("get_entries", 1_000));
+        assert!(performance.record("search_pages", 400_000));
+        assert!(!performance.record("search_pages", 2_000));
+        performance.set_threshold_ms(0);
+        assert!(!performance.record("search_pages", 900_000));
+        let timings = performance.timings().expect("timings");
+        assert_eq!(timings[0].command, "search_pages");
+        assert_eq!((timings[0].calls, timings[0].slow_calls), (3, 1));
+        assert_eq!(timings[0].max_ms, 900.0);
+        assert_eq!(timings[1].mean_ms, 1.0);
+
+        record_slow_op_in_conn(&conn, "search_pages", 400, "query=<4 chars>").expect("record");
+        record_slow_op_in_conn(&conn, "export_all_pages", 1_200, "dir=<12 chars>").expect("record");
+        let slow_ops = get_slow_ops_in_conn(&conn, 10).expect("slow ops");
+        assert_eq!(
+            slow_ops
+                .iter()
+                .map(|op| (op.command.as_str(), op.duration_ms))
+                .collect::<Vec<_>>(),
+            vec![("export_all_pages", 1_200), ("search_pages", 400)]
+        );
+        for index in 0..600 {
+            record_slow_op_in_conn(&conn, "get_entries", index, "").expect("record");
+        }
+        let kept: i64 = conn
+            .query_row("SELECT COUNT(*) FROM slow_ops", [], |row| row.get(0))
+            .expect("count");
+        assert_eq!(kept, 500);
+    }
+
+    #[test]
+    fn common_query_paths_are_served_by_indexes() {
+        let conn = command_test_connection();
+        let checks = check_query_plans_in_conn(&conn).expect("query plans");
+        assert!(checks.len() >= 10);
+        let scanning = checks
+            .iter()
+            .filter(|check| !check.uses_index)
+            .map(|check| format!("{}: {:?}", check.name, check.plan))
+            .collect::<Vec<_>>();
+        assert!(scanning.is_empty(), "full table scans: {scanning:?}");
+
+        conn.execute_batch("DROP INDEX idx_habit_logs_date")
+            .expect("drop index");
+        let habit_logs = check_query_plans_in_conn(&conn)
+            .expect("query plans")
+            .into_iter()
+            .find(|check| check.name == "habit_logs_by_day")
+            .expect("habit log path");
+        assert!(!habit_logs.uses_index, "{:?}", habit_logs.plan);
+        assert!(habit_logs.full_scans[0].contains("habit_logs"));
+    }
+
+    #[test]
+    fn task_list_reports_subtask_progress_and_subtasks_reorder() {
+        let mut conn = command_test_connection();
+        conn.execute_batch(
+            "INSERT INTO tasks (id, title, description, status, created_at, updated_at)
+             VALUES (1, 'Ship release', '', 'todo', '2026-01-01T00:00:00Z', '2026-01-01T00:00:00Z'),
+                    (2, 'Plain task', '', 'todo', '2026-01-01T00:00:00Z', '2026-01-01T00:00:00Z');",
+        )
+        .unwrap();
+        let first = create_task_subtask_in_conn(&conn, 1, "Write notes".to_string()).unwrap();
+        let second = create_task_subtask_in_conn(&conn, 1, "Tag build".to_string()).unwrap();
+        let third = create_task_subtask_in_conn(&conn, 1, "Announce".to_string()).unwrap();
+        conn.execute(
+            "UPDATE task_subtasks SET completed = 1 WHERE id = ?1",
+            [second.id],
+        )
+        .unwrap();
+
+        let tasks = get_tasks_in_conn(&conn, None).unwrap();
+        let release = tasks.iter().find(|task| task.id == 1).unwrap();
+        assert_eq!((release.subtask_count, release.subtasks_completed), (3, 1));
+        let plain = tasks.iter().find(|task| task.id == 2).unwrap();
+        assert_eq!((plain.subtask_count, plain.subtasks_completed), (0, 0));
+
+        reorder_task_subtasks_in_conn(&mut conn, 1, &[third.id, first.id, second.id]).unwrap();
+        let order: Vec<i64> = conn
+            .prepare("SELECT id FROM task_subtasks WHERE task_id = 1 ORDER BY position")
+            .unwrap()
+            .query_map([], |row| row.get(0))
+            .unwrap()
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert_eq!(order, vec![third.id, first.id, second.id]);
+
+        assert!(reorder_task_subtasks_in_conn(&mut conn, 1, &[third.id, first.id]).is_err());
+        assert!(reorder_task_subtasks_in_conn(&mut conn, 2, &[first.id]).is_err());
+        assert!(
+            reorder_task_subtasks_in_conn(&mut conn, 1, &[first.id, first.id, second.id]).is_err()
+        );
+    }
+
+    #[test]
+    fn presence_follows_the_latest_running_timer_and_drops_ended_focus() {
+        let conn = command_test_connection();
+        assert_eq!(
+            presence_in_conn(&conn, None).unwrap(),
+            crate::models::Presence::default()
+        );
+
+        conn.execute_batch(
+            "INSERT INTO tasks (id, title, description, status, timer_started_at, created_at, updated_at)
+             VALUES (1, 'Review PR', '', 'in_progress', '2026-05-01T09:00:00+00:00', '2026-05-01T08:00:00Z', '2026-05-01T08:00:00Z'),
+                    (2, 'Fix login', '', 'in_progress', '2026-05-01T10:00:00+00:00', '2026-05-01T08:00:00Z', '2026-05-01T08:00:00Z'),
+                    (3, 'Idle', '', 'todo', NULL, '2026-05-01T08:00:00Z', '2026-05-01T08:00:00Z');",
+        )
+        .unwrap();
+        let focus = |ends_at: chrono::DateTime<Utc>| crate::models::FocusPresence {
+            kind: "focus".to_string(),
+            task_id: Some(2),
+            started_at: Utc::now().to_rfc3339(),
+            ends_at: ends_at.to_rfc3339(),
+        };
+
+        let live = focus(Utc::now() + chrono::Duration::minutes(20));
+        let presence = presence_in_conn(&conn, Some(live.clone())).unwrap();
+        assert_eq!(presence.task_id, Some(2));
+        assert_eq!(presence.task_title.as_deref(), Some("Fix login"));
+        assert_eq!(presence.since.as_deref(), Some("2026-05-01T10:00:00+00:00"));
+        assert_eq!(presence.focus, Some(live));
+
+        let ended = focus(Utc::now() - chrono::Duration::minutes(1));
+        assert_eq!(presence_in_conn(&conn, Some(ended)).unwrap().focus, None);
+
+        conn.execute("UPDATE tasks SET timer_started_at = NULL", [])
+            .unwrap();
+        assert_eq!(presence_in_conn(&conn, None).unwrap().task_id, None);
+    }
+
+    #[test]
+    fn slack_status_follows_the_running_timer_and_respects_opt_outs() {
+        let working = crate::models::Presence {
+            task_id: Some(4),
+            task_title: Some(" Fix login ".to_string()),
+            since: Some("2026-05-01T10:00:00+00:00".to_string()),
+            focus: None,
+        };
+        let idle = crate::models::Presence::default();
+
+        assert_eq!(
+            plan_slack_status_update(&working, true, false, None),
+            SlackStatusUpdate::Set {
+                task_id: 4,
+                text: "Working on Fix login".to_string()
+            }
+        );
+        assert_eq!(
+            plan_slack_status_update(&working, true, false, Some(4)),
+            SlackStatusUpdate::Unchanged
+        );
+        assert!(matches!(
+            plan_slack_status_update(&working, true, false, Some(3)),
+            SlackStatusUpdate::Set { task_id: 4, .. }
+        ));
+        assert_eq!(
+            plan_slack_status_update(&idle, true, false, Some(4)),
+            SlackStatusUpdate::Clear
+        );
+        assert_eq!(
+            plan_slack_status_update(&idle, true, false, None),
+            SlackStatusUpdate::Unchanged
+        );
+        assert_eq!(
+            plan_slack_status_update(&working, true, true, Some(4)),
+            SlackStatusUpdate::Clear
+        );
+        assert_eq!(
+            plan_slack_status_update(&working, false, false, None),
+            SlackStatusUpdate::Unchanged
+        );
+
+        let long_title = crate::models::Presence {
+            task_title: Some("x".repeat(300)),
+            ..working.clone()
+        };
+        let SlackStatusUpdate::Set { text, .. } =
+            plan_slack_status_update(&long_title, true, false, None)
+        else {
+            panic!("expected a status");
+        };
+        assert_eq!(text.chars().count(), 100);
+
+        let conn = command_test_connection();
+        conn.execute(
+            "INSERT INTO tasks (id, title, description, status, created_at, updated_at)
+             VALUES (4, 'Fix login', '', 'todo', '2026-05-01T08:00:00Z', '2026-05-01T08:00:00Z')",
+            [],
+        )
+        .unwrap();
+        assert!(!is_slack_status_opted_out_in_conn(&conn, 4).unwrap());
+        set_slack_status_opt_out_in_conn(&conn, 4, true).unwrap();
+        set_slack_status_opt_out_in_conn(&conn, 4, true).unwrap();
+        assert!(is_slack_status_opted_out_in_conn(&conn, 4).unwrap());
+        set_slack_status_opt_out_in_conn(&conn, 4, false).unwrap();
+        assert!(!is_slack_status_opted_out_in_conn(&conn, 4).unwrap());
+        assert!(set_slack_status_opt_out_in_conn(&conn, 99, true).is_err());
+    }
+
+    #[test]
+    fn task_tags_are_replaced_and_filter_the_board() {
+        let mut conn = command_test_connection();
+        conn.execute_batch(
+            "INSERT INTO tasks (id, title, description, status, created_at, updated_at)
+             VALUES (1, 'Fix crash', '', 'todo', '2026-05-01T08:00:00Z', '2026-05-01T08:00:00Z'),
+                    (2, 'Bump deps', '', 'todo', '2026-05-01T08:00:00Z', '2026-05-01T09:00:00Z');
+             INSERT INTO task_tags (task_id, tag, created_at)
+             VALUES (1, 'backend', '2026-01-01T00:00:00Z');",
+        )
+        .unwrap();
+
+        let tags = set_task_tags_in_conn(
+            &mut conn,
+            1,
+            &["#Bug".to_string(), "BACKEND".to_string(), "bug".to_string()],
+        )
+        .unwrap();
+        assert_eq!(tags, vec!["backend", "Bug"]);
+        let kept_created_at: String = conn
+            .query_row(
+                "SELECT created_at FROM task_tags WHERE task_id = 1 AND tag = 'backend'",
+                [],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert_eq!(kept_created_at, "2026-01-01T00:00:00Z");
+        set_task_tags_in_conn(&mut conn, 2, &["chore".to_string()]).unwrap();
+
+        let ids = |conn: &Connection, tag: Option<&str>| -> Vec<i64> {
+            get_tasks_in_conn(conn, tag)
+                .unwrap()
+                .into_iter()
+                .map(|task| task.id)
+                .collect()
+        };
+        assert_eq!(ids(&conn, None), vec![2, 1]);
+        assert_eq!(ids(&conn, Some("#bug")), vec![1]);
+        assert_eq!(ids(&conn, Some("chore")), vec![2]);
+        assert_eq!(ids(&conn, Some("  ")), vec![2, 1]);
+        assert!(ids(&conn, Some("frontend")).is_empty());
+
+        assert!(set_task_tags_in_conn(&mut conn, 1, &[]).unwrap().is_empty());
+        assert!(ids(&conn, Some("backend")).is_empty());
+        assert!(set_task_tags_in_conn(&mut conn, 1, &["  ".to_string()]).is_err());
+        assert!(set_task_tags_in_conn(&mut conn, 99, &["bug".to_string()]).is_err());
+    }
+
+    #[test]
+    fn shutdown_checklist_lists_the_missing_entry_and_running_timers_after_the_hour() {
+        let conn = command_test_connection();
+        let day = NaiveDate::from_ymd_opt(2026, 5, 8).expect("date");
+        let checklist = |hour: u32| {
+            shutdown_checklist_in_conn(&conn, day.and_hms_opt(hour, 30, 0).expect("time"))
+                .expect("checklist")
+        };
+        conn.execute(
+            "INSERT INTO tasks (title, description, status, priority, created_at, updated_at)
+             VALUES ('Fix login bug', '', 'in_progress', 'medium', '2026-05-08T09:00:00Z', '2026-05-08T09:00:00Z')",
+            [],
+        )
+        .expect("insert task");
+
+        // Off by default.
+        assert_eq!(checklist(18), None);
+
+        settings::write_setting(&conn, "shutdown_ritual_enabled", "true").expect("enable");
+        assert_eq!(checklist(16), None);
+        let found = checklist(17).expect("entry missing");
+        assert!(found.entry_missing);
+        assert!(found.running_timers.is_empty());
+        assert_eq!(found.summary, "entry missing");
+
+        conn.execute(
+            "UPDATE tasks SET timer_started_at = '2026-05-08T15:00:00Z'",
+            [],
+        )
+        .expect("start timer");
+        let found = checklist(18).expect("timer running");
+        assert_eq!(found.running_timers, vec!["Fix login bug".to_string()]);
+        assert_eq!(found.summary, "entry missing, 1 timer running");
+
+        conn.execute(
+            "INSERT INTO entries (date, yesterday, today, created_at)
+             VALUES ('2026-05-08', 'Reviewed PRs', '', '2026-05-08T09:00:00Z')",
+            [],
+        )
+        .expect("entry");
+        assert_eq!(
+            checklist(18).expect("timer only").summary,
+            "1 timer running"
+        );
+
+        pause_all_timers_in_conn(&conn).expect("pause");
+        assert_eq!(checklist(18), None);
+    }
+
+    #[test]
+    fn event_subscriptions_limit_subscribed_windows_to_their_topics() {
+        let subscriptions = events::EventSubscriptions::default();
+
+        // Windows that never subscribed get everything.
+        assert!(subscriptions.wants("main", "timers-changed"));
+
+        let topics = subscriptions
+            .subscribe(
+                "view-tasks",
+                &[
+                    "timers-changed".to_string(),
+                    " presence-changed ".to_string(),
+                ],
+            )
+            .expect("subscribe");
+        assert_eq!(topics, vec!["presence-changed", "timers-changed"]);
+        assert!(subscriptions.wants("view-tasks", "timers-changed"));
+        assert!(!subscriptions.wants("view-tasks", "habits-changed"));
+        assert!(subscriptions.wants("main", "habits-changed"));
+
+        let error = subscriptions
+            .subscribe("view-tasks", &["timer-changed".to_string()])
+            .expect_err("typo");
+        assert!(error.contains("timer-changed"));
+        assert!(subscriptions.wants("view-tasks", "timers-changed"));
+
+        subscriptions
+            .subscribe("view-tasks", &[])
+            .expect("subscribe to nothing");
+        assert!(!subscriptions.wants("view-tasks", "timers-changed"));
+
+        subscriptions
+            .unsubscribe("view-tasks")
+            .expect("unsubscribe");
+        assert!(subscriptions.wants("view-tasks", "habits-changed"));
+    }
+
+    #[test]
+    fn due_reminders_fire_once_per_lead_time_and_wait_out_snoozes() {
+        use chrono::TimeZone;
+
+        assert_eq!(
+            parse_due_reminder_leads("1d, 1h, 30m, 45, soon, 0h, 1H"),
+            vec![1440, 60, 45, 30]
+        );
+        assert_eq!(format_time_left(23 * 60), "23h");
+        assert_eq!(format_time_left(2 * 24 * 60 + 100), "2d");
+        assert_eq!(format_time_left(45), "45m");
+
+        let conn = command_test_connection();
+        for title in ["Ship release notes", "Renew certificate"] {
+            conn.execute(
+                "INSERT INTO tasks (title, description, status, priority, due_date, created_at, updated_at)
+                 VALUES (?1, '', 'todo', 'medium', '2026-05-08', '2026-05-01T09:00:00Z', '2026-05-01T09:00:00Z')",
+                params![title],
+            )
+            .expect("insert task");
+        }
+        let release_notes = 1;
+        let due_at = chrono::Local
+            .from_local_datetime(
+                &NaiveDate::from_ymd_opt(2026, 5, 9)
+                    .expect("date")
+                    .and_hms_opt(0, 0, 0)
+                    .expect("midnight"),
+            )
+            .earliest()
+            .expect("local midnight")
+            .with_timezone(&Utc);
+        let take = |minutes_before: i64| {
+            take_due_task_reminders_in_conn(&conn, due_at - Duration::minutes(minutes_before))
+                .expect("take reminders")
+        };
+
+        // Default leads are 1 day and 1 hour.
+        assert!(take(30 * 60).is_empty());
+        conn.execute(
+            "UPDATE tasks SET status = 'done' WHERE title = 'Renew certificate'",
+            [],
+        )
+        .expect("complete task");
+        let sent = take(23 * 60);
+        assert_eq!(sent.len(), 1);
+        assert_eq!(sent[0].task_id, release_notes);
+        assert_eq!(sent[0].minutes_left, 23 * 60);
+        assert!(!sent[0].snoozed);
+        assert!(take(22 * 60).is_empty());
+
+        let snoozed_until = snooze_task_due_reminder_in_conn(
+            &conn,
+            release_notes,
+            Some(3 * 60),
+            due_at - Duration::minutes(22 * 60),
+        )
+        .expect("snooze")
+        .expect("snoozed until");
+        assert_eq!(
+            snoozed_until,
+            (due_at - Duration::minutes(19 * 60)).to_rfc3339()
+        );
+        assert!(take(20 * 60).is_empty());
+        let sent = take(19 * 60);
+        assert_eq!(sent.len(), 1);
+        assert!(sent[0].snoozed);
+        assert!(take(18 * 60).is_empty());
+
+        // A task first seen after both leads passed gets a single reminder.
+        conn.execute(
+            "UPDATE tasks SET status = 'todo' WHERE title = 'Renew certificate'",
+            [],
+        )
+        .expect("reopen task");
+        let sent = take(50);
+        assert_eq!(sent.len(), 2);
+        assert_eq!(sent[1].title, "Renew certificate");
+        assert!(take(40).is_empty());
+        assert!(take(-10).is_empty());
+
+        assert!(snooze_task_due_reminder_in_conn(&conn, 999, Some(60), Utc::now()).is_err());
+    }
+
+    #[test]
+    fn workspace_snapshots_restore_everything_and_keep_the_state_they_replace() {
+        let mut conn = command_test_connection();
+        let dir = std::env::temp_dir().join(format!(
+            "dev-journal-snapshots-test-{}",
+            Utc::now().timestamp_nanos_opt().unwrap_or_default()
+        ));
+        let count_tasks = |conn: &Connection| -> i64 {
+            conn.query_row("SELECT COUNT(*) FROM tasks", [], |row| row.get(0))
+                .expect("count tasks")
+        };
+        conn.execute(
+            "INSERT INTO tasks (title, description, status, priority, created_at, updated_at)
+             VALUES ('Keep me', '', 'todo', 'medium', '2026-05-08T09:00:00Z', '2026-05-08T09:00:00Z')",
+            [],
+        )
+        .expect("insert task");
+
+        let snapshot =
+            create_workspace_snapshot_in_conn(&conn, &dir, " Before cleanup ").expect("snapshot");
+        assert_eq!(snapshot.name, "Before cleanup");
+        assert_eq!(snapshot.schema_version, crate::db::SCHEMA_VERSION);
+        assert!(create_workspace_snapshot_in_conn(&conn, &dir, "Before cleanup").is_err());
+        assert!(create_workspace_snapshot_in_conn(&conn, &dir, "../escape").is_err());
+
+        conn.execute("DELETE FROM tasks", []).expect("bulk cleanup");
+        settings::write_setting(&conn, "workday_hours", "6").expect("setting");
+
+        let now = NaiveDate::from_ymd_opt(2026, 5, 8)
+            .and_then(|day| day.and_hms_opt(10, 15, 0))
+            .and_then(|time| time.and_local_timezone(chrono::Local).single())
+            .expect("now");
+        let safety = restore_workspace_snapshot_in_conn(&mut conn, &dir, "Before cleanup", now)
+            .expect("restore");
+        assert_eq!(safety.name, "before-restore-20260508-101500");
+        assert_eq!(count_tasks(&conn), 1);
+        assert_eq!(
+            settings::read_setting(&conn, "workday_hours").expect("read"),
+            "8"
+        );
+
+        // The automatic snapshot undoes the restore.
+        restore_workspace_snapshot_in_conn(
+            &mut conn,
+            &dir,
+            &safety.name,
+            now + chrono::Duration::minutes(1),
+        )
+        .expect("undo restore");
+        assert_eq!(count_tasks(&conn), 0);
+
+        let names: Vec<String> = list_workspace_snapshots_in_dir(&dir)
+            .expect("list")
+            .into_iter()
+            .map(|snapshot| snapshot.name)
+            .collect();
+        assert_eq!(names.len(), 3);
+        assert!(names.contains(&"Before cleanup".to_string()));
+
+        delete_workspace_snapshot_in_dir(&dir, "Before cleanup").expect("delete");
+        assert!(
+            restore_workspace_snapshot_in_conn(&mut conn, &dir, "Before cleanup", now).is_err()
+        );
+        fs::remove_dir_all(dir).ok();
+    }
+
+    #[test]
+    fn board_columns_keep_their_manual_order() {
+        let mut conn = command_test_connection();
+        conn.execute_batch(
+            "INSERT INTO tasks (id, title, description, status, created_at, updated_at)
+             VALUES (1, 'Oldest', '', 'todo', '2026-01-01T00:00:00Z', '2026-01-01T00:00:00Z'),
+                    (2, 'Middle', '', 'todo', '2026-01-02T00:00:00Z', '2026-01-02T00:00:00Z'),
+                    (3, 'Newest', '', 'todo', '2026-01-03T00:00:00Z', '2026-01-03T00:00:00Z'),
+                    (4, 'Started', '', 'in_progress', '2026-01-04T00:00:00Z', '2026-01-04T00:00:00Z');
+             INSERT INTO task_tags (task_id, tag, created_at)
+             VALUES (1, 'ops', '2026-01-01T00:00:00Z'), (3, 'ops', '2026-01-01T00:00:00Z');",
+        )
+        .unwrap();
+        let column = |conn: &Connection, status: &str, tag: Option<&str>| -> Vec<i64> {
+            get_tasks_in_conn(conn, tag)
+                .unwrap()
+                .into_iter()
+                .filter(|task| task.status == status)
+                .map(|task| task.id)
+                .collect()
+        };
+        assert_eq!(column(&conn, "todo", None), vec![3, 2, 1]);
+
+        reorder_tasks_in_conn(&mut conn, "todo".to_string(), &[1, 3, 2]).unwrap();
+        assert_eq!(column(&conn, "todo", None), vec![1, 3, 2]);
+
+        // A filtered view only swaps the tasks it shows; task 3 keeps its place.
+        reorder_tasks_in_conn(&mut conn, "todo".to_string(), &[3, 1]).unwrap();
+        assert_eq!(column(&conn, "todo", Some("ops")), vec![3, 1]);
+        assert_eq!(column(&conn, "todo", None), vec![3, 1, 2]);
+
+        // Moving a task to another column puts it on top there.
+        set_task_status_in_conn(&conn, 2, "in_progress".to_string()).unwrap();
+        assert_eq!(column(&conn, "in_progress", None), vec![2, 4]);
+        reorder_tasks_in_conn(&mut conn, "in_progress".to_string(), &[4, 2]).unwrap();
+        assert_eq!(column(&conn, "in_progress", None), vec![4, 2]);
+
+        assert!(reorder_tasks_in_conn(&mut conn, "todo".to_string(), &[1, 4]).is_err());
+        assert!(reorder_tasks_in_conn(&mut conn, "todo".to_string(), &[1, 1]).is_err());
+    }
 }
@@ -0,0 +1,233 @@
+use crate::models::AccessibleSummary;
+use chrono::{Local, NaiveDate, Utc};
+use rusqlite::{params, Connection};
+use std::collections::HashMap;
+use tauri::State;
+
+use super::entries::get_entry_streaks_in_conn;
+use super::settings::read_i64_setting;
+use super::AppState;
+
+/// Views `get_accessible_summary` can describe.
+pub(crate) const ACCESSIBLE_SUMMARY_VIEWS: [&str; 4] = ["today", "tasks", "habits", "journal"];
+
+fn count_phrase(count: i64, singular: &str, plural: &str) -> String {
+    format!("{} {}", count, if count == 1 { singular } else { plural })
+}
+
+struct TaskCounts {
+    open: i64,
+    due_today: i64,
+    overdue: i64,
+    running_timer: Option<String>,
+}
+
+fn task_counts_in_conn(conn: &Connection, today: &str) -> Result<TaskCounts, String> {
+    let (open, due_today, overdue) = conn
+        .query_row(
+            "SELECT COUNT(*),
+                    COALESCE(SUM(due_date = ?1), 0),
+                    COALESCE(SUM(due_date < ?1), 0)
+             FROM tasks
+             WHERE status != 'done' AND id NOT IN (SELECT task_id FROM review_queue)",
+            params![today],
+            |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
+        )
+        .map_err(|e| e.to_string())?;
+    let running_timer = conn
+        .query_row(
+            "SELECT title FROM tasks WHERE timer_started_at IS NOT NULL
+             ORDER BY timer_started_at DESC LIMIT 1",
+            [],
+            |row| row.get(0),
+        )
+        .ok();
+
+    Ok(TaskCounts {
+        open,
+        due_today,
+        overdue,
+        running_timer,
+    })
+}
+
+struct HabitCounts {
+    total: i64,
+    done_today: i64,
+    /// Longest current streak and its habit.
+    best_streak: Option<(String, i64)>,
+}
+
+fn habit_counts_in_conn(conn: &Connection, today: NaiveDate) -> Result<HabitCounts, String> {
+    let titles = conn
+        .prepare("SELECT id, title FROM habits")
+        .map_err(|e| e.to_string())?
+        .query_map([], |row| {
+            Ok((row.get::<_, i64>(0)?, row.get::<_, String>(1)?))
+        })
+        .map_err(|e| e.to_string())?
+        .collect::<Result<HashMap<_, _>, _>>()
+        .map_err(|e| e.to_string())?;
+    let freeze_every = read_i64_setting(conn, "streak_freeze_every")?.max(0);
+    let histories = super::load_habit_histories_in_conn(conn)?;
+    let streaks = super::compute_habit_streaks(&histories, freeze_every, today);
+    let today_text = today.format("%Y-%m-%d").to_string();
+
+    let mut counts = HabitCounts {
+        total: 0,
+        done_today: 0,
+        best_streak: None,
+    };
+    for history in histories {
+        counts.total += 1;
+        if history.completed_dates.contains(&today_text) {
+            counts.done_today += 1;
+        }
+        let streak = streaks
+            .by_habit
+            .get(&history.habit_id)
+            .map_or(0, |streak| streak.current_streak);
+        if streak > 0
+            && counts
+                .best_streak
+                .as_ref()
+                .is_none_or(|(_, best)| streak > *best)
+        {
+            let title = titles.get(&history.habit_id).cloned().unwrap_or_default();
+            counts.best_streak = Some((title, streak));
+        }
+    }
+
+    Ok(counts)
+}
+
+/// Whether `date` has an entry with any text in it.
+fn entry_written_in_conn(conn: &Connection, date: &str) -> Result<bool, String> {
+    conn.query_row(
+        "SELECT EXISTS(
+             SELECT 1 FROM entries
+             WHERE date = ?1 AND deleted_at IS NULL
+               AND (trim(yesterday) <> '' OR trim(today) <> ''
+                    OR trim(blockers) <> '' OR trim(notes) <> '')
+         )",
+        params![date],
+        |row| row.get(0),
+    )
+    .map_err(|e| e.to_string())
+}
+
+fn task_parts(tasks: &TaskCounts, detailed: bool) -> Vec<String> {
+    let mut parts = vec![format!(
+        "{} due today",
+        count_phrase(tasks.due_today, "task", "tasks")
+    )];
+    if tasks.overdue > 0 {
+        parts.push(format!("{} overdue", tasks.overdue));
+    }
+    if detailed {
+        parts.insert(
+            0,
+            format!("{} open", count_phrase(tasks.open, "task", "tasks")),
+        );
+    }
+    if let Some(title) = &tasks.running_timer {
+        parts.push(format!("timer running on \"{}\"", title));
+    }
+    parts
+}
+
+fn habit_parts(habits: &HabitCounts, detailed: bool) -> Vec<String> {
+    if habits.total == 0 {
+        return vec!["no habits yet".to_string()];
+    }
+    let mut parts = Vec::new();
+    if detailed {
+        parts.push(format!(
+            "{} of {} done today",
+            habits.done_today,
+            count_phrase(habits.total, "habit", "habits")
+        ));
+    }
+    match &habits.best_streak {
+        Some((title, days)) if detailed => parts.push(format!(
+            "longest streak {} on \"{}\"",
+            count_phrase(*days, "day", "days"),
+            title
+        )),
+        Some((_, days)) => parts.push(format!(
+            "habit streak {}",
+            count_phrase(*days, "day", "days")
+        )),
+        None => parts.push("no habit streak running".to_string()),
+    }
+    parts
+}
+
+/// A short sentence describing `view` for screen readers: counts only, no lists, so
+/// it fits an ARIA live region. `view` is one of `ACCESSIBLE_SUMMARY_VIEWS`.
+pub(crate) fn get_accessible_summary_in_conn(
+    conn: &Connection,
+    view: &str,
+    today: NaiveDate,
+) -> Result<AccessibleSummary, String> {
+    let view = view.trim().to_lowercase();
+    let today_text = today.format("%Y-%m-%d").to_string();
+    let entry_part = |conn: &Connection| -> Result<String, String> {
+        Ok(if entry_written_in_conn(conn, &today_text)? {
+            "entry written".to_string()
+        } else {
+            "entry not yet written".to_string()
+        })
+    };
+
+    let parts = match view.as_str() {
+        "today" => {
+            let mut parts = task_parts(&task_counts_in_conn(conn, &today_text)?, false);
+            parts.extend(habit_parts(&habit_counts_in_conn(conn, today)?, false));
+            parts.push(entry_part(conn)?);
+            parts
+        }
+        "tasks" => task_parts(&task_counts_in_conn(conn, &today_text)?, true),
+        "habits" => habit_parts(&habit_counts_in_conn(conn, today)?, true),
+        "journal" => {
+            let streaks = get_entry_streaks_in_conn(conn, today)?;
+            vec![
+                format!("today's {}", entry_part(conn)?),
+                format!(
+                    "journal streak {}",
+                    count_phrase(streaks.current_streak, "day", "days")
+                ),
+                count_phrase(streaks.total_entries, "entry", "entries"),
+            ]
+        }
+        _ => {
+            return Err(format!(
+                "Unknown view: {} (expected one of: {})",
+                view,
+                ACCESSIBLE_SUMMARY_VIEWS.join(", ")
+            ))
+        }
+    };
+
+    let mut text = parts.join(", ");
+    if let Some(first) = text.get(..1) {
+        text = first.to_uppercase() + &text[1..];
+    }
+    Ok(AccessibleSummary {
+        view,
+        text: format!("{}.", text),
+        parts,
+        generated_at: Utc::now().to_rfc3339(),
+    })
+}
+
+/// Pre-composed summary of `view` (`today`, `tasks`, `habits` or `journal`) for an
+/// ARIA live region.
+#[tauri::command]
+pub fn get_accessible_summary(
+    view: String,
+    state: State<'_, AppState>,
+) -> Result<AccessibleSummary, String> {
+    let conn = state.db.lock().map_err(|e| e.to_string())?;
+    get_accessible_summary_in_conn(&conn, &view, Local::now().date_naive())
+}
@@ -0,0 +1,288 @@
+use crate::models::Achievement;
+use chrono::Utc;
+use rusqlite::{params, Connection, OptionalExtension};
+use tauri::{AppHandle, Manager, State};
+use tauri_plugin_notification::NotificationExt;
+
+use super::events::emit_event;
+use super::AppState;
+
+#[derive(Clone, Copy, PartialEq)]
+enum AchievementMetric {
+    EntriesWritten,
+    LongestHabitStreak,
+    TasksCompleted,
+    FocusSessions,
+}
+
+struct AchievementDefinition {
+    key: &'static str,
+    title: &'static str,
+    description: &'static str,
+    metric: AchievementMetric,
+    threshold: i64,
+}
+
+const fn achievement(
+    key: &'static str,
+    title: &'static str,
+    description: &'static str,
+    metric: AchievementMetric,
+    threshold: i64,
+) -> AchievementDefinition {
+    AchievementDefinition {
+        key,
+        title,
+        description,
+        metric,
+        threshold,
+    }
+}
+
+/// Built-in milestones. Unlocks are stored by key, so keys must never change.
+const ACHIEVEMENTS: &[AchievementDefinition] = &[
+    achievement(
+        "entries_1",
+        "First page",
+        "Write your first journal entry",
+        AchievementMetric::EntriesWritten,
+        1,
+    ),
+    achievement(
+        "entries_30",
+        "Habit of writing",
+        "Write 30 journal entries",
+        AchievementMetric::EntriesWritten,
+        30,
+    ),
+    achievement(
+        "entries_100",
+        "Centurion",
+        "Write 100 journal entries",
+        AchievementMetric::EntriesWritten,
+        100,
+    ),
+    achievement(
+        "entries_365",
+        "A year in writing",
+        "Write 365 journal entries",
+        AchievementMetric::EntriesWritten,
+        365,
+    ),
+    achievement(
+        "habit_streak_7",
+        "One week strong",
+        "Keep a habit going for 7 days in a row",
+        AchievementMetric::LongestHabitStreak,
+        7,
+    ),
+    achievement(
+        "habit_streak_30",
+        "Thirty days",
+        "Keep a habit going for 30 days in a row",
+        AchievementMetric::LongestHabitStreak,
+        30,
+    ),
+    achievement(
+        "habit_streak_100",
+        "Unbreakable",
+        "Keep a habit going for 100 days in a row",
+        AchievementMetric::LongestHabitStreak,
+        100,
+    ),
+    achievement(
+        "tasks_done_10",
+        "Getting things done",
+        "Complete 10 tasks",
+        AchievementMetric::TasksCompleted,
+        10,
+    ),
+    achievement(
+        "tasks_done_100",
+        "Shipping machine",
+        "Complete 100 tasks",
+        AchievementMetric::TasksCompleted,
+        100,
+    ),
+    achievement(
+        "tasks_done_500",
+        "Finisher",
+        "Complete 500 tasks",
+        AchievementMetric::TasksCompleted,
+        500,
+    ),
+    achievement(
+        "focus_sessions_25",
+        "In the zone",
+        "Finish 25 focus sessions",
+        AchievementMetric::FocusSessions,
+        25,
+    ),
+    achievement(
+        "focus_sessions_100",
+        "Deep worker",
+        "Finish 100 focus sessions",
+        AchievementMetric::FocusSessions,
+        100,
+    ),
+];
+
+fn metric_value(conn: &Connection, metric: AchievementMetric) -> Result<i64, String> {
+    let sql = match metric {
+        AchievementMetric::EntriesWritten => {
+            "SELECT COUNT(*) FROM entries WHERE deleted_at IS NULL"
+        }
+        AchievementMetric::TasksCompleted => "SELECT COUNT(*) FROM tasks WHERE status = 'done'",
+        AchievementMetric::FocusSessions => {
+            "SELECT COUNT(*) FROM pomodoro_sessions WHERE kind = 'focus'"
+        }
+        // Consecutive dates share the same (day number - row number) group.
+        AchievementMetric::LongestHabitStreak => {
+            "SELECT COALESCE(MAX(run_length), 0)
+             FROM (
+                SELECT COUNT(*) AS run_length
+                FROM (
+                    SELECT habit_id,
+                           CAST(julianday(date) AS INTEGER)
+                             - ROW_NUMBER() OVER (PARTITION BY habit_id ORDER BY date) AS run_group
+                    FROM habit_logs
+                )
+                GROUP BY habit_id, run_group
+             )"
+        }
+    };
+
+    conn.query_row(sql, [], |row| row.get(0))
+        .map_err(|e| e.to_string())
+}
+
+fn metric_values(conn: &Connection) -> Result<Vec<(AchievementMetric, i64)>, String> {
+    [
+        AchievementMetric::EntriesWritten,
+        AchievementMetric::LongestHabitStreak,
+        AchievementMetric::TasksCompleted,
+        AchievementMetric::FocusSessions,
+    ]
+    .into_iter()
+    .map(|metric| Ok((metric, metric_value(conn, metric)?)))
+    .collect()
+}
+
+fn progress_for(values: &[(AchievementMetric, i64)], metric: AchievementMetric) -> i64 {
+    values
+        .iter()
+        .find(|(candidate, _)| *candidate == metric)
+        .map(|(_, value)| *value)
+        .unwrap_or(0)
+}
+
+fn unlocked_at(conn: &Connection, key: &str) -> Result<Option<String>, String> {
+    conn.query_row(
+        "SELECT unlocked_at FROM achievements WHERE key = ?1",
+        params![key],
+        |row| row.get(0),
+    )
+    .optional()
+    .map_err(|e| e.to_string())
+}
+
+fn to_achievement(
+    definition: &AchievementDefinition,
+    progress: i64,
+    unlocked_at: Option<String>,
+) -> Achievement {
+    Achievement {
+        key: definition.key.to_string(),
+        title: definition.title.to_string(),
+        description: definition.description.to_string(),
+        threshold: definition.threshold,
+        progress: progress.min(definition.threshold),
+        unlocked_at,
+    }
+}
+
+pub(crate) fn get_achievements_in_conn(conn: &Connection) -> Result<Vec<Achievement>, String> {
+    let values = metric_values(conn)?;
+
+    ACHIEVEMENTS
+        .iter()
+        .map(|definition| {
+            Ok(to_achievement(
+                definition,
+                progress_for(&values, definition.metric),
+                unlocked_at(conn, definition.key)?,
+            ))
+        })
+        .collect()
+}
+
+/// Records every milestone whose threshold is now met and returns only the new unlocks.
+/// Unlocks are permanent, even if the underlying data is later deleted.
+pub(crate) fn evaluate_achievements_in_conn(conn: &Connection) -> Result<Vec<Achievement>, String> {
+    let values = metric_values(conn)?;
+    let now = Utc::now().to_rfc3339();
+    let mut unlocked = Vec::new();
+
+    for definition in ACHIEVEMENTS {
+        let progress = progress_for(&values, definition.metric);
+        if progress < definition.threshold {
+            continue;
+        }
+
+        let inserted = conn
+            .execute(
+                "INSERT OR IGNORE INTO achievements (key, unlocked_at) VALUES (?1, ?2)",
+                params![definition.key, now],
+            )
+            .map_err(|e| e.to_string())?;
+        if inserted > 0 {
+            unlocked.push(to_achievement(definition, progress, Some(now.clone())));
+        }
+    }
+
+    Ok(unlocked)
+}
+
+/// Evaluates achievements after a relevant change and announces new unlocks.
+/// Must be called without holding the database lock; failures are only logged.
+pub fn evaluate_achievements_for_app(app: &AppHandle) {
+    let Some(state) = app.try_state::<AppState>() else {
+        return;
+    };
+    let unlocked = match state
+        .db
+        .lock()
+        .map_err(|e| e.to_string())
+        .and_then(|conn| evaluate_achievements_in_conn(&conn))
+    {
+        Ok(unlocked) => unlocked,
+        Err(error) => {
+            eprintln!("Failed to evaluate achievements: {error}");
+            return;
+        }
+    };
+    if unlocked.is_empty() {
+        return;
+    }
+
+    for achievement in &unlocked {
+        if let Err(error) = app
+            .notification()
+            .builder()
+            .title(format!("Achievement unlocked: {}", achievement.title))
+            .body(&achievement.description)
+            .show()
+        {
+            eprintln!("Failed to show achievement notification: {error}");
+        }
+    }
+    if let Err(error) = emit_event(app, "achievements-unlocked", &unlocked) {
+        eprintln!("Failed to emit achievements: {error}");
+    }
+}
+
+#[tauri::command]
+pub fn get_achievements(state: State<'_, AppState>) -> Result<Vec<Achievement>, String> {
+    let conn = state.db.lock().map_err(|e| e.to_string())?;
+    get_achievements_in_conn(&conn)
+}
@@ -0,0 +1,397 @@
+use crate::models::{ApiToken, CreatedApiToken, ExternalAccessLogEntry};
+use chrono::{Duration, Utc};
+use rusqlite::{params, Connection, OptionalExtension};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeSet;
+use tauri::State;
+
+use super::settings::read_i64_setting;
+use super::validation::{decode_json_string_list, encode_json_string_list};
+use super::AppState;
+
+/// Every scope a token can hold; each one guards at least one external handler.
+/// Encrypted journal content is never served externally, whatever the scopes.
+pub(crate) const API_SCOPES: [&str; 5] = [
+    "entries:read",
+    "entries:write",
+    "tasks:read",
+    "tasks:write",
+    "bookmarks:write",
+];
+
+/// Access log rows older than this are pruned as new calls are recorded.
+const ACCESS_LOG_RETENTION_DAYS: i64 = 90;
+
+/// Named scope bundles offered by the settings UI.
+pub(crate) fn preset_scopes(preset: &str) -> Option<Vec<String>> {
+    let scopes: Vec<&str> = match preset {
+        "read_only" => API_SCOPES
+            .iter()
+            .copied()
+            .filter(|scope| scope.ends_with(":read"))
+            .collect(),
+        "tasks_only" => vec!["tasks:read", "tasks:write"],
+        "capture" => vec!["bookmarks:write", "tasks:write"],
+        "full" => API_SCOPES.to_vec(),
+        _ => return None,
+    };
+
+    Some(scopes.into_iter().map(str::to_string).collect())
+}
+
+/// Validates, dedupes and sorts requested scopes.
+pub(crate) fn normalize_scopes(scopes: &[String]) -> Result<Vec<String>, String> {
+    let mut normalized = BTreeSet::new();
+    for scope in scopes {
+        let scope = scope.trim().to_ascii_lowercase();
+        if !API_SCOPES.contains(&scope.as_str()) {
+            return Err(format!("Unknown API scope: {}", scope));
+        }
+        normalized.insert(scope);
+    }
+    if normalized.is_empty() {
+        return Err("A token needs at least one scope".to_string());
+    }
+
+    Ok(normalized.into_iter().collect())
+}
+
+pub(crate) fn hash_token(token: &str) -> String {
+    format!("{:x}", Sha256::digest(token.as_bytes()))
+}
+
+fn generate_token() -> Result<String, String> {
+    let mut bytes = [0u8; 24];
+    getrandom::fill(&mut bytes).map_err(|e| e.to_string())?;
+    let hex: String = bytes.iter().map(|byte| format!("{:02x}", byte)).collect();
+    Ok(format!("dj_{}", hex))
+}
+
+/// Scopes granted to an authenticated token. External handlers call `require` before
+/// touching data.
+#[derive(Debug, Clone)]
+pub struct ApiAccess {
+    token_id: i64,
+    token_name: String,
+    scopes: BTreeSet<String>,
+}
+
+impl ApiAccess {
+    pub(crate) fn token_id(&self) -> i64 {
+        self.token_id
+    }
+
+    pub(crate) fn token_name(&self) -> &str {
+        &self.token_name
+    }
+
+    pub(crate) fn has_scope(&self, scope: &str) -> bool {
+        self.scopes.contains(scope)
+    }
+
+    pub(crate) fn require(&self, scope: &str) -> Result<(), String> {
+        if self.has_scope(scope) {
+            Ok(())
+        } else {
+            Err(format!(
+                "Token \"{}\" is missing the {} scope",
+                self.token_name, scope
+            ))
+        }
+    }
+
+    pub(crate) fn scopes(&self) -> Vec<String> {
+        self.scopes.iter().cloned().collect()
+    }
+}
+
+fn api_token_from_row(row: &rusqlite::Row<'_>) -> Result<ApiToken, String> {
+    Ok(ApiToken {
+        id: row.get(0).map_err(|e| e.to_string())?,
+        name: row.get(1).map_err(|e| e.to_string())?,
+        token_prefix: row.get(2).map_err(|e| e.to_string())?,
+        scopes: decode_json_string_list(row.get(3).map_err(|e| e.to_string())?)?,
+        created_at: row.get(4).map_err(|e| e.to_string())?,
+        last_used_at: row.get(5).map_err(|e| e.to_string())?,
+    })
+}
+
+/// Stores a new token and returns its secret; only the hash is kept afterwards.
+pub(crate) fn create_api_token_in_conn(
+    conn: &Connection,
+    name: &str,
+    scopes: &[String],
+) -> Result<CreatedApiToken, String> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err("Token name is required".to_string());
+    }
+    let scopes = normalize_scopes(scopes)?;
+    let secret = generate_token()?;
+    let token_prefix: String = secret.chars().take(10).collect();
+    let now = Utc::now().to_rfc3339();
+
+    conn.execute(
+        "INSERT INTO api_tokens (name, token_hash, token_prefix, scopes_json, created_at)
+         VALUES (?1, ?2, ?3, ?4, ?5)",
+        params![
+            name,
+            hash_token(&secret),
+            token_prefix,
+            encode_json_string_list(&scopes)?,
+            now
+        ],
+    )
+    .map_err(|e| e.to_string())?;
+
+    Ok(CreatedApiToken {
+        token: ApiToken {
+            id: conn.last_insert_rowid(),
+            name: name.to_string(),
+            token_prefix,
+            scopes,
+            created_at: now,
+            last_used_at: None,
+        },
+        secret,
+    })
+}
+
+pub(crate) fn list_api_tokens_in_conn(conn: &Connection) -> Result<Vec<ApiToken>, String> {
+    let mut stmt = conn
+        .prepare(
+            "SELECT id, name, token_prefix, scopes_json, created_at, last_used_at
+             FROM api_tokens ORDER BY created_at DESC, id DESC",
+        )
+        .map_err(|e| e.to_string())?;
+
+    let mut rows = stmt.query([]).map_err(|e| e.to_string())?;
+    let mut tokens = Vec::new();
+    while let Some(row) = rows.next().map_err(|e| e.to_string())? {
+        tokens.push(api_token_from_row(row)?);
+    }
+
+    Ok(tokens)
+}
+
+/// Resolves a presented secret to its scopes, recording when it was last used.
+pub(crate) fn authorize_api_token_in_conn(
+    conn: &Connection,
+    secret: &str,
+) -> Result<ApiAccess, String> {
+    let token: Option<(i64, String, String)> = conn
+        .query_row(
+            "SELECT id, name, scopes_json FROM api_tokens WHERE token_hash = ?1",
+            params![hash_token(secret.trim())],
+            |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
+        )
+        .optional()
+        .map_err(|e| e.to_string())?;
+    let (token_id, token_name, scopes_json) =
+        token.ok_or_else(|| "Invalid API token".to_string())?;
+
+    conn.execute(
+        "UPDATE api_tokens SET last_used_at = ?1 WHERE id = ?2",
+        params![Utc::now().to_rfc3339(), token_id],
+    )
+    .map_err(|e| e.to_string())?;
+
+    Ok(ApiAccess {
+        token_id,
+        token_name,
+        scopes: decode_json_string_list(scopes_json)?.into_iter().collect(),
+    })
+}
+
+/// Records one external call. `outcome` is `allowed`, `denied`, `rate_limited` or `error`.
+pub(crate) fn record_external_access_in_conn(
+    conn: &Connection,
+    token_id: Option<i64>,
+    caller: &str,
+    endpoint: &str,
+    outcome: &str,
+    detail: &str,
+) -> Result<(), String> {
+    let now = Utc::now();
+    conn.execute(
+        "INSERT INTO external_access_log (token_id, caller, endpoint, outcome, detail, created_at)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+        params![
+            token_id,
+            caller,
+            endpoint,
+            outcome,
+            detail,
+            now.to_rfc3339()
+        ],
+    )
+    .map_err(|e| e.to_string())?;
+    conn.execute(
+        "DELETE FROM external_access_log WHERE created_at < ?1",
+        params![(now - Duration::days(ACCESS_LOG_RETENTION_DAYS)).to_rfc3339()],
+    )
+    .map_err(|e| e.to_string())?;
+
+    Ok(())
+}
+
+/// Entry point for every external handler: authenticates the token, applies the per-token
+/// rate limit (`api_rate_limit_per_minute`, 0 = unlimited), checks `scope` and logs the outcome.
+pub(crate) fn authorize_external_call_in_conn(
+    conn: &Connection,
+    secret: &str,
+    endpoint: &str,
+    scope: Option<&str>,
+) -> Result<ApiAccess, String> {
+    let access = match authorize_api_token_in_conn(conn, secret) {
+        Ok(access) => access,
+        Err(error) => {
+            record_external_access_in_conn(conn, None, "unknown", endpoint, "denied", &error)?;
+            return Err(error);
+        }
+    };
+
+    let limit = read_i64_setting(conn, "api_rate_limit_per_minute")?;
+    if limit > 0 {
+        let recent_calls: i64 = conn
+            .query_row(
+                "SELECT COUNT(*) FROM external_access_log
+                 WHERE token_id = ?1 AND outcome = 'allowed' AND created_at >= ?2",
+                params![
+                    access.token_id,
+                    (Utc::now() - Duration::minutes(1)).to_rfc3339()
+                ],
+                |row| row.get(0),
+            )
+            .map_err(|e| e.to_string())?;
+        if recent_calls >= limit {
+            let error = format!("Rate limit of {} calls per minute exceeded", limit);
+            record_external_access_in_conn(
+                conn,
+                Some(access.token_id),
+                &access.token_name,
+                endpoint,
+                "rate_limited",
+                &error,
+            )?;
+            return Err(error);
+        }
+    }
+
+    if let Some(scope) = scope {
+        if let Err(error) = access.require(scope) {
+            record_external_access_in_conn(
+                conn,
+                Some(access.token_id),
+                &access.token_name,
+                endpoint,
+                "denied",
+                &error,
+            )?;
+            return Err(error);
+        }
+    }
+
+    record_external_access_in_conn(
+        conn,
+        Some(access.token_id),
+        &access.token_name,
+        endpoint,
+        "allowed",
+        "",
+    )?;
+    Ok(access)
+}
+
+pub(crate) fn get_external_access_log_in_conn(
+    conn: &Connection,
+    limit: i64,
+) -> Result<Vec<ExternalAccessLogEntry>, String> {
+    let mut stmt = conn
+        .prepare(
+            "SELECT id, token_id, caller, endpoint, outcome, detail, created_at
+             FROM external_access_log ORDER BY created_at DESC, id DESC LIMIT ?1",
+        )
+        .map_err(|e| e.to_string())?;
+    let entries_iter = stmt
+        .query_map(params![limit], |row| {
+            Ok(ExternalAccessLogEntry {
+                id: row.get(0)?,
+                token_id: row.get(1)?,
+                caller: row.get(2)?,
+                endpoint: row.get(3)?,
+                outcome: row.get(4)?,
+                detail: row.get(5)?,
+                created_at: row.get(6)?,
+            })
+        })
+        .map_err(|e| e.to_string())?;
+
+    let mut entries = Vec::new();
+    for entry in entries_iter {
+        entries.push(entry.map_err(|e| e.to_string())?);
+    }
+
+    Ok(entries)
+}
+
+#[tauri::command]
+pub fn create_api_token(
+    name: String,
+    scopes: Option<Vec<String>>,
+    preset: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<CreatedApiToken, String> {
+    let scopes = match (scopes, preset) {
+        (Some(scopes), _) => scopes,
+        (None, Some(preset)) => {
+            preset_scopes(&preset).ok_or_else(|| format!("Unknown scope preset: {}", preset))?
+        }
+        (None, None) => return Err("Provide scopes or a scope preset".to_string()),
+    };
+    let conn = state.db.lock().map_err(|e| e.to_string())?;
+    create_api_token_in_conn(&conn, &name, &scopes)
+}
+
+#[tauri::command]
+pub fn list_api_tokens(state: State<'_, AppState>) -> Result<Vec<ApiToken>, String> {
+    let conn = state.db.lock().map_err(|e| e.to_string())?;
+    list_api_tokens_in_conn(&conn)
+}
+
+#[tauri::command]
+pub fn revoke_api_token(id: i64, state: State<'_, AppState>) -> Result<(), String> {
+    let conn = state.db.lock().map_err(|e| e.to_string())?;
+    conn.execute("DELETE FROM api_tokens WHERE id = ?1", params![id])
+        .map_err(|e| e.to_string())?;
+
+    Ok(())
+}
+
+/// Lets the settings screen verify a pasted token, optionally against one scope.
+#[tauri::command]
+pub fn check_api_token(
+    secret: String,
+    scope: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<Vec<String>, String> {
+    let conn = state.db.lock().map_err(|e| e.to_string())?;
+    let access =
+        authorize_external_call_in_conn(&conn, &secret, "settings/check_token", scope.as_deref())?;
+
+    Ok(access.scopes())
+}
+
+#[tauri::command]
+pub fn get_api_scopes() -> Vec<String> {
+    API_SCOPES.iter().map(|scope| scope.to_string()).collect()
+}
+
+#[tauri::command]
+pub fn get_external_access_log(
+    limit: Option<i64>,
+    state: State<'_, AppState>,
+) -> Result<Vec<ExternalAccessLogEntry>, String> {
+    let conn = state.db.lock().map_err(|e| e.to_string())?;
+    get_external_access_log_in_conn(&conn, limit.unwrap_or(100).clamp(1, 1000))
+}
@@ -0,0 +1,165 @@
+use crate::models::AppearanceSettings;
+use rusqlite::Connection;
+use tauri::{AppHandle, State};
+
+use super::events::emit_event;
+use super::settings::{read_bool_setting, read_i64_setting, read_setting, write_setting};
+use super::AppState;
+
+/// Emitted to every window with the new `AppearanceSettings` after they change.
+pub(crate) const APPEARANCE_CHANGED_EVENT: &str = "appearance-changed";
+
+/// App settings backing `AppearanceSettings`; they travel with the backup like any other.
+pub(crate) const APPEARANCE_SETTING_KEYS: [&str; 7] = [
+    "appearance_theme_preset",
+    "appearance_mode",
+    "appearance_accent_color",
+    "appearance_font_preset",
+    "appearance_font_size",
+    "appearance_compact",
+    "appearance_border_radius",
+];
+
+const THEME_PRESETS: [&str; 6] = [
+    "monochrome",
+    "ocean",
+    "forest",
+    "sunset",
+    "midnight",
+    "cherry",
+];
+const APPEARANCE_MODES: [&str; 3] = ["system", "light", "dark"];
+const FONT_PRESETS: [&str; 3] = ["inter", "roboto", "mono"];
+const FONT_SIZE_RANGE: std::ops::RangeInclusive<i64> = 12..=20;
+const BORDER_RADIUS_RANGE: std::ops::RangeInclusive<i64> = 6..=18;
+
+pub(crate) fn get_appearance_settings_in_conn(
+    conn: &Connection,
+) -> Result<AppearanceSettings, String> {
+    let placeholders = vec!["?"; APPEARANCE_SETTING_KEYS.len()].join(", ");
+    let updated_at: Option<String> = conn
+        .query_row(
+            &format!(
+                "SELECT MAX(updated_at) FROM app_settings WHERE key IN ({})",
+                placeholders
+            ),
+            rusqlite::params_from_iter(APPEARANCE_SETTING_KEYS),
+            |row| row.get(0),
+        )
+        .map_err(|e| e.to_string())?;
+
+    Ok(AppearanceSettings {
+        theme_preset: read_setting(conn, "appearance_theme_preset")?,
+        mode: read_setting(conn, "appearance_mode")?,
+        accent_color: Some(read_setting(conn, "appearance_accent_color")?)
+            .filter(|color| !color.is_empty()),
+        font_preset: read_setting(conn, "appearance_font_preset")?,
+        font_size: read_i64_setting(conn, "appearance_font_size")?,
+        compact: read_bool_setting(conn, "appearance_compact")?,
+        border_radius: read_i64_setting(conn, "appearance_border_radius")?,
+        updated_at,
+    })
+}
+
+/// `#rgb` or `#rrggbb`, returned as lower-case `#rrggbb`.
+fn normalize_accent_color(color: &str) -> Result<String, String> {
+    let hex = color.trim().trim_start_matches('#');
+    if !hex.chars().all(|digit| digit.is_ascii_hexdigit()) {
+        return Err(format!("Invalid accent color: {}", color));
+    }
+    match hex.len() {
+        3 => Ok(format!(
+            "#{}",
+            hex.chars()
+                .flat_map(|digit| [digit, digit])
+                .collect::<String>()
+                .to_lowercase()
+        )),
+        6 => Ok(format!("#{}", hex.to_lowercase())),
+        _ => Err(format!("Invalid accent color: {}", color)),
+    }
+}
+
+fn one_of(value: &str, allowed: &[&str], label: &str) -> Result<String, String> {
+    let value = value.trim().to_lowercase();
+    if allowed.contains(&value.as_str()) {
+        Ok(value)
+    } else {
+        Err(format!("{} must be one of: {}", label, allowed.join(", ")))
+    }
+}
+
+/// Validates and stores every field of `settings`; `updated_at` is ignored.
+pub(crate) fn update_appearance_settings_in_conn(
+    conn: &Connection,
+    settings: &AppearanceSettings,
+) -> Result<AppearanceSettings, String> {
+    let theme_preset = one_of(&settings.theme_preset, &THEME_PRESETS, "Theme")?;
+    let mode = one_of(&settings.mode, &APPEARANCE_MODES, "Appearance mode")?;
+    let font_preset = one_of(&settings.font_preset, &FONT_PRESETS, "Font")?;
+    let accent_color = match settings.accent_color.as_deref().map(str::trim) {
+        Some(color) if !color.is_empty() => normalize_accent_color(color)?,
+        _ => String::new(),
+    };
+    if !FONT_SIZE_RANGE.contains(&settings.font_size) {
+        return Err(format!(
+            "Font size must be between {} and {}",
+            FONT_SIZE_RANGE.start(),
+            FONT_SIZE_RANGE.end()
+        ));
+    }
+    if !BORDER_RADIUS_RANGE.contains(&settings.border_radius) {
+        return Err(format!(
+            "Corner radius must be between {} and {}",
+            BORDER_RADIUS_RANGE.start(),
+            BORDER_RADIUS_RANGE.end()
+        ));
+    }
+
+    for (key, value) in [
+        ("appearance_theme_preset", theme_preset),
+        ("appearance_mode", mode),
+        ("appearance_accent_color", accent_color),
+        ("appearance_font_preset", font_preset),
+        ("appearance_font_size", settings.font_size.to_string()),
+        ("appearance_compact", settings.compact.to_string()),
+        (
+            "appearance_border_radius",
+            settings.border_radius.to_string(),
+        ),
+    ] {
+        write_setting(conn, key, &value)?;
+    }
+
+    get_appearance_settings_in_conn(conn)
+}
+
+/// Tells every window, the widget included, about the stored appearance.
+pub(crate) fn emit_appearance_changed(app: &AppHandle, conn: &Connection) {
+    let result = get_appearance_settings_in_conn(conn).and_then(|settings| {
+        emit_event(app, APPEARANCE_CHANGED_EVENT, &settings).map_err(|e| e.to_string())
+    });
+    if let Err(error) = result {
+        eprintln!("Failed to emit appearance change: {error}");
+    }
+}
+
+/// Theme, accent color, font and density shared by all windows. `updated_at` is `None`
+/// until the appearance was saved once.
+#[tauri::command]
+pub fn get_appearance_settings(state: State<'_, AppState>) -> Result<AppearanceSettings, String> {
+    let conn = state.db.lock().map_err(|e| e.to_string())?;
+    get_appearance_settings_in_conn(&conn)
+}
+
+#[tauri::command]
+pub fn update_appearance_settings(
+    settings: AppearanceSettings,
+    app: AppHandle,
+    state: State<'_, AppState>,
+) -> Result<AppearanceSettings, String> {
+    let conn = state.db.lock().map_err(|e| e.to_string())?;
+    let updated = update_appearance_settings_in_conn(&conn, &settings)?;
+    emit_appearance_changed(&app, &conn);
+    Ok(updated)
+}
@@ -0,0 +1,517 @@
+use crate::models::{Attachment, EntrySearchResult};
+use chrono::{NaiveDate, Utc};
+use rusqlite::{params, Connection, OptionalExtension};
+use std::fs;
+use std::io::{ErrorKind, Read};
+use std::path::{Path, PathBuf};
+use tauri::{AppHandle, Manager, State};
+
+use super::encryption::journal_encryption_enabled_in_conn;
+use super::entries::entry_from_row;
+use super::AppState;
+
+/// Copies larger than this are rejected; attachments are meant for screenshots and small files.
+const MAX_ATTACHMENT_BYTES: u64 = 50 * 1024 * 1024;
+/// Only the start of large text files and command logs goes into the search index.
+const MAX_EXTRACTED_TEXT_BYTES: usize = 1024 * 1024;
+
+pub(crate) const ATTACHMENT_KIND_FILE: &str = "file";
+pub(crate) const ATTACHMENT_KIND_COMMAND_OUTPUT: &str = "command_output";
+const COMMAND_OUTPUT_FILE_NAME: &str = "command-output.log";
+
+/// Directory under `app_data_dir` holding one sub-folder per entry id.
+pub(crate) fn attachments_root(app: &AppHandle) -> Result<PathBuf, String> {
+    let app_data_dir = app.path().app_data_dir().map_err(|e| e.to_string())?;
+    Ok(app_data_dir.join("attachments"))
+}
+
+/// Keeps the original name readable on disk while stripping path separators and odd characters.
+pub(crate) fn sanitize_file_name(name: &str) -> String {
+    let sanitized: String = name
+        .chars()
+        .map(|character| {
+            if character.is_alphanumeric() || matches!(character, '.' | '-' | '_') {
+                character
+            } else {
+                '_'
+            }
+        })
+        .take(80)
+        .collect();
+    let sanitized = sanitized.trim_matches('.').to_string();
+    if sanitized.is_empty() {
+        "file".to_string()
+    } else {
+        sanitized
+    }
+}
+
+pub(crate) fn mime_type_for(file_name: &str) -> &'static str {
+    let extension = Path::new(file_name)
+        .extension()
+        .and_then(|extension| extension.to_str())
+        .unwrap_or_default()
+        .to_ascii_lowercase();
+    match extension.as_str() {
+        "png" => "image/png",
+        "jpg" | "jpeg" => "image/jpeg",
+        "gif" => "image/gif",
+        "webp" => "image/webp",
+        "svg" => "image/svg+xml",
+        "pdf" => "application/pdf",
+        "txt" | "log" => "text/plain",
+        "md" => "text/markdown",
+        "json" => "application/json",
+        "csv" => "text/csv",
+        "zip" => "application/zip",
+        _ => "application/octet-stream",
+    }
+}
+
+fn attachment_from_row(row: &rusqlite::Row<'_>, root: &Path) -> rusqlite::Result<Attachment> {
+    let stored_path: String = row.get(5)?;
+    Ok(Attachment {
+        id: row.get(0)?,
+        entry_id: row.get(1)?,
+        file_name: row.get(2)?,
+        mime_type: row.get(3)?,
+        size_bytes: row.get(4)?,
+        path: root.join(stored_path).to_string_lossy().to_string(),
+        created_at: row.get(6)?,
+        kind: row.get(7)?,
+        command: row.get(8)?,
+    })
+}
+
+/// Cuts `text` to at most `max_bytes` without splitting a character.
+fn truncate_to_bytes(text: &str, max_bytes: usize) -> &str {
+    if text.len() <= max_bytes {
+        return text;
+    }
+    let mut end = max_bytes;
+    while !text.is_char_boundary(end) {
+        end -= 1;
+    }
+    &text[..end]
+}
+
+/// Text indexed for search: the start of text-like files, nothing for binaries.
+fn extract_text(source: &Path, mime_type: &str) -> String {
+    let is_text = mime_type.starts_with("text/") || mime_type == "application/json";
+    if !is_text {
+        return String::new();
+    }
+
+    let mut bytes = Vec::new();
+    let read = fs::File::open(source).and_then(|file| {
+        file.take(MAX_EXTRACTED_TEXT_BYTES as u64)
+            .read_to_end(&mut bytes)
+    });
+    match read {
+        Ok(_) => String::from_utf8_lossy(&bytes).to_string(),
+        Err(_) => String::new(),
+    }
+}
+
+/// Drops ANSI escape sequences (colors, cursor movement, window titles) from terminal output.
+pub(crate) fn strip_ansi_codes(text: &str) -> String {
+    let mut stripped = String::with_capacity(text.len());
+    let mut characters = text.chars().peekable();
+    while let Some(character) = characters.next() {
+        if character != '\u{1b}' {
+            stripped.push(character);
+            continue;
+        }
+        match characters.next() {
+            // CSI: parameters until a final byte in `@`..=`~`.
+            Some('[') => {
+                for next in characters.by_ref() {
+                    if ('@'..='~').contains(&next) {
+                        break;
+                    }
+                }
+            }
+            // OSC: until BEL or ESC `\`.
+            Some(']') => {
+                while let Some(next) = characters.next() {
+                    if next == '\u{7}' {
+                        break;
+                    }
+                    if next == '\u{1b}' && characters.peek() == Some(&'\\') {
+                        characters.next();
+                        break;
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    stripped.replace("\r\n", "\n")
+}
+
+/// Removes stored files, ignoring ones that are already gone.
+pub(crate) fn remove_attachment_files(root: &Path, stored_paths: &[String]) {
+    for stored_path in stored_paths {
+        if let Err(error) = fs::remove_file(root.join(stored_path)) {
+            if error.kind() != ErrorKind::NotFound {
+                eprintln!("Failed to remove attachment {stored_path}: {error}");
+            }
+        }
+    }
+}
+
+/// Stored paths of an entry's attachments, collected before the entry is deleted.
+pub(crate) fn entry_attachment_paths(conn: &Connection, date: &str) -> Result<Vec<String>, String> {
+    let mut stmt = conn
+        .prepare(
+            "SELECT a.stored_path FROM attachments a
+             JOIN entries e ON e.id = a.entry_id
+             WHERE e.date = ?1",
+        )
+        .map_err(|e| e.to_string())?;
+    let paths_iter = stmt
+        .query_map(params![date], |row| row.get::<_, String>(0))
+        .map_err(|e| e.to_string())?;
+
+    let mut paths = Vec::new();
+    for path in paths_iter {
+        paths.push(path.map_err(|e| e.to_string())?);
+    }
+
+    Ok(paths)
+}
+
+pub(crate) fn attach_file_to_entry_in_conn(
+    conn: &Connection,
+    root: &Path,
+    entry_id: i64,
+    source: &Path,
+) -> Result<Attachment, String> {
+    let entry_exists: bool = conn
+        .query_row(
+            "SELECT EXISTS(SELECT 1 FROM entries WHERE id = ?1)",
+            params![entry_id],
+            |row| row.get(0),
+        )
+        .map_err(|e| e.to_string())?;
+    if !entry_exists {
+        return Err(format!("Entry #{} not found", entry_id));
+    }
+
+    let metadata =
+        fs::metadata(source).map_err(|e| format!("Cannot read {}: {}", source.display(), e))?;
+    if !metadata.is_file() {
+        return Err(format!("{} is not a file", source.display()));
+    }
+    if metadata.len() > MAX_ATTACHMENT_BYTES {
+        return Err(format!(
+            "Attachments are limited to {} MB",
+            MAX_ATTACHMENT_BYTES / 1024 / 1024
+        ));
+    }
+
+    let file_name = source
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_else(|| "file".to_string());
+    let now = Utc::now();
+    let stored_path = format!(
+        "{}/{}-{}",
+        entry_id,
+        now.timestamp_nanos_opt().unwrap_or_default(),
+        sanitize_file_name(&file_name)
+    );
+    let destination = root.join(&stored_path);
+    if let Some(parent) = destination.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create {}: {}", parent.display(), e))?;
+    }
+    let size_bytes = fs::copy(source, &destination)
+        .map_err(|e| format!("Failed to copy {}: {}", source.display(), e))?;
+
+    let inserted = conn.execute(
+        "INSERT INTO attachments
+            (entry_id, file_name, mime_type, size_bytes, stored_path, created_at, kind, extracted_text)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+        params![
+            entry_id,
+            file_name,
+            mime_type_for(&file_name),
+            size_bytes as i64,
+            stored_path,
+            now.to_rfc3339(),
+            ATTACHMENT_KIND_FILE,
+            extract_text(&destination, mime_type_for(&file_name))
+        ],
+    );
+    if let Err(error) = inserted {
+        remove_attachment_files(root, &[stored_path]);
+        return Err(error.to_string());
+    }
+
+    Ok(Attachment {
+        id: conn.last_insert_rowid(),
+        entry_id,
+        file_name: file_name.clone(),
+        mime_type: mime_type_for(&file_name).to_string(),
+        size_bytes: size_bytes as i64,
+        path: destination.to_string_lossy().to_string(),
+        created_at: now.to_rfc3339(),
+        kind: ATTACHMENT_KIND_FILE.to_string(),
+        command: None,
+    })
+}
+
+/// Stores captured terminal output as a `command_output` attachment on the entry for
+/// `entry_date`, creating (or restoring from the trash) an empty entry if needed.
+/// Refused while journal encryption is on: the output would be stored and indexed as
+/// plaintext.
+pub(crate) fn attach_command_output_in_conn(
+    conn: &Connection,
+    root: &Path,
+    entry_date: &str,
+    command: &str,
+    output: &str,
+) -> Result<Attachment, String> {
+    let entry_date = NaiveDate::parse_from_str(entry_date.trim(), "%Y-%m-%d")
+        .map_err(|_| format!("Invalid date: {}", entry_date))?
+        .format("%Y-%m-%d")
+        .to_string();
+    let command = command.trim();
+    if command.is_empty() {
+        return Err("Command is required".to_string());
+    }
+    if journal_encryption_enabled_in_conn(conn)? {
+        return Err(
+            "The journal is encrypted; command output can't be attached unencrypted".to_string(),
+        );
+    }
+    let output = strip_ansi_codes(output);
+    if output.trim().is_empty() {
+        return Err("Command output is empty".to_string());
+    }
+    if output.len() as u64 > MAX_ATTACHMENT_BYTES {
+        return Err(format!(
+            "Attachments are limited to {} MB",
+            MAX_ATTACHMENT_BYTES / 1024 / 1024
+        ));
+    }
+
+    let now = Utc::now();
+    conn.execute(
+        "INSERT INTO entries (date, yesterday, today, created_at) VALUES (?1, '', '', ?2)
+         ON CONFLICT(date) DO UPDATE SET deleted_at = NULL",
+        params![entry_date, now.to_rfc3339()],
+    )
+    .map_err(|e| e.to_string())?;
+    let entry_id: i64 = conn
+        .query_row(
+            "SELECT id FROM entries WHERE date = ?1",
+            params![entry_date],
+            |row| row.get(0),
+        )
+        .map_err(|e| e.to_string())?;
+
+    let stored_path = format!(
+        "{}/{}-{}",
+        entry_id,
+        now.timestamp_nanos_opt().unwrap_or_default(),
+        COMMAND_OUTPUT_FILE_NAME
+    );
+    let destination = root.join(&stored_path);
+    if let Some(parent) = destination.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create {}: {}", parent.display(), e))?;
+    }
+    fs::write(&destination, &output)
+        .map_err(|e| format!("Failed to write {}: {}", destination.display(), e))?;
+
+    let mime_type = mime_type_for(COMMAND_OUTPUT_FILE_NAME);
+    let inserted = conn.execute(
+        "INSERT INTO attachments
+            (entry_id, file_name, mime_type, size_bytes, stored_path, created_at, kind, command, extracted_text)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+        params![
+            entry_id,
+            COMMAND_OUTPUT_FILE_NAME,
+            mime_type,
+            output.len() as i64,
+            stored_path,
+            now.to_rfc3339(),
+            ATTACHMENT_KIND_COMMAND_OUTPUT,
+            command,
+            truncate_to_bytes(&output, MAX_EXTRACTED_TEXT_BYTES)
+        ],
+    );
+    if let Err(error) = inserted {
+        remove_attachment_files(root, &[stored_path]);
+        return Err(error.to_string());
+    }
+
+    Ok(Attachment {
+        id: conn.last_insert_rowid(),
+        entry_id,
+        file_name: COMMAND_OUTPUT_FILE_NAME.to_string(),
+        mime_type: mime_type.to_string(),
+        size_bytes: output.len() as i64,
+        path: destination.to_string_lossy().to_string(),
+        created_at: now.to_rfc3339(),
+        kind: ATTACHMENT_KIND_COMMAND_OUTPUT.to_string(),
+        command: Some(command.to_string()),
+    })
+}
+
+/// Indexed text of an attachment; for command output this is the whole captured log
+/// up to the index limit.
+pub(crate) fn get_attachment_text_in_conn(conn: &Connection, id: i64) -> Result<String, String> {
+    conn.query_row(
+        "SELECT extracted_text FROM attachments WHERE id = ?1",
+        params![id],
+        |row| row.get(0),
+    )
+    .optional()
+    .map_err(|e| e.to_string())?
+    .ok_or_else(|| format!("Attachment #{} not found", id))
+}
+
+/// Entries whose attachments match `fts_query` (see `fts_query_from_text`), one row
+/// per entry with the best-ranked attachment as the snippet.
+pub(crate) fn search_attachment_text_in_conn(
+    conn: &Connection,
+    fts_query: &str,
+) -> Result<Vec<EntrySearchResult>, String> {
+    let mut stmt = conn
+        .prepare(
+            "SELECT e.id, e.date, e.yesterday, e.today, e.project_id, e.created_at, e.pinned, e.blockers, e.notes,
+                    snippet(attachments_fts, -1, '<mark>', '</mark>', '…', 16),
+                    bm25(attachments_fts)
+             FROM attachments_fts
+             JOIN attachments a ON a.id = attachments_fts.rowid
+             JOIN entries e ON e.id = a.entry_id
+             WHERE attachments_fts MATCH ?1 AND e.deleted_at IS NULL
+             ORDER BY bm25(attachments_fts) ASC, e.date DESC
+             LIMIT 100",
+        )
+        .map_err(|e| e.to_string())?;
+    let results_iter = stmt
+        .query_map(params![fts_query], |row| {
+            Ok(EntrySearchResult {
+                entry: entry_from_row(row)?,
+                snippet: row.get(9)?,
+                rank: row.get(10)?,
+            })
+        })
+        .map_err(|e| e.to_string())?;
+
+    let mut results: Vec<EntrySearchResult> = Vec::new();
+    for result in results_iter {
+        let result = result.map_err(|e| e.to_string())?;
+        if !results
+            .iter()
+            .any(|existing| existing.entry.id == result.entry.id)
+        {
+            results.push(result);
+        }
+    }
+
+    Ok(results)
+}
+
+pub(crate) fn list_entry_attachments_in_conn(
+    conn: &Connection,
+    root: &Path,
+    entry_id: i64,
+) -> Result<Vec<Attachment>, String> {
+    let mut stmt = conn
+        .prepare(
+            "SELECT id, entry_id, file_name, mime_type, size_bytes, stored_path, created_at, kind, command
+             FROM attachments WHERE entry_id = ?1 ORDER BY created_at ASC, id ASC",
+        )
+        .map_err(|e| e.to_string())?;
+    let attachments_iter = stmt
+        .query_map(params![entry_id], |row| attachment_from_row(row, root))
+        .map_err(|e| e.to_string())?;
+
+    let mut attachments = Vec::new();
+    for attachment in attachments_iter {
+        attachments.push(attachment.map_err(|e| e.to_string())?);
+    }
+
+    Ok(attachments)
+}
+
+pub(crate) fn delete_attachment_in_conn(
+    conn: &Connection,
+    root: &Path,
+    id: i64,
+) -> Result<(), String> {
+    let stored_path: Option<String> = conn
+        .query_row(
+            "SELECT stored_path FROM attachments WHERE id = ?1",
+            params![id],
+            |row| row.get(0),
+        )
+        .optional()
+        .map_err(|e| e.to_string())?;
+    let stored_path = stored_path.ok_or_else(|| format!("Attachment #{} not found", id))?;
+
+    conn.execute("DELETE FROM attachments WHERE id = ?1", params![id])
+        .map_err(|e| e.to_string())?;
+    remove_attachment_files(root, &[stored_path]);
+
+    Ok(())
+}
+
+#[tauri::command]
+pub fn attach_file_to_entry(
+    entry_id: i64,
+    path: String,
+    app: AppHandle,
+    state: State<'_, AppState>,
+) -> Result<Attachment, String> {
+    let root = attachments_root(&app)?;
+    let conn = state.db.lock().map_err(|e| e.to_string())?;
+    attach_file_to_entry_in_conn(&conn, &root, entry_id, Path::new(path.trim()))
+}
+
+#[tauri::command]
+pub fn list_entry_attachments(
+    entry_id: i64,
+    app: AppHandle,
+    state: State<'_, AppState>,
+) -> Result<Vec<Attachment>, String> {
+    let root = attachments_root(&app)?;
+    let conn = state.db.lock().map_err(|e| e.to_string())?;
+    list_entry_attachments_in_conn(&conn, &root, entry_id)
+}
+
+#[tauri::command]
+pub fn delete_attachment(
+    id: i64,
+    app: AppHandle,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    let root = attachments_root(&app)?;
+    let conn = state.db.lock().map_err(|e| e.to_string())?;
+    delete_attachment_in_conn(&conn, &root, id)
+}
+
+#[tauri::command]
+pub fn attach_command_output(
+    entry_date: String,
+    command: String,
+    output: String,
+    app: AppHandle,
+    state: State<'_, AppState>,
+) -> Result<Attachment, String> {
+    let root = attachments_root(&app)?;
+    let conn = state.db.lock().map_err(|e| e.to_string())?;
+    attach_command_output_in_conn(&conn, &root, &entry_date, &command, &output)
+}
+
+#[tauri::command]
+pub fn get_attachment_text(id: i64, state: State<'_, AppState>) -> Result<String, String> {
+    let conn = state.db.lock().map_err(|e| e.to_string())?;
+    get_attachment_text_in_conn(&conn, id)
+}
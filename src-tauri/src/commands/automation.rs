@@ -0,0 +1,449 @@
+use crate::models::{AutomationRule, AutomationRuleChange, AutomationRunLogEntry, TaskTag};
+use chrono::{DateTime, Duration, Local, Utc};
+use rusqlite::{params, Connection, OptionalExtension};
+use serde::Deserialize;
+use tauri::{AppHandle, Manager, State};
+
+use super::events::emit_event;
+use super::validation::normalize_entry_tag;
+use super::AppState;
+
+const AUTOMATION_RULE_COLUMNS: &str = "id, name, condition, condition_value, priority_filter, action, action_value, enabled, created_at, updated_at";
+const RULE_CONDITIONS: &[&str] = &["due_within_hours", "in_progress_days"];
+const TASK_PRIORITIES: &[&str] = &["low", "medium", "high", "urgent"];
+
+#[derive(Debug, Deserialize)]
+pub struct AutomationRuleInput {
+    pub name: String,
+    pub condition: String,
+    pub condition_value: i64,
+    pub priority_filter: Option<String>,
+    pub action: String,
+    pub action_value: String,
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+/// Validated column values for an insert or update.
+struct AutomationRuleFields {
+    name: String,
+    condition: String,
+    condition_value: i64,
+    priority_filter: Option<String>,
+    action: String,
+    action_value: String,
+}
+
+fn normalize_rule_priority(value: &str) -> Result<String, String> {
+    let priority = value.trim().to_lowercase();
+    if TASK_PRIORITIES.contains(&priority.as_str()) {
+        Ok(priority)
+    } else {
+        Err(format!("Unknown priority: {}", value))
+    }
+}
+
+fn normalize_automation_rule_input(
+    input: &AutomationRuleInput,
+) -> Result<AutomationRuleFields, String> {
+    let name = input.name.trim();
+    if name.is_empty() {
+        return Err("Rule name is required".to_string());
+    }
+    let condition = input.condition.trim().to_lowercase();
+    if !RULE_CONDITIONS.contains(&condition.as_str()) {
+        return Err(format!("Unknown rule condition: {}", input.condition));
+    }
+    if input.condition_value < 1 {
+        return Err("Rule threshold must be at least 1".to_string());
+    }
+    let priority_filter = match input.priority_filter.as_deref().map(str::trim) {
+        None | Some("") => None,
+        Some(priority) => Some(normalize_rule_priority(priority)?),
+    };
+    let action = input.action.trim().to_lowercase();
+    let action_value = match action.as_str() {
+        "set_priority" => normalize_rule_priority(&input.action_value)?,
+        "add_tag" => normalize_entry_tag(&input.action_value)?,
+        _ => return Err(format!("Unknown rule action: {}", input.action)),
+    };
+
+    Ok(AutomationRuleFields {
+        name: name.to_string(),
+        condition,
+        condition_value: input.condition_value,
+        priority_filter,
+        action,
+        action_value,
+    })
+}
+
+fn automation_rule_from_row(row: &rusqlite::Row<'_>) -> rusqlite::Result<AutomationRule> {
+    Ok(AutomationRule {
+        id: row.get(0)?,
+        name: row.get(1)?,
+        condition: row.get(2)?,
+        condition_value: row.get(3)?,
+        priority_filter: row.get(4)?,
+        action: row.get(5)?,
+        action_value: row.get(6)?,
+        enabled: row.get(7)?,
+        created_at: row.get(8)?,
+        updated_at: row.get(9)?,
+    })
+}
+
+fn get_automation_rule_in_conn(conn: &Connection, id: i64) -> Result<AutomationRule, String> {
+    conn.query_row(
+        &format!(
+            "SELECT {} FROM automation_rules WHERE id = ?1",
+            AUTOMATION_RULE_COLUMNS
+        ),
+        params![id],
+        automation_rule_from_row,
+    )
+    .optional()
+    .map_err(|e| e.to_string())?
+    .ok_or_else(|| "Rule not found".to_string())
+}
+
+pub(crate) fn get_automation_rules_in_conn(
+    conn: &Connection,
+) -> Result<Vec<AutomationRule>, String> {
+    let mut stmt = conn
+        .prepare(&format!(
+            "SELECT {} FROM automation_rules ORDER BY id ASC",
+            AUTOMATION_RULE_COLUMNS
+        ))
+        .map_err(|e| e.to_string())?;
+    let rules_iter = stmt
+        .query_map([], automation_rule_from_row)
+        .map_err(|e| e.to_string())?;
+
+    let mut rules = Vec::new();
+    for rule in rules_iter {
+        rules.push(rule.map_err(|e| e.to_string())?);
+    }
+
+    Ok(rules)
+}
+
+pub(crate) fn create_automation_rule_in_conn(
+    conn: &Connection,
+    input: &AutomationRuleInput,
+) -> Result<AutomationRule, String> {
+    let fields = normalize_automation_rule_input(input)?;
+    let now = Utc::now().to_rfc3339();
+    conn.execute(
+        "INSERT INTO automation_rules (name, condition, condition_value, priority_filter, action, action_value, enabled, created_at, updated_at)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?8)",
+        params![
+            fields.name,
+            fields.condition,
+            fields.condition_value,
+            fields.priority_filter,
+            fields.action,
+            fields.action_value,
+            input.enabled,
+            now
+        ],
+    )
+    .map_err(|e| e.to_string())?;
+
+    get_automation_rule_in_conn(conn, conn.last_insert_rowid())
+}
+
+pub(crate) fn update_automation_rule_in_conn(
+    conn: &Connection,
+    id: i64,
+    input: &AutomationRuleInput,
+) -> Result<AutomationRule, String> {
+    let fields = normalize_automation_rule_input(input)?;
+    let changed = conn
+        .execute(
+            "UPDATE automation_rules
+             SET name = ?1, condition = ?2, condition_value = ?3, priority_filter = ?4,
+                 action = ?5, action_value = ?6, enabled = ?7, updated_at = ?8
+             WHERE id = ?9",
+            params![
+                fields.name,
+                fields.condition,
+                fields.condition_value,
+                fields.priority_filter,
+                fields.action,
+                fields.action_value,
+                input.enabled,
+                Utc::now().to_rfc3339(),
+                id
+            ],
+        )
+        .map_err(|e| e.to_string())?;
+    if changed == 0 {
+        return Err("Rule not found".to_string());
+    }
+
+    get_automation_rule_in_conn(conn, id)
+}
+
+/// Open tasks outside the review queue that match the rule's condition, as
+/// `(id, title, priority)`. Due dates are local days, so a task counts as due at
+/// the end of its due date; overdue tasks match `due_within_hours` too.
+fn tasks_matching_rule(
+    conn: &Connection,
+    rule: &AutomationRule,
+    now: DateTime<Utc>,
+) -> Result<Vec<(i64, String, String)>, String> {
+    let (condition_sql, cutoff) = match rule.condition.as_str() {
+        "due_within_hours" => {
+            let horizon =
+                now.with_timezone(&Local).naive_local() + Duration::hours(rule.condition_value);
+            let last_due_day = horizon.date() - Duration::days(1);
+            (
+                "due_date IS NOT NULL AND due_date <= ?1",
+                last_due_day.format("%Y-%m-%d").to_string(),
+            )
+        }
+        "in_progress_days" => (
+            "status = 'in_progress' AND updated_at <= ?1",
+            (now - Duration::days(rule.condition_value)).to_rfc3339(),
+        ),
+        _ => return Ok(Vec::new()),
+    };
+
+    let mut stmt = conn
+        .prepare(&format!(
+            "SELECT id, title, priority FROM tasks
+             WHERE status != 'done'
+               AND id NOT IN (SELECT task_id FROM review_queue)
+               AND (?2 IS NULL OR priority = ?2)
+               AND {}
+             ORDER BY id ASC",
+            condition_sql
+        ))
+        .map_err(|e| e.to_string())?;
+    let tasks_iter = stmt
+        .query_map(params![cutoff, rule.priority_filter], |row| {
+            Ok((row.get(0)?, row.get(1)?, row.get(2)?))
+        })
+        .map_err(|e| e.to_string())?;
+
+    let mut tasks = Vec::new();
+    for task in tasks_iter {
+        tasks.push(task.map_err(|e| e.to_string())?);
+    }
+
+    Ok(tasks)
+}
+
+fn task_has_tag(conn: &Connection, task_id: i64, tag: &str) -> Result<bool, String> {
+    conn.query_row(
+        "SELECT EXISTS(SELECT 1 FROM task_tags WHERE task_id = ?1 AND tag = ?2)",
+        params![task_id, tag],
+        |row| row.get(0),
+    )
+    .map_err(|e| e.to_string())
+}
+
+/// Evaluates every enabled rule in order. With `apply` the changes are made and
+/// logged; otherwise nothing is written (a dry run). Rules only report changes that
+/// would do something, so running them again is harmless. In a dry run later rules
+/// do not see what earlier ones would change.
+pub(crate) fn evaluate_automation_rules_in_conn(
+    conn: &Connection,
+    now: DateTime<Utc>,
+    apply: bool,
+) -> Result<Vec<AutomationRuleChange>, String> {
+    let ran_at = now.to_rfc3339();
+    let mut changes = Vec::new();
+
+    for rule in get_automation_rules_in_conn(conn)? {
+        if !rule.enabled {
+            continue;
+        }
+        for (task_id, title, priority) in tasks_matching_rule(conn, &rule, now)? {
+            let change = match rule.action.as_str() {
+                "set_priority" if priority != rule.action_value => {
+                    format!("priority {} → {}", priority, rule.action_value)
+                }
+                "add_tag" if !task_has_tag(conn, task_id, &rule.action_value)? => {
+                    format!("tagged #{}", rule.action_value)
+                }
+                _ => continue,
+            };
+
+            if apply {
+                match rule.action.as_str() {
+                    "set_priority" => conn.execute(
+                        "UPDATE tasks SET priority = ?1, updated_at = ?2 WHERE id = ?3",
+                        params![rule.action_value, ran_at, task_id],
+                    ),
+                    // Tagging leaves `updated_at` alone so it does not reset staleness.
+                    _ => conn.execute(
+                        "INSERT OR IGNORE INTO task_tags (task_id, tag, created_at)
+                         VALUES (?1, ?2, ?3)",
+                        params![task_id, rule.action_value, ran_at],
+                    ),
+                }
+                .map_err(|e| e.to_string())?;
+                conn.execute(
+                    "INSERT INTO automation_rule_runs (rule_id, rule_name, task_id, task_title, change, ran_at)
+                     VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+                    params![rule.id, rule.name, task_id, title, change, ran_at],
+                )
+                .map_err(|e| e.to_string())?;
+            }
+
+            changes.push(AutomationRuleChange {
+                rule_id: rule.id,
+                rule_name: rule.name.clone(),
+                task_id,
+                task_title: title,
+                change,
+            });
+        }
+    }
+
+    Ok(changes)
+}
+
+pub(crate) fn get_automation_log_in_conn(
+    conn: &Connection,
+    limit: i64,
+) -> Result<Vec<AutomationRunLogEntry>, String> {
+    let mut stmt = conn
+        .prepare(
+            "SELECT id, rule_id, rule_name, task_id, task_title, change, ran_at
+             FROM automation_rule_runs ORDER BY ran_at DESC, id DESC LIMIT ?1",
+        )
+        .map_err(|e| e.to_string())?;
+    let log_iter = stmt
+        .query_map(params![limit.max(0)], |row| {
+            Ok(AutomationRunLogEntry {
+                id: row.get(0)?,
+                rule_id: row.get(1)?,
+                rule_name: row.get(2)?,
+                task_id: row.get(3)?,
+                task_title: row.get(4)?,
+                change: row.get(5)?,
+                ran_at: row.get(6)?,
+            })
+        })
+        .map_err(|e| e.to_string())?;
+
+    let mut log = Vec::new();
+    for entry in log_iter {
+        log.push(entry.map_err(|e| e.to_string())?);
+    }
+
+    Ok(log)
+}
+
+pub(crate) fn get_task_tags_in_conn(conn: &Connection) -> Result<Vec<TaskTag>, String> {
+    let mut stmt = conn
+        .prepare(
+            "SELECT task_id, tag, created_at FROM task_tags
+             ORDER BY task_id ASC, tag COLLATE NOCASE ASC",
+        )
+        .map_err(|e| e.to_string())?;
+    let tags_iter = stmt
+        .query_map([], |row| {
+            Ok(TaskTag {
+                task_id: row.get(0)?,
+                tag: row.get(1)?,
+                created_at: row.get(2)?,
+            })
+        })
+        .map_err(|e| e.to_string())?;
+
+    let mut tags = Vec::new();
+    for tag in tags_iter {
+        tags.push(tag.map_err(|e| e.to_string())?);
+    }
+
+    Ok(tags)
+}
+
+/// Scheduler job: applies the enabled rules and emits `automation-rules-applied`
+/// with the changes when any were made.
+pub(crate) fn apply_automation_rules_for_app(app: &AppHandle) -> Result<(), String> {
+    let changes = {
+        let state = app.state::<AppState>();
+        let conn = state.db.lock().map_err(|e| e.to_string())?;
+        evaluate_automation_rules_in_conn(&conn, Utc::now(), true)?
+    };
+    if !changes.is_empty() {
+        emit_event(app, "automation-rules-applied", &changes).map_err(|e| e.to_string())?;
+    }
+
+    Ok(())
+}
+
+#[tauri::command]
+pub fn get_automation_rules(state: State<'_, AppState>) -> Result<Vec<AutomationRule>, String> {
+    let conn = state.db.lock().map_err(|e| e.to_string())?;
+    get_automation_rules_in_conn(&conn)
+}
+
+#[tauri::command]
+pub fn create_automation_rule(
+    input: AutomationRuleInput,
+    state: State<'_, AppState>,
+) -> Result<AutomationRule, String> {
+    let conn = state.db.lock().map_err(|e| e.to_string())?;
+    create_automation_rule_in_conn(&conn, &input)
+}
+
+#[tauri::command]
+pub fn update_automation_rule(
+    id: i64,
+    input: AutomationRuleInput,
+    state: State<'_, AppState>,
+) -> Result<AutomationRule, String> {
+    let conn = state.db.lock().map_err(|e| e.to_string())?;
+    update_automation_rule_in_conn(&conn, id, &input)
+}
+
+#[tauri::command]
+pub fn delete_automation_rule(id: i64, state: State<'_, AppState>) -> Result<(), String> {
+    let conn = state.db.lock().map_err(|e| e.to_string())?;
+    conn.execute("DELETE FROM automation_rules WHERE id = ?1", params![id])
+        .map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+/// Dry run: what the enabled rules would change right now.
+#[tauri::command]
+pub fn preview_automation_rules(
+    state: State<'_, AppState>,
+) -> Result<Vec<AutomationRuleChange>, String> {
+    let conn = state.db.lock().map_err(|e| e.to_string())?;
+    evaluate_automation_rules_in_conn(&conn, Utc::now(), false)
+}
+
+/// Applies the rules now instead of waiting for the next scheduler tick.
+#[tauri::command]
+pub fn run_automation_rules(
+    state: State<'_, AppState>,
+) -> Result<Vec<AutomationRuleChange>, String> {
+    let conn = state.db.lock().map_err(|e| e.to_string())?;
+    evaluate_automation_rules_in_conn(&conn, Utc::now(), true)
+}
+
+#[tauri::command]
+pub fn get_automation_log(
+    limit: Option<i64>,
+    state: State<'_, AppState>,
+) -> Result<Vec<AutomationRunLogEntry>, String> {
+    let conn = state.db.lock().map_err(|e| e.to_string())?;
+    get_automation_log_in_conn(&conn, limit.unwrap_or(50).clamp(1, 500))
+}
+
+#[tauri::command]
+pub fn get_task_tags(state: State<'_, AppState>) -> Result<Vec<TaskTag>, String> {
+    let conn = state.db.lock().map_err(|e| e.to_string())?;
+    get_task_tags_in_conn(&conn)
+}
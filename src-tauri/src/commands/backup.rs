@@ -1,8 +1,14 @@
 use chrono::Utc;
 use rusqlite::{params, Connection};
+use serde_json::{json, Value};
 use std::collections::HashSet;
-use tauri::State;
+use tauri::{AppHandle, State};
 
+use super::encryption::{encrypt_journal_in_conn, JOURNAL_LOCKED};
+use super::page_locks::is_locked_page_content;
+use super::page_properties::set_page_properties_in_conn;
+use super::pages::{set_page_links_in_conn, validate_page_parent};
+use super::settings::{default_setting, write_setting};
 use super::validation::{
     elapsed_since, encode_json_action_items, encode_json_string_list, habit_exists,
     normalize_accumulated_seconds, normalize_goal_id, normalize_goal_milestone_title,
@@ -10,22 +16,120 @@ use super::validation::{
     normalize_meeting_action_items, normalize_meeting_participants, normalize_meeting_range,
     normalize_meeting_recurrence, normalize_meeting_reminder_minutes, normalize_meeting_status,
     normalize_meeting_title, normalize_optional_date, normalize_optional_http_url,
-    normalize_priority, normalize_progress, normalize_project_branch_name,
-    normalize_project_branch_status, normalize_project_color, normalize_project_id,
-    normalize_project_name, normalize_project_status, normalize_status, normalize_subtask_title,
-    normalize_target_per_week, normalize_task_recurrence, normalize_time_estimate_minutes,
-    normalize_parent_task_id, sanitize_meeting_action_item_task_ids,
+    normalize_page_cover, normalize_page_icon, normalize_parent_task_id, normalize_priority,
+    normalize_progress, normalize_project_branch_name, normalize_project_branch_status,
+    normalize_project_color, normalize_project_id, normalize_project_name,
+    normalize_project_status, normalize_status, normalize_subtask_title, normalize_target_per_week,
+    normalize_task_recurrence, normalize_time_estimate_minutes,
+    sanitize_meeting_action_item_task_ids,
 };
 use super::{sync_goal_progress_from_milestones, AppState, BackupPayload};
+use crate::db::SCHEMA_VERSION;
+use crate::models::BackupFormat;
+
+/// Stamped into exports as `format_version`. Bump it whenever the payload shape
+/// changes and add the matching step to `upgrade_backup_payload`; unstamped
+/// payloads are format 1.
+pub(crate) const BACKUP_FORMAT_VERSION: i64 = 2;
+
+fn backup_format_version(payload: &Value) -> Result<i64, String> {
+    match payload.get("format_version") {
+        None | Some(Value::Null) => Ok(1),
+        Some(value) => value
+            .as_i64()
+            .filter(|version| *version >= 1)
+            .ok_or_else(|| format!("Invalid backup format_version: {}", value)),
+    }
+}
+
+/// Format 1 exports could lack `habit_logs`; the completions then only live in
+/// each habit's `completed_dates`.
+fn upgrade_backup_v1_to_v2(payload: &mut Value) {
+    if payload
+        .get("habit_logs")
+        .is_some_and(|logs| !logs.is_null())
+    {
+        return;
+    }
+
+    let mut habit_logs = Vec::new();
+    if let Some(habits) = payload.get("habits").and_then(Value::as_array) {
+        for habit in habits {
+            let (Some(habit_id), Some(dates)) = (
+                habit.get("id").and_then(Value::as_i64),
+                habit.get("completed_dates").and_then(Value::as_array),
+            ) else {
+                continue;
+            };
+            for date in dates.iter().filter_map(Value::as_str) {
+                habit_logs.push(json!({ "habit_id": habit_id, "date": date }));
+            }
+        }
+    }
+    payload["habit_logs"] = Value::Array(habit_logs);
+}
+
+/// Brings a backup of any older format up to `BACKUP_FORMAT_VERSION`, one step at a time.
+pub(crate) fn upgrade_backup_payload(mut payload: Value) -> Result<BackupPayload, String> {
+    if !payload.is_object() {
+        return Err("Invalid backup: expected a JSON object".to_string());
+    }
+
+    let mut version = backup_format_version(&payload)?;
+    if version > BACKUP_FORMAT_VERSION {
+        return Err(format!(
+            "This backup uses format {}, but this version of Dev Journal only reads up to format {}. Update the app to import it.",
+            version, BACKUP_FORMAT_VERSION
+        ));
+    }
+
+    while version < BACKUP_FORMAT_VERSION {
+        match version {
+            1 => upgrade_backup_v1_to_v2(&mut payload),
+            _ => return Err(format!("No upgrade step from backup format {}", version)),
+        }
+        version += 1;
+    }
+    payload["format_version"] = json!(BACKUP_FORMAT_VERSION);
+
+    serde_json::from_value(payload).map_err(|e| format!("Invalid backup: {}", e))
+}
+
+#[tauri::command]
+pub fn get_backup_format() -> BackupFormat {
+    BackupFormat {
+        format_version: BACKUP_FORMAT_VERSION,
+        schema_version: SCHEMA_VERSION,
+    }
+}
 
 #[tauri::command]
 pub fn import_backup(
-    payload: BackupPayload,
+    payload: Value,
     replace_existing: bool,
+    app: AppHandle,
     state: State<'_, AppState>,
 ) -> Result<(), String> {
+    let payload = upgrade_backup_payload(payload)?;
+    let cipher = state.journal_cipher()?;
+    if cipher.is_locked() {
+        return Err(JOURNAL_LOCKED.to_string());
+    }
+
     let mut conn = state.db.lock().map_err(|e| e.to_string())?;
-    import_backup_into_conn(&mut conn, payload, replace_existing)
+    import_backup_into_conn(&mut conn, payload, replace_existing)?;
+    // Backups hold plaintext; re-encrypt what was just imported.
+    if cipher.is_enabled() {
+        encrypt_journal_in_conn(&conn, &cipher)?;
+    }
+    drop(conn);
+
+    // The imported settings may carry a different timer shortcut.
+    if let Err(error) = crate::shortcuts::setup_shortcuts(&app) {
+        eprintln!("Failed to re-register shortcuts after import: {error}");
+    }
+
+    Ok(())
 }
 
 pub(crate) fn import_backup_into_conn(
@@ -62,27 +166,85 @@ pub(crate) fn import_backup_into_conn(
 
     let now = Utc::now().to_rfc3339();
 
+    let mut deferred_page_parents = Vec::new();
     for page in payload.pages {
         let created_at = page.created_at.unwrap_or_else(|| now.clone());
         let updated_at = page.updated_at.unwrap_or_else(|| created_at.clone());
+        // Locked pages carry their salt in the content, so the lock survives as-is.
+        let locked = is_locked_page_content(&page.content);
+        let icon = normalize_page_icon(page.icon);
+        let cover = normalize_page_cover(page.cover);
 
-        if let Some(id) = page.id {
+        let page_id = if let Some(id) = page.id {
             tx.execute(
-                "INSERT INTO pages (id, title, content, created_at, updated_at)
-                 VALUES (?1, ?2, ?3, ?4, ?5)
+                "INSERT INTO pages (id, title, content, parent_id, created_at, updated_at, pinned, favorite, archived, sort_index, locked, icon, cover)
+                 VALUES (?1, ?2, ?3, NULL, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12)
                  ON CONFLICT(id) DO UPDATE SET
                     title = excluded.title,
                     content = excluded.content,
+                    parent_id = NULL,
                     created_at = excluded.created_at,
-                    updated_at = excluded.updated_at",
-                params![id, page.title, page.content, created_at, updated_at],
+                    updated_at = excluded.updated_at,
+                    pinned = excluded.pinned,
+                    favorite = excluded.favorite,
+                    archived = excluded.archived,
+                    sort_index = excluded.sort_index,
+                    locked = excluded.locked,
+                    icon = excluded.icon,
+                    cover = excluded.cover,
+                    deleted_at = NULL",
+                params![
+                    id,
+                    page.title,
+                    page.content,
+                    created_at,
+                    updated_at,
+                    page.pinned,
+                    page.favorite,
+                    page.archived,
+                    page.sort_index,
+                    locked,
+                    icon,
+                    cover
+                ],
             )
             .map_err(|e| e.to_string())?;
+            id
         } else {
             tx.execute(
-                "INSERT INTO pages (title, content, created_at, updated_at)
-                 VALUES (?1, ?2, ?3, ?4)",
-                params![page.title, page.content, created_at, updated_at],
+                "INSERT INTO pages (title, content, created_at, updated_at, pinned, favorite, archived, sort_index, locked, icon, cover)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11)",
+                params![
+                    page.title,
+                    page.content,
+                    created_at,
+                    updated_at,
+                    page.pinned,
+                    page.favorite,
+                    page.archived,
+                    page.sort_index,
+                    locked,
+                    icon,
+                    cover
+                ],
+            )
+            .map_err(|e| e.to_string())?;
+            tx.last_insert_rowid()
+        };
+        set_page_links_in_conn(&tx, page_id, &page.content)?;
+        set_page_properties_in_conn(&tx, page_id, &page.content)?;
+        if let Some(parent_id) = page.parent_id {
+            deferred_page_parents.push((page_id, parent_id));
+        }
+    }
+
+    // Parents are linked once every page exists; missing parents and links that
+    // would form a cycle leave the page at the top level.
+    for (page_id, parent_id) in deferred_page_parents {
+        if let Ok(Some(parent_id)) = validate_page_parent(&tx, Some(page_id), Some(parent_id)) {
+            tx.execute(
+                "UPDATE pages SET parent_id = ?1 WHERE id = ?2",
+                params![parent_id, page_id],
             )
             .map_err(|e| e.to_string())?;
         }
@@ -158,19 +320,26 @@ pub(crate) fn import_backup_into_conn(
         let project_id = normalize_project_id(&tx, entry.project_id)?;
 
         tx.execute(
-            "INSERT INTO entries (date, yesterday, today, project_id, created_at)
-             VALUES (?1, ?2, ?3, ?4, ?5)
+            "INSERT INTO entries (date, yesterday, today, blockers, notes, project_id, created_at, pinned)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)
              ON CONFLICT(date) DO UPDATE SET
                 yesterday = excluded.yesterday,
                 today = excluded.today,
+                blockers = excluded.blockers,
+                notes = excluded.notes,
                 project_id = excluded.project_id,
-                created_at = excluded.created_at",
+                created_at = excluded.created_at,
+                pinned = excluded.pinned,
+                deleted_at = NULL",
             params![
                 entry.date,
                 entry.yesterday,
                 entry.today,
+                entry.blockers,
+                entry.notes,
                 project_id,
-                entry.created_at.unwrap_or_else(|| now.clone())
+                entry.created_at.unwrap_or_else(|| now.clone()),
+                entry.pinned
             ],
         )
         .map_err(|e| e.to_string())?;
@@ -612,6 +781,13 @@ pub(crate) fn import_backup_into_conn(
         }
     }
 
+    // Settings this version does not know (e.g. from a newer app) are skipped.
+    for setting in payload.app_settings {
+        if default_setting(&setting.key).is_some() {
+            write_setting(&tx, &setting.key, &setting.value)?;
+        }
+    }
+
     tx.commit().map_err(|e| e.to_string())?;
     Ok(())
 }
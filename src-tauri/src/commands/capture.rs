@@ -0,0 +1,174 @@
+use crate::models::{Bookmark, CaptureResult};
+use chrono::Utc;
+use rusqlite::{params, Connection};
+use serde::Deserialize;
+use tauri::State;
+
+use super::api_access::{authorize_external_call_in_conn, record_external_access_in_conn};
+use super::review_queue::queue_task_for_review;
+use super::tasks::find_duplicate_tasks_in_conn;
+use super::validation::{normalize_optional_http_url, normalize_optional_text};
+use super::AppState;
+
+/// Tasks clipped from the companion browser extension.
+pub(crate) const REVIEW_SOURCE_BROWSER_CAPTURE: &str = "browser_capture";
+
+/// Body of `POST /capture`. `kind` is `bookmark` (default) or `task`.
+#[derive(Debug, Deserialize)]
+pub struct CapturePayload {
+    pub url: String,
+    pub title: Option<String>,
+    pub selection: Option<String>,
+    pub kind: Option<String>,
+}
+
+pub(crate) fn create_bookmark_in_conn(
+    conn: &Connection,
+    url: &str,
+    title: &str,
+    selection: &str,
+) -> Result<Bookmark, String> {
+    let now = Utc::now().to_rfc3339();
+    conn.execute(
+        "INSERT INTO bookmarks (url, title, selection, created_at) VALUES (?1, ?2, ?3, ?4)",
+        params![url, title, selection, now],
+    )
+    .map_err(|e| e.to_string())?;
+
+    Ok(Bookmark {
+        id: conn.last_insert_rowid(),
+        url: url.to_string(),
+        title: title.to_string(),
+        selection: selection.to_string(),
+        created_at: now,
+    })
+}
+
+/// Creates a todo from a clipped page; it waits in the review queue like other automation.
+fn create_captured_task_in_conn(
+    conn: &Connection,
+    url: &str,
+    title: &str,
+    selection: &str,
+) -> Result<i64, String> {
+    let now = Utc::now().to_rfc3339();
+    let mut description = format!("Source: {}", url);
+    if !selection.is_empty() {
+        let quoted: Vec<String> = selection
+            .lines()
+            .map(|line| format!("> {}", line))
+            .collect();
+        description.push_str(&format!("\n\n{}", quoted.join("\n")));
+    }
+
+    conn.execute(
+        "INSERT INTO tasks (title, description, status, priority, project_id, goal_id, due_date, completed_at, time_estimate_minutes, timer_started_at, timer_accumulated_seconds, created_at, updated_at)
+         VALUES (?1, ?2, 'todo', 'medium', NULL, NULL, NULL, NULL, 0, NULL, 0, ?3, ?3)",
+        params![title, description, now],
+    )
+    .map_err(|e| e.to_string())?;
+    let task_id = conn.last_insert_rowid();
+    queue_task_for_review(conn, task_id, REVIEW_SOURCE_BROWSER_CAPTURE)?;
+
+    Ok(task_id)
+}
+
+/// Handles one clip from the browser extension: authorizes the token for the matching
+/// write scope (which also logs and rate-limits the call) and stores the item.
+pub(crate) fn capture_in_conn(
+    conn: &Connection,
+    secret: &str,
+    payload: CapturePayload,
+) -> Result<CaptureResult, String> {
+    let kind = payload
+        .kind
+        .as_deref()
+        .map(str::trim)
+        .unwrap_or("bookmark")
+        .to_ascii_lowercase();
+    let scope = match kind.as_str() {
+        "bookmark" => "bookmarks:write",
+        "task" => "tasks:write",
+        other => return Err(format!("Unsupported capture kind: {}", other)),
+    };
+    let access = authorize_external_call_in_conn(conn, secret, "capture", Some(scope))?;
+
+    let stored = (|| {
+        let url = normalize_optional_http_url(Some(payload.url))
+            .ok_or_else(|| "A http(s) URL is required".to_string())?;
+        let title = normalize_optional_text(payload.title).unwrap_or_else(|| url.clone());
+        let selection = normalize_optional_text(payload.selection).unwrap_or_default();
+        let (id, duplicates) = match kind.as_str() {
+            "task" => {
+                let duplicates = find_duplicate_tasks_in_conn(conn, &title)?;
+                let id = create_captured_task_in_conn(conn, &url, &title, &selection)?;
+                (id, duplicates)
+            }
+            _ => (
+                create_bookmark_in_conn(conn, &url, &title, &selection)?.id,
+                Vec::new(),
+            ),
+        };
+
+        Ok::<_, String>(CaptureResult {
+            kind: kind.clone(),
+            id,
+            duplicates,
+        })
+    })();
+
+    if let Err(error) = &stored {
+        record_external_access_in_conn(
+            conn,
+            Some(access.token_id()),
+            access.token_name(),
+            "capture",
+            "error",
+            error,
+        )?;
+    }
+
+    stored
+}
+
+pub(crate) fn get_bookmarks_in_conn(conn: &Connection) -> Result<Vec<Bookmark>, String> {
+    let mut stmt = conn
+        .prepare(
+            "SELECT id, url, title, selection, created_at
+             FROM bookmarks ORDER BY created_at DESC, id DESC",
+        )
+        .map_err(|e| e.to_string())?;
+    let bookmarks_iter = stmt
+        .query_map([], |row| {
+            Ok(Bookmark {
+                id: row.get(0)?,
+                url: row.get(1)?,
+                title: row.get(2)?,
+                selection: row.get(3)?,
+                created_at: row.get(4)?,
+            })
+        })
+        .map_err(|e| e.to_string())?;
+
+    let mut bookmarks = Vec::new();
+    for bookmark in bookmarks_iter {
+        bookmarks.push(bookmark.map_err(|e| e.to_string())?);
+    }
+
+    Ok(bookmarks)
+}
+
+#[tauri::command]
+pub fn get_bookmarks(state: State<'_, AppState>) -> Result<Vec<Bookmark>, String> {
+    let conn = state.db.lock().map_err(|e| e.to_string())?;
+    get_bookmarks_in_conn(&conn)
+}
+
+#[tauri::command]
+pub fn delete_bookmark(id: i64, state: State<'_, AppState>) -> Result<(), String> {
+    let conn = state.db.lock().map_err(|e| e.to_string())?;
+    conn.execute("DELETE FROM bookmarks WHERE id = ?1", params![id])
+        .map_err(|e| e.to_string())?;
+
+    Ok(())
+}
@@ -0,0 +1,219 @@
+use crate::models::Countdown;
+use chrono::{DateTime, Duration, Utc};
+use rusqlite::{params, Connection, OptionalExtension};
+use std::thread;
+use tauri::{AppHandle, Manager, State};
+use tauri_plugin_notification::NotificationExt;
+
+use super::events::emit_event;
+use super::notifications::{notification_templates_in_conn, render_notification_template};
+use super::AppState;
+
+const COUNTDOWN_COLUMNS: &str =
+    "id, label, duration_minutes, started_at, ends_at, completed_at, cancelled_at";
+
+fn countdown_from_row(row: &rusqlite::Row<'_>) -> rusqlite::Result<Countdown> {
+    Ok(Countdown {
+        id: row.get(0)?,
+        label: row.get(1)?,
+        duration_minutes: row.get(2)?,
+        started_at: row.get(3)?,
+        ends_at: row.get(4)?,
+        completed_at: row.get(5)?,
+        cancelled_at: row.get(6)?,
+    })
+}
+
+fn get_countdown_in_conn(conn: &Connection, id: i64) -> Result<Option<Countdown>, String> {
+    conn.query_row(
+        &format!("SELECT {} FROM countdowns WHERE id = ?1", COUNTDOWN_COLUMNS),
+        params![id],
+        countdown_from_row,
+    )
+    .optional()
+    .map_err(|e| e.to_string())
+}
+
+pub(crate) fn start_countdown_in_conn(
+    conn: &Connection,
+    label: &str,
+    minutes: i64,
+    now: DateTime<Utc>,
+) -> Result<Countdown, String> {
+    let label = label.trim();
+    let label = if label.is_empty() { "Timebox" } else { label };
+    let minutes = minutes.clamp(1, 24 * 60);
+    let ends_at = now + Duration::minutes(minutes);
+
+    conn.execute(
+        "INSERT INTO countdowns (label, duration_minutes, started_at, ends_at) VALUES (?1, ?2, ?3, ?4)",
+        params![label, minutes, now.to_rfc3339(), ends_at.to_rfc3339()],
+    )
+    .map_err(|e| e.to_string())?;
+
+    get_countdown_in_conn(conn, conn.last_insert_rowid())?
+        .ok_or_else(|| "Failed to load created countdown".to_string())
+}
+
+/// Countdowns that are neither finished nor cancelled, soonest first.
+pub(crate) fn get_active_countdowns_in_conn(conn: &Connection) -> Result<Vec<Countdown>, String> {
+    let mut stmt = conn
+        .prepare(&format!(
+            "SELECT {} FROM countdowns
+             WHERE completed_at IS NULL AND cancelled_at IS NULL
+             ORDER BY ends_at ASC, id ASC",
+            COUNTDOWN_COLUMNS
+        ))
+        .map_err(|e| e.to_string())?;
+
+    let countdowns_iter = stmt
+        .query_map([], countdown_from_row)
+        .map_err(|e| e.to_string())?;
+
+    let mut countdowns = Vec::new();
+    for countdown in countdowns_iter {
+        countdowns.push(countdown.map_err(|e| e.to_string())?);
+    }
+
+    Ok(countdowns)
+}
+
+/// Marks every active countdown that has reached its end time as completed and returns them.
+pub(crate) fn complete_due_countdowns_in_conn(
+    conn: &Connection,
+    now: DateTime<Utc>,
+) -> Result<Vec<Countdown>, String> {
+    let completed_at = now.to_rfc3339();
+    let due: Vec<Countdown> = get_active_countdowns_in_conn(conn)?
+        .into_iter()
+        .filter(|countdown| {
+            DateTime::parse_from_rfc3339(&countdown.ends_at)
+                .map(|ends_at| ends_at.with_timezone(&Utc) <= now)
+                .unwrap_or(true)
+        })
+        .map(|countdown| Countdown {
+            completed_at: Some(completed_at.clone()),
+            ..countdown
+        })
+        .collect();
+
+    for countdown in &due {
+        conn.execute(
+            "UPDATE countdowns SET completed_at = ?1 WHERE id = ?2",
+            params![completed_at, countdown.id],
+        )
+        .map_err(|e| e.to_string())?;
+    }
+
+    Ok(due)
+}
+
+/// Completes due countdowns, notifying about each one and refreshing the tray.
+pub(crate) fn complete_due_countdowns_for_app(app: &AppHandle) -> Result<(), String> {
+    let (completed, (title_template, body_template)) = {
+        let state = app.state::<AppState>();
+        let conn = state.db.lock().map_err(|e| e.to_string())?;
+        (
+            complete_due_countdowns_in_conn(&conn, Utc::now())?,
+            notification_templates_in_conn(&conn, "timebox")?,
+        )
+    };
+    if completed.is_empty() {
+        return Ok(());
+    }
+
+    for countdown in &completed {
+        let minutes = countdown.duration_minutes.to_string();
+        let values = [
+            ("label", countdown.label.as_str()),
+            ("minutes", minutes.as_str()),
+        ];
+        if let Err(error) = app
+            .notification()
+            .builder()
+            .title(render_notification_template(&title_template, &values))
+            .body(render_notification_template(&body_template, &values))
+            .show()
+        {
+            eprintln!("Failed to show countdown notification: {error}");
+        }
+    }
+
+    crate::tray::refresh_tray_menu(app);
+    if let Err(error) = emit_event(app, "countdowns-changed", &completed) {
+        eprintln!("Failed to emit countdown change: {error}");
+    }
+
+    Ok(())
+}
+
+/// Sleeps until `ends_at` on a background thread, then completes due countdowns.
+/// The scheduler tick also completes them, which covers suspend/resume.
+fn watch_countdown(app: AppHandle, ends_at: &str) {
+    let wait = DateTime::parse_from_rfc3339(ends_at)
+        .map(|ends_at| ends_at.with_timezone(&Utc) - Utc::now())
+        .ok()
+        .and_then(|remaining| remaining.to_std().ok())
+        .unwrap_or_default();
+
+    thread::spawn(move || {
+        thread::sleep(wait);
+        if let Err(error) = complete_due_countdowns_for_app(&app) {
+            eprintln!("Countdown completion failed: {error}");
+        }
+    });
+}
+
+/// Resumes watchers for countdowns persisted by a previous run.
+pub(crate) fn watch_active_countdowns(app: &AppHandle) -> Result<(), String> {
+    let countdowns = {
+        let state = app.state::<AppState>();
+        let conn = state.db.lock().map_err(|e| e.to_string())?;
+        get_active_countdowns_in_conn(&conn)?
+    };
+
+    for countdown in countdowns {
+        watch_countdown(app.clone(), &countdown.ends_at);
+    }
+
+    Ok(())
+}
+
+#[tauri::command]
+pub fn start_countdown(
+    label: String,
+    minutes: i64,
+    app: AppHandle,
+    state: State<'_, AppState>,
+) -> Result<Countdown, String> {
+    let countdown = {
+        let conn = state.db.lock().map_err(|e| e.to_string())?;
+        start_countdown_in_conn(&conn, &label, minutes, Utc::now())?
+    };
+    watch_countdown(app.clone(), &countdown.ends_at);
+    crate::tray::refresh_tray_menu(&app);
+
+    Ok(countdown)
+}
+
+#[tauri::command]
+pub fn get_active_countdowns(state: State<'_, AppState>) -> Result<Vec<Countdown>, String> {
+    let conn = state.db.lock().map_err(|e| e.to_string())?;
+    get_active_countdowns_in_conn(&conn)
+}
+
+#[tauri::command]
+pub fn cancel_countdown(id: i64, app: AppHandle, state: State<'_, AppState>) -> Result<(), String> {
+    {
+        let conn = state.db.lock().map_err(|e| e.to_string())?;
+        conn.execute(
+            "UPDATE countdowns SET cancelled_at = ?1
+             WHERE id = ?2 AND completed_at IS NULL AND cancelled_at IS NULL",
+            params![Utc::now().to_rfc3339(), id],
+        )
+        .map_err(|e| e.to_string())?;
+    }
+    crate::tray::refresh_tray_menu(&app);
+
+    Ok(())
+}
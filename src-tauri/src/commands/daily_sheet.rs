@@ -0,0 +1,317 @@
+use chrono::{Duration, NaiveDate};
+use rusqlite::{params, Connection};
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+use tauri::State;
+
+use crate::models::Task;
+
+use super::encryption::{JournalCipher, JournalContent};
+use super::entries::get_entry_in_conn;
+use super::meetings::titled_meeting_occurrences_in_conn;
+use super::tasks::get_open_tasks_in_conn;
+use super::weeks::read_week_numbering_in_conn;
+use super::AppState;
+
+/// Tasks printed with a checkbox; the sheet is meant to fit on one page.
+const DAILY_SHEET_TASK_LIMIT: usize = 8;
+/// Ruled lines left for handwriting under the plan, tasks and notes.
+const BLANK_LINES: usize = 4;
+
+const DAILY_SHEET_STYLE: &str = "
+  @page { size: A4; margin: 12mm; }
+  * { box-sizing: border-box; }
+  body { font: 11pt/1.45 -apple-system, 'Segoe UI', Inter, sans-serif; color: #111; margin: 0 auto; max-width: 190mm; }
+  header { display: flex; justify-content: space-between; align-items: baseline; border-bottom: 2px solid #111; margin-bottom: 4mm; }
+  h1 { font-size: 18pt; margin: 0 0 1mm; }
+  h2 { font-size: 9pt; letter-spacing: 0.08em; text-transform: uppercase; color: #555; margin: 5mm 0 1.5mm; }
+  ul { list-style: none; margin: 0; padding: 0; }
+  li { display: flex; gap: 2.5mm; align-items: baseline; padding: 1mm 0; border-bottom: 1px solid #ddd; }
+  .box { flex: none; width: 3.6mm; height: 3.6mm; border: 1.2px solid #111; border-radius: 0.6mm; transform: translateY(0.5mm); }
+  .meta { margin-left: auto; color: #666; font-size: 9pt; white-space: nowrap; }
+  .line { height: 7mm; border-bottom: 1px solid #ccc; }
+  time { flex: none; width: 26mm; font-variant-numeric: tabular-nums; color: #333; }
+  .empty { color: #888; font-style: italic; }
+  table { border-collapse: collapse; width: 100%; }
+  th, td { border: 1px solid #bbb; padding: 1mm 1.5mm; text-align: center; font-size: 9.5pt; }
+  th:first-child, td:first-child { text-align: left; width: 45%; }
+  .today { background: #eee; }
+  .columns { display: grid; grid-template-columns: 1fr 1fr; gap: 6mm; }
+  @media print { body { max-width: none; } }
+";
+
+pub(crate) fn escape_html(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for ch in value.chars() {
+        match ch {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(ch),
+        }
+    }
+    escaped
+}
+
+fn priority_rank(priority: &str) -> u8 {
+    match priority {
+        "urgent" => 0,
+        "high" => 1,
+        "medium" => 2,
+        "low" => 3,
+        _ => 4,
+    }
+}
+
+/// Open tasks for the sheet: due by `date` first, then by priority and due date.
+fn top_tasks_in_conn(conn: &Connection, date: NaiveDate) -> Result<Vec<Task>, String> {
+    let date = date.format("%Y-%m-%d").to_string();
+    let mut tasks = get_open_tasks_in_conn(conn, 500)?;
+    tasks.sort_by_key(|task| {
+        let due = task.due_date.clone().filter(|due| !due.trim().is_empty());
+        let due_by_date = due.as_deref().is_some_and(|due| due <= date.as_str());
+        (
+            !due_by_date,
+            priority_rank(&task.priority),
+            due.is_none(),
+            due,
+        )
+    });
+    tasks.truncate(DAILY_SHEET_TASK_LIMIT);
+    Ok(tasks)
+}
+
+/// `(title, done per day)` for every habit over the Monday-to-Sunday week starting at
+/// `week_start`.
+fn habit_week_in_conn(
+    conn: &Connection,
+    week_start: NaiveDate,
+) -> Result<Vec<(String, [bool; 7])>, String> {
+    let week_end = week_start + Duration::days(6);
+    let mut stmt = conn
+        .prepare(
+            "SELECT l.habit_id, l.date FROM habit_logs l
+             WHERE l.date BETWEEN ?1 AND ?2",
+        )
+        .map_err(|e| e.to_string())?;
+    let logs_iter = stmt
+        .query_map(
+            params![
+                week_start.format("%Y-%m-%d").to_string(),
+                week_end.format("%Y-%m-%d").to_string()
+            ],
+            |row| Ok((row.get::<_, i64>(0)?, row.get::<_, String>(1)?)),
+        )
+        .map_err(|e| e.to_string())?;
+    let mut logs = HashSet::new();
+    for log in logs_iter {
+        logs.insert(log.map_err(|e| e.to_string())?);
+    }
+
+    let mut stmt = conn
+        .prepare("SELECT id, title FROM habits ORDER BY title COLLATE NOCASE, id")
+        .map_err(|e| e.to_string())?;
+    let habits_iter = stmt
+        .query_map([], |row| {
+            Ok((row.get::<_, i64>(0)?, row.get::<_, String>(1)?))
+        })
+        .map_err(|e| e.to_string())?;
+
+    let mut habits = Vec::new();
+    for habit in habits_iter {
+        let (id, title) = habit.map_err(|e| e.to_string())?;
+        let mut done = [false; 7];
+        for (offset, slot) in done.iter_mut().enumerate() {
+            let day = (week_start + Duration::days(offset as i64))
+                .format("%Y-%m-%d")
+                .to_string();
+            *slot = logs.contains(&(id, day));
+        }
+        habits.push((title, done));
+    }
+
+    Ok(habits)
+}
+
+fn blank_lines(html: &mut String, count: usize) {
+    for _ in 0..count {
+        html.push_str("<div class=\"line\"></div>");
+    }
+}
+
+/// A self-contained, print-ready HTML page for `date`: the plan from the day's
+/// entry, the top open tasks with checkboxes, meetings, and the week's habit grid.
+pub(crate) fn daily_sheet_html_in_conn(
+    conn: &Connection,
+    date: NaiveDate,
+    cipher: &JournalCipher,
+) -> Result<String, String> {
+    let iso_date = date.format("%Y-%m-%d").to_string();
+    let entry = get_entry_in_conn(conn, &iso_date)?.decrypt_with(cipher)?;
+    let plan: Vec<String> = entry
+        .map(|entry| {
+            entry
+                .today
+                .lines()
+                .map(|line| {
+                    line.trim()
+                        .trim_start_matches(['-', '*'])
+                        .trim()
+                        .to_string()
+                })
+                .filter(|line| !line.is_empty())
+                .collect()
+        })
+        .unwrap_or_default();
+    let week_numbering = read_week_numbering_in_conn(conn)?;
+
+    let mut html = String::new();
+    html.push_str("<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n");
+    html.push_str(&format!(
+        "<title>Daily sheet {}</title>\n<style>{}</style>\n</head>\n<body>\n",
+        iso_date, DAILY_SHEET_STYLE
+    ));
+    html.push_str(&format!(
+        "<header><h1>{}</h1><span>Week {}</span></header>\n",
+        date.format("%A, %B %-d, %Y"),
+        week_numbering.week_number(date).1
+    ));
+
+    html.push_str("<h2>Plan</h2>\n<ul>");
+    for line in &plan {
+        html.push_str(&format!("<li>{}</li>", escape_html(line)));
+    }
+    html.push_str("</ul>");
+    if plan.is_empty() {
+        blank_lines(&mut html, BLANK_LINES);
+    }
+
+    html.push_str("\n<div class=\"columns\">\n<section>\n<h2>Top tasks</h2>\n<ul>");
+    for task in top_tasks_in_conn(conn, date)? {
+        let mut meta = Vec::new();
+        if let Some(due) = task.due_date.as_deref().filter(|due| !due.is_empty()) {
+            let due = due.get(..10).unwrap_or(due);
+            meta.push(if due < iso_date.as_str() {
+                format!("overdue {}", due)
+            } else {
+                format!("due {}", due)
+            });
+        }
+        if matches!(task.priority.as_str(), "urgent" | "high") {
+            meta.push(task.priority.clone());
+        }
+        html.push_str(&format!(
+            "<li><span class=\"box\"></span>{}<span class=\"meta\">{}</span></li>",
+            escape_html(&task.title),
+            escape_html(&meta.join(" · "))
+        ));
+    }
+    html.push_str("</ul>");
+    blank_lines(&mut html, BLANK_LINES);
+
+    html.push_str("\n</section>\n<section>\n<h2>Meetings</h2>\n<ul>");
+    let meetings = titled_meeting_occurrences_in_conn(conn, date, date)?;
+    for ((start, end), title) in &meetings {
+        html.push_str(&format!(
+            "<li><time>{}–{}</time>{}</li>",
+            start.format("%H:%M"),
+            end.format("%H:%M"),
+            escape_html(title)
+        ));
+    }
+    if meetings.is_empty() {
+        html.push_str("<li class=\"empty\">No meetings</li>");
+    }
+    html.push_str("</ul>\n");
+
+    let week_start = week_numbering.week_start(date);
+    let habits = habit_week_in_conn(conn, week_start)?;
+    if !habits.is_empty() {
+        html.push_str("<h2>Habits</h2>\n<table>\n<thead><tr><th></th>");
+        for offset in 0..7 {
+            let day = week_start + Duration::days(offset);
+            let class = if day == date { " class=\"today\"" } else { "" };
+            html.push_str(&format!("<th{}>{}</th>", class, day.format("%a")));
+        }
+        html.push_str("</tr></thead>\n<tbody>");
+        for (title, done) in &habits {
+            html.push_str(&format!("<tr><td>{}</td>", escape_html(title)));
+            for (offset, done) in done.iter().enumerate() {
+                let class = if week_start + Duration::days(offset as i64) == date {
+                    " class=\"today\""
+                } else {
+                    ""
+                };
+                html.push_str(&format!(
+                    "<td{}>{}</td>",
+                    class,
+                    if *done { "✓" } else { "" }
+                ));
+            }
+            html.push_str("</tr>");
+        }
+        html.push_str("</tbody>\n</table>\n");
+    }
+    html.push_str("</section>\n</div>\n");
+
+    html.push_str("<h2>Notes</h2>\n");
+    blank_lines(&mut html, BLANK_LINES * 2);
+    html.push_str("\n</body>\n</html>\n");
+
+    Ok(html)
+}
+
+/// Where the sheet is written: `path` itself, or `daily-sheet-<date>.html` inside it
+/// when it is an existing folder. Only HTML is produced; print it to PDF from a browser.
+pub(crate) fn daily_sheet_path(path: &str, date: NaiveDate) -> Result<PathBuf, String> {
+    let path = path.trim();
+    if path.is_empty() {
+        return Err("Output path is required".to_string());
+    }
+    let path = PathBuf::from(path);
+    if path.is_dir() {
+        return Ok(path.join(format!("daily-sheet-{}.html", date.format("%Y-%m-%d"))));
+    }
+    match path.extension().and_then(|extension| extension.to_str()) {
+        Some(extension) if extension.eq_ignore_ascii_case("html") => Ok(path),
+        Some(extension) if extension.eq_ignore_ascii_case("htm") => Ok(path),
+        Some(extension) if extension.eq_ignore_ascii_case("pdf") => {
+            Err("PDF output is not supported; save an .html sheet and print it to PDF".to_string())
+        }
+        _ => Ok(path.with_extension("html")),
+    }
+}
+
+fn write_daily_sheet(path: &Path, html: &str) -> Result<(), String> {
+    if let Some(parent) = path
+        .parent()
+        .filter(|parent| !parent.as_os_str().is_empty())
+    {
+        fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create {}: {}", parent.display(), e))?;
+    }
+    fs::write(path, html).map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+}
+
+/// Writes the printable sheet for `date` and returns the file path.
+#[tauri::command]
+pub fn generate_daily_sheet(
+    date: String,
+    path: String,
+    state: State<'_, AppState>,
+) -> Result<String, String> {
+    let date = NaiveDate::parse_from_str(date.trim(), "%Y-%m-%d")
+        .map_err(|_| format!("Invalid date: {}", date))?;
+    let path = daily_sheet_path(&path, date)?;
+
+    let cipher = state.journal_cipher()?;
+    let conn = state.db.lock().map_err(|e| e.to_string())?;
+    let html = daily_sheet_html_in_conn(&conn, date, &cipher)?;
+    drop(conn);
+
+    write_daily_sheet(&path, &html)?;
+    Ok(path.display().to_string())
+}
@@ -0,0 +1,242 @@
+use crate::models::{DateShift, DateShiftRow};
+use chrono::{Duration, NaiveDate};
+use rusqlite::{params, Connection};
+use serde::Deserialize;
+use std::collections::HashSet;
+use tauri::State;
+
+use super::mirror::resync_vault_mirror;
+use super::AppState;
+
+/// Ten years either way; anything larger is a typo, not a timezone fix.
+const MAX_SHIFT_DAYS: i64 = 3660;
+
+/// Dated rows a shift can move, e.g. habit logs an import stored one day early.
+#[derive(Debug, Clone, Copy, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum DateShiftEntity {
+    Entries,
+    HabitLogs,
+    StreakFreezes,
+    Moods,
+    TaskDueDates,
+    GoalTargetDates,
+}
+
+impl DateShiftEntity {
+    fn as_str(self) -> &'static str {
+        match self {
+            DateShiftEntity::Entries => "entries",
+            DateShiftEntity::HabitLogs => "habit_logs",
+            DateShiftEntity::StreakFreezes => "streak_freezes",
+            DateShiftEntity::Moods => "moods",
+            DateShiftEntity::TaskDueDates => "task_due_dates",
+            DateShiftEntity::GoalTargetDates => "goal_target_dates",
+        }
+    }
+
+    /// Rows as `(key, scope, label, date)`. Dates must stay unique within a scope
+    /// (the habit for logs and freezes, the whole table for entries and moods).
+    fn select_sql(self) -> &'static str {
+        match self {
+            DateShiftEntity::Entries => "SELECT id, 0, 'Journal entry', date FROM entries",
+            DateShiftEntity::HabitLogs => {
+                "SELECT l.id, l.habit_id, h.title, l.date
+                 FROM habit_logs l JOIN habits h ON h.id = l.habit_id"
+            }
+            DateShiftEntity::StreakFreezes => {
+                "SELECT f.id, f.habit_id, h.title, f.date
+                 FROM streak_freezes f JOIN habits h ON h.id = f.habit_id"
+            }
+            DateShiftEntity::Moods => "SELECT rowid, 0, 'Mood ' || mood, date FROM daily_moods",
+            DateShiftEntity::TaskDueDates => {
+                "SELECT id, 0, title, due_date FROM tasks WHERE due_date IS NOT NULL"
+            }
+            DateShiftEntity::GoalTargetDates => {
+                "SELECT id, 0, title, target_date FROM goals WHERE target_date IS NOT NULL"
+            }
+        }
+    }
+
+    fn update_sql(self) -> &'static str {
+        match self {
+            DateShiftEntity::Entries => "UPDATE entries SET date = ?1 WHERE id = ?2",
+            DateShiftEntity::HabitLogs => "UPDATE habit_logs SET date = ?1 WHERE id = ?2",
+            DateShiftEntity::StreakFreezes => "UPDATE streak_freezes SET date = ?1 WHERE id = ?2",
+            DateShiftEntity::Moods => "UPDATE daily_moods SET date = ?1 WHERE rowid = ?2",
+            DateShiftEntity::TaskDueDates => "UPDATE tasks SET due_date = ?1 WHERE id = ?2",
+            DateShiftEntity::GoalTargetDates => "UPDATE goals SET target_date = ?1 WHERE id = ?2",
+        }
+    }
+
+    fn has_unique_dates(self) -> bool {
+        !matches!(
+            self,
+            DateShiftEntity::TaskDueDates | DateShiftEntity::GoalTargetDates
+        )
+    }
+
+    fn is_per_habit(self) -> bool {
+        matches!(
+            self,
+            DateShiftEntity::HabitLogs | DateShiftEntity::StreakFreezes
+        )
+    }
+}
+
+/// Which rows to shift: one kind, optionally limited to dates in `from_date..=to_date`
+/// and, for habit logs and freezes, to one habit.
+#[derive(Debug, Deserialize)]
+pub struct DateShiftFilter {
+    pub entity: DateShiftEntity,
+    pub from_date: Option<String>,
+    pub to_date: Option<String>,
+    pub habit_id: Option<i64>,
+}
+
+fn parse_bound(date: Option<&str>) -> Result<Option<NaiveDate>, String> {
+    date.map(str::trim)
+        .filter(|date| !date.is_empty())
+        .map(|date| {
+            NaiveDate::parse_from_str(date, "%Y-%m-%d")
+                .map_err(|_| format!("Invalid date: {}", date))
+        })
+        .transpose()
+}
+
+/// Works out which rows `filter` matches and where they land. Rows whose date does
+/// not parse are left out. With `apply`, the rows are moved in one transaction, and
+/// nothing is moved when any of them would land on a date its scope already uses.
+pub(crate) fn shift_dates_in_conn(
+    conn: &mut Connection,
+    filter: &DateShiftFilter,
+    delta_days: i64,
+    apply: bool,
+) -> Result<DateShift, String> {
+    if delta_days == 0 || delta_days.abs() > MAX_SHIFT_DAYS {
+        return Err(format!(
+            "Shift by 1 to {} days, earlier or later",
+            MAX_SHIFT_DAYS
+        ));
+    }
+    if filter.habit_id.is_some() && !filter.entity.is_per_habit() {
+        return Err("Only habit logs and streak freezes can be limited to a habit".to_string());
+    }
+    let from_date = parse_bound(filter.from_date.as_deref())?;
+    let to_date = parse_bound(filter.to_date.as_deref())?;
+
+    let tx = conn.transaction().map_err(|e| e.to_string())?;
+    let mut all_rows = Vec::new();
+    {
+        let mut stmt = tx
+            .prepare(filter.entity.select_sql())
+            .map_err(|e| e.to_string())?;
+        let rows_iter = stmt
+            .query_map([], |row| {
+                Ok((
+                    row.get::<_, i64>(0)?,
+                    row.get::<_, i64>(1)?,
+                    row.get::<_, String>(2)?,
+                    row.get::<_, String>(3)?,
+                ))
+            })
+            .map_err(|e| e.to_string())?;
+        for row in rows_iter {
+            all_rows.push(row.map_err(|e| e.to_string())?);
+        }
+    }
+
+    let mut moved = Vec::new();
+    let mut staying = HashSet::new();
+    for (key, scope, label, date) in all_rows {
+        let parsed = NaiveDate::parse_from_str(date.trim(), "%Y-%m-%d").ok();
+        let matches = parsed.is_some_and(|parsed| {
+            from_date.is_none_or(|from| parsed >= from)
+                && to_date.is_none_or(|to| parsed <= to)
+                && filter.habit_id.is_none_or(|habit_id| habit_id == scope)
+        });
+        match parsed {
+            Some(parsed) if matches => moved.push((key, scope, label, date, parsed)),
+            _ => {
+                staying.insert((scope, date.trim().to_string()));
+            }
+        }
+    }
+    // Later rows move first when shifting forward (earlier ones when shifting back),
+    // so a run of consecutive days never collides with itself mid-update.
+    moved.sort_by(|left, right| left.4.cmp(&right.4).then(left.0.cmp(&right.0)));
+    if delta_days > 0 {
+        moved.reverse();
+    }
+
+    let mut rows = Vec::new();
+    let mut conflicts = Vec::new();
+    for (key, scope, label, from, parsed) in moved {
+        let to = (parsed + Duration::days(delta_days))
+            .format("%Y-%m-%d")
+            .to_string();
+        let collides = filter.entity.has_unique_dates() && staying.contains(&(scope, to.clone()));
+        let row = DateShiftRow {
+            id: key,
+            label,
+            from_date: from,
+            to_date: to,
+        };
+        if collides {
+            conflicts.push(row);
+        } else {
+            rows.push(row);
+        }
+    }
+
+    let applied = apply && !rows.is_empty();
+    if apply {
+        if !conflicts.is_empty() {
+            return Err(format!(
+                "{} row(s) would land on a date that is already taken; narrow the filter first",
+                conflicts.len()
+            ));
+        }
+        for row in &rows {
+            tx.execute(filter.entity.update_sql(), params![row.to_date, row.id])
+                .map_err(|e| e.to_string())?;
+        }
+        tx.commit().map_err(|e| e.to_string())?;
+    }
+
+    Ok(DateShift {
+        entity: filter.entity.as_str().to_string(),
+        delta_days,
+        rows,
+        conflicts,
+        applied,
+    })
+}
+
+/// Dry run of `shift_dates`: the rows it would move and the ones blocking it.
+#[tauri::command]
+pub fn preview_date_shift(
+    filter: DateShiftFilter,
+    delta_days: i64,
+    state: State<'_, AppState>,
+) -> Result<DateShift, String> {
+    let mut conn = state.db.lock().map_err(|e| e.to_string())?;
+    shift_dates_in_conn(&mut conn, &filter, delta_days, false)
+}
+
+/// Moves every matching date by `delta_days`, to repair imports that landed a day
+/// off (timezones, exclusive end dates).
+#[tauri::command]
+pub fn shift_dates(
+    filter: DateShiftFilter,
+    delta_days: i64,
+    state: State<'_, AppState>,
+) -> Result<DateShift, String> {
+    let mut conn = state.db.lock().map_err(|e| e.to_string())?;
+    let shift = shift_dates_in_conn(&mut conn, &filter, delta_days, true)?;
+    if shift.applied && filter.entity == DateShiftEntity::Entries {
+        resync_vault_mirror(&conn);
+    }
+
+    Ok(shift)
+}
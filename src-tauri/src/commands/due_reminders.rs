@@ -0,0 +1,193 @@
+use crate::models::TaskDueReminder;
+use chrono::{DateTime, Duration, Local, NaiveDate, TimeZone, Utc};
+use rusqlite::{params, Connection};
+use tauri::State;
+
+use super::settings::read_setting;
+use super::validation::{parse_datetime_utc, task_exists};
+use super::AppState;
+
+pub(crate) const DUE_REMINDER_LEADS_SETTING: &str = "due_reminder_leads";
+const SNOOZE_MIN_MINUTES: i64 = 5;
+const SNOOZE_MAX_MINUTES: i64 = 7 * 24 * 60;
+
+/// Lead times such as "1d, 1h, 30m" in minutes, largest first. A bare number is
+/// minutes; parts that do not parse are dropped, so a blank setting turns reminders off.
+pub(crate) fn parse_due_reminder_leads(value: &str) -> Vec<i64> {
+    let mut leads: Vec<i64> = value
+        .split(',')
+        .filter_map(|part| {
+            let part = part.trim().to_lowercase();
+            let split = part
+                .find(|c: char| !c.is_ascii_digit())
+                .unwrap_or(part.len());
+            let (number, unit) = part.split_at(split);
+            let number = number.parse::<i64>().ok()?;
+            let minutes = match unit.trim() {
+                "" | "m" | "min" => number,
+                "h" => number * 60,
+                "d" => number * 24 * 60,
+                _ => return None,
+            };
+            (minutes > 0).then_some(minutes)
+        })
+        .collect();
+    leads.sort_unstable_by(|a, b| b.cmp(a));
+    leads.dedup();
+    leads
+}
+
+/// Due dates are local days, so a task is due when its due date ends.
+fn due_at(due_date: &str) -> Option<DateTime<Utc>> {
+    let midnight = NaiveDate::parse_from_str(due_date, "%Y-%m-%d")
+        .ok()?
+        .succ_opt()?
+        .and_hms_opt(0, 0, 0)?;
+    Local
+        .from_local_datetime(&midnight)
+        .earliest()
+        .map(|due_at| due_at.with_timezone(&Utc))
+}
+
+/// Reminders to send at `now` for open tasks, recorded in `task_due_reminders` so
+/// each lead time fires once per due date, across restarts too. When several lead
+/// times passed since the last check only one reminder goes out. A snoozed task
+/// gets nothing until its snooze ends, then one reminder, and the snooze is cleared.
+pub(crate) fn take_due_task_reminders_in_conn(
+    conn: &Connection,
+    now: DateTime<Utc>,
+) -> Result<Vec<TaskDueReminder>, String> {
+    let leads = parse_due_reminder_leads(&read_setting(conn, DUE_REMINDER_LEADS_SETTING)?);
+    let Some(&horizon) = leads.first() else {
+        return Ok(Vec::new());
+    };
+
+    let tasks: Vec<(i64, String, String, Option<String>)> = {
+        let mut stmt = conn
+            .prepare(
+                "SELECT t.id, t.title, t.due_date, s.snoozed_until
+                 FROM tasks t
+                 LEFT JOIN task_due_snoozes s ON s.task_id = t.id
+                 WHERE t.status != 'done' AND t.due_date >= ?1
+                 ORDER BY t.due_date ASC, t.id ASC",
+            )
+            .map_err(|e| e.to_string())?;
+        let rows = stmt
+            .query_map(
+                params![now
+                    .with_timezone(&Local)
+                    .date_naive()
+                    .format("%Y-%m-%d")
+                    .to_string()],
+                |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?)),
+            )
+            .map_err(|e| e.to_string())?;
+        rows.collect::<Result<Vec<_>, _>>()
+            .map_err(|e| e.to_string())?
+    };
+
+    let sent_at = now.to_rfc3339();
+    let mut reminders = Vec::new();
+    for (task_id, title, due_date, snoozed_until) in tasks {
+        let Some(due_at) = due_at(&due_date) else {
+            continue;
+        };
+        let minutes_left = (due_at - now).num_minutes();
+
+        let snooze_ended = match snoozed_until {
+            Some(snoozed_until) => {
+                if parse_datetime_utc(&snoozed_until)? > now {
+                    continue;
+                }
+                conn.execute(
+                    "DELETE FROM task_due_snoozes WHERE task_id = ?1",
+                    params![task_id],
+                )
+                .map_err(|e| e.to_string())?;
+                true
+            }
+            None => false,
+        };
+        if minutes_left <= 0 || minutes_left > horizon {
+            continue;
+        }
+
+        let mut newly_passed = false;
+        for lead in leads.iter().filter(|lead| minutes_left <= **lead) {
+            newly_passed |= conn
+                .execute(
+                    "INSERT OR IGNORE INTO task_due_reminders (task_id, due_date, lead_minutes, sent_at)
+                     VALUES (?1, ?2, ?3, ?4)",
+                    params![task_id, due_date, lead, sent_at],
+                )
+                .map_err(|e| e.to_string())?
+                > 0;
+        }
+        if newly_passed || snooze_ended {
+            reminders.push(TaskDueReminder {
+                task_id,
+                title,
+                due_date,
+                minutes_left,
+                snoozed: snooze_ended,
+            });
+        }
+    }
+
+    Ok(reminders)
+}
+
+/// Holds the task's due reminders for `minutes` (5 minutes to a week) from `now`;
+/// `None` clears the snooze. Returns when the snooze ends.
+pub(crate) fn snooze_task_due_reminder_in_conn(
+    conn: &Connection,
+    task_id: i64,
+    minutes: Option<i64>,
+    now: DateTime<Utc>,
+) -> Result<Option<String>, String> {
+    if !task_exists(conn, task_id)? {
+        return Err("Task not found".to_string());
+    }
+
+    let Some(minutes) = minutes else {
+        conn.execute(
+            "DELETE FROM task_due_snoozes WHERE task_id = ?1",
+            params![task_id],
+        )
+        .map_err(|e| e.to_string())?;
+        return Ok(None);
+    };
+
+    let snoozed_until = (now
+        + Duration::minutes(minutes.clamp(SNOOZE_MIN_MINUTES, SNOOZE_MAX_MINUTES)))
+    .to_rfc3339();
+    conn.execute(
+        "INSERT INTO task_due_snoozes (task_id, snoozed_until) VALUES (?1, ?2)
+         ON CONFLICT(task_id) DO UPDATE SET snoozed_until = excluded.snoozed_until",
+        params![task_id, snoozed_until],
+    )
+    .map_err(|e| e.to_string())?;
+    Ok(Some(snoozed_until))
+}
+
+/// "2d", "5h" or "40m", for the `{time_left}` placeholder.
+pub(crate) fn format_time_left(minutes: i64) -> String {
+    let minutes = minutes.max(0);
+    if minutes >= 24 * 60 {
+        format!("{}d", (minutes + 12 * 60) / (24 * 60))
+    } else if minutes >= 60 {
+        format!("{}h", (minutes + 30) / 60)
+    } else {
+        format!("{}m", minutes)
+    }
+}
+
+#[tauri::command]
+pub fn snooze_task_due_reminder(
+    task_id: i64,
+    minutes: Option<i64>,
+    state: State<'_, AppState>,
+) -> Result<Option<String>, String> {
+    let conn = state.db.lock().map_err(|e| e.to_string())?;
+    snooze_task_due_reminder_in_conn(&conn, task_id, minutes, Utc::now())
+}
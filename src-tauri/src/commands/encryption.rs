@@ -0,0 +1,722 @@
+use aes_gcm::aead::{Aead, KeyInit};
+use aes_gcm::{Aes256Gcm, Key, Nonce};
+use argon2::Argon2;
+use base64::{engine::general_purpose::STANDARD, Engine};
+use chrono::Utc;
+use rusqlite::{params, Connection, OptionalExtension};
+use tauri::State;
+
+use crate::models::{
+    Entry, EntryDraft, EntryNote, EntrySearchResult, EntrySection, JournalEncryptionStatus, Page,
+    PageRevision, PageSearchResult, TrashedEntry, TrashedPage,
+};
+
+use super::attachments::search_attachment_text_in_conn;
+use super::entries::{fts_query_from_text, get_entries_in_conn};
+use super::page_properties::reindex_page_properties_in_conn;
+use super::pages::get_pages_in_conn;
+use super::AppState;
+
+/// Prefix of encrypted column values: `enc:v1:<base64(nonce || ciphertext)>`.
+const ENCRYPTED_PREFIX: &str = "enc:v1:";
+/// Known plaintext stored encrypted so a wrong passphrase is detected on unlock.
+const VERIFIER_PLAINTEXT: &str = "dev-journal";
+pub(crate) const SALT_LEN: usize = 16;
+const NONCE_LEN: usize = 12;
+pub(crate) const MIN_PASSPHRASE_CHARS: usize = 8;
+const SEARCH_SNIPPET_CHARS: usize = 160;
+
+pub(crate) const JOURNAL_LOCKED: &str =
+    "The journal is locked. Unlock it with your passphrase first.";
+
+/// Whether entry and page content is encrypted and, once unlocked, the derived key.
+/// Held in `AppState`; commands take a snapshot with `AppState::journal_cipher`.
+#[derive(Clone, Default)]
+pub struct JournalCipher {
+    enabled: bool,
+    key: Option<[u8; 32]>,
+}
+
+impl JournalCipher {
+    pub(crate) fn from_conn(conn: &Connection) -> Result<Self, String> {
+        Ok(Self {
+            enabled: journal_encryption_enabled_in_conn(conn)?,
+            key: None,
+        })
+    }
+
+    pub(crate) fn unlocked(key: [u8; 32]) -> Self {
+        Self {
+            enabled: true,
+            key: Some(key),
+        }
+    }
+
+    pub(crate) fn status(&self) -> JournalEncryptionStatus {
+        JournalEncryptionStatus {
+            enabled: self.enabled,
+            unlocked: !self.is_locked(),
+        }
+    }
+
+    /// Plaintext passes through while encryption is off; a locked journal refuses writes.
+    pub(crate) fn encrypt(&self, plaintext: &str) -> Result<String, String> {
+        if !self.enabled {
+            return Ok(plaintext.to_string());
+        }
+        let key = self
+            .key
+            .as_ref()
+            .ok_or_else(|| JOURNAL_LOCKED.to_string())?;
+
+        let mut nonce = [0u8; NONCE_LEN];
+        getrandom::fill(&mut nonce).map_err(|e| e.to_string())?;
+        let ciphertext = Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(key))
+            .encrypt(Nonce::from_slice(&nonce), plaintext.as_bytes())
+            .map_err(|_| "Failed to encrypt journal content".to_string())?;
+
+        let mut payload = nonce.to_vec();
+        payload.extend(ciphertext);
+        Ok(format!("{}{}", ENCRYPTED_PREFIX, STANDARD.encode(payload)))
+    }
+
+    /// Values written before encryption was turned on are returned unchanged.
+    pub(crate) fn decrypt(&self, stored: &str) -> Result<String, String> {
+        let Some(encoded) = stored.strip_prefix(ENCRYPTED_PREFIX) else {
+            return Ok(stored.to_string());
+        };
+        let key = self
+            .key
+            .as_ref()
+            .ok_or_else(|| JOURNAL_LOCKED.to_string())?;
+
+        let payload = STANDARD
+            .decode(encoded)
+            .map_err(|_| "Encrypted journal content is corrupted".to_string())?;
+        if payload.len() < NONCE_LEN {
+            return Err("Encrypted journal content is corrupted".to_string());
+        }
+        let (nonce, ciphertext) = payload.split_at(NONCE_LEN);
+        let plaintext = Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(key))
+            .decrypt(Nonce::from_slice(nonce), ciphertext)
+            .map_err(|_| "Failed to decrypt journal content".to_string())?;
+
+        String::from_utf8(plaintext).map_err(|e| e.to_string())
+    }
+
+    pub(crate) fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    pub(crate) fn is_locked(&self) -> bool {
+        self.enabled && self.key.is_none()
+    }
+}
+
+/// Values returned to the UI that carry encrypted columns.
+pub(crate) trait JournalContent: Sized {
+    fn decrypt_with(self, cipher: &JournalCipher) -> Result<Self, String>;
+}
+
+impl JournalContent for Entry {
+    fn decrypt_with(mut self, cipher: &JournalCipher) -> Result<Self, String> {
+        self.yesterday = cipher.decrypt(&self.yesterday)?;
+        self.today = cipher.decrypt(&self.today)?;
+        self.blockers = cipher.decrypt(&self.blockers)?;
+        self.notes = cipher.decrypt(&self.notes)?;
+        Ok(self)
+    }
+}
+
+impl JournalContent for EntryDraft {
+    fn decrypt_with(mut self, cipher: &JournalCipher) -> Result<Self, String> {
+        self.yesterday = cipher.decrypt(&self.yesterday)?;
+        self.today = cipher.decrypt(&self.today)?;
+        self.blockers = cipher.decrypt(&self.blockers)?;
+        self.notes = cipher.decrypt(&self.notes)?;
+        Ok(self)
+    }
+}
+
+impl JournalContent for EntryNote {
+    fn decrypt_with(mut self, cipher: &JournalCipher) -> Result<Self, String> {
+        self.text = cipher.decrypt(&self.text)?;
+        Ok(self)
+    }
+}
+
+impl JournalContent for EntrySection {
+    fn decrypt_with(mut self, cipher: &JournalCipher) -> Result<Self, String> {
+        self.content = cipher.decrypt(&self.content)?;
+        Ok(self)
+    }
+}
+
+impl JournalContent for Page {
+    fn decrypt_with(mut self, cipher: &JournalCipher) -> Result<Self, String> {
+        self.content = cipher.decrypt(&self.content)?;
+        Ok(self)
+    }
+}
+
+impl JournalContent for PageRevision {
+    fn decrypt_with(mut self, cipher: &JournalCipher) -> Result<Self, String> {
+        self.content = cipher.decrypt(&self.content)?;
+        Ok(self)
+    }
+}
+
+impl JournalContent for TrashedEntry {
+    fn decrypt_with(mut self, cipher: &JournalCipher) -> Result<Self, String> {
+        self.entry = self.entry.decrypt_with(cipher)?;
+        Ok(self)
+    }
+}
+
+impl JournalContent for TrashedPage {
+    fn decrypt_with(mut self, cipher: &JournalCipher) -> Result<Self, String> {
+        self.page = self.page.decrypt_with(cipher)?;
+        Ok(self)
+    }
+}
+
+impl JournalContent for EntrySearchResult {
+    fn decrypt_with(mut self, cipher: &JournalCipher) -> Result<Self, String> {
+        self.entry = self.entry.decrypt_with(cipher)?;
+        Ok(self)
+    }
+}
+
+impl<T: JournalContent> JournalContent for Option<T> {
+    fn decrypt_with(self, cipher: &JournalCipher) -> Result<Self, String> {
+        self.map(|value| value.decrypt_with(cipher)).transpose()
+    }
+}
+
+impl<T: JournalContent> JournalContent for Vec<T> {
+    fn decrypt_with(self, cipher: &JournalCipher) -> Result<Self, String> {
+        self.into_iter()
+            .map(|value| value.decrypt_with(cipher))
+            .collect()
+    }
+}
+
+impl AppState {
+    pub(crate) fn journal_cipher(&self) -> Result<JournalCipher, String> {
+        Ok(self
+            .journal_cipher
+            .lock()
+            .map_err(|e| e.to_string())?
+            .clone())
+    }
+
+    fn set_journal_cipher(&self, cipher: JournalCipher) -> Result<(), String> {
+        *self.journal_cipher.lock().map_err(|e| e.to_string())? = cipher;
+        Ok(())
+    }
+}
+
+pub(crate) fn journal_encryption_enabled_in_conn(conn: &Connection) -> Result<bool, String> {
+    conn.query_row(
+        "SELECT EXISTS(SELECT 1 FROM journal_encryption WHERE id = 1)",
+        [],
+        |row| row.get(0),
+    )
+    .map_err(|e| e.to_string())
+}
+
+pub(crate) fn derive_key(passphrase: &str, salt: &[u8]) -> Result<[u8; 32], String> {
+    let mut key = [0u8; 32];
+    Argon2::default()
+        .hash_password_into(passphrase.as_bytes(), salt, &mut key)
+        .map_err(|e| e.to_string())?;
+    Ok(key)
+}
+
+/// Checks the passphrase against the stored verifier and returns an unlocked cipher.
+pub(crate) fn unlock_journal_in_conn(
+    conn: &Connection,
+    passphrase: &str,
+) -> Result<JournalCipher, String> {
+    let (salt, verifier) = conn
+        .query_row(
+            "SELECT kdf_salt, verifier FROM journal_encryption WHERE id = 1",
+            [],
+            |row| Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?)),
+        )
+        .optional()
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| "Journal encryption is not enabled".to_string())?;
+    let salt = STANDARD.decode(salt).map_err(|e| e.to_string())?;
+
+    let cipher = JournalCipher::unlocked(derive_key(passphrase, &salt)?);
+    match cipher.decrypt(&verifier) {
+        Ok(plaintext) if plaintext == VERIFIER_PLAINTEXT => Ok(cipher),
+        _ => Err("Wrong passphrase".to_string()),
+    }
+}
+
+/// Encrypts every entry and page value that is still plaintext. Used when encryption
+/// is turned on and after a backup import brings in plaintext rows.
+pub(crate) fn encrypt_journal_in_conn(
+    conn: &Connection,
+    cipher: &JournalCipher,
+) -> Result<usize, String> {
+    let entries = entry_texts(
+        conn,
+        "SELECT id, yesterday, today, blockers, notes FROM entries
+         WHERE yesterday NOT LIKE 'enc:v1:%' OR today NOT LIKE 'enc:v1:%'
+            OR blockers NOT LIKE 'enc:v1:%' OR notes NOT LIKE 'enc:v1:%'",
+    )?;
+
+    let mut stmt = conn
+        .prepare("SELECT id, content FROM pages WHERE content NOT LIKE 'enc:v1:%'")
+        .map_err(|e| e.to_string())?;
+    let pages_iter = stmt
+        .query_map([], |row| {
+            Ok((row.get::<_, i64>(0)?, row.get::<_, String>(1)?))
+        })
+        .map_err(|e| e.to_string())?;
+    let mut pages = Vec::new();
+    for page in pages_iter {
+        pages.push(page.map_err(|e| e.to_string())?);
+    }
+
+    let encrypt = |value: &str| {
+        if value.starts_with(ENCRYPTED_PREFIX) {
+            Ok(value.to_string())
+        } else {
+            cipher.encrypt(value)
+        }
+    };
+    for (id, [yesterday, today, blockers, notes]) in &entries {
+        conn.execute(
+            "UPDATE entries SET yesterday = ?1, today = ?2, blockers = ?3, notes = ?4 WHERE id = ?5",
+            params![
+                encrypt(yesterday)?,
+                encrypt(today)?,
+                encrypt(blockers)?,
+                encrypt(notes)?,
+                id
+            ],
+        )
+        .map_err(|e| e.to_string())?;
+    }
+    for (id, content) in &pages {
+        conn.execute(
+            "UPDATE pages SET content = ?1 WHERE id = ?2",
+            params![encrypt(content)?, id],
+        )
+        .map_err(|e| e.to_string())?;
+    }
+    for (date, [yesterday, today, blockers, notes]) in &entry_drafts(conn)? {
+        conn.execute(
+            "UPDATE entry_drafts SET yesterday = ?1, today = ?2, blockers = ?3, notes = ?4
+             WHERE date = ?5",
+            params![
+                encrypt(yesterday)?,
+                encrypt(today)?,
+                encrypt(blockers)?,
+                encrypt(notes)?,
+                date
+            ],
+        )
+        .map_err(|e| e.to_string())?;
+    }
+    for (id, text) in &stored_texts(conn, "entry_notes", "text", "NOT LIKE")? {
+        conn.execute(
+            "UPDATE entry_notes SET text = ?1 WHERE id = ?2",
+            params![encrypt(text)?, id],
+        )
+        .map_err(|e| e.to_string())?;
+    }
+    for (id, content) in &stored_texts(conn, "page_revisions", "content", "NOT LIKE")? {
+        conn.execute(
+            "UPDATE page_revisions SET content = ?1 WHERE id = ?2",
+            params![encrypt(content)?, id],
+        )
+        .map_err(|e| e.to_string())?;
+    }
+    for (id, content) in &stored_texts(conn, "entry_sections", "content", "NOT LIKE")? {
+        conn.execute(
+            "UPDATE entry_sections SET content = ?1 WHERE id = ?2",
+            params![encrypt(content)?, id],
+        )
+        .map_err(|e| e.to_string())?;
+    }
+    // Property values are plaintext copies of page frontmatter; an encrypted journal
+    // keeps none.
+    conn.execute("DELETE FROM page_properties", [])
+        .map_err(|e| e.to_string())?;
+
+    Ok(entries.len() + pages.len())
+}
+
+/// `(id, [yesterday, today, blockers, notes])` for the entries matched by `sql`.
+fn entry_texts(conn: &Connection, sql: &str) -> Result<Vec<(i64, [String; 4])>, String> {
+    let mut stmt = conn.prepare(sql).map_err(|e| e.to_string())?;
+    let entries_iter = stmt
+        .query_map([], |row| {
+            Ok((
+                row.get(0)?,
+                [row.get(1)?, row.get(2)?, row.get(3)?, row.get(4)?],
+            ))
+        })
+        .map_err(|e| e.to_string())?;
+    let mut entries = Vec::new();
+    for entry in entries_iter {
+        entries.push(entry.map_err(|e| e.to_string())?);
+    }
+
+    Ok(entries)
+}
+
+/// Every autosaved draft as `(date, [yesterday, today, blockers, notes])`; there
+/// are only a handful.
+fn entry_drafts(conn: &Connection) -> Result<Vec<(String, [String; 4])>, String> {
+    let mut stmt = conn
+        .prepare("SELECT date, yesterday, today, blockers, notes FROM entry_drafts")
+        .map_err(|e| e.to_string())?;
+    let drafts_iter = stmt
+        .query_map([], |row| {
+            Ok((
+                row.get(0)?,
+                [row.get(1)?, row.get(2)?, row.get(3)?, row.get(4)?],
+            ))
+        })
+        .map_err(|e| e.to_string())?;
+    let mut drafts = Vec::new();
+    for draft in drafts_iter {
+        drafts.push(draft.map_err(|e| e.to_string())?);
+    }
+
+    Ok(drafts)
+}
+
+/// `(id, value)` of the rows in `table` whose `column` is (`LIKE`) or is not
+/// (`NOT LIKE`) encrypted yet; used for day-log notes, page revisions and custom
+/// entry sections.
+fn stored_texts(
+    conn: &Connection,
+    table: &str,
+    column: &str,
+    like: &str,
+) -> Result<Vec<(i64, String)>, String> {
+    let mut stmt = conn
+        .prepare(&format!(
+            "SELECT id, {column} FROM {table} WHERE {column} {like} 'enc:v1:%'"
+        ))
+        .map_err(|e| e.to_string())?;
+    let rows_iter = stmt
+        .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))
+        .map_err(|e| e.to_string())?;
+    let mut rows = Vec::new();
+    for row in rows_iter {
+        rows.push(row.map_err(|e| e.to_string())?);
+    }
+
+    Ok(rows)
+}
+
+fn decrypt_journal_in_conn(conn: &Connection, cipher: &JournalCipher) -> Result<(), String> {
+    let entries = entry_texts(
+        conn,
+        "SELECT id, yesterday, today, blockers, notes FROM entries
+         WHERE yesterday LIKE 'enc:v1:%' OR today LIKE 'enc:v1:%'
+            OR blockers LIKE 'enc:v1:%' OR notes LIKE 'enc:v1:%'",
+    )?;
+    for (id, [yesterday, today, blockers, notes]) in &entries {
+        conn.execute(
+            "UPDATE entries SET yesterday = ?1, today = ?2, blockers = ?3, notes = ?4 WHERE id = ?5",
+            params![
+                cipher.decrypt(yesterday)?,
+                cipher.decrypt(today)?,
+                cipher.decrypt(blockers)?,
+                cipher.decrypt(notes)?,
+                id
+            ],
+        )
+        .map_err(|e| e.to_string())?;
+    }
+
+    let mut stmt = conn
+        .prepare("SELECT id, content FROM pages WHERE content LIKE 'enc:v1:%'")
+        .map_err(|e| e.to_string())?;
+    let pages_iter = stmt
+        .query_map([], |row| {
+            Ok((row.get::<_, i64>(0)?, row.get::<_, String>(1)?))
+        })
+        .map_err(|e| e.to_string())?;
+    let mut pages = Vec::new();
+    for page in pages_iter {
+        pages.push(page.map_err(|e| e.to_string())?);
+    }
+    for (id, content) in &pages {
+        conn.execute(
+            "UPDATE pages SET content = ?1 WHERE id = ?2",
+            params![cipher.decrypt(content)?, id],
+        )
+        .map_err(|e| e.to_string())?;
+    }
+    for (date, [yesterday, today, blockers, notes]) in &entry_drafts(conn)? {
+        conn.execute(
+            "UPDATE entry_drafts SET yesterday = ?1, today = ?2, blockers = ?3, notes = ?4
+             WHERE date = ?5",
+            params![
+                cipher.decrypt(yesterday)?,
+                cipher.decrypt(today)?,
+                cipher.decrypt(blockers)?,
+                cipher.decrypt(notes)?,
+                date
+            ],
+        )
+        .map_err(|e| e.to_string())?;
+    }
+    for (id, text) in &stored_texts(conn, "entry_notes", "text", "LIKE")? {
+        conn.execute(
+            "UPDATE entry_notes SET text = ?1 WHERE id = ?2",
+            params![cipher.decrypt(text)?, id],
+        )
+        .map_err(|e| e.to_string())?;
+    }
+    for (id, content) in &stored_texts(conn, "page_revisions", "content", "LIKE")? {
+        conn.execute(
+            "UPDATE page_revisions SET content = ?1 WHERE id = ?2",
+            params![cipher.decrypt(content)?, id],
+        )
+        .map_err(|e| e.to_string())?;
+    }
+    for (id, content) in &stored_texts(conn, "entry_sections", "content", "LIKE")? {
+        conn.execute(
+            "UPDATE entry_sections SET content = ?1 WHERE id = ?2",
+            params![cipher.decrypt(content)?, id],
+        )
+        .map_err(|e| e.to_string())?;
+    }
+
+    Ok(())
+}
+
+/// Search fallback for an encrypted journal: decrypts every entry and keeps those
+/// containing all query words (case-insensitive). Snippets are the start of the
+/// first matching field, without highlighting.
+pub(crate) fn search_encrypted_entries_in_conn(
+    conn: &Connection,
+    cipher: &JournalCipher,
+    query: &str,
+) -> Result<Vec<EntrySearchResult>, String> {
+    let terms: Vec<String> = query.split_whitespace().map(str::to_lowercase).collect();
+    if terms.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let mut results = Vec::new();
+    for entry in get_entries_in_conn(conn, None, None)?.decrypt_with(cipher)? {
+        let sources = [
+            &entry.yesterday,
+            &entry.today,
+            &entry.blockers,
+            &entry.notes,
+        ];
+        let fields = sources.map(|source| source.to_lowercase());
+        let matches = |field: &String| terms.iter().any(|term| field.contains(term.as_str()));
+        if !terms
+            .iter()
+            .all(|term| fields.iter().any(|field| field.contains(term.as_str())))
+        {
+            continue;
+        }
+
+        let source = fields
+            .iter()
+            .position(matches)
+            .map_or(&entry.today, |index| sources[index]);
+        let snippet: String = source.chars().take(SEARCH_SNIPPET_CHARS).collect();
+        results.push(EntrySearchResult {
+            entry,
+            snippet,
+            rank: 0.0,
+        });
+    }
+
+    // Attachment text is not encrypted, so its index still works.
+    if let Some(fts_query) = fts_query_from_text(query) {
+        for hit in search_attachment_text_in_conn(conn, &fts_query)?.decrypt_with(cipher)? {
+            if !results.iter().any(|result| result.entry.id == hit.entry.id) {
+                results.push(hit);
+            }
+        }
+    }
+
+    Ok(results)
+}
+
+/// Page search fallback for an encrypted journal, matching like
+/// `search_encrypted_entries_in_conn`; title matches sort first.
+pub(crate) fn search_encrypted_pages_in_conn(
+    conn: &Connection,
+    cipher: &JournalCipher,
+    query: &str,
+) -> Result<Vec<PageSearchResult>, String> {
+    let terms: Vec<String> = query.split_whitespace().map(str::to_lowercase).collect();
+    if terms.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let mut results = Vec::new();
+    for page in get_pages_in_conn(conn)?.decrypt_with(cipher)? {
+        let title = page.title.to_lowercase();
+        // Locked pages are found by title only.
+        let content = if page.locked {
+            String::new()
+        } else {
+            page.content.to_lowercase()
+        };
+        if !terms
+            .iter()
+            .all(|term| title.contains(term.as_str()) || content.contains(term.as_str()))
+        {
+            continue;
+        }
+
+        let in_title = terms.iter().any(|term| title.contains(term.as_str()));
+        let snippet: String = if page.locked {
+            String::new()
+        } else {
+            page.content.chars().take(SEARCH_SNIPPET_CHARS).collect()
+        };
+        results.push(PageSearchResult {
+            page,
+            snippet,
+            rank: if in_title { -1.0 } else { 0.0 },
+        });
+    }
+    results.sort_by(|a, b| a.rank.total_cmp(&b.rank));
+
+    Ok(results)
+}
+
+/// Derives a key from `passphrase`, stores its salt and verifier and encrypts the
+/// existing journal in one transaction. Returns the unlocked cipher.
+pub(crate) fn enable_journal_encryption_in_conn(
+    conn: &mut Connection,
+    passphrase: &str,
+) -> Result<JournalCipher, String> {
+    if passphrase.chars().count() < MIN_PASSPHRASE_CHARS {
+        return Err(format!(
+            "Passphrase must be at least {} characters",
+            MIN_PASSPHRASE_CHARS
+        ));
+    }
+    if journal_encryption_enabled_in_conn(conn)? {
+        return Err("Journal encryption is already enabled".to_string());
+    }
+
+    let mut salt = [0u8; SALT_LEN];
+    getrandom::fill(&mut salt).map_err(|e| e.to_string())?;
+    let cipher = JournalCipher::unlocked(derive_key(passphrase, &salt)?);
+
+    let tx = conn.transaction().map_err(|e| e.to_string())?;
+    tx.execute(
+        "INSERT INTO journal_encryption (id, kdf_salt, verifier, created_at)
+         VALUES (1, ?1, ?2, ?3)",
+        params![
+            STANDARD.encode(salt),
+            cipher.encrypt(VERIFIER_PLAINTEXT)?,
+            Utc::now().to_rfc3339()
+        ],
+    )
+    .map_err(|e| e.to_string())?;
+    encrypt_journal_in_conn(&tx, &cipher)?;
+    tx.commit().map_err(|e| e.to_string())?;
+    scrub_plaintext_in_conn(conn)?;
+
+    Ok(cipher)
+}
+
+pub(crate) fn disable_journal_encryption_in_conn(
+    conn: &mut Connection,
+    passphrase: &str,
+) -> Result<(), String> {
+    let cipher = unlock_journal_in_conn(conn, passphrase)?;
+
+    let tx = conn.transaction().map_err(|e| e.to_string())?;
+    decrypt_journal_in_conn(&tx, &cipher)?;
+    tx.execute("DELETE FROM journal_encryption", [])
+        .map_err(|e| e.to_string())?;
+    reindex_page_properties_in_conn(&tx)?;
+    tx.commit().map_err(|e| e.to_string())
+}
+
+/// Rewrites the search index and reclaims free pages so replaced plaintext, and the
+/// page property rows deleted by `encrypt_journal_in_conn`, do not linger in the
+/// database file.
+fn scrub_plaintext_in_conn(conn: &Connection) -> Result<(), String> {
+    conn.execute_batch(
+        "INSERT INTO entries_fts(entries_fts) VALUES ('optimize');
+         INSERT INTO pages_fts(pages_fts) VALUES ('optimize');
+         VACUUM;",
+    )
+    .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn get_journal_encryption_status(
+    state: State<'_, AppState>,
+) -> Result<JournalEncryptionStatus, String> {
+    Ok(state.journal_cipher()?.status())
+}
+
+#[tauri::command]
+pub fn enable_journal_encryption(
+    passphrase: String,
+    state: State<'_, AppState>,
+) -> Result<JournalEncryptionStatus, String> {
+    let mut conn = state.db.lock().map_err(|e| e.to_string())?;
+    let cipher = enable_journal_encryption_in_conn(&mut conn, &passphrase)?;
+    drop(conn);
+
+    let status = cipher.status();
+    state.set_journal_cipher(cipher)?;
+    Ok(status)
+}
+
+#[tauri::command]
+pub fn unlock_journal(
+    passphrase: String,
+    state: State<'_, AppState>,
+) -> Result<JournalEncryptionStatus, String> {
+    let conn = state.db.lock().map_err(|e| e.to_string())?;
+    let cipher = unlock_journal_in_conn(&conn, &passphrase)?;
+    drop(conn);
+
+    let status = cipher.status();
+    state.set_journal_cipher(cipher)?;
+    Ok(status)
+}
+
+#[tauri::command]
+pub fn lock_journal(state: State<'_, AppState>) -> Result<JournalEncryptionStatus, String> {
+    let conn = state.db.lock().map_err(|e| e.to_string())?;
+    let cipher = JournalCipher::from_conn(&conn)?;
+    drop(conn);
+
+    let status = cipher.status();
+    state.set_journal_cipher(cipher)?;
+    Ok(status)
+}
+
+#[tauri::command]
+pub fn disable_journal_encryption(
+    passphrase: String,
+    state: State<'_, AppState>,
+) -> Result<JournalEncryptionStatus, String> {
+    let mut conn = state.db.lock().map_err(|e| e.to_string())?;
+    disable_journal_encryption_in_conn(&mut conn, &passphrase)?;
+    drop(conn);
+
+    let cipher = JournalCipher::default();
+    let status = cipher.status();
+    state.set_journal_cipher(cipher)?;
+    Ok(status)
+}
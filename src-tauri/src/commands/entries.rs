@@ -0,0 +1,1330 @@
+use crate::models::{
+    Entry, EntryCommit, EntryDraft, EntryNote, EntrySearchResult, EntrySection,
+    EntrySectionTemplate, EntryStreaks, EntryTag, TrashedEntry,
+};
+use chrono::{Datelike, Duration, Local, NaiveDate, NaiveDateTime, NaiveTime, Utc, Weekday};
+use rusqlite::{params, Connection, OptionalExtension};
+use serde::Deserialize;
+use std::collections::HashSet;
+use tauri::{AppHandle, Manager, State};
+
+use super::attachments::{
+    attachments_root, entry_attachment_paths, remove_attachment_files,
+    search_attachment_text_in_conn,
+};
+use super::encryption::JournalContent;
+use super::mirror::{mirror_entry, mirror_entry_by_id};
+use super::page_assets::collect_orphan_page_assets;
+use super::pages::empty_page_trash_in_conn;
+use super::settings::read_setting;
+use super::validation::{entry_exists, normalize_entry_tag};
+use super::AppState;
+
+/// Trashed entries are purged for good after this many days.
+pub(crate) const TRASH_RETENTION_DAYS: i64 = 30;
+
+pub(crate) fn entry_from_row(row: &rusqlite::Row<'_>) -> rusqlite::Result<Entry> {
+    Ok(Entry {
+        id: row.get(0)?,
+        date: row.get(1)?,
+        yesterday: row.get(2)?,
+        today: row.get(3)?,
+        project_id: row.get(4)?,
+        created_at: row.get(5)?,
+        pinned: row.get(6)?,
+        blockers: row.get(7)?,
+        notes: row.get(8)?,
+    })
+}
+
+/// Turns free text into an FTS5 query: every word must match, the last one as a prefix.
+/// Words are quoted so FTS operators in user input are treated as plain text.
+pub(crate) fn fts_query_from_text(query: &str) -> Option<String> {
+    let terms: Vec<String> = query
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|term| !term.is_empty())
+        .map(|term| format!("\"{}\"", term))
+        .collect();
+
+    if terms.is_empty() {
+        None
+    } else {
+        Some(format!("{}*", terms.join(" ")))
+    }
+}
+
+/// Ranked full-text search over entry text and attachment text (e.g. captured
+/// command output), best matches first.
+pub(crate) fn search_entries_in_conn(
+    conn: &Connection,
+    query: &str,
+) -> Result<Vec<EntrySearchResult>, String> {
+    let Some(fts_query) = fts_query_from_text(query) else {
+        return Ok(Vec::new());
+    };
+
+    let mut stmt = conn
+        .prepare(
+            "SELECT e.id, e.date, e.yesterday, e.today, e.project_id, e.created_at, e.pinned, e.blockers, e.notes,
+                    snippet(entries_fts, -1, '<mark>', '</mark>', '…', 16),
+                    bm25(entries_fts)
+             FROM entries_fts
+             JOIN entries e ON e.id = entries_fts.rowid
+             WHERE entries_fts MATCH ?1 AND e.deleted_at IS NULL
+             ORDER BY bm25(entries_fts) ASC, e.date DESC
+             LIMIT 100",
+        )
+        .map_err(|e| e.to_string())?;
+
+    let results_iter = stmt
+        .query_map(params![fts_query], |row| {
+            Ok(EntrySearchResult {
+                entry: entry_from_row(row)?,
+                snippet: row.get(9)?,
+                rank: row.get(10)?,
+            })
+        })
+        .map_err(|e| e.to_string())?;
+
+    let mut results = Vec::new();
+    for result in results_iter {
+        results.push(result.map_err(|e| e.to_string())?);
+    }
+
+    for hit in search_attachment_text_in_conn(conn, &fts_query)? {
+        if !results.iter().any(|result| result.entry.id == hit.entry.id) {
+            results.push(hit);
+        }
+    }
+    results.sort_by(|a, b| a.rank.total_cmp(&b.rank));
+
+    Ok(results)
+}
+
+/// Adds a tag to an entry. Tags are case-insensitive, so re-adding one is a no-op.
+pub(crate) fn add_entry_tag_in_conn(
+    conn: &Connection,
+    entry_id: i64,
+    tag: &str,
+) -> Result<(), String> {
+    let tag = normalize_entry_tag(tag)?;
+    if !entry_exists(conn, entry_id)? {
+        return Err(format!("Entry {} not found", entry_id));
+    }
+
+    conn.execute(
+        "INSERT OR IGNORE INTO entry_tags (entry_id, tag, created_at) VALUES (?1, ?2, ?3)",
+        params![entry_id, tag, Utc::now().to_rfc3339()],
+    )
+    .map_err(|e| e.to_string())?;
+
+    Ok(())
+}
+
+pub(crate) fn get_entries_by_tag_in_conn(
+    conn: &Connection,
+    tag: &str,
+) -> Result<Vec<Entry>, String> {
+    let tag = normalize_entry_tag(tag)?;
+    let mut stmt = conn
+        .prepare(
+            "SELECT e.id, e.date, e.yesterday, e.today, e.project_id, e.created_at, e.pinned, e.blockers, e.notes
+             FROM entries e
+             JOIN entry_tags t ON t.entry_id = e.id
+             WHERE t.tag = ?1 AND e.deleted_at IS NULL
+             ORDER BY e.date DESC",
+        )
+        .map_err(|e| e.to_string())?;
+
+    let entries_iter = stmt
+        .query_map(params![tag], entry_from_row)
+        .map_err(|e| e.to_string())?;
+
+    let mut entries = Vec::new();
+    for entry in entries_iter {
+        entries.push(entry.map_err(|e| e.to_string())?);
+    }
+
+    Ok(entries)
+}
+
+pub(crate) fn get_entry_in_conn(conn: &Connection, date: &str) -> Result<Option<Entry>, String> {
+    conn.query_row(
+        "SELECT id, date, yesterday, today, project_id, created_at, pinned, blockers, notes
+         FROM entries WHERE date = ?1 AND deleted_at IS NULL",
+        params![date],
+        entry_from_row,
+    )
+    .optional()
+    .map_err(|e| e.to_string())
+}
+
+pub(crate) fn set_entry_pinned_in_conn(
+    conn: &Connection,
+    date: &str,
+    pinned: bool,
+) -> Result<(), String> {
+    let updated = conn
+        .execute(
+            "UPDATE entries SET pinned = ?1 WHERE date = ?2 AND deleted_at IS NULL",
+            params![pinned, date],
+        )
+        .map_err(|e| e.to_string())?;
+    if updated == 0 {
+        return Err(format!("No entry for {}", date));
+    }
+
+    Ok(())
+}
+
+/// Pinned entries, newest first.
+pub(crate) fn get_pinned_entries_in_conn(conn: &Connection) -> Result<Vec<Entry>, String> {
+    let mut stmt = conn
+        .prepare(
+            "SELECT id, date, yesterday, today, project_id, created_at, pinned, blockers, notes
+             FROM entries WHERE pinned = 1 AND deleted_at IS NULL ORDER BY date DESC",
+        )
+        .map_err(|e| e.to_string())?;
+    let entries_iter = stmt
+        .query_map([], entry_from_row)
+        .map_err(|e| e.to_string())?;
+
+    let mut entries = Vec::new();
+    for entry in entries_iter {
+        entries.push(entry.map_err(|e| e.to_string())?);
+    }
+
+    Ok(entries)
+}
+
+/// Moves an entry to the trash; tags, sections and attachments stay with it until purge.
+pub(crate) fn trash_entry_in_conn(conn: &Connection, date: &str) -> Result<(), String> {
+    let trashed = conn
+        .execute(
+            "UPDATE entries SET deleted_at = ?1 WHERE date = ?2 AND deleted_at IS NULL",
+            params![Utc::now().to_rfc3339(), date],
+        )
+        .map_err(|e| e.to_string())?;
+    if trashed == 0 {
+        return Err(format!("No entry for {}", date));
+    }
+
+    Ok(())
+}
+
+pub(crate) fn restore_entry_in_conn(conn: &Connection, date: &str) -> Result<(), String> {
+    let restored = conn
+        .execute(
+            "UPDATE entries SET deleted_at = NULL WHERE date = ?1 AND deleted_at IS NOT NULL",
+            params![date],
+        )
+        .map_err(|e| e.to_string())?;
+    if restored == 0 {
+        return Err(format!("No trashed entry for {}", date));
+    }
+
+    Ok(())
+}
+
+/// Trashed entries, most recently deleted first.
+pub(crate) fn get_trashed_entries_in_conn(conn: &Connection) -> Result<Vec<TrashedEntry>, String> {
+    let mut stmt = conn
+        .prepare(
+            "SELECT id, date, yesterday, today, project_id, created_at, pinned, blockers, notes, deleted_at
+             FROM entries WHERE deleted_at IS NOT NULL
+             ORDER BY deleted_at DESC, date DESC",
+        )
+        .map_err(|e| e.to_string())?;
+    let entries_iter = stmt
+        .query_map([], |row| {
+            Ok(TrashedEntry {
+                entry: entry_from_row(row)?,
+                deleted_at: row.get(9)?,
+            })
+        })
+        .map_err(|e| e.to_string())?;
+
+    let mut entries = Vec::new();
+    for entry in entries_iter {
+        entries.push(entry.map_err(|e| e.to_string())?);
+    }
+
+    Ok(entries)
+}
+
+/// Permanently deletes trashed entries, all of them or only those trashed before
+/// `trashed_before`. Returns how many were purged and the attachment files to remove.
+pub(crate) fn purge_trash_in_conn(
+    conn: &Connection,
+    trashed_before: Option<&str>,
+) -> Result<(usize, Vec<String>), String> {
+    let mut stmt = conn
+        .prepare(
+            "SELECT date FROM entries
+             WHERE deleted_at IS NOT NULL AND (?1 IS NULL OR deleted_at < ?1)",
+        )
+        .map_err(|e| e.to_string())?;
+    let dates_iter = stmt
+        .query_map(params![trashed_before], |row| row.get::<_, String>(0))
+        .map_err(|e| e.to_string())?;
+
+    let mut attachment_paths = Vec::new();
+    let mut purged = 0;
+    for date in dates_iter {
+        let date = date.map_err(|e| e.to_string())?;
+        attachment_paths.extend(entry_attachment_paths(conn, &date)?);
+        conn.execute("DELETE FROM entries WHERE date = ?1", params![date])
+            .map_err(|e| e.to_string())?;
+        purged += 1;
+    }
+
+    Ok((purged, attachment_paths))
+}
+
+/// Saving a new entry over a date whose entry sits in the trash replaces the trashed one.
+pub(crate) fn discard_trashed_entry_in_conn(
+    conn: &Connection,
+    date: &str,
+) -> Result<Vec<String>, String> {
+    let attachment_paths = entry_attachment_paths(conn, date)?;
+    let discarded = conn
+        .execute(
+            "DELETE FROM entries WHERE date = ?1 AND deleted_at IS NOT NULL",
+            params![date],
+        )
+        .map_err(|e| e.to_string())?;
+
+    Ok(if discarded > 0 {
+        attachment_paths
+    } else {
+        Vec::new()
+    })
+}
+
+/// Appends a line to the "today" section of the entry for `date`, creating the entry
+/// when needed (a trashed entry for that date is restored). Used by editor plugins
+/// that jot notes without opening the app.
+pub(crate) fn append_to_entry_in_conn(
+    conn: &Connection,
+    date: &str,
+    text: &str,
+) -> Result<Entry, String> {
+    let text = text.trim();
+    if text.is_empty() {
+        return Err("Nothing to append".to_string());
+    }
+
+    conn.execute(
+        "INSERT INTO entries (date, yesterday, today, project_id, created_at)
+         VALUES (?1, '', ?2, NULL, ?3)
+         ON CONFLICT(date) DO UPDATE SET
+            today = CASE WHEN trim(entries.today) = '' THEN excluded.today
+                         ELSE entries.today || char(10) || excluded.today END,
+            deleted_at = NULL",
+        params![date, text, Utc::now().to_rfc3339()],
+    )
+    .map_err(|e| e.to_string())?;
+
+    get_entry_in_conn(conn, date)?.ok_or_else(|| "Entry not found".to_string())
+}
+
+/// Entries newest first; `limit`/`offset` page through history, a missing limit returns the rest.
+pub(crate) fn get_entries_in_conn(
+    conn: &Connection,
+    limit: Option<i64>,
+    offset: Option<i64>,
+) -> Result<Vec<Entry>, String> {
+    // SQLite treats a negative LIMIT as "no limit".
+    let limit = limit.map(|limit| limit.clamp(1, 1000)).unwrap_or(-1);
+    let offset = offset.unwrap_or(0).max(0);
+    let mut stmt = conn
+        .prepare(
+            "SELECT id, date, yesterday, today, project_id, created_at, pinned, blockers, notes
+             FROM entries
+             WHERE deleted_at IS NULL
+             ORDER BY date DESC
+             LIMIT ?1 OFFSET ?2",
+        )
+        .map_err(|e| e.to_string())?;
+
+    let entries_iter = stmt
+        .query_map(params![limit, offset], entry_from_row)
+        .map_err(|e| e.to_string())?;
+
+    let mut entries = Vec::new();
+    for entry in entries_iter {
+        entries.push(entry.map_err(|e| e.to_string())?);
+    }
+
+    Ok(entries)
+}
+
+/// Entries dated within `start..=end` (inclusive, `YYYY-MM-DD`), oldest first.
+pub(crate) fn get_entries_between_in_conn(
+    conn: &Connection,
+    start: &str,
+    end: &str,
+) -> Result<Vec<Entry>, String> {
+    let parse = |value: &str| {
+        NaiveDate::parse_from_str(value.trim(), "%Y-%m-%d")
+            .map_err(|_| format!("Invalid date: {}", value))
+    };
+    let (start, end) = (parse(start)?, parse(end)?);
+    if start > end {
+        return Err("Start date must not be after end date".to_string());
+    }
+
+    let mut stmt = conn
+        .prepare(
+            "SELECT id, date, yesterday, today, project_id, created_at, pinned, blockers, notes
+             FROM entries
+             WHERE date BETWEEN ?1 AND ?2 AND deleted_at IS NULL
+             ORDER BY date ASC",
+        )
+        .map_err(|e| e.to_string())?;
+
+    let entries_iter = stmt
+        .query_map(
+            params![
+                start.format("%Y-%m-%d").to_string(),
+                end.format("%Y-%m-%d").to_string()
+            ],
+            entry_from_row,
+        )
+        .map_err(|e| e.to_string())?;
+
+    let mut entries = Vec::new();
+    for entry in entries_iter {
+        entries.push(entry.map_err(|e| e.to_string())?);
+    }
+
+    Ok(entries)
+}
+
+#[tauri::command]
+pub fn get_entries_between(
+    start: String,
+    end: String,
+    state: State<'_, AppState>,
+) -> Result<Vec<Entry>, String> {
+    let conn = state.db.lock().map_err(|e| e.to_string())?;
+    get_entries_between_in_conn(&conn, &start, &end)?.decrypt_with(&state.journal_cipher()?)
+}
+
+/// Entries written on `month_day` (`MM-DD`) in years before `before_year`, newest first.
+pub(crate) fn get_entries_on_this_day_in_conn(
+    conn: &Connection,
+    month_day: &str,
+    before_year: i32,
+) -> Result<Vec<Entry>, String> {
+    let month_day = month_day.trim();
+    // 2000 is a leap year, so 02-29 is accepted.
+    NaiveDate::parse_from_str(&format!("2000-{}", month_day), "%Y-%m-%d")
+        .map_err(|_| format!("Invalid month and day: {}", month_day))?;
+
+    let mut stmt = conn
+        .prepare(
+            "SELECT id, date, yesterday, today, project_id, created_at, pinned, blockers, notes
+             FROM entries
+             WHERE substr(date, 6, 5) = ?1 AND date < ?2 AND deleted_at IS NULL
+             ORDER BY date DESC",
+        )
+        .map_err(|e| e.to_string())?;
+
+    let entries_iter = stmt
+        .query_map(
+            params![month_day, format!("{:04}-01-01", before_year)],
+            entry_from_row,
+        )
+        .map_err(|e| e.to_string())?;
+
+    let mut entries = Vec::new();
+    for entry in entries_iter {
+        entries.push(entry.map_err(|e| e.to_string())?);
+    }
+
+    Ok(entries)
+}
+
+/// A random entry dated `older_than_days` or more before `today` (any earlier day
+/// when `None`). Picks a random offset into the date index instead of sorting by
+/// `RANDOM()`, so it stays cheap with years of entries.
+pub(crate) fn get_random_entry_in_conn(
+    conn: &Connection,
+    today: NaiveDate,
+    older_than_days: Option<i64>,
+) -> Result<Option<Entry>, String> {
+    let cutoff = match older_than_days {
+        Some(days) if days < 0 => return Err("older_than_days must not be negative".to_string()),
+        Some(days) => today - Duration::days(days),
+        None => today - Duration::days(1),
+    };
+
+    conn.query_row(
+        "SELECT id, date, yesterday, today, project_id, created_at, pinned, blockers, notes
+         FROM entries
+         WHERE date <= ?1 AND deleted_at IS NULL
+         ORDER BY date
+         LIMIT 1 OFFSET (
+             SELECT abs(random()) % max(COUNT(*), 1)
+             FROM entries WHERE date <= ?1 AND deleted_at IS NULL
+         )",
+        params![cutoff.format("%Y-%m-%d").to_string()],
+        entry_from_row,
+    )
+    .optional()
+    .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn get_entries_on_this_day(
+    month_day: String,
+    state: State<'_, AppState>,
+) -> Result<Vec<Entry>, String> {
+    let conn = state.db.lock().map_err(|e| e.to_string())?;
+    get_entries_on_this_day_in_conn(&conn, &month_day, Local::now().year())?
+        .decrypt_with(&state.journal_cipher()?)
+}
+
+/// Surfaces an old entry for reflection; `None` when there is nothing old enough.
+#[tauri::command]
+pub fn get_random_entry(
+    older_than_days: Option<i64>,
+    state: State<'_, AppState>,
+) -> Result<Option<Entry>, String> {
+    let conn = state.db.lock().map_err(|e| e.to_string())?;
+    get_random_entry_in_conn(&conn, Local::now().date_naive(), older_than_days)?
+        .decrypt_with(&state.journal_cipher()?)
+}
+
+#[tauri::command]
+pub fn get_trashed_entries(state: State<'_, AppState>) -> Result<Vec<TrashedEntry>, String> {
+    let conn = state.db.lock().map_err(|e| e.to_string())?;
+    get_trashed_entries_in_conn(&conn)?.decrypt_with(&state.journal_cipher()?)
+}
+
+#[tauri::command]
+pub fn restore_entry(date: String, state: State<'_, AppState>) -> Result<(), String> {
+    let conn = state.db.lock().map_err(|e| e.to_string())?;
+    restore_entry_in_conn(&conn, &date)?;
+    mirror_entry(&conn, &date);
+
+    Ok(())
+}
+
+#[tauri::command]
+pub fn purge_trash(app: AppHandle, state: State<'_, AppState>) -> Result<usize, String> {
+    let conn = state.db.lock().map_err(|e| e.to_string())?;
+    let (purged, attachment_paths) = purge_trash_in_conn(&conn, None)?;
+    drop(conn);
+
+    if !attachment_paths.is_empty() {
+        remove_attachment_files(&attachments_root(&app)?, &attachment_paths);
+    }
+
+    Ok(purged)
+}
+
+/// Scheduler job: drops entries and pages that have been in the trash longer than
+/// `TRASH_RETENTION_DAYS`.
+pub(crate) fn purge_expired_trash_for_app(app: &AppHandle) -> Result<(), String> {
+    let cutoff = (Utc::now() - Duration::days(TRASH_RETENTION_DAYS)).to_rfc3339();
+    let state = app.state::<AppState>();
+    let conn = state.db.lock().map_err(|e| e.to_string())?;
+    let (_, attachment_paths) = purge_trash_in_conn(&conn, Some(&cutoff))?;
+    empty_page_trash_in_conn(&conn, Some(&cutoff))?;
+    collect_orphan_page_assets(&conn, app);
+    drop(conn);
+
+    if !attachment_paths.is_empty() {
+        remove_attachment_files(&attachments_root(app)?, &attachment_paths);
+    }
+
+    Ok(())
+}
+
+/// Whether the daily "write your entry" reminder is due: it is past
+/// `journal_reminder_time` (a blank time turns it off) and `now`'s day has no
+/// entry, or only an empty one.
+pub(crate) fn journal_reminder_due_in_conn(
+    conn: &Connection,
+    now: NaiveDateTime,
+) -> Result<bool, String> {
+    let reminder_time = read_setting(conn, "journal_reminder_time")?;
+    let Ok(reminder_time) = NaiveTime::parse_from_str(reminder_time.trim(), "%H:%M") else {
+        return Ok(false);
+    };
+    if now.time() < reminder_time {
+        return Ok(false);
+    }
+
+    Ok(!entry_written_on_in_conn(conn, now.date())?)
+}
+
+/// Whether `date` has an entry with any non-blank section.
+pub(crate) fn entry_written_on_in_conn(conn: &Connection, date: NaiveDate) -> Result<bool, String> {
+    conn.query_row(
+        "SELECT EXISTS(
+                 SELECT 1 FROM entries
+                 WHERE date = ?1 AND deleted_at IS NULL
+                   AND (trim(yesterday) <> '' OR trim(today) <> ''
+                        OR trim(blockers) <> '' OR trim(notes) <> '')
+             )",
+        params![date.format("%Y-%m-%d").to_string()],
+        |row| row.get(0),
+    )
+    .map_err(|e| e.to_string())
+}
+
+/// Autosaved standup text for `date`. Drafts live in `entry_drafts`, apart from
+/// `entries`, so half-written text never shows up in lists, search or stats.
+/// Callers pass already encrypted text when journal encryption is on.
+pub(crate) fn save_entry_draft_in_conn(
+    conn: &Connection,
+    date: &str,
+    yesterday: &str,
+    today: &str,
+    blockers: &str,
+    notes: &str,
+) -> Result<EntryDraft, String> {
+    let date = NaiveDate::parse_from_str(date.trim(), "%Y-%m-%d")
+        .map_err(|_| format!("Invalid date: {}", date))?
+        .format("%Y-%m-%d")
+        .to_string();
+    let updated_at = Utc::now().to_rfc3339();
+
+    conn.execute(
+        "INSERT INTO entry_drafts (date, yesterday, today, blockers, notes, updated_at)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6)
+         ON CONFLICT(date) DO UPDATE SET
+            yesterday = excluded.yesterday,
+            today = excluded.today,
+            blockers = excluded.blockers,
+            notes = excluded.notes,
+            updated_at = excluded.updated_at",
+        params![date, yesterday, today, blockers, notes, updated_at],
+    )
+    .map_err(|e| e.to_string())?;
+
+    Ok(EntryDraft {
+        date,
+        yesterday: yesterday.to_string(),
+        today: today.to_string(),
+        blockers: blockers.to_string(),
+        notes: notes.to_string(),
+        updated_at,
+    })
+}
+
+pub(crate) fn get_entry_draft_in_conn(
+    conn: &Connection,
+    date: &str,
+) -> Result<Option<EntryDraft>, String> {
+    conn.query_row(
+        "SELECT date, yesterday, today, blockers, notes, updated_at
+         FROM entry_drafts WHERE date = ?1",
+        params![date],
+        |row| {
+            Ok(EntryDraft {
+                date: row.get(0)?,
+                yesterday: row.get(1)?,
+                today: row.get(2)?,
+                blockers: row.get(3)?,
+                notes: row.get(4)?,
+                updated_at: row.get(5)?,
+            })
+        },
+    )
+    .optional()
+    .map_err(|e| e.to_string())
+}
+
+/// Drops the draft for `date`; called when the entry is saved or the draft is reset.
+pub(crate) fn discard_entry_draft_in_conn(conn: &Connection, date: &str) -> Result<(), String> {
+    conn.execute("DELETE FROM entry_drafts WHERE date = ?1", params![date])
+        .map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+#[tauri::command]
+pub fn save_entry_draft(
+    date: String,
+    yesterday: String,
+    today: String,
+    blockers: Option<String>,
+    notes: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<EntryDraft, String> {
+    let (blockers, notes) = (blockers.unwrap_or_default(), notes.unwrap_or_default());
+    let cipher = state.journal_cipher()?;
+    let conn = state.db.lock().map_err(|e| e.to_string())?;
+    let draft = save_entry_draft_in_conn(
+        &conn,
+        &date,
+        &cipher.encrypt(&yesterday)?,
+        &cipher.encrypt(&today)?,
+        &cipher.encrypt(&blockers)?,
+        &cipher.encrypt(&notes)?,
+    )?;
+
+    Ok(EntryDraft {
+        yesterday,
+        today,
+        blockers,
+        notes,
+        ..draft
+    })
+}
+
+#[tauri::command]
+pub fn get_entry_draft(
+    date: String,
+    state: State<'_, AppState>,
+) -> Result<Option<EntryDraft>, String> {
+    let cipher = state.journal_cipher()?;
+    let conn = state.db.lock().map_err(|e| e.to_string())?;
+    get_entry_draft_in_conn(&conn, &date)?.decrypt_with(&cipher)
+}
+
+#[tauri::command]
+pub fn discard_entry_draft(date: String, state: State<'_, AppState>) -> Result<(), String> {
+    let conn = state.db.lock().map_err(|e| e.to_string())?;
+    discard_entry_draft_in_conn(&conn, &date)
+}
+
+/// Adds a timestamped note to `date`'s log. Callers pass already encrypted text
+/// when journal encryption is on.
+pub(crate) fn append_entry_note_in_conn(
+    conn: &Connection,
+    date: &str,
+    text: &str,
+) -> Result<EntryNote, String> {
+    let date = NaiveDate::parse_from_str(date.trim(), "%Y-%m-%d")
+        .map_err(|_| format!("Invalid date: {}", date))?
+        .format("%Y-%m-%d")
+        .to_string();
+    let created_at = Utc::now().to_rfc3339();
+    conn.execute(
+        "INSERT INTO entry_notes (date, text, created_at) VALUES (?1, ?2, ?3)",
+        params![date, text, created_at],
+    )
+    .map_err(|e| e.to_string())?;
+
+    Ok(EntryNote {
+        id: conn.last_insert_rowid(),
+        date,
+        text: text.to_string(),
+        created_at,
+    })
+}
+
+/// Notes logged on `date`, oldest first.
+pub(crate) fn get_entry_notes_in_conn(
+    conn: &Connection,
+    date: &str,
+) -> Result<Vec<EntryNote>, String> {
+    let mut stmt = conn
+        .prepare(
+            "SELECT id, date, text, created_at FROM entry_notes
+             WHERE date = ?1 ORDER BY created_at ASC, id ASC",
+        )
+        .map_err(|e| e.to_string())?;
+    let notes_iter = stmt
+        .query_map(params![date], |row| {
+            Ok(EntryNote {
+                id: row.get(0)?,
+                date: row.get(1)?,
+                text: row.get(2)?,
+                created_at: row.get(3)?,
+            })
+        })
+        .map_err(|e| e.to_string())?;
+
+    let mut notes = Vec::new();
+    for note in notes_iter {
+        notes.push(note.map_err(|e| e.to_string())?);
+    }
+
+    Ok(notes)
+}
+
+#[tauri::command]
+pub fn append_entry_note(
+    date: String,
+    text: String,
+    state: State<'_, AppState>,
+) -> Result<EntryNote, String> {
+    let text = text.trim().to_string();
+    if text.is_empty() {
+        return Err("Note text is required".to_string());
+    }
+    let cipher = state.journal_cipher()?;
+    let conn = state.db.lock().map_err(|e| e.to_string())?;
+    let note = append_entry_note_in_conn(&conn, &date, &cipher.encrypt(&text)?)?;
+
+    Ok(EntryNote { text, ..note })
+}
+
+#[tauri::command]
+pub fn get_entry_notes(date: String, state: State<'_, AppState>) -> Result<Vec<EntryNote>, String> {
+    let cipher = state.journal_cipher()?;
+    let conn = state.db.lock().map_err(|e| e.to_string())?;
+    get_entry_notes_in_conn(&conn, &date)?.decrypt_with(&cipher)
+}
+
+#[tauri::command]
+pub fn delete_entry_note(id: i64, state: State<'_, AppState>) -> Result<(), String> {
+    let conn = state.db.lock().map_err(|e| e.to_string())?;
+    conn.execute("DELETE FROM entry_notes WHERE id = ?1", params![id])
+        .map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+#[tauri::command]
+pub fn set_entry_pinned(
+    date: String,
+    pinned: bool,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    let conn = state.db.lock().map_err(|e| e.to_string())?;
+    set_entry_pinned_in_conn(&conn, &date, pinned)
+}
+
+#[tauri::command]
+pub fn get_pinned_entries(state: State<'_, AppState>) -> Result<Vec<Entry>, String> {
+    let conn = state.db.lock().map_err(|e| e.to_string())?;
+    get_pinned_entries_in_conn(&conn)?.decrypt_with(&state.journal_cipher()?)
+}
+
+#[tauri::command]
+pub fn add_entry_tag(entry_id: i64, tag: String, state: State<'_, AppState>) -> Result<(), String> {
+    let conn = state.db.lock().map_err(|e| e.to_string())?;
+    add_entry_tag_in_conn(&conn, entry_id, &tag)?;
+    mirror_entry_by_id(&conn, entry_id);
+
+    Ok(())
+}
+
+#[tauri::command]
+pub fn remove_entry_tag(
+    entry_id: i64,
+    tag: String,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    let conn = state.db.lock().map_err(|e| e.to_string())?;
+    let tag = normalize_entry_tag(&tag)?;
+
+    conn.execute(
+        "DELETE FROM entry_tags WHERE entry_id = ?1 AND tag = ?2",
+        params![entry_id, tag],
+    )
+    .map_err(|e| e.to_string())?;
+    mirror_entry_by_id(&conn, entry_id);
+
+    Ok(())
+}
+
+/// All entry/tag pairs, so the UI can render tag chips and a tag filter from one query.
+#[tauri::command]
+pub fn get_entry_tags(state: State<'_, AppState>) -> Result<Vec<EntryTag>, String> {
+    let conn = state.db.lock().map_err(|e| e.to_string())?;
+    let mut stmt = conn
+        .prepare(
+            "SELECT t.entry_id, t.tag, t.created_at FROM entry_tags t
+             JOIN entries e ON e.id = t.entry_id
+             WHERE e.deleted_at IS NULL
+             ORDER BY t.tag ASC, t.entry_id ASC",
+        )
+        .map_err(|e| e.to_string())?;
+
+    let tags_iter = stmt
+        .query_map([], |row| {
+            Ok(EntryTag {
+                entry_id: row.get(0)?,
+                tag: row.get(1)?,
+                created_at: row.get(2)?,
+            })
+        })
+        .map_err(|e| e.to_string())?;
+
+    let mut tags = Vec::new();
+    for tag in tags_iter {
+        tags.push(tag.map_err(|e| e.to_string())?);
+    }
+
+    Ok(tags)
+}
+
+#[tauri::command]
+pub fn get_entries_by_tag(tag: String, state: State<'_, AppState>) -> Result<Vec<Entry>, String> {
+    let conn = state.db.lock().map_err(|e| e.to_string())?;
+    get_entries_by_tag_in_conn(&conn, &tag)?.decrypt_with(&state.journal_cipher()?)
+}
+
+/// Section keys backed by the fixed `entries` columns; templates cannot reuse them.
+const BUILT_IN_SECTION_KEYS: &[&str] = &["yesterday", "today"];
+
+#[derive(Debug, Deserialize)]
+pub struct EntrySectionTemplateInput {
+    pub section_key: Option<String>,
+    pub label: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct EntrySectionInput {
+    pub section_key: String,
+    pub content: String,
+}
+
+/// Lowercase `a-z0-9_` key derived from a label, e.g. "Wins & Learnings" -> "wins_learnings".
+pub(crate) fn section_key_from_label(label: &str) -> String {
+    label
+        .to_lowercase()
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<_>>()
+        .join("_")
+}
+
+pub(crate) fn get_entry_section_templates_in_conn(
+    conn: &Connection,
+) -> Result<Vec<EntrySectionTemplate>, String> {
+    let mut stmt = conn
+        .prepare(
+            "SELECT id, section_key, label, sort_order
+             FROM entry_section_templates
+             ORDER BY sort_order ASC, id ASC",
+        )
+        .map_err(|e| e.to_string())?;
+
+    let templates_iter = stmt
+        .query_map([], |row| {
+            Ok(EntrySectionTemplate {
+                id: row.get(0)?,
+                section_key: row.get(1)?,
+                label: row.get(2)?,
+                sort_order: row.get(3)?,
+            })
+        })
+        .map_err(|e| e.to_string())?;
+
+    let mut templates = Vec::new();
+    for template in templates_iter {
+        templates.push(template.map_err(|e| e.to_string())?);
+    }
+
+    Ok(templates)
+}
+
+/// Replaces the section template list; list order becomes `sort_order`.
+/// Content already written under removed keys is kept on the entries.
+pub(crate) fn save_entry_section_templates_in_conn(
+    conn: &Connection,
+    templates: Vec<EntrySectionTemplateInput>,
+) -> Result<Vec<EntrySectionTemplate>, String> {
+    let mut seen_keys = HashSet::new();
+    let mut normalized = Vec::new();
+    for template in templates {
+        let label = template.label.trim().to_string();
+        if label.is_empty() {
+            return Err("Section label cannot be empty".to_string());
+        }
+
+        let section_key = template
+            .section_key
+            .as_deref()
+            .map(section_key_from_label)
+            .filter(|key| !key.is_empty())
+            .unwrap_or_else(|| section_key_from_label(&label));
+        if section_key.is_empty() {
+            return Err(format!("Section \"{}\" needs a letter or digit", label));
+        }
+        if BUILT_IN_SECTION_KEYS.contains(&section_key.as_str()) {
+            return Err(format!("\"{}\" is a built-in section", section_key));
+        }
+        if !seen_keys.insert(section_key.clone()) {
+            return Err(format!("Duplicate section: {}", section_key));
+        }
+
+        normalized.push((section_key, label));
+    }
+
+    let now = Utc::now().to_rfc3339();
+    conn.execute("DELETE FROM entry_section_templates", [])
+        .map_err(|e| e.to_string())?;
+    for (index, (section_key, label)) in normalized.iter().enumerate() {
+        conn.execute(
+            "INSERT INTO entry_section_templates (section_key, label, sort_order, created_at)
+             VALUES (?1, ?2, ?3, ?4)",
+            params![section_key, label, index as i64, now],
+        )
+        .map_err(|e| e.to_string())?;
+    }
+
+    get_entry_section_templates_in_conn(conn)
+}
+
+fn entry_id_for_date(conn: &Connection, date: &str) -> Result<Option<i64>, String> {
+    conn.query_row(
+        "SELECT id FROM entries WHERE date = ?1 AND deleted_at IS NULL",
+        params![date],
+        |row| row.get(0),
+    )
+    .optional()
+    .map_err(|e| e.to_string())
+}
+
+pub(crate) fn get_entry_sections_in_conn(
+    conn: &Connection,
+    date: &str,
+) -> Result<Vec<EntrySection>, String> {
+    let mut stmt = conn
+        .prepare(
+            "SELECT s.id, s.entry_id, s.section_key, s.content, s.sort_order, s.updated_at
+             FROM entry_sections s
+             JOIN entries e ON e.id = s.entry_id
+             WHERE e.date = ?1 AND e.deleted_at IS NULL
+             ORDER BY s.sort_order ASC, s.id ASC",
+        )
+        .map_err(|e| e.to_string())?;
+
+    let sections_iter = stmt
+        .query_map(params![date], |row| {
+            Ok(EntrySection {
+                id: row.get(0)?,
+                entry_id: row.get(1)?,
+                section_key: row.get(2)?,
+                content: row.get(3)?,
+                sort_order: row.get(4)?,
+                updated_at: row.get(5)?,
+            })
+        })
+        .map_err(|e| e.to_string())?;
+
+    let mut sections = Vec::new();
+    for section in sections_iter {
+        sections.push(section.map_err(|e| e.to_string())?);
+    }
+
+    Ok(sections)
+}
+
+/// Upserts section content for the entry on `date`; blank content removes the section.
+/// Sections not present in `sections` are left untouched.
+pub(crate) fn save_entry_sections_in_conn(
+    conn: &Connection,
+    date: &str,
+    sections: Vec<EntrySectionInput>,
+) -> Result<Vec<EntrySection>, String> {
+    let entry_id = entry_id_for_date(conn, date)?
+        .ok_or_else(|| format!("No entry for {}; save the entry first", date))?;
+    let template_order: Vec<String> = get_entry_section_templates_in_conn(conn)?
+        .into_iter()
+        .map(|template| template.section_key)
+        .collect();
+    let now = Utc::now().to_rfc3339();
+
+    for (index, section) in sections.into_iter().enumerate() {
+        let section_key = section_key_from_label(&section.section_key);
+        if section_key.is_empty() || BUILT_IN_SECTION_KEYS.contains(&section_key.as_str()) {
+            return Err(format!("Invalid section: {}", section.section_key));
+        }
+
+        if section.content.trim().is_empty() {
+            conn.execute(
+                "DELETE FROM entry_sections WHERE entry_id = ?1 AND section_key = ?2",
+                params![entry_id, section_key],
+            )
+            .map_err(|e| e.to_string())?;
+            continue;
+        }
+
+        let sort_order = template_order
+            .iter()
+            .position(|key| *key == section_key)
+            .unwrap_or(template_order.len() + index) as i64;
+        conn.execute(
+            "INSERT INTO entry_sections (entry_id, section_key, content, sort_order, updated_at)
+             VALUES (?1, ?2, ?3, ?4, ?5)
+             ON CONFLICT(entry_id, section_key) DO UPDATE SET
+                content = excluded.content,
+                sort_order = excluded.sort_order,
+                updated_at = excluded.updated_at",
+            params![entry_id, section_key, section.content, sort_order, now],
+        )
+        .map_err(|e| e.to_string())?;
+    }
+
+    get_entry_sections_in_conn(conn, date)
+}
+
+#[tauri::command]
+pub fn get_entry_section_templates(
+    state: State<'_, AppState>,
+) -> Result<Vec<EntrySectionTemplate>, String> {
+    let conn = state.db.lock().map_err(|e| e.to_string())?;
+    get_entry_section_templates_in_conn(&conn)
+}
+
+#[tauri::command]
+pub fn save_entry_section_templates(
+    templates: Vec<EntrySectionTemplateInput>,
+    state: State<'_, AppState>,
+) -> Result<Vec<EntrySectionTemplate>, String> {
+    let mut conn = state.db.lock().map_err(|e| e.to_string())?;
+    let tx = conn.transaction().map_err(|e| e.to_string())?;
+    let saved = save_entry_section_templates_in_conn(&tx, templates)?;
+    tx.commit().map_err(|e| e.to_string())?;
+
+    Ok(saved)
+}
+
+#[tauri::command]
+pub fn get_entry_sections(
+    date: String,
+    state: State<'_, AppState>,
+) -> Result<Vec<EntrySection>, String> {
+    let conn = state.db.lock().map_err(|e| e.to_string())?;
+    get_entry_sections_in_conn(&conn, &date)?.decrypt_with(&state.journal_cipher()?)
+}
+
+#[tauri::command]
+pub fn save_entry_sections(
+    date: String,
+    sections: Vec<EntrySectionInput>,
+    state: State<'_, AppState>,
+) -> Result<Vec<EntrySection>, String> {
+    let cipher = state.journal_cipher()?;
+    // Blank content stays blank so it still removes the section.
+    let sections = sections
+        .into_iter()
+        .map(|section| {
+            let content = if section.content.trim().is_empty() {
+                section.content
+            } else {
+                cipher.encrypt(&section.content)?
+            };
+            Ok(EntrySectionInput { content, ..section })
+        })
+        .collect::<Result<Vec<_>, String>>()?;
+    let mut conn = state.db.lock().map_err(|e| e.to_string())?;
+    let tx = conn.transaction().map_err(|e| e.to_string())?;
+    let saved = save_entry_sections_in_conn(&tx, &date, sections)?;
+    tx.commit().map_err(|e| e.to_string())?;
+    mirror_entry(&conn, &date);
+
+    saved.decrypt_with(&cipher)
+}
+
+/// Splits a `git log --oneline` line into hash and message.
+pub(crate) fn parse_oneline_commit(line: &str) -> Option<(String, String)> {
+    let line = line.trim();
+    let (hash, message) = line.split_once(char::is_whitespace).unwrap_or((line, ""));
+    if hash.len() < 4 || !hash.chars().all(|character| character.is_ascii_hexdigit()) {
+        return None;
+    }
+
+    Some((hash.to_ascii_lowercase(), message.trim().to_string()))
+}
+
+pub(crate) fn get_entry_commits_in_conn(
+    conn: &Connection,
+    date: &str,
+) -> Result<Vec<EntryCommit>, String> {
+    let mut stmt = conn
+        .prepare(
+            "SELECT c.id, c.entry_id, c.hash, c.message, c.position, c.created_at
+             FROM entry_commits c
+             JOIN entries e ON e.id = c.entry_id
+             WHERE e.date = ?1 AND e.deleted_at IS NULL
+             ORDER BY c.position ASC, c.id ASC",
+        )
+        .map_err(|e| e.to_string())?;
+    let commits_iter = stmt
+        .query_map(params![date], |row| {
+            Ok(EntryCommit {
+                id: row.get(0)?,
+                entry_id: row.get(1)?,
+                hash: row.get(2)?,
+                message: row.get(3)?,
+                position: row.get(4)?,
+                created_at: row.get(5)?,
+            })
+        })
+        .map_err(|e| e.to_string())?;
+
+    let mut commits = Vec::new();
+    for commit in commits_iter {
+        commits.push(commit.map_err(|e| e.to_string())?);
+    }
+
+    Ok(commits)
+}
+
+/// Adds commits (`git log --oneline` lines) to the entry's snapshot. Already stored
+/// hashes are kept as-is, so saving from another machine never drops earlier commits.
+pub(crate) fn save_entry_commits_in_conn(
+    conn: &Connection,
+    date: &str,
+    commits: &[String],
+) -> Result<Vec<EntryCommit>, String> {
+    let entry_id = entry_id_for_date(conn, date)?
+        .ok_or_else(|| format!("No entry for {}; save the entry first", date))?;
+    let next_position: i64 = conn
+        .query_row(
+            "SELECT COALESCE(MAX(position) + 1, 0) FROM entry_commits WHERE entry_id = ?1",
+            params![entry_id],
+            |row| row.get(0),
+        )
+        .map_err(|e| e.to_string())?;
+    let now = Utc::now().to_rfc3339();
+
+    // git log lists newest first; store oldest first so the snapshot reads chronologically.
+    let mut position = next_position;
+    for line in commits.iter().rev() {
+        let Some((hash, message)) = parse_oneline_commit(line) else {
+            continue;
+        };
+        let inserted = conn
+            .execute(
+                "INSERT OR IGNORE INTO entry_commits (entry_id, hash, message, position, created_at)
+                 VALUES (?1, ?2, ?3, ?4, ?5)",
+                params![entry_id, hash, message, position, now],
+            )
+            .map_err(|e| e.to_string())?;
+        position += inserted as i64;
+    }
+
+    get_entry_commits_in_conn(conn, date)
+}
+
+#[tauri::command]
+pub fn get_entry_commits(
+    date: String,
+    state: State<'_, AppState>,
+) -> Result<Vec<EntryCommit>, String> {
+    let conn = state.db.lock().map_err(|e| e.to_string())?;
+    get_entry_commits_in_conn(&conn, &date)
+}
+
+#[tauri::command]
+pub fn save_entry_commits(
+    date: String,
+    commits: Vec<String>,
+    state: State<'_, AppState>,
+) -> Result<Vec<EntryCommit>, String> {
+    let mut conn = state.db.lock().map_err(|e| e.to_string())?;
+    let tx = conn.transaction().map_err(|e| e.to_string())?;
+    let saved = save_entry_commits_in_conn(&tx, &date, &commits)?;
+    tx.commit().map_err(|e| e.to_string())?;
+
+    Ok(saved)
+}
+
+/// The `work_days` weekdays minus the dates listed in `days_off` (comma-separated
+/// `YYYY-MM-DD`). Unparseable values are ignored.
+pub(crate) struct WorkCalendar {
+    work_days: HashSet<Weekday>,
+    days_off: HashSet<NaiveDate>,
+}
+
+impl WorkCalendar {
+    pub(crate) fn load(conn: &Connection) -> Result<Self, String> {
+        Ok(Self {
+            work_days: read_setting(conn, "work_days")?
+                .split(',')
+                .filter_map(|day| day.trim().parse().ok())
+                .collect(),
+            days_off: read_setting(conn, "days_off")?
+                .split([',', '\n'])
+                .filter_map(|date| NaiveDate::parse_from_str(date.trim(), "%Y-%m-%d").ok())
+                .collect(),
+        })
+    }
+
+    pub(crate) fn is_workday(&self, day: NaiveDate) -> bool {
+        self.work_days.contains(&day.weekday()) && !self.days_off.contains(&day)
+    }
+}
+
+/// The working day before `today`, where the standup window starts: the closest
+/// earlier day on the `WorkCalendar`, so a Monday standup covers Friday. With no
+/// working day in the last four weeks it is yesterday.
+pub(crate) fn previous_workday_in_conn(
+    conn: &Connection,
+    today: NaiveDate,
+) -> Result<NaiveDate, String> {
+    let calendar = WorkCalendar::load(conn)?;
+
+    let yesterday = today - Duration::days(1);
+    Ok((1..=28)
+        .map(|offset| today - Duration::days(offset))
+        .find(|day| calendar.is_workday(*day))
+        .unwrap_or(yesterday))
+}
+
+#[tauri::command]
+pub fn get_previous_workday(state: State<'_, AppState>) -> Result<String, String> {
+    let conn = state.db.lock().map_err(|e| e.to_string())?;
+    Ok(previous_workday_in_conn(&conn, Local::now().date_naive())?
+        .format("%Y-%m-%d")
+        .to_string())
+}
+
+/// Like habit streaks, a run still counts as current when its last day is yesterday,
+/// so the streak does not read 0 before today's entry is written.
+pub(crate) fn compute_entry_streaks(dates: &[String], today: NaiveDate) -> EntryStreaks {
+    let mut days: Vec<NaiveDate> = dates
+        .iter()
+        .filter_map(|date| NaiveDate::parse_from_str(date, "%Y-%m-%d").ok())
+        .filter(|date| *date <= today)
+        .collect();
+    days.sort();
+    days.dedup();
+
+    let mut streaks = EntryStreaks {
+        total_entries: dates.len() as i64,
+        ..EntryStreaks::default()
+    };
+    let mut run = 0;
+    let mut previous: Option<NaiveDate> = None;
+    for day in &days {
+        run = match previous {
+            Some(previous) if *day - previous == Duration::days(1) => run + 1,
+            _ => 1,
+        };
+        streaks.longest_streak = streaks.longest_streak.max(run);
+        previous = Some(*day);
+    }
+
+    if previous.is_some_and(|last| today - last <= Duration::days(1)) {
+        streaks.current_streak = run;
+    }
+    streaks
+}
+
+pub(crate) fn get_entry_streaks_in_conn(
+    conn: &Connection,
+    today: NaiveDate,
+) -> Result<EntryStreaks, String> {
+    let mut stmt = conn
+        .prepare("SELECT date FROM entries WHERE deleted_at IS NULL ORDER BY date ASC")
+        .map_err(|e| e.to_string())?;
+    let dates_iter = stmt
+        .query_map([], |row| row.get::<_, String>(0))
+        .map_err(|e| e.to_string())?;
+
+    let mut dates = Vec::new();
+    for date in dates_iter {
+        dates.push(date.map_err(|e| e.to_string())?);
+    }
+
+    Ok(compute_entry_streaks(&dates, today))
+}
+
+/// Entry dates are local calendar days, so the streak is measured against local today.
+#[tauri::command]
+pub fn get_entry_streaks(state: State<'_, AppState>) -> Result<EntryStreaks, String> {
+    let conn = state.db.lock().map_err(|e| e.to_string())?;
+    get_entry_streaks_in_conn(&conn, Local::now().date_naive())
+}
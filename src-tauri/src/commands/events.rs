@@ -0,0 +1,108 @@
+use serde::Serialize;
+use std::collections::{BTreeSet, HashMap};
+use std::sync::Mutex;
+use tauri::{AppHandle, Emitter, EventTarget, Manager, WebviewWindow};
+
+/// Every event the backend emits. Windows subscribe to a subset of these names.
+pub(crate) const EVENT_TOPICS: &[&str] = &[
+    "achievements-unlocked",
+    "appearance-changed",
+    "automation-rules-applied",
+    "break-reminder",
+    "capture-received",
+    "countdowns-changed",
+    "digest-ready",
+    "goal-checkin-due",
+    "habits-changed",
+    "integrity-violations",
+    "journal-reminder",
+    "linked-items-changed",
+    "migration-progress",
+    "presence-changed",
+    "shutdown-checklist",
+    "streaks-at-risk",
+    "task-due-reminder",
+    "tasks-rolled-over",
+    "timers-changed",
+    "update-available",
+];
+
+/// Topics each window label asked for. A window that never subscribed receives
+/// every event, so a window still loading its listeners misses nothing.
+#[derive(Default)]
+pub struct EventSubscriptions(Mutex<HashMap<String, BTreeSet<String>>>);
+
+impl EventSubscriptions {
+    /// Replaces the topics of `label`; unknown topics are rejected.
+    pub(crate) fn subscribe(&self, label: &str, topics: &[String]) -> Result<Vec<String>, String> {
+        let mut wanted = BTreeSet::new();
+        for topic in topics {
+            let topic = topic.trim();
+            if !EVENT_TOPICS.contains(&topic) {
+                return Err(format!("Unknown event topic: {}", topic));
+            }
+            wanted.insert(topic.to_string());
+        }
+
+        let subscribed = wanted.iter().cloned().collect();
+        self.0
+            .lock()
+            .map_err(|e| e.to_string())?
+            .insert(label.to_string(), wanted);
+        Ok(subscribed)
+    }
+
+    /// Forgets `label`, which then receives every event again.
+    pub(crate) fn unsubscribe(&self, label: &str) -> Result<(), String> {
+        self.0.lock().map_err(|e| e.to_string())?.remove(label);
+        Ok(())
+    }
+
+    pub(crate) fn wants(&self, label: &str, topic: &str) -> bool {
+        match self.0.lock() {
+            Ok(subscriptions) => subscriptions
+                .get(label)
+                .is_none_or(|topics| topics.contains(topic)),
+            // Delivering too much beats dropping events.
+            Err(_) => true,
+        }
+    }
+}
+
+/// Emits `topic` to the windows subscribed to it. Listeners on the Rust side and
+/// frontend listeners not bound to a window (`listen` from `@tauri-apps/api/event`)
+/// still receive every event; the frontend's `listenTopic` binds to its window.
+pub(crate) fn emit_event<S: Serialize + Clone>(
+    app: &AppHandle,
+    topic: &str,
+    payload: S,
+) -> tauri::Result<()> {
+    let Some(subscriptions) = app.try_state::<EventSubscriptions>() else {
+        return app.emit(topic, payload);
+    };
+    app.emit_filter(topic, payload, |target| match target {
+        EventTarget::Window { label }
+        | EventTarget::Webview { label }
+        | EventTarget::WebviewWindow { label }
+        | EventTarget::AnyLabel { label } => subscriptions.wants(label, topic),
+        _ => true,
+    })
+}
+
+/// Called by each window with every topic it listens to; replaces what it asked for before.
+#[tauri::command]
+pub fn subscribe_events(
+    topics: Vec<String>,
+    window: WebviewWindow,
+    subscriptions: tauri::State<'_, EventSubscriptions>,
+) -> Result<Vec<String>, String> {
+    subscriptions.subscribe(window.label(), &topics)
+}
+
+#[tauri::command]
+pub fn unsubscribe_events(
+    window: WebviewWindow,
+    subscriptions: tauri::State<'_, EventSubscriptions>,
+) -> Result<(), String> {
+    subscriptions.unsubscribe(window.label())
+}
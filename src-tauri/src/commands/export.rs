@@ -0,0 +1,500 @@
+use crate::models::{Entry, FilteredExportSummary, Page, Task};
+use chrono::Utc;
+use rusqlite::{params, Connection, OptionalExtension};
+use serde::Deserialize;
+use serde_json::json;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use tauri::State;
+
+use super::encryption::{JournalCipher, JournalContent};
+use super::entries::get_entries_in_conn;
+use super::markdown::entry_markdown_in_conn;
+use super::mirror::{page_file_name, page_markdown_in_conn};
+use super::pages::get_pages_in_conn;
+use super::tasks::{task_from_row, TASK_COLUMNS};
+use super::AppState;
+
+/// Limits an export to content carrying `tag` and/or belonging to `project_id`; both
+/// must match when both are set. Pages have no project, so a project filter keeps the
+/// pages tagged with the project's name.
+#[derive(Debug, Default, Deserialize)]
+pub struct ExportFilter {
+    pub tag: Option<String>,
+    pub project_id: Option<i64>,
+}
+
+/// `markdown` writes a folder of files, `json` one document and `csv` one table per kind.
+#[derive(Debug, Clone, Copy, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum ExportFormat {
+    Markdown,
+    Json,
+    Csv,
+}
+
+/// Tags of every entry, page or task, keyed by its id.
+fn tags_by_owner(conn: &Connection, sql: &str) -> Result<HashMap<i64, Vec<String>>, String> {
+    let mut stmt = conn.prepare(sql).map_err(|e| e.to_string())?;
+    let tags_iter = stmt
+        .query_map([], |row| {
+            Ok((row.get::<_, i64>(0)?, row.get::<_, String>(1)?))
+        })
+        .map_err(|e| e.to_string())?;
+
+    let mut tags: HashMap<i64, Vec<String>> = HashMap::new();
+    for tag in tags_iter {
+        let (owner_id, tag) = tag.map_err(|e| e.to_string())?;
+        tags.entry(owner_id).or_default().push(tag);
+    }
+
+    Ok(tags)
+}
+
+fn has_tag(tags: &HashMap<i64, Vec<String>>, id: i64, tag: &str) -> bool {
+    tags.get(&id).is_some_and(|tags| {
+        tags.iter()
+            .any(|candidate| candidate.eq_ignore_ascii_case(tag))
+    })
+}
+
+/// Tags of the content an export covers; each list is sorted case-insensitively.
+struct ExportTags {
+    entries: HashMap<i64, Vec<String>>,
+    pages: HashMap<i64, Vec<String>>,
+    tasks: HashMap<i64, Vec<String>>,
+}
+
+impl ExportTags {
+    fn load(conn: &Connection) -> Result<Self, String> {
+        Ok(Self {
+            entries: tags_by_owner(
+                conn,
+                "SELECT entry_id, tag FROM entry_tags ORDER BY tag COLLATE NOCASE",
+            )?,
+            pages: tags_by_owner(
+                conn,
+                "SELECT page_id, tag FROM page_tags ORDER BY tag COLLATE NOCASE",
+            )?,
+            tasks: tags_by_owner(
+                conn,
+                "SELECT task_id, tag FROM task_tags ORDER BY tag COLLATE NOCASE",
+            )?,
+        })
+    }
+
+    fn entry(&self, id: i64) -> &[String] {
+        self.entries.get(&id).map(Vec::as_slice).unwrap_or_default()
+    }
+
+    fn page(&self, id: i64) -> &[String] {
+        self.pages.get(&id).map(Vec::as_slice).unwrap_or_default()
+    }
+
+    fn task(&self, id: i64) -> &[String] {
+        self.tasks.get(&id).map(Vec::as_slice).unwrap_or_default()
+    }
+}
+
+impl ExportFilter {
+    fn tag(&self) -> Option<&str> {
+        self.tag
+            .as_deref()
+            .map(|tag| tag.trim().trim_start_matches('#').trim())
+            .filter(|tag| !tag.is_empty())
+    }
+
+    fn project_name(&self, conn: &Connection) -> Result<Option<String>, String> {
+        let Some(project_id) = self.project_id else {
+            return Ok(None);
+        };
+        conn.query_row(
+            "SELECT name FROM projects WHERE id = ?1",
+            params![project_id],
+            |row| row.get::<_, String>(0),
+        )
+        .optional()
+        .map_err(|e| e.to_string())?
+        .map(Some)
+        .ok_or_else(|| format!("Project #{} not found", project_id))
+    }
+
+    fn retain_entries(&self, tags: &ExportTags, entries: &mut Vec<Entry>) {
+        entries.retain(|entry| {
+            self.tag()
+                .is_none_or(|tag| has_tag(&tags.entries, entry.id, tag))
+                && self
+                    .project_id
+                    .is_none_or(|project_id| entry.project_id == Some(project_id))
+        });
+    }
+
+    fn retain_pages(&self, tags: &ExportTags, project_name: Option<&str>, pages: &mut Vec<Page>) {
+        pages.retain(|page| {
+            self.tag()
+                .is_none_or(|tag| has_tag(&tags.pages, page.id, tag))
+                && project_name.is_none_or(|name| has_tag(&tags.pages, page.id, name.trim()))
+        });
+    }
+
+    fn retain_tasks(&self, tags: &ExportTags, tasks: &mut Vec<Task>) {
+        tasks.retain(|task| {
+            self.tag()
+                .is_none_or(|tag| has_tag(&tags.tasks, task.id, tag))
+                && self
+                    .project_id
+                    .is_none_or(|project_id| task.project_id == Some(project_id))
+        });
+    }
+}
+
+/// Entries, pages and tasks (outside the review queue) matching `filter`, decrypted.
+struct FilteredContent {
+    project_name: Option<String>,
+    entries: Vec<Entry>,
+    pages: Vec<Page>,
+    tasks: Vec<Task>,
+    tags: ExportTags,
+    project_names: HashMap<i64, String>,
+}
+
+fn filtered_content_in_conn(
+    conn: &Connection,
+    filter: &ExportFilter,
+    cipher: &JournalCipher,
+) -> Result<FilteredContent, String> {
+    let project_name = filter.project_name(conn)?;
+    let tags = ExportTags::load(conn)?;
+
+    let mut entries = get_entries_in_conn(conn, None, None)?;
+    filter.retain_entries(&tags, &mut entries);
+    entries.reverse();
+    let mut pages = get_pages_in_conn(conn)?;
+    filter.retain_pages(&tags, project_name.as_deref(), &mut pages);
+    pages.sort_by_key(|page| page.id);
+
+    let mut stmt = conn
+        .prepare(&format!(
+            "SELECT {} FROM tasks
+             WHERE id NOT IN (SELECT task_id FROM review_queue)
+             ORDER BY id ASC",
+            TASK_COLUMNS
+        ))
+        .map_err(|e| e.to_string())?;
+    let tasks_iter = stmt
+        .query_map([], task_from_row)
+        .map_err(|e| e.to_string())?;
+    let mut tasks = Vec::new();
+    for task in tasks_iter {
+        tasks.push(task.map_err(|e| e.to_string())?);
+    }
+    filter.retain_tasks(&tags, &mut tasks);
+
+    let mut project_names = HashMap::new();
+    let mut stmt = conn
+        .prepare("SELECT id, name FROM projects")
+        .map_err(|e| e.to_string())?;
+    let projects_iter = stmt
+        .query_map([], |row| {
+            Ok((row.get::<_, i64>(0)?, row.get::<_, String>(1)?))
+        })
+        .map_err(|e| e.to_string())?;
+    for project in projects_iter {
+        let (id, name) = project.map_err(|e| e.to_string())?;
+        project_names.insert(id, name);
+    }
+
+    Ok(FilteredContent {
+        project_name,
+        entries: entries.decrypt_with(cipher)?,
+        pages: pages.decrypt_with(cipher)?,
+        tasks,
+        tags,
+        project_names,
+    })
+}
+
+/// RFC 4180 field: quoted when it holds a comma, quote or line break.
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+fn csv_table(header: &[&str], rows: Vec<Vec<String>>) -> String {
+    let mut csv = header.join(",");
+    csv.push_str("\r\n");
+    for row in rows {
+        let fields: Vec<String> = row.iter().map(|value| csv_field(value)).collect();
+        csv.push_str(&fields.join(","));
+        csv.push_str("\r\n");
+    }
+    csv
+}
+
+fn task_markdown(content: &FilteredContent) -> String {
+    let mut markdown = String::from("# Tasks\n\n");
+    if content.tasks.is_empty() {
+        markdown.push_str("_No tasks._\n");
+    }
+    for task in &content.tasks {
+        let mut details = vec![task.priority.clone()];
+        if let Some(project) = task
+            .project_id
+            .and_then(|id| content.project_names.get(&id))
+        {
+            details.insert(0, project.clone());
+        }
+        if let Some(due) = &task.due_date {
+            details.push(format!("due {}", due));
+        }
+        markdown.push_str(&format!(
+            "- [{}] {} ({})",
+            if task.status == "done" { "x" } else { " " },
+            task.title.trim(),
+            details.join(", ")
+        ));
+        for tag in content.tags.task(task.id) {
+            markdown.push_str(&format!(" #{}", tag.replace(' ', "-")));
+        }
+        markdown.push('\n');
+        if !task.description.trim().is_empty() {
+            markdown.push_str(&format!(
+                "  {}\n",
+                task.description
+                    .trim()
+                    .lines()
+                    .collect::<Vec<_>>()
+                    .join("\n  ")
+            ));
+        }
+    }
+    markdown
+}
+
+fn write_export_file(path: &Path, contents: &str) -> Result<(), String> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create {}: {}", parent.display(), e))?;
+    }
+    fs::write(path, contents).map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+}
+
+/// Writes the entries, pages and tasks matching `filter` into `dir`:
+/// `journal/<date>.md`, `pages/<id>-<title>.md` and `tasks.md` for Markdown,
+/// `dev-journal-export.json` for JSON and `entries.csv`, `pages.csv` and `tasks.csv`
+/// for CSV.
+pub(crate) fn export_filtered_in_conn(
+    conn: &Connection,
+    dir: &Path,
+    format: ExportFormat,
+    filter: &ExportFilter,
+    cipher: &JournalCipher,
+) -> Result<FilteredExportSummary, String> {
+    let content = filtered_content_in_conn(conn, filter, cipher)?;
+    let project_name = |project_id: Option<i64>| {
+        project_id
+            .and_then(|id| content.project_names.get(&id))
+            .cloned()
+            .unwrap_or_default()
+    };
+    let mut files: Vec<(PathBuf, String)> = Vec::new();
+
+    match format {
+        ExportFormat::Markdown => {
+            for entry in &content.entries {
+                files.push((
+                    dir.join("journal").join(format!("{}.md", entry.date)),
+                    entry_markdown_in_conn(conn, entry, cipher)?,
+                ));
+            }
+            for page in &content.pages {
+                files.push((
+                    dir.join("pages").join(page_file_name(page)),
+                    page_markdown_in_conn(conn, page)?,
+                ));
+            }
+            files.push((dir.join("tasks.md"), task_markdown(&content)));
+        }
+        ExportFormat::Json => {
+            let entries: Vec<serde_json::Value> = content
+                .entries
+                .iter()
+                .map(|entry| {
+                    let mut value = json!(entry);
+                    value["tags"] = json!(content.tags.entry(entry.id));
+                    value
+                })
+                .collect();
+            let pages: Vec<serde_json::Value> = content
+                .pages
+                .iter()
+                .map(|page| {
+                    let mut value = json!(page);
+                    value["tags"] = json!(content.tags.page(page.id));
+                    value
+                })
+                .collect();
+            let tasks: Vec<serde_json::Value> = content
+                .tasks
+                .iter()
+                .map(|task| {
+                    let mut value = json!(task);
+                    value["tags"] = json!(content.tags.task(task.id));
+                    value["project"] = json!(task
+                        .project_id
+                        .and_then(|id| content.project_names.get(&id)));
+                    value
+                })
+                .collect();
+            let document = json!({
+                "exported_at": Utc::now().to_rfc3339(),
+                "filter": {
+                    "tag": filter.tag(),
+                    "project": content.project_name,
+                },
+                "entries": entries,
+                "pages": pages,
+                "tasks": tasks,
+            });
+            files.push((
+                dir.join("dev-journal-export.json"),
+                serde_json::to_string_pretty(&document).map_err(|e| e.to_string())?,
+            ));
+        }
+        ExportFormat::Csv => {
+            let entries = content
+                .entries
+                .iter()
+                .map(|entry| {
+                    vec![
+                        entry.date.clone(),
+                        project_name(entry.project_id),
+                        content.tags.entry(entry.id).join("; "),
+                        entry.yesterday.clone(),
+                        entry.today.clone(),
+                        entry.blockers.clone(),
+                        entry.notes.clone(),
+                        entry.created_at.clone(),
+                    ]
+                })
+                .collect();
+            files.push((
+                dir.join("entries.csv"),
+                csv_table(
+                    &[
+                        "date",
+                        "project",
+                        "tags",
+                        "yesterday",
+                        "today",
+                        "blockers",
+                        "notes",
+                        "created_at",
+                    ],
+                    entries,
+                ),
+            ));
+            let pages = content
+                .pages
+                .iter()
+                .map(|page| {
+                    vec![
+                        page.id.to_string(),
+                        page.title.clone(),
+                        page.parent_id.map(|id| id.to_string()).unwrap_or_default(),
+                        content.tags.page(page.id).join("; "),
+                        page.content.clone(),
+                        page.created_at.clone(),
+                        page.updated_at.clone(),
+                    ]
+                })
+                .collect();
+            files.push((
+                dir.join("pages.csv"),
+                csv_table(
+                    &[
+                        "id",
+                        "title",
+                        "parent_id",
+                        "tags",
+                        "content",
+                        "created_at",
+                        "updated_at",
+                    ],
+                    pages,
+                ),
+            ));
+            let tasks = content
+                .tasks
+                .iter()
+                .map(|task| {
+                    vec![
+                        task.id.to_string(),
+                        task.title.clone(),
+                        task.status.clone(),
+                        task.priority.clone(),
+                        project_name(task.project_id),
+                        task.due_date.clone().unwrap_or_default(),
+                        content.tags.task(task.id).join("; "),
+                        task.description.clone(),
+                        task.completed_at.clone().unwrap_or_default(),
+                        task.created_at.clone(),
+                    ]
+                })
+                .collect();
+            files.push((
+                dir.join("tasks.csv"),
+                csv_table(
+                    &[
+                        "id",
+                        "title",
+                        "status",
+                        "priority",
+                        "project",
+                        "due_date",
+                        "tags",
+                        "description",
+                        "completed_at",
+                        "created_at",
+                    ],
+                    tasks,
+                ),
+            ));
+        }
+    }
+
+    for (path, contents) in &files {
+        write_export_file(path, contents)?;
+    }
+
+    Ok(FilteredExportSummary {
+        directory: dir.display().to_string(),
+        entries_exported: content.entries.len() as i64,
+        pages_exported: content.pages.len() as i64,
+        tasks_exported: content.tasks.len() as i64,
+        files_written: files.len() as i64,
+    })
+}
+
+/// Exports only the entries, pages and tasks matching `filter`, e.g. everything
+/// tagged `project-x` when handing a project over.
+#[tauri::command]
+pub fn export_filtered(
+    dir: String,
+    format: ExportFormat,
+    filter: ExportFilter,
+    state: State<'_, AppState>,
+) -> Result<FilteredExportSummary, String> {
+    let dir = dir.trim();
+    if dir.is_empty() {
+        return Err("Export folder is required".to_string());
+    }
+
+    let cipher = state.journal_cipher()?;
+    let conn = state.db.lock().map_err(|e| e.to_string())?;
+    export_filtered_in_conn(&conn, &PathBuf::from(dir), format, &filter, &cipher)
+}
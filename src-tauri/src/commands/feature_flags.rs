@@ -0,0 +1,113 @@
+use crate::models::FeatureFlag;
+use chrono::Utc;
+use rusqlite::{params, Connection, OptionalExtension};
+use tauri::State;
+
+use super::AppState;
+
+/// Experimental subsystems that ship disabled: key, description, default. Each one
+/// must check `require_feature_in_conn` before doing any work, so turning a flag off
+/// takes effect on the next call without a rebuild.
+pub(crate) const FEATURE_FLAGS: &[(&str, &str, bool)] = &[
+    ("sync", "Synchronize the journal between devices", false),
+    (
+        "llm",
+        "Summaries and suggestions from a language model",
+        false,
+    ),
+    (
+        "rest_api",
+        "Token-authenticated REST API on localhost",
+        false,
+    ),
+];
+
+fn known_flag(key: &str) -> Result<&'static (&'static str, &'static str, bool), String> {
+    FEATURE_FLAGS
+        .iter()
+        .find(|(known_key, _, _)| *known_key == key)
+        .ok_or_else(|| format!("Unknown feature flag: {}", key))
+}
+
+pub(crate) fn is_feature_enabled_in_conn(conn: &Connection, key: &str) -> Result<bool, String> {
+    let (_, _, default_enabled) = known_flag(key)?;
+    let stored: Option<bool> = conn
+        .query_row(
+            "SELECT enabled FROM feature_flags WHERE key = ?1",
+            params![key],
+            |row| row.get(0),
+        )
+        .optional()
+        .map_err(|e| e.to_string())?;
+
+    Ok(stored.unwrap_or(*default_enabled))
+}
+
+/// Guard for the entry points of a flagged subsystem.
+pub(crate) fn require_feature_in_conn(conn: &Connection, key: &str) -> Result<(), String> {
+    if is_feature_enabled_in_conn(conn, key)? {
+        Ok(())
+    } else {
+        Err(format!("The {} feature is disabled", key))
+    }
+}
+
+pub(crate) fn get_flags_in_conn(conn: &Connection) -> Result<Vec<FeatureFlag>, String> {
+    let mut stmt = conn
+        .prepare("SELECT enabled, updated_at FROM feature_flags WHERE key = ?1")
+        .map_err(|e| e.to_string())?;
+
+    FEATURE_FLAGS
+        .iter()
+        .map(|(key, description, default_enabled)| {
+            let stored: Option<(bool, String)> = stmt
+                .query_row(params![key], |row| Ok((row.get(0)?, row.get(1)?)))
+                .optional()
+                .map_err(|e| e.to_string())?;
+            Ok(FeatureFlag {
+                key: key.to_string(),
+                description: description.to_string(),
+                enabled: stored
+                    .as_ref()
+                    .map_or(*default_enabled, |(enabled, _)| *enabled),
+                default_enabled: *default_enabled,
+                updated_at: stored.map(|(_, updated_at)| updated_at),
+            })
+        })
+        .collect()
+}
+
+pub(crate) fn set_flag_in_conn(
+    conn: &Connection,
+    key: &str,
+    enabled: bool,
+) -> Result<Vec<FeatureFlag>, String> {
+    let (key, _, _) = known_flag(key.trim())?;
+    conn.execute(
+        "INSERT INTO feature_flags (key, enabled, updated_at)
+         VALUES (?1, ?2, ?3)
+         ON CONFLICT(key) DO UPDATE SET enabled = excluded.enabled, updated_at = excluded.updated_at",
+        params![key, enabled, Utc::now().to_rfc3339()],
+    )
+    .map_err(|e| e.to_string())?;
+
+    get_flags_in_conn(conn)
+}
+
+/// Every known flag with its current state; `updated_at` is `None` while a flag still
+/// uses its default.
+#[tauri::command]
+pub fn get_flags(state: State<'_, AppState>) -> Result<Vec<FeatureFlag>, String> {
+    let conn = state.db.lock().map_err(|e| e.to_string())?;
+    get_flags_in_conn(&conn)
+}
+
+#[tauri::command]
+pub fn set_flag(
+    key: String,
+    enabled: bool,
+    state: State<'_, AppState>,
+) -> Result<Vec<FeatureFlag>, String> {
+    let conn = state.db.lock().map_err(|e| e.to_string())?;
+    set_flag_in_conn(&conn, &key, enabled)
+}
@@ -0,0 +1,216 @@
+use crate::models::{PomodoroDayCount, PomodoroSession, PomodoroStatus};
+use chrono::{Duration, Local, NaiveDate, Utc};
+use rusqlite::{params, Connection};
+use std::collections::HashMap;
+use tauri::{AppHandle, State};
+
+use super::settings::read_i64_setting;
+use super::validation::task_exists;
+use super::AppState;
+
+pub(crate) fn normalize_pomodoro_kind(kind: Option<String>) -> String {
+    match kind.as_deref() {
+        Some("focus") | Some("short_break") | Some("long_break") => {
+            kind.unwrap_or_else(|| "focus".to_string())
+        }
+        _ => "focus".to_string(),
+    }
+}
+
+fn local_today() -> String {
+    Local::now().date_naive().format("%Y-%m-%d").to_string()
+}
+
+pub(crate) fn pomodoro_status_in_conn(
+    conn: &Connection,
+    date: &str,
+) -> Result<PomodoroStatus, String> {
+    let long_break_every = read_i64_setting(conn, "pomodoro_long_break_every")?.clamp(2, 12);
+    let long_break_minutes = read_i64_setting(conn, "pomodoro_long_break_minutes")?.clamp(5, 60);
+    let daily_goal = read_i64_setting(conn, "pomodoro_daily_goal")?.clamp(0, 48);
+
+    let (completed_today, focus_minutes_today): (i64, i64) = conn
+        .query_row(
+            "SELECT COUNT(*), COALESCE(SUM(duration_minutes), 0)
+             FROM pomodoro_sessions
+             WHERE date = ?1 AND kind = 'focus'",
+            params![date],
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        )
+        .map_err(|e| e.to_string())?;
+
+    // Cycles restart after each long break taken today.
+    let focus_since_long_break: i64 = conn
+        .query_row(
+            "SELECT COUNT(*)
+             FROM pomodoro_sessions
+             WHERE date = ?1
+               AND kind = 'focus'
+               AND id > COALESCE(
+                    (SELECT MAX(id) FROM pomodoro_sessions WHERE date = ?1 AND kind = 'long_break'),
+                    0
+               )",
+            params![date],
+            |row| row.get(0),
+        )
+        .map_err(|e| e.to_string())?;
+
+    let next_break_kind =
+        if focus_since_long_break > 0 && focus_since_long_break % long_break_every == 0 {
+            "long_break"
+        } else {
+            "short_break"
+        };
+    let goal_progress = if daily_goal > 0 {
+        (completed_today * 100 / daily_goal).min(100)
+    } else {
+        0
+    };
+
+    Ok(PomodoroStatus {
+        date: date.to_string(),
+        completed_today,
+        focus_minutes_today,
+        daily_goal,
+        goal_progress,
+        cycle_position: focus_since_long_break % long_break_every,
+        long_break_every,
+        next_break_kind: next_break_kind.to_string(),
+        long_break_minutes,
+    })
+}
+
+/// Per-day focus session counts for the `days` days ending at `end_date`, zero-filled.
+pub(crate) fn pomodoro_heatmap_in_conn(
+    conn: &Connection,
+    end_date: NaiveDate,
+    days: i64,
+) -> Result<Vec<PomodoroDayCount>, String> {
+    let days = days.clamp(1, 366);
+    let start_date = end_date - Duration::days(days - 1);
+    let mut stmt = conn
+        .prepare(
+            "SELECT date, COUNT(*), COALESCE(SUM(duration_minutes), 0)
+             FROM pomodoro_sessions
+             WHERE kind = 'focus' AND date BETWEEN ?1 AND ?2
+             GROUP BY date",
+        )
+        .map_err(|e| e.to_string())?;
+
+    let rows = stmt
+        .query_map(
+            params![
+                start_date.format("%Y-%m-%d").to_string(),
+                end_date.format("%Y-%m-%d").to_string()
+            ],
+            |row| Ok((row.get::<_, String>(0)?, (row.get(1)?, row.get(2)?))),
+        )
+        .map_err(|e| e.to_string())?;
+    let counts = rows
+        .collect::<Result<HashMap<String, (i64, i64)>, _>>()
+        .map_err(|e| e.to_string())?;
+
+    Ok((0..days)
+        .map(|offset| {
+            let date = (start_date + Duration::days(offset))
+                .format("%Y-%m-%d")
+                .to_string();
+            let (count, focus_minutes) = counts.get(&date).copied().unwrap_or((0, 0));
+            PomodoroDayCount {
+                date,
+                count,
+                focus_minutes,
+            }
+        })
+        .collect())
+}
+
+#[tauri::command]
+pub fn record_pomodoro_session(
+    kind: String,
+    duration_minutes: i64,
+    task_id: Option<i64>,
+    app: AppHandle,
+    state: State<'_, AppState>,
+) -> Result<PomodoroStatus, String> {
+    let conn = state.db.lock().map_err(|e| e.to_string())?;
+    let kind = normalize_pomodoro_kind(Some(kind));
+    let duration_minutes = duration_minutes.clamp(1, 240);
+    let task_id = match task_id {
+        Some(task_id) if task_exists(&conn, task_id)? => Some(task_id),
+        _ => None,
+    };
+    let date = local_today();
+
+    conn.execute(
+        "INSERT INTO pomodoro_sessions (kind, task_id, duration_minutes, date, completed_at)
+         VALUES (?1, ?2, ?3, ?4, ?5)",
+        params![
+            kind,
+            task_id,
+            duration_minutes,
+            date,
+            Utc::now().to_rfc3339()
+        ],
+    )
+    .map_err(|e| e.to_string())?;
+
+    let status = pomodoro_status_in_conn(&conn, &date)?;
+    drop(conn);
+    if kind == "focus" {
+        super::achievements::evaluate_achievements_for_app(&app);
+    }
+    Ok(status)
+}
+
+#[tauri::command]
+pub fn get_pomodoro_status(state: State<'_, AppState>) -> Result<PomodoroStatus, String> {
+    let conn = state.db.lock().map_err(|e| e.to_string())?;
+    pomodoro_status_in_conn(&conn, &local_today())
+}
+
+#[tauri::command]
+pub fn get_pomodoro_sessions(
+    date: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<Vec<PomodoroSession>, String> {
+    let conn = state.db.lock().map_err(|e| e.to_string())?;
+    let date = date.unwrap_or_else(local_today);
+    let mut stmt = conn
+        .prepare(
+            "SELECT id, kind, task_id, duration_minutes, date, completed_at
+             FROM pomodoro_sessions
+             WHERE date = ?1
+             ORDER BY id ASC",
+        )
+        .map_err(|e| e.to_string())?;
+
+    let sessions_iter = stmt
+        .query_map(params![date], |row| {
+            Ok(PomodoroSession {
+                id: row.get(0)?,
+                kind: row.get(1)?,
+                task_id: row.get(2)?,
+                duration_minutes: row.get(3)?,
+                date: row.get(4)?,
+                completed_at: row.get(5)?,
+            })
+        })
+        .map_err(|e| e.to_string())?;
+
+    let mut sessions = Vec::new();
+    for session in sessions_iter {
+        sessions.push(session.map_err(|e| e.to_string())?);
+    }
+
+    Ok(sessions)
+}
+
+#[tauri::command]
+pub fn get_pomodoro_heatmap(
+    days: Option<i64>,
+    state: State<'_, AppState>,
+) -> Result<Vec<PomodoroDayCount>, String> {
+    let conn = state.db.lock().map_err(|e| e.to_string())?;
+    pomodoro_heatmap_in_conn(&conn, Local::now().date_naive(), days.unwrap_or(90))
+}
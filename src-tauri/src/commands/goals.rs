@@ -0,0 +1,284 @@
+use crate::models::{GoalCheckin, GoalReflection};
+use chrono::{DateTime, Datelike, Duration, NaiveDateTime, NaiveTime, Utc, Weekday};
+use rusqlite::{params, Connection};
+use std::collections::HashSet;
+use tauri::State;
+
+use super::settings::read_setting;
+use super::validation::normalize_progress;
+use super::AppState;
+
+/// Goals without an update for this many days are due for a check-in.
+const GOAL_CHECKIN_STALE_DAYS: i64 = 7;
+
+const REFLECTION_COLUMNS: &str = "id, goal_id, goal_title, what_worked, what_didnt, created_at";
+
+/// Words too common to say anything about whether two goals are alike.
+const SIMILARITY_STOP_WORDS: &[&str] = &[
+    "the", "and", "for", "with", "from", "into", "that", "this", "our", "my", "your", "get",
+    "make", "more", "less", "new", "all",
+];
+
+fn reflection_from_row(row: &rusqlite::Row<'_>) -> rusqlite::Result<GoalReflection> {
+    Ok(GoalReflection {
+        id: row.get(0)?,
+        goal_id: row.get(1)?,
+        goal_title: row.get(2)?,
+        what_worked: row.get(3)?,
+        what_didnt: row.get(4)?,
+        created_at: row.get(5)?,
+    })
+}
+
+fn title_keywords(title: &str) -> HashSet<String> {
+    title
+        .split(|c: char| !c.is_alphanumeric())
+        .map(str::to_lowercase)
+        .filter(|word| word.chars().count() >= 3 && !SIMILARITY_STOP_WORDS.contains(&word.as_str()))
+        .collect()
+}
+
+/// Share of keywords two goal titles have in common (Jaccard index, 0.0..=1.0).
+pub(crate) fn goal_title_similarity(a: &str, b: &str) -> f64 {
+    let (a, b) = (title_keywords(a), title_keywords(b));
+    let union = a.union(&b).count();
+    if union == 0 {
+        return 0.0;
+    }
+
+    a.intersection(&b).count() as f64 / union as f64
+}
+
+pub(crate) fn save_goal_reflection_in_conn(
+    conn: &Connection,
+    goal_id: i64,
+    what_worked: &str,
+    what_didnt: &str,
+) -> Result<GoalReflection, String> {
+    let (what_worked, what_didnt) = (what_worked.trim(), what_didnt.trim());
+    if what_worked.is_empty() && what_didnt.is_empty() {
+        return Err("Reflection is empty".to_string());
+    }
+
+    let goal_title: String = conn
+        .query_row(
+            "SELECT title FROM goals WHERE id = ?1",
+            params![goal_id],
+            |row| row.get(0),
+        )
+        .map_err(|_| format!("Goal {} not found", goal_id))?;
+
+    conn.execute(
+        "INSERT INTO goal_reflections (goal_id, goal_title, what_worked, what_didnt, created_at)
+         VALUES (?1, ?2, ?3, ?4, ?5)",
+        params![
+            goal_id,
+            goal_title,
+            what_worked,
+            what_didnt,
+            Utc::now().to_rfc3339()
+        ],
+    )
+    .map_err(|e| e.to_string())?;
+
+    conn.query_row(
+        &format!(
+            "SELECT {} FROM goal_reflections WHERE id = ?1",
+            REFLECTION_COLUMNS
+        ),
+        params![conn.last_insert_rowid()],
+        reflection_from_row,
+    )
+    .map_err(|e| e.to_string())
+}
+
+pub(crate) fn get_goal_reflections_in_conn(
+    conn: &Connection,
+    goal_id: Option<i64>,
+) -> Result<Vec<GoalReflection>, String> {
+    let mut stmt = conn
+        .prepare(&format!(
+            "SELECT {} FROM goal_reflections
+             WHERE ?1 IS NULL OR goal_id = ?1
+             ORDER BY created_at DESC, id DESC",
+            REFLECTION_COLUMNS
+        ))
+        .map_err(|e| e.to_string())?;
+
+    let reflections_iter = stmt
+        .query_map(params![goal_id], reflection_from_row)
+        .map_err(|e| e.to_string())?;
+
+    let mut reflections = Vec::new();
+    for reflection in reflections_iter {
+        reflections.push(reflection.map_err(|e| e.to_string())?);
+    }
+
+    Ok(reflections)
+}
+
+/// Past reflections whose goal title shares keywords with `title`, most similar first.
+pub(crate) fn get_similar_goal_reflections_in_conn(
+    conn: &Connection,
+    title: &str,
+    limit: usize,
+) -> Result<Vec<GoalReflection>, String> {
+    let mut scored: Vec<(f64, GoalReflection)> = get_goal_reflections_in_conn(conn, None)?
+        .into_iter()
+        .map(|reflection| {
+            (
+                goal_title_similarity(title, &reflection.goal_title),
+                reflection,
+            )
+        })
+        .filter(|(score, _)| *score > 0.0)
+        .collect();
+    // Stable sort keeps newer reflections first among equal scores.
+    scored.sort_by(|a, b| b.0.total_cmp(&a.0));
+
+    Ok(scored
+        .into_iter()
+        .take(limit)
+        .map(|(_, reflection)| reflection)
+        .collect())
+}
+
+/// Active goals not updated for `GOAL_CHECKIN_STALE_DAYS` as of `now`, stalest
+/// first. Completing or editing a milestone counts as an update.
+pub(crate) fn goals_needing_checkin_in_conn(
+    conn: &Connection,
+    now: DateTime<Utc>,
+) -> Result<Vec<GoalCheckin>, String> {
+    let cutoff = (now - Duration::days(GOAL_CHECKIN_STALE_DAYS)).to_rfc3339();
+    let mut stmt = conn
+        .prepare(
+            "SELECT id, title, progress, last_updated_at FROM (
+                SELECT g.id, g.title, g.progress,
+                       MAX(g.updated_at, COALESCE(
+                           (SELECT MAX(m.updated_at) FROM goal_milestones m WHERE m.goal_id = g.id),
+                           ''
+                       )) AS last_updated_at
+                FROM goals g
+                WHERE g.status = 'active'
+             )
+             WHERE last_updated_at < ?1
+             ORDER BY last_updated_at ASC, id ASC",
+        )
+        .map_err(|e| e.to_string())?;
+    let goals_iter = stmt
+        .query_map(params![cutoff], |row| {
+            Ok((
+                row.get::<_, i64>(0)?,
+                row.get::<_, String>(1)?,
+                row.get::<_, i64>(2)?,
+                row.get::<_, String>(3)?,
+            ))
+        })
+        .map_err(|e| e.to_string())?;
+
+    let mut goals = Vec::new();
+    for goal in goals_iter {
+        let (goal_id, title, progress, last_updated_at) = goal.map_err(|e| e.to_string())?;
+        let days_since_update = DateTime::parse_from_rfc3339(&last_updated_at)
+            .map(|updated_at| (now - updated_at.with_timezone(&Utc)).num_days())
+            .unwrap_or(GOAL_CHECKIN_STALE_DAYS);
+        goals.push(GoalCheckin {
+            goal_id,
+            title,
+            progress,
+            last_updated_at,
+            days_since_update,
+        });
+    }
+
+    Ok(goals)
+}
+
+/// Goals to prompt about at local time `now`: empty unless it is the configured
+/// `goal_checkin_day` at or after `goal_checkin_time` (a blank time turns it off).
+pub(crate) fn goal_checkin_due_in_conn(
+    conn: &Connection,
+    now: NaiveDateTime,
+) -> Result<Vec<GoalCheckin>, String> {
+    let checkin_time = read_setting(conn, "goal_checkin_time")?;
+    let Ok(checkin_time) = NaiveTime::parse_from_str(checkin_time.trim(), "%H:%M") else {
+        return Ok(Vec::new());
+    };
+    let Ok(checkin_day) = read_setting(conn, "goal_checkin_day")?
+        .trim()
+        .parse::<Weekday>()
+    else {
+        return Ok(Vec::new());
+    };
+    if now.weekday() != checkin_day || now.time() < checkin_time {
+        return Ok(Vec::new());
+    }
+
+    goals_needing_checkin_in_conn(conn, Utc::now())
+}
+
+/// Records a progress update for a goal, which also resets its check-in clock.
+pub(crate) fn check_in_goal_in_conn(
+    conn: &Connection,
+    id: i64,
+    progress: i64,
+) -> Result<(), String> {
+    let changed = conn
+        .execute(
+            "UPDATE goals SET progress = ?1, updated_at = ?2 WHERE id = ?3",
+            params![
+                normalize_progress(Some(progress)),
+                Utc::now().to_rfc3339(),
+                id
+            ],
+        )
+        .map_err(|e| e.to_string())?;
+    if changed == 0 {
+        return Err("Goal not found".to_string());
+    }
+
+    Ok(())
+}
+
+#[tauri::command]
+pub fn save_goal_reflection(
+    goal_id: i64,
+    what_worked: String,
+    what_didnt: String,
+    state: State<'_, AppState>,
+) -> Result<GoalReflection, String> {
+    let conn = state.db.lock().map_err(|e| e.to_string())?;
+    save_goal_reflection_in_conn(&conn, goal_id, &what_worked, &what_didnt)
+}
+
+#[tauri::command]
+pub fn get_goal_reflections(
+    goal_id: Option<i64>,
+    state: State<'_, AppState>,
+) -> Result<Vec<GoalReflection>, String> {
+    let conn = state.db.lock().map_err(|e| e.to_string())?;
+    get_goal_reflections_in_conn(&conn, goal_id)
+}
+
+#[tauri::command]
+pub fn get_similar_goal_reflections(
+    title: String,
+    limit: Option<i64>,
+    state: State<'_, AppState>,
+) -> Result<Vec<GoalReflection>, String> {
+    let conn = state.db.lock().map_err(|e| e.to_string())?;
+    let limit = limit.unwrap_or(3).clamp(1, 20) as usize;
+    get_similar_goal_reflections_in_conn(&conn, &title, limit)
+}
+
+#[tauri::command]
+pub fn get_goals_needing_checkin(state: State<'_, AppState>) -> Result<Vec<GoalCheckin>, String> {
+    let conn = state.db.lock().map_err(|e| e.to_string())?;
+    goals_needing_checkin_in_conn(&conn, Utc::now())
+}
+
+#[tauri::command]
+pub fn check_in_goal(id: i64, progress: i64, state: State<'_, AppState>) -> Result<(), String> {
+    let conn = state.db.lock().map_err(|e| e.to_string())?;
+    check_in_goal_in_conn(&conn, id, progress)
+}
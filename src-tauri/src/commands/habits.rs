@@ -0,0 +1,111 @@
+use crate::models::StreakAtRisk;
+use chrono::{Duration, Local, NaiveDate, NaiveDateTime, NaiveTime};
+use rusqlite::{params, Connection};
+use std::collections::HashSet;
+use tauri::State;
+
+use super::settings::{read_i64_setting, read_setting};
+use super::AppState;
+
+/// Habits whose streak ended yesterday with at least `min_streak_days` days and
+/// that are not logged yet on `today`, longest streak first.
+pub(crate) fn streaks_at_risk_in_conn(
+    conn: &Connection,
+    today: NaiveDate,
+    min_streak_days: i64,
+) -> Result<Vec<StreakAtRisk>, String> {
+    let mut habits_stmt = conn
+        .prepare("SELECT id, title, color FROM habits ORDER BY id ASC")
+        .map_err(|e| e.to_string())?;
+    // Frozen days keep a streak alive without adding to it.
+    let mut logs_stmt = conn
+        .prepare(
+            "SELECT date, 1 FROM habit_logs WHERE habit_id = ?1
+             UNION ALL
+             SELECT date, 0 FROM streak_freezes WHERE habit_id = ?1",
+        )
+        .map_err(|e| e.to_string())?;
+
+    let habits_iter = habits_stmt
+        .query_map([], |row| {
+            Ok((
+                row.get::<_, i64>(0)?,
+                row.get::<_, String>(1)?,
+                row.get::<_, String>(2)?,
+            ))
+        })
+        .map_err(|e| e.to_string())?;
+
+    let mut at_risk = Vec::new();
+    for habit in habits_iter {
+        let (habit_id, title, color) = habit.map_err(|e| e.to_string())?;
+        let dates_iter = logs_stmt
+            .query_map(params![habit_id], |row| {
+                Ok((row.get::<_, String>(0)?, row.get::<_, bool>(1)?))
+            })
+            .map_err(|e| e.to_string())?;
+
+        let mut logged_dates = HashSet::new();
+        let mut frozen_dates = HashSet::new();
+        for date in dates_iter {
+            let (date, logged) = date.map_err(|e| e.to_string())?;
+            if let Ok(date) = NaiveDate::parse_from_str(&date, "%Y-%m-%d") {
+                if logged {
+                    logged_dates.insert(date);
+                } else {
+                    frozen_dates.insert(date);
+                }
+            }
+        }
+
+        if logged_dates.contains(&today) {
+            continue;
+        }
+
+        let mut cursor = today - Duration::days(1);
+        let mut streak_days = 0;
+        while logged_dates.contains(&cursor) || frozen_dates.contains(&cursor) {
+            if logged_dates.contains(&cursor) {
+                streak_days += 1;
+            }
+            cursor -= Duration::days(1);
+        }
+
+        if streak_days > 0 && streak_days >= min_streak_days {
+            at_risk.push(StreakAtRisk {
+                habit_id,
+                title,
+                color,
+                streak_days,
+            });
+        }
+    }
+
+    at_risk.sort_by_key(|habit| std::cmp::Reverse(habit.streak_days));
+    Ok(at_risk)
+}
+
+/// Streaks to warn about at `now`: empty before the configured evening time
+/// or when `streak_risk_time` is blank.
+pub(crate) fn streak_risk_reminders_due_in_conn(
+    conn: &Connection,
+    now: NaiveDateTime,
+) -> Result<Vec<StreakAtRisk>, String> {
+    let reminder_time = read_setting(conn, "streak_risk_time")?;
+    let Ok(reminder_time) = NaiveTime::parse_from_str(reminder_time.trim(), "%H:%M") else {
+        return Ok(Vec::new());
+    };
+    if now.time() < reminder_time {
+        return Ok(Vec::new());
+    }
+
+    let min_streak_days = read_i64_setting(conn, "streak_risk_min_days")?.max(1);
+    streaks_at_risk_in_conn(conn, now.date(), min_streak_days)
+}
+
+#[tauri::command]
+pub fn get_streaks_at_risk(state: State<'_, AppState>) -> Result<Vec<StreakAtRisk>, String> {
+    let conn = state.db.lock().map_err(|e| e.to_string())?;
+    let min_streak_days = read_i64_setting(&conn, "streak_risk_min_days")?.max(1);
+    streaks_at_risk_in_conn(&conn, Local::now().date_naive(), min_streak_days)
+}
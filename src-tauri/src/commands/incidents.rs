@@ -0,0 +1,390 @@
+use crate::models::{Incident, IncidentTimelineEvent};
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+use rusqlite::{params, Connection, OptionalExtension};
+use serde::Deserialize;
+use tauri::State;
+
+use super::validation::{normalize_optional_text, parse_datetime_utc, task_exists};
+use super::AppState;
+
+const INCIDENT_COLUMNS: &str =
+    "id, started_at, ended_at, severity, summary, resolution, created_at, updated_at";
+const INCIDENT_SEVERITIES: &[&str] = &["sev1", "sev2", "sev3", "sev4"];
+
+#[derive(Debug, Deserialize)]
+pub struct IncidentInput {
+    pub started_at: String,
+    pub ended_at: Option<String>,
+    pub severity: Option<String>,
+    pub summary: String,
+    #[serde(default)]
+    pub resolution: String,
+    #[serde(default)]
+    pub task_ids: Vec<i64>,
+    #[serde(default)]
+    pub page_ids: Vec<i64>,
+}
+
+/// Validated column values for an insert or update.
+struct IncidentFields {
+    started_at: String,
+    ended_at: Option<String>,
+    severity: String,
+    summary: String,
+    resolution: String,
+}
+
+fn normalize_incident_severity(severity: Option<String>) -> Result<String, String> {
+    let severity = severity
+        .map(|value| value.trim().to_lowercase())
+        .unwrap_or_else(|| "sev3".to_string());
+    if INCIDENT_SEVERITIES.contains(&severity.as_str()) {
+        Ok(severity)
+    } else {
+        Err(format!("Unknown incident severity: {}", severity))
+    }
+}
+
+fn normalize_incident_input(input: &IncidentInput) -> Result<IncidentFields, String> {
+    let summary = input.summary.trim();
+    if summary.is_empty() {
+        return Err("Incident summary is required".to_string());
+    }
+    let started_at = parse_datetime_utc(input.started_at.trim())?;
+    let ended_at = match normalize_optional_text(input.ended_at.clone()) {
+        Some(ended_at) => Some(parse_datetime_utc(&ended_at)?),
+        None => None,
+    };
+    if ended_at.is_some_and(|ended_at| ended_at < started_at) {
+        return Err("Incident end time must be after start time".to_string());
+    }
+
+    Ok(IncidentFields {
+        started_at: started_at.to_rfc3339(),
+        ended_at: ended_at.map(|ended_at| ended_at.to_rfc3339()),
+        severity: normalize_incident_severity(input.severity.clone())?,
+        summary: summary.to_string(),
+        resolution: input.resolution.trim().to_string(),
+    })
+}
+
+fn incident_from_row(row: &rusqlite::Row<'_>) -> rusqlite::Result<Incident> {
+    Ok(Incident {
+        id: row.get(0)?,
+        started_at: row.get(1)?,
+        ended_at: row.get(2)?,
+        severity: row.get(3)?,
+        summary: row.get(4)?,
+        resolution: row.get(5)?,
+        created_at: row.get(6)?,
+        updated_at: row.get(7)?,
+        task_ids: Vec::new(),
+        page_ids: Vec::new(),
+    })
+}
+
+fn linked_ids(conn: &Connection, sql: &str, incident_id: i64) -> Result<Vec<i64>, String> {
+    let mut stmt = conn.prepare(sql).map_err(|e| e.to_string())?;
+    let ids_iter = stmt
+        .query_map(params![incident_id], |row| row.get(0))
+        .map_err(|e| e.to_string())?;
+
+    let mut ids = Vec::new();
+    for id in ids_iter {
+        ids.push(id.map_err(|e| e.to_string())?);
+    }
+
+    Ok(ids)
+}
+
+fn with_links(conn: &Connection, mut incident: Incident) -> Result<Incident, String> {
+    incident.task_ids = linked_ids(
+        conn,
+        "SELECT task_id FROM incident_tasks WHERE incident_id = ?1 ORDER BY task_id",
+        incident.id,
+    )?;
+    incident.page_ids = linked_ids(
+        conn,
+        "SELECT page_id FROM incident_pages WHERE incident_id = ?1 ORDER BY page_id",
+        incident.id,
+    )?;
+    Ok(incident)
+}
+
+/// Replaces the linked tasks and pages; ids that no longer exist are skipped.
+fn save_links(
+    conn: &Connection,
+    incident_id: i64,
+    task_ids: &[i64],
+    page_ids: &[i64],
+) -> Result<(), String> {
+    conn.execute(
+        "DELETE FROM incident_tasks WHERE incident_id = ?1",
+        params![incident_id],
+    )
+    .map_err(|e| e.to_string())?;
+    conn.execute(
+        "DELETE FROM incident_pages WHERE incident_id = ?1",
+        params![incident_id],
+    )
+    .map_err(|e| e.to_string())?;
+
+    for task_id in task_ids {
+        if task_exists(conn, *task_id)? {
+            conn.execute(
+                "INSERT OR IGNORE INTO incident_tasks (incident_id, task_id) VALUES (?1, ?2)",
+                params![incident_id, task_id],
+            )
+            .map_err(|e| e.to_string())?;
+        }
+    }
+    for page_id in page_ids {
+        conn.execute(
+            "INSERT OR IGNORE INTO incident_pages (incident_id, page_id)
+             SELECT ?1, id FROM pages WHERE id = ?2",
+            params![incident_id, page_id],
+        )
+        .map_err(|e| e.to_string())?;
+    }
+
+    Ok(())
+}
+
+pub(crate) fn get_incident_in_conn(conn: &Connection, id: i64) -> Result<Option<Incident>, String> {
+    let incident = conn
+        .query_row(
+            &format!("SELECT {} FROM incidents WHERE id = ?1", INCIDENT_COLUMNS),
+            params![id],
+            incident_from_row,
+        )
+        .optional()
+        .map_err(|e| e.to_string())?;
+
+    incident
+        .map(|incident| with_links(conn, incident))
+        .transpose()
+}
+
+/// Ongoing incidents first, then the rest by start time, newest first.
+pub(crate) fn get_incidents_in_conn(conn: &Connection) -> Result<Vec<Incident>, String> {
+    let mut stmt = conn
+        .prepare(&format!(
+            "SELECT {} FROM incidents
+             ORDER BY ended_at IS NOT NULL, started_at DESC, id DESC",
+            INCIDENT_COLUMNS
+        ))
+        .map_err(|e| e.to_string())?;
+    let incidents_iter = stmt
+        .query_map([], incident_from_row)
+        .map_err(|e| e.to_string())?;
+
+    let mut incidents = Vec::new();
+    for incident in incidents_iter {
+        incidents.push(with_links(conn, incident.map_err(|e| e.to_string())?)?);
+    }
+
+    Ok(incidents)
+}
+
+pub(crate) fn create_incident_in_conn(
+    conn: &Connection,
+    input: &IncidentInput,
+) -> Result<Incident, String> {
+    let fields = normalize_incident_input(input)?;
+    let now = Utc::now().to_rfc3339();
+
+    conn.execute(
+        "INSERT INTO incidents (started_at, ended_at, severity, summary, resolution, created_at, updated_at)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?6)",
+        params![
+            fields.started_at,
+            fields.ended_at,
+            fields.severity,
+            fields.summary,
+            fields.resolution,
+            now
+        ],
+    )
+    .map_err(|e| e.to_string())?;
+    let id = conn.last_insert_rowid();
+    save_links(conn, id, &input.task_ids, &input.page_ids)?;
+
+    get_incident_in_conn(conn, id)?.ok_or_else(|| "Failed to load created incident".to_string())
+}
+
+pub(crate) fn update_incident_in_conn(
+    conn: &Connection,
+    id: i64,
+    input: &IncidentInput,
+) -> Result<Incident, String> {
+    let fields = normalize_incident_input(input)?;
+
+    let updated = conn
+        .execute(
+            "UPDATE incidents
+             SET started_at = ?1, ended_at = ?2, severity = ?3, summary = ?4, resolution = ?5, updated_at = ?6
+             WHERE id = ?7",
+            params![
+                fields.started_at,
+                fields.ended_at,
+                fields.severity,
+                fields.summary,
+                fields.resolution,
+                Utc::now().to_rfc3339(),
+                id
+            ],
+        )
+        .map_err(|e| e.to_string())?;
+    if updated == 0 {
+        return Err(format!("Incident #{} not found", id));
+    }
+    save_links(conn, id, &input.task_ids, &input.page_ids)?;
+
+    get_incident_in_conn(conn, id)?.ok_or_else(|| format!("Incident #{} not found", id))
+}
+
+/// Incidents overlapping `from..=to` (local `YYYY-MM-DD` bounds, compared by the
+/// date part of the stored timestamps), oldest first. Ongoing incidents overlap
+/// every range after their start.
+pub(crate) fn get_incidents_between_in_conn(
+    conn: &Connection,
+    from: &str,
+    to: &str,
+) -> Result<Vec<Incident>, String> {
+    let mut stmt = conn
+        .prepare(&format!(
+            "SELECT {} FROM incidents
+             WHERE substr(started_at, 1, 10) <= ?2
+               AND (ended_at IS NULL OR substr(ended_at, 1, 10) >= ?1)
+             ORDER BY started_at ASC, id ASC",
+            INCIDENT_COLUMNS
+        ))
+        .map_err(|e| e.to_string())?;
+    let incidents_iter = stmt
+        .query_map(params![from, to], incident_from_row)
+        .map_err(|e| e.to_string())?;
+
+    let mut incidents = Vec::new();
+    for incident in incidents_iter {
+        incidents.push(with_links(conn, incident.map_err(|e| e.to_string())?)?);
+    }
+
+    Ok(incidents)
+}
+
+/// Chronological events for the postmortem view: incidents starting and resolving,
+/// and linked tasks completed, within `from..=to`.
+pub(crate) fn get_incident_timeline_in_conn(
+    conn: &Connection,
+    from: &str,
+    to: &str,
+) -> Result<Vec<IncidentTimelineEvent>, String> {
+    let from = NaiveDate::parse_from_str(from.trim(), "%Y-%m-%d")
+        .map_err(|_| format!("Invalid date: {}", from))?
+        .format("%Y-%m-%d")
+        .to_string();
+    let to = NaiveDate::parse_from_str(to.trim(), "%Y-%m-%d")
+        .map_err(|_| format!("Invalid date: {}", to))?
+        .format("%Y-%m-%d")
+        .to_string();
+    let in_range = |at: &str| {
+        let day = at.get(..10).unwrap_or(at);
+        day >= from.as_str() && day <= to.as_str()
+    };
+
+    let mut events = Vec::new();
+    for incident in get_incidents_between_in_conn(conn, &from, &to)? {
+        let event = |at: &str, kind: &str, detail: String| IncidentTimelineEvent {
+            at: at.to_string(),
+            kind: kind.to_string(),
+            incident_id: incident.id,
+            severity: incident.severity.clone(),
+            summary: incident.summary.clone(),
+            detail,
+        };
+        if in_range(&incident.started_at) {
+            events.push(event(&incident.started_at, "started", String::new()));
+        }
+        if let Some(ended_at) = incident.ended_at.as_deref().filter(|at| in_range(at)) {
+            events.push(event(ended_at, "resolved", incident.resolution.clone()));
+        }
+
+        let mut stmt = conn
+            .prepare(
+                "SELECT t.completed_at, t.title
+                 FROM incident_tasks it
+                 JOIN tasks t ON t.id = it.task_id
+                 WHERE it.incident_id = ?1 AND t.completed_at IS NOT NULL",
+            )
+            .map_err(|e| e.to_string())?;
+        let tasks_iter = stmt
+            .query_map(params![incident.id], |row| {
+                Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?))
+            })
+            .map_err(|e| e.to_string())?;
+        for task in tasks_iter {
+            let (completed_at, title) = task.map_err(|e| e.to_string())?;
+            if in_range(&completed_at) {
+                events.push(event(&completed_at, "task_completed", title));
+            }
+        }
+    }
+    events.sort_by(|a, b| a.at.cmp(&b.at));
+
+    Ok(events)
+}
+
+/// "2h 15m" style duration for reports; `None` while the incident is ongoing.
+pub(crate) fn incident_duration_label(incident: &Incident) -> Option<String> {
+    let started_at = DateTime::parse_from_rfc3339(&incident.started_at).ok()?;
+    let ended_at = DateTime::parse_from_rfc3339(incident.ended_at.as_deref()?).ok()?;
+    let minutes = (ended_at - started_at).max(Duration::zero()).num_minutes();
+    Some(match (minutes / 60, minutes % 60) {
+        (0, minutes) => format!("{}m", minutes),
+        (hours, 0) => format!("{}h", hours),
+        (hours, minutes) => format!("{}h {}m", hours, minutes),
+    })
+}
+
+#[tauri::command]
+pub fn get_incidents(state: State<'_, AppState>) -> Result<Vec<Incident>, String> {
+    let conn = state.db.lock().map_err(|e| e.to_string())?;
+    get_incidents_in_conn(&conn)
+}
+
+#[tauri::command]
+pub fn create_incident(
+    input: IncidentInput,
+    state: State<'_, AppState>,
+) -> Result<Incident, String> {
+    let conn = state.db.lock().map_err(|e| e.to_string())?;
+    create_incident_in_conn(&conn, &input)
+}
+
+#[tauri::command]
+pub fn update_incident(
+    id: i64,
+    input: IncidentInput,
+    state: State<'_, AppState>,
+) -> Result<Incident, String> {
+    let conn = state.db.lock().map_err(|e| e.to_string())?;
+    update_incident_in_conn(&conn, id, &input)
+}
+
+#[tauri::command]
+pub fn delete_incident(id: i64, state: State<'_, AppState>) -> Result<(), String> {
+    let conn = state.db.lock().map_err(|e| e.to_string())?;
+    conn.execute("DELETE FROM incidents WHERE id = ?1", params![id])
+        .map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+#[tauri::command]
+pub fn get_incident_timeline(
+    from: String,
+    to: String,
+    state: State<'_, AppState>,
+) -> Result<Vec<IncidentTimelineEvent>, String> {
+    let conn = state.db.lock().map_err(|e| e.to_string())?;
+    get_incident_timeline_in_conn(&conn, &from, &to)
+}
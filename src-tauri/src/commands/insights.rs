@@ -0,0 +1,528 @@
+use crate::models::{
+    DailyContextSwitches, DailyMeetingLoad, DailyMood, DailyWordCount, MoodCorrelation,
+    WritingStats,
+};
+use chrono::{DateTime, Datelike, Duration, Local, NaiveDate, Utc, Weekday};
+use rusqlite::{params, Connection};
+use std::collections::{HashMap, HashSet};
+use tauri::State;
+
+use super::encryption::{JournalCipher, JournalContent};
+use super::entries::{get_entries_between_in_conn, get_entries_in_conn};
+use super::markdown::EntryDateRange;
+use super::meetings::meeting_occurrences_in_conn;
+use super::oncall::oncall_days_in_conn;
+use super::settings::read_i64_setting;
+use super::AppState;
+
+/// Fewer mood samples than this produce a correlation of 0.
+const MIN_CORRELATION_SAMPLES: usize = 3;
+
+/// Pearson correlation coefficient, or `None` when either series has no variance.
+pub(crate) fn pearson_correlation(xs: &[f64], ys: &[f64]) -> Option<f64> {
+    if xs.len() != ys.len() || xs.len() < 2 {
+        return None;
+    }
+
+    let n = xs.len() as f64;
+    let mean_x = xs.iter().sum::<f64>() / n;
+    let mean_y = ys.iter().sum::<f64>() / n;
+    let mut covariance = 0.0;
+    let mut variance_x = 0.0;
+    let mut variance_y = 0.0;
+    for (x, y) in xs.iter().zip(ys) {
+        covariance += (x - mean_x) * (y - mean_y);
+        variance_x += (x - mean_x).powi(2);
+        variance_y += (y - mean_y).powi(2);
+    }
+
+    if variance_x == 0.0 || variance_y == 0.0 {
+        return None;
+    }
+
+    Some(covariance / (variance_x.sqrt() * variance_y.sqrt()))
+}
+
+fn parse_range(
+    start_date: Option<String>,
+    end_date: Option<String>,
+) -> Result<(NaiveDate, NaiveDate), String> {
+    let parse = |value: &str| {
+        NaiveDate::parse_from_str(value.trim(), "%Y-%m-%d")
+            .map_err(|_| format!("Invalid date: {}", value))
+    };
+    let end = match end_date {
+        Some(value) => parse(&value)?,
+        None => Local::now().date_naive(),
+    };
+    let start = match start_date {
+        Some(value) => parse(&value)?,
+        None => end - Duration::days(29),
+    };
+
+    if start > end {
+        return Err("Start date must not be after end date".to_string());
+    }
+
+    Ok((start, end))
+}
+
+fn daily_counts(
+    conn: &Connection,
+    sql: &str,
+    start: &str,
+    end: &str,
+) -> Result<HashMap<String, f64>, String> {
+    let mut stmt = conn.prepare(sql).map_err(|e| e.to_string())?;
+    let rows = stmt
+        .query_map(params![start, end], |row| {
+            Ok((row.get::<_, String>(0)?, row.get::<_, f64>(1)?))
+        })
+        .map_err(|e| e.to_string())?;
+
+    rows.collect::<Result<HashMap<_, _>, _>>()
+        .map_err(|e| e.to_string())
+}
+
+/// Correlates the daily mood with same-day activity metrics, strongest factors first.
+/// `commits_by_day` is gathered outside the DB lock since it shells out to git.
+pub(crate) fn mood_correlations_in_conn(
+    conn: &Connection,
+    start: NaiveDate,
+    end: NaiveDate,
+    commits_by_day: &HashMap<String, f64>,
+) -> Result<Vec<MoodCorrelation>, String> {
+    let oncall_days: HashMap<String, f64> = oncall_days_in_conn(conn, start, end)?
+        .into_iter()
+        .map(|day| (day, 1.0))
+        .collect();
+    let start = start.format("%Y-%m-%d").to_string();
+    let end = end.format("%Y-%m-%d").to_string();
+
+    let moods = daily_counts(
+        conn,
+        "SELECT date, mood FROM daily_moods WHERE date BETWEEN ?1 AND ?2 ORDER BY date",
+        &start,
+        &end,
+    )?;
+    let factors = [
+        ("commits", commits_by_day.clone()),
+        (
+            "meetings",
+            daily_counts(
+                conn,
+                "SELECT substr(start_at, 1, 10) AS day, COUNT(*) FROM meetings
+                 WHERE status != 'cancelled' AND substr(start_at, 1, 10) BETWEEN ?1 AND ?2
+                 GROUP BY day",
+                &start,
+                &end,
+            )?,
+        ),
+        (
+            "tasks_completed",
+            daily_counts(
+                conn,
+                "SELECT date(completed_at, 'localtime') AS day, COUNT(*) FROM tasks
+                 WHERE completed_at IS NOT NULL AND date(completed_at, 'localtime') BETWEEN ?1 AND ?2
+                 GROUP BY day",
+                &start,
+                &end,
+            )?,
+        ),
+        (
+            "deep_work_minutes",
+            daily_counts(
+                conn,
+                "SELECT date, SUM(duration_minutes) FROM pomodoro_sessions
+                 WHERE kind = 'focus' AND date BETWEEN ?1 AND ?2
+                 GROUP BY date",
+                &start,
+                &end,
+            )?,
+        ),
+        ("on_call", oncall_days),
+    ];
+
+    let mut mood_days: Vec<(&String, &f64)> = moods.iter().collect();
+    mood_days.sort_by(|a, b| a.0.cmp(b.0));
+    let mood_values: Vec<f64> = mood_days.iter().map(|(_, mood)| **mood).collect();
+
+    let mut correlations: Vec<MoodCorrelation> = factors
+        .iter()
+        .map(|(factor, values_by_day)| {
+            let values: Vec<f64> = mood_days
+                .iter()
+                .map(|(day, _)| values_by_day.get(*day).copied().unwrap_or(0.0))
+                .collect();
+            let correlation = if mood_values.len() >= MIN_CORRELATION_SAMPLES {
+                pearson_correlation(&values, &mood_values).unwrap_or(0.0)
+            } else {
+                0.0
+            };
+
+            MoodCorrelation {
+                factor: factor.to_string(),
+                correlation: (correlation * 1000.0).round() / 1000.0,
+                sample_days: mood_values.len() as i64,
+            }
+        })
+        .collect();
+
+    correlations.sort_by(|a, b| b.correlation.abs().total_cmp(&a.correlation.abs()));
+    Ok(correlations)
+}
+
+fn rounded_hours(minutes: f64) -> f64 {
+    (minutes / 6.0).round() / 10.0
+}
+
+/// Meeting and deep-work hours for each local day in `start..=end`, oldest first.
+/// Meetings are clipped at midnight and overlapping ones are counted once.
+pub(crate) fn meeting_load_in_conn(
+    conn: &Connection,
+    start: NaiveDate,
+    end: NaiveDate,
+) -> Result<Vec<DailyMeetingLoad>, String> {
+    let heavy_hours = read_i64_setting(conn, "meeting_heavy_hours")?;
+    let focus_minutes = daily_counts(
+        conn,
+        "SELECT date, SUM(duration_minutes) FROM pomodoro_sessions
+         WHERE kind = 'focus' AND date BETWEEN ?1 AND ?2
+         GROUP BY date",
+        &start.format("%Y-%m-%d").to_string(),
+        &end.format("%Y-%m-%d").to_string(),
+    )?;
+
+    // Occurrences come sorted by start, so tracking the furthest end seen so far is
+    // enough to skip time already covered by an earlier meeting.
+    let mut meeting_minutes: HashMap<NaiveDate, f64> = HashMap::new();
+    let mut covered_until: Option<DateTime<Local>> = None;
+    for (occurrence_start, occurrence_end) in meeting_occurrences_in_conn(conn, start, end)? {
+        let day = occurrence_start.date_naive();
+        let midnight = (day + Duration::days(1))
+            .and_hms_opt(0, 0, 0)
+            .and_then(|midnight| midnight.and_local_timezone(Local).earliest());
+        let occurrence_end =
+            midnight.map_or(occurrence_end, |midnight| occurrence_end.min(midnight));
+        let counted_from =
+            covered_until.map_or(occurrence_start, |until| until.max(occurrence_start));
+        if occurrence_end > counted_from {
+            *meeting_minutes.entry(day).or_default() +=
+                (occurrence_end - counted_from).num_minutes() as f64;
+            covered_until = Some(occurrence_end);
+        }
+    }
+
+    let mut days = Vec::new();
+    let mut day = start;
+    while day <= end {
+        let date = day.format("%Y-%m-%d").to_string();
+        let meeting_hours = rounded_hours(meeting_minutes.get(&day).copied().unwrap_or(0.0));
+        days.push(DailyMeetingLoad {
+            deep_work_hours: rounded_hours(focus_minutes.get(&date).copied().unwrap_or(0.0)),
+            meeting_heavy: heavy_hours > 0 && meeting_hours >= heavy_hours as f64,
+            meeting_hours,
+            date,
+        });
+        day += Duration::days(1);
+    }
+
+    Ok(days)
+}
+
+/// Context switches for each local day in `start..=end`, oldest first. A switch is
+/// a timer run on a different task than the run before it on the same day; a
+/// timer that is still running counts up to now. Runs belong to the day they
+/// started on.
+pub(crate) fn context_switches_in_conn(
+    conn: &Connection,
+    start: NaiveDate,
+    end: NaiveDate,
+) -> Result<Vec<DailyContextSwitches>, String> {
+    // Stored timestamps are UTC, so widen the window by a day on each side and
+    // bucket by local date below.
+    let from = (start - Duration::days(1)).format("%Y-%m-%d").to_string();
+    let to = (end + Duration::days(2)).format("%Y-%m-%d").to_string();
+    let now = Utc::now().to_rfc3339();
+    let mut stmt = conn
+        .prepare(
+            "SELECT task_id, started_at, ended_at FROM task_time_entries
+             WHERE started_at >= ?1 AND started_at < ?2
+             UNION ALL
+             SELECT id, timer_started_at, ?3 FROM tasks
+             WHERE timer_started_at IS NOT NULL AND timer_started_at >= ?1 AND timer_started_at < ?2",
+        )
+        .map_err(|e| e.to_string())?;
+    let runs_iter = stmt
+        .query_map(params![from, to, now], |row| {
+            Ok((
+                row.get::<_, i64>(0)?,
+                row.get::<_, String>(1)?,
+                row.get::<_, String>(2)?,
+            ))
+        })
+        .map_err(|e| e.to_string())?;
+
+    let mut runs_by_day: HashMap<NaiveDate, Vec<(DateTime<Local>, i64, i64)>> = HashMap::new();
+    for run in runs_iter {
+        let (task_id, started_at, ended_at) = run.map_err(|e| e.to_string())?;
+        let (Ok(started_at), Ok(ended_at)) = (
+            DateTime::parse_from_rfc3339(&started_at),
+            DateTime::parse_from_rfc3339(&ended_at),
+        ) else {
+            continue;
+        };
+        let started_at = started_at.with_timezone(&Local);
+        let day = started_at.date_naive();
+        if day < start || day > end {
+            continue;
+        }
+        let seconds = (ended_at.with_timezone(&Local) - started_at)
+            .num_seconds()
+            .max(0);
+        runs_by_day
+            .entry(day)
+            .or_default()
+            .push((started_at, task_id, seconds));
+    }
+
+    let mut days = Vec::new();
+    let mut day = start;
+    while day <= end {
+        let mut runs = runs_by_day.remove(&day).unwrap_or_default();
+        runs.sort();
+        let switches = runs
+            .windows(2)
+            .filter(|pair| pair[0].1 != pair[1].1)
+            .count() as i64;
+        let tasks_touched = runs.iter().map(|run| run.1).collect::<HashSet<_>>().len() as i64;
+        let tracked_seconds: i64 = runs.iter().map(|run| run.2).sum();
+        let switches_per_hour = if tracked_seconds > 0 {
+            (switches as f64 * 36000.0 / tracked_seconds as f64).round() / 10.0
+        } else {
+            0.0
+        };
+        days.push(DailyContextSwitches {
+            date: day.format("%Y-%m-%d").to_string(),
+            switches,
+            tasks_touched,
+            tracked_minutes: tracked_seconds / 60,
+            switches_per_hour,
+        });
+        day += Duration::days(1);
+    }
+
+    Ok(days)
+}
+
+fn weekday_name(weekday: Weekday) -> &'static str {
+    match weekday {
+        Weekday::Mon => "Monday",
+        Weekday::Tue => "Tuesday",
+        Weekday::Wed => "Wednesday",
+        Weekday::Thu => "Thursday",
+        Weekday::Fri => "Friday",
+        Weekday::Sat => "Saturday",
+        Weekday::Sun => "Sunday",
+    }
+}
+
+/// Word counts per day, average entry length and the most productive weekday for
+/// `range` (all entries when `None`). Counting happens here so the dashboard only
+/// receives numbers, not entry text.
+pub(crate) fn writing_stats_in_conn(
+    conn: &Connection,
+    range: Option<&EntryDateRange>,
+    cipher: &JournalCipher,
+) -> Result<WritingStats, String> {
+    let mut entries = match range {
+        Some(range) => get_entries_between_in_conn(conn, &range.start, &range.end)?,
+        None => get_entries_in_conn(conn, None, None)?,
+    }
+    .decrypt_with(cipher)?;
+    entries.sort_by(|a, b| a.date.cmp(&b.date));
+
+    // (total words, entries) per weekday, Monday first.
+    let mut weekdays = [(0i64, 0i64); 7];
+    let mut days = Vec::new();
+    for entry in &entries {
+        let words = (entry.yesterday.split_whitespace().count()
+            + entry.today.split_whitespace().count()) as i64;
+        if let Ok(date) = NaiveDate::parse_from_str(&entry.date, "%Y-%m-%d") {
+            let bucket = &mut weekdays[date.weekday().num_days_from_monday() as usize];
+            bucket.0 += words;
+            bucket.1 += 1;
+        }
+        days.push(DailyWordCount {
+            date: entry.date.clone(),
+            words,
+        });
+    }
+
+    let total_words: i64 = days.iter().map(|day| day.words).sum();
+    let entry_count = days.len() as i64;
+    let average_words_per_entry = if entry_count == 0 {
+        0.0
+    } else {
+        (total_words as f64 / entry_count as f64 * 10.0).round() / 10.0
+    };
+    let mut most_productive: Option<(Weekday, f64)> = None;
+    let mut weekday = Weekday::Mon;
+    for (words, count) in weekdays {
+        if count > 0 {
+            let average = words as f64 / count as f64;
+            if most_productive.is_none_or(|(_, best)| average > best) {
+                most_productive = Some((weekday, average));
+            }
+        }
+        weekday = weekday.succ();
+    }
+
+    Ok(WritingStats {
+        days,
+        total_words,
+        entry_count,
+        average_words_per_entry,
+        most_productive_weekday: most_productive
+            .map(|(weekday, _)| weekday_name(weekday).to_string()),
+    })
+}
+
+/// Commits per author date in the working directory's repository; empty when git is unavailable.
+fn git_commit_counts_by_day(start: NaiveDate, end: NaiveDate) -> HashMap<String, f64> {
+    let output = std::process::Command::new("git")
+        .args([
+            "log",
+            &format!("--since={} 00:00", start.format("%Y-%m-%d")),
+            &format!("--until={} 23:59:59", end.format("%Y-%m-%d")),
+            "--format=%ad",
+            "--date=short",
+        ])
+        .current_dir(std::env::current_dir().unwrap_or_else(|_| std::path::PathBuf::from(".")))
+        .output();
+
+    let mut counts = HashMap::new();
+    if let Ok(output) = output {
+        if output.status.success() {
+            for day in String::from_utf8_lossy(&output.stdout).lines() {
+                *counts.entry(day.trim().to_string()).or_insert(0.0) += 1.0;
+            }
+        }
+    }
+
+    counts
+}
+
+#[tauri::command]
+pub fn set_daily_mood(
+    date: String,
+    mood: Option<i64>,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    let date = NaiveDate::parse_from_str(date.trim(), "%Y-%m-%d")
+        .map_err(|_| format!("Invalid date: {}", date))?
+        .format("%Y-%m-%d")
+        .to_string();
+    let conn = state.db.lock().map_err(|e| e.to_string())?;
+
+    match mood {
+        Some(mood) => conn.execute(
+            "INSERT INTO daily_moods (date, mood, updated_at) VALUES (?1, ?2, ?3)
+             ON CONFLICT(date) DO UPDATE SET mood = excluded.mood, updated_at = excluded.updated_at",
+            params![date, mood.clamp(1, 5), Utc::now().to_rfc3339()],
+        ),
+        None => conn.execute("DELETE FROM daily_moods WHERE date = ?1", params![date]),
+    }
+    .map_err(|e| e.to_string())?;
+
+    Ok(())
+}
+
+#[tauri::command]
+pub fn get_daily_moods(
+    start_date: Option<String>,
+    end_date: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<Vec<DailyMood>, String> {
+    let (start, end) = parse_range(start_date, end_date)?;
+    let conn = state.db.lock().map_err(|e| e.to_string())?;
+    let mut stmt = conn
+        .prepare(
+            "SELECT date, mood, updated_at FROM daily_moods
+             WHERE date BETWEEN ?1 AND ?2
+             ORDER BY date ASC",
+        )
+        .map_err(|e| e.to_string())?;
+
+    let moods_iter = stmt
+        .query_map(
+            params![
+                start.format("%Y-%m-%d").to_string(),
+                end.format("%Y-%m-%d").to_string()
+            ],
+            |row| {
+                Ok(DailyMood {
+                    date: row.get(0)?,
+                    mood: row.get(1)?,
+                    updated_at: row.get(2)?,
+                })
+            },
+        )
+        .map_err(|e| e.to_string())?;
+
+    let mut moods = Vec::new();
+    for mood in moods_iter {
+        moods.push(mood.map_err(|e| e.to_string())?);
+    }
+
+    Ok(moods)
+}
+
+/// Defaults to the last 30 days when no range is given.
+#[tauri::command]
+pub async fn get_mood_correlations(
+    start_date: Option<String>,
+    end_date: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<Vec<MoodCorrelation>, String> {
+    let (start, end) = parse_range(start_date, end_date)?;
+    let commits_by_day = git_commit_counts_by_day(start, end);
+    let conn = state.db.lock().map_err(|e| e.to_string())?;
+    mood_correlations_in_conn(&conn, start, end, &commits_by_day)
+}
+
+/// Defaults to the last 30 days when no range is given.
+#[tauri::command]
+pub fn get_meeting_load(
+    start_date: Option<String>,
+    end_date: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<Vec<DailyMeetingLoad>, String> {
+    let (start, end) = parse_range(start_date, end_date)?;
+    let conn = state.db.lock().map_err(|e| e.to_string())?;
+    meeting_load_in_conn(&conn, start, end)
+}
+
+/// Defaults to the last 30 days when no range is given.
+#[tauri::command]
+pub fn get_context_switches(
+    range: Option<EntryDateRange>,
+    state: State<'_, AppState>,
+) -> Result<Vec<DailyContextSwitches>, String> {
+    let (start, end) = match range {
+        Some(range) => parse_range(Some(range.start), Some(range.end))?,
+        None => parse_range(None, None)?,
+    };
+    let conn = state.db.lock().map_err(|e| e.to_string())?;
+    context_switches_in_conn(&conn, start, end)
+}
+
+#[tauri::command]
+pub fn get_writing_stats(
+    range: Option<EntryDateRange>,
+    state: State<'_, AppState>,
+) -> Result<WritingStats, String> {
+    let cipher = state.journal_cipher()?;
+    let conn = state.db.lock().map_err(|e| e.to_string())?;
+    writing_stats_in_conn(&conn, range.as_ref(), &cipher)
+}
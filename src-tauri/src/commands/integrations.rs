@@ -0,0 +1,864 @@
+use crate::models::{IntegrationSettings, LinkedItemChange, Task, TaskLink, TaskWithLink};
+use base64::{engine::general_purpose::STANDARD, Engine};
+use chrono::Utc;
+use rusqlite::{params, Connection, OptionalExtension};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::time::Duration;
+use tauri::{AppHandle, Manager, State};
+use tauri_plugin_notification::NotificationExt;
+
+use super::events::emit_event;
+use super::review_queue::{queue_task_for_review, REVIEW_SOURCE_URL_CAPTURE};
+use super::settings::read_bool_setting;
+use super::tasks::{find_duplicate_tasks_in_conn, set_task_status_in_conn};
+use super::validation::{
+    decode_json_string_list, encode_json_string_list, normalize_optional_http_url,
+    normalize_optional_text,
+};
+use super::AppState;
+
+pub(crate) const INTEGRATION_PROVIDERS: [&str; 3] = ["github", "gitlab", "jira"];
+
+const HTTP_TIMEOUT_SECONDS: u64 = 10;
+
+const TASK_LINK_COLUMNS: &str = "id, task_id, url, provider, kind, external_ref, title, labels_json, state, last_checked_at, created_at, updated_at";
+
+/// Issue or pull request reference parsed out of a pasted URL.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct IssueReference {
+    pub provider: String,
+    pub kind: String,
+    pub origin: String,
+    pub project: String,
+    pub number: String,
+}
+
+impl IssueReference {
+    pub(crate) fn external_ref(&self) -> String {
+        match (self.provider.as_str(), self.kind.as_str()) {
+            ("jira", _) => self.number.clone(),
+            ("gitlab", "pull_request") => format!("{}!{}", self.project, self.number),
+            _ => format!("{}#{}", self.project, self.number),
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+pub(crate) struct IssueMetadata {
+    pub title: String,
+    pub labels: Vec<String>,
+    pub state: Option<String>,
+}
+
+#[derive(Debug, Default)]
+pub(crate) struct StoredIntegration {
+    pub base_url: Option<String>,
+    pub username: Option<String>,
+    pub token: String,
+}
+
+fn task_link_from_row(row: &rusqlite::Row<'_>) -> Result<TaskLink, String> {
+    Ok(TaskLink {
+        id: row.get(0).map_err(|e| e.to_string())?,
+        task_id: row.get(1).map_err(|e| e.to_string())?,
+        url: row.get(2).map_err(|e| e.to_string())?,
+        provider: row.get(3).map_err(|e| e.to_string())?,
+        kind: row.get(4).map_err(|e| e.to_string())?,
+        external_ref: row.get(5).map_err(|e| e.to_string())?,
+        title: row.get(6).map_err(|e| e.to_string())?,
+        labels: decode_json_string_list(row.get(7).map_err(|e| e.to_string())?)?,
+        state: row.get(8).map_err(|e| e.to_string())?,
+        last_checked_at: row.get(9).map_err(|e| e.to_string())?,
+        created_at: row.get(10).map_err(|e| e.to_string())?,
+        updated_at: row.get(11).map_err(|e| e.to_string())?,
+    })
+}
+
+fn is_numeric(value: &str) -> bool {
+    !value.is_empty() && value.chars().all(|ch| ch.is_ascii_digit())
+}
+
+fn is_jira_key(value: &str) -> bool {
+    let Some((project, number)) = value.split_once('-') else {
+        return false;
+    };
+
+    project.starts_with(|ch: char| ch.is_ascii_uppercase())
+        && project
+            .chars()
+            .all(|ch| ch.is_ascii_uppercase() || ch.is_ascii_digit() || ch == '_')
+        && is_numeric(number)
+}
+
+pub(crate) fn parse_issue_url(url: &str) -> Option<IssueReference> {
+    let (scheme, rest) = url.trim().split_once("://")?;
+    let scheme = scheme.to_ascii_lowercase();
+    if scheme != "https" && scheme != "http" {
+        return None;
+    }
+
+    let rest = rest.split(['?', '#']).next().unwrap_or_default();
+    let (host, path) = rest.split_once('/').unwrap_or((rest, ""));
+    if host.is_empty() {
+        return None;
+    }
+
+    let origin = format!("{}://{}", scheme, host.to_ascii_lowercase());
+    let segments: Vec<&str> = path
+        .split('/')
+        .filter(|segment| !segment.is_empty())
+        .collect();
+
+    if let Some(position) = segments.iter().position(|segment| *segment == "browse") {
+        let key = segments.get(position + 1)?;
+        if !is_jira_key(key) {
+            return None;
+        }
+
+        let context_path = segments[..position].join("/");
+        let origin = if context_path.is_empty() {
+            origin
+        } else {
+            format!("{}/{}", origin, context_path)
+        };
+
+        return Some(IssueReference {
+            provider: "jira".to_string(),
+            kind: "issue".to_string(),
+            origin,
+            project: key.split('-').next().unwrap_or_default().to_string(),
+            number: key.to_string(),
+        });
+    }
+
+    if let Some(position) = segments.iter().position(|segment| *segment == "-") {
+        let kind = match segments.get(position + 1).copied() {
+            Some("issues") => "issue",
+            Some("merge_requests") => "pull_request",
+            _ => return None,
+        };
+        let number = segments.get(position + 2)?;
+        if position < 2 || !is_numeric(number) {
+            return None;
+        }
+
+        return Some(IssueReference {
+            provider: "gitlab".to_string(),
+            kind: kind.to_string(),
+            origin,
+            project: segments[..position].join("/"),
+            number: number.to_string(),
+        });
+    }
+
+    if segments.len() >= 4 && is_numeric(segments[3]) {
+        let kind = match segments[2] {
+            "issues" => "issue",
+            "pull" => "pull_request",
+            _ => return None,
+        };
+
+        return Some(IssueReference {
+            provider: "github".to_string(),
+            kind: kind.to_string(),
+            origin,
+            project: format!("{}/{}", segments[0], segments[1]),
+            number: segments[3].to_string(),
+        });
+    }
+
+    None
+}
+
+fn url_origin(url: &str) -> Option<String> {
+    let (scheme, rest) = url.trim().split_once("://")?;
+    let host = rest.split(['/', '?', '#']).next().unwrap_or_default();
+    if host.is_empty() {
+        return None;
+    }
+    Some(format!(
+        "{}://{}",
+        scheme.to_ascii_lowercase(),
+        host.to_ascii_lowercase()
+    ))
+}
+
+/// Stored credentials only go to the host they were saved for: the origin of the
+/// configured `base_url`, or github.com when GitHub has none. Links to any other host
+/// are fetched anonymously.
+pub(crate) fn sends_credentials_to(reference: &IssueReference, base_url: Option<&str>) -> bool {
+    let trusted = match base_url {
+        Some(base_url) => url_origin(base_url).map(|origin| {
+            if origin == "https://api.github.com" {
+                "https://github.com".to_string()
+            } else {
+                origin
+            }
+        }),
+        None if reference.provider == "github" => Some("https://github.com".to_string()),
+        None => None,
+    };
+    trusted.is_some_and(|trusted| url_origin(&reference.origin).as_deref() == Some(&trusted))
+}
+
+/// Maps common priority labels (`p0`, `priority: high`, `critical`, ...) onto task priorities.
+pub(crate) fn priority_from_labels(labels: &[String]) -> String {
+    let mut rank = None;
+    for label in labels {
+        let normalized = label.trim().to_ascii_lowercase();
+        let normalized = normalized
+            .trim_start_matches("priority")
+            .trim_start_matches([':', '/', '-', ' ', '_'])
+            .trim();
+
+        let label_rank = match normalized {
+            "urgent" | "critical" | "blocker" | "highest" | "p0" => 3,
+            "high" | "p1" => 2,
+            "medium" | "normal" | "p2" => 1,
+            "low" | "lowest" | "minor" | "p3" | "p4" => 0,
+            _ => continue,
+        };
+        rank = rank.max(Some(label_rank));
+    }
+
+    match rank {
+        Some(3) => "urgent",
+        Some(2) => "high",
+        Some(0) => "low",
+        _ => "medium",
+    }
+    .to_string()
+}
+
+fn percent_encode_path(value: &str) -> String {
+    value
+        .bytes()
+        .map(|byte| match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                (byte as char).to_string()
+            }
+            _ => format!("%{:02X}", byte),
+        })
+        .collect()
+}
+
+pub(crate) fn load_stored_integration(
+    conn: &Connection,
+    provider: &str,
+) -> Result<Option<StoredIntegration>, String> {
+    conn.query_row(
+        "SELECT base_url, username, token FROM integration_settings WHERE provider = ?1",
+        params![provider],
+        |row| {
+            Ok(StoredIntegration {
+                base_url: row.get(0)?,
+                username: row.get(1)?,
+                token: row.get(2)?,
+            })
+        },
+    )
+    .optional()
+    .map_err(|e| e.to_string())
+}
+
+fn api_get(url: &str, headers: &[(&str, String)]) -> Result<Value, String> {
+    let mut request = ureq::get(url)
+        .timeout(Duration::from_secs(HTTP_TIMEOUT_SECONDS))
+        .set("User-Agent", "dev-journal")
+        .set("Accept", "application/json");
+    for (name, value) in headers {
+        request = request.set(name, value);
+    }
+
+    request
+        .call()
+        .map_err(|e| e.to_string())?
+        .into_json::<Value>()
+        .map_err(|e| e.to_string())
+}
+
+fn json_string(value: &Value, pointer: &str) -> Option<String> {
+    value
+        .pointer(pointer)
+        .and_then(Value::as_str)
+        .map(|text| text.trim().to_string())
+        .filter(|text| !text.is_empty())
+}
+
+fn json_labels(value: &Value, pointer: &str) -> Vec<String> {
+    value
+        .pointer(pointer)
+        .and_then(Value::as_array)
+        .map(|labels| {
+            labels
+                .iter()
+                .filter_map(|label| {
+                    label
+                        .as_str()
+                        .or_else(|| label.get("name").and_then(Value::as_str))
+                        .map(|name| name.trim().to_string())
+                })
+                .filter(|name| !name.is_empty())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Fetches issue metadata from the provider API. `base_url` in the stored
+/// integration overrides the API root derived from the issue URL; the integration is
+/// ignored for links on other hosts (see `sends_credentials_to`).
+pub(crate) fn fetch_issue_metadata(
+    reference: &IssueReference,
+    integration: Option<&StoredIntegration>,
+) -> Result<IssueMetadata, String> {
+    let integration = integration
+        .filter(|integration| sends_credentials_to(reference, integration.base_url.as_deref()));
+    let token = integration
+        .map(|integration| integration.token.trim())
+        .filter(|token| !token.is_empty());
+    let base_url = integration.and_then(|integration| integration.base_url.clone());
+    let mut headers = Vec::new();
+
+    match reference.provider.as_str() {
+        "github" => {
+            let api_root = base_url.unwrap_or_else(|| {
+                if reference.origin == "https://github.com" {
+                    "https://api.github.com".to_string()
+                } else {
+                    format!("{}/api/v3", reference.origin)
+                }
+            });
+            if let Some(token) = token {
+                headers.push(("Authorization", format!("Bearer {}", token)));
+            }
+            let endpoint = if reference.kind == "pull_request" {
+                "pulls"
+            } else {
+                "issues"
+            };
+            let body = api_get(
+                &format!(
+                    "{}/repos/{}/{}/{}",
+                    api_root.trim_end_matches('/'),
+                    reference.project,
+                    endpoint,
+                    reference.number
+                ),
+                &headers,
+            )?;
+
+            let merged = body.get("merged_at").is_some_and(|value| !value.is_null());
+            Ok(IssueMetadata {
+                title: json_string(&body, "/title").unwrap_or_default(),
+                labels: json_labels(&body, "/labels"),
+                state: if merged {
+                    Some("merged".to_string())
+                } else {
+                    json_string(&body, "/state")
+                },
+            })
+        }
+        "gitlab" => {
+            let api_root = base_url.unwrap_or_else(|| format!("{}/api/v4", reference.origin));
+            if let Some(token) = token {
+                headers.push(("PRIVATE-TOKEN", token.to_string()));
+            }
+            let endpoint = if reference.kind == "pull_request" {
+                "merge_requests"
+            } else {
+                "issues"
+            };
+            let body = api_get(
+                &format!(
+                    "{}/projects/{}/{}/{}",
+                    api_root.trim_end_matches('/'),
+                    percent_encode_path(&reference.project),
+                    endpoint,
+                    reference.number
+                ),
+                &headers,
+            )?;
+
+            Ok(IssueMetadata {
+                title: json_string(&body, "/title").unwrap_or_default(),
+                labels: json_labels(&body, "/labels"),
+                state: json_string(&body, "/state"),
+            })
+        }
+        "jira" => {
+            let api_root = base_url.unwrap_or_else(|| reference.origin.clone());
+            if let Some(token) = token {
+                let username = integration
+                    .and_then(|integration| integration.username.as_deref())
+                    .filter(|username| !username.trim().is_empty());
+                let authorization = match username {
+                    Some(username) => format!(
+                        "Basic {}",
+                        STANDARD.encode(format!("{}:{}", username.trim(), token))
+                    ),
+                    None => format!("Bearer {}", token),
+                };
+                headers.push(("Authorization", authorization));
+            }
+            let body = api_get(
+                &format!(
+                    "{}/rest/api/2/issue/{}?fields=summary,labels,status",
+                    api_root.trim_end_matches('/'),
+                    reference.number
+                ),
+                &headers,
+            )?;
+
+            Ok(IssueMetadata {
+                title: json_string(&body, "/fields/summary").unwrap_or_default(),
+                labels: json_labels(&body, "/fields/labels"),
+                state: json_string(&body, "/fields/status/name"),
+            })
+        }
+        other => Err(format!("Unsupported integration provider: {}", other)),
+    }
+}
+
+/// Fails when `url` is already linked to a task.
+fn ensure_url_not_linked(conn: &Connection, url: &str) -> Result<(), String> {
+    let existing_task_id: Option<i64> = conn
+        .query_row(
+            "SELECT task_id FROM task_links WHERE url = ?1 LIMIT 1",
+            params![url],
+            |row| row.get(0),
+        )
+        .optional()
+        .map_err(|e| e.to_string())?;
+    match existing_task_id {
+        Some(task_id) => Err(format!("This URL is already linked to task #{}", task_id)),
+        None => Ok(()),
+    }
+}
+
+pub(crate) fn create_linked_task_in_conn(
+    conn: &mut Connection,
+    url: &str,
+    reference: &IssueReference,
+    metadata: IssueMetadata,
+) -> Result<TaskWithLink, String> {
+    let now = Utc::now().to_rfc3339();
+    let external_ref = reference.external_ref();
+    let title = if metadata.title.is_empty() {
+        external_ref.clone()
+    } else {
+        metadata.title
+    };
+    let priority = priority_from_labels(&metadata.labels);
+    let description = if metadata.labels.is_empty() {
+        format!("Source: {}", url)
+    } else {
+        format!("Source: {}\nLabels: {}", url, metadata.labels.join(", "))
+    };
+    let labels_json = encode_json_string_list(&metadata.labels)?;
+    let duplicates = find_duplicate_tasks_in_conn(conn, &title)?;
+
+    let tx = conn.transaction().map_err(|e| e.to_string())?;
+    // Checked again here: the lock is released while metadata is fetched, so another
+    // call may have linked the URL in the meantime.
+    ensure_url_not_linked(&tx, url)?;
+
+    tx.execute(
+        "INSERT INTO tasks (title, description, status, priority, project_id, goal_id, due_date, completed_at, time_estimate_minutes, timer_started_at, timer_accumulated_seconds, created_at, updated_at)
+         VALUES (?1, ?2, 'todo', ?3, NULL, NULL, NULL, NULL, 0, NULL, 0, ?4, ?5)",
+        params![title, description, priority, now, now],
+    )
+    .map_err(|e| e.to_string())?;
+    let task_id = tx.last_insert_rowid();
+
+    tx.execute(
+        "INSERT INTO task_links (task_id, url, provider, kind, external_ref, title, labels_json, state, last_checked_at, created_at, updated_at)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?9, ?9)",
+        params![
+            task_id,
+            url,
+            reference.provider,
+            reference.kind,
+            external_ref,
+            title,
+            labels_json,
+            metadata.state,
+            now
+        ],
+    )
+    .map_err(|e| e.to_string())?;
+    let link_id = tx.last_insert_rowid();
+    queue_task_for_review(&tx, task_id, REVIEW_SOURCE_URL_CAPTURE)?;
+
+    tx.commit().map_err(|e| e.to_string())?;
+
+    Ok(TaskWithLink {
+        task: Task {
+            id: task_id,
+            title: title.clone(),
+            description,
+            status: "todo".to_string(),
+            priority,
+            project_id: None,
+            goal_id: None,
+            due_date: None,
+            recurrence: "none".to_string(),
+            recurrence_until: None,
+            parent_task_id: None,
+            completed_at: None,
+            time_estimate_minutes: 0,
+            timer_started_at: None,
+            timer_accumulated_seconds: 0,
+            created_at: now.clone(),
+            updated_at: now.clone(),
+            subtask_count: 0,
+            subtasks_completed: 0,
+        },
+        link: TaskLink {
+            id: link_id,
+            task_id,
+            url: url.to_string(),
+            provider: reference.provider.clone(),
+            kind: reference.kind.clone(),
+            external_ref,
+            title,
+            labels: metadata.labels,
+            state: metadata.state,
+            last_checked_at: Some(now.clone()),
+            created_at: now.clone(),
+            updated_at: now,
+        },
+        duplicates,
+    })
+}
+
+#[tauri::command]
+pub async fn create_task_from_url(
+    url: String,
+    state: State<'_, AppState>,
+) -> Result<TaskWithLink, String> {
+    let url = normalize_optional_http_url(Some(url)).ok_or("Invalid issue URL")?;
+    let reference = parse_issue_url(&url)
+        .ok_or("URL is not a recognized GitHub, GitLab, or Jira issue link")?;
+
+    // The lock is released while the provider API is queried.
+    let integration = {
+        let conn = state.db.lock().map_err(|e| e.to_string())?;
+        ensure_url_not_linked(&conn, &url)?;
+        load_stored_integration(&conn, &reference.provider)?
+    };
+
+    // ureq blocks, so the request runs on the blocking pool rather than an async worker.
+    let fetch_reference = reference.clone();
+    let fetched = tauri::async_runtime::spawn_blocking(move || {
+        fetch_issue_metadata(&fetch_reference, integration.as_ref())
+    })
+    .await
+    .map_err(|e| e.to_string())?;
+    let metadata = match fetched {
+        Ok(metadata) => metadata,
+        Err(error) => {
+            eprintln!("Failed to fetch metadata for {url}: {error}");
+            IssueMetadata::default()
+        }
+    };
+
+    let mut conn = state.db.lock().map_err(|e| e.to_string())?;
+    create_linked_task_in_conn(&mut conn, &url, &reference, metadata)
+}
+
+#[tauri::command]
+pub fn get_task_links(
+    task_id: Option<i64>,
+    state: State<'_, AppState>,
+) -> Result<Vec<TaskLink>, String> {
+    let conn = state.db.lock().map_err(|e| e.to_string())?;
+    let mut stmt = conn
+        .prepare(&format!(
+            "SELECT {} FROM task_links WHERE ?1 IS NULL OR task_id = ?1 ORDER BY id ASC",
+            TASK_LINK_COLUMNS
+        ))
+        .map_err(|e| e.to_string())?;
+
+    let mut rows = stmt.query(params![task_id]).map_err(|e| e.to_string())?;
+    let mut links = Vec::new();
+    while let Some(row) = rows.next().map_err(|e| e.to_string())? {
+        links.push(task_link_from_row(row)?);
+    }
+
+    Ok(links)
+}
+
+pub(crate) fn is_terminal_link_state(state: Option<&str>) -> bool {
+    matches!(
+        state.map(|value| value.to_ascii_lowercase()).as_deref(),
+        Some("merged") | Some("closed")
+    )
+}
+
+#[cfg(test)]
+pub(crate) fn get_task_link_in_conn(conn: &Connection, task_id: i64) -> Result<TaskLink, String> {
+    let mut stmt = conn
+        .prepare(&format!(
+            "SELECT {} FROM task_links WHERE task_id = ?1 ORDER BY id ASC LIMIT 1",
+            TASK_LINK_COLUMNS
+        ))
+        .map_err(|e| e.to_string())?;
+    let mut rows = stmt.query(params![task_id]).map_err(|e| e.to_string())?;
+
+    match rows.next().map_err(|e| e.to_string())? {
+        Some(row) => task_link_from_row(row),
+        None => Err("Task link not found".to_string()),
+    }
+}
+
+fn load_refreshable_links(conn: &Connection) -> Result<Vec<TaskLink>, String> {
+    let mut stmt = conn
+        .prepare(&format!(
+            "SELECT {} FROM task_links
+             WHERE state IS NULL OR lower(state) NOT IN ('merged', 'closed')
+             ORDER BY COALESCE(last_checked_at, '') ASC, id ASC",
+            TASK_LINK_COLUMNS
+        ))
+        .map_err(|e| e.to_string())?;
+
+    let mut rows = stmt.query([]).map_err(|e| e.to_string())?;
+    let mut links = Vec::new();
+    while let Some(row) = rows.next().map_err(|e| e.to_string())? {
+        links.push(task_link_from_row(row)?);
+    }
+
+    Ok(links)
+}
+
+/// Stores freshly fetched metadata for a link. Returns a change only when a
+/// pull request transitions into a merged/closed state.
+pub(crate) fn apply_linked_item_refresh_in_conn(
+    conn: &Connection,
+    link: TaskLink,
+    metadata: IssueMetadata,
+    auto_complete: bool,
+) -> Result<Option<LinkedItemChange>, String> {
+    let now = Utc::now().to_rfc3339();
+    let previous_state = link.state.clone();
+    let state_changed = previous_state != metadata.state;
+    let title = if metadata.title.is_empty() {
+        link.title.clone()
+    } else {
+        metadata.title
+    };
+    let updated_at = if state_changed {
+        now.clone()
+    } else {
+        link.updated_at.clone()
+    };
+
+    conn.execute(
+        "UPDATE task_links
+         SET title = ?1, labels_json = ?2, state = ?3, last_checked_at = ?4, updated_at = ?5
+         WHERE id = ?6",
+        params![
+            title,
+            encode_json_string_list(&metadata.labels)?,
+            metadata.state,
+            now,
+            updated_at,
+            link.id
+        ],
+    )
+    .map_err(|e| e.to_string())?;
+
+    let updated_link = TaskLink {
+        title,
+        labels: metadata.labels,
+        state: metadata.state,
+        last_checked_at: Some(now),
+        updated_at,
+        ..link
+    };
+
+    if updated_link.kind != "pull_request"
+        || !state_changed
+        || !is_terminal_link_state(updated_link.state.as_deref())
+    {
+        return Ok(None);
+    }
+
+    let merged = updated_link.state.as_deref() == Some("merged");
+    let task_status: Option<String> = conn
+        .query_row(
+            "SELECT status FROM tasks WHERE id = ?1",
+            params![updated_link.task_id],
+            |row| row.get(0),
+        )
+        .optional()
+        .map_err(|e| e.to_string())?;
+    let task_completed =
+        auto_complete && merged && task_status.is_some_and(|status| status != "done");
+    if task_completed {
+        set_task_status_in_conn(conn, updated_link.task_id, "done".to_string())?;
+    }
+
+    Ok(Some(LinkedItemChange {
+        link: updated_link,
+        previous_state,
+        task_completed,
+    }))
+}
+
+fn notify_linked_item_change(app: &AppHandle, change: &LinkedItemChange) {
+    let title = if change.link.state.as_deref() == Some("merged") {
+        "Pull request merged"
+    } else {
+        "Pull request closed"
+    };
+    let mut body = format!("{}: {}", change.link.external_ref, change.link.title);
+    if change.task_completed {
+        body.push_str("\nLinked task moved to done.");
+    }
+
+    if let Err(error) = app.notification().builder().title(title).body(body).show() {
+        eprintln!("Failed to show linked item notification: {error}");
+    }
+}
+
+/// Polls every open linked issue/PR, notifying about merged or closed pull requests.
+/// Goes through `fetch_issue_metadata`, so a link stored for another host never gets
+/// the integration's credentials.
+pub(crate) fn refresh_linked_items_for_app(
+    app: &AppHandle,
+) -> Result<Vec<LinkedItemChange>, String> {
+    let state = app.state::<AppState>();
+    let (links, integrations, auto_complete) = {
+        let conn = state.db.lock().map_err(|e| e.to_string())?;
+        let mut integrations = HashMap::new();
+        for provider in INTEGRATION_PROVIDERS {
+            if let Some(integration) = load_stored_integration(&conn, provider)? {
+                integrations.insert(provider, integration);
+            }
+        }
+
+        (
+            load_refreshable_links(&conn)?,
+            integrations,
+            read_bool_setting(&conn, "linked_items_auto_complete")?,
+        )
+    };
+
+    let mut changes = Vec::new();
+    for link in links {
+        let Some(reference) = parse_issue_url(&link.url) else {
+            continue;
+        };
+        let metadata =
+            match fetch_issue_metadata(&reference, integrations.get(link.provider.as_str())) {
+                Ok(metadata) => metadata,
+                Err(error) => {
+                    eprintln!("Failed to refresh {}: {error}", link.url);
+                    continue;
+                }
+            };
+
+        let conn = state.db.lock().map_err(|e| e.to_string())?;
+        if let Some(change) =
+            apply_linked_item_refresh_in_conn(&conn, link, metadata, auto_complete)?
+        {
+            changes.push(change);
+        }
+    }
+
+    for change in &changes {
+        notify_linked_item_change(app, change);
+    }
+    if !changes.is_empty() {
+        if let Err(error) = emit_event(app, "linked-items-changed", &changes) {
+            eprintln!("Failed to emit linked item changes: {error}");
+        }
+    }
+
+    Ok(changes)
+}
+
+#[tauri::command]
+pub async fn refresh_linked_items(app: AppHandle) -> Result<Vec<LinkedItemChange>, String> {
+    refresh_linked_items_for_app(&app)
+}
+
+#[tauri::command]
+pub fn get_integration_settings(
+    state: State<'_, AppState>,
+) -> Result<Vec<IntegrationSettings>, String> {
+    let conn = state.db.lock().map_err(|e| e.to_string())?;
+    let mut settings = Vec::new();
+
+    for provider in INTEGRATION_PROVIDERS {
+        let row: Option<(Option<String>, Option<String>, String, String)> = conn
+            .query_row(
+                "SELECT base_url, username, token, updated_at FROM integration_settings WHERE provider = ?1",
+                params![provider],
+                |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?)),
+            )
+            .optional()
+            .map_err(|e| e.to_string())?;
+
+        settings.push(match row {
+            Some((base_url, username, token, updated_at)) => IntegrationSettings {
+                provider: provider.to_string(),
+                base_url,
+                username,
+                has_token: !token.is_empty(),
+                updated_at: Some(updated_at),
+            },
+            None => IntegrationSettings {
+                provider: provider.to_string(),
+                base_url: None,
+                username: None,
+                has_token: false,
+                updated_at: None,
+            },
+        });
+    }
+
+    Ok(settings)
+}
+
+/// Passing `token: None` keeps the stored token; an empty string clears it.
+#[tauri::command]
+pub fn save_integration_settings(
+    provider: String,
+    base_url: Option<String>,
+    username: Option<String>,
+    token: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    let provider = provider.trim().to_ascii_lowercase();
+    if !INTEGRATION_PROVIDERS.contains(&provider.as_str()) {
+        return Err(format!("Unsupported integration provider: {}", provider));
+    }
+
+    let conn = state.db.lock().map_err(|e| e.to_string())?;
+    let now = Utc::now().to_rfc3339();
+    let base_url =
+        normalize_optional_http_url(base_url).map(|value| value.trim_end_matches('/').to_string());
+    let username = normalize_optional_text(username);
+    let token = match token {
+        Some(token) => token.trim().to_string(),
+        None => load_stored_integration(&conn, &provider)?
+            .map(|integration| integration.token)
+            .unwrap_or_default(),
+    };
+
+    conn.execute(
+        "INSERT INTO integration_settings (provider, base_url, username, token, updated_at)
+         VALUES (?1, ?2, ?3, ?4, ?5)
+         ON CONFLICT(provider) DO UPDATE SET
+            base_url = excluded.base_url,
+            username = excluded.username,
+            token = excluded.token,
+            updated_at = excluded.updated_at",
+        params![provider, base_url, username, token, now],
+    )
+    .map_err(|e| e.to_string())?;
+
+    Ok(())
+}
@@ -0,0 +1,245 @@
+use crate::models::{IntegrityReport, IntegrityRuleStatus, IntegrityViolation};
+use chrono::Utc;
+use rusqlite::{params, Connection, OptionalExtension};
+use std::collections::HashMap;
+use tauri::{AppHandle, Manager, State};
+
+use super::events::emit_event;
+use super::settings::read_bool_setting;
+use super::AppState;
+
+/// Offending rows stored per rule; the count always covers all of them.
+const INTEGRITY_SAMPLE_LIMIT: i64 = 50;
+
+/// An invariant the data should hold. `check` selects `(row id, detail)` for every
+/// row breaking it; `fix`, when set, repairs those rows without losing anything the
+/// user wrote, so the verifier may run it unattended.
+struct IntegrityRule {
+    key: &'static str,
+    description: &'static str,
+    check: &'static str,
+    fix: Option<&'static str>,
+}
+
+const INTEGRITY_RULES: &[IntegrityRule] = &[
+    IntegrityRule {
+        key: "entry_date_valid",
+        description: "Entry dates are real YYYY-MM-DD days",
+        check: "SELECT id, date FROM entries WHERE date(date) IS NOT date",
+        fix: None,
+    },
+    IntegrityRule {
+        key: "entry_date_unique",
+        description: "Each day has at most one entry",
+        check: "SELECT e.id, e.date FROM entries e
+                WHERE date(e.date) IS NOT NULL AND EXISTS (
+                    SELECT 1 FROM entries other
+                    WHERE other.id < e.id AND date(other.date) = date(e.date)
+                )",
+        fix: None,
+    },
+    IntegrityRule {
+        key: "goal_progress_range",
+        description: "Goal progress is between 0 and 100",
+        check: "SELECT id, title || ': ' || progress FROM goals
+                WHERE progress < 0 OR progress > 100",
+        fix: Some(
+            "UPDATE goals SET progress = MAX(0, MIN(100, progress))
+             WHERE progress < 0 OR progress > 100",
+        ),
+    },
+    IntegrityRule {
+        key: "done_task_completed_at",
+        description: "Done tasks have a completion time",
+        check: "SELECT id, title FROM tasks WHERE status = 'done' AND completed_at IS NULL",
+        // The last edit is the best guess for when the task was finished.
+        fix: Some(
+            "UPDATE tasks SET completed_at = updated_at
+             WHERE status = 'done' AND completed_at IS NULL",
+        ),
+    },
+    IntegrityRule {
+        key: "open_task_completed_at",
+        description: "Open tasks have no completion time",
+        check: "SELECT id, title FROM tasks WHERE status != 'done' AND completed_at IS NOT NULL",
+        fix: Some(
+            "UPDATE tasks SET completed_at = NULL
+             WHERE status != 'done' AND completed_at IS NOT NULL",
+        ),
+    },
+    IntegrityRule {
+        key: "habit_log_habit_exists",
+        description: "Habit check-ins belong to an existing habit",
+        check: "SELECT id, 'habit ' || habit_id || ' on ' || date FROM habit_logs
+                WHERE habit_id NOT IN (SELECT id FROM habits)",
+        fix: Some("DELETE FROM habit_logs WHERE habit_id NOT IN (SELECT id FROM habits)"),
+    },
+    IntegrityRule {
+        key: "streak_freeze_habit_exists",
+        description: "Streak freezes belong to an existing habit",
+        check: "SELECT id, 'habit ' || habit_id || ' on ' || date FROM streak_freezes
+                WHERE habit_id NOT IN (SELECT id FROM habits)",
+        fix: Some("DELETE FROM streak_freezes WHERE habit_id NOT IN (SELECT id FROM habits)"),
+    },
+];
+
+/// Checks every rule and stores the result for `get_integrity_report`. With `auto_fix`
+/// the safe fixes run first, in the same transaction, so the report lists only what
+/// is left for the user.
+pub(crate) fn verify_integrity_in_conn(
+    conn: &mut Connection,
+    auto_fix: bool,
+) -> Result<IntegrityReport, String> {
+    let checked_at = Utc::now().to_rfc3339();
+    let tx = conn.transaction().map_err(|e| e.to_string())?;
+    tx.execute("DELETE FROM integrity_violations", [])
+        .map_err(|e| e.to_string())?;
+
+    for rule in INTEGRITY_RULES {
+        let fixed_count = match (auto_fix, rule.fix) {
+            (true, Some(fix)) => tx.execute(fix, []).map_err(|e| e.to_string())?,
+            _ => 0,
+        };
+        let violations = {
+            let mut stmt = tx.prepare(rule.check).map_err(|e| e.to_string())?;
+            let rows = stmt
+                .query_map([], |row| {
+                    Ok((row.get::<_, i64>(0)?, row.get::<_, Option<String>>(1)?))
+                })
+                .map_err(|e| e.to_string())?;
+            rows.collect::<Result<Vec<_>, _>>()
+                .map_err(|e| e.to_string())?
+        };
+
+        for (row_id, detail) in violations.iter().take(INTEGRITY_SAMPLE_LIMIT as usize) {
+            tx.execute(
+                "INSERT INTO integrity_violations (rule, row_id, detail) VALUES (?1, ?2, ?3)",
+                params![rule.key, row_id, detail.as_deref().unwrap_or("")],
+            )
+            .map_err(|e| e.to_string())?;
+        }
+        tx.execute(
+            "INSERT INTO integrity_checks (rule, violation_count, fixed_count, checked_at)
+             VALUES (?1, ?2, ?3, ?4)
+             ON CONFLICT(rule) DO UPDATE SET
+                 violation_count = excluded.violation_count,
+                 fixed_count = excluded.fixed_count,
+                 checked_at = excluded.checked_at",
+            params![
+                rule.key,
+                violations.len() as i64,
+                fixed_count as i64,
+                checked_at
+            ],
+        )
+        .map_err(|e| e.to_string())?;
+    }
+    tx.commit().map_err(|e| e.to_string())?;
+
+    get_integrity_report_in_conn(conn)
+}
+
+/// Result of the last check, one status per rule; rules never checked report no
+/// violations and no `checked_at`.
+pub(crate) fn get_integrity_report_in_conn(conn: &Connection) -> Result<IntegrityReport, String> {
+    let mut checks = HashMap::new();
+    {
+        let mut stmt = conn
+            .prepare("SELECT rule, violation_count, fixed_count, checked_at FROM integrity_checks")
+            .map_err(|e| e.to_string())?;
+        let rows = stmt
+            .query_map([], |row| {
+                Ok((
+                    row.get::<_, String>(0)?,
+                    (
+                        row.get::<_, i64>(1)?,
+                        row.get::<_, i64>(2)?,
+                        row.get::<_, String>(3)?,
+                    ),
+                ))
+            })
+            .map_err(|e| e.to_string())?;
+        for row in rows {
+            let (rule, check) = row.map_err(|e| e.to_string())?;
+            checks.insert(rule, check);
+        }
+    }
+
+    let mut stmt = conn
+        .prepare("SELECT row_id, detail FROM integrity_violations WHERE rule = ?1 ORDER BY id")
+        .map_err(|e| e.to_string())?;
+    let mut rules = Vec::new();
+    for rule in INTEGRITY_RULES {
+        let (violation_count, fixed_count, checked_at) = match checks.remove(rule.key) {
+            Some((violations, fixed, checked_at)) => (violations, fixed, Some(checked_at)),
+            None => (0, 0, None),
+        };
+        let violations = stmt
+            .query_map(params![rule.key], |row| {
+                Ok(IntegrityViolation {
+                    row_id: row.get(0)?,
+                    detail: row.get(1)?,
+                })
+            })
+            .map_err(|e| e.to_string())?
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| e.to_string())?;
+
+        rules.push(IntegrityRuleStatus {
+            rule: rule.key.to_string(),
+            description: rule.description.to_string(),
+            auto_fixable: rule.fix.is_some(),
+            violation_count,
+            fixed_count,
+            checked_at,
+            violations,
+        });
+    }
+    let checked_at = conn
+        .query_row("SELECT MAX(checked_at) FROM integrity_checks", [], |row| {
+            row.get::<_, Option<String>>(0)
+        })
+        .optional()
+        .map_err(|e| e.to_string())?
+        .flatten();
+
+    Ok(IntegrityReport {
+        checked_at,
+        violation_count: rules.iter().map(|rule| rule.violation_count).sum(),
+        rules,
+    })
+}
+
+/// The `integrity` job: verifies with fixes when `integrity_auto_fix` is on and tells
+/// the UI when something is left to look at.
+pub(crate) fn run_integrity_check_for_app(app: &AppHandle) -> Result<(), String> {
+    let report = {
+        let state = app.state::<AppState>();
+        let mut conn = state.db.lock().map_err(|e| e.to_string())?;
+        let auto_fix = read_bool_setting(&conn, "integrity_auto_fix")?;
+        verify_integrity_in_conn(&mut conn, auto_fix)?
+    };
+    if report.violation_count > 0 {
+        emit_event(app, "integrity-violations", report.violation_count)
+            .map_err(|e| e.to_string())?;
+    }
+    Ok(())
+}
+
+/// Violations found by the last check, scheduled or manual.
+#[tauri::command]
+pub fn get_integrity_report(state: State<'_, AppState>) -> Result<IntegrityReport, String> {
+    let conn = state.db.lock().map_err(|e| e.to_string())?;
+    get_integrity_report_in_conn(&conn)
+}
+
+/// Checks now instead of waiting for the `integrity` job; `auto_fix` repairs the safe
+/// cases first.
+#[tauri::command]
+pub fn verify_integrity(
+    auto_fix: bool,
+    state: State<'_, AppState>,
+) -> Result<IntegrityReport, String> {
+    let mut conn = state.db.lock().map_err(|e| e.to_string())?;
+    verify_integrity_in_conn(&mut conn, auto_fix)
+}
@@ -0,0 +1,521 @@
+use crate::models::{ScheduledJob, WeeklyDigest};
+use chrono::{DateTime, Datelike, Duration, Local, NaiveDate, NaiveDateTime, Utc};
+use rusqlite::{params, Connection, OptionalExtension};
+use std::fs;
+use std::path::{Path, PathBuf};
+use tauri::{AppHandle, Manager, State};
+use tauri_plugin_notification::NotificationExt;
+
+use super::events::emit_event;
+use super::notifications::{notification_templates_in_conn, render_notification_template};
+use super::AppState;
+
+/// Jobs the scheduler runs, with their default cron schedule and whether they are
+/// on out of the box. `reminders` covers the break, streak, goal check-in, journal
+/// and task due-date reminders, which still apply their own time settings when it
+/// runs. `update_check` asks GitHub for a newer release and is off by default since
+/// it contacts the network. `integrity` checks the rules in `integrity.rs`, fixing
+/// safe cases unless `integrity_auto_fix` is off.
+const SCHEDULED_JOB_DEFAULTS: &[(&str, &str, bool)] = &[
+    ("reminders", "* * * * *", true),
+    ("backup", "0 18 * * *", true),
+    ("digest", "0 9 * * mon", true),
+    ("rollover", "5 0 * * *", false),
+    ("update_check", "0 10 * * *", false),
+    ("integrity", "30 3 * * *", true),
+];
+
+/// Database snapshots kept by the `backup` job; older ones are deleted.
+const SCHEDULED_BACKUP_KEEP: usize = 7;
+const SCHEDULED_BACKUP_PREFIX: &str = "dev_journal-";
+
+/// Longest gap the scheduler catches up on (sleep, a blocked tick). A job due
+/// several times in that window still runs once.
+const MAX_CATCH_UP_MINUTES: i64 = 24 * 60;
+
+const MONTH_NAMES: &[&str] = &[
+    "jan", "feb", "mar", "apr", "may", "jun", "jul", "aug", "sep", "oct", "nov", "dec",
+];
+const WEEKDAY_NAMES: &[&str] = &["sun", "mon", "tue", "wed", "thu", "fri", "sat"];
+
+/// Five-field cron expression (`minute hour day-of-month month day-of-week`) in
+/// local time. Fields take `*`, numbers, `a-b` ranges, `,` lists and `/n` steps;
+/// months and weekdays also take three-letter names, and `@hourly`, `@daily`,
+/// `@weekly`, `@monthly` and `@yearly` are accepted. Unlike classic cron a range
+/// may wrap around (`22-6` hours, `fri-mon`) so night shifts need one range. When
+/// both day fields are restricted, a day matching either of them is enough.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct CronSchedule {
+    minutes: u64,
+    hours: u64,
+    days_of_month: u64,
+    months: u64,
+    days_of_week: u64,
+    any_day_of_month: bool,
+    any_day_of_week: bool,
+}
+
+fn parse_cron_value(value: &str, min: u32, max: u32, names: &[&str]) -> Result<u32, String> {
+    let lower = value.to_ascii_lowercase();
+    if let Some(index) = names.iter().position(|name| *name == lower) {
+        // Month names start at 1, weekday names at 0 (Sunday).
+        return Ok(index as u32 + min.min(1));
+    }
+    let number: u32 = value
+        .parse()
+        .map_err(|_| format!("Invalid cron value \"{}\"", value))?;
+    if number < min || number > max {
+        return Err(format!("Cron value {} is outside {}-{}", number, min, max));
+    }
+    Ok(number)
+}
+
+fn parse_cron_field(field: &str, min: u32, max: u32, names: &[&str]) -> Result<u64, String> {
+    let mut bits = 0u64;
+    for part in field.split(',') {
+        let (range, step) = match part.split_once('/') {
+            Some((range, step)) => {
+                let step = step
+                    .parse::<u32>()
+                    .ok()
+                    .filter(|step| *step > 0)
+                    .ok_or_else(|| format!("Invalid cron step in \"{}\"", part))?;
+                (range, step)
+            }
+            None => (part, 1),
+        };
+        let (start, end) = if range == "*" {
+            (min, max)
+        } else if let Some((start, end)) = range.split_once('-') {
+            (
+                parse_cron_value(start, min, max, names)?,
+                parse_cron_value(end, min, max, names)?,
+            )
+        } else {
+            let start = parse_cron_value(range, min, max, names)?;
+            // `5/15` runs every 15 starting at 5.
+            (start, if part.contains('/') { max } else { start })
+        };
+
+        let size = max - min + 1;
+        let span = if end >= start {
+            end - start
+        } else {
+            end + size - start
+        };
+        let mut offset = 0;
+        while offset <= span {
+            bits |= 1 << (min + (start - min + offset) % size);
+            offset += step;
+        }
+    }
+    Ok(bits)
+}
+
+impl CronSchedule {
+    pub(crate) fn parse(expression: &str) -> Result<Self, String> {
+        let expression = expression.trim();
+        let expanded = match expression.to_ascii_lowercase().as_str() {
+            "@hourly" => "0 * * * *",
+            "@daily" | "@midnight" => "0 0 * * *",
+            "@weekly" => "0 0 * * 0",
+            "@monthly" => "0 0 1 * *",
+            "@yearly" | "@annually" => "0 0 1 1 *",
+            _ => expression,
+        };
+        let fields: Vec<&str> = expanded.split_whitespace().collect();
+        let [minute, hour, day_of_month, month, day_of_week] = fields[..] else {
+            return Err(
+                "A schedule needs five fields: minute hour day-of-month month day-of-week"
+                    .to_string(),
+            );
+        };
+
+        // Both 0 and 7 mean Sunday.
+        let mut days_of_week = parse_cron_field(day_of_week, 0, 7, WEEKDAY_NAMES)?;
+        if days_of_week & (1 << 7) != 0 {
+            days_of_week = (days_of_week & !(1 << 7)) | 1;
+        }
+
+        Ok(Self {
+            minutes: parse_cron_field(minute, 0, 59, &[])?,
+            hours: parse_cron_field(hour, 0, 23, &[])?,
+            days_of_month: parse_cron_field(day_of_month, 1, 31, &[])?,
+            months: parse_cron_field(month, 1, 12, MONTH_NAMES)?,
+            days_of_week,
+            any_day_of_month: day_of_month.starts_with('*'),
+            any_day_of_week: day_of_week.starts_with('*'),
+        })
+    }
+
+    fn matches_day(&self, date: NaiveDate) -> bool {
+        let day_of_month = self.days_of_month & (1 << date.day()) != 0;
+        let day_of_week = self.days_of_week & (1 << date.weekday().num_days_from_sunday()) != 0;
+        let day = if self.any_day_of_month || self.any_day_of_week {
+            day_of_month && day_of_week
+        } else {
+            day_of_month || day_of_week
+        };
+        day && self.months & (1 << date.month()) != 0
+    }
+
+    /// First matching minute strictly after `after`. Searches eight years ahead so
+    /// a February 29 schedule still resolves; `None` means it never runs.
+    pub(crate) fn next_after(&self, after: NaiveDateTime) -> Option<NaiveDateTime> {
+        let start = after.date();
+        for offset in 0..8 * 366 {
+            let date = start + Duration::days(offset);
+            if !self.matches_day(date) {
+                continue;
+            }
+            for hour in (0..24).filter(|hour| self.hours & (1 << hour) != 0) {
+                for minute in (0..60).filter(|minute| self.minutes & (1 << minute) != 0) {
+                    let candidate = date.and_hms_opt(hour, minute, 0)?;
+                    if candidate > after {
+                        return Some(candidate);
+                    }
+                }
+            }
+        }
+        None
+    }
+
+    /// Whether a matching minute falls in `(after, until]`.
+    pub(crate) fn due_between(&self, after: NaiveDateTime, until: NaiveDateTime) -> bool {
+        let after = after.max(until - Duration::minutes(MAX_CATCH_UP_MINUTES));
+        self.next_after(after).is_some_and(|next| next <= until)
+    }
+}
+
+fn job_default(job: &str) -> Result<(&'static str, bool), String> {
+    SCHEDULED_JOB_DEFAULTS
+        .iter()
+        .find(|(known_job, _, _)| *known_job == job)
+        .map(|(_, schedule, enabled)| (*schedule, *enabled))
+        .ok_or_else(|| format!("Unknown job \"{}\"", job))
+}
+
+pub(crate) fn list_scheduled_jobs_in_conn(
+    conn: &Connection,
+    now: NaiveDateTime,
+) -> Result<Vec<ScheduledJob>, String> {
+    SCHEDULED_JOB_DEFAULTS
+        .iter()
+        .map(|(job, default_schedule, default_enabled)| {
+            let stored = conn
+                .query_row(
+                    "SELECT schedule, enabled, last_run_at, last_status, last_error
+                     FROM scheduled_jobs WHERE job = ?1",
+                    params![job],
+                    |row| {
+                        Ok((
+                            row.get::<_, String>(0)?,
+                            row.get::<_, bool>(1)?,
+                            row.get(2)?,
+                            row.get(3)?,
+                            row.get(4)?,
+                        ))
+                    },
+                )
+                .optional()
+                .map_err(|e| e.to_string())?;
+            let (schedule, enabled, last_run_at, last_status, last_error) =
+                stored.unwrap_or_else(|| {
+                    (
+                        default_schedule.to_string(),
+                        *default_enabled,
+                        None,
+                        None,
+                        None,
+                    )
+                });
+            let next_run_at = enabled
+                .then(|| CronSchedule::parse(&schedule).ok()?.next_after(now))
+                .flatten()
+                .map(|next| next.format("%Y-%m-%dT%H:%M:%S").to_string());
+
+            Ok(ScheduledJob {
+                job: job.to_string(),
+                schedule,
+                default_schedule: default_schedule.to_string(),
+                enabled,
+                next_run_at,
+                last_run_at,
+                last_status,
+                last_error,
+            })
+        })
+        .collect()
+}
+
+/// Stores a job's schedule; a blank schedule restores the default. Schedules that
+/// never match (e.g. `0 0 31 2 *`) are rejected.
+pub(crate) fn set_job_schedule_in_conn(
+    conn: &Connection,
+    job: &str,
+    schedule: &str,
+    enabled: bool,
+    now: NaiveDateTime,
+) -> Result<ScheduledJob, String> {
+    let (default_schedule, _) = job_default(job)?;
+    let schedule = match schedule.trim() {
+        "" => default_schedule,
+        schedule => schedule,
+    };
+    if CronSchedule::parse(schedule)?.next_after(now).is_none() {
+        return Err("This schedule never runs".to_string());
+    }
+
+    conn.execute(
+        "INSERT INTO scheduled_jobs (job, schedule, enabled, updated_at)
+         VALUES (?1, ?2, ?3, ?4)
+         ON CONFLICT(job) DO UPDATE SET
+             schedule = excluded.schedule,
+             enabled = excluded.enabled,
+             updated_at = excluded.updated_at",
+        params![job, schedule, enabled, Utc::now().to_rfc3339()],
+    )
+    .map_err(|e| e.to_string())?;
+
+    list_scheduled_jobs_in_conn(conn, now)?
+        .into_iter()
+        .find(|scheduled| scheduled.job == job)
+        .ok_or_else(|| format!("Unknown job \"{}\"", job))
+}
+
+/// Enabled jobs with a scheduled minute in `(after, until]`.
+pub(crate) fn due_scheduled_jobs_in_conn(
+    conn: &Connection,
+    after: NaiveDateTime,
+    until: NaiveDateTime,
+) -> Result<Vec<String>, String> {
+    Ok(list_scheduled_jobs_in_conn(conn, after)?
+        .into_iter()
+        .filter(|scheduled| {
+            scheduled.enabled
+                && CronSchedule::parse(&scheduled.schedule)
+                    .is_ok_and(|schedule| schedule.due_between(after, until))
+        })
+        .map(|scheduled| scheduled.job)
+        .collect())
+}
+
+pub(crate) fn record_job_run_in_conn(
+    conn: &Connection,
+    job: &str,
+    result: &Result<(), String>,
+    ran_at: &str,
+) -> Result<(), String> {
+    let (default_schedule, default_enabled) = job_default(job)?;
+    let (status, error) = match result {
+        Ok(()) => ("ok", None),
+        Err(error) => ("error", Some(error.as_str())),
+    };
+    conn.execute(
+        "INSERT INTO scheduled_jobs (job, schedule, enabled, last_run_at, last_status, last_error, updated_at)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?4)
+         ON CONFLICT(job) DO UPDATE SET
+             last_run_at = excluded.last_run_at,
+             last_status = excluded.last_status,
+             last_error = excluded.last_error",
+        params![job, default_schedule, default_enabled, ran_at, status, error],
+    )
+    .map_err(|e| e.to_string())?;
+
+    Ok(())
+}
+
+/// Copies the whole database into `dir` with `VACUUM INTO` and keeps the newest
+/// `SCHEDULED_BACKUP_KEEP` snapshots. Encrypted content stays encrypted.
+pub(crate) fn write_database_snapshot_in_conn(
+    conn: &Connection,
+    dir: &Path,
+    now: NaiveDateTime,
+) -> Result<PathBuf, String> {
+    fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+    let path = dir.join(format!(
+        "{}{}.db",
+        SCHEDULED_BACKUP_PREFIX,
+        now.format("%Y%m%d-%H%M%S")
+    ));
+    if path.exists() {
+        fs::remove_file(&path).map_err(|e| e.to_string())?;
+    }
+    conn.execute("VACUUM INTO ?1", params![path.to_string_lossy()])
+        .map_err(|e| e.to_string())?;
+
+    let mut snapshots: Vec<PathBuf> = fs::read_dir(dir)
+        .map_err(|e| e.to_string())?
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|candidate| {
+            candidate.file_name().is_some_and(|name| {
+                let name = name.to_string_lossy();
+                name.starts_with(SCHEDULED_BACKUP_PREFIX) && name.ends_with(".db")
+            })
+        })
+        .collect();
+    // Timestamped names sort chronologically.
+    snapshots.sort();
+    let excess = snapshots.len().saturating_sub(SCHEDULED_BACKUP_KEEP);
+    for old in snapshots.drain(..excess) {
+        if let Err(error) = fs::remove_file(&old) {
+            eprintln!("Failed to remove old backup {}: {error}", old.display());
+        }
+    }
+
+    Ok(path)
+}
+
+pub(crate) fn weekly_digest_in_conn(
+    conn: &Connection,
+    now: DateTime<Local>,
+) -> Result<WeeklyDigest, String> {
+    let today = now.date_naive().format("%Y-%m-%d").to_string();
+    let week_ago = now - Duration::days(7);
+    let entries = conn
+        .query_row(
+            "SELECT COUNT(*) FROM entries
+             WHERE deleted_at IS NULL AND date > ?1 AND date <= ?2",
+            params![week_ago.date_naive().format("%Y-%m-%d").to_string(), today],
+            |row| row.get(0),
+        )
+        .map_err(|e| e.to_string())?;
+    let tasks_done = conn
+        .query_row(
+            "SELECT COUNT(*) FROM tasks WHERE status = 'done' AND completed_at >= ?1",
+            params![week_ago.with_timezone(&Utc).to_rfc3339()],
+            |row| row.get(0),
+        )
+        .map_err(|e| e.to_string())?;
+    let overdue = conn
+        .query_row(
+            "SELECT COUNT(*) FROM tasks
+             WHERE status != 'done' AND due_date IS NOT NULL AND due_date < ?1
+               AND id NOT IN (SELECT task_id FROM review_queue)",
+            params![today],
+            |row| row.get(0),
+        )
+        .map_err(|e| e.to_string())?;
+
+    Ok(WeeklyDigest {
+        entries,
+        tasks_done,
+        overdue,
+    })
+}
+
+/// Moves open, non-recurring tasks that are past due to `today`. Recurring tasks
+/// are left alone because their next occurrence is derived from the due date.
+/// `updated_at` is not touched so the `in_progress_days` automation clock keeps
+/// running.
+pub(crate) fn rollover_overdue_tasks_in_conn(
+    conn: &Connection,
+    today: NaiveDate,
+) -> Result<usize, String> {
+    conn.execute(
+        "UPDATE tasks SET due_date = ?1
+         WHERE status != 'done' AND recurrence = 'none'
+           AND due_date IS NOT NULL AND due_date < ?1
+           AND id NOT IN (SELECT task_id FROM review_queue)",
+        params![today.format("%Y-%m-%d").to_string()],
+    )
+    .map_err(|e| e.to_string())
+}
+
+fn scheduled_backups_root(app: &AppHandle) -> Result<PathBuf, String> {
+    let app_data_dir = app.path().app_data_dir().map_err(|e| e.to_string())?;
+    Ok(app_data_dir.join("backups"))
+}
+
+fn send_weekly_digest(app: &AppHandle) -> Result<(), String> {
+    let (digest, (title_template, body_template)) = {
+        let state = app.state::<AppState>();
+        let conn = state.db.lock().map_err(|e| e.to_string())?;
+        (
+            weekly_digest_in_conn(&conn, Local::now())?,
+            notification_templates_in_conn(&conn, "digest")?,
+        )
+    };
+
+    let entries = digest.entries.to_string();
+    let tasks_done = digest.tasks_done.to_string();
+    let overdue = digest.overdue.to_string();
+    let values = [
+        ("entries", entries.as_str()),
+        ("tasks_done", tasks_done.as_str()),
+        ("overdue", overdue.as_str()),
+    ];
+    app.notification()
+        .builder()
+        .title(render_notification_template(&title_template, &values))
+        .body(render_notification_template(&body_template, &values))
+        .show()
+        .map_err(|e| e.to_string())?;
+    emit_event(app, "digest-ready", &digest).map_err(|e| e.to_string())
+}
+
+/// Runs `backup`, `digest`, `rollover`, `update_check` or `integrity`. `reminders`
+/// runs inside the scheduler loop, which keeps track of what was already sent.
+pub(crate) fn run_scheduled_job_for_app(app: &AppHandle, job: &str) -> Result<(), String> {
+    match job {
+        "backup" => {
+            let dir = scheduled_backups_root(app)?;
+            let state = app.state::<AppState>();
+            let conn = state.db.lock().map_err(|e| e.to_string())?;
+            write_database_snapshot_in_conn(&conn, &dir, Local::now().naive_local())?;
+            Ok(())
+        }
+        "digest" => send_weekly_digest(app),
+        "rollover" => {
+            let moved = {
+                let state = app.state::<AppState>();
+                let conn = state.db.lock().map_err(|e| e.to_string())?;
+                rollover_overdue_tasks_in_conn(&conn, Local::now().date_naive())?
+            };
+            if moved > 0 {
+                emit_event(app, "tasks-rolled-over", moved).map_err(|e| e.to_string())?;
+            }
+            Ok(())
+        }
+        "update_check" => super::updates::run_update_check_for_app(app),
+        "integrity" => super::integrity::run_integrity_check_for_app(app),
+        _ => Err(format!("Unknown job \"{}\"", job)),
+    }
+}
+
+pub(crate) fn due_scheduled_jobs_for_app(
+    app: &AppHandle,
+    after: NaiveDateTime,
+    until: NaiveDateTime,
+) -> Result<Vec<String>, String> {
+    let state = app.state::<AppState>();
+    let conn = state.db.lock().map_err(|e| e.to_string())?;
+    due_scheduled_jobs_in_conn(&conn, after, until)
+}
+
+pub(crate) fn record_job_run_for_app(
+    app: &AppHandle,
+    job: &str,
+    result: &Result<(), String>,
+) -> Result<(), String> {
+    let state = app.state::<AppState>();
+    let conn = state.db.lock().map_err(|e| e.to_string())?;
+    record_job_run_in_conn(&conn, job, result, &Utc::now().to_rfc3339())
+}
+
+#[tauri::command]
+pub fn list_scheduled_jobs(state: State<'_, AppState>) -> Result<Vec<ScheduledJob>, String> {
+    let conn = state.db.lock().map_err(|e| e.to_string())?;
+    list_scheduled_jobs_in_conn(&conn, Local::now().naive_local())
+}
+
+#[tauri::command]
+pub fn set_job_schedule(
+    job: String,
+    schedule: String,
+    enabled: bool,
+    state: State<'_, AppState>,
+) -> Result<ScheduledJob, String> {
+    let conn = state.db.lock().map_err(|e| e.to_string())?;
+    set_job_schedule_in_conn(&conn, &job, &schedule, enabled, Local::now().naive_local())
+}
@@ -0,0 +1,475 @@
+use crate::models::{Entry, MarkdownExportSummary, MarkdownImportSummary};
+use chrono::{NaiveDate, Utc};
+use rusqlite::{params, Connection, OptionalExtension};
+use serde::Deserialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+use tauri::State;
+
+use super::encryption::{JournalCipher, JournalContent};
+use super::entries::{
+    add_entry_tag_in_conn, discard_trashed_entry_in_conn, get_entries_between_in_conn,
+    get_entries_in_conn, get_entry_in_conn,
+};
+use super::validation::normalize_entry_tag;
+use super::AppState;
+
+/// Inclusive `YYYY-MM-DD` range used to limit exports.
+#[derive(Debug, Deserialize)]
+pub struct EntryDateRange {
+    pub start: String,
+    pub end: String,
+}
+
+/// Double-quoted YAML scalar, safe for any user text.
+pub(crate) fn yaml_string(value: &str) -> String {
+    let mut quoted = String::with_capacity(value.len() + 2);
+    quoted.push('"');
+    for character in value.chars() {
+        match character {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            '\r' => quoted.push_str("\\r"),
+            '\t' => quoted.push_str("\\t"),
+            _ => quoted.push(character),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+fn entry_tags(conn: &Connection, entry_id: i64) -> Result<Vec<String>, String> {
+    let mut stmt = conn
+        .prepare("SELECT tag FROM entry_tags WHERE entry_id = ?1 ORDER BY tag COLLATE NOCASE")
+        .map_err(|e| e.to_string())?;
+    let tags_iter = stmt
+        .query_map(params![entry_id], |row| row.get::<_, String>(0))
+        .map_err(|e| e.to_string())?;
+
+    let mut tags = Vec::new();
+    for tag in tags_iter {
+        tags.push(tag.map_err(|e| e.to_string())?);
+    }
+
+    Ok(tags)
+}
+
+/// Extra sections with their template label, falling back to the key for removed templates.
+fn entry_extra_sections(
+    conn: &Connection,
+    entry_id: i64,
+    cipher: &JournalCipher,
+) -> Result<Vec<(String, String)>, String> {
+    let mut stmt = conn
+        .prepare(
+            "SELECT COALESCE(t.label, s.section_key), s.content
+             FROM entry_sections s
+             LEFT JOIN entry_section_templates t ON t.section_key = s.section_key
+             WHERE s.entry_id = ?1 AND TRIM(s.content) != ''
+             ORDER BY s.sort_order ASC, s.id ASC",
+        )
+        .map_err(|e| e.to_string())?;
+    let sections_iter = stmt
+        .query_map(params![entry_id], |row| Ok((row.get(0)?, row.get(1)?)))
+        .map_err(|e| e.to_string())?;
+
+    let mut sections = Vec::new();
+    for section in sections_iter {
+        let (label, content): (String, String) = section.map_err(|e| e.to_string())?;
+        sections.push((label, cipher.decrypt(&content)?));
+    }
+
+    Ok(sections)
+}
+
+/// Renders an entry (already decrypted) as Markdown with a YAML frontmatter block;
+/// `cipher` decrypts its custom sections.
+pub(crate) fn entry_markdown_in_conn(
+    conn: &Connection,
+    entry: &Entry,
+    cipher: &JournalCipher,
+) -> Result<String, String> {
+    let project: Option<String> = match entry.project_id {
+        Some(project_id) => conn
+            .query_row(
+                "SELECT name FROM projects WHERE id = ?1",
+                params![project_id],
+                |row| row.get(0),
+            )
+            .optional()
+            .map_err(|e| e.to_string())?,
+        None => None,
+    };
+    let mood: Option<i64> = conn
+        .query_row(
+            "SELECT mood FROM daily_moods WHERE date = ?1",
+            params![entry.date],
+            |row| row.get(0),
+        )
+        .optional()
+        .map_err(|e| e.to_string())?;
+    let tags = entry_tags(conn, entry.id)?;
+
+    let mut markdown = String::from("---\n");
+    markdown.push_str(&format!("date: {}\n", entry.date));
+    markdown.push_str(&format!("created_at: {}\n", yaml_string(&entry.created_at)));
+    if let Some(project) = project {
+        markdown.push_str(&format!("project: {}\n", yaml_string(&project)));
+    }
+    let tags: Vec<String> = tags.iter().map(|tag| yaml_string(tag)).collect();
+    markdown.push_str(&format!("tags: [{}]\n", tags.join(", ")));
+    if let Some(mood) = mood {
+        markdown.push_str(&format!("mood: {}\n", mood));
+    }
+    markdown.push_str("---\n\n");
+    markdown.push_str(&format!("# {}\n", entry.date));
+
+    let mut sections = vec![
+        ("Yesterday".to_string(), entry.yesterday.clone()),
+        ("Today".to_string(), entry.today.clone()),
+    ];
+    for (label, content) in [("Blockers", &entry.blockers), ("Notes", &entry.notes)] {
+        if !content.trim().is_empty() {
+            sections.push((label.to_string(), content.clone()));
+        }
+    }
+    sections.extend(entry_extra_sections(conn, entry.id, cipher)?);
+    for (label, content) in sections {
+        markdown.push_str(&format!("\n## {}\n\n{}\n", label, content.trim_end()));
+    }
+
+    Ok(markdown)
+}
+
+/// Writes one `YYYY-MM-DD.md` per entry into `dir`, overwriting earlier exports of the same day.
+pub(crate) fn export_entries_markdown_in_conn(
+    conn: &Connection,
+    dir: &Path,
+    range: Option<&EntryDateRange>,
+    cipher: &JournalCipher,
+) -> Result<MarkdownExportSummary, String> {
+    let entries = match range {
+        Some(range) => get_entries_between_in_conn(conn, &range.start, &range.end)?,
+        None => get_entries_in_conn(conn, None, None)?,
+    }
+    .decrypt_with(cipher)?;
+
+    fs::create_dir_all(dir).map_err(|e| format!("Failed to create {}: {}", dir.display(), e))?;
+    let mut files_written = 0;
+    for entry in &entries {
+        let path = dir.join(format!("{}.md", entry.date));
+        fs::write(&path, entry_markdown_in_conn(conn, entry, cipher)?)
+            .map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+        files_written += 1;
+    }
+
+    Ok(MarkdownExportSummary {
+        directory: dir.display().to_string(),
+        files_written,
+    })
+}
+
+/// Reverses `yaml_string` for double-quoted values; single-quoted and bare values are
+/// taken as written.
+pub(crate) fn yaml_scalar(value: &str) -> String {
+    let value = value.trim();
+    if let Some(inner) = value
+        .strip_prefix('"')
+        .and_then(|rest| rest.strip_suffix('"'))
+    {
+        let mut unquoted = String::with_capacity(inner.len());
+        let mut chars = inner.chars();
+        while let Some(character) = chars.next() {
+            if character != '\\' {
+                unquoted.push(character);
+                continue;
+            }
+            match chars.next() {
+                Some('n') => unquoted.push('\n'),
+                Some('r') => unquoted.push('\r'),
+                Some('t') => unquoted.push('\t'),
+                Some(other) => unquoted.push(other),
+                None => unquoted.push('\\'),
+            }
+        }
+        return unquoted;
+    }
+    if let Some(inner) = value
+        .strip_prefix('\'')
+        .and_then(|rest| rest.strip_suffix('\''))
+    {
+        return inner.replace("''", "'");
+    }
+    value.to_string()
+}
+
+/// Daily note parsed from Markdown: optional frontmatter plus the standup sections.
+#[derive(Debug, Default, PartialEq)]
+pub(crate) struct MarkdownNote {
+    pub yesterday: String,
+    pub today: String,
+    pub blockers: String,
+    pub notes: String,
+    pub tags: Vec<String>,
+    pub project: Option<String>,
+    pub mood: Option<i64>,
+    pub created_at: Option<String>,
+}
+
+/// Parses a daily note as written by `entry_markdown_in_conn`, or any Markdown file
+/// with optional YAML frontmatter (Obsidian-style `tags: [a, b]` or `- a` lists).
+/// Text under `## Yesterday` / `## Today` / `## Blockers` / `## Notes` headings goes
+/// to those sections; without them, the whole body becomes "today". Other headings
+/// stay in "today" as written.
+pub(crate) fn parse_markdown_note(content: &str, date: &str) -> MarkdownNote {
+    let mut note = MarkdownNote::default();
+    let content = content.trim_start_matches('\u{feff}');
+    let mut lines = content.lines().peekable();
+
+    if lines.peek().is_some_and(|line| line.trim() == "---") {
+        lines.next();
+        let mut list_key: Option<String> = None;
+        for line in lines.by_ref() {
+            if line.trim() == "---" {
+                break;
+            }
+            if let Some(item) = line.trim_start().strip_prefix("- ") {
+                if list_key.as_deref() == Some("tags") {
+                    note.tags.push(yaml_scalar(item));
+                }
+                continue;
+            }
+            let Some((key, value)) = line.split_once(':') else {
+                continue;
+            };
+            let key = key.trim().to_lowercase();
+            let value = value.trim();
+            list_key = value.is_empty().then(|| key.clone());
+            match key.as_str() {
+                "tags" if value.starts_with('[') => {
+                    let items = value.trim_start_matches('[').trim_end_matches(']');
+                    note.tags.extend(
+                        items
+                            .split(',')
+                            .map(yaml_scalar)
+                            .filter(|tag| !tag.is_empty()),
+                    );
+                }
+                "tags" if !value.is_empty() => note.tags.push(yaml_scalar(value)),
+                "project" if !value.is_empty() => note.project = Some(yaml_scalar(value)),
+                "mood" => {
+                    note.mood = value
+                        .parse::<i64>()
+                        .ok()
+                        .filter(|mood| (1..=5).contains(mood))
+                }
+                "created_at" if !value.is_empty() => note.created_at = Some(yaml_scalar(value)),
+                _ => {}
+            }
+        }
+    }
+
+    // Split the body on `## ` headings; the `# <date>` title the exporter writes is dropped.
+    let mut chunks: Vec<(Option<String>, Vec<&str>)> = vec![(None, Vec::new())];
+    for line in lines {
+        if line.trim() == format!("# {}", date) {
+            continue;
+        }
+        match line.strip_prefix("## ") {
+            Some(heading) => chunks.push((Some(heading.trim().to_string()), Vec::new())),
+            None => {
+                if let Some((_, body)) = chunks.last_mut() {
+                    body.push(line);
+                }
+            }
+        }
+    }
+
+    let mut today_parts = Vec::new();
+    for (heading, body) in &chunks {
+        let text = body.join("\n").trim().to_string();
+        match heading.as_deref().map(str::to_lowercase).as_deref() {
+            Some("yesterday") => note.yesterday = text,
+            Some("blockers") => note.blockers = text,
+            Some("notes") => note.notes = text,
+            Some("today") => today_parts.push(text),
+            Some(_) if !text.is_empty() => today_parts.push(format!(
+                "## {}\n\n{}",
+                heading.as_deref().unwrap_or_default(),
+                text
+            )),
+            None if !text.is_empty() => today_parts.push(text),
+            _ => {}
+        }
+    }
+    note.today = today_parts
+        .into_iter()
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<_>>()
+        .join("\n\n");
+    note
+}
+
+/// Creates the entry for `date` from an imported note; the caller checks that the day
+/// has no entry yet. Empty notes are skipped (`None`); otherwise returns the attachment
+/// files of the trashed entry the new one replaces.
+pub(crate) fn insert_markdown_note_in_conn(
+    conn: &Connection,
+    date: &str,
+    note: MarkdownNote,
+    cipher: &JournalCipher,
+) -> Result<Option<Vec<String>>, String> {
+    if [&note.yesterday, &note.today, &note.blockers, &note.notes]
+        .iter()
+        .all(|text| text.is_empty())
+    {
+        return Ok(None);
+    }
+
+    let project_id: Option<i64> = match &note.project {
+        Some(project) => conn
+            .query_row(
+                "SELECT id FROM projects WHERE name = ?1 COLLATE NOCASE",
+                params![project],
+                |row| row.get(0),
+            )
+            .optional()
+            .map_err(|e| e.to_string())?,
+        None => None,
+    };
+    let discarded_attachments = discard_trashed_entry_in_conn(conn, date)?;
+    conn.execute(
+        "INSERT INTO entries (date, yesterday, today, blockers, notes, project_id, created_at)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+        params![
+            date,
+            cipher.encrypt(&note.yesterday)?,
+            cipher.encrypt(&note.today)?,
+            cipher.encrypt(&note.blockers)?,
+            cipher.encrypt(&note.notes)?,
+            project_id,
+            note.created_at.unwrap_or_else(|| Utc::now().to_rfc3339())
+        ],
+    )
+    .map_err(|e| e.to_string())?;
+    let entry_id = conn.last_insert_rowid();
+    for tag in &note.tags {
+        if normalize_entry_tag(tag).is_ok() {
+            add_entry_tag_in_conn(conn, entry_id, tag)?;
+        }
+    }
+    if let Some(mood) = note.mood {
+        conn.execute(
+            "INSERT OR IGNORE INTO daily_moods (date, mood, updated_at) VALUES (?1, ?2, ?3)",
+            params![date, mood, Utc::now().to_rfc3339()],
+        )
+        .map_err(|e| e.to_string())?;
+    }
+
+    Ok(Some(discarded_attachments))
+}
+
+fn collect_daily_note_files(dir: &Path, files: &mut Vec<(String, PathBuf)>) -> Result<(), String> {
+    let items =
+        fs::read_dir(dir).map_err(|e| format!("Failed to read {}: {}", dir.display(), e))?;
+    for item in items {
+        let path = item.map_err(|e| e.to_string())?.path();
+        if path.is_dir() {
+            collect_daily_note_files(&path, files)?;
+            continue;
+        }
+        let is_markdown = path
+            .extension()
+            .is_some_and(|ext| ext.eq_ignore_ascii_case("md"));
+        let stem = path
+            .file_stem()
+            .and_then(|stem| stem.to_str())
+            .unwrap_or_default();
+        if is_markdown && NaiveDate::parse_from_str(stem, "%Y-%m-%d").is_ok() {
+            files.push((stem.to_string(), path));
+        }
+    }
+
+    Ok(())
+}
+
+/// Imports every `YYYY-MM-DD.md` under `dir` (subfolders included) as that day's entry.
+/// Days that already have an entry are left alone and counted as skipped. Returns the
+/// attachment files of trashed entries that were replaced, for the caller to delete.
+pub(crate) fn import_markdown_folder_in_conn(
+    conn: &mut Connection,
+    dir: &Path,
+    cipher: &JournalCipher,
+) -> Result<(MarkdownImportSummary, Vec<String>), String> {
+    let mut files = Vec::new();
+    collect_daily_note_files(dir, &mut files)?;
+    files.sort();
+
+    let tx = conn.transaction().map_err(|e| e.to_string())?;
+    let mut summary = MarkdownImportSummary {
+        files_read: files.len() as i64,
+        entries_imported: 0,
+        skipped_existing: 0,
+    };
+    let mut discarded_attachments = Vec::new();
+    for (date, path) in &files {
+        if get_entry_in_conn(&tx, date)?.is_some() {
+            summary.skipped_existing += 1;
+            continue;
+        }
+        let content = fs::read_to_string(path)
+            .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+        let note = parse_markdown_note(&content, date);
+        if let Some(discarded) = insert_markdown_note_in_conn(&tx, date, note, cipher)? {
+            discarded_attachments.extend(discarded);
+            summary.entries_imported += 1;
+        }
+    }
+    tx.commit().map_err(|e| e.to_string())?;
+
+    Ok((summary, discarded_attachments))
+}
+
+#[tauri::command]
+pub fn export_entries_markdown(
+    dir: String,
+    range: Option<EntryDateRange>,
+    state: State<'_, AppState>,
+) -> Result<MarkdownExportSummary, String> {
+    let dir = dir.trim();
+    if dir.is_empty() {
+        return Err("Export folder is required".to_string());
+    }
+
+    let cipher = state.journal_cipher()?;
+    let conn = state.db.lock().map_err(|e| e.to_string())?;
+    export_entries_markdown_in_conn(&conn, &PathBuf::from(dir), range.as_ref(), &cipher)
+}
+
+#[tauri::command]
+pub fn import_markdown_folder(
+    dir: String,
+    app: tauri::AppHandle,
+    state: State<'_, AppState>,
+) -> Result<MarkdownImportSummary, String> {
+    let dir = dir.trim();
+    if dir.is_empty() {
+        return Err("Import folder is required".to_string());
+    }
+
+    let cipher = state.journal_cipher()?;
+    let mut conn = state.db.lock().map_err(|e| e.to_string())?;
+    let (summary, discarded_attachments) =
+        import_markdown_folder_in_conn(&mut conn, &PathBuf::from(dir), &cipher)?;
+
+    drop(conn);
+    if !discarded_attachments.is_empty() {
+        super::attachments::remove_attachment_files(
+            &super::attachments::attachments_root(&app)?,
+            &discarded_attachments,
+        );
+    }
+    super::achievements::evaluate_achievements_for_app(&app);
+    Ok(summary)
+}
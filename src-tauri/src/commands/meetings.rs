@@ -1,6 +1,6 @@
 use crate::models::{Meeting, MeetingActionItem, Task};
-use chrono::Utc;
-use rusqlite::{params, OptionalExtension};
+use chrono::{DateTime, Datelike, Duration, Local, NaiveDate, Utc, Weekday};
+use rusqlite::{params, Connection, OptionalExtension};
 use tauri::State;
 
 use super::validation::{
@@ -12,6 +12,96 @@ use super::validation::{
 };
 use super::AppState;
 
+/// Local start and end of one meeting occurrence.
+pub(crate) type MeetingOccurrence = (DateTime<Local>, DateTime<Local>);
+
+/// Start and end of every non-cancelled meeting occurrence that begins on a local day
+/// in `from..=to`. Recurring meetings are expanded like the planner does
+/// (`expandMeetingOccurrences`): same local time of day, up to `recurrence_until`.
+pub(crate) fn meeting_occurrences_in_conn(
+    conn: &Connection,
+    from: NaiveDate,
+    to: NaiveDate,
+) -> Result<Vec<MeetingOccurrence>, String> {
+    Ok(titled_meeting_occurrences_in_conn(conn, from, to)?
+        .into_iter()
+        .map(|(occurrence, _)| occurrence)
+        .collect())
+}
+
+/// `meeting_occurrences_in_conn` together with each meeting's title.
+pub(crate) fn titled_meeting_occurrences_in_conn(
+    conn: &Connection,
+    from: NaiveDate,
+    to: NaiveDate,
+) -> Result<Vec<(MeetingOccurrence, String)>, String> {
+    let mut stmt = conn
+        .prepare(
+            "SELECT start_at, end_at, recurrence, recurrence_until, title FROM meetings
+             WHERE status != 'cancelled'",
+        )
+        .map_err(|e| e.to_string())?;
+    let meetings_iter = stmt
+        .query_map([], |row| {
+            Ok((
+                row.get::<_, String>(0)?,
+                row.get::<_, String>(1)?,
+                row.get::<_, String>(2)?,
+                row.get::<_, Option<String>>(3)?,
+                row.get::<_, String>(4)?,
+            ))
+        })
+        .map_err(|e| e.to_string())?;
+
+    let mut occurrences = Vec::new();
+    for meeting in meetings_iter {
+        let (start_at, end_at, recurrence, recurrence_until, title) =
+            meeting.map_err(|e| e.to_string())?;
+        let (Ok(start), Ok(end)) = (
+            DateTime::parse_from_rfc3339(&start_at),
+            DateTime::parse_from_rfc3339(&end_at),
+        ) else {
+            continue;
+        };
+        let (start, end) = (start.with_timezone(&Local), end.with_timezone(&Local));
+
+        if recurrence == "none" {
+            if (from..=to).contains(&start.date_naive()) {
+                occurrences.push(((start, end), title));
+            }
+            continue;
+        }
+
+        let until = recurrence_until
+            .and_then(|until| NaiveDate::parse_from_str(&until, "%Y-%m-%d").ok())
+            .unwrap_or(to);
+        let mut day = from.max(start.date_naive());
+        while day <= to.min(until) {
+            let weekend = matches!(day.weekday(), Weekday::Sat | Weekday::Sun);
+            let included = match recurrence.as_str() {
+                "daily" => true,
+                "weekdays" => !weekend,
+                "weekly" => day.weekday() == start.weekday(),
+                _ => false,
+            };
+            let occurrence_start = day
+                .and_time(start.time())
+                .and_local_timezone(Local)
+                .earliest();
+            if let (true, Some(occurrence_start)) = (included, occurrence_start) {
+                occurrences.push((
+                    (occurrence_start, occurrence_start + (end - start)),
+                    title.clone(),
+                ));
+            }
+            day += Duration::days(1);
+        }
+    }
+
+    occurrences.sort();
+    Ok(occurrences)
+}
+
 #[tauri::command]
 pub fn get_meetings(state: State<'_, AppState>) -> Result<Vec<Meeting>, String> {
     let conn = state.db.lock().map_err(|e| e.to_string())?;
@@ -310,6 +400,8 @@ pub fn materialize_meeting_action_items(
             timer_accumulated_seconds: 0,
             created_at: now.clone(),
             updated_at: now.clone(),
+            subtask_count: 0,
+            subtasks_completed: 0,
         });
     }
 
@@ -0,0 +1,321 @@
+use crate::models::{Page, VaultMirrorSummary};
+use chrono::NaiveDate;
+use rusqlite::{params, Connection, OptionalExtension};
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+use tauri::State;
+
+use super::attachments::sanitize_file_name;
+use super::encryption::{journal_encryption_enabled_in_conn, JournalCipher};
+use super::entries::{get_entries_in_conn, get_entry_in_conn};
+use super::markdown::{entry_markdown_in_conn, yaml_string};
+use super::mirror_git::commit_mirror_if_enabled;
+use super::pages::{get_page_in_conn, get_pages_in_conn};
+use super::settings::read_setting;
+use super::AppState;
+
+/// Folder the vault mirror writes into; empty turns the mirror off.
+pub(crate) const VAULT_MIRROR_SETTING: &str = "vault_mirror_dir";
+
+const JOURNAL_FOLDER: &str = "journal";
+const PAGES_FOLDER: &str = "pages";
+/// Folders the mirror owns inside the mirror root.
+pub(crate) const MIRROR_FOLDERS: [&str; 2] = [JOURNAL_FOLDER, PAGES_FOLDER];
+
+/// The configured mirror folder, or `None` when the mirror is off. Encrypted journals
+/// are never mirrored, since the files would hold the plaintext.
+pub(crate) fn mirror_root_in_conn(conn: &Connection) -> Result<Option<PathBuf>, String> {
+    let dir = read_setting(conn, VAULT_MIRROR_SETTING)?;
+    if dir.trim().is_empty() || journal_encryption_enabled_in_conn(conn)? {
+        return Ok(None);
+    }
+    Ok(Some(PathBuf::from(dir.trim())))
+}
+
+pub(crate) fn page_tags(conn: &Connection, page_id: i64) -> Result<Vec<String>, String> {
+    let mut stmt = conn
+        .prepare("SELECT tag FROM page_tags WHERE page_id = ?1 ORDER BY tag COLLATE NOCASE")
+        .map_err(|e| e.to_string())?;
+    let tags_iter = stmt
+        .query_map(params![page_id], |row| row.get::<_, String>(0))
+        .map_err(|e| e.to_string())?;
+
+    let mut tags = Vec::new();
+    for tag in tags_iter {
+        tags.push(tag.map_err(|e| e.to_string())?);
+    }
+
+    Ok(tags)
+}
+
+/// Renders a page as Markdown with a YAML frontmatter block. `updated_at` is left out so
+/// autosaves that change nothing leave the file untouched.
+pub(crate) fn page_markdown_in_conn(conn: &Connection, page: &Page) -> Result<String, String> {
+    let tags: Vec<String> = page_tags(conn, page.id)?
+        .iter()
+        .map(|tag| yaml_string(tag))
+        .collect();
+
+    let mut markdown = String::from("---\n");
+    markdown.push_str(&format!("id: {}\n", page.id));
+    markdown.push_str(&format!("title: {}\n", yaml_string(&page.title)));
+    if let Some(parent_id) = page.parent_id {
+        markdown.push_str(&format!("parent_id: {}\n", parent_id));
+    }
+    markdown.push_str(&format!("created_at: {}\n", yaml_string(&page.created_at)));
+    markdown.push_str(&format!("tags: [{}]\n", tags.join(", ")));
+    markdown.push_str("---\n\n");
+    markdown.push_str(&format!(
+        "# {}\n\n{}\n",
+        page.title,
+        page.content.trim_end()
+    ));
+
+    Ok(markdown)
+}
+
+/// `pages/<id>-<title>.md`; the id prefix keeps the file findable after a rename.
+pub(crate) fn page_file_name(page: &Page) -> String {
+    let title = if page.title.trim().is_empty() {
+        "Untitled"
+    } else {
+        page.title.trim()
+    };
+    format!("{}-{}.md", page.id, sanitize_file_name(title))
+}
+
+/// Page id from a mirrored page file name, for pruning.
+fn page_file_id(file_name: &str) -> Option<i64> {
+    let stem = file_name.strip_suffix(".md")?;
+    stem.split('-').next()?.parse().ok()
+}
+
+/// Writes `contents` unless the file already holds exactly that, so an unchanged
+/// entry keeps its modification time and never shows up in `git status`.
+fn write_if_changed(path: &Path, contents: &str) -> Result<bool, String> {
+    if fs::read_to_string(path).is_ok_and(|current| current == contents) {
+        return Ok(false);
+    }
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create {}: {}", parent.display(), e))?;
+    }
+    fs::write(path, contents).map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+    Ok(true)
+}
+
+fn remove_file(path: &Path) -> Result<(), String> {
+    match fs::remove_file(path) {
+        Ok(()) => Ok(()),
+        Err(error) if error.kind() == std::io::ErrorKind::NotFound => Ok(()),
+        Err(error) => Err(format!("Failed to remove {}: {}", path.display(), error)),
+    }
+}
+
+/// Mirrored page files in `pages/` with their ids; other files are never touched.
+fn mirrored_page_files(root: &Path) -> Result<Vec<(i64, PathBuf)>, String> {
+    let dir = root.join(PAGES_FOLDER);
+    let read_dir = match fs::read_dir(&dir) {
+        Ok(read_dir) => read_dir,
+        Err(error) if error.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(error) => return Err(format!("Failed to read {}: {}", dir.display(), error)),
+    };
+
+    let mut files = Vec::new();
+    for item in read_dir {
+        let path = item.map_err(|e| e.to_string())?.path();
+        let id = path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .and_then(page_file_id);
+        if let Some(id) = id.filter(|_| path.is_file()) {
+            files.push((id, path));
+        }
+    }
+
+    Ok(files)
+}
+
+fn mirror_entry_into(conn: &Connection, root: &Path, date: &str) -> Result<(), String> {
+    if NaiveDate::parse_from_str(date, "%Y-%m-%d").is_err() {
+        return Err(format!("Invalid entry date: {}", date));
+    }
+    let path = root.join(JOURNAL_FOLDER).join(format!("{}.md", date));
+    match get_entry_in_conn(conn, date)? {
+        Some(entry) => write_if_changed(
+            &path,
+            &entry_markdown_in_conn(conn, &entry, &JournalCipher::default())?,
+        )
+        .map(|_| ()),
+        None => remove_file(&path),
+    }
+}
+
+fn mirror_page_into(conn: &Connection, root: &Path, page_id: i64) -> Result<(), String> {
+    // Locked pages are not mirrored; locking one removes its file.
+    let page = get_page_in_conn(conn, page_id)?.filter(|page| !page.locked);
+    let current = page.as_ref().map(page_file_name);
+    // A rename or delete leaves the old `<id>-*.md` behind otherwise.
+    for (id, path) in mirrored_page_files(root)? {
+        let is_current = path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .is_some_and(|name| current.as_deref() == Some(name));
+        if id == page_id && !is_current {
+            remove_file(&path)?;
+        }
+    }
+    if let (Some(page), Some(file_name)) = (page, current) {
+        let path = root.join(PAGES_FOLDER).join(file_name);
+        write_if_changed(&path, &page_markdown_in_conn(conn, &page)?)?;
+    }
+
+    Ok(())
+}
+
+/// Brings `journal/<date>.md` in line with the entry, removing it once the entry is
+/// trashed. Does nothing while the mirror is off.
+pub(crate) fn mirror_entry_in_conn(conn: &Connection, date: &str) -> Result<(), String> {
+    if let Some(root) = mirror_root_in_conn(conn)? {
+        mirror_entry_into(conn, &root, date)?;
+        commit_mirror_if_enabled(conn, &root);
+    }
+
+    Ok(())
+}
+
+/// Brings `pages/<id>-<title>.md` in line with the page, removing it once the page is
+/// deleted. Does nothing while the mirror is off.
+pub(crate) fn mirror_page_in_conn(conn: &Connection, page_id: i64) -> Result<(), String> {
+    if let Some(root) = mirror_root_in_conn(conn)? {
+        mirror_page_into(conn, &root, page_id)?;
+        commit_mirror_if_enabled(conn, &root);
+    }
+
+    Ok(())
+}
+
+/// Best-effort variant used after saves: SQLite stays the source of truth, so a
+/// missing or read-only mirror folder never fails the save itself.
+pub(crate) fn mirror_entry(conn: &Connection, date: &str) {
+    if let Err(error) = mirror_entry_in_conn(conn, date) {
+        eprintln!("Failed to mirror entry {date}: {error}");
+    }
+}
+
+/// `mirror_entry` for commands that only know the entry id.
+pub(crate) fn mirror_entry_by_id(conn: &Connection, entry_id: i64) {
+    let date = conn
+        .query_row(
+            "SELECT date FROM entries WHERE id = ?1",
+            params![entry_id],
+            |row| row.get::<_, String>(0),
+        )
+        .optional();
+    match date {
+        Ok(Some(date)) => mirror_entry(conn, &date),
+        Ok(None) => {}
+        Err(error) => eprintln!("Failed to mirror entry {entry_id}: {error}"),
+    }
+}
+
+/// Best-effort variant of `mirror_page_in_conn`, see `mirror_entry`.
+pub(crate) fn mirror_page(conn: &Connection, page_id: i64) {
+    if let Err(error) = mirror_page_in_conn(conn, page_id) {
+        eprintln!("Failed to mirror page {page_id}: {error}");
+    }
+}
+
+/// Best-effort full pass after bulk changes such as a date shift, which would
+/// otherwise mirror (and auto-commit) every entry on its own.
+pub(crate) fn resync_vault_mirror(conn: &Connection) {
+    let result = mirror_root_in_conn(conn).and_then(|root| match root {
+        Some(root) => {
+            sync_vault_mirror_in_conn(conn, &root).map(|_| commit_mirror_if_enabled(conn, &root))
+        }
+        None => Ok(()),
+    });
+    if let Err(error) = result {
+        eprintln!("Failed to sync the vault mirror: {error}");
+    }
+}
+
+/// Rewrites every entry and page under `root` and removes mirror files whose entry or
+/// page is gone. Files the mirror did not create (a `.git` folder, notes) are left alone.
+pub(crate) fn sync_vault_mirror_in_conn(
+    conn: &Connection,
+    root: &Path,
+) -> Result<VaultMirrorSummary, String> {
+    let mut summary = VaultMirrorSummary {
+        directory: root.display().to_string(),
+        entries_written: 0,
+        pages_written: 0,
+        files_removed: 0,
+    };
+
+    let journal_dir = root.join(JOURNAL_FOLDER);
+    let mut entry_files = HashSet::new();
+    for entry in get_entries_in_conn(conn, None, None)? {
+        let file_name = format!("{}.md", entry.date);
+        let markdown = entry_markdown_in_conn(conn, &entry, &JournalCipher::default())?;
+        if write_if_changed(&journal_dir.join(&file_name), &markdown)? {
+            summary.entries_written += 1;
+        }
+        entry_files.insert(file_name);
+    }
+    if let Ok(read_dir) = fs::read_dir(&journal_dir) {
+        for item in read_dir {
+            let path = item.map_err(|e| e.to_string())?.path();
+            let Some(file_name) = path.file_name().and_then(|name| name.to_str()) else {
+                continue;
+            };
+            let is_entry_file = file_name
+                .strip_suffix(".md")
+                .is_some_and(|date| NaiveDate::parse_from_str(date, "%Y-%m-%d").is_ok());
+            if is_entry_file && !entry_files.contains(file_name) {
+                remove_file(&path)?;
+                summary.files_removed += 1;
+            }
+        }
+    }
+
+    let pages: Vec<Page> = get_pages_in_conn(conn)?
+        .into_iter()
+        .filter(|page| !page.locked)
+        .collect();
+    let page_files: HashSet<PathBuf> = pages
+        .iter()
+        .map(|page| root.join(PAGES_FOLDER).join(page_file_name(page)))
+        .collect();
+    for (_, path) in mirrored_page_files(root)? {
+        if !page_files.contains(&path) {
+            remove_file(&path)?;
+            summary.files_removed += 1;
+        }
+    }
+    for page in &pages {
+        let path = root.join(PAGES_FOLDER).join(page_file_name(page));
+        if write_if_changed(&path, &page_markdown_in_conn(conn, page)?)? {
+            summary.pages_written += 1;
+        }
+    }
+
+    Ok(summary)
+}
+
+/// Full pass over the configured mirror folder, used when the mirror is first turned
+/// on or the folder was edited by hand. Saves keep it current afterwards.
+#[tauri::command]
+pub fn sync_vault_mirror(state: State<'_, AppState>) -> Result<VaultMirrorSummary, String> {
+    let conn = state.db.lock().map_err(|e| e.to_string())?;
+    if journal_encryption_enabled_in_conn(&conn)? {
+        return Err("The vault mirror is paused while journal encryption is on".to_string());
+    }
+    let root =
+        mirror_root_in_conn(&conn)?.ok_or_else(|| "Choose a mirror folder first".to_string())?;
+    let summary = sync_vault_mirror_in_conn(&conn, &root)?;
+    commit_mirror_if_enabled(&conn, &root);
+
+    Ok(summary)
+}
@@ -0,0 +1,213 @@
+use crate::models::MirrorCommit;
+use chrono::{DateTime, FixedOffset, Local, NaiveDate};
+use git2::{Commit, IndexAddOption, Oid, Repository, Signature, Sort};
+use std::path::{Component, Path, PathBuf};
+use tauri::State;
+
+use super::mirror::{mirror_root_in_conn, MIRROR_FOLDERS};
+use super::settings::read_bool_setting;
+use super::AppState;
+
+/// Commits the vault mirror into a git repository in the mirror folder.
+pub(crate) const VAULT_MIRROR_GIT_SETTING: &str = "vault_mirror_git";
+
+const MIRROR_HISTORY_LIMIT: usize = 200;
+
+fn git_error(error: git2::Error) -> String {
+    error.message().to_string()
+}
+
+/// Message of the auto-commit for `day`; saves on the same day fold into it.
+fn mirror_commit_message(day: NaiveDate) -> String {
+    format!("Journal mirror {}", day.format("%Y-%m-%d"))
+}
+
+/// Whether a remote-tracking branch already contains `commit`, which makes it unsafe
+/// to amend.
+fn is_published(repo: &Repository, commit: Oid) -> Result<bool, String> {
+    for reference in repo.references_glob("refs/remotes/*").map_err(git_error)? {
+        let Some(target) = reference.map_err(git_error)?.target() else {
+            continue;
+        };
+        if target == commit
+            || repo
+                .graph_descendant_of(target, commit)
+                .map_err(git_error)?
+        {
+            return Ok(true);
+        }
+    }
+
+    Ok(false)
+}
+
+/// Stages `journal/` and `pages/` (other files in the folder are left alone) and
+/// commits them if anything changed. The first save of a day makes a new commit; later
+/// saves that day amend it while it is unpublished, so history stays one commit per
+/// day. Returns the new commit, or `None` when the mirror already matched `HEAD`.
+pub(crate) fn commit_mirror_changes(root: &Path, day: NaiveDate) -> Result<Option<Oid>, String> {
+    let repo = match Repository::open(root) {
+        Ok(repo) => repo,
+        Err(_) => Repository::init(root).map_err(git_error)?,
+    };
+    let mut index = repo.index().map_err(git_error)?;
+    index
+        .add_all(MIRROR_FOLDERS, IndexAddOption::DEFAULT, None)
+        .map_err(git_error)?;
+    index.update_all(MIRROR_FOLDERS, None).map_err(git_error)?;
+    index.write().map_err(git_error)?;
+    let tree = repo
+        .find_tree(index.write_tree().map_err(git_error)?)
+        .map_err(git_error)?;
+
+    let head: Option<Commit<'_>> = repo.head().ok().and_then(|head| head.peel_to_commit().ok());
+    if head
+        .as_ref()
+        .is_some_and(|head| head.tree_id() == tree.id())
+    {
+        return Ok(None);
+    }
+
+    let signature = repo
+        .signature()
+        .or_else(|_| Signature::now("Dev Journal", "dev-journal@localhost"))
+        .map_err(git_error)?;
+    let message = mirror_commit_message(day);
+    let oid = match head {
+        Some(head)
+            if head.message() == Some(message.as_str()) && !is_published(&repo, head.id())? =>
+        {
+            head.amend(
+                Some("HEAD"),
+                None,
+                Some(&signature),
+                None,
+                None,
+                Some(&tree),
+            )
+            .map_err(git_error)?
+        }
+        Some(head) => repo
+            .commit(
+                Some("HEAD"),
+                &signature,
+                &signature,
+                &message,
+                &tree,
+                &[&head],
+            )
+            .map_err(git_error)?,
+        None => repo
+            .commit(Some("HEAD"), &signature, &signature, &message, &tree, &[])
+            .map_err(git_error)?,
+    };
+
+    Ok(Some(oid))
+}
+
+/// Auto-commit after a mirror write, when turned on. Errors are logged like other
+/// mirror failures and never fail the save.
+pub(crate) fn commit_mirror_if_enabled(conn: &rusqlite::Connection, root: &Path) {
+    let result = read_bool_setting(conn, VAULT_MIRROR_GIT_SETTING).and_then(|enabled| {
+        if enabled {
+            commit_mirror_changes(root, Local::now().date_naive())
+        } else {
+            Ok(None)
+        }
+    });
+    if let Err(error) = result {
+        eprintln!("Failed to commit the vault mirror: {error}");
+    }
+}
+
+/// `path` relative to the mirror folder; absolute paths inside it are accepted too.
+fn relative_mirror_path(root: &Path, path: &str) -> Result<PathBuf, String> {
+    let path = Path::new(path.trim());
+    let relative = if path.is_absolute() {
+        path.strip_prefix(root)
+            .map_err(|_| format!("{} is outside the mirror folder", path.display()))?
+    } else {
+        path
+    };
+    if relative
+        .components()
+        .any(|component| !matches!(component, Component::Normal(_)))
+    {
+        return Err(format!("Invalid mirror path: {}", relative.display()));
+    }
+
+    Ok(relative.to_path_buf())
+}
+
+fn commit_time(commit: &Commit<'_>) -> String {
+    let time = commit.time();
+    FixedOffset::east_opt(time.offset_minutes() * 60)
+        .and_then(|offset| {
+            DateTime::from_timestamp(time.seconds(), 0).map(|utc| utc.with_timezone(&offset))
+        })
+        .map(|time| time.to_rfc3339())
+        .unwrap_or_default()
+}
+
+/// Commits of the mirror repository, newest first. With a `path` (such as
+/// `journal/2026-05-06.md`) only commits that changed that file are listed.
+pub(crate) fn mirror_history(root: &Path, path: &str) -> Result<Vec<MirrorCommit>, String> {
+    let repo = Repository::open(root)
+        .map_err(|_| "The mirror folder has no git history yet".to_string())?;
+    let path = if path.trim().is_empty() {
+        None
+    } else {
+        Some(relative_mirror_path(root, path)?)
+    };
+    if repo.head().is_err() {
+        return Ok(Vec::new());
+    }
+
+    let mut walk = repo.revwalk().map_err(git_error)?;
+    walk.set_sorting(Sort::TOPOLOGICAL | Sort::TIME)
+        .map_err(git_error)?;
+    walk.push_head().map_err(git_error)?;
+
+    let mut history = Vec::new();
+    for oid in walk {
+        let commit = repo
+            .find_commit(oid.map_err(git_error)?)
+            .map_err(git_error)?;
+        if let Some(path) = &path {
+            let blob_at = |commit: &Commit<'_>| {
+                commit
+                    .tree()
+                    .ok()
+                    .and_then(|tree| tree.get_path(path).ok())
+                    .map(|entry| entry.id())
+            };
+            let before = commit.parent(0).ok().and_then(|parent| blob_at(&parent));
+            if blob_at(&commit) == before {
+                continue;
+            }
+        }
+        history.push(MirrorCommit {
+            id: commit.id().to_string(),
+            summary: commit.summary().unwrap_or_default().to_string(),
+            committed_at: commit_time(&commit),
+        });
+        if history.len() >= MIRROR_HISTORY_LIMIT {
+            break;
+        }
+    }
+
+    Ok(history)
+}
+
+/// Git history of the vault mirror; `path` limits it to one mirrored file.
+#[tauri::command]
+pub fn get_mirror_history(
+    path: String,
+    state: State<'_, AppState>,
+) -> Result<Vec<MirrorCommit>, String> {
+    let root = {
+        let conn = state.db.lock().map_err(|e| e.to_string())?;
+        mirror_root_in_conn(&conn)?.ok_or_else(|| "Choose a mirror folder first".to_string())?
+    };
+    mirror_history(&root, &path)
+}
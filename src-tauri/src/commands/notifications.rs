@@ -0,0 +1,134 @@
+use crate::models::NotificationTemplate;
+use chrono::NaiveTime;
+use rusqlite::Connection;
+use tauri::State;
+
+use super::settings::{default_setting, read_setting};
+use super::AppState;
+
+/// Notification types whose text can be customized, with the placeholders each
+/// template accepts and sample values for the Settings preview. The title and body
+/// live in the `notification_<kind>_title` / `_body` app settings.
+const NOTIFICATION_TEMPLATE_KINDS: &[(&str, &[(&str, &str)])] = &[
+    ("journal_reminder", &[("date", "2026-05-04")]),
+    ("streak_risk", &[("habit", "Read 20 pages"), ("days", "12")]),
+    (
+        "break_reminder",
+        &[("task", "Refactor sync worker"), ("duration", "1h 32m")],
+    ),
+    (
+        "due_reminder",
+        &[
+            ("task", "Ship release notes"),
+            ("time_left", "1h"),
+            ("date", "2026-05-08"),
+        ],
+    ),
+    (
+        "goal_checkin",
+        &[("goals", "\"Ship v2\", \"Learn Rust\""), ("count", "2")],
+    ),
+    ("timebox", &[("label", "Inbox zero"), ("minutes", "25")]),
+    (
+        "digest",
+        &[("entries", "5"), ("tasks_done", "12"), ("overdue", "2")],
+    ),
+    (
+        "update_available",
+        &[("version", "1.2.0"), ("channel", "stable")],
+    ),
+];
+
+/// Replaces each `{name}` with its value. Unknown placeholders are left as typed so
+/// a misspelling shows up in the notification instead of silently disappearing.
+pub(crate) fn render_notification_template(template: &str, values: &[(&str, &str)]) -> String {
+    let mut rendered = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        rendered.push_str(&rest[..start]);
+        let after = &rest[start + 1..];
+        let value = after.find('}').and_then(|end| {
+            values
+                .iter()
+                .find(|(name, _)| *name == &after[..end])
+                .map(|(_, value)| (*value, end))
+        });
+        match value {
+            Some((value, end)) => {
+                rendered.push_str(value);
+                rest = &after[end + 1..];
+            }
+            None => {
+                rendered.push('{');
+                rest = after;
+            }
+        }
+    }
+    rendered.push_str(rest);
+    rendered
+}
+
+/// Whether `time` falls between `quiet_hours_start` and `quiet_hours_end` (local
+/// `HH:MM`, the end excluded). The range may wrap past midnight; a blank or invalid
+/// bound turns quiet hours off.
+pub(crate) fn in_quiet_hours_in_conn(conn: &Connection, time: NaiveTime) -> Result<bool, String> {
+    let parse = |key: &str| -> Result<Option<NaiveTime>, String> {
+        Ok(NaiveTime::parse_from_str(read_setting(conn, key)?.trim(), "%H:%M").ok())
+    };
+    let (Some(start), Some(end)) = (parse("quiet_hours_start")?, parse("quiet_hours_end")?) else {
+        return Ok(false);
+    };
+
+    Ok(if start <= end {
+        start <= time && time < end
+    } else {
+        time >= start || time < end
+    })
+}
+
+fn read_template_setting(conn: &Connection, key: &str) -> Result<String, String> {
+    let value = read_setting(conn, key)?;
+    if value.trim().is_empty() {
+        return Ok(default_setting(key).unwrap_or_default().to_string());
+    }
+    Ok(value)
+}
+
+/// The `(title, body)` templates for a notification type; a blank setting falls
+/// back to the built-in text.
+pub(crate) fn notification_templates_in_conn(
+    conn: &Connection,
+    kind: &str,
+) -> Result<(String, String), String> {
+    Ok((
+        read_template_setting(conn, &format!("notification_{}_title", kind))?,
+        read_template_setting(conn, &format!("notification_{}_body", kind))?,
+    ))
+}
+
+pub(crate) fn get_notification_templates_in_conn(
+    conn: &Connection,
+) -> Result<Vec<NotificationTemplate>, String> {
+    NOTIFICATION_TEMPLATE_KINDS
+        .iter()
+        .map(|(kind, samples)| {
+            let (title, body) = notification_templates_in_conn(conn, kind)?;
+            Ok(NotificationTemplate {
+                kind: kind.to_string(),
+                placeholders: samples.iter().map(|(name, _)| name.to_string()).collect(),
+                preview_title: render_notification_template(&title, samples),
+                preview_body: render_notification_template(&body, samples),
+                title,
+                body,
+            })
+        })
+        .collect()
+}
+
+#[tauri::command]
+pub fn get_notification_templates(
+    state: State<'_, AppState>,
+) -> Result<Vec<NotificationTemplate>, String> {
+    let conn = state.db.lock().map_err(|e| e.to_string())?;
+    get_notification_templates_in_conn(&conn)
+}
@@ -0,0 +1,468 @@
+use crate::models::ObsidianImportSummary;
+use chrono::{NaiveDate, Utc};
+use rusqlite::{params, Connection, OptionalExtension};
+use std::collections::{BTreeSet, HashMap};
+use std::fs;
+use std::path::{Path, PathBuf};
+use tauri::{AppHandle, State};
+
+use super::attachments::{attachments_root, mime_type_for, remove_attachment_files};
+use super::encryption::{JournalCipher, JournalContent};
+use super::entries::get_entry_in_conn;
+use super::markdown::{insert_markdown_note_in_conn, parse_markdown_note};
+use super::mirror::{mirror_entry, mirror_page};
+use super::page_assets::{page_asset_url, page_assets_root, save_page_asset_in_conn};
+use super::page_properties::set_page_properties_in_conn;
+use super::pages::{
+    add_page_tag_in_conn, create_page_in_conn, get_page_children_in_conn, set_page_links_in_conn,
+    snapshot_page_in_conn,
+};
+use super::validation::normalize_entry_tag;
+use super::AppState;
+
+/// A note outside the daily notes, with the vault folders it sits in.
+struct VaultNote {
+    folders: Vec<String>,
+    title: String,
+    path: PathBuf,
+}
+
+#[derive(Default)]
+struct VaultFiles {
+    notes: Vec<VaultNote>,
+    daily_notes: Vec<(String, PathBuf)>,
+    /// Every other file by lower-cased file name, for resolving `![[image.png]]`.
+    attachments: HashMap<String, PathBuf>,
+}
+
+/// Walks the vault; dot-folders such as `.obsidian` and `.trash` are skipped.
+fn walk_vault(dir: &Path, folders: &mut Vec<String>, files: &mut VaultFiles) -> Result<(), String> {
+    let mut items = Vec::new();
+    for item in fs::read_dir(dir).map_err(|e| format!("Failed to read {}: {}", dir.display(), e))? {
+        items.push(item.map_err(|e| e.to_string())?.path());
+    }
+    items.sort();
+
+    for path in items {
+        let Some(name) = path.file_name().and_then(|name| name.to_str()) else {
+            continue;
+        };
+        if name.starts_with('.') {
+            continue;
+        }
+        if path.is_dir() {
+            folders.push(name.to_string());
+            walk_vault(&path, folders, files)?;
+            folders.pop();
+            continue;
+        }
+        let is_markdown = path
+            .extension()
+            .is_some_and(|extension| extension.eq_ignore_ascii_case("md"));
+        let stem = path
+            .file_stem()
+            .and_then(|stem| stem.to_str())
+            .unwrap_or_default()
+            .to_string();
+        if !is_markdown {
+            files
+                .attachments
+                .entry(name.to_lowercase())
+                .or_insert(path.clone());
+        } else if NaiveDate::parse_from_str(&stem, "%Y-%m-%d").is_ok() {
+            files.daily_notes.push((stem, path.clone()));
+        } else {
+            files.notes.push(VaultNote {
+                folders: folders.clone(),
+                title: stem,
+                path: path.clone(),
+            });
+        }
+    }
+
+    Ok(())
+}
+
+/// Splits off a leading `---` frontmatter block, returning its tags and the body.
+fn note_tags_and_body(content: &str) -> (Vec<String>, &str) {
+    let content = content.trim_start_matches('\u{feff}');
+    let Some(rest) = content
+        .strip_prefix("---\n")
+        .or_else(|| content.strip_prefix("---\r\n"))
+    else {
+        return (Vec::new(), content);
+    };
+    let mut offset = 0;
+    for line in rest.split_inclusive('\n') {
+        offset += line.len();
+        if line.trim() == "---" {
+            let frontmatter = &content[..content.len() - rest.len() + offset];
+            let tags = parse_markdown_note(frontmatter, "").tags;
+            return (tags, rest[offset..].trim_start_matches(['\r', '\n']));
+        }
+    }
+    (Vec::new(), content)
+}
+
+/// `[[Folder/Note#Heading|label]]` as a journal wiki link: pages are linked by title,
+/// so the folder path and heading are dropped.
+fn convert_wiki_link(inner: &str) -> String {
+    let (target, label) = match inner.split_once('|') {
+        Some((target, label)) => (target, Some(label.trim())),
+        None => (inner, None),
+    };
+    let target = target.split('#').next().unwrap_or_default();
+    let title = target.rsplit('/').next().unwrap_or_default().trim();
+    let title = title.strip_suffix(".md").unwrap_or(title);
+    match (title.is_empty(), label) {
+        (true, Some(label)) => label.to_string(),
+        (true, None) => inner.trim_start_matches('#').trim().to_string(),
+        (false, Some(label)) if !label.is_empty() => format!("[[{}|{}]]", title, label),
+        (false, _) => format!("[[{}]]", title),
+    }
+}
+
+fn is_image(file_name: &str) -> bool {
+    mime_type_for(file_name).starts_with("image/")
+}
+
+/// Rewrites Obsidian syntax into the page editor's Markdown: wiki links lose folder
+/// paths and headings, and image embeds (`![[a.png]]` or `![](a.png)`) are replaced by
+/// the URL `embed` returns. Images it cannot resolve stay as written.
+pub(crate) fn convert_obsidian_markdown(
+    body: &str,
+    embed: &mut dyn FnMut(&str) -> Option<String>,
+) -> String {
+    let mut converted = String::with_capacity(body.len());
+    let mut rest = body;
+    while let Some(start) = rest.find(['[', '!']) {
+        converted.push_str(&rest[..start]);
+        let tail = &rest[start..];
+
+        if let Some(inner) = tail
+            .strip_prefix("![[")
+            .and_then(|after| after.find("]]").map(|end| &after[..end]))
+            .filter(|inner| !inner.contains('\n'))
+        {
+            let target = inner.split('|').next().unwrap_or_default().trim();
+            let replacement = if is_image(target) {
+                embed(target).map(|url| {
+                    let name = target.rsplit('/').next().unwrap_or(target);
+                    format!("![{}]({})", name, url)
+                })
+            } else {
+                Some(convert_wiki_link(inner))
+            };
+            converted.push_str(&replacement.unwrap_or_else(|| format!("![[{}]]", inner)));
+            rest = &tail[inner.len() + 5..];
+            continue;
+        }
+        if let Some(inner) = tail
+            .strip_prefix("[[")
+            .and_then(|after| after.find("]]").map(|end| &after[..end]))
+            .filter(|inner| !inner.contains('\n') && !inner.contains("[["))
+        {
+            converted.push_str(&convert_wiki_link(inner));
+            rest = &tail[inner.len() + 4..];
+            continue;
+        }
+        if let Some((alt, url)) = tail.strip_prefix("![").and_then(|after| {
+            let alt_end = after.find("](")?;
+            let url_end = after[alt_end + 2..].find(')')?;
+            Some((
+                &after[..alt_end],
+                &after[alt_end + 2..alt_end + 2 + url_end],
+            ))
+        }) {
+            let local = !url.contains("://") && !url.starts_with("data:");
+            let target = url.trim().trim_start_matches('<').trim_end_matches('>');
+            let target = target.replace("%20", " ");
+            let used = alt.len() + url.len() + 5;
+            let embedded = if local && is_image(&target) {
+                embed(&target)
+            } else {
+                None
+            };
+            match embedded {
+                Some(new_url) => converted.push_str(&format!("![{}]({})", alt, new_url)),
+                None => converted.push_str(&tail[..used]),
+            }
+            rest = &tail[used..];
+            continue;
+        }
+
+        let character = tail.chars().next().unwrap_or_default();
+        converted.push(character);
+        rest = &tail[character.len_utf8()..];
+    }
+    converted.push_str(rest);
+    converted
+}
+
+/// URL of the image `target` refers to, stored as an asset of `page_id`. An asset with
+/// the same name and size is reused, so importing the vault again adds no copies.
+fn vault_image_url(
+    conn: &Connection,
+    assets_root: &Path,
+    page_id: i64,
+    attachments: &HashMap<String, PathBuf>,
+    target: &str,
+    assets_imported: &mut i64,
+) -> Option<String> {
+    let file_name = target.rsplit('/').next().unwrap_or(target).trim();
+    let path = attachments.get(&file_name.to_lowercase())?;
+    let bytes = fs::read(path).ok()?;
+    let existing: Option<String> = conn
+        .query_row(
+            "SELECT stored_path FROM page_assets
+             WHERE page_id = ?1 AND file_name = ?2 AND size_bytes = ?3",
+            params![page_id, file_name, bytes.len() as i64],
+            |row| row.get(0),
+        )
+        .optional()
+        .ok()?;
+    if let Some(stored_path) = existing {
+        return Some(page_asset_url(&stored_path));
+    }
+    let asset = save_page_asset_in_conn(conn, assets_root, page_id, &bytes, file_name).ok()?;
+    *assets_imported += 1;
+    Some(asset.url)
+}
+
+/// Finds or creates the page `title` under `parent_id`, then stores the converted
+/// note as its content. Returns the page id and whether it was created or changed.
+#[allow(clippy::too_many_arguments)]
+fn import_vault_page(
+    conn: &Connection,
+    parent_id: Option<i64>,
+    title: &str,
+    note: &str,
+    tags: &[String],
+    assets_root: &Path,
+    attachments: &HashMap<String, PathBuf>,
+    cipher: &JournalCipher,
+    summary: &mut ObsidianImportSummary,
+) -> Result<(i64, bool), String> {
+    let existing = get_page_children_in_conn(conn, parent_id)?
+        .into_iter()
+        .find(|page| page.title.trim().eq_ignore_ascii_case(title.trim()));
+    let (page_id, old_content, created) = match existing {
+        Some(page) => {
+            let page = page.decrypt_with(cipher)?;
+            (page.id, page.content, false)
+        }
+        None => {
+            let page = create_page_in_conn(conn, title, &cipher.encrypt("")?, parent_id)?;
+            (page.id, String::new(), true)
+        }
+    };
+
+    let content = convert_obsidian_markdown(note.trim_end(), &mut |target| {
+        vault_image_url(
+            conn,
+            assets_root,
+            page_id,
+            attachments,
+            target,
+            &mut summary.assets_imported,
+        )
+    });
+    let changed = content != old_content;
+    if changed {
+        if !created {
+            snapshot_page_in_conn(conn, page_id)?;
+        }
+        conn.execute(
+            "UPDATE pages SET content = ?1, updated_at = ?2 WHERE id = ?3",
+            params![cipher.encrypt(&content)?, Utc::now().to_rfc3339(), page_id],
+        )
+        .map_err(|e| e.to_string())?;
+        set_page_links_in_conn(conn, page_id, &content)?;
+        set_page_properties_in_conn(conn, page_id, &content)?;
+    }
+    for tag in tags {
+        if normalize_entry_tag(tag).is_ok() {
+            add_page_tag_in_conn(conn, page_id, tag)?;
+        }
+    }
+
+    if created {
+        summary.pages_created += 1;
+    } else if changed {
+        summary.pages_updated += 1;
+    }
+    Ok((page_id, created || changed))
+}
+
+/// What `import_obsidian_vault_in_conn` did, plus the pages and entries to mirror and
+/// the attachment files of replaced trashed entries.
+pub(crate) struct ObsidianImport {
+    pub summary: ObsidianImportSummary,
+    pub written_pages: Vec<i64>,
+    pub written_entries: Vec<String>,
+    pub discarded_attachments: Vec<String>,
+}
+
+/// Imports an Obsidian vault. `YYYY-MM-DD.md` daily notes become entries (days that
+/// already have one are skipped); other notes become top-level pages nested like the
+/// vault's folders. Folders become pages too, using a same-named sibling note as the
+/// folder's content when there is one. Pages are matched by title under their parent,
+/// so importing the vault again updates them.
+pub(crate) fn import_obsidian_vault_in_conn(
+    conn: &mut Connection,
+    vault: &Path,
+    assets_root: &Path,
+    cipher: &JournalCipher,
+) -> Result<ObsidianImport, String> {
+    if !vault.is_dir() {
+        return Err(format!("{} is not a folder", vault.display()));
+    }
+    let mut files = VaultFiles::default();
+    walk_vault(vault, &mut Vec::new(), &mut files)?;
+
+    let mut summary = ObsidianImportSummary {
+        notes_read: (files.notes.len() + files.daily_notes.len()) as i64,
+        pages_created: 0,
+        pages_updated: 0,
+        entries_imported: 0,
+        skipped_existing: 0,
+        assets_imported: 0,
+    };
+    let mut written_pages = Vec::new();
+    let mut written_entries = Vec::new();
+    let mut discarded_attachments = Vec::new();
+
+    // Every folder on the way to a note, parents first.
+    let mut folders: BTreeSet<(usize, Vec<String>)> = BTreeSet::new();
+    for note in &files.notes {
+        for depth in 1..=note.folders.len() {
+            folders.insert((depth, note.folders[..depth].to_vec()));
+        }
+    }
+
+    let tx = conn.transaction().map_err(|e| e.to_string())?;
+    let mut folder_pages: HashMap<Vec<String>, i64> = HashMap::new();
+    let mut folder_notes = Vec::new();
+    for (_, folder) in &folders {
+        let Some((name, parent)) = folder.split_last() else {
+            continue;
+        };
+        let folder_note = files.notes.iter().position(|note| {
+            note.folders.as_slice() == parent && note.title.eq_ignore_ascii_case(name)
+        });
+        let text = match folder_note {
+            Some(index) => fs::read_to_string(&files.notes[index].path).map_err(|e| {
+                format!(
+                    "Failed to read {}: {}",
+                    files.notes[index].path.display(),
+                    e
+                )
+            })?,
+            None => String::new(),
+        };
+        let (tags, body) = note_tags_and_body(&text);
+        let (page_id, written) = import_vault_page(
+            &tx,
+            folder_pages.get(parent).copied(),
+            name,
+            body,
+            &tags,
+            assets_root,
+            &files.attachments,
+            cipher,
+            &mut summary,
+        )?;
+        if written {
+            written_pages.push(page_id);
+        }
+        folder_pages.insert(folder.clone(), page_id);
+        folder_notes.extend(folder_note);
+    }
+
+    for (index, note) in files.notes.iter().enumerate() {
+        if folder_notes.contains(&index) {
+            continue;
+        }
+        let text = fs::read_to_string(&note.path)
+            .map_err(|e| format!("Failed to read {}: {}", note.path.display(), e))?;
+        let (tags, body) = note_tags_and_body(&text);
+        let (page_id, written) = import_vault_page(
+            &tx,
+            folder_pages.get(&note.folders).copied(),
+            &note.title,
+            body,
+            &tags,
+            assets_root,
+            &files.attachments,
+            cipher,
+            &mut summary,
+        )?;
+        if written {
+            written_pages.push(page_id);
+        }
+    }
+
+    files.daily_notes.sort();
+    for (date, path) in &files.daily_notes {
+        if get_entry_in_conn(&tx, date)?.is_some() {
+            summary.skipped_existing += 1;
+            continue;
+        }
+        let text = fs::read_to_string(path)
+            .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+        let mut note = parse_markdown_note(&text, date);
+        for section in [
+            &mut note.yesterday,
+            &mut note.today,
+            &mut note.blockers,
+            &mut note.notes,
+        ] {
+            *section = convert_obsidian_markdown(section, &mut |_| None);
+        }
+        if let Some(discarded) = insert_markdown_note_in_conn(&tx, date, note, cipher)? {
+            discarded_attachments.extend(discarded);
+            written_entries.push(date.clone());
+            summary.entries_imported += 1;
+        }
+    }
+    tx.commit().map_err(|e| e.to_string())?;
+
+    Ok(ObsidianImport {
+        summary,
+        written_pages,
+        written_entries,
+        discarded_attachments,
+    })
+}
+
+/// Imports the vault at `dir`; see `import_obsidian_vault_in_conn`.
+#[tauri::command]
+pub fn import_obsidian_vault(
+    dir: String,
+    app: AppHandle,
+    state: State<'_, AppState>,
+) -> Result<ObsidianImportSummary, String> {
+    let dir = dir.trim();
+    if dir.is_empty() {
+        return Err("Vault folder is required".to_string());
+    }
+
+    let assets_root = page_assets_root(&app)?;
+    let cipher = state.journal_cipher()?;
+    let mut conn = state.db.lock().map_err(|e| e.to_string())?;
+    let import =
+        import_obsidian_vault_in_conn(&mut conn, &PathBuf::from(dir), &assets_root, &cipher)?;
+    for page_id in import.written_pages {
+        mirror_page(&conn, page_id);
+    }
+    for date in &import.written_entries {
+        mirror_entry(&conn, date);
+    }
+
+    drop(conn);
+    if !import.discarded_attachments.is_empty() {
+        remove_attachment_files(&attachments_root(&app)?, &import.discarded_attachments);
+    }
+    super::achievements::evaluate_achievements_for_app(&app);
+    Ok(import.summary)
+}
@@ -0,0 +1,281 @@
+use crate::models::OnCallShift;
+use chrono::{Duration, NaiveDate, Utc};
+use rusqlite::{params, Connection, OptionalExtension};
+use std::collections::BTreeSet;
+use tauri::State;
+
+use super::AppState;
+
+const ONCALL_SHIFT_COLUMNS: &str = "id, start_date, end_date, note, source, created_at";
+
+fn parse_shift_date(value: &str) -> Result<NaiveDate, String> {
+    NaiveDate::parse_from_str(value.trim(), "%Y-%m-%d")
+        .map_err(|_| format!("Invalid date: {}", value))
+}
+
+fn oncall_shift_from_row(row: &rusqlite::Row<'_>) -> rusqlite::Result<OnCallShift> {
+    Ok(OnCallShift {
+        id: row.get(0)?,
+        start_date: row.get(1)?,
+        end_date: row.get(2)?,
+        note: row.get(3)?,
+        source: row.get(4)?,
+        created_at: row.get(5)?,
+    })
+}
+
+/// Reads the date part of an ICS `DATE` (`20240301`) or `DATE-TIME`
+/// (`20240301T090000Z`) value. Time zones are ignored; shifts are whole days.
+fn parse_ics_date(value: &str) -> Option<(NaiveDate, bool)> {
+    let date = NaiveDate::parse_from_str(value.get(..8)?, "%Y%m%d").ok()?;
+    let starts_at_midnight = value.len() == 8 || value.get(9..15) == Some("000000");
+    Some((date, starts_at_midnight))
+}
+
+#[derive(Default)]
+struct IcsEvent {
+    start: Option<NaiveDate>,
+    end: Option<(NaiveDate, bool)>,
+    summary: String,
+}
+
+/// Extracts `(start, end, summary)` day ranges from the `VEVENT`s of an ICS
+/// calendar. `DTEND` is exclusive, so an end at midnight belongs to the day before;
+/// events without `DTEND` cover their start day only.
+pub(crate) fn parse_ics_shifts(content: &str) -> Vec<(NaiveDate, NaiveDate, String)> {
+    // Unfold continuation lines (RFC 5545 §3.1) before reading properties.
+    let mut lines: Vec<String> = Vec::new();
+    for line in content.lines() {
+        match (line.strip_prefix([' ', '\t']), lines.last_mut()) {
+            (Some(rest), Some(last)) => last.push_str(rest),
+            _ => lines.push(line.trim_end().to_string()),
+        }
+    }
+
+    let mut shifts = Vec::new();
+    let mut event: Option<IcsEvent> = None;
+    for line in &lines {
+        if line.eq_ignore_ascii_case("BEGIN:VEVENT") {
+            event = Some(IcsEvent::default());
+            continue;
+        }
+        if line.eq_ignore_ascii_case("END:VEVENT") {
+            if let Some(IcsEvent {
+                start: Some(start),
+                end,
+                summary,
+            }) = event.take()
+            {
+                let end = match end {
+                    Some((end, true)) => end - Duration::days(1),
+                    Some((end, false)) => end,
+                    None => start,
+                };
+                shifts.push((start, end.max(start), summary));
+            }
+            continue;
+        }
+
+        let (Some(current), Some((name, value))) = (event.as_mut(), line.split_once(':')) else {
+            continue;
+        };
+        let property = name.split(';').next().unwrap_or_default().to_uppercase();
+        match property.as_str() {
+            "DTSTART" => current.start = parse_ics_date(value).map(|(date, _)| date),
+            "DTEND" => current.end = parse_ics_date(value),
+            "SUMMARY" => {
+                current.summary = value
+                    .replace("\\n", " ")
+                    .replace("\\,", ",")
+                    .replace("\\;", ";")
+                    .replace("\\\\", "\\")
+                    .trim()
+                    .to_string()
+            }
+            _ => {}
+        }
+    }
+
+    shifts
+}
+
+pub(crate) fn get_oncall_shifts_in_conn(conn: &Connection) -> Result<Vec<OnCallShift>, String> {
+    let mut stmt = conn
+        .prepare(&format!(
+            "SELECT {} FROM oncall_shifts ORDER BY start_date DESC, id DESC",
+            ONCALL_SHIFT_COLUMNS
+        ))
+        .map_err(|e| e.to_string())?;
+    let shifts_iter = stmt
+        .query_map([], oncall_shift_from_row)
+        .map_err(|e| e.to_string())?;
+
+    let mut shifts = Vec::new();
+    for shift in shifts_iter {
+        shifts.push(shift.map_err(|e| e.to_string())?);
+    }
+
+    Ok(shifts)
+}
+
+fn insert_oncall_shift(
+    conn: &Connection,
+    start: NaiveDate,
+    end: NaiveDate,
+    note: &str,
+    source: &str,
+) -> Result<i64, String> {
+    conn.execute(
+        "INSERT INTO oncall_shifts (start_date, end_date, note, source, created_at)
+         VALUES (?1, ?2, ?3, ?4, ?5)",
+        params![
+            start.format("%Y-%m-%d").to_string(),
+            end.format("%Y-%m-%d").to_string(),
+            note.trim(),
+            source,
+            Utc::now().to_rfc3339()
+        ],
+    )
+    .map_err(|e| e.to_string())?;
+
+    Ok(conn.last_insert_rowid())
+}
+
+/// `start_date..=end_date` are local `YYYY-MM-DD` days.
+pub(crate) fn create_oncall_shift_in_conn(
+    conn: &Connection,
+    start_date: &str,
+    end_date: &str,
+    note: &str,
+) -> Result<OnCallShift, String> {
+    let start = parse_shift_date(start_date)?;
+    let end = parse_shift_date(end_date)?;
+    if end < start {
+        return Err("On-call end date must not be before the start date".to_string());
+    }
+
+    let id = insert_oncall_shift(conn, start, end, note, "manual")?;
+    conn.query_row(
+        &format!(
+            "SELECT {} FROM oncall_shifts WHERE id = ?1",
+            ONCALL_SHIFT_COLUMNS
+        ),
+        params![id],
+        oncall_shift_from_row,
+    )
+    .map_err(|e| e.to_string())
+}
+
+/// Imports the events of an ICS calendar as shifts and returns how many were added.
+/// Events matching an existing shift's dates are skipped, so re-importing the same
+/// calendar export is harmless.
+pub(crate) fn import_oncall_ics_in_conn(conn: &Connection, content: &str) -> Result<i64, String> {
+    let shifts = parse_ics_shifts(content);
+    if shifts.is_empty() && !content.contains("BEGIN:VCALENDAR") {
+        return Err("Not an ICS calendar file".to_string());
+    }
+
+    let mut imported = 0;
+    for (start, end, summary) in shifts {
+        let exists = conn
+            .query_row(
+                "SELECT id FROM oncall_shifts WHERE start_date = ?1 AND end_date = ?2",
+                params![
+                    start.format("%Y-%m-%d").to_string(),
+                    end.format("%Y-%m-%d").to_string()
+                ],
+                |row| row.get::<_, i64>(0),
+            )
+            .optional()
+            .map_err(|e| e.to_string())?
+            .is_some();
+        if !exists {
+            insert_oncall_shift(conn, start, end, &summary, "ics")?;
+            imported += 1;
+        }
+    }
+
+    Ok(imported)
+}
+
+/// Every on-call day within `from..=to`, in order.
+pub(crate) fn oncall_days_in_conn(
+    conn: &Connection,
+    from: NaiveDate,
+    to: NaiveDate,
+) -> Result<BTreeSet<String>, String> {
+    let mut stmt = conn
+        .prepare(
+            "SELECT start_date, end_date FROM oncall_shifts
+             WHERE start_date <= ?2 AND end_date >= ?1",
+        )
+        .map_err(|e| e.to_string())?;
+    let ranges_iter = stmt
+        .query_map(
+            params![
+                from.format("%Y-%m-%d").to_string(),
+                to.format("%Y-%m-%d").to_string()
+            ],
+            |row| Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?)),
+        )
+        .map_err(|e| e.to_string())?;
+
+    let mut days = BTreeSet::new();
+    for range in ranges_iter {
+        let (start, end) = range.map_err(|e| e.to_string())?;
+        let mut day = parse_shift_date(&start)?.max(from);
+        let end = parse_shift_date(&end)?.min(to);
+        while day <= end {
+            days.insert(day.format("%Y-%m-%d").to_string());
+            day += Duration::days(1);
+        }
+    }
+
+    Ok(days)
+}
+
+#[tauri::command]
+pub fn get_oncall_shifts(state: State<'_, AppState>) -> Result<Vec<OnCallShift>, String> {
+    let conn = state.db.lock().map_err(|e| e.to_string())?;
+    get_oncall_shifts_in_conn(&conn)
+}
+
+#[tauri::command]
+pub fn create_oncall_shift(
+    start_date: String,
+    end_date: String,
+    note: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<OnCallShift, String> {
+    let conn = state.db.lock().map_err(|e| e.to_string())?;
+    create_oncall_shift_in_conn(&conn, &start_date, &end_date, &note.unwrap_or_default())
+}
+
+#[tauri::command]
+pub fn delete_oncall_shift(id: i64, state: State<'_, AppState>) -> Result<(), String> {
+    let conn = state.db.lock().map_err(|e| e.to_string())?;
+    conn.execute("DELETE FROM oncall_shifts WHERE id = ?1", params![id])
+        .map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+#[tauri::command]
+pub fn import_oncall_ics(content: String, state: State<'_, AppState>) -> Result<i64, String> {
+    let conn = state.db.lock().map_err(|e| e.to_string())?;
+    import_oncall_ics_in_conn(&conn, &content)
+}
+
+/// On-call days between two local `YYYY-MM-DD` dates, for the heatmap and dashboard.
+#[tauri::command]
+pub fn get_oncall_days(
+    start_date: String,
+    end_date: String,
+    state: State<'_, AppState>,
+) -> Result<Vec<String>, String> {
+    let start = parse_shift_date(&start_date)?;
+    let end = parse_shift_date(&end_date)?;
+    let conn = state.db.lock().map_err(|e| e.to_string())?;
+    Ok(oncall_days_in_conn(&conn, start, end)?
+        .into_iter()
+        .collect())
+}
@@ -0,0 +1,825 @@
+use crate::models::{OrgImportSummary, Page, Task};
+use chrono::{Local, NaiveDate, NaiveTime, TimeZone, Utc};
+use rusqlite::{params, Connection};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use tauri::State;
+
+use super::automation::get_task_tags_in_conn;
+use super::encryption::{JournalCipher, JournalContent};
+use super::mirror::mirror_page;
+use super::page_properties::set_page_properties_in_conn;
+use super::pages::{
+    add_page_tag_in_conn, create_page_in_conn, get_page_children_in_conn, get_page_tags_in_conn,
+    get_pages_in_conn, set_page_links_in_conn, snapshot_page_in_conn,
+};
+use super::tasks::{
+    task_from_row, upsert_imported_task_in_conn, ImportedStatus, ImportedTask, TASK_COLUMNS,
+};
+use super::validation::normalize_entry_tag;
+use super::AppState;
+
+/// Keywords used when a file has no `#+TODO:` line of its own.
+const DEFAULT_TODO_KEYWORDS: &str = "TODO NEXT STARTED WAITING | DONE CANCELED CANCELLED";
+/// Open keywords that mean work has begun; every other open keyword is `todo`.
+const IN_PROGRESS_KEYWORDS: [&str; 5] = ["NEXT", "STARTED", "DOING", "IN-PROGRESS", "INPROGRESS"];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct TodoKeywords {
+    open: Vec<String>,
+    done: Vec<String>,
+}
+
+impl TodoKeywords {
+    /// Collects `#+TODO:`, `#+SEQ_TODO:` and `#+TYP_TODO:` lines. Without a `|` the
+    /// last keyword of a line is the done state, as in Org itself.
+    fn from_text(text: &str) -> Self {
+        let mut keywords = Self {
+            open: Vec::new(),
+            done: Vec::new(),
+        };
+        for line in text.lines() {
+            let Some(value) = ["#+todo:", "#+seq_todo:", "#+typ_todo:"]
+                .iter()
+                .find_map(|prefix| strip_prefix_ignore_case(line.trim(), prefix))
+            else {
+                continue;
+            };
+            keywords.add_line(value);
+        }
+        if keywords.open.is_empty() && keywords.done.is_empty() {
+            keywords.add_line(DEFAULT_TODO_KEYWORDS);
+        }
+        keywords
+    }
+
+    fn add_line(&mut self, value: &str) {
+        // `TODO(t)` and `DONE(d!)` carry fast-access keys and logging flags.
+        let words: Vec<String> = value
+            .split_whitespace()
+            .map(|word| word.split('(').next().unwrap_or(word).to_string())
+            .collect();
+        match words.iter().position(|word| word == "|") {
+            Some(split) => {
+                self.open.extend_from_slice(&words[..split]);
+                self.done.extend_from_slice(&words[split + 1..]);
+            }
+            None => {
+                if let Some((last, open)) = words.split_last() {
+                    self.open.extend_from_slice(open);
+                    self.done.push(last.clone());
+                }
+            }
+        }
+    }
+
+    fn is_keyword(&self, word: &str) -> bool {
+        self.open
+            .iter()
+            .chain(&self.done)
+            .any(|keyword| keyword == word)
+    }
+}
+
+fn strip_prefix_ignore_case<'a>(value: &'a str, prefix: &str) -> Option<&'a str> {
+    value
+        .get(..prefix.len())
+        .filter(|head| head.eq_ignore_ascii_case(prefix))
+        .map(|_| value[prefix.len()..].trim())
+}
+
+/// An active or inactive Org timestamp such as `<2026-05-06 Wed 10:00 +1w>`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct OrgTimestamp {
+    pub date: NaiveDate,
+    pub time: Option<NaiveTime>,
+    /// `daily` or `weekly` for `+1d` / `+1w` repeaters; others have no task equivalent.
+    pub recurrence: Option<&'static str>,
+}
+
+fn parse_org_timestamp(value: &str) -> Option<OrgTimestamp> {
+    let start = value.find(['<', '['])?;
+    let inner = &value[start + 1..];
+    let inner = &inner[..inner.find(['>', ']'])?];
+    let mut parts = inner.split_whitespace();
+    let date = NaiveDate::parse_from_str(parts.next()?, "%Y-%m-%d").ok()?;
+    let mut timestamp = OrgTimestamp {
+        date,
+        time: None,
+        recurrence: None,
+    };
+    for part in parts {
+        if let Ok(time) = NaiveTime::parse_from_str(part.get(..5).unwrap_or(part), "%H:%M") {
+            timestamp.time = Some(time);
+        }
+        let repeater = part.trim_start_matches(['.', '+']);
+        if part.starts_with(['.', '+']) {
+            timestamp.recurrence = match repeater {
+                "1d" => Some("daily"),
+                "1w" => Some("weekly"),
+                "1m" => Some("monthly"),
+                _ => None,
+            };
+        }
+    }
+    Some(timestamp)
+}
+
+/// One Org heading with its planning line and body; drawers are dropped.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(crate) struct OrgHeading {
+    pub level: usize,
+    pub keyword: Option<String>,
+    pub done: bool,
+    pub priority: Option<char>,
+    pub title: String,
+    pub tags: Vec<String>,
+    pub scheduled: Option<OrgTimestamp>,
+    pub deadline: Option<OrgTimestamp>,
+    pub closed: Option<OrgTimestamp>,
+    pub body: Vec<String>,
+}
+
+fn parse_heading_line(line: &str, keywords: &TodoKeywords) -> Option<OrgHeading> {
+    let level = line.chars().take_while(|ch| *ch == '*').count();
+    let rest = line.get(level..)?;
+    if level == 0 || !(rest.is_empty() || rest.starts_with(' ')) {
+        return None;
+    }
+
+    let mut heading = OrgHeading {
+        level,
+        ..OrgHeading::default()
+    };
+    let mut rest = rest.trim();
+    if let Some(word) = rest
+        .split_whitespace()
+        .next()
+        .filter(|word| keywords.is_keyword(word))
+    {
+        heading.done = keywords.done.iter().any(|keyword| keyword == word);
+        heading.keyword = Some(word.to_string());
+        rest = rest[word.len()..].trim_start();
+    }
+    if let Some(cookie) = rest.strip_prefix("[#") {
+        let mut chars = cookie.chars();
+        if let (Some(priority), Some(']')) = (chars.next(), chars.next()) {
+            heading.priority = Some(priority.to_ascii_uppercase());
+            rest = chars.as_str().trim_start();
+        }
+    }
+    if let Some((title, tags)) = rest.rsplit_once(char::is_whitespace) {
+        let tags = tags.trim();
+        if tags.len() > 1 && tags.starts_with(':') && tags.ends_with(':') {
+            heading.tags = tags
+                .split(':')
+                .filter(|tag| !tag.is_empty())
+                .map(str::to_string)
+                .collect();
+            rest = title;
+        }
+    }
+    heading.title = rest.trim().to_string();
+
+    Some(heading)
+}
+
+fn is_drawer_start(line: &str) -> bool {
+    line.len() > 2
+        && line.starts_with(':')
+        && line.ends_with(':')
+        && !line.eq_ignore_ascii_case(":END:")
+        && line[1..line.len() - 1]
+            .chars()
+            .all(|ch| ch.is_ascii_alphanumeric() || ch == '_' || ch == '-')
+}
+
+fn parse_planning_line(line: &str, heading: &mut OrgHeading) -> bool {
+    let mut found = false;
+    for keyword in ["SCHEDULED:", "DEADLINE:", "CLOSED:"] {
+        let Some(position) = line.find(keyword) else {
+            continue;
+        };
+        let timestamp = parse_org_timestamp(&line[position + keyword.len()..]);
+        match keyword {
+            "SCHEDULED:" => heading.scheduled = timestamp,
+            "DEADLINE:" => heading.deadline = timestamp,
+            _ => heading.closed = timestamp,
+        }
+        found = true;
+    }
+    found
+}
+
+/// Headings of an Org file in document order; text before the first heading
+/// (`#+TITLE:` and other settings) is not imported.
+pub(crate) fn parse_org(text: &str) -> Vec<OrgHeading> {
+    let keywords = TodoKeywords::from_text(text);
+    let mut headings: Vec<OrgHeading> = Vec::new();
+    let mut in_drawer = false;
+    for line in text.lines() {
+        if let Some(heading) = parse_heading_line(line, &keywords) {
+            headings.push(heading);
+            in_drawer = false;
+            continue;
+        }
+        let Some(heading) = headings.last_mut() else {
+            continue;
+        };
+        let trimmed = line.trim();
+        if in_drawer {
+            in_drawer = !trimmed.eq_ignore_ascii_case(":END:");
+            continue;
+        }
+        if is_drawer_start(trimmed) {
+            in_drawer = true;
+            continue;
+        }
+        if heading.body.is_empty() && parse_planning_line(trimmed, heading) {
+            continue;
+        }
+        heading.body.push(line.to_string());
+    }
+    headings
+}
+
+/// `[[url][label]]` becomes a Markdown link; internal links (`[[*Heading]]`) become
+/// the `[[Title]]` wiki links pages already use.
+fn org_links_to_markdown(line: &str) -> String {
+    let mut converted = String::with_capacity(line.len());
+    let mut rest = line;
+    while let Some(start) = rest.find("[[") {
+        let Some(end) = rest[start..].find("]]") else {
+            break;
+        };
+        converted.push_str(&rest[..start]);
+        let inner = &rest[start + 2..start + end];
+        let (target, label) = inner.split_once("][").unwrap_or((inner, inner));
+        if target.contains("://") || target.starts_with("mailto:") {
+            converted.push_str(&format!("[{}]({})", label, target));
+        } else if target == label {
+            converted.push_str(&format!("[[{}]]", target.trim_start_matches(['*', '#'])));
+        } else {
+            converted.push_str(&format!("[[{}]]", label));
+        }
+        rest = &rest[start + end + 2..];
+    }
+    converted.push_str(rest);
+    converted
+}
+
+/// Org body text to the Markdown stored in pages and task descriptions: source and
+/// example blocks become fences, links are converted and other `#+` lines dropped.
+fn org_body_to_markdown(lines: &[String]) -> String {
+    let mut markdown = Vec::new();
+    let mut in_block = false;
+    for line in lines {
+        let trimmed = line.trim_start();
+        if let Some(language) = strip_prefix_ignore_case(trimmed, "#+begin_src")
+            .or_else(|| strip_prefix_ignore_case(trimmed, "#+begin_example"))
+        {
+            let language = language.split_whitespace().next().unwrap_or_default();
+            markdown.push(format!("```{}", language));
+            in_block = true;
+        } else if strip_prefix_ignore_case(trimmed, "#+end_src").is_some()
+            || strip_prefix_ignore_case(trimmed, "#+end_example").is_some()
+        {
+            markdown.push("```".to_string());
+            in_block = false;
+        } else if in_block {
+            // Org escapes `*` and `#+` at the start of block lines with a comma.
+            let unescaped = match trimmed.strip_prefix(',') {
+                Some(rest) if rest.starts_with('*') || rest.starts_with("#+") => rest,
+                _ => line.as_str(),
+            };
+            markdown.push(unescaped.to_string());
+        } else if trimmed.starts_with("#+") {
+            continue;
+        } else {
+            let indent = &line[..line.len() - trimmed.len()];
+            let line = match trimmed.strip_prefix("+ ") {
+                Some(item) => format!("{}- {}", indent, item),
+                None => line.clone(),
+            };
+            markdown.push(org_links_to_markdown(&line));
+        }
+    }
+    markdown.join("\n").trim().to_string()
+}
+
+/// `[label](url)` to `[[url][label]]`; wiki links are valid Org links already.
+fn markdown_links_to_org(line: &str) -> String {
+    let mut converted = String::with_capacity(line.len());
+    let mut rest = line;
+    while let Some(start) = rest.find('[') {
+        if rest[start..].starts_with("[[") {
+            let end = rest[start..]
+                .find("]]")
+                .map_or(rest.len(), |end| start + end + 2);
+            converted.push_str(&rest[..end]);
+            rest = &rest[end..];
+            continue;
+        }
+        let link = rest[start..].find("](").and_then(|middle| {
+            let url_start = start + middle + 2;
+            rest[url_start..]
+                .find(')')
+                .map(|end| (start + middle, url_start, url_start + end))
+        });
+        let Some((label_end, url_start, url_end)) = link else {
+            converted.push_str(&rest[..=start]);
+            rest = &rest[start + 1..];
+            continue;
+        };
+        converted.push_str(&rest[..start]);
+        converted.push_str(&format!(
+            "[[{}][{}]]",
+            &rest[url_start..url_end],
+            &rest[start + 1..label_end]
+        ));
+        rest = &rest[url_end + 1..];
+    }
+    converted.push_str(rest);
+    converted
+}
+
+/// Markdown content to an Org body that cannot be mistaken for headings or keywords.
+fn markdown_to_org_body(markdown: &str) -> String {
+    let mut org = Vec::new();
+    let mut in_fence = false;
+    for line in markdown.lines() {
+        let trimmed = line.trim_start();
+        if let Some(language) = trimmed.strip_prefix("```") {
+            org.push(if in_fence {
+                "#+end_src".to_string()
+            } else {
+                format!("#+begin_src {}", language.trim())
+                    .trim_end()
+                    .to_string()
+            });
+            in_fence = !in_fence;
+        } else if in_fence {
+            if line.starts_with('*') || line.starts_with("#+") {
+                org.push(format!(",{}", line));
+            } else {
+                org.push(line.to_string());
+            }
+        } else if let Some(item) = line.strip_prefix("* ") {
+            org.push(markdown_links_to_org(&format!("- {}", item)));
+        } else if let Some(title) = trimmed
+            .strip_prefix('#')
+            .map(|title| title.trim_start_matches('#'))
+            .and_then(|title| title.strip_prefix(' '))
+        {
+            // `# ` starts a comment in Org; Markdown headings become bold lines.
+            org.push(format!("*{}*", markdown_links_to_org(title.trim())));
+        } else {
+            org.push(markdown_links_to_org(line));
+        }
+    }
+    if in_fence {
+        org.push("#+end_src".to_string());
+    }
+    org.join("\n").trim_end().to_string()
+}
+
+/// Org tags allow letters, digits, `_`, `@`, `#` and `%`; anything else becomes `_`.
+fn org_tag(tag: &str) -> String {
+    tag.chars()
+        .map(|ch| {
+            if ch.is_alphanumeric() || matches!(ch, '_' | '@' | '#' | '%') {
+                ch
+            } else {
+                '_'
+            }
+        })
+        .collect()
+}
+
+fn org_heading_line(level: usize, prefix: &str, title: &str, tags: &[String]) -> String {
+    let mut line = format!("{} {}{}", "*".repeat(level), prefix, title.trim());
+    if !tags.is_empty() {
+        let tags: Vec<String> = tags.iter().map(|tag| org_tag(tag)).collect();
+        line.push_str(&format!(" :{}:", tags.join(":")));
+    }
+    line
+}
+
+fn org_date(date: &str) -> Option<String> {
+    let date = NaiveDate::parse_from_str(date.get(..10)?, "%Y-%m-%d").ok()?;
+    Some(date.format("%Y-%m-%d %a").to_string())
+}
+
+fn task_heading(task: &Task, tags: &[String]) -> String {
+    let keyword = match task.status.as_str() {
+        "done" => "DONE",
+        "in_progress" => "STARTED",
+        _ => "TODO",
+    };
+    let priority = match task.priority.as_str() {
+        "urgent" | "high" => "[#A] ",
+        "low" => "[#C] ",
+        _ => "",
+    };
+    let mut heading = org_heading_line(1, &format!("{} {}", keyword, priority), &task.title, tags);
+
+    let mut planning = Vec::new();
+    if let Some(completed_at) = task
+        .completed_at
+        .as_deref()
+        .filter(|_| task.status == "done")
+    {
+        if let Ok(completed_at) = chrono::DateTime::parse_from_rfc3339(completed_at) {
+            planning.push(format!(
+                "CLOSED: [{}]",
+                completed_at
+                    .with_timezone(&Local)
+                    .format("%Y-%m-%d %a %H:%M")
+            ));
+        }
+    }
+    if let Some(due) = task.due_date.as_deref().and_then(org_date) {
+        let repeater = match task.recurrence.as_str() {
+            "daily" => " +1d",
+            "weekly" => " +1w",
+            "monthly" => " +1m",
+            _ => "",
+        };
+        planning.push(format!("DEADLINE: <{}{}>", due, repeater));
+    }
+    if !planning.is_empty() {
+        heading.push('\n');
+        heading.push_str(&planning.join(" "));
+    }
+    let description = markdown_to_org_body(&task.description);
+    if !description.is_empty() {
+        heading.push('\n');
+        heading.push_str(&description);
+    }
+    heading
+}
+
+fn push_page_tree(
+    org: &mut String,
+    page: &Page,
+    level: usize,
+    children: &HashMap<Option<i64>, Vec<Page>>,
+    tags: &HashMap<i64, Vec<String>>,
+) {
+    org.push_str(&org_heading_line(
+        level,
+        "",
+        &page.title,
+        tags.get(&page.id).map(Vec::as_slice).unwrap_or_default(),
+    ));
+    org.push('\n');
+    let body = markdown_to_org_body(&page.content);
+    if !body.is_empty() {
+        org.push_str(&body);
+        org.push('\n');
+    }
+    for child in children.get(&Some(page.id)).into_iter().flatten() {
+        push_page_tree(org, child, level + 1, children, tags);
+    }
+}
+
+/// One Org file with every task as a top-level `TODO`/`STARTED`/`DONE` heading
+/// followed by the page tree as plain headings. Importing it back updates the same
+/// tasks and pages instead of duplicating them.
+pub(crate) fn org_export_in_conn(
+    conn: &Connection,
+    cipher: &JournalCipher,
+) -> Result<String, String> {
+    let mut org = format!(
+        "#+TITLE: Dev Journal\n#+DATE: [{}]\n#+TODO: TODO STARTED | DONE\n\n",
+        Local::now().format("%Y-%m-%d %a")
+    );
+
+    let mut task_tags: HashMap<i64, Vec<String>> = HashMap::new();
+    for tag in get_task_tags_in_conn(conn)? {
+        task_tags.entry(tag.task_id).or_default().push(tag.tag);
+    }
+    let mut stmt = conn
+        .prepare(&format!(
+            "SELECT {} FROM tasks
+             WHERE id NOT IN (SELECT task_id FROM review_queue)
+             ORDER BY status = 'done', due_date IS NULL, due_date ASC, id ASC",
+            TASK_COLUMNS
+        ))
+        .map_err(|e| e.to_string())?;
+    let tasks_iter = stmt
+        .query_map([], task_from_row)
+        .map_err(|e| e.to_string())?;
+    for task in tasks_iter {
+        let task = task.map_err(|e| e.to_string())?;
+        let tags = task_tags
+            .get(&task.id)
+            .map(Vec::as_slice)
+            .unwrap_or_default();
+        org.push_str(&task_heading(&task, tags));
+        org.push('\n');
+    }
+
+    let mut page_tags: HashMap<i64, Vec<String>> = HashMap::new();
+    for tag in get_page_tags_in_conn(conn)? {
+        page_tags.entry(tag.page_id).or_default().push(tag.tag);
+    }
+    let mut children: HashMap<Option<i64>, Vec<Page>> = HashMap::new();
+    for page in get_pages_in_conn(conn)?.decrypt_with(cipher)? {
+        children.entry(page.parent_id).or_default().push(page);
+    }
+    for pages in children.values_mut() {
+        pages.sort_by_key(|page| page.title.to_lowercase());
+    }
+    for page in children.get(&None).into_iter().flatten() {
+        push_page_tree(&mut org, page, 1, &children, &page_tags);
+    }
+
+    Ok(org)
+}
+
+/// Where the export is written: `path` itself with an `.org` extension, or
+/// `dev-journal.org` inside it when it is an existing folder.
+pub(crate) fn org_export_path(path: &str) -> Result<PathBuf, String> {
+    let path = path.trim();
+    if path.is_empty() {
+        return Err("Output path is required".to_string());
+    }
+    let path = PathBuf::from(path);
+    if path.is_dir() {
+        return Ok(path.join("dev-journal.org"));
+    }
+    Ok(path.with_extension("org"))
+}
+
+#[derive(Debug, Default)]
+struct OrgTaskDraft {
+    heading: OrgHeading,
+    /// Plain headings nested under the task fold into its description.
+    description: Vec<String>,
+}
+
+#[derive(Debug)]
+struct OrgPageDraft {
+    title: String,
+    content: String,
+    tags: Vec<String>,
+    /// Index of the enclosing page draft; `None` sits under the import parent.
+    parent: Option<usize>,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum OrgNode {
+    Task(usize),
+    Page(usize),
+}
+
+fn org_priority(priority: Option<char>) -> &'static str {
+    match priority {
+        Some('A') => "high",
+        Some('C') => "low",
+        _ => "medium",
+    }
+}
+
+fn org_status(heading: &OrgHeading) -> ImportedStatus {
+    if heading.done {
+        ImportedStatus::Done
+    } else if heading
+        .keyword
+        .as_deref()
+        .is_some_and(|keyword| IN_PROGRESS_KEYWORDS.contains(&keyword))
+    {
+        ImportedStatus::InProgress
+    } else {
+        ImportedStatus::Todo
+    }
+}
+
+fn org_local_time(timestamp: &OrgTimestamp) -> String {
+    let time = timestamp.time.unwrap_or_default();
+    Local
+        .from_local_datetime(&timestamp.date.and_time(time))
+        .earliest()
+        .map(|time| time.to_rfc3339())
+        .unwrap_or_else(|| Utc::now().to_rfc3339())
+}
+
+fn imported_org_task(draft: &OrgTaskDraft) -> ImportedTask {
+    let heading = &draft.heading;
+    let planned = heading.deadline.or(heading.scheduled);
+    ImportedTask {
+        title: heading.title.clone(),
+        description: Some(draft.description.join("\n\n")),
+        status: org_status(heading),
+        priority: org_priority(heading.priority).to_string(),
+        due_date: planned.map(|timestamp| timestamp.date.format("%Y-%m-%d").to_string()),
+        recurrence: planned
+            .and_then(|timestamp| timestamp.recurrence)
+            .unwrap_or("none")
+            .to_string(),
+        project_id: None,
+        created_at: None,
+        completed_at: heading.closed.as_ref().map(org_local_time),
+        tags: heading.tags.clone(),
+    }
+}
+
+/// Imports Org files: headings with a TODO keyword become tasks (matched by title
+/// on re-import, so a file can be synced one way), and other headings become pages
+/// nested like the outline under `parent_id`. Text under a plain heading inside a
+/// task is added to the task description.
+pub(crate) fn import_org_in_conn(
+    conn: &mut Connection,
+    documents: &[String],
+    parent_id: Option<i64>,
+    cipher: &JournalCipher,
+) -> Result<OrgImportSummary, String> {
+    let mut summary = OrgImportSummary {
+        headings_read: 0,
+        tasks_created: 0,
+        tasks_updated: 0,
+        pages_created: 0,
+        pages_updated: 0,
+    };
+    let mut tasks: Vec<OrgTaskDraft> = Vec::new();
+    let mut pages: Vec<OrgPageDraft> = Vec::new();
+    for document in documents {
+        let mut outline: Vec<(usize, OrgNode)> = Vec::new();
+        for heading in parse_org(document) {
+            summary.headings_read += 1;
+            while outline
+                .last()
+                .is_some_and(|(level, _)| *level >= heading.level)
+            {
+                outline.pop();
+            }
+            let enclosing = outline.last().map(|(_, node)| *node);
+            let level = heading.level;
+            let body = org_body_to_markdown(&heading.body);
+            let node = match (heading.keyword.is_some(), enclosing) {
+                (false, Some(OrgNode::Task(index))) => {
+                    let section = format!("**{}**\n\n{}", heading.title, body);
+                    tasks[index].description.push(section.trim().to_string());
+                    OrgNode::Task(index)
+                }
+                (false, enclosing) => {
+                    pages.push(OrgPageDraft {
+                        title: heading.title.clone(),
+                        content: body,
+                        tags: heading.tags.clone(),
+                        parent: match enclosing {
+                            Some(OrgNode::Page(index)) => Some(index),
+                            _ => None,
+                        },
+                    });
+                    OrgNode::Page(pages.len() - 1)
+                }
+                (true, _) => {
+                    tasks.push(OrgTaskDraft {
+                        description: if body.is_empty() {
+                            Vec::new()
+                        } else {
+                            vec![body]
+                        },
+                        heading,
+                    });
+                    OrgNode::Task(tasks.len() - 1)
+                }
+            };
+            outline.push((level, node));
+        }
+    }
+
+    let tx = conn.transaction().map_err(|e| e.to_string())?;
+    let mut page_ids: Vec<i64> = Vec::with_capacity(pages.len());
+    let mut written_pages = Vec::new();
+    for draft in &pages {
+        let parent = match draft.parent {
+            Some(index) => Some(page_ids[index]),
+            None => parent_id,
+        };
+        let existing = get_page_children_in_conn(&tx, parent)?
+            .into_iter()
+            .find(|page| page.title.trim() == draft.title.trim());
+        let page_id = match existing {
+            Some(page) => {
+                let page = page.decrypt_with(cipher)?;
+                if page.content != draft.content {
+                    snapshot_page_in_conn(&tx, page.id)?;
+                    tx.execute(
+                        "UPDATE pages SET content = ?1, updated_at = ?2 WHERE id = ?3",
+                        params![
+                            cipher.encrypt(&draft.content)?,
+                            Utc::now().to_rfc3339(),
+                            page.id
+                        ],
+                    )
+                    .map_err(|e| e.to_string())?;
+                    set_page_links_in_conn(&tx, page.id, &draft.content)?;
+                    set_page_properties_in_conn(&tx, page.id, &draft.content)?;
+                    summary.pages_updated += 1;
+                    written_pages.push(page.id);
+                }
+                page.id
+            }
+            None => {
+                let page = create_page_in_conn(
+                    &tx,
+                    &draft.title,
+                    &cipher.encrypt(&draft.content)?,
+                    parent,
+                )?;
+                set_page_links_in_conn(&tx, page.id, &draft.content)?;
+                set_page_properties_in_conn(&tx, page.id, &draft.content)?;
+                summary.pages_created += 1;
+                written_pages.push(page.id);
+                page.id
+            }
+        };
+        for tag in &draft.tags {
+            if normalize_entry_tag(tag).is_ok() {
+                add_page_tag_in_conn(&tx, page_id, tag)?;
+            }
+        }
+        page_ids.push(page_id);
+    }
+
+    for draft in &tasks {
+        match upsert_imported_task_in_conn(&tx, &imported_org_task(draft))? {
+            (true, _) => summary.tasks_created += 1,
+            (false, true) => summary.tasks_updated += 1,
+            (false, false) => {}
+        }
+    }
+    tx.commit().map_err(|e| e.to_string())?;
+
+    for page_id in written_pages {
+        mirror_page(conn, page_id);
+    }
+
+    Ok(summary)
+}
+
+/// Writes every task and page to one `.org` file and returns its path.
+#[tauri::command]
+pub fn export_org(path: String, state: State<'_, AppState>) -> Result<String, String> {
+    let path = org_export_path(&path)?;
+    let cipher = state.journal_cipher()?;
+    let org = {
+        let conn = state.db.lock().map_err(|e| e.to_string())?;
+        org_export_in_conn(&conn, &cipher)?
+    };
+
+    if let Some(parent) = path
+        .parent()
+        .filter(|parent| !parent.as_os_str().is_empty())
+    {
+        fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create {}: {}", parent.display(), e))?;
+    }
+    fs::write(&path, org).map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+    Ok(path.display().to_string())
+}
+
+fn org_files(path: &Path) -> Result<Vec<PathBuf>, String> {
+    if !path.is_dir() {
+        return Ok(vec![path.to_path_buf()]);
+    }
+    let mut files = Vec::new();
+    for item in fs::read_dir(path).map_err(|e| e.to_string())? {
+        let file = item.map_err(|e| e.to_string())?.path();
+        if file
+            .extension()
+            .is_some_and(|ext| ext.eq_ignore_ascii_case("org"))
+        {
+            files.push(file);
+        }
+    }
+    files.sort();
+    if files.is_empty() {
+        return Err(format!("No .org files found in {}", path.display()));
+    }
+    Ok(files)
+}
+
+/// `path` is an `.org` file or a folder of them; pages land under `parent_id`.
+#[tauri::command]
+pub fn import_org(
+    path: String,
+    parent_id: Option<i64>,
+    state: State<'_, AppState>,
+) -> Result<OrgImportSummary, String> {
+    let mut documents = Vec::new();
+    for file in org_files(&PathBuf::from(path.trim()))? {
+        documents.push(
+            fs::read_to_string(&file)
+                .map_err(|e| format!("Failed to read {}: {}", file.display(), e))?,
+        );
+    }
+
+    let cipher = state.journal_cipher()?;
+    let mut conn = state.db.lock().map_err(|e| e.to_string())?;
+    import_org_in_conn(&mut conn, &documents, parent_id, &cipher)
+}
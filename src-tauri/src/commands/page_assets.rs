@@ -0,0 +1,258 @@
+use crate::models::PageAsset;
+use chrono::{DateTime, Duration, Utc};
+use rusqlite::{params, Connection};
+use std::borrow::Cow;
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+use tauri::http::{header, Request, Response, StatusCode};
+use tauri::{AppHandle, Manager, State};
+
+use super::attachments::{mime_type_for, remove_attachment_files, sanitize_file_name};
+use super::encryption::journal_encryption_enabled_in_conn;
+use super::AppState;
+
+/// URI scheme the webview loads page images from; see `serve_page_asset`.
+pub(crate) const PAGE_ASSET_SCHEME: &str = "journal-asset";
+
+/// Pasted screenshots are the common case; anything larger belongs in a file attachment.
+const MAX_PAGE_ASSET_BYTES: usize = 20 * 1024 * 1024;
+/// Unreferenced assets younger than this are kept, since the page that embeds a fresh
+/// upload may not have been saved yet.
+const ORPHAN_ASSET_GRACE_MINUTES: i64 = 60;
+
+/// Directory under `app_data_dir` holding one sub-folder per page id.
+pub(crate) fn page_assets_root(app: &AppHandle) -> Result<PathBuf, String> {
+    let app_data_dir = app.path().app_data_dir().map_err(|e| e.to_string())?;
+    Ok(app_data_dir.join("assets"))
+}
+
+/// URL a page embeds for `stored_path`. Windows webviews serve custom schemes over
+/// `http://<scheme>.localhost`, other platforms as `<scheme>://localhost`.
+pub(crate) fn page_asset_url(stored_path: &str) -> String {
+    if cfg!(windows) {
+        format!("http://{}.localhost/{}", PAGE_ASSET_SCHEME, stored_path)
+    } else {
+        format!("{}://localhost/{}", PAGE_ASSET_SCHEME, stored_path)
+    }
+}
+
+/// Stored path behind a URL made by `page_asset_url`, on any platform.
+pub(crate) fn page_asset_stored_path(url: &str) -> Option<&str> {
+    url.strip_prefix(&format!("{}://localhost/", PAGE_ASSET_SCHEME))
+        .or_else(|| url.strip_prefix(&format!("http://{}.localhost/", PAGE_ASSET_SCHEME)))
+        .filter(|stored_path| !stored_path.is_empty())
+}
+
+/// The asset file for `stored_path`; only plain relative paths inside `root` resolve.
+pub(crate) fn page_asset_file(root: &Path, stored_path: &str) -> Option<PathBuf> {
+    let relative = Path::new(stored_path);
+    if stored_path.is_empty()
+        || relative
+            .components()
+            .any(|component| !matches!(component, Component::Normal(_)))
+    {
+        return None;
+    }
+    Some(root.join(relative))
+}
+
+/// `sanitize_file_name` restricted to ASCII, so stored paths need no escaping in URLs.
+fn asset_file_name(name: &str) -> String {
+    sanitize_file_name(name)
+        .chars()
+        .map(
+            |character| {
+                if character.is_ascii() {
+                    character
+                } else {
+                    '_'
+                }
+            },
+        )
+        .collect()
+}
+
+/// Stores `bytes` as an image of `page_id` and records it. The file name only needs
+/// a known image extension; the content is stored as given.
+pub(crate) fn save_page_asset_in_conn(
+    conn: &Connection,
+    root: &Path,
+    page_id: i64,
+    bytes: &[u8],
+    file_name: &str,
+) -> Result<PageAsset, String> {
+    let page_exists: bool = conn
+        .query_row(
+            "SELECT EXISTS(SELECT 1 FROM pages WHERE id = ?1 AND deleted_at IS NULL)",
+            params![page_id],
+            |row| row.get(0),
+        )
+        .map_err(|e| e.to_string())?;
+    if !page_exists {
+        return Err("Page not found".to_string());
+    }
+    let file_name = file_name.trim();
+    let file_name = if file_name.is_empty() {
+        "image.png"
+    } else {
+        file_name
+    };
+    let mime_type = mime_type_for(file_name);
+    if !mime_type.starts_with("image/") {
+        return Err(format!("{} is not a supported image", file_name));
+    }
+    if bytes.is_empty() {
+        return Err("Image is empty".to_string());
+    }
+    if bytes.len() > MAX_PAGE_ASSET_BYTES {
+        return Err(format!(
+            "Images are limited to {} MB",
+            MAX_PAGE_ASSET_BYTES / 1024 / 1024
+        ));
+    }
+
+    let now = Utc::now();
+    let stored_path = format!(
+        "{}/{}-{}",
+        page_id,
+        now.timestamp_nanos_opt().unwrap_or_default(),
+        asset_file_name(file_name)
+    );
+    let destination = root.join(&stored_path);
+    if let Some(parent) = destination.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create {}: {}", parent.display(), e))?;
+    }
+    fs::write(&destination, bytes)
+        .map_err(|e| format!("Failed to write {}: {}", destination.display(), e))?;
+
+    let inserted = conn.execute(
+        "INSERT INTO page_assets (page_id, file_name, mime_type, size_bytes, stored_path, created_at)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+        params![
+            page_id,
+            file_name,
+            mime_type,
+            bytes.len() as i64,
+            stored_path,
+            now.to_rfc3339()
+        ],
+    );
+    if let Err(error) = inserted {
+        remove_attachment_files(root, &[stored_path]);
+        return Err(error.to_string());
+    }
+
+    Ok(PageAsset {
+        id: conn.last_insert_rowid(),
+        page_id: Some(page_id),
+        file_name: file_name.to_string(),
+        mime_type: mime_type.to_string(),
+        size_bytes: bytes.len() as i64,
+        url: page_asset_url(&stored_path),
+        created_at: now.to_rfc3339(),
+    })
+}
+
+/// Deletes asset records that no page, page cover or page revision mentions anymore,
+/// once their page is deleted or the grace period is over. While the journal is
+/// encrypted the content cannot be searched, so only assets of deleted pages go.
+/// Returns the stored paths so the caller can remove the files.
+pub(crate) fn collect_orphan_page_assets_in_conn(
+    conn: &Connection,
+    now: DateTime<Utc>,
+) -> Result<Vec<String>, String> {
+    let check_references = !journal_encryption_enabled_in_conn(conn)?;
+    let cutoff = (now - Duration::minutes(ORPHAN_ASSET_GRACE_MINUTES)).to_rfc3339();
+    let mut stmt = conn
+        .prepare(
+            "SELECT a.id, a.stored_path FROM page_assets a
+             LEFT JOIN pages p ON p.id = a.page_id
+             WHERE CASE WHEN ?1 THEN
+                 (p.id IS NULL OR a.created_at < ?2)
+                 AND NOT EXISTS (
+                     SELECT 1 FROM pages q
+                     WHERE instr(q.content, a.stored_path) > 0
+                        OR instr(COALESCE(q.cover, ''), a.stored_path) > 0
+                 )
+                 AND NOT EXISTS (
+                     SELECT 1 FROM page_revisions r WHERE instr(r.content, a.stored_path) > 0
+                 )
+             ELSE p.id IS NULL END",
+        )
+        .map_err(|e| e.to_string())?;
+    let orphans_iter = stmt
+        .query_map(params![check_references, cutoff], |row| {
+            Ok((row.get::<_, i64>(0)?, row.get::<_, String>(1)?))
+        })
+        .map_err(|e| e.to_string())?;
+
+    let mut orphans = Vec::new();
+    for orphan in orphans_iter {
+        orphans.push(orphan.map_err(|e| e.to_string())?);
+    }
+    for (id, _) in &orphans {
+        conn.execute("DELETE FROM page_assets WHERE id = ?1", params![id])
+            .map_err(|e| e.to_string())?;
+    }
+
+    Ok(orphans.into_iter().map(|(_, path)| path).collect())
+}
+
+/// Runs the collection after pages were deleted and removes the files. Failures are
+/// logged; they never fail the delete itself.
+pub(crate) fn collect_orphan_page_assets(conn: &Connection, app: &AppHandle) {
+    let result = collect_orphan_page_assets_in_conn(conn, Utc::now()).and_then(|paths| {
+        if !paths.is_empty() {
+            remove_attachment_files(&page_assets_root(app)?, &paths);
+        }
+        Ok(())
+    });
+    if let Err(error) = result {
+        eprintln!("Failed to collect page assets: {error}");
+    }
+}
+
+fn asset_response(status: StatusCode) -> Response<Cow<'static, [u8]>> {
+    Response::builder()
+        .status(status)
+        .body(Cow::Borrowed(&[][..]))
+        .unwrap_or_default()
+}
+
+/// Handler for `journal-asset://localhost/<page id>/<file>`. Only plain relative paths
+/// inside the assets folder are served.
+pub(crate) fn serve_page_asset(
+    app: &AppHandle,
+    request: &Request<Vec<u8>>,
+) -> Response<Cow<'static, [u8]>> {
+    let stored_path = request.uri().path().trim_start_matches('/');
+    let Ok(root) = page_assets_root(app) else {
+        return asset_response(StatusCode::INTERNAL_SERVER_ERROR);
+    };
+    let Some(file) = page_asset_file(&root, stored_path) else {
+        return asset_response(StatusCode::BAD_REQUEST);
+    };
+
+    match fs::read(file) {
+        Ok(bytes) => Response::builder()
+            .header(header::CONTENT_TYPE, mime_type_for(stored_path))
+            .body(Cow::Owned(bytes))
+            .unwrap_or_else(|_| asset_response(StatusCode::INTERNAL_SERVER_ERROR)),
+        Err(_) => asset_response(StatusCode::NOT_FOUND),
+    }
+}
+
+/// Stores an image pasted or dropped into a page and returns the URL to embed.
+#[tauri::command]
+pub fn save_page_asset(
+    page_id: i64,
+    bytes: Vec<u8>,
+    filename: String,
+    app: AppHandle,
+    state: State<'_, AppState>,
+) -> Result<PageAsset, String> {
+    let root = page_assets_root(&app)?;
+    let conn = state.db.lock().map_err(|e| e.to_string())?;
+    save_page_asset_in_conn(&conn, &root, page_id, &bytes, &filename)
+}
@@ -0,0 +1,604 @@
+use crate::models::{Page, PageExportSummary};
+use base64::{engine::general_purpose::STANDARD, Engine};
+use rusqlite::Connection;
+use serde::Deserialize;
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+use tauri::{AppHandle, State};
+
+use super::attachments::mime_type_for;
+use super::daily_sheet::escape_html;
+use super::encryption::{JournalCipher, JournalContent};
+use super::mirror::{page_file_name, page_markdown_in_conn, page_tags};
+use super::page_assets::{
+    page_asset_file, page_asset_stored_path, page_assets_root, PAGE_ASSET_SCHEME,
+};
+use super::page_locks::{is_locked_page_content, reveal_page, PageKey, PAGE_LOCKED};
+use super::pages::{get_page_in_conn, get_pages_in_conn};
+use super::AppState;
+
+/// Folder next to exported Markdown files that receives the pages' images.
+const ASSETS_FOLDER: &str = "assets";
+
+const PAGE_HTML_STYLE: &str = "
+  body { font: 16px/1.6 -apple-system, 'Segoe UI', Inter, sans-serif; color: #1f2328; margin: 0 auto; max-width: 46rem; padding: 2rem 1.25rem 4rem; }
+  h1, h2, h3, h4, h5, h6 { line-height: 1.25; margin: 1.6em 0 0.6em; }
+  h1.title { margin-top: 0; }
+  .meta { color: #656d76; font-size: 0.9rem; margin: -0.3rem 0 2rem; }
+  img { max-width: 100%; height: auto; }
+  pre { background: #f6f8fa; border-radius: 6px; overflow-x: auto; padding: 0.9rem 1rem; }
+  code { font: 0.9em ui-monospace, SFMono-Regular, Menlo, monospace; }
+  :not(pre) > code { background: #f6f8fa; border-radius: 4px; padding: 0.1em 0.35em; }
+  blockquote { border-left: 4px solid #d0d7de; color: #59636e; margin: 0; padding: 0 1rem; }
+  table { border-collapse: collapse; }
+  th, td { border: 1px solid #d0d7de; padding: 0.35rem 0.75rem; }
+  li.task { list-style: none; margin-left: -1.4rem; }
+  hr { border: 0; border-top: 1px solid #d0d7de; margin: 2rem 0; }
+";
+
+#[derive(Debug, Clone, Copy, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum PageExportFormat {
+    Markdown,
+    Html,
+}
+
+impl PageExportFormat {
+    fn extension(self) -> &'static str {
+        match self {
+            PageExportFormat::Markdown => "md",
+            PageExportFormat::Html => "html",
+        }
+    }
+}
+
+/// `[label](url)` at the start of `text`; returns the label, the URL and the bytes used.
+fn parse_link(text: &str) -> Option<(&str, &str, usize)> {
+    let rest = text.strip_prefix('[')?;
+    let label_end = rest.find("](")?;
+    let url_start = label_end + 2;
+    let url_end = url_start + rest[url_start..].find(')')?;
+    let url = rest[url_start..url_end].trim();
+    // `![alt](url "title")`: the title is dropped.
+    let url = url.split_once(" \"").map_or(url, |(url, _)| url);
+    Some((&rest[..label_end], url, url_end + 2))
+}
+
+/// Renders emphasis, strikethrough, code spans, links and images; everything else is
+/// escaped. `image` maps an image URL to the `src` to write.
+fn render_inline(text: &str, image: &dyn Fn(&str) -> String, html: &mut String) {
+    let mut index = 0;
+    while index < text.len() {
+        let rest = &text[index..];
+        if let Some(code) = rest.strip_prefix('`') {
+            if let Some(end) = code.find('`') {
+                html.push_str(&format!("<code>{}</code>", escape_html(&code[..end])));
+                index += end + 2;
+                continue;
+            }
+        }
+        if let Some((alt, url, used)) = rest.strip_prefix('!').and_then(parse_link) {
+            html.push_str(&format!(
+                "<img src=\"{}\" alt=\"{}\">",
+                escape_html(&image(url)),
+                escape_html(alt)
+            ));
+            index += used + 1;
+            continue;
+        }
+        if let Some((label, url, used)) = parse_link(rest) {
+            html.push_str(&format!("<a href=\"{}\">", escape_html(url)));
+            render_inline(label, image, html);
+            html.push_str("</a>");
+            index += used;
+            continue;
+        }
+        let mut styled = false;
+        for (marker, tag) in [("**", "strong"), ("__", "strong"), ("~~", "del")] {
+            if let Some(inner) = rest.strip_prefix(marker) {
+                if let Some(end) = inner.find(marker).filter(|end| *end > 0) {
+                    html.push_str(&format!("<{}>", tag));
+                    render_inline(&inner[..end], image, html);
+                    html.push_str(&format!("</{}>", tag));
+                    index += end + marker.len() * 2;
+                    styled = true;
+                    break;
+                }
+            }
+        }
+        if styled {
+            continue;
+        }
+        let starts_word = text[..index]
+            .chars()
+            .next_back()
+            .is_none_or(|previous| !previous.is_alphanumeric());
+        if let Some(marker) = rest
+            .chars()
+            .next()
+            .filter(|marker| (*marker == '*' || *marker == '_') && starts_word)
+        {
+            let inner = &rest[1..];
+            if let Some(end) = inner
+                .find(marker)
+                .filter(|end| *end > 0 && !inner.starts_with(' '))
+            {
+                html.push_str("<em>");
+                render_inline(&inner[..end], image, html);
+                html.push_str("</em>");
+                index += end + 2;
+                continue;
+            }
+        }
+
+        let character = rest.chars().next().unwrap_or_default();
+        html.push_str(&escape_html(&character.to_string()));
+        index += character.len_utf8();
+    }
+}
+
+fn inline_html(text: &str, image: &dyn Fn(&str) -> String) -> String {
+    let mut html = String::new();
+    render_inline(text, image, &mut html);
+    html
+}
+
+/// `ul`/`ol` and the item text for a list line.
+fn list_item(line: &str) -> Option<(&'static str, &str)> {
+    for marker in ["- ", "* ", "+ "] {
+        if let Some(item) = line.strip_prefix(marker) {
+            return Some(("ul", item));
+        }
+    }
+    let digits = line.chars().take_while(char::is_ascii_digit).count();
+    if digits > 0 {
+        if let Some(item) = line[digits..].strip_prefix(". ") {
+            return Some(("ol", item));
+        }
+    }
+    None
+}
+
+fn table_cells(line: &str) -> Vec<&str> {
+    let line = line.trim();
+    let line = line.strip_prefix('|').unwrap_or(line);
+    let line = line.strip_suffix('|').unwrap_or(line);
+    line.split('|').map(str::trim).collect()
+}
+
+fn is_table_separator(line: &str) -> bool {
+    let line = line.trim();
+    line.starts_with('|')
+        && line.contains('-')
+        && line
+            .chars()
+            .all(|character| matches!(character, '|' | '-' | ':' | ' '))
+}
+
+fn is_rule(line: &str) -> bool {
+    let compact: String = line.chars().filter(|c| !c.is_whitespace()).collect();
+    compact.len() >= 3
+        && ['-', '*', '_']
+            .iter()
+            .any(|marker| compact.chars().all(|character| character == *marker))
+}
+
+struct BlockWriter<'a> {
+    html: String,
+    paragraph: Vec<&'a str>,
+    list: Option<&'static str>,
+}
+
+impl<'a> BlockWriter<'a> {
+    fn flush(&mut self, image: &dyn Fn(&str) -> String) {
+        if !self.paragraph.is_empty() {
+            let text = self.paragraph.join("\n");
+            self.html
+                .push_str(&format!("<p>{}</p>\n", inline_html(&text, image)));
+            self.paragraph.clear();
+        }
+        if let Some(list) = self.list.take() {
+            self.html.push_str(&format!("</{}>\n", list));
+        }
+    }
+}
+
+/// A small CommonMark subset, enough for what the page editor produces: headings,
+/// paragraphs, flat lists and checklists, blockquotes, fenced code, rules and tables.
+pub(crate) fn markdown_to_html(markdown: &str, image: &dyn Fn(&str) -> String) -> String {
+    let lines: Vec<&str> = markdown.lines().collect();
+    let mut writer = BlockWriter {
+        html: String::new(),
+        paragraph: Vec::new(),
+        list: None,
+    };
+    let mut index = 0;
+
+    while index < lines.len() {
+        let line = lines[index];
+        let trimmed = line.trim();
+        index += 1;
+
+        if let Some(language) = trimmed.strip_prefix("```") {
+            writer.flush(image);
+            let mut code = Vec::new();
+            while index < lines.len() && !lines[index].trim().starts_with("```") {
+                code.push(lines[index]);
+                index += 1;
+            }
+            index += 1;
+            let class = if language.trim().is_empty() {
+                String::new()
+            } else {
+                format!(" class=\"language-{}\"", escape_html(language.trim()))
+            };
+            writer.html.push_str(&format!(
+                "<pre><code{}>{}</code></pre>\n",
+                class,
+                escape_html(&code.join("\n"))
+            ));
+            continue;
+        }
+        if trimmed.is_empty() {
+            writer.flush(image);
+            continue;
+        }
+        let level = trimmed.chars().take_while(|c| *c == '#').count();
+        if (1..=6).contains(&level) && trimmed[level..].starts_with(' ') {
+            writer.flush(image);
+            writer.html.push_str(&format!(
+                "<h{0}>{1}</h{0}>\n",
+                level,
+                inline_html(trimmed[level..].trim(), image)
+            ));
+            continue;
+        }
+        if is_rule(trimmed) {
+            writer.flush(image);
+            writer.html.push_str("<hr>\n");
+            continue;
+        }
+        if trimmed.starts_with('>') {
+            writer.flush(image);
+            let mut quoted = vec![trimmed.trim_start_matches('>').trim_start()];
+            while index < lines.len() && lines[index].trim().starts_with('>') {
+                quoted.push(lines[index].trim().trim_start_matches('>').trim_start());
+                index += 1;
+            }
+            writer.html.push_str(&format!(
+                "<blockquote>\n{}</blockquote>\n",
+                markdown_to_html(&quoted.join("\n"), image)
+            ));
+            continue;
+        }
+        if trimmed.starts_with('|')
+            && lines
+                .get(index)
+                .is_some_and(|next| is_table_separator(next))
+        {
+            writer.flush(image);
+            index += 1;
+            writer.html.push_str("<table>\n<thead><tr>");
+            for cell in table_cells(trimmed) {
+                writer
+                    .html
+                    .push_str(&format!("<th>{}</th>", inline_html(cell, image)));
+            }
+            writer.html.push_str("</tr></thead>\n<tbody>\n");
+            while index < lines.len() && lines[index].trim().starts_with('|') {
+                writer.html.push_str("<tr>");
+                for cell in table_cells(lines[index]) {
+                    writer
+                        .html
+                        .push_str(&format!("<td>{}</td>", inline_html(cell, image)));
+                }
+                writer.html.push_str("</tr>\n");
+                index += 1;
+            }
+            writer.html.push_str("</tbody>\n</table>\n");
+            continue;
+        }
+        if let Some((kind, item)) = list_item(trimmed) {
+            if writer.list != Some(kind) {
+                writer.flush(image);
+                writer.html.push_str(&format!("<{}>\n", kind));
+                writer.list = Some(kind);
+            }
+            let checkbox = [("[ ] ", ""), ("[x] ", " checked"), ("[X] ", " checked")]
+                .into_iter()
+                .find_map(|(marker, checked)| {
+                    item.strip_prefix(marker).map(|rest| (checked, rest))
+                });
+            match checkbox {
+                Some((checked, rest)) => writer.html.push_str(&format!(
+                    "<li class=\"task\"><input type=\"checkbox\" disabled{}> {}</li>\n",
+                    checked,
+                    inline_html(rest, image)
+                )),
+                None => writer
+                    .html
+                    .push_str(&format!("<li>{}</li>\n", inline_html(item, image))),
+            }
+            continue;
+        }
+        if writer.list.is_some() {
+            writer.flush(image);
+        }
+        writer.paragraph.push(trimmed);
+    }
+    writer.flush(image);
+
+    writer.html
+}
+
+/// Stored paths of the page assets `text` embeds, in order of first use.
+fn embedded_asset_paths(text: &str) -> Vec<String> {
+    let mut paths = Vec::new();
+    let mut seen = HashSet::new();
+    let prefixes = [
+        format!("{}://localhost/", PAGE_ASSET_SCHEME),
+        format!("http://{}.localhost/", PAGE_ASSET_SCHEME),
+    ];
+    for prefix in &prefixes {
+        for (start, _) in text.match_indices(prefix.as_str()) {
+            let url = &text[start..];
+            let end = url
+                .find(|c: char| c.is_whitespace() || matches!(c, ')' | '"' | '\'' | '>'))
+                .unwrap_or(url.len());
+            if let Some(stored_path) = page_asset_stored_path(&url[..end]) {
+                if seen.insert(stored_path.to_string()) {
+                    paths.push(stored_path.to_string());
+                }
+            }
+        }
+    }
+    paths
+}
+
+/// `data:` URI for a page asset, so the HTML file stands alone. URLs that are not page
+/// assets, or whose file is gone, are kept as written.
+fn embedded_image_src(assets_root: &Path, url: &str) -> String {
+    page_asset_stored_path(url)
+        .and_then(|stored_path| page_asset_file(assets_root, stored_path))
+        .and_then(|file| {
+            let bytes = fs::read(&file).ok()?;
+            Some(format!(
+                "data:{};base64,{}",
+                mime_type_for(&file.to_string_lossy()),
+                STANDARD.encode(bytes)
+            ))
+        })
+        .unwrap_or_else(|| url.to_string())
+}
+
+/// A standalone HTML document for `page` with its images inlined.
+pub(crate) fn page_html_in_conn(
+    conn: &Connection,
+    page: &Page,
+    assets_root: &Path,
+) -> Result<String, String> {
+    let title = if page.title.trim().is_empty() {
+        "Untitled"
+    } else {
+        page.title.trim()
+    };
+    let mut meta = vec![format!(
+        "Created {}",
+        escape_html(page.created_at.get(..10).unwrap_or(&page.created_at))
+    )];
+    let tags = page_tags(conn, page.id)?;
+    if !tags.is_empty() {
+        let tags: Vec<String> = tags
+            .iter()
+            .map(|tag| format!("#{}", escape_html(tag)))
+            .collect();
+        meta.push(tags.join(" "));
+    }
+    let body = markdown_to_html(&page.content, &|url| embedded_image_src(assets_root, url));
+
+    Ok(format!(
+        "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n\
+         <meta name=\"viewport\" content=\"width=device-width, initial-scale=1\">\n\
+         <title>{title}</title>\n<style>{style}</style>\n</head>\n<body>\n<article>\n\
+         <h1 class=\"title\">{title}</h1>\n<p class=\"meta\">{meta}</p>\n{body}</article>\n\
+         </body>\n</html>\n",
+        title = escape_html(title),
+        style = PAGE_HTML_STYLE,
+        meta = meta.join(" · "),
+        body = body,
+    ))
+}
+
+fn write_page_file(path: &Path, contents: &str) -> Result<(), String> {
+    if let Some(parent) = path
+        .parent()
+        .filter(|parent| !parent.as_os_str().is_empty())
+    {
+        fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create {}: {}", parent.display(), e))?;
+    }
+    fs::write(path, contents).map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+}
+
+/// Writes `page` to `path`. Markdown files get their images copied into an `assets`
+/// folder beside them and linked relatively; HTML files embed them. Returns the number
+/// of images copied.
+fn write_page_export(
+    conn: &Connection,
+    page: &Page,
+    format: PageExportFormat,
+    path: &Path,
+    assets_root: &Path,
+) -> Result<i64, String> {
+    match format {
+        PageExportFormat::Html => {
+            write_page_file(path, &page_html_in_conn(conn, page, assets_root)?)?;
+            Ok(0)
+        }
+        PageExportFormat::Markdown => {
+            let mut markdown = page_markdown_in_conn(conn, page)?;
+            let folder = path.parent().unwrap_or_else(|| Path::new(""));
+            let mut assets_copied = 0;
+            for stored_path in embedded_asset_paths(&markdown) {
+                let Some(source) = page_asset_file(assets_root, &stored_path) else {
+                    continue;
+                };
+                if !source.is_file() {
+                    continue;
+                }
+                let destination = folder.join(ASSETS_FOLDER).join(&stored_path);
+                if let Some(parent) = destination.parent() {
+                    fs::create_dir_all(parent)
+                        .map_err(|e| format!("Failed to create {}: {}", parent.display(), e))?;
+                }
+                fs::copy(&source, &destination)
+                    .map_err(|e| format!("Failed to copy {}: {}", source.display(), e))?;
+                assets_copied += 1;
+
+                let relative = format!("{}/{}", ASSETS_FOLDER, stored_path);
+                for prefix in [
+                    format!("{}://localhost/", PAGE_ASSET_SCHEME),
+                    format!("http://{}.localhost/", PAGE_ASSET_SCHEME),
+                ] {
+                    markdown = markdown.replace(&format!("{}{}", prefix, stored_path), &relative);
+                }
+            }
+            write_page_file(path, &markdown)?;
+            Ok(assets_copied)
+        }
+    }
+}
+
+/// Where `export_page` writes: `path` itself, or `<id>-<title>.<ext>` inside it when it
+/// is an existing folder. A missing or different extension is replaced.
+fn page_export_path(path: &str, page: &Page, format: PageExportFormat) -> Result<PathBuf, String> {
+    let path = path.trim();
+    if path.is_empty() {
+        return Err("Output path is required".to_string());
+    }
+    let path = PathBuf::from(path);
+    if path.is_dir() {
+        return Ok(path
+            .join(page_file_name(page))
+            .with_extension(format.extension()));
+    }
+    match path.extension().and_then(|extension| extension.to_str()) {
+        Some(extension)
+            if format == PageExportFormat::Markdown
+                && (extension.eq_ignore_ascii_case("md")
+                    || extension.eq_ignore_ascii_case("markdown")) =>
+        {
+            Ok(path)
+        }
+        Some(extension)
+            if format == PageExportFormat::Html
+                && (extension.eq_ignore_ascii_case("html")
+                    || extension.eq_ignore_ascii_case("htm")) =>
+        {
+            Ok(path)
+        }
+        _ => Ok(path.with_extension(format.extension())),
+    }
+}
+
+/// A locked page is only exported while it is unlocked (`key`), as its plaintext.
+pub(crate) fn export_page_in_conn(
+    conn: &Connection,
+    id: i64,
+    format: PageExportFormat,
+    path: &str,
+    assets_root: &Path,
+    cipher: &JournalCipher,
+    key: Option<&PageKey>,
+) -> Result<PathBuf, String> {
+    let page = get_page_in_conn(conn, id)?
+        .ok_or_else(|| "Page not found".to_string())?
+        .decrypt_with(cipher)?;
+    let page = reveal_page(page, key)?;
+    if is_locked_page_content(&page.content) {
+        return Err(PAGE_LOCKED.to_string());
+    }
+    let path = page_export_path(path, &page, format)?;
+    write_page_export(conn, &page, format, &path, assets_root)?;
+    Ok(path)
+}
+
+/// Writes every page (trash and locked pages excluded) into `dir` as
+/// `<id>-<title>.<ext>`; Markdown exports share one `assets` folder.
+pub(crate) fn export_all_pages_in_conn(
+    conn: &Connection,
+    dir: &Path,
+    format: PageExportFormat,
+    assets_root: &Path,
+    cipher: &JournalCipher,
+) -> Result<PageExportSummary, String> {
+    let mut pages = get_pages_in_conn(conn)?.decrypt_with(cipher)?;
+    pages.retain(|page| !page.locked);
+    pages.sort_by_key(|page| page.id);
+
+    fs::create_dir_all(dir).map_err(|e| format!("Failed to create {}: {}", dir.display(), e))?;
+    let mut assets_copied = 0;
+    for page in &pages {
+        let path = dir
+            .join(page_file_name(page))
+            .with_extension(format.extension());
+        assets_copied += write_page_export(conn, page, format, &path, assets_root)?;
+    }
+
+    Ok(PageExportSummary {
+        directory: dir.display().to_string(),
+        pages_written: pages.len() as i64,
+        assets_copied,
+    })
+}
+
+/// Writes one page as Markdown or standalone HTML and returns the file path.
+#[tauri::command]
+pub fn export_page(
+    id: i64,
+    format: PageExportFormat,
+    path: String,
+    app: AppHandle,
+    state: State<'_, AppState>,
+) -> Result<String, String> {
+    let assets_root = page_assets_root(&app)?;
+    let cipher = state.journal_cipher()?;
+    let conn = state.db.lock().map_err(|e| e.to_string())?;
+    let key = state.page_key(id)?;
+    let path = export_page_in_conn(
+        &conn,
+        id,
+        format,
+        &path,
+        &assets_root,
+        &cipher,
+        key.as_ref(),
+    )?;
+    Ok(path.display().to_string())
+}
+
+/// Writes every page into `dir`; Markdown unless `format` says otherwise.
+#[tauri::command]
+pub fn export_all_pages(
+    dir: String,
+    format: Option<PageExportFormat>,
+    app: AppHandle,
+    state: State<'_, AppState>,
+) -> Result<PageExportSummary, String> {
+    let dir = dir.trim();
+    if dir.is_empty() {
+        return Err("Export folder is required".to_string());
+    }
+
+    let assets_root = page_assets_root(&app)?;
+    let cipher = state.journal_cipher()?;
+    let conn = state.db.lock().map_err(|e| e.to_string())?;
+    export_all_pages_in_conn(
+        &conn,
+        &PathBuf::from(dir),
+        format.unwrap_or(PageExportFormat::Markdown),
+        &assets_root,
+        &cipher,
+    )
+}
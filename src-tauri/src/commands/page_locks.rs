@@ -0,0 +1,315 @@
+use crate::models::Page;
+use base64::{engine::general_purpose::STANDARD, Engine};
+use rusqlite::{params, Connection};
+use std::collections::HashMap;
+use tauri::State;
+
+use super::encryption::{
+    derive_key, JournalCipher, JournalContent, MIN_PASSPHRASE_CHARS, SALT_LEN,
+};
+use super::mirror::mirror_page;
+use super::page_properties::set_page_properties_in_conn;
+use super::pages::{get_page_in_conn, set_page_links_in_conn};
+use super::AppState;
+
+/// Prefix of locked page content: `lock:v1:<base64 salt>:<content encrypted with the
+/// page key>`. Journal encryption, when on, wraps the whole value like any other.
+const LOCKED_PREFIX: &str = "lock:v1:";
+
+pub(crate) const PAGE_LOCKED: &str = "This page is locked. Unlock it with its passphrase first.";
+
+/// Key of one locked page, derived from its passphrase and the salt in its content.
+/// Kept in `AppState` while the page is unlocked for the session.
+#[derive(Clone)]
+pub struct PageKey {
+    salt: String,
+    cipher: JournalCipher,
+}
+
+impl PageKey {
+    fn derive(passphrase: &str, salt: &str) -> Result<Self, String> {
+        let salt_bytes = STANDARD
+            .decode(salt)
+            .map_err(|_| "Locked page content is corrupted".to_string())?;
+        Ok(Self {
+            salt: salt.to_string(),
+            cipher: JournalCipher::unlocked(derive_key(passphrase, &salt_bytes)?),
+        })
+    }
+
+    /// Checks `passphrase` against content locked with it and returns the key.
+    fn for_locked(passphrase: &str, locked: &str) -> Result<Self, String> {
+        let (salt, _) = split_locked(locked)?;
+        let key = Self::derive(passphrase, salt)?;
+        key.unlock(locked)
+            .map_err(|_| "Wrong passphrase".to_string())?;
+        Ok(key)
+    }
+
+    fn lock(&self, plaintext: &str) -> Result<String, String> {
+        Ok(format!(
+            "{}{}:{}",
+            LOCKED_PREFIX,
+            self.salt,
+            self.cipher.encrypt(plaintext)?
+        ))
+    }
+
+    fn unlock(&self, locked: &str) -> Result<String, String> {
+        let (_, encrypted) = split_locked(locked)?;
+        self.cipher
+            .decrypt(encrypted)
+            .map_err(|_| "Failed to decrypt locked page".to_string())
+    }
+}
+
+/// Keys of pages unlocked this session, by page id.
+pub type PageKeys = HashMap<i64, PageKey>;
+
+fn split_locked(locked: &str) -> Result<(&str, &str), String> {
+    locked
+        .strip_prefix(LOCKED_PREFIX)
+        .and_then(|rest| rest.split_once(':'))
+        .ok_or_else(|| "Locked page content is corrupted".to_string())
+}
+
+/// Whether journal-decrypted page content is locked with a page passphrase.
+pub(crate) fn is_locked_page_content(content: &str) -> bool {
+    content.starts_with(LOCKED_PREFIX)
+}
+
+impl AppState {
+    pub(crate) fn page_key(&self, page_id: i64) -> Result<Option<PageKey>, String> {
+        Ok(self
+            .page_keys
+            .lock()
+            .map_err(|e| e.to_string())?
+            .get(&page_id)
+            .cloned())
+    }
+
+    fn set_page_key(&self, page_id: i64, key: Option<PageKey>) -> Result<(), String> {
+        let mut keys = self.page_keys.lock().map_err(|e| e.to_string())?;
+        match key {
+            Some(key) => keys.insert(page_id, key),
+            None => keys.remove(&page_id),
+        };
+        Ok(())
+    }
+}
+
+/// Content of a locked page as it is stored before journal encryption. Saving a
+/// locked page needs its key from `unlock_page`.
+pub(crate) fn lock_page_content(
+    page: &Page,
+    content: &str,
+    key: Option<&PageKey>,
+) -> Result<String, String> {
+    if !page.locked {
+        return Ok(content.to_string());
+    }
+    key.ok_or_else(|| PAGE_LOCKED.to_string())?.lock(content)
+}
+
+/// `page` with its content unlocked when `key` is known; otherwise unchanged, so the
+/// content stays locked.
+pub(crate) fn reveal_page(mut page: Page, key: Option<&PageKey>) -> Result<Page, String> {
+    if let (true, Some(key)) = (page.locked, key) {
+        if is_locked_page_content(&page.content) {
+            page.content = key.unlock(&page.content)?;
+        }
+    }
+    Ok(page)
+}
+
+/// Rewrites the page and every stored revision through `rewrite`, which gets and
+/// returns journal-decrypted content, then sets the `locked` flag.
+fn rewrite_page_contents_in_conn(
+    conn: &mut Connection,
+    id: i64,
+    journal: &JournalCipher,
+    locked: bool,
+    rewrite: impl Fn(&str) -> Result<String, String>,
+) -> Result<(), String> {
+    let tx = conn.transaction().map_err(|e| e.to_string())?;
+    let mut contents = vec![(
+        "pages",
+        id,
+        tx.query_row(
+            "SELECT content FROM pages WHERE id = ?1",
+            params![id],
+            |row| row.get::<_, String>(0),
+        )
+        .map_err(|e| e.to_string())?,
+    )];
+    {
+        let mut stmt = tx
+            .prepare("SELECT id, content FROM page_revisions WHERE page_id = ?1")
+            .map_err(|e| e.to_string())?;
+        let revisions = stmt
+            .query_map(params![id], |row| Ok((row.get(0)?, row.get(1)?)))
+            .map_err(|e| e.to_string())?;
+        for revision in revisions {
+            let (revision_id, content) = revision.map_err(|e| e.to_string())?;
+            contents.push(("page_revisions", revision_id, content));
+        }
+    }
+
+    for (table, row_id, stored) in contents {
+        let content = journal.encrypt(&rewrite(&journal.decrypt(&stored)?)?)?;
+        tx.execute(
+            &format!("UPDATE {} SET content = ?1 WHERE id = ?2", table),
+            params![content, row_id],
+        )
+        .map_err(|e| e.to_string())?;
+    }
+    tx.execute(
+        "UPDATE pages SET locked = ?1 WHERE id = ?2",
+        params![locked, id],
+    )
+    .map_err(|e| e.to_string())?;
+    tx.commit().map_err(|e| e.to_string())
+}
+
+/// Locks the page and its revisions with `passphrase`. Returns the key, so the page
+/// stays open for the session like a freshly encrypted journal. `updated_at` is left
+/// alone: the text did not change.
+pub(crate) fn enable_page_lock_in_conn(
+    conn: &mut Connection,
+    id: i64,
+    passphrase: &str,
+    journal: &JournalCipher,
+) -> Result<PageKey, String> {
+    if passphrase.chars().count() < MIN_PASSPHRASE_CHARS {
+        return Err(format!(
+            "Passphrase must be at least {} characters",
+            MIN_PASSPHRASE_CHARS
+        ));
+    }
+    let page = get_page_in_conn(conn, id)?.ok_or_else(|| "Page not found".to_string())?;
+    if page.locked {
+        return Err("Page is already locked".to_string());
+    }
+
+    let mut salt = [0u8; SALT_LEN];
+    getrandom::fill(&mut salt).map_err(|e| e.to_string())?;
+    let key = PageKey::derive(passphrase, &STANDARD.encode(salt))?;
+    rewrite_page_contents_in_conn(conn, id, journal, true, |content| {
+        if is_locked_page_content(content) {
+            Ok(content.to_string())
+        } else {
+            key.lock(content)
+        }
+    })?;
+    set_page_links_in_conn(conn, id, "")?;
+    set_page_properties_in_conn(conn, id, "")?;
+    // Drop the replaced plaintext from the search index and the database file.
+    conn.execute_batch(
+        "INSERT INTO pages_fts(pages_fts) VALUES ('optimize');
+         VACUUM;",
+    )
+    .map_err(|e| e.to_string())?;
+
+    Ok(key)
+}
+
+/// Checks `passphrase` and returns the page with its content unlocked, plus the key.
+pub(crate) fn unlock_page_in_conn(
+    conn: &Connection,
+    id: i64,
+    passphrase: &str,
+    journal: &JournalCipher,
+) -> Result<(Page, PageKey), String> {
+    let page = get_page_in_conn(conn, id)?
+        .ok_or_else(|| "Page not found".to_string())?
+        .decrypt_with(journal)?;
+    if !page.locked {
+        return Err("Page is not locked".to_string());
+    }
+    let key = PageKey::for_locked(passphrase, &page.content)?;
+
+    Ok((reveal_page(page, Some(&key))?, key))
+}
+
+/// Removes the lock for good: the page and its revisions are stored unlocked again.
+pub(crate) fn disable_page_lock_in_conn(
+    conn: &mut Connection,
+    id: i64,
+    passphrase: &str,
+    journal: &JournalCipher,
+) -> Result<Page, String> {
+    let (_, key) = unlock_page_in_conn(conn, id, passphrase, journal)?;
+    rewrite_page_contents_in_conn(conn, id, journal, false, |content| {
+        if is_locked_page_content(content) {
+            key.unlock(content)
+        } else {
+            Ok(content.to_string())
+        }
+    })?;
+
+    let page = get_page_in_conn(conn, id)?
+        .ok_or_else(|| "Page not found".to_string())?
+        .decrypt_with(journal)?;
+    set_page_links_in_conn(conn, id, &page.content)?;
+    set_page_properties_in_conn(conn, id, &page.content)?;
+    Ok(page)
+}
+
+#[tauri::command]
+pub fn enable_page_lock(
+    id: i64,
+    passphrase: String,
+    state: State<'_, AppState>,
+) -> Result<Page, String> {
+    let journal = state.journal_cipher()?;
+    let mut conn = state.db.lock().map_err(|e| e.to_string())?;
+    let key = enable_page_lock_in_conn(&mut conn, id, &passphrase, &journal)?;
+    mirror_page(&conn, id);
+    let page = get_page_in_conn(&conn, id)?
+        .ok_or_else(|| "Page not found".to_string())?
+        .decrypt_with(&journal)?;
+    drop(conn);
+
+    let page = reveal_page(page, Some(&key))?;
+    state.set_page_key(id, Some(key))?;
+    Ok(page)
+}
+
+/// Opens a locked page for this session; later `get_page` calls return its text and
+/// `update_page` locks edits with the same key until `lock_page` or a restart.
+#[tauri::command]
+pub fn unlock_page(
+    id: i64,
+    passphrase: String,
+    state: State<'_, AppState>,
+) -> Result<Page, String> {
+    let journal = state.journal_cipher()?;
+    let conn = state.db.lock().map_err(|e| e.to_string())?;
+    let (page, key) = unlock_page_in_conn(&conn, id, &passphrase, &journal)?;
+    drop(conn);
+
+    state.set_page_key(id, Some(key))?;
+    Ok(page)
+}
+
+/// Forgets the session key, so the page needs its passphrase again.
+#[tauri::command]
+pub fn lock_page(id: i64, state: State<'_, AppState>) -> Result<(), String> {
+    state.set_page_key(id, None)
+}
+
+#[tauri::command]
+pub fn disable_page_lock(
+    id: i64,
+    passphrase: String,
+    state: State<'_, AppState>,
+) -> Result<Page, String> {
+    let journal = state.journal_cipher()?;
+    let mut conn = state.db.lock().map_err(|e| e.to_string())?;
+    let page = disable_page_lock_in_conn(&mut conn, id, &passphrase, &journal)?;
+    mirror_page(&conn, id);
+    drop(conn);
+
+    state.set_page_key(id, None)?;
+    Ok(page)
+}
@@ -0,0 +1,229 @@
+use crate::models::{Page, PageProperty, PagePropertyValue};
+use rusqlite::{params, Connection};
+use tauri::State;
+
+use super::encryption::{journal_encryption_enabled_in_conn, JournalContent};
+use super::markdown::yaml_scalar;
+use super::pages::{collect_pages, is_page_locked_in_conn};
+use super::AppState;
+
+/// Longest property value kept; longer ones are prose, not something to filter by.
+const MAX_PROPERTY_VALUE_CHARS: usize = 200;
+
+/// `key: value` pairs of a leading `---` frontmatter block, in order. Keys are
+/// lowercased; `key: [a, b]` and `- a` lists under `key:` give one pair per item.
+/// Nested maps, empty values and content without a closing `---` give nothing.
+pub(crate) fn extract_page_properties(content: &str) -> Vec<(String, String)> {
+    let mut lines = content.trim_start_matches('\u{feff}').lines();
+    if lines.next().map(str::trim) != Some("---") {
+        return Vec::new();
+    }
+
+    let mut properties = Vec::new();
+    let mut list_key: Option<String> = None;
+    let mut closed = false;
+    for line in lines {
+        if line.trim() == "---" {
+            closed = true;
+            break;
+        }
+        if let Some(item) = line.trim_start().strip_prefix("- ") {
+            if let Some(key) = &list_key {
+                properties.push((key.clone(), yaml_scalar(item)));
+            }
+            continue;
+        }
+        if line.starts_with([' ', '\t']) {
+            continue;
+        }
+        let Some((key, value)) = line.split_once(':') else {
+            list_key = None;
+            continue;
+        };
+        let key = key.trim().to_lowercase();
+        let value = value.trim();
+        if key.is_empty() || key.starts_with('#') {
+            list_key = None;
+            continue;
+        }
+        list_key = value.is_empty().then(|| key.clone());
+        match value
+            .strip_prefix('[')
+            .and_then(|rest| rest.strip_suffix(']'))
+        {
+            Some(items) => properties.extend(
+                items
+                    .split(',')
+                    .map(|item| (key.clone(), yaml_scalar(item))),
+            ),
+            None if !value.is_empty() => properties.push((key, yaml_scalar(value))),
+            None => {}
+        }
+    }
+    if !closed {
+        return Vec::new();
+    }
+
+    properties.retain(|(_, value)| {
+        !value.is_empty() && value.chars().count() <= MAX_PROPERTY_VALUE_CHARS
+    });
+    properties
+}
+
+/// Replaces the stored properties of a page with the frontmatter of `content`, which
+/// must be the plaintext; callers index before encrypting, like page links. Values
+/// are stored as plaintext, so locked pages and encrypted journals keep none.
+pub(crate) fn set_page_properties_in_conn(
+    conn: &Connection,
+    page_id: i64,
+    content: &str,
+) -> Result<(), String> {
+    conn.execute(
+        "DELETE FROM page_properties WHERE page_id = ?1",
+        params![page_id],
+    )
+    .map_err(|e| e.to_string())?;
+    if is_page_locked_in_conn(conn, page_id)? || journal_encryption_enabled_in_conn(conn)? {
+        return Ok(());
+    }
+
+    for (key, value) in extract_page_properties(content) {
+        conn.execute(
+            "INSERT OR IGNORE INTO page_properties (page_id, key, value) VALUES (?1, ?2, ?3)",
+            params![page_id, key, value],
+        )
+        .map_err(|e| e.to_string())?;
+    }
+
+    Ok(())
+}
+
+/// Indexes the properties of every page again from its stored content, which must
+/// not be journal-encrypted; used once journal encryption is turned off.
+pub(crate) fn reindex_page_properties_in_conn(conn: &Connection) -> Result<(), String> {
+    let mut stmt = conn
+        .prepare("SELECT id, content FROM pages")
+        .map_err(|e| e.to_string())?;
+    let pages_iter = stmt
+        .query_map([], |row| {
+            Ok((row.get::<_, i64>(0)?, row.get::<_, String>(1)?))
+        })
+        .map_err(|e| e.to_string())?;
+    let mut pages = Vec::new();
+    for page in pages_iter {
+        pages.push(page.map_err(|e| e.to_string())?);
+    }
+    for (id, content) in &pages {
+        set_page_properties_in_conn(conn, *id, content)?;
+    }
+
+    Ok(())
+}
+
+/// Properties of one page in frontmatter order.
+pub(crate) fn get_page_properties_in_conn(
+    conn: &Connection,
+    page_id: i64,
+) -> Result<Vec<PageProperty>, String> {
+    let mut stmt = conn
+        .prepare("SELECT key, value FROM page_properties WHERE page_id = ?1 ORDER BY id")
+        .map_err(|e| e.to_string())?;
+    let properties = stmt
+        .query_map(params![page_id], |row| {
+            Ok(PageProperty {
+                key: row.get(0)?,
+                value: row.get(1)?,
+            })
+        })
+        .map_err(|e| e.to_string())?;
+
+    properties
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| e.to_string())
+}
+
+/// Live pages with property `key`, or with `key: value` when `value` is given. Both
+/// match case-insensitively; most recently edited first.
+pub(crate) fn get_pages_by_property_in_conn(
+    conn: &Connection,
+    key: &str,
+    value: Option<&str>,
+) -> Result<Vec<Page>, String> {
+    let key = key.trim();
+    if key.is_empty() {
+        return Err("Property name is required".to_string());
+    }
+    let value = value.map(str::trim).filter(|value| !value.is_empty());
+    let mut stmt = conn
+        .prepare(
+            "SELECT p.id, p.title, p.content, p.parent_id, p.created_at, p.updated_at,
+                    p.pinned, p.favorite, p.archived, p.sort_index, p.locked, p.icon, p.cover
+             FROM pages p
+             WHERE p.deleted_at IS NULL AND EXISTS (
+                 SELECT 1 FROM page_properties pp
+                 WHERE pp.page_id = p.id AND pp.key = ?1 AND (?2 IS NULL OR pp.value = ?2)
+             )
+             ORDER BY p.updated_at DESC",
+        )
+        .map_err(|e| e.to_string())?;
+    collect_pages(&mut stmt, params![key, value])
+}
+
+/// Values used for `key` on live pages with their page counts, most used first; the
+/// columns of a board grouped by that property.
+pub(crate) fn get_page_property_values_in_conn(
+    conn: &Connection,
+    key: &str,
+) -> Result<Vec<PagePropertyValue>, String> {
+    let mut stmt = conn
+        .prepare(
+            "SELECT MIN(pp.value), COUNT(DISTINCT pp.page_id) FROM page_properties pp
+             JOIN pages p ON p.id = pp.page_id
+             WHERE pp.key = ?1 AND p.deleted_at IS NULL
+             GROUP BY pp.value
+             ORDER BY 2 DESC, 1 COLLATE NOCASE",
+        )
+        .map_err(|e| e.to_string())?;
+    let values = stmt
+        .query_map(params![key.trim()], |row| {
+            Ok(PagePropertyValue {
+                value: row.get(0)?,
+                page_count: row.get(1)?,
+            })
+        })
+        .map_err(|e| e.to_string())?;
+
+    values
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn get_page_properties(
+    page_id: i64,
+    state: State<'_, AppState>,
+) -> Result<Vec<PageProperty>, String> {
+    let conn = state.db.lock().map_err(|e| e.to_string())?;
+    get_page_properties_in_conn(&conn, page_id)
+}
+
+/// Pages whose frontmatter sets `key` (to `value`, when given), e.g. `status: doing`.
+#[tauri::command]
+pub fn get_pages_by_property(
+    key: String,
+    value: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<Vec<Page>, String> {
+    let conn = state.db.lock().map_err(|e| e.to_string())?;
+    get_pages_by_property_in_conn(&conn, &key, value.as_deref())?
+        .decrypt_with(&state.journal_cipher()?)
+}
+
+#[tauri::command]
+pub fn get_page_property_values(
+    key: String,
+    state: State<'_, AppState>,
+) -> Result<Vec<PagePropertyValue>, String> {
+    let conn = state.db.lock().map_err(|e| e.to_string())?;
+    get_page_property_values_in_conn(&conn, &key)
+}
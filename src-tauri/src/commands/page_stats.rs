@@ -0,0 +1,193 @@
+use crate::models::{Page, PageStats, PagesStats};
+use chrono::{DateTime, Utc};
+use rusqlite::Connection;
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use tauri::State;
+
+use super::encryption::{JournalCipher, JournalContent};
+use super::pages::{get_page_in_conn, get_pages_in_conn};
+use super::AppState;
+
+/// Average silent reading speed for technical prose.
+const WORDS_PER_MINUTE: i64 = 200;
+/// Pages not edited for this long count as stale in the health summary.
+const STALE_PAGE_DAYS: i64 = 90;
+/// Length of each ranked list in `PagesStats`.
+const PAGE_STATS_LIST_LIMIT: usize = 5;
+
+/// Words outside fenced code blocks. Markdown markers such as `#`, `-` or `|` are not
+/// words; anything containing a letter or digit is.
+pub(crate) fn markdown_word_count(markdown: &str) -> i64 {
+    let mut in_code_block = false;
+    let mut words = 0;
+    for line in markdown.lines() {
+        if line.trim_start().starts_with("```") {
+            in_code_block = !in_code_block;
+            continue;
+        }
+        if !in_code_block {
+            words += line
+                .split_whitespace()
+                .filter(|word| word.chars().any(char::is_alphanumeric))
+                .count() as i64;
+        }
+    }
+    words
+}
+
+/// Whole minutes, rounded up; an empty page takes no time.
+fn reading_minutes(words: i64) -> i64 {
+    (words + WORDS_PER_MINUTE - 1) / WORDS_PER_MINUTE
+}
+
+struct PageLinkCounts {
+    outgoing_links: i64,
+    backlinks: i64,
+    revision_count: i64,
+}
+
+fn page_link_counts_in_conn(conn: &Connection) -> Result<HashMap<i64, PageLinkCounts>, String> {
+    let mut stmt = conn
+        .prepare(
+            "SELECT p.id,
+                    (SELECT COUNT(*) FROM page_links l WHERE l.source_page_id = p.id),
+                    (SELECT COUNT(DISTINCT l.source_page_id) FROM page_links l
+                     JOIN pages source ON source.id = l.source_page_id
+                     WHERE l.target_title = trim(p.title) AND source.id != p.id
+                       AND source.deleted_at IS NULL),
+                    (SELECT COUNT(*) FROM page_revisions r WHERE r.page_id = p.id)
+             FROM pages p WHERE p.deleted_at IS NULL",
+        )
+        .map_err(|e| e.to_string())?;
+    let counts_iter = stmt
+        .query_map([], |row| {
+            Ok((
+                row.get::<_, i64>(0)?,
+                PageLinkCounts {
+                    outgoing_links: row.get(1)?,
+                    backlinks: row.get(2)?,
+                    revision_count: row.get(3)?,
+                },
+            ))
+        })
+        .map_err(|e| e.to_string())?;
+
+    let mut counts = HashMap::new();
+    for row in counts_iter {
+        let (page_id, page_counts) = row.map_err(|e| e.to_string())?;
+        counts.insert(page_id, page_counts);
+    }
+    Ok(counts)
+}
+
+/// Locked pages report no content figures: their stored text is ciphertext.
+fn page_stats(page: &Page, counts: Option<&PageLinkCounts>, now: DateTime<Utc>) -> PageStats {
+    let content = if page.locked { "" } else { &page.content };
+    let word_count = markdown_word_count(content);
+    let days_since_edit = DateTime::parse_from_rfc3339(&page.updated_at)
+        .map(|updated_at| (now - updated_at.with_timezone(&Utc)).num_days().max(0))
+        .unwrap_or(0);
+
+    PageStats {
+        page_id: page.id,
+        title: page.title.clone(),
+        parent_id: page.parent_id,
+        word_count,
+        character_count: content.chars().count() as i64,
+        reading_minutes: reading_minutes(word_count),
+        heading_count: content
+            .lines()
+            .filter(|line| {
+                let hashes = line.len() - line.trim_start_matches('#').len();
+                (1..=6).contains(&hashes) && line[hashes..].starts_with(' ')
+            })
+            .count() as i64,
+        outgoing_links: counts.map_or(0, |counts| counts.outgoing_links),
+        backlinks: counts.map_or(0, |counts| counts.backlinks),
+        revision_count: counts.map_or(0, |counts| counts.revision_count),
+        created_at: page.created_at.clone(),
+        updated_at: page.updated_at.clone(),
+        days_since_edit,
+    }
+}
+
+pub(crate) fn get_page_stats_in_conn(
+    conn: &Connection,
+    id: i64,
+    cipher: &JournalCipher,
+    now: DateTime<Utc>,
+) -> Result<PageStats, String> {
+    let page = get_page_in_conn(conn, id)?
+        .ok_or_else(|| "Page not found".to_string())?
+        .decrypt_with(cipher)?;
+    let counts = page_link_counts_in_conn(conn)?;
+    Ok(page_stats(&page, counts.get(&page.id), now))
+}
+
+fn top_pages(
+    stats: &[PageStats],
+    compare: impl Fn(&PageStats, &PageStats) -> Ordering,
+) -> Vec<PageStats> {
+    let mut ranked = stats.to_vec();
+    ranked.sort_by(|left, right| compare(left, right).then_with(|| left.title.cmp(&right.title)));
+    ranked.truncate(PAGE_STATS_LIST_LIMIT);
+    ranked
+}
+
+/// Knowledge base health: totals over all live unlocked pages, how many are empty,
+/// stale or orphaned (no parent and no backlinks), and the largest, stalest and most
+/// recently edited pages.
+pub(crate) fn get_pages_stats_in_conn(
+    conn: &Connection,
+    cipher: &JournalCipher,
+    now: DateTime<Utc>,
+) -> Result<PagesStats, String> {
+    let counts = page_link_counts_in_conn(conn)?;
+    let stats = get_pages_in_conn(conn)?
+        .decrypt_with(cipher)?
+        .iter()
+        .filter(|page| !page.locked)
+        .map(|page| page_stats(page, counts.get(&page.id), now))
+        .collect::<Vec<_>>();
+
+    let page_count = stats.len() as i64;
+    let total_words = stats.iter().map(|page| page.word_count).sum::<i64>();
+    let count_where = |predicate: &dyn Fn(&PageStats) -> bool| {
+        stats.iter().filter(|page| predicate(page)).count() as i64
+    };
+
+    Ok(PagesStats {
+        page_count,
+        total_words,
+        total_reading_minutes: stats.iter().map(|page| page.reading_minutes).sum(),
+        average_words: if page_count == 0 {
+            0
+        } else {
+            total_words / page_count
+        },
+        empty_pages: count_where(&|page| page.word_count == 0),
+        stale_pages: count_where(&|page| page.days_since_edit >= STALE_PAGE_DAYS),
+        orphan_pages: count_where(&|page| page.parent_id.is_none() && page.backlinks == 0),
+        edited_last_7_days: count_where(&|page| page.days_since_edit < 7),
+        edited_last_30_days: count_where(&|page| page.days_since_edit < 30),
+        stale_after_days: STALE_PAGE_DAYS,
+        largest: top_pages(&stats, |left, right| right.word_count.cmp(&left.word_count)),
+        stalest: top_pages(&stats, |left, right| left.updated_at.cmp(&right.updated_at)),
+        recently_edited: top_pages(&stats, |left, right| right.updated_at.cmp(&left.updated_at)),
+    })
+}
+
+#[tauri::command]
+pub fn get_page_stats(id: i64, state: State<'_, AppState>) -> Result<PageStats, String> {
+    let cipher = state.journal_cipher()?;
+    let conn = state.db.lock().map_err(|e| e.to_string())?;
+    get_page_stats_in_conn(&conn, id, &cipher, Utc::now())
+}
+
+#[tauri::command]
+pub fn get_pages_stats(state: State<'_, AppState>) -> Result<PagesStats, String> {
+    let cipher = state.journal_cipher()?;
+    let conn = state.db.lock().map_err(|e| e.to_string())?;
+    get_pages_stats_in_conn(&conn, &cipher, Utc::now())
+}
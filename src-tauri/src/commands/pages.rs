@@ -0,0 +1,1080 @@
+use crate::models::{Page, PageRevision, PageSearchResult, PageTag, RecentPage, TrashedPage};
+use chrono::Utc;
+use rusqlite::{params, Connection, OptionalExtension};
+use tauri::{AppHandle, State};
+
+use super::encryption::{search_encrypted_pages_in_conn, JournalContent};
+use super::entries::fts_query_from_text;
+use super::mirror::mirror_page;
+use super::page_assets::collect_orphan_page_assets;
+use super::page_locks::{lock_page_content, reveal_page};
+use super::page_properties::set_page_properties_in_conn;
+use super::settings::read_i64_setting;
+use super::validation::{normalize_entry_tag, normalize_page_cover, normalize_page_icon};
+use super::AppState;
+
+const PAGE_COLUMNS: &str =
+    "id, title, content, parent_id, created_at, updated_at, pinned, favorite, archived, sort_index, locked, icon, cover";
+
+fn page_from_row(row: &rusqlite::Row<'_>) -> rusqlite::Result<Page> {
+    Ok(Page {
+        id: row.get(0)?,
+        title: row.get(1)?,
+        content: row.get(2)?,
+        parent_id: row.get(3)?,
+        created_at: row.get(4)?,
+        updated_at: row.get(5)?,
+        pinned: row.get(6)?,
+        favorite: row.get(7)?,
+        archived: row.get(8)?,
+        sort_index: row.get(9)?,
+        locked: row.get(10)?,
+        icon: row.get(11)?,
+        cover: row.get(12)?,
+    })
+}
+
+pub(crate) fn collect_pages(
+    stmt: &mut rusqlite::Statement<'_>,
+    params: impl rusqlite::Params,
+) -> Result<Vec<Page>, String> {
+    let pages_iter = stmt
+        .query_map(params, page_from_row)
+        .map_err(|e| e.to_string())?;
+
+    let mut pages = Vec::new();
+    for page in pages_iter {
+        pages.push(page.map_err(|e| e.to_string())?);
+    }
+
+    Ok(pages)
+}
+
+/// Every page: pinned ones first, then in manual order, then most recently edited;
+/// `parent_id` lets the UI build the tree.
+pub(crate) fn get_pages_in_conn(conn: &Connection) -> Result<Vec<Page>, String> {
+    let mut stmt = conn
+        .prepare(&format!(
+            "SELECT {} FROM pages WHERE deleted_at IS NULL ORDER BY pinned DESC, sort_index IS NULL, sort_index, updated_at DESC",
+            PAGE_COLUMNS
+        ))
+        .map_err(|e| e.to_string())?;
+    collect_pages(&mut stmt, [])
+}
+
+/// `get_pages_in_conn` without archived pages and their subpages, unless
+/// `include_archived` is set.
+pub(crate) fn get_listed_pages_in_conn(
+    conn: &Connection,
+    include_archived: bool,
+) -> Result<Vec<Page>, String> {
+    if include_archived {
+        return get_pages_in_conn(conn);
+    }
+    let mut stmt = conn
+        .prepare(&format!(
+            "WITH RECURSIVE hidden(id) AS (
+                 SELECT id FROM pages WHERE archived = 1
+                 UNION
+                 SELECT child.id FROM pages child JOIN hidden ON child.parent_id = hidden.id
+             )
+             SELECT {} FROM pages
+             WHERE deleted_at IS NULL AND id NOT IN (SELECT id FROM hidden)
+             ORDER BY pinned DESC, sort_index IS NULL, sort_index, updated_at DESC",
+            PAGE_COLUMNS
+        ))
+        .map_err(|e| e.to_string())?;
+    collect_pages(&mut stmt, [])
+}
+
+pub(crate) fn get_page_in_conn(conn: &Connection, id: i64) -> Result<Option<Page>, String> {
+    conn.query_row(
+        &format!(
+            "SELECT {} FROM pages WHERE id = ?1 AND deleted_at IS NULL",
+            PAGE_COLUMNS
+        ),
+        params![id],
+        page_from_row,
+    )
+    .optional()
+    .map_err(|e| e.to_string())
+}
+
+/// Direct children of `parent_id` (top-level pages for `None`), by title.
+pub(crate) fn get_page_children_in_conn(
+    conn: &Connection,
+    parent_id: Option<i64>,
+) -> Result<Vec<Page>, String> {
+    let mut stmt = conn
+        .prepare(&format!(
+            "SELECT {} FROM pages
+             WHERE parent_id IS ?1 AND deleted_at IS NULL
+             ORDER BY title COLLATE NOCASE, id",
+            PAGE_COLUMNS
+        ))
+        .map_err(|e| e.to_string())?;
+    collect_pages(&mut stmt, params![parent_id])
+}
+
+/// Checks that `parent_id` exists and is neither `page_id` itself nor one of its
+/// descendants, which would cut the subtree off from the root.
+pub(crate) fn validate_page_parent(
+    conn: &Connection,
+    page_id: Option<i64>,
+    parent_id: Option<i64>,
+) -> Result<Option<i64>, String> {
+    let Some(parent_id) = parent_id else {
+        return Ok(None);
+    };
+    if get_page_in_conn(conn, parent_id)?.is_none() {
+        return Err("Parent page not found".to_string());
+    }
+    let Some(page_id) = page_id else {
+        return Ok(Some(parent_id));
+    };
+
+    // Walks up from the new parent; `UNION` stops on rows already seen.
+    let creates_cycle: bool = conn
+        .query_row(
+            "WITH RECURSIVE ancestors(id) AS (
+                 SELECT ?1
+                 UNION
+                 SELECT p.parent_id FROM pages p
+                 JOIN ancestors a ON p.id = a.id
+                 WHERE p.parent_id IS NOT NULL
+             )
+             SELECT EXISTS(SELECT 1 FROM ancestors WHERE id = ?2)",
+            params![parent_id, page_id],
+            |row| row.get(0),
+        )
+        .map_err(|e| e.to_string())?;
+    if creates_cycle {
+        return Err("A page cannot be moved into itself or one of its subpages".to_string());
+    }
+
+    Ok(Some(parent_id))
+}
+
+/// `content` is stored as given, so callers encrypt it first.
+pub(crate) fn create_page_in_conn(
+    conn: &Connection,
+    title: &str,
+    content: &str,
+    parent_id: Option<i64>,
+) -> Result<Page, String> {
+    let parent_id = validate_page_parent(conn, None, parent_id)?;
+    let now = Utc::now().to_rfc3339();
+    conn.execute(
+        "INSERT INTO pages (title, content, parent_id, created_at, updated_at)
+         VALUES (?1, ?2, ?3, ?4, ?4)",
+        params![title, content, parent_id, now],
+    )
+    .map_err(|e| e.to_string())?;
+
+    Ok(Page {
+        id: conn.last_insert_rowid(),
+        title: title.to_string(),
+        content: content.to_string(),
+        parent_id,
+        created_at: now.clone(),
+        updated_at: now,
+        pinned: false,
+        favorite: false,
+        archived: false,
+        sort_index: None,
+        locked: false,
+        icon: None,
+        cover: None,
+    })
+}
+
+/// Sets the icon and cover. `None` keeps a field; a blank or invalid value clears it
+/// (see `normalize_page_icon` and `normalize_page_cover`). Like flags, this is not an
+/// edit of the text, so `updated_at` is left alone.
+pub(crate) fn set_page_appearance_in_conn(
+    conn: &Connection,
+    id: i64,
+    icon: Option<String>,
+    cover: Option<String>,
+) -> Result<(), String> {
+    let updated = conn
+        .execute(
+            "UPDATE pages SET
+                 icon = CASE WHEN ?1 THEN ?2 ELSE icon END,
+                 cover = CASE WHEN ?3 THEN ?4 ELSE cover END
+             WHERE id = ?5 AND deleted_at IS NULL",
+            params![
+                icon.is_some(),
+                normalize_page_icon(icon),
+                cover.is_some(),
+                normalize_page_cover(cover),
+                id
+            ],
+        )
+        .map_err(|e| e.to_string())?;
+    if updated == 0 {
+        return Err("Page not found".to_string());
+    }
+
+    Ok(())
+}
+
+/// Sets one of the page flags (`pinned`, `favorite` or `archived`). Flags are not
+/// edits, so `updated_at` is left alone.
+fn set_page_flag_in_conn(
+    conn: &Connection,
+    id: i64,
+    column: &str,
+    value: bool,
+) -> Result<(), String> {
+    let updated = conn
+        .execute(
+            &format!(
+                "UPDATE pages SET {} = ?1 WHERE id = ?2 AND deleted_at IS NULL",
+                column
+            ),
+            params![value, id],
+        )
+        .map_err(|e| e.to_string())?;
+    if updated == 0 {
+        return Err("Page not found".to_string());
+    }
+
+    Ok(())
+}
+
+pub(crate) fn set_page_pinned_in_conn(
+    conn: &Connection,
+    id: i64,
+    pinned: bool,
+) -> Result<(), String> {
+    set_page_flag_in_conn(conn, id, "pinned", pinned)
+}
+
+pub(crate) fn set_page_favorite_in_conn(
+    conn: &Connection,
+    id: i64,
+    favorite: bool,
+) -> Result<(), String> {
+    set_page_flag_in_conn(conn, id, "favorite", favorite)
+}
+
+/// Archived pages keep their place in the tree, links and tags but leave the default
+/// listing, and so does everything below them.
+pub(crate) fn set_page_archived_in_conn(
+    conn: &Connection,
+    id: i64,
+    archived: bool,
+) -> Result<(), String> {
+    set_page_flag_in_conn(conn, id, "archived", archived)
+}
+
+/// Re-parents a page (`None` moves it to the top level). Moving is not an edit, so
+/// `updated_at` is left alone.
+pub(crate) fn move_page_in_conn(
+    conn: &Connection,
+    id: i64,
+    new_parent: Option<i64>,
+) -> Result<(), String> {
+    if get_page_in_conn(conn, id)?.is_none() {
+        return Err("Page not found".to_string());
+    }
+    let parent_id = validate_page_parent(conn, Some(id), new_parent)?;
+    conn.execute(
+        "UPDATE pages SET parent_id = ?1 WHERE id = ?2",
+        params![parent_id, id],
+    )
+    .map_err(|e| e.to_string())?;
+
+    Ok(())
+}
+
+/// Records that `id` was opened at `viewed_at`; only the latest view and a count are kept.
+pub(crate) fn record_page_view_in_conn(
+    conn: &Connection,
+    id: i64,
+    viewed_at: &str,
+) -> Result<(), String> {
+    if get_page_in_conn(conn, id)?.is_none() {
+        return Err("Page not found".to_string());
+    }
+    conn.execute(
+        "INSERT INTO page_views (page_id, viewed_at) VALUES (?1, ?2)
+         ON CONFLICT(page_id) DO UPDATE SET
+            viewed_at = excluded.viewed_at,
+            view_count = view_count + 1",
+        params![id, viewed_at],
+    )
+    .map_err(|e| e.to_string())?;
+
+    Ok(())
+}
+
+/// Pages by when they were last opened, newest first; trashed pages are skipped.
+pub(crate) fn get_recent_pages_in_conn(
+    conn: &Connection,
+    limit: i64,
+) -> Result<Vec<RecentPage>, String> {
+    let mut stmt = conn
+        .prepare(
+            "SELECT p.id, p.title, p.parent_id, v.viewed_at, v.view_count
+             FROM page_views v
+             JOIN pages p ON p.id = v.page_id
+             WHERE p.deleted_at IS NULL
+             ORDER BY v.viewed_at DESC, p.id DESC
+             LIMIT ?1",
+        )
+        .map_err(|e| e.to_string())?;
+    let pages_iter = stmt
+        .query_map(params![limit.max(0)], |row| {
+            Ok(RecentPage {
+                page_id: row.get(0)?,
+                title: row.get(1)?,
+                parent_id: row.get(2)?,
+                viewed_at: row.get(3)?,
+                view_count: row.get(4)?,
+            })
+        })
+        .map_err(|e| e.to_string())?;
+
+    let mut pages = Vec::new();
+    for page in pages_iter {
+        pages.push(page.map_err(|e| e.to_string())?);
+    }
+
+    Ok(pages)
+}
+
+/// Stores the drag-and-drop order of one tree level: each page's `sort_index` becomes
+/// its position in `ordered_ids`. All pages must share a parent; siblings left out
+/// keep their index. Pinned pages still come first. Order changes are not edits, so
+/// `updated_at` is left alone.
+pub(crate) fn reorder_pages_in_conn(
+    conn: &mut Connection,
+    ordered_ids: &[i64],
+) -> Result<(), String> {
+    let mut parent_id = None;
+    for (position, id) in ordered_ids.iter().enumerate() {
+        if ordered_ids[..position].contains(id) {
+            return Err(format!("Page {} is listed twice", id));
+        }
+        let page = get_page_in_conn(conn, *id)?.ok_or_else(|| "Page not found".to_string())?;
+        match parent_id {
+            None => parent_id = Some(page.parent_id),
+            Some(parent) if parent != page.parent_id => {
+                return Err("Pages to reorder must share a parent".to_string())
+            }
+            Some(_) => {}
+        }
+    }
+
+    let tx = conn.transaction().map_err(|e| e.to_string())?;
+    for (position, id) in ordered_ids.iter().enumerate() {
+        tx.execute(
+            "UPDATE pages SET sort_index = ?1 WHERE id = ?2",
+            params![position as i64, id],
+        )
+        .map_err(|e| e.to_string())?;
+    }
+    tx.commit().map_err(|e| e.to_string())
+}
+
+/// Ranked full-text search over page titles and content, best matches first. A title
+/// match weighs ten times as much as one in the body.
+pub(crate) fn search_pages_in_conn(
+    conn: &Connection,
+    query: &str,
+) -> Result<Vec<PageSearchResult>, String> {
+    let Some(fts_query) = fts_query_from_text(query) else {
+        return Ok(Vec::new());
+    };
+
+    let mut stmt = conn
+        .prepare(
+            "SELECT p.id, p.title, p.content, p.parent_id, p.created_at, p.updated_at,
+                    p.pinned, p.favorite, p.archived, p.sort_index, p.locked, p.icon, p.cover,
+                    CASE WHEN p.locked THEN ''
+                         ELSE snippet(pages_fts, 1, '<mark>', '</mark>', '…', 16) END,
+                    bm25(pages_fts, 10.0, 1.0)
+             FROM pages_fts
+             JOIN pages p ON p.id = pages_fts.rowid
+             WHERE pages_fts MATCH ?1 AND p.deleted_at IS NULL
+             ORDER BY bm25(pages_fts, 10.0, 1.0) ASC, p.updated_at DESC
+             LIMIT 100",
+        )
+        .map_err(|e| e.to_string())?;
+
+    let results_iter = stmt
+        .query_map(params![fts_query], |row| {
+            Ok(PageSearchResult {
+                page: page_from_row(row)?,
+                snippet: row.get(13)?,
+                rank: row.get(14)?,
+            })
+        })
+        .map_err(|e| e.to_string())?;
+
+    let mut results = Vec::new();
+    for result in results_iter {
+        results.push(result.map_err(|e| e.to_string())?);
+    }
+
+    Ok(results)
+}
+
+/// Subpages of `id` move up to its parent instead of all landing at the top level.
+fn lift_page_children(conn: &Connection, id: i64) -> Result<(), String> {
+    conn.execute(
+        "UPDATE pages SET parent_id = (SELECT parent_id FROM pages WHERE id = ?1)
+         WHERE parent_id = ?1",
+        params![id],
+    )
+    .map_err(|e| e.to_string())?;
+
+    Ok(())
+}
+
+/// Permanently deletes a page; its subpages move up to the deleted page's parent.
+pub(crate) fn delete_page_in_conn(conn: &Connection, id: i64) -> Result<(), String> {
+    lift_page_children(conn, id)?;
+    conn.execute("DELETE FROM pages WHERE id = ?1", params![id])
+        .map_err(|e| e.to_string())?;
+
+    Ok(())
+}
+
+/// Moves a page to the trash. Subpages move up to its parent and stay there after a
+/// restore; tags, links and revisions stay with the page until the trash is emptied.
+pub(crate) fn trash_page_in_conn(conn: &Connection, id: i64) -> Result<(), String> {
+    if get_page_in_conn(conn, id)?.is_none() {
+        return Err("Page not found".to_string());
+    }
+    lift_page_children(conn, id)?;
+    conn.execute(
+        "UPDATE pages SET deleted_at = ?1 WHERE id = ?2",
+        params![Utc::now().to_rfc3339(), id],
+    )
+    .map_err(|e| e.to_string())?;
+
+    Ok(())
+}
+
+/// Takes a page out of the trash, back under its old parent when that page is still
+/// around and at the top level otherwise.
+pub(crate) fn restore_page_in_conn(conn: &Connection, id: i64) -> Result<Page, String> {
+    let parent_id: Option<i64> = conn
+        .query_row(
+            "SELECT parent_id FROM pages WHERE id = ?1 AND deleted_at IS NOT NULL",
+            params![id],
+            |row| row.get(0),
+        )
+        .optional()
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| "No trashed page with that id".to_string())?;
+    let parent_id = match parent_id {
+        Some(parent_id) if get_page_in_conn(conn, parent_id)?.is_some() => Some(parent_id),
+        _ => None,
+    };
+    conn.execute(
+        "UPDATE pages SET deleted_at = NULL, parent_id = ?1 WHERE id = ?2",
+        params![parent_id, id],
+    )
+    .map_err(|e| e.to_string())?;
+
+    get_page_in_conn(conn, id)?.ok_or_else(|| "Page not found".to_string())
+}
+
+/// Trashed pages, most recently deleted first.
+pub(crate) fn get_trashed_pages_in_conn(conn: &Connection) -> Result<Vec<TrashedPage>, String> {
+    let mut stmt = conn
+        .prepare(&format!(
+            "SELECT {}, deleted_at FROM pages WHERE deleted_at IS NOT NULL
+             ORDER BY deleted_at DESC, id DESC",
+            PAGE_COLUMNS
+        ))
+        .map_err(|e| e.to_string())?;
+    let pages_iter = stmt
+        .query_map([], |row| {
+            Ok(TrashedPage {
+                page: page_from_row(row)?,
+                deleted_at: row.get(13)?,
+            })
+        })
+        .map_err(|e| e.to_string())?;
+
+    let mut pages = Vec::new();
+    for page in pages_iter {
+        pages.push(page.map_err(|e| e.to_string())?);
+    }
+
+    Ok(pages)
+}
+
+/// Permanently deletes trashed pages, all of them or only those trashed before
+/// `trashed_before`. Returns the deleted ids.
+pub(crate) fn empty_page_trash_in_conn(
+    conn: &Connection,
+    trashed_before: Option<&str>,
+) -> Result<Vec<i64>, String> {
+    let mut stmt = conn
+        .prepare(
+            "SELECT id FROM pages
+             WHERE deleted_at IS NOT NULL AND (?1 IS NULL OR deleted_at < ?1)",
+        )
+        .map_err(|e| e.to_string())?;
+    let ids_iter = stmt
+        .query_map(params![trashed_before], |row| row.get::<_, i64>(0))
+        .map_err(|e| e.to_string())?;
+
+    let mut ids = Vec::new();
+    for id in ids_iter {
+        ids.push(id.map_err(|e| e.to_string())?);
+    }
+    for id in &ids {
+        delete_page_in_conn(conn, *id)?;
+    }
+
+    Ok(ids)
+}
+
+/// Titles referenced as `[[Title]]` or `[[Title|label]]`, trimmed and deduplicated
+/// (case-insensitively, like the `page_links` collation) in order of appearance.
+pub(crate) fn extract_wiki_links(content: &str) -> Vec<String> {
+    let mut links: Vec<String> = Vec::new();
+    let mut rest = content;
+    while let Some(start) = rest.find("[[") {
+        let after = &rest[start + 2..];
+        let Some(end) = after.find("]]") else {
+            break;
+        };
+        // `[[a [[b]]` links to `b`; links never span lines.
+        let inner = after[..end].rsplit("[[").next().unwrap_or_default();
+        let title = inner.split('|').next().unwrap_or_default().trim();
+        if !title.is_empty()
+            && !title.contains('\n')
+            && !links.iter().any(|link| link.eq_ignore_ascii_case(title))
+        {
+            links.push(title.to_string());
+        }
+        rest = &after[end + 2..];
+    }
+    links
+}
+
+/// Replaces the stored links of a page with the ones in `content`, which must be
+/// the plaintext; callers index before encrypting. Locked pages keep no links, so
+/// their link graph stays as private as their text.
+pub(crate) fn set_page_links_in_conn(
+    conn: &Connection,
+    page_id: i64,
+    content: &str,
+) -> Result<(), String> {
+    conn.execute(
+        "DELETE FROM page_links WHERE source_page_id = ?1",
+        params![page_id],
+    )
+    .map_err(|e| e.to_string())?;
+    if is_page_locked_in_conn(conn, page_id)? {
+        return Ok(());
+    }
+
+    let now = Utc::now().to_rfc3339();
+    for title in extract_wiki_links(content) {
+        conn.execute(
+            "INSERT OR IGNORE INTO page_links (source_page_id, target_title, created_at)
+             VALUES (?1, ?2, ?3)",
+            params![page_id, title, now],
+        )
+        .map_err(|e| e.to_string())?;
+    }
+
+    Ok(())
+}
+
+pub(crate) fn is_page_locked_in_conn(conn: &Connection, page_id: i64) -> Result<bool, String> {
+    conn.query_row(
+        "SELECT locked FROM pages WHERE id = ?1",
+        params![page_id],
+        |row| row.get(0),
+    )
+    .optional()
+    .map(|locked| locked.unwrap_or(false))
+    .map_err(|e| e.to_string())
+}
+
+/// Pages linking to `page_id` under its current title, most recently edited first.
+/// Renaming a page therefore drops the links that used the old title.
+pub(crate) fn get_backlinks_in_conn(conn: &Connection, page_id: i64) -> Result<Vec<Page>, String> {
+    if get_page_in_conn(conn, page_id)?.is_none() {
+        return Err("Page not found".to_string());
+    }
+    let mut stmt = conn
+        .prepare(
+            "SELECT p.id, p.title, p.content, p.parent_id, p.created_at, p.updated_at,
+                    p.pinned, p.favorite, p.archived, p.sort_index, p.locked, p.icon, p.cover
+             FROM pages p
+             WHERE p.id != ?1 AND p.deleted_at IS NULL AND EXISTS (
+                 SELECT 1 FROM page_links l
+                 JOIN pages target ON target.id = ?1
+                 WHERE l.source_page_id = p.id AND l.target_title = trim(target.title)
+             )
+             ORDER BY p.updated_at DESC",
+        )
+        .map_err(|e| e.to_string())?;
+    collect_pages(&mut stmt, params![page_id])
+}
+
+fn page_revision_from_row(row: &rusqlite::Row<'_>) -> rusqlite::Result<PageRevision> {
+    Ok(PageRevision {
+        id: row.get(0)?,
+        page_id: row.get(1)?,
+        title: row.get(2)?,
+        content: row.get(3)?,
+        created_at: row.get(4)?,
+    })
+}
+
+/// Copies the page as currently stored (content still encrypted if it is) into
+/// `page_revisions`, then drops the oldest revisions beyond `page_revision_limit`.
+pub(crate) fn snapshot_page_in_conn(conn: &Connection, page_id: i64) -> Result<(), String> {
+    let inserted = conn
+        .execute(
+            "INSERT INTO page_revisions (page_id, title, content, created_at)
+             SELECT id, title, content, ?2 FROM pages WHERE id = ?1",
+            params![page_id, Utc::now().to_rfc3339()],
+        )
+        .map_err(|e| e.to_string())?;
+    if inserted == 0 {
+        return Err("Page not found".to_string());
+    }
+
+    let limit = read_i64_setting(conn, "page_revision_limit")?.max(1);
+    conn.execute(
+        "DELETE FROM page_revisions
+         WHERE page_id = ?1 AND id NOT IN (
+             SELECT id FROM page_revisions WHERE page_id = ?1 ORDER BY id DESC LIMIT ?2
+         )",
+        params![page_id, limit],
+    )
+    .map_err(|e| e.to_string())?;
+
+    Ok(())
+}
+
+/// Revisions of a page, newest first.
+pub(crate) fn get_page_revisions_in_conn(
+    conn: &Connection,
+    page_id: i64,
+) -> Result<Vec<PageRevision>, String> {
+    let mut stmt = conn
+        .prepare(
+            "SELECT id, page_id, title, content, created_at FROM page_revisions
+             WHERE page_id = ?1 ORDER BY id DESC",
+        )
+        .map_err(|e| e.to_string())?;
+    let revisions_iter = stmt
+        .query_map(params![page_id], page_revision_from_row)
+        .map_err(|e| e.to_string())?;
+
+    let mut revisions = Vec::new();
+    for revision in revisions_iter {
+        revisions.push(revision.map_err(|e| e.to_string())?);
+    }
+
+    Ok(revisions)
+}
+
+pub(crate) fn get_page_revision_in_conn(
+    conn: &Connection,
+    rev_id: i64,
+) -> Result<Option<PageRevision>, String> {
+    conn.query_row(
+        "SELECT id, page_id, title, content, created_at FROM page_revisions WHERE id = ?1",
+        params![rev_id],
+        page_revision_from_row,
+    )
+    .optional()
+    .map_err(|e| e.to_string())
+}
+
+/// Puts a revision's title and content back on its page. The current version is
+/// snapshotted first, so a restore can itself be undone. Returns the stored page;
+/// callers re-index its links from the decrypted content.
+pub(crate) fn restore_page_revision_in_conn(
+    conn: &Connection,
+    rev_id: i64,
+) -> Result<Page, String> {
+    let revision =
+        get_page_revision_in_conn(conn, rev_id)?.ok_or_else(|| "Revision not found".to_string())?;
+    snapshot_page_in_conn(conn, revision.page_id)?;
+    conn.execute(
+        "UPDATE pages SET title = ?1, content = ?2, updated_at = ?3 WHERE id = ?4",
+        params![
+            revision.title,
+            revision.content,
+            Utc::now().to_rfc3339(),
+            revision.page_id
+        ],
+    )
+    .map_err(|e| e.to_string())?;
+
+    get_page_in_conn(conn, revision.page_id)?.ok_or_else(|| "Page not found".to_string())
+}
+
+/// Adds a tag to a page. Tags follow the entry tag rules and are case-insensitive,
+/// so re-adding one is a no-op.
+pub(crate) fn add_page_tag_in_conn(
+    conn: &Connection,
+    page_id: i64,
+    tag: &str,
+) -> Result<(), String> {
+    let tag = normalize_entry_tag(tag)?;
+    if get_page_in_conn(conn, page_id)?.is_none() {
+        return Err("Page not found".to_string());
+    }
+
+    conn.execute(
+        "INSERT OR IGNORE INTO page_tags (page_id, tag, created_at) VALUES (?1, ?2, ?3)",
+        params![page_id, tag, Utc::now().to_rfc3339()],
+    )
+    .map_err(|e| e.to_string())?;
+
+    Ok(())
+}
+
+pub(crate) fn remove_page_tag_in_conn(
+    conn: &Connection,
+    page_id: i64,
+    tag: &str,
+) -> Result<(), String> {
+    let tag = normalize_entry_tag(tag)?;
+    conn.execute(
+        "DELETE FROM page_tags WHERE page_id = ?1 AND tag = ?2",
+        params![page_id, tag],
+    )
+    .map_err(|e| e.to_string())?;
+
+    Ok(())
+}
+
+/// All page/tag pairs, so the UI can render tag chips and a tag filter from one query.
+pub(crate) fn get_page_tags_in_conn(conn: &Connection) -> Result<Vec<PageTag>, String> {
+    let mut stmt = conn
+        .prepare(
+            "SELECT t.page_id, t.tag, t.created_at FROM page_tags t
+             JOIN pages p ON p.id = t.page_id
+             WHERE p.deleted_at IS NULL
+             ORDER BY t.tag ASC, t.page_id ASC",
+        )
+        .map_err(|e| e.to_string())?;
+
+    let tags_iter = stmt
+        .query_map([], |row| {
+            Ok(PageTag {
+                page_id: row.get(0)?,
+                tag: row.get(1)?,
+                created_at: row.get(2)?,
+            })
+        })
+        .map_err(|e| e.to_string())?;
+
+    let mut tags = Vec::new();
+    for tag in tags_iter {
+        tags.push(tag.map_err(|e| e.to_string())?);
+    }
+
+    Ok(tags)
+}
+
+pub(crate) fn get_pages_by_tag_in_conn(conn: &Connection, tag: &str) -> Result<Vec<Page>, String> {
+    let tag = normalize_entry_tag(tag)?;
+    let mut stmt = conn
+        .prepare(
+            "SELECT p.id, p.title, p.content, p.parent_id, p.created_at, p.updated_at,
+                    p.pinned, p.favorite, p.archived, p.sort_index, p.locked, p.icon, p.cover
+             FROM pages p
+             JOIN page_tags t ON t.page_id = p.id
+             WHERE t.tag = ?1 AND p.deleted_at IS NULL
+             ORDER BY p.updated_at DESC",
+        )
+        .map_err(|e| e.to_string())?;
+    collect_pages(&mut stmt, params![tag])
+}
+
+/// Pages for the sidebar; archived pages and their subpages only with `include_archived`.
+#[tauri::command]
+pub fn get_pages(
+    include_archived: Option<bool>,
+    state: State<'_, AppState>,
+) -> Result<Vec<Page>, String> {
+    let conn = state.db.lock().map_err(|e| e.to_string())?;
+    get_listed_pages_in_conn(&conn, include_archived.unwrap_or(false))?
+        .decrypt_with(&state.journal_cipher()?)
+}
+
+#[tauri::command]
+pub fn get_page(id: i64, state: State<'_, AppState>) -> Result<Option<Page>, String> {
+    let conn = state.db.lock().map_err(|e| e.to_string())?;
+    let page = get_page_in_conn(&conn, id)?.decrypt_with(&state.journal_cipher()?)?;
+    drop(conn);
+
+    // Only the single-page read opens a page unlocked this session; listings and
+    // backups keep locked content as stored.
+    let key = state.page_key(id)?;
+    page.map(|page| reveal_page(page, key.as_ref())).transpose()
+}
+
+#[tauri::command]
+pub fn get_page_children(
+    parent_id: Option<i64>,
+    state: State<'_, AppState>,
+) -> Result<Vec<Page>, String> {
+    let conn = state.db.lock().map_err(|e| e.to_string())?;
+    get_page_children_in_conn(&conn, parent_id)?.decrypt_with(&state.journal_cipher()?)
+}
+
+#[tauri::command]
+pub fn create_page(
+    title: String,
+    content: String,
+    parent_id: Option<i64>,
+    icon: Option<String>,
+    cover: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<Page, String> {
+    let stored_content = state.journal_cipher()?.encrypt(&content)?;
+    let conn = state.db.lock().map_err(|e| e.to_string())?;
+    let page = create_page_in_conn(&conn, &title, &stored_content, parent_id)?;
+    set_page_appearance_in_conn(&conn, page.id, icon, cover)?;
+    set_page_links_in_conn(&conn, page.id, &content)?;
+    set_page_properties_in_conn(&conn, page.id, &content)?;
+    mirror_page(&conn, page.id);
+
+    let page = get_page_in_conn(&conn, page.id)?.ok_or_else(|| "Page not found".to_string())?;
+    Ok(Page { content, ..page })
+}
+
+/// `icon` and `cover` are only changed when sent; send an empty string to clear one.
+#[tauri::command]
+pub fn update_page(
+    id: i64,
+    title: String,
+    content: String,
+    icon: Option<String>,
+    cover: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    let cipher = state.journal_cipher()?;
+    let key = state.page_key(id)?;
+    let conn = state.db.lock().map_err(|e| e.to_string())?;
+    let current = get_page_in_conn(&conn, id)?
+        .ok_or_else(|| "Page not found".to_string())?
+        .decrypt_with(&cipher)?;
+    let stored_content = cipher.encrypt(&lock_page_content(&current, &content, key.as_ref())?)?;
+    let current = reveal_page(current, key.as_ref())?;
+    // Autosave resends unchanged pages; only real edits get a revision.
+    if current.title != title || current.content != content {
+        snapshot_page_in_conn(&conn, id)?;
+    }
+    let now = Utc::now().to_rfc3339();
+
+    conn.execute(
+        "UPDATE pages SET title = ?1, content = ?2, updated_at = ?3 WHERE id = ?4",
+        params![title, stored_content, now, id],
+    )
+    .map_err(|e| e.to_string())?;
+    set_page_appearance_in_conn(&conn, id, icon, cover)?;
+    set_page_links_in_conn(&conn, id, &content)?;
+    set_page_properties_in_conn(&conn, id, &content)?;
+    mirror_page(&conn, id);
+
+    Ok(())
+}
+
+#[tauri::command]
+pub fn set_page_pinned(id: i64, pinned: bool, state: State<'_, AppState>) -> Result<(), String> {
+    let conn = state.db.lock().map_err(|e| e.to_string())?;
+    set_page_pinned_in_conn(&conn, id, pinned)
+}
+
+#[tauri::command]
+pub fn set_page_favorite(
+    id: i64,
+    favorite: bool,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    let conn = state.db.lock().map_err(|e| e.to_string())?;
+    set_page_favorite_in_conn(&conn, id, favorite)
+}
+
+#[tauri::command]
+pub fn archive_page(id: i64, state: State<'_, AppState>) -> Result<(), String> {
+    let conn = state.db.lock().map_err(|e| e.to_string())?;
+    set_page_archived_in_conn(&conn, id, true)
+}
+
+#[tauri::command]
+pub fn unarchive_page(id: i64, state: State<'_, AppState>) -> Result<(), String> {
+    let conn = state.db.lock().map_err(|e| e.to_string())?;
+    set_page_archived_in_conn(&conn, id, false)
+}
+
+#[tauri::command]
+pub fn move_page(
+    id: i64,
+    new_parent: Option<i64>,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    let conn = state.db.lock().map_err(|e| e.to_string())?;
+    move_page_in_conn(&conn, id, new_parent)?;
+    mirror_page(&conn, id);
+
+    Ok(())
+}
+
+#[tauri::command]
+pub fn record_page_view(id: i64, state: State<'_, AppState>) -> Result<(), String> {
+    let conn = state.db.lock().map_err(|e| e.to_string())?;
+    record_page_view_in_conn(&conn, id, &Utc::now().to_rfc3339())
+}
+
+/// The "jump back in" list: the `limit` (default 10) most recently opened pages.
+#[tauri::command]
+pub fn get_recent_pages(
+    limit: Option<i64>,
+    state: State<'_, AppState>,
+) -> Result<Vec<RecentPage>, String> {
+    let conn = state.db.lock().map_err(|e| e.to_string())?;
+    get_recent_pages_in_conn(&conn, limit.unwrap_or(10).clamp(1, 100))
+}
+
+#[tauri::command]
+pub fn reorder_pages(ordered_ids: Vec<i64>, state: State<'_, AppState>) -> Result<(), String> {
+    let mut conn = state.db.lock().map_err(|e| e.to_string())?;
+    reorder_pages_in_conn(&mut conn, &ordered_ids)
+}
+
+#[tauri::command]
+pub fn delete_page(id: i64, app: AppHandle, state: State<'_, AppState>) -> Result<(), String> {
+    let conn = state.db.lock().map_err(|e| e.to_string())?;
+    // Children move up a level, which changes their mirrored `parent_id`.
+    let children = get_page_children_in_conn(&conn, Some(id))?;
+    trash_page_in_conn(&conn, id)?;
+    mirror_page(&conn, id);
+    for child in children {
+        mirror_page(&conn, child.id);
+    }
+    collect_orphan_page_assets(&conn, &app);
+
+    Ok(())
+}
+
+#[tauri::command]
+pub fn search_pages(
+    query: String,
+    state: State<'_, AppState>,
+) -> Result<Vec<PageSearchResult>, String> {
+    let cipher = state.journal_cipher()?;
+    let conn = state.db.lock().map_err(|e| e.to_string())?;
+    // The full-text index only holds ciphertext once the journal is encrypted.
+    if cipher.is_enabled() {
+        return search_encrypted_pages_in_conn(&conn, &cipher, &query);
+    }
+    search_pages_in_conn(&conn, &query)
+}
+
+#[tauri::command]
+pub fn get_trashed_pages(state: State<'_, AppState>) -> Result<Vec<TrashedPage>, String> {
+    let conn = state.db.lock().map_err(|e| e.to_string())?;
+    get_trashed_pages_in_conn(&conn)?.decrypt_with(&state.journal_cipher()?)
+}
+
+#[tauri::command]
+pub fn restore_page(id: i64, state: State<'_, AppState>) -> Result<Page, String> {
+    let cipher = state.journal_cipher()?;
+    let conn = state.db.lock().map_err(|e| e.to_string())?;
+    let page = restore_page_in_conn(&conn, id)?;
+    mirror_page(&conn, id);
+
+    page.decrypt_with(&cipher)
+}
+
+/// Permanently deletes every trashed page and returns how many were removed.
+#[tauri::command]
+pub fn empty_page_trash(app: AppHandle, state: State<'_, AppState>) -> Result<usize, String> {
+    let conn = state.db.lock().map_err(|e| e.to_string())?;
+    let deleted = empty_page_trash_in_conn(&conn, None)?.len();
+    collect_orphan_page_assets(&conn, &app);
+
+    Ok(deleted)
+}
+
+#[tauri::command]
+pub fn add_page_tag(page_id: i64, tag: String, state: State<'_, AppState>) -> Result<(), String> {
+    let conn = state.db.lock().map_err(|e| e.to_string())?;
+    add_page_tag_in_conn(&conn, page_id, &tag)?;
+    mirror_page(&conn, page_id);
+
+    Ok(())
+}
+
+#[tauri::command]
+pub fn remove_page_tag(
+    page_id: i64,
+    tag: String,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    let conn = state.db.lock().map_err(|e| e.to_string())?;
+    remove_page_tag_in_conn(&conn, page_id, &tag)?;
+    mirror_page(&conn, page_id);
+
+    Ok(())
+}
+
+#[tauri::command]
+pub fn get_page_tags(state: State<'_, AppState>) -> Result<Vec<PageTag>, String> {
+    let conn = state.db.lock().map_err(|e| e.to_string())?;
+    get_page_tags_in_conn(&conn)
+}
+
+#[tauri::command]
+pub fn get_pages_by_tag(tag: String, state: State<'_, AppState>) -> Result<Vec<Page>, String> {
+    let conn = state.db.lock().map_err(|e| e.to_string())?;
+    get_pages_by_tag_in_conn(&conn, &tag)?.decrypt_with(&state.journal_cipher()?)
+}
+
+#[tauri::command]
+pub fn get_backlinks(page_id: i64, state: State<'_, AppState>) -> Result<Vec<Page>, String> {
+    let conn = state.db.lock().map_err(|e| e.to_string())?;
+    get_backlinks_in_conn(&conn, page_id)?.decrypt_with(&state.journal_cipher()?)
+}
+
+#[tauri::command]
+pub fn get_page_revisions(
+    id: i64,
+    state: State<'_, AppState>,
+) -> Result<Vec<PageRevision>, String> {
+    let conn = state.db.lock().map_err(|e| e.to_string())?;
+    get_page_revisions_in_conn(&conn, id)?.decrypt_with(&state.journal_cipher()?)
+}
+
+#[tauri::command]
+pub fn get_page_revision(
+    rev_id: i64,
+    state: State<'_, AppState>,
+) -> Result<Option<PageRevision>, String> {
+    let conn = state.db.lock().map_err(|e| e.to_string())?;
+    get_page_revision_in_conn(&conn, rev_id)?.decrypt_with(&state.journal_cipher()?)
+}
+
+#[tauri::command]
+pub fn restore_page_revision(rev_id: i64, state: State<'_, AppState>) -> Result<Page, String> {
+    let cipher = state.journal_cipher()?;
+    let conn = state.db.lock().map_err(|e| e.to_string())?;
+    let page = restore_page_revision_in_conn(&conn, rev_id)?.decrypt_with(&cipher)?;
+    set_page_links_in_conn(&conn, page.id, &page.content)?;
+    set_page_properties_in_conn(&conn, page.id, &page.content)?;
+    mirror_page(&conn, page.id);
+
+    Ok(page)
+}
@@ -0,0 +1,235 @@
+use crate::models::{CommandTiming, PerformanceReport, SlowOp};
+use chrono::Utc;
+use rusqlite::{params, Connection};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicI64, Ordering};
+use std::sync::Mutex;
+use std::time::Instant;
+use tauri::ipc::{Invoke, InvokeBody};
+use tauri::{Manager, Runtime, State};
+
+use super::settings::read_i64_setting;
+use super::AppState;
+
+pub(crate) const SLOW_OP_THRESHOLD_SETTING: &str = "slow_op_threshold_ms";
+
+/// Slow invocations kept in `slow_ops`; older ones are dropped as new ones come in.
+const SLOW_OPS_KEPT: i64 = 500;
+
+/// Longest `params` summary stored with a slow invocation.
+const PARAMS_SUMMARY_CHARS: usize = 200;
+
+#[derive(Default, Clone, Copy)]
+struct TimingTotals {
+    calls: i64,
+    slow_calls: i64,
+    total_micros: i64,
+    max_micros: i64,
+}
+
+/// Timings since the app started plus the cached `slow_op_threshold_ms`, so the
+/// invoke handler does not read settings on every call.
+pub struct PerformanceState {
+    threshold_ms: AtomicI64,
+    started_at: String,
+    totals: Mutex<HashMap<String, TimingTotals>>,
+}
+
+impl PerformanceState {
+    pub(crate) fn from_conn(conn: &Connection) -> Result<Self, String> {
+        Ok(Self {
+            threshold_ms: AtomicI64::new(read_i64_setting(conn, SLOW_OP_THRESHOLD_SETTING)?),
+            started_at: Utc::now().to_rfc3339(),
+            totals: Mutex::new(HashMap::new()),
+        })
+    }
+
+    /// Applies a changed `slow_op_threshold_ms`; 0 or less stops recording.
+    pub(crate) fn set_threshold_ms(&self, threshold_ms: i64) {
+        self.threshold_ms.store(threshold_ms, Ordering::Relaxed);
+    }
+
+    fn threshold_ms(&self) -> i64 {
+        self.threshold_ms.load(Ordering::Relaxed)
+    }
+
+    /// Adds one call; returns whether it counts as slow.
+    pub(crate) fn record(&self, command: &str, micros: i64) -> bool {
+        let threshold_ms = self.threshold_ms();
+        let slow = threshold_ms > 0 && micros >= threshold_ms * 1000;
+        if let Ok(mut totals) = self.totals.lock() {
+            let totals = totals.entry(command.to_string()).or_default();
+            totals.calls += 1;
+            totals.slow_calls += i64::from(slow);
+            totals.total_micros += micros;
+            totals.max_micros = totals.max_micros.max(micros);
+        }
+        slow
+    }
+
+    /// Per-command timings, most total time first.
+    pub(crate) fn timings(&self) -> Result<Vec<CommandTiming>, String> {
+        let totals = self.totals.lock().map_err(|e| e.to_string())?;
+        let mut timings = totals
+            .iter()
+            .map(|(command, totals)| CommandTiming {
+                command: command.clone(),
+                calls: totals.calls,
+                slow_calls: totals.slow_calls,
+                total_ms: totals.total_micros as f64 / 1000.0,
+                mean_ms: totals.total_micros as f64 / 1000.0 / totals.calls.max(1) as f64,
+                max_ms: totals.max_micros as f64 / 1000.0,
+            })
+            .collect::<Vec<_>>();
+        timings.sort_by(|left, right| {
+            right
+                .total_ms
+                .total_cmp(&left.total_ms)
+                .then_with(|| left.command.cmp(&right.command))
+        });
+        Ok(timings)
+    }
+}
+
+/// Argument names with their shape, never their text: numbers, booleans and nulls
+/// as sent, strings by length, arrays by item count. Keeps journal content out of
+/// the log while still telling a 50-character save from a 5 MB one.
+pub(crate) fn summarize_params(payload: &Value) -> String {
+    let describe = |value: &Value| match value {
+        Value::Null | Value::Bool(_) | Value::Number(_) => value.to_string(),
+        Value::String(text) => format!("<{} chars>", text.chars().count()),
+        Value::Array(items) => format!("[{} items]", items.len()),
+        Value::Object(fields) => format!("{{{} fields}}", fields.len()),
+    };
+    let summary = match payload {
+        Value::Object(fields) => {
+            let mut fields = fields.iter().collect::<Vec<_>>();
+            fields.sort_by(|left, right| left.0.cmp(right.0));
+            fields
+                .into_iter()
+                .map(|(key, value)| format!("{}={}", key, describe(value)))
+                .collect::<Vec<_>>()
+                .join(", ")
+        }
+        other => describe(other),
+    };
+    match summary.char_indices().nth(PARAMS_SUMMARY_CHARS) {
+        Some((cut, _)) => format!("{}…", &summary[..cut]),
+        None => summary,
+    }
+}
+
+pub(crate) fn record_slow_op_in_conn(
+    conn: &Connection,
+    command: &str,
+    duration_ms: i64,
+    params_summary: &str,
+) -> Result<(), String> {
+    conn.execute(
+        "INSERT INTO slow_ops (command, duration_ms, params, created_at) VALUES (?1, ?2, ?3, ?4)",
+        params![
+            command,
+            duration_ms,
+            params_summary,
+            Utc::now().to_rfc3339()
+        ],
+    )
+    .map_err(|e| e.to_string())?;
+    conn.execute(
+        "DELETE FROM slow_ops WHERE id <= (SELECT MAX(id) FROM slow_ops) - ?1",
+        params![SLOW_OPS_KEPT],
+    )
+    .map_err(|e| e.to_string())?;
+
+    Ok(())
+}
+
+/// Recorded slow invocations, slowest first.
+pub(crate) fn get_slow_ops_in_conn(conn: &Connection, limit: i64) -> Result<Vec<SlowOp>, String> {
+    let mut stmt = conn
+        .prepare(
+            "SELECT id, command, duration_ms, params, created_at FROM slow_ops
+             ORDER BY duration_ms DESC, id DESC
+             LIMIT ?1",
+        )
+        .map_err(|e| e.to_string())?;
+    let slow_ops = stmt
+        .query_map(params![limit], |row| {
+            Ok(SlowOp {
+                id: row.get(0)?,
+                command: row.get(1)?,
+                duration_ms: row.get(2)?,
+                params: row.get(3)?,
+                created_at: row.get(4)?,
+            })
+        })
+        .map_err(|e| e.to_string())?;
+
+    slow_ops
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| e.to_string())
+}
+
+/// Wraps the app's invoke handler so every IPC call is timed; see `timed_invoke`.
+pub(crate) fn timed_handler<R: Runtime>(
+    handler: impl Fn(Invoke<R>) -> bool + Send + Sync + 'static,
+) -> impl Fn(Invoke<R>) -> bool + Send + Sync + 'static {
+    move |invoke| timed_invoke(invoke, &handler)
+}
+
+/// Runs `handler` for one IPC call and times it. Blocking commands finish inside the
+/// handler, so their time is the real cost; async ones only show their dispatch.
+/// Calls at or over the threshold are written to `slow_ops` with a params summary.
+fn timed_invoke<R: Runtime>(invoke: Invoke<R>, handler: &dyn Fn(Invoke<R>) -> bool) -> bool {
+    let webview = invoke.message.webview();
+    let Some(performance) = webview.try_state::<PerformanceState>() else {
+        return handler(invoke);
+    };
+    let command = invoke.message.command().to_string();
+    let params_summary = match invoke.message.payload() {
+        InvokeBody::Json(payload) => summarize_params(payload),
+        InvokeBody::Raw(bytes) => format!("<{} bytes>", bytes.len()),
+    };
+
+    let started = Instant::now();
+    let handled = handler(invoke);
+    let micros = started.elapsed().as_micros().min(i64::MAX as u128) as i64;
+
+    if performance.record(&command, micros) {
+        if let Some(state) = webview.try_state::<AppState>() {
+            let recorded = state.db.lock().map_err(|e| e.to_string()).and_then(|conn| {
+                record_slow_op_in_conn(&conn, &command, micros / 1000, &params_summary)
+            });
+            if let Err(error) = recorded {
+                eprintln!("Failed to record slow command {command}: {error}");
+            }
+        }
+    }
+    handled
+}
+
+/// Where the time goes: per-command timings since the app started and the slowest
+/// recorded invocations (kept across restarts).
+#[tauri::command]
+pub fn get_performance_report(
+    limit: Option<i64>,
+    performance: State<'_, PerformanceState>,
+    state: State<'_, AppState>,
+) -> Result<PerformanceReport, String> {
+    let conn = state.db.lock().map_err(|e| e.to_string())?;
+    Ok(PerformanceReport {
+        threshold_ms: performance.threshold_ms(),
+        since: performance.started_at.clone(),
+        commands: performance.timings()?,
+        slow_ops: get_slow_ops_in_conn(&conn, limit.unwrap_or(50).clamp(1, SLOW_OPS_KEPT))?,
+    })
+}
+
+#[tauri::command]
+pub fn clear_slow_ops(state: State<'_, AppState>) -> Result<(), String> {
+    let conn = state.db.lock().map_err(|e| e.to_string())?;
+    conn.execute("DELETE FROM slow_ops", [])
+        .map_err(|e| e.to_string())?;
+    Ok(())
+}
@@ -0,0 +1,149 @@
+use crate::models::{FocusPresence, Presence};
+use chrono::{DateTime, Duration, Utc};
+use rusqlite::{Connection, OptionalExtension};
+use tauri::{AppHandle, Manager, State};
+
+use super::events::emit_event;
+use super::focus::normalize_pomodoro_kind;
+use super::validation::task_exists;
+use super::AppState;
+
+pub(crate) const PRESENCE_CHANGED_EVENT: &str = "presence-changed";
+
+/// Presence as stored in the database right now: the running timer comes from
+/// `tasks`, while `focus` is carried over unless its session has already ended.
+pub(crate) fn presence_in_conn(
+    conn: &Connection,
+    focus: Option<FocusPresence>,
+) -> Result<Presence, String> {
+    let running = conn
+        .query_row(
+            "SELECT id, title, timer_started_at FROM tasks
+             WHERE timer_started_at IS NOT NULL
+             ORDER BY timer_started_at DESC, id DESC
+             LIMIT 1",
+            [],
+            |row| {
+                Ok((
+                    row.get::<_, i64>(0)?,
+                    row.get::<_, String>(1)?,
+                    row.get::<_, String>(2)?,
+                ))
+            },
+        )
+        .optional()
+        .map_err(|e| e.to_string())?;
+
+    let now = Utc::now();
+    let focus = focus.filter(|focus| {
+        DateTime::parse_from_rfc3339(&focus.ends_at)
+            .map(|ends_at| ends_at.with_timezone(&Utc) > now)
+            .unwrap_or(false)
+    });
+
+    Ok(match running {
+        Some((task_id, title, started_at)) => Presence {
+            task_id: Some(task_id),
+            task_title: Some(title),
+            since: Some(started_at),
+            focus,
+        },
+        None => Presence {
+            focus,
+            ..Presence::default()
+        },
+    })
+}
+
+impl AppState {
+    pub(crate) fn presence(&self) -> Result<Presence, String> {
+        Ok(self.presence.lock().map_err(|e| e.to_string())?.clone())
+    }
+
+    /// Stores `next` and reports whether it differs from what was held before.
+    pub(crate) fn replace_presence(&self, next: Presence) -> Result<bool, String> {
+        let mut presence = self.presence.lock().map_err(|e| e.to_string())?;
+        if *presence == next {
+            return Ok(false);
+        }
+        *presence = next;
+        Ok(true)
+    }
+}
+
+/// Recomputes presence, optionally replacing the focus session, and emits
+/// `presence-changed` when the result differs from the held value.
+fn sync_presence(
+    app: &AppHandle,
+    state: &AppState,
+    focus: Option<Option<FocusPresence>>,
+) -> Result<Presence, String> {
+    let focus = match focus {
+        Some(focus) => focus,
+        None => state.presence()?.focus,
+    };
+    let next = {
+        let conn = state.db.lock().map_err(|e| e.to_string())?;
+        presence_in_conn(&conn, focus)?
+    };
+
+    if state.replace_presence(next.clone())? {
+        if let Err(error) = emit_event(app, PRESENCE_CHANGED_EVENT, &next) {
+            eprintln!("Failed to emit presence change: {error}");
+        }
+        super::slack_status::sync_slack_status(app, &next);
+    }
+    Ok(next)
+}
+
+/// Picks up timer changes made anywhere in the backend. The tray calls this on every
+/// rebuild, which covers timer commands, the global shortcut and the scheduler tick.
+pub fn refresh_presence(app: &AppHandle) {
+    let Some(state) = app.try_state::<AppState>() else {
+        return;
+    };
+    if let Err(error) = sync_presence(app, &state, None) {
+        eprintln!("Failed to refresh presence: {error}");
+    }
+}
+
+#[tauri::command]
+pub fn get_presence(app: AppHandle, state: State<'_, AppState>) -> Result<Presence, String> {
+    sync_presence(&app, &state, None)
+}
+
+/// Called by the focus board when a session starts (`kind` set) or stops (`kind` empty).
+#[tauri::command]
+pub fn set_focus_presence(
+    kind: Option<String>,
+    task_id: Option<i64>,
+    duration_minutes: Option<i64>,
+    app: AppHandle,
+    state: State<'_, AppState>,
+) -> Result<Presence, String> {
+    let focus = match kind {
+        Some(kind) => {
+            let task_id = match task_id {
+                Some(task_id) => {
+                    let conn = state.db.lock().map_err(|e| e.to_string())?;
+                    task_exists(&conn, task_id)?.then_some(task_id)
+                }
+                None => None,
+            };
+            let started_at = Utc::now();
+            let ends_at =
+                started_at + Duration::minutes(duration_minutes.unwrap_or(25).clamp(1, 240));
+            Some(FocusPresence {
+                kind: normalize_pomodoro_kind(Some(kind)),
+                task_id,
+                started_at: started_at.to_rfc3339(),
+                ends_at: ends_at.to_rfc3339(),
+            })
+        }
+        None => None,
+    };
+
+    let presence = sync_presence(&app, &state, Some(focus))?;
+    crate::tray::refresh_tray_menu(&app);
+    Ok(presence)
+}
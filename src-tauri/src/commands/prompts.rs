@@ -0,0 +1,144 @@
+use crate::models::JournalPrompt;
+use chrono::{NaiveDate, Utc};
+use rusqlite::{params, Connection};
+use tauri::State;
+
+use super::AppState;
+
+const PROMPT_COLUMNS: &str = "id, text, is_builtin, enabled, created_at";
+
+fn prompt_from_row(row: &rusqlite::Row<'_>) -> rusqlite::Result<JournalPrompt> {
+    Ok(JournalPrompt {
+        id: row.get(0)?,
+        text: row.get(1)?,
+        is_builtin: row.get::<_, i64>(2)? == 1,
+        enabled: row.get::<_, i64>(3)? == 1,
+        created_at: row.get(4)?,
+    })
+}
+
+fn load_prompts(conn: &Connection, enabled_only: bool) -> Result<Vec<JournalPrompt>, String> {
+    let filter = if enabled_only {
+        "WHERE enabled = 1"
+    } else {
+        ""
+    };
+    let mut stmt = conn
+        .prepare(&format!(
+            "SELECT {} FROM journal_prompts {} ORDER BY id ASC",
+            PROMPT_COLUMNS, filter
+        ))
+        .map_err(|e| e.to_string())?;
+
+    let prompts_iter = stmt
+        .query_map([], prompt_from_row)
+        .map_err(|e| e.to_string())?;
+
+    let mut prompts = Vec::new();
+    for prompt in prompts_iter {
+        prompts.push(prompt.map_err(|e| e.to_string())?);
+    }
+
+    Ok(prompts)
+}
+
+/// Picks the prompt for `date` by rotating through enabled prompts in id order,
+/// so every day maps to the same prompt until the library changes.
+pub(crate) fn daily_prompt_in_conn(
+    conn: &Connection,
+    date: &str,
+) -> Result<Option<JournalPrompt>, String> {
+    let date = NaiveDate::parse_from_str(date.trim(), "%Y-%m-%d")
+        .map_err(|_| format!("Invalid date: {}", date))?;
+    let mut prompts = load_prompts(conn, true)?;
+    if prompts.is_empty() {
+        return Ok(None);
+    }
+
+    let day_index = date.signed_duration_since(NaiveDate::default()).num_days();
+    let index = day_index.rem_euclid(prompts.len() as i64) as usize;
+    Ok(Some(prompts.swap_remove(index)))
+}
+
+pub(crate) fn add_journal_prompt_in_conn(
+    conn: &Connection,
+    text: &str,
+) -> Result<JournalPrompt, String> {
+    let text = text.trim();
+    if text.is_empty() {
+        return Err("Prompt cannot be empty".to_string());
+    }
+
+    conn.execute(
+        "INSERT INTO journal_prompts (text, is_builtin, enabled, created_at)
+         VALUES (?1, 0, 1, ?2)
+         ON CONFLICT(text) DO UPDATE SET enabled = 1",
+        params![text, Utc::now().to_rfc3339()],
+    )
+    .map_err(|e| e.to_string())?;
+
+    conn.query_row(
+        &format!(
+            "SELECT {} FROM journal_prompts WHERE text = ?1",
+            PROMPT_COLUMNS
+        ),
+        params![text],
+        prompt_from_row,
+    )
+    .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn get_journal_prompts(state: State<'_, AppState>) -> Result<Vec<JournalPrompt>, String> {
+    let conn = state.db.lock().map_err(|e| e.to_string())?;
+    load_prompts(&conn, false)
+}
+
+#[tauri::command]
+pub fn get_daily_prompt(
+    date: String,
+    state: State<'_, AppState>,
+) -> Result<Option<JournalPrompt>, String> {
+    let conn = state.db.lock().map_err(|e| e.to_string())?;
+    daily_prompt_in_conn(&conn, &date)
+}
+
+#[tauri::command]
+pub fn add_journal_prompt(
+    text: String,
+    state: State<'_, AppState>,
+) -> Result<JournalPrompt, String> {
+    let conn = state.db.lock().map_err(|e| e.to_string())?;
+    add_journal_prompt_in_conn(&conn, &text)
+}
+
+#[tauri::command]
+pub fn set_journal_prompt_enabled(
+    id: i64,
+    enabled: bool,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    let conn = state.db.lock().map_err(|e| e.to_string())?;
+
+    conn.execute(
+        "UPDATE journal_prompts SET enabled = ?1 WHERE id = ?2",
+        params![if enabled { 1 } else { 0 }, id],
+    )
+    .map_err(|e| e.to_string())?;
+
+    Ok(())
+}
+
+/// Deletes a user-added prompt. Built-in prompts can only be disabled.
+#[tauri::command]
+pub fn delete_journal_prompt(id: i64, state: State<'_, AppState>) -> Result<(), String> {
+    let conn = state.db.lock().map_err(|e| e.to_string())?;
+
+    conn.execute(
+        "DELETE FROM journal_prompts WHERE id = ?1 AND is_builtin = 0",
+        params![id],
+    )
+    .map_err(|e| e.to_string())?;
+
+    Ok(())
+}
@@ -0,0 +1,168 @@
+use crate::models::QueryPlanCheck;
+use rusqlite::Connection;
+use tauri::State;
+
+use super::AppState;
+
+/// A query shape the app runs often, written with literal values so it can be planned
+/// without binding parameters. Keep these in step with the real queries and the
+/// indexes added for them.
+struct QueryPath {
+    name: &'static str,
+    description: &'static str,
+    sql: &'static str,
+    /// Reads rows in index order up to a LIMIT, so `SCAN t USING INDEX` is expected.
+    ordered: bool,
+}
+
+const QUERY_PATHS: &[QueryPath] = &[
+    QueryPath {
+        name: "entry_by_date",
+        description: "Entry for a day",
+        sql: "SELECT id FROM entries WHERE date = '2026-01-01' AND deleted_at IS NULL",
+        ordered: false,
+    },
+    QueryPath {
+        name: "recent_pages",
+        description: "Most recently edited pages",
+        sql: "SELECT id FROM pages WHERE deleted_at IS NULL ORDER BY updated_at DESC LIMIT 20",
+        ordered: true,
+    },
+    QueryPath {
+        name: "page_children",
+        description: "Subpages of a page",
+        sql: "SELECT id FROM pages WHERE parent_id = 1 AND deleted_at IS NULL",
+        ordered: false,
+    },
+    QueryPath {
+        name: "recent_tasks",
+        description: "Most recently updated tasks",
+        sql: "SELECT id FROM tasks ORDER BY updated_at DESC LIMIT 50",
+        ordered: true,
+    },
+    QueryPath {
+        name: "tasks_by_project",
+        description: "Tasks of a project",
+        sql: "SELECT id FROM tasks WHERE project_id = 1",
+        ordered: false,
+    },
+    QueryPath {
+        name: "tasks_by_priority",
+        description: "Tasks of one priority",
+        sql: "SELECT id FROM tasks WHERE priority = 'high'",
+        ordered: false,
+    },
+    QueryPath {
+        name: "tasks_due",
+        description: "Open tasks due by a day",
+        sql: "SELECT id FROM tasks WHERE status = 'todo' AND due_date <= '2026-01-01'",
+        ordered: false,
+    },
+    QueryPath {
+        name: "tasks_completed_since",
+        description: "Tasks completed since a time",
+        sql: "SELECT id FROM tasks WHERE completed_at >= '2026-01-01'",
+        ordered: false,
+    },
+    QueryPath {
+        name: "habit_logs_by_day",
+        description: "Habit check-ins in a date range",
+        sql: "SELECT habit_id FROM habit_logs WHERE date BETWEEN '2026-01-01' AND '2026-01-07'",
+        ordered: false,
+    },
+    QueryPath {
+        name: "entries_by_tag",
+        description: "Entries with a tag",
+        sql: "SELECT entry_id FROM entry_tags WHERE tag = 'rust'",
+        ordered: false,
+    },
+    QueryPath {
+        name: "tasks_by_tag",
+        description: "Tasks with a tag",
+        sql: "SELECT task_id FROM task_tags WHERE tag = 'rust'",
+        ordered: false,
+    },
+    QueryPath {
+        name: "pages_by_tag",
+        description: "Pages with a tag",
+        sql: "SELECT page_id FROM page_tags WHERE tag = 'rust'",
+        ordered: false,
+    },
+    QueryPath {
+        name: "pages_by_property",
+        description: "Pages with a frontmatter property",
+        sql: "SELECT page_id FROM page_properties WHERE key = 'status' AND value = 'doing'",
+        ordered: false,
+    },
+    QueryPath {
+        name: "page_backlinks",
+        description: "Links to a page title",
+        sql: "SELECT source_page_id FROM page_links WHERE target_title = 'Index'",
+        ordered: false,
+    },
+    QueryPath {
+        name: "entry_search",
+        description: "Full-text entry search",
+        sql: "SELECT e.id FROM entries_fts
+              JOIN entries e ON e.id = entries_fts.rowid
+              WHERE entries_fts MATCH 'rust'",
+        ordered: false,
+    },
+    QueryPath {
+        name: "page_search",
+        description: "Full-text page search",
+        sql: "SELECT p.id FROM pages_fts
+              JOIN pages p ON p.id = pages_fts.rowid
+              WHERE pages_fts MATCH 'rust'",
+        ordered: false,
+    },
+];
+
+/// Plan steps that read a whole table or index. FTS tables are scanned through their
+/// own index, and an `ordered` path may walk an index in order (`SCAN t USING INDEX`,
+/// possibly `COVERING`) since its LIMIT stops early.
+fn full_scans(plan: &[String], ordered: bool) -> Vec<String> {
+    plan.iter()
+        .filter(|step| {
+            step.starts_with("SCAN ")
+                && !step.contains("VIRTUAL TABLE")
+                && !step.starts_with("SCAN CONSTANT ROW")
+                && (!ordered || !step.contains(" USING "))
+        })
+        .cloned()
+        .collect()
+}
+
+/// Runs `EXPLAIN QUERY PLAN` for every entry of `QUERY_PATHS` and reports the ones
+/// that fall back to reading a whole table, e.g. after an index went missing.
+pub(crate) fn check_query_plans_in_conn(conn: &Connection) -> Result<Vec<QueryPlanCheck>, String> {
+    QUERY_PATHS
+        .iter()
+        .map(|path| {
+            let mut stmt = conn
+                .prepare(&format!("EXPLAIN QUERY PLAN {}", path.sql))
+                .map_err(|e| format!("{}: {}", path.name, e))?;
+            let plan = stmt
+                .query_map([], |row| row.get::<_, String>(3))
+                .map_err(|e| e.to_string())?
+                .collect::<Result<Vec<_>, _>>()
+                .map_err(|e| e.to_string())?;
+            let full_scans = full_scans(&plan, path.ordered);
+
+            Ok(QueryPlanCheck {
+                name: path.name.to_string(),
+                description: path.description.to_string(),
+                uses_index: full_scans.is_empty(),
+                full_scans,
+                plan,
+            })
+        })
+        .collect()
+}
+
+/// Self-check for huge journals: which common queries are served by an index.
+#[tauri::command]
+pub fn check_query_plans(state: State<'_, AppState>) -> Result<Vec<QueryPlanCheck>, String> {
+    let conn = state.db.lock().map_err(|e| e.to_string())?;
+    check_query_plans_in_conn(&conn)
+}
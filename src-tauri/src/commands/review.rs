@@ -0,0 +1,573 @@
+use crate::models::{Page, Task};
+use chrono::{Duration, NaiveDate, NaiveTime, Utc};
+use rusqlite::{params, Connection, OptionalExtension};
+use std::collections::HashMap;
+use tauri::State;
+
+use super::encryption::{JournalCipher, JournalContent};
+use super::entries::{get_entries_between_in_conn, WorkCalendar};
+use super::goals::goals_needing_checkin_in_conn;
+use super::incidents::{get_incidents_between_in_conn, incident_duration_label};
+use super::insights::{context_switches_in_conn, meeting_load_in_conn};
+use super::mirror::mirror_page;
+use super::oncall::oncall_days_in_conn;
+use super::page_properties::set_page_properties_in_conn;
+use super::pages::{
+    create_page_in_conn, get_page_in_conn, set_page_links_in_conn, snapshot_page_in_conn,
+};
+use super::settings::read_i64_setting;
+use super::tasks::{task_from_row, TASK_COLUMNS};
+use super::weeks::week_start_in_conn;
+use super::AppState;
+
+/// Indents continuation lines so multi-line text stays inside its list item.
+fn list_item_text(text: &str) -> String {
+    text.trim().lines().collect::<Vec<_>>().join("\n  ")
+}
+
+/// Titles and project names of tasks completed between two `YYYY-MM-DD` days.
+fn completed_tasks_between(
+    conn: &Connection,
+    start_key: &str,
+    end_key: &str,
+) -> Result<Vec<(String, Option<String>)>, String> {
+    let mut stmt = conn
+        .prepare(
+            "SELECT t.title, p.name
+             FROM tasks t
+             LEFT JOIN projects p ON p.id = t.project_id
+             WHERE t.status = 'done'
+               AND t.completed_at IS NOT NULL
+               AND substr(t.completed_at, 1, 10) BETWEEN ?1 AND ?2
+             ORDER BY t.completed_at ASC, t.id ASC",
+        )
+        .map_err(|e| e.to_string())?;
+    let tasks_iter = stmt
+        .query_map(params![start_key, end_key], |row| {
+            Ok((row.get::<_, String>(0)?, row.get::<_, Option<String>>(1)?))
+        })
+        .map_err(|e| e.to_string())?;
+    let mut tasks = Vec::new();
+    for task in tasks_iter {
+        tasks.push(task.map_err(|e| e.to_string())?);
+    }
+
+    Ok(tasks)
+}
+
+/// First day of the configured week (see `week_numbering`) containing `date`.
+fn parse_week_start(conn: &Connection, date: &str) -> Result<NaiveDate, String> {
+    let date = NaiveDate::parse_from_str(date.trim(), "%Y-%m-%d")
+        .map_err(|_| format!("Invalid date: {}", date))?;
+    week_start_in_conn(conn, date)
+}
+
+/// Markdown review of the week containing `week_start`: journal entries,
+/// tasks completed, meeting and deep-work hours, context switches, incidents (only
+/// when there were any), habit completions against target and goal progress.
+pub(crate) fn generate_weekly_review_in_conn(
+    conn: &Connection,
+    week_start: &str,
+    cipher: &JournalCipher,
+) -> Result<String, String> {
+    let start = parse_week_start(conn, week_start)?;
+    let end = start + Duration::days(6);
+    let start_key = start.format("%Y-%m-%d").to_string();
+    let end_key = end.format("%Y-%m-%d").to_string();
+
+    let mut markdown = format!(
+        "# Weekly review: {} – {}\n",
+        start.format("%b %-d"),
+        end.format("%b %-d, %Y")
+    );
+
+    markdown.push_str("\n## Journal\n\n");
+    let entries = get_entries_between_in_conn(conn, &start_key, &end_key)?.decrypt_with(cipher)?;
+    if entries.is_empty() {
+        markdown.push_str("_No entries this week._\n");
+    }
+    for entry in &entries {
+        let heading = NaiveDate::parse_from_str(&entry.date, "%Y-%m-%d")
+            .map(|date| date.format("%A, %b %-d").to_string())
+            .unwrap_or_else(|_| entry.date.clone());
+        markdown.push_str(&format!("### {}\n\n", heading));
+        if !entry.yesterday.trim().is_empty() {
+            markdown.push_str(&format!("- Done: {}\n", list_item_text(&entry.yesterday)));
+        }
+        if !entry.today.trim().is_empty() {
+            markdown.push_str(&format!("- Planned: {}\n", list_item_text(&entry.today)));
+        }
+        if !entry.blockers.trim().is_empty() {
+            markdown.push_str(&format!(
+                "- Blocked by: {}\n",
+                list_item_text(&entry.blockers)
+            ));
+        }
+        markdown.push('\n');
+    }
+    if !entries.is_empty() {
+        markdown.pop();
+    }
+
+    markdown.push_str("\n## Completed tasks\n\n");
+    let tasks = completed_tasks_between(conn, &start_key, &end_key)?;
+    if tasks.is_empty() {
+        markdown.push_str("_No tasks completed._\n");
+    }
+    for (title, project) in &tasks {
+        match project {
+            Some(project) => markdown.push_str(&format!("- {} ({})\n", title.trim(), project)),
+            None => markdown.push_str(&format!("- {}\n", title.trim())),
+        }
+    }
+
+    markdown.push_str("\n## Time\n\n");
+    let load = meeting_load_in_conn(conn, start, end)?;
+    let total_hours = |hours: f64| (hours * 10.0).round() / 10.0;
+    markdown.push_str(&format!(
+        "- Meetings: {}h\n- Deep work: {}h\n",
+        total_hours(load.iter().map(|day| day.meeting_hours).sum()),
+        total_hours(load.iter().map(|day| day.deep_work_hours).sum())
+    ));
+    let heavy_days: Vec<String> = load
+        .iter()
+        .filter(|day| day.meeting_heavy)
+        .filter_map(|day| NaiveDate::parse_from_str(&day.date, "%Y-%m-%d").ok())
+        .map(|date| date.format("%A").to_string())
+        .collect();
+    if !heavy_days.is_empty() {
+        markdown.push_str(&format!(
+            "- Meeting-heavy days: {}\n",
+            heavy_days.join(", ")
+        ));
+    }
+    let switches = context_switches_in_conn(conn, start, end)?;
+    let total_switches: i64 = switches.iter().map(|day| day.switches).sum();
+    if total_switches > 0 {
+        let mut line = format!("- Context switches: {}", total_switches);
+        if let Some(busiest) = switches
+            .iter()
+            .filter(|day| day.switches > 0)
+            .max_by_key(|day| day.switches)
+        {
+            if let Ok(date) = NaiveDate::parse_from_str(&busiest.date, "%Y-%m-%d") {
+                line.push_str(&format!(
+                    " (most fragmented: {}, {})",
+                    date.format("%A"),
+                    busiest.switches
+                ));
+            }
+        }
+        markdown.push_str(&line);
+        markdown.push('\n');
+    }
+
+    let incidents = get_incidents_between_in_conn(conn, &start_key, &end_key)?;
+    if !incidents.is_empty() {
+        markdown.push_str("\n## Incidents\n\n");
+    }
+    for incident in &incidents {
+        let status = match incident_duration_label(incident) {
+            Some(duration) => format!("resolved in {}", duration),
+            None => "ongoing".to_string(),
+        };
+        markdown.push_str(&format!(
+            "- {} {} ({})\n",
+            incident.severity.to_uppercase(),
+            list_item_text(&incident.summary),
+            status
+        ));
+        if !incident.resolution.trim().is_empty() {
+            markdown.push_str(&format!(
+                "  - Resolution: {}\n",
+                incident
+                    .resolution
+                    .trim()
+                    .lines()
+                    .collect::<Vec<_>>()
+                    .join("\n    ")
+            ));
+        }
+    }
+
+    markdown.push_str("\n## Habits\n\n");
+    let mut stmt = conn
+        .prepare(
+            "SELECT h.title, h.target_per_week, COUNT(l.id)
+             FROM habits h
+             LEFT JOIN habit_logs l ON l.habit_id = h.id AND l.date BETWEEN ?1 AND ?2
+             GROUP BY h.id
+             ORDER BY h.title COLLATE NOCASE",
+        )
+        .map_err(|e| e.to_string())?;
+    let habits_iter = stmt
+        .query_map(params![start_key, end_key], |row| {
+            Ok((
+                row.get::<_, String>(0)?,
+                row.get::<_, i64>(1)?,
+                row.get::<_, i64>(2)?,
+            ))
+        })
+        .map_err(|e| e.to_string())?;
+    let mut habits = Vec::new();
+    for habit in habits_iter {
+        habits.push(habit.map_err(|e| e.to_string())?);
+    }
+    if habits.is_empty() {
+        markdown.push_str("_No habits tracked._\n");
+    }
+    for (title, target, completions) in &habits {
+        let marker = if completions >= target { " ✅" } else { "" };
+        markdown.push_str(&format!(
+            "- {}: {}/{}{}\n",
+            title.trim(),
+            completions,
+            target,
+            marker
+        ));
+    }
+
+    // Goals still in play plus anything that changed this week (e.g. completed or paused).
+    markdown.push_str("\n## Goals\n\n");
+    let mut stmt = conn
+        .prepare(
+            "SELECT g.title, g.status, g.progress,
+                    (SELECT COUNT(*) FROM goal_milestones m
+                     WHERE m.goal_id = g.id AND m.completed = 1
+                       AND substr(m.updated_at, 1, 10) BETWEEN ?1 AND ?2)
+             FROM goals g
+             WHERE g.status = 'active' OR substr(g.updated_at, 1, 10) BETWEEN ?1 AND ?2
+             ORDER BY g.status = 'completed' DESC, g.progress DESC, g.title COLLATE NOCASE",
+        )
+        .map_err(|e| e.to_string())?;
+    let goals_iter = stmt
+        .query_map(params![start_key, end_key], |row| {
+            Ok((
+                row.get::<_, String>(0)?,
+                row.get::<_, String>(1)?,
+                row.get::<_, i64>(2)?,
+                row.get::<_, i64>(3)?,
+            ))
+        })
+        .map_err(|e| e.to_string())?;
+    let mut goals = Vec::new();
+    for goal in goals_iter {
+        goals.push(goal.map_err(|e| e.to_string())?);
+    }
+    if goals.is_empty() {
+        markdown.push_str("_No active goals._\n");
+    }
+    for (title, status, progress, milestones_done) in &goals {
+        let mut line = format!("- {}: {}% ({})", title.trim(), progress, status);
+        if *milestones_done > 0 {
+            line.push_str(&format!(
+                ", {} milestone{} done this week",
+                milestones_done,
+                if *milestones_done == 1 { "" } else { "s" }
+            ));
+        }
+        markdown.push_str(&line);
+        markdown.push('\n');
+    }
+
+    Ok(markdown)
+}
+
+/// Title of the plan page for the seven days starting at `start`.
+pub(crate) fn week_plan_title(start: NaiveDate) -> String {
+    format!(
+        "Week plan: {} – {}",
+        start.format("%b %-d"),
+        (start + Duration::days(6)).format("%b %-d, %Y")
+    )
+}
+
+fn plan_task_line(task: &Task, project: Option<&str>, with_due: bool) -> String {
+    let mut details = Vec::new();
+    if let Some(project) = project {
+        details.push(project.to_string());
+    }
+    if task.status == "in_progress" {
+        details.push("in progress".to_string());
+    }
+    if let Some(due) = task
+        .due_date
+        .as_deref()
+        .and_then(|due| NaiveDate::parse_from_str(due.get(..10)?, "%Y-%m-%d").ok())
+        .filter(|_| with_due)
+    {
+        details.push(format!("due {}", due.format("%a %b %-d")));
+    }
+    if details.is_empty() {
+        format!("- [ ] {}\n", list_item_text(&task.title))
+    } else {
+        format!(
+            "- [ ] {} ({})\n",
+            list_item_text(&task.title),
+            details.join(", ")
+        )
+    }
+}
+
+/// Markdown plan for the week containing `week_start`: a summary of the week
+/// before, open tasks carried over (in progress or overdue), tasks due this week,
+/// goals that are stale or due, and per-day capacity (`workday_hours` on working
+/// days minus meetings) against the estimates of the tasks due that day.
+pub(crate) fn generate_week_plan_markdown_in_conn(
+    conn: &Connection,
+    week_start: &str,
+    cipher: &JournalCipher,
+) -> Result<String, String> {
+    let start = parse_week_start(conn, week_start)?;
+    let end = start + Duration::days(6);
+    let last_start = start - Duration::days(7);
+    let last_end = start - Duration::days(1);
+    let key = |date: NaiveDate| date.format("%Y-%m-%d").to_string();
+    let total_hours = |hours: f64| (hours * 10.0).round() / 10.0;
+
+    let mut markdown = format!("# {}\n", week_plan_title(start));
+
+    markdown.push_str("\n## Last week\n\n");
+    let entries = get_entries_between_in_conn(conn, &key(last_start), &key(last_end))?
+        .decrypt_with(cipher)?;
+    let completed = completed_tasks_between(conn, &key(last_start), &key(last_end))?;
+    let load = meeting_load_in_conn(conn, last_start, last_end)?;
+    markdown.push_str(&format!(
+        "- Journal: {}/7 days\n- Tasks completed: {}\n- Meetings: {}h, deep work: {}h\n",
+        entries.len(),
+        completed.len(),
+        total_hours(load.iter().map(|day| day.meeting_hours).sum()),
+        total_hours(load.iter().map(|day| day.deep_work_hours).sum())
+    ));
+    let incidents = get_incidents_between_in_conn(conn, &key(last_start), &key(last_end))?;
+    if !incidents.is_empty() {
+        markdown.push_str(&format!("- Incidents: {}\n", incidents.len()));
+    }
+    let blockers: Vec<String> = entries
+        .iter()
+        .filter(|entry| !entry.blockers.trim().is_empty())
+        .map(|entry| list_item_text(&entry.blockers))
+        .collect();
+    if let Some(blocker) = blockers.last() {
+        markdown.push_str(&format!("- Last blocker: {}\n", blocker));
+    }
+
+    let mut stmt = conn
+        .prepare(&format!(
+            "SELECT {}, (SELECT name FROM projects WHERE projects.id = tasks.project_id)
+             FROM tasks
+             WHERE status != 'done' AND id NOT IN (SELECT task_id FROM review_queue)
+             ORDER BY due_date IS NULL, due_date ASC, id ASC",
+            TASK_COLUMNS
+        ))
+        .map_err(|e| e.to_string())?;
+    let tasks_iter = stmt
+        .query_map([], |row| {
+            Ok((task_from_row(row)?, row.get::<_, Option<String>>(17)?))
+        })
+        .map_err(|e| e.to_string())?;
+    let mut carried_over = Vec::new();
+    let mut due_this_week = Vec::new();
+    for task in tasks_iter {
+        let (task, project) = task.map_err(|e| e.to_string())?;
+        let due = task
+            .due_date
+            .as_deref()
+            .and_then(|due| NaiveDate::parse_from_str(due.get(..10)?, "%Y-%m-%d").ok());
+        match due {
+            Some(due) if due >= start && due <= end => due_this_week.push((task, project, due)),
+            Some(due) if due < start => carried_over.push((task, project)),
+            _ if task.status == "in_progress" => carried_over.push((task, project)),
+            _ => {}
+        }
+    }
+
+    markdown.push_str("\n## Carried over\n\n");
+    if carried_over.is_empty() {
+        markdown.push_str("_Nothing left over._\n");
+    }
+    for (task, project) in &carried_over {
+        markdown.push_str(&plan_task_line(task, project.as_deref(), true));
+    }
+
+    markdown.push_str("\n## Due this week\n\n");
+    if due_this_week.is_empty() {
+        markdown.push_str("_No tasks due._\n");
+    }
+    for (task, project, _) in &due_this_week {
+        markdown.push_str(&plan_task_line(task, project.as_deref(), true));
+    }
+
+    markdown.push_str("\n## Goals needing attention\n\n");
+    let week_start_utc = start.and_time(NaiveTime::MIN).and_utc();
+    let stale: HashMap<i64, i64> = goals_needing_checkin_in_conn(conn, week_start_utc)?
+        .into_iter()
+        .map(|goal| (goal.goal_id, goal.days_since_update))
+        .collect();
+    let mut stmt = conn
+        .prepare(
+            "SELECT id, title, progress, target_date FROM goals
+             WHERE status = 'active'
+             ORDER BY target_date IS NULL, target_date ASC, title COLLATE NOCASE",
+        )
+        .map_err(|e| e.to_string())?;
+    let goals_iter = stmt
+        .query_map([], |row| {
+            Ok((
+                row.get::<_, i64>(0)?,
+                row.get::<_, String>(1)?,
+                row.get::<_, i64>(2)?,
+                row.get::<_, Option<String>>(3)?,
+            ))
+        })
+        .map_err(|e| e.to_string())?;
+    let mut attention = Vec::new();
+    for goal in goals_iter {
+        let (id, title, progress, target_date) = goal.map_err(|e| e.to_string())?;
+        let target = target_date
+            .as_deref()
+            .and_then(|date| NaiveDate::parse_from_str(date.get(..10)?, "%Y-%m-%d").ok());
+        let mut reasons = Vec::new();
+        if let Some(target) = target.filter(|target| *target <= end) {
+            if target < start {
+                reasons.push(format!("overdue since {}", target.format("%b %-d")));
+            } else {
+                reasons.push(format!("due {}", target.format("%a %b %-d")));
+            }
+        }
+        if let Some(days) = stale.get(&id) {
+            reasons.push(format!("no update for {} days", days));
+        }
+        if !reasons.is_empty() {
+            attention.push(format!(
+                "- {}: {}% ({})\n",
+                title.trim(),
+                progress,
+                reasons.join(", ")
+            ));
+        }
+    }
+    if attention.is_empty() {
+        markdown.push_str("_All goals are on track._\n");
+    }
+    for line in &attention {
+        markdown.push_str(line);
+    }
+
+    markdown.push_str("\n## Capacity\n\n");
+    markdown.push_str("| Day | Meetings | Available | Due (estimated) |\n");
+    markdown.push_str("| --- | --- | --- | --- |\n");
+    let calendar = WorkCalendar::load(conn)?;
+    let workday_hours = read_i64_setting(conn, "workday_hours")?.max(0) as f64;
+    let oncall = oncall_days_in_conn(conn, start, end)?;
+    let mut total_available = 0.0;
+    for day in meeting_load_in_conn(conn, start, end)? {
+        let Ok(date) = NaiveDate::parse_from_str(&day.date, "%Y-%m-%d") else {
+            continue;
+        };
+        let mut label = date.format("%a %b %-d").to_string();
+        if oncall.contains(&day.date) {
+            label.push_str(" (on call)");
+        }
+        let available = if calendar.is_workday(date) {
+            total_hours((workday_hours - day.meeting_hours).max(0.0))
+        } else {
+            0.0
+        };
+        total_available += available;
+        let due: Vec<&Task> = due_this_week
+            .iter()
+            .filter(|(_, _, due)| *due == date)
+            .map(|(task, _, _)| task)
+            .collect();
+        let estimated = total_hours(
+            due.iter()
+                .map(|task| task.time_estimate_minutes as f64 / 60.0)
+                .sum(),
+        );
+        let available_label = if calendar.is_workday(date) {
+            format!("{}h", available)
+        } else {
+            "day off".to_string()
+        };
+        let due_label = match (due.len(), estimated > available) {
+            (0, _) => "–".to_string(),
+            (count, true) => format!("{} ({}h) ⚠️", count, estimated),
+            (count, false) => format!("{} ({}h)", count, estimated),
+        };
+        markdown.push_str(&format!(
+            "| {} | {}h | {} | {} |\n",
+            label, day.meeting_hours, available_label, due_label
+        ));
+    }
+    markdown.push_str(&format!(
+        "\nAvailable this week: {}h\n",
+        total_hours(total_available)
+    ));
+
+    markdown.push_str("\n## Focus for the week\n\n- [ ] \n");
+
+    Ok(markdown)
+}
+
+/// Creates the plan page for the week containing `week_start`, or rewrites the
+/// existing top-level page of that title (its previous text is kept as a revision).
+pub(crate) fn generate_week_plan_in_conn(
+    conn: &Connection,
+    week_start: &str,
+    cipher: &JournalCipher,
+) -> Result<Page, String> {
+    let markdown = generate_week_plan_markdown_in_conn(conn, week_start, cipher)?;
+    let start = parse_week_start(conn, week_start)?;
+    let title = week_plan_title(start);
+    let stored_content = cipher.encrypt(&markdown)?;
+
+    let existing: Option<i64> = conn
+        .query_row(
+            "SELECT id FROM pages
+             WHERE title = ?1 AND parent_id IS NULL AND deleted_at IS NULL
+             ORDER BY id ASC LIMIT 1",
+            params![title],
+            |row| row.get(0),
+        )
+        .optional()
+        .map_err(|e| e.to_string())?;
+    let page_id = match existing {
+        Some(id) => {
+            snapshot_page_in_conn(conn, id)?;
+            conn.execute(
+                "UPDATE pages SET content = ?1, updated_at = ?2 WHERE id = ?3",
+                params![stored_content, Utc::now().to_rfc3339(), id],
+            )
+            .map_err(|e| e.to_string())?;
+            id
+        }
+        None => create_page_in_conn(conn, &title, &stored_content, None)?.id,
+    };
+    set_page_links_in_conn(conn, page_id, &markdown)?;
+    set_page_properties_in_conn(conn, page_id, &markdown)?;
+
+    get_page_in_conn(conn, page_id)?
+        .ok_or_else(|| "Page not found".to_string())?
+        .decrypt_with(cipher)
+}
+
+#[tauri::command]
+pub fn generate_week_plan(week_start: String, state: State<'_, AppState>) -> Result<Page, String> {
+    let cipher = state.journal_cipher()?;
+    let conn = state.db.lock().map_err(|e| e.to_string())?;
+    let page = generate_week_plan_in_conn(&conn, &week_start, &cipher)?;
+    mirror_page(&conn, page.id);
+
+    Ok(page)
+}
+
+#[tauri::command]
+pub fn generate_weekly_review(
+    week_start: String,
+    state: State<'_, AppState>,
+) -> Result<String, String> {
+    let cipher = state.journal_cipher()?;
+    let conn = state.db.lock().map_err(|e| e.to_string())?;
+    generate_weekly_review_in_conn(&conn, &week_start, &cipher)
+}
@@ -0,0 +1,107 @@
+use crate::models::ReviewQueueItem;
+use chrono::Utc;
+use rusqlite::{params, Connection};
+use tauri::State;
+
+use super::tasks::{task_from_row, TASK_COLUMNS};
+use super::AppState;
+
+/// Tasks created from a pasted issue/PR URL.
+pub(crate) const REVIEW_SOURCE_URL_CAPTURE: &str = "url_capture";
+
+/// Holds an auto-created task back from the board until the user reviews it.
+pub(crate) fn queue_task_for_review(
+    conn: &Connection,
+    task_id: i64,
+    source: &str,
+) -> Result<(), String> {
+    conn.execute(
+        "INSERT OR REPLACE INTO review_queue (task_id, source, created_at) VALUES (?1, ?2, ?3)",
+        params![task_id, source, Utc::now().to_rfc3339()],
+    )
+    .map_err(|e| e.to_string())?;
+
+    Ok(())
+}
+
+pub(crate) fn get_review_queue_in_conn(conn: &Connection) -> Result<Vec<ReviewQueueItem>, String> {
+    // The subquery renames queue columns so TASK_COLUMNS stay unambiguous.
+    let mut stmt = conn
+        .prepare(&format!(
+            "SELECT {}, q.source, q.queued_at,
+                    (SELECT url FROM task_links WHERE task_links.task_id = q.task_id ORDER BY task_links.id LIMIT 1)
+             FROM tasks
+             JOIN (SELECT task_id, source, created_at AS queued_at FROM review_queue) q
+               ON q.task_id = tasks.id
+             ORDER BY q.queued_at ASC, q.task_id ASC",
+            TASK_COLUMNS
+        ))
+        .map_err(|e| e.to_string())?;
+    let column_count = TASK_COLUMNS.split(", ").count();
+    let items_iter = stmt
+        .query_map([], |row| {
+            Ok(ReviewQueueItem {
+                task: task_from_row(row)?,
+                source: row.get(column_count)?,
+                queued_at: row.get(column_count + 1)?,
+                source_url: row.get(column_count + 2)?,
+            })
+        })
+        .map_err(|e| e.to_string())?;
+
+    let mut items = Vec::new();
+    for item in items_iter {
+        items.push(item.map_err(|e| e.to_string())?);
+    }
+
+    Ok(items)
+}
+
+fn remove_from_queue(conn: &Connection, task_id: i64) -> Result<(), String> {
+    let removed = conn
+        .execute(
+            "DELETE FROM review_queue WHERE task_id = ?1",
+            params![task_id],
+        )
+        .map_err(|e| e.to_string())?;
+    if removed == 0 {
+        return Err(format!("Task #{} is not awaiting review", task_id));
+    }
+
+    Ok(())
+}
+
+/// Moves the task onto the board as-is.
+pub(crate) fn accept_review_item_in_conn(conn: &Connection, task_id: i64) -> Result<(), String> {
+    remove_from_queue(conn, task_id)
+}
+
+/// Deletes the queued task together with its links.
+pub(crate) fn dismiss_review_item_in_conn(
+    conn: &mut Connection,
+    task_id: i64,
+) -> Result<(), String> {
+    let tx = conn.transaction().map_err(|e| e.to_string())?;
+    remove_from_queue(&tx, task_id)?;
+    tx.execute("DELETE FROM tasks WHERE id = ?1", params![task_id])
+        .map_err(|e| e.to_string())?;
+    tx.commit().map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn get_review_queue(state: State<'_, AppState>) -> Result<Vec<ReviewQueueItem>, String> {
+    let conn = state.db.lock().map_err(|e| e.to_string())?;
+    get_review_queue_in_conn(&conn)
+}
+
+#[tauri::command]
+pub fn accept_review_item(task_id: i64, state: State<'_, AppState>) -> Result<(), String> {
+    let conn = state.db.lock().map_err(|e| e.to_string())?;
+    accept_review_item_in_conn(&conn, task_id)
+}
+
+#[tauri::command]
+pub fn dismiss_review_item(task_id: i64, state: State<'_, AppState>) -> Result<(), String> {
+    let mut conn = state.db.lock().map_err(|e| e.to_string())?;
+    dismiss_review_item_in_conn(&mut conn, task_id)
+}
@@ -0,0 +1,197 @@
+use crate::models::AppSetting;
+use chrono::Utc;
+use rusqlite::{params, Connection, OptionalExtension};
+use tauri::{AppHandle, Manager, State};
+
+use super::AppState;
+
+/// Known app settings with their defaults. Unknown keys are rejected on write.
+pub(crate) const APP_SETTING_DEFAULTS: &[(&str, &str)] = &[
+    ("linked_items_poll_minutes", "15"),
+    ("linked_items_auto_complete", "false"),
+    ("timer_toggle_shortcut", "CommandOrControl+Shift+T"),
+    ("pomodoro_long_break_every", "4"),
+    ("pomodoro_long_break_minutes", "15"),
+    ("pomodoro_daily_goal", "8"),
+    ("break_reminder_minutes", "90"),
+    ("streak_risk_time", "20:00"),
+    ("streak_risk_min_days", "3"),
+    ("streak_freeze_every", "0"),
+    ("api_rate_limit_per_minute", "60"),
+    ("capture_server_enabled", "false"),
+    ("capture_server_port", "17321"),
+    ("meeting_heavy_hours", "4"),
+    ("goal_checkin_day", "friday"),
+    ("goal_checkin_time", "16:00"),
+    ("journal_reminder_time", "18:00"),
+    ("work_days", "monday,tuesday,wednesday,thursday,friday"),
+    ("days_off", ""),
+    ("workday_hours", "8"),
+    ("appearance_theme_preset", "monochrome"),
+    ("appearance_mode", "system"),
+    ("appearance_accent_color", ""),
+    ("appearance_font_preset", "inter"),
+    ("appearance_font_size", "14"),
+    ("appearance_compact", "false"),
+    ("appearance_border_radius", "16"),
+    ("last_seen_app_version", ""),
+    ("update_channel", "stable"),
+    ("week_numbering", "iso"),
+    ("integrity_auto_fix", "true"),
+    ("slow_op_threshold_ms", "250"),
+    ("update_last_notified_version", ""),
+    ("quiet_hours_start", ""),
+    ("quiet_hours_end", ""),
+    ("page_revision_limit", "50"),
+    ("vault_mirror_dir", ""),
+    ("vault_mirror_git", "false"),
+    ("slack_status_enabled", "false"),
+    ("due_reminder_leads", "1d, 1h"),
+    ("shutdown_ritual_enabled", "false"),
+    ("shutdown_ritual_after_hour", "17"),
+    (
+        "slack_standup_yesterday_headers",
+        "yesterday, what did you do yesterday, what have you done since yesterday, done",
+    ),
+    (
+        "slack_standup_today_headers",
+        "today, what will you do today, what are you working on today, plan",
+    ),
+    (
+        "slack_standup_blockers_headers",
+        "blockers, blocker, blocked, any blockers, anything blocking your progress, impediments",
+    ),
+    (
+        "notification_journal_reminder_title",
+        "Dev Journal Reminder",
+    ),
+    (
+        "notification_journal_reminder_body",
+        "You haven't written today's entry",
+    ),
+    ("notification_streak_risk_title", "Streak at risk"),
+    (
+        "notification_streak_risk_body",
+        "Your {days}-day \"{habit}\" streak ends at midnight unless you log it today.",
+    ),
+    ("notification_break_reminder_title", "Time for a break"),
+    (
+        "notification_break_reminder_body",
+        "\"{task}\" has been running for {duration}. Pause it from the tray or the app.",
+    ),
+    ("notification_due_reminder_title", "Task due soon"),
+    (
+        "notification_due_reminder_body",
+        "\"{task}\" is due in {time_left} ({date}).",
+    ),
+    ("notification_goal_checkin_title", "Weekly goal check-in"),
+    (
+        "notification_goal_checkin_body",
+        "Not updated in a week: {goals}. How is it going?",
+    ),
+    ("notification_timebox_title", "Timebox finished"),
+    (
+        "notification_timebox_body",
+        "{label} ({minutes} min) is done.",
+    ),
+    ("notification_digest_title", "Your week in Dev Journal"),
+    (
+        "notification_digest_body",
+        "Last 7 days: {entries} journal entries, {tasks_done} tasks done, {overdue} overdue.",
+    ),
+    ("notification_update_available_title", "Update available"),
+    (
+        "notification_update_available_body",
+        "Dev Journal {version} is available on the {channel} channel.",
+    ),
+];
+
+pub(crate) fn default_setting(key: &str) -> Option<&'static str> {
+    APP_SETTING_DEFAULTS
+        .iter()
+        .find(|(known_key, _)| *known_key == key)
+        .map(|(_, value)| *value)
+}
+
+pub(crate) fn read_setting(conn: &Connection, key: &str) -> Result<String, String> {
+    let stored: Option<String> = conn
+        .query_row(
+            "SELECT value FROM app_settings WHERE key = ?1",
+            params![key],
+            |row| row.get(0),
+        )
+        .optional()
+        .map_err(|e| e.to_string())?;
+
+    Ok(stored.unwrap_or_else(|| default_setting(key).unwrap_or_default().to_string()))
+}
+
+pub(crate) fn read_bool_setting(conn: &Connection, key: &str) -> Result<bool, String> {
+    Ok(matches!(read_setting(conn, key)?.as_str(), "true" | "1"))
+}
+
+pub(crate) fn read_i64_setting(conn: &Connection, key: &str) -> Result<i64, String> {
+    let value = read_setting(conn, key)?;
+    Ok(value.trim().parse::<i64>().unwrap_or_else(|_| {
+        default_setting(key)
+            .and_then(|fallback| fallback.parse().ok())
+            .unwrap_or(0)
+    }))
+}
+
+pub(crate) fn write_setting(conn: &Connection, key: &str, value: &str) -> Result<(), String> {
+    if default_setting(key).is_none() {
+        return Err(format!("Unknown setting: {}", key));
+    }
+
+    conn.execute(
+        "INSERT INTO app_settings (key, value, updated_at)
+         VALUES (?1, ?2, ?3)
+         ON CONFLICT(key) DO UPDATE SET value = excluded.value, updated_at = excluded.updated_at",
+        params![key, value.trim(), Utc::now().to_rfc3339()],
+    )
+    .map_err(|e| e.to_string())?;
+
+    Ok(())
+}
+
+#[tauri::command]
+pub fn get_app_settings(state: State<'_, AppState>) -> Result<Vec<AppSetting>, String> {
+    let conn = state.db.lock().map_err(|e| e.to_string())?;
+
+    APP_SETTING_DEFAULTS
+        .iter()
+        .map(|(key, _)| {
+            Ok(AppSetting {
+                key: key.to_string(),
+                value: read_setting(&conn, key)?,
+            })
+        })
+        .collect()
+}
+
+#[tauri::command]
+pub fn update_app_setting(
+    key: String,
+    value: String,
+    app: AppHandle,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    let key = key.trim();
+
+    if key == crate::shortcuts::TIMER_TOGGLE_SHORTCUT_SETTING {
+        crate::shortcuts::register_timer_toggle_shortcut(&app, value.trim())?;
+    }
+
+    let conn = state.db.lock().map_err(|e| e.to_string())?;
+    write_setting(&conn, key, &value)?;
+    if super::appearance::APPEARANCE_SETTING_KEYS.contains(&key) {
+        super::appearance::emit_appearance_changed(&app, &conn);
+    }
+    if key == super::performance::SLOW_OP_THRESHOLD_SETTING {
+        if let Some(performance) = app.try_state::<super::performance::PerformanceState>() {
+            performance.set_threshold_ms(read_i64_setting(&conn, key)?);
+        }
+    }
+    Ok(())
+}
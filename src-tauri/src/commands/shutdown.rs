@@ -0,0 +1,116 @@
+use crate::models::ShutdownChecklist;
+use chrono::{Local, NaiveDateTime, Timelike};
+use rusqlite::Connection;
+use tauri::{AppHandle, Manager, State};
+
+use super::entries::entry_written_on_in_conn;
+use super::events::emit_event;
+use super::settings::{read_bool_setting, read_i64_setting};
+use super::tasks::pause_all_timers_in_conn;
+use super::AppState;
+
+pub(crate) const SHUTDOWN_CHECKLIST_EVENT: &str = "shutdown-checklist";
+
+/// What is left open when quitting at `now`. `None` when the ritual is off, it is
+/// before `shutdown_ritual_after_hour`, or today's entry is written and no timer runs.
+pub(crate) fn shutdown_checklist_in_conn(
+    conn: &Connection,
+    now: NaiveDateTime,
+) -> Result<Option<ShutdownChecklist>, String> {
+    if !read_bool_setting(conn, "shutdown_ritual_enabled")? {
+        return Ok(None);
+    }
+    let after_hour = read_i64_setting(conn, "shutdown_ritual_after_hour")?.clamp(0, 23);
+    if i64::from(now.hour()) < after_hour {
+        return Ok(None);
+    }
+
+    let entry_missing = !entry_written_on_in_conn(conn, now.date())?;
+    let running_timers = {
+        let mut stmt = conn
+            .prepare(
+                "SELECT title FROM tasks
+                 WHERE timer_started_at IS NOT NULL
+                 ORDER BY timer_started_at, id",
+            )
+            .map_err(|e| e.to_string())?;
+        let rows = stmt
+            .query_map([], |row| row.get::<_, String>(0))
+            .map_err(|e| e.to_string())?;
+        rows.collect::<Result<Vec<_>, _>>()
+            .map_err(|e| e.to_string())?
+    };
+    if !entry_missing && running_timers.is_empty() {
+        return Ok(None);
+    }
+
+    let mut parts = Vec::new();
+    if entry_missing {
+        parts.push("entry missing".to_string());
+    }
+    match running_timers.len() {
+        0 => {}
+        1 => parts.push("1 timer running".to_string()),
+        count => parts.push(format!("{} timers running", count)),
+    }
+
+    Ok(Some(ShutdownChecklist {
+        date: now.date().format("%Y-%m-%d").to_string(),
+        entry_missing,
+        running_timers,
+        summary: parts.join(", "),
+    }))
+}
+
+/// The tray's Quit. Exits straight away unless the shutdown ritual finds loose ends;
+/// then the main window comes up with the checklist and quitting waits for
+/// `confirm_shutdown`. A failed check never keeps the app from quitting.
+pub fn request_quit(app: &AppHandle) {
+    let checklist = match app.try_state::<AppState>() {
+        Some(state) => state
+            .db
+            .lock()
+            .map_err(|e| e.to_string())
+            .and_then(|conn| shutdown_checklist_in_conn(&conn, Local::now().naive_local())),
+        None => Ok(None),
+    };
+
+    match checklist {
+        Ok(Some(checklist)) => {
+            crate::tray::show_main_window(app);
+            if let Err(error) = emit_event(app, SHUTDOWN_CHECKLIST_EVENT, &checklist) {
+                eprintln!("Failed to emit shutdown checklist: {error}");
+                app.exit(0);
+            }
+        }
+        Ok(None) => app.exit(0),
+        Err(error) => {
+            eprintln!("Failed to check shutdown ritual: {error}");
+            app.exit(0);
+        }
+    }
+}
+
+#[tauri::command]
+pub fn get_shutdown_checklist(
+    state: State<'_, AppState>,
+) -> Result<Option<ShutdownChecklist>, String> {
+    let conn = state.db.lock().map_err(|e| e.to_string())?;
+    shutdown_checklist_in_conn(&conn, Local::now().naive_local())
+}
+
+/// Acknowledges the checklist and quits, optionally stopping running timers first
+/// so their time is logged.
+#[tauri::command]
+pub fn confirm_shutdown(
+    stop_timers: bool,
+    app: AppHandle,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    if stop_timers {
+        let conn = state.db.lock().map_err(|e| e.to_string())?;
+        pause_all_timers_in_conn(&conn)?;
+    }
+    app.exit(0);
+    Ok(())
+}
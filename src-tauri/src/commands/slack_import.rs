@@ -0,0 +1,519 @@
+use crate::models::SlackImportSummary;
+use chrono::{DateTime, Local, NaiveDate};
+use rusqlite::{params, Connection};
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use tauri::{AppHandle, State};
+
+use super::encryption::JournalCipher;
+use super::entries::{discard_trashed_entry_in_conn, get_entry_in_conn};
+use super::mirror::mirror_entry;
+use super::settings::read_setting;
+use super::{achievements, attachments, AppState};
+
+/// Comma-separated headers that start each standup section, matched case-insensitively
+/// at the start of a line (`*Yesterday:*`, `What did you do yesterday?`).
+pub(crate) const SLACK_YESTERDAY_HEADERS_SETTING: &str = "slack_standup_yesterday_headers";
+pub(crate) const SLACK_TODAY_HEADERS_SETTING: &str = "slack_standup_today_headers";
+pub(crate) const SLACK_BLOCKERS_HEADERS_SETTING: &str = "slack_standup_blockers_headers";
+
+/// Message subtypes that can carry a standup; joins, topic changes and the like are skipped.
+const STANDUP_SUBTYPES: [&str; 3] = ["bot_message", "thread_broadcast", "me_message"];
+
+/// One message of a Slack export day file (`<channel>/YYYY-MM-DD.json`).
+#[derive(Debug, Deserialize)]
+pub(crate) struct SlackMessage {
+    #[serde(default)]
+    subtype: Option<String>,
+    #[serde(default)]
+    user: Option<String>,
+    #[serde(default)]
+    username: Option<String>,
+    #[serde(default)]
+    user_profile: Option<SlackUserProfile>,
+    #[serde(default)]
+    text: String,
+    #[serde(default)]
+    ts: Option<String>,
+    #[serde(default)]
+    attachments: Vec<SlackAttachment>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct SlackUserProfile {
+    #[serde(default)]
+    real_name: Option<String>,
+    #[serde(default)]
+    display_name: Option<String>,
+    #[serde(default)]
+    name: Option<String>,
+}
+
+/// Standup bots such as Geekbot post each question as an attachment (or field) whose
+/// title is the question and whose text is the answer.
+#[derive(Debug, Default, Deserialize)]
+struct SlackAttachment {
+    #[serde(default)]
+    author_name: Option<String>,
+    #[serde(default)]
+    pretext: Option<String>,
+    #[serde(default)]
+    title: Option<String>,
+    #[serde(default)]
+    text: Option<String>,
+    #[serde(default)]
+    fields: Vec<SlackField>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct SlackField {
+    #[serde(default)]
+    title: String,
+    #[serde(default)]
+    value: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum StandupSection {
+    Yesterday,
+    Today,
+    Blockers,
+}
+
+/// Section headers loaded from the settings, lowercased.
+#[derive(Debug, Clone)]
+pub(crate) struct StandupHeaders {
+    headers: Vec<(String, StandupSection)>,
+}
+
+impl StandupHeaders {
+    pub(crate) fn load(conn: &Connection) -> Result<Self, String> {
+        Ok(Self::new(
+            &read_setting(conn, SLACK_YESTERDAY_HEADERS_SETTING)?,
+            &read_setting(conn, SLACK_TODAY_HEADERS_SETTING)?,
+            &read_setting(conn, SLACK_BLOCKERS_HEADERS_SETTING)?,
+        ))
+    }
+
+    pub(crate) fn new(yesterday: &str, today: &str, blockers: &str) -> Self {
+        let mut headers = Vec::new();
+        for (list, section) in [
+            (yesterday, StandupSection::Yesterday),
+            (today, StandupSection::Today),
+            (blockers, StandupSection::Blockers),
+        ] {
+            for header in list.split([',', '\n']) {
+                let header = header.trim().to_lowercase();
+                if !header.is_empty() {
+                    headers.push((header, section));
+                }
+            }
+        }
+        // Longest first, so "what did you do yesterday" wins over "what".
+        headers.sort_by_key(|(header, _)| std::cmp::Reverse(header.len()));
+        Self { headers }
+    }
+
+    /// The section a line opens and the answer written on the same line, if any. A
+    /// header must be followed by `:`, `?`, closing emphasis or the end of the line, so
+    /// "Yesterday I fixed the build" stays an answer.
+    fn match_line(&self, line: &str) -> Option<(StandupSection, String)> {
+        let stripped = strip_emoji_prefix(line.trim_start_matches(['#', '*', '_', '>', ' ']));
+        let lowered = stripped.to_lowercase();
+        for (header, section) in &self.headers {
+            let Some(rest) = lowered.strip_prefix(header.as_str()) else {
+                continue;
+            };
+            if !(rest.is_empty() || rest.starts_with([':', '?', '*', '_'])) {
+                continue;
+            }
+            // `to_lowercase` can change byte lengths, so slice the original by characters.
+            let answer: String = stripped.chars().skip(header.chars().count()).collect();
+            let answer = answer
+                .trim_start_matches([':', '?', '*', '_', ' ', '-', '–'])
+                .trim()
+                .to_string();
+            return Some((*section, answer));
+        }
+        None
+    }
+}
+
+/// Drops leading `:emoji:` codes that standup bots put in front of questions.
+fn strip_emoji_prefix(line: &str) -> &str {
+    let mut line = line.trim_start();
+    while let Some(rest) = line.strip_prefix(':') {
+        match rest.find(':') {
+            Some(end) if end > 0 && !rest[..end].contains(' ') => {
+                line = rest[end + 1..].trim_start()
+            }
+            _ => break,
+        }
+    }
+    line
+}
+
+/// Slack's escaped mrkdwn to plain text: `&amp;` entities, `<url|label>` links and
+/// `•` bullets.
+fn slack_plain_text(text: &str) -> String {
+    let mut plain = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find('<') {
+        plain.push_str(&rest[..start]);
+        let Some(end) = rest[start..].find('>') else {
+            plain.push_str(&rest[start..]);
+            rest = "";
+            break;
+        };
+        let inner = &rest[start + 1..start + end];
+        match inner.split_once('|') {
+            Some((_, label)) => plain.push_str(label),
+            None => plain.push_str(inner.trim_start_matches('!')),
+        }
+        rest = &rest[start + end + 1..];
+    }
+    plain.push_str(rest);
+
+    plain
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&amp;", "&")
+        .lines()
+        .map(|line| match line.trim_start().strip_prefix('•') {
+            Some(item) => format!("- {}", item.trim()),
+            None => line.trim_end().to_string(),
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+impl SlackMessage {
+    fn is_candidate(&self) -> bool {
+        self.subtype
+            .as_deref()
+            .is_none_or(|subtype| STANDUP_SUBTYPES.contains(&subtype))
+    }
+
+    /// Matches the Slack user id, the profile or bot name, or, for bot posts such as
+    /// "*Jane Doe* posted an update", the name in the first line or attachment author.
+    fn is_from(&self, author: &str) -> bool {
+        let author = author.trim().trim_start_matches('@').to_lowercase();
+        if author.is_empty() {
+            return true;
+        }
+        let profile = self.user_profile.as_ref();
+        let names = [
+            self.user.as_deref(),
+            self.username.as_deref(),
+            profile.and_then(|profile| profile.real_name.as_deref()),
+            profile.and_then(|profile| profile.display_name.as_deref()),
+            profile.and_then(|profile| profile.name.as_deref()),
+        ];
+        if names
+            .into_iter()
+            .flatten()
+            .any(|name| name.trim().to_lowercase() == author)
+        {
+            return true;
+        }
+
+        let bot_post = self.subtype.as_deref() == Some("bot_message");
+        bot_post
+            && (self
+                .text
+                .lines()
+                .next()
+                .is_some_and(|line| line.to_lowercase().contains(&author))
+                || self.attachments.iter().any(|attachment| {
+                    attachment
+                        .author_name
+                        .as_deref()
+                        .is_some_and(|name| name.trim().to_lowercase() == author)
+                }))
+    }
+
+    /// The message text followed by attachment questions and answers, one per line.
+    /// Attachment titles always count as headers, whatever their punctuation. A bot
+    /// post's own text ("Jane posted an update") is left out when it has attachments.
+    fn lines(&self) -> Vec<String> {
+        let bot_summary =
+            self.subtype.as_deref() == Some("bot_message") && !self.attachments.is_empty();
+        let mut text = if bot_summary {
+            String::new()
+        } else {
+            slack_plain_text(&self.text)
+        };
+        for attachment in &self.attachments {
+            if let Some(pretext) = attachment.pretext.as_deref() {
+                text.push('\n');
+                text.push_str(&slack_plain_text(pretext));
+            }
+            push_question(
+                &mut text,
+                attachment.title.as_deref().unwrap_or_default(),
+                attachment.text.as_deref().unwrap_or_default(),
+            );
+            for field in &attachment.fields {
+                push_question(&mut text, &field.title, &field.value);
+            }
+        }
+
+        text.lines().map(str::to_string).collect()
+    }
+}
+
+fn push_question(text: &mut String, question: &str, answer: &str) {
+    let question = slack_plain_text(question.trim());
+    if !question.is_empty() {
+        text.push('\n');
+        text.push_str(question.trim_end_matches([':', '?']));
+        text.push(':');
+    }
+    text.push('\n');
+    text.push_str(&slack_plain_text(answer));
+}
+
+/// A standup split into sections; text before the first header goes to notes.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub(crate) struct ParsedStandup {
+    pub yesterday: String,
+    pub today: String,
+    pub blockers: String,
+    pub notes: String,
+}
+
+fn push_line(section: &mut String, line: &str) {
+    if line.trim().is_empty() && section.is_empty() {
+        return;
+    }
+    if !section.is_empty() {
+        section.push('\n');
+    }
+    section.push_str(line.trim_end());
+}
+
+/// `None` when no line opens a section, which is how ordinary channel chatter is told
+/// apart from standup posts.
+pub(crate) fn parse_standup(lines: &[String], headers: &StandupHeaders) -> Option<ParsedStandup> {
+    let mut standup = ParsedStandup::default();
+    let mut current = None;
+    let mut found = false;
+    for line in lines {
+        if let Some((section, answer)) = headers.match_line(line) {
+            current = Some(section);
+            found = true;
+            if answer.is_empty() {
+                continue;
+            }
+            push_line(standup.section_mut(current), &answer);
+        } else {
+            push_line(standup.section_mut(current), line);
+        }
+    }
+    if !found {
+        return None;
+    }
+    for section in [
+        &mut standup.yesterday,
+        &mut standup.today,
+        &mut standup.blockers,
+        &mut standup.notes,
+    ] {
+        *section = section.trim().to_string();
+    }
+
+    Some(standup)
+}
+
+impl ParsedStandup {
+    fn section_mut(&mut self, section: Option<StandupSection>) -> &mut String {
+        match section {
+            Some(StandupSection::Yesterday) => &mut self.yesterday,
+            Some(StandupSection::Today) => &mut self.today,
+            Some(StandupSection::Blockers) => &mut self.blockers,
+            None => &mut self.notes,
+        }
+    }
+}
+
+fn message_time(ts: &str) -> Option<DateTime<Local>> {
+    let (seconds, _) = ts.split_once('.').unwrap_or((ts, ""));
+    DateTime::from_timestamp(seconds.parse().ok()?, 0).map(|time| time.with_timezone(&Local))
+}
+
+/// Appends `text` to an entry section unless it is already there.
+fn append_section(section: &mut String, text: &str) -> bool {
+    if text.is_empty() || section.contains(text) {
+        return false;
+    }
+    if !section.trim().is_empty() {
+        section.push('\n');
+    }
+    section.push_str(text);
+    true
+}
+
+/// Imports standup posts by `author` (any author when empty) grouped by local day.
+/// Sections are appended to existing entries unless the text is already there, so
+/// importing the same export twice changes nothing. Returns the attachment files of
+/// trashed entries that were replaced, for the caller to delete.
+pub(crate) fn import_slack_standups_in_conn(
+    conn: &mut Connection,
+    messages: Vec<SlackMessage>,
+    author: &str,
+    cipher: &JournalCipher,
+) -> Result<(SlackImportSummary, Vec<String>), String> {
+    let headers = StandupHeaders::load(conn)?;
+    let messages_read = messages.len() as i64;
+    let mut days: BTreeMap<NaiveDate, Vec<(DateTime<Local>, ParsedStandup)>> = BTreeMap::new();
+    let mut standups_found = 0;
+    for message in messages {
+        if !message.is_candidate() || !message.is_from(author) {
+            continue;
+        }
+        let Some(time) = message.ts.as_deref().and_then(message_time) else {
+            continue;
+        };
+        if let Some(standup) = parse_standup(&message.lines(), &headers) {
+            standups_found += 1;
+            days.entry(time.date_naive())
+                .or_default()
+                .push((time, standup));
+        }
+    }
+
+    let tx = conn.transaction().map_err(|e| e.to_string())?;
+    let mut written = Vec::new();
+    let mut discarded_attachments = Vec::new();
+    for (date, mut standups) in days {
+        standups.sort_by_key(|(time, _)| *time);
+        let date = date.format("%Y-%m-%d").to_string();
+        let existing = get_entry_in_conn(&tx, &date)?;
+        let mut sections = match &existing {
+            Some(entry) => [
+                cipher.decrypt(&entry.yesterday)?,
+                cipher.decrypt(&entry.today)?,
+                cipher.decrypt(&entry.blockers)?,
+                cipher.decrypt(&entry.notes)?,
+            ],
+            None => Default::default(),
+        };
+        let mut changed = false;
+        for (_, standup) in &standups {
+            for (section, text) in sections.iter_mut().zip([
+                &standup.yesterday,
+                &standup.today,
+                &standup.blockers,
+                &standup.notes,
+            ]) {
+                changed |= append_section(section, text);
+            }
+        }
+        if !changed {
+            continue;
+        }
+
+        discarded_attachments.extend(discard_trashed_entry_in_conn(&tx, &date)?);
+        let [yesterday, today, blockers, notes] = &sections;
+        tx.execute(
+            "INSERT INTO entries (date, yesterday, today, blockers, notes, project_id, created_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, NULL, ?6)
+             ON CONFLICT(date) DO UPDATE SET
+                yesterday = excluded.yesterday,
+                today = excluded.today,
+                blockers = excluded.blockers,
+                notes = excluded.notes",
+            params![
+                date,
+                cipher.encrypt(yesterday)?,
+                cipher.encrypt(today)?,
+                cipher.encrypt(blockers)?,
+                cipher.encrypt(notes)?,
+                standups[0].0.to_rfc3339()
+            ],
+        )
+        .map_err(|e| e.to_string())?;
+        written.push(date);
+    }
+    tx.commit().map_err(|e| e.to_string())?;
+
+    for date in &written {
+        mirror_entry(conn, date);
+    }
+
+    Ok((
+        SlackImportSummary {
+            messages_read,
+            standups_found,
+            days_written: written.len() as i64,
+        },
+        discarded_attachments,
+    ))
+}
+
+/// Day files of a channel folder (`YYYY-MM-DD.json`), or the single JSON file given.
+fn slack_export_files(path: &Path) -> Result<Vec<PathBuf>, String> {
+    if !path.is_dir() {
+        return Ok(vec![path.to_path_buf()]);
+    }
+    let mut files = Vec::new();
+    for item in std::fs::read_dir(path).map_err(|e| e.to_string())? {
+        let file = item.map_err(|e| e.to_string())?.path();
+        let is_day_file = file
+            .file_name()
+            .and_then(|name| name.to_str())
+            .and_then(|name| name.strip_suffix(".json"))
+            .is_some_and(|day| NaiveDate::parse_from_str(day, "%Y-%m-%d").is_ok());
+        if is_day_file {
+            files.push(file);
+        }
+    }
+    files.sort();
+    if files.is_empty() {
+        return Err(format!(
+            "No Slack day files (YYYY-MM-DD.json) found in {}",
+            path.display()
+        ));
+    }
+
+    Ok(files)
+}
+
+/// `path` is a channel folder from an unzipped Slack export or one day file; `author`
+/// is your Slack user id, name or display name (empty imports every poster).
+#[tauri::command]
+pub fn import_slack_standups(
+    path: String,
+    author: Option<String>,
+    app: AppHandle,
+    state: State<'_, AppState>,
+) -> Result<SlackImportSummary, String> {
+    let path = PathBuf::from(path.trim());
+    let mut messages = Vec::new();
+    for file in slack_export_files(&path)? {
+        let content = std::fs::read_to_string(&file).map_err(|e| e.to_string())?;
+        let day: Vec<SlackMessage> = serde_json::from_str(&content)
+            .map_err(|e| format!("{} is not a Slack export day file: {}", file.display(), e))?;
+        messages.extend(day);
+    }
+
+    let cipher = state.journal_cipher()?;
+    let mut conn = state.db.lock().map_err(|e| e.to_string())?;
+    let (summary, discarded_attachments) = import_slack_standups_in_conn(
+        &mut conn,
+        messages,
+        author.as_deref().unwrap_or_default(),
+        &cipher,
+    )?;
+
+    drop(conn);
+    if !discarded_attachments.is_empty() {
+        attachments::remove_attachment_files(
+            &attachments::attachments_root(&app)?,
+            &discarded_attachments,
+        );
+    }
+    achievements::evaluate_achievements_for_app(&app);
+    Ok(summary)
+}
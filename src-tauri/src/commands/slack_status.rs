@@ -0,0 +1,303 @@
+use crate::models::{Presence, SlackStatusSettings};
+use chrono::{Duration as ChronoDuration, Utc};
+use rusqlite::{params, Connection};
+use serde_json::{json, Value};
+use std::sync::mpsc::{self, Sender};
+use std::sync::Mutex;
+use std::thread;
+use std::time::Duration;
+use tauri::{AppHandle, Manager, State};
+
+use super::settings::{read_bool_setting, write_setting};
+use super::validation::task_exists;
+use super::AppState;
+
+pub(crate) const SLACK_STATUS_ENABLED_SETTING: &str = "slack_status_enabled";
+
+const KEYCHAIN_SERVICE: &str = "com.devjournal.desktop";
+const KEYCHAIN_ACCOUNT: &str = "slack-status-token";
+const SLACK_PROFILE_SET_URL: &str = "https://slack.com/api/users.profile.set";
+const HTTP_TIMEOUT_SECONDS: u64 = 10;
+const STATUS_EMOJI: &str = ":hammer:";
+/// Slack rejects longer status texts.
+const STATUS_TEXT_MAX_CHARS: usize = 100;
+/// The status expires on its own if the app quits or crashes before clearing it.
+const STATUS_EXPIRATION_HOURS: i64 = 8;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum SlackStatusUpdate {
+    Set { task_id: i64, text: String },
+    Clear,
+    Unchanged,
+}
+
+/// What the Slack status should do for `presence`, given the task whose status is
+/// currently shown. A status we did not set is never cleared.
+pub(crate) fn plan_slack_status_update(
+    presence: &Presence,
+    enabled: bool,
+    opted_out: bool,
+    shown_task_id: Option<i64>,
+) -> SlackStatusUpdate {
+    let wanted = match (presence.task_id, presence.task_title.as_deref()) {
+        (Some(task_id), Some(title)) if enabled && !opted_out => Some((task_id, title)),
+        _ => None,
+    };
+
+    match (wanted, shown_task_id) {
+        (Some((task_id, _)), Some(shown)) if task_id == shown => SlackStatusUpdate::Unchanged,
+        (Some((task_id, title)), _) => SlackStatusUpdate::Set {
+            task_id,
+            text: format!("Working on {}", title.trim())
+                .chars()
+                .take(STATUS_TEXT_MAX_CHARS)
+                .collect(),
+        },
+        (None, Some(_)) => SlackStatusUpdate::Clear,
+        (None, None) => SlackStatusUpdate::Unchanged,
+    }
+}
+
+pub(crate) fn is_slack_status_opted_out_in_conn(
+    conn: &Connection,
+    task_id: i64,
+) -> Result<bool, String> {
+    conn.query_row(
+        "SELECT EXISTS(SELECT 1 FROM slack_status_opt_outs WHERE task_id = ?1)",
+        params![task_id],
+        |row| row.get::<_, i64>(0),
+    )
+    .map(|exists| exists == 1)
+    .map_err(|e| e.to_string())
+}
+
+pub(crate) fn set_slack_status_opt_out_in_conn(
+    conn: &Connection,
+    task_id: i64,
+    opted_out: bool,
+) -> Result<(), String> {
+    if !task_exists(conn, task_id)? {
+        return Err("Task not found".to_string());
+    }
+
+    if opted_out {
+        conn.execute(
+            "INSERT OR IGNORE INTO slack_status_opt_outs (task_id, created_at) VALUES (?1, ?2)",
+            params![task_id, Utc::now().to_rfc3339()],
+        )
+    } else {
+        conn.execute(
+            "DELETE FROM slack_status_opt_outs WHERE task_id = ?1",
+            params![task_id],
+        )
+    }
+    .map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+fn keychain_entry() -> Result<keyring::Entry, String> {
+    keyring::Entry::new(KEYCHAIN_SERVICE, KEYCHAIN_ACCOUNT).map_err(|e| e.to_string())
+}
+
+fn read_token() -> Result<Option<String>, String> {
+    match keychain_entry()?.get_password() {
+        Ok(token) => Ok(Some(token)),
+        Err(keyring::Error::NoEntry) => Ok(None),
+        Err(error) => Err(error.to_string()),
+    }
+}
+
+fn write_token(token: &str) -> Result<(), String> {
+    let entry = keychain_entry()?;
+    if token.is_empty() {
+        return match entry.delete_credential() {
+            Ok(()) | Err(keyring::Error::NoEntry) => Ok(()),
+            Err(error) => Err(error.to_string()),
+        };
+    }
+    entry.set_password(token).map_err(|e| e.to_string())
+}
+
+fn set_profile_status(token: &str, text: &str, emoji: &str, expiration: i64) -> Result<(), String> {
+    let response = ureq::post(SLACK_PROFILE_SET_URL)
+        .timeout(Duration::from_secs(HTTP_TIMEOUT_SECONDS))
+        .set("User-Agent", "dev-journal")
+        .set("Authorization", &format!("Bearer {}", token))
+        .send_json(json!({
+            "profile": {
+                "status_text": text,
+                "status_emoji": emoji,
+                "status_expiration": expiration,
+            }
+        }))
+        .map_err(|e| e.to_string())?
+        .into_json::<Value>()
+        .map_err(|e| e.to_string())?;
+
+    if response.get("ok").and_then(Value::as_bool) == Some(true) {
+        return Ok(());
+    }
+    Err(format!(
+        "Slack rejected the status update: {}",
+        response
+            .get("error")
+            .and_then(Value::as_str)
+            .unwrap_or("unknown error")
+    ))
+}
+
+fn apply_update(update: SlackStatusUpdate) -> Result<(), String> {
+    let Some(token) = read_token()? else {
+        return Ok(());
+    };
+    match update {
+        SlackStatusUpdate::Set { text, .. } => {
+            let expiration =
+                (Utc::now() + ChronoDuration::hours(STATUS_EXPIRATION_HOURS)).timestamp();
+            set_profile_status(&token, &text, STATUS_EMOJI, expiration)
+        }
+        SlackStatusUpdate::Clear => set_profile_status(&token, "", "", 0),
+        SlackStatusUpdate::Unchanged => Ok(()),
+    }
+}
+
+/// Task whose status is shown, plus a worker that sends updates one at a time in
+/// order, so a slow Slack call never blocks a timer command.
+pub struct SlackStatusState {
+    shown_task_id: Mutex<Option<i64>>,
+    updates: Mutex<Sender<SlackStatusUpdate>>,
+}
+
+impl SlackStatusState {
+    pub fn start() -> Self {
+        let (sender, receiver) = mpsc::channel::<SlackStatusUpdate>();
+        thread::spawn(move || {
+            for update in receiver {
+                if let Err(error) = apply_update(update) {
+                    eprintln!("Failed to update Slack status: {error}");
+                }
+            }
+        });
+        Self {
+            shown_task_id: Mutex::new(None),
+            updates: Mutex::new(sender),
+        }
+    }
+}
+
+fn try_sync_slack_status(
+    state: &AppState,
+    slack: &SlackStatusState,
+    presence: &Presence,
+) -> Result<(), String> {
+    let (enabled, opted_out) = {
+        let conn = state.db.lock().map_err(|e| e.to_string())?;
+        let opted_out = match presence.task_id {
+            Some(task_id) => is_slack_status_opted_out_in_conn(&conn, task_id)?,
+            None => false,
+        };
+        (
+            read_bool_setting(&conn, SLACK_STATUS_ENABLED_SETTING)?,
+            opted_out,
+        )
+    };
+
+    let mut shown = slack.shown_task_id.lock().map_err(|e| e.to_string())?;
+    let update = plan_slack_status_update(presence, enabled, opted_out, *shown);
+    *shown = match &update {
+        SlackStatusUpdate::Set { task_id, .. } => Some(*task_id),
+        SlackStatusUpdate::Clear => None,
+        SlackStatusUpdate::Unchanged => return Ok(()),
+    };
+    slack
+        .updates
+        .lock()
+        .map_err(|e| e.to_string())?
+        .send(update)
+        .map_err(|e| e.to_string())
+}
+
+/// Brings the Slack status in line with `presence`; called on every presence change
+/// and after the integration settings or a task's opt-out change.
+pub(crate) fn sync_slack_status(app: &AppHandle, presence: &Presence) {
+    let (Some(state), Some(slack)) = (
+        app.try_state::<AppState>(),
+        app.try_state::<SlackStatusState>(),
+    ) else {
+        return;
+    };
+    if let Err(error) = try_sync_slack_status(&state, &slack, presence) {
+        eprintln!("Failed to sync Slack status: {error}");
+    }
+}
+
+fn resync(app: &AppHandle, state: &AppState) -> Result<(), String> {
+    sync_slack_status(app, &state.presence()?);
+    Ok(())
+}
+
+#[tauri::command]
+pub fn get_slack_status_settings(
+    state: State<'_, AppState>,
+) -> Result<SlackStatusSettings, String> {
+    let enabled = {
+        let conn = state.db.lock().map_err(|e| e.to_string())?;
+        read_bool_setting(&conn, SLACK_STATUS_ENABLED_SETTING)?
+    };
+    Ok(SlackStatusSettings {
+        enabled,
+        has_token: read_token()?.is_some_and(|token| !token.is_empty()),
+    })
+}
+
+/// Passing `token: None` keeps the stored token; an empty string removes it from the keychain.
+#[tauri::command]
+pub fn save_slack_status_settings(
+    enabled: bool,
+    token: Option<String>,
+    app: AppHandle,
+    state: State<'_, AppState>,
+) -> Result<SlackStatusSettings, String> {
+    if let Some(token) = token {
+        write_token(token.trim())?;
+    }
+    {
+        let conn = state.db.lock().map_err(|e| e.to_string())?;
+        write_setting(
+            &conn,
+            SLACK_STATUS_ENABLED_SETTING,
+            if enabled { "true" } else { "false" },
+        )?;
+    }
+
+    resync(&app, &state)?;
+    get_slack_status_settings(state)
+}
+
+#[tauri::command]
+pub fn get_slack_status_opt_outs(state: State<'_, AppState>) -> Result<Vec<i64>, String> {
+    let conn = state.db.lock().map_err(|e| e.to_string())?;
+    let mut stmt = conn
+        .prepare("SELECT task_id FROM slack_status_opt_outs ORDER BY task_id")
+        .map_err(|e| e.to_string())?;
+    let task_ids = stmt
+        .query_map([], |row| row.get(0))
+        .map_err(|e| e.to_string())?
+        .collect::<Result<Vec<i64>, _>>()
+        .map_err(|e| e.to_string())?;
+    Ok(task_ids)
+}
+
+#[tauri::command]
+pub fn set_task_slack_status_opt_out(
+    task_id: i64,
+    opted_out: bool,
+    app: AppHandle,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    {
+        let conn = state.db.lock().map_err(|e| e.to_string())?;
+        set_slack_status_opt_out_in_conn(&conn, task_id, opted_out)?;
+    }
+    resync(&app, &state)
+}
@@ -0,0 +1,214 @@
+use crate::models::WorkspaceSnapshot;
+use chrono::{DateTime, Local, Utc};
+use rusqlite::{params, Connection, OpenFlags, MAIN_DB};
+use std::fs;
+use std::path::{Path, PathBuf};
+use tauri::{AppHandle, Manager, State};
+
+use super::encryption::JournalCipher;
+use super::AppState;
+
+const SNAPSHOT_NAME_MAX_CHARS: usize = 64;
+
+/// Snapshot names double as file names, so only letters, digits, spaces, `-` and `_`.
+pub(crate) fn normalize_snapshot_name(name: &str) -> Result<String, String> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err("Snapshot name is required".to_string());
+    }
+    if name.chars().count() > SNAPSHOT_NAME_MAX_CHARS {
+        return Err(format!(
+            "Snapshot name must be at most {} characters",
+            SNAPSHOT_NAME_MAX_CHARS
+        ));
+    }
+    if !name
+        .chars()
+        .all(|c| c.is_alphanumeric() || matches!(c, ' ' | '-' | '_'))
+    {
+        return Err(
+            "Snapshot names may only contain letters, digits, spaces, '-' and '_'".to_string(),
+        );
+    }
+    Ok(name.to_string())
+}
+
+fn snapshot_path(dir: &Path, name: &str) -> Result<PathBuf, String> {
+    Ok(dir.join(format!("{}.db", normalize_snapshot_name(name)?)))
+}
+
+fn read_snapshot(path: &Path) -> Result<WorkspaceSnapshot, String> {
+    let name = path
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().to_string())
+        .ok_or_else(|| "Invalid snapshot file".to_string())?;
+    let metadata = fs::metadata(path).map_err(|e| e.to_string())?;
+    let created_at = metadata
+        .modified()
+        .map(|modified| DateTime::<Utc>::from(modified).to_rfc3339())
+        .map_err(|e| e.to_string())?;
+    let schema_version = Connection::open_with_flags(path, OpenFlags::SQLITE_OPEN_READ_ONLY)
+        .and_then(|conn| {
+            conn.query_row(
+                "SELECT COALESCE(MAX(version), 0) FROM schema_migrations",
+                [],
+                |row| row.get(0),
+            )
+        })
+        .map_err(|e| e.to_string())?;
+
+    Ok(WorkspaceSnapshot {
+        name,
+        created_at,
+        size_bytes: i64::try_from(metadata.len()).unwrap_or(i64::MAX),
+        schema_version,
+    })
+}
+
+/// Copies the whole database, app settings included, into `dir/<name>.db`.
+/// Existing snapshots are never overwritten.
+pub(crate) fn create_workspace_snapshot_in_conn(
+    conn: &Connection,
+    dir: &Path,
+    name: &str,
+) -> Result<WorkspaceSnapshot, String> {
+    let path = snapshot_path(dir, name)?;
+    if path.exists() {
+        return Err(format!(
+            "A snapshot named \"{}\" already exists",
+            name.trim()
+        ));
+    }
+    fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+    conn.execute("VACUUM INTO ?1", params![path.to_string_lossy()])
+        .map_err(|e| e.to_string())?;
+    read_snapshot(&path)
+}
+
+/// Snapshots in `dir`, newest first.
+pub(crate) fn list_workspace_snapshots_in_dir(
+    dir: &Path,
+) -> Result<Vec<WorkspaceSnapshot>, String> {
+    if !dir.exists() {
+        return Ok(Vec::new());
+    }
+    let mut snapshots = Vec::new();
+    for entry in fs::read_dir(dir).map_err(|e| e.to_string())? {
+        let path = entry.map_err(|e| e.to_string())?.path();
+        if path.extension().is_some_and(|extension| extension == "db") {
+            match read_snapshot(&path) {
+                Ok(snapshot) => snapshots.push(snapshot),
+                Err(error) => eprintln!("Skipping snapshot {}: {error}", path.display()),
+            }
+        }
+    }
+    snapshots.sort_by(|a, b| {
+        b.created_at
+            .cmp(&a.created_at)
+            .then_with(|| a.name.cmp(&b.name))
+    });
+    Ok(snapshots)
+}
+
+/// Replaces the live database with snapshot `name` and migrates it to the current
+/// schema. The current state is saved first as a `before-restore-…` snapshot, which
+/// is returned, so a restore can itself be undone. Snapshots from a newer app
+/// version are refused.
+pub(crate) fn restore_workspace_snapshot_in_conn(
+    conn: &mut Connection,
+    dir: &Path,
+    name: &str,
+    now: DateTime<Local>,
+) -> Result<WorkspaceSnapshot, String> {
+    let path = snapshot_path(dir, name)?;
+    if !path.exists() {
+        return Err("Snapshot not found".to_string());
+    }
+    let snapshot = read_snapshot(&path)?;
+    if snapshot.schema_version > crate::db::SCHEMA_VERSION {
+        return Err(format!(
+            "Snapshot \"{}\" was made by a newer version of Dev Journal",
+            snapshot.name
+        ));
+    }
+
+    let safety = create_workspace_snapshot_in_conn(
+        conn,
+        dir,
+        &format!("before-restore-{}", now.format("%Y%m%d-%H%M%S")),
+    )?;
+    conn.restore(MAIN_DB, &path, None::<fn(rusqlite::backup::Progress)>)
+        .map_err(|e| e.to_string())?;
+    crate::db::migrate(conn, &|_| {}).map_err(|e| e.to_string())?;
+
+    Ok(safety)
+}
+
+/// Deletes snapshot `name`; the live database is not touched.
+pub(crate) fn delete_workspace_snapshot_in_dir(dir: &Path, name: &str) -> Result<(), String> {
+    let path = snapshot_path(dir, name)?;
+    if !path.exists() {
+        return Err("Snapshot not found".to_string());
+    }
+    fs::remove_file(path).map_err(|e| e.to_string())
+}
+
+fn workspace_snapshots_root(app: &AppHandle) -> Result<PathBuf, String> {
+    let app_data_dir = app.path().app_data_dir().map_err(|e| e.to_string())?;
+    Ok(app_data_dir.join("snapshots"))
+}
+
+#[tauri::command]
+pub fn list_workspace_snapshots(app: AppHandle) -> Result<Vec<WorkspaceSnapshot>, String> {
+    list_workspace_snapshots_in_dir(&workspace_snapshots_root(&app)?)
+}
+
+#[tauri::command]
+pub fn create_workspace_snapshot(
+    name: String,
+    app: AppHandle,
+    state: State<'_, AppState>,
+) -> Result<WorkspaceSnapshot, String> {
+    let dir = workspace_snapshots_root(&app)?;
+    let conn = state.db.lock().map_err(|e| e.to_string())?;
+    create_workspace_snapshot_in_conn(&conn, &dir, &name)
+}
+
+/// Restores snapshot `name`. Journal encryption and unlocked pages are locked
+/// again since the key may not match the restored data; the timer shortcut and
+/// slow-op threshold follow the restored settings, and every window refetches
+/// after the mutation succeeds.
+#[tauri::command]
+pub fn restore_workspace_snapshot(
+    name: String,
+    app: AppHandle,
+    state: State<'_, AppState>,
+) -> Result<WorkspaceSnapshot, String> {
+    let dir = workspace_snapshots_root(&app)?;
+    let safety = {
+        let mut conn = state.db.lock().map_err(|e| e.to_string())?;
+        let safety = restore_workspace_snapshot_in_conn(&mut conn, &dir, &name, Local::now())?;
+        *state.journal_cipher.lock().map_err(|e| e.to_string())? = JournalCipher::from_conn(&conn)?;
+        state.page_keys.lock().map_err(|e| e.to_string())?.clear();
+        super::appearance::emit_appearance_changed(&app, &conn);
+        if let Some(performance) = app.try_state::<super::performance::PerformanceState>() {
+            performance.set_threshold_ms(super::settings::read_i64_setting(
+                &conn,
+                super::performance::SLOW_OP_THRESHOLD_SETTING,
+            )?);
+        }
+        safety
+    };
+
+    // The restored settings may carry a different timer shortcut.
+    if let Err(error) = crate::shortcuts::setup_shortcuts(&app) {
+        eprintln!("Failed to re-register shortcuts after snapshot restore: {error}");
+    }
+    crate::tray::refresh_tray_menu(&app);
+    Ok(safety)
+}
+
+#[tauri::command]
+pub fn delete_workspace_snapshot(name: String, app: AppHandle) -> Result<(), String> {
+    delete_workspace_snapshot_in_dir(&workspace_snapshots_root(&app)?, &name)
+}
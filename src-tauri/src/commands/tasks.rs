@@ -1,13 +1,14 @@
-use crate::models::{Task, TaskSubtask};
+use crate::models::{DuplicateTaskMatch, Task, TaskSubtask};
+use crate::tray::refresh_tray_menu;
 use chrono::{Datelike, Utc};
-use rusqlite::{params, OptionalExtension};
-use tauri::State;
+use rusqlite::{params, Connection, OptionalExtension};
+use std::collections::{HashMap, HashSet};
+use tauri::{AppHandle, State};
 
 use super::validation::{
-    elapsed_since, normalize_goal_id, normalize_optional_date,
-    normalize_priority, normalize_status, normalize_subtask_title,
-    normalize_task_recurrence, normalize_time_estimate_minutes, normalize_project_id,
-    task_exists, touch_task_updated_at,
+    elapsed_since, normalize_entry_tag, normalize_goal_id, normalize_optional_date,
+    normalize_priority, normalize_project_id, normalize_status, normalize_subtask_title,
+    normalize_task_recurrence, normalize_time_estimate_minutes, task_exists, touch_task_updated_at,
 };
 use super::AppState;
 
@@ -23,12 +24,51 @@ pub(crate) fn compute_next_due_date(current_due_date: &str, recurrence: &str) ->
             candidate
         }
         "weekly" => date + chrono::Duration::days(7),
+        "monthly" => add_one_month(date, date.day())?,
         _ => return None,
     };
 
     Some(next.format("%Y-%m-%d").to_string())
 }
 
+/// Same day next month, clamped to the month's last day. `anchor_day` is the day the
+/// series started on, so a task due on the 31st returns to the 31st after February.
+fn add_one_month(date: chrono::NaiveDate, anchor_day: u32) -> Option<chrono::NaiveDate> {
+    let next_month = date
+        .with_day(1)?
+        .checked_add_months(chrono::Months::new(1))?;
+    let last_day = next_month
+        .checked_add_months(chrono::Months::new(1))?
+        .pred_opt()?
+        .day();
+    next_month.with_day(anchor_day.min(last_day))
+}
+
+/// Day of month of the first task in a recurring series, found by following
+/// `parent_task_id` back to the root.
+fn series_anchor_day(conn: &Connection, task_id: i64) -> Result<Option<u32>, String> {
+    let root_due_date = conn
+        .query_row(
+            "WITH RECURSIVE series(id, parent_task_id, due_date, depth) AS (
+                SELECT id, parent_task_id, due_date, 0 FROM tasks WHERE id = ?1
+                UNION ALL
+                SELECT t.id, t.parent_task_id, t.due_date, s.depth + 1
+                FROM tasks t JOIN series s ON t.id = s.parent_task_id
+                WHERE s.depth < 1000
+             )
+             SELECT due_date FROM series ORDER BY depth DESC LIMIT 1",
+            params![task_id],
+            |row| row.get::<_, Option<String>>(0),
+        )
+        .optional()
+        .map_err(|e| e.to_string())?
+        .flatten();
+
+    Ok(root_due_date
+        .and_then(|due| chrono::NaiveDate::parse_from_str(due.get(..10)?, "%Y-%m-%d").ok())
+        .map(|date| date.day()))
+}
+
 pub(crate) fn materialize_recurring_successor(
     conn: &rusqlite::Connection,
     task_id: i64,
@@ -79,7 +119,17 @@ pub(crate) fn materialize_recurring_successor(
         return Ok(());
     }
 
-    let Some(next_due_date) = compute_next_due_date(&due_date, &recurrence) else {
+    let next_due_date = match recurrence.as_str() {
+        "monthly" => {
+            let anchor_day = series_anchor_day(conn, task_id)?;
+            chrono::NaiveDate::parse_from_str(&due_date, "%Y-%m-%d")
+                .ok()
+                .and_then(|date| add_one_month(date, anchor_day.unwrap_or(date.day())))
+                .map(|date| date.format("%Y-%m-%d").to_string())
+        }
+        _ => compute_next_due_date(&due_date, &recurrence),
+    };
+    let Some(next_due_date) = next_due_date else {
         return Ok(());
     };
 
@@ -122,35 +172,181 @@ pub(crate) fn materialize_recurring_successor(
     Ok(())
 }
 
+pub(crate) const TASK_COLUMNS: &str = "id, title, description, status, priority, project_id, goal_id, due_date, recurrence, recurrence_until, parent_task_id, completed_at, time_estimate_minutes, timer_started_at, timer_accumulated_seconds, created_at, updated_at";
+
+pub(crate) fn task_from_row(row: &rusqlite::Row<'_>) -> rusqlite::Result<Task> {
+    Ok(Task {
+        id: row.get(0)?,
+        title: row.get(1)?,
+        description: row.get(2)?,
+        status: row.get(3)?,
+        priority: row.get(4)?,
+        project_id: row.get(5)?,
+        goal_id: row.get(6)?,
+        due_date: row.get(7)?,
+        recurrence: row.get(8)?,
+        recurrence_until: row.get(9)?,
+        parent_task_id: row.get(10)?,
+        completed_at: row.get(11)?,
+        time_estimate_minutes: row.get(12)?,
+        timer_started_at: row.get(13)?,
+        timer_accumulated_seconds: row.get(14)?,
+        created_at: row.get(15)?,
+        updated_at: row.get(16)?,
+        subtask_count: 0,
+        subtasks_completed: 0,
+    })
+}
+
+/// Fills in `subtask_count` and `subtasks_completed` with one grouped query.
+pub(crate) fn attach_subtask_counts_in_conn(
+    conn: &Connection,
+    tasks: &mut [Task],
+) -> Result<(), String> {
+    let mut stmt = conn
+        .prepare(
+            "SELECT task_id, COUNT(*), COALESCE(SUM(completed = 1), 0)
+             FROM task_subtasks GROUP BY task_id",
+        )
+        .map_err(|e| e.to_string())?;
+    let counts = stmt
+        .query_map([], |row| {
+            Ok((row.get::<_, i64>(0)?, (row.get(1)?, row.get(2)?)))
+        })
+        .map_err(|e| e.to_string())?
+        .collect::<Result<HashMap<i64, (i64, i64)>, _>>()
+        .map_err(|e| e.to_string())?;
+
+    for task in tasks {
+        let (count, completed) = counts.get(&task.id).copied().unwrap_or_default();
+        task.subtask_count = count;
+        task.subtasks_completed = completed;
+    }
+    Ok(())
+}
+
+/// Tasks outside the review queue with checklist progress. Each column keeps its manual
+/// order, with tasks never placed by hand on top, newest first; `tag` (with or without
+/// `#`) keeps only tasks carrying it.
+pub(crate) fn get_tasks_in_conn(conn: &Connection, tag: Option<&str>) -> Result<Vec<Task>, String> {
+    let tag = match tag.map(str::trim).filter(|tag| !tag.is_empty()) {
+        Some(tag) => Some(normalize_entry_tag(tag)?),
+        None => None,
+    };
+    let mut stmt = conn
+        .prepare(&format!(
+            "SELECT {} FROM tasks
+             WHERE id NOT IN (SELECT task_id FROM review_queue)
+               AND (?1 IS NULL OR id IN (SELECT task_id FROM task_tags WHERE tag = ?1))
+             ORDER BY sort_index IS NOT NULL, sort_index ASC, updated_at DESC, id DESC",
+            TASK_COLUMNS
+        ))
+        .map_err(|e| e.to_string())?;
+
+    let tasks_iter = stmt
+        .query_map(params![tag], task_from_row)
+        .map_err(|e| e.to_string())?;
+
+    let mut tasks = Vec::new();
+    for task in tasks_iter {
+        tasks.push(task.map_err(|e| e.to_string())?);
+    }
+    attach_subtask_counts_in_conn(conn, &mut tasks)?;
+
+    Ok(tasks)
+}
+
 #[tauri::command]
-pub fn get_tasks(state: State<'_, AppState>) -> Result<Vec<Task>, String> {
+pub fn get_tasks(tag: Option<String>, state: State<'_, AppState>) -> Result<Vec<Task>, String> {
     let conn = state.db.lock().map_err(|e| e.to_string())?;
+    get_tasks_in_conn(&conn, tag.as_deref())
+}
+
+/// Replaces a task's tags with `tags`. Tags follow the entry tag rules and compare
+/// case-insensitively; tags the task keeps retain their `created_at`. Returns the
+/// stored tags in display order.
+pub(crate) fn set_task_tags_in_conn(
+    conn: &mut Connection,
+    task_id: i64,
+    tags: &[String],
+) -> Result<Vec<String>, String> {
+    if !task_exists(conn, task_id)? {
+        return Err("Task not found".to_string());
+    }
+
+    let mut seen = HashSet::new();
+    let mut normalized = Vec::new();
+    for tag in tags {
+        let tag = normalize_entry_tag(tag)?;
+        if seen.insert(tag.to_lowercase()) {
+            normalized.push(tag);
+        }
+    }
+
+    let now = Utc::now().to_rfc3339();
+    let tx = conn.transaction().map_err(|e| e.to_string())?;
+    let existing = tx
+        .prepare("SELECT tag FROM task_tags WHERE task_id = ?1")
+        .map_err(|e| e.to_string())?
+        .query_map(params![task_id], |row| row.get::<_, String>(0))
+        .map_err(|e| e.to_string())?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| e.to_string())?;
+    for tag in existing {
+        if !seen.contains(&tag.to_lowercase()) {
+            tx.execute(
+                "DELETE FROM task_tags WHERE task_id = ?1 AND tag = ?2",
+                params![task_id, tag],
+            )
+            .map_err(|e| e.to_string())?;
+        }
+    }
+    for tag in &normalized {
+        tx.execute(
+            "INSERT OR IGNORE INTO task_tags (task_id, tag, created_at) VALUES (?1, ?2, ?3)",
+            params![task_id, tag, now],
+        )
+        .map_err(|e| e.to_string())?;
+    }
+
+    let stored = tx
+        .prepare("SELECT tag FROM task_tags WHERE task_id = ?1 ORDER BY tag COLLATE NOCASE")
+        .map_err(|e| e.to_string())?
+        .query_map(params![task_id], |row| row.get::<_, String>(0))
+        .map_err(|e| e.to_string())?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| e.to_string())?;
+    tx.commit().map_err(|e| e.to_string())?;
+    Ok(stored)
+}
+
+#[tauri::command]
+pub fn set_task_tags(
+    id: i64,
+    tags: Vec<String>,
+    state: State<'_, AppState>,
+) -> Result<Vec<String>, String> {
+    let mut conn = state.db.lock().map_err(|e| e.to_string())?;
+    set_task_tags_in_conn(&mut conn, id, &tags)
+}
+
+/// Unfinished board tasks, soonest due first; tasks waiting in the review queue are left out.
+pub(crate) fn get_open_tasks_in_conn(
+    conn: &rusqlite::Connection,
+    limit: i64,
+) -> Result<Vec<Task>, String> {
     let mut stmt = conn
-        .prepare("SELECT id, title, description, status, priority, project_id, goal_id, due_date, recurrence, recurrence_until, parent_task_id, completed_at, time_estimate_minutes, timer_started_at, timer_accumulated_seconds, created_at, updated_at FROM tasks ORDER BY updated_at DESC")
+        .prepare(&format!(
+            "SELECT {} FROM tasks
+             WHERE status != 'done' AND id NOT IN (SELECT task_id FROM review_queue)
+             ORDER BY due_date IS NULL, due_date ASC, updated_at DESC, id DESC
+             LIMIT ?1",
+            TASK_COLUMNS
+        ))
         .map_err(|e| e.to_string())?;
 
     let tasks_iter = stmt
-        .query_map([], |row| {
-            Ok(Task {
-                id: row.get(0)?,
-                title: row.get(1)?,
-                description: row.get(2)?,
-                status: row.get(3)?,
-                priority: row.get(4)?,
-                project_id: row.get(5)?,
-                goal_id: row.get(6)?,
-                due_date: row.get(7)?,
-                recurrence: row.get(8)?,
-                recurrence_until: row.get(9)?,
-                parent_task_id: row.get(10)?,
-                completed_at: row.get(11)?,
-                time_estimate_minutes: row.get(12)?,
-                timer_started_at: row.get(13)?,
-                timer_accumulated_seconds: row.get(14)?,
-                created_at: row.get(15)?,
-                updated_at: row.get(16)?,
-            })
-        })
+        .query_map(params![limit.max(0)], task_from_row)
         .map_err(|e| e.to_string())?;
 
     let mut tasks = Vec::new();
@@ -161,6 +357,268 @@ pub fn get_tasks(state: State<'_, AppState>) -> Result<Vec<Task>, String> {
     Ok(tasks)
 }
 
+/// Unfinished tasks with timer activity, running timers first.
+pub(crate) fn get_recent_timer_tasks_in_conn(
+    conn: &rusqlite::Connection,
+    limit: i64,
+) -> Result<Vec<Task>, String> {
+    let mut stmt = conn
+        .prepare(&format!(
+            "SELECT {} FROM tasks
+             WHERE status != 'done'
+               AND (timer_started_at IS NOT NULL OR timer_accumulated_seconds > 0 OR status = 'in_progress')
+             ORDER BY (timer_started_at IS NOT NULL) DESC, updated_at DESC, id DESC
+             LIMIT ?1",
+            TASK_COLUMNS
+        ))
+        .map_err(|e| e.to_string())?;
+
+    let tasks_iter = stmt
+        .query_map(params![limit.max(0)], task_from_row)
+        .map_err(|e| e.to_string())?;
+
+    let mut tasks = Vec::new();
+    for task in tasks_iter {
+        tasks.push(task.map_err(|e| e.to_string())?);
+    }
+
+    Ok(tasks)
+}
+
+#[tauri::command]
+pub fn get_recent_timer_tasks(
+    limit: Option<i64>,
+    state: State<'_, AppState>,
+) -> Result<Vec<Task>, String> {
+    let conn = state.db.lock().map_err(|e| e.to_string())?;
+    get_recent_timer_tasks_in_conn(&conn, limit.unwrap_or(5).clamp(1, 20))
+}
+
+/// Titles at least this similar (0–1) to an open task are offered as duplicates.
+const DUPLICATE_TITLE_SIMILARITY: f64 = 0.8;
+const MAX_DUPLICATE_MATCHES: usize = 5;
+
+/// Lowercased words of a title with punctuation dropped, so "Fix: login bug!" and
+/// "fix login  bug" compare equal.
+pub(crate) fn normalize_title_for_matching(title: &str) -> String {
+    title
+        .to_lowercase()
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Dice coefficient over character bigrams of two normalized titles; tolerant of
+/// typos and small rewordings.
+pub(crate) fn title_similarity(left: &str, right: &str) -> f64 {
+    if left == right {
+        return 1.0;
+    }
+    let bigrams = |text: &str| -> HashSet<(char, char)> {
+        let chars: Vec<char> = text.chars().collect();
+        chars.windows(2).map(|pair| (pair[0], pair[1])).collect()
+    };
+    let (left, right) = (bigrams(left), bigrams(right));
+    if left.is_empty() || right.is_empty() {
+        return 0.0;
+    }
+
+    2.0 * left.intersection(&right).count() as f64 / (left.len() + right.len()) as f64
+}
+
+/// Status of a task read from a plain-text format.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ImportedStatus {
+    Todo,
+    InProgress,
+    Done,
+    /// Not done, without saying whether work has started (todo.txt): new tasks start
+    /// as `todo` and an open task keeps its status.
+    Open,
+}
+
+impl ImportedStatus {
+    fn resolve(self, current: Option<&str>) -> &'static str {
+        match (self, current) {
+            (Self::Todo, _) => "todo",
+            (Self::InProgress, _) => "in_progress",
+            (Self::Done, _) => "done",
+            (Self::Open, Some("in_progress")) => "in_progress",
+            (Self::Open, _) => "todo",
+        }
+    }
+}
+
+/// A task read from an Org or todo.txt file. Re-imports match an existing task by
+/// title and update it, so a file can be synced into the board one way.
+#[derive(Debug, Clone)]
+pub(crate) struct ImportedTask {
+    pub title: String,
+    /// `None` keeps the current description, for formats that have none.
+    pub description: Option<String>,
+    pub status: ImportedStatus,
+    pub priority: String,
+    pub due_date: Option<String>,
+    pub recurrence: String,
+    /// `None` keeps the current project; `Some(None)` clears it.
+    pub project_id: Option<Option<i64>>,
+    /// Used for new tasks only.
+    pub created_at: Option<String>,
+    pub completed_at: Option<String>,
+    /// Added to the task's tags; tags the file no longer lists are kept.
+    pub tags: Vec<String>,
+}
+
+/// Creates the task or brings the task with the same title in line with it.
+/// Returns `(created, updated)`; an unchanged task is neither.
+pub(crate) fn upsert_imported_task_in_conn(
+    conn: &rusqlite::Connection,
+    imported: &ImportedTask,
+) -> Result<(bool, bool), String> {
+    let now = Utc::now().to_rfc3339();
+    let priority = normalize_priority(Some(imported.priority.clone()));
+    let recurrence = normalize_task_recurrence(Some(imported.recurrence.clone()));
+    let due_date = normalize_optional_date(imported.due_date.clone());
+    let existing = conn
+        .query_row(
+            &format!(
+                "SELECT {} FROM tasks WHERE title = ?1 ORDER BY updated_at DESC, id DESC LIMIT 1",
+                TASK_COLUMNS
+            ),
+            params![imported.title],
+            task_from_row,
+        )
+        .optional()
+        .map_err(|e| e.to_string())?;
+
+    let (task_id, created, mut changed) = match existing {
+        Some(task) => {
+            let description = imported
+                .description
+                .clone()
+                .unwrap_or_else(|| task.description.clone());
+            let project_id = imported.project_id.unwrap_or(task.project_id);
+            let mut changed = false;
+            if task.priority != priority
+                || task.due_date != due_date
+                || task.recurrence != recurrence
+                || task.description != description
+                || task.project_id != project_id
+            {
+                conn.execute(
+                    "UPDATE tasks SET description = ?1, priority = ?2, due_date = ?3, recurrence = ?4,
+                        project_id = ?5, updated_at = ?6
+                     WHERE id = ?7",
+                    params![description, priority, due_date, recurrence, project_id, now, task.id],
+                )
+                .map_err(|e| e.to_string())?;
+                changed = true;
+            }
+            let status = imported.status.resolve(Some(&task.status));
+            if task.status != status {
+                set_task_status_in_conn(conn, task.id, status.to_string())?;
+                changed = true;
+            }
+            (task.id, false, changed)
+        }
+        None => {
+            let status = imported.status.resolve(None);
+            let completed_at = (status == "done")
+                .then(|| imported.completed_at.clone().unwrap_or_else(|| now.clone()));
+            conn.execute(
+                "INSERT INTO tasks (title, description, status, priority, project_id, due_date, recurrence,
+                    completed_at, created_at, updated_at)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)",
+                params![
+                    imported.title,
+                    imported.description.clone().unwrap_or_default(),
+                    status,
+                    priority,
+                    imported.project_id.flatten(),
+                    due_date,
+                    recurrence,
+                    completed_at,
+                    imported.created_at.clone().unwrap_or_else(|| now.clone()),
+                    now
+                ],
+            )
+            .map_err(|e| e.to_string())?;
+            (conn.last_insert_rowid(), true, false)
+        }
+    };
+
+    for tag in &imported.tags {
+        if let Ok(tag) = normalize_entry_tag(tag) {
+            let added = conn
+                .execute(
+                    "INSERT OR IGNORE INTO task_tags (task_id, tag, created_at) VALUES (?1, ?2, ?3)",
+                    params![task_id, tag, now],
+                )
+                .map_err(|e| e.to_string())?;
+            changed |= added > 0 && !created;
+        }
+    }
+
+    Ok((created, changed))
+}
+
+/// Open tasks whose title nearly matches `title`, most similar first. Checked before a
+/// task is created so quick capture and integrations can point at the existing one.
+pub(crate) fn find_duplicate_tasks_in_conn(
+    conn: &rusqlite::Connection,
+    title: &str,
+) -> Result<Vec<DuplicateTaskMatch>, String> {
+    let needle = normalize_title_for_matching(title);
+    if needle.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let mut stmt = conn
+        .prepare("SELECT id, title, status FROM tasks WHERE status != 'done'")
+        .map_err(|e| e.to_string())?;
+    let tasks_iter = stmt
+        .query_map([], |row| {
+            Ok((
+                row.get::<_, i64>(0)?,
+                row.get::<_, String>(1)?,
+                row.get::<_, String>(2)?,
+            ))
+        })
+        .map_err(|e| e.to_string())?;
+
+    let mut matches = Vec::new();
+    for task in tasks_iter {
+        let (task_id, title, status) = task.map_err(|e| e.to_string())?;
+        let similarity = title_similarity(&needle, &normalize_title_for_matching(&title));
+        if similarity >= DUPLICATE_TITLE_SIMILARITY {
+            matches.push(DuplicateTaskMatch {
+                task_id,
+                title,
+                status,
+                similarity: (similarity * 100.0).round() / 100.0,
+            });
+        }
+    }
+    matches.sort_by(|a, b| {
+        b.similarity
+            .total_cmp(&a.similarity)
+            .then(b.task_id.cmp(&a.task_id))
+    });
+    matches.truncate(MAX_DUPLICATE_MATCHES);
+
+    Ok(matches)
+}
+
+#[tauri::command]
+pub fn find_duplicate_tasks(
+    title: String,
+    state: State<'_, AppState>,
+) -> Result<Vec<DuplicateTaskMatch>, String> {
+    let conn = state.db.lock().map_err(|e| e.to_string())?;
+    find_duplicate_tasks_in_conn(&conn, &title)
+}
+
 #[tauri::command]
 pub fn create_task(
     title: String,
@@ -237,6 +695,8 @@ pub fn create_task(
         timer_accumulated_seconds,
         created_at: now.clone(),
         updated_at: now,
+        subtask_count: 0,
+        subtasks_completed: 0,
     })
 }
 
@@ -253,6 +713,7 @@ pub fn update_task(
     recurrence: Option<String>,
     recurrence_until: Option<String>,
     time_estimate_minutes: Option<i64>,
+    app: AppHandle,
     state: State<'_, AppState>,
 ) -> Result<(), String> {
     let conn = state.db.lock().map_err(|e| e.to_string())?;
@@ -292,6 +753,7 @@ pub fn update_task(
     if status == "done" {
         if let Some(started_at) = timer_started_at.as_deref() {
             timer_accumulated_seconds += elapsed_since(started_at);
+            record_timer_run(&conn, id, started_at, &now)?;
         }
         timer_started_at = None;
     }
@@ -326,14 +788,19 @@ pub fn update_task(
 
     if status == "done" && previous_status != "done" {
         materialize_recurring_successor(&conn, id)?;
+        drop(conn);
+        refresh_tray_menu(&app);
+        super::achievements::evaluate_achievements_for_app(&app);
     }
 
     Ok(())
 }
 
-#[tauri::command]
-pub fn update_task_status(id: i64, status: String, state: State<'_, AppState>) -> Result<(), String> {
-    let conn = state.db.lock().map_err(|e| e.to_string())?;
+pub(crate) fn set_task_status_in_conn(
+    conn: &rusqlite::Connection,
+    id: i64,
+    status: String,
+) -> Result<(), String> {
     let now = Utc::now().to_rfc3339();
     let status = normalize_status(status);
     let previous_status: String = conn
@@ -363,6 +830,7 @@ pub fn update_task_status(id: i64, status: String, state: State<'_, AppState>) -
     if status == "done" {
         if let Some(started_at) = timer_started_at.as_deref() {
             timer_accumulated_seconds += elapsed_since(started_at);
+            record_timer_run(conn, id, started_at, &now)?;
         }
         timer_started_at = None;
     }
@@ -378,17 +846,106 @@ pub fn update_task_status(id: i64, status: String, state: State<'_, AppState>) -
         params![status, completed_at, timer_started_at, timer_accumulated_seconds, now, id],
     )
     .map_err(|e| e.to_string())?;
+    if status != previous_status {
+        // A task moved to another column lands on top of it until it is placed by hand.
+        conn.execute(
+            "UPDATE tasks SET sort_index = NULL WHERE id = ?1",
+            params![id],
+        )
+        .map_err(|e| e.to_string())?;
+    }
 
     if status == "done" && previous_status != "done" {
-        materialize_recurring_successor(&conn, id)?;
+        materialize_recurring_successor(conn, id)?;
     }
 
     Ok(())
 }
 
 #[tauri::command]
-pub fn start_task_timer(id: i64, state: State<'_, AppState>) -> Result<(), String> {
-    let conn = state.db.lock().map_err(|e| e.to_string())?;
+pub fn update_task_status(
+    id: i64,
+    status: String,
+    app: AppHandle,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    let completed = status == "done";
+    {
+        let conn = state.db.lock().map_err(|e| e.to_string())?;
+        set_task_status_in_conn(&conn, id, status)?;
+    }
+
+    if completed {
+        // Completing a task stops its timer.
+        refresh_tray_menu(&app);
+        super::achievements::evaluate_achievements_for_app(&app);
+    }
+    Ok(())
+}
+
+/// Stores a dragged board order for the `status` column. `ordered_ids` may be only the
+/// tasks visible under a filter: they are rearranged among the places they already
+/// hold, and every other task in the column keeps its place.
+pub(crate) fn reorder_tasks_in_conn(
+    conn: &mut Connection,
+    status: String,
+    ordered_ids: &[i64],
+) -> Result<(), String> {
+    let status = normalize_status(status);
+    let column = conn
+        .prepare(
+            "SELECT id FROM tasks
+             WHERE status = ?1 AND id NOT IN (SELECT task_id FROM review_queue)
+             ORDER BY sort_index IS NOT NULL, sort_index ASC, updated_at DESC, id DESC",
+        )
+        .map_err(|e| e.to_string())?
+        .query_map(params![status], |row| row.get::<_, i64>(0))
+        .map_err(|e| e.to_string())?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| e.to_string())?;
+
+    let requested = ordered_ids.iter().copied().collect::<HashSet<_>>();
+    if requested.len() != ordered_ids.len() {
+        return Err("Reorder must list each task only once".to_string());
+    }
+    if !requested.iter().all(|id| column.contains(id)) {
+        return Err("Reorder can only list tasks from that column".to_string());
+    }
+
+    let mut reordered = ordered_ids.iter();
+    let order = column
+        .iter()
+        .map(|id| {
+            if requested.contains(id) {
+                *reordered.next().unwrap_or(id)
+            } else {
+                *id
+            }
+        })
+        .collect::<Vec<_>>();
+
+    let tx = conn.transaction().map_err(|e| e.to_string())?;
+    for (sort_index, id) in order.iter().enumerate() {
+        tx.execute(
+            "UPDATE tasks SET sort_index = ?1 WHERE id = ?2",
+            params![sort_index as i64, id],
+        )
+        .map_err(|e| e.to_string())?;
+    }
+    tx.commit().map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn reorder_tasks(
+    status: String,
+    ordered_ids: Vec<i64>,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    let mut conn = state.db.lock().map_err(|e| e.to_string())?;
+    reorder_tasks_in_conn(&mut conn, status, &ordered_ids)
+}
+
+pub(crate) fn start_task_timer_in_conn(conn: &rusqlite::Connection, id: i64) -> Result<(), String> {
     let now = Utc::now().to_rfc3339();
 
     let task_row: Option<(String, Option<String>)> = conn
@@ -428,9 +985,23 @@ pub fn start_task_timer(id: i64, state: State<'_, AppState>) -> Result<(), Strin
     Ok(())
 }
 
-#[tauri::command]
-pub fn pause_task_timer(id: i64, state: State<'_, AppState>) -> Result<(), String> {
-    let conn = state.db.lock().map_err(|e| e.to_string())?;
+/// Logs one finished timer run; the runs feed the context-switch insights.
+fn record_timer_run(
+    conn: &rusqlite::Connection,
+    task_id: i64,
+    started_at: &str,
+    ended_at: &str,
+) -> Result<(), String> {
+    conn.execute(
+        "INSERT INTO task_time_entries (task_id, started_at, ended_at) VALUES (?1, ?2, ?3)",
+        params![task_id, started_at, ended_at],
+    )
+    .map_err(|e| e.to_string())?;
+
+    Ok(())
+}
+
+pub(crate) fn pause_task_timer_in_conn(conn: &rusqlite::Connection, id: i64) -> Result<(), String> {
     let now = Utc::now().to_rfc3339();
 
     let task_row: Option<(Option<String>, i64)> = conn
@@ -451,6 +1022,7 @@ pub fn pause_task_timer(id: i64, state: State<'_, AppState>) -> Result<(), Strin
     };
 
     let next_accumulated_seconds = timer_accumulated_seconds + elapsed_since(&started_at);
+    record_timer_run(conn, id, &started_at, &now)?;
 
     conn.execute(
         "UPDATE tasks SET timer_started_at = NULL, timer_accumulated_seconds = ?1, updated_at = ?2 WHERE id = ?3",
@@ -461,16 +1033,162 @@ pub fn pause_task_timer(id: i64, state: State<'_, AppState>) -> Result<(), Strin
     Ok(())
 }
 
+/// Pauses every other running timer before starting `id`, so only one task is timed at once.
+pub(crate) fn switch_task_timer_in_conn(
+    conn: &rusqlite::Connection,
+    id: i64,
+) -> Result<(), String> {
+    let running_ids = {
+        let mut stmt = conn
+            .prepare("SELECT id FROM tasks WHERE timer_started_at IS NOT NULL AND id != ?1")
+            .map_err(|e| e.to_string())?;
+        let rows = stmt
+            .query_map(params![id], |row| row.get::<_, i64>(0))
+            .map_err(|e| e.to_string())?;
+        rows.collect::<Result<Vec<_>, _>>()
+            .map_err(|e| e.to_string())?
+    };
+
+    for running_id in running_ids {
+        pause_task_timer_in_conn(conn, running_id)?;
+    }
+
+    start_task_timer_in_conn(conn, id)
+}
+
+/// Flips the timer of a task: pauses it when running, otherwise switches to it.
+pub(crate) fn toggle_task_timer_in_conn(
+    conn: &rusqlite::Connection,
+    id: i64,
+) -> Result<(), String> {
+    let running: Option<bool> = conn
+        .query_row(
+            "SELECT timer_started_at IS NOT NULL FROM tasks WHERE id = ?1",
+            params![id],
+            |row| row.get(0),
+        )
+        .optional()
+        .map_err(|e| e.to_string())?;
+
+    match running {
+        Some(true) => pause_task_timer_in_conn(conn, id),
+        Some(false) => switch_task_timer_in_conn(conn, id),
+        None => Ok(()),
+    }
+}
+
+/// Pauses every running timer and returns the paused task titles, most recently updated first.
+pub(crate) fn pause_all_timers_in_conn(conn: &rusqlite::Connection) -> Result<Vec<String>, String> {
+    let running: Vec<(i64, String)> = {
+        let mut stmt = conn
+            .prepare(
+                "SELECT id, title FROM tasks WHERE timer_started_at IS NOT NULL ORDER BY updated_at DESC",
+            )
+            .map_err(|e| e.to_string())?;
+        let rows = stmt
+            .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))
+            .map_err(|e| e.to_string())?;
+        rows.collect::<Result<Vec<_>, _>>()
+            .map_err(|e| e.to_string())?
+    };
+
+    for (id, _) in &running {
+        pause_task_timer_in_conn(conn, *id)?;
+    }
+
+    Ok(running.into_iter().map(|(_, title)| title).collect())
+}
+
+/// Tasks whose current timer run has lasted at least `min_seconds` without a pause.
+pub(crate) fn long_running_timer_tasks_in_conn(
+    conn: &rusqlite::Connection,
+    min_seconds: i64,
+) -> Result<Vec<Task>, String> {
+    let mut stmt = conn
+        .prepare(&format!(
+            "SELECT {} FROM tasks WHERE timer_started_at IS NOT NULL ORDER BY timer_started_at ASC",
+            TASK_COLUMNS
+        ))
+        .map_err(|e| e.to_string())?;
+
+    let tasks_iter = stmt
+        .query_map([], task_from_row)
+        .map_err(|e| e.to_string())?;
+
+    let mut tasks = Vec::new();
+    for task in tasks_iter {
+        let task = task.map_err(|e| e.to_string())?;
+        let running_seconds = task
+            .timer_started_at
+            .as_deref()
+            .map(elapsed_since)
+            .unwrap_or(0);
+        if running_seconds >= min_seconds {
+            tasks.push(task);
+        }
+    }
+
+    Ok(tasks)
+}
+
+pub(crate) enum TimerToggleOutcome {
+    Paused(String),
+    Started(String),
+    NoTask,
+}
+
+/// Pauses whatever is running, or resumes the most recently used task when nothing is.
+pub(crate) fn toggle_active_timer_in_conn(
+    conn: &rusqlite::Connection,
+) -> Result<TimerToggleOutcome, String> {
+    if let Some(title) = pause_all_timers_in_conn(conn)?.into_iter().next() {
+        return Ok(TimerToggleOutcome::Paused(title));
+    }
+
+    match get_recent_timer_tasks_in_conn(conn, 1)?.into_iter().next() {
+        Some(task) => {
+            start_task_timer_in_conn(conn, task.id)?;
+            Ok(TimerToggleOutcome::Started(task.title))
+        }
+        None => Ok(TimerToggleOutcome::NoTask),
+    }
+}
+
+#[tauri::command]
+pub fn start_task_timer(id: i64, app: AppHandle, state: State<'_, AppState>) -> Result<(), String> {
+    {
+        let conn = state.db.lock().map_err(|e| e.to_string())?;
+        start_task_timer_in_conn(&conn, id)?;
+    }
+    refresh_tray_menu(&app);
+
+    Ok(())
+}
+
 #[tauri::command]
-pub fn reset_task_timer(id: i64, state: State<'_, AppState>) -> Result<(), String> {
-    let conn = state.db.lock().map_err(|e| e.to_string())?;
-    let now = Utc::now().to_rfc3339();
+pub fn pause_task_timer(id: i64, app: AppHandle, state: State<'_, AppState>) -> Result<(), String> {
+    {
+        let conn = state.db.lock().map_err(|e| e.to_string())?;
+        pause_task_timer_in_conn(&conn, id)?;
+    }
+    refresh_tray_menu(&app);
 
-    conn.execute(
-        "UPDATE tasks SET timer_started_at = NULL, timer_accumulated_seconds = 0, updated_at = ?1 WHERE id = ?2",
-        params![now, id],
-    )
-    .map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+#[tauri::command]
+pub fn reset_task_timer(id: i64, app: AppHandle, state: State<'_, AppState>) -> Result<(), String> {
+    {
+        let conn = state.db.lock().map_err(|e| e.to_string())?;
+        let now = Utc::now().to_rfc3339();
+
+        conn.execute(
+            "UPDATE tasks SET timer_started_at = NULL, timer_accumulated_seconds = 0, updated_at = ?1 WHERE id = ?2",
+            params![now, id],
+        )
+        .map_err(|e| e.to_string())?;
+    }
+    refresh_tray_menu(&app);
 
     Ok(())
 }
@@ -560,7 +1278,15 @@ pub fn create_task_subtask(
     state: State<'_, AppState>,
 ) -> Result<TaskSubtask, String> {
     let conn = state.db.lock().map_err(|e| e.to_string())?;
-    if !task_exists(&conn, task_id)? {
+    create_task_subtask_in_conn(&conn, task_id, title)
+}
+
+pub(crate) fn create_task_subtask_in_conn(
+    conn: &Connection,
+    task_id: i64,
+    title: String,
+) -> Result<TaskSubtask, String> {
+    if !task_exists(conn, task_id)? {
         return Err("Task not found".to_string());
     }
 
@@ -582,7 +1308,7 @@ pub fn create_task_subtask(
     .map_err(|e| e.to_string())?;
     let id = conn.last_insert_rowid();
 
-    touch_task_updated_at(&conn, task_id, &now)?;
+    touch_task_updated_at(conn, task_id, &now)?;
 
     Ok(TaskSubtask {
         id,
@@ -638,6 +1364,50 @@ pub fn update_task_subtask(
     Ok(())
 }
 
+/// Stores a dragged checklist order: each subtask's `position` becomes its index in
+/// `ordered_ids`, which must be exactly the subtasks of `task_id`.
+pub(crate) fn reorder_task_subtasks_in_conn(
+    conn: &mut Connection,
+    task_id: i64,
+    ordered_ids: &[i64],
+) -> Result<(), String> {
+    let mut current = conn
+        .prepare("SELECT id FROM task_subtasks WHERE task_id = ?1")
+        .map_err(|e| e.to_string())?
+        .query_map(params![task_id], |row| row.get::<_, i64>(0))
+        .map_err(|e| e.to_string())?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| e.to_string())?;
+    let mut requested = ordered_ids.to_vec();
+    current.sort_unstable();
+    requested.sort_unstable();
+    if current != requested {
+        return Err("Reorder must list every subtask of the task exactly once".to_string());
+    }
+
+    let now = Utc::now().to_rfc3339();
+    let tx = conn.transaction().map_err(|e| e.to_string())?;
+    for (position, id) in ordered_ids.iter().enumerate() {
+        tx.execute(
+            "UPDATE task_subtasks SET position = ?1 WHERE id = ?2",
+            params![position as i64, id],
+        )
+        .map_err(|e| e.to_string())?;
+    }
+    touch_task_updated_at(&tx, task_id, &now)?;
+    tx.commit().map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn reorder_task_subtasks(
+    task_id: i64,
+    ordered_ids: Vec<i64>,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    let mut conn = state.db.lock().map_err(|e| e.to_string())?;
+    reorder_task_subtasks_in_conn(&mut conn, task_id, &ordered_ids)
+}
+
 #[tauri::command]
 pub fn delete_task_subtask(id: i64, state: State<'_, AppState>) -> Result<(), String> {
     let conn = state.db.lock().map_err(|e| e.to_string())?;
@@ -0,0 +1,340 @@
+use crate::models::{Task, TodoTxtImportSummary};
+use chrono::{DateTime, Local, NaiveDate, TimeZone};
+use rusqlite::Connection;
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+use tauri::State;
+
+use super::automation::get_task_tags_in_conn;
+use super::tasks::{
+    task_from_row, upsert_imported_task_in_conn, ImportedStatus, ImportedTask, TASK_COLUMNS,
+};
+use super::AppState;
+
+/// A parsed todo.txt line, before projects are resolved against the database.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(crate) struct TodoTxtTask {
+    pub done: bool,
+    pub priority: Option<char>,
+    pub completed_on: Option<NaiveDate>,
+    pub created_on: Option<NaiveDate>,
+    pub title: String,
+    pub projects: Vec<String>,
+    pub contexts: Vec<String>,
+    pub due: Option<NaiveDate>,
+    /// `daily`, `weekdays` or `weekly` from a `rec:` tag.
+    pub recurrence: Option<&'static str>,
+}
+
+fn todo_date(token: &str) -> Option<NaiveDate> {
+    NaiveDate::parse_from_str(token, "%Y-%m-%d").ok()
+}
+
+/// `rec:1d`, `rec:+1w`, `rec:b`, `rec:1m`; other intervals have no task equivalent.
+fn todo_recurrence(value: &str) -> Option<&'static str> {
+    match value.trim_start_matches('+') {
+        "d" | "1d" => Some("daily"),
+        "b" | "1b" => Some("weekdays"),
+        "w" | "1w" => Some("weekly"),
+        "m" | "1m" => Some("monthly"),
+        _ => None,
+    }
+}
+
+/// Parses one line of todo.txt: `x` and the completion date, `(A)` priority, the
+/// creation date, `+project` and `@context` words, and the `due:`, `rec:` and `pri:`
+/// tags. Other `key:value` tags stay in the title. Blank lines give `None`.
+pub(crate) fn parse_todo_txt_line(line: &str) -> Option<TodoTxtTask> {
+    let mut tokens = line.split_whitespace().peekable();
+    let mut task = TodoTxtTask::default();
+
+    if tokens.peek() == Some(&"x") {
+        tokens.next();
+        task.done = true;
+        if let Some(date) = tokens.peek().and_then(|token| todo_date(token)) {
+            task.completed_on = Some(date);
+            tokens.next();
+        }
+    } else if let Some(priority) = tokens.peek().and_then(|token| {
+        let mut chars = token.chars();
+        match (chars.next(), chars.next(), chars.next(), chars.next()) {
+            (Some('('), Some(letter), Some(')'), None) if letter.is_ascii_uppercase() => {
+                Some(letter)
+            }
+            _ => None,
+        }
+    }) {
+        task.priority = Some(priority);
+        tokens.next();
+    }
+    if let Some(date) = tokens.peek().and_then(|token| todo_date(token)) {
+        task.created_on = Some(date);
+        tokens.next();
+    }
+
+    let mut title = Vec::new();
+    for token in tokens {
+        if let Some(project) = token.strip_prefix('+').filter(|name| !name.is_empty()) {
+            task.projects.push(project.to_string());
+        } else if let Some(context) = token.strip_prefix('@').filter(|name| !name.is_empty()) {
+            task.contexts.push(context.to_string());
+        } else if let Some(date) = token.strip_prefix("due:").and_then(todo_date) {
+            task.due = Some(date);
+        } else if let Some(recurrence) = token.strip_prefix("rec:").and_then(todo_recurrence) {
+            task.recurrence = Some(recurrence);
+        } else if let Some(priority) = token
+            .strip_prefix("pri:")
+            .and_then(|value| value.chars().next())
+            .filter(|priority| priority.is_ascii_uppercase())
+        {
+            // Done tasks keep their priority as a tag, since `(A)` may only open a line.
+            task.priority.get_or_insert(priority);
+        } else {
+            title.push(token);
+        }
+    }
+    task.title = title.join(" ");
+    if task.title.is_empty() {
+        return None;
+    }
+
+    Some(task)
+}
+
+/// `(A)` is the most urgent: A to urgent, B to high, C to medium, anything later to low.
+fn todo_priority(priority: Option<char>) -> &'static str {
+    match priority {
+        Some('A') => "urgent",
+        Some('B') => "high",
+        Some('C') | None => "medium",
+        Some(_) => "low",
+    }
+}
+
+/// Project names may contain spaces; todo.txt words cannot, so spaces become `-`.
+fn project_word(name: &str) -> String {
+    name.split_whitespace().collect::<Vec<_>>().join("-")
+}
+
+fn project_ids_by_word(conn: &Connection) -> Result<HashMap<String, i64>, String> {
+    let mut stmt = conn
+        .prepare("SELECT id, name FROM projects")
+        .map_err(|e| e.to_string())?;
+    let projects_iter = stmt
+        .query_map([], |row| {
+            Ok((row.get::<_, i64>(0)?, row.get::<_, String>(1)?))
+        })
+        .map_err(|e| e.to_string())?;
+
+    let mut projects = HashMap::new();
+    for project in projects_iter {
+        let (id, name) = project.map_err(|e| e.to_string())?;
+        projects.insert(project_word(&name).to_lowercase(), id);
+    }
+
+    Ok(projects)
+}
+
+fn local_date_time(date: NaiveDate) -> Option<String> {
+    Local
+        .from_local_datetime(&date.and_hms_opt(0, 0, 0)?)
+        .earliest()
+        .map(|time| time.to_rfc3339())
+}
+
+/// Imports a todo.txt file. `@context`s become task tags; the first `+project` that
+/// names an existing project sets the task's project and the others become tags too.
+/// Tasks are matched by title, so importing the same file again only applies changes.
+pub(crate) fn import_todo_txt_in_conn(
+    conn: &mut Connection,
+    text: &str,
+) -> Result<TodoTxtImportSummary, String> {
+    let projects = project_ids_by_word(conn)?;
+    let mut summary = TodoTxtImportSummary {
+        tasks_read: 0,
+        tasks_created: 0,
+        tasks_updated: 0,
+    };
+
+    let tx = conn.transaction().map_err(|e| e.to_string())?;
+    for task in text.lines().filter_map(parse_todo_txt_line) {
+        summary.tasks_read += 1;
+        let mut project_id = None;
+        let mut tags = task.contexts.clone();
+        for project in &task.projects {
+            match projects.get(&project.to_lowercase()) {
+                Some(id) if project_id.is_none() => project_id = Some(*id),
+                _ => tags.push(project.clone()),
+            }
+        }
+
+        let imported = ImportedTask {
+            title: task.title.clone(),
+            description: None,
+            status: if task.done {
+                ImportedStatus::Done
+            } else {
+                ImportedStatus::Open
+            },
+            priority: todo_priority(task.priority).to_string(),
+            due_date: task.due.map(|due| due.format("%Y-%m-%d").to_string()),
+            recurrence: task.recurrence.unwrap_or("none").to_string(),
+            project_id: Some(project_id),
+            created_at: task.created_on.and_then(local_date_time),
+            completed_at: task.completed_on.and_then(local_date_time),
+            tags,
+        };
+        match upsert_imported_task_in_conn(&tx, &imported)? {
+            (true, _) => summary.tasks_created += 1,
+            (false, true) => summary.tasks_updated += 1,
+            (false, false) => {}
+        }
+    }
+    tx.commit().map_err(|e| e.to_string())?;
+
+    Ok(summary)
+}
+
+fn local_date(timestamp: &str) -> Option<String> {
+    DateTime::parse_from_rfc3339(timestamp)
+        .ok()
+        .map(|time| time.with_timezone(&Local).format("%Y-%m-%d").to_string())
+}
+
+fn todo_txt_line(task: &Task, project: Option<&str>, tags: &[String]) -> String {
+    let priority = match task.priority.as_str() {
+        "urgent" => Some('A'),
+        "high" => Some('B'),
+        "low" => Some('D'),
+        _ => None,
+    };
+    let mut words: Vec<String> = Vec::new();
+    let done = task.status == "done";
+    if done {
+        words.push("x".to_string());
+        if let Some(completed) = task.completed_at.as_deref().and_then(local_date) {
+            words.push(completed);
+        }
+    } else if let Some(priority) = priority {
+        words.push(format!("({})", priority));
+    }
+    if let Some(created) = local_date(&task.created_at) {
+        words.push(created);
+    }
+    words.extend(task.title.split_whitespace().map(str::to_string));
+    if let Some(project) = project {
+        words.push(format!("+{}", project_word(project)));
+    }
+    words.extend(tags.iter().map(|tag| format!("@{}", project_word(tag))));
+    if let Some(due) = task.due_date.as_deref().and_then(|due| due.get(..10)) {
+        words.push(format!("due:{}", due));
+    }
+    match task.recurrence.as_str() {
+        "daily" => words.push("rec:1d".to_string()),
+        "weekdays" => words.push("rec:1b".to_string()),
+        "weekly" => words.push("rec:1w".to_string()),
+        "monthly" => words.push("rec:1m".to_string()),
+        _ => {}
+    }
+    if let Some(priority) = priority.filter(|_| done) {
+        words.push(format!("pri:{}", priority));
+    }
+    words.join(" ")
+}
+
+/// Every task as one todo.txt line, open tasks first; tags are written as `@context`s
+/// and the project as a `+project`.
+pub(crate) fn todo_txt_export_in_conn(conn: &Connection) -> Result<String, String> {
+    let mut project_names: HashMap<i64, String> = HashMap::new();
+    let mut stmt = conn
+        .prepare("SELECT id, name FROM projects")
+        .map_err(|e| e.to_string())?;
+    let projects_iter = stmt
+        .query_map([], |row| {
+            Ok((row.get::<_, i64>(0)?, row.get::<_, String>(1)?))
+        })
+        .map_err(|e| e.to_string())?;
+    for project in projects_iter {
+        let (id, name) = project.map_err(|e| e.to_string())?;
+        project_names.insert(id, name);
+    }
+    let mut task_tags: HashMap<i64, Vec<String>> = HashMap::new();
+    for tag in get_task_tags_in_conn(conn)? {
+        task_tags.entry(tag.task_id).or_default().push(tag.tag);
+    }
+
+    let mut stmt = conn
+        .prepare(&format!(
+            "SELECT {} FROM tasks
+             WHERE id NOT IN (SELECT task_id FROM review_queue)
+             ORDER BY status = 'done', due_date IS NULL, due_date ASC, id ASC",
+            TASK_COLUMNS
+        ))
+        .map_err(|e| e.to_string())?;
+    let tasks_iter = stmt
+        .query_map([], task_from_row)
+        .map_err(|e| e.to_string())?;
+
+    let mut lines = String::new();
+    for task in tasks_iter {
+        let task = task.map_err(|e| e.to_string())?;
+        let project = task
+            .project_id
+            .and_then(|id| project_names.get(&id))
+            .map(String::as_str);
+        let tags = task_tags
+            .get(&task.id)
+            .map(Vec::as_slice)
+            .unwrap_or_default();
+        lines.push_str(&todo_txt_line(&task, project, tags));
+        lines.push('\n');
+    }
+
+    Ok(lines)
+}
+
+/// `path` is the todo.txt file; a folder means `todo.txt` inside it.
+fn todo_txt_path(path: &str) -> Result<PathBuf, String> {
+    let path = path.trim();
+    if path.is_empty() {
+        return Err("Path is required".to_string());
+    }
+    let path = PathBuf::from(path);
+    if path.is_dir() {
+        return Ok(path.join("todo.txt"));
+    }
+    Ok(path)
+}
+
+#[tauri::command]
+pub fn import_todo_txt(
+    path: String,
+    state: State<'_, AppState>,
+) -> Result<TodoTxtImportSummary, String> {
+    let path = todo_txt_path(&path)?;
+    let text = fs::read_to_string(&path)
+        .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    let mut conn = state.db.lock().map_err(|e| e.to_string())?;
+    import_todo_txt_in_conn(&mut conn, &text)
+}
+
+/// Writes every task to a todo.txt file and returns its path.
+#[tauri::command]
+pub fn export_todo_txt(path: String, state: State<'_, AppState>) -> Result<String, String> {
+    let path = todo_txt_path(&path)?;
+    let text = {
+        let conn = state.db.lock().map_err(|e| e.to_string())?;
+        todo_txt_export_in_conn(&conn)?
+    };
+
+    if let Some(parent) = path
+        .parent()
+        .filter(|parent| !parent.as_os_str().is_empty())
+    {
+        fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create {}: {}", parent.display(), e))?;
+    }
+    fs::write(&path, text).map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+    Ok(path.display().to_string())
+}
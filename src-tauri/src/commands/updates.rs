@@ -0,0 +1,150 @@
+use crate::models::UpdateCheck;
+use chrono::{Local, Utc};
+use rusqlite::Connection;
+use serde::Deserialize;
+use std::cmp::Ordering;
+use std::time::Duration;
+use tauri::{AppHandle, Manager};
+use tauri_plugin_notification::NotificationExt;
+
+use super::events::emit_event;
+use super::notifications::{
+    in_quiet_hours_in_conn, notification_templates_in_conn, render_notification_template,
+};
+use super::settings::{read_setting, write_setting};
+use super::whats_new::{compare_versions, parse_version};
+use super::AppState;
+
+const RELEASES_URL: &str = "https://api.github.com/repos/whylineee/dev_journal/releases";
+const APP_VERSION: &str = env!("CARGO_PKG_VERSION");
+const HTTP_TIMEOUT_SECONDS: u64 = 15;
+
+/// The fields of a GitHub release the check needs.
+#[derive(Debug, Clone, Deserialize)]
+pub(crate) struct PublishedRelease {
+    pub tag_name: String,
+    #[serde(default)]
+    pub prerelease: bool,
+    #[serde(default)]
+    pub draft: bool,
+    #[serde(default)]
+    pub html_url: Option<String>,
+    #[serde(default)]
+    pub body: Option<String>,
+    #[serde(default)]
+    pub published_at: Option<String>,
+}
+
+/// Core versions are compared first; with equal cores a release build is newer than
+/// a pre-release such as `1.2.0-beta.1`.
+fn compare_release_versions(left: &str, right: &str) -> Option<Ordering> {
+    let core = compare_versions(&parse_version(left)?, &parse_version(right)?);
+    let is_prerelease = |version: &str| version.trim().trim_start_matches('v').contains('-');
+    Some(core.then_with(|| is_prerelease(right).cmp(&is_prerelease(left))))
+}
+
+/// The newest published release on `channel` that is newer than `current_version`.
+/// `stable` skips pre-releases, `beta` includes them; drafts are always skipped.
+pub(crate) fn newest_release<'a>(
+    releases: &'a [PublishedRelease],
+    channel: &str,
+    current_version: &str,
+) -> Option<&'a PublishedRelease> {
+    releases
+        .iter()
+        .filter(|release| !release.draft && (channel == "beta" || !release.prerelease))
+        .filter(|release| {
+            compare_release_versions(&release.tag_name, current_version)
+                .is_some_and(Ordering::is_gt)
+        })
+        .max_by(|left, right| {
+            compare_release_versions(&left.tag_name, &right.tag_name).unwrap_or(Ordering::Equal)
+        })
+}
+
+pub(crate) fn update_check_from_releases(
+    releases: &[PublishedRelease],
+    channel: &str,
+    current_version: &str,
+) -> UpdateCheck {
+    let newest = newest_release(releases, channel, current_version);
+    UpdateCheck {
+        current_version: current_version.to_string(),
+        channel: channel.to_string(),
+        update_available: newest.is_some(),
+        latest_version: newest
+            .map(|release| release.tag_name.trim().trim_start_matches('v').to_string()),
+        release_url: newest.and_then(|release| release.html_url.clone()),
+        release_notes: newest.and_then(|release| release.body.clone()),
+        published_at: newest.and_then(|release| release.published_at.clone()),
+        checked_at: Utc::now().to_rfc3339(),
+    }
+}
+
+/// `update_channel`; anything other than `beta` means `stable`.
+pub(crate) fn read_update_channel_in_conn(conn: &Connection) -> Result<&'static str, String> {
+    let channel = read_setting(conn, "update_channel")?.to_lowercase();
+    Ok(if channel == "beta" { "beta" } else { "stable" })
+}
+
+fn fetch_releases() -> Result<Vec<PublishedRelease>, String> {
+    ureq::get(RELEASES_URL)
+        .timeout(Duration::from_secs(HTTP_TIMEOUT_SECONDS))
+        .set("User-Agent", "dev-journal")
+        .set("Accept", "application/vnd.github+json")
+        .call()
+        .map_err(|e| format!("Update check failed: {}", e))?
+        .into_json::<Vec<PublishedRelease>>()
+        .map_err(|e| format!("Update check failed: {}", e))
+}
+
+fn check_for_updates_for_app(app: &AppHandle) -> Result<UpdateCheck, String> {
+    let channel = {
+        let state = app.state::<AppState>();
+        let conn = state.db.lock().map_err(|e| e.to_string())?;
+        read_update_channel_in_conn(&conn)?
+    };
+    // The lock is not held during the request.
+    let releases = fetch_releases()?;
+    Ok(update_check_from_releases(&releases, channel, APP_VERSION))
+}
+
+/// The `update_check` job: notifies once per new version, and not during quiet hours.
+/// A check that falls into quiet hours notifies on the next run instead.
+pub(crate) fn run_update_check_for_app(app: &AppHandle) -> Result<(), String> {
+    let check = check_for_updates_for_app(app)?;
+    let Some(version) = check.latest_version.as_deref() else {
+        return Ok(());
+    };
+
+    let (title_template, body_template) = {
+        let state = app.state::<AppState>();
+        let conn = state.db.lock().map_err(|e| e.to_string())?;
+        if read_setting(&conn, "update_last_notified_version")? == version
+            || in_quiet_hours_in_conn(&conn, Local::now().time())?
+        {
+            return Ok(());
+        }
+        write_setting(&conn, "update_last_notified_version", version)?;
+        notification_templates_in_conn(&conn, "update_available")?
+    };
+
+    let values = [("version", version), ("channel", check.channel.as_str())];
+    if let Err(error) = app
+        .notification()
+        .builder()
+        .title(render_notification_template(&title_template, &values))
+        .body(render_notification_template(&body_template, &values))
+        .show()
+    {
+        eprintln!("Failed to show update notification: {error}");
+    }
+    emit_event(app, "update-available", &check).map_err(|e| e.to_string())
+}
+
+/// Asks the release feed for a newer version on the configured channel. Never
+/// downloads anything; `release_url` points at the release page.
+#[tauri::command]
+pub async fn check_for_updates(app: AppHandle) -> Result<UpdateCheck, String> {
+    check_for_updates_for_app(&app)
+}
@@ -3,6 +3,8 @@ use chrono::{NaiveDate, Utc};
 use rusqlite::{params, Connection};
 use serde_json::{from_str, to_string};
 
+use super::page_assets::page_asset_stored_path;
+
 pub(crate) fn normalize_status(status: String) -> String {
     match status.as_str() {
         "todo" | "in_progress" | "done" => status,
@@ -21,7 +23,7 @@ pub(crate) fn normalize_priority(priority: Option<String>) -> String {
 
 pub(crate) fn normalize_task_recurrence(recurrence: Option<String>) -> String {
     match recurrence.as_deref() {
-        Some("none") | Some("daily") | Some("weekdays") | Some("weekly") => {
+        Some("none") | Some("daily") | Some("weekdays") | Some("weekly") | Some("monthly") => {
             recurrence.unwrap_or_else(|| "none".to_string())
         }
         _ => "none".to_string(),
@@ -112,6 +114,29 @@ pub(crate) fn normalize_optional_http_url(value: Option<String>) -> Option<Strin
     })
 }
 
+/// A page icon is one emoji; ZWJ sequences and flags take several chars, so up to 16
+/// are kept. Plain text is dropped.
+pub(crate) fn normalize_page_icon(value: Option<String>) -> Option<String> {
+    normalize_optional_text(value).filter(|icon| {
+        icon.chars().count() <= 16 && !icon.chars().any(char::is_whitespace) && !icon.is_ascii()
+    })
+}
+
+/// A page cover is a `#rgb`/`#rrggbb` color (stored lowercase), an http(s) image URL
+/// or a page asset URL; anything else is dropped.
+pub(crate) fn normalize_page_cover(value: Option<String>) -> Option<String> {
+    normalize_optional_text(value).and_then(|cover| {
+        if let Some(hex) = cover.strip_prefix('#') {
+            let is_color = matches!(hex.len(), 3 | 6) && hex.chars().all(|c| c.is_ascii_hexdigit());
+            return is_color.then(|| cover.to_ascii_lowercase());
+        }
+        if page_asset_stored_path(&cover).is_some() {
+            return Some(cover);
+        }
+        normalize_optional_http_url(Some(cover))
+    })
+}
+
 pub(crate) fn normalize_meeting_status(status: Option<String>) -> String {
     match status.as_deref() {
         Some("planned") | Some("live") | Some("done") | Some("missed") | Some("cancelled") => {
@@ -350,6 +375,25 @@ pub(crate) fn normalize_goal_milestone_title(title: String) -> String {
     }
 }
 
+/// Trims a tag, drops a leading `#`, and collapses inner whitespace.
+pub(crate) fn normalize_entry_tag(tag: &str) -> Result<String, String> {
+    let normalized = tag
+        .trim()
+        .trim_start_matches('#')
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ");
+
+    if normalized.is_empty() {
+        return Err("Tag cannot be empty".to_string());
+    }
+    if normalized.chars().count() > 48 {
+        return Err("Tag is too long (max 48 characters)".to_string());
+    }
+
+    Ok(normalized)
+}
+
 pub(crate) fn normalize_optional_date(value: Option<String>) -> Option<String> {
     value.and_then(|raw| {
         let trimmed = raw.trim().to_string();
@@ -375,6 +419,16 @@ pub(crate) fn task_exists(conn: &Connection, task_id: i64) -> Result<bool, Strin
     .map_err(|e| e.to_string())
 }
 
+pub(crate) fn entry_exists(conn: &Connection, entry_id: i64) -> Result<bool, String> {
+    conn.query_row(
+        "SELECT EXISTS(SELECT 1 FROM entries WHERE id = ?1)",
+        params![entry_id],
+        |row| row.get::<_, i64>(0),
+    )
+    .map(|value| value == 1)
+    .map_err(|e| e.to_string())
+}
+
 pub(crate) fn habit_exists(conn: &Connection, habit_id: i64) -> Result<bool, String> {
     conn.query_row(
         "SELECT EXISTS(SELECT 1 FROM habits WHERE id = ?1)",
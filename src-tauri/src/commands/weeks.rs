@@ -0,0 +1,104 @@
+use crate::models::WeekInfo;
+use chrono::{Datelike, Duration, Local, NaiveDate};
+use rusqlite::Connection;
+use tauri::State;
+
+use super::settings::read_setting;
+use super::AppState;
+
+/// How weeks start and are numbered, from the `week_numbering` setting.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum WeekNumbering {
+    /// ISO 8601: weeks start on Monday, week 1 holds the year's first Thursday.
+    Iso,
+    /// US: weeks start on Sunday, week 1 holds January 1st.
+    Us,
+}
+
+impl WeekNumbering {
+    pub(crate) fn as_str(self) -> &'static str {
+        match self {
+            WeekNumbering::Iso => "iso",
+            WeekNumbering::Us => "us",
+        }
+    }
+
+    /// First day of the week containing `date`.
+    pub(crate) fn week_start(self, date: NaiveDate) -> NaiveDate {
+        let offset = match self {
+            WeekNumbering::Iso => date.weekday().num_days_from_monday(),
+            WeekNumbering::Us => date.weekday().num_days_from_sunday(),
+        };
+        date - Duration::days(i64::from(offset))
+    }
+
+    /// Week-numbering year and week number of `date`. Near New Year the year can
+    /// differ from the calendar year, e.g. US week 1 of 2027 starts on Dec 27, 2026.
+    pub(crate) fn week_number(self, date: NaiveDate) -> (i32, u32) {
+        match self {
+            WeekNumbering::Iso => {
+                let week = date.iso_week();
+                (week.year(), week.week())
+            }
+            WeekNumbering::Us => {
+                let start = self.week_start(date);
+                // The week holding January 1st is week 1 of that year, so the
+                // week belongs to the year its Saturday falls in.
+                let year = (start + Duration::days(6)).year();
+                let first_week =
+                    self.week_start(NaiveDate::from_ymd_opt(year, 1, 1).unwrap_or(date));
+                (year, ((start - first_week).num_days() / 7 + 1) as u32)
+            }
+        }
+    }
+}
+
+/// `week_numbering`; anything other than `us` means ISO 8601.
+pub(crate) fn read_week_numbering_in_conn(conn: &Connection) -> Result<WeekNumbering, String> {
+    let numbering = read_setting(conn, "week_numbering")?.trim().to_lowercase();
+    Ok(match numbering.as_str() {
+        "us" => WeekNumbering::Us,
+        _ => WeekNumbering::Iso,
+    })
+}
+
+/// First day of the configured week containing `date`.
+pub(crate) fn week_start_in_conn(conn: &Connection, date: NaiveDate) -> Result<NaiveDate, String> {
+    Ok(read_week_numbering_in_conn(conn)?.week_start(date))
+}
+
+pub(crate) fn get_week_info_in_conn(
+    conn: &Connection,
+    date: NaiveDate,
+) -> Result<WeekInfo, String> {
+    let numbering = read_week_numbering_in_conn(conn)?;
+    let week_start = numbering.week_start(date);
+    let (year, week) = numbering.week_number(date);
+
+    Ok(WeekInfo {
+        numbering: numbering.as_str().to_string(),
+        date: date.format("%Y-%m-%d").to_string(),
+        week_start: week_start.format("%Y-%m-%d").to_string(),
+        week_end: (week_start + Duration::days(6))
+            .format("%Y-%m-%d")
+            .to_string(),
+        year,
+        week,
+    })
+}
+
+/// The configured week around `date` (default today): its number and first and last day.
+#[tauri::command]
+pub fn get_week_info(date: Option<String>, state: State<'_, AppState>) -> Result<WeekInfo, String> {
+    let date = match date
+        .as_deref()
+        .map(str::trim)
+        .filter(|date| !date.is_empty())
+    {
+        Some(date) => NaiveDate::parse_from_str(date, "%Y-%m-%d")
+            .map_err(|_| format!("Invalid date: {}", date))?,
+        None => Local::now().date_naive(),
+    };
+    let conn = state.db.lock().map_err(|e| e.to_string())?;
+    get_week_info_in_conn(&conn, date)
+}
@@ -0,0 +1,193 @@
+use crate::db::migration_note;
+use crate::models::{AppliedMigration, ReleaseNoteSection, ReleaseNotes, WhatsNew};
+use rusqlite::{Connection, OptionalExtension};
+use std::cmp::Ordering;
+use tauri::State;
+
+use super::settings::{read_setting, write_setting};
+use super::AppState;
+
+/// Bundled at build time so the notes always describe the running binary.
+const CHANGELOG: &str = include_str!("../../../CHANGELOG.md");
+const APP_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// `1.2.3`, optionally prefixed with `v`; pre-release and build suffixes are ignored.
+pub(crate) fn parse_version(version: &str) -> Option<Vec<u64>> {
+    let version = version.trim().trim_start_matches('v');
+    let core = version.split(['-', '+']).next()?;
+    core.split('.').map(|part| part.parse().ok()).collect()
+}
+
+pub(crate) fn compare_versions(left: &[u64], right: &[u64]) -> Ordering {
+    (0..left.len().max(right.len()))
+        .map(|index| {
+            let left = left.get(index).copied().unwrap_or(0);
+            let right = right.get(index).copied().unwrap_or(0);
+            left.cmp(&right)
+        })
+        .find(|ordering| ordering.is_ne())
+        .unwrap_or(Ordering::Equal)
+}
+
+/// Releases of a Keep a Changelog file in file order. Headings without a version,
+/// such as `## [Unreleased]`, are skipped.
+pub(crate) fn parse_changelog(markdown: &str) -> Vec<ReleaseNotes> {
+    let mut releases = Vec::new();
+    let mut current: Option<ReleaseNotes> = None;
+
+    for line in markdown.lines() {
+        if let Some(heading) = line.strip_prefix("## ") {
+            releases.extend(current.take());
+            let (version, date) = match heading.split_once(" - ") {
+                Some((version, date)) => (version, Some(date.trim().to_string())),
+                None => (heading, None),
+            };
+            let version = version.trim().trim_start_matches('[').trim_end_matches(']');
+            if parse_version(version).is_some() {
+                current = Some(ReleaseNotes {
+                    version: version.to_string(),
+                    date,
+                    sections: Vec::new(),
+                });
+            }
+            continue;
+        }
+        let Some(release) = current.as_mut() else {
+            continue;
+        };
+
+        if let Some(title) = line.strip_prefix("### ") {
+            release.sections.push(ReleaseNoteSection {
+                title: title.trim().to_string(),
+                items: Vec::new(),
+            });
+        } else if let Some(item) = line.strip_prefix("- ").or_else(|| line.strip_prefix("* ")) {
+            if release.sections.is_empty() {
+                release.sections.push(ReleaseNoteSection {
+                    title: "Changes".to_string(),
+                    items: Vec::new(),
+                });
+            }
+            if let Some(section) = release.sections.last_mut() {
+                section.items.push(item.trim().to_string());
+            }
+        } else if line.starts_with(' ') && !line.trim().is_empty() {
+            // Wrapped list item.
+            if let Some(item) = release
+                .sections
+                .last_mut()
+                .and_then(|section| section.items.last_mut())
+            {
+                item.push(' ');
+                item.push_str(line.trim());
+            }
+        }
+    }
+    releases.extend(current);
+
+    releases
+}
+
+/// Releases after `since_version` up to `current_version` and the migrations later app
+/// versions ran on this journal. Without `since_version` the last version marked as
+/// seen is used, then the version that created the journal. When none is known, only
+/// the current release and the migrations with a recorded app version are returned.
+pub(crate) fn get_whats_new_in_conn(
+    conn: &Connection,
+    changelog: &str,
+    current_version: &str,
+    since_version: Option<&str>,
+) -> Result<WhatsNew, String> {
+    let current = parse_version(current_version)
+        .ok_or_else(|| format!("Invalid version: {}", current_version))?;
+    let since_version = match since_version.map(str::trim).filter(|v| !v.is_empty()) {
+        Some(version) => Some(version.to_string()),
+        None => {
+            let last_seen = read_setting(conn, "last_seen_app_version")?;
+            if last_seen.is_empty() {
+                conn.query_row(
+                    "SELECT app_version FROM schema_migrations WHERE version = 1",
+                    [],
+                    |row| row.get::<_, Option<String>>(0),
+                )
+                .optional()
+                .map_err(|e| e.to_string())?
+                .flatten()
+            } else {
+                Some(last_seen)
+            }
+        }
+    };
+    let since = since_version
+        .as_deref()
+        .map(|version| {
+            parse_version(version).ok_or_else(|| format!("Invalid version: {}", version))
+        })
+        .transpose()?;
+    let is_new = |version: &[u64]| match &since {
+        Some(since) => compare_versions(version, since).is_gt(),
+        None => compare_versions(version, &current).is_eq(),
+    };
+
+    let releases = parse_changelog(changelog)
+        .into_iter()
+        .filter(|release| {
+            parse_version(&release.version).is_some_and(|version| {
+                compare_versions(&version, &current).is_le() && is_new(&version)
+            })
+        })
+        .collect();
+
+    let mut stmt = conn
+        .prepare("SELECT version, applied_at, app_version FROM schema_migrations ORDER BY version")
+        .map_err(|e| e.to_string())?;
+    let migrations_iter = stmt
+        .query_map([], |row| {
+            Ok(AppliedMigration {
+                version: row.get(0)?,
+                note: migration_note(row.get(0)?).to_string(),
+                applied_at: row.get(1)?,
+                app_version: row.get(2)?,
+            })
+        })
+        .map_err(|e| e.to_string())?;
+    let mut migrations = Vec::new();
+    for migration in migrations_iter {
+        let migration = migration.map_err(|e| e.to_string())?;
+        let ran_since = match (&since, migration.app_version.as_deref()) {
+            (Some(_), Some(app_version)) => {
+                parse_version(app_version).is_some_and(|version| is_new(&version))
+            }
+            (None, app_version) => app_version.is_some(),
+            (Some(_), None) => false,
+        };
+        if ran_since {
+            migrations.push(migration);
+        }
+    }
+
+    Ok(WhatsNew {
+        current_version: current_version.to_string(),
+        since_version,
+        releases,
+        migrations,
+    })
+}
+
+/// Release notes and the data migrations run since `since_version`; see
+/// `get_whats_new_in_conn` for the default.
+#[tauri::command]
+pub fn get_whats_new(
+    since_version: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<WhatsNew, String> {
+    let conn = state.db.lock().map_err(|e| e.to_string())?;
+    get_whats_new_in_conn(&conn, CHANGELOG, APP_VERSION, since_version.as_deref())
+}
+
+/// Records the running version as seen, so "What's new" stays quiet until the next update.
+#[tauri::command]
+pub fn mark_whats_new_seen(state: State<'_, AppState>) -> Result<(), String> {
+    let conn = state.db.lock().map_err(|e| e.to_string())?;
+    write_setting(&conn, "last_seen_app_version", APP_VERSION)
+}
@@ -0,0 +1,101 @@
+use tauri::{AppHandle, Manager, WebviewUrl, WebviewWindowBuilder};
+
+use super::pages::get_page_in_conn;
+use super::AppState;
+
+/// Labels of windows opened with `open_window`; the frontend reads the view back
+/// out of the label, and closing one of them never hides it to the tray.
+pub(crate) const DETACHED_WINDOW_PREFIX: &str = "view-";
+
+/// A view that can be opened in its own window: `journal`, `tasks` or `page:<id>`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum DetachedView {
+    Journal,
+    Tasks,
+    Page(i64),
+}
+
+impl DetachedView {
+    pub(crate) fn parse(view: &str) -> Result<Self, String> {
+        match view.trim() {
+            "journal" => Ok(Self::Journal),
+            "tasks" => Ok(Self::Tasks),
+            other => other
+                .strip_prefix("page:")
+                .and_then(|id| id.trim().parse::<i64>().ok())
+                .filter(|id| *id > 0)
+                .map(Self::Page)
+                .ok_or_else(|| {
+                    format!(
+                        "Unknown window view '{}'; expected journal, tasks or page:<id>",
+                        view
+                    )
+                }),
+        }
+    }
+
+    /// One window per view, so opening the same view again focuses it instead.
+    pub(crate) fn label(self) -> String {
+        match self {
+            Self::Journal => format!("{}journal", DETACHED_WINDOW_PREFIX),
+            Self::Tasks => format!("{}tasks", DETACHED_WINDOW_PREFIX),
+            Self::Page(id) => format!("{}page-{}", DETACHED_WINDOW_PREFIX, id),
+        }
+    }
+}
+
+/// Opens (or focuses) a separate window for `view` and returns its label. Every
+/// window shares the same backend state; the frontends keep their caches in sync
+/// by broadcasting `windows-data-changed` after each successful mutation.
+#[tauri::command]
+pub async fn open_window(view: String, app: AppHandle) -> Result<String, String> {
+    let view = DetachedView::parse(&view)?;
+    let label = view.label();
+
+    if let Some(window) = app.get_webview_window(&label) {
+        window.unminimize().map_err(|e| e.to_string())?;
+        window.show().map_err(|e| e.to_string())?;
+        window.set_focus().map_err(|e| e.to_string())?;
+        return Ok(label);
+    }
+
+    let title = match view {
+        DetachedView::Journal => "Journal".to_string(),
+        DetachedView::Tasks => "Tasks".to_string(),
+        DetachedView::Page(id) => {
+            let state = app.state::<AppState>();
+            let conn = state.db.lock().map_err(|e| e.to_string())?;
+            let page = get_page_in_conn(&conn, id)?.ok_or("Page not found")?;
+            if page.title.trim().is_empty() {
+                "Untitled".to_string()
+            } else {
+                page.title
+            }
+        }
+    };
+
+    let (width, height) = match view {
+        DetachedView::Tasks => (1100.0, 760.0),
+        DetachedView::Journal | DetachedView::Page(_) => (760.0, 820.0),
+    };
+    WebviewWindowBuilder::new(&app, &label, WebviewUrl::default())
+        .title(format!("{} — Dev Journal", title))
+        .inner_size(width, height)
+        .build()
+        .map_err(|e| e.to_string())?;
+
+    Ok(label)
+}
+
+/// Closes a window opened with `open_window`; the main window is left alone.
+#[tauri::command]
+pub fn close_window(label: String, app: AppHandle) -> Result<(), String> {
+    if !label.starts_with(DETACHED_WINDOW_PREFIX) {
+        return Err(format!("Window '{}' cannot be closed this way", label));
+    }
+    if let Some(window) = app.get_webview_window(&label) {
+        window.close().map_err(|e| e.to_string())?;
+    }
+
+    Ok(())
+}
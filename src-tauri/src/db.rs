@@ -2,8 +2,99 @@ use rusqlite::{params, Connection, Result};
 use std::fs;
 use std::path::PathBuf;
 
+/// Latest migration version; bump it together with every new `apply_migration` call.
+pub const SCHEMA_VERSION: i64 = 65;
+
+/// One line per migration, oldest first, shown in "What's new" after an update. Keep
+/// them short and written for users rather than as schema notes.
+const MIGRATION_NOTES: [&str; SCHEMA_VERSION as usize] = [
+    "Journal entries, pages and tasks",
+    "Task priorities and due dates",
+    "Goals",
+    "Habits and daily check-ins",
+    "Task timers that survive restarts",
+    "Projects, linked to entries, tasks and goals",
+    "Project branches",
+    "Subtasks",
+    "Tasks linked to goals",
+    "Meetings",
+    "Meeting reminders, recurrence, notes, participants and action items",
+    "Recurring tasks and goal milestones",
+    "Project and goal links checked by the database; links to missing items were cleared",
+    "Subtask, milestone and meeting tables rebuilt to point at the current parent tables",
+    "GitHub, GitLab and Jira links on tasks",
+    "App settings",
+    "Pomodoro history",
+    "Entry tags",
+    "Countdown timers",
+    "Custom entry sections",
+    "Journaling prompts, with the built-in set added",
+    "Daily mood",
+    "Full-text search over entries, built from the existing journal",
+    "Habit streak freezes",
+    "Achievements",
+    "Goal reflections",
+    "Review queue for automatically created tasks",
+    "Entry attachments",
+    "API tokens",
+    "Git commits saved with entries",
+    "API access log",
+    "Links captured from the browser",
+    "Pinned entries",
+    "Entry trash",
+    "Journal encryption",
+    "Command output attachments and search over attachment text",
+    "Incident log",
+    "Autosaved entry drafts",
+    "On-call schedule",
+    "Task timer history",
+    "Standup blockers and notes; the entry search index was rebuilt",
+    "Timestamped notes through the day",
+    "Task tags and escalation rules",
+    "Nested pages",
+    "Schedules for background jobs",
+    "Page tags",
+    "Links between pages",
+    "Page version history",
+    "Page trash",
+    "Full-text search over pages, built from the existing pages",
+    "Pinned and favorite pages",
+    "Images embedded in pages",
+    "Archived pages",
+    "Feature flags",
+    "Manual page order",
+    "Recently viewed pages",
+    "Page locks",
+    "Data integrity checks",
+    "Page icons and covers",
+    "Page properties",
+    "Slow command log",
+    "Query path indexes",
+    "Slack status opt-outs",
+    "Task due-date reminders and snoozes",
+    "Manual task order on the board",
+];
+
+/// User-facing description of migration `version`.
+pub fn migration_note(version: i64) -> &'static str {
+    usize::try_from(version - 1)
+        .ok()
+        .and_then(|index| MIGRATION_NOTES.get(index))
+        .copied()
+        .unwrap_or("Database update")
+}
+
 /// Initializes SQLite connection, enables DB PRAGMAs, and applies migrations.
 pub fn init(app_data_dir: PathBuf) -> Result<Connection> {
+    let conn = open(app_data_dir)?;
+    migrate(&conn, &|_| {})?;
+
+    Ok(conn)
+}
+
+/// Opens the database without migrating it, so startup can decide how to run
+/// pending migrations (see `startup.rs`).
+pub fn open(app_data_dir: PathBuf) -> Result<Connection> {
     if !app_data_dir.exists() {
         fs::create_dir_all(&app_data_dir).expect("Failed to create app data directory");
     }
@@ -13,12 +104,30 @@ pub fn init(app_data_dir: PathBuf) -> Result<Connection> {
 
     configure_connection(&conn)?;
 
-    run_migrations(&conn)?;
-    enable_foreign_keys(&conn)?;
-
     Ok(conn)
 }
 
+/// Migration versions not applied yet, oldest first.
+pub fn pending_migrations(conn: &Connection) -> Result<Vec<i64>> {
+    create_migrations_table(conn)?;
+    let mut stmt = conn.prepare("SELECT version FROM schema_migrations")?;
+    let applied = stmt
+        .query_map([], |row| row.get::<_, i64>(0))?
+        .collect::<Result<std::collections::HashSet<i64>>>()?;
+
+    Ok((1..=SCHEMA_VERSION)
+        .filter(|version| !applied.contains(version))
+        .collect())
+}
+
+/// Applies pending migrations, calling `on_progress` with each version before it runs.
+pub fn migrate(conn: &Connection, on_progress: &dyn Fn(i64)) -> Result<()> {
+    run_migrations(conn, on_progress)?;
+    enable_foreign_keys(conn)?;
+
+    Ok(())
+}
+
 fn configure_connection(conn: &Connection) -> Result<()> {
     conn.execute_batch(
         "PRAGMA journal_mode = WAL;
@@ -33,7 +142,7 @@ fn enable_foreign_keys(conn: &Connection) -> Result<()> {
     Ok(())
 }
 
-fn run_migrations(conn: &Connection) -> Result<()> {
+fn create_migrations_table(conn: &Connection) -> Result<()> {
     conn.execute(
         "CREATE TABLE IF NOT EXISTS schema_migrations (
             version INTEGER PRIMARY KEY,
@@ -41,9 +150,17 @@ fn run_migrations(conn: &Connection) -> Result<()> {
         )",
         [],
     )?;
+    // Migrations applied before this column existed have no app version.
+    ensure_column(conn, "schema_migrations", "app_version", "TEXT")?;
+
+    Ok(())
+}
+
+fn run_migrations(conn: &Connection, on_progress: &dyn Fn(i64)) -> Result<()> {
+    create_migrations_table(conn)?;
 
     // v1: base journal/page/task entities.
-    apply_migration(conn, 1, |conn| {
+    apply_migration(conn, on_progress, 1, |conn| {
         conn.execute(
             "CREATE TABLE IF NOT EXISTS entries (
                 id INTEGER PRIMARY KEY,
@@ -82,7 +199,7 @@ fn run_migrations(conn: &Connection) -> Result<()> {
     })?;
 
     // v2: task priority + due date support.
-    apply_migration(conn, 2, |conn| {
+    apply_migration(conn, on_progress, 2, |conn| {
         ensure_column(conn, "tasks", "priority", "TEXT NOT NULL DEFAULT 'medium'")?;
         ensure_column(conn, "tasks", "due_date", "TEXT")?;
         ensure_column(conn, "tasks", "completed_at", "TEXT")?;
@@ -96,7 +213,7 @@ fn run_migrations(conn: &Connection) -> Result<()> {
     })?;
 
     // v3: goals domain.
-    apply_migration(conn, 3, |conn| {
+    apply_migration(conn, on_progress, 3, |conn| {
         conn.execute(
             "CREATE TABLE IF NOT EXISTS goals (
                 id INTEGER PRIMARY KEY,
@@ -120,7 +237,7 @@ fn run_migrations(conn: &Connection) -> Result<()> {
     })?;
 
     // v4: habits and daily completion logs.
-    apply_migration(conn, 4, |conn| {
+    apply_migration(conn, on_progress, 4, |conn| {
         conn.execute(
             "CREATE TABLE IF NOT EXISTS habits (
                 id INTEGER PRIMARY KEY,
@@ -155,7 +272,7 @@ fn run_migrations(conn: &Connection) -> Result<()> {
     })?;
 
     // v5: persistent task timer fields.
-    apply_migration(conn, 5, |conn| {
+    apply_migration(conn, on_progress, 5, |conn| {
         ensure_column(
             conn,
             "tasks",
@@ -179,7 +296,7 @@ fn run_migrations(conn: &Connection) -> Result<()> {
     })?;
 
     // v6: project hub domain + project links for entries/tasks/goals.
-    apply_migration(conn, 6, |conn| {
+    apply_migration(conn, on_progress, 6, |conn| {
         conn.execute(
             "CREATE TABLE IF NOT EXISTS projects (
                 id INTEGER PRIMARY KEY,
@@ -218,7 +335,7 @@ fn run_migrations(conn: &Connection) -> Result<()> {
     })?;
 
     // v7: branches per project workspace.
-    apply_migration(conn, 7, |conn| {
+    apply_migration(conn, on_progress, 7, |conn| {
         conn.execute(
             "CREATE TABLE IF NOT EXISTS project_branches (
                 id INTEGER PRIMARY KEY,
@@ -243,7 +360,7 @@ fn run_migrations(conn: &Connection) -> Result<()> {
     })?;
 
     // v8: subtasks for task cards.
-    apply_migration(conn, 8, |conn| {
+    apply_migration(conn, on_progress, 8, |conn| {
         conn.execute(
             "CREATE TABLE IF NOT EXISTS task_subtasks (
                 id INTEGER PRIMARY KEY,
@@ -267,7 +384,7 @@ fn run_migrations(conn: &Connection) -> Result<()> {
     })?;
 
     // v9: link tasks to goals.
-    apply_migration(conn, 9, |conn| {
+    apply_migration(conn, on_progress, 9, |conn| {
         ensure_column(conn, "tasks", "goal_id", "INTEGER")?;
         conn.execute(
             "CREATE INDEX IF NOT EXISTS idx_tasks_goal_id ON tasks(goal_id)",
@@ -277,7 +394,7 @@ fn run_migrations(conn: &Connection) -> Result<()> {
     })?;
 
     // v10: meetings and calendar planning.
-    apply_migration(conn, 10, |conn| {
+    apply_migration(conn, on_progress, 10, |conn| {
         conn.execute(
             "CREATE TABLE IF NOT EXISTS meetings (
                 id INTEGER PRIMARY KEY,
@@ -309,7 +426,7 @@ fn run_migrations(conn: &Connection) -> Result<()> {
     })?;
 
     // v11: enrich meetings with reminders, recurrence, notes, participants, and action items.
-    apply_migration(conn, 11, |conn| {
+    apply_migration(conn, on_progress, 11, |conn| {
         ensure_column(
             conn,
             "meetings",
@@ -347,13 +464,8 @@ fn run_migrations(conn: &Connection) -> Result<()> {
     })?;
 
     // v12: recurring tasks + goal milestones.
-    apply_migration(conn, 12, |conn| {
-        ensure_column(
-            conn,
-            "tasks",
-            "recurrence",
-            "TEXT NOT NULL DEFAULT 'none'",
-        )?;
+    apply_migration(conn, on_progress, 12, |conn| {
+        ensure_column(conn, "tasks", "recurrence", "TEXT NOT NULL DEFAULT 'none'")?;
         ensure_column(conn, "tasks", "recurrence_until", "TEXT")?;
         ensure_column(conn, "tasks", "parent_task_id", "INTEGER")?;
 
@@ -393,7 +505,7 @@ fn run_migrations(conn: &Connection) -> Result<()> {
     })?;
 
     // v13: enforce referential integrity on project/goal/task links and clean invalid references.
-    apply_migration(conn, 13, |conn| {
+    apply_migration(conn, on_progress, 13, |conn| {
         conn.execute_batch("PRAGMA foreign_keys = OFF;")?;
 
         conn.execute(
@@ -615,7 +727,7 @@ fn run_migrations(conn: &Connection) -> Result<()> {
     })?;
 
     // v14: rebuild child tables whose foreign keys still targeted pre-v13 renamed parents.
-    apply_migration(conn, 14, |conn| {
+    apply_migration(conn, on_progress, 14, |conn| {
         conn.execute_batch("PRAGMA foreign_keys = OFF;")?;
 
         conn.execute(
@@ -700,10 +812,1080 @@ fn run_migrations(conn: &Connection) -> Result<()> {
         Ok(())
     })?;
 
+    // v15: issue tracker integrations and task links to external issues/PRs.
+    apply_migration(conn, on_progress, 15, |conn| {
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS integration_settings (
+                provider TEXT PRIMARY KEY,
+                base_url TEXT,
+                username TEXT,
+                token TEXT NOT NULL DEFAULT '',
+                updated_at TEXT NOT NULL
+            )",
+            [],
+        )?;
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS task_links (
+                id INTEGER PRIMARY KEY,
+                task_id INTEGER NOT NULL,
+                url TEXT NOT NULL,
+                provider TEXT NOT NULL,
+                kind TEXT NOT NULL,
+                external_ref TEXT NOT NULL,
+                title TEXT NOT NULL DEFAULT '',
+                labels_json TEXT NOT NULL DEFAULT '[]',
+                state TEXT,
+                created_at TEXT NOT NULL,
+                updated_at TEXT NOT NULL,
+                FOREIGN KEY(task_id) REFERENCES tasks(id) ON DELETE CASCADE
+            )",
+            [],
+        )?;
+        conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_task_links_task_id ON task_links(task_id)",
+            [],
+        )?;
+        conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_task_links_url ON task_links(url)",
+            [],
+        )?;
+
+        Ok(())
+    })?;
+
+    // v16: key/value app settings + link refresh bookkeeping.
+    apply_migration(conn, on_progress, 16, |conn| {
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS app_settings (
+                key TEXT PRIMARY KEY,
+                value TEXT NOT NULL,
+                updated_at TEXT NOT NULL
+            )",
+            [],
+        )?;
+        ensure_column(conn, "task_links", "last_checked_at", "TEXT")?;
+
+        Ok(())
+    })?;
+
+    // v17: persisted pomodoro sessions for cycles, daily goals, and heatmaps.
+    apply_migration(conn, on_progress, 17, |conn| {
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS pomodoro_sessions (
+                id INTEGER PRIMARY KEY,
+                kind TEXT NOT NULL,
+                task_id INTEGER,
+                duration_minutes INTEGER NOT NULL,
+                date TEXT NOT NULL,
+                completed_at TEXT NOT NULL,
+                FOREIGN KEY(task_id) REFERENCES tasks(id) ON DELETE SET NULL
+            )",
+            [],
+        )?;
+        conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_pomodoro_sessions_date_kind ON pomodoro_sessions(date, kind)",
+            [],
+        )?;
+
+        Ok(())
+    })?;
+
+    // v18: free-form tags on journal entries.
+    apply_migration(conn, on_progress, 18, |conn| {
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS entry_tags (
+                id INTEGER PRIMARY KEY,
+                entry_id INTEGER NOT NULL,
+                tag TEXT NOT NULL COLLATE NOCASE,
+                created_at TEXT NOT NULL,
+                UNIQUE(entry_id, tag),
+                FOREIGN KEY(entry_id) REFERENCES entries(id) ON DELETE CASCADE
+            )",
+            [],
+        )?;
+        conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_entry_tags_tag ON entry_tags(tag)",
+            [],
+        )?;
+
+        Ok(())
+    })?;
+
+    // v19: standalone countdown timers for timeboxing.
+    apply_migration(conn, on_progress, 19, |conn| {
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS countdowns (
+                id INTEGER PRIMARY KEY,
+                label TEXT NOT NULL,
+                duration_minutes INTEGER NOT NULL,
+                started_at TEXT NOT NULL,
+                ends_at TEXT NOT NULL,
+                completed_at TEXT,
+                cancelled_at TEXT
+            )",
+            [],
+        )?;
+        conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_countdowns_ends_at ON countdowns(ends_at)",
+            [],
+        )?;
+
+        Ok(())
+    })?;
+
+    // v20: user-defined entry sections beyond yesterday/today.
+    apply_migration(conn, on_progress, 20, |conn| {
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS entry_section_templates (
+                id INTEGER PRIMARY KEY,
+                section_key TEXT NOT NULL UNIQUE,
+                label TEXT NOT NULL,
+                sort_order INTEGER NOT NULL DEFAULT 0,
+                created_at TEXT NOT NULL
+            )",
+            [],
+        )?;
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS entry_sections (
+                id INTEGER PRIMARY KEY,
+                entry_id INTEGER NOT NULL,
+                section_key TEXT NOT NULL,
+                content TEXT NOT NULL,
+                sort_order INTEGER NOT NULL DEFAULT 0,
+                updated_at TEXT NOT NULL,
+                UNIQUE(entry_id, section_key),
+                FOREIGN KEY(entry_id) REFERENCES entries(id) ON DELETE CASCADE
+            )",
+            [],
+        )?;
+
+        Ok(())
+    })?;
+
+    // v21: journaling prompt library, seeded with built-in prompts.
+    apply_migration(conn, on_progress, 21, |conn| {
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS journal_prompts (
+                id INTEGER PRIMARY KEY,
+                text TEXT NOT NULL UNIQUE,
+                is_builtin INTEGER NOT NULL DEFAULT 0,
+                enabled INTEGER NOT NULL DEFAULT 1,
+                created_at TEXT NOT NULL
+            )",
+            [],
+        )?;
+
+        let now = chrono::Utc::now().to_rfc3339();
+        for prompt in [
+            "What would make today a win?",
+            "What slowed you down yesterday, and how can you avoid it today?",
+            "What did you learn recently that surprised you?",
+            "Which task have you been putting off, and why?",
+            "Who could you help or ask for help today?",
+            "What is one thing you can simplify this week?",
+            "What are you proud of from the last few days?",
+            "Where did you lose focus, and what pulled you away?",
+            "What decision are you waiting on, and who owns it?",
+            "If you only finish one thing today, what should it be?",
+        ] {
+            conn.execute(
+                "INSERT OR IGNORE INTO journal_prompts (text, is_builtin, enabled, created_at)
+                 VALUES (?1, 1, 1, ?2)",
+                params![prompt, now],
+            )?;
+        }
+
+        Ok(())
+    })?;
+
+    // v22: daily mood ratings for correlation insights.
+    apply_migration(conn, on_progress, 22, |conn| {
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS daily_moods (
+                date TEXT PRIMARY KEY,
+                mood INTEGER NOT NULL CHECK(mood BETWEEN 1 AND 5),
+                updated_at TEXT NOT NULL
+            )",
+            [],
+        )?;
+
+        Ok(())
+    })?;
+
+    // v23: FTS5 index over entry text, kept in sync by triggers.
+    apply_migration(conn, on_progress, 23, |conn| {
+        conn.execute_batch(
+            "CREATE VIRTUAL TABLE IF NOT EXISTS entries_fts USING fts5(
+                yesterday,
+                today,
+                content='entries',
+                content_rowid='id',
+                tokenize='unicode61 remove_diacritics 2'
+            );
+
+            CREATE TRIGGER IF NOT EXISTS entries_fts_after_insert AFTER INSERT ON entries BEGIN
+                INSERT INTO entries_fts(rowid, yesterday, today)
+                VALUES (new.id, new.yesterday, new.today);
+            END;
+
+            CREATE TRIGGER IF NOT EXISTS entries_fts_after_delete AFTER DELETE ON entries BEGIN
+                INSERT INTO entries_fts(entries_fts, rowid, yesterday, today)
+                VALUES ('delete', old.id, old.yesterday, old.today);
+            END;
+
+            CREATE TRIGGER IF NOT EXISTS entries_fts_after_update AFTER UPDATE ON entries BEGIN
+                INSERT INTO entries_fts(entries_fts, rowid, yesterday, today)
+                VALUES ('delete', old.id, old.yesterday, old.today);
+                INSERT INTO entries_fts(rowid, yesterday, today)
+                VALUES (new.id, new.yesterday, new.today);
+            END;
+
+            INSERT INTO entries_fts(entries_fts) VALUES ('rebuild');",
+        )?;
+
+        Ok(())
+    })?;
+
+    // v24: streak freezes that cover a missed habit day.
+    apply_migration(conn, on_progress, 24, |conn| {
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS streak_freezes (
+                id INTEGER PRIMARY KEY,
+                habit_id INTEGER NOT NULL,
+                date TEXT NOT NULL,
+                created_at TEXT NOT NULL,
+                UNIQUE(habit_id, date),
+                FOREIGN KEY(habit_id) REFERENCES habits(id) ON DELETE CASCADE
+            )",
+            [],
+        )?;
+
+        Ok(())
+    })?;
+
+    // v25: unlocked achievements; definitions live in commands/achievements.rs.
+    apply_migration(conn, on_progress, 25, |conn| {
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS achievements (
+                key TEXT PRIMARY KEY,
+                unlocked_at TEXT NOT NULL
+            )",
+            [],
+        )?;
+
+        Ok(())
+    })?;
+
+    // v26: reflections captured when goals are completed.
+    apply_migration(conn, on_progress, 26, |conn| {
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS goal_reflections (
+                id INTEGER PRIMARY KEY,
+                goal_id INTEGER,
+                goal_title TEXT NOT NULL,
+                what_worked TEXT NOT NULL DEFAULT '',
+                what_didnt TEXT NOT NULL DEFAULT '',
+                created_at TEXT NOT NULL,
+                FOREIGN KEY(goal_id) REFERENCES goals(id) ON DELETE SET NULL
+            )",
+            [],
+        )?;
+        conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_goal_reflections_goal ON goal_reflections(goal_id)",
+            [],
+        )?;
+
+        Ok(())
+    })?;
+
+    // v27: review queue holding auto-created tasks until they are accepted.
+    apply_migration(conn, on_progress, 27, |conn| {
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS review_queue (
+                task_id INTEGER PRIMARY KEY,
+                source TEXT NOT NULL,
+                created_at TEXT NOT NULL,
+                FOREIGN KEY(task_id) REFERENCES tasks(id) ON DELETE CASCADE
+            )",
+            [],
+        )?;
+
+        Ok(())
+    })?;
+
+    // v28: entry attachments; files live under app_data_dir/attachments.
+    apply_migration(conn, on_progress, 28, |conn| {
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS attachments (
+                id INTEGER PRIMARY KEY,
+                entry_id INTEGER NOT NULL,
+                file_name TEXT NOT NULL,
+                mime_type TEXT NOT NULL,
+                size_bytes INTEGER NOT NULL,
+                stored_path TEXT NOT NULL UNIQUE,
+                created_at TEXT NOT NULL,
+                FOREIGN KEY(entry_id) REFERENCES entries(id) ON DELETE CASCADE
+            )",
+            [],
+        )?;
+        conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_attachments_entry ON attachments(entry_id)",
+            [],
+        )?;
+
+        Ok(())
+    })?;
+
+    // v29: scoped tokens for external interfaces; only a SHA-256 hash of each token is stored.
+    apply_migration(conn, on_progress, 29, |conn| {
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS api_tokens (
+                id INTEGER PRIMARY KEY,
+                name TEXT NOT NULL,
+                token_hash TEXT NOT NULL UNIQUE,
+                token_prefix TEXT NOT NULL,
+                scopes_json TEXT NOT NULL DEFAULT '[]',
+                created_at TEXT NOT NULL,
+                last_used_at TEXT
+            )",
+            [],
+        )?;
+
+        Ok(())
+    })?;
+
+    // v30: git commits snapshotted with an entry.
+    apply_migration(conn, on_progress, 30, |conn| {
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS entry_commits (
+                id INTEGER PRIMARY KEY,
+                entry_id INTEGER NOT NULL,
+                hash TEXT NOT NULL,
+                message TEXT NOT NULL DEFAULT '',
+                position INTEGER NOT NULL DEFAULT 0,
+                created_at TEXT NOT NULL,
+                UNIQUE(entry_id, hash),
+                FOREIGN KEY(entry_id) REFERENCES entries(id) ON DELETE CASCADE
+            )",
+            [],
+        )?;
+
+        Ok(())
+    })?;
+
+    // v31: audit log of external API calls, also used for per-token rate limiting.
+    apply_migration(conn, on_progress, 31, |conn| {
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS external_access_log (
+                id INTEGER PRIMARY KEY,
+                token_id INTEGER,
+                caller TEXT NOT NULL,
+                endpoint TEXT NOT NULL,
+                outcome TEXT NOT NULL,
+                detail TEXT NOT NULL DEFAULT '',
+                created_at TEXT NOT NULL,
+                FOREIGN KEY(token_id) REFERENCES api_tokens(id) ON DELETE SET NULL
+            )",
+            [],
+        )?;
+        conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_external_access_log_token_created
+             ON external_access_log(token_id, created_at)",
+            [],
+        )?;
+
+        Ok(())
+    })?;
+
+    // v32: links clipped by the browser extension companion.
+    apply_migration(conn, on_progress, 32, |conn| {
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS bookmarks (
+                id INTEGER PRIMARY KEY,
+                url TEXT NOT NULL,
+                title TEXT NOT NULL,
+                selection TEXT NOT NULL DEFAULT '',
+                created_at TEXT NOT NULL
+            )",
+            [],
+        )?;
+
+        Ok(())
+    })?;
+
+    // v33: pinned entries.
+    apply_migration(conn, on_progress, 33, |conn| {
+        ensure_column(conn, "entries", "pinned", "INTEGER NOT NULL DEFAULT 0")?;
+        conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_entries_pinned ON entries(date) WHERE pinned = 1",
+            [],
+        )?;
+
+        Ok(())
+    })?;
+
+    // v34: entry trash (soft delete).
+    apply_migration(conn, on_progress, 34, |conn| {
+        ensure_column(conn, "entries", "deleted_at", "TEXT")?;
+        conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_entries_deleted_at
+             ON entries(deleted_at) WHERE deleted_at IS NOT NULL",
+            [],
+        )?;
+
+        Ok(())
+    })?;
+
+    // v35: at-rest encryption metadata (single row: argon2 salt + passphrase verifier).
+    apply_migration(conn, on_progress, 35, |conn| {
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS journal_encryption (
+                id INTEGER PRIMARY KEY CHECK (id = 1),
+                kdf_salt TEXT NOT NULL,
+                verifier TEXT NOT NULL,
+                created_at TEXT NOT NULL
+            )",
+            [],
+        )?;
+
+        Ok(())
+    })?;
+
+    // v36: attachment kinds (captured command output) and an FTS index over extracted text.
+    apply_migration(conn, on_progress, 36, |conn| {
+        ensure_column(conn, "attachments", "kind", "TEXT NOT NULL DEFAULT 'file'")?;
+        ensure_column(conn, "attachments", "command", "TEXT")?;
+        ensure_column(
+            conn,
+            "attachments",
+            "extracted_text",
+            "TEXT NOT NULL DEFAULT ''",
+        )?;
+        conn.execute_batch(
+            "CREATE VIRTUAL TABLE IF NOT EXISTS attachments_fts USING fts5(
+                command,
+                extracted_text,
+                content='attachments',
+                content_rowid='id',
+                tokenize='unicode61 remove_diacritics 2'
+            );
+
+            CREATE TRIGGER IF NOT EXISTS attachments_fts_after_insert AFTER INSERT ON attachments BEGIN
+                INSERT INTO attachments_fts(rowid, command, extracted_text)
+                VALUES (new.id, COALESCE(new.command, ''), new.extracted_text);
+            END;
+
+            CREATE TRIGGER IF NOT EXISTS attachments_fts_after_delete AFTER DELETE ON attachments BEGIN
+                INSERT INTO attachments_fts(attachments_fts, rowid, command, extracted_text)
+                VALUES ('delete', old.id, COALESCE(old.command, ''), old.extracted_text);
+            END;
+
+            CREATE TRIGGER IF NOT EXISTS attachments_fts_after_update AFTER UPDATE ON attachments BEGIN
+                INSERT INTO attachments_fts(attachments_fts, rowid, command, extracted_text)
+                VALUES ('delete', old.id, COALESCE(old.command, ''), old.extracted_text);
+                INSERT INTO attachments_fts(rowid, command, extracted_text)
+                VALUES (new.id, COALESCE(new.command, ''), new.extracted_text);
+            END;
+
+            INSERT INTO attachments_fts(attachments_fts) VALUES ('rebuild');",
+        )?;
+
+        Ok(())
+    })?;
+
+    // v37: incident log with linked tasks and pages.
+    apply_migration(conn, on_progress, 37, |conn| {
+        conn.execute_batch(
+            "CREATE TABLE IF NOT EXISTS incidents (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                started_at TEXT NOT NULL,
+                ended_at TEXT,
+                severity TEXT NOT NULL DEFAULT 'sev3',
+                summary TEXT NOT NULL,
+                resolution TEXT NOT NULL DEFAULT '',
+                created_at TEXT NOT NULL,
+                updated_at TEXT NOT NULL
+            );
+            CREATE INDEX IF NOT EXISTS idx_incidents_started_at ON incidents(started_at);
+
+            CREATE TABLE IF NOT EXISTS incident_tasks (
+                incident_id INTEGER NOT NULL,
+                task_id INTEGER NOT NULL,
+                PRIMARY KEY(incident_id, task_id),
+                FOREIGN KEY(incident_id) REFERENCES incidents(id) ON DELETE CASCADE,
+                FOREIGN KEY(task_id) REFERENCES tasks(id) ON DELETE CASCADE
+            );
+
+            CREATE TABLE IF NOT EXISTS incident_pages (
+                incident_id INTEGER NOT NULL,
+                page_id INTEGER NOT NULL,
+                PRIMARY KEY(incident_id, page_id),
+                FOREIGN KEY(incident_id) REFERENCES incidents(id) ON DELETE CASCADE,
+                FOREIGN KEY(page_id) REFERENCES pages(id) ON DELETE CASCADE
+            );",
+        )?;
+
+        Ok(())
+    })?;
+
+    // v38: autosaved entry drafts, kept apart from saved entries.
+    apply_migration(conn, on_progress, 38, |conn| {
+        conn.execute_batch(
+            "CREATE TABLE IF NOT EXISTS entry_drafts (
+                date TEXT PRIMARY KEY,
+                yesterday TEXT NOT NULL DEFAULT '',
+                today TEXT NOT NULL DEFAULT '',
+                updated_at TEXT NOT NULL
+            );",
+        )?;
+
+        Ok(())
+    })?;
+
+    // v39: on-call schedule, entered by hand or imported from ICS.
+    apply_migration(conn, on_progress, 39, |conn| {
+        conn.execute_batch(
+            "CREATE TABLE IF NOT EXISTS oncall_shifts (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                start_date TEXT NOT NULL,
+                end_date TEXT NOT NULL,
+                note TEXT NOT NULL DEFAULT '',
+                source TEXT NOT NULL DEFAULT 'manual',
+                created_at TEXT NOT NULL
+            );
+            CREATE INDEX IF NOT EXISTS idx_oncall_shifts_range ON oncall_shifts(start_date, end_date);",
+        )?;
+
+        Ok(())
+    })?;
+
+    // v40: finished task timer runs, the source for context-switch insights.
+    apply_migration(conn, on_progress, 40, |conn| {
+        conn.execute_batch(
+            "CREATE TABLE IF NOT EXISTS task_time_entries (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                task_id INTEGER NOT NULL,
+                started_at TEXT NOT NULL,
+                ended_at TEXT NOT NULL,
+                FOREIGN KEY(task_id) REFERENCES tasks(id) ON DELETE CASCADE
+            );
+            CREATE INDEX IF NOT EXISTS idx_task_time_entries_started_at ON task_time_entries(started_at);",
+        )?;
+
+        Ok(())
+    })?;
+
+    // v41: standup blockers and notes, on entries and their drafts; the entry
+    // search index is rebuilt to cover them.
+    apply_migration(conn, on_progress, 41, |conn| {
+        conn.execute_batch(
+            "ALTER TABLE entries ADD COLUMN blockers TEXT NOT NULL DEFAULT '';
+            ALTER TABLE entries ADD COLUMN notes TEXT NOT NULL DEFAULT '';
+            ALTER TABLE entry_drafts ADD COLUMN blockers TEXT NOT NULL DEFAULT '';
+            ALTER TABLE entry_drafts ADD COLUMN notes TEXT NOT NULL DEFAULT '';
+
+            DROP TRIGGER IF EXISTS entries_fts_after_insert;
+            DROP TRIGGER IF EXISTS entries_fts_after_delete;
+            DROP TRIGGER IF EXISTS entries_fts_after_update;
+            DROP TABLE IF EXISTS entries_fts;
+
+            CREATE VIRTUAL TABLE entries_fts USING fts5(
+                yesterday,
+                today,
+                blockers,
+                notes,
+                content='entries',
+                content_rowid='id',
+                tokenize='unicode61 remove_diacritics 2'
+            );
+
+            CREATE TRIGGER entries_fts_after_insert AFTER INSERT ON entries BEGIN
+                INSERT INTO entries_fts(rowid, yesterday, today, blockers, notes)
+                VALUES (new.id, new.yesterday, new.today, new.blockers, new.notes);
+            END;
+
+            CREATE TRIGGER entries_fts_after_delete AFTER DELETE ON entries BEGIN
+                INSERT INTO entries_fts(entries_fts, rowid, yesterday, today, blockers, notes)
+                VALUES ('delete', old.id, old.yesterday, old.today, old.blockers, old.notes);
+            END;
+
+            CREATE TRIGGER entries_fts_after_update AFTER UPDATE ON entries BEGIN
+                INSERT INTO entries_fts(entries_fts, rowid, yesterday, today, blockers, notes)
+                VALUES ('delete', old.id, old.yesterday, old.today, old.blockers, old.notes);
+                INSERT INTO entries_fts(rowid, yesterday, today, blockers, notes)
+                VALUES (new.id, new.yesterday, new.today, new.blockers, new.notes);
+            END;
+
+            INSERT INTO entries_fts(entries_fts) VALUES ('rebuild');",
+        )?;
+
+        Ok(())
+    })?;
+
+    // v42: timestamped notes logged through the day, keyed by date so they can
+    // start before the day's entry exists.
+    apply_migration(conn, on_progress, 42, |conn| {
+        conn.execute_batch(
+            "CREATE TABLE IF NOT EXISTS entry_notes (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                date TEXT NOT NULL,
+                text TEXT NOT NULL,
+                created_at TEXT NOT NULL
+            );
+            CREATE INDEX IF NOT EXISTS idx_entry_notes_date ON entry_notes(date, created_at);",
+        )?;
+
+        Ok(())
+    })?;
+
+    // v43: task tags, plus escalation rules run by the scheduler and their log.
+    apply_migration(conn, on_progress, 43, |conn| {
+        conn.execute_batch(
+            "CREATE TABLE IF NOT EXISTS task_tags (
+                id INTEGER PRIMARY KEY,
+                task_id INTEGER NOT NULL,
+                tag TEXT NOT NULL COLLATE NOCASE,
+                created_at TEXT NOT NULL,
+                UNIQUE(task_id, tag),
+                FOREIGN KEY(task_id) REFERENCES tasks(id) ON DELETE CASCADE
+            );
+
+            CREATE TABLE IF NOT EXISTS automation_rules (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                name TEXT NOT NULL,
+                condition TEXT NOT NULL,
+                condition_value INTEGER NOT NULL,
+                priority_filter TEXT,
+                action TEXT NOT NULL,
+                action_value TEXT NOT NULL,
+                enabled INTEGER NOT NULL DEFAULT 1,
+                created_at TEXT NOT NULL,
+                updated_at TEXT NOT NULL
+            );
+
+            CREATE TABLE IF NOT EXISTS automation_rule_runs (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                rule_id INTEGER,
+                rule_name TEXT NOT NULL,
+                task_id INTEGER,
+                task_title TEXT NOT NULL,
+                change TEXT NOT NULL,
+                ran_at TEXT NOT NULL,
+                FOREIGN KEY(rule_id) REFERENCES automation_rules(id) ON DELETE SET NULL,
+                FOREIGN KEY(task_id) REFERENCES tasks(id) ON DELETE SET NULL
+            );
+            CREATE INDEX IF NOT EXISTS idx_automation_rule_runs_ran_at
+                ON automation_rule_runs(ran_at);",
+        )?;
+
+        Ok(())
+    })?;
+
+    // v44: page hierarchy.
+    apply_migration(conn, on_progress, 44, |conn| {
+        conn.execute_batch(
+            "ALTER TABLE pages ADD COLUMN parent_id INTEGER REFERENCES pages(id) ON DELETE SET NULL;
+            CREATE INDEX IF NOT EXISTS idx_pages_parent_id ON pages(parent_id);",
+        )?;
+
+        Ok(())
+    })?;
+
+    // v45: user-configurable schedules for background jobs and their last run.
+    apply_migration(conn, on_progress, 45, |conn| {
+        conn.execute_batch(
+            "CREATE TABLE IF NOT EXISTS scheduled_jobs (
+                job TEXT PRIMARY KEY,
+                schedule TEXT NOT NULL,
+                enabled INTEGER NOT NULL DEFAULT 1,
+                last_run_at TEXT,
+                last_status TEXT,
+                last_error TEXT,
+                updated_at TEXT NOT NULL
+            );",
+        )?;
+
+        Ok(())
+    })?;
+
+    // v46: free-form tags on wiki pages.
+    apply_migration(conn, on_progress, 46, |conn| {
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS page_tags (
+                id INTEGER PRIMARY KEY,
+                page_id INTEGER NOT NULL,
+                tag TEXT NOT NULL COLLATE NOCASE,
+                created_at TEXT NOT NULL,
+                UNIQUE(page_id, tag),
+                FOREIGN KEY(page_id) REFERENCES pages(id) ON DELETE CASCADE
+            )",
+            [],
+        )?;
+        conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_page_tags_tag ON page_tags(tag)",
+            [],
+        )?;
+
+        Ok(())
+    })?;
+
+    // v47: `[[Title]]` links between pages. Links are stored by target title so
+    // they resolve once a page with that title exists. Encrypted pages are indexed
+    // on their next save.
+    apply_migration(conn, on_progress, 47, |conn| {
+        conn.execute_batch(
+            "CREATE TABLE IF NOT EXISTS page_links (
+                id INTEGER PRIMARY KEY,
+                source_page_id INTEGER NOT NULL,
+                target_title TEXT NOT NULL COLLATE NOCASE,
+                created_at TEXT NOT NULL,
+                UNIQUE(source_page_id, target_title),
+                FOREIGN KEY(source_page_id) REFERENCES pages(id) ON DELETE CASCADE
+            );
+            CREATE INDEX IF NOT EXISTS idx_page_links_target ON page_links(target_title);",
+        )?;
+
+        let pages = {
+            let mut stmt =
+                conn.prepare("SELECT id, content FROM pages WHERE content NOT LIKE 'enc:v1:%'")?;
+            let rows = stmt.query_map([], |row| {
+                Ok((row.get::<_, i64>(0)?, row.get::<_, String>(1)?))
+            })?;
+            rows.collect::<Result<Vec<_>>>()?
+        };
+        let now = chrono::Utc::now().to_rfc3339();
+        for (page_id, content) in pages {
+            for title in crate::commands::pages::extract_wiki_links(&content) {
+                conn.execute(
+                    "INSERT OR IGNORE INTO page_links (source_page_id, target_title, created_at)
+                     VALUES (?1, ?2, ?3)",
+                    params![page_id, title, now],
+                )?;
+            }
+        }
+
+        Ok(())
+    })?;
+
+    // v48: snapshots of a page taken before each edit, for version history.
+    apply_migration(conn, on_progress, 48, |conn| {
+        conn.execute_batch(
+            "CREATE TABLE IF NOT EXISTS page_revisions (
+                id INTEGER PRIMARY KEY,
+                page_id INTEGER NOT NULL,
+                title TEXT NOT NULL,
+                content TEXT NOT NULL,
+                created_at TEXT NOT NULL,
+                FOREIGN KEY(page_id) REFERENCES pages(id) ON DELETE CASCADE
+            );
+            CREATE INDEX IF NOT EXISTS idx_page_revisions_page ON page_revisions(page_id, id);",
+        )?;
+
+        Ok(())
+    })?;
+
+    // v49: page trash (soft delete).
+    apply_migration(conn, on_progress, 49, |conn| {
+        ensure_column(conn, "pages", "deleted_at", "TEXT")?;
+        conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_pages_deleted_at
+             ON pages(deleted_at) WHERE deleted_at IS NOT NULL",
+            [],
+        )?;
+
+        Ok(())
+    })?;
+
+    // v50: full-text index over page titles and content.
+    apply_migration(conn, on_progress, 50, |conn| {
+        conn.execute_batch(
+            "CREATE VIRTUAL TABLE IF NOT EXISTS pages_fts USING fts5(
+                title,
+                content,
+                content='pages',
+                content_rowid='id',
+                tokenize='unicode61 remove_diacritics 2'
+            );
+
+            CREATE TRIGGER IF NOT EXISTS pages_fts_after_insert AFTER INSERT ON pages BEGIN
+                INSERT INTO pages_fts(rowid, title, content)
+                VALUES (new.id, new.title, new.content);
+            END;
+
+            CREATE TRIGGER IF NOT EXISTS pages_fts_after_delete AFTER DELETE ON pages BEGIN
+                INSERT INTO pages_fts(pages_fts, rowid, title, content)
+                VALUES ('delete', old.id, old.title, old.content);
+            END;
+
+            CREATE TRIGGER IF NOT EXISTS pages_fts_after_update AFTER UPDATE ON pages BEGIN
+                INSERT INTO pages_fts(pages_fts, rowid, title, content)
+                VALUES ('delete', old.id, old.title, old.content);
+                INSERT INTO pages_fts(rowid, title, content)
+                VALUES (new.id, new.title, new.content);
+            END;
+
+            INSERT INTO pages_fts(pages_fts) VALUES ('rebuild');",
+        )?;
+
+        Ok(())
+    })?;
+
+    // v51: pinned and favorite pages. The FTS trigger now only fires for title and
+    // content changes, so toggling a flag does not rewrite the index row.
+    apply_migration(conn, on_progress, 51, |conn| {
+        ensure_column(conn, "pages", "pinned", "INTEGER NOT NULL DEFAULT 0")?;
+        ensure_column(conn, "pages", "favorite", "INTEGER NOT NULL DEFAULT 0")?;
+        conn.execute_batch(
+            "DROP TRIGGER IF EXISTS pages_fts_after_update;
+
+            CREATE TRIGGER pages_fts_after_update AFTER UPDATE OF title, content ON pages BEGIN
+                INSERT INTO pages_fts(pages_fts, rowid, title, content)
+                VALUES ('delete', old.id, old.title, old.content);
+                INSERT INTO pages_fts(rowid, title, content)
+                VALUES (new.id, new.title, new.content);
+            END;",
+        )?;
+
+        Ok(())
+    })?;
+
+    // v52: images embedded in pages; files live under app_data_dir/assets. `page_id`
+    // turns NULL when the page is deleted so the file can be collected afterwards.
+    apply_migration(conn, on_progress, 52, |conn| {
+        conn.execute_batch(
+            "CREATE TABLE IF NOT EXISTS page_assets (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                page_id INTEGER REFERENCES pages(id) ON DELETE SET NULL,
+                file_name TEXT NOT NULL,
+                mime_type TEXT NOT NULL,
+                size_bytes INTEGER NOT NULL,
+                stored_path TEXT NOT NULL UNIQUE,
+                created_at TEXT NOT NULL
+            );
+            CREATE INDEX IF NOT EXISTS idx_page_assets_page ON page_assets(page_id);",
+        )?;
+
+        Ok(())
+    })?;
+
+    // v53: archived pages, hidden from the default page listing together with their
+    // subpages.
+    apply_migration(conn, on_progress, 53, |conn| {
+        ensure_column(conn, "pages", "archived", "INTEGER NOT NULL DEFAULT 0")?;
+
+        Ok(())
+    })?;
+
+    // v54: runtime feature flags; a missing row means the flag's built-in default.
+    apply_migration(conn, on_progress, 54, |conn| {
+        conn.execute_batch(
+            "CREATE TABLE IF NOT EXISTS feature_flags (
+                key TEXT PRIMARY KEY,
+                enabled INTEGER NOT NULL,
+                updated_at TEXT NOT NULL
+            );",
+        )?;
+
+        Ok(())
+    })?;
+
+    // v55: manual page order within a level; NULL until the level is reordered.
+    apply_migration(conn, on_progress, 55, |conn| {
+        ensure_column(conn, "pages", "sort_index", "INTEGER")?;
+        conn.execute_batch(
+            "CREATE INDEX IF NOT EXISTS idx_pages_parent_sort ON pages(parent_id, sort_index);",
+        )?;
+
+        Ok(())
+    })?;
+
+    // v56: when each page was last opened, for the "jump back in" list.
+    apply_migration(conn, on_progress, 56, |conn| {
+        conn.execute_batch(
+            "CREATE TABLE IF NOT EXISTS page_views (
+                page_id INTEGER PRIMARY KEY REFERENCES pages(id) ON DELETE CASCADE,
+                viewed_at TEXT NOT NULL,
+                view_count INTEGER NOT NULL DEFAULT 1
+            );
+            CREATE INDEX IF NOT EXISTS idx_page_views_viewed_at ON page_views(viewed_at);",
+        )?;
+
+        Ok(())
+    })?;
+
+    // v57: pages locked with their own passphrase; the key salt is stored with the
+    // locked content, so a backup restores the lock as-is.
+    apply_migration(conn, on_progress, 57, |conn| {
+        ensure_column(conn, "pages", "locked", "INTEGER NOT NULL DEFAULT 0")?;
+
+        Ok(())
+    })?;
+
+    // v58: results of the last data integrity check; counts per rule and a sample of
+    // the offending rows.
+    apply_migration(conn, on_progress, 58, |conn| {
+        conn.execute_batch(
+            "CREATE TABLE IF NOT EXISTS integrity_checks (
+                rule TEXT PRIMARY KEY,
+                violation_count INTEGER NOT NULL,
+                fixed_count INTEGER NOT NULL,
+                checked_at TEXT NOT NULL
+            );
+            CREATE TABLE IF NOT EXISTS integrity_violations (
+                id INTEGER PRIMARY KEY,
+                rule TEXT NOT NULL,
+                row_id INTEGER NOT NULL,
+                detail TEXT NOT NULL
+            );
+            CREATE INDEX IF NOT EXISTS idx_integrity_violations_rule
+                ON integrity_violations(rule);",
+        )?;
+
+        Ok(())
+    })?;
+
+    // v59: page icon (an emoji) and cover (a color or image URL) for the page list.
+    apply_migration(conn, on_progress, 59, |conn| {
+        ensure_column(conn, "pages", "icon", "TEXT")?;
+        ensure_column(conn, "pages", "cover", "TEXT")?;
+
+        Ok(())
+    })?;
+
+    // v60: `key: value` pairs from page frontmatter, so pages can be filtered like a
+    // small database. Values are plaintext, so locked pages and encrypted journals
+    // are never indexed.
+    apply_migration(conn, on_progress, 60, |conn| {
+        conn.execute_batch(
+            "CREATE TABLE IF NOT EXISTS page_properties (
+                id INTEGER PRIMARY KEY,
+                page_id INTEGER NOT NULL,
+                key TEXT NOT NULL COLLATE NOCASE,
+                value TEXT NOT NULL COLLATE NOCASE,
+                UNIQUE(page_id, key, value),
+                FOREIGN KEY(page_id) REFERENCES pages(id) ON DELETE CASCADE
+            );
+            CREATE INDEX IF NOT EXISTS idx_page_properties_key_value
+                ON page_properties(key, value);",
+        )?;
+
+        let pages = {
+            let mut stmt = conn.prepare(
+                "SELECT id, content FROM pages
+                 WHERE locked = 0
+                   AND NOT EXISTS (SELECT 1 FROM journal_encryption WHERE id = 1)",
+            )?;
+            let rows = stmt.query_map([], |row| {
+                Ok((row.get::<_, i64>(0)?, row.get::<_, String>(1)?))
+            })?;
+            rows.collect::<Result<Vec<_>>>()?
+        };
+        for (page_id, content) in pages {
+            for (key, value) in crate::commands::page_properties::extract_page_properties(&content)
+            {
+                conn.execute(
+                    "INSERT OR IGNORE INTO page_properties (page_id, key, value)
+                     VALUES (?1, ?2, ?3)",
+                    params![page_id, key, value],
+                )?;
+            }
+        }
+
+        Ok(())
+    })?;
+
+    // v61: command invocations slower than `slow_op_threshold_ms`, for the performance report.
+    apply_migration(conn, on_progress, 61, |conn| {
+        conn.execute_batch(
+            "CREATE TABLE IF NOT EXISTS slow_ops (
+                id INTEGER PRIMARY KEY,
+                command TEXT NOT NULL,
+                duration_ms INTEGER NOT NULL,
+                params TEXT NOT NULL DEFAULT '',
+                created_at TEXT NOT NULL
+            );",
+        )?;
+
+        Ok(())
+    })?;
+
+    // v62: indexes for query paths that still scanned whole tables (recent pages and
+    // tasks, habit logs by day, tasks by tag, priority or completion), a merge of
+    // the FTS segments, and fresh planner statistics. `check_query_plans` verifies
+    // the result.
+    apply_migration(conn, on_progress, 62, |conn| {
+        conn.execute_batch(
+            "CREATE INDEX IF NOT EXISTS idx_pages_updated_at ON pages(updated_at);
+            CREATE INDEX IF NOT EXISTS idx_tasks_updated_at ON tasks(updated_at);
+            CREATE INDEX IF NOT EXISTS idx_tasks_priority ON tasks(priority);
+            CREATE INDEX IF NOT EXISTS idx_tasks_completed_at ON tasks(completed_at);
+            CREATE INDEX IF NOT EXISTS idx_habit_logs_date ON habit_logs(date);
+            CREATE INDEX IF NOT EXISTS idx_task_tags_tag ON task_tags(tag);
+            INSERT INTO entries_fts(entries_fts) VALUES ('optimize');
+            INSERT INTO pages_fts(pages_fts) VALUES ('optimize');
+            INSERT INTO attachments_fts(attachments_fts) VALUES ('optimize');
+            PRAGMA optimize;",
+        )?;
+
+        Ok(())
+    })?;
+
+    // v63: tasks whose running timer is never shown in the Slack status.
+    apply_migration(conn, on_progress, 63, |conn| {
+        conn.execute_batch(
+            "CREATE TABLE IF NOT EXISTS slack_status_opt_outs (
+                task_id INTEGER PRIMARY KEY,
+                created_at TEXT NOT NULL,
+                FOREIGN KEY(task_id) REFERENCES tasks(id) ON DELETE CASCADE
+            );",
+        )?;
+
+        Ok(())
+    })?;
+
+    // v64: due-date reminders already sent per task, due date and lead time, and
+    // per-task snoozes.
+    apply_migration(conn, on_progress, 64, |conn| {
+        conn.execute_batch(
+            "CREATE TABLE IF NOT EXISTS task_due_reminders (
+                task_id INTEGER NOT NULL,
+                due_date TEXT NOT NULL,
+                lead_minutes INTEGER NOT NULL,
+                sent_at TEXT NOT NULL,
+                PRIMARY KEY(task_id, due_date, lead_minutes),
+                FOREIGN KEY(task_id) REFERENCES tasks(id) ON DELETE CASCADE
+            );
+            CREATE TABLE IF NOT EXISTS task_due_snoozes (
+                task_id INTEGER PRIMARY KEY,
+                snoozed_until TEXT NOT NULL,
+                FOREIGN KEY(task_id) REFERENCES tasks(id) ON DELETE CASCADE
+            );",
+        )?;
+
+        Ok(())
+    })?;
+
+    // v65: manual task order within each board column; NULL sorts before ordered
+    // tasks, newest first.
+    apply_migration(conn, on_progress, 65, |conn| {
+        ensure_column(conn, "tasks", "sort_index", "INTEGER")?;
+        conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_tasks_status_sort_index ON tasks(status, sort_index)",
+            [],
+        )?;
+
+        Ok(())
+    })?;
+
     Ok(())
 }
 
-fn apply_migration<F>(conn: &Connection, version: i64, migration: F) -> Result<()>
+fn apply_migration<F>(
+    conn: &Connection,
+    on_progress: &dyn Fn(i64),
+    version: i64,
+    migration: F,
+) -> Result<()>
 where
     F: FnOnce(&Connection) -> Result<()>,
 {
@@ -717,11 +1899,16 @@ where
         return Ok(());
     }
 
+    on_progress(version);
     migration(conn)?;
 
     conn.execute(
-        "INSERT INTO schema_migrations (version, applied_at) VALUES (?1, ?2)",
-        params![version, chrono::Utc::now().to_rfc3339()],
+        "INSERT INTO schema_migrations (version, applied_at, app_version) VALUES (?1, ?2, ?3)",
+        params![
+            version,
+            chrono::Utc::now().to_rfc3339(),
+            env!("CARGO_PKG_VERSION")
+        ],
     )?;
 
     Ok(())
@@ -760,7 +1947,7 @@ mod tests {
     fn run_migrations_enables_integrity_schema() {
         let conn = Connection::open_in_memory().expect("in-memory db");
         configure_connection(&conn).expect("configure");
-        run_migrations(&conn).expect("migrate");
+        run_migrations(&conn, &|_| {}).expect("migrate");
         enable_foreign_keys(&conn).expect("fk pragma");
 
         let foreign_keys_enabled: i64 = conn
@@ -796,6 +1983,34 @@ mod tests {
         assert_eq!(task_subtask_fk_count, 1);
     }
 
+    #[test]
+    fn migrate_reports_each_pending_version_up_to_schema_version() {
+        let conn = Connection::open_in_memory().expect("in-memory db");
+        configure_connection(&conn).expect("configure");
+        assert_eq!(
+            pending_migrations(&conn).expect("pending"),
+            (1..=SCHEMA_VERSION).collect::<Vec<_>>()
+        );
+
+        let reported = std::cell::RefCell::new(Vec::new());
+        migrate(&conn, &|version| reported.borrow_mut().push(version)).expect("migrate");
+        assert_eq!(
+            reported.into_inner(),
+            (1..=SCHEMA_VERSION).collect::<Vec<_>>()
+        );
+        assert!(pending_migrations(&conn).expect("pending").is_empty());
+
+        let latest: i64 = conn
+            .query_row("SELECT MAX(version) FROM schema_migrations", [], |row| {
+                row.get(0)
+            })
+            .expect("latest");
+        assert_eq!(
+            latest, SCHEMA_VERSION,
+            "bump SCHEMA_VERSION with new migrations"
+        );
+    }
+
     #[test]
     fn migration_v13_cleans_invalid_project_and_goal_links() {
         let conn = Connection::open_in_memory().expect("in-memory db");
@@ -880,6 +2095,17 @@ mod tests {
             [],
         )
         .expect("entries");
+        conn.execute(
+            "CREATE TABLE pages (
+                id INTEGER PRIMARY KEY,
+                title TEXT NOT NULL,
+                content TEXT NOT NULL,
+                created_at TEXT NOT NULL,
+                updated_at TEXT NOT NULL
+            )",
+            [],
+        )
+        .expect("pages");
         conn.execute(
             "CREATE TABLE meetings (
                 id INTEGER PRIMARY KEY,
@@ -993,7 +2219,7 @@ mod tests {
         )
         .expect("entry row");
 
-        run_migrations(&conn).expect("apply v13");
+        run_migrations(&conn, &|_| {}).expect("apply v13");
         enable_foreign_keys(&conn).expect("fk pragma");
 
         let goal_project_id: Option<i64> = conn
@@ -1,12 +1,19 @@
+mod bridge;
+mod capture_server;
 mod commands;
 mod db;
 mod models;
+mod scheduler;
+mod shortcuts;
+mod startup;
 mod tray;
 
-use std::sync::Mutex;
 use tauri::{Manager, WindowEvent};
 
-struct TrayAvailability(bool);
+/// Headless `--bridge` mode for editor plugins; see `bridge.rs`.
+pub fn run_bridge(args: &[String]) -> i32 {
+    bridge::run(args)
+}
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
@@ -18,31 +25,54 @@ pub fn run() {
             tauri_plugin_autostart::MacosLauncher::LaunchAgent,
             Some(vec![]),
         ))
+        .plugin(tauri_plugin_global_shortcut::Builder::new().build())
+        .register_uri_scheme_protocol(
+            commands::page_assets::PAGE_ASSET_SCHEME,
+            |context, request| {
+                commands::page_assets::serve_page_asset(context.app_handle(), &request)
+            },
+        )
+        .manage(commands::events::EventSubscriptions::default())
+        .manage(shortcuts::TimerShortcutState::default())
         .setup(|app| {
-            // Setup DB
-            let app_data_dir = app.path().app_data_dir().expect("Cannot get app data dir");
-            let conn = db::init(app_data_dir).expect("Failed to initialize database");
-            app.manage(commands::AppState {
-                db: Mutex::new(conn),
-            });
-
-            // Setup Tray
-            let tray_available = match tray::setup_tray(app.handle()) {
-                Ok(()) => true,
-                Err(error) => {
-                    eprintln!("Tray setup failed, continuing without tray support: {error}");
-                    false
-                }
-            };
-            app.manage(TrayAvailability(tray_available));
+            startup::start(app.handle())?;
 
             Ok(())
         })
         .on_window_event(|window, event| {
+            if let WindowEvent::Destroyed = event {
+                // A detached view reopened under the same label subscribes afresh.
+                if let Some(subscriptions) = window
+                    .app_handle()
+                    .try_state::<commands::events::EventSubscriptions>()
+                {
+                    if let Err(error) = subscriptions.unsubscribe(window.label()) {
+                        eprintln!("Failed to drop event subscriptions: {error}");
+                    }
+                }
+            }
             if let WindowEvent::CloseRequested { api, .. } = event {
+                if window.label() == startup::UPGRADE_WINDOW_LABEL {
+                    let migrating = window
+                        .app_handle()
+                        .try_state::<startup::MigrationState>()
+                        .is_some_and(|state| state.is_running());
+                    if migrating {
+                        api.prevent_close();
+                    }
+                    return;
+                }
+                // Detached views really close; only the main window hides to the tray.
+                if window
+                    .label()
+                    .starts_with(commands::windows::DETACHED_WINDOW_PREFIX)
+                {
+                    return;
+                }
+
                 let tray_available = window
                     .app_handle()
-                    .try_state::<TrayAvailability>()
+                    .try_state::<startup::TrayAvailability>()
                     .map(|state| state.0)
                     .unwrap_or(false);
 
@@ -55,69 +85,314 @@ pub fn run() {
                 }
             }
         })
-        .invoke_handler(tauri::generate_handler![
-            // Entries
-            commands::get_entries,
-            commands::get_entry,
-            commands::save_entry,
-            commands::delete_entry,
-            commands::search_entries,
-            commands::get_git_commits,
-            // Pages
-            commands::get_pages,
-            commands::get_page,
-            commands::create_page,
-            commands::update_page,
-            commands::delete_page,
-            // Tasks (from submodule)
-            commands::tasks::get_tasks,
-            commands::tasks::create_task,
-            commands::tasks::update_task,
-            commands::tasks::update_task_status,
-            commands::tasks::start_task_timer,
-            commands::tasks::pause_task_timer,
-            commands::tasks::reset_task_timer,
-            commands::tasks::delete_task,
-            commands::tasks::get_task_subtasks,
-            commands::tasks::create_task_subtask,
-            commands::tasks::update_task_subtask,
-            commands::tasks::delete_task_subtask,
-            // Goal milestones
-            commands::get_goal_milestones,
-            commands::create_goal_milestone,
-            commands::update_goal_milestone,
-            commands::delete_goal_milestone,
-            // Meetings (from submodule)
-            commands::meetings::get_meetings,
-            commands::meetings::create_meeting,
-            commands::meetings::update_meeting,
-            commands::meetings::delete_meeting,
-            commands::meetings::materialize_meeting_action_items,
-            // Projects
-            commands::get_projects,
-            commands::create_project,
-            commands::update_project,
-            commands::delete_project,
-            commands::get_project_branches,
-            commands::create_project_branch,
-            commands::update_project_branch,
-            commands::delete_project_branch,
-            // Goals
-            commands::get_goals,
-            commands::create_goal,
-            commands::update_goal,
-            commands::delete_goal,
-            // Habits
-            commands::get_habits,
-            commands::create_habit,
-            commands::update_habit,
-            commands::delete_habit,
-            commands::toggle_habit_completion,
-            // Backup
-            commands::backup::import_backup,
-            // Tray
-            tray::set_tray_timer
-        ])
+        .invoke_handler(commands::performance::timed_handler(
+            tauri::generate_handler![
+                // Startup
+                startup::get_migration_progress,
+                // Entries
+                commands::get_entries,
+                commands::count_entries,
+                commands::get_entry,
+                commands::save_entry,
+                commands::delete_entry,
+                commands::search_entries,
+                commands::get_git_commits,
+                commands::entries::get_entry_commits,
+                commands::entries::save_entry_commits,
+                commands::entries::get_previous_workday,
+                commands::entries::get_entry_streaks,
+                commands::entries::set_entry_pinned,
+                commands::entries::get_pinned_entries,
+                commands::entries::get_entries_on_this_day,
+                commands::entries::get_random_entry,
+                commands::entries::get_trashed_entries,
+                commands::entries::restore_entry,
+                commands::entries::purge_trash,
+                commands::entries::save_entry_draft,
+                commands::entries::get_entry_draft,
+                commands::entries::discard_entry_draft,
+                commands::entries::append_entry_note,
+                commands::entries::get_entry_notes,
+                commands::entries::delete_entry_note,
+                // Entry tags
+                commands::entries::add_entry_tag,
+                commands::entries::remove_entry_tag,
+                commands::entries::get_entry_tags,
+                commands::entries::get_entries_by_tag,
+                commands::entries::get_entries_between,
+                // Entry sections
+                commands::entries::get_entry_section_templates,
+                commands::entries::save_entry_section_templates,
+                commands::entries::get_entry_sections,
+                commands::entries::save_entry_sections,
+                // Attachments
+                commands::attachments::attach_file_to_entry,
+                commands::attachments::list_entry_attachments,
+                commands::attachments::delete_attachment,
+                commands::attachments::attach_command_output,
+                commands::attachments::get_attachment_text,
+                // Journal prompts
+                commands::prompts::get_journal_prompts,
+                commands::prompts::get_daily_prompt,
+                commands::prompts::add_journal_prompt,
+                commands::prompts::set_journal_prompt_enabled,
+                commands::prompts::delete_journal_prompt,
+                // Pages
+                commands::pages::get_pages,
+                commands::pages::get_page,
+                commands::pages::create_page,
+                commands::pages::update_page,
+                commands::pages::delete_page,
+                commands::pages::search_pages,
+                commands::pages::get_trashed_pages,
+                commands::pages::restore_page,
+                commands::pages::empty_page_trash,
+                commands::pages::get_page_children,
+                commands::pages::move_page,
+                commands::pages::reorder_pages,
+                commands::pages::record_page_view,
+                commands::pages::get_recent_pages,
+                commands::pages::set_page_pinned,
+                commands::pages::set_page_favorite,
+                commands::pages::archive_page,
+                commands::pages::unarchive_page,
+                commands::pages::add_page_tag,
+                commands::pages::remove_page_tag,
+                commands::pages::get_page_tags,
+                commands::pages::get_pages_by_tag,
+                commands::pages::get_backlinks,
+                commands::pages::get_page_revisions,
+                commands::pages::get_page_revision,
+                commands::pages::restore_page_revision,
+                // Page locks
+                commands::page_locks::enable_page_lock,
+                commands::page_locks::unlock_page,
+                commands::page_locks::lock_page,
+                commands::page_locks::disable_page_lock,
+                // Page properties
+                commands::page_properties::get_page_properties,
+                commands::page_properties::get_pages_by_property,
+                commands::page_properties::get_page_property_values,
+                // Page stats
+                commands::page_stats::get_page_stats,
+                commands::page_stats::get_pages_stats,
+                // Page assets
+                commands::page_assets::save_page_asset,
+                // Page export
+                commands::page_export::export_page,
+                commands::page_export::export_all_pages,
+                // Windows
+                commands::windows::open_window,
+                commands::windows::close_window,
+                // Tasks (from submodule)
+                commands::tasks::get_tasks,
+                commands::tasks::create_task,
+                commands::tasks::update_task,
+                commands::tasks::update_task_status,
+                commands::tasks::start_task_timer,
+                commands::tasks::pause_task_timer,
+                commands::tasks::reset_task_timer,
+                commands::tasks::get_recent_timer_tasks,
+                commands::tasks::find_duplicate_tasks,
+                commands::tasks::delete_task,
+                commands::tasks::get_task_subtasks,
+                commands::tasks::create_task_subtask,
+                commands::tasks::update_task_subtask,
+                commands::tasks::delete_task_subtask,
+                commands::tasks::reorder_task_subtasks,
+                commands::tasks::reorder_tasks,
+                commands::tasks::set_task_tags,
+                // Automation rules
+                commands::automation::get_automation_rules,
+                commands::automation::create_automation_rule,
+                commands::automation::update_automation_rule,
+                commands::automation::delete_automation_rule,
+                commands::automation::preview_automation_rules,
+                commands::automation::run_automation_rules,
+                commands::automation::get_automation_log,
+                commands::automation::get_task_tags,
+                // Due reminders
+                commands::due_reminders::snooze_task_due_reminder,
+                // Goal milestones
+                commands::get_goal_milestones,
+                commands::create_goal_milestone,
+                commands::update_goal_milestone,
+                commands::delete_goal_milestone,
+                // Meetings (from submodule)
+                commands::meetings::get_meetings,
+                commands::meetings::create_meeting,
+                commands::meetings::update_meeting,
+                commands::meetings::delete_meeting,
+                commands::meetings::materialize_meeting_action_items,
+                // Incidents
+                commands::incidents::get_incidents,
+                commands::incidents::create_incident,
+                commands::incidents::update_incident,
+                commands::incidents::delete_incident,
+                commands::incidents::get_incident_timeline,
+                // On-call schedule
+                commands::oncall::get_oncall_shifts,
+                commands::oncall::create_oncall_shift,
+                commands::oncall::delete_oncall_shift,
+                commands::oncall::import_oncall_ics,
+                commands::oncall::get_oncall_days,
+                // Projects
+                commands::get_projects,
+                commands::create_project,
+                commands::update_project,
+                commands::delete_project,
+                commands::get_project_branches,
+                commands::create_project_branch,
+                commands::update_project_branch,
+                commands::delete_project_branch,
+                // Goals
+                commands::get_goals,
+                commands::create_goal,
+                commands::update_goal,
+                commands::delete_goal,
+                // Goal reflections
+                commands::goals::save_goal_reflection,
+                commands::goals::get_goal_reflections,
+                commands::goals::get_similar_goal_reflections,
+                commands::goals::get_goals_needing_checkin,
+                commands::goals::check_in_goal,
+                // Habits
+                commands::get_habits,
+                commands::create_habit,
+                commands::update_habit,
+                commands::delete_habit,
+                commands::toggle_habit_completion,
+                commands::log_habits_bulk,
+                commands::habits::get_streaks_at_risk,
+                // Achievements
+                commands::achievements::get_achievements,
+                // Countdowns
+                commands::countdowns::start_countdown,
+                commands::countdowns::get_active_countdowns,
+                commands::countdowns::cancel_countdown,
+                // Mood insights
+                commands::insights::set_daily_mood,
+                commands::insights::get_daily_moods,
+                commands::insights::get_mood_correlations,
+                commands::insights::get_writing_stats,
+                commands::insights::get_meeting_load,
+                commands::insights::get_context_switches,
+                // Focus / pomodoro
+                commands::focus::record_pomodoro_session,
+                commands::focus::get_pomodoro_status,
+                commands::focus::get_pomodoro_sessions,
+                commands::focus::get_pomodoro_heatmap,
+                // Presence
+                commands::presence::get_presence,
+                commands::presence::set_focus_presence,
+                // Shutdown checklist
+                commands::shutdown::get_shutdown_checklist,
+                commands::shutdown::confirm_shutdown,
+                // Integrations
+                commands::integrations::create_task_from_url,
+                commands::integrations::get_task_links,
+                commands::integrations::get_integration_settings,
+                commands::integrations::save_integration_settings,
+                commands::integrations::refresh_linked_items,
+                // Slack status
+                commands::slack_status::get_slack_status_settings,
+                commands::slack_status::save_slack_status_settings,
+                commands::slack_status::get_slack_status_opt_outs,
+                commands::slack_status::set_task_slack_status_opt_out,
+                // Review queue
+                commands::review_queue::get_review_queue,
+                commands::review_queue::accept_review_item,
+                commands::review_queue::dismiss_review_item,
+                // External API tokens
+                commands::api_access::create_api_token,
+                commands::api_access::list_api_tokens,
+                commands::api_access::revoke_api_token,
+                commands::api_access::check_api_token,
+                commands::api_access::get_external_access_log,
+                commands::api_access::get_api_scopes,
+                // Browser capture
+                commands::capture::get_bookmarks,
+                commands::capture::delete_bookmark,
+                // Events
+                commands::events::subscribe_events,
+                commands::events::unsubscribe_events,
+                // Journal encryption
+                commands::encryption::get_journal_encryption_status,
+                commands::encryption::enable_journal_encryption,
+                commands::encryption::unlock_journal,
+                commands::encryption::lock_journal,
+                commands::encryption::disable_journal_encryption,
+                // Settings
+                commands::settings::get_app_settings,
+                commands::settings::update_app_setting,
+                // Weeks
+                commands::weeks::get_week_info,
+                // Appearance
+                commands::appearance::get_appearance_settings,
+                commands::appearance::update_appearance_settings,
+                // Feature flags
+                commands::feature_flags::get_flags,
+                commands::feature_flags::set_flag,
+                // Notifications
+                commands::notifications::get_notification_templates,
+                // Scheduled jobs
+                commands::jobs::list_scheduled_jobs,
+                commands::jobs::set_job_schedule,
+                // Updates
+                commands::updates::check_for_updates,
+                // What's new
+                commands::whats_new::get_whats_new,
+                commands::whats_new::mark_whats_new_seen,
+                // Accessibility
+                commands::accessibility::get_accessible_summary,
+                // Weekly review
+                commands::review::generate_weekly_review,
+                commands::review::generate_week_plan,
+                // Daily sheet
+                commands::daily_sheet::generate_daily_sheet,
+                // Day One import
+                commands::import_day_one,
+                // Slack import
+                commands::slack_import::import_slack_standups,
+                // Obsidian import
+                commands::obsidian::import_obsidian_vault,
+                // Org import/export
+                commands::org::import_org,
+                commands::org::export_org,
+                // todo.txt import/export
+                commands::todo_txt::import_todo_txt,
+                commands::todo_txt::export_todo_txt,
+                // Markdown import/export
+                commands::markdown::export_entries_markdown,
+                commands::markdown::import_markdown_folder,
+                // Filtered export
+                commands::export::export_filtered,
+                // Vault mirror
+                commands::mirror::sync_vault_mirror,
+                commands::mirror_git::get_mirror_history,
+                // Backup
+                commands::backup::get_backup_format,
+                commands::backup::import_backup,
+                // Workspace snapshots
+                commands::snapshots::list_workspace_snapshots,
+                commands::snapshots::create_workspace_snapshot,
+                commands::snapshots::restore_workspace_snapshot,
+                commands::snapshots::delete_workspace_snapshot,
+                // Date shift
+                commands::date_shift::preview_date_shift,
+                commands::date_shift::shift_dates,
+                // Integrity
+                commands::integrity::get_integrity_report,
+                commands::integrity::verify_integrity,
+                // Performance
+                commands::performance::get_performance_report,
+                commands::performance::clear_slow_ops,
+                // Query plans
+                commands::query_plans::check_query_plans,
+                // Tray
+                tray::set_tray_timer
+            ],
+        ))
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
 }
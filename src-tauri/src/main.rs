@@ -2,5 +2,10 @@
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 
 fn main() {
+    let args: Vec<String> = std::env::args().collect();
+    if args.iter().any(|arg| arg == "--bridge") {
+        std::process::exit(tauri_app_lib::run_bridge(&args));
+    }
+
     tauri_app_lib::run()
 }
@@ -8,6 +8,117 @@ pub struct Entry {
     pub today: String,
     pub project_id: Option<i64>,
     pub created_at: String,
+    pub pinned: bool,
+    /// Standup impediments, kept apart from the Today plan.
+    pub blockers: String,
+    pub notes: String,
+}
+
+/// Entry matched by full-text search; `snippet` wraps matches in `<mark>`/`</mark>`.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct EntrySearchResult {
+    pub entry: Entry,
+    pub snippet: String,
+    pub rank: f64,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct EntryTag {
+    pub entry_id: i64,
+    pub tag: String,
+    pub created_at: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct EntrySectionTemplate {
+    pub id: i64,
+    pub section_key: String,
+    pub label: String,
+    pub sort_order: i64,
+}
+
+/// Commit snapshotted with an entry; `hash` is the abbreviated hash from `git log --oneline`.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct EntryCommit {
+    pub id: i64,
+    pub entry_id: i64,
+    pub hash: String,
+    pub message: String,
+    pub position: i64,
+    pub created_at: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct EntrySection {
+    pub id: i64,
+    pub entry_id: i64,
+    pub section_key: String,
+    pub content: String,
+    pub sort_order: i64,
+    pub updated_at: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct JournalPrompt {
+    pub id: i64,
+    pub text: String,
+    pub is_builtin: bool,
+    pub enabled: bool,
+    pub created_at: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DailyMood {
+    pub date: String,
+    pub mood: i64,
+    pub updated_at: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct MoodCorrelation {
+    pub factor: String,
+    pub correlation: f64,
+    pub sample_days: i64,
+}
+
+/// Hours in meetings (overlaps counted once) and focus sessions on one local day.
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct DailyMeetingLoad {
+    pub date: String,
+    pub meeting_hours: f64,
+    pub deep_work_hours: f64,
+    /// Meeting hours reached the `meeting_heavy_hours` setting (0 turns the warning off).
+    pub meeting_heavy: bool,
+}
+
+/// Task timer runs on one local day and how often the timed task changed between them.
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct DailyContextSwitches {
+    pub date: String,
+    pub switches: i64,
+    pub tasks_touched: i64,
+    pub tracked_minutes: i64,
+    /// Switches per tracked hour, the score used to compare fragmented days.
+    pub switches_per_hour: f64,
+}
+
+/// Words in one day's entry (Yesterday + Today).
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct DailyWordCount {
+    pub date: String,
+    pub words: i64,
+}
+
+/// Writing statistics over a date range; `days` only lists days with an entry.
+/// `most_productive_weekday` is the English weekday name with the highest average
+/// words per entry, or `None` without entries.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct WritingStats {
+    pub days: Vec<DailyWordCount>,
+    pub total_words: i64,
+    pub entry_count: i64,
+    pub average_words_per_entry: f64,
+    pub most_productive_weekday: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -15,8 +126,64 @@ pub struct Page {
     pub id: i64,
     pub title: String,
     pub content: String,
+    /// Containing page; `None` for top-level pages.
+    pub parent_id: Option<i64>,
     pub created_at: String,
     pub updated_at: String,
+    /// Pinned pages are listed first by `get_pages`.
+    pub pinned: bool,
+    pub favorite: bool,
+    /// Archived pages and their subpages are left out of the default `get_pages`.
+    pub archived: bool,
+    /// Position among its siblings set by `reorder_pages`; `None` until the level is
+    /// reordered, and such pages come after the ordered ones.
+    pub sort_index: Option<i64>,
+    /// Locked with its own passphrase; `content` stays locked until `unlock_page`.
+    pub locked: bool,
+    /// Emoji shown before the title.
+    pub icon: Option<String>,
+    /// `#rrggbb` color or image URL shown above the page.
+    pub cover: Option<String>,
+}
+
+/// A page from `get_recent_pages`, with when it was last opened.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RecentPage {
+    pub page_id: i64,
+    pub title: String,
+    pub parent_id: Option<i64>,
+    pub viewed_at: String,
+    pub view_count: i64,
+}
+
+/// A page as it was before an edit or restore.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PageRevision {
+    pub id: i64,
+    pub page_id: i64,
+    pub title: String,
+    pub content: String,
+    pub created_at: String,
+}
+
+/// One `key: value` pair from a page's frontmatter; list values give one per item.
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+pub struct PageProperty {
+    pub key: String,
+    pub value: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+pub struct PagePropertyValue {
+    pub value: String,
+    pub page_count: i64,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PageTag {
+    pub page_id: i64,
+    pub tag: String,
+    pub created_at: String,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -38,6 +205,11 @@ pub struct Task {
     pub timer_accumulated_seconds: i64,
     pub created_at: String,
     pub updated_at: String,
+    /// Checklist progress from `task_subtasks`; filled in by `get_tasks`, 0 elsewhere.
+    #[serde(default)]
+    pub subtask_count: i64,
+    #[serde(default)]
+    pub subtasks_completed: i64,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -51,6 +223,448 @@ pub struct TaskSubtask {
     pub updated_at: String,
 }
 
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TaskLink {
+    pub id: i64,
+    pub task_id: i64,
+    pub url: String,
+    pub provider: String,
+    pub kind: String,
+    pub external_ref: String,
+    pub title: String,
+    pub labels: Vec<String>,
+    pub state: Option<String>,
+    pub last_checked_at: Option<String>,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+/// File copied next to the database; `path` is the absolute location on disk.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Attachment {
+    pub id: i64,
+    pub entry_id: i64,
+    pub file_name: String,
+    pub mime_type: String,
+    pub size_bytes: i64,
+    pub path: String,
+    pub created_at: String,
+    /// `file` or `command_output`.
+    pub kind: String,
+    pub command: Option<String>,
+}
+
+/// Image stored for a page; `url` is what the page embeds, served by the
+/// `journal-asset` protocol.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PageAsset {
+    pub id: i64,
+    pub page_id: Option<i64>,
+    pub file_name: String,
+    pub mime_type: String,
+    pub size_bytes: i64,
+    pub url: String,
+    pub created_at: String,
+}
+
+/// Look and feel shared by every window. `mode` is `system`, `light` or `dark`;
+/// `accent_color` overrides the preset's primary color.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AppearanceSettings {
+    pub theme_preset: String,
+    pub mode: String,
+    pub accent_color: Option<String>,
+    pub font_preset: String,
+    pub font_size: i64,
+    pub compact: bool,
+    pub border_radius: i64,
+    /// `None` until the appearance is saved for the first time.
+    #[serde(default)]
+    pub updated_at: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ReleaseNoteSection {
+    pub title: String,
+    pub items: Vec<String>,
+}
+
+/// One release from the bundled changelog.
+#[derive(Debug, Clone, Serialize)]
+pub struct ReleaseNotes {
+    pub version: String,
+    pub date: Option<String>,
+    pub sections: Vec<ReleaseNoteSection>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct AppliedMigration {
+    pub version: i64,
+    pub note: String,
+    pub applied_at: String,
+    /// App version that ran the migration; `None` for migrations from before it was recorded.
+    pub app_version: Option<String>,
+}
+
+/// The week around `date` under the `week_numbering` setting (`iso` or `us`).
+/// `year` is the week-numbering year, which can differ from the calendar year
+/// around New Year.
+#[derive(Debug, Clone, Serialize)]
+pub struct WeekInfo {
+    pub numbering: String,
+    pub date: String,
+    pub week_start: String,
+    pub week_end: String,
+    pub year: i32,
+    pub week: u32,
+}
+
+/// Result of `check_for_updates`; the `latest_*` and release fields are only set
+/// when a newer version exists on the channel.
+#[derive(Debug, Clone, Serialize)]
+pub struct UpdateCheck {
+    pub current_version: String,
+    pub channel: String,
+    pub update_available: bool,
+    pub latest_version: Option<String>,
+    pub release_url: Option<String>,
+    pub release_notes: Option<String>,
+    pub published_at: Option<String>,
+    pub checked_at: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct WhatsNew {
+    pub current_version: String,
+    pub since_version: Option<String>,
+    /// Newest first.
+    pub releases: Vec<ReleaseNotes>,
+    pub migrations: Vec<AppliedMigration>,
+}
+
+/// Size, links and freshness of one page; word counts skip fenced code blocks.
+#[derive(Debug, Clone, Serialize)]
+pub struct PageStats {
+    pub page_id: i64,
+    pub title: String,
+    pub parent_id: Option<i64>,
+    pub word_count: i64,
+    pub character_count: i64,
+    pub reading_minutes: i64,
+    pub heading_count: i64,
+    /// `[[Title]]` links on this page.
+    pub outgoing_links: i64,
+    /// Other pages linking here.
+    pub backlinks: i64,
+    pub revision_count: i64,
+    pub created_at: String,
+    pub updated_at: String,
+    pub days_since_edit: i64,
+}
+
+/// Knowledge base health across all live pages.
+#[derive(Debug, Serialize)]
+pub struct PagesStats {
+    pub page_count: i64,
+    pub total_words: i64,
+    pub total_reading_minutes: i64,
+    pub average_words: i64,
+    pub empty_pages: i64,
+    /// Not edited for `stale_after_days` days.
+    pub stale_pages: i64,
+    /// Top-level pages no other page links to.
+    pub orphan_pages: i64,
+    pub edited_last_7_days: i64,
+    pub edited_last_30_days: i64,
+    pub stale_after_days: i64,
+    pub largest: Vec<PageStats>,
+    pub stalest: Vec<PageStats>,
+    pub recently_edited: Vec<PageStats>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct MarkdownExportSummary {
+    pub directory: String,
+    pub files_written: i64,
+}
+
+/// Result of `import_obsidian_vault`; `notes_read` counts daily notes too.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ObsidianImportSummary {
+    pub notes_read: i64,
+    pub pages_created: i64,
+    pub pages_updated: i64,
+    pub entries_imported: i64,
+    pub skipped_existing: i64,
+    pub assets_imported: i64,
+}
+
+/// Result of `export_all_pages`; `assets_copied` counts images copied beside Markdown files.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PageExportSummary {
+    pub directory: String,
+    pub pages_written: i64,
+    pub assets_copied: i64,
+}
+
+/// Result of `export_filtered`.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct FilteredExportSummary {
+    pub directory: String,
+    pub entries_exported: i64,
+    pub pages_exported: i64,
+    pub tasks_exported: i64,
+    pub files_written: i64,
+}
+
+/// Result of a full `sync_vault_mirror` pass.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct VaultMirrorSummary {
+    pub directory: String,
+    pub entries_written: i64,
+    pub pages_written: i64,
+    /// Mirror files whose entry or page no longer exists.
+    pub files_removed: i64,
+}
+
+/// A commit of the vault mirror's git repository.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct MirrorCommit {
+    pub id: String,
+    pub summary: String,
+    pub committed_at: String,
+}
+
+/// Daily notes found by `import_markdown_folder`; days that already had an entry are skipped.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct MarkdownImportSummary {
+    pub files_read: i64,
+    pub entries_imported: i64,
+    pub skipped_existing: i64,
+}
+
+/// `entries_read` counts Day One entries; several of them can land on the same day.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DayOneImportSummary {
+    pub entries_read: i64,
+    pub days_written: i64,
+}
+
+/// `messages_read` counts every message in the export; `standups_found` only posts by the
+/// chosen author that had at least one section header.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SlackImportSummary {
+    pub messages_read: i64,
+    pub standups_found: i64,
+    pub days_written: i64,
+}
+
+/// Headings read from the Org files and what became of them; unchanged tasks and pages
+/// count in neither `_created` nor `_updated`.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct OrgImportSummary {
+    pub headings_read: i64,
+    pub tasks_created: i64,
+    pub tasks_updated: i64,
+    pub pages_created: i64,
+    pub pages_updated: i64,
+}
+
+/// Tasks read from a todo.txt file; unchanged tasks count in neither `_created` nor
+/// `_updated`.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TodoTxtImportSummary {
+    pub tasks_read: i64,
+    pub tasks_created: i64,
+    pub tasks_updated: i64,
+}
+
+/// Milestone with progress toward its threshold; `unlocked_at` is set once reached.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Achievement {
+    pub key: String,
+    pub title: String,
+    pub description: String,
+    pub threshold: i64,
+    pub progress: i64,
+    pub unlocked_at: Option<String>,
+}
+
+/// Habit with an active streak that is still unlogged today.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StreakAtRisk {
+    pub habit_id: i64,
+    pub title: String,
+    pub color: String,
+    pub streak_days: i64,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct BreakReminder {
+    pub task_id: i64,
+    pub title: String,
+    pub running_minutes: i64,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct LinkedItemChange {
+    pub link: TaskLink,
+    pub previous_state: Option<String>,
+    pub task_completed: bool,
+}
+
+/// Auto-created task held out of the task board until accepted or dismissed.
+/// `source_url` is the linked issue for tasks captured from a URL.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ReviewQueueItem {
+    pub task: Task,
+    pub source: String,
+    pub source_url: Option<String>,
+    pub queued_at: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TaskWithLink {
+    pub task: Task,
+    pub link: TaskLink,
+    /// Open tasks that already looked like this issue when it was added.
+    pub duplicates: Vec<DuplicateTaskMatch>,
+}
+
+/// An open task whose title nearly matches one being created; `similarity` is 0–1.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DuplicateTaskMatch {
+    pub task_id: i64,
+    pub title: String,
+    pub status: String,
+    pub similarity: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Countdown {
+    pub id: i64,
+    pub label: String,
+    pub duration_minutes: i64,
+    pub started_at: String,
+    pub ends_at: String,
+    pub completed_at: Option<String>,
+    pub cancelled_at: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PomodoroSession {
+    pub id: i64,
+    pub kind: String,
+    pub task_id: Option<i64>,
+    pub duration_minutes: i64,
+    pub date: String,
+    pub completed_at: String,
+}
+
+/// What the user is working on right now. Held in `AppState` so the tray, every window
+/// and status integrations read the same value; changes are emitted as `presence-changed`.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Presence {
+    /// Task whose timer is running; the most recently started one if several are.
+    pub task_id: Option<i64>,
+    pub task_title: Option<String>,
+    /// When that timer was started.
+    pub since: Option<String>,
+    pub focus: Option<FocusPresence>,
+}
+
+/// A due-date reminder sent by the scheduler, also emitted as `task-due-reminder`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TaskDueReminder {
+    pub task_id: i64,
+    pub title: String,
+    pub due_date: String,
+    pub minutes_left: i64,
+    /// Sent because a snooze ended rather than because a lead time passed.
+    pub snoozed: bool,
+}
+
+/// Loose ends found by the shutdown ritual when quitting; the UI shows them and
+/// quitting waits until the user acknowledges with `confirm_shutdown`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ShutdownChecklist {
+    pub date: String,
+    pub entry_missing: bool,
+    /// Titles of tasks whose timer is still running.
+    pub running_timers: Vec<String>,
+    /// e.g. "entry missing, 1 timer running".
+    pub summary: String,
+}
+
+/// A pomodoro session in progress, reported by the focus board when it starts one.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FocusPresence {
+    pub kind: String,
+    pub task_id: Option<i64>,
+    pub started_at: String,
+    pub ends_at: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PomodoroStatus {
+    pub date: String,
+    pub completed_today: i64,
+    pub focus_minutes_today: i64,
+    pub daily_goal: i64,
+    pub goal_progress: i64,
+    pub cycle_position: i64,
+    pub long_break_every: i64,
+    pub next_break_kind: String,
+    pub long_break_minutes: i64,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PomodoroDayCount {
+    pub date: String,
+    pub count: i64,
+    pub focus_minutes: i64,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct AppSetting {
+    pub key: String,
+    pub value: String,
+}
+
+/// Screen reader summary of one view. `text` is `parts` joined into a sentence.
+#[derive(Debug, Serialize)]
+pub struct AccessibleSummary {
+    pub view: String,
+    pub text: String,
+    pub parts: Vec<String>,
+    pub generated_at: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct FeatureFlag {
+    pub key: String,
+    pub description: String,
+    pub enabled: bool,
+    pub default_enabled: bool,
+    pub updated_at: Option<String>,
+}
+
+/// The Slack token itself lives in the OS keychain, never in the database.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SlackStatusSettings {
+    pub enabled: bool,
+    pub has_token: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct IntegrationSettings {
+    pub provider: String,
+    pub base_url: Option<String>,
+    pub username: Option<String>,
+    pub has_token: bool,
+    pub updated_at: Option<String>,
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct MeetingActionItem {
     pub id: String,
@@ -94,6 +708,29 @@ pub struct Goal {
     pub updated_at: String,
 }
 
+/// Active goal whose progress (or any of its milestones) has not been touched for a
+/// week or more.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GoalCheckin {
+    pub goal_id: i64,
+    pub title: String,
+    pub progress: i64,
+    pub last_updated_at: String,
+    pub days_since_update: i64,
+}
+
+/// Lessons recorded when a goal is completed. `goal_title` is kept so reflections
+/// outlive the goal they belong to.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct GoalReflection {
+    pub id: i64,
+    pub goal_id: Option<i64>,
+    pub goal_title: String,
+    pub what_worked: String,
+    pub what_didnt: String,
+    pub created_at: String,
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct GoalMilestone {
     pub id: i64,
@@ -147,8 +784,344 @@ pub struct HabitWithLogs {
     pub target_per_week: i64,
     pub color: String,
     pub completed_dates: Vec<String>,
+    /// Missed days covered by a streak freeze, newest first.
+    pub frozen_dates: Vec<String>,
     pub current_streak: i64,
+    /// Unspent freeze tokens in the pool shared by all habits; the same on every habit.
+    pub freeze_tokens: i64,
     pub this_week_count: i64,
     pub created_at: String,
     pub updated_at: String,
 }
+
+/// Token for an external interface; the secret itself is never stored.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ApiToken {
+    pub id: i64,
+    pub name: String,
+    pub token_prefix: String,
+    pub scopes: Vec<String>,
+    pub created_at: String,
+    pub last_used_at: Option<String>,
+}
+
+/// Returned once on creation; `secret` cannot be retrieved later.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CreatedApiToken {
+    pub token: ApiToken,
+    pub secret: String,
+}
+
+/// One external interface call; `token_id` is `None` when the token was not recognised.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ExternalAccessLogEntry {
+    pub id: i64,
+    pub token_id: Option<i64>,
+    pub caller: String,
+    pub endpoint: String,
+    pub outcome: String,
+    pub detail: String,
+    pub created_at: String,
+}
+
+/// `unlocked` is always true while encryption is off.
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct JournalEncryptionStatus {
+    pub enabled: bool,
+    pub unlocked: bool,
+}
+
+/// Version stamp written into JSON backups; `schema_version` is informational.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct BackupFormat {
+    pub format_version: i64,
+    pub schema_version: i64,
+}
+
+/// Soft-deleted entry waiting in the trash.
+/// Autosaved, not yet saved standup text for one day.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct EntryDraft {
+    pub date: String,
+    pub yesterday: String,
+    pub today: String,
+    pub blockers: String,
+    pub notes: String,
+    pub updated_at: String,
+}
+
+/// One timestamped note logged during the day ("interstitial journaling").
+#[derive(Debug, Serialize, Deserialize)]
+pub struct EntryNote {
+    pub id: i64,
+    pub date: String,
+    pub text: String,
+    pub created_at: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TrashedEntry {
+    pub entry: Entry,
+    pub deleted_at: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TrashedPage {
+    pub page: Page,
+    pub deleted_at: String,
+}
+
+/// `snippet` marks matched words with `<mark>`; lower `rank` is a better match.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PageSearchResult {
+    pub page: Page,
+    pub snippet: String,
+    pub rank: f64,
+}
+
+/// Days journaled in a row; the current streak survives until today's entry is missed.
+#[derive(Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct EntryStreaks {
+    pub current_streak: i64,
+    pub longest_streak: i64,
+    pub total_entries: i64,
+}
+
+/// Link clipped from the browser through the localhost capture endpoint.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Bookmark {
+    pub id: i64,
+    pub url: String,
+    pub title: String,
+    pub selection: String,
+    pub created_at: String,
+}
+
+/// What `POST /capture` created; `kind` is `bookmark` or `task`.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CaptureResult {
+    pub kind: String,
+    pub id: i64,
+    /// Near-duplicate open tasks for a captured task; empty for bookmarks.
+    pub duplicates: Vec<DuplicateTaskMatch>,
+}
+
+/// On-call incident; `severity` is `sev1` (worst) to `sev4`, `ended_at` is unset while ongoing.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Incident {
+    pub id: i64,
+    pub started_at: String,
+    pub ended_at: Option<String>,
+    pub severity: String,
+    pub summary: String,
+    pub resolution: String,
+    pub task_ids: Vec<i64>,
+    pub page_ids: Vec<i64>,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+/// Inclusive range of local days on call; `source` is `manual` or `ics`.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct OnCallShift {
+    pub id: i64,
+    pub start_date: String,
+    pub end_date: String,
+    pub note: String,
+    pub source: String,
+    pub created_at: String,
+}
+
+/// One point on the incident timeline; `kind` is `started`, `resolved` or `task_completed`.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct IncidentTimelineEvent {
+    pub at: String,
+    pub kind: String,
+    pub incident_id: i64,
+    pub severity: String,
+    pub summary: String,
+    /// Resolution text or the completed task's title.
+    pub detail: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TaskTag {
+    pub task_id: i64,
+    pub tag: String,
+    pub created_at: String,
+}
+
+/// Escalation rule evaluated by the scheduler: open tasks matching `condition`
+/// (`due_within_hours` or `in_progress_days`, with `condition_value` as the
+/// threshold, optionally only at `priority_filter`) get `action` (`set_priority`
+/// or `add_tag`, with `action_value` as the priority or tag).
+#[derive(Debug, Serialize, Deserialize)]
+pub struct AutomationRule {
+    pub id: i64,
+    pub name: String,
+    pub condition: String,
+    pub condition_value: i64,
+    pub priority_filter: Option<String>,
+    pub action: String,
+    pub action_value: String,
+    pub enabled: bool,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+/// A change a rule made to a task, or would make in a dry run.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct AutomationRuleChange {
+    pub rule_id: i64,
+    pub rule_name: String,
+    pub task_id: i64,
+    pub task_title: String,
+    /// Human-readable summary, e.g. `priority low → medium` or `tagged #stale`.
+    pub change: String,
+}
+
+/// Execution log row; names are copied so the log outlives deleted rules and tasks.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct AutomationRunLogEntry {
+    pub id: i64,
+    pub rule_id: Option<i64>,
+    pub rule_name: String,
+    pub task_id: Option<i64>,
+    pub task_title: String,
+    pub change: String,
+    pub ran_at: String,
+}
+
+/// Customizable notification text; `preview_*` renders it with sample values.
+#[derive(Debug, Serialize)]
+pub struct NotificationTemplate {
+    pub kind: String,
+    pub placeholders: Vec<String>,
+    pub title: String,
+    pub body: String,
+    pub preview_title: String,
+    pub preview_body: String,
+}
+
+/// A row breaking an integrity rule; `row_id` is its id in the rule's table.
+#[derive(Debug, Serialize)]
+pub struct IntegrityViolation {
+    pub row_id: i64,
+    pub detail: String,
+}
+
+/// One rule of the last integrity check. `violations` holds a sample of at most 50
+/// rows; `violation_count` counts all of them.
+#[derive(Debug, Serialize)]
+pub struct IntegrityRuleStatus {
+    pub rule: String,
+    pub description: String,
+    pub auto_fixable: bool,
+    pub violation_count: i64,
+    pub fixed_count: i64,
+    pub checked_at: Option<String>,
+    pub violations: Vec<IntegrityViolation>,
+}
+
+/// Outcome of the last integrity check; `checked_at` is `None` until one ran.
+#[derive(Debug, Serialize)]
+pub struct IntegrityReport {
+    pub checked_at: Option<String>,
+    pub violation_count: i64,
+    pub rules: Vec<IntegrityRuleStatus>,
+}
+
+/// One row a date shift moves; `id` is the row id in the shifted table.
+#[derive(Debug, Serialize)]
+pub struct DateShiftRow {
+    pub id: i64,
+    pub label: String,
+    pub from_date: String,
+    pub to_date: String,
+}
+
+/// Result of `shift_dates` or its preview. `conflicts` would land on a date their
+/// habit (or the journal) already has and block the shift; `applied` is false for a
+/// preview.
+#[derive(Debug, Serialize)]
+pub struct DateShift {
+    pub entity: String,
+    pub delta_days: i64,
+    pub rows: Vec<DateShiftRow>,
+    pub conflicts: Vec<DateShiftRow>,
+    pub applied: bool,
+}
+
+/// Background job with its cron schedule; `next_run_at` is local time and `None`
+/// while the job is disabled.
+#[derive(Debug, Serialize)]
+pub struct ScheduledJob {
+    pub job: String,
+    pub schedule: String,
+    pub default_schedule: String,
+    pub enabled: bool,
+    pub next_run_at: Option<String>,
+    pub last_run_at: Option<String>,
+    pub last_status: Option<String>,
+    pub last_error: Option<String>,
+}
+
+/// A named copy of the whole database, kept under `app_data_dir/snapshots`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WorkspaceSnapshot {
+    pub name: String,
+    pub created_at: String,
+    pub size_bytes: i64,
+    /// Migration level of the copied database.
+    pub schema_version: i64,
+}
+
+/// Counts for the past seven days sent by the `digest` job.
+#[derive(Debug, Clone, Serialize)]
+pub struct WeeklyDigest {
+    pub entries: i64,
+    pub tasks_done: i64,
+    pub overdue: i64,
+}
+
+/// Calls of one command since the app started.
+#[derive(Debug, Serialize)]
+pub struct CommandTiming {
+    pub command: String,
+    pub calls: i64,
+    pub slow_calls: i64,
+    pub total_ms: f64,
+    pub mean_ms: f64,
+    pub max_ms: f64,
+}
+
+/// A command invocation that took at least `slow_op_threshold_ms`. `params` names the
+/// arguments with their size, not their content.
+#[derive(Debug, Serialize)]
+pub struct SlowOp {
+    pub id: i64,
+    pub command: String,
+    pub duration_ms: i64,
+    pub params: String,
+    pub created_at: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct PerformanceReport {
+    pub threshold_ms: i64,
+    /// When the in-memory `commands` timings started, i.e. app start.
+    pub since: String,
+    pub commands: Vec<CommandTiming>,
+    pub slow_ops: Vec<SlowOp>,
+}
+
+/// `EXPLAIN QUERY PLAN` of one common query; `full_scans` lists the steps that read a
+/// whole table.
+#[derive(Debug, Serialize)]
+pub struct QueryPlanCheck {
+    pub name: String,
+    pub description: String,
+    pub uses_index: bool,
+    pub full_scans: Vec<String>,
+    pub plan: Vec<String>,
+}
@@ -0,0 +1,405 @@
+use crate::commands::events::emit_event;
+use crate::commands::notifications::render_notification_template;
+use crate::commands::{self, AppState};
+use crate::models::BreakReminder;
+use chrono::{Local, NaiveDate, Utc};
+use std::collections::HashSet;
+use std::thread;
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Manager};
+use tauri_plugin_notification::NotificationExt;
+
+const TICK: Duration = Duration::from_secs(60);
+
+/// Background jobs that run while the app is open; the tray menu is refreshed
+/// on every tick so running timers show current totals. The jobs in
+/// `commands::jobs` run when their cron schedule matched a minute since the last
+/// tick; the rest decide on every tick whether they are due. Linked items are
+/// polled on a thread of their own so slow provider APIs never hold up the tick.
+pub fn spawn(app: AppHandle) {
+    if let Err(error) = commands::countdowns::watch_active_countdowns(&app) {
+        eprintln!("Failed to resume countdowns: {error}");
+    }
+    spawn_linked_items_poll(app.clone());
+
+    thread::spawn(move || {
+        let mut reminders = ReminderState::default();
+        // Local day of the last daily pass (trash purge, streak freezes).
+        let mut last_daily_pass: Option<NaiveDate> = None;
+        // Jobs run for scheduled minutes after this point.
+        let mut last_job_check = Local::now().naive_local();
+
+        loop {
+            thread::sleep(TICK);
+            crate::tray::refresh_tray_menu(&app);
+
+            if let Err(error) = commands::countdowns::complete_due_countdowns_for_app(&app) {
+                eprintln!("Countdown check failed: {error}");
+            }
+
+            let break_reminder_minutes = match read_i64_setting(&app, "break_reminder_minutes") {
+                Ok(settings) => settings,
+                Err(error) => {
+                    eprintln!("Scheduler failed to read settings: {error}");
+                    continue;
+                }
+            };
+
+            let now = Local::now().naive_local();
+            let due_jobs =
+                match commands::jobs::due_scheduled_jobs_for_app(&app, last_job_check, now) {
+                    Ok(jobs) => jobs,
+                    Err(error) => {
+                        eprintln!("Scheduler failed to read job schedules: {error}");
+                        Vec::new()
+                    }
+                };
+            last_job_check = now;
+            for job in due_jobs {
+                let result = if job == "reminders" {
+                    send_reminders(&app, break_reminder_minutes, &mut reminders)
+                } else {
+                    commands::jobs::run_scheduled_job_for_app(&app, &job)
+                };
+                if let Err(error) = &result {
+                    eprintln!("Scheduled job {job} failed: {error}");
+                }
+                if let Err(error) = commands::jobs::record_job_run_for_app(&app, &job, &result) {
+                    eprintln!("Failed to record {job} run: {error}");
+                }
+            }
+
+            if let Err(error) = commands::automation::apply_automation_rules_for_app(&app) {
+                eprintln!("Automation rules failed: {error}");
+            }
+
+            let today = Local::now().date_naive();
+            if last_daily_pass != Some(today) {
+                last_daily_pass = Some(today);
+                if let Err(error) = commands::entries::purge_expired_trash_for_app(&app) {
+                    eprintln!("Trash purge failed: {error}");
+                }
+                if let Err(error) = commands::record_streak_freezes_for_app(&app) {
+                    eprintln!("Recording streak freezes failed: {error}");
+                }
+            }
+        }
+    });
+}
+
+/// Refreshes linked issues and pull requests every `linked_items_poll_minutes`
+/// (0 turns polling off).
+fn spawn_linked_items_poll(app: AppHandle) {
+    thread::spawn(move || {
+        let mut last_refresh: Option<Instant> = None;
+
+        loop {
+            thread::sleep(TICK);
+
+            let poll_minutes = match read_i64_setting(&app, "linked_items_poll_minutes") {
+                Ok(minutes) => minutes,
+                Err(error) => {
+                    eprintln!("Linked item poll failed to read settings: {error}");
+                    continue;
+                }
+            };
+            let due = poll_minutes > 0
+                && last_refresh.is_none_or(|last| {
+                    last.elapsed() >= Duration::from_secs(poll_minutes as u64 * 60)
+                });
+            if due {
+                last_refresh = Some(Instant::now());
+                if let Err(error) = commands::integrations::refresh_linked_items_for_app(&app) {
+                    eprintln!("Linked item refresh failed: {error}");
+                }
+            }
+        }
+    });
+}
+
+/// What the `reminders` job already sent, so each reminder goes out once.
+#[derive(Default)]
+struct ReminderState {
+    // Timer runs (task id + start timestamp) that were already reminded about.
+    timer_runs: HashSet<(i64, String)>,
+    // Habits already warned about, per local day.
+    streaks: HashSet<(i64, NaiveDate)>,
+    // Local day the weekly goal check-in was last sent.
+    goal_checkin: Option<NaiveDate>,
+    // Local day the journal reminder was last sent.
+    journal: Option<NaiveDate>,
+}
+
+/// The `reminders` job: every check runs even if an earlier one fails, and the
+/// failures are reported together.
+fn send_reminders(
+    app: &AppHandle,
+    break_reminder_minutes: i64,
+    sent: &mut ReminderState,
+) -> Result<(), String> {
+    let mut errors = Vec::new();
+    if break_reminder_minutes > 0 {
+        if let Err(error) = send_break_reminders(app, break_reminder_minutes, &mut sent.timer_runs)
+        {
+            errors.push(format!("break reminder: {error}"));
+        }
+    }
+    if let Err(error) = send_streak_risk_reminders(app, &mut sent.streaks) {
+        errors.push(format!("streak risk: {error}"));
+    }
+    if let Err(error) = send_goal_checkin_reminder(app, &mut sent.goal_checkin) {
+        errors.push(format!("goal check-in: {error}"));
+    }
+    if let Err(error) = send_journal_reminder(app, &mut sent.journal) {
+        errors.push(format!("journal reminder: {error}"));
+    }
+    if let Err(error) = send_due_task_reminders(app) {
+        errors.push(format!("due reminder: {error}"));
+    }
+
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors.join("; "))
+    }
+}
+
+fn read_i64_setting(app: &AppHandle, key: &str) -> Result<i64, String> {
+    let state = app.state::<AppState>();
+    let conn = state.db.lock().map_err(|e| e.to_string())?;
+    commands::settings::read_i64_setting(&conn, key)
+}
+
+/// Notifies once per timer run that has gone `threshold_minutes` without a pause.
+/// The frontend receives a `break-reminder` event so it can offer a pause action;
+/// the tray's "Take a break" item does the same while the window is hidden.
+fn send_break_reminders(
+    app: &AppHandle,
+    threshold_minutes: i64,
+    reminded_timer_runs: &mut HashSet<(i64, String)>,
+) -> Result<(), String> {
+    let (tasks, (title_template, body_template)) = {
+        let state = app.state::<AppState>();
+        let conn = state.db.lock().map_err(|e| e.to_string())?;
+        (
+            commands::tasks::long_running_timer_tasks_in_conn(&conn, threshold_minutes * 60)?,
+            commands::notifications::notification_templates_in_conn(&conn, "break_reminder")?,
+        )
+    };
+
+    let running_runs: HashSet<(i64, String)> = tasks
+        .iter()
+        .filter_map(|task| Some((task.id, task.timer_started_at.clone()?)))
+        .collect();
+    reminded_timer_runs.retain(|run| running_runs.contains(run));
+
+    for task in tasks {
+        let Some(started_at) = task.timer_started_at.clone() else {
+            continue;
+        };
+        if !reminded_timer_runs.insert((task.id, started_at.clone())) {
+            continue;
+        }
+
+        let reminder = BreakReminder {
+            task_id: task.id,
+            title: task.title,
+            running_minutes: commands::elapsed_since(&started_at) / 60,
+        };
+        let duration = crate::tray::format_tracked_seconds(reminder.running_minutes * 60);
+        let values = [
+            ("task", reminder.title.as_str()),
+            ("duration", duration.as_str()),
+        ];
+        if let Err(error) = app
+            .notification()
+            .builder()
+            .title(render_notification_template(&title_template, &values))
+            .body(render_notification_template(&body_template, &values))
+            .show()
+        {
+            eprintln!("Failed to show break reminder: {error}");
+        }
+        if let Err(error) = emit_event(app, "break-reminder", &reminder) {
+            eprintln!("Failed to emit break reminder: {error}");
+        }
+    }
+
+    Ok(())
+}
+
+/// Warns once per habit and day when an active streak is still unlogged after
+/// the configured evening time, and emits `streaks-at-risk` for the dashboard.
+fn send_streak_risk_reminders(
+    app: &AppHandle,
+    reminded_streaks: &mut HashSet<(i64, NaiveDate)>,
+) -> Result<(), String> {
+    let now = Local::now().naive_local();
+    let (at_risk, (title_template, body_template)) = {
+        let state = app.state::<AppState>();
+        let conn = state.db.lock().map_err(|e| e.to_string())?;
+        (
+            commands::habits::streak_risk_reminders_due_in_conn(&conn, now)?,
+            commands::notifications::notification_templates_in_conn(&conn, "streak_risk")?,
+        )
+    };
+
+    let today = now.date();
+    reminded_streaks.retain(|(_, date)| *date == today);
+    let new_risks: Vec<_> = at_risk
+        .into_iter()
+        .filter(|habit| reminded_streaks.insert((habit.habit_id, today)))
+        .collect();
+    if new_risks.is_empty() {
+        return Ok(());
+    }
+
+    for habit in &new_risks {
+        let days = habit.streak_days.to_string();
+        let values = [("habit", habit.title.as_str()), ("days", days.as_str())];
+        if let Err(error) = app
+            .notification()
+            .builder()
+            .title(render_notification_template(&title_template, &values))
+            .body(render_notification_template(&body_template, &values))
+            .show()
+        {
+            eprintln!("Failed to show streak reminder: {error}");
+        }
+    }
+    if let Err(error) = emit_event(app, "streaks-at-risk", &new_risks) {
+        eprintln!("Failed to emit streak reminder: {error}");
+    }
+
+    Ok(())
+}
+
+/// Prompts once on the configured check-in day for active goals that have gone a
+/// week without an update, and emits `goal-checkin-due` for the dashboard card.
+fn send_goal_checkin_reminder(
+    app: &AppHandle,
+    last_goal_checkin: &mut Option<NaiveDate>,
+) -> Result<(), String> {
+    let now = Local::now().naive_local();
+    if *last_goal_checkin == Some(now.date()) {
+        return Ok(());
+    }
+    let (goals, (title_template, body_template)) = {
+        let state = app.state::<AppState>();
+        let conn = state.db.lock().map_err(|e| e.to_string())?;
+        (
+            commands::goals::goal_checkin_due_in_conn(&conn, now)?,
+            commands::notifications::notification_templates_in_conn(&conn, "goal_checkin")?,
+        )
+    };
+    if goals.is_empty() {
+        return Ok(());
+    }
+    *last_goal_checkin = Some(now.date());
+
+    let titles = goals
+        .iter()
+        .map(|goal| format!("\"{}\"", goal.title))
+        .collect::<Vec<_>>()
+        .join(", ");
+    let count = goals.len().to_string();
+    let values = [("goals", titles.as_str()), ("count", count.as_str())];
+    if let Err(error) = app
+        .notification()
+        .builder()
+        .title(render_notification_template(&title_template, &values))
+        .body(render_notification_template(&body_template, &values))
+        .show()
+    {
+        eprintln!("Failed to show goal check-in: {error}");
+    }
+    if let Err(error) = emit_event(app, "goal-checkin-due", &goals) {
+        eprintln!("Failed to emit goal check-in: {error}");
+    }
+
+    Ok(())
+}
+
+/// Reminds once a day, after the configured time, when today's entry is still
+/// empty; the `journal-reminder` event lets the open window show it as well.
+fn send_journal_reminder(
+    app: &AppHandle,
+    last_journal_reminder: &mut Option<NaiveDate>,
+) -> Result<(), String> {
+    let now = Local::now().naive_local();
+    if *last_journal_reminder == Some(now.date()) {
+        return Ok(());
+    }
+    let (due, (title_template, body_template)) = {
+        let state = app.state::<AppState>();
+        let conn = state.db.lock().map_err(|e| e.to_string())?;
+        (
+            commands::entries::journal_reminder_due_in_conn(&conn, now)?,
+            commands::notifications::notification_templates_in_conn(&conn, "journal_reminder")?,
+        )
+    };
+    if !due {
+        return Ok(());
+    }
+    *last_journal_reminder = Some(now.date());
+
+    let date = now.date().format("%Y-%m-%d").to_string();
+    let values = [("date", date.as_str())];
+    if let Err(error) = app
+        .notification()
+        .builder()
+        .title(render_notification_template(&title_template, &values))
+        .body(render_notification_template(&body_template, &values))
+        .show()
+    {
+        eprintln!("Failed to show journal reminder: {error}");
+    }
+    if let Err(error) = emit_event(app, "journal-reminder", ()) {
+        eprintln!("Failed to emit journal reminder: {error}");
+    }
+
+    Ok(())
+}
+
+/// Notifies about tasks coming due at the `due_reminder_leads` lead times. What was
+/// sent is kept in the database, so a restart does not repeat reminders. During
+/// quiet hours nothing is taken, so pending reminders go out once they end.
+fn send_due_task_reminders(app: &AppHandle) -> Result<(), String> {
+    let (reminders, (title_template, body_template)) = {
+        let state = app.state::<AppState>();
+        let conn = state.db.lock().map_err(|e| e.to_string())?;
+        if commands::notifications::in_quiet_hours_in_conn(&conn, Local::now().time())? {
+            return Ok(());
+        }
+        (
+            commands::due_reminders::take_due_task_reminders_in_conn(&conn, Utc::now())?,
+            commands::notifications::notification_templates_in_conn(&conn, "due_reminder")?,
+        )
+    };
+    if reminders.is_empty() {
+        return Ok(());
+    }
+
+    for reminder in &reminders {
+        let time_left = commands::due_reminders::format_time_left(reminder.minutes_left);
+        let values = [
+            ("task", reminder.title.as_str()),
+            ("time_left", time_left.as_str()),
+            ("date", reminder.due_date.as_str()),
+        ];
+        if let Err(error) = app
+            .notification()
+            .builder()
+            .title(render_notification_template(&title_template, &values))
+            .body(render_notification_template(&body_template, &values))
+            .show()
+        {
+            eprintln!("Failed to show due reminder: {error}");
+        }
+    }
+    if let Err(error) = emit_event(app, "task-due-reminder", &reminders) {
+        eprintln!("Failed to emit due reminders: {error}");
+    }
+
+    Ok(())
+}
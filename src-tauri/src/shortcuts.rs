@@ -0,0 +1,99 @@
+use crate::commands::events::emit_event;
+use crate::commands::tasks::{toggle_active_timer_in_conn, TimerToggleOutcome};
+use crate::commands::{settings, AppState};
+use std::sync::Mutex;
+use tauri::{AppHandle, Manager};
+use tauri_plugin_global_shortcut::{GlobalShortcutExt, Shortcut, ShortcutState};
+use tauri_plugin_notification::NotificationExt;
+
+pub const TIMER_TOGGLE_SHORTCUT_SETTING: &str = "timer_toggle_shortcut";
+
+/// The timer toggle shortcut currently registered, so replacing it leaves any other
+/// global shortcut alone.
+#[derive(Default)]
+pub struct TimerShortcutState(Mutex<Option<Shortcut>>);
+
+fn toggle_active_timer(app: &AppHandle) {
+    let Some(state) = app.try_state::<AppState>() else {
+        return;
+    };
+
+    let outcome = state
+        .db
+        .lock()
+        .map_err(|e| e.to_string())
+        .and_then(|conn| toggle_active_timer_in_conn(&conn));
+    let (title, body) = match outcome {
+        Ok(TimerToggleOutcome::Paused(task_title)) => ("Timer paused", task_title),
+        Ok(TimerToggleOutcome::Started(task_title)) => ("Timer started", task_title),
+        Ok(TimerToggleOutcome::NoTask) => (
+            "No timer to resume",
+            "Start a task timer once to enable the shortcut.".to_string(),
+        ),
+        Err(error) => {
+            eprintln!("Failed to toggle timer from shortcut: {error}");
+            return;
+        }
+    };
+
+    crate::tray::refresh_tray_menu(app);
+    if let Err(error) = emit_event(app, "timers-changed", ()) {
+        eprintln!("Failed to emit timer change: {error}");
+    }
+    if let Err(error) = app.notification().builder().title(title).body(body).show() {
+        eprintln!("Failed to show timer notification: {error}");
+    }
+}
+
+/// Replaces the timer toggle shortcut. An empty value disables it. The new shortcut is
+/// registered before the previous one is released, so an invalid value keeps the
+/// current shortcut working.
+pub fn register_timer_toggle_shortcut(app: &AppHandle, shortcut: &str) -> Result<(), String> {
+    let next = if shortcut.is_empty() {
+        None
+    } else {
+        Some(
+            shortcut
+                .parse::<Shortcut>()
+                .map_err(|e| format!("Invalid shortcut \"{}\": {}", shortcut, e))?,
+        )
+    };
+    let state = app.state::<TimerShortcutState>();
+    let mut current = state.0.lock().map_err(|e| e.to_string())?;
+    if *current == next {
+        return Ok(());
+    }
+
+    let global_shortcut = app.global_shortcut();
+    if let Some(next) = next {
+        global_shortcut
+            .on_shortcut(next, |app, _shortcut, event| {
+                if event.state == ShortcutState::Pressed {
+                    toggle_active_timer(app);
+                }
+            })
+            .map_err(|e| format!("Invalid shortcut \"{}\": {}", shortcut, e))?;
+    }
+    if let Some(previous) = *current {
+        if let Err(error) = global_shortcut.unregister(previous) {
+            // Keep the previous shortcut as the only one registered.
+            if let Some(next) = next {
+                let _ = global_shortcut.unregister(next);
+            }
+            return Err(error.to_string());
+        }
+    }
+    *current = next;
+
+    Ok(())
+}
+
+pub fn setup_shortcuts(app: &AppHandle) -> Result<(), String> {
+    let shortcut = {
+        let state = app.state::<AppState>();
+        let conn = state.db.lock().map_err(|e| e.to_string())?;
+        settings::read_setting(&conn, TIMER_TOGGLE_SHORTCUT_SETTING)?
+    };
+
+    register_timer_toggle_shortcut(app, shortcut.trim())
+}
@@ -0,0 +1,177 @@
+use crate::commands::events::emit_event;
+use crate::{capture_server, commands, db, scheduler, shortcuts, tray};
+use rusqlite::Connection;
+use serde::Serialize;
+use std::error::Error;
+use std::sync::Mutex;
+use std::thread;
+use tauri::{AppHandle, Manager, State, WebviewUrl, WebviewWindowBuilder};
+
+pub(crate) const MAIN_WINDOW_LABEL: &str = "main";
+pub(crate) const UPGRADE_WINDOW_LABEL: &str = "upgrade";
+
+pub(crate) struct TrayAvailability(pub bool);
+
+/// Progress of the startup migrations, streamed to the upgrade window as `migration-progress`.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct MigrationProgress {
+    pub completed: usize,
+    pub total: usize,
+    /// Version currently being applied.
+    pub version: Option<i64>,
+    pub done: bool,
+    pub error: Option<String>,
+}
+
+pub struct MigrationState(Mutex<MigrationProgress>);
+
+impl MigrationState {
+    /// The upgrade window may only be closed once migrations stopped running.
+    pub(crate) fn is_running(&self) -> bool {
+        self.0
+            .lock()
+            .map(|progress| !progress.done && progress.error.is_none())
+            .unwrap_or(false)
+    }
+}
+
+fn report(app: &AppHandle, update: impl FnOnce(&mut MigrationProgress)) {
+    let state = app.state::<MigrationState>();
+    let progress = match state.0.lock() {
+        Ok(mut progress) => {
+            update(&mut progress);
+            progress.clone()
+        }
+        Err(error) => {
+            eprintln!("Migration progress lock poisoned: {error}");
+            return;
+        }
+    };
+    if let Err(error) = emit_event(app, "migration-progress", &progress) {
+        eprintln!("Failed to emit migration progress: {error}");
+    }
+}
+
+/// Opens the database and brings the app up. Fresh or current databases migrate inline;
+/// upgrading an existing journal runs on a worker thread behind a small progress
+/// window so long migrations (backfills, index rebuilds) never show a frozen window.
+pub(crate) fn start(app: &AppHandle) -> Result<(), Box<dyn Error>> {
+    let app_data_dir = app.path().app_data_dir()?;
+    let conn = db::open(app_data_dir)?;
+    let pending = db::pending_migrations(&conn)?;
+    let is_new_database = pending.len() as i64 == db::SCHEMA_VERSION;
+
+    app.manage(MigrationState(Mutex::new(MigrationProgress {
+        total: pending.len(),
+        done: pending.is_empty(),
+        ..MigrationProgress::default()
+    })));
+
+    if pending.is_empty() || is_new_database {
+        db::migrate(&conn, &|_| {})?;
+        report(app, |progress| {
+            progress.completed = progress.total;
+            progress.done = true;
+        });
+        return finish(app, conn);
+    }
+
+    WebviewWindowBuilder::new(app, UPGRADE_WINDOW_LABEL, WebviewUrl::default())
+        .title("Dev Journal")
+        .inner_size(420.0, 190.0)
+        .resizable(false)
+        .center()
+        .build()?;
+
+    let app = app.clone();
+    thread::spawn(move || {
+        let result = db::migrate(&conn, &|version| {
+            let completed = pending.iter().position(|pending| *pending == version);
+            report(&app, |progress| {
+                progress.completed = completed.unwrap_or(progress.completed);
+                progress.version = Some(version);
+            });
+        });
+
+        match result {
+            Ok(()) => {
+                report(&app, |progress| {
+                    progress.completed = progress.total;
+                    progress.done = true;
+                });
+                let handle = app.clone();
+                let scheduled = app.run_on_main_thread(move || {
+                    if let Err(error) = finish(&handle, conn) {
+                        eprintln!("Startup failed after migrations: {error}");
+                    }
+                });
+                if let Err(error) = scheduled {
+                    eprintln!("Failed to finish startup: {error}");
+                }
+            }
+            Err(error) => {
+                eprintln!("Database migration failed: {error}");
+                report(&app, |progress| progress.error = Some(error.to_string()));
+            }
+        }
+    });
+
+    Ok(())
+}
+
+/// Everything that needs a migrated database: shared state, tray, shortcuts,
+/// background jobs and finally the main window (declared with `create: false`).
+fn finish(app: &AppHandle, conn: Connection) -> Result<(), Box<dyn Error>> {
+    let journal_cipher = commands::encryption::JournalCipher::from_conn(&conn)?;
+    app.manage(commands::performance::PerformanceState::from_conn(&conn)?);
+    app.manage(commands::slack_status::SlackStatusState::start());
+    let presence = commands::presence::presence_in_conn(&conn, None)?;
+    app.manage(commands::AppState {
+        db: Mutex::new(conn),
+        journal_cipher: Mutex::new(journal_cipher),
+        page_keys: Mutex::new(Default::default()),
+        presence: Mutex::new(presence),
+    });
+
+    // Setup Tray
+    let tray_available = match tray::setup_tray(app) {
+        Ok(()) => true,
+        Err(error) => {
+            eprintln!("Tray setup failed, continuing without tray support: {error}");
+            false
+        }
+    };
+    app.manage(TrayAvailability(tray_available));
+
+    // Setup global shortcuts
+    if let Err(error) = shortcuts::setup_shortcuts(app) {
+        eprintln!("Global shortcut setup failed: {error}");
+    }
+
+    scheduler::spawn(app.clone());
+    capture_server::spawn(app.clone());
+
+    if let Some(config) = app
+        .config()
+        .app
+        .windows
+        .iter()
+        .find(|window| window.label == MAIN_WINDOW_LABEL)
+    {
+        WebviewWindowBuilder::from_config(app, config)?.build()?;
+    }
+    if let Some(window) = app.get_webview_window(UPGRADE_WINDOW_LABEL) {
+        window.destroy()?;
+    }
+
+    Ok(())
+}
+
+/// Lets the upgrade window catch up on progress emitted before it started listening.
+#[tauri::command]
+pub fn get_migration_progress(
+    state: State<'_, MigrationState>,
+) -> Result<MigrationProgress, String> {
+    let progress = state.0.lock().map_err(|e| e.to_string())?;
+    Ok(progress.clone())
+}
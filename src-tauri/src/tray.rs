@@ -1,13 +1,20 @@
+use crate::commands::events::emit_event;
+use crate::commands::{self, AppState};
+use crate::models::{Presence, Task};
 use std::sync::Mutex;
 use tauri::{
-    menu::{Menu, MenuItem},
+    menu::{CheckMenuItem, Menu, MenuItem, PredefinedMenuItem, Submenu},
     tray::{MouseButton, MouseButtonState, TrayIcon, TrayIconBuilder, TrayIconEvent},
     AppHandle, Manager,
 };
 
 pub struct TrayState(pub Mutex<Option<TrayIcon>>);
 
-fn show_main_window(app: &AppHandle) {
+const TIMER_ITEM_PREFIX: &str = "timer:";
+const RECENT_TIMER_TASKS_LIMIT: i64 = 5;
+const TAKE_BREAK_ITEM_ID: &str = "take-break";
+
+pub(crate) fn show_main_window(app: &AppHandle) {
     if let Some(window) = app.get_webview_window("main") {
         if let Err(error) = window.show() {
             eprintln!("Failed to show main window from tray: {error}");
@@ -18,10 +25,229 @@ fn show_main_window(app: &AppHandle) {
     }
 }
 
-pub fn setup_tray(app: &AppHandle) -> Result<(), Box<dyn std::error::Error>> {
+pub(crate) fn format_tracked_seconds(seconds: i64) -> String {
+    let minutes = seconds.max(0) / 60;
+    if minutes >= 60 {
+        format!("{}h {:02}m", minutes / 60, minutes % 60)
+    } else {
+        format!("{}m", minutes)
+    }
+}
+
+fn format_countdown_remaining(ends_at: &str) -> String {
+    let remaining_seconds = chrono::DateTime::parse_from_rfc3339(ends_at)
+        .map(|ends_at| (ends_at.with_timezone(&chrono::Utc) - chrono::Utc::now()).num_seconds())
+        .unwrap_or(0);
+    // Round up so a countdown never reads "0m left" while still running.
+    format_tracked_seconds(remaining_seconds + 59)
+}
+
+/// Disabled header line mirroring the shared presence, e.g. "Now: Fix login (12m)".
+fn presence_label(presence: &Presence) -> Option<String> {
+    let task = presence
+        .task_title
+        .as_ref()
+        .map(|title| match presence.since.as_deref() {
+            Some(since) => format!(
+                "Now: {} ({})",
+                title,
+                format_tracked_seconds(commands::elapsed_since(since))
+            ),
+            None => format!("Now: {}", title),
+        });
+    let focus = presence.focus.as_ref().map(|focus| {
+        let kind = if focus.kind == "focus" {
+            "Focus"
+        } else {
+            "Break"
+        };
+        format!(
+            "{} ({} left)",
+            kind,
+            format_countdown_remaining(&focus.ends_at)
+        )
+    });
+
+    match (task, focus) {
+        (Some(task), Some(focus)) => Some(format!("{} · {}", task, focus)),
+        (task, focus) => task.or(focus),
+    }
+}
+
+fn build_timers_submenu(app: &AppHandle, tasks: Vec<Task>) -> tauri::Result<Submenu<tauri::Wry>> {
+    let submenu = Submenu::with_id(app, "timers", "Timers", true)?;
+    if tasks.is_empty() {
+        let empty_i =
+            MenuItem::with_id(app, "timers-empty", "No recent tasks", false, None::<&str>)?;
+        submenu.append(&empty_i)?;
+        return Ok(submenu);
+    }
+
+    for task in tasks {
+        let running_seconds = task
+            .timer_started_at
+            .as_deref()
+            .map(commands::elapsed_since)
+            .unwrap_or(0);
+        let label = format!(
+            "{} ({})",
+            task.title,
+            format_tracked_seconds(task.timer_accumulated_seconds + running_seconds)
+        );
+        let item = CheckMenuItem::with_id(
+            app,
+            format!("{}{}", TIMER_ITEM_PREFIX, task.id),
+            label,
+            true,
+            task.timer_started_at.is_some(),
+            None::<&str>,
+        )?;
+        submenu.append(&item)?;
+    }
+
+    Ok(submenu)
+}
+
+fn build_tray_menu(app: &AppHandle) -> tauri::Result<Menu<tauri::Wry>> {
+    let tasks = app
+        .try_state::<AppState>()
+        .and_then(|state| {
+            let conn = state.db.lock().ok()?;
+            commands::tasks::get_recent_timer_tasks_in_conn(&conn, RECENT_TIMER_TASKS_LIMIT).ok()
+        })
+        .unwrap_or_default();
+    let any_running = tasks.iter().any(|task| task.timer_started_at.is_some());
+    let countdowns = app
+        .try_state::<AppState>()
+        .and_then(|state| {
+            let conn = state.db.lock().ok()?;
+            commands::countdowns::get_active_countdowns_in_conn(&conn).ok()
+        })
+        .unwrap_or_default();
+
+    let presence = app
+        .try_state::<AppState>()
+        .and_then(|state| state.presence().ok())
+        .unwrap_or_default();
+
+    let timers_i = build_timers_submenu(app, tasks)?;
+    let take_break_i = MenuItem::with_id(
+        app,
+        TAKE_BREAK_ITEM_ID,
+        "Take a break",
+        any_running,
+        None::<&str>,
+    )?;
+    let separator_i = PredefinedMenuItem::separator(app)?;
     let quit_i = MenuItem::with_id(app, "quit", "Quit", true, None::<&str>)?;
     let show_i = MenuItem::with_id(app, "show", "Show", true, None::<&str>)?;
-    let menu = Menu::with_items(app, &[&show_i, &quit_i])?;
+    let menu = Menu::new(app)?;
+    if let Some(label) = presence_label(&presence) {
+        menu.append(&MenuItem::with_id(
+            app,
+            "presence",
+            label,
+            false,
+            None::<&str>,
+        )?)?;
+        menu.append(&PredefinedMenuItem::separator(app)?)?;
+    }
+    menu.append(&timers_i)?;
+    menu.append(&take_break_i)?;
+
+    // Countdowns are display-only; they are started and cancelled from the app.
+    if !countdowns.is_empty() {
+        menu.append(&PredefinedMenuItem::separator(app)?)?;
+        for countdown in countdowns {
+            let item = MenuItem::with_id(
+                app,
+                format!("countdown:{}", countdown.id),
+                format!(
+                    "{} ({} left)",
+                    countdown.label,
+                    format_countdown_remaining(&countdown.ends_at)
+                ),
+                false,
+                None::<&str>,
+            )?;
+            menu.append(&item)?;
+        }
+    }
+
+    menu.append(&separator_i)?;
+    menu.append(&show_i)?;
+    menu.append(&quit_i)?;
+    Ok(menu)
+}
+
+/// Rebuilds the tray menu so the Timers submenu reflects the current timer state.
+/// Presence is refreshed first, so every timer change also updates it.
+pub fn refresh_tray_menu(app: &AppHandle) {
+    commands::presence::refresh_presence(app);
+    let Some(state) = app.try_state::<TrayState>() else {
+        return;
+    };
+    let Ok(guard) = state.0.lock() else {
+        return;
+    };
+    let Some(tray) = guard.as_ref() else {
+        return;
+    };
+
+    match build_tray_menu(app) {
+        Ok(menu) => {
+            if let Err(error) = tray.set_menu(Some(menu)) {
+                eprintln!("Failed to update tray menu: {error}");
+            }
+        }
+        Err(error) => eprintln!("Failed to build tray menu: {error}"),
+    }
+}
+
+fn toggle_timer_from_tray(app: &AppHandle, task_id: i64) {
+    let Some(state) = app.try_state::<AppState>() else {
+        return;
+    };
+
+    let result = state
+        .db
+        .lock()
+        .map_err(|e| e.to_string())
+        .and_then(|conn| commands::tasks::toggle_task_timer_in_conn(&conn, task_id));
+    if let Err(error) = result {
+        eprintln!("Failed to toggle timer from tray: {error}");
+        return;
+    }
+
+    refresh_tray_menu(app);
+    if let Err(error) = emit_event(app, "timers-changed", task_id) {
+        eprintln!("Failed to emit timer change: {error}");
+    }
+}
+
+fn pause_all_timers_from_tray(app: &AppHandle) {
+    let Some(state) = app.try_state::<AppState>() else {
+        return;
+    };
+
+    let result = state
+        .db
+        .lock()
+        .map_err(|e| e.to_string())
+        .and_then(|conn| commands::tasks::pause_all_timers_in_conn(&conn));
+    if let Err(error) = result {
+        eprintln!("Failed to pause timers from tray: {error}");
+        return;
+    }
+
+    refresh_tray_menu(app);
+    if let Err(error) = emit_event(app, "timers-changed", ()) {
+        eprintln!("Failed to emit timer change: {error}");
+    }
+}
+
+pub fn setup_tray(app: &AppHandle) -> Result<(), Box<dyn std::error::Error>> {
+    let menu = build_tray_menu(app)?;
 
     let mut tray_builder = TrayIconBuilder::new()
         .menu(&menu)
@@ -29,12 +255,22 @@ pub fn setup_tray(app: &AppHandle) -> Result<(), Box<dyn std::error::Error>> {
         .on_menu_event(|app: &tauri::AppHandle, event: tauri::menu::MenuEvent| {
             match event.id.as_ref() {
                 "quit" => {
-                    std::process::exit(0);
+                    crate::commands::shutdown::request_quit(app);
                 }
                 "show" => {
                     show_main_window(app);
                 }
-                _ => {}
+                TAKE_BREAK_ITEM_ID => {
+                    pause_all_timers_from_tray(app);
+                }
+                id => {
+                    if let Some(task_id) = id
+                        .strip_prefix(TIMER_ITEM_PREFIX)
+                        .and_then(|value| value.parse::<i64>().ok())
+                    {
+                        toggle_timer_from_tray(app, task_id);
+                    }
+                }
             }
         })
         .on_tray_icon_event(
@@ -1,12 +1,13 @@
-import { lazy, Suspense, useEffect, useMemo, useState } from "react";
+import { lazy, Suspense, useCallback, useEffect, useMemo, useState } from "react";
 import { Layout } from "./components/Layout";
 import type { CommandAction } from "./components/CommandPalette";
 import { format } from "date-fns";
-import { Box, CircularProgress } from "@mui/material";
-import { useEntries } from "./hooks/useEntries";
+import { Box, Button, CircularProgress, TextField } from "@mui/material";
+import { getCurrentWindow } from "@tauri-apps/api/window";
+import { useEntriesPage, usePinnedEntries, useRandomEntry } from "./hooks/useEntries";
 import { usePages } from "./hooks/usePages";
 import { useGoals } from "./hooks/useGoals";
-import { useHabits } from "./hooks/useHabits";
+import { useHabits, useLogHabitsBulk } from "./hooks/useHabits";
 import { useProjects } from "./hooks/useProjects";
 import { useMeetings } from "./hooks/useMeetings";
 import { useThemeContext } from "./theme/ThemeContext";
@@ -14,6 +15,11 @@ import { useI18n } from "./i18n/I18nContext";
 import { EntryForm } from "./components/EntryForm";
 import { PlannerBoard } from "./components/PlannerBoard";
 import { CommandPalette } from "./components/CommandPalette";
+import { JournalUnlockDialog } from "./components/JournalUnlockDialog";
+import { ShutdownChecklistDialog } from "./components/ShutdownChecklistDialog";
+import { WhatsNewDialog } from "./components/WhatsNewDialog";
+import { AccessibleSummaryRegion } from "./components/AccessibleSummaryRegion";
+import { PageTree } from "./components/page-editor/PageTree";
 import { AnimatePresence, motion } from "framer-motion";
 import { useAppNotifications } from "./notifications/AppNotifications";
 import { useAppShellPreferences } from "./hooks/useAppShellPreferences";
@@ -21,14 +27,25 @@ import { useNotificationPermission } from "./hooks/useNotificationPermission";
 import { useJournalReminder } from "./hooks/useJournalReminder";
 import { useMeetingReminders } from "./hooks/useMeetingReminders";
 import { useAppUsageTracking } from "./hooks/useAppUsageTracking";
+import { useBackendEvents } from "./hooks/useBackendEvents";
+import { useWindowSync } from "./hooks/useWindowSync";
+import { closeWindow, openWindow } from "./api";
 import { dispatchTasksFilterPreference } from "./utils/preferencesStorage";
-import type { AppTab } from "./types/shell";
+import type { AppTab, DetachedView, DetachedWindow } from "./types/shell";
+
+const RECENT_ENTRIES_LIMIT = 10;
+// The random entry command skips the last month so it surfaces something worth rereading.
+const RANDOM_ENTRY_MIN_AGE_DAYS = 30;
 
 const JournalScreen = lazy(() =>
   Promise.all([
     import("./components/WeeklySummary"),
     import("./components/Stats"),
-  ]).then(([weeklySummaryModule, statsModule]) => ({
+    import("./components/GitCommits"),
+    import("./components/CapturedLinks"),
+    import("./components/OnThisDay"),
+    import("./components/IncidentLog"),
+  ]).then(([weeklySummaryModule, statsModule, gitCommitsModule, capturedLinksModule, onThisDayModule, incidentLogModule]) => ({
     default: ({
       date,
       previewEnabled,
@@ -44,6 +61,10 @@ const JournalScreen = lazy(() =>
           previewEnabled={previewEnabled}
           autosaveEnabled={autosaveEnabled}
         />
+        <gitCommitsModule.GitCommits date={date} />
+        <capturedLinksModule.CapturedLinks />
+        <onThisDayModule.OnThisDay date={date} />
+        <incidentLogModule.IncidentLog />
         <Box sx={{ mt: 4 }}>
           <weeklySummaryModule.WeeklySummary />
         </Box>
@@ -92,10 +113,16 @@ const TabLoadingFallback = () => (
   </Box>
 );
 
-function App() {
-  const [activeTab, setActiveTab] = useState<AppTab>("planner");
+interface AppProps {
+  /** Set when this window was opened with `open_window` to show a single view. */
+  detachedWindow?: DetachedWindow | null;
+}
+
+function App({ detachedWindow = null }: AppProps) {
+  const [activeTab, setActiveTab] = useState<AppTab>(detachedWindow?.tab ?? "planner");
   const [selectedDate, setSelectedDate] = useState(format(new Date(), "yyyy-MM-dd"));
-  const [selectedPageId, setSelectedPageId] = useState<number | null>(null);
+  const [selectedPageId, setSelectedPageId] = useState<number | null>(detachedWindow?.pageId ?? null);
+  const [newPageParentId, setNewPageParentId] = useState<number | null>(null);
   const {
     reminderEnabled,
     setReminderEnabled,
@@ -110,10 +137,14 @@ function App() {
   } = useAppShellPreferences();
   const [commandPaletteOpen, setCommandPaletteOpen] = useState(false);
 
-  const { data: entries } = useEntries();
+  // The shell only needs recent history for the palette shortcuts.
+  const { data: entries } = useEntriesPage(RECENT_ENTRIES_LIMIT);
+  const { data: pinnedEntries } = usePinnedEntries();
   const { data: pages } = usePages();
   const { data: goals } = useGoals();
   const { data: habits } = useHabits();
+  const { mutate: logHabitsBulk } = useLogHabitsBulk();
+  const { mutate: findRandomEntry } = useRandomEntry();
   const { data: projects } = useProjects();
   const { data: meetings } = useMeetings();
   const { appearanceMode, setAppearanceMode } = useThemeContext();
@@ -121,23 +152,25 @@ function App() {
   const { notify } = useAppNotifications();
   const ensureNotificationPermission = useNotificationPermission();
 
-  useJournalReminder({
-    entries,
-    reminderEnabled,
-    reminderHour,
-    ensureNotificationPermission,
-    notify,
-    t,
-  });
+  useJournalReminder({ reminderEnabled, reminderHour });
 
   useMeetingReminders({
-    meetings,
+    meetings: detachedWindow ? undefined : meetings,
     ensureNotificationPermission,
     notify,
     t,
   });
 
   useAppUsageTracking();
+  useBackendEvents({ notifications: !detachedWindow });
+  useWindowSync();
+
+  const openInWindow = useCallback(
+    (view: DetachedView) => {
+      openWindow(view).catch((error) => notify(String(error), "error"));
+    },
+    [notify]
+  );
 
   useEffect(() => {
     const handleKeyDown = (event: KeyboardEvent) => {
@@ -175,6 +208,26 @@ function App() {
           setSelectedDate(today);
         },
       },
+      {
+        id: "open-random-entry",
+        title: t("Open a random past entry"),
+        subtitle: t("Reread something you wrote at least a month ago"),
+        section: t("Quick Actions"),
+        keywords: ["random", "reflect", "memory", "past", "journal"],
+        onSelect: () => {
+          findRandomEntry(RANDOM_ENTRY_MIN_AGE_DAYS, {
+            onSuccess: (entry) => {
+              if (!entry) {
+                notify(t("No entries older than a month yet."), "info");
+                return;
+              }
+              setActiveTab("journal");
+              setSelectedDate(entry.date);
+            },
+            onError: (error) => notify(String(error), "error"),
+          });
+        },
+      },
       {
         id: "open-focus",
         title: t("Open Focus Session"),
@@ -256,6 +309,7 @@ function App() {
         onSelect: () => {
           setActiveTab("page");
           setSelectedPageId(null);
+          setNewPageParentId(null);
         },
       },
       {
@@ -268,6 +322,22 @@ function App() {
           setActiveTab("settings");
         },
       },
+      {
+        id: "window-journal",
+        title: t("Open Journal in New Window"),
+        subtitle: t("Keep the journal next to another view"),
+        section: t("Windows"),
+        keywords: ["window", "split", "journal", "detach"],
+        onSelect: () => openInWindow("journal"),
+      },
+      {
+        id: "window-tasks",
+        title: t("Open Tasks Board in New Window"),
+        subtitle: t("Keep the board next to another view"),
+        section: t("Windows"),
+        keywords: ["window", "split", "tasks", "board", "detach"],
+        onSelect: () => openInWindow("tasks"),
+      },
       {
         id: "toggle-theme-mode",
         title: t("Switch to {mode} Mode", {
@@ -294,7 +364,21 @@ function App() {
       },
     ];
 
-    (entries ?? []).slice(0, 10).forEach((entry) => {
+    (pinnedEntries ?? []).forEach((entry) => {
+      actions.push({
+        id: `pinned-entry-${entry.date}`,
+        title: t("Open pinned entry: {date}", { date: entry.date }),
+        subtitle: entry.yesterday.split("\n")[0] || entry.today.split("\n")[0] || t("Jump to saved daily entry"),
+        section: t("Pinned"),
+        keywords: ["pinned", "favorite", "retro", entry.date],
+        onSelect: () => {
+          setActiveTab("journal");
+          setSelectedDate(entry.date);
+        },
+      });
+    });
+
+    (entries ?? []).forEach((entry) => {
       actions.push({
         id: `entry-${entry.date}`,
         title: `Open Journal: ${entry.date}`,
@@ -335,6 +419,29 @@ function App() {
       });
     });
 
+    const todayKey = format(new Date(), "yyyy-MM-dd");
+    const openHabitIds = (habits ?? [])
+      .filter((habit) => !habit.completed_dates.includes(todayKey))
+      .map((habit) => habit.id);
+    if (openHabitIds.length > 0) {
+      actions.push({
+        id: "log-all-habits-today",
+        title: t("Log all habits for today"),
+        subtitle: t("{count} habits not yet done today", { count: openHabitIds.length }),
+        section: t("Habits"),
+        keywords: ["habit", "routine", "morning", "log", "done"],
+        onSelect: () => {
+          logHabitsBulk(
+            { date: todayKey, habit_ids: openHabitIds },
+            {
+              onSuccess: (logged) => notify(t("Logged {count} habits", { count: logged.length }), "success"),
+              onError: (error) => notify(String(error), "error"),
+            }
+          );
+        },
+      });
+    }
+
     (habits ?? []).slice(0, 10).forEach((habit) => {
       actions.push({
         id: `habit-${habit.id}`,
@@ -362,7 +469,23 @@ function App() {
     });
 
     return actions;
-  }, [appearanceMode, entries, goals, habits, language, pages, projects, setAppearanceMode, setLanguage, t]);
+  }, [
+    appearanceMode,
+    entries,
+    findRandomEntry,
+    goals,
+    habits,
+    language,
+    logHabitsBulk,
+    notify,
+    openInWindow,
+    pages,
+    pinnedEntries,
+    projects,
+    setAppearanceMode,
+    setLanguage,
+    t,
+  ]);
 
   const renderActiveTab = () => {
     if (activeTab === "journal") {
@@ -456,23 +579,91 @@ function App() {
       );
     }
 
+    if (detachedWindow) {
+      return (
+        <Suspense fallback={<TabLoadingFallback />}>
+          <PageEditor
+            pageId={selectedPageId}
+            previewEnabled={pagePreviewEnabled}
+            autosaveEnabled={autosaveEnabled}
+            onSaveSuccess={(id) => {
+              setSelectedPageId(id);
+            }}
+            onDeleteSuccess={() => {
+              closeWindow(getCurrentWindow().label).catch(() => undefined);
+            }}
+            onOpenPage={(id) => openInWindow(`page:${id}`)}
+          />
+        </Suspense>
+      );
+    }
+
     return (
-      <Suspense fallback={<TabLoadingFallback />}>
-        <PageEditor
-          pageId={selectedPageId}
-          previewEnabled={pagePreviewEnabled}
-          autosaveEnabled={autosaveEnabled}
-          onSaveSuccess={(id) => {
-            setSelectedPageId(id);
-          }}
-          onDeleteSuccess={() => {
+      <Box
+        sx={{
+          display: "grid",
+          gridTemplateColumns: { xs: "1fr", lg: "240px minmax(0, 1fr)" },
+          gap: 2,
+          alignItems: "start",
+        }}
+      >
+        <PageTree
+          selectedPageId={selectedPageId}
+          onSelectPage={setSelectedPageId}
+          onCreatePage={(parentId) => {
             setSelectedPageId(null);
+            setNewPageParentId(parentId);
           }}
+          onOpenInWindow={(id) => openInWindow(`page:${id}`)}
         />
-      </Suspense>
+        <Suspense fallback={<TabLoadingFallback />}>
+          <PageEditor
+            pageId={selectedPageId}
+            newPageParentId={newPageParentId}
+            previewEnabled={pagePreviewEnabled}
+            autosaveEnabled={autosaveEnabled}
+            onSaveSuccess={(id) => {
+              setSelectedPageId(id);
+            }}
+            onDeleteSuccess={() => {
+              setSelectedPageId(null);
+              setNewPageParentId(null);
+            }}
+            onOpenPage={setSelectedPageId}
+          />
+        </Suspense>
+      </Box>
     );
   };
 
+  if (detachedWindow) {
+    return (
+      <>
+        <Box sx={{ minHeight: "100vh", px: { xs: 1.5, md: 3 }, py: 2 }}>
+          <Box sx={{ display: "flex", alignItems: "center", gap: 1, mb: 2 }}>
+            {activeTab === "journal" ? (
+              <TextField
+                type="date"
+                size="small"
+                value={selectedDate}
+                onChange={(event) => {
+                  if (event.target.value) setSelectedDate(event.target.value);
+                }}
+              />
+            ) : null}
+            <Box sx={{ flex: 1 }} />
+            <Button size="small" onClick={() => closeWindow(getCurrentWindow().label).catch(() => undefined)}>
+              {t("Close window")}
+            </Button>
+          </Box>
+          {renderActiveTab()}
+        </Box>
+
+        <JournalUnlockDialog />
+      </>
+    );
+  }
+
   return (
     <>
       <Layout
@@ -499,10 +690,23 @@ function App() {
         </AnimatePresence>
       </Layout>
 
+      <JournalUnlockDialog />
+      <WhatsNewDialog />
+      <ShutdownChecklistDialog />
+      <AccessibleSummaryRegion activeTab={activeTab} />
+
       <CommandPalette
         open={commandPaletteOpen}
         onClose={() => setCommandPaletteOpen(false)}
         actions={commandActions}
+        onOpenEntry={(date) => {
+          setActiveTab("journal");
+          setSelectedDate(date);
+        }}
+        onOpenPage={(id) => {
+          setActiveTab("page");
+          setSelectedPageId(id);
+        }}
       />
     </>
   );
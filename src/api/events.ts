@@ -0,0 +1,63 @@
+import { invoke } from "@tauri-apps/api/core";
+import type { EventCallback, UnlistenFn } from "@tauri-apps/api/event";
+import { getCurrentWebviewWindow } from "@tauri-apps/api/webviewWindow";
+
+// Mirrors `EVENT_TOPICS` in src-tauri/src/commands/events.rs.
+export type EventTopic =
+    | "achievements-unlocked"
+    | "appearance-changed"
+    | "automation-rules-applied"
+    | "break-reminder"
+    | "capture-received"
+    | "countdowns-changed"
+    | "digest-ready"
+    | "goal-checkin-due"
+    | "habits-changed"
+    | "integrity-violations"
+    | "journal-reminder"
+    | "linked-items-changed"
+    | "migration-progress"
+    | "presence-changed"
+    | "shutdown-checklist"
+    | "streaks-at-risk"
+    | "task-due-reminder"
+    | "tasks-rolled-over"
+    | "timers-changed"
+    | "update-available";
+
+// Listeners per topic in this window. The backend only sends a window the topics it
+// subscribed to, so the whole set is re-sent whenever it changes; changes made in the
+// same tick go out as one call.
+const topicListeners = new Map<EventTopic, number>();
+let pendingSync: Promise<void> | null = null;
+
+const syncSubscriptions = (): Promise<void> => {
+    if (!pendingSync) {
+        pendingSync = Promise.resolve().then(() => {
+            pendingSync = null;
+            return invoke<string[]>("subscribe_events", { topics: Array.from(topicListeners.keys()) }).then(
+                () => undefined
+            );
+        });
+    }
+    return pendingSync;
+};
+
+// Like `listen`, but bound to this window and subscribed to `topic`, so the backend
+// stops sending this window events nothing in it listens to.
+export const listenTopic = async <T>(topic: EventTopic, handler: EventCallback<T>): Promise<UnlistenFn> => {
+    const unlisten = await getCurrentWebviewWindow().listen<T>(topic, handler);
+    topicListeners.set(topic, (topicListeners.get(topic) ?? 0) + 1);
+    await syncSubscriptions().catch(() => undefined);
+
+    return () => {
+        unlisten();
+        const remaining = (topicListeners.get(topic) ?? 1) - 1;
+        if (remaining > 0) {
+            topicListeners.set(topic, remaining);
+        } else {
+            topicListeners.delete(topic);
+        }
+        syncSubscriptions().catch(() => undefined);
+    };
+};
@@ -1,53 +1,351 @@
 import { invoke } from "@tauri-apps/api/core";
 import type {
+    AccessibleSummary,
+    AccessibleSummaryView,
+    Achievement,
+    AppSetting,
+    AppearanceSettings,
+    WhatsNew,
+    BackupFormat,
+    JournalEncryptionStatus,
     BackupPayload,
+    Countdown,
+    DailyMood,
+    DateShift,
+    DateShiftFilter,
+    IntegrityReport,
+    WorkspaceSnapshot,
+    PerformanceReport,
+    QueryPlanCheck,
     Entry,
+    EntryDateRange,
+    EntrySearchResult,
+    EntrySection,
+    EntrySectionTemplate,
+    EntryTag,
     Goal,
     GoalMilestone,
+    GoalCheckin,
+    GoalReflection,
     GoalStatus,
     Habit,
     HabitWithLogs,
+    IntegrationProvider,
+    IntegrationSettings,
+    JournalPrompt,
+    MarkdownExportSummary,
+    ExportFilter,
+    ExportFormat,
+    FeatureFlag,
+    FilteredExportSummary,
+    VaultMirrorSummary,
+    MirrorCommit,
+    SlackImportSummary,
+    OrgImportSummary,
+    ObsidianImportSummary,
+    TodoTxtImportSummary,
+    DayOneImportSummary,
+    MarkdownImportSummary,
+    Attachment,
+    EntryCommit,
+    EntryStreaks,
+    EntryDraft,
+    EntryNote,
+    WritingStats,
+    DailyContextSwitches,
+    DailyMeetingLoad,
+    DuplicateTaskMatch,
+    AutomationRule,
+    NotificationTemplate,
+    ScheduledJob,
+    ScheduledJobName,
+    AutomationRuleChange,
+    AutomationRuleInput,
+    AutomationRunLogEntry,
+    TaskTag,
+    ApiScopePreset,
+    ApiToken,
+    CreatedApiToken,
+    ExternalAccessLogEntry,
+    Bookmark,
+    Incident,
+    IncidentInput,
+    IncidentTimelineEvent,
+    OnCallShift,
+    LinkedItemChange,
     Meeting,
     MeetingActionItem,
     MeetingRecurrence,
     MeetingStatus,
+    MoodCorrelation,
     Page,
+    PageAppearance,
+    PageProperty,
+    PagePropertyValue,
+    PageRevision,
+    PageAsset,
+    PageExportFormat,
+    PageExportSummary,
+    PageStats,
+    PagesStats,
+    PageTag,
+    RecentPage,
+    PomodoroDayCount,
+    PomodoroKind,
+    PomodoroSession,
+    PomodoroStatus,
+    Presence,
+    ShutdownChecklist,
+    SlackStatusSettings,
     Project,
     ProjectBranch,
     ProjectBranchStatus,
     ProjectStatus,
+    StreakAtRisk,
     Task,
     TaskPriority,
     TaskRecurrence,
     TaskStatus,
+    TaskLink,
+    ReviewQueueItem,
     TaskSubtask,
+    TaskWithLink,
+    MigrationProgress,
+    TrashedEntry,
+    TrashedPage,
+    PageSearchResult,
+    UpdateCheck,
+    WeekInfo,
 } from "../types";
+import type { DetachedView } from "../types/shell";
+
+// Startup
+export const getMigrationProgress = (): Promise<MigrationProgress> => invoke("get_migration_progress");
 
 // Entries
 export const getEntries = (): Promise<Entry[]> => invoke("get_entries");
+export const getEntriesPage = (limit: number, offset: number): Promise<Entry[]> =>
+    invoke("get_entries", { limit, offset });
+export const countEntries = (): Promise<number> => invoke("count_entries");
 export const getEntry = (date: string): Promise<Entry | null> => invoke("get_entry", { date });
-export const saveEntry = (date: string, yesterday: string, today: string, projectId?: number | null): Promise<void> =>
-    invoke("save_entry", { date, yesterday, today, projectId });
+export const saveEntry = (
+    date: string,
+    yesterday: string,
+    today: string,
+    projectId?: number | null,
+    blockers = "",
+    notes = ""
+): Promise<void> => invoke("save_entry", { date, yesterday, today, projectId, blockers, notes });
 export const deleteEntry = (date: string): Promise<void> => invoke("delete_entry", { date });
-export const searchEntries = (query: string): Promise<Entry[]> => invoke("search_entries", { query });
+export const setEntryPinned = (date: string, pinned: boolean): Promise<void> =>
+    invoke("set_entry_pinned", { date, pinned });
+export const getPinnedEntries = (): Promise<Entry[]> => invoke("get_pinned_entries");
+export const getTrashedEntries = (): Promise<TrashedEntry[]> => invoke("get_trashed_entries");
+export const restoreEntry = (date: string): Promise<void> => invoke("restore_entry", { date });
+export const purgeTrash = (): Promise<number> => invoke("purge_trash");
+export const searchEntries = (query: string): Promise<EntrySearchResult[]> =>
+    invoke("search_entries", { query });
+export const getEntryTags = (): Promise<EntryTag[]> => invoke("get_entry_tags");
+export const getEntriesByTag = (tag: string): Promise<Entry[]> => invoke("get_entries_by_tag", { tag });
+export const getEntriesBetween = (start: string, end: string): Promise<Entry[]> =>
+    invoke("get_entries_between", { start, end });
+export const getEntryStreaks = (): Promise<EntryStreaks> => invoke("get_entry_streaks");
+export const saveEntryDraft = (
+    date: string,
+    yesterday: string,
+    today: string,
+    blockers = "",
+    notes = ""
+): Promise<EntryDraft> => invoke("save_entry_draft", { date, yesterday, today, blockers, notes });
+export const getEntryDraft = (date: string): Promise<EntryDraft | null> => invoke("get_entry_draft", { date });
+export const discardEntryDraft = (date: string): Promise<void> => invoke("discard_entry_draft", { date });
+export const appendEntryNote = (date: string, text: string): Promise<EntryNote> =>
+    invoke("append_entry_note", { date, text });
+export const getEntryNotes = (date: string): Promise<EntryNote[]> => invoke("get_entry_notes", { date });
+export const deleteEntryNote = (id: number): Promise<void> => invoke("delete_entry_note", { id });
+export const getWritingStats = (range: EntryDateRange | null): Promise<WritingStats> =>
+    invoke("get_writing_stats", { range });
+export const getEntriesOnThisDay = (monthDay: string): Promise<Entry[]> =>
+    invoke("get_entries_on_this_day", { monthDay });
+export const getRandomEntry = (olderThanDays?: number): Promise<Entry | null> =>
+    invoke("get_random_entry", { olderThanDays });
+export const addEntryTag = (entryId: number, tag: string): Promise<void> => invoke("add_entry_tag", { entryId, tag });
+export const removeEntryTag = (entryId: number, tag: string): Promise<void> =>
+    invoke("remove_entry_tag", { entryId, tag });
+export const getEntrySectionTemplates = (): Promise<EntrySectionTemplate[]> => invoke("get_entry_section_templates");
+export const saveEntrySectionTemplates = (
+    templates: { section_key?: string | null; label: string }[]
+): Promise<EntrySectionTemplate[]> => invoke("save_entry_section_templates", { templates });
+export const getEntrySections = (date: string): Promise<EntrySection[]> => invoke("get_entry_sections", { date });
+export const saveEntrySections = (
+    date: string,
+    sections: { section_key: string; content: string }[]
+): Promise<EntrySection[]> => invoke("save_entry_sections", { date, sections });
+
+// Attachments
+export const attachFileToEntry = (entryId: number, path: string): Promise<Attachment> =>
+    invoke("attach_file_to_entry", { entryId, path });
+export const listEntryAttachments = (entryId: number): Promise<Attachment[]> =>
+    invoke("list_entry_attachments", { entryId });
+export const deleteAttachment = (id: number): Promise<void> => invoke("delete_attachment", { id });
+export const attachCommandOutput = (entryDate: string, command: string, output: string): Promise<Attachment> =>
+    invoke("attach_command_output", { entryDate, command, output });
+export const getAttachmentText = (id: number): Promise<string> => invoke("get_attachment_text", { id });
+
+// Journal prompts
+export const getJournalPrompts = (): Promise<JournalPrompt[]> => invoke("get_journal_prompts");
+export const getDailyPrompt = (date: string): Promise<JournalPrompt | null> => invoke("get_daily_prompt", { date });
+export const addJournalPrompt = (text: string): Promise<JournalPrompt> => invoke("add_journal_prompt", { text });
+export const setJournalPromptEnabled = (id: number, enabled: boolean): Promise<void> =>
+    invoke("set_journal_prompt_enabled", { id, enabled });
+export const deleteJournalPrompt = (id: number): Promise<void> => invoke("delete_journal_prompt", { id });
+
+// Mood insights
+export const setDailyMood = (date: string, mood: number | null): Promise<void> => invoke("set_daily_mood", { date, mood });
+export const getDailyMoods = (startDate?: string, endDate?: string): Promise<DailyMood[]> =>
+    invoke("get_daily_moods", { startDate, endDate });
+export const getMoodCorrelations = (startDate?: string, endDate?: string): Promise<MoodCorrelation[]> =>
+    invoke("get_mood_correlations", { startDate, endDate });
 
 // Git
 export const getGitCommits = (): Promise<string[]> => invoke("get_git_commits");
+export const getPreviousWorkday = (): Promise<string> => invoke("get_previous_workday");
+export const getEntryCommits = (date: string): Promise<EntryCommit[]> => invoke("get_entry_commits", { date });
+export const saveEntryCommits = (date: string, commits: string[]): Promise<EntryCommit[]> =>
+    invoke("save_entry_commits", { date, commits });
 
 // Backup
+export const getBackupFormat = (): Promise<BackupFormat> => invoke("get_backup_format");
 export const importBackup = (payload: BackupPayload, replaceExisting: boolean): Promise<void> =>
     invoke("import_backup", { payload, replaceExisting });
+export const exportEntriesMarkdown = (dir: string, range: EntryDateRange | null): Promise<MarkdownExportSummary> =>
+    invoke("export_entries_markdown", { dir, range });
+/** Exports only entries, pages and tasks matching `filter`; pages match a project by carrying its name as a tag. */
+export const exportFiltered = (dir: string, format: ExportFormat, filter: ExportFilter): Promise<FilteredExportSummary> =>
+    invoke("export_filtered", { dir, format, filter });
+/** Reads every `YYYY-MM-DD.md` under `dir`, subfolders included. */
+export const importMarkdownFolder = (dir: string): Promise<MarkdownImportSummary> =>
+    invoke("import_markdown_folder", { dir });
+/** `path` is a Day One JSON file or the unzipped export folder. */
+export const importDayOne = (path: string): Promise<DayOneImportSummary> => invoke("import_day_one", { path });
+/** `path` is an .org file or a folder of them; pages are created under `parentId`. */
+export const importOrg = (path: string, parentId: number | null): Promise<OrgImportSummary> =>
+    invoke("import_org", { path, parentId });
+/** Writes every task and page to one .org file and returns its path. */
+export const exportOrg = (path: string): Promise<string> => invoke("export_org", { path });
+/** Notes become pages nested like the vault's folders; `YYYY-MM-DD.md` daily notes become entries. */
+export const importObsidianVault = (dir: string): Promise<ObsidianImportSummary> =>
+    invoke("import_obsidian_vault", { dir });
+/** `path` is a todo.txt file, or a folder holding `todo.txt`. */
+export const importTodoTxt = (path: string): Promise<TodoTxtImportSummary> => invoke("import_todo_txt", { path });
+/** Writes every task to a todo.txt file and returns its path. */
+export const exportTodoTxt = (path: string): Promise<string> => invoke("export_todo_txt", { path });
+/** `author` is a Slack user id or name; empty imports standups from everyone in the channel. */
+export const importSlackStandups = (path: string, author: string): Promise<SlackImportSummary> =>
+    invoke("import_slack_standups", { path, author: author.trim() || null });
+export const previewDateShift = (filter: DateShiftFilter, deltaDays: number): Promise<DateShift> =>
+    invoke("preview_date_shift", { filter, deltaDays });
+/** Moves every matching date by `deltaDays` in one transaction; fails without changes on any conflict. */
+export const shiftDates = (filter: DateShiftFilter, deltaDays: number): Promise<DateShift> =>
+    invoke("shift_dates", { filter, deltaDays });
+export const generateWeeklyReview = (weekStart: string): Promise<string> =>
+    invoke("generate_weekly_review", { weekStart });
+/** Creates (or rewrites) the "Week plan" page for the week starting at `weekStart`. */
+export const generateWeekPlan = (weekStart: string): Promise<Page> => invoke("generate_week_plan", { weekStart });
+/** Rewrites the `vault_mirror_dir` folder; saves keep it current afterwards. */
+export const syncVaultMirror = (): Promise<VaultMirrorSummary> => invoke("sync_vault_mirror");
+/** Commits of the mirror's git repository, newest first; `path` is relative to the mirror folder. */
+export const getMirrorHistory = (path: string): Promise<MirrorCommit[]> => invoke("get_mirror_history", { path });
+/** Writes a printable HTML sheet; `path` may be a folder. Resolves to the written file. */
+export const generateDailySheet = (date: string, path: string): Promise<string> =>
+    invoke("generate_daily_sheet", { date, path });
+
+// Journal encryption
+export const getJournalEncryptionStatus = (): Promise<JournalEncryptionStatus> =>
+    invoke("get_journal_encryption_status");
+export const enableJournalEncryption = (passphrase: string): Promise<JournalEncryptionStatus> =>
+    invoke("enable_journal_encryption", { passphrase });
+export const unlockJournal = (passphrase: string): Promise<JournalEncryptionStatus> =>
+    invoke("unlock_journal", { passphrase });
+export const lockJournal = (): Promise<JournalEncryptionStatus> => invoke("lock_journal");
+export const disableJournalEncryption = (passphrase: string): Promise<JournalEncryptionStatus> =>
+    invoke("disable_journal_encryption", { passphrase });
 
 // Pages
-export const getPages = (): Promise<Page[]> => invoke("get_pages");
+/** Archived pages and their subpages are left out unless `includeArchived` is set. */
+export const getPages = (includeArchived = false): Promise<Page[]> => invoke("get_pages", { includeArchived });
 export const getPage = (id: number): Promise<Page | null> => invoke("get_page", { id });
-export const createPage = (title: string, content: string): Promise<Page> => invoke("create_page", { title, content });
-export const updatePage = (id: number, title: string, content: string): Promise<void> =>
-    invoke("update_page", { id, title, content });
+export const getPageChildren = (parentId: number | null): Promise<Page[]> =>
+    invoke("get_page_children", { parentId });
+export const createPage = (
+    title: string,
+    content: string,
+    parentId: number | null = null,
+    appearance: PageAppearance = {}
+): Promise<Page> => invoke("create_page", { title, content, parentId, ...appearance });
+export const updatePage = (id: number, title: string, content: string, appearance: PageAppearance = {}): Promise<void> =>
+    invoke("update_page", { id, title, content, ...appearance });
+export const getPageProperties = (pageId: number): Promise<PageProperty[]> =>
+    invoke("get_page_properties", { pageId });
+/** Pages whose frontmatter sets `key`, or `key: value` when `value` is given (case-insensitive). */
+export const getPagesByProperty = (key: string, value?: string): Promise<Page[]> =>
+    invoke("get_pages_by_property", { key, value });
+/** Values used for `key` with their page counts, most used first. */
+export const getPagePropertyValues = (key: string): Promise<PagePropertyValue[]> =>
+    invoke("get_page_property_values", { key });
+export const movePage = (id: number, newParent: number | null): Promise<void> =>
+    invoke("move_page", { id, newParent });
+export const reorderPages = (orderedIds: number[]): Promise<void> => invoke("reorder_pages", { orderedIds });
+export const recordPageView = (id: number): Promise<void> => invoke("record_page_view", { id });
+/** Most recently opened pages first; `limit` defaults to 10. */
+export const getRecentPages = (limit?: number): Promise<RecentPage[]> => invoke("get_recent_pages", { limit });
+export const setPagePinned = (id: number, pinned: boolean): Promise<void> => invoke("set_page_pinned", { id, pinned });
+export const setPageFavorite = (id: number, favorite: boolean): Promise<void> =>
+    invoke("set_page_favorite", { id, favorite });
+/** Locks the page and its history with `passphrase`; it stays open until `lockPage` or a restart. */
+export const enablePageLock = (id: number, passphrase: string): Promise<Page> =>
+    invoke("enable_page_lock", { id, passphrase });
+/** Opens a locked page for this session and returns it with its content. */
+export const unlockPage = (id: number, passphrase: string): Promise<Page> => invoke("unlock_page", { id, passphrase });
+export const lockPage = (id: number): Promise<void> => invoke("lock_page", { id });
+export const disablePageLock = (id: number, passphrase: string): Promise<Page> =>
+    invoke("disable_page_lock", { id, passphrase });
+export const archivePage = (id: number): Promise<void> => invoke("archive_page", { id });
+export const unarchivePage = (id: number): Promise<void> => invoke("unarchive_page", { id });
+/** Moves the page to the trash; its subpages move up a level. */
 export const deletePage = (id: number): Promise<void> => invoke("delete_page", { id });
+/** Stores an image for the page and returns the URL to embed as `![](url)`. */
+export const savePageAsset = (pageId: number, bytes: Uint8Array, filename: string): Promise<PageAsset> =>
+    invoke("save_page_asset", { pageId, bytes: Array.from(bytes), filename });
+/** `path` is the file, or a folder that gets `<id>-<title>.md|html`; returns the file written. */
+export const exportPage = (id: number, format: PageExportFormat, path: string): Promise<string> =>
+    invoke("export_page", { id, format, path });
+/** Writes every page into `dir`; Markdown copies images into `dir/assets`, HTML embeds them. */
+export const exportAllPages = (dir: string, format: PageExportFormat): Promise<PageExportSummary> =>
+    invoke("export_all_pages", { dir, format });
+export const searchPages = (query: string): Promise<PageSearchResult[]> => invoke("search_pages", { query });
+export const getPageStats = (id: number): Promise<PageStats> => invoke("get_page_stats", { id });
+export const getPagesStats = (): Promise<PagesStats> => invoke("get_pages_stats");
+export const getTrashedPages = (): Promise<TrashedPage[]> => invoke("get_trashed_pages");
+export const restorePage = (id: number): Promise<Page> => invoke("restore_page", { id });
+export const emptyPageTrash = (): Promise<number> => invoke("empty_page_trash");
+export const getPageRevisions = (id: number): Promise<PageRevision[]> => invoke("get_page_revisions", { id });
+export const getPageRevision = (revId: number): Promise<PageRevision | null> =>
+    invoke("get_page_revision", { revId });
+export const restorePageRevision = (revId: number): Promise<Page> => invoke("restore_page_revision", { revId });
+export const getBacklinks = (pageId: number): Promise<Page[]> => invoke("get_backlinks", { pageId });
+export const getPageTags = (): Promise<PageTag[]> => invoke("get_page_tags");
+export const getPagesByTag = (tag: string): Promise<Page[]> => invoke("get_pages_by_tag", { tag });
+export const addPageTag = (pageId: number, tag: string): Promise<void> => invoke("add_page_tag", { pageId, tag });
+export const removePageTag = (pageId: number, tag: string): Promise<void> =>
+    invoke("remove_page_tag", { pageId, tag });
+
+// Windows
+export const openWindow = (view: DetachedView): Promise<string> => invoke("open_window", { view });
+export const closeWindow = (label: string): Promise<void> => invoke("close_window", { label });
 
 // Tasks
-export const getTasks = (): Promise<Task[]> => invoke("get_tasks");
+export const getTasks = (tag?: string | null): Promise<Task[]> => invoke("get_tasks", { tag: tag ?? null });
 export const createTask = (params: {
     title: string;
     description: string;
@@ -78,7 +376,15 @@ export const updateTaskStatus = (id: number, status: TaskStatus): Promise<void>
 export const deleteTask = (id: number): Promise<void> => invoke("delete_task", { id });
 export const startTaskTimer = (id: number): Promise<void> => invoke("start_task_timer", { id });
 export const pauseTaskTimer = (id: number): Promise<void> => invoke("pause_task_timer", { id });
+// Holds the task's due-date reminders for `minutes`; `null` clears the snooze. Resolves to when it ends.
+export const snoozeTaskDueReminder = (taskId: number, minutes: number | null): Promise<string | null> =>
+    invoke("snooze_task_due_reminder", { taskId, minutes });
 export const resetTaskTimer = (id: number): Promise<void> => invoke("reset_task_timer", { id });
+export const getRecentTimerTasks = (limit?: number): Promise<Task[]> => invoke("get_recent_timer_tasks", { limit });
+export const findDuplicateTasks = (title: string): Promise<DuplicateTaskMatch[]> =>
+    invoke("find_duplicate_tasks", { title });
+export const getContextSwitches = (range: EntryDateRange | null): Promise<DailyContextSwitches[]> =>
+    invoke("get_context_switches", { range });
 
 // Task Subtasks
 export const getTaskSubtasks = (taskId: number | null): Promise<TaskSubtask[]> =>
@@ -88,6 +394,118 @@ export const createTaskSubtask = (taskId: number, title: string): Promise<TaskSu
 export const updateTaskSubtask = (id: number, title: string | null, completed: boolean | null): Promise<void> =>
     invoke("update_task_subtask", { id, title, completed });
 export const deleteTaskSubtask = (id: number): Promise<void> => invoke("delete_task_subtask", { id });
+export const reorderTaskSubtasks = (taskId: number, orderedIds: number[]): Promise<void> =>
+    invoke("reorder_task_subtasks", { taskId, orderedIds });
+// `orderedIds` may be just the visible part of the column; hidden tasks keep their places.
+export const reorderTasks = (status: TaskStatus, orderedIds: number[]): Promise<void> =>
+    invoke("reorder_tasks", { status, orderedIds });
+
+// Task automation rules
+export const getTaskTags = (): Promise<TaskTag[]> => invoke("get_task_tags");
+export const setTaskTags = (id: number, tags: string[]): Promise<string[]> => invoke("set_task_tags", { id, tags });
+export const getAutomationRules = (): Promise<AutomationRule[]> => invoke("get_automation_rules");
+export const createAutomationRule = (input: AutomationRuleInput): Promise<AutomationRule> =>
+    invoke("create_automation_rule", { input });
+export const updateAutomationRule = (id: number, input: AutomationRuleInput): Promise<AutomationRule> =>
+    invoke("update_automation_rule", { id, input });
+export const deleteAutomationRule = (id: number): Promise<void> => invoke("delete_automation_rule", { id });
+export const previewAutomationRules = (): Promise<AutomationRuleChange[]> => invoke("preview_automation_rules");
+export const runAutomationRules = (): Promise<AutomationRuleChange[]> => invoke("run_automation_rules");
+export const getAutomationLog = (limit?: number): Promise<AutomationRunLogEntry[]> =>
+    invoke("get_automation_log", { limit });
+
+// Countdowns
+export const startCountdown = (label: string, minutes: number): Promise<Countdown> =>
+    invoke("start_countdown", { label, minutes });
+export const getActiveCountdowns = (): Promise<Countdown[]> => invoke("get_active_countdowns");
+export const cancelCountdown = (id: number): Promise<void> => invoke("cancel_countdown", { id });
+
+// Focus / pomodoro
+export const recordPomodoroSession = (
+    kind: PomodoroKind,
+    durationMinutes: number,
+    taskId: number | null
+): Promise<PomodoroStatus> => invoke("record_pomodoro_session", { kind, durationMinutes, taskId });
+export const getPomodoroStatus = (): Promise<PomodoroStatus> => invoke("get_pomodoro_status");
+export const getPresence = (): Promise<Presence> => invoke("get_presence");
+export const setFocusPresence = (
+    kind: PomodoroKind | null,
+    taskId: number | null,
+    durationMinutes: number | null
+): Promise<Presence> => invoke("set_focus_presence", { kind, taskId, durationMinutes });
+
+// Shutdown ritual
+export const getShutdownChecklist = (): Promise<ShutdownChecklist | null> => invoke("get_shutdown_checklist");
+export const confirmShutdown = (stopTimers: boolean): Promise<void> => invoke("confirm_shutdown", { stopTimers });
+
+// Slack status
+export const getSlackStatusSettings = (): Promise<SlackStatusSettings> => invoke("get_slack_status_settings");
+// `token: null` keeps the keychain token; an empty string removes it.
+export const saveSlackStatusSettings = (enabled: boolean, token: string | null): Promise<SlackStatusSettings> =>
+    invoke("save_slack_status_settings", { enabled, token });
+export const getSlackStatusOptOuts = (): Promise<number[]> => invoke("get_slack_status_opt_outs");
+export const setTaskSlackStatusOptOut = (taskId: number, optedOut: boolean): Promise<void> =>
+    invoke("set_task_slack_status_opt_out", { taskId, optedOut });
+export const getPomodoroSessions = (date?: string): Promise<PomodoroSession[]> =>
+    invoke("get_pomodoro_sessions", { date });
+export const getPomodoroHeatmap = (days?: number): Promise<PomodoroDayCount[]> =>
+    invoke("get_pomodoro_heatmap", { days });
+
+// Integrations
+export const createTaskFromUrl = (url: string): Promise<TaskWithLink> => invoke("create_task_from_url", { url });
+export const getTaskLinks = (taskId: number | null): Promise<TaskLink[]> => invoke("get_task_links", { taskId });
+
+// Review queue
+export const getReviewQueue = (): Promise<ReviewQueueItem[]> => invoke("get_review_queue");
+export const acceptReviewItem = (taskId: number): Promise<void> => invoke("accept_review_item", { taskId });
+export const dismissReviewItem = (taskId: number): Promise<void> => invoke("dismiss_review_item", { taskId });
+export const getIntegrationSettings = (): Promise<IntegrationSettings[]> => invoke("get_integration_settings");
+export const saveIntegrationSettings = (params: {
+    provider: IntegrationProvider;
+    baseUrl: string | null;
+    username: string | null;
+    token?: string | null;
+}): Promise<void> => invoke("save_integration_settings", params);
+export const refreshLinkedItems = (): Promise<LinkedItemChange[]> => invoke("refresh_linked_items");
+
+// App settings
+export const getAppSettings = (): Promise<AppSetting[]> => invoke("get_app_settings");
+export const updateAppSetting = (key: string, value: string): Promise<void> =>
+    invoke("update_app_setting", { key, value });
+export const getWhatsNew = (sinceVersion: string | null): Promise<WhatsNew> =>
+    invoke("get_whats_new", { sinceVersion });
+export const markWhatsNewSeen = (): Promise<void> => invoke("mark_whats_new_seen");
+export const checkForUpdates = (): Promise<UpdateCheck> => invoke("check_for_updates");
+/** The week containing `date` (default today) under the `week_numbering` setting. */
+export const getWeekInfo = (date?: string): Promise<WeekInfo> => invoke("get_week_info", { date });
+export const getAccessibleSummary = (view: AccessibleSummaryView): Promise<AccessibleSummary> =>
+    invoke("get_accessible_summary", { view });
+export const getFlags = (): Promise<FeatureFlag[]> => invoke("get_flags");
+export const setFlag = (key: string, enabled: boolean): Promise<FeatureFlag[]> => invoke("set_flag", { key, enabled });
+export const getAppearanceSettings = (): Promise<AppearanceSettings> => invoke("get_appearance_settings");
+export const updateAppearanceSettings = (settings: AppearanceSettings): Promise<AppearanceSettings> =>
+    invoke("update_appearance_settings", { settings });
+export const getNotificationTemplates = (): Promise<NotificationTemplate[]> => invoke("get_notification_templates");
+export const listScheduledJobs = (): Promise<ScheduledJob[]> => invoke("list_scheduled_jobs");
+/** Result of the last integrity check, scheduled or manual. */
+export const getIntegrityReport = (): Promise<IntegrityReport> => invoke("get_integrity_report");
+export const verifyIntegrity = (autoFix: boolean): Promise<IntegrityReport> => invoke("verify_integrity", { autoFix });
+
+// Workspace snapshots
+export const listWorkspaceSnapshots = (): Promise<WorkspaceSnapshot[]> => invoke("list_workspace_snapshots");
+export const createWorkspaceSnapshot = (name: string): Promise<WorkspaceSnapshot> =>
+    invoke("create_workspace_snapshot", { name });
+// Resolves to the snapshot of the state that was replaced.
+export const restoreWorkspaceSnapshot = (name: string): Promise<WorkspaceSnapshot> =>
+    invoke("restore_workspace_snapshot", { name });
+export const deleteWorkspaceSnapshot = (name: string): Promise<void> => invoke("delete_workspace_snapshot", { name });
+/** Command timings since app start plus the slowest recorded calls (`limit` defaults to 50). */
+export const getPerformanceReport = (limit?: number): Promise<PerformanceReport> =>
+    invoke("get_performance_report", { limit });
+export const clearSlowOps = (): Promise<void> => invoke("clear_slow_ops");
+export const checkQueryPlans = (): Promise<QueryPlanCheck[]> => invoke("check_query_plans");
+export const setJobSchedule = (job: ScheduledJobName, schedule: string, enabled: boolean): Promise<ScheduledJob> =>
+    invoke("set_job_schedule", { job, schedule, enabled });
 
 // Goals
 export const getGoals = (): Promise<Goal[]> => invoke("get_goals");
@@ -110,6 +528,17 @@ export const updateGoal = (params: {
 }): Promise<void> => invoke("update_goal", params);
 export const deleteGoal = (id: number): Promise<void> => invoke("delete_goal", { id });
 
+// Goal reflections
+export const saveGoalReflection = (goalId: number, whatWorked: string, whatDidnt: string): Promise<GoalReflection> =>
+    invoke("save_goal_reflection", { goalId, whatWorked, whatDidnt });
+export const getGoalReflections = (goalId: number | null): Promise<GoalReflection[]> =>
+    invoke("get_goal_reflections", { goalId });
+export const getSimilarGoalReflections = (title: string, limit?: number): Promise<GoalReflection[]> =>
+    invoke("get_similar_goal_reflections", { title, limit });
+export const getGoalsNeedingCheckin = (): Promise<GoalCheckin[]> => invoke("get_goals_needing_checkin");
+export const checkInGoal = (id: number, progress: number): Promise<void> =>
+    invoke("check_in_goal", { id, progress });
+
 // Goal Milestones
 export const getGoalMilestones = (goalId: number | null): Promise<GoalMilestone[]> =>
     invoke("get_goal_milestones", { goalId });
@@ -137,6 +566,10 @@ export const updateHabit = (
 export const deleteHabit = (id: number): Promise<void> => invoke("delete_habit", { id });
 export const toggleHabitCompletion = (habitId: number, date: string, completed: boolean): Promise<void> =>
     invoke("toggle_habit_completion", { habitId, date, completed });
+export const getAchievements = (): Promise<Achievement[]> => invoke("get_achievements");
+export const getStreaksAtRisk = (): Promise<StreakAtRisk[]> => invoke("get_streaks_at_risk");
+export const logHabitsBulk = (date: string, habitIds: number[]): Promise<number[]> =>
+    invoke("log_habits_bulk", { date, habitIds });
 
 // Projects
 export const getProjects = (): Promise<Project[]> => invoke("get_projects");
@@ -174,6 +607,9 @@ export const deleteProjectBranch = (id: number): Promise<void> => invoke("delete
 
 // Meetings
 export const getMeetings = (): Promise<Meeting[]> => invoke("get_meetings");
+/** Defaults to the last 30 days when no range is given. */
+export const getMeetingLoad = (startDate?: string, endDate?: string): Promise<DailyMeetingLoad[]> =>
+    invoke("get_meeting_load", { startDate, endDate });
 export const createMeeting = (params: {
     title: string;
     agenda: string;
@@ -213,13 +649,99 @@ export const deleteMeeting = (id: number): Promise<void> => invoke("delete_meeti
 export const materializeMeetingActionItems = (meetingId: number, dueDate: string | null): Promise<Task[]> =>
     invoke("materialize_meeting_action_items", { meetingId, dueDate });
 
+// External API tokens
+export const listApiTokens = (): Promise<ApiToken[]> => invoke("list_api_tokens");
+export const createApiToken = (
+    name: string,
+    scopes: string[] | null,
+    preset: ApiScopePreset | null
+): Promise<CreatedApiToken> => invoke("create_api_token", { name, scopes, preset });
+export const revokeApiToken = (id: number): Promise<void> => invoke("revoke_api_token", { id });
+export const getApiScopes = (): Promise<string[]> => invoke("get_api_scopes");
+export const getExternalAccessLog = (limit?: number): Promise<ExternalAccessLogEntry[]> =>
+    invoke("get_external_access_log", { limit });
+
+export const getBookmarks = (): Promise<Bookmark[]> => invoke("get_bookmarks");
+export const deleteBookmark = (id: number): Promise<void> => invoke("delete_bookmark", { id });
+
+// Incidents
+export const getIncidents = (): Promise<Incident[]> => invoke("get_incidents");
+export const createIncident = (input: IncidentInput): Promise<Incident> => invoke("create_incident", { input });
+export const updateIncident = (id: number, input: IncidentInput): Promise<Incident> =>
+    invoke("update_incident", { id, input });
+export const deleteIncident = (id: number): Promise<void> => invoke("delete_incident", { id });
+export const getIncidentTimeline = (from: string, to: string): Promise<IncidentTimelineEvent[]> =>
+    invoke("get_incident_timeline", { from, to });
+
+// On-call schedule
+export const getOnCallShifts = (): Promise<OnCallShift[]> => invoke("get_oncall_shifts");
+export const createOnCallShift = (startDate: string, endDate: string, note?: string): Promise<OnCallShift> =>
+    invoke("create_oncall_shift", { startDate, endDate, note });
+export const deleteOnCallShift = (id: number): Promise<void> => invoke("delete_oncall_shift", { id });
+/** Returns how many shifts were added; events already on the schedule are skipped. */
+export const importOnCallIcs = (content: string): Promise<number> => invoke("import_oncall_ics", { content });
+export const getOnCallDays = (startDate: string, endDate: string): Promise<string[]> =>
+    invoke("get_oncall_days", { startDate, endDate });
+
 // Legacy object API for backward compatibility
 export const api = {
     getEntries,
+    getEntriesPage,
+    countEntries,
     getEntry,
     saveEntry,
     deleteEntry,
+    setEntryPinned,
+    getPinnedEntries,
+    getTrashedEntries,
+    restoreEntry,
+    purgeTrash,
     searchEntries,
+    getEntryTags,
+    getEntriesByTag,
+    getEntriesBetween,
+    getEntryStreaks,
+    getWritingStats,
+    saveEntryDraft,
+    getEntryDraft,
+    discardEntryDraft,
+    appendEntryNote,
+    getEntryNotes,
+    deleteEntryNote,
+    getEntriesOnThisDay,
+    getRandomEntry,
+    addEntryTag,
+    removeEntryTag,
+    getEntrySectionTemplates,
+    saveEntrySectionTemplates,
+    getEntrySections,
+    saveEntrySections,
+    attachFileToEntry,
+    listEntryAttachments,
+    deleteAttachment,
+    attachCommandOutput,
+    getAttachmentText,
     getGitCommits,
+    getPreviousWorkday,
+    getEntryCommits,
+    saveEntryCommits,
+    getBackupFormat,
     importBackup,
+    exportEntriesMarkdown,
+    exportFiltered,
+    importMarkdownFolder,
+    importDayOne,
+    importSlackStandups,
+    previewDateShift,
+    shiftDates,
+    importOrg,
+    exportOrg,
+    importObsidianVault,
+    importTodoTxt,
+    exportTodoTxt,
+    generateWeeklyReview,
+    generateWeekPlan,
+    generateDailySheet,
+    syncVaultMirror,
+    getMirrorHistory,
 };
@@ -0,0 +1,38 @@
+import { Box } from "@mui/material";
+import { useAccessibleSummary } from "../hooks/useAccessibleSummary";
+import type { AccessibleSummaryView } from "../types";
+import type { AppTab } from "../types/shell";
+
+const SUMMARY_VIEW_BY_TAB: Partial<Record<AppTab, AccessibleSummaryView>> = {
+  planner: "today",
+  tasks: "tasks",
+  habits: "habits",
+  journal: "journal",
+};
+
+// Off-screen polite live region: screen readers announce the summary of the open tab
+// whenever its text changes.
+export const AccessibleSummaryRegion = ({ activeTab }: { activeTab: AppTab }) => {
+  const { data: summary } = useAccessibleSummary(SUMMARY_VIEW_BY_TAB[activeTab] ?? null);
+
+  return (
+    <Box
+      role="status"
+      aria-live="polite"
+      aria-atomic="true"
+      sx={{
+        position: "absolute",
+        width: "1px",
+        height: "1px",
+        p: 0,
+        margin: "-1px",
+        overflow: "hidden",
+        clip: "rect(0 0 0 0)",
+        whiteSpace: "nowrap",
+        border: 0,
+      }}
+    >
+      {SUMMARY_VIEW_BY_TAB[activeTab] ? summary?.text ?? "" : ""}
+    </Box>
+  );
+};
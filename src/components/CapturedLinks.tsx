@@ -0,0 +1,66 @@
+import BookmarkBorderIcon from "@mui/icons-material/BookmarkBorder";
+import DeleteOutlineIcon from "@mui/icons-material/DeleteOutline";
+import { Box, IconButton, Link, Paper, Typography } from "@mui/material";
+import { alpha, useTheme } from "@mui/material/styles";
+import { openUrl } from "@tauri-apps/plugin-opener";
+import { format } from "date-fns";
+import { useBookmarks, useDeleteBookmark } from "../hooks/useBookmarks";
+import { useI18n } from "../i18n/I18nContext";
+
+const VISIBLE_LINKS = 8;
+
+// Latest links clipped from the browser; hidden until the extension has sent something.
+export const CapturedLinks = () => {
+  const muiTheme = useTheme();
+  const { t } = useI18n();
+  const { data: bookmarks = [] } = useBookmarks();
+  const deleteBookmark = useDeleteBookmark();
+
+  if (bookmarks.length === 0) return null;
+
+  return (
+    <Paper
+      sx={{
+        mt: 4,
+        p: 3,
+        border: "1px solid",
+        borderColor: "divider",
+        bgcolor: alpha(muiTheme.palette.background.paper, 0.82),
+      }}
+    >
+      <Typography variant="h6" sx={{ display: "flex", alignItems: "center", gap: 1, fontWeight: 600, mb: 1 }}>
+        <BookmarkBorderIcon color="primary" /> {t("Captured links")}
+      </Typography>
+      {bookmarks.slice(0, VISIBLE_LINKS).map((bookmark) => (
+        <Box key={bookmark.id} sx={{ display: "flex", alignItems: "flex-start", gap: 1, py: 0.75 }}>
+          <Box sx={{ flex: 1, minWidth: 0 }}>
+            <Link
+              component="button"
+              variant="body2"
+              underline="hover"
+              onClick={() => openUrl(bookmark.url).catch(() => undefined)}
+              sx={{ display: "block", maxWidth: "100%", textAlign: "left", overflow: "hidden", textOverflow: "ellipsis", whiteSpace: "nowrap" }}
+            >
+              {bookmark.title}
+            </Link>
+            {bookmark.selection ? (
+              <Typography
+                variant="caption"
+                color="text.secondary"
+                sx={{ display: "block", fontStyle: "italic", whiteSpace: "pre-wrap", lineHeight: 1.45 }}
+              >
+                “{bookmark.selection}”
+              </Typography>
+            ) : null}
+            <Typography variant="caption" color="text.secondary">
+              {format(new Date(bookmark.created_at), "MMM d, HH:mm")}
+            </Typography>
+          </Box>
+          <IconButton size="small" aria-label={t("Delete link")} onClick={() => deleteBookmark.mutate(bookmark.id)}>
+            <DeleteOutlineIcon fontSize="small" />
+          </IconButton>
+        </Box>
+      ))}
+    </Paper>
+  );
+};
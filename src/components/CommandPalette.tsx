@@ -13,6 +13,8 @@ import {
 } from "@mui/material";
 import SearchIcon from "@mui/icons-material/Search";
 import { useI18n } from "../i18n/I18nContext";
+import { useSearchEntries } from "../hooks/useEntries";
+import { useSearchPages } from "../hooks/usePages";
 
 export interface CommandAction {
   id: string;
@@ -27,15 +29,39 @@ interface CommandPaletteProps {
   open: boolean;
   actions: CommandAction[];
   onClose: () => void;
+  onOpenEntry?: (date: string) => void;
+  onOpenPage?: (id: number) => void;
 }
 
 const normalize = (value: string) => value.trim().toLowerCase();
 
-export const CommandPalette = ({ open, actions, onClose }: CommandPaletteProps) => {
+const MIN_ENTRY_SEARCH_LENGTH = 2;
+
+// Renders an FTS snippet, highlighting the `<mark>`-wrapped hits without injecting HTML.
+const renderSnippet = (snippet: string) =>
+  snippet.split(/<mark>(.*?)<\/mark>/g).map((part, index) =>
+    index % 2 === 1 ? (
+      <Box component="mark" key={index} sx={{ bgcolor: "warning.light", color: "inherit", px: 0.25 }}>
+        {part}
+      </Box>
+    ) : (
+      part
+    )
+  );
+
+export const CommandPalette = ({ open, actions, onClose, onOpenEntry, onOpenPage }: CommandPaletteProps) => {
   const { t } = useI18n();
   const [query, setQuery] = useState("");
   const [selectedIndex, setSelectedIndex] = useState(0);
   const deferredQuery = useDeferredValue(query);
+  const entrySearchQuery =
+    open && onOpenEntry && deferredQuery.trim().length >= MIN_ENTRY_SEARCH_LENGTH
+      ? deferredQuery.trim()
+      : "";
+  const { data: entryMatches } = useSearchEntries(entrySearchQuery);
+  const pageSearchQuery =
+    open && onOpenPage && deferredQuery.trim().length >= MIN_ENTRY_SEARCH_LENGTH ? deferredQuery.trim() : "";
+  const { data: pageMatches } = useSearchPages(pageSearchQuery);
 
   const indexedActions = useMemo(
     () =>
@@ -59,10 +85,43 @@ export const CommandPalette = ({ open, actions, onClose }: CommandPaletteProps)
       return indexedActions.map(({ action }) => action);
     }
 
-    return indexedActions
+    const matchingActions = indexedActions
       .filter(({ searchText }) => searchText.includes(q))
       .map(({ action }) => action);
-  }, [deferredQuery, indexedActions]);
+
+    const entryActions: CommandAction[] =
+      onOpenEntry && entrySearchQuery
+        ? (entryMatches ?? []).slice(0, 8).map((match) => ({
+            id: `entry-match-${match.entry.id}`,
+            title: match.entry.date,
+            subtitle: match.snippet,
+            section: t("Journal matches"),
+            onSelect: () => onOpenEntry(match.entry.date),
+          }))
+        : [];
+    const pageActions: CommandAction[] =
+      onOpenPage && pageSearchQuery
+        ? (pageMatches ?? []).slice(0, 8).map((match) => ({
+            id: `page-match-${match.page.id}`,
+            title: match.page.title || t("Untitled"),
+            subtitle: match.snippet,
+            section: t("Page matches"),
+            onSelect: () => onOpenPage(match.page.id),
+          }))
+        : [];
+
+    return [...matchingActions, ...pageActions, ...entryActions];
+  }, [
+    deferredQuery,
+    indexedActions,
+    entryMatches,
+    entrySearchQuery,
+    onOpenEntry,
+    pageMatches,
+    pageSearchQuery,
+    onOpenPage,
+    t,
+  ]);
 
   useEffect(() => {
     if (!open) {
@@ -163,9 +222,16 @@ export const CommandPalette = ({ open, actions, onClose }: CommandPaletteProps)
               <ListItemText
                 primary={action.title}
                 secondary={
-                  action.subtitle
-                    ? `${action.section ? `${action.section} • ` : ""}${action.subtitle}`
-                    : action.section
+                  action.subtitle ? (
+                    <>
+                      {action.section ? `${action.section} • ` : ""}
+                      {action.id.startsWith("entry-match-") || action.id.startsWith("page-match-")
+                        ? renderSnippet(action.subtitle)
+                        : action.subtitle}
+                    </>
+                  ) : (
+                    action.section
+                  )
                 }
               />
             </ListItemButton>
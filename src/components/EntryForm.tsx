@@ -16,9 +16,25 @@ import {
     ListItemIcon,
     ListItemText,
 } from "@mui/material";
-import { useState, useEffect, useCallback } from "react";
+import { useState, useEffect, useCallback, useRef } from "react";
 import Markdown from "react-markdown";
-import { useDeleteEntry, useEntry, useSaveEntry } from "../hooks/useEntries";
+import { api } from "../api";
+import {
+    useAddEntryTag,
+    useDeleteEntry,
+    useDiscardEntryDraft,
+    useEntry,
+    useEntrySections,
+    useEntrySectionTemplates,
+    useEntryTags,
+    useGitCommits,
+    useRemoveEntryTag,
+    useSaveEntry,
+    useSaveEntryDraft,
+    useSetEntryPinned,
+} from "../hooks/useEntries";
+import { useDailyPrompt } from "../hooks/useJournalPrompts";
+import { useDailyMoods, useSetDailyMood } from "../hooks/useMoodInsights";
 import { useProjects } from "../hooks/useProjects";
 import { useI18n } from "../i18n/I18nContext";
 import { format, parseISO } from "date-fns";
@@ -34,9 +50,13 @@ import ContentCopyIcon from "@mui/icons-material/ContentCopy";
 import DeleteOutlineIcon from "@mui/icons-material/DeleteOutline";
 import MoreHorizIcon from "@mui/icons-material/MoreHoriz";
 import DescriptionOutlinedIcon from "@mui/icons-material/DescriptionOutlined";
+import PushPinIcon from "@mui/icons-material/PushPin";
+import PushPinOutlinedIcon from "@mui/icons-material/PushPinOutlined";
 import { useAppNotifications } from "../notifications/AppNotifications";
 import { EnergyTag, readEntryEnergyMap, writeEntryEnergyTag } from "../utils/analyticsStorage";
-import { persistEntryDraft, readEntryDraft, removeEntryDraft } from "../utils/draftStorage";
+import { readEntryDraft, removeEntryDraft } from "../utils/draftStorage";
+import { EntryAttachments } from "./entry-form/EntryAttachments";
+import { EntryNoteLog } from "./entry-form/EntryNoteLog";
 
 interface EntryFormProps {
     date: string;
@@ -53,6 +73,14 @@ const formatDraftTime = (value: string) => {
     }
 };
 
+const MOOD_OPTIONS = [
+    { value: 1, label: "1 · Rough" },
+    { value: 2, label: "2 · Low" },
+    { value: 3, label: "3 · Okay" },
+    { value: 4, label: "4 · Good" },
+    { value: 5, label: "5 · Great" },
+] as const;
+
 const ENERGY_OPTIONS: { value: EnergyTag; label: string; color: "success" | "primary" | "warning" | "error" }[] = [
     { value: "focused", label: "Focused", color: "success" },
     { value: "deep_work", label: "Deep Work", color: "primary" },
@@ -60,47 +88,85 @@ const ENERGY_OPTIONS: { value: EnergyTag; label: string; color: "success" | "pri
     { value: "distracted", label: "Distracted", color: "error" },
 ];
 
+// Drafts from older versions live in localStorage; they move to the backend on first load.
+const loadEntryDraft = async (date: string) => {
+    const draft = await api.getEntryDraft(date);
+    const legacyDraft = readEntryDraft(date);
+    if (!legacyDraft) return draft;
+
+    const migrated = draft ?? (await api.saveEntryDraft(date, legacyDraft.yesterday, legacyDraft.today));
+    removeEntryDraft(date);
+    return migrated;
+};
+
 export const EntryForm = ({ date, previewEnabled, autosaveEnabled }: EntryFormProps) => {
     const muiTheme = useTheme();
     const { t } = useI18n();
     const { data: entry, isLoading } = useEntry(date);
     const { data: projects = [] } = useProjects();
     const saveMutation = useSaveEntry();
+    const { data: liveCommits = [] } = useGitCommits();
     const deleteMutation = useDeleteEntry();
+    const { data: entryTags = [] } = useEntryTags();
+    const { data: sectionTemplates = [] } = useEntrySectionTemplates();
+    const { data: entrySections } = useEntrySections(date);
+    const { data: dailyPrompt } = useDailyPrompt(date);
+    const { data: dayMoods = [] } = useDailyMoods(date, date);
+    const setDailyMood = useSetDailyMood();
+    const mood = dayMoods[0]?.mood ?? null;
+    const addTagMutation = useAddEntryTag();
+    const removeTagMutation = useRemoveEntryTag();
+    const setPinnedMutation = useSetEntryPinned();
     const { notify } = useAppNotifications();
 
     const [yesterday, setYesterday] = useState("");
     const [today, setToday] = useState("");
+    const [blockers, setBlockers] = useState("");
+    const [notes, setNotes] = useState("");
     const [draftRestoredAt, setDraftRestoredAt] = useState<string | null>(null);
+    const draftStoredRef = useRef(false);
+    const saveDraftMutation = useSaveEntryDraft();
+    const discardDraftMutation = useDiscardEntryDraft();
     const [hydrated, setHydrated] = useState(false);
     const [confirmDeleteOpen, setConfirmDeleteOpen] = useState(false);
     const [energyTag, setEnergyTag] = useState<EnergyTag | null>(null);
     const [projectId, setProjectId] = useState<number | "">("");
     const [lastFocused, setLastFocused] = useState<"yesterday" | "today">("yesterday");
     const [moreAnchor, setMoreAnchor] = useState<HTMLElement | null>(null);
+    const [tagInput, setTagInput] = useState("");
+    const [sectionValues, setSectionValues] = useState<Record<string, string>>({});
 
     useEffect(() => {
-        const entryYesterday = entry?.yesterday ?? "";
-        const entryToday = entry?.today ?? "";
-
-        let nextYesterday = entryYesterday;
-        let nextToday = entryToday;
-        let restoredAt: string | null = null;
-
-        const draft = readEntryDraft(date);
-        if (draft) {
-            nextYesterday = draft.yesterday ?? entryYesterday;
-            nextToday = draft.today ?? entryToday;
-            restoredAt = draft.updatedAt ?? null;
-        }
+        let cancelled = false;
+        setHydrated(false);
+
+        loadEntryDraft(date)
+            .catch(() => null)
+            .then((draft) => {
+                if (cancelled) return;
+                setYesterday(draft?.yesterday ?? entry?.yesterday ?? "");
+                setToday(draft?.today ?? entry?.today ?? "");
+                setBlockers(draft?.blockers ?? entry?.blockers ?? "");
+                setNotes(draft?.notes ?? entry?.notes ?? "");
+                setProjectId(entry?.project_id ?? "");
+                setDraftRestoredAt(draft?.updated_at ?? null);
+                draftStoredRef.current = Boolean(draft);
+                setHydrated(true);
+            });
 
-        setYesterday(nextYesterday);
-        setToday(nextToday);
-        setProjectId(entry?.project_id ?? "");
-        setDraftRestoredAt(restoredAt);
-        setHydrated(true);
+        return () => {
+            cancelled = true;
+        };
     }, [entry, date]);
 
+    useEffect(() => {
+        const next: Record<string, string> = {};
+        (entrySections ?? []).forEach((section) => {
+            next[section.section_key] = section.content;
+        });
+        setSectionValues(next);
+    }, [entrySections, date]);
+
     useEffect(() => {
         try {
             const next = readEntryEnergyMap()[date];
@@ -120,24 +186,58 @@ export const EntryForm = ({ date, previewEnabled, autosaveEnabled }: EntryFormPr
         }
 
         const timeout = setTimeout(() => {
-            persistEntryDraft(date, { yesterday, today, updatedAt: new Date().toISOString() });
+            // Text matching the saved entry needs no draft; drop one left from earlier edits.
+            if (
+                yesterday === (entry?.yesterday ?? "") &&
+                today === (entry?.today ?? "") &&
+                blockers === (entry?.blockers ?? "") &&
+                notes === (entry?.notes ?? "")
+            ) {
+                if (draftStoredRef.current) {
+                    draftStoredRef.current = false;
+                    discardDraftMutation.mutate(date);
+                }
+                return;
+            }
+
+            draftStoredRef.current = true;
+            saveDraftMutation.mutate({ date, yesterday, today, blockers, notes });
         }, 700);
 
         return () => clearTimeout(timeout);
-    }, [autosaveEnabled, date, hydrated, today, yesterday]);
+        // Mutation objects change identity on every state update; re-running on them would loop.
+        // eslint-disable-next-line react-hooks/exhaustive-deps
+    }, [autosaveEnabled, blockers, date, entry, hydrated, notes, today, yesterday]);
 
     const handleSave = useCallback(() => {
+        const sections = sectionTemplates.map((template) => ({
+            section_key: template.section_key,
+            content: sectionValues[template.section_key] ?? "",
+        }));
+
+        // `git log --since=midnight` only describes today, so older entries keep their snapshot.
+        const commits = date === format(new Date(), "yyyy-MM-dd") ? liveCommits : undefined;
         saveMutation.mutate(
-            { date, yesterday, today, project_id: projectId === "" ? null : projectId },
+            {
+                date,
+                yesterday,
+                today,
+                blockers,
+                notes,
+                project_id: projectId === "" ? null : projectId,
+                sections,
+                commits,
+            },
             {
                 onSuccess: () => {
-                    removeEntryDraft(date);
+                    // Saving the entry also drops its draft on the backend.
+                    draftStoredRef.current = false;
                     setDraftRestoredAt(null);
                     notify(t("Journal entry saved."), "success");
                 },
             },
         );
-    }, [date, notify, projectId, saveMutation, t, today, yesterday]);
+    }, [blockers, date, liveCommits, notes, notify, projectId, saveMutation, sectionTemplates, sectionValues, t, today, yesterday]);
 
     useEffect(() => {
         const handleKeyDown = (event: KeyboardEvent) => {
@@ -185,10 +285,13 @@ export const EntryForm = ({ date, previewEnabled, autosaveEnabled }: EntryFormPr
     };
 
     const clearDraft = () => {
-        removeEntryDraft(date);
+        draftStoredRef.current = false;
+        discardDraftMutation.mutate(date);
         setDraftRestoredAt(null);
         setYesterday(entry?.yesterday ?? "");
         setToday(entry?.today ?? "");
+        setBlockers(entry?.blockers ?? "");
+        setNotes(entry?.notes ?? "");
         setProjectId(entry?.project_id ?? "");
     };
 
@@ -203,15 +306,43 @@ export const EntryForm = ({ date, previewEnabled, autosaveEnabled }: EntryFormPr
         }
     };
 
+    const currentTags = entry ? entryTags.filter((tag) => tag.entry_id === entry.id).map((tag) => tag.tag) : [];
+    const knownTags = Array.from(new Set(entryTags.map((tag) => tag.tag))).filter(
+        (tag) => !currentTags.some((current) => current.toLowerCase() === tag.toLowerCase()),
+    );
+
+    const handleAddTag = () => {
+        const tag = tagInput.trim();
+        if (!entry || !tag) return;
+
+        addTagMutation.mutate(
+            { entryId: entry.id, tag },
+            {
+                onSuccess: () => setTagInput(""),
+                onError: (error) => notify(String(error), "error"),
+            },
+        );
+    };
+
+    const togglePinned = () => {
+        if (!entry) return;
+        setPinnedMutation.mutate(
+            { date, pinned: !entry.pinned },
+            { onError: (error) => notify(String(error), "error") },
+        );
+    };
+
     const handleDeleteEntry = () => {
         deleteMutation.mutate(date, {
             onSuccess: () => {
-                removeEntryDraft(date);
+                draftStoredRef.current = false;
                 setDraftRestoredAt(null);
                 setYesterday("");
                 setToday("");
+                setBlockers("");
+                setNotes("");
                 setConfirmDeleteOpen(false);
-                notify(t("Journal entry deleted."), "info");
+                notify(t("Journal entry moved to trash."), "info");
             },
         });
     };
@@ -256,6 +387,7 @@ export const EntryForm = ({ date, previewEnabled, autosaveEnabled }: EntryFormPr
 
     const statusParts: string[] = [];
     if (entry) statusParts.push(t("Saved"));
+    if (entry?.pinned) statusParts.push(t("Pinned"));
     if (draftRestoredAt) statusParts.push(`${t("Draft")} ${formatDraftTime(draftRestoredAt)}`);
     if (autosaveEnabled) statusParts.push(t("Autosave on"));
 
@@ -335,6 +467,12 @@ export const EntryForm = ({ date, previewEnabled, autosaveEnabled }: EntryFormPr
                                     <ListItemIcon><RestartAltIcon fontSize="small" /></ListItemIcon>
                                     <ListItemText>{t("Reset draft")}</ListItemText>
                                 </MenuItem>
+                                <MenuItem disabled={!entry || setPinnedMutation.isPending} onClick={() => { togglePinned(); setMoreAnchor(null); }}>
+                                    <ListItemIcon>
+                                        {entry?.pinned ? <PushPinIcon fontSize="small" /> : <PushPinOutlinedIcon fontSize="small" />}
+                                    </ListItemIcon>
+                                    <ListItemText>{entry?.pinned ? t("Unpin entry") : t("Pin entry")}</ListItemText>
+                                </MenuItem>
                                 <MenuItem disabled={!entry || deleteMutation.isPending} onClick={() => { setMoreAnchor(null); setConfirmDeleteOpen(true); }} sx={{ color: "error.main" }}>
                                     <ListItemIcon><DeleteOutlineIcon fontSize="small" color="error" /></ListItemIcon>
                                     <ListItemText>{t("Delete entry")}</ListItemText>
@@ -343,7 +481,13 @@ export const EntryForm = ({ date, previewEnabled, autosaveEnabled }: EntryFormPr
                         </Box>
                     </Box>
 
-                    <Box sx={{ display: "grid", gridTemplateColumns: { xs: "1fr", sm: "1fr 1fr" }, gap: 1.5 }}>
+                    {dailyPrompt && (
+                        <Typography variant="body2" color="text.secondary" sx={{ fontStyle: "italic", mb: 1.5 }}>
+                            💡 {dailyPrompt.text}
+                        </Typography>
+                    )}
+
+                    <Box sx={{ display: "grid", gridTemplateColumns: { xs: "1fr", sm: "1fr 1fr 1fr" }, gap: 1.5 }}>
                         <TextField
                             select size="small" label={t("Project")}
                             value={projectId === "" ? "" : String(projectId)}
@@ -368,7 +512,52 @@ export const EntryForm = ({ date, previewEnabled, autosaveEnabled }: EntryFormPr
                                 <option key={opt.value} value={opt.value}>{t(opt.label)}</option>
                             ))}
                         </TextField>
+                        <TextField
+                            select size="small" label={t("Mood")}
+                            value={mood === null ? "" : String(mood)}
+                            onChange={(e) => { const v = e.target.value; setDailyMood.mutate({ date, mood: v === "" ? null : Number(v) }); }}
+                            fullWidth SelectProps={{ native: true }}
+                            InputLabelProps={{ shrink: true }}
+                        >
+                            <option value="">{t("Not rated")}</option>
+                            {MOOD_OPTIONS.map((opt) => (
+                                <option key={opt.value} value={opt.value}>{t(opt.label)}</option>
+                            ))}
+                        </TextField>
+                    </Box>
+
+                    <Box sx={{ display: "flex", flexWrap: "wrap", alignItems: "center", gap: 0.75, mt: 1.5 }}>
+                        {currentTags.map((tag) => (
+                            <Chip
+                                key={tag}
+                                label={`#${tag}`}
+                                size="small"
+                                onDelete={entry ? () => removeTagMutation.mutate({ entryId: entry.id, tag }) : undefined}
+                            />
+                        ))}
+                        <TextField
+                            size="small"
+                            value={tagInput}
+                            disabled={!entry}
+                            placeholder={entry ? t("Add tag") : t("Save the entry to add tags")}
+                            onChange={(e) => setTagInput(e.target.value)}
+                            onKeyDown={(e) => {
+                                if (e.key === "Enter") {
+                                    e.preventDefault();
+                                    handleAddTag();
+                                }
+                            }}
+                            inputProps={{ list: "entry-tag-suggestions" }}
+                            sx={{ minWidth: 160, "& .MuiInputBase-input": { py: 0.5, fontSize: "0.8rem" } }}
+                        />
+                        <datalist id="entry-tag-suggestions">
+                            {knownTags.map((tag) => (
+                                <option key={tag} value={tag} />
+                            ))}
+                        </datalist>
                     </Box>
+
+                    <EntryAttachments entryId={entry?.id} />
                 </Box>
 
                 {/* ── Yesterday card ── */}
@@ -436,13 +625,60 @@ export const EntryForm = ({ date, previewEnabled, autosaveEnabled }: EntryFormPr
                         )}
                     </AnimatePresence>
                 </Box>
+
+                {/* ── Blockers and notes ── */}
+                <Box sx={{ ...glassSx, p: { xs: 2, sm: 2.5 } }}>
+                    <Typography variant="subtitle2" sx={{ fontWeight: 700, mb: 1 }}>
+                        🚧 {t("Blockers")}
+                    </Typography>
+                    <TextField
+                        multiline rows={2} fullWidth value={blockers}
+                        onChange={(e) => setBlockers(e.target.value)}
+                        placeholder={t("Anything slowing you down or waiting on someone?")}
+                    />
+                </Box>
+                <Box sx={{ ...glassSx, p: { xs: 2, sm: 2.5 } }}>
+                    <Typography variant="subtitle2" sx={{ fontWeight: 700, mb: 1 }}>
+                        📝 {t("Notes")}
+                    </Typography>
+                    <TextField
+                        multiline rows={3} fullWidth value={notes}
+                        onChange={(e) => setNotes(e.target.value)}
+                        placeholder={t("Anything else worth remembering about today")}
+                    />
+                </Box>
+
+                {/* ── Day log ── */}
+                <Box sx={{ ...glassSx, p: { xs: 2, sm: 2.5 } }}>
+                    <Typography variant="subtitle2" sx={{ fontWeight: 700, mb: 1 }}>
+                        🕒 {t("Day log")}
+                    </Typography>
+                    <EntryNoteLog date={date} />
+                </Box>
+
+                {/* ── Custom sections ── */}
+                {sectionTemplates.map((template) => (
+                    <Box key={template.section_key} sx={{ ...glassSx, p: { xs: 2, sm: 2.5 } }}>
+                        <Typography variant="subtitle2" sx={{ fontWeight: 700, mb: 1 }}>
+                            {template.label}
+                        </Typography>
+                        <TextField
+                            multiline rows={3} fullWidth
+                            value={sectionValues[template.section_key] ?? ""}
+                            onChange={(e) => {
+                                const value = e.target.value;
+                                setSectionValues((prev) => ({ ...prev, [template.section_key]: value }));
+                            }}
+                        />
+                    </Box>
+                ))}
             </Box>
 
             <Dialog open={confirmDeleteOpen} onClose={() => setConfirmDeleteOpen(false)}>
                 <DialogTitle>{t("Delete entry")}</DialogTitle>
                 <DialogContent>
                     <Typography variant="body2" color="text.secondary">
-                        {t("This will permanently delete the saved journal entry for")} {displayDate}.
+                        {t("This will move the saved journal entry to the trash for")} {displayDate}.
                     </Typography>
                 </DialogContent>
                 <DialogActions>
@@ -24,6 +24,10 @@ import LocalFireDepartmentIcon from "@mui/icons-material/LocalFireDepartment";
 import BarChartRoundedIcon from "@mui/icons-material/BarChartRounded";
 import { readFocusSessionsMap, writeFocusSessionsMap } from "../utils/focusSessionStorage";
 import { useTrayTimer } from "../hooks/useTrayTimer";
+import { usePomodoroStatus, usePresence, useRecordPomodoroSession, useSetFocusPresence } from "../hooks/usePomodoro";
+import { useWeekNumbering } from "../hooks/useAppSettings";
+import { useActiveCountdowns, useCancelCountdown, useStartCountdown } from "../hooks/useCountdowns";
+import { FocusTimeboxSection } from "./focus/FocusTimeboxSection";
 
 const FOCUS_DURATION_PRESETS = [
   { label: "25m", minutes: 25 },
@@ -45,6 +49,14 @@ export const FocusBoard = () => {
   const { data: tasks = [] } = useTasks();
   const updateTaskStatus = useUpdateTaskStatus();
   const updateTrayTimer = useTrayTimer();
+  const { data: pomodoroStatus } = usePomodoroStatus();
+  const { weekStartsOn } = useWeekNumbering();
+  const recordPomodoroSession = useRecordPomodoroSession();
+  const { data: presence } = usePresence();
+  const { mutate: setFocusPresence } = useSetFocusPresence();
+  const { data: countdowns = [] } = useActiveCountdowns();
+  const startCountdown = useStartCountdown();
+  const cancelCountdown = useCancelCountdown();
 
   const today = format(new Date(), "yyyy-MM-dd");
 
@@ -53,6 +65,7 @@ export const FocusBoard = () => {
   const [focusDurationMinutes, setFocusDurationMinutes] = useState(25);
   const [breakDurationMinutes, setBreakDurationMinutes] = useState(5);
   const [isBreakMode, setIsBreakMode] = useState(false);
+  const [breakKind, setBreakKind] = useState<"short_break" | "long_break">("short_break");
   const [breakPending, setBreakPending] = useState(false);
   const [focusTaskId, setFocusTaskId] = useState<number | "">("");
   const [completedAnimation, setCompletedAnimation] = useState(false);
@@ -82,9 +95,13 @@ export const FocusBoard = () => {
   );
 
   const sessionsToday = sessionsMap[today] ?? 0;
+  const longBreakMinutes = pomodoroStatus?.long_break_minutes ?? 15;
+  const currentBreakMinutes = breakKind === "long_break" ? longBreakMinutes : breakDurationMinutes;
+  const dailyGoal = pomodoroStatus?.daily_goal ?? 0;
+  const completedToday = pomodoroStatus?.completed_today ?? sessionsToday;
 
   const weeklyData = useMemo(() => {
-    const weekStart = startOfWeek(new Date(), { weekStartsOn: 1 });
+    const weekStart = startOfWeek(new Date(), { weekStartsOn });
     return Array.from({ length: 7 }, (_, i) => {
       const date = format(addDays(weekStart, i), "yyyy-MM-dd");
       return {
@@ -94,12 +111,12 @@ export const FocusBoard = () => {
         isToday: date === today,
       };
     });
-  }, [sessionsMap, today]);
+  }, [sessionsMap, today, weekStartsOn]);
 
   const totalThisWeek = weeklyData.reduce((sum, d) => sum + d.count, 0);
   const maxWeekDay = Math.max(1, ...weeklyData.map((d) => d.count));
 
-  const totalDuration = (isBreakMode ? breakDurationMinutes : focusDurationMinutes) * 60;
+  const totalDuration = (isBreakMode ? currentBreakMinutes : focusDurationMinutes) * 60;
   const elapsed = totalDuration - focusSecondsLeft;
   const progress = totalDuration > 0 ? (elapsed / totalDuration) * 100 : 0;
   const activeDurationPresets = isBreakMode ? BREAK_DURATION_PRESETS : FOCUS_DURATION_PRESETS;
@@ -122,16 +139,38 @@ export const FocusBoard = () => {
         return nextMap;
       });
 
-      const nextBreakSeconds = breakDurationMinutes * 60;
+      // Predict the break locally so the UI does not wait for the backend round trip.
+      const predictedLongBreak =
+        pomodoroStatus !== undefined &&
+        (pomodoroStatus.cycle_position + 1) % pomodoroStatus.long_break_every === 0;
+      const nextBreakKind = predictedLongBreak ? "long_break" : "short_break";
+      const nextBreakSeconds = (predictedLongBreak ? longBreakMinutes : breakDurationMinutes) * 60;
       setFocusRunning(false);
       setBreakPending(true);
       setIsBreakMode(true);
+      setBreakKind(nextBreakKind);
       focusSecondsLeftRef.current = nextBreakSeconds;
       setFocusSecondsLeft(nextBreakSeconds);
       updateTrayTimer(null);
       notify(notifyMessage, "success");
+
+      recordPomodoroSession.mutate({
+        kind: "focus",
+        duration_minutes: focusDurationMinutes,
+        task_id: typeof focusTaskId === "number" ? focusTaskId : null,
+      });
     },
-    [breakDurationMinutes, notify, today, updateTrayTimer]
+    [
+      breakDurationMinutes,
+      focusDurationMinutes,
+      focusTaskId,
+      longBreakMinutes,
+      notify,
+      pomodoroStatus,
+      recordPomodoroSession,
+      today,
+      updateTrayTimer,
+    ]
   );
 
   const handleToggleFocus = () => {
@@ -143,7 +182,7 @@ export const FocusBoard = () => {
 
     if (isBreakMode) {
       if (focusSecondsLeft <= 0 || breakPending) {
-        const nextBreakSeconds = breakDurationMinutes * 60;
+        const nextBreakSeconds = currentBreakMinutes * 60;
         focusSecondsLeftRef.current = nextBreakSeconds;
         setFocusSecondsLeft(nextBreakSeconds);
       }
@@ -214,6 +253,11 @@ export const FocusBoard = () => {
         setBreakPending(false);
         setFocusSecondsLeft(nextFocusSeconds);
         updateTrayTimer(null);
+        recordPomodoroSession.mutate({
+          kind: breakKind,
+          duration_minutes: currentBreakMinutes,
+          task_id: null,
+        });
         notify(t("Break completed!"), "success");
         sendNotification({ title: "Dev Journal", body: t("Break completed!") });
         return;
@@ -235,11 +279,14 @@ export const FocusBoard = () => {
       window.clearInterval(timer);
     };
   }, [
+    breakKind,
     completeFocusSession,
+    currentBreakMinutes,
     focusDurationMinutes,
     focusRunning,
     isBreakMode,
     notify,
+    recordPomodoroSession,
     t,
     updateTrayTimer,
   ]);
@@ -254,14 +301,29 @@ export const FocusBoard = () => {
     );
   }, [focusRunning, focusSecondsLeft, isBreakMode, t, updateTrayTimer]);
 
+  // Report the session to the backend presence so the tray and other windows see it.
+  useEffect(() => {
+    if (!focusRunning) {
+      setFocusPresence({ kind: null, task_id: null, duration_minutes: null });
+      return;
+    }
+
+    setFocusPresence({
+      kind: isBreakMode ? breakKind : "focus",
+      task_id: !isBreakMode && typeof focusTaskId === "number" ? focusTaskId : null,
+      duration_minutes: Math.max(1, Math.ceil(focusSecondsLeftRef.current / 60)),
+    });
+  }, [breakKind, focusRunning, focusTaskId, isBreakMode, setFocusPresence]);
+
   useEffect(() => {
     return () => {
       if (completedAnimationTimeoutRef.current) {
         window.clearTimeout(completedAnimationTimeoutRef.current);
       }
       updateTrayTimer(null);
+      setFocusPresence({ kind: null, task_id: null, duration_minutes: null });
     };
-  }, [updateTrayTimer]);
+  }, [setFocusPresence, updateTrayTimer]);
 
   const surfaceSx = {
     p: { xs: 1.5, sm: 2 },
@@ -294,8 +356,16 @@ export const FocusBoard = () => {
             <Chip
               size="small"
               color={focusRunning ? "primary" : "default"}
-              label={isBreakMode ? t("Break") : t("Focus")}
+              label={isBreakMode ? (breakKind === "long_break" ? t("Long break") : t("Break")) : t("Focus")}
             />
+            {dailyGoal > 0 && (
+              <Chip
+                size="small"
+                variant="outlined"
+                color={completedToday >= dailyGoal ? "success" : "default"}
+                label={`${t("Daily goal")}: ${completedToday}/${dailyGoal}`}
+              />
+            )}
             <Chip
               size="small"
               variant="outlined"
@@ -466,6 +536,7 @@ export const FocusBoard = () => {
                 onClick={() => {
                   if (isBreakMode) {
                     setBreakDurationMinutes(preset.minutes);
+                    setBreakKind("short_break");
                     if (!focusRunning) {
                       const nextBreakSeconds = preset.minutes * 60;
                       focusSecondsLeftRef.current = nextBreakSeconds;
@@ -554,6 +625,11 @@ export const FocusBoard = () => {
                 {t("Pick a task or run a free-focus session.")}
               </Typography>
             )}
+            {!selectedFocusTask && presence?.task_title && (
+              <Typography variant="caption" color="text.secondary" sx={{ display: "block", mt: 0.5 }}>
+                {t("Timer running on \"{title}\"", { title: presence.task_title })}
+              </Typography>
+            )}
           </Box>
 
           {/* Today stats */}
@@ -594,6 +670,16 @@ export const FocusBoard = () => {
               </Typography>
             )}
           </Box>
+
+          {/* Timeboxes independent of tasks */}
+          <FocusTimeboxSection
+            busy={startCountdown.isPending}
+            countdowns={countdowns}
+            onCancel={(countdownId) => cancelCountdown.mutate(countdownId)}
+            onStart={(label, minutes) => startCountdown.mutate({ label, minutes })}
+            surfaceSx={{ ...surfaceSx, p: { xs: 2, sm: 2.25 } }}
+            t={t}
+          />
         </Box>
       </Box>
 
@@ -1,6 +1,8 @@
 import { Box, Typography, List, ListItem, ListItemText, Paper, Avatar } from "@mui/material";
 import { alpha, useTheme } from "@mui/material/styles";
-import { useGitCommits } from "../hooks/useEntries";
+import { useMemo } from "react";
+import { format, parseISO } from "date-fns";
+import { useEntryCommits, useGitCommits, usePreviousWorkday } from "../hooks/useEntries";
 import { motion } from "framer-motion";
 import CommitIcon from '@mui/icons-material/Commit';
 
@@ -17,9 +19,28 @@ const itemVariants = {
     show: { opacity: 1, x: 0, transition: { duration: 0.3 } }
 };
 
-export const GitCommits = () => {
+interface GitCommitsProps {
+    date: string;
+}
+
+export const GitCommits = ({ date }: GitCommitsProps) => {
     const muiTheme = useTheme();
-    const { data: commits, isLoading } = useGitCommits();
+    const isToday = date === format(new Date(), "yyyy-MM-dd");
+    const { data: savedCommits = [], isLoading } = useEntryCommits(date);
+    const { data: liveCommits = [] } = useGitCommits();
+    const { data: previousWorkday } = usePreviousWorkday();
+
+    // Saved snapshot first, then today's commits that are not saved with the entry yet.
+    const commits = useMemo(() => {
+        const saved = savedCommits.map((commit) => ({ hash: commit.hash, message: commit.message, saved: true }));
+        if (!isToday) return saved;
+        const savedHashes = new Set(saved.map((commit) => commit.hash));
+        const live = [...liveCommits]
+            .reverse()
+            .map((line) => ({ hash: line.substring(0, 7).toLowerCase(), message: line.substring(8), saved: false }))
+            .filter((commit) => !savedHashes.has(commit.hash));
+        return [...saved, ...live];
+    }, [isToday, liveCommits, savedCommits]);
 
     if (isLoading) return <Box p={2}><Typography>Loading commits...</Typography></Box>;
 
@@ -35,16 +56,17 @@ export const GitCommits = () => {
                 }}
             >
                 <Typography variant="h5" gutterBottom sx={{ display: 'flex', alignItems: 'center', gap: 1, color: 'text.primary', fontWeight: 600 }}>
-                    <CommitIcon color="primary" /> Today's Commits
+                    <CommitIcon color="primary" />{" "}
+                    {isToday && previousWorkday ? `Commits since ${format(parseISO(previousWorkday), "EEE, MMM d")}` : "Commits"}
                 </Typography>
 
-                {commits && commits.length > 0 ? (
+                {commits.length > 0 ? (
                     <List dense component={motion.ul} variants={containerVariants} initial="hidden" animate="show" sx={{ pt: 1 }}>
-                        {commits.map((commit, idx) => {
-                            const hash = commit.substring(0, 7);
-                            const msg = commit.substring(8);
+                        {commits.map((commit) => {
+                            const hash = commit.hash.substring(0, 7);
+                            const msg = commit.message;
                             return (
-                                <ListItem key={idx} component={motion.li} variants={itemVariants} sx={{
+                                <ListItem key={commit.hash} component={motion.li} variants={itemVariants} sx={{
                                     mb: 1,
                                     backgroundColor: alpha(muiTheme.palette.background.default, 0.42),
                                     borderRadius: 2,
@@ -61,7 +83,7 @@ export const GitCommits = () => {
                                     </Avatar>
                                     <ListItemText
                                         primary={msg}
-                                        secondary={hash}
+                                        secondary={commit.saved ? hash : `${hash} · not saved with entry yet`}
                                         primaryTypographyProps={{ variant: 'body1', color: 'text.primary' }}
                                         secondaryTypographyProps={{ variant: 'caption', color: 'text.secondary', fontFamily: 'monospace' }}
                                     />
@@ -72,11 +94,15 @@ export const GitCommits = () => {
                 ) : (
                     <Box sx={{ py: 4, textAlign: 'center' }}>
                         <Typography variant="body1" color="text.secondary">
-                            No commits found for today in this project directory.
-                        </Typography>
-                        <Typography variant="caption" color="text.disabled" sx={{ display: 'block', mt: 1 }}>
-                            Make some changes and commit!
+                            {isToday
+                                ? "No commits found for today in this project directory."
+                                : "No commits were saved with this entry."}
                         </Typography>
+                        {isToday ? (
+                            <Typography variant="caption" color="text.disabled" sx={{ display: 'block', mt: 1 }}>
+                                Make some changes and commit!
+                            </Typography>
+                        ) : null}
                     </Box>
                 )}
             </Paper>
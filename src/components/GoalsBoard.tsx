@@ -1,4 +1,4 @@
-import { useMemo, useState } from "react";
+import { useDeferredValue, useMemo, useState } from "react";
 import {
   Box,
   Button,
@@ -30,6 +30,8 @@ import {
   useDeleteGoalMilestone,
   useGoalMilestones,
   useGoals,
+  useSaveGoalReflection,
+  useSimilarGoalReflections,
   useUpdateGoal,
   useUpdateGoalMilestone,
 } from "../hooks/useGoals";
@@ -123,6 +125,9 @@ export const GoalsBoard = () => {
   const updateGoalMilestone = useUpdateGoalMilestone();
   const deleteGoalMilestone = useDeleteGoalMilestone();
   const deleteGoal = useDeleteGoal();
+  const saveGoalReflection = useSaveGoalReflection();
+  const deferredTitle = useDeferredValue(editingGoal ? "" : title);
+  const { data: similarReflections = [] } = useSimilarGoalReflections(deferredTitle);
 
   const [query, setQuery] = useState("");
   const [statusFilter, setStatusFilter] = useState<"all" | GoalStatus>("all");
@@ -139,6 +144,9 @@ export const GoalsBoard = () => {
   const [confirmDeleteId, setConfirmDeleteId] = useState<number | null>(null);
   const [newMilestoneTitles, setNewMilestoneTitles] = useState<Record<number, string>>({});
   const [newMilestoneDueDates, setNewMilestoneDueDates] = useState<Record<number, string>>({});
+  const [reflectionGoal, setReflectionGoal] = useState<Goal | null>(null);
+  const [whatWorked, setWhatWorked] = useState("");
+  const [whatDidnt, setWhatDidnt] = useState("");
 
   const busy =
     createGoal.isPending ||
@@ -273,6 +281,9 @@ export const GoalsBoard = () => {
           onSuccess: () => {
             setDialogOpen(false);
             setDialogError("");
+            if (normalizedStatus === "completed" && editingGoal.status !== "completed") {
+              openReflection({ ...editingGoal, title: cleanTitle });
+            }
           },
           onError: () => {
             setDialogError(t("Failed to save goal. Please try again."));
@@ -344,15 +355,53 @@ export const GoalsBoard = () => {
   };
 
   const quickUpdate = (goal: Goal, nextStatus: GoalStatus, nextProgress: number) => {
-    updateGoal.mutate({
-      id: goal.id,
-      title: goal.title,
-      description: goal.description,
-      status: nextStatus,
-      progress: normalizeProgress(nextProgress),
-      project_id: goal.project_id,
-      target_date: goal.target_date,
-    });
+    updateGoal.mutate(
+      {
+        id: goal.id,
+        title: goal.title,
+        description: goal.description,
+        status: nextStatus,
+        progress: normalizeProgress(nextProgress),
+        project_id: goal.project_id,
+        target_date: goal.target_date,
+      },
+      {
+        onSuccess: () => {
+          if (nextStatus === "completed" && goal.status !== "completed") {
+            openReflection(goal);
+          }
+        },
+      }
+    );
+  };
+
+  const openReflection = (goal: Goal) => {
+    setWhatWorked("");
+    setWhatDidnt("");
+    setReflectionGoal(goal);
+  };
+
+  const handleSaveReflection = () => {
+    if (!reflectionGoal) {
+      return;
+    }
+
+    saveGoalReflection.mutate(
+      {
+        goal_id: reflectionGoal.id,
+        what_worked: whatWorked.trim(),
+        what_didnt: whatDidnt.trim(),
+      },
+      {
+        onSuccess: () => {
+          setReflectionGoal(null);
+          notify(t("Reflection saved."), "success");
+        },
+        onError: () => {
+          notify(t("Failed to save reflection. Please try again."), "error");
+        },
+      }
+    );
   };
 
   return (
@@ -805,6 +854,33 @@ export const GoalsBoard = () => {
               fullWidth
             />
 
+            {similarReflections.length > 0 ? (
+              <Paper variant="outlined" sx={{ p: 1.5 }}>
+                <Typography variant="subtitle2" sx={{ mb: 1 }}>
+                  {t("Lessons from similar goals")}
+                </Typography>
+                <Stack spacing={1}>
+                  {similarReflections.map((reflection) => (
+                    <Box key={reflection.id}>
+                      <Typography variant="body2" fontWeight={600}>
+                        {reflection.goal_title}
+                      </Typography>
+                      {reflection.what_worked ? (
+                        <Typography variant="body2" color="text.secondary">
+                          {t("Worked: {text}", { text: reflection.what_worked })}
+                        </Typography>
+                      ) : null}
+                      {reflection.what_didnt ? (
+                        <Typography variant="body2" color="text.secondary">
+                          {t("Didn't work: {text}", { text: reflection.what_didnt })}
+                        </Typography>
+                      ) : null}
+                    </Box>
+                  ))}
+                </Stack>
+              </Paper>
+            ) : null}
+
             <TextField
               label={t("Description")}
               value={description}
@@ -878,6 +954,49 @@ export const GoalsBoard = () => {
         </DialogActions>
       </Dialog>
 
+      <Dialog open={reflectionGoal !== null} onClose={() => setReflectionGoal(null)} fullWidth maxWidth="sm">
+        <DialogTitle>{t("Reflect on \"{title}\"", { title: reflectionGoal?.title ?? "" })}</DialogTitle>
+        <DialogContent>
+          <Stack spacing={2} sx={{ mt: 1 }}>
+            <Typography variant="body2" color="text.secondary">
+              {t("Goal completed. A short reflection will be shown when you create similar goals.")}
+            </Typography>
+            <TextField
+              label={t("What worked")}
+              value={whatWorked}
+              onChange={(event) => setWhatWorked(event.target.value)}
+              multiline
+              minRows={2}
+              autoFocus
+              fullWidth
+            />
+            <TextField
+              label={t("What didn't")}
+              value={whatDidnt}
+              onChange={(event) => setWhatDidnt(event.target.value)}
+              multiline
+              minRows={2}
+              fullWidth
+            />
+          </Stack>
+        </DialogContent>
+        <DialogActions>
+          <Button onClick={() => setReflectionGoal(null)} color="inherit">
+            {t("Skip")}
+          </Button>
+          <Button
+            onClick={handleSaveReflection}
+            variant="contained"
+            disabled={
+              saveGoalReflection.isPending ||
+              (whatWorked.trim().length === 0 && whatDidnt.trim().length === 0)
+            }
+          >
+            {t("Save reflection")}
+          </Button>
+        </DialogActions>
+      </Dialog>
+
       <Dialog open={confirmDeleteId !== null} onClose={() => setConfirmDeleteId(null)}>
         <DialogTitle>{t("Delete goal?")}</DialogTitle>
         <DialogContent>
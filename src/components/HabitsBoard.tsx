@@ -27,9 +27,13 @@ import {
   useToggleHabitCompletion,
   useUpdateHabit,
 } from "../hooks/useHabits";
+import { useAppSetting, useUpdateAppSetting } from "../hooks/useAppSettings";
+import { queryKeys } from "../hooks/queryInvalidation";
 import { HabitWithLogs } from "../types";
 import { useI18n } from "../i18n/I18nContext";
 
+const STREAK_FREEZE_OPTIONS = [0, 5, 7, 10, 14];
+
 const computeWeekDates = () =>
   Array.from({ length: 7 }, (_, index) =>
     format(subDays(new Date(), 6 - index), "yyyy-MM-dd")
@@ -70,6 +74,10 @@ export const HabitsBoard = () => {
   const updateHabit = useUpdateHabit();
   const deleteHabit = useDeleteHabit();
   const toggleCompletion = useToggleHabitCompletion();
+  const streakFreezeEvery = useAppSetting("streak_freeze_every") ?? "0";
+  // Freeze tokens form one pool shared by all habits.
+  const freezeTokens = habits[0]?.freeze_tokens ?? 0;
+  const updateSetting = useUpdateAppSetting([queryKeys.habits]);
 
   const [query, setQuery] = useState("");
   const [onlyNeedsAttention, setOnlyNeedsAttention] = useState(false);
@@ -236,6 +244,24 @@ export const HabitsBoard = () => {
             size="small"
           />
           <Chip label={t("Avg streak: {count}d", { count: stats.avgStreak })} color="default" variant="outlined" size="small" />
+          {freezeTokens > 0 ? (
+            <Chip label={t("❄ {count} freezes", { count: freezeTokens })} variant="outlined" size="small" />
+          ) : null}
+          <TextField
+            select
+            size="small"
+            label={t("Streak freezes")}
+            value={streakFreezeEvery}
+            onChange={(event) => updateSetting.mutate({ key: "streak_freeze_every", value: event.target.value })}
+            SelectProps={{ native: true }}
+            sx={{ minWidth: 200 }}
+          >
+            {STREAK_FREEZE_OPTIONS.map((every) => (
+              <option key={every} value={String(every)}>
+                {every === 0 ? t("Off") : t("1 per {count} completions", { count: every })}
+              </option>
+            ))}
+          </TextField>
         </Stack>
 
         <Stack direction={{ xs: "column", md: "row" }} spacing={2} sx={{ mt: 2 }}>
@@ -327,13 +353,14 @@ export const HabitsBoard = () => {
                   <Stack direction="row" spacing={0} sx={{ mt: 1.5, flexWrap: "wrap", gap: 0.75 }}>
                     {weekDates.map((date) => {
                       const completed = habit.completed_dates.includes(date);
+                      const frozen = !completed && habit.frozen_dates.includes(date);
 
                       return (
                         <Chip
                           key={`${habit.id}-${date}`}
                           size="small"
-                          label={toDayLabel(date)}
-                          color={completed ? "success" : "default"}
+                          label={frozen ? `❄ ${toDayLabel(date)}` : toDayLabel(date)}
+                          color={completed ? "success" : frozen ? "info" : "default"}
                           variant={completed ? "filled" : "outlined"}
                           onClick={() => handleToggle(habit, date)}
                           clickable
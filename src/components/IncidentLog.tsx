@@ -0,0 +1,289 @@
+import { FormEvent, useState } from "react";
+import AddIcon from "@mui/icons-material/Add";
+import DeleteOutlineIcon from "@mui/icons-material/DeleteOutline";
+import ReportProblemOutlinedIcon from "@mui/icons-material/ReportProblemOutlined";
+import {
+  Autocomplete,
+  Box,
+  Button,
+  Chip,
+  Dialog,
+  DialogActions,
+  DialogContent,
+  DialogTitle,
+  MenuItem,
+  Paper,
+  Stack,
+  TextField,
+  Typography,
+} from "@mui/material";
+import { alpha, useTheme } from "@mui/material/styles";
+import { differenceInMinutes, format, subDays } from "date-fns";
+import {
+  useCreateIncident,
+  useDeleteIncident,
+  useIncidentTimeline,
+  useIncidents,
+  useUpdateIncident,
+} from "../hooks/useIncidents";
+import { usePages } from "../hooks/usePages";
+import { useTasks } from "../hooks/useTasks";
+import { useI18n } from "../i18n/I18nContext";
+import type { Incident, IncidentInput, IncidentSeverity } from "../types";
+
+const VISIBLE_INCIDENTS = 6;
+const TIMELINE_DAYS = 7;
+const SEVERITIES: IncidentSeverity[] = ["sev1", "sev2", "sev3", "sev4"];
+const SEVERITY_COLORS: Record<IncidentSeverity, "error" | "warning" | "info" | "default"> = {
+  sev1: "error",
+  sev2: "warning",
+  sev3: "info",
+  sev4: "default",
+};
+
+// `datetime-local` inputs work in local time without a zone; the backend stores UTC.
+const toInputValue = (iso: string | null) => (iso ? format(new Date(iso), "yyyy-MM-dd'T'HH:mm") : "");
+const fromInputValue = (value: string) => (value ? new Date(value).toISOString() : null);
+
+const durationLabel = (incident: Incident) => {
+  if (!incident.ended_at) return null;
+  const minutes = Math.max(0, differenceInMinutes(new Date(incident.ended_at), new Date(incident.started_at)));
+  const hours = Math.floor(minutes / 60);
+  if (hours === 0) return `${minutes}m`;
+  return minutes % 60 === 0 ? `${hours}h` : `${hours}h ${minutes % 60}m`;
+};
+
+const emptyInput = (): IncidentInput => ({
+  started_at: new Date().toISOString(),
+  ended_at: null,
+  severity: "sev3",
+  summary: "",
+  resolution: "",
+  task_ids: [],
+  page_ids: [],
+});
+
+const IncidentDialog = ({
+  incident,
+  open,
+  onClose,
+}: {
+  incident: Incident | null;
+  open: boolean;
+  onClose: () => void;
+}) => {
+  const { t } = useI18n();
+  const { data: tasks = [] } = useTasks();
+  const { data: pages = [] } = usePages();
+  const createIncident = useCreateIncident();
+  const updateIncident = useUpdateIncident();
+  const deleteIncident = useDeleteIncident();
+  const [draft, setDraft] = useState<IncidentInput>(() =>
+    incident
+      ? {
+          started_at: incident.started_at,
+          ended_at: incident.ended_at,
+          severity: incident.severity,
+          summary: incident.summary,
+          resolution: incident.resolution,
+          task_ids: incident.task_ids,
+          page_ids: incident.page_ids,
+        }
+      : emptyInput()
+  );
+  const saving = createIncident.isPending || updateIncident.isPending;
+  const error = createIncident.error ?? updateIncident.error;
+
+  const handleSubmit = (event: FormEvent) => {
+    event.preventDefault();
+    if (incident) {
+      updateIncident.mutate({ id: incident.id, input: draft }, { onSuccess: onClose });
+    } else {
+      createIncident.mutate(draft, { onSuccess: onClose });
+    }
+  };
+
+  return (
+    <Dialog open={open} onClose={onClose} maxWidth="sm" fullWidth>
+      <Box component="form" onSubmit={handleSubmit}>
+        <DialogTitle>{incident ? t("Edit incident") : t("Log incident")}</DialogTitle>
+        <DialogContent>
+          <Stack spacing={2} sx={{ pt: 1 }}>
+            <TextField
+              autoFocus
+              fullWidth
+              size="small"
+              label={t("Summary")}
+              value={draft.summary}
+              onChange={(event) => setDraft({ ...draft, summary: event.target.value })}
+            />
+            <Stack direction="row" spacing={1.5}>
+              <TextField
+                select
+                size="small"
+                label={t("Severity")}
+                value={draft.severity}
+                onChange={(event) => setDraft({ ...draft, severity: event.target.value as IncidentSeverity })}
+                sx={{ minWidth: 110 }}
+              >
+                {SEVERITIES.map((severity) => (
+                  <MenuItem key={severity} value={severity}>
+                    {severity.toUpperCase()}
+                  </MenuItem>
+                ))}
+              </TextField>
+              <TextField
+                fullWidth
+                size="small"
+                type="datetime-local"
+                label={t("Started")}
+                value={toInputValue(draft.started_at)}
+                onChange={(event) => setDraft({ ...draft, started_at: fromInputValue(event.target.value) ?? draft.started_at })}
+                InputLabelProps={{ shrink: true }}
+              />
+              <TextField
+                fullWidth
+                size="small"
+                type="datetime-local"
+                label={t("Resolved")}
+                value={toInputValue(draft.ended_at)}
+                onChange={(event) => setDraft({ ...draft, ended_at: fromInputValue(event.target.value) })}
+                InputLabelProps={{ shrink: true }}
+              />
+            </Stack>
+            <TextField
+              fullWidth
+              multiline
+              minRows={3}
+              size="small"
+              label={t("Resolution and follow-ups")}
+              value={draft.resolution}
+              onChange={(event) => setDraft({ ...draft, resolution: event.target.value })}
+            />
+            <Autocomplete
+              multiple
+              size="small"
+              options={tasks.map((task) => task.id)}
+              value={draft.task_ids}
+              getOptionLabel={(id) => tasks.find((task) => task.id === id)?.title ?? `#${id}`}
+              onChange={(_event, taskIds) => setDraft({ ...draft, task_ids: taskIds })}
+              renderInput={(params) => <TextField {...params} label={t("Linked tasks")} />}
+            />
+            <Autocomplete
+              multiple
+              size="small"
+              options={pages.map((page) => page.id)}
+              value={draft.page_ids}
+              getOptionLabel={(id) => pages.find((page) => page.id === id)?.title || t("Untitled")}
+              onChange={(_event, pageIds) => setDraft({ ...draft, page_ids: pageIds })}
+              renderInput={(params) => <TextField {...params} label={t("Linked pages")} />}
+            />
+            {error ? (
+              <Typography variant="caption" color="error">
+                {String(error)}
+              </Typography>
+            ) : null}
+          </Stack>
+        </DialogContent>
+        <DialogActions>
+          {incident ? (
+            <Button
+              color="error"
+              startIcon={<DeleteOutlineIcon />}
+              onClick={() => deleteIncident.mutate(incident.id, { onSuccess: onClose })}
+              sx={{ mr: "auto" }}
+            >
+              {t("Delete")}
+            </Button>
+          ) : null}
+          <Button onClick={onClose}>{t("Cancel")}</Button>
+          <Button type="submit" variant="contained" disabled={!draft.summary.trim() || saving}>
+            {t("Save")}
+          </Button>
+        </DialogActions>
+      </Box>
+    </Dialog>
+  );
+};
+
+// Structured on-call notes: ongoing incidents stay on top, the timeline covers the last week.
+export const IncidentLog = () => {
+  const muiTheme = useTheme();
+  const { t } = useI18n();
+  const { data: incidents = [] } = useIncidents();
+  const today = new Date();
+  const { data: timeline = [] } = useIncidentTimeline(
+    format(subDays(today, TIMELINE_DAYS - 1), "yyyy-MM-dd"),
+    format(today, "yyyy-MM-dd")
+  );
+  const [editing, setEditing] = useState<Incident | null>(null);
+  const [dialogOpen, setDialogOpen] = useState(false);
+
+  const openDialog = (incident: Incident | null) => {
+    setEditing(incident);
+    setDialogOpen(true);
+  };
+
+  return (
+    <Paper
+      sx={{
+        mt: 4,
+        p: 3,
+        border: "1px solid",
+        borderColor: "divider",
+        bgcolor: alpha(muiTheme.palette.background.paper, 0.82),
+      }}
+    >
+      <Box sx={{ display: "flex", alignItems: "center", justifyContent: "space-between", mb: 1 }}>
+        <Typography variant="h6" sx={{ display: "flex", alignItems: "center", gap: 1, fontWeight: 600 }}>
+          <ReportProblemOutlinedIcon color="primary" /> {t("Incidents")}
+        </Typography>
+        <Button size="small" startIcon={<AddIcon />} onClick={() => openDialog(null)}>
+          {t("Log incident")}
+        </Button>
+      </Box>
+      {incidents.length === 0 ? (
+        <Typography variant="body2" color="text.secondary">
+          {t("No incidents logged.")}
+        </Typography>
+      ) : null}
+      {incidents.slice(0, VISIBLE_INCIDENTS).map((incident) => (
+        <Box
+          key={incident.id}
+          onClick={() => openDialog(incident)}
+          sx={{ display: "flex", alignItems: "center", gap: 1, py: 0.75, cursor: "pointer" }}
+        >
+          <Chip size="small" color={SEVERITY_COLORS[incident.severity]} label={incident.severity.toUpperCase()} />
+          <Typography variant="body2" noWrap sx={{ flex: 1, minWidth: 0 }}>
+            {incident.summary}
+          </Typography>
+          <Typography variant="caption" color={incident.ended_at ? "text.secondary" : "error"}>
+            {incident.ended_at
+              ? t("Resolved in {duration}", { duration: durationLabel(incident) ?? "" })
+              : t("Ongoing since {time}", { time: format(new Date(incident.started_at), "MMM d, HH:mm") })}
+          </Typography>
+        </Box>
+      ))}
+      {timeline.length > 0 ? (
+        <Box sx={{ mt: 2 }}>
+          <Typography variant="subtitle2" color="text.secondary" sx={{ mb: 0.5 }}>
+            {t("Timeline, last 7 days")}
+          </Typography>
+          {timeline.map((event, index) => (
+            <Typography key={`${event.incident_id}-${event.kind}-${index}`} variant="caption" sx={{ display: "block" }}>
+              {format(new Date(event.at), "EEE HH:mm")} · {event.severity.toUpperCase()} {event.summary}:{" "}
+              {event.kind === "started"
+                ? t("started")
+                : event.kind === "resolved"
+                  ? t("resolved")
+                  : t("task done: {title}", { title: event.detail })}
+            </Typography>
+          ))}
+        </Box>
+      ) : null}
+      {dialogOpen ? (
+        <IncidentDialog incident={editing} open={dialogOpen} onClose={() => setDialogOpen(false)} />
+      ) : null}
+    </Paper>
+  );
+};
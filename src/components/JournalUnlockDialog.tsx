@@ -0,0 +1,48 @@
+import { FormEvent, useState } from "react";
+import LockOutlinedIcon from "@mui/icons-material/LockOutlined";
+import { Box, Button, Dialog, DialogActions, DialogContent, DialogTitle, TextField, Typography } from "@mui/material";
+import { useJournalEncryptionStatus, useUnlockJournal } from "../hooks/useJournalEncryption";
+import { useI18n } from "../i18n/I18nContext";
+
+// Blocks the app while an encrypted journal is locked; entry and page content cannot be read until then.
+export const JournalUnlockDialog = () => {
+  const { t } = useI18n();
+  const { data: status } = useJournalEncryptionStatus();
+  const unlockJournal = useUnlockJournal();
+  const [passphrase, setPassphrase] = useState("");
+
+  const handleSubmit = (event: FormEvent) => {
+    event.preventDefault();
+    unlockJournal.mutate(passphrase, { onSuccess: () => setPassphrase("") });
+  };
+
+  return (
+    <Dialog open={Boolean(status?.enabled && !status.unlocked)} maxWidth="xs" fullWidth>
+      <Box component="form" onSubmit={handleSubmit}>
+        <DialogTitle sx={{ display: "flex", alignItems: "center", gap: 1 }}>
+          <LockOutlinedIcon color="primary" /> {t("Unlock journal")}
+        </DialogTitle>
+        <DialogContent>
+          <Typography variant="body2" color="text.secondary" sx={{ mb: 2 }}>
+            {t("Your entries and pages are encrypted. Enter your passphrase to read and edit them.")}
+          </Typography>
+          <TextField
+            autoFocus
+            fullWidth
+            type="password"
+            label={t("Passphrase")}
+            value={passphrase}
+            onChange={(event) => setPassphrase(event.target.value)}
+            error={unlockJournal.isError}
+            helperText={unlockJournal.isError ? String(unlockJournal.error) : " "}
+          />
+        </DialogContent>
+        <DialogActions>
+          <Button type="submit" variant="contained" disabled={!passphrase || unlockJournal.isPending}>
+            {unlockJournal.isPending ? t("Unlocking...") : t("Unlock")}
+          </Button>
+        </DialogActions>
+      </Box>
+    </Dialog>
+  );
+};
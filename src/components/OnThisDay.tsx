@@ -0,0 +1,52 @@
+import HistoryRoundedIcon from "@mui/icons-material/HistoryRounded";
+import { Box, Paper, Typography } from "@mui/material";
+import { alpha, useTheme } from "@mui/material/styles";
+import { useEntriesOnThisDay } from "../hooks/useEntries";
+import { useI18n } from "../i18n/I18nContext";
+
+const PREVIEW_LENGTH = 220;
+
+const preview = (text: string) =>
+  text.length > PREVIEW_LENGTH ? `${text.slice(0, PREVIEW_LENGTH).trimEnd()}…` : text;
+
+// Same calendar day in earlier years; hidden when there is nothing to look back on.
+export const OnThisDay = ({ date }: { date: string }) => {
+  const muiTheme = useTheme();
+  const { t } = useI18n();
+  const { data: entries = [] } = useEntriesOnThisDay(date.slice(5));
+
+  if (entries.length === 0) return null;
+
+  return (
+    <Paper
+      sx={{
+        mt: 4,
+        p: 3,
+        border: "1px solid",
+        borderColor: "divider",
+        bgcolor: alpha(muiTheme.palette.background.paper, 0.82),
+      }}
+    >
+      <Typography variant="h6" sx={{ display: "flex", alignItems: "center", gap: 1, fontWeight: 600, mb: 1 }}>
+        <HistoryRoundedIcon color="primary" /> {t("On this day")}
+      </Typography>
+      {entries.map((entry) => (
+        <Box key={entry.id} sx={{ py: 0.75 }}>
+          <Typography variant="body2" sx={{ fontWeight: 600 }}>
+            {entry.date.slice(0, 4)}
+          </Typography>
+          {entry.yesterday.trim() ? (
+            <Typography variant="body2" color="text.secondary" sx={{ whiteSpace: "pre-wrap", lineHeight: 1.5 }}>
+              {preview(entry.yesterday.trim())}
+            </Typography>
+          ) : null}
+          {entry.today.trim() ? (
+            <Typography variant="caption" color="text.secondary" sx={{ display: "block", whiteSpace: "pre-wrap" }}>
+              {t("Planned")}: {preview(entry.today.trim())}
+            </Typography>
+          ) : null}
+        </Box>
+      ))}
+    </Paper>
+  );
+};
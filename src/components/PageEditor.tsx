@@ -17,10 +17,25 @@ import {
     Stack,
     useMediaQuery,
 } from "@mui/material";
-import { useState, useEffect, useMemo, useCallback, useRef } from "react";
+import { useState, useEffect, useMemo, useCallback, useRef, type ClipboardEvent, type DragEvent } from "react";
 import { PageEditorHeader } from "./page-editor/PageEditorHeader";
 import { PageEditorToolbar } from "./page-editor/PageEditorToolbar";
-import { usePage, useCreatePage, useUpdatePage, useDeletePage } from "../hooks/usePages";
+import { PageExportDialog } from "./page-editor/PageExportDialog";
+import { PageHistoryDialog } from "./page-editor/PageHistoryDialog";
+import { isPageContentLocked, PageLockDialog, PageLockScreen } from "./page-editor/PageLock";
+import { PageLinks } from "./page-editor/PageLinks";
+import { PageProperties } from "./page-editor/PageProperties";
+import { PageTags } from "./page-editor/PageTags";
+import {
+    usePage,
+    usePageStats,
+    useCreatePage,
+    useUpdatePage,
+    useDeletePage,
+    useLockPage,
+    useRecordPageView,
+    useSavePageAsset,
+} from "../hooks/usePages";
 import { useGoals } from "../hooks/useGoals";
 import { useProjects } from "../hooks/useProjects";
 import { useTasks, useUpdateTaskStatus } from "../hooks/useTasks";
@@ -28,6 +43,10 @@ import { motion } from "framer-motion";
 import { useTheme } from "@mui/material/styles";
 import DeleteIcon from '@mui/icons-material/Delete';
 import RestartAltIcon from '@mui/icons-material/RestartAlt';
+import HistoryIcon from '@mui/icons-material/History';
+import LockOpenOutlinedIcon from '@mui/icons-material/LockOpenOutlined';
+import LockOutlinedIcon from '@mui/icons-material/LockOutlined';
+import DownloadIcon from '@mui/icons-material/Download';
 import StarBorderIcon from '@mui/icons-material/StarBorder';
 import PersonOutlineIcon from '@mui/icons-material/PersonOutline';
 import ChecklistRtlIcon from '@mui/icons-material/ChecklistRtl';
@@ -84,10 +103,14 @@ import { useAppNotifications } from "../notifications/AppNotifications";
 
 interface PageEditorProps {
     pageId: number | null;
+    /** Parent for the page created when `pageId` is null. */
+    newPageParentId?: number | null;
     previewEnabled: boolean;
     autosaveEnabled: boolean;
     onSaveSuccess: (id: number) => void;
     onDeleteSuccess: () => void;
+    /** Follows a `[[wiki link]]` or backlink to another page. */
+    onOpenPage?: (id: number) => void;
 }
 
 const PageTaskTable = ({
@@ -996,22 +1019,31 @@ const PageTaskTrackerDatabase = ({
     );
 };
 
-export const PageEditor = ({ pageId, previewEnabled, autosaveEnabled, onSaveSuccess, onDeleteSuccess }: PageEditorProps) => {
+export const PageEditor = ({ pageId, newPageParentId = null, previewEnabled, autosaveEnabled, onSaveSuccess, onDeleteSuccess, onOpenPage }: PageEditorProps) => {
     const muiTheme = useTheme();
     const isCompactDesktop = useMediaQuery(muiTheme.breakpoints.between("md", "xl"));
     const { data: page, isLoading } = usePage(pageId);
+    const { data: pageStats } = usePageStats(pageId);
     const { data: tasks = [] } = useTasks();
     const { data: projects = [] } = useProjects();
     const { data: goals = [] } = useGoals();
     const createMutation = useCreatePage();
     const updateMutation = useUpdatePage();
     const deleteMutation = useDeletePage();
+    const savePageAsset = useSavePageAsset();
+    const recordPageView = useRecordPageView();
+    const lockPage = useLockPage();
     const updateTaskStatus = useUpdateTaskStatus();
     const { notify } = useAppNotifications();
 
     const [title, setTitle] = useState("Untitled Page");
     const [content, setContent] = useState("");
+    const [icon, setIcon] = useState("");
+    const [cover, setCover] = useState("");
     const [draftRestored, setDraftRestored] = useState(false);
+    const [historyOpen, setHistoryOpen] = useState(false);
+    const [exportOpen, setExportOpen] = useState(false);
+    const [lockDialogMode, setLockDialogMode] = useState<"enable" | "disable" | null>(null);
     const [pageSection, setPageSection] = useState<"page" | "tasks" | "checklist">("page");
     const pageIdKey: PageIdKey = pageId ?? "new";
     const [taskTrackerDataById, setTaskTrackerDataById] = useState<Record<string, TaskTrackerData>>({});
@@ -1027,6 +1059,13 @@ export const PageEditor = ({ pageId, previewEnabled, autosaveEnabled, onSaveSucc
         }
     }, []);
 
+    // One view per opened page; saves refetch the page but keep its id.
+    const { mutate: recordView } = recordPageView;
+    const viewedPageId = page?.id;
+    useEffect(() => {
+        if (viewedPageId !== undefined) recordView(viewedPageId);
+    }, [viewedPageId, recordView]);
+
     useEffect(() => {
         const pageTitle = page?.title ?? "Untitled Page";
         const pageContent = page?.content ?? "";
@@ -1048,13 +1087,17 @@ export const PageEditor = ({ pageId, previewEnabled, autosaveEnabled, onSaveSucc
 
         setTitle(nextTitle);
         setContent(nextContent);
+        setIcon(page?.icon ?? "");
+        setCover(page?.cover ?? "");
         setDraftRestored(restored);
     }, [page, pageIdKey]);
 
+    // Drafts live in local storage, so locked pages never keep one.
+    const isLockedPage = Boolean(page?.locked);
     useEffect(() => {
         clearPendingAutosave();
 
-        if (!autosaveEnabled) {
+        if (!autosaveEnabled || isLockedPage) {
             return;
         }
 
@@ -1064,7 +1107,7 @@ export const PageEditor = ({ pageId, previewEnabled, autosaveEnabled, onSaveSucc
         }, 700);
 
         return clearPendingAutosave;
-    }, [autosaveEnabled, clearPendingAutosave, content, pageIdKey, title]);
+    }, [autosaveEnabled, clearPendingAutosave, content, isLockedPage, pageIdKey, title]);
 
     useEffect(() => {
         const stored = readPageTaskTrackerDataById(pageIdKey);
@@ -1171,7 +1214,7 @@ export const PageEditor = ({ pageId, previewEnabled, autosaveEnabled, onSaveSucc
     const handleSave = useCallback(() => {
         const contentToPersist = materializeTaskTrackerTokensForSave(content, taskTrackerDataById);
         if (pageId) {
-            updateMutation.mutate({ id: pageId, title, content: contentToPersist }, {
+            updateMutation.mutate({ id: pageId, title, content: contentToPersist, appearance: { icon, cover } }, {
                 onSuccess: () => {
                     clearPendingAutosave();
                     removePageDraft(pageIdKey);
@@ -1183,7 +1226,7 @@ export const PageEditor = ({ pageId, previewEnabled, autosaveEnabled, onSaveSucc
                 },
             });
         } else {
-            createMutation.mutate({ title, content: contentToPersist }, {
+            createMutation.mutate({ title, content: contentToPersist, parentId: newPageParentId, appearance: { icon, cover } }, {
                 onSuccess: (newPage) => {
                     clearPendingAutosave();
                     removePageDraft(pageIdKey);
@@ -1196,7 +1239,7 @@ export const PageEditor = ({ pageId, previewEnabled, autosaveEnabled, onSaveSucc
                 },
             });
         }
-    }, [clearPendingAutosave, content, createMutation, notify, onSaveSuccess, pageId, pageIdKey, taskTrackerDataById, title, updateMutation]);
+    }, [clearPendingAutosave, content, cover, createMutation, icon, newPageParentId, notify, onSaveSuccess, pageId, pageIdKey, taskTrackerDataById, title, updateMutation]);
 
     useEffect(() => {
         const handleKeyDown = (event: KeyboardEvent) => {
@@ -1214,7 +1257,7 @@ export const PageEditor = ({ pageId, previewEnabled, autosaveEnabled, onSaveSucc
 
     const handleDelete = () => {
         if (pageId) {
-            const confirmed = window.confirm("Delete this page?");
+            const confirmed = window.confirm("Move this page to the trash? It can be restored from Settings for 30 days.");
             if (!confirmed) {
                 return;
             }
@@ -1350,6 +1393,43 @@ export const PageEditor = ({ pageId, previewEnabled, autosaveEnabled, onSaveSucc
         const embeddedTokens = extractEmbeddedBlockTokens(content);
         setContent(fromEditorDisplayContent(nextValue, embeddedTokens));
     }, [content]);
+    // Pasted or dropped images are stored as page assets and embedded at the cursor.
+    const insertImageFiles = useCallback(async (files: File[], textarea: HTMLTextAreaElement) => {
+        if (pageId === null) {
+            notify("Save the page before adding images.", "info");
+            return;
+        }
+        const start = textarea.selectionStart;
+        const end = textarea.selectionEnd;
+        try {
+            const embeds: string[] = [];
+            for (const file of files) {
+                const bytes = new Uint8Array(await file.arrayBuffer());
+                const asset = await savePageAsset.mutateAsync({ pageId, bytes, filename: file.name || "image.png" });
+                embeds.push(`![${asset.file_name.replace(/\.[^.]+$/, "")}](${asset.url})`);
+            }
+            const current = textarea.value;
+            handleEditorContentChange(`${current.slice(0, start)}${embeds.join("\n")}${current.slice(end)}`);
+        } catch (error) {
+            notify(`Failed to add image: ${String(error)}`, "error");
+        }
+    }, [handleEditorContentChange, notify, pageId, savePageAsset]);
+    const handleEditorPaste = useCallback((event: ClipboardEvent<HTMLDivElement>) => {
+        const images = Array.from(event.clipboardData.files).filter((file) => file.type.startsWith("image/"));
+        if (images.length === 0 || !(event.target instanceof HTMLTextAreaElement)) {
+            return;
+        }
+        event.preventDefault();
+        void insertImageFiles(images, event.target);
+    }, [insertImageFiles]);
+    const handleEditorDrop = useCallback((event: DragEvent<HTMLDivElement>) => {
+        const images = Array.from(event.dataTransfer.files).filter((file) => file.type.startsWith("image/"));
+        if (images.length === 0 || !(event.target instanceof HTMLTextAreaElement)) {
+            return;
+        }
+        event.preventDefault();
+        void insertImageFiles(images, event.target);
+    }, [insertImageFiles]);
     const commitEditorBlocks = useCallback((nextBlocks: EditorBlock[]) => {
         handleEditorContentChange(editorBlocksToMarkdown(nextBlocks));
     }, [handleEditorContentChange]);
@@ -1436,6 +1516,8 @@ export const PageEditor = ({ pageId, previewEnabled, autosaveEnabled, onSaveSucc
         </Box>
     );
 
+    if (page && isPageContentLocked(page)) return <PageLockScreen page={page} />;
+
     return (
         <motion.div
             key={pageId || 'new'}
@@ -1457,14 +1539,21 @@ export const PageEditor = ({ pageId, previewEnabled, autosaveEnabled, onSaveSucc
                 }}
             >
                 <PageEditorHeader
+                    cover={cover}
                     draftRestored={draftRestored}
+                    icon={icon}
                     isCompactDesktop={isCompactDesktop}
                     onSave={handleSave}
                     saving={createMutation.isPending || updateMutation.isPending}
+                    setCover={setCover}
+                    setIcon={setIcon}
                     setTitle={setTitle}
                     title={title}
                 />
 
+                <PageTags pageId={pageId} />
+                <PageProperties pageId={pageId} onOpenPage={onOpenPage} />
+
                 <PageEditorToolbar
                     insertChecklist={insertChecklist}
                     insertCodeBlock={insertCodeBlock}
@@ -1494,6 +1583,8 @@ export const PageEditor = ({ pageId, previewEnabled, autosaveEnabled, onSaveSucc
                             minRows={16}
                             value={editorDisplayContent}
                             onChange={(event) => handleEditorContentChange(event.target.value)}
+                            onPaste={handleEditorPaste}
+                            onDrop={handleEditorDrop}
                             placeholder="Write anything you need. Notes, ideas, requirements..."
                             data-testid="page-editor-freewrite-input"
                             inputProps={{ "data-testid": "page-editor-freewrite-textarea" }}
@@ -1688,6 +1779,15 @@ export const PageEditor = ({ pageId, previewEnabled, autosaveEnabled, onSaveSucc
                     </Box>
                 ) : null}
 
+                <PageLinks pageId={pageId} content={content} onOpenPage={onOpenPage} />
+
+                {pageStats ? (
+                    <Typography variant="caption" color="text.secondary" sx={{ display: "block", mt: 1.5 }}>
+                        {pageStats.word_count} words · {pageStats.reading_minutes} min read · {pageStats.revision_count}{" "}
+                        {pageStats.revision_count === 1 ? "revision" : "revisions"}
+                    </Typography>
+                ) : null}
+
                 <Box
                     sx={{
                         mt: 2.5,
@@ -1710,6 +1810,56 @@ export const PageEditor = ({ pageId, previewEnabled, autosaveEnabled, onSaveSucc
                                 Delete Page
                             </Button>
                         ) : null}
+                        {pageId && !isLockedPage ? (
+                            <Button
+                                variant="text"
+                                color="inherit"
+                                startIcon={<HistoryIcon />}
+                                onClick={() => setHistoryOpen(true)}
+                            >
+                                History
+                            </Button>
+                        ) : null}
+                        {pageId && !isLockedPage ? (
+                            <Button
+                                variant="text"
+                                color="inherit"
+                                startIcon={<LockOutlinedIcon />}
+                                onClick={() => setLockDialogMode("enable")}
+                            >
+                                Lock Page
+                            </Button>
+                        ) : null}
+                        {pageId && isLockedPage ? (
+                            <Button
+                                variant="text"
+                                color="inherit"
+                                startIcon={<LockOutlinedIcon />}
+                                onClick={() => lockPage.mutate(pageId)}
+                            >
+                                Lock Now
+                            </Button>
+                        ) : null}
+                        {pageId && isLockedPage ? (
+                            <Button
+                                variant="text"
+                                color="inherit"
+                                startIcon={<LockOpenOutlinedIcon />}
+                                onClick={() => setLockDialogMode("disable")}
+                            >
+                                Remove Lock
+                            </Button>
+                        ) : null}
+                        {pageId ? (
+                            <Button
+                                variant="text"
+                                color="inherit"
+                                startIcon={<DownloadIcon />}
+                                onClick={() => setExportOpen(true)}
+                            >
+                                Export
+                            </Button>
+                        ) : null}
                         <Button
                             variant="text"
                             color="inherit"
@@ -1721,6 +1871,32 @@ export const PageEditor = ({ pageId, previewEnabled, autosaveEnabled, onSaveSucc
                     </Stack>
                 </Box>
             </Box>
+            {pageId ? (
+                <PageHistoryDialog
+                    pageId={pageId}
+                    open={historyOpen}
+                    onClose={() => setHistoryOpen(false)}
+                    onRestored={() => {
+                        // The restored version replaces any unsaved draft.
+                        clearPendingAutosave();
+                        removePageDraft(pageIdKey);
+                        setDraftRestored(false);
+                    }}
+                />
+            ) : null}
+            {pageId ? (
+                <PageExportDialog pageId={pageId} open={exportOpen} onClose={() => setExportOpen(false)} />
+            ) : null}
+            {/* A draft saved before locking must not stay in local storage. */}
+            {page ? (
+                <PageLockDialog
+                    page={page}
+                    mode={lockDialogMode ?? "enable"}
+                    open={lockDialogMode !== null}
+                    onClose={() => setLockDialogMode(null)}
+                    onChanged={() => removePageDraft(pageIdKey)}
+                />
+            ) : null}
         </motion.div>
     );
 };
@@ -6,17 +6,21 @@ import { PlannerDashboardSection } from "./planner/PlannerDashboardSection";
 import { PlannerMeetingsSection } from "./planner/PlannerMeetingsSection";
 import { PlannerWeeklyReviewSection } from "./planner/PlannerWeeklyReviewSection";
 import { useEntries } from "../hooks/useEntries";
-import { useGoals } from "../hooks/useGoals";
-import { useHabits, useToggleHabitCompletion } from "../hooks/useHabits";
+import { useCheckInGoal, useGoals, useGoalsNeedingCheckin } from "../hooks/useGoals";
+import { useHabits, useStreaksAtRisk, useToggleHabitCompletion } from "../hooks/useHabits";
 import { useProjects } from "../hooks/useProjects";
-import { useCreateTask, useTasks, useUpdateTaskStatus } from "../hooks/useTasks";
+import { useCreateTask, useFindDuplicateTasks, useTasks, useUpdateTaskStatus } from "../hooks/useTasks";
 import {
   useDeleteMeeting,
   useMaterializeMeetingActionItems,
+  useMeetingLoad,
   useMeetings,
 } from "../hooks/useMeetings";
 import { usePlannerMeetingForm } from "../hooks/usePlannerMeetingForm";
 import { usePlannerPreferences } from "../hooks/usePlannerPreferences";
+import { usePomodoroStatus } from "../hooks/usePomodoro";
+import { useMoodCorrelations } from "../hooks/useMoodInsights";
+import { useOnCallDays } from "../hooks/useOnCall";
 import {
   FOCUS_SESSIONS_UPDATED_EVENT,
   readFocusSessionsMap,
@@ -40,7 +44,7 @@ import { isSafeExternalUrl } from "../utils/urlUtils";
 import { useI18n } from "../i18n/I18nContext";
 import { useAppNotifications } from "../notifications/AppNotifications";
 import { openUrl } from "@tauri-apps/plugin-opener";
-import { Meeting, MeetingStatus } from "../types";
+import { DuplicateTaskMatch, Meeting, MeetingStatus } from "../types";
 
 const buildGoogleCalendarLink = (params: {
   title: string;
@@ -91,21 +95,34 @@ export const PlannerBoard = ({
   const toggleHabitCompletion = useToggleHabitCompletion();
   const updateTaskStatus = useUpdateTaskStatus();
   const createTask = useCreateTask();
+  const findDuplicateTasks = useFindDuplicateTasks();
+  const checkInGoal = useCheckInGoal();
   const deleteMeeting = useDeleteMeeting();
   const materializeMeetingActionItems = useMaterializeMeetingActionItems();
 
   const { data: entries = [] } = useEntries();
   const { data: tasks = [] } = useTasks();
   const { data: goals = [] } = useGoals();
+  const { data: goalCheckins = [] } = useGoalsNeedingCheckin();
   const { data: habits = [] } = useHabits();
+  const { data: streaksAtRisk = [] } = useStreaksAtRisk();
   const { data: projects = [] } = useProjects();
   const { data: meetings = [] } = useMeetings();
+  const { data: pomodoroStatus } = usePomodoroStatus();
+  const { data: moodCorrelations = [] } = useMoodCorrelations();
+  const { data: onCallDays } = useOnCallDays(today, today);
+  const { data: meetingLoad = [] } = useMeetingLoad(today, format(addDays(new Date(), 6), "yyyy-MM-dd"));
+  const meetingLoadByDay = useMemo(
+    () => Object.fromEntries(meetingLoad.map((day) => [day.date, day])),
+    [meetingLoad]
+  );
 
   const [quickTaskTitle, setQuickTaskTitle] = useState("");
   const [quickDueMode, setQuickDueMode] = useState<"today" | "tomorrow" | "none">("today");
   const [quickProjectId, setQuickProjectId] = useState<number | "">("");
   const [quickTaskFeedback, setQuickTaskFeedback] = useState("");
   const [quickTaskFeedbackTone, setQuickTaskFeedbackTone] = useState<"success" | "error">("success");
+  const [quickTaskDuplicates, setQuickTaskDuplicates] = useState<DuplicateTaskMatch[]>([]);
   const [focusSessionsMap, setFocusSessionsMap] = useState<Record<string, number>>(() =>
     readFocusSessionsMap()
   );
@@ -207,12 +224,14 @@ export const PlannerBoard = ({
     toggleHabitCompletion.isPending ||
     updateTaskStatus.isPending ||
     createTask.isPending ||
+    findDuplicateTasks.isPending ||
     createMeeting.isPending ||
     updateMeeting.isPending ||
     deleteMeeting.isPending ||
     materializeMeetingActionItems.isPending;
 
-  const focusSessionsToday = focusSessionsMap[today] ?? 0;
+  const focusSessionsToday = pomodoroStatus?.completed_today ?? focusSessionsMap[today] ?? 0;
+  const pomodoroDailyGoal = pomodoroStatus?.daily_goal ?? 0;
   const isDark = muiTheme.palette.mode === "dark";
   const plannerCardSx = {
     p: { xs: 1.5, sm: 2 },
@@ -241,6 +260,11 @@ export const PlannerBoard = ({
     bgcolor: "background.default",
   };
 
+  const handleQuickTaskTitleChange = (value: string) => {
+    setQuickTaskTitle(value);
+    setQuickTaskDuplicates([]);
+  };
+
   const handleQuickAddTask = () => {
     const title = quickTaskTitle.trim();
     if (!title) {
@@ -249,6 +273,22 @@ export const PlannerBoard = ({
       return;
     }
 
+    // Offer the existing task first; a failed check should not block adding.
+    findDuplicateTasks.mutate(title, {
+      onSuccess: (duplicates) => {
+        if (duplicates.length > 0) {
+          setQuickTaskDuplicates(duplicates);
+          setQuickTaskFeedback("");
+          return;
+        }
+        addQuickTask(title);
+      },
+      onError: () => addQuickTask(title),
+    });
+  };
+
+  const addQuickTask = (title: string) => {
+    setQuickTaskDuplicates([]);
     createTask.mutate(
       {
         title,
@@ -348,8 +388,13 @@ export const PlannerBoard = ({
       <PlannerDashboardSection
         busy={busy}
         focusSessionsToday={focusSessionsToday}
+        goalCheckins={goalCheckins}
+        pomodoroDailyGoal={pomodoroDailyGoal}
         habitsWithTodayState={habitsWithTodayState}
         isDark={isDark}
+        meetingLoadToday={meetingLoadByDay[today]}
+        onCallToday={onCallDays?.has(today) ?? false}
+        onGoalCheckIn={(goalId, progress) => checkInGoal.mutate({ goal_id: goalId, progress })}
         onHabitToggle={(habitId, completed) =>
           toggleHabitCompletion.mutate({ habit_id: habitId, date: today, completed })
         }
@@ -357,7 +402,8 @@ export const PlannerBoard = ({
         onOpenHabits={onOpenHabits}
         onOpenTasks={onOpenTasks}
         onQuickTaskProjectChange={(value) => setQuickProjectId(value === "" ? "" : Number(value))}
-        onQuickTaskTitleChange={setQuickTaskTitle}
+        onQuickTaskTitleChange={handleQuickTaskTitleChange}
+        onAddQuickTaskAnyway={() => addQuickTask(quickTaskTitle.trim())}
         onQuickTaskDueModeChange={setQuickDueMode}
         onSubmitQuickTask={handleQuickAddTask}
         plannerInsetCardSx={plannerInsetCardSx}
@@ -369,7 +415,9 @@ export const PlannerBoard = ({
         quickProjectId={quickProjectId}
         quickTaskFeedback={quickTaskFeedback}
         quickTaskFeedbackTone={quickTaskFeedbackTone}
+        quickTaskDuplicates={quickTaskDuplicates}
         quickTaskTitle={quickTaskTitle}
+        streaksAtRisk={streaksAtRisk}
         t={t}
         todayMeetings={todayMeetings}
         updateTaskStatus={(task, checked) =>
@@ -393,6 +441,7 @@ export const PlannerBoard = ({
         meetingAgenda={meetingAgenda}
         meetingCalendarUrl={meetingCalendarUrl}
         meetingDayBuckets={meetingDayBuckets}
+        meetingLoadByDay={meetingLoadByDay}
         meetingDecisions={meetingDecisions}
         meetingEndAt={meetingEndAt}
         meetingFeedback={meetingFeedback}
@@ -439,6 +488,7 @@ export const PlannerBoard = ({
         handleAddDailyWin={handleAddDailyWin}
         handleRemoveDailyWin={handleRemoveDailyWin}
         isSectionCollapsed={(section) => isSectionCollapsed(section)}
+        moodCorrelations={moodCorrelations}
         nearGoals={nearGoals}
         onDailyWinsInputChange={setDailyWinsInput}
         onOpenFocus={onOpenFocus}
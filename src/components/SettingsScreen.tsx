@@ -1,10 +1,11 @@
-import { ChangeEvent, ReactNode, useMemo, useRef, useState } from "react";
+import { ChangeEvent, ReactNode, useEffect, useMemo, useRef, useState } from "react";
 import {
   Box,
   Button,
   ButtonBase,
   Chip,
   Divider,
+  IconButton,
   Slider,
   Switch,
   TextField,
@@ -18,7 +19,10 @@ import LightModeRoundedIcon from "@mui/icons-material/LightModeRounded";
 import DarkModeRoundedIcon from "@mui/icons-material/DarkModeRounded";
 import AutoAwesomeRoundedIcon from "@mui/icons-material/AutoAwesomeRounded";
 import TuneRoundedIcon from "@mui/icons-material/TuneRounded";
+import DeleteOutlineIcon from "@mui/icons-material/DeleteOutline";
+import RestoreRoundedIcon from "@mui/icons-material/RestoreRounded";
 import { format } from "date-fns";
+import { openUrl } from "@tauri-apps/plugin-opener";
 import {
   FontPreset,
   THEME_PRESETS,
@@ -26,19 +30,99 @@ import {
   useThemeContext,
 } from "../theme/ThemeContext";
 import { useI18n } from "../i18n/I18nContext";
-import { useEntries, useImportBackup } from "../hooks/useEntries";
-import { usePages } from "../hooks/usePages";
+import * as api from "../api";
+import {
+  useEntries,
+  useEntrySectionTemplates,
+  useExportEntriesMarkdown,
+  useGenerateDailySheet,
+  useImportBackup,
+  useExportOrg,
+  useExportFiltered,
+  useExportTodoTxt,
+  useImportDayOne,
+  useImportOrg,
+  useImportSlackStandups,
+  useImportObsidianVault,
+  useImportTodoTxt,
+  useImportMarkdownFolder,
+  usePreviewDateShift,
+  usePurgeTrash,
+  useRestoreEntry,
+  useShiftDates,
+  useSaveEntrySectionTemplates,
+  useMirrorHistory,
+  useSyncVaultMirror,
+  useTrashedEntries,
+} from "../hooks/useEntries";
+import {
+  useAddJournalPrompt,
+  useDeleteJournalPrompt,
+  useJournalPrompts,
+  useSetJournalPromptEnabled,
+} from "../hooks/useJournalPrompts";
+import { useEmptyPageTrash, useExportAllPages, usePages, useRestorePage, useTrashedPages } from "../hooks/usePages";
 import { useTaskSubtasks, useTasks } from "../hooks/useTasks";
 import { useGoalMilestones, useGoals } from "../hooks/useGoals";
 import { useHabits } from "../hooks/useHabits";
 import { useProjects } from "../hooks/useProjects";
 import { useProjectBranches } from "../hooks/useProjectBranches";
 import { useMeetings } from "../hooks/useMeetings";
-import { BackupPayload } from "../types";
+import {
+  useApiTokens,
+  useCreateApiToken,
+  useExternalAccessLog,
+  useRevokeApiToken,
+} from "../hooks/useApiTokens";
+import {
+  useAppSetting,
+  useCheckForUpdates,
+  useFeatureFlags,
+  useNotificationTemplates,
+  useSetFeatureFlag,
+  useUpdateAppSetting,
+} from "../hooks/useAppSettings";
+import { queryKeys } from "../hooks/queryInvalidation";
+import {
+  useDisableJournalEncryption,
+  useEnableJournalEncryption,
+  useJournalEncryptionStatus,
+  useLockJournal,
+} from "../hooks/useJournalEncryption";
+import {
+  useCreateOnCallShift,
+  useDeleteOnCallShift,
+  useImportOnCallIcs,
+  useOnCallShifts,
+} from "../hooks/useOnCall";
+import { useScheduledJobs, useSetJobSchedule } from "../hooks/useScheduledJobs";
+import { useIntegrityReport, useVerifyIntegrity } from "../hooks/useIntegrity";
+import {
+  useCreateWorkspaceSnapshot,
+  useDeleteWorkspaceSnapshot,
+  useRestoreWorkspaceSnapshot,
+  useWorkspaceSnapshots,
+} from "../hooks/useWorkspaceSnapshots";
+import { useCheckQueryPlans, useClearSlowOps, usePerformanceReport } from "../hooks/usePerformance";
+import { useSaveSlackStatusSettings, useSlackStatusSettings } from "../hooks/useSlackStatus";
+import {
+  ApiScopePreset,
+  BackupPayload,
+  DateShift,
+  DateShiftEntity,
+  DateShiftFilter,
+  ExportFormat,
+  NotificationKind,
+  PageExportFormat,
+  NotificationTemplate,
+  ScheduledJob,
+  ScheduledJobName,
+} from "../types";
 import {
   applyPreferenceSnapshot,
   exportPreferenceSnapshot,
 } from "../utils/preferencesStorage";
+import { AutomationRulesPanel } from "./tasks/AutomationRulesPanel";
 
 interface SettingsScreenProps {
   reminderEnabled: boolean;
@@ -53,109 +137,2661 @@ interface SettingsScreenProps {
   onAutosaveEnabledChange: (enabled: boolean) => void;
 }
 
-interface OptionTileProps {
-  title: string;
-  description?: string;
-  selected: boolean;
-  onClick: () => void;
-  accent?: string;
-  preview?: ReactNode;
-  radius?: string;
-}
+interface OptionTileProps {
+  title: string;
+  description?: string;
+  selected: boolean;
+  onClick: () => void;
+  accent?: string;
+  preview?: ReactNode;
+  radius?: string;
+}
+
+interface ToggleRowProps {
+  label: string;
+  description: string;
+  checked: boolean;
+  onChange: (checked: boolean) => void;
+}
+
+const SettingsOptionTile = ({
+  title,
+  description,
+  selected,
+  onClick,
+  accent,
+  preview,
+  radius = "16px",
+}: OptionTileProps) => {
+  const theme = useTheme();
+  const isDark = theme.palette.mode === "dark";
+  const { t } = useI18n();
+
+  return (
+    <ButtonBase
+      onClick={onClick}
+      sx={{
+        width: "100%",
+        borderRadius: radius,
+        textAlign: "left",
+        alignItems: "stretch",
+        overflow: "hidden",
+        transition: "border-color 0.14s ease, box-shadow 0.14s ease",
+      }}
+    >
+      <Box
+        sx={{
+          width: "100%",
+          minHeight: 116,
+          p: 1.35,
+          borderRadius: radius,
+          border: "1px solid",
+          borderColor: selected
+            ? accent ?? "primary.main"
+            : theme.palette.divider,
+          bgcolor: selected
+            ? alpha(accent ?? theme.palette.primary.main, isDark ? 0.18 : 0.12)
+            : "background.default",
+          transition: "border-color 0.16s ease, background-color 0.16s ease, box-shadow 0.16s ease",
+          "&:hover": {
+            borderColor: accent ?? "primary.main",
+            boxShadow: theme.shadows[1],
+          },
+        }}
+      >
+        <Box sx={{ display: "flex", alignItems: "flex-start", justifyContent: "space-between", gap: 1 }}>
+          <Box sx={{ minWidth: 0, flex: 1 }}>
+            <Typography variant="body2" sx={{ fontWeight: 700, lineHeight: 1.25 }}>
+              {title}
+            </Typography>
+            {description ? (
+              <Typography variant="caption" color="text.secondary" sx={{ display: "block", mt: 0.4, lineHeight: 1.45 }}>
+                {description}
+              </Typography>
+            ) : null}
+          </Box>
+          {selected ? <Chip size="small" label={t("Active")} color="primary" sx={{ flexShrink: 0, alignSelf: "flex-start" }} /> : null}
+        </Box>
+        {preview ? <Box sx={{ mt: 1.4 }}>{preview}</Box> : null}
+      </Box>
+    </ButtonBase>
+  );
+};
+
+const SettingsToggleRow = ({ label, description, checked, onChange }: ToggleRowProps) => {
+  return (
+    <Box
+      sx={{
+        display: "flex",
+        alignItems: "center",
+        justifyContent: "space-between",
+        gap: 2,
+        py: 1.35,
+        borderBottom: "1px solid",
+        borderColor: "divider",
+      }}
+    >
+      <Box sx={{ minWidth: 0 }}>
+        <Typography variant="body2" sx={{ fontWeight: 600 }}>
+          {label}
+        </Typography>
+        <Typography variant="caption" color="text.secondary" sx={{ display: "block", mt: 0.35, lineHeight: 1.45 }}>
+          {description}
+        </Typography>
+      </Box>
+      <Switch checked={checked} onChange={(event) => onChange(event.target.checked)} />
+    </Box>
+  );
+};
+
+const EntrySectionTemplatesEditor = () => {
+  const { t } = useI18n();
+  const { data: templates = [] } = useEntrySectionTemplates();
+  const saveTemplates = useSaveEntrySectionTemplates();
+  const [draft, setDraft] = useState<{ section_key: string | null; label: string }[]>([]);
+  const [status, setStatus] = useState("");
+
+  useEffect(() => {
+    setDraft(templates.map((template) => ({ section_key: template.section_key, label: template.label })));
+  }, [templates]);
+
+  const handleSave = () => {
+    saveTemplates.mutate(
+      draft.filter((template) => template.label.trim().length > 0),
+      {
+        onSuccess: () => setStatus(t("Entry sections saved.")),
+        onError: (error) => setStatus(String(error)),
+      }
+    );
+  };
+
+  return (
+    <Box sx={{ py: 1.35 }}>
+      <Typography variant="body2" sx={{ fontWeight: 600 }}>
+        {t("Extra entry sections")}
+      </Typography>
+      <Typography variant="caption" color="text.secondary" sx={{ display: "block", mt: 0.35, mb: 1, lineHeight: 1.45 }}>
+        {t("Shown below Yesterday and Today in every journal entry, e.g. Blockers or Wins.")}
+      </Typography>
+      <Box sx={{ display: "grid", gap: 0.8 }}>
+        {draft.map((template, index) => (
+          <Box key={index} sx={{ display: "flex", gap: 0.8, alignItems: "center" }}>
+            <TextField
+              size="small"
+              fullWidth
+              value={template.label}
+              onChange={(event) => {
+                const label = event.target.value;
+                setDraft((prev) => prev.map((item, itemIndex) => (itemIndex === index ? { ...item, label } : item)));
+              }}
+            />
+            <IconButton
+              size="small"
+              aria-label={t("Delete")}
+              onClick={() => setDraft((prev) => prev.filter((_, itemIndex) => itemIndex !== index))}
+            >
+              <DeleteOutlineIcon fontSize="small" />
+            </IconButton>
+          </Box>
+        ))}
+      </Box>
+      <Box sx={{ display: "flex", flexWrap: "wrap", gap: 1, mt: 1.2 }}>
+        <Button size="small" onClick={() => setDraft((prev) => [...prev, { section_key: null, label: "" }])}>
+          {t("Add section")}
+        </Button>
+        <Button size="small" variant="contained" onClick={handleSave} disabled={saveTemplates.isPending}>
+          {t("Save")}
+        </Button>
+      </Box>
+      {status ? (
+        <Typography variant="caption" color="text.secondary" sx={{ display: "block", mt: 1 }}>
+          {status}
+        </Typography>
+      ) : null}
+    </Box>
+  );
+};
+
+const MarkdownExportPanel = () => {
+  const { t } = useI18n();
+  const exportMarkdown = useExportEntriesMarkdown();
+  const importMarkdown = useImportMarkdownFolder();
+  const [dir, setDir] = useState("");
+  const [start, setStart] = useState("");
+  const [end, setEnd] = useState("");
+  const [status, setStatus] = useState("");
+
+  const handleExport = () => {
+    const range = start && end ? { start, end } : null;
+    exportMarkdown.mutate(
+      { dir: dir.trim(), range },
+      {
+        onSuccess: (summary) =>
+          setStatus(
+            t("Exported {count} entries to {dir}", { count: summary.files_written, dir: summary.directory })
+          ),
+        onError: (error) => setStatus(String(error)),
+      }
+    );
+  };
+
+  const handleImport = () => {
+    importMarkdown.mutate(dir.trim(), {
+      onSuccess: (summary) =>
+        setStatus(
+          t("Imported {count} daily notes, skipped {skipped} days that already had an entry.", {
+            count: summary.entries_imported,
+            skipped: summary.skipped_existing,
+          })
+        ),
+      onError: (error) => setStatus(String(error)),
+    });
+  };
+
+  return (
+    <Box sx={{ mt: 1.5 }}>
+      <Typography variant="body2" sx={{ fontWeight: 600 }}>
+        {t("Markdown archive")}
+      </Typography>
+      <Typography variant="caption" color="text.secondary" sx={{ display: "block", mt: 0.35, mb: 1, lineHeight: 1.45 }}>
+        {t("Writes one YYYY-MM-DD.md file per entry with a frontmatter block. Leave dates empty to export everything.")}{" "}
+        {t("Importing reads YYYY-MM-DD.md files from the folder, such as Obsidian daily notes.")}
+      </Typography>
+      <Box sx={{ display: "grid", gridTemplateColumns: { xs: "1fr", sm: "2fr 1fr 1fr" }, gap: 1 }}>
+        <TextField
+          size="small"
+          label={t("Folder")}
+          placeholder="/Users/me/Documents/journal"
+          value={dir}
+          onChange={(event) => setDir(event.target.value)}
+        />
+        <TextField
+          size="small"
+          type="date"
+          label={t("From")}
+          value={start}
+          onChange={(event) => setStart(event.target.value)}
+          InputLabelProps={{ shrink: true }}
+        />
+        <TextField
+          size="small"
+          type="date"
+          label={t("To")}
+          value={end}
+          onChange={(event) => setEnd(event.target.value)}
+          InputLabelProps={{ shrink: true }}
+        />
+      </Box>
+      <Box sx={{ display: "flex", alignItems: "center", gap: 1, mt: 1 }}>
+        <Button
+          size="small"
+          variant="outlined"
+          startIcon={<DownloadIcon />}
+          onClick={handleExport}
+          disabled={exportMarkdown.isPending || dir.trim().length === 0}
+        >
+          {exportMarkdown.isPending ? t("Exporting...") : t("Export Markdown")}
+        </Button>
+        <Button
+          size="small"
+          startIcon={<UploadFileIcon />}
+          onClick={handleImport}
+          disabled={importMarkdown.isPending || dir.trim().length === 0}
+        >
+          {importMarkdown.isPending ? t("Importing...") : t("Import daily notes")}
+        </Button>
+        {status ? (
+          <Typography variant="caption" color="text.secondary">
+            {status}
+          </Typography>
+        ) : null}
+      </Box>
+    </Box>
+  );
+};
+
+// One-way copy of entries and pages as Markdown, kept current after every save.
+const VaultMirrorPanel = () => {
+  const { t } = useI18n();
+  const savedDir = useAppSetting("vault_mirror_dir") ?? "";
+  const gitEnabled = useAppSetting("vault_mirror_git") === "true";
+  const updateSetting = useUpdateAppSetting();
+  const syncMirror = useSyncVaultMirror();
+  const { data: history = [] } = useMirrorHistory("", gitEnabled && savedDir.trim().length > 0);
+  const { data: encryption } = useJournalEncryptionStatus();
+  const [dir, setDir] = useState<string | null>(null);
+  const [status, setStatus] = useState("");
+  const value = dir ?? savedDir;
+
+  const runSync = () => {
+    syncMirror.mutate(undefined, {
+      onSuccess: (summary) =>
+        setStatus(
+          t("Mirrored {entries} entries and {pages} pages to {dir}, removed {removed} stale files.", {
+            entries: summary.entries_written,
+            pages: summary.pages_written,
+            removed: summary.files_removed,
+            dir: summary.directory,
+          })
+        ),
+      onError: (error) => setStatus(String(error)),
+    });
+  };
+
+  const handleSave = () => {
+    const next = value.trim();
+    updateSetting.mutate(
+      { key: "vault_mirror_dir", value: next },
+      {
+        onSuccess: () => {
+          setDir(null);
+          if (next) {
+            runSync();
+          } else {
+            setStatus(t("Vault mirror turned off. Existing files were left in place."));
+          }
+        },
+        onError: (error) => setStatus(String(error)),
+      }
+    );
+  };
+
+  return (
+    <Box sx={{ mt: 1.5 }}>
+      <Typography variant="body2" sx={{ fontWeight: 600 }}>
+        {t("Vault mirror")}
+      </Typography>
+      <Typography variant="caption" color="text.secondary" sx={{ display: "block", mt: 0.35, mb: 1, lineHeight: 1.45 }}>
+        {t("Writes every entry and page as a Markdown file after each save, so the folder can live in a git repo. Changes in the folder are never read back. Leave empty to turn it off.")}
+      </Typography>
+      {encryption?.enabled ? (
+        <Typography variant="caption" color="warning.main" sx={{ display: "block", mb: 1 }}>
+          {t("Paused while journal encryption is on.")}
+        </Typography>
+      ) : null}
+      <TextField
+        size="small"
+        fullWidth
+        label={t("Folder")}
+        placeholder="/Users/me/notes/journal-vault"
+        value={value}
+        onChange={(event) => setDir(event.target.value)}
+      />
+      <Box sx={{ display: "flex", alignItems: "center", gap: 1, mt: 1 }}>
+        <Button
+          size="small"
+          variant="outlined"
+          onClick={handleSave}
+          disabled={updateSetting.isPending || value.trim() === savedDir.trim()}
+        >
+          {t("Save")}
+        </Button>
+        <Button
+          size="small"
+          onClick={runSync}
+          disabled={syncMirror.isPending || !savedDir.trim() || encryption?.enabled}
+        >
+          {syncMirror.isPending ? t("Mirroring...") : t("Mirror now")}
+        </Button>
+        {status ? (
+          <Typography variant="caption" color="text.secondary">
+            {status}
+          </Typography>
+        ) : null}
+      </Box>
+      <Box sx={{ display: "flex", alignItems: "center", justifyContent: "space-between", gap: 2, mt: 1 }}>
+        <Box sx={{ minWidth: 0 }}>
+          <Typography variant="body2">{t("Commit changes to git")}</Typography>
+          <Typography variant="caption" color="text.secondary" sx={{ display: "block", lineHeight: 1.45 }}>
+            {t("Creates a git repository in the folder if needed and keeps one commit per day. Commits that were already pushed are never rewritten.")}
+          </Typography>
+        </Box>
+        <Switch
+          checked={gitEnabled}
+          onChange={(event) =>
+            updateSetting.mutate({ key: "vault_mirror_git", value: event.target.checked ? "true" : "false" })
+          }
+        />
+      </Box>
+      {gitEnabled && history.length > 0 ? (
+        <Box sx={{ mt: 0.5 }}>
+          {history.slice(0, 7).map((commit) => (
+            <Typography key={commit.id} variant="caption" color="text.secondary" sx={{ display: "block" }} noWrap>
+              <Box component="span" sx={{ fontFamily: "monospace", mr: 1 }}>
+                {commit.id.slice(0, 7)}
+              </Box>
+              {commit.summary} · {format(new Date(commit.committed_at), "MMM d, HH:mm")}
+            </Typography>
+          ))}
+        </Box>
+      ) : null}
+    </Box>
+  );
+};
+
+const DailySheetPanel = () => {
+  const { t } = useI18n();
+  const generateSheet = useGenerateDailySheet();
+  const [date, setDate] = useState(() => format(new Date(), "yyyy-MM-dd"));
+  const [path, setPath] = useState("");
+  const [status, setStatus] = useState("");
+
+  const handleGenerate = () => {
+    generateSheet.mutate(
+      { date, path: path.trim() },
+      {
+        onSuccess: (file) => setStatus(t("Saved daily sheet to {path}", { path: file })),
+        onError: (error) => setStatus(String(error)),
+      }
+    );
+  };
+
+  return (
+    <Box sx={{ mt: 1.5 }}>
+      <Typography variant="body2" sx={{ fontWeight: 600 }}>
+        {t("Printable daily sheet")}
+      </Typography>
+      <Typography variant="caption" color="text.secondary" sx={{ display: "block", mt: 0.35, mb: 1, lineHeight: 1.45 }}>
+        {t("A one-page HTML sheet with the day's plan, top tasks, meetings and habit grid. Open it in a browser to print or save as PDF.")}
+      </Typography>
+      <Box sx={{ display: "grid", gridTemplateColumns: { xs: "1fr", sm: "2fr 1fr" }, gap: 1 }}>
+        <TextField
+          size="small"
+          label={t("File or folder")}
+          placeholder="/Users/me/Documents/daily-sheet.html"
+          value={path}
+          onChange={(event) => setPath(event.target.value)}
+        />
+        <TextField
+          size="small"
+          type="date"
+          label={t("Date")}
+          value={date}
+          onChange={(event) => setDate(event.target.value)}
+          InputLabelProps={{ shrink: true }}
+        />
+      </Box>
+      <Box sx={{ display: "flex", alignItems: "center", gap: 1, mt: 1 }}>
+        <Button
+          size="small"
+          variant="outlined"
+          startIcon={<DownloadIcon />}
+          onClick={handleGenerate}
+          disabled={generateSheet.isPending || !date || path.trim().length === 0}
+        >
+          {t("Generate sheet")}
+        </Button>
+        {status ? (
+          <Typography variant="caption" color="text.secondary">
+            {status}
+          </Typography>
+        ) : null}
+      </Box>
+    </Box>
+  );
+};
+
+const DayOneImportPanel = () => {
+  const { t } = useI18n();
+  const importDayOne = useImportDayOne();
+  const [path, setPath] = useState("");
+  const [status, setStatus] = useState("");
+
+  const handleImport = () => {
+    importDayOne.mutate(path.trim(), {
+      onSuccess: (summary) =>
+        setStatus(
+          t("Imported {entries} Day One entries into {days} days.", {
+            entries: summary.entries_read,
+            days: summary.days_written,
+          })
+        ),
+      onError: (error) => setStatus(String(error)),
+    });
+  };
+
+  return (
+    <Box sx={{ mt: 1.5 }}>
+      <Typography variant="body2" sx={{ fontWeight: 600 }}>
+        {t("Import from Day One")}
+      </Typography>
+      <Typography variant="caption" color="text.secondary" sx={{ display: "block", mt: 0.35, mb: 1, lineHeight: 1.45 }}>
+        {t("Point to the JSON file or unzipped folder of a Day One export. Text is added to each day's entry; tags are kept.")}
+      </Typography>
+      <Box sx={{ display: "flex", flexWrap: "wrap", alignItems: "center", gap: 1 }}>
+        <TextField
+          size="small"
+          label={t("Export path")}
+          placeholder="/Users/me/Downloads/Journal.json"
+          value={path}
+          onChange={(event) => setPath(event.target.value)}
+          sx={{ flex: 1, minWidth: 220 }}
+        />
+        <Button
+          size="small"
+          variant="outlined"
+          startIcon={<UploadFileIcon />}
+          onClick={handleImport}
+          disabled={importDayOne.isPending || path.trim().length === 0}
+        >
+          {importDayOne.isPending ? t("Importing...") : t("Import")}
+        </Button>
+      </Box>
+      {status ? (
+        <Typography variant="caption" color="text.secondary" sx={{ display: "block", mt: 0.75 }}>
+          {status}
+        </Typography>
+      ) : null}
+    </Box>
+  );
+};
+
+const ObsidianImportPanel = () => {
+  const { t } = useI18n();
+  const importVault = useImportObsidianVault();
+  const [dir, setDir] = useState("");
+  const [status, setStatus] = useState("");
+
+  const handleImport = () => {
+    importVault.mutate(dir.trim(), {
+      onSuccess: (summary) =>
+        setStatus(
+          t("Read {notes} notes: {created} pages created, {updated} updated, {entries} daily notes imported.", {
+            notes: summary.notes_read,
+            created: summary.pages_created,
+            updated: summary.pages_updated,
+            entries: summary.entries_imported,
+          })
+        ),
+      onError: (error) => setStatus(String(error)),
+    });
+  };
+
+  return (
+    <Box sx={{ mt: 1.5 }}>
+      <Typography variant="body2" sx={{ fontWeight: 600 }}>
+        {t("Import an Obsidian vault")}
+      </Typography>
+      <Typography variant="caption" color="text.secondary" sx={{ display: "block", mt: 0.35, mb: 1, lineHeight: 1.45 }}>
+        {t("Notes become pages nested like the vault's folders, with wiki links and images kept. Daily notes named YYYY-MM-DD become entries. Importing again updates the same pages.")}
+      </Typography>
+      <Box sx={{ display: "flex", flexWrap: "wrap", alignItems: "center", gap: 1 }}>
+        <TextField
+          size="small"
+          label={t("Vault folder")}
+          placeholder="/Users/me/Documents/Obsidian Vault"
+          value={dir}
+          onChange={(event) => setDir(event.target.value)}
+          sx={{ flex: 1, minWidth: 220 }}
+        />
+        <Button
+          size="small"
+          variant="outlined"
+          startIcon={<UploadFileIcon />}
+          onClick={handleImport}
+          disabled={importVault.isPending || dir.trim().length === 0}
+        >
+          {importVault.isPending ? t("Importing...") : t("Import")}
+        </Button>
+      </Box>
+      {status ? (
+        <Typography variant="caption" color="text.secondary" sx={{ display: "block", mt: 0.75 }}>
+          {status}
+        </Typography>
+      ) : null}
+    </Box>
+  );
+};
+
+const OrgModePanel = () => {
+  const { t } = useI18n();
+  const importOrg = useImportOrg();
+  const exportOrg = useExportOrg();
+  const [path, setPath] = useState("");
+  const [status, setStatus] = useState("");
+
+  const handleImport = () => {
+    importOrg.mutate(
+      { path: path.trim(), parentId: null },
+      {
+        onSuccess: (summary) =>
+          setStatus(
+            t("Read {headings} headings: {tasksCreated} new and {tasksUpdated} updated tasks, {pagesCreated} new and {pagesUpdated} updated pages.", {
+              headings: summary.headings_read,
+              tasksCreated: summary.tasks_created,
+              tasksUpdated: summary.tasks_updated,
+              pagesCreated: summary.pages_created,
+              pagesUpdated: summary.pages_updated,
+            })
+          ),
+        onError: (error) => setStatus(String(error)),
+      }
+    );
+  };
+
+  const handleExport = () => {
+    exportOrg.mutate(path.trim(), {
+      onSuccess: (file) => setStatus(t("Saved Org file to {path}", { path: file })),
+      onError: (error) => setStatus(String(error)),
+    });
+  };
+
+  return (
+    <Box sx={{ mt: 1.5 }}>
+      <Typography variant="body2" sx={{ fontWeight: 600 }}>
+        {t("Org-mode")}
+      </Typography>
+      <Typography variant="caption" color="text.secondary" sx={{ display: "block", mt: 0.35, mb: 1, lineHeight: 1.45 }}>
+        {t("TODO headings become tasks with their priority and DEADLINE or SCHEDULED date; other headings become nested pages. Importing a file again updates the same tasks and pages.")}
+      </Typography>
+      <Box sx={{ display: "flex", flexWrap: "wrap", alignItems: "center", gap: 1 }}>
+        <TextField
+          size="small"
+          label={t("File or folder")}
+          placeholder="/Users/me/org/notes.org"
+          value={path}
+          onChange={(event) => setPath(event.target.value)}
+          sx={{ flex: 1, minWidth: 220 }}
+        />
+        <Button
+          size="small"
+          variant="outlined"
+          startIcon={<UploadFileIcon />}
+          onClick={handleImport}
+          disabled={importOrg.isPending || path.trim().length === 0}
+        >
+          {importOrg.isPending ? t("Importing...") : t("Import")}
+        </Button>
+        <Button
+          size="small"
+          variant="outlined"
+          startIcon={<DownloadIcon />}
+          onClick={handleExport}
+          disabled={exportOrg.isPending || path.trim().length === 0}
+        >
+          {t("Export")}
+        </Button>
+      </Box>
+      {status ? (
+        <Typography variant="caption" color="text.secondary" sx={{ display: "block", mt: 0.75 }}>
+          {status}
+        </Typography>
+      ) : null}
+    </Box>
+  );
+};
+
+// Every page as Markdown or standalone HTML, so notes can leave the app.
+const PageExportPanel = () => {
+  const { t } = useI18n();
+  const exportAllPages = useExportAllPages();
+  const [dir, setDir] = useState("");
+  const [format, setFormat] = useState<PageExportFormat>("markdown");
+  const [status, setStatus] = useState("");
+
+  const handleExport = () => {
+    exportAllPages.mutate(
+      { dir: dir.trim(), format },
+      {
+        onSuccess: (summary) =>
+          setStatus(
+            t("Exported {count} pages to {dir}", { count: summary.pages_written, dir: summary.directory })
+          ),
+        onError: (error) => setStatus(String(error)),
+      }
+    );
+  };
+
+  return (
+    <Box sx={{ mt: 1.5 }}>
+      <Typography variant="body2" sx={{ fontWeight: 600 }}>
+        {t("Export pages")}
+      </Typography>
+      <Typography variant="caption" color="text.secondary" sx={{ display: "block", mt: 0.35, mb: 1, lineHeight: 1.45 }}>
+        {t("Writes one file per page. Markdown copies images into an assets folder; HTML files embed them.")}
+      </Typography>
+      <Box sx={{ display: "flex", flexWrap: "wrap", alignItems: "center", gap: 1 }}>
+        <TextField
+          size="small"
+          label={t("Folder")}
+          placeholder="/Users/me/Documents/pages"
+          value={dir}
+          onChange={(event) => setDir(event.target.value)}
+          sx={{ flex: 1, minWidth: 220 }}
+        />
+        <TextField
+          select
+          size="small"
+          label={t("Format")}
+          value={format}
+          onChange={(event) => setFormat(event.target.value as PageExportFormat)}
+          SelectProps={{ native: true }}
+          InputLabelProps={{ shrink: true }}
+          sx={{ width: 130 }}
+        >
+          <option value="markdown">Markdown</option>
+          <option value="html">HTML</option>
+        </TextField>
+        <Button
+          size="small"
+          variant="outlined"
+          startIcon={<DownloadIcon />}
+          onClick={handleExport}
+          disabled={exportAllPages.isPending || dir.trim().length === 0}
+        >
+          {t("Export")}
+        </Button>
+      </Box>
+      {status ? (
+        <Typography variant="caption" color="text.secondary" sx={{ display: "block", mt: 0.75 }}>
+          {status}
+        </Typography>
+      ) : null}
+    </Box>
+  );
+};
+
+const EXPORT_FORMATS: { value: ExportFormat; label: string }[] = [
+  { value: "markdown", label: "Markdown" },
+  { value: "json", label: "JSON" },
+  { value: "csv", label: "CSV" },
+];
+
+// Hands over one project's or tag's content without the rest of the journal.
+const FilteredExportPanel = () => {
+  const { t } = useI18n();
+  const { data: projects } = useProjects();
+  const exportFiltered = useExportFiltered();
+  const [dir, setDir] = useState("");
+  const [format, setFormat] = useState<ExportFormat>("markdown");
+  const [tag, setTag] = useState("");
+  const [projectId, setProjectId] = useState("");
+  const [status, setStatus] = useState("");
+  const hasFilter = tag.trim().length > 0 || projectId !== "";
+
+  const handleExport = () => {
+    exportFiltered.mutate(
+      {
+        dir: dir.trim(),
+        format,
+        filter: { tag: tag.trim() || null, project_id: projectId ? Number(projectId) : null },
+      },
+      {
+        onSuccess: (summary) =>
+          setStatus(
+            t("Exported {entries} entries, {pages} pages and {tasks} tasks to {dir}", {
+              entries: summary.entries_exported,
+              pages: summary.pages_exported,
+              tasks: summary.tasks_exported,
+              dir: summary.directory,
+            })
+          ),
+        onError: (error) => setStatus(String(error)),
+      }
+    );
+  };
+
+  return (
+    <Box sx={{ mt: 1.5 }}>
+      <Typography variant="body2" sx={{ fontWeight: 600 }}>
+        {t("Export by tag or project")}
+      </Typography>
+      <Typography variant="caption" color="text.secondary" sx={{ display: "block", mt: 0.35, mb: 1, lineHeight: 1.45 }}>
+        {t("Only entries, pages and tasks matching the filter are written. Pages belong to a project when they carry its name as a tag.")}
+      </Typography>
+      <Box sx={{ display: "flex", flexWrap: "wrap", alignItems: "center", gap: 1 }}>
+        <TextField
+          size="small"
+          label={t("Tag")}
+          placeholder="project-x"
+          value={tag}
+          onChange={(event) => setTag(event.target.value)}
+          sx={{ width: 160 }}
+        />
+        <TextField
+          select
+          size="small"
+          label={t("Project")}
+          value={projectId}
+          onChange={(event) => setProjectId(event.target.value)}
+          SelectProps={{ native: true }}
+          InputLabelProps={{ shrink: true }}
+          sx={{ width: 180 }}
+        >
+          <option value="">{t("Any project")}</option>
+          {(projects ?? []).map((project) => (
+            <option key={project.id} value={project.id}>
+              {project.name}
+            </option>
+          ))}
+        </TextField>
+        <TextField
+          select
+          size="small"
+          label={t("Format")}
+          value={format}
+          onChange={(event) => setFormat(event.target.value as ExportFormat)}
+          SelectProps={{ native: true }}
+          InputLabelProps={{ shrink: true }}
+          sx={{ width: 130 }}
+        >
+          {EXPORT_FORMATS.map((option) => (
+            <option key={option.value} value={option.value}>
+              {option.label}
+            </option>
+          ))}
+        </TextField>
+      </Box>
+      <Box sx={{ display: "flex", flexWrap: "wrap", alignItems: "center", gap: 1, mt: 1 }}>
+        <TextField
+          size="small"
+          label={t("Folder")}
+          placeholder="/Users/me/handover"
+          value={dir}
+          onChange={(event) => setDir(event.target.value)}
+          sx={{ flex: 1, minWidth: 220 }}
+        />
+        <Button
+          size="small"
+          variant="outlined"
+          startIcon={<DownloadIcon />}
+          onClick={handleExport}
+          disabled={exportFiltered.isPending || dir.trim().length === 0 || !hasFilter}
+        >
+          {t("Export")}
+        </Button>
+      </Box>
+      {status ? (
+        <Typography variant="caption" color="text.secondary" sx={{ display: "block", mt: 0.75 }}>
+          {status}
+        </Typography>
+      ) : null}
+    </Box>
+  );
+};
+
+const TodoTxtPanel = () => {
+  const { t } = useI18n();
+  const importTodoTxt = useImportTodoTxt();
+  const exportTodoTxt = useExportTodoTxt();
+  const [path, setPath] = useState("");
+  const [status, setStatus] = useState("");
+
+  const handleImport = () => {
+    importTodoTxt.mutate(path.trim(), {
+      onSuccess: (summary) =>
+        setStatus(
+          t("Read {tasks} tasks: {created} new, {updated} updated.", {
+            tasks: summary.tasks_read,
+            created: summary.tasks_created,
+            updated: summary.tasks_updated,
+          })
+        ),
+      onError: (error) => setStatus(String(error)),
+    });
+  };
+
+  const handleExport = () => {
+    exportTodoTxt.mutate(path.trim(), {
+      onSuccess: (file) => setStatus(t("Saved todo.txt to {path}", { path: file })),
+      onError: (error) => setStatus(String(error)),
+    });
+  };
+
+  return (
+    <Box sx={{ mt: 1.5 }}>
+      <Typography variant="body2" sx={{ fontWeight: 600 }}>
+        todo.txt
+      </Typography>
+      <Typography variant="caption" color="text.secondary" sx={{ display: "block", mt: 0.35, mb: 1, lineHeight: 1.45 }}>
+        {t("Priorities (A) to (D) map to urgent through low, @contexts become task tags and a +project that matches a project name sets the task's project. Importing a file again updates the same tasks.")}
+      </Typography>
+      <Box sx={{ display: "flex", flexWrap: "wrap", alignItems: "center", gap: 1 }}>
+        <TextField
+          size="small"
+          label={t("File or folder")}
+          placeholder="/Users/me/todo/todo.txt"
+          value={path}
+          onChange={(event) => setPath(event.target.value)}
+          sx={{ flex: 1, minWidth: 220 }}
+        />
+        <Button
+          size="small"
+          variant="outlined"
+          startIcon={<UploadFileIcon />}
+          onClick={handleImport}
+          disabled={importTodoTxt.isPending || path.trim().length === 0}
+        >
+          {importTodoTxt.isPending ? t("Importing...") : t("Import")}
+        </Button>
+        <Button
+          size="small"
+          variant="outlined"
+          startIcon={<DownloadIcon />}
+          onClick={handleExport}
+          disabled={exportTodoTxt.isPending || path.trim().length === 0}
+        >
+          {t("Export")}
+        </Button>
+      </Box>
+      {status ? (
+        <Typography variant="caption" color="text.secondary" sx={{ display: "block", mt: 0.75 }}>
+          {status}
+        </Typography>
+      ) : null}
+    </Box>
+  );
+};
+
+const DATE_SHIFT_ENTITIES: { value: DateShiftEntity; label: string }[] = [
+  { value: "habit_logs", label: "Habit check-ins" },
+  { value: "streak_freezes", label: "Streak freezes" },
+  { value: "entries", label: "Journal entries" },
+  { value: "moods", label: "Moods" },
+  { value: "task_due_dates", label: "Task due dates" },
+  { value: "goal_target_dates", label: "Goal target dates" },
+];
+const DATE_SHIFT_PREVIEW_ROWS = 8;
+
+// Repairs imports that landed a day off: preview first, then move everything in one go.
+const DateShiftPanel = () => {
+  const { t } = useI18n();
+  const { data: habits } = useHabits();
+  const previewShift = usePreviewDateShift();
+  const shiftDates = useShiftDates();
+  const [entity, setEntity] = useState<DateShiftEntity>("habit_logs");
+  const [habitId, setHabitId] = useState("");
+  const [fromDate, setFromDate] = useState("");
+  const [toDate, setToDate] = useState("");
+  const [deltaDays, setDeltaDays] = useState("1");
+  const [preview, setPreview] = useState<DateShift | null>(null);
+  const [status, setStatus] = useState("");
+
+  const perHabit = entity === "habit_logs" || entity === "streak_freezes";
+  const delta = Number.parseInt(deltaDays, 10);
+  const filter: DateShiftFilter = {
+    entity,
+    from_date: fromDate || null,
+    to_date: toDate || null,
+    habit_id: perHabit && habitId ? Number(habitId) : null,
+  };
+  const resetPreview = () => {
+    setPreview(null);
+    setStatus("");
+  };
+
+  const handlePreview = () => {
+    previewShift.mutate(
+      { filter, deltaDays: delta },
+      {
+        onSuccess: (result) => {
+          setPreview(result);
+          setStatus(
+            result.conflicts.length > 0
+              ? t("{count} rows would land on a date that is already taken.", { count: result.conflicts.length })
+              : t("{count} rows will move.", { count: result.rows.length })
+          );
+        },
+        onError: (error) => setStatus(String(error)),
+      }
+    );
+  };
+
+  const handleApply = () => {
+    shiftDates.mutate(
+      { filter, deltaDays: delta },
+      {
+        onSuccess: (result) => {
+          setPreview(null);
+          setStatus(t("Moved {count} rows.", { count: result.rows.length }));
+        },
+        onError: (error) => setStatus(String(error)),
+      }
+    );
+  };
+
+  return (
+    <Box sx={{ mt: 1.5 }}>
+      <Typography variant="body2" sx={{ fontWeight: 600 }}>
+        {t("Shift dates")}
+      </Typography>
+      <Typography variant="caption" color="text.secondary" sx={{ display: "block", mt: 0.35, mb: 1, lineHeight: 1.45 }}>
+        {t("Fixes imports that landed a day early or late. Preview the rows first; nothing moves while any of them would land on a date that is already taken.")}
+      </Typography>
+      <Box sx={{ display: "flex", flexWrap: "wrap", alignItems: "center", gap: 1 }}>
+        <TextField
+          select
+          size="small"
+          label={t("Data")}
+          value={entity}
+          onChange={(event) => {
+            setEntity(event.target.value as DateShiftEntity);
+            resetPreview();
+          }}
+          SelectProps={{ native: true }}
+          InputLabelProps={{ shrink: true }}
+          sx={{ width: 180 }}
+        >
+          {DATE_SHIFT_ENTITIES.map((option) => (
+            <option key={option.value} value={option.value}>
+              {t(option.label)}
+            </option>
+          ))}
+        </TextField>
+        {perHabit ? (
+          <TextField
+            select
+            size="small"
+            label={t("Habit")}
+            value={habitId}
+            onChange={(event) => {
+              setHabitId(event.target.value);
+              resetPreview();
+            }}
+            SelectProps={{ native: true }}
+            InputLabelProps={{ shrink: true }}
+            sx={{ width: 160 }}
+          >
+            <option value="">{t("All habits")}</option>
+            {(habits ?? []).map((habit) => (
+              <option key={habit.id} value={habit.id}>
+                {habit.title}
+              </option>
+            ))}
+          </TextField>
+        ) : null}
+        <TextField
+          size="small"
+          type="date"
+          label={t("From")}
+          value={fromDate}
+          onChange={(event) => {
+            setFromDate(event.target.value);
+            resetPreview();
+          }}
+          InputLabelProps={{ shrink: true }}
+          sx={{ width: 160 }}
+        />
+        <TextField
+          size="small"
+          type="date"
+          label={t("To")}
+          value={toDate}
+          onChange={(event) => {
+            setToDate(event.target.value);
+            resetPreview();
+          }}
+          InputLabelProps={{ shrink: true }}
+          sx={{ width: 160 }}
+        />
+        <TextField
+          size="small"
+          type="number"
+          label={t("Days (+/-)")}
+          value={deltaDays}
+          onChange={(event) => {
+            setDeltaDays(event.target.value);
+            resetPreview();
+          }}
+          sx={{ width: 110 }}
+        />
+        <Button
+          size="small"
+          variant="outlined"
+          onClick={handlePreview}
+          disabled={previewShift.isPending || !Number.isFinite(delta) || delta === 0}
+        >
+          {t("Preview")}
+        </Button>
+        <Button
+          size="small"
+          variant="contained"
+          onClick={handleApply}
+          disabled={
+            shiftDates.isPending || !preview || preview.rows.length === 0 || preview.conflicts.length > 0
+          }
+        >
+          {t("Shift")}
+        </Button>
+      </Box>
+      {status ? (
+        <Typography variant="caption" color="text.secondary" sx={{ display: "block", mt: 0.75 }}>
+          {status}
+        </Typography>
+      ) : null}
+      {preview
+        ? [...preview.conflicts, ...preview.rows].slice(0, DATE_SHIFT_PREVIEW_ROWS).map((row, index) => (
+            <Typography
+              key={`${row.id}-${row.from_date}`}
+              variant="caption"
+              color={index < preview.conflicts.length ? "error" : "text.secondary"}
+              sx={{ display: "block" }}
+              noWrap
+            >
+              {row.label}: {row.from_date} → {row.to_date}
+            </Typography>
+          ))
+        : null}
+    </Box>
+  );
+};
+
+const SLACK_HEADER_SETTINGS = [
+  { key: "slack_standup_yesterday_headers", label: "Yesterday headers" },
+  { key: "slack_standup_today_headers", label: "Today headers" },
+  { key: "slack_standup_blockers_headers", label: "Blockers headers" },
+] as const;
+
+const SlackImportPanel = () => {
+  const { t } = useI18n();
+  const importSlack = useImportSlackStandups();
+  const updateSetting = useUpdateAppSetting();
+  const savedHeaders = {
+    slack_standup_yesterday_headers: useAppSetting("slack_standup_yesterday_headers") ?? "",
+    slack_standup_today_headers: useAppSetting("slack_standup_today_headers") ?? "",
+    slack_standup_blockers_headers: useAppSetting("slack_standup_blockers_headers") ?? "",
+  };
+  const [headerDrafts, setHeaderDrafts] = useState<Partial<Record<keyof typeof savedHeaders, string>>>({});
+  const [path, setPath] = useState("");
+  const [author, setAuthor] = useState("");
+  const [status, setStatus] = useState("");
+
+  const handleImport = async () => {
+    try {
+      // Edited headers are saved first; the importer reads them from the settings.
+      for (const [key, value] of Object.entries(headerDrafts)) {
+        await updateSetting.mutateAsync({ key, value: value.trim() });
+      }
+      setHeaderDrafts({});
+      const summary = await importSlack.mutateAsync({ path: path.trim(), author });
+      setStatus(
+        t("Found {standups} standups in {messages} messages and wrote {days} days.", {
+          standups: summary.standups_found,
+          messages: summary.messages_read,
+          days: summary.days_written,
+        })
+      );
+    } catch (error) {
+      setStatus(String(error));
+    }
+  };
+
+  return (
+    <Box sx={{ mt: 1.5 }}>
+      <Typography variant="body2" sx={{ fontWeight: 600 }}>
+        {t("Import Slack standups")}
+      </Typography>
+      <Typography variant="caption" color="text.secondary" sx={{ display: "block", mt: 0.35, mb: 1, lineHeight: 1.45 }}>
+        {t("Point to a channel folder of an unzipped Slack export. Posts with a section header become Yesterday, Today and Blockers; other lines go to notes.")}
+      </Typography>
+      <Box sx={{ display: "flex", flexWrap: "wrap", alignItems: "center", gap: 1 }}>
+        <TextField
+          size="small"
+          label={t("Channel folder")}
+          placeholder="/Users/me/Downloads/slack-export/standup"
+          value={path}
+          onChange={(event) => setPath(event.target.value)}
+          sx={{ flex: 2, minWidth: 220 }}
+        />
+        <TextField
+          size="small"
+          label={t("Your Slack name or ID")}
+          value={author}
+          onChange={(event) => setAuthor(event.target.value)}
+          sx={{ flex: 1, minWidth: 160 }}
+        />
+        <Button
+          size="small"
+          variant="outlined"
+          startIcon={<UploadFileIcon />}
+          onClick={() => void handleImport()}
+          disabled={importSlack.isPending || path.trim().length === 0}
+        >
+          {importSlack.isPending ? t("Importing...") : t("Import")}
+        </Button>
+      </Box>
+      <Box sx={{ display: "grid", gridTemplateColumns: { xs: "1fr", md: "repeat(3, 1fr)" }, gap: 1, mt: 1 }}>
+        {SLACK_HEADER_SETTINGS.map(({ key, label }) => (
+          <TextField
+            key={key}
+            size="small"
+            label={t(label)}
+            helperText={t("Comma-separated")}
+            value={headerDrafts[key] ?? savedHeaders[key]}
+            onChange={(event) => setHeaderDrafts((drafts) => ({ ...drafts, [key]: event.target.value }))}
+          />
+        ))}
+      </Box>
+      {status ? (
+        <Typography variant="caption" color="text.secondary" sx={{ display: "block", mt: 0.75 }}>
+          {status}
+        </Typography>
+      ) : null}
+    </Box>
+  );
+};
+
+const SlackStatusPanel = () => {
+  const { t } = useI18n();
+  const { data: settings } = useSlackStatusSettings();
+  const saveSettings = useSaveSlackStatusSettings();
+  const [token, setToken] = useState("");
+  const [message, setMessage] = useState("");
+
+  const enabled = settings?.enabled ?? false;
+  const save = (nextEnabled: boolean, nextToken: string | null) => {
+    saveSettings.mutate(
+      { enabled: nextEnabled, token: nextToken },
+      {
+        onSuccess: () => {
+          setToken("");
+          setMessage(nextToken === null ? "" : nextToken ? t("Token saved to the system keychain.") : t("Token removed."));
+        },
+        onError: (error) => setMessage(String(error)),
+      }
+    );
+  };
+
+  return (
+    <Box sx={{ mt: 1.5 }}>
+      <Box sx={{ display: "flex", alignItems: "center", justifyContent: "space-between", gap: 2 }}>
+        <Box sx={{ minWidth: 0 }}>
+          <Typography variant="body2" sx={{ fontWeight: 600 }}>
+            {t("Slack status while a timer runs")}
+          </Typography>
+          <Typography variant="caption" color="text.secondary" sx={{ display: "block", mt: 0.35, lineHeight: 1.45 }}>
+            {t("Sets your status to \"Working on <task>\" when a task timer starts and clears it when the timer stops. Needs a user token with the users.profile:write scope; tasks can opt out in their details.")}
+          </Typography>
+        </Box>
+        <Switch
+          checked={enabled}
+          disabled={saveSettings.isPending}
+          onChange={(event) => save(event.target.checked, null)}
+        />
+      </Box>
+      <Box sx={{ display: "flex", flexWrap: "wrap", alignItems: "center", gap: 1, mt: 1 }}>
+        <TextField
+          size="small"
+          type="password"
+          label={settings?.has_token ? t("Replace Slack token") : t("Slack user token")}
+          placeholder="xoxp-..."
+          value={token}
+          onChange={(event) => setToken(event.target.value)}
+          sx={{ flex: 1, minWidth: 220 }}
+        />
+        <Button
+          size="small"
+          variant="outlined"
+          onClick={() => save(enabled, token.trim())}
+          disabled={saveSettings.isPending || token.trim().length === 0}
+        >
+          {t("Save token")}
+        </Button>
+        {settings?.has_token ? (
+          <Button size="small" color="error" onClick={() => save(enabled, "")} disabled={saveSettings.isPending}>
+            {t("Remove token")}
+          </Button>
+        ) : null}
+      </Box>
+      {message ? (
+        <Typography variant="caption" color="text.secondary" sx={{ display: "block", mt: 0.75 }}>
+          {message}
+        </Typography>
+      ) : null}
+    </Box>
+  );
+};
+
+const EntryTrashPanel = () => {
+  const { t } = useI18n();
+  const { data: trashed = [] } = useTrashedEntries();
+  const restoreEntry = useRestoreEntry();
+  const purgeTrash = usePurgeTrash();
+
+  return (
+    <Box sx={{ mt: 1.5 }}>
+      <Typography variant="body2" sx={{ fontWeight: 600 }}>
+        {t("Trash")}
+      </Typography>
+      <Typography variant="caption" color="text.secondary" sx={{ display: "block", mt: 0.35, mb: 1, lineHeight: 1.45 }}>
+        {t("Deleted entries stay here for 30 days before they are removed for good.")}
+      </Typography>
+      {trashed.length === 0 ? (
+        <Typography variant="caption" color="text.secondary">
+          {t("Trash is empty.")}
+        </Typography>
+      ) : null}
+      {trashed.map(({ entry, deleted_at }) => (
+        <Box key={entry.id} sx={{ display: "flex", alignItems: "center", gap: 1, mt: 1 }}>
+          <Box sx={{ flex: 1, minWidth: 0 }}>
+            <Typography variant="body2" noWrap>
+              {format(new Date(`${entry.date}T00:00:00`), "MMM d, yyyy")}
+            </Typography>
+            <Typography variant="caption" color="text.secondary" sx={{ display: "block" }} noWrap>
+              {t("Deleted {date}", { date: format(new Date(deleted_at), "MMM d, HH:mm") })}
+            </Typography>
+          </Box>
+          <IconButton
+            size="small"
+            aria-label={t("Restore entry")}
+            onClick={() => restoreEntry.mutate(entry.date)}
+            disabled={restoreEntry.isPending}
+          >
+            <RestoreRoundedIcon fontSize="small" />
+          </IconButton>
+        </Box>
+      ))}
+      {trashed.length > 0 ? (
+        <Button
+          size="small"
+          color="error"
+          startIcon={<DeleteOutlineIcon />}
+          onClick={() => purgeTrash.mutate()}
+          disabled={purgeTrash.isPending}
+          sx={{ mt: 1 }}
+        >
+          {t("Empty trash")}
+        </Button>
+      ) : null}
+    </Box>
+  );
+};
+
+const PageTrashPanel = () => {
+  const { t } = useI18n();
+  const { data: trashed = [] } = useTrashedPages();
+  const restorePage = useRestorePage();
+  const emptyTrash = useEmptyPageTrash();
+
+  if (trashed.length === 0) return null;
+
+  return (
+    <Box sx={{ mt: 1.5 }}>
+      <Typography variant="body2" sx={{ fontWeight: 600 }}>
+        {t("Deleted pages")}
+      </Typography>
+      <Typography variant="caption" color="text.secondary" sx={{ display: "block", mt: 0.35, mb: 1, lineHeight: 1.45 }}>
+        {t("Restored pages go back under their parent page, or to the top level if it was deleted too.")}
+      </Typography>
+      {trashed.map(({ page, deleted_at }) => (
+        <Box key={page.id} sx={{ display: "flex", alignItems: "center", gap: 1, mt: 1 }}>
+          <Box sx={{ flex: 1, minWidth: 0 }}>
+            <Typography variant="body2" noWrap>
+              {page.title.trim() || t("Untitled")}
+            </Typography>
+            <Typography variant="caption" color="text.secondary" sx={{ display: "block" }} noWrap>
+              {t("Deleted {date}", { date: format(new Date(deleted_at), "MMM d, HH:mm") })}
+            </Typography>
+          </Box>
+          <IconButton
+            size="small"
+            aria-label={t("Restore page")}
+            onClick={() => restorePage.mutate(page.id)}
+            disabled={restorePage.isPending}
+          >
+            <RestoreRoundedIcon fontSize="small" />
+          </IconButton>
+        </Box>
+      ))}
+      <Button
+        size="small"
+        color="error"
+        startIcon={<DeleteOutlineIcon />}
+        onClick={() => {
+          if (window.confirm(t("Permanently delete all trashed pages? Their version history is removed too."))) {
+            emptyTrash.mutate();
+          }
+        }}
+        disabled={emptyTrash.isPending}
+        sx={{ mt: 1 }}
+      >
+        {t("Empty page trash")}
+      </Button>
+    </Box>
+  );
+};
+
+const MeetingLoadThresholdField = () => {
+  const { t } = useI18n();
+  const heavyHours = useAppSetting("meeting_heavy_hours") ?? "4";
+  const updateSetting = useUpdateAppSetting([queryKeys.meetingLoad()]);
+
+  return (
+    <TextField
+      type="number"
+      label={t("Meeting-heavy day at (hours, 0 = off)")}
+      value={heavyHours}
+      onChange={(event) =>
+        updateSetting.mutate({
+          key: "meeting_heavy_hours",
+          value: String(Math.min(24, Math.max(0, Math.round(Number(event.target.value) || 0)))),
+        })
+      }
+      sx={{ mt: 1.5, ml: { sm: 1.5 }, width: { xs: "100%", sm: 240 } }}
+      inputProps={{ min: 0, max: 24, step: 1 }}
+    />
+  );
+};
+
+// How many saved versions each page keeps in its history.
+const PageRevisionLimitField = () => {
+  const { t } = useI18n();
+  const limit = useAppSetting("page_revision_limit") ?? "50";
+  const updateSetting = useUpdateAppSetting();
+
+  return (
+    <TextField
+      type="number"
+      label={t("Page versions to keep")}
+      value={limit}
+      onChange={(event) =>
+        updateSetting.mutate({
+          key: "page_revision_limit",
+          value: String(Math.min(1000, Math.max(1, Math.round(Number(event.target.value) || 1)))),
+        })
+      }
+      sx={{ mt: 1.5, width: { xs: "100%", sm: 240 } }}
+      inputProps={{ min: 1, max: 1000, step: 1 }}
+    />
+  );
+};
+
+const CHECKIN_WEEKDAYS = ["monday", "tuesday", "wednesday", "thursday", "friday", "saturday", "sunday"];
+
+// When the scheduler prompts for progress on goals that went a week without an update.
+const GoalCheckinScheduleFields = () => {
+  const { t } = useI18n();
+  const checkinDay = useAppSetting("goal_checkin_day") ?? "friday";
+  const checkinTime = useAppSetting("goal_checkin_time") ?? "16:00";
+  const updateSetting = useUpdateAppSetting();
+
+  return (
+    <Box sx={{ display: "flex", flexWrap: "wrap", gap: 1.5, mt: 1.5 }}>
+      <TextField
+        select
+        label={t("Weekly goal check-in")}
+        value={checkinDay}
+        onChange={(event) => updateSetting.mutate({ key: "goal_checkin_day", value: event.target.value })}
+        SelectProps={{ native: true }}
+        InputLabelProps={{ shrink: true }}
+        sx={{ width: { xs: "100%", sm: 240 } }}
+      >
+        {CHECKIN_WEEKDAYS.map((day) => (
+          <option key={day} value={day}>
+            {t(day.charAt(0).toUpperCase() + day.slice(1))}
+          </option>
+        ))}
+      </TextField>
+      <TextField
+        type="time"
+        label={t("Check-in time (empty = off)")}
+        value={checkinTime}
+        onChange={(event) => updateSetting.mutate({ key: "goal_checkin_time", value: event.target.value })}
+        InputLabelProps={{ shrink: true }}
+        sx={{ width: { xs: "100%", sm: 240 } }}
+      />
+    </Box>
+  );
+};
+
+// Notifications that can wait, such as update notices, are held back between these times.
+const QuietHoursFields = () => {
+  const { t } = useI18n();
+  const start = useAppSetting("quiet_hours_start") ?? "";
+  const end = useAppSetting("quiet_hours_end") ?? "";
+  const updateSetting = useUpdateAppSetting();
+
+  return (
+    <Box sx={{ display: "flex", flexWrap: "wrap", gap: 1.5, mt: 1.5 }}>
+      <TextField
+        type="time"
+        label={t("Quiet hours from (empty = off)")}
+        value={start}
+        onChange={(event) => updateSetting.mutate({ key: "quiet_hours_start", value: event.target.value })}
+        InputLabelProps={{ shrink: true }}
+        sx={{ width: { xs: "100%", sm: 240 } }}
+      />
+      <TextField
+        type="time"
+        label={t("Quiet hours until")}
+        value={end}
+        onChange={(event) => updateSetting.mutate({ key: "quiet_hours_end", value: event.target.value })}
+        InputLabelProps={{ shrink: true }}
+        sx={{ width: { xs: "100%", sm: 240 } }}
+      />
+    </Box>
+  );
+};
+
+// The tray's Quit checks for today's entry and running timers after this hour and
+// asks before quitting.
+const ShutdownRitualFields = () => {
+  const { t } = useI18n();
+  const enabled = useAppSetting("shutdown_ritual_enabled") === "true";
+  const afterHour = useAppSetting("shutdown_ritual_after_hour") ?? "17";
+  const updateSetting = useUpdateAppSetting();
+
+  return (
+    <Box sx={{ mt: 1.5 }}>
+      <SettingsToggleRow
+        label={t("Shutdown ritual")}
+        description={t("When quitting late in the day, check for today's entry and running timers first.")}
+        checked={enabled}
+        onChange={(checked) => updateSetting.mutate({ key: "shutdown_ritual_enabled", value: String(checked) })}
+      />
+      <TextField
+        type="number"
+        label={t("Check from hour (0-23)")}
+        value={afterHour}
+        onChange={(event) => {
+          const value = Number(event.target.value);
+          if (Number.isInteger(value)) {
+            updateSetting.mutate({ key: "shutdown_ritual_after_hour", value: String(Math.min(23, Math.max(0, value))) });
+          }
+        }}
+        disabled={!enabled}
+        sx={{ mt: 1.5, width: { xs: "100%", sm: 240 } }}
+        inputProps={{ min: 0, max: 23, step: 1 }}
+      />
+    </Box>
+  );
+};
+
+// Lead times before a task's due date ends, such as "1d, 1h"; blank turns due reminders off.
+const DueReminderLeadsField = () => {
+  const { t } = useI18n();
+  const saved = useAppSetting("due_reminder_leads") ?? "1d, 1h";
+  const updateSetting = useUpdateAppSetting();
+  const [draft, setDraft] = useState(saved);
+
+  useEffect(() => {
+    setDraft(saved);
+  }, [saved]);
+
+  return (
+    <TextField
+      label={t("Remind before tasks are due")}
+      value={draft}
+      onChange={(event) => setDraft(event.target.value)}
+      onBlur={() => {
+        if (draft !== saved) updateSetting.mutate({ key: "due_reminder_leads", value: draft });
+      }}
+      helperText={t("Comma-separated, e.g. 1d, 1h, 30m. Empty = off")}
+      sx={{ mt: 1.5, width: { xs: "100%", sm: 360 } }}
+    />
+  );
+};
+
+// Release channel and a manual check; background checks are the opt-in `update_check` job.
+const UpdatesPanel = () => {
+  const { t } = useI18n();
+  const channel = useAppSetting("update_channel") ?? "stable";
+  const updateSetting = useUpdateAppSetting();
+  const checkForUpdates = useCheckForUpdates();
+  const result = checkForUpdates.data;
+
+  return (
+    <Box sx={{ mt: 1.5 }}>
+      <Typography variant="body2" sx={{ fontWeight: 600 }}>
+        {t("Updates")}
+      </Typography>
+      <Box sx={{ display: "flex", flexWrap: "wrap", alignItems: "center", gap: 1.5, mt: 1 }}>
+        <TextField
+          select
+          size="small"
+          label={t("Release channel")}
+          value={channel}
+          onChange={(event) => {
+            updateSetting.mutate({ key: "update_channel", value: event.target.value });
+            checkForUpdates.reset();
+          }}
+          SelectProps={{ native: true }}
+          sx={{ width: { xs: "100%", sm: 200 } }}
+        >
+          <option value="stable">{t("Stable")}</option>
+          <option value="beta">{t("Beta")}</option>
+        </TextField>
+        <Button size="small" variant="outlined" onClick={() => checkForUpdates.mutate()} disabled={checkForUpdates.isPending}>
+          {checkForUpdates.isPending ? t("Checking...") : t("Check for updates")}
+        </Button>
+      </Box>
+      {checkForUpdates.error ? (
+        <Typography variant="caption" color="error" sx={{ display: "block", mt: 0.75 }}>
+          {String(checkForUpdates.error)}
+        </Typography>
+      ) : null}
+      {result ? (
+        <Box sx={{ display: "flex", alignItems: "center", flexWrap: "wrap", gap: 1, mt: 0.75 }}>
+          <Typography variant="caption" color="text.secondary">
+            {result.update_available && result.latest_version
+              ? t("Version {version} is available (you have {current}).", {
+                  version: result.latest_version,
+                  current: result.current_version,
+                })
+              : t("You are on the latest version ({current}).", { current: result.current_version })}
+          </Typography>
+          {result.release_url ? (
+            <Button size="small" onClick={() => openUrl(result.release_url ?? "").catch(() => undefined)}>
+              {t("Release notes")}
+            </Button>
+          ) : null}
+        </Box>
+      ) : null}
+    </Box>
+  );
+};
+
+// Working days decide where the standup commit window starts ("since the previous working day").
+const WorkScheduleFields = () => {
+  const { t } = useI18n();
+  const workDays = useAppSetting("work_days") ?? "monday,tuesday,wednesday,thursday,friday";
+  const savedDaysOff = useAppSetting("days_off") ?? "";
+  const savedWorkdayHours = useAppSetting("workday_hours") ?? "8";
+  const weekNumbering = useAppSetting("week_numbering") ?? "iso";
+  const updateSetting = useUpdateAppSetting([queryKeys.gitCommits]);
+  // Habit week counts depend on where the week starts.
+  const updateWeekNumbering = useUpdateAppSetting([queryKeys.habits]);
+  const [daysOff, setDaysOff] = useState(savedDaysOff);
+  const [workdayHours, setWorkdayHours] = useState(savedWorkdayHours);
+
+  useEffect(() => {
+    setDaysOff(savedDaysOff);
+  }, [savedDaysOff]);
+
+  useEffect(() => {
+    setWorkdayHours(savedWorkdayHours);
+  }, [savedWorkdayHours]);
+
+  const selected = new Set(workDays.split(",").map((day) => day.trim()));
+  const toggleDay = (day: string) => {
+    const next = CHECKIN_WEEKDAYS.filter((known) => (known === day ? !selected.has(known) : selected.has(known)));
+    updateSetting.mutate({ key: "work_days", value: next.join(",") });
+  };
+
+  return (
+    <Box sx={{ mt: 1.5 }}>
+      <TextField
+        select
+        size="small"
+        label={t("Week numbering")}
+        value={weekNumbering}
+        onChange={(event) => updateWeekNumbering.mutate({ key: "week_numbering", value: event.target.value })}
+        SelectProps={{ native: true }}
+        InputLabelProps={{ shrink: true }}
+        sx={{ width: { xs: "100%", sm: 280 }, mb: 0.35 }}
+      >
+        <option value="iso">{t("ISO 8601 (weeks start on Monday)")}</option>
+        <option value="us">{t("US (weeks start on Sunday)")}</option>
+      </TextField>
+      <Typography variant="caption" color="text.secondary" sx={{ display: "block", mb: 1.25, lineHeight: 1.45 }}>
+        {t("Used for habit week counts, weekly reviews and plans, the daily sheet and the activity map.")}
+      </Typography>
+      <Typography variant="body2">{t("Working days")}</Typography>
+      <Box sx={{ display: "flex", flexWrap: "wrap", gap: 0.75, mt: 0.5 }}>
+        {CHECKIN_WEEKDAYS.map((day) => (
+          <Chip
+            key={day}
+            size="small"
+            label={t(day.charAt(0).toUpperCase() + day.slice(1))}
+            color={selected.has(day) ? "primary" : "default"}
+            variant={selected.has(day) ? "filled" : "outlined"}
+            onClick={() => toggleDay(day)}
+          />
+        ))}
+      </Box>
+      <TextField
+        size="small"
+        fullWidth
+        label={t("Days off (YYYY-MM-DD, comma-separated)")}
+        value={daysOff}
+        onChange={(event) => setDaysOff(event.target.value)}
+        onBlur={() => {
+          if (daysOff.trim() !== savedDaysOff) {
+            updateSetting.mutate({ key: "days_off", value: daysOff });
+          }
+        }}
+        sx={{ mt: 1.25 }}
+      />
+      <Typography variant="caption" color="text.secondary" sx={{ display: "block", mt: 0.35, lineHeight: 1.45 }}>
+        {t("Today's commits start from the previous working day, so a Monday standup includes Friday.")}
+      </Typography>
+      <TextField
+        size="small"
+        type="number"
+        label={t("Hours per working day")}
+        value={workdayHours}
+        onChange={(event) => setWorkdayHours(event.target.value)}
+        onBlur={() => {
+          const hours = Math.max(0, Math.round(Number(workdayHours) || 0));
+          if (String(hours) !== savedWorkdayHours) {
+            updateSetting.mutate({ key: "workday_hours", value: String(hours) });
+          } else {
+            setWorkdayHours(savedWorkdayHours);
+          }
+        }}
+        inputProps={{ min: 0, max: 24 }}
+        sx={{ mt: 1.25, width: 200 }}
+      />
+      <Typography variant="caption" color="text.secondary" sx={{ display: "block", mt: 0.35, lineHeight: 1.45 }}>
+        {t("Week plans count these hours, minus meetings, as the capacity of each working day.")}
+      </Typography>
+    </Box>
+  );
+};
+
+const NOTIFICATION_KIND_LABEL: Record<NotificationKind, string> = {
+  journal_reminder: "Daily journal reminder",
+  streak_risk: "Habit streak at risk",
+  break_reminder: "Break reminder",
+  due_reminder: "Task due reminder",
+  goal_checkin: "Weekly goal check-in",
+  timebox: "Timebox finished",
+  digest: "Weekly digest",
+  update_available: "Update available",
+};
+
+// One notification type; edits are saved on blur so the backend is not written on every keystroke.
+const NotificationTemplateFields = ({ template }: { template: NotificationTemplate }) => {
+  const { t } = useI18n();
+  const updateSetting = useUpdateAppSetting();
+  const [title, setTitle] = useState(template.title);
+  const [body, setBody] = useState(template.body);
+
+  useEffect(() => {
+    setTitle(template.title);
+    setBody(template.body);
+  }, [template.title, template.body]);
+
+  const save = (part: "title" | "body", value: string, saved: string) => {
+    if (value !== saved) {
+      updateSetting.mutate({ key: `notification_${template.kind}_${part}`, value });
+    }
+  };
+
+  return (
+    <Box sx={{ mt: 1.5 }}>
+      <Box sx={{ display: "flex", alignItems: "center", gap: 1 }}>
+        <Typography variant="body2" sx={{ flex: 1 }}>
+          {t(NOTIFICATION_KIND_LABEL[template.kind])}
+        </Typography>
+        <IconButton
+          size="small"
+          aria-label={t("Restore default text")}
+          onClick={() => {
+            updateSetting.mutate({ key: `notification_${template.kind}_title`, value: "" });
+            updateSetting.mutate({ key: `notification_${template.kind}_body`, value: "" });
+          }}
+        >
+          <RestoreRoundedIcon fontSize="small" />
+        </IconButton>
+      </Box>
+      <Box sx={{ display: "grid", gridTemplateColumns: { xs: "1fr", sm: "1fr 2fr" }, gap: 1, mt: 0.5 }}>
+        <TextField
+          size="small"
+          label={t("Title")}
+          value={title}
+          onChange={(event) => setTitle(event.target.value)}
+          onBlur={() => save("title", title, template.title)}
+        />
+        <TextField
+          size="small"
+          label={t("Text")}
+          value={body}
+          onChange={(event) => setBody(event.target.value)}
+          onBlur={() => save("body", body, template.body)}
+        />
+      </Box>
+      <Typography variant="caption" color="text.secondary" sx={{ display: "block", mt: 0.5 }}>
+        {t("Placeholders: {placeholders}", {
+          placeholders: template.placeholders.map((name) => `{${name}}`).join(", "),
+        })}
+      </Typography>
+      <Typography variant="caption" color="text.secondary" sx={{ display: "block" }}>
+        {t("Preview: {text}", { text: `${template.preview_title} — ${template.preview_body}` })}
+      </Typography>
+    </Box>
+  );
+};
+
+const NotificationTemplatesPanel = () => {
+  const { t } = useI18n();
+  const { data: templates = [] } = useNotificationTemplates();
+
+  return (
+    <Box sx={{ mt: 2 }}>
+      <Typography variant="body2" sx={{ fontWeight: 600 }}>
+        {t("Notification text")}
+      </Typography>
+      <Typography variant="caption" color="text.secondary" sx={{ display: "block", mt: 0.35, lineHeight: 1.45 }}>
+        {t("Customize system notifications. Clear a field to go back to the default text.")}
+      </Typography>
+      {templates.map((template) => (
+        <NotificationTemplateFields key={template.kind} template={template} />
+      ))}
+    </Box>
+  );
+};
+
+const SCHEDULED_JOB_LABEL: Record<ScheduledJobName, { title: string; description: string }> = {
+  reminders: {
+    title: "Reminders",
+    description: "When break, streak, goal check-in and journal reminders are checked.",
+  },
+  backup: { title: "Database backup", description: "Copies the database into the backups folder; the last 7 are kept." },
+  digest: { title: "Weekly digest", description: "Notification with entries, finished and overdue tasks from the last 7 days." },
+  rollover: { title: "Task rollover", description: "Moves overdue open tasks to today. Recurring tasks are skipped." },
+  update_check: {
+    title: "Update check",
+    description: "Looks for a newer release on the update channel and notifies once per version, outside quiet hours.",
+  },
+  integrity: { title: "Data check", description: "Checks the data integrity rules and fixes the safe cases if allowed." },
+};
+
+// One job row; the cron expression is saved on blur and rejected by the backend if it is invalid.
+const ScheduledJobRow = ({ job }: { job: ScheduledJob }) => {
+  const { t } = useI18n();
+  const setSchedule = useSetJobSchedule();
+  const [schedule, setScheduleText] = useState(job.schedule);
+  const [error, setError] = useState("");
+
+  useEffect(() => {
+    setScheduleText(job.schedule);
+  }, [job.schedule]);
+
+  const save = (nextSchedule: string, enabled: boolean) => {
+    setSchedule.mutate(
+      { job: job.job, schedule: nextSchedule, enabled },
+      {
+        onSuccess: () => setError(""),
+        onError: (mutationError) => setError(String(mutationError)),
+      }
+    );
+  };
+
+  const label = SCHEDULED_JOB_LABEL[job.job];
+  const status = [
+    job.next_run_at ? t("Next run: {time}", { time: format(new Date(job.next_run_at), "EEE MMM d, HH:mm") }) : null,
+    job.last_run_at
+      ? t("Last run: {time} ({status})", {
+          time: format(new Date(job.last_run_at), "MMM d, HH:mm"),
+          status: job.last_status === "error" ? t("failed") : t("ok"),
+        })
+      : null,
+  ]
+    .filter(Boolean)
+    .join(" · ");
+
+  return (
+    <Box sx={{ mt: 1.5 }}>
+      <Box sx={{ display: "flex", alignItems: "center", gap: 1 }}>
+        <Switch
+          size="small"
+          checked={job.enabled}
+          onChange={(event) => save(job.schedule, event.target.checked)}
+          inputProps={{ "aria-label": t(label.title) }}
+        />
+        <Box sx={{ flex: 1, minWidth: 0 }}>
+          <Typography variant="body2">{t(label.title)}</Typography>
+          <Typography variant="caption" color="text.secondary" sx={{ display: "block", lineHeight: 1.45 }}>
+            {t(label.description)}
+          </Typography>
+        </Box>
+        <TextField
+          size="small"
+          label={t("Schedule (cron)")}
+          value={schedule}
+          placeholder={job.default_schedule}
+          onChange={(event) => setScheduleText(event.target.value)}
+          onBlur={() => {
+            if (schedule.trim() !== job.schedule) {
+              save(schedule, job.enabled);
+            }
+          }}
+          error={Boolean(error)}
+          sx={{ width: { xs: 150, sm: 180 } }}
+          inputProps={{ style: { fontFamily: "monospace" } }}
+        />
+      </Box>
+      <Typography
+        variant="caption"
+        color={error || job.last_status === "error" ? "error" : "text.secondary"}
+        sx={{ display: "block", mt: 0.35, pl: 5.5 }}
+      >
+        {error || (job.last_status === "error" && job.last_error ? `${status} — ${job.last_error}` : status)}
+      </Typography>
+    </Box>
+  );
+};
+
+const ScheduledJobsPanel = () => {
+  const { t } = useI18n();
+  const { data: jobs = [] } = useScheduledJobs();
+
+  return (
+    <Box sx={{ mt: 2 }}>
+      <Typography variant="body2" sx={{ fontWeight: 600 }}>
+        {t("Background jobs")}
+      </Typography>
+      <Typography variant="caption" color="text.secondary" sx={{ display: "block", mt: 0.35, lineHeight: 1.45 }}>
+        {t("Cron schedules in local time: minute hour day month weekday. Ranges may wrap past midnight, e.g. 0 22-6 * * * for a night shift. Clear a field to restore the default.")}
+      </Typography>
+      {jobs.map((job) => (
+        <ScheduledJobRow key={job.job} job={job} />
+      ))}
+    </Box>
+  );
+};
+
+// Last integrity check, from the `integrity` job or the buttons here.
+const IntegrityPanel = () => {
+  const { t } = useI18n();
+  const { data: report } = useIntegrityReport();
+  const verify = useVerifyIntegrity();
+  const autoFix = useAppSetting("integrity_auto_fix") !== "false";
+  const updateSetting = useUpdateAppSetting();
+  const [error, setError] = useState("");
+  const failing = (report?.rules ?? []).filter((rule) => rule.violation_count > 0 || rule.fixed_count > 0);
+
+  const run = (fix: boolean) => {
+    verify.mutate(fix, {
+      onSuccess: () => setError(""),
+      onError: (verifyError) => setError(String(verifyError)),
+    });
+  };
+
+  return (
+    <Box sx={{ mt: 2 }}>
+      <Typography variant="body2" sx={{ fontWeight: 600 }}>
+        {t("Data integrity")}
+      </Typography>
+      <Typography variant="caption" color="text.secondary" sx={{ display: "block", mt: 0.35, lineHeight: 1.45 }}>
+        {report?.checked_at
+          ? t("Last checked {date}: {count} problems.", {
+              date: format(new Date(report.checked_at), "MMM d, HH:mm"),
+              count: report.violation_count,
+            })
+          : t("Not checked yet.")}
+      </Typography>
+      {failing.map((rule) => (
+        <Box key={rule.rule} sx={{ mt: 1 }}>
+          <Typography variant="body2" color={rule.violation_count > 0 ? "error" : "text.primary"}>
+            {t(rule.description)}
+          </Typography>
+          <Typography variant="caption" color="text.secondary" sx={{ display: "block" }}>
+            {rule.fixed_count > 0 ? t("{count} fixed automatically.", { count: rule.fixed_count }) + " " : ""}
+            {rule.violations
+              .slice(0, 5)
+              .map((violation) => `#${violation.row_id} ${violation.detail}`)
+              .join(", ")}
+          </Typography>
+        </Box>
+      ))}
+      <Box sx={{ display: "flex", flexWrap: "wrap", alignItems: "center", gap: 1, mt: 1 }}>
+        <Button size="small" variant="outlined" onClick={() => run(false)} disabled={verify.isPending}>
+          {t("Check now")}
+        </Button>
+        <Button size="small" variant="outlined" onClick={() => run(true)} disabled={verify.isPending}>
+          {t("Check and fix")}
+        </Button>
+        {error ? (
+          <Typography variant="caption" color="error">
+            {error}
+          </Typography>
+        ) : null}
+      </Box>
+      <Box sx={{ display: "flex", alignItems: "center", justifyContent: "space-between", gap: 2, mt: 1 }}>
+        <Box sx={{ minWidth: 0 }}>
+          <Typography variant="body2">{t("Fix safe cases automatically")}</Typography>
+          <Typography variant="caption" color="text.secondary" sx={{ display: "block", lineHeight: 1.45 }}>
+            {t("The scheduled check clamps goal progress, fills in or clears task completion times and removes check-ins of deleted habits. Entry dates are only reported.")}
+          </Typography>
+        </Box>
+        <Switch
+          checked={autoFix}
+          onChange={(event) =>
+            updateSetting.mutate({ key: "integrity_auto_fix", value: event.target.checked ? "true" : "false" })
+          }
+        />
+      </Box>
+    </Box>
+  );
+};
+
+// Named copies of the whole database to try bulk cleanups, imports or automation
+// rules on; restoring first saves the current state as another snapshot.
+const WorkspaceSnapshotsPanel = () => {
+  const { t } = useI18n();
+  const { data: snapshots = [] } = useWorkspaceSnapshots();
+  const createSnapshot = useCreateWorkspaceSnapshot();
+  const restoreSnapshot = useRestoreWorkspaceSnapshot();
+  const deleteSnapshot = useDeleteWorkspaceSnapshot();
+  const [name, setName] = useState("");
+  const [status, setStatus] = useState("");
+  const busy = createSnapshot.isPending || restoreSnapshot.isPending || deleteSnapshot.isPending;
+
+  const create = () => {
+    createSnapshot.mutate(name, {
+      onSuccess: (snapshot) => {
+        setName("");
+        setStatus(t("Saved snapshot \"{name}\".", { name: snapshot.name }));
+      },
+      onError: (error) => setStatus(String(error)),
+    });
+  };
+
+  const restore = (snapshotName: string) => {
+    if (!window.confirm(t("Replace all journal data and settings with snapshot \"{name}\"?", { name: snapshotName }))) {
+      return;
+    }
+    restoreSnapshot.mutate(snapshotName, {
+      onSuccess: (previous) =>
+        setStatus(
+          t("Restored \"{name}\". The previous state was saved as \"{previous}\".", {
+            name: snapshotName,
+            previous: previous.name,
+          })
+        ),
+      onError: (error) => setStatus(String(error)),
+    });
+  };
+
+  const remove = (snapshotName: string) => {
+    if (!window.confirm(t("Delete snapshot \"{name}\"?", { name: snapshotName }))) {
+      return;
+    }
+    deleteSnapshot.mutate(snapshotName, {
+      onSuccess: () => setStatus(""),
+      onError: (error) => setStatus(String(error)),
+    });
+  };
+
+  return (
+    <Box sx={{ mt: 2 }}>
+      <Typography variant="body2" sx={{ fontWeight: 600 }}>
+        {t("Workspace snapshots")}
+      </Typography>
+      <Typography variant="caption" color="text.secondary" sx={{ display: "block", mt: 0.35, lineHeight: 1.45 }}>
+        {t("Save everything before a bulk cleanup, import or new automation rule and restore it if you don't like the result. Attachments are not included.")}
+      </Typography>
+      <Box sx={{ display: "flex", flexWrap: "wrap", alignItems: "center", gap: 1, mt: 1 }}>
+        <TextField
+          size="small"
+          label={t("Snapshot name")}
+          value={name}
+          onChange={(event) => setName(event.target.value)}
+          inputProps={{ maxLength: 64 }}
+          sx={{ width: { xs: "100%", sm: 240 } }}
+        />
+        <Button size="small" variant="outlined" onClick={create} disabled={busy || !name.trim()}>
+          {t("Save snapshot")}
+        </Button>
+      </Box>
+      {snapshots.map((snapshot) => (
+        <Box key={snapshot.name} sx={{ display: "flex", alignItems: "center", gap: 1, mt: 1 }}>
+          <Box sx={{ flex: 1, minWidth: 0 }}>
+            <Typography variant="body2" noWrap>
+              {snapshot.name}
+            </Typography>
+            <Typography variant="caption" color="text.secondary">
+              {format(new Date(snapshot.created_at), "MMM d, yyyy HH:mm")} · {(snapshot.size_bytes / (1024 * 1024)).toFixed(1)} MB
+            </Typography>
+          </Box>
+          <Button size="small" startIcon={<RestoreRoundedIcon />} onClick={() => restore(snapshot.name)} disabled={busy}>
+            {t("Restore")}
+          </Button>
+          <IconButton size="small" aria-label={t("Delete")} onClick={() => remove(snapshot.name)} disabled={busy}>
+            <DeleteOutlineIcon fontSize="small" />
+          </IconButton>
+        </Box>
+      ))}
+      {status ? (
+        <Typography variant="caption" color="text.secondary" sx={{ display: "block", mt: 1 }}>
+          {status}
+        </Typography>
+      ) : null}
+    </Box>
+  );
+};
+
+// Where time goes with a large journal: slowest commands since start and the slow-call log.
+const PerformancePanel = () => {
+  const { t } = useI18n();
+  const [open, setOpen] = useState(false);
+  const { data: report, refetch, isFetching } = usePerformanceReport(open);
+  const clearSlowOps = useClearSlowOps();
+  const checkQueryPlans = useCheckQueryPlans();
+  const scanningPaths = (checkQueryPlans.data ?? []).filter((check) => !check.uses_index);
+  const threshold = useAppSetting("slow_op_threshold_ms") ?? "250";
+  const updateSetting = useUpdateAppSetting();
 
-interface ToggleRowProps {
-  label: string;
-  description: string;
-  checked: boolean;
-  onChange: (checked: boolean) => void;
-}
+  return (
+    <Box sx={{ mt: 2 }}>
+      <Typography variant="body2" sx={{ fontWeight: 600 }}>
+        {t("Performance")}
+      </Typography>
+      <Typography variant="caption" color="text.secondary" sx={{ display: "block", mt: 0.35, lineHeight: 1.45 }}>
+        {t("Calls slower than the threshold are logged with their argument sizes, never their content. 0 turns the log off.")}
+      </Typography>
+      <Box sx={{ display: "flex", flexWrap: "wrap", alignItems: "center", gap: 1, mt: 1 }}>
+        <TextField
+          type="number"
+          size="small"
+          label={t("Slow call threshold (ms)")}
+          value={threshold}
+          onChange={(event) =>
+            updateSetting.mutate({
+              key: "slow_op_threshold_ms",
+              value: String(Math.max(0, Math.round(Number(event.target.value) || 0))),
+            })
+          }
+          sx={{ width: { xs: "100%", sm: 220 } }}
+          inputProps={{ min: 0, step: 50 }}
+        />
+        <Button
+          size="small"
+          variant="outlined"
+          onClick={() => (open ? void refetch() : setOpen(true))}
+          disabled={isFetching}
+        >
+          {open ? t("Refresh") : t("Show report")}
+        </Button>
+        {open ? (
+          <Button size="small" color="inherit" onClick={() => clearSlowOps.mutate()} disabled={clearSlowOps.isPending}>
+            {t("Clear slow log")}
+          </Button>
+        ) : null}
+        <Button
+          size="small"
+          variant="outlined"
+          onClick={() => checkQueryPlans.mutate()}
+          disabled={checkQueryPlans.isPending}
+        >
+          {t("Check query plans")}
+        </Button>
+      </Box>
+      {checkQueryPlans.data ? (
+        <Box sx={{ mt: 1 }}>
+          <Typography variant="caption" color={scanningPaths.length > 0 ? "error" : "text.secondary"}>
+            {scanningPaths.length > 0
+              ? t("{count} of {total} common queries read whole tables.", {
+                  count: scanningPaths.length,
+                  total: checkQueryPlans.data.length,
+                })
+              : t("All {total} common queries use an index.", { total: checkQueryPlans.data.length })}
+          </Typography>
+          {scanningPaths.map((check) => (
+            <Typography key={check.name} variant="caption" sx={{ display: "block", fontFamily: "monospace" }}>
+              {`${t(check.description)}: ${check.full_scans.join("; ")}`}
+            </Typography>
+          ))}
+        </Box>
+      ) : null}
+      {checkQueryPlans.error ? (
+        <Typography variant="caption" color="error" sx={{ display: "block", mt: 0.5 }}>
+          {String(checkQueryPlans.error)}
+        </Typography>
+      ) : null}
+      {open && report ? (
+        <Box sx={{ mt: 1 }}>
+          <Typography variant="caption" color="text.secondary" sx={{ display: "block" }}>
+            {t("Since {date}", { date: format(new Date(report.since), "MMM d, HH:mm") })}
+          </Typography>
+          {report.commands.slice(0, 10).map((timing) => (
+            <Typography key={timing.command} variant="caption" sx={{ display: "block", fontFamily: "monospace" }}>
+              {`${timing.command}: ${timing.calls}× · ${t("mean")} ${timing.mean_ms.toFixed(1)} ms · ${t("max")} ${timing.max_ms.toFixed(0)} ms`}
+            </Typography>
+          ))}
+          <Typography variant="body2" sx={{ mt: 1 }}>
+            {t("Slowest recorded calls")}
+          </Typography>
+          {report.slow_ops.length === 0 ? (
+            <Typography variant="caption" color="text.secondary">
+              {t("No slow calls recorded.")}
+            </Typography>
+          ) : (
+            report.slow_ops.slice(0, 10).map((op) => (
+              <Typography key={op.id} variant="caption" sx={{ display: "block", fontFamily: "monospace" }}>
+                {`${op.duration_ms} ms · ${op.command}(${op.params}) · ${format(new Date(op.created_at), "MMM d, HH:mm")}`}
+              </Typography>
+            ))
+          )}
+        </Box>
+      ) : null}
+    </Box>
+  );
+};
 
-const SettingsOptionTile = ({
-  title,
-  description,
-  selected,
-  onClick,
-  accent,
-  preview,
-  radius = "16px",
-}: OptionTileProps) => {
-  const theme = useTheme();
-  const isDark = theme.palette.mode === "dark";
+const OnCallSchedulePanel = () => {
   const { t } = useI18n();
+  const { data: shifts = [] } = useOnCallShifts();
+  const createShift = useCreateOnCallShift();
+  const deleteShift = useDeleteOnCallShift();
+  const importIcs = useImportOnCallIcs();
+  const icsInputRef = useRef<HTMLInputElement | null>(null);
+  const [start, setStart] = useState("");
+  const [end, setEnd] = useState("");
+  const [note, setNote] = useState("");
+  const [status, setStatus] = useState("");
+
+  const handleAdd = () => {
+    createShift.mutate(
+      { startDate: start, endDate: end || start, note: note.trim() },
+      {
+        onSuccess: () => {
+          setStart("");
+          setEnd("");
+          setNote("");
+          setStatus("");
+        },
+        onError: (error) => setStatus(String(error)),
+      }
+    );
+  };
+
+  const handleImportIcs = async (event: ChangeEvent<HTMLInputElement>) => {
+    const file = event.target.files?.[0];
+    if (!file) {
+      return;
+    }
+
+    try {
+      const content = await file.text();
+      importIcs.mutate(content, {
+        onSuccess: (count) => setStatus(t("Imported {count} on-call shifts.", { count })),
+        onError: (error) => setStatus(String(error)),
+      });
+    } finally {
+      event.target.value = "";
+    }
+  };
 
   return (
-    <ButtonBase
-      onClick={onClick}
-      sx={{
-        width: "100%",
-        borderRadius: radius,
-        textAlign: "left",
-        alignItems: "stretch",
-        overflow: "hidden",
-        transition: "border-color 0.14s ease, box-shadow 0.14s ease",
-      }}
-    >
-      <Box
-        sx={{
-          width: "100%",
-          minHeight: 116,
-          p: 1.35,
-          borderRadius: radius,
-          border: "1px solid",
-          borderColor: selected
-            ? accent ?? "primary.main"
-            : theme.palette.divider,
-          bgcolor: selected
-            ? alpha(accent ?? theme.palette.primary.main, isDark ? 0.18 : 0.12)
-            : "background.default",
-          transition: "border-color 0.16s ease, background-color 0.16s ease, box-shadow 0.16s ease",
-          "&:hover": {
-            borderColor: accent ?? "primary.main",
-            boxShadow: theme.shadows[1],
-          },
-        }}
-      >
-        <Box sx={{ display: "flex", alignItems: "flex-start", justifyContent: "space-between", gap: 1 }}>
-          <Box sx={{ minWidth: 0, flex: 1 }}>
-            <Typography variant="body2" sx={{ fontWeight: 700, lineHeight: 1.25 }}>
-              {title}
+    <Box sx={{ mt: 2 }}>
+      <Typography variant="body2" sx={{ fontWeight: 600 }}>
+        {t("On-call schedule")}
+      </Typography>
+      <Typography variant="caption" color="text.secondary" sx={{ display: "block", mt: 0.35, mb: 1, lineHeight: 1.45 }}>
+        {t("On-call days are marked in the activity map and Today dashboard, and compared against your mood.")}
+      </Typography>
+      <Box sx={{ display: "grid", gridTemplateColumns: { xs: "1fr", sm: "1fr 1fr 2fr" }, gap: 1 }}>
+        <TextField
+          size="small"
+          type="date"
+          label={t("From")}
+          value={start}
+          onChange={(event) => setStart(event.target.value)}
+          InputLabelProps={{ shrink: true }}
+        />
+        <TextField
+          size="small"
+          type="date"
+          label={t("To")}
+          value={end}
+          onChange={(event) => setEnd(event.target.value)}
+          InputLabelProps={{ shrink: true }}
+        />
+        <TextField size="small" label={t("Note")} value={note} onChange={(event) => setNote(event.target.value)} />
+      </Box>
+      <Box sx={{ display: "flex", flexWrap: "wrap", alignItems: "center", gap: 1, mt: 1 }}>
+        <Button size="small" variant="outlined" onClick={handleAdd} disabled={!start || createShift.isPending}>
+          {t("Add shift")}
+        </Button>
+        <Button
+          size="small"
+          startIcon={<UploadFileIcon />}
+          onClick={() => icsInputRef.current?.click()}
+          disabled={importIcs.isPending}
+        >
+          {t("Import ICS")}
+        </Button>
+        {status ? (
+          <Typography variant="caption" color="text.secondary">
+            {status}
+          </Typography>
+        ) : null}
+      </Box>
+      <input
+        ref={icsInputRef}
+        type="file"
+        accept="text/calendar,.ics"
+        onChange={handleImportIcs}
+        style={{ display: "none" }}
+      />
+      {shifts.map((shift) => (
+        <Box key={shift.id} sx={{ display: "flex", alignItems: "center", gap: 1, mt: 1 }}>
+          <Box sx={{ flex: 1, minWidth: 0 }}>
+            <Typography variant="body2" noWrap>
+              {shift.start_date === shift.end_date
+                ? format(new Date(`${shift.start_date}T00:00:00`), "MMM d, yyyy")
+                : `${format(new Date(`${shift.start_date}T00:00:00`), "MMM d")} – ${format(
+                    new Date(`${shift.end_date}T00:00:00`),
+                    "MMM d, yyyy"
+                  )}`}
             </Typography>
-            {description ? (
-              <Typography variant="caption" color="text.secondary" sx={{ display: "block", mt: 0.4, lineHeight: 1.45 }}>
-                {description}
+            {shift.note ? (
+              <Typography variant="caption" color="text.secondary" sx={{ display: "block" }} noWrap>
+                {shift.note}
               </Typography>
             ) : null}
           </Box>
-          {selected ? <Chip size="small" label={t("Active")} color="primary" sx={{ flexShrink: 0, alignSelf: "flex-start" }} /> : null}
+          <IconButton
+            size="small"
+            aria-label={t("Delete shift")}
+            onClick={() => deleteShift.mutate(shift.id)}
+            disabled={deleteShift.isPending}
+          >
+            <DeleteOutlineIcon fontSize="small" />
+          </IconButton>
         </Box>
-        {preview ? <Box sx={{ mt: 1.4 }}>{preview}</Box> : null}
+      ))}
+    </Box>
+  );
+};
+
+const JournalEncryptionPanel = () => {
+  const { t } = useI18n();
+  const { data: status } = useJournalEncryptionStatus();
+  const enableEncryption = useEnableJournalEncryption();
+  const disableEncryption = useDisableJournalEncryption();
+  const lockJournal = useLockJournal();
+  const [passphrase, setPassphrase] = useState("");
+  const [confirmation, setConfirmation] = useState("");
+  const [message, setMessage] = useState("");
+
+  const reset = (nextMessage: string) => {
+    setPassphrase("");
+    setConfirmation("");
+    setMessage(nextMessage);
+  };
+
+  const handleEnable = () => {
+    if (passphrase !== confirmation) {
+      setMessage(t("Passphrases do not match."));
+      return;
+    }
+    enableEncryption.mutate(passphrase, {
+      onSuccess: () => reset(t("Journal encrypted. Keep your passphrase safe: it cannot be recovered.")),
+      onError: (error) => setMessage(String(error)),
+    });
+  };
+
+  const handleDisable = () => {
+    disableEncryption.mutate(passphrase, {
+      onSuccess: () => reset(t("Encryption turned off.")),
+      onError: (error) => setMessage(String(error)),
+    });
+  };
+
+  const enabled = status?.enabled ?? false;
+
+  return (
+    <Box sx={{ mt: 1.5 }}>
+      <Typography variant="body2" sx={{ fontWeight: 600 }}>
+        {t("Encryption")}
+      </Typography>
+      <Typography variant="caption" color="text.secondary" sx={{ display: "block", mt: 0.35, mb: 1, lineHeight: 1.45 }}>
+        {enabled
+          ? t("Entry and page content is encrypted with your passphrase. The journal asks for it on every launch.")
+          : t("Encrypt entry and page content with a passphrase. Without it, the content cannot be recovered.")}
+      </Typography>
+      <Box sx={{ display: "grid", gridTemplateColumns: { xs: "1fr", sm: "1fr 1fr" }, gap: 1 }}>
+        <TextField
+          size="small"
+          type="password"
+          label={t("Passphrase")}
+          value={passphrase}
+          onChange={(event) => setPassphrase(event.target.value)}
+        />
+        {enabled ? null : (
+          <TextField
+            size="small"
+            type="password"
+            label={t("Confirm passphrase")}
+            value={confirmation}
+            onChange={(event) => setConfirmation(event.target.value)}
+          />
+        )}
       </Box>
-    </ButtonBase>
+      <Box sx={{ display: "flex", flexWrap: "wrap", alignItems: "center", gap: 1, mt: 1 }}>
+        {enabled ? (
+          <>
+            <Button size="small" variant="outlined" onClick={() => lockJournal.mutate()} disabled={lockJournal.isPending}>
+              {t("Lock now")}
+            </Button>
+            <Button
+              size="small"
+              color="error"
+              onClick={handleDisable}
+              disabled={disableEncryption.isPending || passphrase.length === 0}
+            >
+              {t("Turn off encryption")}
+            </Button>
+          </>
+        ) : (
+          <Button
+            size="small"
+            variant="outlined"
+            onClick={handleEnable}
+            disabled={enableEncryption.isPending || passphrase.length === 0}
+          >
+            {enableEncryption.isPending ? t("Encrypting...") : t("Encrypt journal")}
+          </Button>
+        )}
+        {message ? (
+          <Typography variant="caption" color="text.secondary">
+            {message}
+          </Typography>
+        ) : null}
+      </Box>
+    </Box>
   );
 };
 
-const SettingsToggleRow = ({ label, description, checked, onChange }: ToggleRowProps) => {
+const API_SCOPE_PRESETS: { value: ApiScopePreset; label: string }[] = [
+  { value: "read_only", label: "Read-only" },
+  { value: "tasks_only", label: "Tasks only" },
+  { value: "capture", label: "Browser capture" },
+  { value: "full", label: "Full access" },
+];
+
+const ACCESS_OUTCOME_LABEL: Record<string, string> = {
+  allowed: "Allowed",
+  denied: "Denied",
+  rate_limited: "Rate limited",
+  error: "Error",
+};
+
+const ApiTokensPanel = () => {
+  const { t } = useI18n();
+  const { data: tokens = [] } = useApiTokens();
+  const createToken = useCreateApiToken();
+  const revokeToken = useRevokeApiToken();
+  const { data: accessLog = [] } = useExternalAccessLog();
+  const rateLimit = useAppSetting("api_rate_limit_per_minute") ?? "60";
+  const captureEnabled = useAppSetting("capture_server_enabled") === "true";
+  const capturePort = useAppSetting("capture_server_port") ?? "17321";
+  const updateSetting = useUpdateAppSetting();
+  const [name, setName] = useState("");
+  const [preset, setPreset] = useState<ApiScopePreset>("read_only");
+  const [newSecret, setNewSecret] = useState("");
+
+  const handleCreate = () => {
+    const trimmed = name.trim();
+    if (!trimmed) return;
+    createToken.mutate(
+      { name: trimmed, scopes: null, preset },
+      {
+        onSuccess: (created) => {
+          setNewSecret(created.secret);
+          setName("");
+        },
+      }
+    );
+  };
+
   return (
-    <Box
-      sx={{
-        display: "flex",
-        alignItems: "center",
-        justifyContent: "space-between",
-        gap: 2,
-        py: 1.35,
-        borderBottom: "1px solid",
-        borderColor: "divider",
-      }}
-    >
-      <Box sx={{ minWidth: 0 }}>
-        <Typography variant="body2" sx={{ fontWeight: 600 }}>
-          {label}
+    <Box sx={{ mt: 1.5 }}>
+      <Typography variant="body2" sx={{ fontWeight: 600 }}>
+        {t("API tokens")}
+      </Typography>
+      <Typography variant="caption" color="text.secondary" sx={{ display: "block", mt: 0.35, mb: 1, lineHeight: 1.45 }}>
+        {t("Tokens for external tools such as AI assistants. Private content is never included in a preset.")}
+      </Typography>
+      <Box sx={{ display: "grid", gridTemplateColumns: { xs: "1fr", sm: "2fr 1fr auto" }, gap: 1 }}>
+        <TextField size="small" label={t("Token name")} value={name} onChange={(event) => setName(event.target.value)} />
+        <TextField
+          select
+          size="small"
+          label={t("Access")}
+          value={preset}
+          onChange={(event) => setPreset(event.target.value as ApiScopePreset)}
+          SelectProps={{ native: true }}
+        >
+          {API_SCOPE_PRESETS.map((option) => (
+            <option key={option.value} value={option.value}>
+              {t(option.label)}
+            </option>
+          ))}
+        </TextField>
+        <Button size="small" variant="outlined" onClick={handleCreate} disabled={createToken.isPending || !name.trim()}>
+          {t("Create token")}
+        </Button>
+      </Box>
+      {newSecret ? (
+        <Typography variant="caption" sx={{ display: "block", mt: 1, fontFamily: "monospace", wordBreak: "break-all" }}>
+          {t("Copy this token now, it will not be shown again: {token}", { token: newSecret })}
         </Typography>
-        <Typography variant="caption" color="text.secondary" sx={{ display: "block", mt: 0.35, lineHeight: 1.45 }}>
-          {description}
+      ) : null}
+      {tokens.map((token) => (
+        <Box key={token.id} sx={{ display: "flex", alignItems: "center", gap: 1, mt: 1 }}>
+          <Box sx={{ flex: 1, minWidth: 0 }}>
+            <Typography variant="body2" noWrap>
+              {token.name} <Typography component="span" variant="caption" color="text.secondary">{token.token_prefix}…</Typography>
+            </Typography>
+            <Typography variant="caption" color="text.secondary" sx={{ display: "block" }} noWrap>
+              {token.scopes.join(", ")}
+            </Typography>
+          </Box>
+          <IconButton size="small" aria-label={t("Revoke token")} onClick={() => revokeToken.mutate(token.id)}>
+            <DeleteOutlineIcon fontSize="small" />
+          </IconButton>
+        </Box>
+      ))}
+      <TextField
+        size="small"
+        type="number"
+        label={t("Calls per minute per token (0 = unlimited)")}
+        value={rateLimit}
+        onChange={(event) =>
+          updateSetting.mutate({ key: "api_rate_limit_per_minute", value: String(Math.max(0, Number(event.target.value) || 0)) })
+        }
+        inputProps={{ min: 0 }}
+        sx={{ mt: 1.5, maxWidth: 280 }}
+      />
+      <Box sx={{ display: "flex", alignItems: "center", gap: 1, mt: 1.5 }}>
+        <Switch
+          size="small"
+          checked={captureEnabled}
+          onChange={(event) => updateSetting.mutate({ key: "capture_server_enabled", value: String(event.target.checked) })}
+        />
+        <Typography variant="body2" sx={{ flex: 1 }}>
+          {t("Browser capture endpoint")}
         </Typography>
+        <TextField
+          size="small"
+          type="number"
+          label={t("Port")}
+          value={capturePort}
+          onChange={(event) => updateSetting.mutate({ key: "capture_server_port", value: event.target.value })}
+          inputProps={{ min: 1024, max: 65535 }}
+          sx={{ width: 110 }}
+        />
+      </Box>
+      <Typography variant="caption" color="text.secondary" sx={{ display: "block", mt: 0.35, lineHeight: 1.45 }}>
+        {t("The extension posts to http://127.0.0.1:{port}/capture with a \"Browser capture\" token. Changes apply after restarting the app.", {
+          port: capturePort,
+        })}
+      </Typography>
+      {accessLog.length > 0 ? (
+        <Box sx={{ mt: 1.5 }}>
+          <Typography variant="caption" color="text.secondary" sx={{ fontWeight: 700 }}>
+            {t("Recent external access")}
+          </Typography>
+          {accessLog.map((entry) => (
+            <Box key={entry.id} sx={{ display: "flex", alignItems: "center", gap: 1, mt: 0.5 }}>
+              <Chip
+                size="small"
+                label={t(ACCESS_OUTCOME_LABEL[entry.outcome] ?? entry.outcome)}
+                color={entry.outcome === "allowed" ? "success" : entry.outcome === "rate_limited" ? "warning" : "error"}
+                variant="outlined"
+              />
+              <Typography variant="caption" noWrap sx={{ flex: 1, minWidth: 0 }}>
+                {entry.caller} · {entry.endpoint}
+                {entry.detail ? ` · ${entry.detail}` : ""}
+              </Typography>
+              <Typography variant="caption" color="text.secondary">
+                {format(new Date(entry.created_at), "MMM d, HH:mm")}
+              </Typography>
+            </Box>
+          ))}
+        </Box>
+      ) : null}
+    </Box>
+  );
+};
+
+const ExperimentalFeaturesPanel = () => {
+  const { t } = useI18n();
+  const { data: flags = [] } = useFeatureFlags();
+  const setFlag = useSetFeatureFlag();
+
+  return (
+    <Box sx={{ mt: 1.5 }}>
+      <Typography variant="body2" sx={{ fontWeight: 600 }}>
+        {t("Experimental features")}
+      </Typography>
+      <Typography variant="caption" color="text.secondary" sx={{ display: "block", mt: 0.35, mb: 0.5, lineHeight: 1.45 }}>
+        {t("Work in progress that ships turned off. Changes apply immediately.")}
+      </Typography>
+      {flags.map((flag) => (
+        <Box key={flag.key} sx={{ display: "flex", alignItems: "center", gap: 1, mt: 0.5 }}>
+          <Switch
+            size="small"
+            checked={flag.enabled}
+            disabled={setFlag.isPending}
+            onChange={(event) => setFlag.mutate({ key: flag.key, enabled: event.target.checked })}
+          />
+          <Typography variant="body2" sx={{ flex: 1, minWidth: 0 }}>
+            {t(flag.description)}
+          </Typography>
+          <Typography variant="caption" color="text.secondary" sx={{ fontFamily: "monospace" }}>
+            {flag.key}
+          </Typography>
+        </Box>
+      ))}
+    </Box>
+  );
+};
+
+const JournalPromptsEditor = () => {
+  const { t } = useI18n();
+  const { data: prompts = [] } = useJournalPrompts();
+  const addPrompt = useAddJournalPrompt();
+  const setPromptEnabled = useSetJournalPromptEnabled();
+  const deletePrompt = useDeleteJournalPrompt();
+  const [newPrompt, setNewPrompt] = useState("");
+
+  const handleAdd = () => {
+    const text = newPrompt.trim();
+    if (!text) return;
+    addPrompt.mutate(text, { onSuccess: () => setNewPrompt("") });
+  };
+
+  return (
+    <Box sx={{ py: 1.35 }}>
+      <Typography variant="body2" sx={{ fontWeight: 600 }}>
+        {t("Journaling prompts")}
+      </Typography>
+      <Typography variant="caption" color="text.secondary" sx={{ display: "block", mt: 0.35, mb: 1, lineHeight: 1.45 }}>
+        {t("Enabled prompts rotate daily in the journal editor.")}
+      </Typography>
+      <Box sx={{ display: "grid", gap: 0.4, maxHeight: 260, overflowY: "auto" }}>
+        {prompts.map((prompt) => (
+          <Box key={prompt.id} sx={{ display: "flex", gap: 0.8, alignItems: "center" }}>
+            <Switch
+              size="small"
+              checked={prompt.enabled}
+              onChange={(event) => setPromptEnabled.mutate({ id: prompt.id, enabled: event.target.checked })}
+            />
+            <Typography variant="body2" sx={{ flex: 1, minWidth: 0 }} color={prompt.enabled ? "text.primary" : "text.secondary"}>
+              {prompt.text}
+            </Typography>
+            {!prompt.is_builtin && (
+              <IconButton size="small" aria-label={t("Delete")} onClick={() => deletePrompt.mutate(prompt.id)}>
+                <DeleteOutlineIcon fontSize="small" />
+              </IconButton>
+            )}
+          </Box>
+        ))}
+      </Box>
+      <Box sx={{ display: "flex", gap: 1, mt: 1.2 }}>
+        <TextField
+          size="small"
+          fullWidth
+          value={newPrompt}
+          placeholder={t("Add your own prompt")}
+          onChange={(event) => setNewPrompt(event.target.value)}
+          onKeyDown={(event) => {
+            if (event.key === "Enter") {
+              event.preventDefault();
+              handleAdd();
+            }
+          }}
+        />
+        <Button size="small" variant="outlined" onClick={handleAdd} disabled={addPrompt.isPending}>
+          {t("Add")}
+        </Button>
       </Box>
-      <Switch checked={checked} onChange={(event) => onChange(event.target.checked)} />
     </Box>
   );
 };
@@ -186,6 +2822,10 @@ export const SettingsScreen = ({
     setUiDensity,
     borderRadius,
     setBorderRadius,
+    accentColor,
+    setAccentColor,
+    fontSize,
+    setFontSize,
     resetTheme,
   } = useThemeContext();
 
@@ -250,9 +2890,12 @@ export const SettingsScreen = ({
     tasks,
   ]);
 
-  const exportBackup = () => {
+  const exportBackup = async () => {
+    const backupFormat = await api.getBackupFormat();
     const data = {
+      ...backupFormat,
       exported_at: new Date().toISOString(),
+      app_settings: await api.getAppSettings(),
       entries: allEntries ?? [],
       pages: pages ?? [],
       tasks: tasks ?? [],
@@ -307,8 +2950,8 @@ export const SettingsScreen = ({
             }
             setImportStatus(t("Backup imported successfully."));
           },
-          onError: () => {
-            setImportStatus(t("Import failed. Check JSON format."));
+          onError: (error) => {
+            setImportStatus(t("Import failed: {error}", { error: String(error) }));
           },
         }
       );
@@ -596,6 +3239,78 @@ export const SettingsScreen = ({
               </Box>
             </Box>
 
+            <Box
+              sx={{
+                display: "grid",
+                gridTemplateColumns: { xs: "1fr", md: "repeat(2, minmax(0, 1fr))" },
+                gap: 1.2,
+                mt: 2,
+              }}
+            >
+              <Box>
+                <Typography variant="body2" sx={{ fontWeight: 700, mb: 0.8 }}>
+                  {t("Font size")}
+                </Typography>
+                <Box
+                  sx={{
+                    px: 1.35,
+                    py: 1.2,
+                    borderRadius: settingsInsetRadius,
+                    border: "1px solid",
+                    borderColor: "divider",
+                    bgcolor: "background.default",
+                  }}
+                >
+                  <Slider
+                    min={12}
+                    max={20}
+                    step={1}
+                    value={fontSize}
+                    onChange={(_, value) => setFontSize(Array.isArray(value) ? value[0] : value)}
+                    valueLabelDisplay="auto"
+                  />
+                  <Typography variant="caption" color="text.secondary">
+                    {t("Scales all text; 14 is the default.")}
+                  </Typography>
+                </Box>
+              </Box>
+
+              <Box>
+                <Typography variant="body2" sx={{ fontWeight: 700, mb: 0.8 }}>
+                  {t("Accent color")}
+                </Typography>
+                <Box
+                  sx={{
+                    px: 1.35,
+                    py: 1.2,
+                    borderRadius: settingsInsetRadius,
+                    border: "1px solid",
+                    borderColor: "divider",
+                    bgcolor: "background.default",
+                    display: "flex",
+                    alignItems: "center",
+                    gap: 1,
+                  }}
+                >
+                  <TextField
+                    type="color"
+                    size="small"
+                    value={accentColor ?? activePalette.primary}
+                    onChange={(event) => setAccentColor(event.target.value.toLowerCase())}
+                    sx={{ width: 72 }}
+                  />
+                  <Button
+                    size="small"
+                    variant="outlined"
+                    disabled={accentColor === null}
+                    onClick={() => setAccentColor(null)}
+                  >
+                    {t("Use theme color")}
+                  </Button>
+                </Box>
+              </Box>
+            </Box>
+
             <Box
               sx={{
                 mt: 2,
@@ -685,6 +3400,23 @@ export const SettingsScreen = ({
                 sx={{ mt: 1.5, width: { xs: "100%", sm: 240 } }}
                 inputProps={{ min: 0, max: 23, step: 1 }}
               />
+              <MeetingLoadThresholdField />
+              <GoalCheckinScheduleFields />
+              <QuietHoursFields />
+              <DueReminderLeadsField />
+              <ShutdownRitualFields />
+              <WorkScheduleFields />
+
+              <EntrySectionTemplatesEditor />
+              <JournalPromptsEditor />
+              <OnCallSchedulePanel />
+              <AutomationRulesPanel />
+              <NotificationTemplatesPanel />
+              <ScheduledJobsPanel />
+              <IntegrityPanel />
+              <WorkspaceSnapshotsPanel />
+              <PerformancePanel />
+              <UpdatesPanel />
             </Box>
 
             <Box
@@ -735,9 +3467,10 @@ export const SettingsScreen = ({
                 checked={replaceExistingOnImport}
                 onChange={setReplaceExistingOnImport}
               />
+              <PageRevisionLimitField />
 
               <Box sx={{ display: "flex", flexWrap: "wrap", gap: 1, mt: 1.5 }}>
-                <Button onClick={exportBackup} startIcon={<DownloadIcon />} variant="contained">
+                <Button onClick={() => void exportBackup()} startIcon={<DownloadIcon />} variant="contained">
                   {t("Export Backup (JSON)")}
                 </Button>
                 <Button
@@ -753,6 +3486,32 @@ export const SettingsScreen = ({
                 </Button>
               </Box>
 
+              <MarkdownExportPanel />
+              <FilteredExportPanel />
+              <PageExportPanel />
+
+              <VaultMirrorPanel />
+
+              <DailySheetPanel />
+
+              <DayOneImportPanel />
+              <SlackImportPanel />
+              <SlackStatusPanel />
+              <ObsidianImportPanel />
+              <OrgModePanel />
+              <TodoTxtPanel />
+              <DateShiftPanel />
+
+              <EntryTrashPanel />
+
+              <PageTrashPanel />
+
+              <JournalEncryptionPanel />
+
+              <ApiTokensPanel />
+
+              <ExperimentalFeaturesPanel />
+
               <input
                 ref={fileInputRef}
                 type="file"
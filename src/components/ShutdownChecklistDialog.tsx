@@ -0,0 +1,74 @@
+import { useEffect, useState } from "react";
+import PowerSettingsNewOutlinedIcon from "@mui/icons-material/PowerSettingsNewOutlined";
+import { Box, Button, Dialog, DialogActions, DialogContent, DialogTitle, Typography } from "@mui/material";
+import * as api from "../api";
+import { listenTopic } from "../api/events";
+import { useI18n } from "../i18n/I18nContext";
+import { useAppNotifications } from "../notifications/AppNotifications";
+import type { ShutdownChecklist } from "../types";
+
+// Shown when the tray's Quit finds loose ends during the shutdown ritual. The app only
+// quits once the user acknowledges here; cancelling keeps it running.
+export const ShutdownChecklistDialog = () => {
+  const { t } = useI18n();
+  const { notify } = useAppNotifications();
+  const [checklist, setChecklist] = useState<ShutdownChecklist | null>(null);
+  const [quitting, setQuitting] = useState(false);
+
+  useEffect(() => {
+    const unlisten = listenTopic<ShutdownChecklist>("shutdown-checklist", (event) => setChecklist(event.payload));
+    return () => {
+      unlisten.then((fn) => fn()).catch(() => undefined);
+    };
+  }, []);
+
+  const quit = (stopTimers: boolean) => {
+    setQuitting(true);
+    api.confirmShutdown(stopTimers).catch((error) => {
+      setQuitting(false);
+      notify(t("Failed to quit: {message}", { message: String(error) }), "error");
+    });
+  };
+
+  const timerCount = checklist?.running_timers.length ?? 0;
+
+  return (
+    <Dialog open={checklist !== null} onClose={() => setChecklist(null)} maxWidth="xs" fullWidth>
+      <DialogTitle sx={{ display: "flex", alignItems: "center", gap: 1 }}>
+        <PowerSettingsNewOutlinedIcon color="warning" /> {t("Before you quit")}
+      </DialogTitle>
+      <DialogContent dividers>
+        {checklist?.entry_missing ? (
+          <Typography variant="body2" sx={{ mb: timerCount > 0 ? 1.5 : 0 }}>
+            {t("Today's journal entry is missing.")}
+          </Typography>
+        ) : null}
+        {timerCount > 0 ? (
+          <Box>
+            <Typography variant="body2">{t("Timers still running: {count}", { count: timerCount })}</Typography>
+            <Box component="ul" sx={{ m: 0, pl: 2.5 }}>
+              {checklist?.running_timers.map((title) => (
+                <Typography key={title} component="li" variant="body2" color="text.secondary">
+                  {title}
+                </Typography>
+              ))}
+            </Box>
+          </Box>
+        ) : null}
+      </DialogContent>
+      <DialogActions>
+        <Button onClick={() => setChecklist(null)} disabled={quitting}>
+          {t("Cancel")}
+        </Button>
+        {timerCount > 0 ? (
+          <Button onClick={() => quit(true)} disabled={quitting}>
+            {t("Stop timers and quit")}
+          </Button>
+        ) : null}
+        <Button variant="contained" color="warning" onClick={() => quit(false)} disabled={quitting}>
+          {t("Quit anyway")}
+        </Button>
+      </DialogActions>
+    </Dialog>
+  );
+};
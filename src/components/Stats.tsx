@@ -1,9 +1,16 @@
-import { Box, Typography, Paper, Tooltip as MuiTooltip, Button } from "@mui/material";
+import { Box, Typography, Paper, Tooltip as MuiTooltip, Button, LinearProgress } from "@mui/material";
 import { alpha, useTheme } from "@mui/material/styles";
-import { useEntries } from "../hooks/useEntries";
+import * as api from "../api";
+import { useEntryStreaks, useWritingStats } from "../hooks/useEntries";
 import { useTasks } from "../hooks/useTasks";
+import { usePomodoroHeatmap } from "../hooks/usePomodoro";
+import { useAchievements } from "../hooks/useAchievements";
+import { useOnCallDays } from "../hooks/useOnCall";
+import { usePagesStats } from "../hooks/usePages";
+import { useWeekNumbering } from "../hooks/useAppSettings";
+import type { PageStats } from "../types";
 import { BarChart, Bar, XAxis, YAxis, Tooltip, ResponsiveContainer, CartesianGrid } from "recharts";
-import { format, subDays } from "date-fns";
+import { format, getDay, subDays } from "date-fns";
 import { motion } from "framer-motion";
 import { useEffect, useMemo, useState } from "react";
 import LocalFireDepartmentIcon from '@mui/icons-material/LocalFireDepartment';
@@ -11,6 +18,7 @@ import MilitaryTechIcon from '@mui/icons-material/MilitaryTech';
 import EditNoteIcon from '@mui/icons-material/EditNote';
 import AnalyticsIcon from '@mui/icons-material/Analytics';
 import DownloadIcon from '@mui/icons-material/Download';
+import WhatshotIcon from '@mui/icons-material/Whatshot';
 import {
     APP_USAGE_UPDATED_EVENT,
     EnergyTag,
@@ -34,9 +42,26 @@ const itemVariants = {
 
 export const Stats = () => {
     const muiTheme = useTheme();
-    const { data: entries } = useEntries();
+    // Word counts come from the backend so the dashboard never loads entry text.
+    const { data: writingStats } = useWritingStats();
     const { data: tasks = [] } = useTasks();
-    const entriesData = entries ?? [];
+    const { data: pomodoroHeatmap = [] } = usePomodoroHeatmap(90);
+    const { data: achievements = [] } = useAchievements();
+    const { data: entryStreaks } = useEntryStreaks();
+    const { data: pagesStats } = usePagesStats();
+    const { weekStartsOn, weekNumber } = useWeekNumbering();
+    const { data: onCallDays } = useOnCallDays(
+        format(subDays(new Date(), 89), "yyyy-MM-dd"),
+        format(new Date(), "yyyy-MM-dd")
+    );
+    const unlockedAchievements = achievements.filter((achievement) => achievement.unlocked_at).length;
+    const wordsByDate = useMemo(() => {
+        const map: Record<string, number> = {};
+        writingStats?.days.forEach((day) => {
+            map[day.date] = day.words;
+        });
+        return map;
+    }, [writingStats]);
     const [energyMap, setEnergyMap] = useState<Record<string, EnergyTag>>({});
     const [usageMap, setUsageMap] = useState<Record<string, number>>({});
 
@@ -68,30 +93,28 @@ export const Stats = () => {
         };
     }, []);
 
-    // Calculate Total Words
-    let totalWords = 0;
-
-    const sortedEntries = [...entriesData].sort((a, b) => new Date(a.date).getTime() - new Date(b.date).getTime());
+    const totalWords = writingStats?.total_words ?? 0;
 
-    sortedEntries.forEach((entry) => {
-        const words = entry.yesterday.split(/\s+/).filter(w => w.length > 0).length +
-            entry.today.split(/\s+/).filter(w => w.length > 0).length;
-        totalWords += words;
-    });
+    const pomodoroCountMap = useMemo(() => {
+        const map: Record<string, number> = {};
+        pomodoroHeatmap.forEach((day) => {
+            map[day.date] = day.count;
+        });
+        return map;
+    }, [pomodoroHeatmap]);
 
     // Activity Map (Last 90 Days)
     const activityData = Array.from({ length: 90 }).map((_, i) => {
         const d = subDays(new Date(), 89 - i);
         const dateStr = format(d, "yyyy-MM-dd");
-        const entry = entriesData.find(e => e.date === dateStr);
-        let words = 0;
-        if (entry) {
-            words = entry.yesterday.split(/\s+/).filter(w => w.length > 0).length +
-                entry.today.split(/\s+/).filter(w => w.length > 0).length;
-        }
+        const words = wordsByDate[dateStr] ?? 0;
         const usageSeconds = usageMap[dateStr] ?? 0;
-        return { date: dateStr, words, usageSeconds };
+        const pomodoros = pomodoroCountMap[dateStr] ?? 0;
+        const onCall = onCallDays?.has(dateStr) ?? false;
+        return { date: dateStr, words, usageSeconds, pomodoros, onCall, week: weekNumber(d) };
     });
+    // Each column is one week, so the first day is pushed down to its weekday row.
+    const activityLeadingDays = (getDay(subDays(new Date(), 89)) - weekStartsOn + 7) % 7;
 
     const formatUsageDuration = (seconds: number) => {
         const h = Math.floor(seconds / 3600);
@@ -114,13 +137,7 @@ export const Stats = () => {
     const chartData = Array.from({ length: 14 }).map((_, i) => {
         const d = subDays(new Date(), 13 - i);
         const dateStr = format(d, "yyyy-MM-dd");
-        const entry = entriesData.find(e => e.date === dateStr);
-        let words = 0;
-        if (entry) {
-            words = entry.yesterday.split(/\s+/).filter(w => w.length > 0).length +
-                entry.today.split(/\s+/).filter(w => w.length > 0).length;
-        }
-        return { day: format(d, "MMM d"), words };
+        return { day: format(d, "MMM d"), words: wordsByDate[dateStr] ?? 0 };
     });
 
     const priorityWeight = {
@@ -152,15 +169,14 @@ export const Stats = () => {
             distracted: { days: 0, words: 0 },
         };
 
-        entriesData.forEach((entry) => {
-            const tag = energyMap[entry.date];
+        writingStats?.days.forEach((day) => {
+            const tag = energyMap[day.date];
             if (!tag) {
                 return;
             }
 
-            const words = `${entry.yesterday} ${entry.today}`.split(/\s+/).filter((word) => word.length > 0).length;
             tagBuckets[tag].days += 1;
-            tagBuckets[tag].words += words;
+            tagBuckets[tag].words += day.words;
         });
 
         return (Object.keys(tagBuckets) as EnergyTag[]).map((tag) => {
@@ -168,7 +184,7 @@ export const Stats = () => {
             const average = bucket.days === 0 ? 0 : Math.round(bucket.words / bucket.days);
             return { tag, days: bucket.days, average };
         });
-    }, [energyMap, entriesData]);
+    }, [energyMap, writingStats]);
 
     return (
         <motion.div variants={containerVariants} initial="hidden" animate="show">
@@ -182,7 +198,8 @@ export const Stats = () => {
                             variant="outlined"
                             size="small"
                             startIcon={<DownloadIcon />}
-                            onClick={() => {
+                            onClick={async () => {
+                                const entriesData = await api.getEntries();
                                 const mdContent = "# Dev Journal Export\n\n" + [...entriesData].sort((a, b) => b.date.localeCompare(a.date)).map(e => `## ${e.date}\n### Yesterday\n${e.yesterday}\n\n### Today\n${e.today}\n`).join("\n---\n\n");
                                 const blob = new Blob([mdContent], { type: 'text/markdown' });
                                 const url = URL.createObjectURL(blob);
@@ -211,15 +228,24 @@ export const Stats = () => {
                 <Box
                     sx={{
                         display: 'grid',
-                        gridTemplateColumns: { xs: '1fr', sm: 'repeat(2, minmax(0, 1fr))', xl: 'repeat(4, minmax(0, 1fr))' },
+                        gridTemplateColumns: { xs: '1fr', sm: 'repeat(2, minmax(0, 1fr))', xl: 'repeat(5, minmax(0, 1fr))' },
                         gap: 2.5
                     }}
                 >
                     {[
                         { title: "Impact Score", value: impactScore, icon: LocalFireDepartmentIcon, color: '#f59e0b', suffix: 'Impact' },
                         { title: "Execution Weight", value: weightedCompletedTasks, icon: MilitaryTechIcon, color: '#3b82f6', suffix: 'Weighted tasks' },
-                        { title: "Total Entries", value: entriesData.length, icon: EditNoteIcon, color: '#10b981', suffix: 'Entries' },
-                        { title: "Total Words", value: totalWords, icon: AnalyticsIcon, color: '#8b5cf6', suffix: 'Words' }
+                        { title: "Total Entries", value: entryStreaks?.total_entries ?? writingStats?.entry_count ?? 0, icon: EditNoteIcon, color: '#10b981', suffix: 'Entries' },
+                        { title: "Journal Streak", value: entryStreaks?.current_streak ?? 0, icon: WhatshotIcon, color: '#ef4444', suffix: `Days in a row · best ${entryStreaks?.longest_streak ?? 0}` },
+                        {
+                            title: "Total Words",
+                            value: totalWords,
+                            icon: AnalyticsIcon,
+                            color: '#8b5cf6',
+                            suffix: writingStats?.most_productive_weekday
+                                ? `Words · avg ${Math.round(writingStats.average_words_per_entry)} per entry · best on ${writingStats.most_productive_weekday}s`
+                                : 'Words',
+                        }
                     ].map((stat, i) => (
                         <Box key={i} component={motion.div} variants={itemVariants}>
                             <Paper
@@ -309,18 +335,24 @@ export const Stats = () => {
                             <Typography variant="subtitle2" gutterBottom color="text.secondary">Activity Map (Last 90 Days)</Typography>
                             <Box sx={{
                                 display: 'grid',
-                                gridTemplateColumns: 'repeat(15, 1fr)',
+                                gridTemplateRows: 'repeat(7, 14px)',
+                                gridAutoFlow: 'column',
+                                gridAutoColumns: '14px',
                                 gap: 1,
                                 mt: 2,
                                 overflowX: 'auto',
                                 pb: 1
                             }}>
+                                {Array.from({ length: activityLeadingDays }).map((_, i) => (
+                                    <Box key={`lead-${i}`} />
+                                ))}
                                 {activityData.map((day, i) => (
                                     <MuiTooltip
                                         key={i}
-                                        title={day.usageSeconds > 0
-                                            ? `${day.words} words on ${day.date} • In app: ${formatUsageDuration(day.usageSeconds)}`
-                                            : `${day.words} words on ${day.date}`}
+                                        title={`${day.words} words on ${day.date} (week ${day.week})`
+                                            + (day.usageSeconds > 0 ? ` • In app: ${formatUsageDuration(day.usageSeconds)}` : "")
+                                            + (day.pomodoros > 0 ? ` • Pomodoros: ${day.pomodoros}` : "")
+                                            + (day.onCall ? " • On call" : "")}
                                         arrow
                                     >
                                         <Box sx={{
@@ -328,6 +360,8 @@ export const Stats = () => {
                                             height: 14,
                                             borderRadius: '3px',
                                             bgcolor: getHeatmapColor(day.words),
+                                            outline: day.onCall ? `2px solid ${muiTheme.palette.warning.main}` : 'none',
+                                            outlineOffset: 1,
                                             transition: 'transform 0.1s',
                                             '&:hover': { transform: 'scale(1.2)', zIndex: 1 }
                                         }} />
@@ -342,11 +376,132 @@ export const Stats = () => {
                                 <Box sx={{ width: 10, height: 10, borderRadius: '2px', bgcolor: alpha(muiTheme.palette.success.main, 0.68) }} />
                                 <Box sx={{ width: 10, height: 10, borderRadius: '2px', bgcolor: muiTheme.palette.success.main }} />
                                 More
+                                <Box sx={{ width: 10, height: 10, ml: 1.5, borderRadius: '2px', outline: `2px solid ${muiTheme.palette.warning.main}` }} />
+                                On call
                             </Box>
                         </Paper>
                     </Box>
                 </Box>
 
+                <Box component={motion.div} variants={itemVariants}>
+                    <Paper sx={{ p: 3 }}>
+                        <Typography variant="subtitle2" gutterBottom color="text.secondary">
+                            Achievements ({unlockedAchievements}/{achievements.length})
+                        </Typography>
+                        <Box
+                            sx={{
+                                display: "grid",
+                                gridTemplateColumns: { xs: "1fr", sm: "repeat(2, minmax(0, 1fr))", xl: "repeat(4, minmax(0, 1fr))" },
+                                gap: 1.5,
+                            }}
+                        >
+                            {achievements.map((achievement) => (
+                                <Paper
+                                    key={achievement.key}
+                                    variant="outlined"
+                                    sx={{ p: 1.5, opacity: achievement.unlocked_at ? 1 : 0.65 }}
+                                >
+                                    <Typography variant="body2" sx={{ fontWeight: 700 }}>
+                                        {achievement.unlocked_at ? "🏆 " : ""}{achievement.title}
+                                    </Typography>
+                                    <Typography variant="caption" color="text.secondary" sx={{ display: "block", mb: 1 }}>
+                                        {achievement.unlocked_at
+                                            ? `${achievement.description} • ${format(new Date(achievement.unlocked_at), "MMM d, yyyy")}`
+                                            : achievement.description}
+                                    </Typography>
+                                    <LinearProgress
+                                        variant="determinate"
+                                        value={Math.round((achievement.progress / achievement.threshold) * 100)}
+                                        color={achievement.unlocked_at ? "success" : "primary"}
+                                        sx={{ height: 6, borderRadius: 3 }}
+                                    />
+                                    <Typography variant="caption" color="text.secondary">
+                                        {achievement.progress}/{achievement.threshold}
+                                    </Typography>
+                                </Paper>
+                            ))}
+                        </Box>
+                    </Paper>
+                </Box>
+
+                {pagesStats && pagesStats.page_count > 0 ? (
+                    <Box component={motion.div} variants={itemVariants}>
+                        <Paper sx={{ p: 3 }}>
+                            <Typography variant="subtitle2" gutterBottom color="text.secondary">
+                                Knowledge Base Health
+                            </Typography>
+                            <Box
+                                sx={{
+                                    display: "grid",
+                                    gridTemplateColumns: { xs: "repeat(2, minmax(0, 1fr))", md: "repeat(4, minmax(0, 1fr))" },
+                                    gap: 1.5,
+                                    mb: 2,
+                                }}
+                            >
+                                {[
+                                    { label: "Pages", value: pagesStats.page_count, hint: `${pagesStats.total_words} words` },
+                                    {
+                                        label: "Reading time",
+                                        value: `${pagesStats.total_reading_minutes} min`,
+                                        hint: `${pagesStats.average_words} words per page`,
+                                    },
+                                    {
+                                        label: "Edited in 30 days",
+                                        value: pagesStats.edited_last_30_days,
+                                        hint: `${pagesStats.edited_last_7_days} in the last 7 days`,
+                                    },
+                                    {
+                                        label: "Needs attention",
+                                        value: pagesStats.empty_pages + pagesStats.stale_pages + pagesStats.orphan_pages,
+                                        hint: `${pagesStats.empty_pages} empty · ${pagesStats.stale_pages} stale (${pagesStats.stale_after_days}+ days) · ${pagesStats.orphan_pages} orphaned`,
+                                    },
+                                ].map((stat) => (
+                                    <Paper key={stat.label} variant="outlined" sx={{ p: 1.5 }}>
+                                        <Typography variant="caption" color="text.secondary">
+                                            {stat.label}
+                                        </Typography>
+                                        <Typography variant="h6" sx={{ fontWeight: 700 }}>
+                                            {stat.value}
+                                        </Typography>
+                                        <Typography variant="caption" color="text.secondary">
+                                            {stat.hint}
+                                        </Typography>
+                                    </Paper>
+                                ))}
+                            </Box>
+                            <Box
+                                sx={{
+                                    display: "grid",
+                                    gridTemplateColumns: { xs: "1fr", md: "repeat(3, minmax(0, 1fr))" },
+                                    gap: 1.5,
+                                }}
+                            >
+                                {([
+                                    ["Largest pages", pagesStats.largest, (page: PageStats) => `${page.word_count} words · ${page.reading_minutes} min`],
+                                    ["Least recently edited", pagesStats.stalest, (page: PageStats) => `${page.days_since_edit} days ago`],
+                                    ["Recently edited", pagesStats.recently_edited, (page: PageStats) => format(new Date(page.updated_at), "MMM d, yyyy")],
+                                ] as const).map(([title, pages, describe]) => (
+                                    <Box key={title}>
+                                        <Typography variant="body2" sx={{ fontWeight: 700, mb: 0.5 }}>
+                                            {title}
+                                        </Typography>
+                                        {pages.map((page) => (
+                                            <Box key={page.page_id} sx={{ display: "flex", justifyContent: "space-between", gap: 1 }}>
+                                                <Typography variant="body2" noWrap>
+                                                    {page.title || "Untitled"}
+                                                </Typography>
+                                                <Typography variant="caption" color="text.secondary" sx={{ flexShrink: 0 }}>
+                                                    {describe(page)}
+                                                </Typography>
+                                            </Box>
+                                        ))}
+                                    </Box>
+                                ))}
+                            </Box>
+                        </Paper>
+                    </Box>
+                ) : null}
+
                 <Box component={motion.div} variants={itemVariants}>
                     <Paper sx={{ p: 3 }}>
                         <Typography variant="subtitle2" gutterBottom color="text.secondary">
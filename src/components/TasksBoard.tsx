@@ -30,6 +30,7 @@ import {
   useSensor,
   useSensors,
 } from "@dnd-kit/core";
+import { ReviewQueuePanel } from "./tasks/ReviewQueuePanel";
 import { TaskDetailsDialog } from "./tasks/TaskDetailsDialog";
 import { TaskEditDialog } from "./tasks/TaskEditDialog";
 import { TasksBoardToolbar } from "./tasks/TasksBoardToolbar";
@@ -39,17 +40,22 @@ import {
   useDeleteTaskSubtask,
   useDeleteTask,
   usePauseTaskTimer,
+  useReorderTaskSubtasks,
+  useReorderTasks,
   useResetTaskTimer,
   useStartTaskTimer,
+  useSetTaskTags,
   useTaskSubtasks,
   useUpdateTaskSubtask,
   useTasks,
   useUpdateTask,
   useUpdateTaskStatus,
 } from "../hooks/useTasks";
+import { useSetTaskSlackStatusOptOut, useSlackStatusOptOuts, useSlackStatusSettings } from "../hooks/useSlackStatus";
 import { useProjects } from "../hooks/useProjects";
 import { useGoals } from "../hooks/useGoals";
 import { useTasksPreferences } from "../hooks/useTasksPreferences";
+import { useTaskTags } from "../hooks/useAutomationRules";
 import { Task, TaskPriority, TaskRecurrence, TaskStatus, TaskSubtask } from "../types";
 import {
   formatDuration,
@@ -107,6 +113,7 @@ const recurrenceLabelKey: Record<TaskRecurrence, string> = {
   daily: "Daily",
   weekdays: "Weekdays",
   weekly: "Weekly",
+  monthly: "Monthly",
 };
 
 const parseDraggedTaskId = (id: string | number): number | null => {
@@ -120,6 +127,14 @@ const parseDraggedTaskId = (id: string | number): number | null => {
   return null;
 };
 
+const parseDropTargetTaskId = (id: string | number): number | null => {
+  if (typeof id === "string" && id.startsWith("before-task-")) {
+    const parsed = Number(id.slice(12));
+    return Number.isFinite(parsed) ? parsed : null;
+  }
+  return null;
+};
+
 interface DroppableColumnProps {
   status: TaskStatus;
   children: ReactNode;
@@ -161,11 +176,13 @@ interface DraggableTaskCardProps {
   children: ReactNode;
 }
 
+// Each card is also a drop target, so dropping a task on it places the task there.
 const DraggableTaskCard = ({ taskId, disabled, children }: DraggableTaskCardProps) => {
   const { attributes, listeners, setNodeRef, transform, isDragging } = useDraggable({
     id: `task-${taskId}`,
     disabled,
   });
+  const { setNodeRef: setDropNodeRef } = useDroppable({ id: `before-task-${taskId}`, disabled });
 
   const styleTransform = transform
     ? `translate3d(${Math.round(transform.x)}px, ${Math.round(transform.y)}px, 0)`
@@ -173,7 +190,10 @@ const DraggableTaskCard = ({ taskId, disabled, children }: DraggableTaskCardProp
 
   return (
     <Box
-      ref={setNodeRef}
+      ref={(node: HTMLElement | null) => {
+        setNodeRef(node);
+        setDropNodeRef(node);
+      }}
       {...attributes}
       {...listeners}
       sx={{
@@ -216,13 +236,16 @@ export const TasksBoard = () => {
       daily: t(recurrenceLabelKey.daily),
       weekdays: t(recurrenceLabelKey.weekdays),
       weekly: t(recurrenceLabelKey.weekly),
+      monthly: t(recurrenceLabelKey.monthly),
     }),
     [t]
   );
   // `nowMs` is updated every second to render live timer values without round-trips.
-  const { data: tasks = [], isLoading } = useTasks();
+  const [tagFilter, setTagFilter] = useState<string | null>(null);
+  const { data: tasks = [], isLoading } = useTasks(tagFilter);
   const { data: projects = [] } = useProjects();
   const { data: goals = [] } = useGoals();
+  const { data: taskTags = [] } = useTaskTags();
   const createTask = useCreateTask();
   const updateTask = useUpdateTask();
   const updateStatus = useUpdateTaskStatus();
@@ -233,6 +256,12 @@ export const TasksBoard = () => {
   const createTaskSubtask = useCreateTaskSubtask();
   const updateTaskSubtask = useUpdateTaskSubtask();
   const deleteTaskSubtask = useDeleteTaskSubtask();
+  const reorderTaskSubtasks = useReorderTaskSubtasks();
+  const reorderTasks = useReorderTasks();
+  const setTaskTags = useSetTaskTags();
+  const { data: slackStatusSettings } = useSlackStatusSettings();
+  const { data: slackStatusOptOuts = [] } = useSlackStatusOptOuts();
+  const setSlackStatusOptOut = useSetTaskSlackStatusOptOut();
 
   const [query, setQuery] = useState("");
   const [statusFilter, setStatusFilter] = useState<"all" | TaskStatus>("all");
@@ -256,6 +285,7 @@ export const TasksBoard = () => {
   const [recurrence, setRecurrence] = useState<TaskRecurrence>("none");
   const [recurrenceUntil, setRecurrenceUntil] = useState("");
   const [timeEstimateMinutes, setTimeEstimateMinutes] = useState(0);
+  const [tagsText, setTagsText] = useState("");
   const [nowMs, setNowMs] = useState(() => Date.now());
   const [beforeOutcome, setBeforeOutcome] = useState("");
   const [afterOutcome, setAfterOutcome] = useState("");
@@ -277,7 +307,9 @@ export const TasksBoard = () => {
     deleteTask.isPending ||
     createTaskSubtask.isPending ||
     updateTaskSubtask.isPending ||
-    deleteTaskSubtask.isPending;
+    deleteTaskSubtask.isPending ||
+    reorderTaskSubtasks.isPending ||
+    reorderTasks.isPending;
 
   const dndSensors = useSensors(
     useSensor(PointerSensor, {
@@ -329,6 +361,20 @@ export const TasksBoard = () => {
 
   const stats = useMemo(() => getTaskBoardStats(tasks), [tasks]);
 
+  const tagsByTask = useMemo(() => {
+    const map = new Map<number, string[]>();
+    taskTags.forEach((tag) => map.set(tag.task_id, [...(map.get(tag.task_id) ?? []), tag.tag]));
+    return map;
+  }, [taskTags]);
+
+  const allTags = useMemo(
+    () =>
+      Array.from(new Map(taskTags.map((tag) => [tag.tag.toLowerCase(), tag.tag])).values()).sort((a, b) =>
+        a.localeCompare(b, undefined, { sensitivity: "base" })
+      ),
+    [taskTags]
+  );
+
   const projectNameById = useMemo(() => {
     const map = new Map<number, string>();
     projects.forEach((project) => map.set(project.id, project.name));
@@ -362,6 +408,7 @@ export const TasksBoard = () => {
     setStatusFilter("all");
     setPriorityFilter("all");
     setProjectFilter("all");
+    setTagFilter(null);
     setShowOverdueOnly(false);
   };
 
@@ -377,6 +424,7 @@ export const TasksBoard = () => {
     setRecurrence("none");
     setRecurrenceUntil("");
     setTimeEstimateMinutes(0);
+    setTagsText("");
     setBeforeOutcome("");
     setAfterOutcome("");
     setDialogOpen(true);
@@ -394,6 +442,7 @@ export const TasksBoard = () => {
     setRecurrence(task.recurrence);
     setRecurrenceUntil(task.recurrence_until ?? "");
     setTimeEstimateMinutes(task.time_estimate_minutes);
+    setTagsText((tagsByTask.get(task.id) ?? []).join(", "));
     const outcome = taskOutcomes[String(task.id)];
     setBeforeOutcome(outcome?.before ?? "");
     setAfterOutcome(outcome?.after ?? "");
@@ -444,12 +493,23 @@ export const TasksBoard = () => {
 
     const normalizedTimeEstimate = normalizeEstimateMinutes(timeEstimateMinutes);
     const normalizedQuery = query.trim().toLowerCase();
+    const tags = tagsText
+      .split(",")
+      .map((tag) => tag.trim().replace(/^#/, ""))
+      .filter(Boolean);
+    const saveTags = (taskId: number) =>
+      setTaskTags.mutate(
+        { task_id: taskId, tags },
+        { onError: (error) => notify(t("Failed to save tags: {message}", { message: String(error) }), "error") }
+      );
 
     const ensureCreatedTaskVisible = (createdTask: Task) => {
       const hiddenByStatus = statusFilter !== "all" && createdTask.status !== statusFilter;
       const hiddenByPriority = priorityFilter !== "all" && createdTask.priority !== priorityFilter;
       const hiddenByProject = projectFilter !== "all" && createdTask.project_id !== projectFilter;
       const hiddenByOverdueOnly = showOverdueOnly && !isTaskOverdue(createdTask);
+      const hiddenByTag =
+        tagFilter !== null && !tags.some((tag) => tag.toLowerCase() === tagFilter.toLowerCase());
       const hiddenByQuery =
         normalizedQuery.length > 0 &&
         !createdTask.title.toLowerCase().includes(normalizedQuery) &&
@@ -460,6 +520,7 @@ export const TasksBoard = () => {
         hiddenByPriority ||
         hiddenByProject ||
         hiddenByOverdueOnly ||
+        hiddenByTag ||
         hiddenByQuery;
 
       if (!needsReset) {
@@ -478,6 +539,9 @@ export const TasksBoard = () => {
       if (hiddenByOverdueOnly) {
         setShowOverdueOnly(false);
       }
+      if (hiddenByTag) {
+        setTagFilter(null);
+      }
       if (hiddenByQuery) {
         setQuery("");
       }
@@ -503,6 +567,7 @@ export const TasksBoard = () => {
         {
           onSuccess: () => {
             saveTaskOutcome(editingTask.id);
+            saveTags(editingTask.id);
             notify(t("Task updated."), "success");
             setDialogOpen(false);
           },
@@ -529,6 +594,9 @@ export const TasksBoard = () => {
           onSuccess: (createdTask) => {
             const filtersReset = ensureCreatedTaskVisible(createdTask);
             saveTaskOutcome(createdTask.id);
+            if (tags.length > 0) {
+              saveTags(createdTask.id);
+            }
             notify(
               filtersReset
                 ? t("Task created. Filters were reset so you can see it.")
@@ -611,13 +679,26 @@ export const TasksBoard = () => {
     if (busy || taskId === null || !overId || typeof overId !== "string") {
       return;
     }
+    const draggedTask = tasks.find((task) => task.id === taskId);
+    if (!draggedTask) {
+      return;
+    }
+
+    const overTaskId = parseDropTargetTaskId(overId);
+    if (overTaskId !== null) {
+      const overTask = tasks.find((task) => task.id === overTaskId);
+      if (!overTask || overTask.id === draggedTask.id) {
+        return;
+      }
+      placeTask(draggedTask, overTask);
+      return;
+    }
+
     if (!columns.some((column) => column.status === overId)) {
       return;
     }
     const nextStatus = overId as TaskStatus;
-
-    const draggedTask = tasks.find((task) => task.id === taskId);
-    if (!draggedTask || draggedTask.status === nextStatus) {
+    if (draggedTask.status === nextStatus) {
       return;
     }
 
@@ -625,6 +706,23 @@ export const TasksBoard = () => {
     notify(t("Task moved to {status}.", { status: statusLabel[nextStatus] }), "info");
   };
 
+  // Dropping on a card takes that card's place in its column, moving the task there first
+  // when it comes from another column.
+  const placeTask = (task: Task, target: Task) => {
+    const nextStatus = target.status;
+    const columnIds = grouped[nextStatus].map((item) => item.id);
+    const orderedIds = columnIds.filter((id) => id !== task.id);
+    orderedIds.splice(columnIds.indexOf(target.id), 0, task.id);
+    const reorder = () => reorderTasks.mutate({ status: nextStatus, ordered_ids: orderedIds });
+
+    if (task.status === nextStatus) {
+      reorder();
+      return;
+    }
+    updateStatus.mutate({ id: task.id, status: nextStatus }, { onSuccess: reorder });
+    notify(t("Task moved to {status}.", { status: statusLabel[nextStatus] }), "info");
+  };
+
   const openEditFromTaskDetails = () => {
     if (!activeTask) {
       return;
@@ -658,6 +756,22 @@ export const TasksBoard = () => {
     updateTaskSubtask.mutate({ id: subtaskId, completed });
   };
 
+  const handleMoveSubtask = (subtaskId: number, offset: -1 | 1) => {
+    if (!activeTaskId) {
+      return;
+    }
+
+    const orderedIds = activeTaskSubtasks.map((subtask) => subtask.id);
+    const from = orderedIds.indexOf(subtaskId);
+    const to = from + offset;
+    if (from < 0 || to < 0 || to >= orderedIds.length) {
+      return;
+    }
+
+    [orderedIds[from], orderedIds[to]] = [orderedIds[to], orderedIds[from]];
+    reorderTaskSubtasks.mutate({ task_id: activeTaskId, ordered_ids: orderedIds });
+  };
+
   const beginSubtaskEdit = (subtask: TaskSubtask) => {
     setEditingSubtaskId(subtask.id);
     setEditingSubtaskTitle(subtask.title);
@@ -710,6 +824,7 @@ export const TasksBoard = () => {
         onQueryChange={setQuery}
         onResetFilters={resetFilters}
         onStatusFilterChange={setStatusFilter}
+        onTagFilterChange={setTagFilter}
         onToggleOverdueOnly={() => setShowOverdueOnly((prev) => !prev)}
         priorityFilter={priorityFilter}
         projectFilter={projectFilter}
@@ -718,9 +833,13 @@ export const TasksBoard = () => {
         showOverdueOnly={showOverdueOnly}
         stats={stats}
         statusFilter={statusFilter}
+        tagFilter={tagFilter}
+        tags={allTags}
         t={t}
       />
 
+      <ReviewQueuePanel boardSurfaceSx={boardSurfaceSx} priorityLabel={priorityLabel} t={t} />
+
       <DndContext
         sensors={dndSensors}
         collisionDetection={closestCorners}
@@ -896,6 +1015,31 @@ export const TasksBoard = () => {
                               />
                             </>
                           ) : null}
+                          {task.subtask_count > 0 ? (
+                            <Chip
+                              size="small"
+                              label={t("Checklist: {done}/{total}", {
+                                done: task.subtasks_completed,
+                                total: task.subtask_count,
+                              })}
+                              color={task.subtasks_completed === task.subtask_count ? "success" : "default"}
+                              variant="outlined"
+                            />
+                          ) : null}
+                          {(tagsByTask.get(task.id) ?? []).map((tag) => (
+                            <Chip
+                              key={tag}
+                              size="small"
+                              label={`#${tag}`}
+                              variant="outlined"
+                              color={tagFilter?.toLowerCase() === tag.toLowerCase() ? "primary" : "default"}
+                              onPointerDown={(event) => event.stopPropagation()}
+                              onClick={(event) => {
+                                event.stopPropagation();
+                                setTagFilter(tag);
+                              }}
+                            />
+                          ))}
                         </Stack>
                         {(task.project_id || task.goal_id || task.recurrence !== "none") ? (
                           <Typography variant="caption" color="text.secondary" sx={{ display: "block", mt: 0.6 }}>
@@ -1103,13 +1247,21 @@ export const TasksBoard = () => {
         onDeleteSubtask={handleDeleteSubtask}
         onEditSubtaskTitleChange={setEditingSubtaskTitle}
         onEditTask={openEditFromTaskDetails}
+        onMoveSubtask={handleMoveSubtask}
         onNewSubtaskTitleChange={setNewSubtaskTitle}
         onSaveSubtaskEdit={saveSubtaskEdit}
+        onSlackStatusOptOutChange={(optedOut) => {
+          if (activeTask) {
+            setSlackStatusOptOut.mutate({ task_id: activeTask.id, opted_out: optedOut });
+          }
+        }}
         onToggleSubtask={handleToggleSubtask}
         outcome={activeTask ? taskOutcomes[String(activeTask.id)] : undefined}
         priorityColor={priorityColor}
         priorityLabel={priorityLabel}
         recurrenceLabel={recurrenceLabel}
+        slackStatusEnabled={slackStatusSettings?.enabled ?? false}
+        slackStatusOptedOut={activeTask ? slackStatusOptOuts.includes(activeTask.id) : false}
         statusLabel={statusLabel}
         t={t}
         formatTaskDateOnly={formatTaskDateOnly}
@@ -1137,6 +1289,7 @@ export const TasksBoard = () => {
         onRecurrenceUntilChange={setRecurrenceUntil}
         onSave={handleSave}
         onStatusChange={setStatus}
+        onTagsTextChange={setTagsText}
         onTimeEstimateMinutesChange={(value) =>
           setTimeEstimateMinutes(normalizeEstimateMinutes(value))
         }
@@ -1148,6 +1301,7 @@ export const TasksBoard = () => {
         recurrenceUntil={recurrenceUntil}
         status={status}
         t={t}
+        tagsText={tagsText}
         timeEstimateMinutes={timeEstimateMinutes}
         title={title}
       />
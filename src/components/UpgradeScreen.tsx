@@ -0,0 +1,52 @@
+import { Box, LinearProgress, Typography } from "@mui/material";
+import { useEffect, useState } from "react";
+import * as api from "../api";
+import { listenTopic } from "../api/events";
+import { useI18n } from "../i18n/I18nContext";
+import type { MigrationProgress } from "../types";
+
+// Content of the small "upgrade" window shown while startup migrations run.
+// The backend closes it and opens the main window once they finish.
+export const UpgradeScreen = () => {
+  const { t } = useI18n();
+  const [progress, setProgress] = useState<MigrationProgress | null>(null);
+
+  useEffect(() => {
+    const unlisten = listenTopic<MigrationProgress>("migration-progress", (event) => setProgress(event.payload));
+    api
+      .getMigrationProgress()
+      .then((current) => setProgress((previous) => previous ?? current))
+      .catch(() => undefined);
+
+    return () => {
+      unlisten.then((fn) => fn()).catch(() => undefined);
+    };
+  }, []);
+
+  const percent = progress && progress.total > 0 ? (progress.completed / progress.total) * 100 : 0;
+
+  return (
+    <Box sx={{ height: "100vh", display: "flex", flexDirection: "column", justifyContent: "center", px: 3, gap: 1.25 }}>
+      <Typography variant="subtitle1" sx={{ fontWeight: 600 }}>
+        {progress?.error ? t("Upgrading your journal failed") : t("Upgrading your journal…")}
+      </Typography>
+      {progress?.error ? (
+        <Typography variant="body2" color="error" sx={{ wordBreak: "break-word" }}>
+          {progress.error}
+        </Typography>
+      ) : (
+        <>
+          <LinearProgress variant={progress ? "determinate" : "indeterminate"} value={percent} />
+          <Typography variant="caption" color="text.secondary">
+            {progress
+              ? t("Step {completed} of {total}. This can take a minute on large journals.", {
+                  completed: Math.min(progress.completed + 1, progress.total),
+                  total: progress.total,
+                })
+              : t("Preparing…")}
+          </Typography>
+        </>
+      )}
+    </Box>
+  );
+};
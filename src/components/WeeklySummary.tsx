@@ -1,8 +1,13 @@
-import { Box, Chip, Paper, Stack, Typography } from "@mui/material";
-import { format, isAfter, parseISO, subDays } from "date-fns";
+import { Box, Button, Chip, Paper, Stack, Typography } from "@mui/material";
+import ContentCopyIcon from "@mui/icons-material/ContentCopy";
+import EventNoteIcon from "@mui/icons-material/EventNote";
+import { addWeeks, format, parseISO, subDays } from "date-fns";
 import { useMemo } from "react";
-import { useEntries } from "../hooks/useEntries";
-import { useTasks } from "../hooks/useTasks";
+import { useWeekInfo } from "../hooks/useAppSettings";
+import { useEntriesBetween, useGenerateWeeklyReview } from "../hooks/useEntries";
+import { useGenerateWeekPlan } from "../hooks/usePages";
+import { useAppNotifications } from "../notifications/AppNotifications";
+import { useContextSwitches, useTasks } from "../hooks/useTasks";
 
 const stopWords = new Set([
   "the",
@@ -50,17 +55,46 @@ const extractTopKeywords = (texts: string[], limit = 5) => {
 };
 
 export const WeeklySummary = () => {
-  const { data: entries = [] } = useEntries();
+  const today = new Date();
+  const { data: weekEntries = [] } = useEntriesBetween(
+    format(subDays(today, 6), "yyyy-MM-dd"),
+    format(today, "yyyy-MM-dd")
+  );
   const { data: tasks = [] } = useTasks();
+  const { data: contextSwitches = [] } = useContextSwitches(
+    format(subDays(today, 6), "yyyy-MM-dd"),
+    format(today, "yyyy-MM-dd")
+  );
+  // The backend snaps any date to its configured week, so today works until this loads.
+  const { data: weekInfo } = useWeekInfo();
+  const weekStart = weekInfo?.week_start ?? format(today, "yyyy-MM-dd");
+  const generateWeeklyReview = useGenerateWeeklyReview();
+  const generateWeekPlan = useGenerateWeekPlan();
+  const { notify } = useAppNotifications();
+
+  const planNextWeek = () => {
+    const nextWeekStart = format(addWeeks(parseISO(weekStart), 1), "yyyy-MM-dd");
+    generateWeekPlan.mutate(nextWeekStart, {
+      onSuccess: (page) => notify(`Created page "${page.title}".`, "success"),
+      onError: () => notify("Failed to generate the week plan.", "error"),
+    });
+  };
+
+  const copyWeeklyReview = () => {
+    generateWeeklyReview.mutate(weekStart, {
+      onSuccess: async (markdown) => {
+        try {
+          await navigator.clipboard.writeText(markdown);
+          notify("Weekly review copied as Markdown.", "success");
+        } catch {
+          notify("Could not access the clipboard.", "error");
+        }
+      },
+      onError: () => notify("Failed to generate weekly review.", "error"),
+    });
+  };
 
   const summary = useMemo(() => {
-    const periodStart = subDays(new Date(), 6);
-    const periodStartDate = parseISO(format(periodStart, "yyyy-MM-dd"));
-
-    const weekEntries = entries.filter((entry) => {
-      const entryDate = parseISO(entry.date);
-      return isAfter(entryDate, subDays(periodStartDate, 1));
-    });
 
     const wordsByDay = weekEntries.map((entry) => {
       const words = `${entry.yesterday} ${entry.today}`
@@ -81,6 +115,9 @@ export const WeeklySummary = () => {
       4
     );
 
+    const totalSwitches = contextSwitches.reduce((sum, day) => sum + day.switches, 0);
+    const mostFragmented = [...contextSwitches].sort((a, b) => b.switches - a.switches)[0];
+
     return {
       journalDays: weekEntries.length,
       totalWords,
@@ -89,14 +126,41 @@ export const WeeklySummary = () => {
       completedTasks,
       activeTasks,
       topKeywords,
+      totalSwitches,
+      mostFragmented,
     };
-  }, [entries, tasks]);
+  }, [weekEntries, tasks, contextSwitches]);
 
   return (
     <Paper sx={{ p: 3 }}>
-      <Typography variant="h6" sx={{ fontWeight: 700 }}>
-        Weekly Summary
-      </Typography>
+      <Stack direction="row" justifyContent="space-between" alignItems="center" spacing={1}>
+        <Typography variant="h6" sx={{ fontWeight: 700 }}>
+          Weekly Summary
+          {weekInfo ? (
+            <Typography component="span" variant="caption" color="text.secondary" sx={{ ml: 1 }}>
+              Week {weekInfo.week}
+            </Typography>
+          ) : null}
+        </Typography>
+        <Stack direction="row" spacing={0.5}>
+          <Button
+            size="small"
+            startIcon={<EventNoteIcon fontSize="small" />}
+            onClick={planNextWeek}
+            disabled={generateWeekPlan.isPending}
+          >
+            Plan next week
+          </Button>
+          <Button
+            size="small"
+            startIcon={<ContentCopyIcon fontSize="small" />}
+            onClick={copyWeeklyReview}
+            disabled={generateWeeklyReview.isPending}
+          >
+            Copy weekly review
+          </Button>
+        </Stack>
+      </Stack>
       <Typography variant="body2" color="text.secondary" sx={{ mb: 2 }}>
         Rolling 7-day snapshot of journal activity and execution.
       </Typography>
@@ -129,6 +193,12 @@ export const WeeklySummary = () => {
           <Typography variant="body2" color="text.secondary" sx={{ mt: 0.5 }}>
             {summary.activeTasks} active tasks
           </Typography>
+          {summary.totalSwitches > 0 && summary.mostFragmented ? (
+            <Typography variant="caption" color="text.secondary" sx={{ display: "block", mt: 1 }}>
+              {summary.totalSwitches} context switches, most on {summary.mostFragmented.date} (
+              {summary.mostFragmented.switches})
+            </Typography>
+          ) : null}
         </Paper>
       </Stack>
 
@@ -0,0 +1,79 @@
+import { useState } from "react";
+import AutoAwesomeOutlinedIcon from "@mui/icons-material/AutoAwesomeOutlined";
+import { Box, Button, Dialog, DialogActions, DialogContent, DialogTitle, Typography } from "@mui/material";
+import { useMarkWhatsNewSeen, useWhatsNew } from "../hooks/useAppSettings";
+import { useI18n } from "../i18n/I18nContext";
+
+// Shown once after an update: release notes since the last seen version and the data
+// migrations that ran on this journal. Closing it marks the running version as seen.
+export const WhatsNewDialog = () => {
+  const { t } = useI18n();
+  const { data: whatsNew } = useWhatsNew();
+  const markSeen = useMarkWhatsNewSeen();
+  const [dismissed, setDismissed] = useState(false);
+
+  const hasNews = Boolean(whatsNew && (whatsNew.releases.length > 0 || whatsNew.migrations.length > 0));
+  const handleClose = () => {
+    setDismissed(true);
+    markSeen.mutate();
+  };
+
+  return (
+    <Dialog open={hasNews && !dismissed} onClose={handleClose} maxWidth="sm" fullWidth>
+      <DialogTitle sx={{ display: "flex", alignItems: "center", gap: 1 }}>
+        <AutoAwesomeOutlinedIcon color="primary" />{" "}
+        {t("What's new in {version}", { version: whatsNew?.current_version ?? "" })}
+      </DialogTitle>
+      <DialogContent dividers>
+        {whatsNew?.releases.map((release) => (
+          <Box key={release.version} sx={{ mb: 2 }}>
+            <Typography variant="subtitle1">
+              {release.version}
+              {release.date ? (
+                <Typography component="span" variant="caption" color="text.secondary" sx={{ ml: 1 }}>
+                  {release.date}
+                </Typography>
+              ) : null}
+            </Typography>
+            {release.sections.map((section) => (
+              <Box key={section.title} sx={{ mt: 1 }}>
+                <Typography variant="body2" sx={{ fontWeight: 700 }}>
+                  {section.title}
+                </Typography>
+                <Box component="ul" sx={{ m: 0, pl: 2.5 }}>
+                  {section.items.map((item) => (
+                    <Typography key={item} component="li" variant="body2">
+                      {item}
+                    </Typography>
+                  ))}
+                </Box>
+              </Box>
+            ))}
+          </Box>
+        ))}
+        {whatsNew && whatsNew.migrations.length > 0 ? (
+          <Box>
+            <Typography variant="body2" sx={{ fontWeight: 700 }}>
+              {t("Journal data updates")}
+            </Typography>
+            <Typography variant="caption" color="text.secondary">
+              {t("These database changes ran on your journal during the update.")}
+            </Typography>
+            <Box component="ul" sx={{ m: 0, pl: 2.5 }}>
+              {whatsNew.migrations.map((migration) => (
+                <Typography key={migration.version} component="li" variant="body2">
+                  v{migration.version}: {migration.note}
+                </Typography>
+              ))}
+            </Box>
+          </Box>
+        ) : null}
+      </DialogContent>
+      <DialogActions>
+        <Button variant="contained" onClick={handleClose}>
+          {t("Got it")}
+        </Button>
+      </DialogActions>
+    </Dialog>
+  );
+};
@@ -0,0 +1,187 @@
+import AttachFileIcon from "@mui/icons-material/AttachFile";
+import DeleteOutlineIcon from "@mui/icons-material/DeleteOutline";
+import ExpandMoreIcon from "@mui/icons-material/ExpandMore";
+import TerminalIcon from "@mui/icons-material/Terminal";
+import { Box, Button, Chip, Collapse, IconButton, Stack, TextField, Tooltip, Typography } from "@mui/material";
+import { getCurrentWebview } from "@tauri-apps/api/webview";
+import { revealItemInDir } from "@tauri-apps/plugin-opener";
+import { useEffect, useState } from "react";
+import {
+  useAttachFileToEntry,
+  useAttachmentText,
+  useDeleteAttachment,
+  useEntryAttachments,
+} from "../../hooks/useEntries";
+import { useI18n } from "../../i18n/I18nContext";
+import { useAppNotifications } from "../../notifications/AppNotifications";
+import type { Attachment } from "../../types";
+
+interface EntryAttachmentsProps {
+  entryId: number | undefined;
+}
+
+const formatSize = (bytes: number) => {
+  if (bytes < 1024) return `${bytes} B`;
+  if (bytes < 1024 * 1024) return `${Math.round(bytes / 1024)} KB`;
+  return `${(bytes / 1024 / 1024).toFixed(1)} MB`;
+};
+
+// Captured terminal output stays collapsed to its command until opened; the log is loaded on demand.
+const CommandOutputAttachment = ({ attachment, onDelete }: { attachment: Attachment; onDelete: () => void }) => {
+  const { t } = useI18n();
+  const [expanded, setExpanded] = useState(false);
+  const { data: output, isLoading } = useAttachmentText(attachment.id, expanded);
+
+  return (
+    <Box sx={{ border: 1, borderColor: "divider", borderRadius: 1 }}>
+      <Stack direction="row" spacing={1} alignItems="center" sx={{ px: 1, py: 0.25 }}>
+        <TerminalIcon sx={{ fontSize: 16, color: "text.secondary" }} />
+        <Typography
+          variant="body2"
+          noWrap
+          onClick={() => setExpanded((value) => !value)}
+          sx={{ flex: 1, fontFamily: "monospace", fontSize: "0.8rem", cursor: "pointer" }}
+        >
+          $ {attachment.command}
+        </Typography>
+        <Typography variant="caption" color="text.secondary">
+          {formatSize(attachment.size_bytes)}
+        </Typography>
+        <Tooltip title={expanded ? t("Hide output") : t("Show output")}>
+          <IconButton size="small" onClick={() => setExpanded((value) => !value)}>
+            <ExpandMoreIcon
+              fontSize="small"
+              sx={{ transform: expanded ? "rotate(180deg)" : "none", transition: "transform 0.2s" }}
+            />
+          </IconButton>
+        </Tooltip>
+        <Tooltip title={t("Delete")}>
+          <IconButton size="small" onClick={onDelete}>
+            <DeleteOutlineIcon fontSize="small" />
+          </IconButton>
+        </Tooltip>
+      </Stack>
+      <Collapse in={expanded} unmountOnExit>
+        <Box
+          component="pre"
+          sx={{
+            m: 0,
+            px: 1.5,
+            py: 1,
+            maxHeight: 320,
+            overflow: "auto",
+            borderTop: 1,
+            borderColor: "divider",
+            fontFamily: "monospace",
+            fontSize: "0.75rem",
+            whiteSpace: "pre-wrap",
+            wordBreak: "break-word",
+          }}
+        >
+          {isLoading ? t("Loading...") : output}
+        </Box>
+      </Collapse>
+    </Box>
+  );
+};
+
+// Files are copied into the app data folder, so the originals can be moved or deleted.
+export const EntryAttachments = ({ entryId }: EntryAttachmentsProps) => {
+  const { t } = useI18n();
+  const { notify } = useAppNotifications();
+  const { data: attachments = [] } = useEntryAttachments(entryId);
+  const attachFile = useAttachFileToEntry();
+  const deleteAttachment = useDeleteAttachment();
+  const [path, setPath] = useState("");
+
+  const attachPaths = (paths: string[]) => {
+    if (entryId === undefined) return;
+    paths.forEach((filePath) =>
+      attachFile.mutate(
+        { entryId, path: filePath },
+        { onError: (error) => notify(t("Failed to attach file: {message}", { message: String(error) }), "error") }
+      )
+    );
+  };
+
+  // Dropping files anywhere on the window attaches them to the open entry.
+  useEffect(() => {
+    if (entryId === undefined) return;
+    const unlisten = getCurrentWebview().onDragDropEvent((event) => {
+      if (event.payload.type === "drop" && event.payload.paths.length > 0) {
+        attachPaths(event.payload.paths);
+      }
+    });
+    return () => {
+      unlisten.then((stop) => stop());
+    };
+    // eslint-disable-next-line react-hooks/exhaustive-deps
+  }, [entryId]);
+
+  const handleAttach = () => {
+    const trimmed = path.trim();
+    if (!trimmed) return;
+    attachPaths([trimmed]);
+    setPath("");
+  };
+
+  if (entryId === undefined) {
+    return (
+      <Typography variant="caption" color="text.secondary" sx={{ display: "block", mt: 1.5 }}>
+        {t("Save the entry to add attachments")}
+      </Typography>
+    );
+  }
+
+  const files = attachments.filter((attachment) => attachment.kind !== "command_output");
+  const commandOutputs = attachments.filter((attachment) => attachment.kind === "command_output");
+
+  return (
+    <Box sx={{ mt: 1.5 }}>
+      {commandOutputs.length > 0 && (
+        <Stack spacing={0.75} sx={{ mb: 1 }}>
+          {commandOutputs.map((attachment) => (
+            <CommandOutputAttachment
+              key={attachment.id}
+              attachment={attachment}
+              onDelete={() => deleteAttachment.mutate(attachment.id)}
+            />
+          ))}
+        </Stack>
+      )}
+      <Stack direction="row" spacing={0} sx={{ flexWrap: "wrap", gap: 0.75, mb: files.length > 0 ? 1 : 0 }}>
+        {files.map((attachment) => (
+          <Tooltip key={attachment.id} title={t("Show in folder")}>
+            <Chip
+              size="small"
+              variant="outlined"
+              icon={<AttachFileIcon sx={{ fontSize: 14 }} />}
+              label={`${attachment.file_name} · ${formatSize(attachment.size_bytes)}`}
+              onClick={() => void revealItemInDir(attachment.path)}
+              onDelete={() => deleteAttachment.mutate(attachment.id)}
+              deleteIcon={<DeleteOutlineIcon />}
+            />
+          </Tooltip>
+        ))}
+      </Stack>
+      <Stack direction="row" spacing={1} alignItems="center">
+        <TextField
+          size="small"
+          value={path}
+          placeholder={t("File path or drop files here")}
+          onChange={(event) => setPath(event.target.value)}
+          onKeyDown={(event) => {
+            if (event.key === "Enter") {
+              event.preventDefault();
+              handleAttach();
+            }
+          }}
+          sx={{ flex: 1, "& .MuiInputBase-input": { py: 0.5, fontSize: "0.8rem" } }}
+        />
+        <Button size="small" startIcon={<AttachFileIcon />} onClick={handleAttach} disabled={attachFile.isPending || !path.trim()}>
+          {t("Attach")}
+        </Button>
+      </Stack>
+    </Box>
+  );
+};
@@ -0,0 +1,75 @@
+import DeleteOutlineIcon from "@mui/icons-material/DeleteOutline";
+import { Box, IconButton, Stack, TextField, Tooltip, Typography } from "@mui/material";
+import { format } from "date-fns";
+import { useState } from "react";
+import { useAppendEntryNote, useDeleteEntryNote, useEntryNotes } from "../../hooks/useEntries";
+import { useI18n } from "../../i18n/I18nContext";
+import { useAppNotifications } from "../../notifications/AppNotifications";
+
+interface EntryNoteLogProps {
+  date: string;
+}
+
+// Interstitial journaling: short timestamped notes logged through the day, separate from the standup text.
+export const EntryNoteLog = ({ date }: EntryNoteLogProps) => {
+  const { t } = useI18n();
+  const { notify } = useAppNotifications();
+  const { data: notes = [] } = useEntryNotes(date);
+  const appendNote = useAppendEntryNote();
+  const deleteNote = useDeleteEntryNote();
+  const [text, setText] = useState("");
+
+  const handleAppend = () => {
+    const value = text.trim();
+    if (!value) return;
+    appendNote.mutate(
+      { date, text: value },
+      {
+        onSuccess: () => setText(""),
+        onError: (error) => notify(String(error), "error"),
+      }
+    );
+  };
+
+  return (
+    <Box>
+      {notes.length > 0 ? (
+        <Stack spacing={0.5} sx={{ mb: 1 }}>
+          {notes.map((note) => (
+            <Stack key={note.id} direction="row" spacing={1} alignItems="flex-start">
+              <Typography
+                variant="caption"
+                color="text.secondary"
+                sx={{ fontFamily: "monospace", pt: 0.25, flexShrink: 0 }}
+              >
+                {format(new Date(note.created_at), "HH:mm")}
+              </Typography>
+              <Typography variant="body2" sx={{ flex: 1, whiteSpace: "pre-wrap", wordBreak: "break-word" }}>
+                {note.text}
+              </Typography>
+              <Tooltip title={t("Delete")}>
+                <IconButton size="small" onClick={() => deleteNote.mutate({ id: note.id, date })}>
+                  <DeleteOutlineIcon sx={{ fontSize: 16 }} />
+                </IconButton>
+              </Tooltip>
+            </Stack>
+          ))}
+        </Stack>
+      ) : null}
+      <TextField
+        fullWidth
+        size="small"
+        value={text}
+        onChange={(event) => setText(event.target.value)}
+        onKeyDown={(event) => {
+          if (event.key === "Enter" && !event.shiftKey) {
+            event.preventDefault();
+            handleAppend();
+          }
+        }}
+        disabled={appendNote.isPending}
+        placeholder={t("Log a note and press Enter")}
+      />
+    </Box>
+  );
+};
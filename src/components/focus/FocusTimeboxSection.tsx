@@ -0,0 +1,117 @@
+import CloseRoundedIcon from "@mui/icons-material/CloseRounded";
+import HourglassEmptyRoundedIcon from "@mui/icons-material/HourglassEmptyRounded";
+import { Box, Button, IconButton, Stack, TextField, Typography } from "@mui/material";
+import type { SxProps, Theme } from "@mui/material/styles";
+import { useEffect, useState } from "react";
+import type { Countdown } from "../../types";
+
+const TIMEBOX_PRESETS = [5, 15, 30, 60] as const;
+
+interface FocusTimeboxSectionProps {
+  busy: boolean;
+  countdowns: Countdown[];
+  onCancel: (countdownId: number) => void;
+  onStart: (label: string, minutes: number) => void;
+  surfaceSx: SxProps<Theme>;
+  t: (key: string, vars?: Record<string, string | number>) => string;
+}
+
+const formatRemaining = (endsAt: string, now: number) => {
+  const totalSeconds = Math.max(0, Math.ceil((new Date(endsAt).getTime() - now) / 1000));
+  const minutes = Math.floor(totalSeconds / 60);
+  const seconds = totalSeconds % 60;
+  return `${minutes}:${String(seconds).padStart(2, "0")}`;
+};
+
+export const FocusTimeboxSection = ({
+  busy,
+  countdowns,
+  onCancel,
+  onStart,
+  surfaceSx,
+  t,
+}: FocusTimeboxSectionProps) => {
+  const [label, setLabel] = useState("");
+  const [minutes, setMinutes] = useState<number>(15);
+  const [now, setNow] = useState(() => Date.now());
+
+  useEffect(() => {
+    if (countdowns.length === 0) {
+      return;
+    }
+
+    const interval = setInterval(() => setNow(Date.now()), 1000);
+    return () => clearInterval(interval);
+  }, [countdowns.length]);
+
+  const handleStart = () => {
+    onStart(label.trim(), minutes);
+    setLabel("");
+  };
+
+  return (
+    <Box sx={surfaceSx}>
+      <Typography variant="subtitle1" sx={{ fontWeight: 700, mb: 2, display: "flex", alignItems: "center", gap: 1 }}>
+        <HourglassEmptyRoundedIcon fontSize="small" sx={{ opacity: 0.6 }} />
+        {t("Timebox")}
+      </Typography>
+      <Stack direction="row" spacing={1} alignItems="center">
+        <TextField
+          size="small"
+          value={label}
+          onChange={(e) => setLabel(e.target.value)}
+          placeholder={t("e.g. Inbox triage")}
+          fullWidth
+        />
+        <TextField
+          select
+          size="small"
+          value={String(minutes)}
+          onChange={(e) => setMinutes(Number(e.target.value))}
+          SelectProps={{ native: true }}
+          sx={{ minWidth: 88 }}
+        >
+          {TIMEBOX_PRESETS.map((preset) => (
+            <option key={preset} value={preset}>
+              {preset}m
+            </option>
+          ))}
+        </TextField>
+        <Button variant="contained" size="small" onClick={handleStart} disabled={busy}>
+          {t("Start")}
+        </Button>
+      </Stack>
+      {countdowns.length > 0 && (
+        <Stack spacing={0} sx={{ mt: 1.5, gap: 0.75 }}>
+          {countdowns.map((countdown) => (
+            <Box
+              key={countdown.id}
+              sx={{
+                px: 1.5,
+                py: 1,
+                borderRadius: 2,
+                border: "1px solid",
+                borderColor: "divider",
+                display: "flex",
+                alignItems: "center",
+                justifyContent: "space-between",
+              }}
+            >
+              <Typography variant="body2" noWrap sx={{ minWidth: 0, mr: 1 }}>
+                {countdown.label}
+              </Typography>
+              <Stack direction="row" spacing={0.5} alignItems="center" sx={{ flexShrink: 0 }}>
+                <Typography variant="body2" sx={{ fontVariantNumeric: "tabular-nums", fontWeight: 600 }}>
+                  {formatRemaining(countdown.ends_at, now)}
+                </Typography>
+                <IconButton size="small" aria-label={t("Cancel")} onClick={() => onCancel(countdown.id)}>
+                  <CloseRoundedIcon fontSize="small" />
+                </IconButton>
+              </Stack>
+            </Box>
+          ))}
+        </Stack>
+      )}
+    </Box>
+  );
+};
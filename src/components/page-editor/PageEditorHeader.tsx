@@ -1,90 +1,150 @@
 import SaveIcon from "@mui/icons-material/Save";
-import { Box, Button, Chip, InputBase, Stack } from "@mui/material";
+import WallpaperIcon from "@mui/icons-material/Wallpaper";
+import { Box, Button, Chip, IconButton, InputBase, Stack, TextField, Tooltip } from "@mui/material";
+import { useState } from "react";
+import { useI18n } from "../../i18n/I18nContext";
 
 interface PageEditorHeaderProps {
+  cover: string;
   draftRestored: boolean;
+  icon: string;
   isCompactDesktop: boolean;
   onSave: () => void;
   saving: boolean;
+  setCover: (value: string) => void;
+  setIcon: (value: string) => void;
   setTitle: (value: string) => void;
   title: string;
 }
 
+// Covers are a `#rrggbb` color or an image URL; the backend drops anything else on save.
+const coverBackground = (cover: string) =>
+  cover.startsWith("#") ? cover : `center / cover no-repeat url("${cover.replace(/"/g, "%22")}")`;
+
 export const PageEditorHeader = ({
+  cover,
   draftRestored,
+  icon,
   isCompactDesktop,
   onSave,
   saving,
+  setCover,
+  setIcon,
   setTitle,
   title,
-}: PageEditorHeaderProps) => (
-  <Box
-    sx={{
-      mb: 1.6,
-      p: { xs: 1.2, md: isCompactDesktop ? 1.45 : 1.8 },
-      borderRadius: isCompactDesktop ? 3.2 : 4,
-      border: "1px solid",
-      borderColor: "divider",
-      backgroundColor: "background.paper",
-    }}
-  >
+}: PageEditorHeaderProps) => {
+  const { t } = useI18n();
+  const [coverFieldOpen, setCoverFieldOpen] = useState(false);
+
+  return (
     <Box
-      display="flex"
-      justifyContent="space-between"
-      alignItems={{ xs: "flex-start", lg: "center" }}
-      gap={2}
-      flexDirection={{ xs: "column", lg: "row" }}
+      sx={{
+        mb: 1.6,
+        p: { xs: 1.2, md: isCompactDesktop ? 1.45 : 1.8 },
+        borderRadius: isCompactDesktop ? 3.2 : 4,
+        border: "1px solid",
+        borderColor: "divider",
+        backgroundColor: "background.paper",
+      }}
     >
-      <Box sx={{ minWidth: 0, flex: 1, width: "100%" }}>
-        <InputBase
-          value={title}
-          onChange={(e) => setTitle(e.target.value)}
-          placeholder="Untitled"
-          sx={{
-            fontSize: { xs: "2rem", sm: "2.4rem", xl: "3rem" },
-            lineHeight: 1.05,
-            fontWeight: 800,
-            letterSpacing: "-0.04em",
-            color: "text.primary",
-            flex: 1,
-            minWidth: 0,
-            width: "100%",
-            mb: 0.5,
-            "& input::placeholder": {
-              color: "text.secondary",
-              opacity: 1,
-            },
-          }}
+      {cover.trim() ? (
+        <Box
+          aria-hidden
+          sx={{ height: { xs: 96, md: 140 }, mb: 1.4, borderRadius: 3, background: coverBackground(cover.trim()) }}
         />
-      </Box>
-
-      <Stack
-        direction="row"
-        spacing={1}
-        alignItems="center"
-        sx={{
-          flexWrap: "wrap",
-          justifyContent: { xs: "flex-start", lg: "flex-end" },
-          width: { xs: "100%", lg: "auto" },
-        }}
+      ) : null}
+      {coverFieldOpen ? (
+        <TextField
+          size="small"
+          fullWidth
+          autoFocus
+          label={t("Cover")}
+          placeholder="#4f46e5 / https://…"
+          value={cover}
+          onChange={(event) => setCover(event.target.value)}
+          onBlur={() => setCoverFieldOpen(false)}
+          helperText={t("A color like #4f46e5 or an image URL. Leave empty to remove the cover.")}
+          sx={{ mb: 1.4 }}
+        />
+      ) : null}
+      <Box
+        display="flex"
+        justifyContent="space-between"
+        alignItems={{ xs: "flex-start", lg: "center" }}
+        gap={2}
+        flexDirection={{ xs: "column", lg: "row" }}
       >
-        {draftRestored ? <Chip label="Draft restored" size="small" color="info" variant="outlined" /> : null}
-        <Button
-          variant="contained"
-          color="primary"
-          startIcon={<SaveIcon />}
-          onClick={onSave}
-          disabled={saving}
+        <Box sx={{ minWidth: 0, flex: 1, width: "100%", display: "flex", alignItems: "center", gap: 1 }}>
+          <Tooltip title={t("Page icon: paste or type one emoji")}>
+            <InputBase
+              value={icon}
+              onChange={(e) => setIcon(e.target.value.trim())}
+              placeholder="＋"
+              inputProps={{ "aria-label": t("Page icon"), maxLength: 16 }}
+              sx={{
+                width: "1.6em",
+                flexShrink: 0,
+                fontSize: { xs: "2rem", sm: "2.4rem", xl: "3rem" },
+                "& input": { textAlign: "center", p: 0 },
+                "& input::placeholder": { color: "text.disabled", opacity: 1 },
+              }}
+            />
+          </Tooltip>
+          <InputBase
+            value={title}
+            onChange={(e) => setTitle(e.target.value)}
+            placeholder="Untitled"
+            sx={{
+              fontSize: { xs: "2rem", sm: "2.4rem", xl: "3rem" },
+              lineHeight: 1.05,
+              fontWeight: 800,
+              letterSpacing: "-0.04em",
+              color: "text.primary",
+              flex: 1,
+              minWidth: 0,
+              width: "100%",
+              mb: 0.5,
+              "& input::placeholder": {
+                color: "text.secondary",
+                opacity: 1,
+              },
+            }}
+          />
+        </Box>
+
+        <Stack
+          direction="row"
+          spacing={1}
+          alignItems="center"
           sx={{
-            px: isCompactDesktop ? 2.1 : 2.6,
-            minWidth: isCompactDesktop ? 132 : 150,
-            minHeight: isCompactDesktop ? 40 : 42,
-            borderRadius: 2.8,
+            flexWrap: "wrap",
+            justifyContent: { xs: "flex-start", lg: "flex-end" },
+            width: { xs: "100%", lg: "auto" },
           }}
         >
-          {saving ? "Saving..." : "Save"}
-        </Button>
-      </Stack>
+          {draftRestored ? <Chip label="Draft restored" size="small" color="info" variant="outlined" /> : null}
+          <Tooltip title={t("Cover")}>
+            <IconButton aria-label={t("Cover")} onClick={() => setCoverFieldOpen((open) => !open)}>
+              <WallpaperIcon fontSize="small" />
+            </IconButton>
+          </Tooltip>
+          <Button
+            variant="contained"
+            color="primary"
+            startIcon={<SaveIcon />}
+            onClick={onSave}
+            disabled={saving}
+            sx={{
+              px: isCompactDesktop ? 2.1 : 2.6,
+              minWidth: isCompactDesktop ? 132 : 150,
+              minHeight: isCompactDesktop ? 40 : 42,
+              borderRadius: 2.8,
+            }}
+          >
+            {saving ? "Saving..." : "Save"}
+          </Button>
+        </Stack>
+      </Box>
     </Box>
-  </Box>
-);
+  );
+};
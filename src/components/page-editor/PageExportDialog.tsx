@@ -0,0 +1,79 @@
+import { Box, Button, Dialog, DialogActions, DialogContent, DialogTitle, TextField, Typography } from "@mui/material";
+import { useState } from "react";
+import { useExportPage } from "../../hooks/usePages";
+import { useI18n } from "../../i18n/I18nContext";
+import { useAppNotifications } from "../../notifications/AppNotifications";
+import { PageExportFormat } from "../../types";
+
+interface PageExportDialogProps {
+  pageId: number;
+  open: boolean;
+  onClose: () => void;
+}
+
+export const PageExportDialog = ({ pageId, open, onClose }: PageExportDialogProps) => {
+  const { t } = useI18n();
+  const { notify } = useAppNotifications();
+  const exportPage = useExportPage();
+  const [format, setFormat] = useState<PageExportFormat>("markdown");
+  const [path, setPath] = useState("");
+
+  const handleExport = () => {
+    exportPage.mutate(
+      { id: pageId, format, path: path.trim() },
+      {
+        onSuccess: (file) => {
+          onClose();
+          notify(t("Page exported to {path}", { path: file }), "success");
+        },
+        onError: (error) => notify(String(error), "error"),
+      }
+    );
+  };
+
+  return (
+    <Dialog open={open} onClose={onClose} fullWidth maxWidth="sm">
+      <DialogTitle>{t("Export page")}</DialogTitle>
+      <DialogContent dividers>
+        <Typography variant="body2" color="text.secondary" sx={{ mb: 2 }}>
+          {format === "html"
+            ? t("A single HTML file with the images embedded, readable in any browser.")
+            : t("A Markdown file; images are copied into an assets folder next to it.")}
+        </Typography>
+        <Box sx={{ display: "flex", flexWrap: "wrap", gap: 1.5 }}>
+          <TextField
+            select
+            size="small"
+            label={t("Format")}
+            value={format}
+            onChange={(event) => setFormat(event.target.value as PageExportFormat)}
+            SelectProps={{ native: true }}
+            InputLabelProps={{ shrink: true }}
+            sx={{ width: 140 }}
+          >
+            <option value="markdown">Markdown</option>
+            <option value="html">HTML</option>
+          </TextField>
+          <TextField
+            size="small"
+            label={t("File or folder")}
+            placeholder="/Users/me/Documents/notes"
+            value={path}
+            onChange={(event) => setPath(event.target.value)}
+            sx={{ flex: 1, minWidth: 220 }}
+          />
+        </Box>
+      </DialogContent>
+      <DialogActions>
+        <Button onClick={onClose}>{t("Cancel")}</Button>
+        <Button
+          variant="contained"
+          onClick={handleExport}
+          disabled={exportPage.isPending || path.trim().length === 0}
+        >
+          {t("Export")}
+        </Button>
+      </DialogActions>
+    </Dialog>
+  );
+};
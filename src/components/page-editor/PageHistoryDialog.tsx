@@ -0,0 +1,98 @@
+import {
+  Box,
+  Button,
+  Dialog,
+  DialogActions,
+  DialogContent,
+  DialogTitle,
+  List,
+  ListItemButton,
+  ListItemText,
+  Typography,
+} from "@mui/material";
+import { format } from "date-fns";
+import { useState } from "react";
+import { usePageRevisions, useRestorePageRevision } from "../../hooks/usePages";
+import { useI18n } from "../../i18n/I18nContext";
+import { useAppNotifications } from "../../notifications/AppNotifications";
+
+interface PageHistoryDialogProps {
+  pageId: number;
+  open: boolean;
+  onClose: () => void;
+  /** Called after a restore so the editor can drop its local draft. */
+  onRestored: () => void;
+}
+
+export const PageHistoryDialog = ({ pageId, open, onClose, onRestored }: PageHistoryDialogProps) => {
+  const { t } = useI18n();
+  const { notify } = useAppNotifications();
+  const { data: revisions = [], isLoading } = usePageRevisions(open ? pageId : null);
+  const restoreRevision = useRestorePageRevision();
+  const [selectedId, setSelectedId] = useState<number | null>(null);
+  const selected = revisions.find((revision) => revision.id === selectedId) ?? revisions[0] ?? null;
+
+  const handleRestore = () => {
+    if (!selected) return;
+    restoreRevision.mutate(selected.id, {
+      onSuccess: () => {
+        onRestored();
+        onClose();
+        notify(t("Page restored."), "success");
+      },
+      onError: (error) => notify(String(error), "error"),
+    });
+  };
+
+  return (
+    <Dialog open={open} onClose={onClose} fullWidth maxWidth="md">
+      <DialogTitle>{t("Page history")}</DialogTitle>
+      <DialogContent dividers>
+        {revisions.length === 0 ? (
+          <Typography variant="body2" color="text.secondary">
+            {isLoading ? t("Loading...") : t("No earlier versions yet. A version is kept each time you save changes.")}
+          </Typography>
+        ) : (
+          <Box sx={{ display: "grid", gridTemplateColumns: { xs: "1fr", md: "220px minmax(0, 1fr)" }, gap: 2 }}>
+            <List dense disablePadding sx={{ maxHeight: 420, overflowY: "auto" }}>
+              {revisions.map((revision) => (
+                <ListItemButton
+                  key={revision.id}
+                  selected={revision.id === selected?.id}
+                  onClick={() => setSelectedId(revision.id)}
+                  sx={{ borderRadius: 1.5 }}
+                >
+                  <ListItemText
+                    primary={format(new Date(revision.created_at), "MMM d, HH:mm")}
+                    secondary={revision.title || t("Untitled")}
+                    secondaryTypographyProps={{ noWrap: true }}
+                  />
+                </ListItemButton>
+              ))}
+            </List>
+            {selected ? (
+              <Box sx={{ minWidth: 0 }}>
+                <Typography variant="subtitle2" sx={{ mb: 1 }}>
+                  {selected.title || t("Untitled")}
+                </Typography>
+                <Typography
+                  component="pre"
+                  variant="body2"
+                  sx={{ m: 0, maxHeight: 390, overflow: "auto", whiteSpace: "pre-wrap", fontFamily: "inherit" }}
+                >
+                  {selected.content}
+                </Typography>
+              </Box>
+            ) : null}
+          </Box>
+        )}
+      </DialogContent>
+      <DialogActions>
+        <Button onClick={onClose}>{t("Close")}</Button>
+        <Button variant="contained" onClick={handleRestore} disabled={!selected || restoreRevision.isPending}>
+          {t("Restore this version")}
+        </Button>
+      </DialogActions>
+    </Dialog>
+  );
+};
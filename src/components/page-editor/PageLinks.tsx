@@ -0,0 +1,80 @@
+import { Box, Chip, Typography } from "@mui/material";
+import { useMemo } from "react";
+import { useBacklinks, usePages } from "../../hooks/usePages";
+import { useI18n } from "../../i18n/I18nContext";
+
+interface PageLinksProps {
+  pageId: number | null;
+  content: string;
+  onOpenPage?: (id: number) => void;
+}
+
+// Mirrors `extract_wiki_links` in src-tauri/src/commands/pages.rs so unsaved links show up too.
+const extractWikiLinks = (content: string) => {
+  const links: string[] = [];
+  for (const match of content.matchAll(/\[\[([^\]\n]+?)\]\]/g)) {
+    const inner = match[1].split("[[").pop() ?? "";
+    const title = inner.split("|")[0].trim();
+    if (title && !links.some((link) => link.toLowerCase() === title.toLowerCase())) {
+      links.push(title);
+    }
+  }
+  return links;
+};
+
+export const PageLinks = ({ pageId, content, onOpenPage }: PageLinksProps) => {
+  const { t } = useI18n();
+  const { data: pages = [] } = usePages();
+  const { data: backlinks = [] } = useBacklinks(pageId);
+
+  const links = useMemo(() => {
+    const byTitle = new Map(pages.map((page) => [page.title.trim().toLowerCase(), page]));
+    return extractWikiLinks(content).map((title) => ({ title, page: byTitle.get(title.toLowerCase()) ?? null }));
+  }, [content, pages]);
+
+  if (links.length === 0 && backlinks.length === 0) return null;
+
+  return (
+    <Box sx={{ mt: 2, display: "grid", gap: 1.2 }}>
+      {links.length > 0 ? (
+        <Box>
+          <Typography variant="caption" color="text.secondary" sx={{ fontWeight: 600, textTransform: "uppercase" }}>
+            {t("Links")}
+          </Typography>
+          <Box sx={{ display: "flex", flexWrap: "wrap", gap: 0.75, mt: 0.5 }}>
+            {links.map(({ title, page }) => (
+              <Chip
+                key={title}
+                size="small"
+                label={title}
+                variant={page ? "filled" : "outlined"}
+                disabled={!page || !onOpenPage}
+                title={page ? undefined : t("No page with this title yet")}
+                onClick={page && onOpenPage ? () => onOpenPage(page.id) : undefined}
+              />
+            ))}
+          </Box>
+        </Box>
+      ) : null}
+      {backlinks.length > 0 ? (
+        <Box>
+          <Typography variant="caption" color="text.secondary" sx={{ fontWeight: 600, textTransform: "uppercase" }}>
+            {t("Linked from")}
+          </Typography>
+          <Box sx={{ display: "flex", flexWrap: "wrap", gap: 0.75, mt: 0.5 }}>
+            {backlinks.map((page) => (
+              <Chip
+                key={page.id}
+                size="small"
+                color="primary"
+                variant="outlined"
+                label={page.title || t("Untitled")}
+                onClick={onOpenPage ? () => onOpenPage(page.id) : undefined}
+              />
+            ))}
+          </Box>
+        </Box>
+      ) : null}
+    </Box>
+  );
+};
@@ -0,0 +1,148 @@
+import LockOutlinedIcon from "@mui/icons-material/LockOutlined";
+import { Box, Button, Dialog, DialogActions, DialogContent, DialogTitle, TextField, Typography } from "@mui/material";
+import { FormEvent, useState } from "react";
+import { useDisablePageLock, useEnablePageLock, useUnlockPage } from "../../hooks/usePages";
+import { useI18n } from "../../i18n/I18nContext";
+import { useAppNotifications } from "../../notifications/AppNotifications";
+import type { Page } from "../../types";
+
+/** Content of a locked page that was not unlocked this session. */
+export const isPageContentLocked = (page: Page | null | undefined) =>
+  Boolean(page?.locked && page.content.startsWith("lock:v1:"));
+
+// Shown instead of the editor until the page's passphrase is entered.
+export const PageLockScreen = ({ page }: { page: Page }) => {
+  const { t } = useI18n();
+  const unlockPage = useUnlockPage();
+  const [passphrase, setPassphrase] = useState("");
+  const [error, setError] = useState("");
+
+  const handleSubmit = (event: FormEvent) => {
+    event.preventDefault();
+    unlockPage.mutate(
+      { id: page.id, passphrase },
+      {
+        onSuccess: () => setPassphrase(""),
+        onError: (unlockError) => setError(String(unlockError)),
+      }
+    );
+  };
+
+  return (
+    <Box
+      component="form"
+      onSubmit={handleSubmit}
+      sx={{
+        maxWidth: 420,
+        mx: "auto",
+        mt: 8,
+        display: "flex",
+        flexDirection: "column",
+        alignItems: "center",
+        gap: 1.5,
+      }}
+    >
+      <LockOutlinedIcon color="action" sx={{ fontSize: 40 }} />
+      <Typography variant="h6">{page.title || t("Untitled")}</Typography>
+      <Typography variant="body2" color="text.secondary" textAlign="center">
+        {t("This page is locked. Enter its passphrase to read or edit it.")}
+      </Typography>
+      <TextField
+        type="password"
+        size="small"
+        fullWidth
+        autoFocus
+        label={t("Passphrase")}
+        value={passphrase}
+        onChange={(event) => {
+          setPassphrase(event.target.value);
+          setError("");
+        }}
+        error={Boolean(error)}
+        helperText={error || " "}
+      />
+      <Button type="submit" variant="contained" disabled={!passphrase || unlockPage.isPending}>
+        {t("Unlock page")}
+      </Button>
+    </Box>
+  );
+};
+
+interface PageLockDialogProps {
+  page: Page;
+  /** `enable` locks the page, `disable` removes its lock for good. */
+  mode: "enable" | "disable";
+  open: boolean;
+  onClose: () => void;
+  onChanged?: () => void;
+}
+
+export const PageLockDialog = ({ page, mode, open, onClose, onChanged }: PageLockDialogProps) => {
+  const { t } = useI18n();
+  const { notify } = useAppNotifications();
+  const enableLock = useEnablePageLock();
+  const disableLock = useDisablePageLock();
+  const [passphrase, setPassphrase] = useState("");
+  const [confirmation, setConfirmation] = useState("");
+  const pending = enableLock.isPending || disableLock.isPending;
+  const canSubmit = mode === "enable" ? passphrase.length >= 8 && passphrase === confirmation : passphrase.length > 0;
+
+  const close = () => {
+    setPassphrase("");
+    setConfirmation("");
+    onClose();
+  };
+  const handleSubmit = () => {
+    const options = {
+      onSuccess: () => {
+        notify(mode === "enable" ? t("Page locked.") : t("Page lock removed."), "success");
+        onChanged?.();
+        close();
+      },
+      onError: (error: unknown) => notify(String(error), "error"),
+    };
+    if (mode === "enable") {
+      enableLock.mutate({ id: page.id, passphrase }, options);
+    } else {
+      disableLock.mutate({ id: page.id, passphrase }, options);
+    }
+  };
+
+  return (
+    <Dialog open={open} onClose={close} fullWidth maxWidth="xs">
+      <DialogTitle>{mode === "enable" ? t("Lock page") : t("Remove page lock")}</DialogTitle>
+      <DialogContent dividers sx={{ display: "flex", flexDirection: "column", gap: 1.5 }}>
+        <Typography variant="body2" color="text.secondary">
+          {mode === "enable"
+            ? t("The page and its history are encrypted with this passphrase. It cannot be recovered if you forget it.")
+            : t("The page and its history are stored unlocked again.")}
+        </Typography>
+        <TextField
+          type="password"
+          size="small"
+          autoFocus
+          label={t("Passphrase")}
+          value={passphrase}
+          onChange={(event) => setPassphrase(event.target.value)}
+          helperText={mode === "enable" ? t("At least 8 characters") : " "}
+        />
+        {mode === "enable" ? (
+          <TextField
+            type="password"
+            size="small"
+            label={t("Confirm passphrase")}
+            value={confirmation}
+            onChange={(event) => setConfirmation(event.target.value)}
+            error={confirmation.length > 0 && confirmation !== passphrase}
+          />
+        ) : null}
+      </DialogContent>
+      <DialogActions>
+        <Button onClick={close}>{t("Cancel")}</Button>
+        <Button variant="contained" onClick={handleSubmit} disabled={!canSubmit || pending}>
+          {mode === "enable" ? t("Lock page") : t("Remove lock")}
+        </Button>
+      </DialogActions>
+    </Dialog>
+  );
+};
@@ -0,0 +1,59 @@
+import { Box, Chip, Typography } from "@mui/material";
+import { useState } from "react";
+import { usePageProperties, usePagesByProperty } from "../../hooks/usePages";
+import { useI18n } from "../../i18n/I18nContext";
+import type { PageProperty } from "../../types";
+
+interface PagePropertiesProps {
+  pageId: number | null;
+  onOpenPage?: (id: number) => void;
+}
+
+// Frontmatter `key: value` pairs as saved; selecting one lists the other pages sharing it.
+export const PageProperties = ({ pageId, onOpenPage }: PagePropertiesProps) => {
+  const { t } = useI18n();
+  const { data: properties = [] } = usePageProperties(pageId);
+  const [selected, setSelected] = useState<PageProperty | null>(null);
+  const active = selected && properties.some((p) => p.key === selected.key && p.value === selected.value) ? selected : null;
+  const { data: matchingPages = [] } = usePagesByProperty(active?.key ?? "", active?.value);
+  const otherPages = matchingPages.filter((page) => page.id !== pageId);
+
+  if (properties.length === 0) return null;
+
+  return (
+    <Box sx={{ mb: 1.2 }}>
+      <Box sx={{ display: "flex", flexWrap: "wrap", gap: 0.75 }}>
+        {properties.map((property) => {
+          const isActive = active?.key === property.key && active.value === property.value;
+          return (
+            <Chip
+              key={`${property.key}:${property.value}`}
+              size="small"
+              variant={isActive ? "filled" : "outlined"}
+              color={isActive ? "primary" : "default"}
+              label={`${property.key}: ${property.value}`}
+              onClick={() => setSelected(isActive ? null : property)}
+            />
+          );
+        })}
+      </Box>
+      {active ? (
+        <Box sx={{ display: "flex", flexWrap: "wrap", alignItems: "center", gap: 0.75, mt: 0.75 }}>
+          <Typography variant="caption" color="text.secondary">
+            {otherPages.length > 0
+              ? t("Also {property}:", { property: `${active.key}: ${active.value}` })
+              : t("No other pages with {property}", { property: `${active.key}: ${active.value}` })}
+          </Typography>
+          {otherPages.map((page) => (
+            <Chip
+              key={page.id}
+              size="small"
+              label={page.icon ? `${page.icon} ${page.title || t("Untitled")}` : page.title || t("Untitled")}
+              onClick={onOpenPage ? () => onOpenPage(page.id) : undefined}
+            />
+          ))}
+        </Box>
+      ) : null}
+    </Box>
+  );
+};
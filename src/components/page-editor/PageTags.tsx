@@ -0,0 +1,68 @@
+import { Box, Chip, TextField } from "@mui/material";
+import { useState } from "react";
+import { useAddPageTag, usePageTags, useRemovePageTag } from "../../hooks/usePages";
+import { useI18n } from "../../i18n/I18nContext";
+import { useAppNotifications } from "../../notifications/AppNotifications";
+
+interface PageTagsProps {
+  pageId: number | null;
+}
+
+export const PageTags = ({ pageId }: PageTagsProps) => {
+  const { t } = useI18n();
+  const { notify } = useAppNotifications();
+  const { data: pageTags = [] } = usePageTags();
+  const addTag = useAddPageTag();
+  const removeTag = useRemovePageTag();
+  const [tagInput, setTagInput] = useState("");
+
+  const currentTags = pageId === null ? [] : pageTags.filter((tag) => tag.page_id === pageId).map((tag) => tag.tag);
+  const knownTags = Array.from(new Set(pageTags.map((tag) => tag.tag))).filter(
+    (tag) => !currentTags.some((current) => current.toLowerCase() === tag.toLowerCase())
+  );
+
+  const handleAddTag = () => {
+    const tag = tagInput.trim();
+    if (pageId === null || !tag) return;
+    addTag.mutate(
+      { pageId, tag },
+      {
+        onSuccess: () => setTagInput(""),
+        onError: (error) => notify(String(error), "error"),
+      }
+    );
+  };
+
+  return (
+    <Box sx={{ display: "flex", flexWrap: "wrap", alignItems: "center", gap: 0.75, mb: 1.2 }}>
+      {currentTags.map((tag) => (
+        <Chip
+          key={tag}
+          label={`#${tag}`}
+          size="small"
+          onDelete={pageId !== null ? () => removeTag.mutate({ pageId, tag }) : undefined}
+        />
+      ))}
+      <TextField
+        size="small"
+        value={tagInput}
+        disabled={pageId === null}
+        placeholder={pageId !== null ? t("Add tag") : t("Save the page to add tags")}
+        onChange={(event) => setTagInput(event.target.value)}
+        onKeyDown={(event) => {
+          if (event.key === "Enter") {
+            event.preventDefault();
+            handleAddTag();
+          }
+        }}
+        inputProps={{ list: "page-tag-suggestions" }}
+        sx={{ minWidth: 160, "& .MuiInputBase-input": { py: 0.5, fontSize: "0.8rem" } }}
+      />
+      <datalist id="page-tag-suggestions">
+        {knownTags.map((tag) => (
+          <option key={tag} value={tag} />
+        ))}
+      </datalist>
+    </Box>
+  );
+};
@@ -0,0 +1,484 @@
+import AddIcon from "@mui/icons-material/Add";
+import ArchiveOutlinedIcon from "@mui/icons-material/ArchiveOutlined";
+import ChevronRightIcon from "@mui/icons-material/ChevronRight";
+import DriveFileMoveOutlinedIcon from "@mui/icons-material/DriveFileMoveOutlined";
+import ExpandMoreIcon from "@mui/icons-material/ExpandMore";
+import HistoryIcon from "@mui/icons-material/History";
+import OpenInNewIcon from "@mui/icons-material/OpenInNew";
+import PushPinIcon from "@mui/icons-material/PushPin";
+import PushPinOutlinedIcon from "@mui/icons-material/PushPinOutlined";
+import StarIcon from "@mui/icons-material/Star";
+import StarBorderIcon from "@mui/icons-material/StarBorder";
+import UnarchiveOutlinedIcon from "@mui/icons-material/UnarchiveOutlined";
+import { Box, Button, Chip, IconButton, Menu, MenuItem, Tooltip, Typography } from "@mui/material";
+import {
+  DndContext,
+  DragEndEvent,
+  PointerSensor,
+  useDraggable,
+  useDroppable,
+  useSensor,
+  useSensors,
+} from "@dnd-kit/core";
+import { ReactNode, useMemo, useState } from "react";
+import {
+  useArchivedPages,
+  useMovePage,
+  usePageTags,
+  usePages,
+  usePagesByTag,
+  useRecentPages,
+  useReorderPages,
+  useSetPageArchived,
+  useSetPageFavorite,
+  useSetPagePinned,
+} from "../../hooks/usePages";
+import { useI18n } from "../../i18n/I18nContext";
+import { useAppNotifications } from "../../notifications/AppNotifications";
+import type { Page } from "../../types";
+
+interface PageTreeProps {
+  selectedPageId: number | null;
+  onSelectPage: (id: number) => void;
+  onCreatePage: (parentId: number | null) => void;
+  onOpenInWindow: (id: number) => void;
+}
+
+/** Title prefixed with the page's emoji icon, if it has one. */
+const withIcon = (page: Page, title: string) => (page.icon ? `${page.icon} ${title}` : title);
+
+const byTitle = (a: Page, b: Page) =>
+  (a.title || "").localeCompare(b.title || "", undefined, { sensitivity: "base" }) || a.id - b.id;
+// Pinned pages stay at the top of their level, then the drag-and-drop order; pages that
+// were never reordered follow by title.
+const bySiblingOrder = (a: Page, b: Page) =>
+  Number(b.pinned) - Number(a.pinned) ||
+  (a.sort_index ?? Number.MAX_SAFE_INTEGER) - (b.sort_index ?? Number.MAX_SAFE_INTEGER) ||
+  byTitle(a, b);
+
+const PAGE_DRAG_PREFIX = "page-";
+const parseDraggedPageId = (id: string | number) =>
+  typeof id === "string" && id.startsWith(PAGE_DRAG_PREFIX) ? Number(id.slice(PAGE_DRAG_PREFIX.length)) : null;
+
+// A tree row that can be dragged onto a sibling to take its place.
+const DraggablePageRow = ({ pageId, children }: { pageId: number; children: ReactNode }) => {
+  const id = `${PAGE_DRAG_PREFIX}${pageId}`;
+  const { attributes, listeners, setNodeRef: setDragRef, transform, isDragging } = useDraggable({ id });
+  const { isOver, setNodeRef: setDropRef } = useDroppable({ id });
+
+  return (
+    <Box
+      ref={(node: HTMLElement | null) => {
+        setDragRef(node);
+        setDropRef(node);
+      }}
+      {...attributes}
+      {...listeners}
+      sx={{
+        transform: transform ? `translate3d(0, ${Math.round(transform.y)}px, 0)` : undefined,
+        zIndex: isDragging ? 2 : "auto",
+        position: "relative",
+        touchAction: "none",
+        borderTop: "2px solid",
+        borderColor: isOver && !isDragging ? "primary.main" : "transparent",
+      }}
+    >
+      {children}
+    </Box>
+  );
+};
+
+// Folder-style page navigation built from each page's parent_id.
+export const PageTree = ({ selectedPageId, onSelectPage, onCreatePage, onOpenInWindow }: PageTreeProps) => {
+  const { t } = useI18n();
+  const { notify } = useAppNotifications();
+  const { data: pages = [] } = usePages();
+  const { data: pageTags = [] } = usePageTags();
+  const [tagFilter, setTagFilter] = useState("");
+  const movePage = useMovePage();
+  const setPinned = useSetPagePinned();
+  const setFavorite = useSetPageFavorite();
+  const { data: archivedPages = [] } = useArchivedPages();
+  const setArchived = useSetPageArchived();
+  const [archiveOpen, setArchiveOpen] = useState(false);
+  const reorderPages = useReorderPages();
+  const dndSensors = useSensors(useSensor(PointerSensor, { activationConstraint: { distance: 6 } }));
+  const [collapsed, setCollapsed] = useState<Set<number>>(() => new Set());
+  const [moveMenu, setMoveMenu] = useState<{ anchor: HTMLElement; page: Page } | null>(null);
+
+  const childrenByParent = useMemo(() => {
+    const ids = new Set(pages.map((page) => page.id));
+    const map = new Map<number | null, Page[]>();
+    pages.forEach((page) => {
+      // A dangling parent would hide the page entirely, so show it at the top level.
+      const parent = page.parent_id !== null && ids.has(page.parent_id) ? page.parent_id : null;
+      map.set(parent, [...(map.get(parent) ?? []), page]);
+    });
+    map.forEach((children) => children.sort(bySiblingOrder));
+    return map;
+  }, [pages]);
+  const favorites = useMemo(() => pages.filter((page) => page.favorite).sort(byTitle), [pages]);
+  const { data: recentPages = [] } = useRecentPages();
+
+  const knownTags = useMemo(() => {
+    const seen = new Map<string, string>();
+    pageTags.forEach((tag) => {
+      if (!seen.has(tag.tag.toLowerCase())) seen.set(tag.tag.toLowerCase(), tag.tag);
+    });
+    return Array.from(seen.values()).sort((a, b) => a.localeCompare(b, undefined, { sensitivity: "base" }));
+  }, [pageTags]);
+  // Falls back to the full tree once the filtered tag is removed from its last page.
+  const activeTag = knownTags.find((tag) => tag.toLowerCase() === tagFilter.toLowerCase()) ?? "";
+  const { data: taggedPages = [] } = usePagesByTag(activeTag);
+
+  const descendantsOf = (id: number) => {
+    const found = new Set<number>([id]);
+    const stack = [id];
+    while (stack.length > 0) {
+      const current = stack.pop() as number;
+      (childrenByParent.get(current) ?? []).forEach((child) => {
+        if (!found.has(child.id)) {
+          found.add(child.id);
+          stack.push(child.id);
+        }
+      });
+    }
+    return found;
+  };
+
+  const toggle = (id: number) => {
+    setCollapsed((current) => {
+      const next = new Set(current);
+      if (next.has(id)) {
+        next.delete(id);
+      } else {
+        next.add(id);
+      }
+      return next;
+    });
+  };
+
+  const handleMove = (newParent: number | null) => {
+    if (!moveMenu) return;
+    const { page } = moveMenu;
+    setMoveMenu(null);
+    if (page.parent_id === newParent) return;
+    movePage.mutate(
+      { id: page.id, newParent },
+      {
+        onSuccess: () => {
+          if (newParent !== null) {
+            setCollapsed((current) => {
+              const next = new Set(current);
+              next.delete(newParent);
+              return next;
+            });
+          }
+        },
+        onError: (error) => notify(String(error), "error"),
+      }
+    );
+  };
+
+  const toggleFlag = (page: Page, flag: "pinned" | "favorite") => {
+    const options = { onError: (error: unknown) => notify(String(error), "error") };
+    if (flag === "pinned") {
+      setPinned.mutate({ id: page.id, pinned: !page.pinned }, options);
+    } else {
+      setFavorite.mutate({ id: page.id, favorite: !page.favorite }, options);
+    }
+  };
+
+  const toggleArchived = (page: Page) => {
+    setArchived.mutate(
+      { id: page.id, archived: !page.archived },
+      {
+        onSuccess: () =>
+          notify(
+            page.archived ? t("Page restored from the archive") : t("Page archived with its subpages"),
+            "success"
+          ),
+        onError: (error) => notify(String(error), "error"),
+      }
+    );
+  };
+
+  // Dropping a page on a sibling puts it in that sibling's place; other drops are ignored.
+  const handleDragEnd = (event: DragEndEvent) => {
+    const draggedId = parseDraggedPageId(event.active.id);
+    const targetId = event.over ? parseDraggedPageId(event.over.id) : null;
+    if (draggedId === null || targetId === null || draggedId === targetId) return;
+    const dragged = pages.find((page) => page.id === draggedId);
+    const target = pages.find((page) => page.id === targetId);
+    if (!dragged || !target || dragged.parent_id !== target.parent_id) return;
+
+    const siblings = childrenByParent.get(dragged.parent_id) ?? [];
+    if (!siblings.includes(dragged)) return;
+    const ordered = siblings.filter((page) => page.id !== draggedId);
+    ordered.splice(
+      ordered.findIndex((page) => page.id === targetId) +
+        (siblings.indexOf(dragged) < siblings.indexOf(target) ? 1 : 0),
+      0,
+      dragged
+    );
+    reorderPages.mutate(ordered.map((page) => page.id), { onError: (error) => notify(String(error), "error") });
+  };
+
+  const renderBranch = (parentId: number | null, depth: number) =>
+    (childrenByParent.get(parentId) ?? []).map((page) => {
+      const children = childrenByParent.get(page.id) ?? [];
+      const isCollapsed = collapsed.has(page.id);
+      const selected = page.id === selectedPageId;
+
+      return (
+        <Box key={page.id}>
+          <DraggablePageRow pageId={page.id}>
+            <Box
+              sx={{
+                display: "flex",
+                alignItems: "center",
+                pl: depth * 1.5,
+                borderRadius: 1.5,
+                backgroundColor: selected ? "action.selected" : "transparent",
+                "&:hover": { backgroundColor: selected ? "action.selected" : "action.hover" },
+                "&:hover .page-tree-actions": { opacity: 1 },
+              }}
+            >
+              <IconButton
+                size="small"
+                onClick={() => toggle(page.id)}
+                sx={{ visibility: children.length > 0 ? "visible" : "hidden", p: 0.25 }}
+                aria-label={isCollapsed ? t("Expand") : t("Collapse")}
+              >
+                {isCollapsed ? <ChevronRightIcon fontSize="small" /> : <ExpandMoreIcon fontSize="small" />}
+              </IconButton>
+              <Typography
+                variant="body2"
+                noWrap
+                onClick={() => onSelectPage(page.id)}
+                sx={{ flex: 1, minWidth: 0, py: 0.6, cursor: "pointer", fontWeight: selected ? 600 : 400 }}
+              >
+                {withIcon(page, page.title || t("Untitled"))}
+              </Typography>
+              {page.pinned ? <PushPinIcon sx={{ fontSize: 13, color: "text.secondary", mr: 0.25 }} /> : null}
+              <Box className="page-tree-actions" sx={{ display: "flex", opacity: selected ? 1 : 0 }}>
+                <Tooltip title={page.pinned ? t("Unpin") : t("Pin to top")}>
+                  <IconButton size="small" onClick={() => toggleFlag(page, "pinned")} sx={{ p: 0.4 }}>
+                    {page.pinned ? (
+                      <PushPinIcon sx={{ fontSize: 16 }} />
+                    ) : (
+                      <PushPinOutlinedIcon sx={{ fontSize: 16 }} />
+                    )}
+                  </IconButton>
+                </Tooltip>
+                <Tooltip title={page.favorite ? t("Remove from favorites") : t("Add to favorites")}>
+                  <IconButton size="small" onClick={() => toggleFlag(page, "favorite")} sx={{ p: 0.4 }}>
+                    {page.favorite ? (
+                      <StarIcon sx={{ fontSize: 16, color: "warning.main" }} />
+                    ) : (
+                      <StarBorderIcon sx={{ fontSize: 16 }} />
+                    )}
+                  </IconButton>
+                </Tooltip>
+                <Tooltip title={t("New subpage")}>
+                  <IconButton size="small" onClick={() => onCreatePage(page.id)} sx={{ p: 0.4 }}>
+                    <AddIcon sx={{ fontSize: 16 }} />
+                  </IconButton>
+                </Tooltip>
+                <Tooltip title={t("Move to…")}>
+                  <IconButton
+                    size="small"
+                    onClick={(event) => setMoveMenu({ anchor: event.currentTarget, page })}
+                    sx={{ p: 0.4 }}
+                  >
+                    <DriveFileMoveOutlinedIcon sx={{ fontSize: 16 }} />
+                  </IconButton>
+                </Tooltip>
+                <Tooltip title={t("Open in new window")}>
+                  <IconButton size="small" onClick={() => onOpenInWindow(page.id)} sx={{ p: 0.4 }}>
+                    <OpenInNewIcon sx={{ fontSize: 16 }} />
+                  </IconButton>
+                </Tooltip>
+                <Tooltip title={t("Archive")}>
+                  <IconButton size="small" onClick={() => toggleArchived(page)} sx={{ p: 0.4 }}>
+                    <ArchiveOutlinedIcon sx={{ fontSize: 16 }} />
+                  </IconButton>
+                </Tooltip>
+              </Box>
+            </Box>
+          </DraggablePageRow>
+          {!isCollapsed ? renderBranch(page.id, depth + 1) : null}
+        </Box>
+      );
+    });
+
+  // A page can't move under itself or its own subpages.
+  const excludedTargets = moveMenu ? descendantsOf(moveMenu.page.id) : new Set<number>();
+  const moveTargets = moveMenu ? pages.filter((page) => !excludedTargets.has(page.id)).sort(byTitle) : [];
+
+  return (
+    <Box
+      sx={{
+        p: 1,
+        borderRadius: 3,
+        border: "1px solid",
+        borderColor: "divider",
+        backgroundColor: "background.paper",
+      }}
+    >
+      <Box sx={{ display: "flex", alignItems: "center", justifyContent: "space-between", px: 0.5, mb: 0.5 }}>
+        <Typography variant="caption" color="text.secondary" sx={{ fontWeight: 600, textTransform: "uppercase" }}>
+          {t("Pages")}
+        </Typography>
+        <Button size="small" startIcon={<AddIcon />} onClick={() => onCreatePage(null)}>
+          {t("New")}
+        </Button>
+      </Box>
+      {knownTags.length > 0 ? (
+        <Box sx={{ display: "flex", flexWrap: "wrap", gap: 0.5, px: 0.5, mb: 0.75 }}>
+          {knownTags.map((tag) => {
+            const active = tag === activeTag;
+            return (
+              <Chip
+                key={tag}
+                label={`#${tag}`}
+                size="small"
+                color={active ? "primary" : "default"}
+                variant={active ? "filled" : "outlined"}
+                onClick={() => setTagFilter(active ? "" : tag)}
+              />
+            );
+          })}
+        </Box>
+      ) : null}
+      {favorites.length > 0 && !activeTag ? (
+        <Box sx={{ mb: 0.75, pb: 0.75, borderBottom: "1px solid", borderColor: "divider" }}>
+          {favorites.map((page) => (
+            <Box
+              key={page.id}
+              onClick={() => onSelectPage(page.id)}
+              sx={{
+                display: "flex",
+                alignItems: "center",
+                gap: 0.75,
+                px: 1,
+                py: 0.5,
+                borderRadius: 1.5,
+                cursor: "pointer",
+                backgroundColor: page.id === selectedPageId ? "action.selected" : "transparent",
+                "&:hover": { backgroundColor: page.id === selectedPageId ? "action.selected" : "action.hover" },
+              }}
+            >
+              <StarIcon sx={{ fontSize: 14, color: "warning.main" }} />
+              <Typography variant="body2" noWrap sx={{ fontWeight: page.id === selectedPageId ? 600 : 400 }}>
+                {withIcon(page, page.title || t("Untitled"))}
+              </Typography>
+            </Box>
+          ))}
+        </Box>
+      ) : null}
+      {recentPages.length > 0 && !activeTag ? (
+        <Box sx={{ mb: 0.75, pb: 0.75, borderBottom: "1px solid", borderColor: "divider" }}>
+          <Typography variant="caption" color="text.secondary" sx={{ display: "block", px: 1, pb: 0.25 }}>
+            {t("Jump back in")}
+          </Typography>
+          {recentPages.map((page) => (
+            <Box
+              key={page.page_id}
+              onClick={() => onSelectPage(page.page_id)}
+              sx={{
+                display: "flex",
+                alignItems: "center",
+                gap: 0.75,
+                px: 1,
+                py: 0.5,
+                borderRadius: 1.5,
+                cursor: "pointer",
+                backgroundColor: page.page_id === selectedPageId ? "action.selected" : "transparent",
+                "&:hover": { backgroundColor: page.page_id === selectedPageId ? "action.selected" : "action.hover" },
+              }}
+            >
+              <HistoryIcon sx={{ fontSize: 14, color: "text.secondary" }} />
+              <Typography variant="body2" noWrap sx={{ fontWeight: page.page_id === selectedPageId ? 600 : 400 }}>
+                {page.title || t("Untitled")}
+              </Typography>
+            </Box>
+          ))}
+        </Box>
+      ) : null}
+      {pages.length === 0 ? (
+        <Typography variant="caption" color="text.secondary" sx={{ display: "block", px: 0.5, py: 1 }}>
+          {t("No pages yet.")}
+        </Typography>
+      ) : activeTag ? (
+        // A tag slices across the hierarchy, so matches are listed flat.
+        [...taggedPages].sort(byTitle).map((page) => (
+          <Typography
+            key={page.id}
+            variant="body2"
+            noWrap
+            onClick={() => onSelectPage(page.id)}
+            sx={{
+              px: 1,
+              py: 0.6,
+              borderRadius: 1.5,
+              cursor: "pointer",
+              fontWeight: page.id === selectedPageId ? 600 : 400,
+              backgroundColor: page.id === selectedPageId ? "action.selected" : "transparent",
+              "&:hover": { backgroundColor: page.id === selectedPageId ? "action.selected" : "action.hover" },
+            }}
+          >
+            {withIcon(page, page.title || t("Untitled"))}
+          </Typography>
+        ))
+      ) : (
+        <DndContext sensors={dndSensors} onDragEnd={handleDragEnd}>
+          {renderBranch(null, 0)}
+        </DndContext>
+      )}
+      {archivedPages.length > 0 ? (
+        <Box sx={{ mt: 0.75, pt: 0.5, borderTop: "1px solid", borderColor: "divider" }}>
+          <Button
+            size="small"
+            color="inherit"
+            onClick={() => setArchiveOpen((open) => !open)}
+            startIcon={archiveOpen ? <ExpandMoreIcon /> : <ChevronRightIcon />}
+            sx={{ color: "text.secondary" }}
+          >
+            {t("Archived pages ({count})", { count: archivedPages.length })}
+          </Button>
+          {archiveOpen
+            ? [...archivedPages].sort(byTitle).map((page) => (
+                <Box key={page.id} sx={{ display: "flex", alignItems: "center", pl: 1 }}>
+                  <Typography
+                    variant="body2"
+                    noWrap
+                    color="text.secondary"
+                    onClick={() => onSelectPage(page.id)}
+                    sx={{ flex: 1, minWidth: 0, py: 0.5, cursor: "pointer" }}
+                  >
+                    {withIcon(page, page.title || t("Untitled"))}
+                  </Typography>
+                  <Tooltip title={t("Unarchive")}>
+                    <IconButton size="small" onClick={() => toggleArchived(page)} sx={{ p: 0.4 }}>
+                      <UnarchiveOutlinedIcon sx={{ fontSize: 16 }} />
+                    </IconButton>
+                  </Tooltip>
+                </Box>
+              ))
+            : null}
+        </Box>
+      ) : null}
+      <Menu anchorEl={moveMenu?.anchor ?? null} open={Boolean(moveMenu)} onClose={() => setMoveMenu(null)}>
+        <MenuItem onClick={() => handleMove(null)} disabled={moveMenu?.page.parent_id === null}>
+          {t("Top level")}
+        </MenuItem>
+        {moveTargets.map((page) => (
+          <MenuItem key={page.id} onClick={() => handleMove(page.id)} disabled={moveMenu?.page.parent_id === page.id}>
+            {withIcon(page, page.title || t("Untitled"))}
+          </MenuItem>
+        ))}
+      </Menu>
+    </Box>
+  );
+};
@@ -5,17 +5,21 @@ import {
   Button,
   Checkbox,
   Chip,
+  LinearProgress,
   Stack,
   TextField,
   Typography,
 } from "@mui/material";
 import type { SxProps, Theme } from "@mui/material/styles";
 import { format } from "date-fns";
-import type { Task } from "../../types";
+import { useState } from "react";
+import type { DailyMeetingLoad, DuplicateTaskMatch, GoalCheckin, StreakAtRisk, Task } from "../../types";
 
 interface PlannerDashboardSectionProps {
   busy: boolean;
   focusSessionsToday: number;
+  goalCheckins: GoalCheckin[];
+  pomodoroDailyGoal: number;
   habitsWithTodayState: Array<{
     id: number;
     title: string;
@@ -25,6 +29,10 @@ interface PlannerDashboardSectionProps {
     doneToday: boolean;
   }>;
   isDark: boolean;
+  meetingLoadToday: DailyMeetingLoad | undefined;
+  onCallToday: boolean;
+  onAddQuickTaskAnyway: () => void;
+  onGoalCheckIn: (goalId: number, progress: number) => void;
   onHabitToggle: (habitId: number, completed: boolean) => void;
   onOpenFocus: () => void;
   onOpenHabits: () => void;
@@ -42,7 +50,9 @@ interface PlannerDashboardSectionProps {
   quickProjectId: number | "";
   quickTaskFeedback: string;
   quickTaskFeedbackTone: "success" | "error";
+  quickTaskDuplicates: DuplicateTaskMatch[];
   quickTaskTitle: string;
+  streaksAtRisk: StreakAtRisk[];
   t: (key: string, variables?: Record<string, string | number>) => string;
   todayMeetings: Array<{
     title: string;
@@ -53,15 +63,65 @@ interface PlannerDashboardSectionProps {
   updateTaskStatus: (task: Task, checked: boolean) => void;
 }
 
+// One stale goal with an inline progress field; saving counts as the week's check-in.
+const GoalCheckinRow = ({
+  busy,
+  goal,
+  onCheckIn,
+  t,
+}: {
+  busy: boolean;
+  goal: GoalCheckin;
+  onCheckIn: (goalId: number, progress: number) => void;
+  t: PlannerDashboardSectionProps["t"];
+}) => {
+  const [progress, setProgress] = useState(String(goal.progress));
+
+  return (
+    <Stack direction="row" alignItems="center" spacing={1}>
+      <Box sx={{ minWidth: 0, flex: 1 }}>
+        <Typography variant="body2" noWrap sx={{ fontWeight: 600 }}>
+          {goal.title}
+        </Typography>
+        <Typography variant="caption" color="text.secondary">
+          {t("Not updated for {days} days", { days: goal.days_since_update })}
+        </Typography>
+      </Box>
+      <TextField
+        size="small"
+        type="number"
+        value={progress}
+        onChange={(event) => setProgress(event.target.value)}
+        inputProps={{ min: 0, max: 100, step: 5, "aria-label": t("Progress (%)") }}
+        sx={{ width: 84 }}
+      />
+      <Button
+        size="small"
+        disabled={busy || progress.trim() === ""}
+        onClick={() => onCheckIn(goal.goal_id, Math.min(100, Math.max(0, Math.round(Number(progress) || 0))))}
+        sx={{ textTransform: "none" }}
+      >
+        {t("Check in")}
+      </Button>
+    </Stack>
+  );
+};
+
 export const PlannerDashboardSection = ({
   busy,
   focusSessionsToday,
+  goalCheckins,
+  pomodoroDailyGoal,
   habitsWithTodayState,
   isDark,
+  meetingLoadToday,
+  onCallToday,
+  onGoalCheckIn,
   onHabitToggle,
   onOpenFocus,
   onOpenHabits,
   onOpenTasks,
+  onAddQuickTaskAnyway,
   onQuickTaskProjectChange,
   onQuickTaskTitleChange,
   onQuickTaskDueModeChange,
@@ -75,7 +135,9 @@ export const PlannerDashboardSection = ({
   quickProjectId,
   quickTaskFeedback,
   quickTaskFeedbackTone,
+  quickTaskDuplicates,
   quickTaskTitle,
+  streaksAtRisk,
   t,
   todayMeetings,
   updateTaskStatus,
@@ -83,14 +145,29 @@ export const PlannerDashboardSection = ({
   return (
     <Box sx={{ ...plannerSurfaceSx, p: { xs: 2, sm: 2.25 }, mb: { xs: 1.75, md: 2.25 } }}>
       <Stack direction="row" justifyContent="space-between" alignItems="center" sx={{ mb: 1.5 }}>
-        <Box>
+        <Box sx={{ display: "flex", alignItems: "center", gap: 1 }}>
           <Typography variant="subtitle1" sx={{ fontWeight: 800 }}>
             {t("Today Dashboard")}
           </Typography>
+          {onCallToday ? <Chip size="small" color="warning" label={t("On call")} /> : null}
+        </Box>
+        <Box sx={{ textAlign: "right" }}>
+          <Typography variant="body2" color="text.secondary" sx={{ whiteSpace: "nowrap" }}>
+            {format(new Date(), "EEE, MMM d")}
+          </Typography>
+          {meetingLoadToday ? (
+            <Typography
+              variant="caption"
+              color={meetingLoadToday.meeting_heavy ? "warning.main" : "text.secondary"}
+              sx={{ whiteSpace: "nowrap" }}
+            >
+              {t("{meetings}h meetings, {focus}h deep work", {
+                meetings: meetingLoadToday.meeting_hours,
+                focus: meetingLoadToday.deep_work_hours,
+              })}
+            </Typography>
+          ) : null}
         </Box>
-        <Typography variant="body2" color="text.secondary" sx={{ whiteSpace: "nowrap" }}>
-          {format(new Date(), "EEE, MMM d")}
-        </Typography>
       </Stack>
 
       <Box
@@ -250,6 +327,29 @@ export const PlannerDashboardSection = ({
                 <option value="none">{t("No date")}</option>
               </TextField>
             </Box>
+            {quickTaskDuplicates.length > 0 ? (
+              <Box sx={{ mt: 1 }}>
+                <Typography variant="caption" color="warning.main" sx={{ display: "block" }}>
+                  {t("Already exists — open it instead?")}
+                </Typography>
+                {quickTaskDuplicates.map((duplicate) => (
+                  <Button
+                    key={duplicate.task_id}
+                    size="small"
+                    onClick={onOpenTasks}
+                    endIcon={<OpenInNewIcon sx={{ fontSize: 14 }} />}
+                    sx={{ textTransform: "none", justifyContent: "flex-start", display: "flex", maxWidth: "100%" }}
+                  >
+                    <Typography variant="body2" noWrap>
+                      {duplicate.title}
+                    </Typography>
+                  </Button>
+                ))}
+                <Button size="small" disabled={busy} onClick={onAddQuickTaskAnyway} sx={{ textTransform: "none" }}>
+                  {t("Add anyway")}
+                </Button>
+              </Box>
+            ) : null}
             <Stack direction="row" justifyContent="space-between" alignItems="center" sx={{ mt: 1 }}>
               {quickTaskFeedback ? (
                 <Typography variant="caption" color={quickTaskFeedbackTone === "error" ? "error.main" : "success.main"}>
@@ -296,7 +396,16 @@ export const PlannerDashboardSection = ({
             )}
             <Typography variant="body2" color="text.secondary">
               {t("Focus sessions today")}: {focusSessionsToday}
+              {pomodoroDailyGoal > 0 && ` / ${pomodoroDailyGoal}`}
             </Typography>
+            {pomodoroDailyGoal > 0 && (
+              <LinearProgress
+                variant="determinate"
+                value={Math.min(100, (focusSessionsToday / pomodoroDailyGoal) * 100)}
+                aria-label={t("Daily goal")}
+                sx={{ mt: 0.75, height: 4, borderRadius: 2 }}
+              />
+            )}
           </Box>
 
           <Box sx={plannerInsetCardSx}>
@@ -308,6 +417,22 @@ export const PlannerDashboardSection = ({
                 {t("Track")}
               </Button>
             </Stack>
+            {streaksAtRisk.length > 0 ? (
+              <Stack direction="row" flexWrap="wrap" useFlexGap spacing={0.75} sx={{ mb: 1 }}>
+                {streaksAtRisk.map((habit) => (
+                  <Chip
+                    key={habit.habit_id}
+                    size="small"
+                    color="warning"
+                    variant="outlined"
+                    label={t("Streak at risk: {title} ({count}d)", {
+                      title: habit.title,
+                      count: habit.streak_days,
+                    })}
+                  />
+                ))}
+              </Stack>
+            ) : null}
             <Stack spacing={0.75}>
               {habitsWithTodayState.length === 0 ? (
                 <Typography variant="body2" color="text.secondary">
@@ -335,6 +460,19 @@ export const PlannerDashboardSection = ({
               )}
             </Stack>
           </Box>
+
+          {goalCheckins.length > 0 ? (
+            <Box sx={plannerInsetCardSx}>
+              <Typography variant="caption" sx={{ fontWeight: 700, mb: 0.8, display: "block", letterSpacing: "0.08em", textTransform: "uppercase", color: "text.secondary" }}>
+                {t("Goal Check-in")}
+              </Typography>
+              <Stack spacing={0.75}>
+                {goalCheckins.slice(0, 4).map((goal) => (
+                  <GoalCheckinRow key={goal.goal_id} busy={busy} goal={goal} onCheckIn={onGoalCheckIn} t={t} />
+                ))}
+              </Stack>
+            </Box>
+          ) : null}
         </Stack>
       </Box>
     </Box>
@@ -22,7 +22,7 @@ import {
 } from "@mui/material";
 import { format, parseISO } from "date-fns";
 import { type MouseEvent, useState } from "react";
-import type { Meeting, MeetingRecurrence, MeetingStatus } from "../../types";
+import type { DailyMeetingLoad, Meeting, MeetingRecurrence, MeetingStatus } from "../../types";
 
 interface PlannerMeetingsSectionProps {
   busy: boolean;
@@ -41,6 +41,7 @@ interface PlannerMeetingsSectionProps {
   meetingActionItems: string;
   meetingCalendarUrl: string;
   meetingDayBuckets: Array<{ day: string; count: number }>;
+  meetingLoadByDay: Record<string, DailyMeetingLoad>;
   meetingDecisions: string;
   meetingEndAt: string;
   meetingFeedback: string;
@@ -102,6 +103,7 @@ export const PlannerMeetingsSection = ({
   meetingAgenda,
   meetingCalendarUrl,
   meetingDayBuckets,
+  meetingLoadByDay,
   meetingDecisions,
   meetingEndAt,
   meetingFeedback,
@@ -190,16 +192,31 @@ export const PlannerMeetingsSection = ({
           <Box sx={{ display: "grid", gridTemplateColumns: { xs: "1fr", xl: "1.1fr 1fr" }, gap: 2 }}>
             <Box>
               <Stack direction="row" spacing={0.8} sx={{ mb: 1.5, flexWrap: "wrap" }}>
-                {meetingDayBuckets.map((bucket) => (
-                  <Chip
-                    key={bucket.day}
-                    size="small"
-                    variant={bucket.day === today ? "filled" : "outlined"}
-                    color={bucket.day === today ? "primary" : "default"}
-                    label={`${format(parseISO(bucket.day), "EEE d")} · ${bucket.count}`}
-                  />
-                ))}
+                {meetingDayBuckets.map((bucket) => {
+                  const load = meetingLoadByDay[bucket.day];
+                  return (
+                    <Chip
+                      key={bucket.day}
+                      size="small"
+                      variant={bucket.day === today ? "filled" : "outlined"}
+                      color={load?.meeting_heavy ? "warning" : bucket.day === today ? "primary" : "default"}
+                      label={`${format(parseISO(bucket.day), "EEE d")} · ${bucket.count}${
+                        load && load.meeting_hours > 0 ? ` · ${load.meeting_hours}h` : ""
+                      }`}
+                    />
+                  );
+                })}
               </Stack>
+              {meetingDayBuckets.some((bucket) => meetingLoadByDay[bucket.day]?.meeting_heavy) ? (
+                <Typography variant="caption" color="warning.main" sx={{ display: "block", mt: -0.75, mb: 1.5 }}>
+                  {t("Meeting-heavy days: {days}. Plan focus work on other days.", {
+                    days: meetingDayBuckets
+                      .filter((bucket) => meetingLoadByDay[bucket.day]?.meeting_heavy)
+                      .map((bucket) => format(parseISO(bucket.day), "EEE d"))
+                      .join(", "),
+                  })}
+                </Typography>
+              ) : null}
 
               <Stack spacing={1}>
                 {upcomingMeetings.length === 0 ? (
@@ -10,7 +10,15 @@ import {
 } from "@mui/material";
 import { alpha, type SxProps, type Theme } from "@mui/material/styles";
 import { format } from "date-fns";
-import type { Goal, Task } from "../../types";
+import type { Goal, MoodCorrelation, Task } from "../../types";
+
+const MOOD_FACTOR_LABELS: Record<MoodCorrelation["factor"], string> = {
+  commits: "Commits",
+  meetings: "Meetings",
+  tasks_completed: "Tasks completed",
+  deep_work_minutes: "Deep work",
+  on_call: "On call",
+};
 
 interface PlannerWeeklyReviewSectionProps {
   currentWeekInterval: { start: Date; end: Date };
@@ -19,6 +27,7 @@ interface PlannerWeeklyReviewSectionProps {
   handleAddDailyWin: () => void;
   handleRemoveDailyWin: (index: number) => void;
   isSectionCollapsed: (section: "dailyWins") => boolean;
+  moodCorrelations: MoodCorrelation[];
   nearGoals: Goal[];
   onDailyWinsInputChange: (value: string) => void;
   onOpenFocus: () => void;
@@ -43,6 +52,7 @@ export const PlannerWeeklyReviewSection = ({
   handleAddDailyWin,
   handleRemoveDailyWin,
   isSectionCollapsed,
+  moodCorrelations,
   nearGoals,
   onDailyWinsInputChange,
   onOpenFocus,
@@ -223,6 +233,32 @@ export const PlannerWeeklyReviewSection = ({
           </Collapse>
         </Box>
       </Box>
+
+      {moodCorrelations.length > 0 && moodCorrelations[0].sample_days >= 3 ? (
+        <Box sx={{ ...plannerInsetCardSx, mt: 1.25 }}>
+          <Stack direction="row" justifyContent="space-between" alignItems="center" sx={{ mb: 1 }}>
+            <Typography variant="subtitle2" sx={{ fontWeight: 700 }}>
+              {t("What moves your mood")}
+            </Typography>
+            <Chip
+              size="small"
+              variant="outlined"
+              label={t("{count} rated days", { count: moodCorrelations[0].sample_days })}
+            />
+          </Stack>
+          <Stack direction="row" spacing={0} sx={{ flexWrap: "wrap", gap: 0.75 }}>
+            {moodCorrelations.map((item) => (
+              <Chip
+                key={item.factor}
+                size="small"
+                variant="outlined"
+                color={item.correlation >= 0.3 ? "success" : item.correlation <= -0.3 ? "error" : "default"}
+                label={`${t(MOOD_FACTOR_LABELS[item.factor])}: ${item.correlation > 0 ? "+" : ""}${item.correlation.toFixed(2)}`}
+              />
+            ))}
+          </Stack>
+        </Box>
+      ) : null}
     </Box>
   );
 };
@@ -0,0 +1,265 @@
+import DeleteOutlineIcon from "@mui/icons-material/DeleteOutline";
+import { Box, Button, IconButton, MenuItem, Switch, TextField, Typography } from "@mui/material";
+import { format } from "date-fns";
+import { useState } from "react";
+import {
+  useAutomationLog,
+  useAutomationPreview,
+  useAutomationRules,
+  useCreateAutomationRule,
+  useDeleteAutomationRule,
+  useRunAutomationRules,
+  useUpdateAutomationRule,
+} from "../../hooks/useAutomationRules";
+import { useI18n } from "../../i18n/I18nContext";
+import type { AutomationAction, AutomationCondition, AutomationRule, TaskPriority } from "../../types";
+
+const PRIORITIES: TaskPriority[] = ["low", "medium", "high", "urgent"];
+const PRIORITY_LABEL: Record<TaskPriority, string> = {
+  low: "Low",
+  medium: "Medium",
+  high: "High",
+  urgent: "Urgent",
+};
+
+const ruleInput = (rule: AutomationRule) => ({
+  name: rule.name,
+  condition: rule.condition,
+  condition_value: rule.condition_value,
+  priority_filter: rule.priority_filter,
+  action: rule.action,
+  action_value: rule.action_value,
+  enabled: rule.enabled,
+});
+
+// Condition → action rules the scheduler applies to open tasks every minute.
+export const AutomationRulesPanel = () => {
+  const { t } = useI18n();
+  const { data: rules = [] } = useAutomationRules();
+  const { data: preview = [] } = useAutomationPreview();
+  const { data: log = [] } = useAutomationLog();
+  const createRule = useCreateAutomationRule();
+  const updateRule = useUpdateAutomationRule();
+  const deleteRule = useDeleteAutomationRule();
+  const runRules = useRunAutomationRules();
+  const [name, setName] = useState("");
+  const [condition, setCondition] = useState<AutomationCondition>("due_within_hours");
+  const [conditionValue, setConditionValue] = useState(24);
+  const [priorityFilter, setPriorityFilter] = useState<TaskPriority | "">("");
+  const [action, setAction] = useState<AutomationAction>("set_priority");
+  const [actionValue, setActionValue] = useState("medium");
+  const [status, setStatus] = useState("");
+
+  const describeRule = (rule: AutomationRule) => {
+    const when =
+      rule.condition === "due_within_hours"
+        ? t("due within {count} h", { count: rule.condition_value })
+        : t("in progress for {count}+ days", { count: rule.condition_value });
+    const scope = rule.priority_filter
+      ? t("{priority} priority, ", { priority: t(PRIORITY_LABEL[rule.priority_filter]) })
+      : "";
+    const then =
+      rule.action === "set_priority"
+        ? t("set priority to {priority}", {
+            priority: t(PRIORITY_LABEL[rule.action_value as TaskPriority] ?? rule.action_value),
+          })
+        : t("add tag #{tag}", { tag: rule.action_value });
+    return `${scope}${when} → ${then}`;
+  };
+
+  const handleAdd = () => {
+    createRule.mutate(
+      {
+        name: name.trim(),
+        condition,
+        condition_value: conditionValue,
+        priority_filter: priorityFilter || null,
+        action,
+        action_value: actionValue.trim(),
+        enabled: true,
+      },
+      {
+        onSuccess: () => {
+          setName("");
+          setStatus("");
+        },
+        onError: (error) => setStatus(String(error)),
+      }
+    );
+  };
+
+  const handleRunNow = () => {
+    runRules.mutate(undefined, {
+      onSuccess: (changes) => setStatus(t("Rules changed {count} tasks.", { count: changes.length })),
+      onError: (error) => setStatus(String(error)),
+    });
+  };
+
+  return (
+    <Box sx={{ mt: 2 }}>
+      <Typography variant="body2" sx={{ fontWeight: 600 }}>
+        {t("Task rules")}
+      </Typography>
+      <Typography variant="caption" color="text.secondary" sx={{ display: "block", mt: 0.35, mb: 1, lineHeight: 1.45 }}>
+        {t("Rules run in the background on open tasks, e.g. raise the priority of tasks due soon or tag work stuck in progress.")}
+      </Typography>
+      <Box sx={{ display: "grid", gridTemplateColumns: { xs: "1fr", sm: "2fr 2fr 1fr 1fr" }, gap: 1 }}>
+        <TextField size="small" label={t("Rule name")} value={name} onChange={(event) => setName(event.target.value)} />
+        <TextField
+          select
+          size="small"
+          label={t("When")}
+          value={condition}
+          onChange={(event) => {
+            const next = event.target.value as AutomationCondition;
+            setCondition(next);
+            setConditionValue(next === "due_within_hours" ? 24 : 7);
+          }}
+        >
+          <MenuItem value="due_within_hours">{t("Due within (hours)")}</MenuItem>
+          <MenuItem value="in_progress_days">{t("In progress for (days)")}</MenuItem>
+        </TextField>
+        <TextField
+          size="small"
+          type="number"
+          label={condition === "due_within_hours" ? t("Hours") : t("Days")}
+          value={conditionValue}
+          onChange={(event) => setConditionValue(Math.max(1, Math.round(Number(event.target.value) || 1)))}
+          inputProps={{ min: 1, step: 1 }}
+        />
+        <TextField
+          select
+          size="small"
+          label={t("Only priority")}
+          value={priorityFilter}
+          onChange={(event) => setPriorityFilter(event.target.value as TaskPriority | "")}
+        >
+          <MenuItem value="">{t("Any")}</MenuItem>
+          {PRIORITIES.map((priority) => (
+            <MenuItem key={priority} value={priority}>
+              {t(PRIORITY_LABEL[priority])}
+            </MenuItem>
+          ))}
+        </TextField>
+        <TextField
+          select
+          size="small"
+          label={t("Then")}
+          value={action}
+          onChange={(event) => {
+            const next = event.target.value as AutomationAction;
+            setAction(next);
+            setActionValue(next === "set_priority" ? "medium" : "stale");
+          }}
+        >
+          <MenuItem value="set_priority">{t("Set priority")}</MenuItem>
+          <MenuItem value="add_tag">{t("Add tag")}</MenuItem>
+        </TextField>
+        {action === "set_priority" ? (
+          <TextField
+            select
+            size="small"
+            label={t("Priority")}
+            value={actionValue}
+            onChange={(event) => setActionValue(event.target.value)}
+          >
+            {PRIORITIES.map((priority) => (
+              <MenuItem key={priority} value={priority}>
+                {t(PRIORITY_LABEL[priority])}
+              </MenuItem>
+            ))}
+          </TextField>
+        ) : (
+          <TextField
+            size="small"
+            label={t("Tag")}
+            value={actionValue}
+            onChange={(event) => setActionValue(event.target.value)}
+          />
+        )}
+      </Box>
+      <Box sx={{ display: "flex", flexWrap: "wrap", alignItems: "center", gap: 1, mt: 1 }}>
+        <Button
+          size="small"
+          variant="outlined"
+          onClick={handleAdd}
+          disabled={!name.trim() || !actionValue.trim() || createRule.isPending}
+        >
+          {t("Add rule")}
+        </Button>
+        <Button size="small" onClick={handleRunNow} disabled={runRules.isPending || preview.length === 0}>
+          {t("Run now")}
+        </Button>
+        {status ? (
+          <Typography variant="caption" color="text.secondary">
+            {status}
+          </Typography>
+        ) : null}
+      </Box>
+      {rules.map((rule) => (
+        <Box key={rule.id} sx={{ display: "flex", alignItems: "center", gap: 1, mt: 1 }}>
+          <Switch
+            size="small"
+            checked={rule.enabled}
+            onChange={(event) =>
+              updateRule.mutate({ id: rule.id, input: { ...ruleInput(rule), enabled: event.target.checked } })
+            }
+            inputProps={{ "aria-label": t("Enable rule") }}
+          />
+          <Box sx={{ flex: 1, minWidth: 0 }}>
+            <Typography variant="body2" noWrap>
+              {rule.name}
+            </Typography>
+            <Typography variant="caption" color="text.secondary" sx={{ display: "block" }} noWrap>
+              {describeRule(rule)}
+            </Typography>
+          </Box>
+          <IconButton
+            size="small"
+            aria-label={t("Delete rule")}
+            onClick={() => deleteRule.mutate(rule.id)}
+            disabled={deleteRule.isPending}
+          >
+            <DeleteOutlineIcon fontSize="small" />
+          </IconButton>
+        </Box>
+      ))}
+      {rules.length > 0 ? (
+        <>
+          <Typography variant="caption" sx={{ display: "block", fontWeight: 600, mt: 1.5 }}>
+            {t("Preview (dry run)")}
+          </Typography>
+          {preview.length === 0 ? (
+            <Typography variant="caption" color="text.secondary" sx={{ display: "block" }}>
+              {t("No tasks would change right now.")}
+            </Typography>
+          ) : (
+            preview.map((change) => (
+              <Typography
+                key={`${change.rule_id}-${change.task_id}`}
+                variant="caption"
+                color="text.secondary"
+                sx={{ display: "block" }}
+                noWrap
+              >
+                {`${change.task_title}: ${change.change}`}
+              </Typography>
+            ))
+          )}
+        </>
+      ) : null}
+      {log.length > 0 ? (
+        <>
+          <Typography variant="caption" sx={{ display: "block", fontWeight: 600, mt: 1.5 }}>
+            {t("Recent rule runs")}
+          </Typography>
+          {log.map((run) => (
+            <Typography key={run.id} variant="caption" color="text.secondary" sx={{ display: "block" }} noWrap>
+              {`${format(new Date(run.ran_at), "MMM d, HH:mm")} · ${run.rule_name} · ${run.task_title}: ${run.change}`}
+            </Typography>
+          ))}
+        </>
+      ) : null}
+    </Box>
+  );
+};
@@ -0,0 +1,80 @@
+import CheckIcon from "@mui/icons-material/Check";
+import CloseIcon from "@mui/icons-material/Close";
+import { Box, Button, Chip, Paper, Stack, Typography } from "@mui/material";
+import type { SxProps, Theme } from "@mui/material";
+import { useAcceptReviewItem, useDismissReviewItem, useReviewQueue } from "../../hooks/useTasks";
+import type { TaskPriority } from "../../types";
+
+interface ReviewQueuePanelProps {
+  boardSurfaceSx: SxProps<Theme>;
+  priorityLabel: Record<TaskPriority, string>;
+  t: (key: string, variables?: Record<string, string | number>) => string;
+}
+
+const sourceLabelKey: Record<string, string> = {
+  url_capture: "Captured from URL",
+};
+
+// Tasks created by integrations stay here, off the board, until accepted.
+export const ReviewQueuePanel = ({ boardSurfaceSx, priorityLabel, t }: ReviewQueuePanelProps) => {
+  const { data: queue = [] } = useReviewQueue();
+  const acceptReviewItem = useAcceptReviewItem();
+  const dismissReviewItem = useDismissReviewItem();
+  const busy = acceptReviewItem.isPending || dismissReviewItem.isPending;
+
+  if (queue.length === 0) {
+    return null;
+  }
+
+  return (
+    <Paper sx={{ ...boardSurfaceSx, p: 1.5, mt: 1.5 }}>
+      <Stack direction="row" spacing={1} alignItems="center" sx={{ mb: 1 }}>
+        <Typography variant="subtitle1" sx={{ fontWeight: 800 }}>
+          {t("Needs review")}
+        </Typography>
+        <Chip label={queue.length} size="small" color="warning" />
+      </Stack>
+      <Stack spacing={1}>
+        {queue.map((item) => (
+          <Stack
+            key={item.task.id}
+            direction={{ xs: "column", sm: "row" }}
+            spacing={1}
+            alignItems={{ xs: "stretch", sm: "center" }}
+            justifyContent="space-between"
+          >
+            <Box sx={{ minWidth: 0 }}>
+              <Typography variant="body2" sx={{ fontWeight: 600 }} noWrap>
+                {item.task.title}
+              </Typography>
+              <Typography variant="caption" color="text.secondary" noWrap component="div">
+                {t(sourceLabelKey[item.source] ?? item.source)} · {priorityLabel[item.task.priority]}
+                {item.source_url ? ` · ${item.source_url}` : ""}
+              </Typography>
+            </Box>
+            <Stack direction="row" spacing={1} sx={{ flexShrink: 0 }}>
+              <Button
+                size="small"
+                variant="contained"
+                startIcon={<CheckIcon fontSize="small" />}
+                onClick={() => acceptReviewItem.mutate(item.task.id)}
+                disabled={busy}
+              >
+                {t("Accept")}
+              </Button>
+              <Button
+                size="small"
+                color="inherit"
+                startIcon={<CloseIcon fontSize="small" />}
+                onClick={() => dismissReviewItem.mutate(item.task.id)}
+                disabled={busy}
+              >
+                {t("Dismiss")}
+              </Button>
+            </Stack>
+          </Stack>
+        ))}
+      </Stack>
+    </Paper>
+  );
+};
@@ -1,3 +1,5 @@
+import ArrowDownwardIcon from "@mui/icons-material/ArrowDownward";
+import ArrowUpwardIcon from "@mui/icons-material/ArrowUpward";
 import DeleteOutlineIcon from "@mui/icons-material/DeleteOutline";
 import EditOutlinedIcon from "@mui/icons-material/EditOutlined";
 import {
@@ -10,9 +12,11 @@ import {
   DialogContent,
   DialogTitle,
   Divider,
+  FormControlLabel,
   IconButton,
   Paper,
   Stack,
+  Switch,
   TextField,
   Typography,
 } from "@mui/material";
@@ -34,13 +38,18 @@ interface TaskDetailsDialogProps {
   onDeleteSubtask: (subtaskId: number) => void;
   onEditSubtaskTitleChange: (value: string) => void;
   onEditTask: () => void;
+  onMoveSubtask: (subtaskId: number, offset: -1 | 1) => void;
   onNewSubtaskTitleChange: (value: string) => void;
   onSaveSubtaskEdit: (subtaskId: number) => void;
+  onSlackStatusOptOutChange: (optedOut: boolean) => void;
   onToggleSubtask: (subtaskId: number, completed: boolean) => void;
   outcome?: { before?: string; after?: string };
   priorityColor: Record<TaskPriority, "default" | "info" | "warning" | "error">;
   priorityLabel: Record<TaskPriority, string>;
   recurrenceLabel: Record<TaskRecurrence, string>;
+  /** The switch is only shown while the Slack status integration is on. */
+  slackStatusEnabled: boolean;
+  slackStatusOptedOut: boolean;
   statusLabel: Record<TaskStatus, string>;
   t: (key: string, variables?: Record<string, string | number>) => string;
   formatTaskDateOnly: (value: string) => string;
@@ -62,13 +71,17 @@ export const TaskDetailsDialog = ({
   onDeleteSubtask,
   onEditSubtaskTitleChange,
   onEditTask,
+  onMoveSubtask,
   onNewSubtaskTitleChange,
   onSaveSubtaskEdit,
+  onSlackStatusOptOutChange,
   onToggleSubtask,
   outcome,
   priorityColor,
   priorityLabel,
   recurrenceLabel,
+  slackStatusEnabled,
+  slackStatusOptedOut,
   statusLabel,
   t,
   formatTaskDateOnly,
@@ -122,6 +135,20 @@ export const TaskDetailsDialog = ({
             </Typography>
           ) : null}
 
+          {slackStatusEnabled ? (
+            <FormControlLabel
+              control={
+                <Switch
+                  size="small"
+                  checked={!slackStatusOptedOut}
+                  onChange={(event) => onSlackStatusOptOutChange(!event.target.checked)}
+                  disabled={busy}
+                />
+              }
+              label={<Typography variant="body2">{t("Show in Slack status while the timer runs")}</Typography>}
+            />
+          ) : null}
+
           <Divider />
 
           <Stack direction="row" justifyContent="space-between" alignItems="center" spacing={1}>
@@ -150,7 +177,7 @@ export const TaskDetailsDialog = ({
           </Stack>
 
           <Stack spacing={1}>
-            {activeTaskSubtasks.map((subtask) => (
+            {activeTaskSubtasks.map((subtask, index) => (
               <Paper key={subtask.id} variant="outlined" sx={{ p: 1 }}>
                 {editingSubtaskId === subtask.id ? (
                   <Stack direction={{ xs: "column", sm: "row" }} spacing={1}>
@@ -192,6 +219,17 @@ export const TaskDetailsDialog = ({
                     >
                       {subtask.title}
                     </Typography>
+                    <IconButton size="small" onClick={() => onMoveSubtask(subtask.id, -1)} disabled={busy || index === 0} aria-label={t("Move up")}>
+                      <ArrowUpwardIcon fontSize="small" />
+                    </IconButton>
+                    <IconButton
+                      size="small"
+                      onClick={() => onMoveSubtask(subtask.id, 1)}
+                      disabled={busy || index === activeTaskSubtasks.length - 1}
+                      aria-label={t("Move down")}
+                    >
+                      <ArrowDownwardIcon fontSize="small" />
+                    </IconButton>
                     <IconButton size="small" onClick={() => onBeginSubtaskEdit(subtask)} disabled={busy}>
                       <EditOutlinedIcon fontSize="small" />
                     </IconButton>
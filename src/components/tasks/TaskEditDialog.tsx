@@ -31,6 +31,7 @@ interface TaskEditDialogProps {
   onRecurrenceUntilChange: (value: string) => void;
   onSave: () => void;
   onStatusChange: (value: TaskStatus) => void;
+  onTagsTextChange: (value: string) => void;
   onTimeEstimateMinutesChange: (value: number) => void;
   onTitleChange: (value: string) => void;
   priority: TaskPriority;
@@ -40,6 +41,7 @@ interface TaskEditDialogProps {
   recurrenceUntil: string;
   status: TaskStatus;
   t: (key: string, variables?: Record<string, string | number>) => string;
+  tagsText: string;
   timeEstimateMinutes: number;
   title: string;
 }
@@ -66,6 +68,7 @@ export const TaskEditDialog = ({
   onRecurrenceUntilChange,
   onSave,
   onStatusChange,
+  onTagsTextChange,
   onTimeEstimateMinutesChange,
   onTitleChange,
   priority,
@@ -75,6 +78,7 @@ export const TaskEditDialog = ({
   recurrenceUntil,
   status,
   t,
+  tagsText,
   timeEstimateMinutes,
   title,
 }: TaskEditDialogProps) => (
@@ -144,10 +148,13 @@ export const TaskEditDialog = ({
             <option value="daily">{t("Daily")}</option>
             <option value="weekdays">{t("Weekdays")}</option>
             <option value="weekly">{t("Weekly")}</option>
+            <option value="monthly">{t("Monthly")}</option>
           </TextField>
           <TextField type="date" label={t("Repeat until")} value={recurrenceUntil} onChange={(event) => onRecurrenceUntilChange(event.target.value)} InputLabelProps={{ shrink: true }} disabled={recurrence === "none"} fullWidth />
         </Stack>
 
+        <TextField label={t("Tags")} value={tagsText} onChange={(event) => onTagsTextChange(event.target.value)} helperText={t("Comma-separated")} placeholder="backend, urgent" fullWidth />
+
         <TextField label={t("Before (planned outcome)")} value={beforeOutcome} onChange={(event) => onBeforeOutcomeChange(event.target.value)} multiline minRows={2} fullWidth />
         <TextField label={t("After (actual outcome)")} value={afterOutcome} onChange={(event) => onAfterOutcomeChange(event.target.value)} multiline minRows={2} fullWidth />
       </Stack>
@@ -26,6 +26,7 @@ interface TasksBoardToolbarProps {
   onQueryChange: (value: string) => void;
   onResetFilters: () => void;
   onStatusFilterChange: (value: "all" | TaskStatus) => void;
+  onTagFilterChange: (value: string | null) => void;
   onToggleOverdueOnly: () => void;
   priorityFilter: "all" | TaskPriority;
   projectFilter: "all" | number;
@@ -34,6 +35,8 @@ interface TasksBoardToolbarProps {
   showOverdueOnly: boolean;
   stats: { total: number; done: number; dueToday: number; overdue: number; activeTimers: number };
   statusFilter: "all" | TaskStatus;
+  tagFilter: string | null;
+  tags: string[];
   t: (key: string, variables?: Record<string, string | number>) => string;
 }
 
@@ -47,6 +50,7 @@ export const TasksBoardToolbar = ({
   onQueryChange,
   onResetFilters,
   onStatusFilterChange,
+  onTagFilterChange,
   onToggleOverdueOnly,
   priorityFilter,
   projectFilter,
@@ -55,6 +59,8 @@ export const TasksBoardToolbar = ({
   showOverdueOnly,
   stats,
   statusFilter,
+  tagFilter,
+  tags,
   t,
 }: TasksBoardToolbarProps) => {
   const hasActiveFilters =
@@ -62,6 +68,7 @@ export const TasksBoardToolbar = ({
     statusFilter !== "all" ||
     priorityFilter !== "all" ||
     projectFilter !== "all" ||
+    tagFilter !== null ||
     showOverdueOnly;
 
   return (
@@ -182,6 +189,26 @@ export const TasksBoardToolbar = ({
           ))}
         </TextField>
 
+        <TextField
+          select
+          label={t("Tag")}
+          value={tagFilter ?? ""}
+          onChange={(event) => onTagFilterChange(event.target.value || null)}
+          sx={{ minWidth: { xs: 0, sm: 136, md: 144 } }}
+          SelectProps={{ native: true }}
+          InputLabelProps={{ shrink: true }}
+        >
+          <option value="">{t("All tags")}</option>
+          {tagFilter && !tags.some((tag) => tag.toLowerCase() === tagFilter.toLowerCase()) ? (
+            <option value={tagFilter}>#{tagFilter}</option>
+          ) : null}
+          {tags.map((tag) => (
+            <option key={tag} value={tag}>
+              #{tag}
+            </option>
+          ))}
+        </TextField>
+
         <Button
           variant={showOverdueOnly ? "contained" : "outlined"}
           color={showOverdueOnly ? "error" : "inherit"}
@@ -4,15 +4,81 @@ export const queryKeys = {
   entries: ["entries"] as const,
   entry: (date?: string) => (date ? (["entry", date] as const) : (["entry"] as const)),
   search: ["search"] as const,
+  entryTags: ["entry-tags"] as const,
+  entrySectionTemplates: ["entry-section-templates"] as const,
+  entrySections: (date?: string) =>
+    date ? (["entry-sections", date] as const) : (["entry-sections"] as const),
+  entryCommits: (date?: string) =>
+    date ? (["entry-commits", date] as const) : (["entry-commits"] as const),
+  // Live `git log` lines; the window starts at the previous working day.
+  gitCommits: ["commits"] as const,
+  previousWorkday: ["commits", "previous-workday"] as const,
+  entryNotes: (date?: string) => (date ? (["entry-notes", date] as const) : (["entry-notes"] as const)),
+  entryAttachments: (entryId?: number) =>
+    entryId === undefined ? (["entry-attachments"] as const) : (["entry-attachments", entryId] as const),
+  attachmentText: (id: number) => ["attachment-text", id] as const,
+  journalPrompts: ["journal-prompts"] as const,
+  dailyPrompt: ["daily-prompt"] as const,
+  dailyMoods: ["daily-moods"] as const,
+  moodCorrelations: ["mood-correlations"] as const,
   pages: ["pages"] as const,
+  pageTags: ["pages", "tags"] as const,
+  pageProperties: ["pages", "properties"] as const,
   tasks: ["tasks"] as const,
   taskSubtasks: ["task-subtasks"] as const,
+  taskLinks: ["task-links"] as const,
+  reviewQueue: ["tasks", "review-queue"] as const,
+  contextSwitches: (start: string, end: string) => ["tasks", "context-switches", start, end] as const,
+  taskTags: ["tasks", "tags"] as const,
+  tasksByTag: (tag: string) => ["tasks", "by-tag", tag] as const,
+  automationRules: ["tasks", "automation-rules"] as const,
+  automationPreview: ["tasks", "automation-rules", "preview"] as const,
+  automationLog: ["tasks", "automation-log"] as const,
   goals: ["goals"] as const,
+  goalCheckins: ["goals", "checkins"] as const,
   goalMilestones: ["goal-milestones"] as const,
+  goalReflections: ["goal-reflections"] as const,
   habits: ["habits"] as const,
+  streaksAtRisk: ["habits", "streaks-at-risk"] as const,
   projects: ["projects"] as const,
   projectBranches: ["project-branches"] as const,
   meetings: ["meetings"] as const,
+  // Nested under `meetings` so meeting changes refresh it too.
+  meetingLoad: (startDate?: string, endDate?: string) =>
+    startDate && endDate
+      ? (["meetings", "load", startDate, endDate] as const)
+      : (["meetings", "load"] as const),
+  pomodoroStatus: ["pomodoro-status"] as const,
+  pomodoroHeatmap: ["pomodoro-heatmap"] as const,
+  presence: ["presence"] as const,
+  slackStatusSettings: ["slack-status", "settings"] as const,
+  slackStatusOptOuts: ["slack-status", "opt-outs"] as const,
+  countdowns: ["countdowns"] as const,
+  appSettings: ["app-settings"] as const,
+  // Git log of the vault mirror folder; `path` narrows it to one mirrored file.
+  mirrorHistory: (path?: string) =>
+    path === undefined ? (["mirror-history"] as const) : (["mirror-history", path] as const),
+  // Nested under `app-settings`, so saving a template refreshes the rendered preview.
+  notificationTemplates: ["app-settings", "notification-templates"] as const,
+  // Nested under `app-settings`: it depends on the last version marked as seen.
+  whatsNew: ["app-settings", "whats-new"] as const,
+  featureFlags: ["app-settings", "feature-flags"] as const,
+  // Under app-settings so changing `week_numbering` refetches it.
+  weekInfo: ["app-settings", "week-info"] as const,
+  accessibleSummary: (view: string) => ["accessible-summary", view] as const,
+  scheduledJobs: ["scheduled-jobs"] as const,
+  integrityReport: ["integrity-report"] as const,
+  workspaceSnapshots: ["workspace-snapshots"] as const,
+  performanceReport: ["performance-report"] as const,
+  achievements: ["achievements"] as const,
+  apiTokens: ["api-tokens"] as const,
+  externalAccessLog: ["external-access-log"] as const,
+  bookmarks: ["bookmarks"] as const,
+  incidents: ["incidents"] as const,
+  incidentTimeline: (from: string, to: string) => ["incident-timeline", from, to] as const,
+  onCallShifts: ["oncall-shifts"] as const,
+  onCallDays: (startDate: string, endDate: string) => ["oncall-days", startDate, endDate] as const,
+  journalEncryption: ["journal-encryption"] as const,
 } as const;
 
 const invalidate = (queryClient: QueryClient, queryKey: readonly unknown[]) =>
@@ -21,6 +87,7 @@ const invalidate = (queryClient: QueryClient, queryKey: readonly unknown[]) =>
 export const invalidateTaskDomain = (queryClient: QueryClient) => {
   invalidate(queryClient, queryKeys.tasks);
   invalidate(queryClient, queryKeys.taskSubtasks);
+  invalidate(queryClient, queryKeys.taskLinks);
 };
 
 export const invalidateGoalDomain = (queryClient: QueryClient) => {
@@ -53,10 +120,31 @@ export const invalidateMeetingDomain = (queryClient: QueryClient) => {
   invalidate(queryClient, queryKeys.tasks);
 };
 
+export const invalidatePomodoroDomain = (queryClient: QueryClient) => {
+  invalidate(queryClient, queryKeys.pomodoroStatus);
+  invalidate(queryClient, queryKeys.pomodoroHeatmap);
+  invalidate(queryClient, queryKeys.meetingLoad());
+};
+
+export const invalidateJournalPromptDomain = (queryClient: QueryClient) => {
+  invalidate(queryClient, queryKeys.journalPrompts);
+  invalidate(queryClient, queryKeys.dailyPrompt);
+};
+
+export const invalidateMoodDomain = (queryClient: QueryClient) => {
+  invalidate(queryClient, queryKeys.dailyMoods);
+  invalidate(queryClient, queryKeys.moodCorrelations);
+};
+
 export const invalidateEntryDomain = (queryClient: QueryClient, date?: string) => {
   invalidate(queryClient, queryKeys.entries);
   invalidate(queryClient, queryKeys.entry(date));
   invalidate(queryClient, queryKeys.search);
+  invalidate(queryClient, queryKeys.entryTags);
+  invalidate(queryClient, queryKeys.entrySections(date));
+  invalidate(queryClient, queryKeys.entryCommits(date));
+  invalidate(queryClient, queryKeys.entryNotes(date));
+  invalidate(queryClient, queryKeys.entryAttachments());
 };
 
 export const invalidateAllDomainQueries = (queryClient: QueryClient) => {
@@ -69,4 +157,8 @@ export const invalidateAllDomainQueries = (queryClient: QueryClient) => {
   invalidate(queryClient, queryKeys.projects);
   invalidate(queryClient, queryKeys.projectBranches);
   invalidate(queryClient, queryKeys.meetings);
+  invalidatePomodoroDomain(queryClient);
+  invalidate(queryClient, queryKeys.countdowns);
+  invalidateMoodDomain(queryClient);
+  invalidate(queryClient, queryKeys.appSettings);
 };
@@ -0,0 +1,15 @@
+import { useQuery } from "@tanstack/react-query";
+import * as api from "../api";
+import type { AccessibleSummaryView } from "../types";
+import { queryKeys } from "./queryInvalidation";
+
+// Backend-composed sentence for screen readers; polled so the live region follows
+// changes made in other windows and by background jobs.
+export const useAccessibleSummary = (view: AccessibleSummaryView | null) => {
+  return useQuery({
+    queryKey: queryKeys.accessibleSummary(view ?? ""),
+    queryFn: () => api.getAccessibleSummary(view as AccessibleSummaryView),
+    enabled: view !== null,
+    refetchInterval: 30_000,
+  });
+};
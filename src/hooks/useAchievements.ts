@@ -0,0 +1,11 @@
+import { useQuery } from "@tanstack/react-query";
+import * as api from "../api";
+import { queryKeys } from "./queryInvalidation";
+
+// Refreshed by the `achievements-unlocked` backend event.
+export const useAchievements = () => {
+  return useQuery({
+    queryKey: queryKeys.achievements,
+    queryFn: api.getAchievements,
+  });
+};
@@ -0,0 +1,46 @@
+import { useMutation, useQuery, useQueryClient } from "@tanstack/react-query";
+import * as api from "../api";
+import type { ApiScopePreset } from "../types";
+import { queryKeys } from "./queryInvalidation";
+
+export const useApiTokens = () => {
+  return useQuery({
+    queryKey: queryKeys.apiTokens,
+    queryFn: api.listApiTokens,
+  });
+};
+
+// What automation touched the journal, newest first.
+export const useExternalAccessLog = (limit = 20) => {
+  return useQuery({
+    queryKey: [...queryKeys.externalAccessLog, limit],
+    queryFn: () => api.getExternalAccessLog(limit),
+    refetchInterval: 30_000,
+  });
+};
+
+export const useCreateApiToken = () => {
+  const queryClient = useQueryClient();
+
+  return useMutation({
+    mutationFn: ({
+      name,
+      scopes,
+      preset,
+    }: {
+      name: string;
+      scopes: string[] | null;
+      preset: ApiScopePreset | null;
+    }) => api.createApiToken(name, scopes, preset),
+    onSuccess: () => queryClient.invalidateQueries({ queryKey: queryKeys.apiTokens }),
+  });
+};
+
+export const useRevokeApiToken = () => {
+  const queryClient = useQueryClient();
+
+  return useMutation({
+    mutationFn: (id: number) => api.revokeApiToken(id),
+    onSuccess: () => queryClient.invalidateQueries({ queryKey: queryKeys.apiTokens }),
+  });
+};
@@ -0,0 +1,99 @@
+import { useMutation, useQuery, useQueryClient } from "@tanstack/react-query";
+import * as api from "../api";
+import { getISOWeek, getWeek } from "date-fns";
+import { queryKeys } from "./queryInvalidation";
+import type { WeekNumbering } from "../types";
+
+export const useAppSettings = () => {
+  return useQuery({
+    queryKey: queryKeys.appSettings,
+    queryFn: api.getAppSettings,
+  });
+};
+
+// Reads one backend setting; `undefined` until settings have loaded.
+export const useAppSetting = (key: string) => {
+  const { data } = useAppSettings();
+  return data?.find((setting) => setting.key === key)?.value;
+};
+
+// Week start and numbering from the `week_numbering` setting, matching the backend's weeks.
+export const useWeekNumbering = () => {
+  const numbering: WeekNumbering = useAppSetting("week_numbering") === "us" ? "us" : "iso";
+  const weekStartsOn: 0 | 1 = numbering === "us" ? 0 : 1;
+  const weekNumber = (date: Date) =>
+    numbering === "us" ? getWeek(date, { weekStartsOn: 0, firstWeekContainsDate: 1 }) : getISOWeek(date);
+  return { numbering, weekStartsOn, weekNumber };
+};
+
+export const useWeekInfo = (date?: string) => {
+  return useQuery({
+    queryKey: [...queryKeys.weekInfo, date ?? "today"],
+    queryFn: () => api.getWeekInfo(date),
+  });
+};
+
+export const useNotificationTemplates = () => {
+  return useQuery({
+    queryKey: queryKeys.notificationTemplates,
+    queryFn: api.getNotificationTemplates,
+  });
+};
+
+export const useUpdateAppSetting = (invalidateKeys: ReadonlyArray<readonly unknown[]> = []) => {
+  const queryClient = useQueryClient();
+
+  return useMutation({
+    mutationFn: ({ key, value }: { key: string; value: string }) => api.updateAppSetting(key, value),
+    onSuccess: () => {
+      queryClient.invalidateQueries({ queryKey: queryKeys.appSettings });
+      invalidateKeys.forEach((queryKey) => queryClient.invalidateQueries({ queryKey }));
+    },
+  });
+};
+
+// Release notes and data migrations since the last version marked as seen.
+export const useWhatsNew = () => {
+  return useQuery({
+    queryKey: queryKeys.whatsNew,
+    queryFn: () => api.getWhatsNew(null),
+    staleTime: Infinity,
+  });
+};
+
+export const useMarkWhatsNewSeen = () => {
+  const queryClient = useQueryClient();
+
+  return useMutation({
+    mutationFn: api.markWhatsNewSeen,
+    onSuccess: () => {
+      queryClient.invalidateQueries({ queryKey: queryKeys.appSettings });
+    },
+  });
+};
+
+// Experimental subsystems; each ships disabled until turned on here.
+export const useFeatureFlags = () => {
+  return useQuery({
+    queryKey: queryKeys.featureFlags,
+    queryFn: api.getFlags,
+  });
+};
+
+export const useSetFeatureFlag = () => {
+  const queryClient = useQueryClient();
+
+  return useMutation({
+    mutationFn: ({ key, enabled }: { key: string; enabled: boolean }) => api.setFlag(key, enabled),
+    onSuccess: (flags) => {
+      queryClient.setQueryData(queryKeys.featureFlags, flags);
+    },
+  });
+};
+
+// Manual "Check for updates"; the opt-in `update_check` job notifies on its own.
+export const useCheckForUpdates = () => {
+  return useMutation({
+    mutationFn: api.checkForUpdates,
+  });
+};
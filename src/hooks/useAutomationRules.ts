@@ -0,0 +1,76 @@
+import { useMutation, useQuery, useQueryClient } from "@tanstack/react-query";
+import * as api from "../api";
+import type { AutomationRuleInput } from "../types";
+import { invalidateTaskDomain, queryKeys } from "./queryInvalidation";
+
+// All keys sit under `tasks`, so task edits and scheduler runs refresh the preview and log too.
+export const useTaskTags = () => {
+  return useQuery({
+    queryKey: queryKeys.taskTags,
+    queryFn: api.getTaskTags,
+  });
+};
+
+export const useAutomationRules = () => {
+  return useQuery({
+    queryKey: queryKeys.automationRules,
+    queryFn: api.getAutomationRules,
+  });
+};
+
+// Dry run of the enabled rules against the current tasks.
+export const useAutomationPreview = () => {
+  return useQuery({
+    queryKey: queryKeys.automationPreview,
+    queryFn: api.previewAutomationRules,
+  });
+};
+
+export const useAutomationLog = (limit = 20) => {
+  return useQuery({
+    queryKey: [...queryKeys.automationLog, limit],
+    queryFn: () => api.getAutomationLog(limit),
+  });
+};
+
+const useInvalidateRules = () => {
+  const queryClient = useQueryClient();
+  return () => queryClient.invalidateQueries({ queryKey: queryKeys.automationRules });
+};
+
+export const useCreateAutomationRule = () => {
+  const invalidateRules = useInvalidateRules();
+
+  return useMutation({
+    mutationFn: (input: AutomationRuleInput) => api.createAutomationRule(input),
+    onSuccess: invalidateRules,
+  });
+};
+
+export const useUpdateAutomationRule = () => {
+  const invalidateRules = useInvalidateRules();
+
+  return useMutation({
+    mutationFn: ({ id, input }: { id: number; input: AutomationRuleInput }) =>
+      api.updateAutomationRule(id, input),
+    onSuccess: invalidateRules,
+  });
+};
+
+export const useDeleteAutomationRule = () => {
+  const invalidateRules = useInvalidateRules();
+
+  return useMutation({
+    mutationFn: api.deleteAutomationRule,
+    onSuccess: invalidateRules,
+  });
+};
+
+export const useRunAutomationRules = () => {
+  const queryClient = useQueryClient();
+
+  return useMutation({
+    mutationFn: api.runAutomationRules,
+    onSuccess: () => invalidateTaskDomain(queryClient),
+  });
+};
@@ -0,0 +1,120 @@
+import { useEffect } from "react";
+import type { UnlistenFn } from "@tauri-apps/api/event";
+import { useQueryClient } from "@tanstack/react-query";
+import * as api from "../api";
+import { listenTopic } from "../api/events";
+import { useI18n } from "../i18n/I18nContext";
+import { useAppNotifications } from "../notifications/AppNotifications";
+import type {
+  Achievement,
+  BreakReminder,
+  CaptureResult,
+  Presence,
+  TaskDueReminder,
+  UpdateCheck,
+  WeeklyDigest,
+} from "../types";
+import { invalidateTaskDomain, queryKeys } from "./queryInvalidation";
+
+// Keeps React Query caches in sync with changes made by backend background jobs.
+// Every window subscribes to these topics; detached windows pass `notifications: false` so each
+// toast only shows once, in the main window.
+export const useBackendEvents = ({ notifications = true }: { notifications?: boolean } = {}) => {
+  const queryClient = useQueryClient();
+  const { notify: showNotification } = useAppNotifications();
+  const { t } = useI18n();
+
+  useEffect(() => {
+    const notify: typeof showNotification = (...args) => {
+      if (notifications) showNotification(...args);
+    };
+    const unlisteners: Promise<UnlistenFn>[] = [
+      listenTopic("linked-items-changed", () => invalidateTaskDomain(queryClient)),
+      listenTopic("timers-changed", () => invalidateTaskDomain(queryClient)),
+      listenTopic<Presence>("presence-changed", (event) => queryClient.setQueryData(queryKeys.presence, event.payload)),
+      listenTopic("automation-rules-applied", () => invalidateTaskDomain(queryClient)),
+      listenTopic("tasks-rolled-over", () => invalidateTaskDomain(queryClient)),
+      listenTopic<number>("integrity-violations", (event) => {
+        queryClient.invalidateQueries({ queryKey: queryKeys.integrityReport });
+        notify(
+          t("The data check found {count} problems. See Settings for details.", { count: event.payload }),
+          "warning"
+        );
+      }),
+      listenTopic("habits-changed", () => queryClient.invalidateQueries({ queryKey: queryKeys.habits })),
+      listenTopic("streaks-at-risk", () => queryClient.invalidateQueries({ queryKey: queryKeys.streaksAtRisk })),
+      listenTopic("goal-checkin-due", () => queryClient.invalidateQueries({ queryKey: queryKeys.goalCheckins })),
+      listenTopic("journal-reminder", () => notify(t("You haven't written today's entry"), "info")),
+      listenTopic("countdowns-changed", () => queryClient.invalidateQueries({ queryKey: queryKeys.countdowns })),
+      listenTopic<WeeklyDigest>("digest-ready", (event) =>
+        notify(
+          t("Last 7 days: {entries} journal entries, {tasks_done} tasks done, {overdue} overdue.", {
+            entries: event.payload.entries,
+            tasks_done: event.payload.tasks_done,
+            overdue: event.payload.overdue,
+          }),
+          "info"
+        )
+      ),
+      listenTopic<UpdateCheck>("update-available", (event) =>
+        notify(t("Dev Journal {version} is available.", { version: event.payload.latest_version ?? "" }), "info")
+      ),
+      listenTopic<CaptureResult>("capture-received", (event) => {
+        if (event.payload.kind === "task") {
+          queryClient.invalidateQueries({ queryKey: queryKeys.reviewQueue });
+          notify(
+            event.payload.duplicates.length > 0
+              ? t("A task clipped from the browser is waiting for review; it looks like \"{title}\".", {
+                  title: event.payload.duplicates[0].title,
+                })
+              : t("A task clipped from the browser is waiting for review."),
+            "info"
+          );
+        } else {
+          queryClient.invalidateQueries({ queryKey: queryKeys.bookmarks });
+        }
+      }),
+      listenTopic<Achievement[]>("achievements-unlocked", (event) => {
+        queryClient.invalidateQueries({ queryKey: queryKeys.achievements });
+        event.payload.forEach((achievement) =>
+          notify(t("Achievement unlocked: {title}", { title: achievement.title }), "success")
+        );
+      }),
+      listenTopic<TaskDueReminder[]>("task-due-reminder", (event) =>
+        event.payload.forEach((reminder) =>
+          notify(t("\"{title}\" is due {date}.", { title: reminder.title, date: reminder.due_date }), "info", {
+            label: t("Snooze 1h"),
+            onClick: () => {
+              api.snoozeTaskDueReminder(reminder.task_id, 60).catch(() => undefined);
+            },
+          })
+        )
+      ),
+      listenTopic<BreakReminder>("break-reminder", (event) => {
+        const reminder = event.payload;
+        notify(
+          t("Time for a break: \"{title}\" has been running for {minutes} min.", {
+            title: reminder.title,
+            minutes: reminder.running_minutes,
+          }),
+          "warning",
+          {
+            label: t("Pause timer"),
+            onClick: () => {
+              api
+                .pauseTaskTimer(reminder.task_id)
+                .then(() => invalidateTaskDomain(queryClient))
+                .catch(() => undefined);
+            },
+          }
+        );
+      }),
+    ];
+
+    return () => {
+      unlisteners.forEach((unlisten) => {
+        unlisten.then((fn) => fn()).catch(() => undefined);
+      });
+    };
+  }, [queryClient, notifications, showNotification, t]);
+};
@@ -0,0 +1,20 @@
+import { useMutation, useQuery, useQueryClient } from "@tanstack/react-query";
+import * as api from "../api";
+import { queryKeys } from "./queryInvalidation";
+
+// Links clipped through the browser extension's localhost capture endpoint.
+export const useBookmarks = () => {
+  return useQuery({
+    queryKey: queryKeys.bookmarks,
+    queryFn: api.getBookmarks,
+  });
+};
+
+export const useDeleteBookmark = () => {
+  const queryClient = useQueryClient();
+
+  return useMutation({
+    mutationFn: (id: number) => api.deleteBookmark(id),
+    onSuccess: () => queryClient.invalidateQueries({ queryKey: queryKeys.bookmarks }),
+  });
+};
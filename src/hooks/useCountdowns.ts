@@ -0,0 +1,29 @@
+import { useMutation, useQuery, useQueryClient } from "@tanstack/react-query";
+import * as api from "../api";
+import { queryKeys } from "./queryInvalidation";
+
+export const useActiveCountdowns = () => {
+  return useQuery({
+    queryKey: queryKeys.countdowns,
+    queryFn: api.getActiveCountdowns,
+  });
+};
+
+export const useStartCountdown = () => {
+  const queryClient = useQueryClient();
+
+  return useMutation({
+    mutationFn: ({ label, minutes }: { label: string; minutes: number }) =>
+      api.startCountdown(label, minutes),
+    onSuccess: () => queryClient.invalidateQueries({ queryKey: queryKeys.countdowns }),
+  });
+};
+
+export const useCancelCountdown = () => {
+  const queryClient = useQueryClient();
+
+  return useMutation({
+    mutationFn: api.cancelCountdown,
+    onSuccess: () => queryClient.invalidateQueries({ queryKey: queryKeys.countdowns }),
+  });
+};
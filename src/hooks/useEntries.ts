@@ -1,7 +1,13 @@
-import { useQuery, useMutation, useQueryClient } from "@tanstack/react-query";
+import { useInfiniteQuery, useQuery, useMutation, useQueryClient } from "@tanstack/react-query";
 import { api } from "../api";
-import { BackupPayload } from "../types";
-import { invalidateAllDomainQueries, invalidateEntryDomain, queryKeys } from "./queryInvalidation";
+import { BackupPayload, DateShiftFilter, EntryDateRange, ExportFilter, ExportFormat } from "../types";
+import {
+    invalidateAllDomainQueries,
+    invalidateEntryDomain,
+    invalidateMoodDomain,
+    invalidateTaskDomain,
+    queryKeys,
+} from "./queryInvalidation";
 
 export const useEntries = () => {
     return useQuery({
@@ -10,6 +16,40 @@ export const useEntries = () => {
     });
 };
 
+// Keyed under `entries` so entry saves and imports refresh every loaded range.
+export const useEntriesBetween = (start: string, end: string) => {
+    return useQuery({
+        queryKey: [...queryKeys.entries, "between", start, end],
+        queryFn: () => api.getEntriesBetween(start, end),
+    });
+};
+
+// Newest-first slice of history; prefer this over `useEntries` when only recent entries are shown.
+export const useEntriesPage = (limit: number, offset = 0) => {
+    return useQuery({
+        queryKey: [...queryKeys.entries, "page", limit, offset],
+        queryFn: () => api.getEntriesPage(limit, offset),
+    });
+};
+
+// Lazily loads history one page at a time via `fetchNextPage`.
+export const useInfiniteEntries = (pageSize = 30) => {
+    return useInfiniteQuery({
+        queryKey: [...queryKeys.entries, "infinite", pageSize],
+        queryFn: ({ pageParam }) => api.getEntriesPage(pageSize, pageParam),
+        initialPageParam: 0,
+        getNextPageParam: (lastPage, allPages) =>
+            lastPage.length < pageSize ? undefined : allPages.length * pageSize,
+    });
+};
+
+export const useEntryCount = () => {
+    return useQuery({
+        queryKey: [...queryKeys.entries, "count"],
+        queryFn: api.countEntries,
+    });
+};
+
 export const useEntry = (date: string) => {
     return useQuery({
         queryKey: queryKeys.entry(date),
@@ -25,12 +65,96 @@ export const useSearchEntries = (query: string) => {
     });
 };
 
+export const useEntryTags = () => {
+    return useQuery({
+        queryKey: queryKeys.entryTags,
+        queryFn: api.getEntryTags,
+    });
+};
+
+export const useEntriesByTag = (tag: string) => {
+    return useQuery({
+        queryKey: [...queryKeys.entryTags, "entries", tag],
+        queryFn: () => api.getEntriesByTag(tag),
+        enabled: tag.length > 0,
+    });
+};
+
+export const useAddEntryTag = () => {
+    const queryClient = useQueryClient();
+
+    return useMutation({
+        mutationFn: ({ entryId, tag }: { entryId: number, tag: string }) => {
+            return api.addEntryTag(entryId, tag);
+        },
+        onSuccess: () => {
+            queryClient.invalidateQueries({ queryKey: queryKeys.entryTags });
+        },
+    });
+};
+
+export const useRemoveEntryTag = () => {
+    const queryClient = useQueryClient();
+
+    return useMutation({
+        mutationFn: ({ entryId, tag }: { entryId: number, tag: string }) => {
+            return api.removeEntryTag(entryId, tag);
+        },
+        onSuccess: () => {
+            queryClient.invalidateQueries({ queryKey: queryKeys.entryTags });
+        },
+    });
+};
+
+export const useEntrySectionTemplates = () => {
+    return useQuery({
+        queryKey: queryKeys.entrySectionTemplates,
+        queryFn: api.getEntrySectionTemplates,
+    });
+};
+
+export const useSaveEntrySectionTemplates = () => {
+    const queryClient = useQueryClient();
+
+    return useMutation({
+        mutationFn: (templates: { section_key?: string | null, label: string }[]) => {
+            return api.saveEntrySectionTemplates(templates);
+        },
+        onSuccess: (templates) => {
+            queryClient.setQueryData(queryKeys.entrySectionTemplates, templates);
+        },
+    });
+};
+
+export const useEntrySections = (date: string) => {
+    return useQuery({
+        queryKey: queryKeys.entrySections(date),
+        queryFn: () => api.getEntrySections(date),
+    });
+};
+
 export const useSaveEntry = () => {
     const queryClient = useQueryClient();
 
     return useMutation({
-        mutationFn: ({ date, yesterday, today, project_id }: { date: string, yesterday: string, today: string, project_id: number | null }) => {
-            return api.saveEntry(date, yesterday, today, project_id);
+        mutationFn: async ({ date, yesterday, today, blockers, notes, project_id, sections, commits }: {
+            date: string,
+            yesterday: string,
+            today: string,
+            blockers?: string,
+            notes?: string,
+            project_id: number | null,
+            sections?: { section_key: string, content: string }[],
+            commits?: string[],
+        }) => {
+            await api.saveEntry(date, yesterday, today, project_id, blockers, notes);
+            if (sections && sections.length > 0) {
+                await api.saveEntrySections(date, sections);
+            }
+            // Snapshot the day's commits so they survive switching machines or repos.
+            if (commits && commits.length > 0) {
+                await api.saveEntryCommits(date, commits);
+            }
         },
         onSuccess: (_, variables) => {
             invalidateEntryDomain(queryClient, variables.date);
@@ -38,6 +162,55 @@ export const useSaveEntry = () => {
     });
 };
 
+// Autosaves never touch the query cache: EntryForm reads the draft once per date, and
+// refetching mid-typing would overwrite newer keystrokes.
+export const useSaveEntryDraft = () => {
+    return useMutation({
+        mutationFn: ({ date, yesterday, today, blockers, notes }: {
+            date: string;
+            yesterday: string;
+            today: string;
+            blockers: string;
+            notes: string;
+        }) => api.saveEntryDraft(date, yesterday, today, blockers, notes),
+    });
+};
+
+export const useDiscardEntryDraft = () => {
+    return useMutation({
+        mutationFn: (date: string) => api.discardEntryDraft(date),
+    });
+};
+
+export const useEntryNotes = (date: string) => {
+    return useQuery({
+        queryKey: queryKeys.entryNotes(date),
+        queryFn: () => api.getEntryNotes(date),
+    });
+};
+
+export const useAppendEntryNote = () => {
+    const queryClient = useQueryClient();
+
+    return useMutation({
+        mutationFn: ({ date, text }: { date: string; text: string }) => api.appendEntryNote(date, text),
+        onSuccess: (_, variables) => {
+            queryClient.invalidateQueries({ queryKey: queryKeys.entryNotes(variables.date) });
+        },
+    });
+};
+
+export const useDeleteEntryNote = () => {
+    const queryClient = useQueryClient();
+
+    return useMutation({
+        mutationFn: ({ id }: { id: number; date: string }) => api.deleteEntryNote(id),
+        onSuccess: (_, variables) => {
+            queryClient.invalidateQueries({ queryKey: queryKeys.entryNotes(variables.date) });
+        },
+    });
+};
+
 export const useDeleteEntry = () => {
     const queryClient = useQueryClient();
 
@@ -51,13 +224,284 @@ export const useDeleteEntry = () => {
     });
 };
 
+// Keyed under `entries` so edits and deletes refresh the pinned list too.
+export const usePinnedEntries = () => {
+    return useQuery({
+        queryKey: [...queryKeys.entries, "pinned"],
+        queryFn: api.getPinnedEntries,
+    });
+};
+
+export const useSetEntryPinned = () => {
+    const queryClient = useQueryClient();
+
+    return useMutation({
+        mutationFn: ({ date, pinned }: { date: string; pinned: boolean }) => api.setEntryPinned(date, pinned),
+        onSuccess: (_, { date }) => {
+            invalidateEntryDomain(queryClient, date);
+        },
+    });
+};
+
+// Keyed under `entries` so deleting an entry shows up in the trash right away.
+export const useTrashedEntries = () => {
+    return useQuery({
+        queryKey: [...queryKeys.entries, "trash"],
+        queryFn: api.getTrashedEntries,
+    });
+};
+
+export const useRestoreEntry = () => {
+    const queryClient = useQueryClient();
+
+    return useMutation({
+        mutationFn: (date: string) => api.restoreEntry(date),
+        onSuccess: (_, date) => {
+            invalidateEntryDomain(queryClient, date);
+        },
+    });
+};
+
+export const usePurgeTrash = () => {
+    const queryClient = useQueryClient();
+
+    return useMutation({
+        mutationFn: api.purgeTrash,
+        onSuccess: () => {
+            queryClient.invalidateQueries({ queryKey: queryKeys.entries });
+        },
+    });
+};
+
+// `monthDay` is `MM-DD`; returns that date in earlier years, newest first.
+export const useEntriesOnThisDay = (monthDay: string) => {
+    return useQuery({
+        queryKey: [...queryKeys.entries, "on-this-day", monthDay],
+        queryFn: () => api.getEntriesOnThisDay(monthDay),
+    });
+};
+
+// Picked on demand, so each call can land on a different entry.
+export const useRandomEntry = () => {
+    return useMutation({
+        mutationFn: (olderThanDays?: number) => api.getRandomEntry(olderThanDays),
+    });
+};
+
 export const useGitCommits = () => {
     return useQuery({
-        queryKey: ["commits"],
+        queryKey: queryKeys.gitCommits,
         queryFn: api.getGitCommits,
     });
 };
 
+// First day of the standup window (skips weekends and configured days off).
+export const usePreviousWorkday = () => {
+    return useQuery({
+        queryKey: queryKeys.previousWorkday,
+        queryFn: api.getPreviousWorkday,
+    });
+};
+
+// Keyed under `entries` so saving or deleting an entry refreshes the streak.
+export const useEntryStreaks = () => {
+    return useQuery({
+        queryKey: [...queryKeys.entries, "streaks"],
+        queryFn: api.getEntryStreaks,
+    });
+};
+
+export const useWritingStats = (range: EntryDateRange | null = null) => {
+    return useQuery({
+        queryKey: [...queryKeys.entries, "writing-stats", range],
+        queryFn: () => api.getWritingStats(range),
+    });
+};
+
+export const useEntryCommits = (date: string) => {
+    return useQuery({
+        queryKey: queryKeys.entryCommits(date),
+        queryFn: () => api.getEntryCommits(date),
+    });
+};
+
+// Writes files only; nothing in the cache changes.
+export const useExportEntriesMarkdown = () => {
+    return useMutation({
+        mutationFn: ({ dir, range }: { dir: string; range: EntryDateRange | null }) =>
+            api.exportEntriesMarkdown(dir, range),
+    });
+};
+
+export const useExportFiltered = () => {
+    return useMutation({
+        mutationFn: ({ dir, format, filter }: { dir: string; format: ExportFormat; filter: ExportFilter }) =>
+            api.exportFiltered(dir, format, filter),
+    });
+};
+
+export const useImportDayOne = () => {
+    const queryClient = useQueryClient();
+
+    return useMutation({
+        mutationFn: (path: string) => api.importDayOne(path),
+        onSuccess: () => invalidateEntryDomain(queryClient),
+    });
+};
+
+export const useImportSlackStandups = () => {
+    const queryClient = useQueryClient();
+
+    return useMutation({
+        mutationFn: ({ path, author }: { path: string; author: string }) => api.importSlackStandups(path, author),
+        onSuccess: () => invalidateEntryDomain(queryClient),
+    });
+};
+
+export const useImportOrg = () => {
+    const queryClient = useQueryClient();
+
+    return useMutation({
+        mutationFn: ({ path, parentId }: { path: string; parentId: number | null }) => api.importOrg(path, parentId),
+        onSuccess: () => {
+            invalidateTaskDomain(queryClient);
+            queryClient.invalidateQueries({ queryKey: queryKeys.pages });
+        },
+    });
+};
+
+export const useImportObsidianVault = () => {
+    const queryClient = useQueryClient();
+
+    return useMutation({
+        mutationFn: (dir: string) => api.importObsidianVault(dir),
+        onSuccess: () => {
+            invalidateEntryDomain(queryClient);
+            queryClient.invalidateQueries({ queryKey: queryKeys.pages });
+        },
+    });
+};
+
+export const useExportOrg = () => {
+    return useMutation({
+        mutationFn: (path: string) => api.exportOrg(path),
+    });
+};
+
+export const useImportTodoTxt = () => {
+    const queryClient = useQueryClient();
+
+    return useMutation({
+        mutationFn: (path: string) => api.importTodoTxt(path),
+        onSuccess: () => invalidateTaskDomain(queryClient),
+    });
+};
+
+export const useExportTodoTxt = () => {
+    return useMutation({
+        mutationFn: (path: string) => api.exportTodoTxt(path),
+    });
+};
+
+export const useImportMarkdownFolder = () => {
+    const queryClient = useQueryClient();
+
+    return useMutation({
+        mutationFn: (dir: string) => api.importMarkdownFolder(dir),
+        onSuccess: () => {
+            invalidateEntryDomain(queryClient);
+            invalidateMoodDomain(queryClient);
+        },
+    });
+};
+
+export const usePreviewDateShift = () => {
+    return useMutation({
+        mutationFn: ({ filter, deltaDays }: { filter: DateShiftFilter, deltaDays: number }) =>
+            api.previewDateShift(filter, deltaDays),
+    });
+};
+
+export const useShiftDates = () => {
+    const queryClient = useQueryClient();
+
+    return useMutation({
+        mutationFn: ({ filter, deltaDays }: { filter: DateShiftFilter, deltaDays: number }) =>
+            api.shiftDates(filter, deltaDays),
+        onSuccess: () => invalidateAllDomainQueries(queryClient),
+    });
+};
+
+export const useEntryAttachments = (entryId: number | undefined) => {
+    return useQuery({
+        queryKey: queryKeys.entryAttachments(entryId),
+        queryFn: () => api.listEntryAttachments(entryId as number),
+        enabled: entryId !== undefined,
+    });
+};
+
+export const useAttachFileToEntry = () => {
+    const queryClient = useQueryClient();
+
+    return useMutation({
+        mutationFn: ({ entryId, path }: { entryId: number; path: string }) => api.attachFileToEntry(entryId, path),
+        onSuccess: (_attachment, { entryId }) => {
+            queryClient.invalidateQueries({ queryKey: queryKeys.entryAttachments(entryId) });
+        },
+    });
+};
+
+export const useAttachmentText = (id: number, enabled: boolean) => {
+    return useQuery({
+        queryKey: queryKeys.attachmentText(id),
+        queryFn: () => api.getAttachmentText(id),
+        enabled,
+        staleTime: Infinity,
+    });
+};
+
+export const useDeleteAttachment = () => {
+    const queryClient = useQueryClient();
+
+    return useMutation({
+        mutationFn: (id: number) => api.deleteAttachment(id),
+        onSuccess: () => {
+            queryClient.invalidateQueries({ queryKey: queryKeys.entryAttachments() });
+        },
+    });
+};
+
+export const useGenerateWeeklyReview = () => {
+    return useMutation({
+        mutationFn: (weekStart: string) => api.generateWeeklyReview(weekStart),
+    });
+};
+
+export const useSyncVaultMirror = () => {
+    const queryClient = useQueryClient();
+
+    return useMutation({
+        mutationFn: () => api.syncVaultMirror(),
+        onSuccess: () => queryClient.invalidateQueries({ queryKey: queryKeys.mirrorHistory() }),
+    });
+};
+
+export const useMirrorHistory = (path: string, enabled: boolean) => {
+    return useQuery({
+        queryKey: queryKeys.mirrorHistory(path),
+        queryFn: () => api.getMirrorHistory(path),
+        enabled,
+        retry: false,
+    });
+};
+
+// Writes a file only; nothing in the cache changes.
+export const useGenerateDailySheet = () => {
+    return useMutation({
+        mutationFn: ({ date, path }: { date: string; path: string }) => api.generateDailySheet(date, path),
+    });
+};
+
 export const useImportBackup = () => {
     const queryClient = useQueryClient();
 
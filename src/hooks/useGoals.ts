@@ -17,6 +17,57 @@ export const useGoalMilestones = (goalId: number | null) => {
   });
 };
 
+// Lives under the `goals` key so goal and milestone edits refresh it.
+export const useGoalsNeedingCheckin = () => {
+  return useQuery({
+    queryKey: queryKeys.goalCheckins,
+    queryFn: api.getGoalsNeedingCheckin,
+  });
+};
+
+export const useCheckInGoal = () => {
+  const queryClient = useQueryClient();
+
+  return useMutation({
+    mutationFn: ({ goal_id, progress }: { goal_id: number; progress: number }) => api.checkInGoal(goal_id, progress),
+    onSuccess: () => invalidateGoalDomain(queryClient),
+  });
+};
+
+export const useGoalReflections = (goalId: number | null) => {
+  return useQuery({
+    queryKey: [...queryKeys.goalReflections, goalId ?? "all"],
+    queryFn: () => api.getGoalReflections(goalId),
+  });
+};
+
+// Lessons from past goals whose titles share keywords with `title`.
+export const useSimilarGoalReflections = (title: string) => {
+  const trimmed = title.trim();
+  return useQuery({
+    queryKey: [...queryKeys.goalReflections, "similar", trimmed],
+    queryFn: () => api.getSimilarGoalReflections(trimmed),
+    enabled: trimmed.length >= 3,
+  });
+};
+
+export const useSaveGoalReflection = () => {
+  const queryClient = useQueryClient();
+
+  return useMutation({
+    mutationFn: ({
+      goal_id,
+      what_worked,
+      what_didnt,
+    }: {
+      goal_id: number;
+      what_worked: string;
+      what_didnt: string;
+    }) => api.saveGoalReflection(goal_id, what_worked, what_didnt),
+    onSuccess: () => queryClient.invalidateQueries({ queryKey: queryKeys.goalReflections }),
+  });
+};
+
 export const useCreateGoal = () => {
   const queryClient = useQueryClient();
 
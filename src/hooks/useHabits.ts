@@ -9,6 +9,14 @@ export const useHabits = () => {
   });
 };
 
+// Lives under the `habits` key so every habit mutation refreshes it.
+export const useStreaksAtRisk = () => {
+  return useQuery({
+    queryKey: queryKeys.streaksAtRisk,
+    queryFn: api.getStreaksAtRisk,
+  });
+};
+
 export const useCreateHabit = () => {
   const queryClient = useQueryClient();
 
@@ -74,3 +82,13 @@ export const useToggleHabitCompletion = () => {
     onSuccess: () => queryClient.invalidateQueries({ queryKey: queryKeys.habits }),
   });
 };
+
+export const useLogHabitsBulk = () => {
+  const queryClient = useQueryClient();
+
+  return useMutation({
+    mutationFn: ({ date, habit_ids }: { date: string; habit_ids: number[] }) =>
+      api.logHabitsBulk(date, habit_ids),
+    onSuccess: () => queryClient.invalidateQueries({ queryKey: queryKeys.habits }),
+  });
+};
@@ -0,0 +1,50 @@
+import { useMutation, useQuery, useQueryClient, type QueryClient } from "@tanstack/react-query";
+import * as api from "../api";
+import type { IncidentInput } from "../types";
+import { queryKeys } from "./queryInvalidation";
+
+const invalidateIncidents = (queryClient: QueryClient) => {
+  queryClient.invalidateQueries({ queryKey: queryKeys.incidents });
+  queryClient.invalidateQueries({ queryKey: ["incident-timeline"] });
+};
+
+export const useIncidents = () => {
+  return useQuery({
+    queryKey: queryKeys.incidents,
+    queryFn: api.getIncidents,
+  });
+};
+
+export const useIncidentTimeline = (from: string, to: string) => {
+  return useQuery({
+    queryKey: queryKeys.incidentTimeline(from, to),
+    queryFn: () => api.getIncidentTimeline(from, to),
+  });
+};
+
+export const useCreateIncident = () => {
+  const queryClient = useQueryClient();
+
+  return useMutation({
+    mutationFn: (input: IncidentInput) => api.createIncident(input),
+    onSuccess: () => invalidateIncidents(queryClient),
+  });
+};
+
+export const useUpdateIncident = () => {
+  const queryClient = useQueryClient();
+
+  return useMutation({
+    mutationFn: ({ id, input }: { id: number; input: IncidentInput }) => api.updateIncident(id, input),
+    onSuccess: () => invalidateIncidents(queryClient),
+  });
+};
+
+export const useDeleteIncident = () => {
+  const queryClient = useQueryClient();
+
+  return useMutation({
+    mutationFn: (id: number) => api.deleteIncident(id),
+    onSuccess: () => invalidateIncidents(queryClient),
+  });
+};
@@ -0,0 +1,26 @@
+import { useMutation, useQuery, useQueryClient } from "@tanstack/react-query";
+import * as api from "../api";
+import { invalidateAllDomainQueries, queryKeys } from "./queryInvalidation";
+
+// The scheduled check stores its result, so this only reads the last report.
+export const useIntegrityReport = () => {
+  return useQuery({
+    queryKey: queryKeys.integrityReport,
+    queryFn: api.getIntegrityReport,
+  });
+};
+
+export const useVerifyIntegrity = () => {
+  const queryClient = useQueryClient();
+
+  return useMutation({
+    mutationFn: (autoFix: boolean) => api.verifyIntegrity(autoFix),
+    onSuccess: (report, autoFix) => {
+      queryClient.setQueryData(queryKeys.integrityReport, report);
+      // Fixes can touch goals, tasks and habits.
+      if (autoFix) {
+        invalidateAllDomainQueries(queryClient);
+      }
+    },
+  });
+};
@@ -0,0 +1,37 @@
+import { useMutation, useQuery, useQueryClient } from "@tanstack/react-query";
+import * as api from "../api";
+import { JournalEncryptionStatus } from "../types";
+import { invalidateAllDomainQueries, queryKeys } from "./queryInvalidation";
+
+export const useJournalEncryptionStatus = () => {
+  return useQuery({
+    queryKey: queryKeys.journalEncryption,
+    queryFn: api.getJournalEncryptionStatus,
+  });
+};
+
+// Every change of lock state alters what entry and page queries can return.
+const useEncryptionMutation = <TVariables>(
+  mutationFn: (variables: TVariables) => Promise<JournalEncryptionStatus>
+) => {
+  const queryClient = useQueryClient();
+
+  return useMutation({
+    mutationFn,
+    onSuccess: (status) => {
+      queryClient.setQueryData(queryKeys.journalEncryption, status);
+      invalidateAllDomainQueries(queryClient);
+    },
+  });
+};
+
+export const useEnableJournalEncryption = () =>
+  useEncryptionMutation((passphrase: string) => api.enableJournalEncryption(passphrase));
+
+export const useUnlockJournal = () =>
+  useEncryptionMutation((passphrase: string) => api.unlockJournal(passphrase));
+
+export const useLockJournal = () => useEncryptionMutation((_: void) => api.lockJournal());
+
+export const useDisableJournalEncryption = () =>
+  useEncryptionMutation((passphrase: string) => api.disableJournalEncryption(passphrase));
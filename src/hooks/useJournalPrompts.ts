@@ -0,0 +1,45 @@
+import { useMutation, useQuery, useQueryClient } from "@tanstack/react-query";
+import * as api from "../api";
+import { invalidateJournalPromptDomain, queryKeys } from "./queryInvalidation";
+
+export const useJournalPrompts = () => {
+  return useQuery({
+    queryKey: queryKeys.journalPrompts,
+    queryFn: api.getJournalPrompts,
+  });
+};
+
+export const useDailyPrompt = (date: string) => {
+  return useQuery({
+    queryKey: [...queryKeys.dailyPrompt, date],
+    queryFn: () => api.getDailyPrompt(date),
+  });
+};
+
+export const useAddJournalPrompt = () => {
+  const queryClient = useQueryClient();
+
+  return useMutation({
+    mutationFn: api.addJournalPrompt,
+    onSuccess: () => invalidateJournalPromptDomain(queryClient),
+  });
+};
+
+export const useSetJournalPromptEnabled = () => {
+  const queryClient = useQueryClient();
+
+  return useMutation({
+    mutationFn: ({ id, enabled }: { id: number; enabled: boolean }) =>
+      api.setJournalPromptEnabled(id, enabled),
+    onSuccess: () => invalidateJournalPromptDomain(queryClient),
+  });
+};
+
+export const useDeleteJournalPrompt = () => {
+  const queryClient = useQueryClient();
+
+  return useMutation({
+    mutationFn: api.deleteJournalPrompt,
+    onSuccess: () => invalidateJournalPromptDomain(queryClient),
+  });
+};
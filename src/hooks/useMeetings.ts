@@ -15,6 +15,13 @@ export const useMeetings = () => {
   });
 };
 
+export const useMeetingLoad = (startDate: string, endDate: string) => {
+  return useQuery({
+    queryKey: queryKeys.meetingLoad(startDate, endDate),
+    queryFn: () => api.getMeetingLoad(startDate, endDate),
+  });
+};
+
 export const useCreateMeeting = () => {
   const invalidateMeetings = useInvalidateMeetings();
 
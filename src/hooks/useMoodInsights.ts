@@ -0,0 +1,26 @@
+import { useMutation, useQuery, useQueryClient } from "@tanstack/react-query";
+import * as api from "../api";
+import { invalidateMoodDomain, queryKeys } from "./queryInvalidation";
+
+export const useDailyMoods = (startDate?: string, endDate?: string) => {
+  return useQuery({
+    queryKey: [...queryKeys.dailyMoods, startDate ?? null, endDate ?? null],
+    queryFn: () => api.getDailyMoods(startDate, endDate),
+  });
+};
+
+export const useSetDailyMood = () => {
+  const queryClient = useQueryClient();
+
+  return useMutation({
+    mutationFn: ({ date, mood }: { date: string; mood: number | null }) => api.setDailyMood(date, mood),
+    onSuccess: () => invalidateMoodDomain(queryClient),
+  });
+};
+
+export const useMoodCorrelations = (startDate?: string, endDate?: string) => {
+  return useQuery({
+    queryKey: [...queryKeys.moodCorrelations, startDate ?? null, endDate ?? null],
+    queryFn: () => api.getMoodCorrelations(startDate, endDate),
+  });
+};
@@ -0,0 +1,53 @@
+import { useMutation, useQuery, useQueryClient, type QueryClient } from "@tanstack/react-query";
+import * as api from "../api";
+import { queryKeys } from "./queryInvalidation";
+
+// On-call days also feed the mood correlations.
+const invalidateOnCall = (queryClient: QueryClient) => {
+  queryClient.invalidateQueries({ queryKey: queryKeys.onCallShifts });
+  queryClient.invalidateQueries({ queryKey: ["oncall-days"] });
+  queryClient.invalidateQueries({ queryKey: queryKeys.moodCorrelations });
+};
+
+export const useOnCallShifts = () => {
+  return useQuery({
+    queryKey: queryKeys.onCallShifts,
+    queryFn: api.getOnCallShifts,
+  });
+};
+
+export const useOnCallDays = (startDate: string, endDate: string) => {
+  return useQuery({
+    queryKey: queryKeys.onCallDays(startDate, endDate),
+    queryFn: () => api.getOnCallDays(startDate, endDate),
+    select: (days) => new Set(days),
+  });
+};
+
+export const useCreateOnCallShift = () => {
+  const queryClient = useQueryClient();
+
+  return useMutation({
+    mutationFn: ({ startDate, endDate, note }: { startDate: string; endDate: string; note?: string }) =>
+      api.createOnCallShift(startDate, endDate, note),
+    onSuccess: () => invalidateOnCall(queryClient),
+  });
+};
+
+export const useDeleteOnCallShift = () => {
+  const queryClient = useQueryClient();
+
+  return useMutation({
+    mutationFn: (id: number) => api.deleteOnCallShift(id),
+    onSuccess: () => invalidateOnCall(queryClient),
+  });
+};
+
+export const useImportOnCallIcs = () => {
+  const queryClient = useQueryClient();
+
+  return useMutation({
+    mutationFn: (content: string) => api.importOnCallIcs(content),
+    onSuccess: () => invalidateOnCall(queryClient),
+  });
+};
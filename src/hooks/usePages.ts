@@ -1,11 +1,12 @@
 import { useQuery, useMutation, useQueryClient } from "@tanstack/react-query";
 import * as api from "../api";
 import { queryKeys } from "./queryInvalidation";
+import { PageAppearance, PageExportFormat } from "../types";
 
 export const usePages = () => {
   return useQuery({
     queryKey: queryKeys.pages,
-    queryFn: api.getPages,
+    queryFn: () => api.getPages(),
   });
 };
 
@@ -17,12 +18,99 @@ export const usePage = (id: number | null) => {
   });
 };
 
+export const useSearchPages = (query: string) => {
+  return useQuery({
+    queryKey: [...queryKeys.pages, "search", query],
+    queryFn: () => api.searchPages(query),
+    enabled: query.length > 0,
+  });
+};
+
+export const usePageRevisions = (pageId: number | null) => {
+  return useQuery({
+    queryKey: [...queryKeys.pages, pageId, "revisions"],
+    queryFn: () => (pageId === null ? Promise.resolve([]) : api.getPageRevisions(pageId)),
+    enabled: pageId !== null,
+  });
+};
+
+export const useBacklinks = (pageId: number | null) => {
+  return useQuery({
+    queryKey: [...queryKeys.pages, pageId, "backlinks"],
+    queryFn: () => (pageId === null ? Promise.resolve([]) : api.getBacklinks(pageId)),
+    enabled: pageId !== null,
+  });
+};
+
+export const usePageProperties = (pageId: number | null) => {
+  return useQuery({
+    queryKey: [...queryKeys.pageProperties, pageId],
+    queryFn: () => (pageId === null ? Promise.resolve([]) : api.getPageProperties(pageId)),
+    enabled: pageId !== null,
+  });
+};
+
+export const usePagesByProperty = (key: string, value?: string) => {
+  return useQuery({
+    queryKey: [...queryKeys.pageProperties, "pages", key, value ?? null],
+    queryFn: () => api.getPagesByProperty(key, value),
+    enabled: key.length > 0,
+  });
+};
+
+export const usePagePropertyValues = (key: string) => {
+  return useQuery({
+    queryKey: [...queryKeys.pageProperties, "values", key],
+    queryFn: () => api.getPagePropertyValues(key),
+    enabled: key.length > 0,
+  });
+};
+
+export const usePageStats = (pageId: number | null) => {
+  return useQuery({
+    queryKey: [...queryKeys.pages, pageId, "stats"],
+    queryFn: () => (pageId === null ? Promise.resolve(null) : api.getPageStats(pageId)),
+    enabled: pageId !== null,
+  });
+};
+
+export const usePagesStats = () => {
+  return useQuery({
+    queryKey: [...queryKeys.pages, "stats"],
+    queryFn: api.getPagesStats,
+  });
+};
+
+export const usePageTags = () => {
+  return useQuery({
+    queryKey: queryKeys.pageTags,
+    queryFn: api.getPageTags,
+  });
+};
+
+export const usePagesByTag = (tag: string) => {
+  return useQuery({
+    queryKey: [...queryKeys.pageTags, "pages", tag],
+    queryFn: () => api.getPagesByTag(tag),
+    enabled: tag.length > 0,
+  });
+};
+
 export const useCreatePage = () => {
   const queryClient = useQueryClient();
 
   return useMutation({
-    mutationFn: ({ title, content }: { title: string; content: string }) =>
-      api.createPage(title, content),
+    mutationFn: ({
+      title,
+      content,
+      parentId = null,
+      appearance,
+    }: {
+      title: string;
+      content: string;
+      parentId?: number | null;
+      appearance?: PageAppearance;
+    }) => api.createPage(title, content, parentId, appearance),
     onSuccess: () => queryClient.invalidateQueries({ queryKey: queryKeys.pages }),
   });
 };
@@ -31,8 +119,17 @@ export const useUpdatePage = () => {
   const queryClient = useQueryClient();
 
   return useMutation({
-    mutationFn: ({ id, title, content }: { id: number; title: string; content: string }) =>
-      api.updatePage(id, title, content),
+    mutationFn: ({
+      id,
+      title,
+      content,
+      appearance,
+    }: {
+      id: number;
+      title: string;
+      content: string;
+      appearance?: PageAppearance;
+    }) => api.updatePage(id, title, content, appearance),
     onSuccess: (_, variables) => {
       queryClient.invalidateQueries({ queryKey: queryKeys.pages });
       queryClient.invalidateQueries({ queryKey: [...queryKeys.pages, variables.id] });
@@ -40,6 +137,170 @@ export const useUpdatePage = () => {
   });
 };
 
+export const useMovePage = () => {
+  const queryClient = useQueryClient();
+
+  return useMutation({
+    mutationFn: ({ id, newParent }: { id: number; newParent: number | null }) => api.movePage(id, newParent),
+    onSuccess: () => queryClient.invalidateQueries({ queryKey: queryKeys.pages }),
+  });
+};
+
+// Saves the order of one tree level after a drag and drop.
+export const useReorderPages = () => {
+  const queryClient = useQueryClient();
+
+  return useMutation({
+    mutationFn: (orderedIds: number[]) => api.reorderPages(orderedIds),
+    onSuccess: () => queryClient.invalidateQueries({ queryKey: queryKeys.pages }),
+  });
+};
+
+/** Pages by when they were last opened, for the "jump back in" list. */
+export const useRecentPages = (limit = 5) => {
+  return useQuery({
+    queryKey: [...queryKeys.pages, "recent", limit],
+    queryFn: () => api.getRecentPages(limit),
+  });
+};
+
+export const useRecordPageView = () => {
+  const queryClient = useQueryClient();
+
+  return useMutation({
+    mutationFn: (id: number) => api.recordPageView(id),
+    onSuccess: () => queryClient.invalidateQueries({ queryKey: [...queryKeys.pages, "recent"] }),
+  });
+};
+
+export const useSetPagePinned = () => {
+  const queryClient = useQueryClient();
+
+  return useMutation({
+    mutationFn: ({ id, pinned }: { id: number; pinned: boolean }) => api.setPagePinned(id, pinned),
+    onSuccess: () => queryClient.invalidateQueries({ queryKey: queryKeys.pages }),
+  });
+};
+
+export const useSetPageFavorite = () => {
+  const queryClient = useQueryClient();
+
+  return useMutation({
+    mutationFn: ({ id, favorite }: { id: number; favorite: boolean }) => api.setPageFavorite(id, favorite),
+    onSuccess: () => queryClient.invalidateQueries({ queryKey: queryKeys.pages }),
+  });
+};
+
+// Page locks change what `getPage` returns, so every variant refetches the pages.
+export const useEnablePageLock = () => {
+  const queryClient = useQueryClient();
+
+  return useMutation({
+    mutationFn: ({ id, passphrase }: { id: number; passphrase: string }) => api.enablePageLock(id, passphrase),
+    onSuccess: () => queryClient.invalidateQueries({ queryKey: queryKeys.pages }),
+  });
+};
+
+export const useUnlockPage = () => {
+  const queryClient = useQueryClient();
+
+  return useMutation({
+    mutationFn: ({ id, passphrase }: { id: number; passphrase: string }) => api.unlockPage(id, passphrase),
+    onSuccess: () => queryClient.invalidateQueries({ queryKey: queryKeys.pages }),
+  });
+};
+
+export const useLockPage = () => {
+  const queryClient = useQueryClient();
+
+  return useMutation({
+    mutationFn: (id: number) => api.lockPage(id),
+    onSuccess: () => queryClient.invalidateQueries({ queryKey: queryKeys.pages }),
+  });
+};
+
+export const useDisablePageLock = () => {
+  const queryClient = useQueryClient();
+
+  return useMutation({
+    mutationFn: ({ id, passphrase }: { id: number; passphrase: string }) => api.disablePageLock(id, passphrase),
+    onSuccess: () => queryClient.invalidateQueries({ queryKey: queryKeys.pages }),
+  });
+};
+
+/** Archived pages themselves; their subpages come back with them when unarchived. */
+export const useArchivedPages = () => {
+  return useQuery({
+    queryKey: [...queryKeys.pages, "archived"],
+    queryFn: async () => (await api.getPages(true)).filter((page) => page.archived),
+  });
+};
+
+export const useSetPageArchived = () => {
+  const queryClient = useQueryClient();
+
+  return useMutation({
+    mutationFn: ({ id, archived }: { id: number; archived: boolean }) =>
+      archived ? api.archivePage(id) : api.unarchivePage(id),
+    onSuccess: () => queryClient.invalidateQueries({ queryKey: queryKeys.pages }),
+  });
+};
+
+/** Uploads an image for a page; the returned URL is embedded by the caller. */
+export const useSavePageAsset = () => {
+  return useMutation({
+    mutationFn: ({ pageId, bytes, filename }: { pageId: number; bytes: Uint8Array; filename: string }) =>
+      api.savePageAsset(pageId, bytes, filename),
+  });
+};
+
+export const useExportPage = () => {
+  return useMutation({
+    mutationFn: ({ id, format, path }: { id: number; format: PageExportFormat; path: string }) =>
+      api.exportPage(id, format, path),
+  });
+};
+
+export const useExportAllPages = () => {
+  return useMutation({
+    mutationFn: ({ dir, format }: { dir: string; format: PageExportFormat }) => api.exportAllPages(dir, format),
+  });
+};
+
+export const useGenerateWeekPlan = () => {
+  const queryClient = useQueryClient();
+
+  return useMutation({
+    mutationFn: (weekStart: string) => api.generateWeekPlan(weekStart),
+    onSuccess: () => queryClient.invalidateQueries({ queryKey: queryKeys.pages }),
+  });
+};
+
+export const useTrashedPages = () => {
+  return useQuery({
+    queryKey: [...queryKeys.pages, "trash"],
+    queryFn: api.getTrashedPages,
+  });
+};
+
+export const useRestorePage = () => {
+  const queryClient = useQueryClient();
+
+  return useMutation({
+    mutationFn: api.restorePage,
+    onSuccess: () => queryClient.invalidateQueries({ queryKey: queryKeys.pages }),
+  });
+};
+
+export const useEmptyPageTrash = () => {
+  const queryClient = useQueryClient();
+
+  return useMutation({
+    mutationFn: api.emptyPageTrash,
+    onSuccess: () => queryClient.invalidateQueries({ queryKey: queryKeys.pages }),
+  });
+};
+
 export const useDeletePage = () => {
   const queryClient = useQueryClient();
 
@@ -48,3 +309,30 @@ export const useDeletePage = () => {
     onSuccess: () => queryClient.invalidateQueries({ queryKey: queryKeys.pages }),
   });
 };
+
+export const useAddPageTag = () => {
+  const queryClient = useQueryClient();
+
+  return useMutation({
+    mutationFn: ({ pageId, tag }: { pageId: number; tag: string }) => api.addPageTag(pageId, tag),
+    onSuccess: () => queryClient.invalidateQueries({ queryKey: queryKeys.pageTags }),
+  });
+};
+
+export const useRemovePageTag = () => {
+  const queryClient = useQueryClient();
+
+  return useMutation({
+    mutationFn: ({ pageId, tag }: { pageId: number; tag: string }) => api.removePageTag(pageId, tag),
+    onSuccess: () => queryClient.invalidateQueries({ queryKey: queryKeys.pageTags }),
+  });
+};
+
+export const useRestorePageRevision = () => {
+  const queryClient = useQueryClient();
+
+  return useMutation({
+    mutationFn: api.restorePageRevision,
+    onSuccess: () => queryClient.invalidateQueries({ queryKey: queryKeys.pages }),
+  });
+};
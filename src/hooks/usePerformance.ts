@@ -0,0 +1,29 @@
+import { useMutation, useQuery, useQueryClient } from "@tanstack/react-query";
+import * as api from "../api";
+import { queryKeys } from "./queryInvalidation";
+
+// Timings change with every call, so the report is only loaded when asked for.
+export const usePerformanceReport = (enabled: boolean) => {
+  return useQuery({
+    queryKey: queryKeys.performanceReport,
+    queryFn: () => api.getPerformanceReport(),
+    enabled,
+    staleTime: 0,
+  });
+};
+
+export const useClearSlowOps = () => {
+  const queryClient = useQueryClient();
+
+  return useMutation({
+    mutationFn: api.clearSlowOps,
+    onSuccess: () => queryClient.invalidateQueries({ queryKey: queryKeys.performanceReport }),
+  });
+};
+
+// A one-off self-check, so it is a mutation rather than a cached query.
+export const useCheckQueryPlans = () => {
+  return useMutation({
+    mutationFn: api.checkQueryPlans,
+  });
+};
@@ -0,0 +1,63 @@
+import { useMutation, useQuery, useQueryClient } from "@tanstack/react-query";
+import * as api from "../api";
+import type { PomodoroKind } from "../types";
+import { invalidatePomodoroDomain, queryKeys } from "./queryInvalidation";
+
+export const usePomodoroStatus = () => {
+  return useQuery({
+    queryKey: queryKeys.pomodoroStatus,
+    queryFn: api.getPomodoroStatus,
+  });
+};
+
+export const usePomodoroHeatmap = (days = 90) => {
+  return useQuery({
+    queryKey: [...queryKeys.pomodoroHeatmap, days],
+    queryFn: () => api.getPomodoroHeatmap(days),
+  });
+};
+
+// Kept current by the `presence-changed` listener in useBackendEvents.
+export const usePresence = () => {
+  return useQuery({
+    queryKey: queryKeys.presence,
+    queryFn: api.getPresence,
+  });
+};
+
+export const useSetFocusPresence = () => {
+  const queryClient = useQueryClient();
+
+  return useMutation({
+    mutationFn: ({
+      kind,
+      task_id,
+      duration_minutes,
+    }: {
+      kind: PomodoroKind | null;
+      task_id: number | null;
+      duration_minutes: number | null;
+    }) => api.setFocusPresence(kind, task_id, duration_minutes),
+    onSuccess: (presence) => queryClient.setQueryData(queryKeys.presence, presence),
+  });
+};
+
+export const useRecordPomodoroSession = () => {
+  const queryClient = useQueryClient();
+
+  return useMutation({
+    mutationFn: ({
+      kind,
+      duration_minutes,
+      task_id,
+    }: {
+      kind: PomodoroKind;
+      duration_minutes: number;
+      task_id: number | null;
+    }) => api.recordPomodoroSession(kind, duration_minutes, task_id),
+    onSuccess: (status) => {
+      queryClient.setQueryData(queryKeys.pomodoroStatus, status);
+      invalidatePomodoroDomain(queryClient);
+    },
+  });
+};
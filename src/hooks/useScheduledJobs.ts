@@ -0,0 +1,23 @@
+import { useMutation, useQuery, useQueryClient } from "@tanstack/react-query";
+import * as api from "../api";
+import type { ScheduledJobName } from "../types";
+import { queryKeys } from "./queryInvalidation";
+
+// Refetched every minute so last-run status follows the scheduler tick.
+export const useScheduledJobs = () => {
+  return useQuery({
+    queryKey: queryKeys.scheduledJobs,
+    queryFn: api.listScheduledJobs,
+    refetchInterval: 60_000,
+  });
+};
+
+export const useSetJobSchedule = () => {
+  const queryClient = useQueryClient();
+
+  return useMutation({
+    mutationFn: ({ job, schedule, enabled }: { job: ScheduledJobName; schedule: string; enabled: boolean }) =>
+      api.setJobSchedule(job, schedule, enabled),
+    onSuccess: () => queryClient.invalidateQueries({ queryKey: queryKeys.scheduledJobs }),
+  });
+};
@@ -0,0 +1,37 @@
+import { useMutation, useQuery, useQueryClient } from "@tanstack/react-query";
+import * as api from "../api";
+import { queryKeys } from "./queryInvalidation";
+
+export const useSlackStatusSettings = () => {
+  return useQuery({
+    queryKey: queryKeys.slackStatusSettings,
+    queryFn: api.getSlackStatusSettings,
+  });
+};
+
+export const useSaveSlackStatusSettings = () => {
+  const queryClient = useQueryClient();
+
+  return useMutation({
+    mutationFn: ({ enabled, token }: { enabled: boolean; token: string | null }) =>
+      api.saveSlackStatusSettings(enabled, token),
+    onSuccess: (settings) => queryClient.setQueryData(queryKeys.slackStatusSettings, settings),
+  });
+};
+
+export const useSlackStatusOptOuts = () => {
+  return useQuery({
+    queryKey: queryKeys.slackStatusOptOuts,
+    queryFn: api.getSlackStatusOptOuts,
+  });
+};
+
+export const useSetTaskSlackStatusOptOut = () => {
+  const queryClient = useQueryClient();
+
+  return useMutation({
+    mutationFn: ({ task_id, opted_out }: { task_id: number; opted_out: boolean }) =>
+      api.setTaskSlackStatusOptOut(task_id, opted_out),
+    onSuccess: () => queryClient.invalidateQueries({ queryKey: queryKeys.slackStatusOptOuts }),
+  });
+};
@@ -8,10 +8,61 @@ const useInvalidateTasks = () => {
   return () => invalidateTaskDomain(queryClient);
 };
 
-export const useTasks = () => {
+// With a tag the backend returns only tasks carrying it.
+export const useTasks = (tag: string | null = null) => {
   return useQuery({
-    queryKey: queryKeys.tasks,
-    queryFn: api.getTasks,
+    queryKey: tag ? queryKeys.tasksByTag(tag) : queryKeys.tasks,
+    queryFn: () => api.getTasks(tag),
+  });
+};
+
+export const useSetTaskTags = () => {
+  const invalidateTasks = useInvalidateTasks();
+
+  return useMutation({
+    mutationFn: ({ task_id, tags }: { task_id: number; tags: string[] }) => api.setTaskTags(task_id, tags),
+    onSuccess: invalidateTasks,
+  });
+};
+
+// Nested under `tasks`, so timer changes refresh it.
+export const useContextSwitches = (start: string, end: string) => {
+  return useQuery({
+    queryKey: queryKeys.contextSwitches(start, end),
+    queryFn: () => api.getContextSwitches({ start, end }),
+  });
+};
+
+// Auto-created tasks waiting to be accepted onto the board.
+export const useReviewQueue = () => {
+  return useQuery({
+    queryKey: queryKeys.reviewQueue,
+    queryFn: api.getReviewQueue,
+  });
+};
+
+export const useAcceptReviewItem = () => {
+  const invalidateTasks = useInvalidateTasks();
+
+  return useMutation({
+    mutationFn: (taskId: number) => api.acceptReviewItem(taskId),
+    onSuccess: invalidateTasks,
+  });
+};
+
+export const useDismissReviewItem = () => {
+  const invalidateTasks = useInvalidateTasks();
+
+  return useMutation({
+    mutationFn: (taskId: number) => api.dismissReviewItem(taskId),
+    onSuccess: invalidateTasks,
+  });
+};
+
+// Checked on demand before quick capture adds a task, so it is a mutation rather than a cached query.
+export const useFindDuplicateTasks = () => {
+  return useMutation({
+    mutationFn: (title: string) => api.findDuplicateTasks(title),
   });
 };
 
@@ -185,6 +236,26 @@ export const useUpdateTaskSubtask = () => {
   });
 };
 
+export const useReorderTaskSubtasks = () => {
+  const invalidateTasks = useInvalidateTasks();
+
+  return useMutation({
+    mutationFn: ({ task_id, ordered_ids }: { task_id: number; ordered_ids: number[] }) =>
+      api.reorderTaskSubtasks(task_id, ordered_ids),
+    onSuccess: invalidateTasks,
+  });
+};
+
+export const useReorderTasks = () => {
+  const invalidateTasks = useInvalidateTasks();
+
+  return useMutation({
+    mutationFn: ({ status, ordered_ids }: { status: TaskStatus; ordered_ids: number[] }) =>
+      api.reorderTasks(status, ordered_ids),
+    onSuccess: invalidateTasks,
+  });
+};
+
 export const useDeleteTaskSubtask = () => {
   const invalidateTasks = useInvalidateTasks();
 
@@ -0,0 +1,38 @@
+import { useEffect } from "react";
+import { emit, listen } from "@tauri-apps/api/event";
+import { getCurrentWindow } from "@tauri-apps/api/window";
+import { useQueryClient } from "@tanstack/react-query";
+import { invalidateAllDomainQueries } from "./queryInvalidation";
+
+const WINDOWS_DATA_CHANGED = "windows-data-changed";
+// Autosave fires mutations in bursts; other windows refetch once per burst.
+const SYNC_DEBOUNCE_MS = 400;
+
+// Every window has its own React Query cache over the same database, so each
+// successful mutation is broadcast and the other windows refetch what they show.
+export const useWindowSync = () => {
+  const queryClient = useQueryClient();
+
+  useEffect(() => {
+    const label = getCurrentWindow().label;
+    let timeout: number | undefined;
+
+    const unsubscribe = queryClient.getMutationCache().subscribe((event) => {
+      if (event.type === "updated" && event.action.type === "success") {
+        emit(WINDOWS_DATA_CHANGED, { source: label }).catch(() => undefined);
+      }
+    });
+
+    const unlisten = listen<{ source: string }>(WINDOWS_DATA_CHANGED, (event) => {
+      if (event.payload.source === label) return;
+      window.clearTimeout(timeout);
+      timeout = window.setTimeout(() => invalidateAllDomainQueries(queryClient), SYNC_DEBOUNCE_MS);
+    });
+
+    return () => {
+      unsubscribe();
+      window.clearTimeout(timeout);
+      unlisten.then((fn) => fn()).catch(() => undefined);
+    };
+  }, [queryClient]);
+};
@@ -0,0 +1,38 @@
+import { useMutation, useQuery, useQueryClient } from "@tanstack/react-query";
+import * as api from "../api";
+import { queryKeys } from "./queryInvalidation";
+
+export const useWorkspaceSnapshots = () => {
+  return useQuery({
+    queryKey: queryKeys.workspaceSnapshots,
+    queryFn: api.listWorkspaceSnapshots,
+  });
+};
+
+export const useCreateWorkspaceSnapshot = () => {
+  const queryClient = useQueryClient();
+
+  return useMutation({
+    mutationFn: (name: string) => api.createWorkspaceSnapshot(name),
+    onSuccess: () => queryClient.invalidateQueries({ queryKey: queryKeys.workspaceSnapshots }),
+  });
+};
+
+// A restore replaces the whole database, so every cached query is stale.
+export const useRestoreWorkspaceSnapshot = () => {
+  const queryClient = useQueryClient();
+
+  return useMutation({
+    mutationFn: (name: string) => api.restoreWorkspaceSnapshot(name),
+    onSuccess: () => queryClient.invalidateQueries(),
+  });
+};
+
+export const useDeleteWorkspaceSnapshot = () => {
+  const queryClient = useQueryClient();
+
+  return useMutation({
+    mutationFn: (name: string) => api.deleteWorkspaceSnapshot(name),
+    onSuccess: () => queryClient.invalidateQueries({ queryKey: queryKeys.workspaceSnapshots }),
+  });
+};
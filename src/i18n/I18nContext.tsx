@@ -69,6 +69,10 @@ const ukTranslations: Record<string, string> = {
   "More breathing room.": "Більше вільного простору.",
   "Compact controls for how the workspace looks, reads, and feels.": "Компактні контролли для того, як виглядає, читається і відчувається workspace.",
   "Corner radius": "Радіус кутів",
+  "Font size": "Розмір шрифту",
+  "Scales all text; 14 is the default.": "Масштабує весь текст; типово 14.",
+  "Accent color": "Акцентний колір",
+  "Use theme color": "Колір теми",
   "Corner radius (6-18)": "Радіус кутів (6-18)",
   "Sharper corners for denser UI, softer corners for calmer surfaces.": "Гостріші кути для щільнішого UI, мʼякші для спокійніших поверхонь.",
   "Language": "Мова",
@@ -101,9 +105,224 @@ const ukTranslations: Record<string, string> = {
   "Export Backup (JSON)": "Експорт бекапу (JSON)",
   "Replace existing data on import": "Замінювати існуючі дані при імпорті",
   "Import Backup (JSON)": "Імпорт бекапу (JSON)",
+  "Markdown archive": "Архів Markdown",
+  "Page matches": "Збіги на сторінках",
+  "Deleted pages": "Видалені сторінки",
+  "Restored pages go back under their parent page, or to the top level if it was deleted too.": "Відновлені сторінки повертаються під свою батьківську сторінку або на верхній рівень, якщо її теж видалено.",
+  "Restore page": "Відновити сторінку",
+  "Permanently delete all trashed pages? Their version history is removed too.": "Остаточно видалити всі сторінки з кошика? Їхню історію версій теж буде видалено.",
+  "Empty page trash": "Очистити кошик сторінок",
+  "Vault mirror": "Дзеркало сховища",
+  "Writes every entry and page as a Markdown file after each save, so the folder can live in a git repo. Changes in the folder are never read back. Leave empty to turn it off.": "Після кожного збереження записує кожен запис і сторінку як файл Markdown, тож папку можна тримати в git-репозиторії. Зміни в папці ніколи не зчитуються назад. Залиш порожнім, щоб вимкнути.",
+  "Paused while journal encryption is on.": "Призупинено, поки увімкнено шифрування журналу.",
+  "Mirror now": "Синхронізувати зараз",
+  "Commit changes to git": "Комітити зміни в git",
+  "Creates a git repository in the folder if needed and keeps one commit per day. Commits that were already pushed are never rewritten.": "За потреби створює git-репозиторій у папці й тримає один коміт на день. Уже запушені коміти ніколи не переписуються.",
+  "Mirroring...": "Синхронізація...",
+  "Mirrored {entries} entries and {pages} pages to {dir}, removed {removed} stale files.": "Записано {entries} записів і {pages} сторінок у {dir}, видалено {removed} застарілих файлів.",
+  "Vault mirror turned off. Existing files were left in place.": "Дзеркало вимкнено. Наявні файли залишено на місці.",
+  "Printable daily sheet": "Денний аркуш для друку",
+  "A one-page HTML sheet with the day's plan, top tasks, meetings and habit grid. Open it in a browser to print or save as PDF.": "Одна сторінка HTML із планом дня, головними задачами, зустрічами та сіткою звичок. Відкрий її в браузері, щоб надрукувати або зберегти як PDF.",
+  "File or folder": "Файл або папка",
+  "Date": "Дата",
+  "Generate sheet": "Створити аркуш",
+  "Saved daily sheet to {path}": "Денний аркуш збережено в {path}",
+  "API tokens": "API-токени",
+  "Tokens for external tools such as AI assistants. Private content is never included in a preset.": "Токени для зовнішніх інструментів, як-от AI-асистенти. Приватний вміст ніколи не входить до пресетів.",
+  "Token name": "Назва токена",
+  "Access": "Доступ",
+  "Read-only": "Лише читання",
+  "Tasks only": "Лише задачі",
+  "Full access": "Повний доступ",
+  "Create token": "Створити токен",
+  "Copy this token now, it will not be shown again: {token}": "Скопіюй токен зараз, його більше не буде показано: {token}",
+  "Revoke token": "Відкликати токен",
+  "Calls per minute per token (0 = unlimited)": "Викликів за хвилину на токен (0 = без обмежень)",
+  "Recent external access": "Нещодавній зовнішній доступ",
+  "Allowed": "Дозволено",
+  "Denied": "Відхилено",
+  "Rate limited": "Обмежено",
+  "Error": "Помилка",
+  "Browser capture": "Захоплення з браузера",
+  "Update available": "Доступне оновлення",
+  "Update check": "Перевірка оновлень",
+  "Looks for a newer release on the update channel and notifies once per version, outside quiet hours.": "Шукає новіший реліз у вибраному каналі та сповіщає один раз про кожну версію поза тихими годинами.",
+  "Quiet hours from (empty = off)": "Тихі години з (порожньо = вимкнено)",
+  "Quiet hours until": "Тихі години до",
+  "Updates": "Оновлення",
+  "Release channel": "Канал випусків",
+  "Stable": "Стабільний",
+  "Beta": "Бета",
+  "Checking...": "Перевірка...",
+  "Check for updates": "Перевірити оновлення",
+  "Version {version} is available (you have {current}).": "Доступна версія {version} (у вас {current}).",
+  "You are on the latest version ({current}).": "У вас остання версія ({current}).",
+  "Release notes": "Нотатки до випуску",
+  "Dev Journal {version} is available.": "Доступна версія Dev Journal {version}.",
+  "Jump back in": "Продовжити",
+  "Week numbering": "Нумерація тижнів",
+  "ISO 8601 (weeks start on Monday)": "ISO 8601 (тиждень починається з понеділка)",
+  "US (weeks start on Sunday)": "США (тиждень починається з неділі)",
+  "Used for habit week counts, weekly reviews and plans, the daily sheet and the activity map.": "Використовується для тижневих лічильників звичок, тижневих оглядів і планів, щоденного аркуша та карти активності.",
+  "This page is locked. Enter its passphrase to read or edit it.": "Ця сторінка заблокована. Введи її парольну фразу, щоб читати чи редагувати.",
+  "Unlock page": "Розблокувати сторінку",
+  "Lock page": "Заблокувати сторінку",
+  "Remove page lock": "Зняти блокування сторінки",
+  "Remove lock": "Зняти блокування",
+  "Page locked.": "Сторінку заблоковано.",
+  "Page lock removed.": "Блокування сторінки знято.",
+  "The page and its history are encrypted with this passphrase. It cannot be recovered if you forget it.": "Сторінку та її історію зашифровано цією парольною фразою. Якщо її забути, відновити не вийде.",
+  "The page and its history are stored unlocked again.": "Сторінка та її історія знову зберігатимуться без блокування.",
+  "At least 8 characters": "Щонайменше 8 символів",
+  "Habit check-ins": "Виконання звичок",
+  "Journal entries": "Записи журналу",
+  "Moods": "Настрій",
+  "Task due dates": "Терміни задач",
+  "Goal target dates": "Цільові дати цілей",
+  "Habit": "Звичка",
+  "All habits": "Усі звички",
+  "Days (+/-)": "Днів (+/-)",
+  "Preview": "Попередній перегляд",
+  "Shift": "Зсунути",
+  "Shift dates": "Зсув дат",
+  "Fixes imports that landed a day early or late. Preview the rows first; nothing moves while any of them would land on a date that is already taken.": "Виправляє імпорт, що потрапив на день раніше чи пізніше. Спершу переглянь рядки; нічого не зсунеться, доки хоч один із них потрапляє на вже зайняту дату.",
+  "{count} rows would land on a date that is already taken.": "Рядків, що потрапили б на вже зайняту дату: {count}.",
+  "{count} rows will move.": "Буде зсунуто рядків: {count}.",
+  "Moved {count} rows.": "Зсунуто рядків: {count}.",
+  "Data check": "Перевірка даних",
+  "Checks the data integrity rules and fixes the safe cases if allowed.": "Перевіряє правила цілісності даних і, якщо дозволено, виправляє безпечні випадки.",
+  "The data check found {count} problems. See Settings for details.": "Перевірка даних знайшла проблем: {count}. Подробиці в налаштуваннях.",
+  "Data integrity": "Цілісність даних",
+  "Last checked {date}: {count} problems.": "Остання перевірка {date}: проблем — {count}.",
+  "Not checked yet.": "Ще не перевірялося.",
+  "{count} fixed automatically.": "Виправлено автоматично: {count}.",
+  "Check now": "Перевірити зараз",
+  "Check and fix": "Перевірити й виправити",
+  "Fix safe cases automatically": "Автоматично виправляти безпечні випадки",
+  "The scheduled check clamps goal progress, fills in or clears task completion times and removes check-ins of deleted habits. Entry dates are only reported.": "Планова перевірка обмежує прогрес цілей, заповнює або очищає час завершення задач і видаляє позначки видалених звичок. Про дати записів лише повідомляє.",
+  "Entry dates are real YYYY-MM-DD days": "Дати записів — справжні дні у форматі YYYY-MM-DD",
+  "Each day has at most one entry": "На кожен день не більше одного запису",
+  "Goal progress is between 0 and 100": "Прогрес цілей від 0 до 100",
+  "Done tasks have a completion time": "Виконані задачі мають час завершення",
+  "Open tasks have no completion time": "Відкриті задачі не мають часу завершення",
+  "Habit check-ins belong to an existing habit": "Позначки звичок належать наявній звичці",
+  "Streak freezes belong to an existing habit": "Заморожування серій належать наявній звичці",
+  "Cover": "Обкладинка",
+  "A color like #4f46e5 or an image URL. Leave empty to remove the cover.": "Колір на кшталт #4f46e5 або URL зображення. Залиште порожнім, щоб прибрати обкладинку.",
+  "Page icon: paste or type one emoji": "Іконка сторінки: вставте або введіть один емодзі",
+  "Page icon": "Іконка сторінки",
+  "Also {property}:": "Також {property}:",
+  "No other pages with {property}": "Немає інших сторінок з {property}",
+  "Performance": "Продуктивність",
+  "Calls slower than the threshold are logged with their argument sizes, never their content. 0 turns the log off.": "Виклики, повільніші за поріг, записуються з розмірами аргументів, але без їхнього вмісту. 0 вимикає журнал.",
+  "Slow call threshold (ms)": "Поріг повільного виклику (мс)",
+  "Refresh": "Оновити",
+  "Show report": "Показати звіт",
+  "Clear slow log": "Очистити журнал повільних викликів",
+  "Since {date}": "З {date}",
+  "mean": "середнє",
+  "max": "макс.",
+  "Slowest recorded calls": "Найповільніші записані виклики",
+  "No slow calls recorded.": "Повільних викликів не записано.",
+  "Check query plans": "Перевірити плани запитів",
+  "{count} of {total} common queries read whole tables.": "{count} з {total} типових запитів читають таблиці повністю.",
+  "All {total} common queries use an index.": "Усі {total} типових запитів використовують індекс.",
+  "Entry for a day": "Запис за день",
+  "Most recently edited pages": "Нещодавно змінені сторінки",
+  "Subpages of a page": "Підсторінки сторінки",
+  "Most recently updated tasks": "Нещодавно оновлені задачі",
+  "Tasks of a project": "Задачі проєкту",
+  "Tasks of one priority": "Задачі одного пріоритету",
+  "Open tasks due by a day": "Відкриті задачі з терміном до дня",
+  "Tasks completed since a time": "Задачі, завершені від певного часу",
+  "Habit check-ins in a date range": "Відмітки звичок за період",
+  "Entries with a tag": "Записи з тегом",
+  "Tasks with a tag": "Задачі з тегом",
+  "Pages with a tag": "Сторінки з тегом",
+  "Pages with a frontmatter property": "Сторінки з властивістю у frontmatter",
+  "Links to a page title": "Посилання на назву сторінки",
+  "Full-text entry search": "Повнотекстовий пошук записів",
+  "Full-text page search": "Повнотекстовий пошук сторінок",
+  "Checklist: {done}/{total}": "Чекліст: {done}/{total}",
+  "Move up": "Перемістити вгору",
+  "Move down": "Перемістити вниз",
+  "Timer running on \"{title}\"": "Таймер запущено для \"{title}\"",
+  "Slack status while a timer runs": "Статус у Slack під час роботи таймера",
+  "Sets your status to \"Working on <task>\" when a task timer starts and clears it when the timer stops. Needs a user token with the users.profile:write scope; tasks can opt out in their details.": "Встановлює статус \"Working on <task>\", коли запускається таймер задачі, і очищає його, коли таймер зупиняється. Потрібен користувацький токен з дозволом users.profile:write; окремі задачі можна вимкнути в їхніх деталях.",
+  "Replace Slack token": "Замінити токен Slack",
+  "Slack user token": "Користувацький токен Slack",
+  "Save token": "Зберегти токен",
+  "Remove token": "Видалити токен",
+  "Token saved to the system keychain.": "Токен збережено в системному сховищі ключів.",
+  "Token removed.": "Токен видалено.",
+  "Show in Slack status while the timer runs": "Показувати в статусі Slack під час роботи таймера",
+  "Tags": "Теги",
+  "All tags": "Усі теги",
+  "Failed to save tags: {message}": "Не вдалося зберегти теги: {message}",
+  "Before you quit": "Перед виходом",
+  "Today's journal entry is missing.": "Сьогоднішнього запису в журналі немає.",
+  "Timers still running: {count}": "Ще працюють таймери: {count}",
+  "Stop timers and quit": "Зупинити таймери й вийти",
+  "Quit anyway": "Усе одно вийти",
+  "Failed to quit: {message}": "Не вдалося вийти: {message}",
+  "Shutdown ritual": "Ритуал завершення дня",
+  "When quitting late in the day, check for today's entry and running timers first.": "Під час виходу наприкінці дня спершу перевіряти сьогоднішній запис і запущені таймери.",
+  "Check from hour (0-23)": "Перевіряти з години (0-23)",
+  "Task due reminder": "Нагадування про термін задачі",
+  "Remind before tasks are due": "Нагадувати до терміну задач",
+  "Comma-separated, e.g. 1d, 1h, 30m. Empty = off": "Через кому, напр. 1d, 1h, 30m. Порожньо = вимкнено",
+  "\"{title}\" is due {date}.": "Термін \"{title}\" — {date}.",
+  "Snooze 1h": "Відкласти на 1 год",
+  "Workspace snapshots": "Знімки робочого простору",
+  "Save everything before a bulk cleanup, import or new automation rule and restore it if you don't like the result. Attachments are not included.": "Збережіть усе перед масовим очищенням, імпортом чи новим правилом автоматизації й відновіть, якщо результат не сподобається. Вкладення не включаються.",
+  "Snapshot name": "Назва знімка",
+  "Save snapshot": "Зберегти знімок",
+  "Restore": "Відновити",
+  "Saved snapshot \"{name}\".": "Знімок \"{name}\" збережено.",
+  "Replace all journal data and settings with snapshot \"{name}\"?": "Замінити всі дані журналу й налаштування знімком \"{name}\"?",
+  "Restored \"{name}\". The previous state was saved as \"{previous}\".": "Відновлено \"{name}\". Попередній стан збережено як \"{previous}\".",
+  "Delete snapshot \"{name}\"?": "Видалити знімок \"{name}\"?",
+  "Experimental features": "Експериментальні функції",
+  "Work in progress that ships turned off. Changes apply immediately.": "Функції в розробці, вимкнені за замовчуванням. Зміни діють одразу.",
+  "Synchronize the journal between devices": "Синхронізація журналу між пристроями",
+  "Summaries and suggestions from a language model": "Підсумки та підказки від мовної моделі",
+  "Token-authenticated REST API on localhost": "REST API на localhost з авторизацією токеном",
+  "Browser capture endpoint": "Точка захоплення для браузера",
+  "Port": "Порт",
+  "The extension posts to http://127.0.0.1:{port}/capture with a \"Browser capture\" token. Changes apply after restarting the app.": "Розширення надсилає дані на http://127.0.0.1:{port}/capture з токеном \"Захоплення з браузера\". Зміни застосуються після перезапуску застосунку.",
+  "Captured links": "Збережені посилання",
+  "Incidents": "Інциденти",
+  "Log incident": "Записати інцидент",
+  "Edit incident": "Редагувати інцидент",
+  "Summary": "Короткий опис",
+  "Started": "Почався",
+  "Resolved": "Вирішено",
+  "Resolution and follow-ups": "Рішення та подальші кроки",
+  "Linked tasks": "Пов'язані задачі",
+  "Linked pages": "Пов'язані сторінки",
+  "No incidents logged.": "Інцидентів не записано.",
+  "Resolved in {duration}": "Вирішено за {duration}",
+  "Ongoing since {time}": "Триває з {time}",
+  "Timeline, last 7 days": "Хронологія за останні 7 днів",
+  "started": "почався",
+  "resolved": "вирішено",
+  "task done: {title}": "задачу виконано: {title}",
+  "On this day": "Цього дня",
+  "Delete link": "Видалити посилання",
+  "A task clipped from the browser is waiting for review.": "Задача, збережена з браузера, очікує на перегляд.",
+  "A task clipped from the browser is waiting for review; it looks like \"{title}\".": "Задача, збережена з браузера, очікує на перегляд; схожа на \"{title}\".",
+  "Already exists — open it instead?": "Вже існує — відкрити її замість цього?",
+  "Add anyway": "Все одно додати",
+  "Writes one YYYY-MM-DD.md file per entry with a frontmatter block. Leave dates empty to export everything.": "Створює окремий файл YYYY-MM-DD.md для кожного запису з блоком frontmatter. Залиште дати порожніми, щоб експортувати все.",
+  "Folder": "Папка",
+  "From": "Від",
+  "To": "До",
+  "Exporting...": "Експорт...",
+  "Export Markdown": "Експорт у Markdown",
+  "Exported {count} entries to {dir}": "Експортовано записів: {count} до {dir}",
   "Importing...": "Імпорт...",
   "Backup imported successfully.": "Бекап успішно імпортовано.",
-  "Import failed. Check JSON format.": "Імпорт не вдався. Перевір формат JSON.",
+  "Import failed: {error}": "Імпорт не вдався: {error}",
   "Import failed. Invalid JSON file.": "Імпорт не вдався. Невалідний JSON-файл.",
   "Reset": "Скинути",
   "Done button": "Готово",
@@ -146,9 +365,11 @@ const ukTranslations: Record<string, string> = {
   "Jump to page editor": "Перейти до редактора сторінки",
   "Open Goals: {title}": "Відкрити цілі: {title}",
   "Open Habits: {title}": "Відкрити звички: {title}",
+  "Log all habits for today": "Відмітити всі звички за сьогодні",
+  "{count} habits not yet done today": "{count} звичок ще не виконано сьогодні",
+  "Logged {count} habits": "Відмічено звичок: {count}",
   "Streak {count}d": "Серія {count}д",
-  "Dev Journal Reminder": "Нагадування Dev Journal",
-  "It's past {hour}:00. Time to write your dev journal!": "Вже після {hour}:00. Час заповнити щоденник!",
+  "You haven't written today's entry": "Ви ще не написали сьогоднішній запис",
   "Daily command center for journal, tasks, goals, and habits.": "Щоденний центр керування журналом, задачами, цілями та звичками.",
   "Journal Today": "Журнал сьогодні",
   "Open Tasks": "Відкрити задачі",
@@ -205,6 +426,10 @@ const ukTranslations: Record<string, string> = {
   "Updated: {datetime}": "Оновлено: {datetime}",
   "Drag": "Перетягнути",
   "Open card": "Відкрити картку",
+  "Needs review": "Потребує перегляду",
+  "Captured from URL": "Додано з посилання",
+  "Accept": "Прийняти",
+  "Dismiss": "Відхилити",
   "Repeats: {value}": "Повтор: {value}",
   "Time limit (minutes)": "Ліміт часу (хвилини)",
   "Before (planned outcome)": "До (очікуваний результат)",
@@ -234,6 +459,10 @@ const ukTranslations: Record<string, string> = {
   "done": "виконано",
   "Targets met: {count}": "Цілі виконано: {count}",
   "Avg streak: {count}d": "Серія в середньому: {count}д",
+  "Streak freezes": "Заморозки серії",
+  "Off": "Вимкнено",
+  "1 per {count} completions": "1 за кожні {count} виконань",
+  "❄ {count} freezes": "❄ заморозок: {count}",
   "Goals in 14d": "Цілі за 14 днів",
   "Habits done today": "Звички виконано сьогодні",
   "App Activity": "Активність",
@@ -253,6 +482,95 @@ const ukTranslations: Record<string, string> = {
   "Session marked as complete.": "Сесію позначено як завершену.",
   "Focus session completed!": "Фокус-сесію завершено!",
   "Break": "Перерва",
+  "Long break": "Довга перерва",
+  "Daily goal": "Денна ціль",
+  "Pomodoros": "Помідори",
+  "Time for a break: \"{title}\" has been running for {minutes} min.": "Час на перерву: \"{title}\" триває вже {minutes} хв.",
+  "Achievement unlocked: {title}": "Досягнення відкрито: {title}",
+  "Add tag": "Додати тег",
+  "Timebox": "Таймбокс",
+  "Extra entry sections": "Додаткові розділи запису",
+  "Shown below Yesterday and Today in every journal entry, e.g. Blockers or Wins.": "Показуються під «Вчора» і «Сьогодні» в кожному записі, напр. Блокери чи Перемоги.",
+  "Add section": "Додати розділ",
+  "Entry sections saved.": "Розділи запису збережено.",
+  "Journaling prompts": "Підказки для щоденника",
+  "Enabled prompts rotate daily in the journal editor.": "Увімкнені підказки щодня змінюються в редакторі щоденника.",
+  "Add your own prompt": "Додай власну підказку",
+  "Mood": "Настрій",
+  "Not rated": "Без оцінки",
+  "1 · Rough": "1 · Важко",
+  "2 · Low": "2 · Слабко",
+  "3 · Okay": "3 · Нормально",
+  "4 · Good": "4 · Добре",
+  "5 · Great": "5 · Чудово",
+  "What moves your mood": "Що впливає на настрій",
+  "{count} rated days": "Оцінених днів: {count}",
+  "Commits": "Коміти",
+  "Tasks completed": "Виконані задачі",
+  "Deep work": "Глибока робота",
+  "On call": "На чергуванні",
+  "On-call schedule": "Графік чергувань",
+  "On-call days are marked in the activity map and Today dashboard, and compared against your mood.": "Дні чергувань позначаються на карті активності й у панелі «Сьогодні» та порівнюються з настроєм.",
+  "Note": "Нотатка",
+  "Add shift": "Додати чергування",
+  "Import ICS": "Імпорт ICS",
+  "Delete shift": "Видалити чергування",
+  "Imported {count} on-call shifts.": "Імпортовано чергувань: {count}.",
+  "Import from Day One": "Імпорт з Day One",
+  "Import": "Імпорт",
+  "Export": "Експорт",
+  "Import daily notes": "Імпорт щоденних нотаток",
+  "Importing reads YYYY-MM-DD.md files from the folder, such as Obsidian daily notes.": "Імпорт читає файли YYYY-MM-DD.md з теки, наприклад щоденні нотатки Obsidian.",
+  "Imported {count} daily notes, skipped {skipped} days that already had an entry.": "Імпортовано щоденних нотаток: {count}, пропущено днів із наявним записом: {skipped}.",
+  "Meeting-heavy days: {days}. Plan focus work on other days.": "Дні, перевантажені зустрічами: {days}. Плануй глибоку роботу на інші дні.",
+  "{meetings}h meetings, {focus}h deep work": "{meetings} год зустрічей, {focus} год глибокої роботи",
+  "Meeting-heavy day at (hours, 0 = off)": "Перевантажений зустрічами день від (год, 0 = вимк.)",
+  "Point to the JSON file or unzipped folder of a Day One export. Text is added to each day's entry; tags are kept.": "Вкажи JSON-файл або розпаковану теку експорту Day One. Текст додається до запису відповідного дня, теги зберігаються.",
+  "Export path": "Шлях до експорту",
+  "Imported {entries} Day One entries into {days} days.": "Імпортовано записів Day One: {entries}, днів: {days}.",
+  "Org-mode": "Org-mode",
+  "TODO headings become tasks with their priority and DEADLINE or SCHEDULED date; other headings become nested pages. Importing a file again updates the same tasks and pages.": "Заголовки з TODO стають задачами з пріоритетом і датою DEADLINE або SCHEDULED; інші заголовки стають вкладеними сторінками. Повторний імпорт файлу оновлює ті самі задачі й сторінки.",
+  "Read {headings} headings: {tasksCreated} new and {tasksUpdated} updated tasks, {pagesCreated} new and {pagesUpdated} updated pages.": "Прочитано заголовків: {headings}. Задачі: нових {tasksCreated}, оновлених {tasksUpdated}. Сторінки: нових {pagesCreated}, оновлених {pagesUpdated}.",
+  "Saved Org file to {path}": "Org-файл збережено в {path}",
+  "Priorities (A) to (D) map to urgent through low, @contexts become task tags and a +project that matches a project name sets the task's project. Importing a file again updates the same tasks.": "Пріоритети від (A) до (D) відповідають терміновому–низькому, @контексти стають тегами задач, а +project з назвою наявного проєкту задає проєкт задачі. Повторний імпорт файлу оновлює ті самі задачі.",
+  "Read {tasks} tasks: {created} new, {updated} updated.": "Прочитано задач: {tasks}. Нових: {created}, оновлених: {updated}.",
+  "Export by tag or project": "Експорт за тегом або проєктом",
+  "Export pages": "Експорт сторінок",
+  "Import an Obsidian vault": "Імпорт сховища Obsidian",
+  "Notes become pages nested like the vault's folders, with wiki links and images kept. Daily notes named YYYY-MM-DD become entries. Importing again updates the same pages.": "Нотатки стають сторінками з тією ж вкладеністю, що й папки сховища; вікі-посилання та зображення зберігаються. Щоденні нотатки з назвою РРРР-ММ-ДД стають записами. Повторний імпорт оновлює ті самі сторінки.",
+  "Vault folder": "Папка сховища",
+  "Read {notes} notes: {created} pages created, {updated} updated, {entries} daily notes imported.": "Прочитано нотаток: {notes}. Створено сторінок: {created}, оновлено: {updated}, імпортовано щоденних нотаток: {entries}.",
+  "Writes one file per page. Markdown copies images into an assets folder; HTML files embed them.": "Кожна сторінка записується в окремий файл. Для Markdown зображення копіюються в папку assets, HTML-файли містять їх усередині.",
+  "Exported {count} pages to {dir}": "Експортовано сторінок: {count} до {dir}",
+  "Export page": "Експорт сторінки",
+  "A single HTML file with the images embedded, readable in any browser.": "Один HTML-файл із вбудованими зображеннями, що відкривається в будь-якому браузері.",
+  "A Markdown file; images are copied into an assets folder next to it.": "Markdown-файл; зображення копіюються в папку assets поруч із ним.",
+  "Page exported to {path}": "Сторінку експортовано до {path}",
+  "Only entries, pages and tasks matching the filter are written. Pages belong to a project when they carry its name as a tag.": "Записуються лише записи, сторінки й задачі, що відповідають фільтру. Сторінка належить до проєкту, якщо має його назву як тег.",
+  "Any project": "Будь-який проєкт",
+  "Format": "Формат",
+  "Exported {entries} entries, {pages} pages and {tasks} tasks to {dir}": "Експортовано записів: {entries}, сторінок: {pages}, задач: {tasks} до {dir}",
+  "Saved todo.txt to {path}": "todo.txt збережено в {path}",
+  "Hours per working day": "Годин у робочому дні",
+  "Week plans count these hours, minus meetings, as the capacity of each working day.": "Плани на тиждень вважають ці години за вирахуванням зустрічей доступним часом кожного робочого дня.",
+  "Point to a channel folder of an unzipped Slack export. Posts with a section header become Yesterday, Today and Blockers; other lines go to notes.": "Вкажи теку каналу з розпакованого експорту Slack. Дописи із заголовками розділів стають «Вчора», «Сьогодні» та «Блокери»; інші рядки йдуть у нотатки.",
+  "Channel folder": "Тека каналу",
+  "Your Slack name or ID": "Твоє ім'я або ID у Slack",
+  "Yesterday headers": "Заголовки «Вчора»",
+  "Today headers": "Заголовки «Сьогодні»",
+  "Blockers headers": "Заголовки «Блокери»",
+  "Comma-separated": "Через кому",
+  "Found {standups} standups in {messages} messages and wrote {days} days.": "Знайдено стендапів: {standups} серед повідомлень: {messages}, записано днів: {days}.",
+  "e.g. Inbox triage": "напр. Розбір пошти",
+  "Save the entry to add tags": "Збережи запис, щоб додати теги",
+  "Save the page to add tags": "Збережи сторінку, щоб додати теги",
+  "Save the entry to add attachments": "Збережи запис, щоб додати вкладення",
+  "Failed to attach file: {message}": "Не вдалося прикріпити файл: {message}",
+  "File path or drop files here": "Шлях до файлу або перетягни файли сюди",
+  "Attach": "Прикріпити",
+  "Show in folder": "Показати в теці",
+  "Show output": "Показати вивід",
+  "Hide output": "Сховати вивід",
   "Break time": "Час перерви",
   "Break ready": "Перерва готова",
   "Break in progress...": "Триває перерва...",
@@ -278,15 +596,49 @@ const ukTranslations: Record<string, string> = {
   "Copy yesterday → today": "Копіювати вчора → сьогодні",
   "Reset draft": "Скинути чернетку",
   "Delete entry": "Видалити запис",
+  "Upgrading your journal…": "Оновлюємо твій журнал…",
+  "Upgrading your journal failed": "Не вдалося оновити журнал",
+  "Step {completed} of {total}. This can take a minute on large journals.": "Крок {completed} з {total}. На великих журналах це може зайняти хвилину.",
+  "Preparing…": "Підготовка…",
+  "Pin entry": "Закріпити запис",
+  "Unpin entry": "Відкріпити запис",
+  "Pinned": "Закріплено",
+  "Open pinned entry: {date}": "Відкрити закріплений запис: {date}",
   "Bold": "Жирний",
   "Italic": "Курсив",
   "Code": "Код",
   "Bullet list": "Маркований список",
   "What did you achieve? Any blockers?": "Що ти досяг? Які були блокери?",
   "What's the main focus for today?": "Який головний фокус на сьогодні?",
-  "This will permanently delete the saved journal entry for": "Це назавжди видалить збережений запис журналу за",
+  "This will move the saved journal entry to the trash for": "Це перемістить збережений запис журналу до кошика за",
   "Journal entry saved.": "Запис журналу збережено.",
-  "Journal entry deleted.": "Запис журналу видалено.",
+  "Journal entry moved to trash.": "Запис журналу переміщено до кошика.",
+  "Trash": "Кошик",
+  "Deleted entries stay here for 30 days before they are removed for good.": "Видалені записи зберігаються тут 30 днів, а потім видаляються назавжди.",
+  "Trash is empty.": "Кошик порожній.",
+  "Deleted {date}": "Видалено {date}",
+  "Restore entry": "Відновити запис",
+  "Empty trash": "Очистити кошик",
+  "Unlock journal": "Розблокувати журнал",
+  "What's new in {version}": "Що нового у {version}",
+  "Journal data updates": "Оновлення даних журналу",
+  "These database changes ran on your journal during the update.": "Під час оновлення у вашому журналі виконано ці зміни бази даних.",
+  "Got it": "Зрозуміло",
+  "Your entries and pages are encrypted. Enter your passphrase to read and edit them.": "Твої записи та сторінки зашифровані. Введи парольну фразу, щоб читати й редагувати їх.",
+  "Passphrase": "Парольна фраза",
+  "Unlocking...": "Розблокування...",
+  "Unlock": "Розблокувати",
+  "Passphrases do not match.": "Парольні фрази не збігаються.",
+  "Journal encrypted. Keep your passphrase safe: it cannot be recovered.": "Журнал зашифровано. Збережи парольну фразу: її неможливо відновити.",
+  "Encryption turned off.": "Шифрування вимкнено.",
+  "Encryption": "Шифрування",
+  "Entry and page content is encrypted with your passphrase. The journal asks for it on every launch.": "Вміст записів і сторінок зашифровано твоєю парольною фразою. Журнал запитує її під час кожного запуску.",
+  "Encrypt entry and page content with a passphrase. Without it, the content cannot be recovered.": "Зашифруй вміст записів і сторінок парольною фразою. Без неї вміст неможливо відновити.",
+  "Confirm passphrase": "Підтверди парольну фразу",
+  "Lock now": "Заблокувати зараз",
+  "Turn off encryption": "Вимкнути шифрування",
+  "Encrypting...": "Шифрування...",
+  "Encrypt journal": "Зашифрувати журнал",
   "Loading...": "Завантаження...",
   "Saving...": "Збереження...",
   "Deleting...": "Видалення...",
@@ -300,6 +652,20 @@ const ukTranslations: Record<string, string> = {
   "Manage": "Керувати",
   "No active goals with deadlines in next 14 days.": "Немає активних цілей з дедлайном у найближчі 14 днів.",
   "Habits Today": "Звички сьогодні",
+  "Streak at risk: {title} ({count}d)": "Серія під загрозою: {title} ({count}д)",
+  "Goal Check-in": "Перевірка цілей",
+  "Not updated for {days} days": "Без оновлень {days} дн.",
+  "Progress (%)": "Прогрес (%)",
+  "Check in": "Оновити",
+  "Weekly goal check-in": "Щотижнева перевірка цілей",
+  "Check-in time (empty = off)": "Час перевірки (порожньо = вимк.)",
+  "Monday": "Понеділок",
+  "Tuesday": "Вівторок",
+  "Wednesday": "Середа",
+  "Thursday": "Четвер",
+  "Friday": "П'ятниця",
+  "Saturday": "Субота",
+  "Sunday": "Неділя",
   "Track": "Відмічати",
   "No habits configured yet.": "Звички ще не налаштовані.",
   "Due tomorrow": "На завтра",
@@ -326,6 +692,7 @@ const ukTranslations: Record<string, string> = {
   "Daily": "Щодня",
   "Weekdays": "По буднях",
   "Weekly": "Щотижня",
+  "Monthly": "Щомісяця",
   "Repeat until": "Повторювати до",
   "Reminder (minutes)": "Нагадування (хв)",
   "Reminder": "Нагадування",
@@ -334,6 +701,99 @@ const ukTranslations: Record<string, string> = {
   "Participants": "Учасники",
   "One participant per line": "Один учасник на рядок",
   "Notes": "Нотатки",
+  "Blockers": "Блокери",
+  "Day log": "Журнал дня",
+  "Log a note and press Enter": "Запишіть нотатку й натисніть Enter",
+  "Task rules": "Правила для задач",
+  "Rules run in the background on open tasks, e.g. raise the priority of tasks due soon or tag work stuck in progress.": "Правила працюють у фоні для відкритих задач, напр. підвищують пріоритет задач із близьким дедлайном або позначають роботу, що застрягла в процесі.",
+  "Rule name": "Назва правила",
+  "When": "Коли",
+  "Then": "Тоді",
+  "Due within (hours)": "Дедлайн упродовж (годин)",
+  "In progress for (days)": "У процесі понад (днів)",
+  "Hours": "Години",
+  "Days": "Дні",
+  "Only priority": "Лише пріоритет",
+  "Any": "Будь-який",
+  "Set priority": "Змінити пріоритет",
+  "Tag": "Тег",
+  "Add rule": "Додати правило",
+  "Run now": "Запустити зараз",
+  "Enable rule": "Увімкнути правило",
+  "Delete rule": "Видалити правило",
+  "Rules changed {count} tasks.": "Правила змінили задач: {count}.",
+  "due within {count} h": "дедлайн упродовж {count} год",
+  "in progress for {count}+ days": "у процесі {count}+ днів",
+  "{priority} priority, ": "пріоритет {priority}, ",
+  "set priority to {priority}": "змінити пріоритет на {priority}",
+  "add tag #{tag}": "додати тег #{tag}",
+  "Preview (dry run)": "Попередній перегляд (без змін)",
+  "No tasks would change right now.": "Зараз жодна задача не зміниться.",
+  "Recent rule runs": "Останні спрацювання правил",
+  "Notification text": "Текст сповіщень",
+  "Customize system notifications. Clear a field to go back to the default text.": "Налаштуйте системні сповіщення. Очистіть поле, щоб повернути стандартний текст.",
+  "Text": "Текст",
+  "Restore default text": "Відновити стандартний текст",
+  "Habit streak at risk": "Серія звички під загрозою",
+  "Break reminder": "Нагадування про перерву",
+  "Timebox finished": "Таймбокс завершено",
+  "Placeholders: {placeholders}": "Підстановки: {placeholders}",
+  "Preview: {text}": "Приклад: {text}",
+  "Open a random past entry": "Відкрити випадковий давній запис",
+  "Reread something you wrote at least a month ago": "Перечитайте те, що писали щонайменше місяць тому",
+  "No entries older than a month yet.": "Поки немає записів, старших за місяць.",
+  "New subpage": "Нова підсторінка",
+  "Pin to top": "Закріпити вгорі",
+  "Archive": "Архівувати",
+  "Unarchive": "Повернути з архіву",
+  "Archived pages ({count})": "Архівні сторінки ({count})",
+  "Page archived with its subpages": "Сторінку та її підсторінки архівовано",
+  "Page restored from the archive": "Сторінку повернуто з архіву",
+  "Unpin": "Відкріпити",
+  "Add to favorites": "Додати в обране",
+  "Remove from favorites": "Прибрати з обраного",
+  "Move to…": "Перемістити до…",
+  "Top level": "Верхній рівень",
+  "No pages yet.": "Сторінок поки немає.",
+  "Expand": "Розгорнути",
+  "Collapse": "Згорнути",
+  "Open in new window": "Відкрити в новому вікні",
+  "Close window": "Закрити вікно",
+  "Links": "Посилання",
+  "Linked from": "Посилаються сюди",
+  "Page history": "Історія сторінки",
+  "Page versions to keep": "Скільки версій сторінки зберігати",
+  "Close": "Закрити",
+  "Restore this version": "Відновити цю версію",
+  "Page restored.": "Сторінку відновлено.",
+  "No earlier versions yet. A version is kept each time you save changes.": "Попередніх версій ще немає. Версія зберігається щоразу, коли ти зберігаєш зміни.",
+  "No page with this title yet": "Сторінки з такою назвою ще немає",
+  "Windows": "Вікна",
+  "Open Journal in New Window": "Відкрити щоденник у новому вікні",
+  "Keep the journal next to another view": "Тримай щоденник поруч з іншим розділом",
+  "Open Tasks Board in New Window": "Відкрити дошку задач у новому вікні",
+  "Keep the board next to another view": "Тримай дошку поруч з іншим розділом",
+  "Working days": "Робочі дні",
+  "Days off (YYYY-MM-DD, comma-separated)": "Вихідні (YYYY-MM-DD через кому)",
+  "Today's commits start from the previous working day, so a Monday standup includes Friday.": "Коміти для стендапу рахуються з попереднього робочого дня, тож у понеділок видно роботу за п'ятницю.",
+  "Weekly digest": "Тижневий підсумок",
+  "Background jobs": "Фонові завдання",
+  "Cron schedules in local time: minute hour day month weekday. Ranges may wrap past midnight, e.g. 0 22-6 * * * for a night shift. Clear a field to restore the default.": "Розклади cron за місцевим часом: хвилина година день місяць день_тижня. Діапазони можуть переходити через північ, наприклад 0 22-6 * * * для нічної зміни. Очистіть поле, щоб повернути типовий розклад.",
+  "When break, streak, goal check-in and journal reminders are checked.": "Коли перевіряються нагадування про перерви, серії звичок, цілі та журнал.",
+  "Database backup": "Резервна копія бази",
+  "Copies the database into the backups folder; the last 7 are kept.": "Копіює базу в папку backups; зберігаються останні 7 копій.",
+  "Notification with entries, finished and overdue tasks from the last 7 days.": "Сповіщення із записами, завершеними та простроченими задачами за останні 7 днів.",
+  "Task rollover": "Перенесення задач",
+  "Moves overdue open tasks to today. Recurring tasks are skipped.": "Переносить прострочені відкриті задачі на сьогодні. Повторювані задачі пропускаються.",
+  "Schedule (cron)": "Розклад (cron)",
+  "Next run: {time}": "Наступний запуск: {time}",
+  "Last run: {time} ({status})": "Останній запуск: {time} ({status})",
+  "failed": "помилка",
+  "ok": "успішно",
+  "Last 7 days: {entries} journal entries, {tasks_done} tasks done, {overdue} overdue.": "За останні 7 днів: записів у журналі — {entries}, виконаних задач — {tasks_done}, прострочених — {overdue}.",
+  "New": "Нова",
+  "Anything slowing you down or waiting on someone?": "Що гальмує роботу або на кого ви чекаєте?",
+  "Anything else worth remembering about today": "Що ще варто запам'ятати про сьогодні",
   "Decisions": "Рішення",
   "Action items": "Action items",
   "One action item per line": "Один action item на рядок",
@@ -412,6 +872,17 @@ const ukTranslations: Record<string, string> = {
   "Target / week": "Ціль / тиждень",
   "Target date": "Цільова дата",
   "Goal deleted.": "Ціль видалено.",
+  "Reflection saved.": "Рефлексію збережено.",
+  "Failed to save reflection. Please try again.": "Не вдалося зберегти рефлексію. Спробуйте ще раз.",
+  "Lessons from similar goals": "Уроки зі схожих цілей",
+  "Worked: {text}": "Спрацювало: {text}",
+  "Didn't work: {text}": "Не спрацювало: {text}",
+  "Reflect on \"{title}\"": "Рефлексія щодо \"{title}\"",
+  "Goal completed. A short reflection will be shown when you create similar goals.": "Ціль виконано. Коротка рефлексія з'явиться, коли ви створюватимете схожі цілі.",
+  "What worked": "Що спрацювало",
+  "What didn't": "Що не спрацювало",
+  "Skip": "Пропустити",
+  "Save reflection": "Зберегти рефлексію",
   "Failed to delete goal: {message}": "Не вдалося видалити ціль: {message}",
   "Failed to delete goal. Please try again.": "Не вдалося видалити ціль. Спробуй ще раз.",
   "Delete goal?": "Видалити ціль?",
@@ -451,6 +922,7 @@ const ukTranslations: Record<string, string> = {
   "Type a command or search": "Введи команду або пошук",
   "{count} commands": "{count} команд",
   "No commands found.": "Команди не знайдено.",
+  "Journal matches": "Збіги в журналі",
   "Insights": "Insights",
   "Decisions, incidents, retros": "Рішення, інциденти, ретро",
   "Track engineering decisions, incidents, retros, and developer intelligence.": "Фіксуй інженерні рішення, інциденти, ретро та інженерну аналітику.",
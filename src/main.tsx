@@ -2,10 +2,13 @@ import React from "react";
 import ReactDOM from "react-dom/client";
 import { QueryClient, QueryClientProvider } from "@tanstack/react-query";
 import { CssBaseline } from "@mui/material";
+import { getCurrentWindow } from "@tauri-apps/api/window";
 import App from "./App";
+import { UpgradeScreen } from "./components/UpgradeScreen";
 import { CustomThemeProvider } from "./theme/ThemeContext";
 import { I18nProvider } from "./i18n/I18nContext";
 import { AppNotificationsProvider } from "./notifications/AppNotifications";
+import { parseDetachedWindowLabel } from "./utils/windowUtils";
 import "@fontsource/inter/400.css";
 import "@fontsource/inter/500.css";
 import "@fontsource/inter/600.css";
@@ -21,6 +24,12 @@ const queryClient = new QueryClient({
   },
 });
 
+const windowLabel = getCurrentWindow().label;
+// The backend opens a separate "upgrade" window while startup migrations run.
+const isUpgradeWindow = windowLabel === "upgrade";
+// Windows opened with `open_window` show a single view without the app shell.
+const detachedWindow = parseDetachedWindowLabel(windowLabel);
+
 ReactDOM.createRoot(document.getElementById("root") as HTMLElement).render(
   <React.StrictMode>
     <QueryClientProvider client={queryClient}>
@@ -28,7 +37,7 @@ ReactDOM.createRoot(document.getElementById("root") as HTMLElement).render(
         <I18nProvider>
           <AppNotificationsProvider>
             <CssBaseline />
-            <App />
+            {isUpgradeWindow ? <UpgradeScreen /> : <App detachedWindow={detachedWindow} />}
           </AppNotificationsProvider>
         </I18nProvider>
       </CustomThemeProvider>
@@ -1,16 +1,22 @@
-import { Alert, Snackbar } from "@mui/material";
+import { Alert, Button, Snackbar } from "@mui/material";
 import { ReactNode, createContext, useCallback, useContext, useMemo, useState } from "react";
 
 type NotificationSeverity = "success" | "info" | "warning" | "error";
 
+interface NotificationAction {
+  label: string;
+  onClick: () => void;
+}
+
 interface NotificationState {
   id: number;
   message: string;
   severity: NotificationSeverity;
+  action?: NotificationAction;
 }
 
 interface AppNotificationsContextValue {
-  notify: (message: string, severity?: NotificationSeverity) => void;
+  notify: (message: string, severity?: NotificationSeverity, action?: NotificationAction) => void;
 }
 
 const AppNotificationsContext = createContext<AppNotificationsContextValue | undefined>(undefined);
@@ -18,13 +24,17 @@ const AppNotificationsContext = createContext<AppNotificationsContextValue | und
 export const AppNotificationsProvider = ({ children }: { children: ReactNode }) => {
   const [activeNotification, setActiveNotification] = useState<NotificationState | null>(null);
 
-  const notify = useCallback((message: string, severity: NotificationSeverity = "info") => {
-    setActiveNotification({
-      id: Date.now(),
-      message,
-      severity,
-    });
-  }, []);
+  const notify = useCallback(
+    (message: string, severity: NotificationSeverity = "info", action?: NotificationAction) => {
+      setActiveNotification({
+        id: Date.now(),
+        message,
+        severity,
+        action,
+      });
+    },
+    []
+  );
 
   const value = useMemo<AppNotificationsContextValue>(() => ({ notify }), [notify]);
 
@@ -33,7 +43,8 @@ export const AppNotificationsProvider = ({ children }: { children: ReactNode })
       {children}
       <Snackbar
         open={Boolean(activeNotification)}
-        autoHideDuration={2800}
+        // Notifications with an action stay up longer so there is time to use it.
+        autoHideDuration={activeNotification?.action ? 10000 : 2800}
         onClose={() => setActiveNotification(null)}
         anchorOrigin={{ vertical: "bottom", horizontal: "right" }}
       >
@@ -42,6 +53,20 @@ export const AppNotificationsProvider = ({ children }: { children: ReactNode })
           variant="filled"
           onClose={() => setActiveNotification(null)}
           severity={activeNotification?.severity ?? "info"}
+          action={
+            activeNotification?.action ? (
+              <Button
+                color="inherit"
+                size="small"
+                onClick={() => {
+                  activeNotification.action?.onClick();
+                  setActiveNotification(null);
+                }}
+              >
+                {activeNotification.action.label}
+              </Button>
+            ) : undefined
+          }
           sx={{ width: "100%" }}
         >
           {activeNotification?.message ?? ""}
@@ -2,7 +2,10 @@ import React, {
   ReactNode,
   createContext,
   useContext,
+  useEffect,
   useMemo,
+  useRef,
+  useState,
 } from "react";
 import {
   Theme,
@@ -14,9 +17,16 @@ import {
   DEFAULT_THEME_PRESET,
   ThemePresetId,
   getThemePreset,
+  isThemePresetId,
 } from "./presets";
 import { usePersistentState } from "../hooks/usePersistentState";
+import { getAppearanceSettings, updateAppearanceSettings } from "../api";
+import { listenTopic, type EventTopic } from "../api/events";
+import type { AppearanceSettings } from "../types";
 import {
+  DEFAULT_FONT_SIZE,
+  MAX_FONT_SIZE,
+  MIN_FONT_SIZE,
   readThemePreferences,
   THEME_STORAGE_KEYS,
 } from "../utils/preferencesStorage";
@@ -38,6 +48,10 @@ interface ThemeContextType {
   setUiDensity: (density: UiDensity) => void;
   borderRadius: number;
   setBorderRadius: (radius: number) => void;
+  accentColor: string | null;
+  setAccentColor: (color: string | null) => void;
+  fontSize: number;
+  setFontSize: (size: number) => void;
   resetTheme: () => void;
 }
 
@@ -47,6 +61,9 @@ const defaultBorderRadius = 16;
 const minBorderRadius = 6;
 const maxBorderRadius = 18;
 
+// Emitted by the backend whenever the stored appearance changes, to every window.
+const APPEARANCE_CHANGED_EVENT: EventTopic = "appearance-changed";
+
 const ThemeContext = createContext<ThemeContextType | undefined>(undefined);
 
 const resolveSystemAppearanceMode = (): AppearanceMode => {
@@ -96,6 +113,13 @@ const clampBorderRadius = (value: number) => {
   }
   return Math.min(maxBorderRadius, Math.max(minBorderRadius, Math.round(value)));
 };
+
+const clampFontSize = (value: number) => {
+  if (!Number.isFinite(value)) {
+    return DEFAULT_FONT_SIZE;
+  }
+  return Math.min(MAX_FONT_SIZE, Math.max(MIN_FONT_SIZE, Math.round(value)));
+};
 const parseHexColor = (value: string) => {
   const normalized = value.replace("#", "").trim();
   if (normalized.length !== 6) {
@@ -139,18 +163,23 @@ const gradientContrastText = (colorA: string, colorB: string) => {
  * Dashboard / data-rich style: solid surfaces, subtle shadows, clean borders.
  */
 const buildMuiTheme = ({
+  accentColor,
   appearanceMode,
   borderRadius,
   fontFamily,
-  palette,
+  fontSize,
+  palette: presetPalette,
   uiDensity,
 }: {
+  accentColor: string | null;
   appearanceMode: AppearanceMode;
   borderRadius: number;
   fontFamily: string;
+  fontSize: number;
   palette: ReturnType<typeof getThemePreset>["light"];
   uiDensity: UiDensity;
 }): Theme => {
+  const palette = accentColor ? { ...presetPalette, primary: accentColor } : presetPalette;
   const isDark = appearanceMode === "dark";
   const borderColor = palette.divider;
   const paperBg = palette.backgroundPaper;
@@ -224,6 +253,10 @@ const buildMuiTheme = ({
           ":root": {
             colorScheme: appearanceMode,
           },
+          // Type sizes are in rem, so scaling the root scales all text together.
+          html: {
+            fontSize: `${(fontSize / DEFAULT_FONT_SIZE) * 100}%`,
+          },
           body: {
             backgroundColor: palette.backgroundDefault,
             backgroundImage: palette.bodyGradient,
@@ -583,16 +616,108 @@ export const CustomThemeProvider: React.FC<CustomThemeProviderProps> = ({ childr
     parse: () => readThemePreferences(resolveSystemAppearanceMode, clampBorderRadius).borderRadius,
     serialize: (value) => String(clampBorderRadius(value)),
   });
+  const [accentColor, setAccentColor] = usePersistentState<string | null>({
+    storageKey: THEME_STORAGE_KEYS.accentColor,
+    parse: () => readThemePreferences(resolveSystemAppearanceMode, clampBorderRadius).accentColor,
+    serialize: (value) => value ?? "",
+  });
+  const [fontSize, setFontSize] = usePersistentState<number>({
+    storageKey: THEME_STORAGE_KEYS.fontSize,
+    parse: () => readThemePreferences(resolveSystemAppearanceMode, clampBorderRadius).fontSize,
+    serialize: (value) => String(clampFontSize(value)),
+  });
+
+  // The backend copy is shared by every window and travels with backups; local
+  // storage only keeps the first paint from flashing the default theme.
+  const [backendReady, setBackendReady] = useState(false);
+  const syncedAppearanceRef = useRef<string | null>(null);
+  const localAppearance: AppearanceSettings = useMemo(
+    () => ({
+      theme_preset: themePreset,
+      mode: appearanceMode,
+      accent_color: accentColor,
+      font_preset: fontPreset,
+      font_size: fontSize,
+      compact: uiDensity === "compact",
+      border_radius: borderRadius,
+    }),
+    [accentColor, appearanceMode, borderRadius, fontPreset, fontSize, themePreset, uiDensity]
+  );
+
+  useEffect(() => {
+    let cancelled = false;
+    const applyAppearance = (settings: AppearanceSettings) => {
+      const applied: AppearanceSettings = {
+        theme_preset: isThemePresetId(settings.theme_preset)
+          ? settings.theme_preset
+          : DEFAULT_THEME_PRESET,
+        mode: settings.mode === "system" ? resolveSystemAppearanceMode() : settings.mode,
+        accent_color: settings.accent_color,
+        font_preset: settings.font_preset,
+        font_size: clampFontSize(settings.font_size),
+        compact: settings.compact,
+        border_radius: clampBorderRadius(settings.border_radius),
+      };
+      syncedAppearanceRef.current = JSON.stringify(applied);
+      setThemePreset(applied.theme_preset as ThemePresetId);
+      setAppearanceMode(applied.mode as AppearanceMode);
+      setAccentColor(applied.accent_color);
+      setFontPreset(applied.font_preset);
+      setFontSize(applied.font_size);
+      setUiDensity(applied.compact ? "compact" : "comfortable");
+      setBorderRadius(applied.border_radius);
+    };
+
+    getAppearanceSettings()
+      .then((settings) => {
+        if (cancelled) return;
+        // Never saved yet: the effect below seeds the backend from this window.
+        if (settings.updated_at) {
+          applyAppearance(settings);
+        }
+        setBackendReady(true);
+      })
+      .catch(() => undefined);
+    const unlisten = listenTopic<AppearanceSettings>(APPEARANCE_CHANGED_EVENT, (event) => {
+      applyAppearance(event.payload);
+    });
+
+    return () => {
+      cancelled = true;
+      unlisten.then((fn) => fn()).catch(() => undefined);
+    };
+  }, [
+    setAccentColor,
+    setAppearanceMode,
+    setBorderRadius,
+    setFontPreset,
+    setFontSize,
+    setThemePreset,
+    setUiDensity,
+  ]);
+
+  useEffect(() => {
+    if (!backendReady) return;
+    const serialized = JSON.stringify(localAppearance);
+    if (syncedAppearanceRef.current === serialized) return;
+    syncedAppearanceRef.current = serialized;
+    updateAppearanceSettings(localAppearance).catch(() => undefined);
+  }, [backendReady, localAppearance]);
 
   const handleSetBorderRadius = (value: number) => {
     setBorderRadius(clampBorderRadius(value));
   };
+  const handleSetFontSize = (value: number) => {
+    setFontSize(clampFontSize(value));
+  };
   const resetTheme = () => {
     setThemePreset(DEFAULT_THEME_PRESET);
     setAppearanceMode(resolveSystemAppearanceMode());
     setFontPreset(defaultFontPreset);
     setUiDensity(defaultUiDensity);
     handleSetBorderRadius(defaultBorderRadius);
+    setAccentColor(null);
+    handleSetFontSize(DEFAULT_FONT_SIZE);
   };
 
   const fontFamily = resolveFontFamily(fontPreset);
@@ -602,8 +727,17 @@ export const CustomThemeProvider: React.FC<CustomThemeProviderProps> = ({ childr
       : getThemePreset(themePreset).light;
 
   const theme: Theme = useMemo(
-    () => buildMuiTheme({ appearanceMode, borderRadius, fontFamily, palette, uiDensity }),
-    [appearanceMode, borderRadius, fontFamily, palette, uiDensity]
+    () =>
+      buildMuiTheme({
+        accentColor,
+        appearanceMode,
+        borderRadius,
+        fontFamily,
+        fontSize,
+        palette,
+        uiDensity,
+      }),
+    [accentColor, appearanceMode, borderRadius, fontFamily, fontSize, palette, uiDensity]
   );
 
   return (
@@ -619,6 +753,10 @@ export const CustomThemeProvider: React.FC<CustomThemeProviderProps> = ({ childr
         setUiDensity,
         borderRadius,
         setBorderRadius: handleSetBorderRadius,
+        accentColor,
+        setAccentColor,
+        fontSize,
+        setFontSize: handleSetFontSize,
         resetTheme,
       }}
     >
@@ -5,19 +5,156 @@ export interface Entry {
     today: string;
     project_id: number | null;
     created_at: string;
+    pinned: boolean;
+    /** Impediments called out separately from the day's plans. */
+    blockers: string;
+    notes: string;
+}
+
+/** Soft-deleted entry; purged automatically 30 days after `deleted_at`. */
+export interface TrashedEntry {
+    entry: Entry;
+    deleted_at: string;
+}
+
+export interface TrashedPage {
+    page: Page;
+    deleted_at: string;
+}
+
+/** `snippet` wraps matched words in `<mark>`; lower `rank` is a better match. */
+export interface PageSearchResult {
+    page: Page;
+    snippet: string;
+    rank: number;
+}
+
+export interface EntrySearchResult {
+    entry: Entry;
+    /** Matched text with hits wrapped in `<mark>`/`</mark>`. */
+    snippet: string;
+    rank: number;
+}
+
+export interface EntryTag {
+    entry_id: number;
+    tag: string;
+    created_at: string;
+}
+
+export interface EntrySectionTemplate {
+    id: number;
+    section_key: string;
+    label: string;
+    sort_order: number;
+}
+
+export interface EntrySection {
+    id: number;
+    entry_id: number;
+    section_key: string;
+    content: string;
+    sort_order: number;
+    updated_at: string;
+}
+
+export interface JournalPrompt {
+    id: number;
+    text: string;
+    is_builtin: boolean;
+    enabled: boolean;
+    created_at: string;
+}
+
+export interface DailyMood {
+    date: string;
+    mood: number;
+    updated_at: string;
+}
+
+export type MoodFactor = "commits" | "meetings" | "tasks_completed" | "deep_work_minutes" | "on_call";
+
+export interface MoodCorrelation {
+    factor: MoodFactor;
+    correlation: number;
+    sample_days: number;
 }
 
 export interface Page {
     id: number;
     title: string;
     content: string;
+    parent_id: number | null;
     created_at: string;
     updated_at: string;
+    pinned: boolean;
+    favorite: boolean;
+    /** Archived pages and their subpages are only listed by `getPages(true)`. */
+    archived: boolean;
+    /** Position among its siblings from `reorderPages`; `null` until the level was reordered. */
+    sort_index: number | null;
+    /** Locked with its own passphrase; `content` stays locked (`lock:v1:…`) until `unlockPage`. */
+    locked: boolean;
+    /** Emoji shown before the title. */
+    icon: string | null;
+    /** `#rrggbb` color, image URL or page asset URL shown above the page. */
+    cover: string | null;
+}
+
+/** One `key: value` pair from a page's frontmatter; lists give one per item. */
+export interface PageProperty {
+    key: string;
+    value: string;
+}
+
+export interface PagePropertyValue {
+    value: string;
+    page_count: number;
+}
+
+/** Icon and cover to set; a missing field keeps its value, `""` clears it. */
+export interface PageAppearance {
+    icon?: string;
+    cover?: string;
+}
+
+/** Image stored for a page; `url` is what the page content embeds. */
+export interface PageAsset {
+    id: number;
+    page_id: number | null;
+    file_name: string;
+    mime_type: string;
+    size_bytes: number;
+    url: string;
+    created_at: string;
+}
+
+/** A page from `getRecentPages`, most recently opened first. */
+export interface RecentPage {
+    page_id: number;
+    title: string;
+    parent_id: number | null;
+    viewed_at: string;
+    view_count: number;
+}
+
+export interface PageRevision {
+    id: number;
+    page_id: number;
+    title: string;
+    content: string;
+    created_at: string;
+}
+
+export interface PageTag {
+    page_id: number;
+    tag: string;
+    created_at: string;
 }
 
 export type TaskStatus = "todo" | "in_progress" | "done";
 export type TaskPriority = "low" | "medium" | "high" | "urgent";
-export type TaskRecurrence = "none" | "daily" | "weekdays" | "weekly";
+export type TaskRecurrence = "none" | "daily" | "weekdays" | "weekly" | "monthly";
 export type GoalStatus = "active" | "paused" | "completed" | "archived";
 export type MeetingStatus = "planned" | "live" | "done" | "missed" | "cancelled";
 export type MeetingRecurrence = "none" | "daily" | "weekdays" | "weekly";
@@ -45,6 +182,8 @@ export interface Task {
     time_estimate_minutes: number;
     timer_started_at: string | null;
     timer_accumulated_seconds: number;
+    subtask_count: number;
+    subtasks_completed: number;
     created_at: string;
     updated_at: string;
 }
@@ -59,6 +198,310 @@ export interface TaskSubtask {
     updated_at: string;
 }
 
+export interface TaskTag {
+    task_id: number;
+    tag: string;
+    created_at: string;
+}
+
+export type AutomationCondition = "due_within_hours" | "in_progress_days";
+export type AutomationAction = "set_priority" | "add_tag";
+
+/** Scheduler rule: open tasks meeting `condition` (threshold `condition_value`) get `action` applied. */
+export interface AutomationRule {
+    id: number;
+    name: string;
+    condition: AutomationCondition;
+    condition_value: number;
+    priority_filter: TaskPriority | null;
+    action: AutomationAction;
+    action_value: string;
+    enabled: boolean;
+    created_at: string;
+    updated_at: string;
+}
+
+export interface AutomationRuleInput {
+    name: string;
+    condition: AutomationCondition;
+    condition_value: number;
+    priority_filter: TaskPriority | null;
+    action: AutomationAction;
+    action_value: string;
+    enabled: boolean;
+}
+
+/** A change a rule made, or would make in a preview. */
+export interface AutomationRuleChange {
+    rule_id: number;
+    rule_name: string;
+    task_id: number;
+    task_title: string;
+    change: string;
+}
+
+export interface AutomationRunLogEntry {
+    id: number;
+    rule_id: number | null;
+    rule_name: string;
+    task_id: number | null;
+    task_title: string;
+    change: string;
+    ran_at: string;
+}
+
+export type IntegrationProvider = "github" | "gitlab" | "jira";
+export type TaskLinkKind = "issue" | "pull_request";
+
+export interface TaskLink {
+    id: number;
+    task_id: number;
+    url: string;
+    provider: IntegrationProvider;
+    kind: TaskLinkKind;
+    external_ref: string;
+    title: string;
+    labels: string[];
+    state: string | null;
+    last_checked_at: string | null;
+    created_at: string;
+    updated_at: string;
+}
+
+export interface BreakReminder {
+    task_id: number;
+    title: string;
+    running_minutes: number;
+}
+
+export interface TaskDueReminder {
+    task_id: number;
+    title: string;
+    due_date: string;
+    minutes_left: number;
+    snoozed: boolean;
+}
+
+export interface LinkedItemChange {
+    link: TaskLink;
+    previous_state: string | null;
+    task_completed: boolean;
+}
+
+export interface ReviewQueueItem {
+    task: Task;
+    source: string;
+    source_url: string | null;
+    queued_at: string;
+}
+
+export interface TaskWithLink {
+    task: Task;
+    link: TaskLink;
+    /** Open tasks that already looked like this issue when it was added. */
+    duplicates: DuplicateTaskMatch[];
+}
+
+/** Open task whose title nearly matches one being created; `similarity` is 0–1. */
+export interface DuplicateTaskMatch {
+    task_id: number;
+    title: string;
+    status: TaskStatus;
+    similarity: number;
+}
+
+export type PomodoroKind = "focus" | "short_break" | "long_break";
+
+export interface Countdown {
+    id: number;
+    label: string;
+    duration_minutes: number;
+    started_at: string;
+    ends_at: string;
+    completed_at: string | null;
+    cancelled_at: string | null;
+}
+
+export interface PomodoroSession {
+    id: number;
+    kind: PomodoroKind;
+    task_id: number | null;
+    duration_minutes: number;
+    date: string;
+    completed_at: string;
+}
+
+export interface FocusPresence {
+    kind: PomodoroKind;
+    task_id: number | null;
+    started_at: string;
+    ends_at: string;
+}
+
+export interface Presence {
+    task_id: number | null;
+    task_title: string | null;
+    since: string | null;
+    focus: FocusPresence | null;
+}
+
+export interface ShutdownChecklist {
+    date: string;
+    entry_missing: boolean;
+    running_timers: string[];
+    summary: string;
+}
+
+export interface SlackStatusSettings {
+    enabled: boolean;
+    has_token: boolean;
+}
+
+export interface PomodoroStatus {
+    date: string;
+    completed_today: number;
+    focus_minutes_today: number;
+    daily_goal: number;
+    goal_progress: number;
+    cycle_position: number;
+    long_break_every: number;
+    next_break_kind: Exclude<PomodoroKind, "focus">;
+    long_break_minutes: number;
+}
+
+export interface PomodoroDayCount {
+    date: string;
+    count: number;
+    focus_minutes: number;
+}
+
+export interface AppSetting {
+    key: string;
+    value: string;
+}
+
+export interface ReleaseNoteSection {
+    title: string;
+    items: string[];
+}
+
+export interface ReleaseNotes {
+    version: string;
+    date: string | null;
+    sections: ReleaseNoteSection[];
+}
+
+export interface AppliedMigration {
+    version: number;
+    note: string;
+    applied_at: string;
+    app_version: string | null;
+}
+
+export type AccessibleSummaryView = "today" | "tasks" | "habits" | "journal";
+
+export interface AccessibleSummary {
+    view: AccessibleSummaryView;
+    /** One sentence, e.g. "2 tasks due today, habit streak 12 days, entry not yet written." */
+    text: string;
+    parts: string[];
+    generated_at: string;
+}
+
+export interface FeatureFlag {
+    key: string;
+    description: string;
+    enabled: boolean;
+    default_enabled: boolean;
+    updated_at: string | null;
+}
+
+export type UpdateChannel = "stable" | "beta";
+
+/** `iso`: weeks start on Monday (ISO 8601 numbering); `us`: weeks start on Sunday, week 1 holds Jan 1. */
+export type WeekNumbering = "iso" | "us";
+
+/** The configured week around `date`; `year` is the week-numbering year. */
+export interface WeekInfo {
+    numbering: WeekNumbering;
+    date: string;
+    week_start: string;
+    week_end: string;
+    year: number;
+    week: number;
+}
+
+export interface UpdateCheck {
+    current_version: string;
+    channel: UpdateChannel;
+    update_available: boolean;
+    latest_version: string | null;
+    release_url: string | null;
+    release_notes: string | null;
+    published_at: string | null;
+    checked_at: string;
+}
+
+export interface WhatsNew {
+    current_version: string;
+    since_version: string | null;
+    releases: ReleaseNotes[];
+    migrations: AppliedMigration[];
+}
+
+export type AppearanceModeSetting = "system" | "light" | "dark";
+
+export interface AppearanceSettings {
+    theme_preset: string;
+    mode: AppearanceModeSetting;
+    accent_color: string | null;
+    font_preset: "inter" | "roboto" | "mono";
+    font_size: number;
+    compact: boolean;
+    border_radius: number;
+    updated_at?: string | null;
+}
+
+export type NotificationKind = "journal_reminder" | "streak_risk" | "break_reminder" | "due_reminder" | "goal_checkin" | "timebox" | "digest" | "update_available";
+
+/** Notification text stored in `notification_<kind>_title/_body`; `preview_*` is rendered with sample values. */
+export interface NotificationTemplate {
+    kind: NotificationKind;
+    placeholders: string[];
+    title: string;
+    body: string;
+    preview_title: string;
+    preview_body: string;
+}
+
+export type ScheduledJobName = "reminders" | "backup" | "digest" | "rollover" | "update_check" | "integrity";
+
+/** Background job with a cron schedule (local time); `next_run_at` is null while disabled. */
+export interface ScheduledJob {
+    job: ScheduledJobName;
+    schedule: string;
+    default_schedule: string;
+    enabled: boolean;
+    next_run_at: string | null;
+    last_run_at: string | null;
+    last_status: "ok" | "error" | null;
+    last_error: string | null;
+}
+
+export interface WeeklyDigest {
+    entries: number;
+    tasks_done: number;
+    overdue: number;
+}
+
+export interface IntegrationSettings {
+    provider: IntegrationProvider;
+    base_url: string | null;
+    username: string | null;
+    has_token: boolean;
+    updated_at: string | null;
+}
+
 export interface Meeting {
     id: number;
     title: string;
@@ -138,11 +581,364 @@ export interface Habit {
 
 export interface HabitWithLogs extends Habit {
     completed_dates: string[];
+    /** Missed days covered by a streak freeze. */
+    frozen_dates: string[];
     current_streak: number;
+    /** Unspent tokens in the pool shared by all habits; the same on every habit. */
+    freeze_tokens: number;
     this_week_count: number;
 }
 
+/** Active goal without a progress or milestone update for a week or more. */
+export interface GoalCheckin {
+    goal_id: number;
+    title: string;
+    progress: number;
+    last_updated_at: string;
+    days_since_update: number;
+}
+
+export interface GoalReflection {
+    id: number;
+    goal_id: number | null;
+    goal_title: string;
+    what_worked: string;
+    what_didnt: string;
+    created_at: string;
+}
+
+export interface EntryDateRange {
+    start: string;
+    end: string;
+}
+
+export interface EntryCommit {
+    id: number;
+    entry_id: number;
+    hash: string;
+    message: string;
+    position: number;
+    created_at: string;
+}
+
+export interface EntryStreaks {
+    current_streak: number;
+    longest_streak: number;
+    total_entries: number;
+}
+
+/** Meeting hours (overlaps counted once) and focus hours on one local day. */
+export interface DailyMeetingLoad {
+    date: string;
+    meeting_hours: number;
+    deep_work_hours: number;
+    /** Meeting hours reached the `meeting_heavy_hours` setting. */
+    meeting_heavy: boolean;
+}
+
+/** Task timer runs on one local day and how often the timed task changed. */
+export interface DailyContextSwitches {
+    date: string;
+    switches: number;
+    tasks_touched: number;
+    tracked_minutes: number;
+    /** Switches per tracked hour. */
+    switches_per_hour: number;
+}
+
+/** Autosaved text that has not been saved as the day's entry yet. */
+export interface EntryDraft {
+    date: string;
+    yesterday: string;
+    today: string;
+    blockers: string;
+    notes: string;
+    updated_at: string;
+}
+
+/** One timestamped note logged during the day ("interstitial journaling"). */
+export interface EntryNote {
+    id: number;
+    date: string;
+    text: string;
+    created_at: string;
+}
+
+export interface DailyWordCount {
+    date: string;
+    words: number;
+}
+
+/** `days` only lists days with an entry; `most_productive_weekday` is an English weekday name. */
+export interface WritingStats {
+    days: DailyWordCount[];
+    total_words: number;
+    entry_count: number;
+    average_words_per_entry: number;
+    most_productive_weekday: string | null;
+}
+
+export interface Attachment {
+    id: number;
+    entry_id: number;
+    file_name: string;
+    mime_type: string;
+    size_bytes: number;
+    path: string;
+    created_at: string;
+    kind: "file" | "command_output";
+    command: string | null;
+}
+
+export interface MarkdownExportSummary {
+    directory: string;
+    files_written: number;
+}
+
+export type ExportFormat = "markdown" | "json" | "csv";
+
+export type PageExportFormat = "markdown" | "html";
+
+/** Word counts skip fenced code blocks; `backlinks` counts other pages linking here. */
+export interface PageStats {
+    page_id: number;
+    title: string;
+    parent_id: number | null;
+    word_count: number;
+    character_count: number;
+    reading_minutes: number;
+    heading_count: number;
+    outgoing_links: number;
+    backlinks: number;
+    revision_count: number;
+    created_at: string;
+    updated_at: string;
+    days_since_edit: number;
+}
+
+/** Knowledge base health; orphans are top-level pages nothing links to. */
+export interface PagesStats {
+    page_count: number;
+    total_words: number;
+    total_reading_minutes: number;
+    average_words: number;
+    empty_pages: number;
+    stale_pages: number;
+    orphan_pages: number;
+    edited_last_7_days: number;
+    edited_last_30_days: number;
+    stale_after_days: number;
+    largest: PageStats[];
+    stalest: PageStats[];
+    recently_edited: PageStats[];
+}
+
+/** `assets_copied` counts images copied next to Markdown files; HTML embeds them instead. */
+export interface PageExportSummary {
+    directory: string;
+    pages_written: number;
+    assets_copied: number;
+}
+
+/** Both set means content must carry the tag and belong to the project. */
+export interface ExportFilter {
+    tag: string | null;
+    project_id: number | null;
+}
+
+export interface FilteredExportSummary {
+    directory: string;
+    entries_exported: number;
+    pages_exported: number;
+    tasks_exported: number;
+    files_written: number;
+}
+
+export interface MirrorCommit {
+    id: string;
+    summary: string;
+    committed_at: string;
+}
+
+/** Result of a full vault mirror pass; unchanged files are not counted as written. */
+export interface VaultMirrorSummary {
+    directory: string;
+    entries_written: number;
+    pages_written: number;
+    files_removed: number;
+}
+
+/** Days that already had an entry are skipped, never overwritten. */
+export interface MarkdownImportSummary {
+    files_read: number;
+    entries_imported: number;
+    skipped_existing: number;
+}
+
+/** `entries_read` counts Day One entries; several can land on the same day. */
+export interface DayOneImportSummary {
+    entries_read: number;
+    days_written: number;
+}
+
+export interface OrgImportSummary {
+    headings_read: number;
+    tasks_created: number;
+    tasks_updated: number;
+    pages_created: number;
+    pages_updated: number;
+}
+
+/** `notes_read` counts daily notes too; `skipped_existing` are days that already had an entry. */
+export interface ObsidianImportSummary {
+    notes_read: number;
+    pages_created: number;
+    pages_updated: number;
+    entries_imported: number;
+    skipped_existing: number;
+    assets_imported: number;
+}
+
+export interface TodoTxtImportSummary {
+    tasks_read: number;
+    tasks_created: number;
+    tasks_updated: number;
+}
+
+export interface SlackImportSummary {
+    messages_read: number;
+    standups_found: number;
+    days_written: number;
+}
+
+export interface IntegrityViolation {
+    row_id: number;
+    detail: string;
+}
+
+/** One rule of the last check; `violations` is a sample of at most 50 rows. */
+export interface IntegrityRuleStatus {
+    rule: string;
+    description: string;
+    auto_fixable: boolean;
+    violation_count: number;
+    fixed_count: number;
+    checked_at: string | null;
+    violations: IntegrityViolation[];
+}
+
+export interface WorkspaceSnapshot {
+    name: string;
+    created_at: string;
+    size_bytes: number;
+    schema_version: number;
+}
+
+export interface IntegrityReport {
+    checked_at: string | null;
+    violation_count: number;
+    rules: IntegrityRuleStatus[];
+}
+
+/** Calls of one command since the app started. */
+export interface CommandTiming {
+    command: string;
+    calls: number;
+    slow_calls: number;
+    total_ms: number;
+    mean_ms: number;
+    max_ms: number;
+}
+
+/** A call at or over `slow_op_threshold_ms`; `params` gives argument sizes, not content. */
+export interface SlowOp {
+    id: number;
+    command: string;
+    duration_ms: number;
+    params: string;
+    created_at: string;
+}
+
+export interface PerformanceReport {
+    threshold_ms: number;
+    since: string;
+    commands: CommandTiming[];
+    slow_ops: SlowOp[];
+}
+
+/** `EXPLAIN QUERY PLAN` of one common query; `full_scans` are steps reading a whole table. */
+export interface QueryPlanCheck {
+    name: string;
+    description: string;
+    uses_index: boolean;
+    full_scans: string[];
+    plan: string[];
+}
+
+export type DateShiftEntity =
+    | "entries"
+    | "habit_logs"
+    | "streak_freezes"
+    | "moods"
+    | "task_due_dates"
+    | "goal_target_dates";
+
+/** Rows to shift; `habit_id` only applies to habit logs and streak freezes. Dates are inclusive. */
+export interface DateShiftFilter {
+    entity: DateShiftEntity;
+    from_date: string | null;
+    to_date: string | null;
+    habit_id: number | null;
+}
+
+export interface DateShiftRow {
+    id: number;
+    label: string;
+    from_date: string;
+    to_date: string;
+}
+
+/** `conflicts` would land on an already used date and block the shift. */
+export interface DateShift {
+    entity: DateShiftEntity;
+    delta_days: number;
+    rows: DateShiftRow[];
+    conflicts: DateShiftRow[];
+    applied: boolean;
+}
+
+export interface Achievement {
+    key: string;
+    title: string;
+    description: string;
+    threshold: number;
+    progress: number;
+    unlocked_at: string | null;
+}
+
+export interface StreakAtRisk {
+    habit_id: number;
+    title: string;
+    color: string;
+    streak_days: number;
+}
+
+/** `unlocked` is always true while encryption is off. */
+export interface JournalEncryptionStatus {
+    enabled: boolean;
+    unlocked: boolean;
+}
+
+/** Written into every export; older formats are upgraded on import. */
+export interface BackupFormat {
+    format_version: number;
+    schema_version: number;
+}
+
 export interface BackupPayload {
+    format_version?: number;
+    schema_version?: number;
+    app_settings?: AppSetting[];
     preferences?: {
         appShell?: {
             reminderEnabled?: boolean;
@@ -285,3 +1081,98 @@ export interface BackupPayload {
         updated_at?: string;
     }>;
 }
+
+export type ApiScopePreset = "read_only" | "tasks_only" | "capture" | "full";
+
+export interface ApiToken {
+    id: number;
+    name: string;
+    token_prefix: string;
+    scopes: string[];
+    created_at: string;
+    last_used_at: string | null;
+}
+
+export interface CreatedApiToken {
+    token: ApiToken;
+    secret: string;
+}
+
+export type ExternalAccessOutcome = "allowed" | "denied" | "rate_limited" | "error";
+
+export interface ExternalAccessLogEntry {
+    id: number;
+    token_id: number | null;
+    caller: string;
+    endpoint: string;
+    outcome: ExternalAccessOutcome;
+    detail: string;
+    created_at: string;
+}
+
+export interface Bookmark {
+    id: number;
+    url: string;
+    title: string;
+    selection: string;
+    created_at: string;
+}
+
+export interface CaptureResult {
+    kind: "bookmark" | "task";
+    id: number;
+    duplicates: DuplicateTaskMatch[];
+}
+
+export interface MigrationProgress {
+    completed: number;
+    total: number;
+    version: number | null;
+    done: boolean;
+    error: string | null;
+}
+
+export type IncidentSeverity = "sev1" | "sev2" | "sev3" | "sev4";
+
+/** `ended_at` is null while the incident is ongoing. */
+export interface Incident {
+    id: number;
+    started_at: string;
+    ended_at: string | null;
+    severity: IncidentSeverity;
+    summary: string;
+    resolution: string;
+    task_ids: number[];
+    page_ids: number[];
+    created_at: string;
+    updated_at: string;
+}
+
+export interface IncidentInput {
+    started_at: string;
+    ended_at: string | null;
+    severity: IncidentSeverity;
+    summary: string;
+    resolution: string;
+    task_ids: number[];
+    page_ids: number[];
+}
+
+export interface IncidentTimelineEvent {
+    at: string;
+    kind: "started" | "resolved" | "task_completed";
+    incident_id: number;
+    severity: IncidentSeverity;
+    summary: string;
+    detail: string;
+}
+
+/** Inclusive range of local days on call. */
+export interface OnCallShift {
+    id: number;
+    start_date: string;
+    end_date: string;
+    note: string;
+    source: "manual" | "ics";
+    created_at: string;
+}
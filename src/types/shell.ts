@@ -9,3 +9,11 @@ export type AppTab =
   | "projects"
   | "insights"
   | "settings";
+
+/** A view opened in its own window with `open_window`. */
+export type DetachedView = "journal" | "tasks" | `page:${number}`;
+
+export interface DetachedWindow {
+  tab: Extract<AppTab, "journal" | "tasks" | "page">;
+  pageId: number | null;
+}
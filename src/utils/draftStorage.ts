@@ -1,3 +1,4 @@
+/** Entry drafts are stored by the backend now; this shape is only read to migrate old ones. */
 export interface EntryDraft {
   yesterday: string;
   today: string;
@@ -41,14 +42,6 @@ export const readEntryDraft = (date: string): EntryDraft | null => {
   }
 };
 
-export const persistEntryDraft = (date: string, draft: EntryDraft): void => {
-  try {
-    localStorage.setItem(getEntryDraftKey(date), JSON.stringify(draft));
-  } catch {
-    // Ignore storage write failures so draft autosave does not break the editor.
-  }
-};
-
 export const removeEntryDraft = (date: string): void => {
   localStorage.removeItem(getEntryDraftKey(date));
 };
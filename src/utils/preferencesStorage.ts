@@ -21,6 +21,8 @@ export interface ThemePreferences {
   fontPreset: FontPreset;
   uiDensity: UiDensity;
   borderRadius: number;
+  accentColor: string | null;
+  fontSize: number;
 }
 
 export interface PlannerPreferences {
@@ -58,8 +60,17 @@ export const THEME_STORAGE_KEYS = {
   fontPreset: "devJournal_fontPreset",
   uiDensity: "devJournal_uiDensity",
   borderRadius: "devJournal_borderRadius",
+  accentColor: "devJournal_accentColor",
+  fontSize: "devJournal_fontSize",
 } as const;
 
+export const DEFAULT_FONT_SIZE = 14;
+export const MIN_FONT_SIZE = 12;
+export const MAX_FONT_SIZE = 20;
+
+const isHexColor = (value: unknown): value is string =>
+  typeof value === "string" && /^#[0-9a-fA-F]{6}$/.test(value);
+
 export const PLANNER_DAILY_WINS_STORAGE_KEY = "devJournal_daily_wins";
 export const PLANNER_COLLAPSE_STORAGE_KEY = "devJournal_planner_collapsed_sections";
 export const TASKS_OVERDUE_ONLY_STORAGE_KEY = "devJournal_tasks_overdue_only";
@@ -204,6 +215,16 @@ export const readThemePreferences = (
     ["comfortable", "compact"] as const
   ),
   borderRadius: clampBorderRadius(Number(readString(THEME_STORAGE_KEYS.borderRadius))),
+  accentColor: (() => {
+    const value = readString(THEME_STORAGE_KEYS.accentColor);
+    return isHexColor(value) ? value.toLowerCase() : null;
+  })(),
+  fontSize: readIntegerPreference(
+    THEME_STORAGE_KEYS.fontSize,
+    DEFAULT_FONT_SIZE,
+    MIN_FONT_SIZE,
+    MAX_FONT_SIZE
+  ),
 });
 
 export const readPlannerPreferences = (): PlannerPreferences => ({
@@ -320,6 +341,17 @@ export const applyPreferenceSnapshot = (snapshot: PreferencesSnapshot) => {
     if (typeof theme.borderRadius === "number" && Number.isFinite(theme.borderRadius)) {
       writeString(THEME_STORAGE_KEYS.borderRadius, String(Math.round(theme.borderRadius)));
     }
+    if (isHexColor(theme.accentColor)) {
+      writeString(THEME_STORAGE_KEYS.accentColor, theme.accentColor.toLowerCase());
+    } else if (theme.accentColor === null) {
+      removeString(THEME_STORAGE_KEYS.accentColor);
+    }
+    if (typeof theme.fontSize === "number" && Number.isFinite(theme.fontSize)) {
+      writeString(
+        THEME_STORAGE_KEYS.fontSize,
+        String(Math.min(MAX_FONT_SIZE, Math.max(MIN_FONT_SIZE, Math.round(theme.fontSize))))
+      );
+    }
   }
 
   if (snapshot.planner) {
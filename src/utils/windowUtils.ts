@@ -0,0 +1,9 @@
+import type { DetachedWindow } from "../types/shell";
+
+// Mirrors `DetachedView::label` in src-tauri/src/commands/windows.rs.
+export const parseDetachedWindowLabel = (label: string): DetachedWindow | null => {
+  if (label === "view-journal") return { tab: "journal", pageId: null };
+  if (label === "view-tasks") return { tab: "tasks", pageId: null };
+  const page = /^view-page-(\d+)$/.exec(label);
+  return page ? { tab: "page", pageId: Number(page[1]) } : null;
+};